@@ -0,0 +1,16 @@
+-- Per-project environment variable vault.
+-- Values are encrypted at rest (AES-256-GCM) by the services layer; the database
+-- only ever sees base64 ciphertext + nonce. Injected into setup scripts, dev
+-- servers and coding agent processes at spawn time.
+CREATE TABLE project_env_vars (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id       UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    key              TEXT NOT NULL,
+    value_ciphertext TEXT NOT NULL,
+    value_nonce      TEXT NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (project_id, key)
+);
+
+CREATE INDEX idx_project_env_vars_project ON project_env_vars(project_id);
@@ -0,0 +1,18 @@
+-- Attribute sessions to the agent (and column) that launched them, so
+-- executions can be aggregated per agent for performance stats.
+CREATE TABLE agent_sessions (
+    session_id UUID PRIMARY KEY REFERENCES sessions(id) ON DELETE CASCADE,
+    agent_id   UUID NOT NULL REFERENCES agents(id) ON DELETE CASCADE,
+    column_id  UUID REFERENCES kanban_columns(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_agent_sessions_agent ON agent_sessions(agent_id);
+
+-- Token usage reported by the coding agent for a single execution process
+CREATE TABLE execution_token_usage (
+    execution_process_id UUID PRIMARY KEY REFERENCES execution_processes(id) ON DELETE CASCADE,
+    input_tokens         BIGINT NOT NULL DEFAULT 0,
+    output_tokens        BIGINT NOT NULL DEFAULT 0,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,11 @@
+-- Optional cheaper executor profile for retries on a transition's else path.
+-- After `downgrade_after` else transitions the retry runs with this profile;
+-- the escalation path always uses the destination agent's own executor.
+CREATE TABLE transition_retry_policies (
+    transition_id   UUID PRIMARY KEY REFERENCES state_transitions(id) ON DELETE CASCADE,
+    executor        TEXT,
+    variant         TEXT,
+    downgrade_after INTEGER NOT NULL DEFAULT 1,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
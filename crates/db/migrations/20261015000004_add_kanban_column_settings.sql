@@ -0,0 +1,8 @@
+-- Per-column behaviour settings kept alongside kanban_columns.
+-- read_only: the agent may only produce artifacts/decisions; any code
+-- changes or commits it makes are discarded when the execution ends.
+CREATE TABLE kanban_column_settings (
+    column_id  UUID PRIMARY KEY REFERENCES kanban_columns(id) ON DELETE CASCADE,
+    read_only  BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
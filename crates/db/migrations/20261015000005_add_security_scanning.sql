@@ -0,0 +1,18 @@
+-- Security scanning gate: scanners configured per column run in the workspace
+-- after the agent finishes. High/critical findings block the success transition.
+ALTER TABLE kanban_column_settings
+    ADD COLUMN security_scanners TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE TABLE security_findings (
+    id                   UUID PRIMARY KEY,
+    execution_process_id UUID NOT NULL REFERENCES execution_processes(id) ON DELETE CASCADE,
+    scanner              TEXT NOT NULL,
+    severity             TEXT NOT NULL,
+    title                TEXT NOT NULL,
+    location             TEXT,
+    details              TEXT,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_security_findings_execution_process_id
+    ON security_findings(execution_process_id);
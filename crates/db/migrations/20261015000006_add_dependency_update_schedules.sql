@@ -0,0 +1,14 @@
+-- Scheduled dependency updates: periodically detect outdated cargo/npm
+-- dependencies in each project repo and open one task per update batch on
+-- the configured workflow board (defaults to the project's board).
+CREATE TABLE dependency_update_schedules (
+    project_id     UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    board_id       UUID REFERENCES boards(id) ON DELETE SET NULL,
+    ecosystems     TEXT[] NOT NULL DEFAULT '{cargo,npm}',
+    interval_hours INTEGER NOT NULL DEFAULT 168,
+    batch_size     INTEGER NOT NULL DEFAULT 10,
+    enabled        BOOLEAN NOT NULL DEFAULT TRUE,
+    last_run_at    TIMESTAMPTZ,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,12 @@
+-- Automation action executor: allow the new action types (and create_workspace,
+-- which the model already accepted but the original constraint rejected)
+
+ALTER TABLE automation_rules DROP CONSTRAINT IF EXISTS automation_rules_action_type_check;
+ALTER TABLE automation_rules ADD CONSTRAINT automation_rules_action_type_check
+    CHECK (action_type IN (
+        'run_agent', 'create_workspace', 'create_pr', 'merge_pr', 'webhook', 'notify',
+        'run_script', 'assign_agent', 'set_labels', 'move_to_column', 'create_follow_up_task'
+    ));
+
+-- Per-rule execution history lookups, newest first
+CREATE INDEX idx_automation_executions_rule_created ON automation_executions(rule_id, created_at DESC);
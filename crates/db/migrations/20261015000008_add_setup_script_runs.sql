@@ -0,0 +1,20 @@
+-- Setup script history per project repo. Failures of scripts that have recently
+-- passed are treated as flaky: they're retried once before the pipeline fails,
+-- and the project surfaces a "flaky setup" warning.
+CREATE TABLE setup_script_runs (
+    id                         UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id                 UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    repo_id                    UUID NOT NULL REFERENCES repos(id) ON DELETE CASCADE,
+    execution_process_id       UUID NOT NULL REFERENCES execution_processes(id) ON DELETE CASCADE,
+    succeeded                  BOOLEAN NOT NULL,
+    exit_code                  INTEGER,
+    -- This run was the automatic retry of a failed run
+    is_retry                   BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Set on a failed run when it was retried
+    retry_execution_process_id UUID REFERENCES execution_processes(id) ON DELETE SET NULL,
+    created_at                 TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_setup_script_runs_repo ON setup_script_runs(project_id, repo_id, created_at DESC);
+CREATE INDEX idx_setup_script_runs_retry ON setup_script_runs(retry_execution_process_id)
+    WHERE retry_execution_process_id IS NOT NULL;
@@ -0,0 +1,25 @@
+-- External notification channels (Slack, Discord or a generic HTTP endpoint),
+-- scoped to a project or to every project of a remote organization. Each channel
+-- renders its own title/message templates and only receives the events it routes.
+CREATE TABLE notification_channels (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id       UUID REFERENCES projects(id) ON DELETE CASCADE,
+    -- Remote organization id; the channel applies to projects linked to it
+    organization_id  UUID,
+    name             TEXT NOT NULL,
+    kind             TEXT NOT NULL CHECK (kind IN ('slack', 'discord', 'http')),
+    webhook_url      TEXT NOT NULL,
+    title_template   TEXT,
+    message_template TEXT,
+    -- Events delivered to the channel; empty routes every event
+    events           TEXT[] NOT NULL DEFAULT '{}',
+    enabled          BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((project_id IS NULL) <> (organization_id IS NULL))
+);
+
+CREATE INDEX idx_notification_channels_project ON notification_channels(project_id)
+    WHERE project_id IS NOT NULL;
+CREATE INDEX idx_notification_channels_organization ON notification_channels(organization_id)
+    WHERE organization_id IS NOT NULL;
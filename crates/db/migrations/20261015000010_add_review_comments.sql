@@ -0,0 +1,17 @@
+-- Inline review comments on a workspace's diff. When the user rejects the work,
+-- pending comments are written into .vibe/decision.json as feedback for the next
+-- agent run and marked as sent.
+CREATE TABLE review_comments (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    repo_id      UUID NOT NULL REFERENCES repos(id) ON DELETE CASCADE,
+    file_path    TEXT NOT NULL,
+    line_number  INTEGER NOT NULL,
+    -- Which side of the diff the line number refers to
+    side         TEXT NOT NULL DEFAULT 'new' CHECK (side IN ('old', 'new')),
+    body         TEXT NOT NULL,
+    sent_at      TIMESTAMPTZ,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_review_comments_workspace ON review_comments(workspace_id, created_at);
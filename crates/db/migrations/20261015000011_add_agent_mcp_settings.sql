@@ -0,0 +1,9 @@
+-- Per-agent MCP settings applied when the agent's executions are spawned.
+-- mcp_base_url: backend URL the vibe_kanban MCP server talks to (VIBE_BACKEND_URL).
+-- mcp_servers: extra servers ([{name, command, args, env}]) only this agent gets.
+CREATE TABLE agent_mcp_settings (
+    agent_id     UUID PRIMARY KEY REFERENCES agents(id) ON DELETE CASCADE,
+    mcp_base_url TEXT,
+    mcp_servers  JSONB NOT NULL DEFAULT '[]',
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,12 @@
+-- Read-only board dashboards for wall displays. The token grants access to a
+-- project's board snapshot only; only its SHA-256 is stored.
+CREATE TABLE dashboard_tokens (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id   UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    name         TEXT NOT NULL,
+    token_hash   TEXT NOT NULL UNIQUE,
+    last_used_at TIMESTAMPTZ,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_dashboard_tokens_project ON dashboard_tokens(project_id);
@@ -0,0 +1,10 @@
+-- Matrix and Telegram notification channels. For both, webhook_url is the API base
+-- (Matrix homeserver, https://api.telegram.org for Telegram), destination is the room
+-- or chat id and access_token the Matrix access token or Telegram bot token.
+ALTER TABLE notification_channels DROP CONSTRAINT notification_channels_kind_check;
+ALTER TABLE notification_channels
+    ADD CONSTRAINT notification_channels_kind_check
+    CHECK (kind IN ('slack', 'discord', 'http', 'matrix', 'telegram'));
+
+ALTER TABLE notification_channels ADD COLUMN destination TEXT;
+ALTER TABLE notification_channels ADD COLUMN access_token TEXT;
@@ -0,0 +1,11 @@
+-- Sub-tasks: tasks created from a parent's implementation plan link back to it
+ALTER TABLE tasks ADD COLUMN parent_task_id UUID REFERENCES tasks(id) ON DELETE SET NULL;
+CREATE INDEX idx_tasks_parent_task_id ON tasks(parent_task_id);
+
+ALTER TABLE automation_rules DROP CONSTRAINT IF EXISTS automation_rules_action_type_check;
+ALTER TABLE automation_rules ADD CONSTRAINT automation_rules_action_type_check
+    CHECK (action_type IN (
+        'run_agent', 'create_workspace', 'create_pr', 'merge_pr', 'webhook', 'notify',
+        'run_script', 'assign_agent', 'set_labels', 'move_to_column', 'create_follow_up_task',
+        'create_subtasks_from_plan'
+    ));
@@ -0,0 +1,4 @@
+-- Human checklist per column: every item must be ticked (recorded as task events)
+-- before a task can leave the column.
+ALTER TABLE kanban_column_settings
+    ADD COLUMN checklist TEXT[] NOT NULL DEFAULT '{}';
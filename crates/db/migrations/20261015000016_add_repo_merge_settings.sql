@@ -0,0 +1,12 @@
+-- Per-repo rules for merging task branches into their target branch.
+-- require_clean_rebase: the task branch must contain the target branch's latest commit.
+-- merge_strategy: squash into one commit, or a merge commit that keeps the branch history.
+-- delete_branch_after_merge: remove the task branch (and its worktree) once merged.
+CREATE TABLE repo_merge_settings (
+    repo_id                   UUID PRIMARY KEY REFERENCES repos(id) ON DELETE CASCADE,
+    require_clean_rebase      BOOLEAN NOT NULL DEFAULT TRUE,
+    merge_strategy            TEXT NOT NULL DEFAULT 'squash'
+                              CHECK (merge_strategy IN ('squash', 'merge_commit')),
+    delete_branch_after_merge BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at                TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
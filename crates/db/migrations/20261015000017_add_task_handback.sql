@@ -0,0 +1,3 @@
+-- Agent handback: a task handed to a human keeps its workspace and history but is
+-- no longer moved between columns or picked up by agents automatically
+ALTER TABLE tasks ADD COLUMN detached_at TIMESTAMPTZ;
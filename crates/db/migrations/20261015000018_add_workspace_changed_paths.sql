@@ -0,0 +1,10 @@
+-- Files each active workspace has changed relative to its target branch, per repo.
+-- Refreshed in the background and compared across workspaces to flag tasks whose
+-- agents are editing the same files.
+CREATE TABLE workspace_changed_paths (
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    repo_id      UUID NOT NULL REFERENCES repos(id) ON DELETE CASCADE,
+    paths        TEXT[] NOT NULL DEFAULT '{}',
+    checked_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (workspace_id, repo_id)
+);
@@ -0,0 +1,17 @@
+-- Handoff notes: what an agent leaves for the next column when it finishes its work.
+-- source: 'agent' when written through the write_handoff_note tool, 'automatic' when
+-- derived from .vibe/summary.md, the decision file and the diff at the end of a run.
+CREATE TABLE handoff_notes (
+    id             UUID PRIMARY KEY,
+    task_id        UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    column_id      UUID REFERENCES kanban_columns(id) ON DELETE SET NULL,
+    workspace_id   UUID REFERENCES workspaces(id) ON DELETE SET NULL,
+    agent_id       UUID REFERENCES agents(id) ON DELETE SET NULL,
+    source         TEXT NOT NULL DEFAULT 'agent' CHECK (source IN ('agent', 'automatic')),
+    summary        TEXT NOT NULL,
+    files_touched  TEXT[] NOT NULL DEFAULT '{}',
+    open_questions TEXT[] NOT NULL DEFAULT '{}',
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_handoff_notes_task_id ON handoff_notes(task_id, created_at);
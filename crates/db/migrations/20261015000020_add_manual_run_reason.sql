@@ -0,0 +1,10 @@
+-- Manual runs: one-off agent executions started by a user with their own prompt,
+-- independent of the agent configured on the task's column.
+-- The CHECK was created on `process_type_new` and kept its name through the renames.
+ALTER TABLE execution_processes
+    DROP CONSTRAINT IF EXISTS execution_processes_process_type_new_check;
+ALTER TABLE execution_processes
+    DROP CONSTRAINT IF EXISTS execution_processes_run_reason_check;
+ALTER TABLE execution_processes
+    ADD CONSTRAINT execution_processes_run_reason_check
+    CHECK (run_reason IN ('setupscript', 'cleanupscript', 'codingagent', 'devserver', 'manualrun'));
@@ -0,0 +1,6 @@
+-- Column entry/exit scripts, run in the task's workspace: the entry script before the
+-- column's agent starts, the exit script after it finishes (a failing exit script
+-- keeps the task in the column).
+ALTER TABLE kanban_column_settings
+    ADD COLUMN on_enter_script TEXT,
+    ADD COLUMN on_exit_script TEXT;
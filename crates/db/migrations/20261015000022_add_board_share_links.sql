@@ -0,0 +1,11 @@
+-- Public read-only links to a board. The token grants access to the board's
+-- shared view only (columns and task summaries); only its SHA-256 is stored.
+CREATE TABLE board_share_links (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    board_id     UUID NOT NULL REFERENCES boards(id) ON DELETE CASCADE,
+    token_hash   TEXT NOT NULL UNIQUE,
+    last_used_at TIMESTAMPTZ,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_board_share_links_board ON board_share_links(board_id);
@@ -0,0 +1,16 @@
+-- Reviewer feedback: what a reviewer (an agent writing "feedback" to its decision
+-- file, or a person rejecting a review) asks the implementer to change. Versions
+-- count up per task; addressed_at is set once the implementer's next run has
+-- finished and the task moved on.
+CREATE TABLE reviewer_feedback (
+    id                   UUID PRIMARY KEY,
+    task_id              UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    version              INTEGER NOT NULL,
+    column_id            UUID REFERENCES kanban_columns(id) ON DELETE SET NULL,
+    execution_process_id UUID REFERENCES execution_processes(id) ON DELETE SET NULL,
+    source               TEXT NOT NULL CHECK (source IN ('agent', 'human')),
+    feedback             TEXT NOT NULL,
+    addressed_at         TIMESTAMPTZ,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (task_id, version)
+);
@@ -0,0 +1,22 @@
+-- Background work that must survive a restart (workspace cleanup after a task is
+-- deleted, propagating shared task updates). A worker claims pending jobs whose
+-- run_after has passed; failures are retried with backoff until max_attempts, after
+-- which the job is left as 'dead' for inspection.
+CREATE TABLE background_jobs (
+    id           UUID PRIMARY KEY,
+    kind         TEXT NOT NULL,
+    payload      JSONB NOT NULL,
+    status       TEXT NOT NULL DEFAULT 'pending'
+                 CHECK (status IN ('pending', 'running', 'completed', 'dead')),
+    attempts     INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL,
+    last_error   TEXT,
+    run_after    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    claimed_at   TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_background_jobs_pending ON background_jobs (run_after) WHERE status = 'pending';
+CREATE INDEX idx_background_jobs_status ON background_jobs (status, created_at DESC);
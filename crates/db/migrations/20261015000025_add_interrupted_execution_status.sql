@@ -0,0 +1,7 @@
+-- Interrupted: the process was running when the server shut down or crashed.
+-- Coding agent runs in this state can be resumed from their agent session.
+ALTER TABLE execution_processes
+    DROP CONSTRAINT IF EXISTS execution_processes_status_check;
+ALTER TABLE execution_processes
+    ADD CONSTRAINT execution_processes_status_check
+    CHECK (status IN ('running', 'completed', 'failed', 'killed', 'interrupted'));
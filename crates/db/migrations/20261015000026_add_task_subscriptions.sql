@@ -0,0 +1,14 @@
+-- Tasks and labels the user watches. Events on a watched task, or on any task
+-- carrying a watched label, notify the user whatever the project's own settings.
+CREATE TABLE task_subscriptions (
+    id         UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    task_id    UUID REFERENCES tasks(id) ON DELETE CASCADE,
+    label_id   UUID REFERENCES task_labels(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((task_id IS NULL) <> (label_id IS NULL))
+);
+
+CREATE UNIQUE INDEX idx_task_subscriptions_task ON task_subscriptions(task_id)
+    WHERE task_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_task_subscriptions_label ON task_subscriptions(label_id)
+    WHERE label_id IS NOT NULL;
@@ -0,0 +1,21 @@
+-- Plan-only runs: the agent writes a plan (.vibe/plan.md) but its code changes are
+-- discarded. A reviewer approves or rejects the plan; until it's approved the task
+-- can't leave a plan-only column or get a manual run with write access.
+ALTER TABLE kanban_column_settings
+    ADD COLUMN plan_only BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE plan_reviews (
+    id          UUID PRIMARY KEY,
+    task_id     UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    session_id  UUID NOT NULL UNIQUE REFERENCES sessions(id) ON DELETE CASCADE,
+    column_id   UUID REFERENCES kanban_columns(id) ON DELETE SET NULL,
+    plan        TEXT,
+    status      TEXT NOT NULL DEFAULT 'pending'
+                    CHECK (status IN ('pending', 'approved', 'rejected')),
+    reviewer    TEXT,
+    comment     TEXT,
+    reviewed_at TIMESTAMPTZ,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_plan_reviews_task ON plan_reviews(task_id, created_at DESC);
@@ -0,0 +1,17 @@
+-- What an execution ran with, for reproducing runs that behaved differently: the
+-- executor profile and its configuration, the model and CLI version the agent
+-- reported, the host, each repo's base branch commit and the environment variables
+-- (secret values redacted).
+CREATE TABLE execution_environments (
+    execution_process_id UUID PRIMARY KEY REFERENCES execution_processes(id) ON DELETE CASCADE,
+    executor             TEXT,
+    executor_config      JSONB,
+    executor_version     TEXT,
+    model                TEXT,
+    os                   TEXT NOT NULL,
+    arch                 TEXT NOT NULL,
+    app_version          TEXT NOT NULL,
+    repos                JSONB NOT NULL DEFAULT '[]',
+    env_vars             JSONB NOT NULL DEFAULT '{}',
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,8 @@
+-- Per-project board behaviour (defaults apply when no row exists).
+-- auto_assign_initial_column: tasks created without a column start in the board's
+-- initial column instead of sitting outside the workflow.
+CREATE TABLE project_board_settings (
+    project_id                 UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    auto_assign_initial_column BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at                 TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
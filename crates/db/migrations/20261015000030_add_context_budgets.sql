@@ -0,0 +1,19 @@
+-- Context token budget overrides for a project or a single column. Unset fields
+-- inherit from the next level: column, then project, then the built-in defaults
+-- (8000 tokens split 50/30/20 across global, task and path scopes).
+CREATE TABLE context_budgets (
+    id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id    UUID REFERENCES projects(id) ON DELETE CASCADE,
+    column_id     UUID REFERENCES kanban_columns(id) ON DELETE CASCADE,
+    token_budget  INTEGER CHECK (token_budget > 0),
+    global_weight INTEGER CHECK (global_weight >= 0),
+    task_weight   INTEGER CHECK (task_weight >= 0),
+    path_weight   INTEGER CHECK (path_weight >= 0),
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((project_id IS NULL) <> (column_id IS NULL))
+);
+
+CREATE UNIQUE INDEX idx_context_budgets_project ON context_budgets(project_id)
+    WHERE project_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_context_budgets_column ON context_budgets(column_id)
+    WHERE column_id IS NOT NULL;
@@ -0,0 +1,15 @@
+-- Bookkeeping for run-once data migrations (backfills of existing rows). They used
+-- to run on every startup; now an admin triggers them and each completes once.
+-- A row appears the first time a migration is started; absent rows are pending.
+CREATE TABLE data_migrations (
+    name         TEXT PRIMARY KEY,
+    status       TEXT NOT NULL DEFAULT 'pending'
+                 CHECK (status IN ('pending', 'running', 'completed', 'failed')),
+    processed    INTEGER NOT NULL DEFAULT 0,
+    total        INTEGER,
+    attempts     INTEGER NOT NULL DEFAULT 0,
+    last_error   TEXT,
+    started_at   TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,13 @@
+-- Declarative label rules on columns: add or remove a label when a task enters
+-- or leaves the column. Covers the common case without a full automation rule.
+CREATE TABLE column_label_rules (
+    id         UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    column_id  UUID NOT NULL REFERENCES kanban_columns(id) ON DELETE CASCADE,
+    label_id   UUID NOT NULL REFERENCES task_labels(id) ON DELETE CASCADE,
+    trigger    TEXT NOT NULL CHECK (trigger IN ('enter', 'exit')),
+    action     TEXT NOT NULL CHECK (action IN ('add', 'remove')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (column_id, label_id, trigger)
+);
+
+CREATE INDEX idx_column_label_rules_column ON column_label_rules(column_id, trigger);
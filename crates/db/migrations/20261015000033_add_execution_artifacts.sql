@@ -0,0 +1,19 @@
+-- Files an agent left in .vibe/artifacts/ (screenshots, coverage reports,
+-- benchmark output), collected when its execution finalizes. The file itself
+-- lives in the images store; this links it to the execution and task.
+CREATE TABLE execution_artifacts (
+    id                   UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    execution_process_id UUID NOT NULL REFERENCES execution_processes(id) ON DELETE CASCADE,
+    task_id              UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    image_id             UUID NOT NULL REFERENCES images(id) ON DELETE CASCADE,
+    -- Path relative to the artifacts directory, e.g. "coverage/summary.json"
+    name                 TEXT NOT NULL,
+    -- Repo whose .vibe/artifacts/ held the file; NULL for the workspace root
+    repo_name            TEXT,
+    mime_type            TEXT,
+    size_bytes           INTEGER NOT NULL,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_execution_artifacts_execution_process ON execution_artifacts(execution_process_id);
+CREATE INDEX idx_execution_artifacts_task ON execution_artifacts(task_id, created_at);
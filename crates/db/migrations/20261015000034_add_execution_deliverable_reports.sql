@@ -0,0 +1,12 @@
+-- Which of the column's deliverable requirements an execution met: the question
+-- answered with a valid option, the files the deliverable names, and passing tests
+-- when it asks for them. Regenerating a report replaces the previous one.
+CREATE TABLE execution_deliverable_reports (
+    execution_process_id UUID PRIMARY KEY REFERENCES execution_processes(id) ON DELETE CASCADE,
+    -- Column the execution ran in; NULL once the column is deleted
+    column_id            UUID REFERENCES kanban_columns(id) ON DELETE SET NULL,
+    requirements         JSONB NOT NULL DEFAULT '[]',
+    satisfied_count      INTEGER NOT NULL,
+    missed_count         INTEGER NOT NULL,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,15 @@
+-- Script languages for a project repo's setup and cleanup scripts, and optional
+-- Windows variants of both, so a repo can run a bash setup on macOS/Linux and a
+-- PowerShell one on Windows without WSL.
+-- script_language: language of setup_script/cleanup_script; NULL runs them in the
+-- platform shell. windows_script_language: language of the Windows scripts; NULL
+-- means PowerShell. On Windows, a repo without a Windows script uses its regular one.
+CREATE TABLE project_repo_script_settings (
+    project_repo_id         UUID PRIMARY KEY REFERENCES project_repos(id) ON DELETE CASCADE,
+    script_language         TEXT CHECK (script_language IN ('bash', 'powershell', 'python', 'cmd')),
+    windows_setup_script    TEXT,
+    windows_cleanup_script  TEXT,
+    windows_script_language TEXT
+                            CHECK (windows_script_language IN ('bash', 'powershell', 'python', 'cmd')),
+    updated_at              TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
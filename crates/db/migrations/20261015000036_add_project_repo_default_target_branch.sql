@@ -0,0 +1,3 @@
+-- Branch new attempts target in a repo when the request doesn't name one; when
+-- unset, the branch checked out in the repo is used
+ALTER TABLE project_repos ADD COLUMN default_target_branch TEXT;
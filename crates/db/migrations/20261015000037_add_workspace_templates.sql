@@ -0,0 +1,19 @@
+-- Files a project seeds into every new workspace (AGENTS.md, .editorconfig, test
+-- harness stubs), on top of the files copied by copy_files. Content may contain
+-- {{variables}} (task id, branch, column) that are filled in when the workspace is
+-- created. repo_id NULL places the file relative to the workspace root, otherwise
+-- relative to that repo's worktree.
+CREATE TABLE workspace_templates (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id  UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    repo_id     UUID REFERENCES repos(id) ON DELETE CASCADE,
+    path        TEXT NOT NULL,
+    content     TEXT NOT NULL,
+    -- Replace a file the repo already has instead of keeping it
+    overwrite   BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_workspace_templates_target
+    ON workspace_templates(project_id, COALESCE(repo_id, '00000000-0000-0000-0000-000000000000'::UUID), path);
@@ -0,0 +1,15 @@
+-- People who receive notification emails, one row per address. Escalations and
+-- failed runs can be emailed right away; everything else is summarised in a daily
+-- digest sent at the chosen UTC hour.
+CREATE TABLE email_notification_preferences (
+    id                    UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    email                 TEXT NOT NULL UNIQUE,
+    name                  TEXT,
+    immediate_escalations BOOLEAN NOT NULL DEFAULT TRUE,
+    immediate_failures    BOOLEAN NOT NULL DEFAULT TRUE,
+    digest_enabled        BOOLEAN NOT NULL DEFAULT TRUE,
+    digest_hour           INTEGER NOT NULL DEFAULT 8 CHECK (digest_hour BETWEEN 0 AND 23),
+    last_digest_at        TIMESTAMPTZ,
+    created_at            TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at            TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
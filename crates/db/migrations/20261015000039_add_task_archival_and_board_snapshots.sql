@@ -0,0 +1,22 @@
+-- Archived tasks keep their history but drop out of task lists and the task stream
+ALTER TABLE tasks ADD COLUMN archived_at TIMESTAMPTZ;
+
+CREATE INDEX idx_tasks_archived ON tasks(project_id) WHERE archived_at IS NOT NULL;
+
+-- Daily task counts per board column, for burn-up charts. Archived tasks are still
+-- counted in their column so history doesn't shrink when a board is tidied up.
+CREATE TABLE board_snapshots (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    board_id    UUID NOT NULL REFERENCES boards(id) ON DELETE CASCADE,
+    captured_on DATE NOT NULL,
+    -- No foreign key, so deleting a column keeps its history
+    column_id   UUID NOT NULL,
+    column_name TEXT NOT NULL,
+    status      TEXT NOT NULL,
+    is_terminal BOOLEAN NOT NULL,
+    task_count  BIGINT NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (board_id, captured_on, column_id)
+);
+
+CREATE INDEX idx_board_snapshots_board ON board_snapshots(board_id, captured_on);
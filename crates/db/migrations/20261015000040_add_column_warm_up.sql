@@ -0,0 +1,10 @@
+-- Column warm-up: before the column's agent starts, an orientation of the workspace
+-- (repo layout and key files) is prepended to its prompt. The orientation is built
+-- once per workspace and reused by later columns.
+ALTER TABLE kanban_column_settings ADD COLUMN warm_up BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE workspace_orientations (
+    workspace_id UUID PRIMARY KEY REFERENCES workspaces(id) ON DELETE CASCADE,
+    content      TEXT NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,26 @@
+-- Board-level notification digests: non-urgent channel notifications (completions,
+-- label changes, ...) for tasks on a board with a digest cadence are queued and sent
+-- to each channel as one message per period. Urgent ones are still sent right away.
+CREATE TABLE board_notification_settings (
+    board_id       UUID PRIMARY KEY REFERENCES boards(id) ON DELETE CASCADE,
+    -- 'off', 'hourly' or 'daily'
+    digest_cadence TEXT NOT NULL DEFAULT 'off',
+    -- Hour of the day (UTC) daily digests are sent
+    digest_hour    INTEGER NOT NULL DEFAULT 9 CHECK (digest_hour BETWEEN 0 AND 23),
+    last_digest_at TIMESTAMPTZ,
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE notification_digest_items (
+    id         UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    board_id   UUID NOT NULL REFERENCES boards(id) ON DELETE CASCADE,
+    channel_id UUID NOT NULL REFERENCES notification_channels(id) ON DELETE CASCADE,
+    event      TEXT NOT NULL,
+    project_id UUID NOT NULL,
+    task_id    UUID NOT NULL,
+    title      TEXT NOT NULL,
+    message    TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_notification_digest_items_board ON notification_digest_items(board_id, created_at);
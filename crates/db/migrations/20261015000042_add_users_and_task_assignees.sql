@@ -0,0 +1,18 @@
+-- People who work on the board. The signed-in account gets a row the first time it acts
+-- (keyed by its remote user id); teammates can be added by hand so tasks can be
+-- assigned to them.
+CREATE TABLE users (
+    id             UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    remote_user_id UUID UNIQUE,
+    username       TEXT,
+    email          TEXT NOT NULL,
+    display_name   TEXT,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_users_email ON users(LOWER(email));
+
+ALTER TABLE tasks ADD COLUMN assignee_id UUID REFERENCES users(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tasks_assignee ON tasks(assignee_id) WHERE assignee_id IS NOT NULL;
@@ -0,0 +1,17 @@
+-- Personal access tokens for CI jobs and remote MCP servers. Only the token's SHA-256
+-- is stored; the token itself is shown once on creation.
+CREATE TABLE api_tokens (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name         TEXT NOT NULL,
+    token_hash   TEXT NOT NULL UNIQUE,
+    -- 'read' (GET requests only) or 'write'
+    scope        TEXT NOT NULL DEFAULT 'read' CHECK (scope IN ('read', 'write')),
+    -- Limits the token to one project's routes when set
+    project_id   UUID REFERENCES projects(id) ON DELETE CASCADE,
+    -- Who created the token
+    user_id      UUID REFERENCES users(id) ON DELETE SET NULL,
+    expires_at   TIMESTAMPTZ,
+    last_used_at TIMESTAMPTZ,
+    revoked_at   TIMESTAMPTZ,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
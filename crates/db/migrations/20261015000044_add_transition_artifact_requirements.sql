@@ -0,0 +1,9 @@
+-- Optional requirement that a stage produced a context artifact of a given type
+-- (e.g. an iplan) before a transition's success path may fire. When the artifact
+-- is missing the transition is treated as failed and takes its else path.
+CREATE TABLE transition_artifact_requirements (
+    transition_id UUID PRIMARY KEY REFERENCES state_transitions(id) ON DELETE CASCADE,
+    artifact_type TEXT NOT NULL,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
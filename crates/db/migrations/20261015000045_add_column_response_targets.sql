@@ -0,0 +1,3 @@
+-- Response-time target for a column, used by the escalation SLO report: a task
+-- escalated into the column breaches the target when it stays longer than this.
+ALTER TABLE kanban_column_settings ADD COLUMN response_target_mins INTEGER;
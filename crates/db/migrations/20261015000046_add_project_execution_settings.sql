@@ -0,0 +1,19 @@
+-- Where a project's agents and scripts run (defaults apply when no row exists).
+-- execution_backend: 'local' runs them in git worktrees on the host; 'docker' clones
+-- the repos into a per-workspace container built from docker_image and runs them
+-- there with `docker exec`.
+CREATE TABLE project_execution_settings (
+    project_id        UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    execution_backend TEXT NOT NULL DEFAULT 'local',
+    docker_image      TEXT,
+    updated_at        TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Docker container of a workspace created with the docker backend. Workspaces keep
+-- the backend they were created with when the project's setting changes.
+CREATE TABLE workspace_containers (
+    workspace_id   UUID PRIMARY KEY REFERENCES workspaces(id) ON DELETE CASCADE,
+    container_name TEXT NOT NULL,
+    image          TEXT NOT NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,11 @@
+-- Order of the sections of an agent's initial prompt (system_prompt, project_context,
+-- workflow_history, task, instructions, decision_instructions, deliverable); sections
+-- not listed are left out. Empty uses the default order. A column's list overrides
+-- the agent's.
+ALTER TABLE kanban_column_settings ADD COLUMN prompt_sections TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE TABLE agent_prompt_settings (
+    agent_id        UUID PRIMARY KEY REFERENCES agents(id) ON DELETE CASCADE,
+    prompt_sections TEXT[] NOT NULL DEFAULT '{}',
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
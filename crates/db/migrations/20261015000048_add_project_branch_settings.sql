@@ -0,0 +1,7 @@
+-- Per-project template for the git branch of new task workspaces, e.g.
+-- '{agent}/{task_id}/{slug}'. NULL uses the default '{prefix}/{short_id}-{slug}'.
+CREATE TABLE project_branch_settings (
+    project_id      UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    branch_template TEXT,
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,11 @@
+-- Per-repo forge used for pull requests, status checks and issue import.
+-- forge: 'github' (via the gh CLI) or 'gitea' (Gitea/Forgejo REST API at base_url).
+-- The API token is sealed with the env vault key like project env vars.
+CREATE TABLE repo_forge_settings (
+    repo_id          UUID PRIMARY KEY REFERENCES repos(id) ON DELETE CASCADE,
+    forge            TEXT NOT NULL DEFAULT 'github' CHECK (forge IN ('github', 'gitea')),
+    base_url         TEXT,
+    token_ciphertext TEXT,
+    token_nonce      TEXT,
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,4 @@
+-- GitLab (gitlab.com or the instance at base_url) and Bitbucket Cloud as repo forges.
+ALTER TABLE repo_forge_settings DROP CONSTRAINT repo_forge_settings_forge_check;
+ALTER TABLE repo_forge_settings ADD CONSTRAINT repo_forge_settings_forge_check
+    CHECK (forge IN ('github', 'gitea', 'gitlab', 'bitbucket'));
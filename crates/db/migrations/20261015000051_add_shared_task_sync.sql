@@ -0,0 +1,33 @@
+-- Two-way sync of shared tasks. The sync state holds the title, description and
+-- status a task and its remote copy last agreed on, the base of a three-way merge.
+CREATE TABLE shared_task_sync_states (
+    task_id           UUID PRIMARY KEY REFERENCES tasks(id) ON DELETE CASCADE,
+    base              JSONB NOT NULL,
+    remote_updated_at TIMESTAMPTZ NOT NULL,
+    synced_at         TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Fields edited on both sides since the last sync. Last-writer-wins projects record
+-- them already resolved; manual projects leave them open until someone picks a side.
+CREATE TABLE shared_task_conflicts (
+    id             UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    task_id        UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    shared_task_id UUID NOT NULL,
+    fields         TEXT[] NOT NULL,
+    local          JSONB NOT NULL,
+    remote         JSONB NOT NULL,
+    resolution     TEXT CHECK (resolution IN ('local', 'remote')),
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at    TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX idx_shared_task_conflicts_open
+    ON shared_task_conflicts(task_id) WHERE resolved_at IS NULL;
+
+-- How a project settles shared task conflicts: 'last_writer_wins' or 'manual'
+CREATE TABLE project_share_sync_settings (
+    project_id        UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    conflict_strategy TEXT NOT NULL DEFAULT 'last_writer_wins'
+                      CHECK (conflict_strategy IN ('last_writer_wins', 'manual')),
+    updated_at        TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,3 @@
+-- Executor profile variant (e.g. 'OPUS', 'PLAN') the column's agent runs with, so one
+-- agent can use different models per column. NULL uses the agent's default variant.
+ALTER TABLE kanban_column_settings ADD COLUMN executor_variant TEXT;
@@ -0,0 +1,30 @@
+-- Chat users allowed to act on approval requests from a Slack or Matrix channel.
+-- `external_user_id` is the Slack member id or the Matrix user id.
+CREATE TABLE notification_channel_identities (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    channel_id       UUID NOT NULL REFERENCES notification_channels(id) ON DELETE CASCADE,
+    external_user_id TEXT NOT NULL,
+    user_id          UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (channel_id, external_user_id)
+);
+
+-- Approval requests posted to a channel, polled for ✅/❌ reactions until decided.
+-- `subject_id` is a plan review or a state transition, per `subject_kind`;
+-- `message_ref` is the Slack message ts or the Matrix event id.
+CREATE TABLE chat_approval_messages (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    channel_id   UUID NOT NULL REFERENCES notification_channels(id) ON DELETE CASCADE,
+    task_id      UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    subject_kind TEXT NOT NULL CHECK (subject_kind IN ('plan_review', 'transition')),
+    subject_id   UUID NOT NULL,
+    message_ref  TEXT NOT NULL,
+    status       TEXT NOT NULL DEFAULT 'pending'
+                 CHECK (status IN ('pending', 'approved', 'rejected', 'expired')),
+    decided_by   UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    decided_at   TIMESTAMPTZ
+);
+
+CREATE INDEX idx_chat_approval_messages_pending
+    ON chat_approval_messages(created_at) WHERE status = 'pending';
@@ -0,0 +1,15 @@
+-- Values the log redaction pass leaves alone for a project, e.g. documented example
+-- keys its tests print. Project env vault values are always redacted.
+CREATE TABLE project_log_redaction_settings (
+    project_id UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    allowlist  TEXT[] NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- How many secrets were masked in an execution's output before it was stored
+CREATE TABLE execution_log_redactions (
+    execution_process_id UUID PRIMARY KEY
+                         REFERENCES execution_processes(id) ON DELETE CASCADE,
+    redaction_count      BIGINT NOT NULL DEFAULT 0,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
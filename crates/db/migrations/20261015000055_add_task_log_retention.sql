@@ -0,0 +1,8 @@
+-- Per-task override of how long execution logs and artifacts are kept.
+-- Tasks without a row follow the default retention.
+CREATE TABLE task_log_retention (
+    task_id    UUID PRIMARY KEY REFERENCES tasks(id) ON DELETE CASCADE,
+    retention  TEXT NOT NULL DEFAULT 'default'
+               CHECK (retention IN ('default', 'keep_forever', 'short')),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,7 @@
+-- Column ordering rules of a board. With `strict_column_order` set, reordering must
+-- keep the initial column first and terminal columns last.
+CREATE TABLE board_layout_settings (
+    board_id            UUID PRIMARY KEY REFERENCES boards(id) ON DELETE CASCADE,
+    strict_column_order BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,12 @@
+-- Links a coding agent run to its replays: the same prompt and agent context re-run
+-- with another executor in a fresh workspace, for side-by-side comparison.
+CREATE TABLE execution_replays (
+    id                          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    source_execution_process_id UUID NOT NULL
+                                REFERENCES execution_processes(id) ON DELETE CASCADE,
+    replay_execution_process_id UUID NOT NULL UNIQUE
+                                REFERENCES execution_processes(id) ON DELETE CASCADE,
+    created_at                  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_execution_replays_source ON execution_replays(source_execution_process_id);
@@ -0,0 +1,16 @@
+-- Dry-run columns: the agent only proposes changes. Nothing it does is committed;
+-- its changes are kept as one patch per repo for a person to apply.
+ALTER TABLE kanban_column_settings ADD COLUMN dry_run BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE dry_run_patches (
+    id                   UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    execution_process_id UUID NOT NULL REFERENCES execution_processes(id) ON DELETE CASCADE,
+    task_id              UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    workspace_id         UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    repo_id              UUID NOT NULL REFERENCES repos(id) ON DELETE CASCADE,
+    patch                TEXT NOT NULL,
+    applied_at           TIMESTAMPTZ,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_dry_run_patches_task ON dry_run_patches(task_id, created_at);
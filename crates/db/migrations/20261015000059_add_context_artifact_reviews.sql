@@ -0,0 +1,23 @@
+-- Review state of context artifacts: draft -> approved -> archived. Only approved
+-- artifacts go into agent prompts. Artifacts without a row were written by a person
+-- or before reviews existed, and count as approved.
+CREATE TABLE context_artifact_reviews (
+    artifact_id UUID PRIMARY KEY REFERENCES context_artifacts(id) ON DELETE CASCADE,
+    status      TEXT NOT NULL DEFAULT 'draft'
+                CHECK (status IN ('draft', 'approved', 'archived')),
+    reviewer    TEXT,
+    reviewed_at TIMESTAMPTZ,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_context_artifact_reviews_status ON context_artifact_reviews(status);
+
+-- How long artifacts of a type stay in agent prompts after approval, per project.
+-- Types without a row never expire.
+CREATE TABLE context_artifact_ttls (
+    project_id    UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    artifact_type TEXT NOT NULL,
+    ttl_days      INTEGER NOT NULL CHECK (ttl_days > 0),
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, artifact_type)
+);
@@ -0,0 +1,8 @@
+-- Per-project settings for refreshing module memories after a merge: an agent run
+-- rewrites the memories of the modules the merged branch touched.
+CREATE TABLE project_module_memory_settings (
+    project_id          UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    refresh_after_merge BOOLEAN NOT NULL DEFAULT FALSE,
+    max_modules         INTEGER NOT NULL DEFAULT 10 CHECK (max_modules > 0),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
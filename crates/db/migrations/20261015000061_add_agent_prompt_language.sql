@@ -0,0 +1,3 @@
+-- Language of the instruction text the server adds to an agent's prompts (section
+-- headings, decision instructions, column notices): english, german or japanese
+ALTER TABLE agent_prompt_settings ADD COLUMN language TEXT NOT NULL DEFAULT 'english';
@@ -0,0 +1,7 @@
+-- Per-project IANA timezone (e.g. 'Europe/Berlin') that digests and daily board
+-- snapshots are evaluated in instead of server UTC.
+CREATE TABLE project_timezone_settings (
+    project_id UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    timezone   TEXT NOT NULL DEFAULT 'UTC',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
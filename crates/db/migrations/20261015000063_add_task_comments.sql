@@ -0,0 +1,13 @@
+-- Comment thread on a task. Comments visible to the agent are appended to the next
+-- agent prompt until a run that started after them moves the task on.
+CREATE TABLE task_comments (
+    id               UUID PRIMARY KEY,
+    task_id          UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    content          TEXT NOT NULL,
+    visible_to_agent BOOLEAN NOT NULL DEFAULT FALSE,
+    delivered_at     TIMESTAMPTZ,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_task_comments_task_id ON task_comments(task_id, created_at);
@@ -0,0 +1,20 @@
+-- Transition conditions containing a comparison operator are now parsed as
+-- expressions. Quote answers saved before that which contain one, so they keep
+-- matching the agent's answer literally: those that are one of the source column's
+-- answer options, and those that don't read as `key <op> value` at all.
+UPDATE state_transitions st
+SET condition_value = CASE
+        WHEN st.condition_value LIKE '%"%' THEN '''' || st.condition_value || ''''
+        ELSE '"' || st.condition_value || '"'
+    END
+WHERE st.condition_value ~ '(==|!=|<|>|\s(not\s+)?in\s*\[)'
+  AND st.condition_value !~ '^(".*"|''.*'')$'
+  AND (
+      st.condition_value !~ '^\s*[A-Za-z_][A-Za-z0-9_.]*\s*(==|!=|>=|<=|>|<|(not\s+)?in\s*\[)'
+      OR EXISTS (
+          SELECT 1
+          FROM kanban_columns kc
+          WHERE kc.id = st.from_column_id
+            AND position(to_json(st.condition_value)::text IN COALESCE(kc.answer_options, '')) > 0
+      )
+  );
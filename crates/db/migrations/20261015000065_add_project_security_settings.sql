@@ -0,0 +1,7 @@
+-- A scanner that is missing or fails to run blocks the security gate unless the
+-- project opts out here
+CREATE TABLE project_security_settings (
+    project_id           UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    allow_scanner_errors BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,10 @@
+-- Which outdated packages each dependency update task covers, so a package isn't
+-- picked up again while its task is open however the next run batches the updates.
+-- source_id is `<ecosystem>:<repo id>:<package>`.
+CREATE TABLE dependency_update_tasks (
+    task_id   UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    source_id TEXT NOT NULL,
+    PRIMARY KEY (task_id, source_id)
+);
+
+CREATE INDEX idx_dependency_update_tasks_source_id ON dependency_update_tasks(source_id);
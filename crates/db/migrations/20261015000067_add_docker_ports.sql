@@ -0,0 +1,8 @@
+-- Container ports a project's Docker workspaces publish on the host's loopback
+-- interface, at the same port number, so dev servers in a container can be reached
+-- and previewed. Containers keep the ports they were created with.
+ALTER TABLE project_execution_settings
+    ADD COLUMN docker_ports INTEGER[] NOT NULL DEFAULT '{}';
+
+ALTER TABLE workspace_containers
+    ADD COLUMN ports INTEGER[] NOT NULL DEFAULT '{}';
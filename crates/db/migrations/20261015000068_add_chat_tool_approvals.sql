@@ -0,0 +1,10 @@
+-- Executor tool approvals can be answered from chat too. Transition and tool approval
+-- requests belong to one run: `execution_process_id` is the execution whose finish
+-- asked to confirm the transition, or whose tool call waits for the answer. Pending
+-- transition requests from before are expired on the next poll.
+ALTER TABLE chat_approval_messages DROP CONSTRAINT chat_approval_messages_subject_kind_check;
+ALTER TABLE chat_approval_messages ADD CONSTRAINT chat_approval_messages_subject_kind_check
+    CHECK (subject_kind IN ('plan_review', 'transition', 'tool_approval'));
+
+ALTER TABLE chat_approval_messages
+    ADD COLUMN execution_process_id UUID REFERENCES execution_processes(id) ON DELETE CASCADE;
@@ -0,0 +1,31 @@
+-- Column behaviour settings (modes, gates, scripts and prompt overrides) live on the
+-- column itself instead of a side table with a row per configured column.
+ALTER TABLE kanban_columns
+    ADD COLUMN read_only BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN plan_only BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN dry_run BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN security_scanners TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN checklist TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN on_enter_script TEXT,
+    ADD COLUMN on_exit_script TEXT,
+    ADD COLUMN warm_up BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN response_target_mins INTEGER,
+    ADD COLUMN prompt_sections TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN executor_variant TEXT;
+
+UPDATE kanban_columns c
+SET read_only = s.read_only,
+    plan_only = s.plan_only,
+    dry_run = s.dry_run,
+    security_scanners = s.security_scanners,
+    checklist = s.checklist,
+    on_enter_script = s.on_enter_script,
+    on_exit_script = s.on_exit_script,
+    warm_up = s.warm_up,
+    response_target_mins = s.response_target_mins,
+    prompt_sections = s.prompt_sections,
+    executor_variant = s.executor_variant
+FROM kanban_column_settings s
+WHERE s.column_id = c.id;
+
+DROP TABLE kanban_column_settings;
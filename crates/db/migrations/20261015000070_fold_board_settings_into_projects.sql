@@ -0,0 +1,11 @@
+-- Board behaviour is a property of the project itself instead of a side table with a row
+-- per configured project.
+ALTER TABLE projects
+    ADD COLUMN auto_assign_initial_column BOOLEAN NOT NULL DEFAULT TRUE;
+
+UPDATE projects p
+SET auto_assign_initial_column = s.auto_assign_initial_column
+FROM project_board_settings s
+WHERE s.project_id = p.id;
+
+DROP TABLE project_board_settings;
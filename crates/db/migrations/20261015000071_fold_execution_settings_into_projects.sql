@@ -0,0 +1,15 @@
+-- A project's execution backend lives on the project itself instead of a side table
+-- with a row per configured project. Workspace containers stay in their own table.
+ALTER TABLE projects
+    ADD COLUMN execution_backend TEXT NOT NULL DEFAULT 'local',
+    ADD COLUMN docker_image TEXT,
+    ADD COLUMN docker_ports INTEGER[] NOT NULL DEFAULT '{}';
+
+UPDATE projects p
+SET execution_backend = s.execution_backend,
+    docker_image = s.docker_image,
+    docker_ports = s.docker_ports
+FROM project_execution_settings s
+WHERE s.project_id = p.id;
+
+DROP TABLE project_execution_settings;
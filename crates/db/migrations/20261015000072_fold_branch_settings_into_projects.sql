@@ -0,0 +1,11 @@
+-- A project's branch template lives on the project itself instead of a side table
+-- with a row per configured project.
+ALTER TABLE projects
+    ADD COLUMN branch_template TEXT;
+
+UPDATE projects p
+SET branch_template = s.branch_template
+FROM project_branch_settings s
+WHERE s.project_id = p.id;
+
+DROP TABLE project_branch_settings;
@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use executors::mcp_config::AgentMcpServer;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// MCP settings for an agent (defaults apply when no row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AgentMcpSettings {
+    pub agent_id: Uuid,
+    /// Backend URL for the vibe_kanban MCP server; None uses the local server
+    pub mcp_base_url: Option<String>,
+    /// Extra MCP servers wired into the coding agent's configuration at spawn
+    #[ts(type = "Array<AgentMcpServer>")]
+    pub mcp_servers: Json<Vec<AgentMcpServer>>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateAgentMcpSettings {
+    /// An empty string clears the override
+    pub mcp_base_url: Option<String>,
+    pub mcp_servers: Option<Vec<AgentMcpServer>>,
+}
+
+impl AgentMcpSettings {
+    fn defaults(agent_id: Uuid) -> Self {
+        Self {
+            agent_id,
+            mcp_base_url: None,
+            mcp_servers: Json(Vec::new()),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// MCP settings of an agent; the local server and no extra servers when none are
+    /// stored
+    pub async fn for_agent(pool: &PgPool, agent_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            AgentMcpSettings,
+            r#"SELECT agent_id as "agent_id!: Uuid",
+                      mcp_base_url,
+                      mcp_servers as "mcp_servers!: Json<Vec<AgentMcpServer>>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM agent_mcp_settings
+               WHERE agent_id = $1"#,
+            agent_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(agent_id)))
+    }
+
+    /// Settings of the agent that launched a session, if it has any
+    pub async fn for_session(pool: &PgPool, session_id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AgentMcpSettings,
+            r#"SELECT s.agent_id as "agent_id!: Uuid",
+                      s.mcp_base_url,
+                      s.mcp_servers as "mcp_servers!: Json<Vec<AgentMcpServer>>",
+                      s.updated_at as "updated_at!: DateTime<Utc>"
+               FROM agent_mcp_settings s
+               JOIN agent_sessions a ON a.agent_id = s.agent_id
+               WHERE a.session_id = $1"#,
+            session_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Change the agent's MCP base URL or extra servers; an empty URL clears the override
+    pub async fn update(
+        pool: &PgPool,
+        agent_id: Uuid,
+        data: &UpdateAgentMcpSettings,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::for_agent(pool, agent_id).await?;
+        let mcp_base_url = match &data.mcp_base_url {
+            Some(url) if url.trim().is_empty() => None,
+            Some(url) => Some(url.trim().to_string()),
+            None => current.mcp_base_url,
+        };
+        let mcp_servers = match &data.mcp_servers {
+            Some(servers) => Json(servers.clone()),
+            None => current.mcp_servers,
+        };
+        sqlx::query_as!(
+            AgentMcpSettings,
+            r#"INSERT INTO agent_mcp_settings (agent_id, mcp_base_url, mcp_servers)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (agent_id) DO UPDATE
+               SET mcp_base_url = EXCLUDED.mcp_base_url,
+                   mcp_servers = EXCLUDED.mcp_servers,
+                   updated_at = NOW()
+               RETURNING agent_id as "agent_id!: Uuid",
+                         mcp_base_url,
+                         mcp_servers as "mcp_servers!: Json<Vec<AgentMcpServer>>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            agent_id,
+            mcp_base_url,
+            mcp_servers as _
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
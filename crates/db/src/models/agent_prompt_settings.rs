@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use executors::actions::coding_agent_initial::{PromptLanguage, PromptSection};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Prompt settings for an agent (defaults apply when no row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AgentPromptSettings {
+    pub agent_id: Uuid,
+    /// Order of the sections of the agent's prompt (see `PromptSection`); sections not
+    /// listed are left out. Empty uses the default order.
+    pub prompt_sections: Vec<String>,
+    /// Language of the instruction text the server adds to the agent's prompts
+    /// (see `PromptLanguage`)
+    pub language: String,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateAgentPromptSettings {
+    /// Empty list restores the default order
+    pub prompt_sections: Option<Vec<PromptSection>>,
+    pub language: Option<PromptLanguage>,
+}
+
+impl AgentPromptSettings {
+    fn defaults(agent_id: Uuid) -> Self {
+        Self {
+            agent_id,
+            prompt_sections: Vec::new(),
+            language: PromptLanguage::default().as_str().to_string(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Prompt settings of an agent; the default section order and English when none are
+    /// stored
+    pub async fn for_agent(pool: &PgPool, agent_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            AgentPromptSettings,
+            r#"SELECT agent_id as "agent_id!: Uuid",
+                      prompt_sections as "prompt_sections!",
+                      language as "language!",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM agent_prompt_settings
+               WHERE agent_id = $1"#,
+            agent_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(agent_id)))
+    }
+
+    /// The agent's prompt section order, if it sets one
+    pub fn prompt_section_order(&self) -> Option<Vec<PromptSection>> {
+        Some(PromptSection::parse_list(&self.prompt_sections)).filter(|s| !s.is_empty())
+    }
+
+    /// Language of the agent's instruction text; unknown values fall back to English
+    pub fn prompt_language(&self) -> PromptLanguage {
+        PromptLanguage::from_str(&self.language).unwrap_or_default()
+    }
+
+    /// Change the agent's prompt section order or instruction language
+    pub async fn update(
+        pool: &PgPool,
+        agent_id: Uuid,
+        data: &UpdateAgentPromptSettings,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::for_agent(pool, agent_id).await?;
+        let prompt_sections = match &data.prompt_sections {
+            Some(sections) => PromptSection::to_names(sections),
+            None => current.prompt_sections,
+        };
+        let language = match data.language {
+            Some(language) => language.as_str().to_string(),
+            None => current.language,
+        };
+        sqlx::query_as!(
+            AgentPromptSettings,
+            r#"INSERT INTO agent_prompt_settings (agent_id, prompt_sections, language)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (agent_id) DO UPDATE
+               SET prompt_sections = EXCLUDED.prompt_sections,
+                   language = EXCLUDED.language,
+                   updated_at = NOW()
+               RETURNING agent_id as "agent_id!: Uuid",
+                         prompt_sections as "prompt_sections!",
+                         language as "language!",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            agent_id,
+            &prompt_sections,
+            language
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Links a session to the agent (and column) that launched it.
+/// Used to attribute execution processes to agents for performance stats.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AgentSession {
+    pub session_id: Uuid,
+    pub agent_id: Uuid,
+    pub column_id: Option<Uuid>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl AgentSession {
+    /// Record which agent launched a session. No-op if already recorded.
+    pub async fn create(
+        pool: &PgPool,
+        session_id: Uuid,
+        agent_id: Uuid,
+        column_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO agent_sessions (session_id, agent_id, column_id)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (session_id) DO NOTHING"#,
+            session_id,
+            agent_id,
+            column_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_session_id(
+        pool: &PgPool,
+        session_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AgentSession,
+            r#"SELECT session_id as "session_id!: Uuid",
+                      agent_id as "agent_id!: Uuid",
+                      column_id as "column_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM agent_sessions
+               WHERE session_id = $1"#,
+            session_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Optional time window for stats queries (`?from=...&to=...`, RFC 3339)
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+pub struct StatsTimeRange {
+    #[ts(type = "Date | null")]
+    pub from: Option<DateTime<Utc>>,
+    #[ts(type = "Date | null")]
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Aggregated workflow performance for an agent (or a whole board)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct PerformanceStats {
+    /// Automated transitions out of columns the agent works in
+    pub transitions: i64,
+    pub success_transitions: i64,
+    pub else_transitions: i64,
+    pub escalations: i64,
+    /// success / (success + else + escalation); null when no conditional transitions ran
+    pub success_rate: Option<f64>,
+    /// escalation / (success + else + escalation)
+    pub escalation_rate: Option<f64>,
+    /// Coding agent execution processes
+    pub executions: i64,
+    pub failed_executions: i64,
+    pub avg_execution_secs: Option<f64>,
+    /// Executions whose agent reported token usage (only Claude Code does); the token
+    /// totals cover these only and are null when there are none
+    pub token_usage_executions: i64,
+    pub input_tokens: Option<i64>,
+    pub output_tokens: Option<i64>,
+    pub total_tokens: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct AgentStats {
+    pub agent_id: Uuid,
+    pub agent_name: String,
+    pub stats: PerformanceStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BoardStats {
+    pub board_id: Uuid,
+    /// Totals across every agent on the board
+    pub totals: PerformanceStats,
+    pub agents: Vec<AgentStats>,
+}
+
+struct TransitionRow {
+    agent_id: Uuid,
+    agent_name: String,
+    total: i64,
+    success: i64,
+    else_path: i64,
+    escalation: i64,
+}
+
+struct ExecutionRow {
+    agent_id: Uuid,
+    agent_name: String,
+    executions: i64,
+    failed: i64,
+    completed: i64,
+    total_duration_secs: f64,
+    token_usage_executions: i64,
+    input_tokens: i64,
+    output_tokens: i64,
+}
+
+/// Raw sums per agent, merged before ratios/averages are derived
+#[derive(Default)]
+struct Accumulator {
+    agent_name: String,
+    transitions: i64,
+    success: i64,
+    else_path: i64,
+    escalation: i64,
+    executions: i64,
+    failed: i64,
+    completed: i64,
+    total_duration_secs: f64,
+    token_usage_executions: i64,
+    input_tokens: i64,
+    output_tokens: i64,
+}
+
+impl Accumulator {
+    fn add(&mut self, other: &Accumulator) {
+        self.transitions += other.transitions;
+        self.success += other.success;
+        self.else_path += other.else_path;
+        self.escalation += other.escalation;
+        self.executions += other.executions;
+        self.failed += other.failed;
+        self.completed += other.completed;
+        self.total_duration_secs += other.total_duration_secs;
+        self.token_usage_executions += other.token_usage_executions;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+    }
+
+    fn finish(&self) -> PerformanceStats {
+        let conditional = self.success + self.else_path + self.escalation;
+        let ratio = |n: i64| (conditional > 0).then(|| n as f64 / conditional as f64);
+        let tokens = |n: i64| (self.token_usage_executions > 0).then_some(n);
+        PerformanceStats {
+            transitions: self.transitions,
+            success_transitions: self.success,
+            else_transitions: self.else_path,
+            escalations: self.escalation,
+            success_rate: ratio(self.success),
+            escalation_rate: ratio(self.escalation),
+            executions: self.executions,
+            failed_executions: self.failed,
+            avg_execution_secs: (self.completed > 0)
+                .then(|| self.total_duration_secs / self.completed as f64),
+            token_usage_executions: self.token_usage_executions,
+            input_tokens: tokens(self.input_tokens),
+            output_tokens: tokens(self.output_tokens),
+            total_tokens: tokens(self.input_tokens + self.output_tokens),
+        }
+    }
+}
+
+impl AgentStats {
+    /// Stats for a single agent across all boards
+    pub async fn for_agent(
+        pool: &PgPool,
+        agent_id: Uuid,
+        agent_name: &str,
+        range: &StatsTimeRange,
+    ) -> Result<Self, sqlx::Error> {
+        let stats = collect(pool, Some(agent_id), None, range)
+            .await?
+            .remove(&agent_id)
+            .map(|acc| acc.finish())
+            .unwrap_or_default();
+
+        Ok(Self {
+            agent_id,
+            agent_name: agent_name.to_string(),
+            stats,
+        })
+    }
+}
+
+impl BoardStats {
+    /// Stats for every agent working on a board's columns, plus board totals
+    pub async fn for_board(
+        pool: &PgPool,
+        board_id: Uuid,
+        range: &StatsTimeRange,
+    ) -> Result<Self, sqlx::Error> {
+        let per_agent = collect(pool, None, Some(board_id), range).await?;
+
+        let mut totals = Accumulator::default();
+        let mut agents = Vec::with_capacity(per_agent.len());
+        for (agent_id, acc) in per_agent {
+            totals.add(&acc);
+            agents.push(AgentStats {
+                agent_id,
+                agent_name: acc.agent_name.clone(),
+                stats: acc.finish(),
+            });
+        }
+        agents.sort_by(|a, b| a.agent_name.cmp(&b.agent_name));
+
+        Ok(Self {
+            board_id,
+            totals: totals.finish(),
+            agents,
+        })
+    }
+}
+
+/// Gather per-agent sums, optionally filtered to one agent and/or one board.
+///
+/// Transitions come from automated `column_enter` events leaving a column the
+/// agent is assigned to (the path is recorded in the event metadata).
+/// Executions come from coding agent processes in sessions the agent launched.
+async fn collect(
+    pool: &PgPool,
+    agent_id: Option<Uuid>,
+    board_id: Option<Uuid>,
+    range: &StatsTimeRange,
+) -> Result<BTreeMap<Uuid, Accumulator>, sqlx::Error> {
+    let transitions = sqlx::query_as!(
+        TransitionRow,
+        r#"SELECT
+                a.id AS "agent_id!: Uuid",
+                a.name AS "agent_name!",
+                COUNT(*) AS "total!",
+                COUNT(*) FILTER (WHERE e.metadata::jsonb->>'transition_path' = 'success')
+                    AS "success!",
+                COUNT(*) FILTER (WHERE e.metadata::jsonb->>'transition_path' = 'else')
+                    AS "else_path!",
+                COUNT(*) FILTER (WHERE e.metadata::jsonb->>'transition_path' = 'escalation')
+                    AS "escalation!"
+           FROM task_events e
+           JOIN kanban_columns c ON c.id = e.from_column_id
+           JOIN agents a ON a.id = c.agent_id
+           WHERE e.event_type = 'column_enter'
+             AND e.trigger_type = 'automation'
+             AND ($1::uuid IS NULL OR a.id = $1)
+             AND ($2::uuid IS NULL OR c.board_id = $2)
+             AND ($3::timestamptz IS NULL OR e.created_at >= $3)
+             AND ($4::timestamptz IS NULL OR e.created_at < $4)
+           GROUP BY a.id, a.name"#,
+        agent_id,
+        board_id,
+        range.from,
+        range.to
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let executions = sqlx::query_as!(
+        ExecutionRow,
+        r#"SELECT
+                a.id AS "agent_id!: Uuid",
+                a.name AS "agent_name!",
+                COUNT(*) AS "executions!",
+                COUNT(*) FILTER (WHERE ep.status = 'failed') AS "failed!",
+                COUNT(ep.completed_at) AS "completed!",
+                COALESCE(SUM(EXTRACT(EPOCH FROM (ep.completed_at - ep.started_at))), 0)::float8
+                    AS "total_duration_secs!",
+                COUNT(tu.execution_process_id) AS "token_usage_executions!",
+                COALESCE(SUM(tu.input_tokens), 0)::bigint AS "input_tokens!",
+                COALESCE(SUM(tu.output_tokens), 0)::bigint AS "output_tokens!"
+           FROM execution_processes ep
+           JOIN agent_sessions s ON s.session_id = ep.session_id
+           JOIN agents a ON a.id = s.agent_id
+           LEFT JOIN kanban_columns c ON c.id = s.column_id
+           LEFT JOIN execution_token_usage tu ON tu.execution_process_id = ep.id
+           WHERE ep.run_reason = 'codingagent'
+             AND ($1::uuid IS NULL OR a.id = $1)
+             AND ($2::uuid IS NULL OR c.board_id = $2)
+             AND ($3::timestamptz IS NULL OR ep.started_at >= $3)
+             AND ($4::timestamptz IS NULL OR ep.started_at < $4)
+           GROUP BY a.id, a.name"#,
+        agent_id,
+        board_id,
+        range.from,
+        range.to
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut per_agent: BTreeMap<Uuid, Accumulator> = BTreeMap::new();
+    for row in transitions {
+        let acc = per_agent.entry(row.agent_id).or_default();
+        acc.agent_name = row.agent_name;
+        acc.transitions = row.total;
+        acc.success = row.success;
+        acc.else_path = row.else_path;
+        acc.escalation = row.escalation;
+    }
+    for row in executions {
+        let acc = per_agent.entry(row.agent_id).or_default();
+        acc.agent_name = row.agent_name;
+        acc.executions = row.executions;
+        acc.failed = row.failed;
+        acc.completed = row.completed;
+        acc.total_duration_secs = row.total_duration_secs;
+        acc.token_usage_executions = row.token_usage_executions;
+        acc.input_tokens = row.input_tokens;
+        acc.output_tokens = row.output_tokens;
+    }
+
+    Ok(per_agent)
+}
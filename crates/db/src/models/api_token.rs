@@ -0,0 +1,182 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// What a personal access token may do
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiTokenScope {
+    /// GET requests only
+    Read,
+    Write,
+}
+
+impl ApiTokenScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiTokenScope::Read => "read",
+            ApiTokenScope::Write => "write",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "read" => Some(ApiTokenScope::Read),
+            "write" => Some(ApiTokenScope::Write),
+            _ => None,
+        }
+    }
+}
+
+/// A personal access token for calling the API without interactive sign-in.
+/// Only the token's hash is stored; the token itself is shown once on creation.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub name: String,
+    /// `read` or `write` (see `ApiTokenScope`)
+    pub scope: String,
+    /// Limits the token to this project's routes when set
+    pub project_id: Option<Uuid>,
+    /// Who created the token
+    pub user_id: Option<Uuid>,
+    #[ts(type = "Date | null")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date | null")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date | null")]
+    pub revoked_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateApiToken {
+    pub name: String,
+    pub scope: ApiTokenScope,
+    pub project_id: Option<Uuid>,
+    /// Token never expires when unset
+    pub expires_in_days: Option<i32>,
+}
+
+/// Returned once when a token is created
+#[derive(Debug, Serialize, TS)]
+pub struct CreatedApiToken {
+    pub api_token: ApiToken,
+    pub token: String,
+}
+
+impl ApiToken {
+    /// Unknown values are treated as `read`
+    pub fn get_scope(&self) -> ApiTokenScope {
+        ApiTokenScope::from_str(&self.scope).unwrap_or(ApiTokenScope::Read)
+    }
+
+    /// Not revoked and not expired at `now`
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires| expires > now)
+    }
+
+    pub async fn find_all(pool: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApiToken,
+            r#"SELECT id as "id!: Uuid",
+                      name as "name!",
+                      scope as "scope!",
+                      project_id as "project_id: Uuid",
+                      user_id as "user_id: Uuid",
+                      expires_at as "expires_at: DateTime<Utc>",
+                      last_used_at as "last_used_at: DateTime<Utc>",
+                      revoked_at as "revoked_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM api_tokens ORDER BY created_at DESC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_hash(
+        pool: &PgPool,
+        token_hash: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApiToken,
+            r#"SELECT id as "id!: Uuid",
+                      name as "name!",
+                      scope as "scope!",
+                      project_id as "project_id: Uuid",
+                      user_id as "user_id: Uuid",
+                      expires_at as "expires_at: DateTime<Utc>",
+                      last_used_at as "last_used_at: DateTime<Utc>",
+                      revoked_at as "revoked_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM api_tokens WHERE token_hash = $1"#,
+            token_hash
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        data: &CreateApiToken,
+        user_id: Option<Uuid>,
+        token_hash: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ApiToken,
+            r#"INSERT INTO api_tokens (id, name, token_hash, scope, project_id, user_id, expires_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         name as "name!",
+                         scope as "scope!",
+                         project_id as "project_id: Uuid",
+                         user_id as "user_id: Uuid",
+                         expires_at as "expires_at: DateTime<Utc>",
+                         last_used_at as "last_used_at: DateTime<Utc>",
+                         revoked_at as "revoked_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            data.name.trim(),
+            token_hash,
+            data.scope.as_str(),
+            data.project_id,
+            user_id,
+            expires_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn touch(pool: &PgPool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!("UPDATE api_tokens SET last_used_at = NOW() WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Revoke the token; it stays listed so its use can still be audited
+    pub async fn revoke(pool: &PgPool, id: Uuid) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ApiToken,
+            r#"UPDATE api_tokens SET revoked_at = COALESCE(revoked_at, NOW())
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         name as "name!",
+                         scope as "scope!",
+                         project_id as "project_id: Uuid",
+                         user_id as "user_id: Uuid",
+                         expires_at as "expires_at: DateTime<Utc>",
+                         last_used_at as "last_used_at: DateTime<Utc>",
+                         revoked_at as "revoked_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+}
@@ -59,7 +59,9 @@ pub struct AutomationExecution {
     pub status: String,
     pub trigger_context: Option<String>,
     pub result: Option<String>,
+    #[ts(type = "Date | null")]
     pub started_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date | null")]
     pub completed_at: Option<DateTime<Utc>>,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
@@ -78,7 +80,9 @@ pub struct AutomationExecutionWithDetails {
     pub status: String,
     pub trigger_context: Option<String>,
     pub result: Option<String>,
+    #[ts(type = "Date | null")]
     pub started_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date | null")]
     pub completed_at: Option<DateTime<Utc>>,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
@@ -161,6 +165,35 @@ impl AutomationExecution {
         .await
     }
 
+    /// Recent executions of a rule, newest first
+    pub async fn find_by_rule(
+        pool: &PgPool,
+        rule_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AutomationExecution,
+            r#"SELECT id as "id!: Uuid",
+                      rule_id as "rule_id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      workspace_id as "workspace_id: Uuid",
+                      status,
+                      trigger_context,
+                      result,
+                      started_at as "started_at: DateTime<Utc>",
+                      completed_at as "completed_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM automation_executions
+               WHERE rule_id = $1
+               ORDER BY created_at DESC
+               LIMIT $2"#,
+            rule_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find pending or running executions
     pub async fn find_active(pool: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
@@ -40,6 +40,12 @@ pub enum ActionType {
     MergePr,
     Webhook,
     Notify,
+    RunScript,
+    AssignAgent,
+    SetLabels,
+    MoveToColumn,
+    CreateFollowUpTask,
+    CreateSubtasksFromPlan,
 }
 
 impl ActionType {
@@ -51,6 +57,12 @@ impl ActionType {
             ActionType::MergePr => "merge_pr",
             ActionType::Webhook => "webhook",
             ActionType::Notify => "notify",
+            ActionType::RunScript => "run_script",
+            ActionType::AssignAgent => "assign_agent",
+            ActionType::SetLabels => "set_labels",
+            ActionType::MoveToColumn => "move_to_column",
+            ActionType::CreateFollowUpTask => "create_follow_up_task",
+            ActionType::CreateSubtasksFromPlan => "create_subtasks_from_plan",
         }
     }
 
@@ -62,6 +74,12 @@ impl ActionType {
             "merge_pr" => Some(ActionType::MergePr),
             "webhook" => Some(ActionType::Webhook),
             "notify" => Some(ActionType::Notify),
+            "run_script" => Some(ActionType::RunScript),
+            "assign_agent" => Some(ActionType::AssignAgent),
+            "set_labels" => Some(ActionType::SetLabels),
+            "move_to_column" => Some(ActionType::MoveToColumn),
+            "create_follow_up_task" => Some(ActionType::CreateFollowUpTask),
+            "create_subtasks_from_plan" => Some(ActionType::CreateSubtasksFromPlan),
             _ => None,
         }
     }
@@ -111,6 +129,51 @@ pub struct NotifyConfig {
     pub message_template: String,
 }
 
+/// Configuration for run_script action (runs in the task's workspace)
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct RunScriptConfig {
+    pub script: String,
+    /// Defaults to 300 seconds
+    pub timeout_seconds: Option<i32>,
+}
+
+/// Configuration for assign_agent action (starts the agent on the task)
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct AssignAgentConfig {
+    pub agent_id: Uuid,
+}
+
+/// Configuration for set_labels action
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SetLabelsConfig {
+    pub label_ids: Vec<Uuid>,
+    /// Remove labels not in the list (default: only add)
+    pub replace: Option<bool>,
+}
+
+/// Configuration for move_to_column action
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct MoveToColumnConfig {
+    pub column_id: Uuid,
+}
+
+/// Configuration for create_follow_up_task action
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct CreateFollowUpTaskConfig {
+    pub title_template: String,
+    pub description_template: Option<String>,
+    /// Column for the new task (defaults to the board's initial column)
+    pub column_id: Option<Uuid>,
+}
+
+/// Configuration for create_subtasks_from_plan action (explodes the task's latest
+/// iplan artifact into sub-tasks)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct CreateSubtasksFromPlanConfig {
+    /// Column for the sub-tasks (defaults to the board's initial column)
+    pub column_id: Option<Uuid>,
+}
+
 /// An automation rule that triggers on column entry/exit
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct AutomationRule {
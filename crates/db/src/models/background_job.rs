@@ -0,0 +1,199 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, PgPool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, TS, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundJobStatus {
+    Pending,
+    Running,
+    Completed,
+    /// Failed `max_attempts` times and won't be retried
+    Dead,
+}
+
+/// A unit of background work persisted so it survives restarts
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct BackgroundJob {
+    pub id: Uuid,
+    pub kind: String,
+    #[ts(type = "unknown")]
+    pub payload: Value,
+    pub status: BackgroundJobStatus,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub last_error: Option<String>,
+    /// Not claimed before this time; pushed back after each failure
+    #[ts(type = "Date")]
+    pub run_after: DateTime<Utc>,
+    #[ts(type = "Date | null")]
+    pub claimed_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date | null")]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BackgroundJob {
+    pub async fn create(
+        pool: &PgPool,
+        kind: &str,
+        payload: &Value,
+        max_attempts: i32,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            BackgroundJob,
+            r#"INSERT INTO background_jobs (id, kind, payload, max_attempts)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         kind as "kind!",
+                         payload as "payload!: Value",
+                         status as "status!: BackgroundJobStatus",
+                         attempts as "attempts!",
+                         max_attempts as "max_attempts!",
+                         last_error,
+                         run_after as "run_after!: DateTime<Utc>",
+                         claimed_at as "claimed_at: DateTime<Utc>",
+                         completed_at as "completed_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            kind,
+            payload,
+            max_attempts
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Claim the next due pending job, counting it as an attempt. Concurrent
+    /// workers never claim the same job.
+    pub async fn claim_next(pool: &PgPool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BackgroundJob,
+            r#"UPDATE background_jobs
+               SET status = 'running', attempts = attempts + 1, claimed_at = NOW(),
+                   updated_at = NOW()
+               WHERE id = (
+                   SELECT id FROM background_jobs
+                   WHERE status = 'pending' AND run_after <= NOW()
+                   ORDER BY run_after ASC
+                   LIMIT 1
+                   FOR UPDATE SKIP LOCKED
+               )
+               RETURNING id as "id!: Uuid",
+                         kind as "kind!",
+                         payload as "payload!: Value",
+                         status as "status!: BackgroundJobStatus",
+                         attempts as "attempts!",
+                         max_attempts as "max_attempts!",
+                         last_error,
+                         run_after as "run_after!: DateTime<Utc>",
+                         claimed_at as "claimed_at: DateTime<Utc>",
+                         completed_at as "completed_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn complete(pool: &PgPool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE background_jobs
+             SET status = 'completed', completed_at = NOW(), last_error = NULL,
+                 updated_at = NOW()
+             WHERE id = $1",
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record a failed attempt: the job is retried at `retry_at`, or marked dead
+    /// once it has used all its attempts
+    pub async fn fail(
+        pool: &PgPool,
+        id: Uuid,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> Result<BackgroundJobStatus, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"UPDATE background_jobs
+               SET status = CASE WHEN attempts >= max_attempts THEN 'dead' ELSE 'pending' END,
+                   last_error = $2, run_after = $3, claimed_at = NULL, updated_at = NOW()
+               WHERE id = $1
+               RETURNING status as "status!: BackgroundJobStatus""#,
+            id,
+            error,
+            retry_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Return jobs left running by a worker that went away (e.g. the server was
+    /// restarted mid-job) to the queue
+    pub async fn release_abandoned(pool: &PgPool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE background_jobs
+             SET status = 'pending', claimed_at = NULL, updated_at = NOW()
+             WHERE status = 'running'"
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Most recently created jobs, optionally only those with `status`
+    pub async fn find_recent(
+        pool: &PgPool,
+        status: Option<BackgroundJobStatus>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BackgroundJob,
+            r#"SELECT id as "id!: Uuid",
+                      kind as "kind!",
+                      payload as "payload!: Value",
+                      status as "status!: BackgroundJobStatus",
+                      attempts as "attempts!",
+                      max_attempts as "max_attempts!",
+                      last_error,
+                      run_after as "run_after!: DateTime<Utc>",
+                      claimed_at as "claimed_at: DateTime<Utc>",
+                      completed_at as "completed_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM background_jobs
+               WHERE $1::TEXT IS NULL OR status = $1
+               ORDER BY created_at DESC
+               LIMIT $2"#,
+            status as _,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Delete completed jobs finished before `before`
+    pub async fn delete_completed_before(
+        pool: &PgPool,
+        before: DateTime<Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM background_jobs WHERE status = 'completed' AND completed_at < $1",
+            before
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
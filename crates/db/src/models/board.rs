@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, PgPool};
+use sqlx::{Executor, FromRow, PgPool, Postgres};
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -106,7 +106,10 @@ impl Board {
     }
 
     /// Create a new board
-    pub async fn create(pool: &PgPool, data: &CreateBoard) -> Result<Self, sqlx::Error> {
+    pub async fn create(
+        executor: impl Executor<'_, Database = Postgres>,
+        data: &CreateBoard,
+    ) -> Result<Self, sqlx::Error> {
         let id = Uuid::new_v4();
 
         sqlx::query_as!(
@@ -127,7 +130,7 @@ impl Board {
             data.name,
             data.description
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
     }
 
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Column ordering rules of a board (defaults apply when no row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct BoardLayoutSettings {
+    pub board_id: Uuid,
+    /// Reordering must keep the initial column first and terminal columns last
+    pub strict_column_order: bool,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateBoardLayoutSettings {
+    pub strict_column_order: Option<bool>,
+}
+
+impl BoardLayoutSettings {
+    fn defaults(board_id: Uuid) -> Self {
+        Self {
+            board_id,
+            strict_column_order: false,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Column ordering rules of a board; reordering is unrestricted when none are stored
+    pub async fn for_board(pool: &PgPool, board_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            BoardLayoutSettings,
+            r#"SELECT board_id as "board_id!: Uuid",
+                      strict_column_order as "strict_column_order!",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM board_layout_settings
+               WHERE board_id = $1"#,
+            board_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(board_id)))
+    }
+
+    /// Turn strict column ordering on or off for a board
+    pub async fn update(
+        pool: &PgPool,
+        board_id: Uuid,
+        data: &UpdateBoardLayoutSettings,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::for_board(pool, board_id).await?;
+        let strict_column_order = data
+            .strict_column_order
+            .unwrap_or(current.strict_column_order);
+        sqlx::query_as!(
+            BoardLayoutSettings,
+            r#"INSERT INTO board_layout_settings (board_id, strict_column_order)
+               VALUES ($1, $2)
+               ON CONFLICT (board_id) DO UPDATE
+               SET strict_column_order = EXCLUDED.strict_column_order,
+                   updated_at = NOW()
+               RETURNING board_id as "board_id!: Uuid",
+                         strict_column_order as "strict_column_order!",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            board_id,
+            strict_column_order
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
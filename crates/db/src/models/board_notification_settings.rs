@@ -0,0 +1,290 @@
+use chrono::{DateTime, Duration, NaiveDateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// How often a board's non-urgent notifications are batched into a digest
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationDigestCadence {
+    /// Every notification is sent right away
+    Off,
+    Hourly,
+    Daily,
+}
+
+impl NotificationDigestCadence {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationDigestCadence::Off => "off",
+            NotificationDigestCadence::Hourly => "hourly",
+            NotificationDigestCadence::Daily => "daily",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "off" => Some(NotificationDigestCadence::Off),
+            "hourly" => Some(NotificationDigestCadence::Hourly),
+            "daily" => Some(NotificationDigestCadence::Daily),
+            _ => None,
+        }
+    }
+}
+
+/// Notification digest settings of a board (defaults apply when no row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct BoardNotificationSettings {
+    pub board_id: Uuid,
+    /// `off`, `hourly` or `daily` (see `NotificationDigestCadence`)
+    pub digest_cadence: String,
+    /// Hour of the day daily digests are sent, in the timezone of the board's projects
+    pub digest_hour: i32,
+    #[ts(type = "Date | null")]
+    pub last_digest_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateBoardNotificationSettings {
+    pub digest_cadence: Option<NotificationDigestCadence>,
+    pub digest_hour: Option<i32>,
+}
+
+impl BoardNotificationSettings {
+    fn defaults(board_id: Uuid) -> Self {
+        Self {
+            board_id,
+            digest_cadence: NotificationDigestCadence::Off.as_str().to_string(),
+            digest_hour: 9,
+            last_digest_at: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Unknown values are treated as `off`
+    pub fn cadence(&self) -> NotificationDigestCadence {
+        NotificationDigestCadence::from_str(&self.digest_cadence)
+            .unwrap_or(NotificationDigestCadence::Off)
+    }
+
+    /// Whether the digest should go out at `now`: an hour after the last one for hourly
+    /// digests, once a local day at or after the chosen local hour for daily ones. A
+    /// daily hour skipped by a DST change is caught up right after it, and a repeated
+    /// hour does not send a second digest.
+    pub fn digest_due(&self, now: DateTime<Utc>, clock: &DigestClock) -> bool {
+        match self.cadence() {
+            NotificationDigestCadence::Off => false,
+            NotificationDigestCadence::Hourly => self
+                .last_digest_at
+                .is_none_or(|sent| now - sent >= Duration::hours(1)),
+            NotificationDigestCadence::Daily => {
+                (clock.now.hour() as i32) >= self.digest_hour
+                    && clock
+                        .last_digest_at
+                        .is_none_or(|sent| sent.date() < clock.now.date())
+            }
+        }
+    }
+
+    /// Digest settings of a board; every notification is sent right away when none are
+    /// stored
+    pub async fn for_board(pool: &PgPool, board_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            BoardNotificationSettings,
+            r#"SELECT board_id as "board_id!: Uuid",
+                      digest_cadence as "digest_cadence!",
+                      digest_hour as "digest_hour!",
+                      last_digest_at as "last_digest_at: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM board_notification_settings
+               WHERE board_id = $1"#,
+            board_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(board_id)))
+    }
+
+    /// Settings of the project's board, if it batches notifications into digests
+    pub async fn batching_for_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BoardNotificationSettings,
+            r#"SELECT s.board_id as "board_id!: Uuid",
+                      s.digest_cadence as "digest_cadence!",
+                      s.digest_hour as "digest_hour!",
+                      s.last_digest_at as "last_digest_at: DateTime<Utc>",
+                      s.updated_at as "updated_at!: DateTime<Utc>"
+               FROM board_notification_settings s
+               JOIN projects p ON p.board_id = s.board_id
+               WHERE p.id = $1 AND s.digest_cadence != 'off'"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Boards that batch notifications into digests
+    pub async fn find_batching(pool: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BoardNotificationSettings,
+            r#"SELECT board_id as "board_id!: Uuid",
+                      digest_cadence as "digest_cadence!",
+                      digest_hour as "digest_hour!",
+                      last_digest_at as "last_digest_at: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM board_notification_settings
+               WHERE digest_cadence != 'off'"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Change how often a board's digests go out and the hour daily ones are sent
+    pub async fn update(
+        pool: &PgPool,
+        board_id: Uuid,
+        data: &UpdateBoardNotificationSettings,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::for_board(pool, board_id).await?;
+        let cadence = data
+            .digest_cadence
+            .map(|c| c.as_str().to_string())
+            .unwrap_or(current.digest_cadence);
+        sqlx::query_as!(
+            BoardNotificationSettings,
+            r#"INSERT INTO board_notification_settings (board_id, digest_cadence, digest_hour)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (board_id) DO UPDATE
+               SET digest_cadence = EXCLUDED.digest_cadence,
+                   digest_hour = EXCLUDED.digest_hour,
+                   updated_at = NOW()
+               RETURNING board_id as "board_id!: Uuid",
+                         digest_cadence as "digest_cadence!",
+                         digest_hour as "digest_hour!",
+                         last_digest_at as "last_digest_at: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            board_id,
+            cadence,
+            data.digest_hour.unwrap_or(current.digest_hour)
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn mark_digest_sent(
+        pool: &PgPool,
+        board_id: Uuid,
+        sent_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE board_notification_settings SET last_digest_at = $2 WHERE board_id = $1",
+            board_id,
+            sent_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Now and the last digest as wall-clock times in a board's timezone
+#[derive(Debug, Clone, Copy)]
+pub struct DigestClock {
+    pub now: NaiveDateTime,
+    pub last_digest_at: Option<NaiveDateTime>,
+}
+
+impl DigestClock {
+    /// Clock of a board evaluated in UTC
+    pub fn utc(now: DateTime<Utc>, last_digest_at: Option<DateTime<Utc>>) -> Self {
+        Self {
+            now: now.naive_utc(),
+            last_digest_at: last_digest_at.map(|sent| sent.naive_utc()),
+        }
+    }
+}
+
+/// A non-urgent notification waiting for its board's next digest
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct NotificationDigestItem {
+    pub id: Uuid,
+    pub board_id: Uuid,
+    pub channel_id: Uuid,
+    pub event: String,
+    pub project_id: Uuid,
+    pub task_id: Uuid,
+    /// Title and message as rendered for the channel when the event happened
+    pub title: String,
+    pub message: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateNotificationDigestItem {
+    pub board_id: Uuid,
+    pub channel_id: Uuid,
+    pub event: String,
+    pub project_id: Uuid,
+    pub task_id: Uuid,
+    pub title: String,
+    pub message: String,
+}
+
+impl NotificationDigestItem {
+    pub async fn create(
+        pool: &PgPool,
+        data: &CreateNotificationDigestItem,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO notification_digest_items
+                   (board_id, channel_id, event, project_id, task_id, title, message)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+            data.board_id,
+            data.channel_id,
+            data.event,
+            data.project_id,
+            data.task_id,
+            data.title,
+            data.message
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Queued items of a board, oldest first
+    pub async fn find_by_board(pool: &PgPool, board_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationDigestItem,
+            r#"SELECT id as "id!: Uuid",
+                      board_id as "board_id!: Uuid",
+                      channel_id as "channel_id!: Uuid",
+                      event as "event!",
+                      project_id as "project_id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      title as "title!",
+                      message as "message!",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM notification_digest_items
+               WHERE board_id = $1
+               ORDER BY created_at"#,
+            board_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn delete_many(pool: &PgPool, ids: &[Uuid]) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM notification_digest_items WHERE id = ANY($1)", ids)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{board::Board, kanban_column::KanbanColumn, task::TaskState};
+
+/// A public link granting read-only access to one board's shared view.
+/// Only the token's hash is stored; the link itself is shown once on creation.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct BoardShareLink {
+    pub id: Uuid,
+    pub board_id: Uuid,
+    #[ts(type = "Date | null")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Returned once when a board share link is created
+#[derive(Debug, Serialize, TS)]
+pub struct CreatedBoardShareLink {
+    pub link: BoardShareLink,
+    pub token: String,
+    /// Path of the shared view, relative to the server's origin
+    pub url: String,
+}
+
+impl BoardShareLink {
+    pub async fn find_by_board(pool: &PgPool, board_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BoardShareLink,
+            r#"SELECT id as "id!: Uuid",
+                      board_id as "board_id!: Uuid",
+                      last_used_at as "last_used_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM board_share_links
+               WHERE board_id = $1
+               ORDER BY created_at ASC"#,
+            board_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_hash(
+        pool: &PgPool,
+        token_hash: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BoardShareLink,
+            r#"SELECT id as "id!: Uuid",
+                      board_id as "board_id!: Uuid",
+                      last_used_at as "last_used_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM board_share_links WHERE token_hash = $1"#,
+            token_hash
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        board_id: Uuid,
+        token_hash: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            BoardShareLink,
+            r#"INSERT INTO board_share_links (id, board_id, token_hash)
+               VALUES ($1, $2, $3)
+               RETURNING id as "id!: Uuid",
+                         board_id as "board_id!: Uuid",
+                         last_used_at as "last_used_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            board_id,
+            token_hash
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn touch(pool: &PgPool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!("UPDATE board_share_links SET last_used_at = NOW() WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &PgPool, board_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM board_share_links WHERE id = $1 AND board_id = $2",
+            id,
+            board_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// A task as shown on a shared board: a summary only, without its description,
+/// attempts or logs
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct SharedBoardTask {
+    pub id: Uuid,
+    pub title: String,
+    pub project_name: String,
+    pub task_state: TaskState,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SharedBoardColumn {
+    pub id: Uuid,
+    pub name: String,
+    pub color: Option<String>,
+    pub is_terminal: bool,
+    pub tasks: Vec<SharedBoardTask>,
+}
+
+/// Read-only view of a board and the tasks of every project using it
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SharedBoardView {
+    pub board_id: Uuid,
+    pub board_name: String,
+    #[ts(type = "Date")]
+    pub generated_at: DateTime<Utc>,
+    pub columns: Vec<SharedBoardColumn>,
+}
+
+struct SharedTaskRow {
+    column_id: Uuid,
+    id: Uuid,
+    title: String,
+    project_name: String,
+    task_state: TaskState,
+    updated_at: DateTime<Utc>,
+}
+
+impl SharedBoardView {
+    pub async fn load(pool: &PgPool, board: &Board) -> Result<Self, sqlx::Error> {
+        let columns = KanbanColumn::find_by_board(pool, board.id).await?;
+        let rows = sqlx::query_as!(
+            SharedTaskRow,
+            r#"SELECT t.column_id as "column_id!: Uuid",
+                      t.id as "id!: Uuid",
+                      t.title as "title!",
+                      p.name as "project_name!",
+                      t.task_state as "task_state!: TaskState",
+                      t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks t
+               JOIN projects p ON p.id = t.project_id
+               JOIN kanban_columns c ON c.id = t.column_id
+               WHERE c.board_id = $1 AND t.archived_at IS NULL
+               ORDER BY t.updated_at DESC"#,
+            board.id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut tasks_by_column: HashMap<Uuid, Vec<SharedBoardTask>> = HashMap::new();
+        for row in rows {
+            tasks_by_column
+                .entry(row.column_id)
+                .or_default()
+                .push(SharedBoardTask {
+                    id: row.id,
+                    title: row.title,
+                    project_name: row.project_name,
+                    task_state: row.task_state,
+                    updated_at: row.updated_at,
+                });
+        }
+        let columns = columns
+            .into_iter()
+            .map(|column| SharedBoardColumn {
+                tasks: tasks_by_column.remove(&column.id).unwrap_or_default(),
+                id: column.id,
+                name: column.name,
+                color: column.color,
+                is_terminal: column.is_terminal,
+            })
+            .collect();
+
+        Ok(Self {
+            board_id: board.id,
+            board_name: board.name.clone(),
+            generated_at: Utc::now(),
+            columns,
+        })
+    }
+}
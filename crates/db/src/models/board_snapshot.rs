@@ -0,0 +1,84 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Number of tasks in a board column on a given day
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct BoardColumnSnapshot {
+    pub id: Uuid,
+    pub board_id: Uuid,
+    #[ts(type = "string")]
+    pub captured_on: NaiveDate,
+    pub column_id: Uuid,
+    pub column_name: String,
+    pub status: String,
+    pub is_terminal: bool,
+    pub task_count: i64,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl BoardColumnSnapshot {
+    /// Record today's column counts for every board, today being the date in the
+    /// board's timezone (see `ProjectTimezoneSettings::for_board`). Captures later in the
+    /// day replace earlier ones, so each day keeps the counts as of its last capture.
+    pub async fn capture_all(pool: &PgPool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"INSERT INTO board_snapshots
+                   (board_id, captured_on, column_id, column_name, status, is_terminal,
+                    task_count)
+               SELECT c.board_id,
+                      (NOW() AT TIME ZONE COALESCE(
+                          (SELECT z.timezone FROM project_timezone_settings z
+                           JOIN projects p ON p.id = z.project_id
+                           WHERE p.board_id = c.board_id
+                           ORDER BY p.created_at LIMIT 1),
+                          'UTC'
+                      ))::date,
+                      c.id, c.name, c.status, c.is_terminal, COUNT(t.id)
+               FROM kanban_columns c
+               LEFT JOIN tasks t ON t.column_id = c.id
+               WHERE NOT c.is_template
+               GROUP BY c.id
+               ON CONFLICT (board_id, captured_on, column_id) DO UPDATE
+               SET column_name = EXCLUDED.column_name,
+                   status = EXCLUDED.status,
+                   is_terminal = EXCLUDED.is_terminal,
+                   task_count = EXCLUDED.task_count"#
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Snapshots of a board taken on or after `since`, oldest day first, columns in
+    /// board order
+    pub async fn find_by_board(
+        pool: &PgPool,
+        board_id: Uuid,
+        since: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BoardColumnSnapshot,
+            r#"SELECT s.id as "id!: Uuid",
+                      s.board_id as "board_id!: Uuid",
+                      s.captured_on as "captured_on!: NaiveDate",
+                      s.column_id as "column_id!: Uuid",
+                      s.column_name as "column_name!",
+                      s.status as "status!",
+                      s.is_terminal as "is_terminal!",
+                      s.task_count as "task_count!",
+                      s.created_at as "created_at!: DateTime<Utc>"
+               FROM board_snapshots s
+               LEFT JOIN kanban_columns c ON c.id = s.column_id
+               WHERE s.board_id = $1 AND s.captured_on >= $2
+               ORDER BY s.captured_on, c.position"#,
+            board_id,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
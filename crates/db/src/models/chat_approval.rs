@@ -0,0 +1,232 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// What an approval request posted to a chat channel asks about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatApprovalSubject {
+    /// A plan waiting for review
+    PlanReview(Uuid),
+    /// A transition that only moves the task once someone confirms it, asked for when
+    /// the execution's run finished
+    Transition {
+        transition_id: Uuid,
+        execution_process_id: Uuid,
+    },
+    /// A tool call the execution's agent waits to be allowed to make
+    ToolApproval {
+        approval_id: Uuid,
+        execution_process_id: Uuid,
+    },
+}
+
+impl ChatApprovalSubject {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ChatApprovalSubject::PlanReview(_) => "plan_review",
+            ChatApprovalSubject::Transition { .. } => "transition",
+            ChatApprovalSubject::ToolApproval { .. } => "tool_approval",
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        match self {
+            ChatApprovalSubject::PlanReview(id) => *id,
+            ChatApprovalSubject::Transition { transition_id, .. } => *transition_id,
+            ChatApprovalSubject::ToolApproval { approval_id, .. } => *approval_id,
+        }
+    }
+
+    /// The run the request belongs to; plan reviews outlive their run
+    pub fn execution_process_id(&self) -> Option<Uuid> {
+        match self {
+            ChatApprovalSubject::PlanReview(_) => None,
+            ChatApprovalSubject::Transition {
+                execution_process_id,
+                ..
+            }
+            | ChatApprovalSubject::ToolApproval {
+                execution_process_id,
+                ..
+            } => Some(*execution_process_id),
+        }
+    }
+
+    pub fn from_parts(kind: &str, id: Uuid, execution_process_id: Option<Uuid>) -> Option<Self> {
+        match (kind, execution_process_id) {
+            ("plan_review", _) => Some(ChatApprovalSubject::PlanReview(id)),
+            ("transition", Some(execution_process_id)) => Some(ChatApprovalSubject::Transition {
+                transition_id: id,
+                execution_process_id,
+            }),
+            ("tool_approval", Some(execution_process_id)) => {
+                Some(ChatApprovalSubject::ToolApproval {
+                    approval_id: id,
+                    execution_process_id,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatApprovalStatus {
+    Pending,
+    Approved,
+    Rejected,
+    /// The subject was settled some other way before anyone reacted
+    Expired,
+}
+
+impl ChatApprovalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChatApprovalStatus::Pending => "pending",
+            ChatApprovalStatus::Approved => "approved",
+            ChatApprovalStatus::Rejected => "rejected",
+            ChatApprovalStatus::Expired => "expired",
+        }
+    }
+}
+
+/// An approval request posted to a Slack or Matrix channel
+#[derive(Debug, Clone, FromRow)]
+pub struct ChatApprovalMessage {
+    pub id: Uuid,
+    pub channel_id: Uuid,
+    pub task_id: Uuid,
+    pub subject_kind: String,
+    pub subject_id: Uuid,
+    /// Run a transition or tool approval request belongs to
+    pub execution_process_id: Option<Uuid>,
+    /// Slack message ts or Matrix event id
+    pub message_ref: String,
+    pub status: String,
+    pub decided_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+impl ChatApprovalMessage {
+    pub fn subject(&self) -> Option<ChatApprovalSubject> {
+        ChatApprovalSubject::from_parts(
+            &self.subject_kind,
+            self.subject_id,
+            self.execution_process_id,
+        )
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        channel_id: Uuid,
+        task_id: Uuid,
+        subject: ChatApprovalSubject,
+        message_ref: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ChatApprovalMessage,
+            r#"INSERT INTO chat_approval_messages
+                   (channel_id, task_id, subject_kind, subject_id, execution_process_id,
+                    message_ref)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         channel_id as "channel_id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         subject_kind as "subject_kind!",
+                         subject_id as "subject_id!: Uuid",
+                         execution_process_id as "execution_process_id: Uuid",
+                         message_ref as "message_ref!",
+                         status as "status!",
+                         decided_by as "decided_by: Uuid",
+                         created_at as "created_at!: DateTime<Utc>",
+                         decided_at as "decided_at: DateTime<Utc>""#,
+            channel_id,
+            task_id,
+            subject.kind(),
+            subject.id(),
+            subject.execution_process_id(),
+            message_ref
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Requests still waiting for a reaction, oldest first
+    pub async fn find_pending(pool: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ChatApprovalMessage,
+            r#"SELECT id as "id!: Uuid",
+                      channel_id as "channel_id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      subject_kind as "subject_kind!",
+                      subject_id as "subject_id!: Uuid",
+                      execution_process_id as "execution_process_id: Uuid",
+                      message_ref as "message_ref!",
+                      status as "status!",
+                      decided_by as "decided_by: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      decided_at as "decided_at: DateTime<Utc>"
+               FROM chat_approval_messages
+               WHERE status = 'pending'
+               ORDER BY created_at"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Settle a pending request; None when it was already settled
+    pub async fn decide(
+        pool: &PgPool,
+        id: Uuid,
+        status: ChatApprovalStatus,
+        decided_by: Option<Uuid>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ChatApprovalMessage,
+            r#"UPDATE chat_approval_messages
+               SET status = $2, decided_by = $3, decided_at = NOW()
+               WHERE id = $1 AND status = 'pending'
+               RETURNING id as "id!: Uuid",
+                         channel_id as "channel_id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         subject_kind as "subject_kind!",
+                         subject_id as "subject_id!: Uuid",
+                         execution_process_id as "execution_process_id: Uuid",
+                         message_ref as "message_ref!",
+                         status as "status!",
+                         decided_by as "decided_by: Uuid",
+                         created_at as "created_at!: DateTime<Utc>",
+                         decided_at as "decided_at: DateTime<Utc>""#,
+            id,
+            status.as_str(),
+            decided_by
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Settle every other pending request about the task's subject, e.g. once one
+    /// channel's reaction has decided it. Transitions are shared by tasks and asked
+    /// about again by later runs, so requests are matched on the task and run as well.
+    pub async fn expire_subject(
+        pool: &PgPool,
+        task_id: Uuid,
+        subject: ChatApprovalSubject,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE chat_approval_messages
+             SET status = 'expired', decided_at = NOW()
+             WHERE task_id = $1 AND subject_kind = $2 AND subject_id = $3
+               AND execution_process_id IS NOT DISTINCT FROM $4
+               AND status = 'pending'",
+            task_id,
+            subject.kind(),
+            subject.id(),
+            subject.execution_process_id()
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
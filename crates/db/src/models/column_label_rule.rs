@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// When a column label rule fires
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, TS, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum LabelRuleTrigger {
+    /// The task moved into the column
+    Enter,
+    /// The task moved out of the column
+    Exit,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, TS, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum LabelRuleAction {
+    Add,
+    Remove,
+}
+
+/// Add or remove a label when a task enters or leaves a column
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ColumnLabelRule {
+    pub id: Uuid,
+    pub column_id: Uuid,
+    pub label_id: Uuid,
+    pub trigger: LabelRuleTrigger,
+    pub action: LabelRuleAction,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateColumnLabelRule {
+    pub label_id: Uuid,
+    pub trigger: LabelRuleTrigger,
+    pub action: LabelRuleAction,
+}
+
+impl ColumnLabelRule {
+    pub async fn find_by_column(pool: &PgPool, column_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ColumnLabelRule,
+            r#"SELECT id as "id!: Uuid",
+                      column_id as "column_id!: Uuid",
+                      label_id as "label_id!: Uuid",
+                      trigger as "trigger!: LabelRuleTrigger",
+                      action as "action!: LabelRuleAction",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM column_label_rules
+               WHERE column_id = $1
+               ORDER BY trigger, created_at"#,
+            column_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Rules of a column that fire on `trigger`, oldest first
+    pub async fn find_for_trigger(
+        pool: &PgPool,
+        column_id: Uuid,
+        trigger: LabelRuleTrigger,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ColumnLabelRule,
+            r#"SELECT id as "id!: Uuid",
+                      column_id as "column_id!: Uuid",
+                      label_id as "label_id!: Uuid",
+                      trigger as "trigger!: LabelRuleTrigger",
+                      action as "action!: LabelRuleAction",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM column_label_rules
+               WHERE column_id = $1 AND trigger = $2
+               ORDER BY created_at"#,
+            column_id,
+            trigger as _
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Create a rule; a rule for the same label and trigger is replaced
+    pub async fn create(
+        pool: &PgPool,
+        column_id: Uuid,
+        data: &CreateColumnLabelRule,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ColumnLabelRule,
+            r#"INSERT INTO column_label_rules (column_id, label_id, trigger, action)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (column_id, label_id, trigger) DO UPDATE
+               SET action = EXCLUDED.action
+               RETURNING id as "id!: Uuid",
+                         column_id as "column_id!: Uuid",
+                         label_id as "label_id!: Uuid",
+                         trigger as "trigger!: LabelRuleTrigger",
+                         action as "action!: LabelRuleAction",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            column_id,
+            data.label_id,
+            data.trigger as _,
+            data.action as _
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Delete a rule; returns false if it doesn't exist
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM column_label_rules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
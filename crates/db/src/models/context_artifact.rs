@@ -5,6 +5,8 @@ use tracing;
 use ts_rs::TS;
 use uuid::Uuid;
 
+use super::{context_artifact_review::ContextArtifactReview, context_budget::ContextBudget};
+
 /// Type of context artifact
 #[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -88,6 +90,16 @@ impl ArtifactType {
         }
     }
 
+    /// Whether agent-written artifacts of this type wait for a person's approval
+    /// before reaching prompts. Plans, briefs and changelog entries drive the
+    /// planning pipeline rather than record knowledge, so they go through unreviewed.
+    pub fn needs_review(&self) -> bool {
+        !matches!(
+            self,
+            ArtifactType::IPlan | ArtifactType::Brief | ArtifactType::ChangelogEntry
+        )
+    }
+
     /// Priority ordering for context budget allocation (lower = higher priority)
     pub fn priority(&self) -> i32 {
         match self {
@@ -113,6 +125,97 @@ pub struct ContextPreviewStats {
     pub artifacts_total: i32,
 }
 
+/// Assembled context before it's wrapped for a caller
+struct AssembledContext {
+    context: String,
+    tokens_used: i32,
+    artifacts_included: i32,
+}
+
+/// Candidate for a budgeted context section, in priority order
+struct BudgetEntry<'a> {
+    heading: String,
+    content: &'a str,
+    tokens: i32,
+}
+
+impl<'a> BudgetEntry<'a> {
+    fn from_artifacts(artifacts: &'a [ContextArtifact]) -> Vec<Self> {
+        artifacts
+            .iter()
+            .map(|artifact| Self {
+                heading: artifact.title.clone(),
+                content: &artifact.content,
+                tokens: artifact.token_estimate,
+            })
+            .collect()
+    }
+}
+
+/// One scope's section of the assembled context
+struct FilledSection {
+    text: Option<String>,
+    tokens_used: i32,
+    included: i32,
+}
+
+/// Add entries until the next one would exceed `cap` tokens; the rest are
+/// trimmed and logged so budget overruns are visible.
+fn fill_section(scope: &str, header: &str, entries: &[BudgetEntry<'_>], cap: i32) -> FilledSection {
+    let mut section = String::from(header);
+    let mut tokens_used = 0;
+    let mut included = 0;
+
+    for entry in entries {
+        if tokens_used + entry.tokens > cap {
+            break;
+        }
+        section.push_str(&format!("## {}\n\n", entry.heading));
+        section.push_str(entry.content);
+        section.push_str("\n\n");
+        tokens_used += entry.tokens;
+        included += 1;
+    }
+
+    if entries.is_empty() {
+        tracing::info!(target: "vibe_kanban::context", "  ├─ {}: none", scope);
+    } else {
+        tracing::info!(
+            target: "vibe_kanban::context",
+            "  ├─ {}: {}/{} artifacts, {}/{} tokens",
+            scope,
+            included,
+            entries.len(),
+            tokens_used,
+            cap
+        );
+    }
+
+    let trimmed = &entries[included..];
+    if !trimmed.is_empty() {
+        let trimmed_tokens: i32 = trimmed.iter().map(|entry| entry.tokens).sum();
+        let titles = trimmed
+            .iter()
+            .map(|entry| format!("{} (~{} tokens)", entry.heading, entry.tokens))
+            .collect::<Vec<_>>()
+            .join(", ");
+        tracing::info!(
+            target: "vibe_kanban::context",
+            "  │  └─ Trimmed {} from {} ({} tokens): {}",
+            trimmed.len(),
+            scope,
+            trimmed_tokens,
+            titles
+        );
+    }
+
+    FilledSection {
+        text: (included > 0).then_some(section),
+        tokens_used,
+        included: included as i32,
+    }
+}
+
 /// A context artifact stores learned knowledge from agent work
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct ContextArtifact {
@@ -515,10 +618,13 @@ impl ContextArtifact {
         project_id: Uuid,
         paths: &[String],
     ) -> Result<String, sqlx::Error> {
+        let excluded = ContextArtifactReview::excluded_from_prompts(pool, project_id).await?;
         let mut context = String::new();
 
         for path in paths {
-            if let Some(memory) = Self::find_module_memory(pool, project_id, path).await? {
+            if let Some(memory) = Self::find_module_memory(pool, project_id, path).await?
+                && !excluded.contains(&memory.id)
+            {
                 context.push_str(&format!("## Module: {}\n\n", path));
                 context.push_str(&memory.content);
                 context.push_str("\n\n");
@@ -528,12 +634,10 @@ impl ContextArtifact {
         Ok(context)
     }
 
-    /// Default token budget for context injection
-    pub const DEFAULT_TOKEN_BUDGET: i32 = 8000;
-
     /// Build full context for agent prompting with token budget (ADR-007).
     ///
-    /// Budget allocation: Global 50%, Task 30%, Path 20%.
+    /// The budget and its Global/Task/Path split come from `ContextBudget::resolve`
+    /// (column override > project override > 8000 tokens at 50/30/20).
     /// Unused budget rolls over to the next scope.
     /// Within each scope, artifacts are prioritized by type (ADR > Pattern > ...) then recency.
     /// Only the latest version per chain_id is included.
@@ -541,157 +645,12 @@ impl ContextArtifact {
         pool: &PgPool,
         project_id: Uuid,
         task_id: Option<Uuid>,
+        column_id: Option<Uuid>,
         paths: &[String],
     ) -> Result<String, sqlx::Error> {
-        let total_budget = Self::DEFAULT_TOKEN_BUDGET;
-
-        tracing::info!(
-            target: "vibe_kanban::context",
-            "📚 Building budgeted context for project {} (task: {:?}, paths: {}, budget: {} tokens)",
-            project_id,
-            task_id,
-            paths.len(),
-            total_budget
-        );
-
-        let mut context_parts = Vec::new();
-        let mut remaining_budget = total_budget;
-
-        // 1. Global artifacts — 50% of budget
-        let global_budget = total_budget / 2;
-        let global_artifacts = Self::find_global_artifacts(pool, project_id).await?;
-        let global_artifacts = Self::dedup_by_chain(global_artifacts);
-        let global_artifacts = Self::sort_by_priority(global_artifacts);
-
-        if !global_artifacts.is_empty() {
-            let mut section = String::from("# Project Context\n\n");
-            let mut included = 0;
-            let mut tokens_used = 0;
-
-            for artifact in &global_artifacts {
-                if tokens_used + artifact.token_estimate > global_budget.max(remaining_budget) {
-                    break;
-                }
-                section.push_str(&format!("## {}\n\n", artifact.title));
-                section.push_str(&artifact.content);
-                section.push_str("\n\n");
-                tokens_used += artifact.token_estimate;
-                included += 1;
-            }
-
-            if included > 0 {
-                tracing::info!(
-                    target: "vibe_kanban::context",
-                    "  ├─ Global: {}/{} artifacts, {} tokens",
-                    included,
-                    global_artifacts.len(),
-                    tokens_used
-                );
-                context_parts.push(section);
-                remaining_budget -= tokens_used;
-            }
-        } else {
-            tracing::info!(
-                target: "vibe_kanban::context",
-                "  ├─ Global artifacts: none"
-            );
-            // Unused global budget rolls over
-        }
-
-        // 2. Task-specific artifacts — 30% of budget (+ rollover)
-        let task_budget = (total_budget * 3) / 10;
-        if let Some(tid) = task_id {
-            let task_artifacts = Self::find_task_artifacts(pool, project_id, tid).await?;
-            let task_artifacts = Self::dedup_by_chain(task_artifacts);
-            let task_artifacts = Self::sort_by_priority(task_artifacts);
-
-            if !task_artifacts.is_empty() {
-                let mut section = String::from("# Task Context\n\n");
-                let mut included = 0;
-                let mut tokens_used = 0;
-                let effective_budget = task_budget.max(remaining_budget.min(task_budget + (total_budget / 2 - (total_budget - remaining_budget)).max(0)));
-
-                for artifact in &task_artifacts {
-                    if tokens_used + artifact.token_estimate > remaining_budget {
-                        break;
-                    }
-                    if tokens_used + artifact.token_estimate > effective_budget && included > 0 {
-                        break;
-                    }
-                    section.push_str(&format!("## {}\n\n", artifact.title));
-                    section.push_str(&artifact.content);
-                    section.push_str("\n\n");
-                    tokens_used += artifact.token_estimate;
-                    included += 1;
-                }
-
-                if included > 0 {
-                    tracing::info!(
-                        target: "vibe_kanban::context",
-                        "  ├─ Task: {}/{} artifacts, {} tokens",
-                        included,
-                        task_artifacts.len(),
-                        tokens_used
-                    );
-                    context_parts.push(section);
-                    remaining_budget -= tokens_used;
-                }
-            } else {
-                tracing::info!(
-                    target: "vibe_kanban::context",
-                    "  ├─ Task artifacts: none for task {}",
-                    tid
-                );
-            }
-        }
-
-        // 3. Path-based artifacts — 20% of budget (+ rollover from above)
-        if !paths.is_empty() && remaining_budget > 0 {
-            let mut section = String::from("# Module Context\n\n");
-            let mut included = 0;
-            let mut tokens_used = 0;
-
-            for path in paths {
-                if let Some(memory) = Self::find_module_memory(pool, project_id, path).await? {
-                    if tokens_used + memory.token_estimate > remaining_budget {
-                        break;
-                    }
-                    section.push_str(&format!("## Module: {}\n\n", path));
-                    section.push_str(&memory.content);
-                    section.push_str("\n\n");
-                    tokens_used += memory.token_estimate;
-                    included += 1;
-                }
-            }
-
-            if included > 0 {
-                tracing::info!(
-                    target: "vibe_kanban::context",
-                    "  ├─ Path: {} modules, {} tokens",
-                    included,
-                    tokens_used
-                );
-                context_parts.push(section);
-                remaining_budget -= tokens_used;
-            } else {
-                tracing::info!(
-                    target: "vibe_kanban::context",
-                    "  ├─ Path artifacts: none for {} paths",
-                    paths.len()
-                );
-            }
-        }
-
-        let tokens_used = total_budget - remaining_budget;
-        tracing::info!(
-            target: "vibe_kanban::context",
-            "  └─ Total: {} sections, {}/{} tokens used",
-            context_parts.len(),
-            tokens_used,
-            total_budget
-        );
-
-        Ok(context_parts.join("\n---\n\n"))
+        let budget = ContextBudget::resolve(pool, project_id, column_id).await?;
+        let assembled = Self::assemble_context(pool, project_id, task_id, paths, &budget).await?;
+        Ok(assembled.context)
     }
 
     /// Build full context and return stats alongside the context string.
@@ -699,115 +658,128 @@ impl ContextArtifact {
         pool: &PgPool,
         project_id: Uuid,
         task_id: Option<Uuid>,
+        column_id: Option<Uuid>,
         paths: &[String],
     ) -> Result<ContextPreviewStats, sqlx::Error> {
         // Count total artifacts for the project
-        let all_artifacts = Self::find_by_project(pool, project_id).await?;
-        let artifacts_total = all_artifacts.len() as i32;
+        let artifacts_total = Self::find_by_project(pool, project_id).await?.len() as i32;
 
-        // Build context normally
-        let total_budget = Self::DEFAULT_TOKEN_BUDGET;
+        let budget = ContextBudget::resolve(pool, project_id, column_id).await?;
+        let assembled = Self::assemble_context(pool, project_id, task_id, paths, &budget).await?;
 
-        let mut context_parts = Vec::new();
-        let mut remaining_budget = total_budget;
-        let mut artifacts_included = 0i32;
+        Ok(ContextPreviewStats {
+            context: assembled.context,
+            tokens_used: assembled.tokens_used,
+            token_budget: budget.token_budget,
+            artifacts_included: assembled.artifacts_included,
+            artifacts_total,
+        })
+    }
 
-        // 1. Global artifacts — 50% of budget
-        let global_budget = total_budget / 2;
-        let global_artifacts = Self::find_global_artifacts(pool, project_id).await?;
-        let global_artifacts = Self::dedup_by_chain(global_artifacts);
-        let global_artifacts = Self::sort_by_priority(global_artifacts);
+    async fn assemble_context(
+        pool: &PgPool,
+        project_id: Uuid,
+        task_id: Option<Uuid>,
+        paths: &[String],
+        budget: &ContextBudget,
+    ) -> Result<AssembledContext, sqlx::Error> {
+        let total_budget = budget.token_budget;
 
-        if !global_artifacts.is_empty() {
-            let mut section = String::from("# Project Context\n\n");
-            let mut tokens_used = 0;
+        tracing::info!(
+            target: "vibe_kanban::context",
+            "📚 Building budgeted context for project {} (task: {:?}, paths: {}, budget: {} tokens, split {}/{}/{})",
+            project_id,
+            task_id,
+            paths.len(),
+            total_budget,
+            budget.global_weight,
+            budget.task_weight,
+            budget.path_weight
+        );
 
-            for artifact in &global_artifacts {
-                if tokens_used + artifact.token_estimate > global_budget.max(remaining_budget) {
-                    break;
-                }
-                section.push_str(&format!("## {}\n\n", artifact.title));
-                section.push_str(&artifact.content);
-                section.push_str("\n\n");
-                tokens_used += artifact.token_estimate;
-                artifacts_included += 1;
-            }
+        let mut sections = Vec::new();
 
-            if artifacts_included > 0 {
-                context_parts.push(section);
-                remaining_budget -= tokens_used;
-            }
-        }
+        // Only approved, unexpired artifacts reach agents. Filtering before the chain
+        // dedup falls back to a chain's latest approved version.
+        let excluded = ContextArtifactReview::excluded_from_prompts(pool, project_id).await?;
+        let usable = |artifacts: Vec<Self>| -> Vec<Self> {
+            artifacts
+                .into_iter()
+                .filter(|artifact| !excluded.contains(&artifact.id))
+                .collect()
+        };
 
-        // 2. Task-specific artifacts — 30% of budget (+ rollover)
-        let task_budget = (total_budget * 3) / 10;
-        if let Some(tid) = task_id {
-            let task_artifacts = Self::find_task_artifacts(pool, project_id, tid).await?;
-            let task_artifacts = Self::dedup_by_chain(task_artifacts);
-            let task_artifacts = Self::sort_by_priority(task_artifacts);
-
-            if !task_artifacts.is_empty() {
-                let mut section = String::from("# Task Context\n\n");
-                let mut included = 0;
-                let mut tokens_used = 0;
-                let effective_budget = task_budget.max(remaining_budget.min(task_budget + (total_budget / 2 - (total_budget - remaining_budget)).max(0)));
-
-                for artifact in &task_artifacts {
-                    if tokens_used + artifact.token_estimate > remaining_budget {
-                        break;
-                    }
-                    if tokens_used + artifact.token_estimate > effective_budget && included > 0 {
-                        break;
-                    }
-                    section.push_str(&format!("## {}\n\n", artifact.title));
-                    section.push_str(&artifact.content);
-                    section.push_str("\n\n");
-                    tokens_used += artifact.token_estimate;
-                    included += 1;
-                }
+        // 1. Global artifacts
+        let global_artifacts = usable(Self::find_global_artifacts(pool, project_id).await?);
+        let global_artifacts = Self::sort_by_priority(Self::dedup_by_chain(global_artifacts));
+        let global_cap = budget.share(budget.global_weight);
+        let global = fill_section(
+            "Global",
+            "# Project Context\n\n",
+            &BudgetEntry::from_artifacts(&global_artifacts),
+            global_cap,
+        );
+        let mut tokens_used = global.tokens_used;
+        let mut artifacts_included = global.included;
+        sections.extend(global.text);
 
-                if included > 0 {
-                    context_parts.push(section);
-                    remaining_budget -= tokens_used;
-                    artifacts_included += included;
-                }
-            }
+        // 2. Task-specific artifacts (+ unused global budget)
+        if let Some(tid) = task_id {
+            let task_artifacts = usable(Self::find_task_artifacts(pool, project_id, tid).await?);
+            let task_artifacts = Self::sort_by_priority(Self::dedup_by_chain(task_artifacts));
+            let task_cap = budget.share(budget.task_weight) + (global_cap - tokens_used);
+            let task = fill_section(
+                "Task",
+                "# Task Context\n\n",
+                &BudgetEntry::from_artifacts(&task_artifacts),
+                task_cap,
+            );
+            tokens_used += task.tokens_used;
+            artifacts_included += task.included;
+            sections.extend(task.text);
         }
 
-        // 3. Path-based artifacts — 20% of budget (+ rollover)
-        if !paths.is_empty() && remaining_budget > 0 {
-            let mut section = String::from("# Module Context\n\n");
-            let mut included = 0;
-            let mut tokens_used = 0;
-
+        // 3. Path-based artifacts (+ whatever is left)
+        if !paths.is_empty() {
+            let mut memories = Vec::new();
             for path in paths {
-                if let Some(memory) = Self::find_module_memory(pool, project_id, path).await? {
-                    if tokens_used + memory.token_estimate > remaining_budget {
-                        break;
-                    }
-                    section.push_str(&format!("## Module: {}\n\n", path));
-                    section.push_str(&memory.content);
-                    section.push_str("\n\n");
-                    tokens_used += memory.token_estimate;
-                    included += 1;
+                if let Some(memory) = Self::find_module_memory(pool, project_id, path).await?
+                    && !excluded.contains(&memory.id)
+                {
+                    memories.push((path, memory));
                 }
             }
-
-            if included > 0 {
-                context_parts.push(section);
-                remaining_budget -= tokens_used;
-                artifacts_included += included;
-            }
+            let entries: Vec<BudgetEntry<'_>> = memories
+                .iter()
+                .map(|(path, memory)| BudgetEntry {
+                    heading: format!("Module: {}", path),
+                    content: &memory.content,
+                    tokens: memory.token_estimate,
+                })
+                .collect();
+            let path = fill_section(
+                "Path",
+                "# Module Context\n\n",
+                &entries,
+                total_budget - tokens_used,
+            );
+            tokens_used += path.tokens_used;
+            artifacts_included += path.included;
+            sections.extend(path.text);
         }
 
-        let tokens_used = total_budget - remaining_budget;
+        tracing::info!(
+            target: "vibe_kanban::context",
+            "  └─ Total: {} sections, {}/{} tokens used",
+            sections.len(),
+            tokens_used,
+            total_budget
+        );
 
-        Ok(ContextPreviewStats {
-            context: context_parts.join("\n---\n\n"),
+        Ok(AssembledContext {
+            context: sections.join("\n---\n\n"),
             tokens_used,
-            token_budget: total_budget,
             artifacts_included,
-            artifacts_total,
         })
     }
 
@@ -967,4 +939,62 @@ impl ContextArtifact {
         .fetch_all(pool)
         .await
     }
+
+    /// The most recent implementation plan written for a task
+    pub async fn find_latest_plan_for_task(
+        pool: &PgPool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ContextArtifact,
+            r#"SELECT
+                id as "id!: Uuid",
+                project_id as "project_id!: Uuid",
+                artifact_type,
+                path,
+                title,
+                content,
+                metadata,
+                source_task_id as "source_task_id: Uuid",
+                source_commit_hash,
+                scope,
+                file_path,
+                supersedes_id as "supersedes_id: Uuid",
+                chain_id as "chain_id: Uuid",
+                version as "version!: i32",
+                token_estimate as "token_estimate!: i32",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM context_artifacts
+               WHERE artifact_type = 'iplan' AND source_task_id = $1
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Whether an artifact of this type was written for the task, optionally only
+    /// counting artifacts created (or revised) at or after `since`
+    pub async fn exists_for_task(
+        pool: &PgPool,
+        task_id: Uuid,
+        artifact_type: &ArtifactType,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM context_artifacts
+                   WHERE source_task_id = $1
+                     AND artifact_type = $2
+                     AND ($3::timestamptz IS NULL OR updated_at >= $3)
+               ) as "exists!""#,
+            task_id,
+            artifact_type.as_str(),
+            since
+        )
+        .fetch_one(pool)
+        .await
+    }
 }
@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, TS, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactReviewStatus {
+    /// Written by an agent and waiting for a person; not used in prompts
+    Draft,
+    /// Used in agent prompts until it expires
+    Approved,
+    /// Kept for reference only
+    Archived,
+}
+
+impl ArtifactReviewStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArtifactReviewStatus::Draft => "draft",
+            ArtifactReviewStatus::Approved => "approved",
+            ArtifactReviewStatus::Archived => "archived",
+        }
+    }
+
+    /// Draft → approved → archived; a draft can also be archived outright, and
+    /// approving an approved artifact again renews its expiry
+    pub fn can_become(&self, next: ArtifactReviewStatus) -> bool {
+        use ArtifactReviewStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Approved) | (Draft, Archived) | (Approved, Approved) | (Approved, Archived)
+        )
+    }
+}
+
+/// Review state of a context artifact. Artifacts without a review row were written by
+/// a person or before reviews existed, and count as approved.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ContextArtifactReview {
+    pub artifact_id: Uuid,
+    pub status: ArtifactReviewStatus,
+    pub reviewer: Option<String>,
+    #[ts(type = "Date | null")]
+    pub reviewed_at: Option<DateTime<Utc>>,
+    /// When the artifact drops out of agent prompts, from its type's TTL; none if it
+    /// never expires
+    #[ts(type = "Date | null")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct ReviewContextArtifact {
+    pub status: ArtifactReviewStatus,
+    /// Who reviewed the artifact
+    pub reviewer: Option<String>,
+}
+
+impl ContextArtifactReview {
+    /// An artifact with its review. The type's TTL runs from the artifact's approval, or
+    /// from its last update when it was never reviewed.
+    pub async fn for_artifact(pool: &PgPool, artifact_id: Uuid) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ContextArtifactReview,
+            r#"SELECT a.id as "artifact_id!: Uuid",
+                      COALESCE(r.status, 'approved') as "status!: ArtifactReviewStatus",
+                      r.reviewer,
+                      r.reviewed_at as "reviewed_at: DateTime<Utc>",
+                      COALESCE(r.reviewed_at, a.updated_at) + make_interval(days => t.ttl_days)
+                          as "expires_at: DateTime<Utc>"
+               FROM context_artifacts a
+               LEFT JOIN context_artifact_reviews r ON r.artifact_id = a.id
+               LEFT JOIN context_artifact_ttls t
+                      ON t.project_id = a.project_id AND t.artifact_type = a.artifact_type
+               WHERE a.id = $1"#,
+            artifact_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Reviews of all of a project's artifacts
+    pub async fn find_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ContextArtifactReview,
+            r#"SELECT a.id as "artifact_id!: Uuid",
+                      COALESCE(r.status, 'approved') as "status!: ArtifactReviewStatus",
+                      r.reviewer,
+                      r.reviewed_at as "reviewed_at: DateTime<Utc>",
+                      COALESCE(r.reviewed_at, a.updated_at) + make_interval(days => t.ttl_days)
+                          as "expires_at: DateTime<Utc>"
+               FROM context_artifacts a
+               LEFT JOIN context_artifact_reviews r ON r.artifact_id = a.id
+               LEFT JOIN context_artifact_ttls t
+                      ON t.project_id = a.project_id AND t.artifact_type = a.artifact_type
+               WHERE a.project_id = $1"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Artifacts of a project that must stay out of agent prompts: those not approved
+    /// and those past their expiry
+    pub async fn excluded_from_prompts(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<HashSet<Uuid>, sqlx::Error> {
+        let ids = sqlx::query_scalar!(
+            r#"SELECT a.id as "id!: Uuid"
+               FROM context_artifacts a
+               LEFT JOIN context_artifact_reviews r ON r.artifact_id = a.id
+               LEFT JOIN context_artifact_ttls t
+                      ON t.project_id = a.project_id AND t.artifact_type = a.artifact_type
+               WHERE a.project_id = $1
+                 AND (COALESCE(r.status, 'approved') <> 'approved'
+                      OR COALESCE(r.reviewed_at, a.updated_at)
+                             + make_interval(days => t.ttl_days) <= NOW())"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(ids.into_iter().collect())
+    }
+
+    /// Put an artifact (back) up for review, e.g. after an agent wrote or rewrote it
+    pub async fn mark_draft(pool: &PgPool, artifact_id: Uuid) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO context_artifact_reviews (artifact_id, status)
+               VALUES ($1, 'draft')
+               ON CONFLICT (artifact_id) DO UPDATE
+               SET status = 'draft', reviewer = NULL, reviewed_at = NULL"#,
+            artifact_id
+        )
+        .execute(pool)
+        .await?;
+        Self::for_artifact(pool, artifact_id).await
+    }
+
+    pub async fn review(
+        pool: &PgPool,
+        artifact_id: Uuid,
+        data: &ReviewContextArtifact,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO context_artifact_reviews (artifact_id, status, reviewer, reviewed_at)
+               VALUES ($1, $2, $3, NOW())
+               ON CONFLICT (artifact_id) DO UPDATE
+               SET status = EXCLUDED.status,
+                   reviewer = EXCLUDED.reviewer,
+                   reviewed_at = EXCLUDED.reviewed_at"#,
+            artifact_id,
+            data.status.as_str(),
+            &data.reviewer
+        )
+        .execute(pool)
+        .await?;
+        Self::for_artifact(pool, artifact_id).await
+    }
+}
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::context_artifact::ArtifactType;
+
+/// How long a project's approved artifacts of one type stay in agent prompts
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ContextArtifactTtl {
+    pub project_id: Uuid,
+    pub artifact_type: String,
+    pub ttl_days: i32,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct SetContextArtifactTtl {
+    pub artifact_type: ArtifactType,
+    /// None removes the TTL, so artifacts of the type never expire
+    pub ttl_days: Option<i32>,
+}
+
+impl ContextArtifactTtl {
+    pub async fn find_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ContextArtifactTtl,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      artifact_type as "artifact_type!",
+                      ttl_days as "ttl_days!",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM context_artifact_ttls
+               WHERE project_id = $1
+               ORDER BY artifact_type"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Set or remove the TTL of an artifact type; returns the project's TTLs
+    pub async fn set(
+        pool: &PgPool,
+        project_id: Uuid,
+        data: &SetContextArtifactTtl,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        match data.ttl_days {
+            Some(ttl_days) => {
+                sqlx::query!(
+                    r#"INSERT INTO context_artifact_ttls (project_id, artifact_type, ttl_days)
+                       VALUES ($1, $2, $3)
+                       ON CONFLICT (project_id, artifact_type) DO UPDATE
+                       SET ttl_days = EXCLUDED.ttl_days, updated_at = NOW()"#,
+                    project_id,
+                    data.artifact_type.as_str(),
+                    ttl_days
+                )
+                .execute(pool)
+                .await?;
+            }
+            None => {
+                sqlx::query!(
+                    r#"DELETE FROM context_artifact_ttls
+                       WHERE project_id = $1 AND artifact_type = $2"#,
+                    project_id,
+                    data.artifact_type.as_str()
+                )
+                .execute(pool)
+                .await?;
+            }
+        }
+        Self::find_by_project(pool, project_id).await
+    }
+}
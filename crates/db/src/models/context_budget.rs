@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Budget override stored for a project or a column. Unset fields inherit
+/// from the next level (column > project > defaults).
+#[derive(Debug, Clone, Default, FromRow, Serialize, Deserialize, TS)]
+pub struct ContextBudgetOverride {
+    /// Total tokens available for injected context
+    pub token_budget: Option<i32>,
+    /// Relative share of the budget for project-wide artifacts
+    pub global_weight: Option<i32>,
+    /// Relative share of the budget for artifacts tied to the task
+    pub task_weight: Option<i32>,
+    /// Relative share of the budget for module memories of touched paths
+    pub path_weight: Option<i32>,
+}
+
+/// Effective budget after applying column and project overrides to the defaults
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct ContextBudget {
+    pub token_budget: i32,
+    pub global_weight: i32,
+    pub task_weight: i32,
+    pub path_weight: i32,
+}
+
+/// An override alongside the budget it resolves to
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ContextBudgetSettings {
+    pub overrides: ContextBudgetOverride,
+    pub effective: ContextBudget,
+}
+
+impl Default for ContextBudget {
+    fn default() -> Self {
+        Self {
+            token_budget: Self::DEFAULT_TOKEN_BUDGET,
+            global_weight: 50,
+            task_weight: 30,
+            path_weight: 20,
+        }
+    }
+}
+
+impl ContextBudget {
+    /// Default token budget for context injection
+    pub const DEFAULT_TOKEN_BUDGET: i32 = 8000;
+
+    fn apply(mut self, overrides: &ContextBudgetOverride) -> Self {
+        self.token_budget = overrides.token_budget.unwrap_or(self.token_budget);
+        self.global_weight = overrides.global_weight.unwrap_or(self.global_weight);
+        self.task_weight = overrides.task_weight.unwrap_or(self.task_weight);
+        self.path_weight = overrides.path_weight.unwrap_or(self.path_weight);
+        self
+    }
+
+    /// Tokens allotted to a scope with the given weight
+    pub fn share(&self, weight: i32) -> i32 {
+        let total_weight = self.global_weight + self.task_weight + self.path_weight;
+        if total_weight <= 0 {
+            return 0;
+        }
+        (self.token_budget as i64 * weight as i64 / total_weight as i64) as i32
+    }
+
+    /// Budget for assembling context in a project, optionally for a task in `column_id`
+    pub async fn resolve(
+        pool: &PgPool,
+        project_id: Uuid,
+        column_id: Option<Uuid>,
+    ) -> Result<Self, sqlx::Error> {
+        let mut budget =
+            Self::default().apply(&ContextBudgetOverride::for_project(pool, project_id).await?);
+        if let Some(column_id) = column_id {
+            budget = budget.apply(&ContextBudgetOverride::for_column(pool, column_id).await?);
+        }
+        // Weights that cancel out across levels fall back to the default split
+        if budget.global_weight + budget.task_weight + budget.path_weight <= 0 {
+            let defaults = Self::default();
+            budget.global_weight = defaults.global_weight;
+            budget.task_weight = defaults.task_weight;
+            budget.path_weight = defaults.path_weight;
+        }
+        Ok(budget)
+    }
+}
+
+impl ContextBudgetOverride {
+    /// Check the override before storing it
+    pub fn validate(&self) -> Result<(), String> {
+        if self.token_budget.is_some_and(|b| b <= 0) {
+            return Err("token_budget must be positive".to_string());
+        }
+        let weights = [self.global_weight, self.task_weight, self.path_weight];
+        if weights.iter().flatten().any(|w| *w < 0) {
+            return Err("Scope weights must not be negative".to_string());
+        }
+        if weights.iter().all(Option::is_some) && weights.iter().flatten().sum::<i32>() == 0 {
+            return Err("At least one scope weight must be positive".to_string());
+        }
+        Ok(())
+    }
+
+    /// Override stored for a project (all fields unset when none)
+    pub async fn for_project(pool: &PgPool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let overrides = sqlx::query_as!(
+            ContextBudgetOverride,
+            r#"SELECT token_budget, global_weight, task_weight, path_weight
+               FROM context_budgets
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(overrides.unwrap_or_default())
+    }
+
+    /// Override stored for a column (all fields unset when none)
+    pub async fn for_column(pool: &PgPool, column_id: Uuid) -> Result<Self, sqlx::Error> {
+        let overrides = sqlx::query_as!(
+            ContextBudgetOverride,
+            r#"SELECT token_budget, global_weight, task_weight, path_weight
+               FROM context_budgets
+               WHERE column_id = $1"#,
+            column_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(overrides.unwrap_or_default())
+    }
+
+    /// Replace a project's override; unset fields go back to inheriting
+    pub async fn set_for_project(
+        pool: &PgPool,
+        project_id: Uuid,
+        data: &Self,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ContextBudgetOverride,
+            r#"INSERT INTO context_budgets
+                   (project_id, token_budget, global_weight, task_weight, path_weight)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (project_id) WHERE project_id IS NOT NULL DO UPDATE
+               SET token_budget = EXCLUDED.token_budget,
+                   global_weight = EXCLUDED.global_weight,
+                   task_weight = EXCLUDED.task_weight,
+                   path_weight = EXCLUDED.path_weight,
+                   updated_at = NOW()
+               RETURNING token_budget, global_weight, task_weight, path_weight"#,
+            project_id,
+            data.token_budget,
+            data.global_weight,
+            data.task_weight,
+            data.path_weight
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Replace a column's override; unset fields go back to inheriting
+    pub async fn set_for_column(
+        pool: &PgPool,
+        column_id: Uuid,
+        data: &Self,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ContextBudgetOverride,
+            r#"INSERT INTO context_budgets
+                   (column_id, token_budget, global_weight, task_weight, path_weight)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (column_id) WHERE column_id IS NOT NULL DO UPDATE
+               SET token_budget = EXCLUDED.token_budget,
+                   global_weight = EXCLUDED.global_weight,
+                   task_weight = EXCLUDED.task_weight,
+                   path_weight = EXCLUDED.path_weight,
+                   updated_at = NOW()
+               RETURNING token_budget, global_weight, task_weight, path_weight"#,
+            column_id,
+            data.token_budget,
+            data.global_weight,
+            data.task_weight,
+            data.path_weight
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
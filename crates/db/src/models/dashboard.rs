@@ -0,0 +1,311 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::project::Project;
+
+/// A token granting read-only access to one project's board dashboard.
+/// Only the token's hash is stored; the token itself is shown once on creation.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct DashboardToken {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    #[ts(type = "Date | null")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateDashboardToken {
+    pub name: String,
+}
+
+/// Returned once when a dashboard token is created
+#[derive(Debug, Serialize, TS)]
+pub struct CreatedDashboardToken {
+    pub dashboard: DashboardToken,
+    pub token: String,
+}
+
+impl DashboardToken {
+    pub async fn find_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DashboardToken,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name as "name!",
+                      last_used_at as "last_used_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM dashboard_tokens
+             WHERE project_id = $1
+             ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_hash(
+        pool: &PgPool,
+        token_hash: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DashboardToken,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name as "name!",
+                      last_used_at as "last_used_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM dashboard_tokens WHERE token_hash = $1"#,
+            token_hash
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        project_id: Uuid,
+        name: &str,
+        token_hash: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            DashboardToken,
+            r#"INSERT INTO dashboard_tokens (id, project_id, name, token_hash)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id as "id!: Uuid",
+                       project_id as "project_id!: Uuid",
+                       name as "name!",
+                       last_used_at as "last_used_at: DateTime<Utc>",
+                       created_at as "created_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            project_id,
+            name,
+            token_hash
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn touch(pool: &PgPool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!("UPDATE dashboard_tokens SET last_used_at = NOW() WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &PgPool, project_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM dashboard_tokens WHERE id = $1 AND project_id = $2",
+            id,
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// A board column with how many of the project's tasks sit in it
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct DashboardColumn {
+    pub id: Uuid,
+    pub name: String,
+    pub color: Option<String>,
+    pub position: i32,
+    pub is_terminal: bool,
+    pub task_count: i64,
+    pub in_progress: i64,
+    pub awaiting_response: i64,
+}
+
+/// A coding agent execution currently running for one of the project's tasks
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct DashboardRunningAgent {
+    pub execution_process_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub column_name: Option<String>,
+    pub agent_name: Option<String>,
+    pub executor: Option<String>,
+    #[ts(type = "Date")]
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DashboardEscalationReason {
+    /// The task's last transition took the escalation path and it is still there
+    Escalated,
+    /// The agent exited without a decision and is waiting for a person
+    AwaitingResponse,
+}
+
+/// A task that needs a person's attention
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct DashboardEscalation {
+    pub task_id: Uuid,
+    pub title: String,
+    pub column_name: Option<String>,
+    pub reason: DashboardEscalationReason,
+    #[ts(type = "Date")]
+    pub since: DateTime<Utc>,
+}
+
+/// Read-only view of a project's board for wall displays
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BoardSnapshot {
+    pub project_id: Uuid,
+    pub project_name: String,
+    #[ts(type = "Date")]
+    pub generated_at: DateTime<Utc>,
+    pub columns: Vec<DashboardColumn>,
+    pub running_agents: Vec<DashboardRunningAgent>,
+    pub escalations: Vec<DashboardEscalation>,
+}
+
+#[derive(FromRow)]
+struct AttentionRow {
+    task_id: Uuid,
+    title: String,
+    column_name: Option<String>,
+    since: DateTime<Utc>,
+}
+
+impl AttentionRow {
+    fn into_escalation(self, reason: DashboardEscalationReason) -> DashboardEscalation {
+        DashboardEscalation {
+            task_id: self.task_id,
+            title: self.title,
+            column_name: self.column_name,
+            reason,
+            since: self.since,
+        }
+    }
+}
+
+impl BoardSnapshot {
+    pub async fn load(pool: &PgPool, project: &Project) -> Result<Self, sqlx::Error> {
+        let columns = match project.board_id {
+            Some(board_id) => {
+                sqlx::query_as!(
+                    DashboardColumn,
+                    r#"SELECT c.id as "id!: Uuid",
+                              c.name as "name!",
+                              c.color,
+                              c.position as "position!",
+                              c.is_terminal as "is_terminal!",
+                              COUNT(t.id) as "task_count!",
+                              COUNT(t.id) FILTER (WHERE t.task_state = 'inprogress')
+                                  as "in_progress!",
+                              COUNT(t.id) FILTER (WHERE t.task_state = 'awaitingresponse')
+                                  as "awaiting_response!"
+                       FROM kanban_columns c
+                       LEFT JOIN tasks t ON t.column_id = c.id AND t.project_id = $2
+                            AND t.archived_at IS NULL
+                       WHERE c.board_id = $1
+                       GROUP BY c.id
+                       ORDER BY c.position ASC"#,
+                    board_id,
+                    project.id
+                )
+                .fetch_all(pool)
+                .await?
+            }
+            None => Vec::new(),
+        };
+
+        let running_agents = sqlx::query_as!(
+            DashboardRunningAgent,
+            r#"SELECT ep.id as "execution_process_id!: Uuid",
+                      t.id as "task_id!: Uuid",
+                      t.title as "task_title!",
+                      c.name AS column_name,
+                      a.name AS agent_name,
+                      s.executor,
+                      ep.started_at as "started_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               LEFT JOIN kanban_columns c ON c.id = t.column_id
+               LEFT JOIN agent_sessions ags ON ags.session_id = ep.session_id
+               LEFT JOIN agents a ON a.id = ags.agent_id
+               WHERE t.project_id = $1
+                 AND ep.status = 'running'
+                 AND ep.run_reason = 'codingagent'
+               ORDER BY ep.started_at ASC"#,
+            project.id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        // Tasks whose most recent column entry was an escalation and that have not moved since
+        let escalated = sqlx::query_as!(
+            AttentionRow,
+            r#"SELECT t.id as "task_id!: Uuid",
+                      t.title as "title!",
+                      c.name AS column_name,
+                      last.created_at as "since!: DateTime<Utc>"
+               FROM tasks t
+               JOIN LATERAL (
+                    SELECT e.metadata, e.to_column_id, e.created_at
+                    FROM task_events e
+                    WHERE e.task_id = t.id AND e.event_type = 'column_enter'
+                    ORDER BY e.created_at DESC
+                    LIMIT 1
+               ) last ON TRUE
+               LEFT JOIN kanban_columns c ON c.id = t.column_id
+               WHERE t.project_id = $1
+                 AND t.archived_at IS NULL
+                 AND last.metadata::jsonb->>'transition_path' = 'escalation'
+                 AND last.to_column_id IS NOT DISTINCT FROM t.column_id
+               ORDER BY last.created_at ASC"#,
+            project.id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let awaiting = sqlx::query_as!(
+            AttentionRow,
+            r#"SELECT t.id as "task_id!: Uuid",
+                      t.title as "title!",
+                      c.name AS column_name,
+                      t.updated_at as "since!: DateTime<Utc>"
+               FROM tasks t
+               LEFT JOIN kanban_columns c ON c.id = t.column_id
+               WHERE t.project_id = $1 AND t.task_state = 'awaitingresponse'
+                 AND t.archived_at IS NULL
+               ORDER BY t.updated_at ASC"#,
+            project.id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut escalations: Vec<DashboardEscalation> = escalated
+            .into_iter()
+            .map(|row| row.into_escalation(DashboardEscalationReason::Escalated))
+            .collect();
+        for row in awaiting {
+            if !escalations.iter().any(|e| e.task_id == row.task_id) {
+                escalations.push(row.into_escalation(DashboardEscalationReason::AwaitingResponse));
+            }
+        }
+
+        Ok(Self {
+            project_id: project.id,
+            project_name: project.name.clone(),
+            generated_at: Utc::now(),
+            columns,
+            running_agents,
+            escalations,
+        })
+    }
+}
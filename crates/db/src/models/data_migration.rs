@@ -0,0 +1,180 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, Type};
+use ts_rs::TS;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, TS, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DataMigrationStatus {
+    Pending,
+    Running,
+    Completed,
+    /// Errored or was interrupted by a restart; can be run again
+    Failed,
+}
+
+/// Run-once bookkeeping for a data migration
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct DataMigration {
+    pub name: String,
+    pub status: DataMigrationStatus,
+    /// Items handled so far in the current (or last) run
+    pub processed: i32,
+    /// Items the current (or last) run found to handle
+    pub total: Option<i32>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    #[ts(type = "Date | null")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date | null")]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DataMigration {
+    /// State of a migration that has never been started
+    pub fn pending(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            status: DataMigrationStatus::Pending,
+            processed: 0,
+            total: None,
+            attempts: 0,
+            last_error: None,
+            started_at: None,
+            completed_at: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub async fn find_all(pool: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DataMigration,
+            r#"SELECT name as "name!",
+                      status as "status!: DataMigrationStatus",
+                      processed as "processed!",
+                      total,
+                      attempts as "attempts!",
+                      last_error,
+                      started_at as "started_at: DateTime<Utc>",
+                      completed_at as "completed_at: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM data_migrations ORDER BY name"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_name(pool: &PgPool, name: &str) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DataMigration,
+            r#"SELECT name as "name!",
+                      status as "status!: DataMigrationStatus",
+                      processed as "processed!",
+                      total,
+                      attempts as "attempts!",
+                      last_error,
+                      started_at as "started_at: DateTime<Utc>",
+                      completed_at as "completed_at: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM data_migrations WHERE name = $1"#,
+            name
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Mark a migration as running, counting the attempt. Returns `None` when it is
+    /// already running or has completed, so a migration never runs twice at once.
+    pub async fn claim(pool: &PgPool, name: &str) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DataMigration,
+            r#"INSERT INTO data_migrations (name, status, attempts, started_at)
+               VALUES ($1, 'running', 1, NOW())
+               ON CONFLICT (name) DO UPDATE
+               SET status = 'running', attempts = data_migrations.attempts + 1,
+                   processed = 0, total = NULL, last_error = NULL, started_at = NOW(),
+                   updated_at = NOW()
+               WHERE data_migrations.status IN ('pending', 'failed')
+               RETURNING name as "name!",
+                         status as "status!: DataMigrationStatus",
+                         processed as "processed!",
+                         total,
+                         attempts as "attempts!",
+                         last_error,
+                         started_at as "started_at: DateTime<Utc>",
+                         completed_at as "completed_at: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            name
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn update_progress(
+        pool: &PgPool,
+        name: &str,
+        processed: i32,
+        total: Option<i32>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE data_migrations
+             SET processed = $2, total = $3, updated_at = NOW()
+             WHERE name = $1",
+            name,
+            processed,
+            total
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn complete(
+        pool: &PgPool,
+        name: &str,
+        processed: i32,
+        total: Option<i32>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE data_migrations
+             SET status = 'completed', processed = $2, total = $3, completed_at = NOW(),
+                 last_error = NULL, updated_at = NOW()
+             WHERE name = $1",
+            name,
+            processed,
+            total
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn fail(pool: &PgPool, name: &str, error: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE data_migrations
+             SET status = 'failed', last_error = $2, updated_at = NOW()
+             WHERE name = $1",
+            name,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark runs cut short by a restart as failed so they can be retried
+    pub async fn fail_abandoned(pool: &PgPool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE data_migrations
+             SET status = 'failed', last_error = 'Interrupted by a server restart',
+                 updated_at = NOW()
+             WHERE status = 'running'"
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
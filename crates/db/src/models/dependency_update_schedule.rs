@@ -0,0 +1,217 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Package ecosystems checked for outdated dependencies
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyEcosystem {
+    Cargo,
+    Npm,
+}
+
+impl DependencyEcosystem {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DependencyEcosystem::Cargo => "cargo",
+            DependencyEcosystem::Npm => "npm",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "cargo" => Some(DependencyEcosystem::Cargo),
+            "npm" => Some(DependencyEcosystem::Npm),
+            _ => None,
+        }
+    }
+}
+
+/// Scheduled trigger that opens dependency update tasks for a project
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct DependencyUpdateSchedule {
+    pub project_id: Uuid,
+    /// Workflow board the update tasks are routed through; null uses the project's board
+    pub board_id: Option<Uuid>,
+    pub ecosystems: Vec<String>,
+    pub interval_hours: i32,
+    /// Maximum number of dependency updates per task
+    pub batch_size: i32,
+    pub enabled: bool,
+    #[ts(type = "Date | null")]
+    pub last_run_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertDependencyUpdateSchedule {
+    pub board_id: Option<Uuid>,
+    /// Defaults to cargo and npm
+    pub ecosystems: Option<Vec<DependencyEcosystem>>,
+    /// Defaults to weekly (168 hours)
+    pub interval_hours: Option<i32>,
+    /// Defaults to 10
+    pub batch_size: Option<i32>,
+    pub enabled: Option<bool>,
+}
+
+impl DependencyUpdateSchedule {
+    /// Configured ecosystems, skipping unknown names
+    pub fn get_ecosystems(&self) -> Vec<DependencyEcosystem> {
+        self.ecosystems
+            .iter()
+            .filter_map(|s| DependencyEcosystem::from_str(s))
+            .collect()
+    }
+
+    pub async fn find_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DependencyUpdateSchedule,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      board_id as "board_id: Uuid",
+                      ecosystems as "ecosystems!",
+                      interval_hours as "interval_hours!",
+                      batch_size as "batch_size!",
+                      enabled as "enabled!",
+                      last_run_at as "last_run_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM dependency_update_schedules WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Enabled schedules whose interval has elapsed since the last run
+    pub async fn find_due(pool: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DependencyUpdateSchedule,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      board_id as "board_id: Uuid",
+                      ecosystems as "ecosystems!",
+                      interval_hours as "interval_hours!",
+                      batch_size as "batch_size!",
+                      enabled as "enabled!",
+                      last_run_at as "last_run_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM dependency_update_schedules
+               WHERE enabled
+                 AND (last_run_at IS NULL
+                      OR last_run_at + make_interval(hours => interval_hours) <= NOW())"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Create or replace the schedule for a project
+    pub async fn upsert(
+        pool: &PgPool,
+        project_id: Uuid,
+        data: &UpsertDependencyUpdateSchedule,
+    ) -> Result<Self, sqlx::Error> {
+        let ecosystems: Vec<String> = data
+            .ecosystems
+            .as_ref()
+            .map(|e| e.iter().map(|e| e.as_str().to_string()).collect())
+            .unwrap_or_else(|| vec!["cargo".to_string(), "npm".to_string()]);
+        sqlx::query_as!(
+            DependencyUpdateSchedule,
+            r#"INSERT INTO dependency_update_schedules
+                   (project_id, board_id, ecosystems, interval_hours, batch_size, enabled)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT (project_id) DO UPDATE
+               SET board_id = EXCLUDED.board_id,
+                   ecosystems = EXCLUDED.ecosystems,
+                   interval_hours = EXCLUDED.interval_hours,
+                   batch_size = EXCLUDED.batch_size,
+                   enabled = EXCLUDED.enabled,
+                   updated_at = NOW()
+               RETURNING project_id as "project_id!: Uuid",
+                         board_id as "board_id: Uuid",
+                         ecosystems as "ecosystems!",
+                         interval_hours as "interval_hours!",
+                         batch_size as "batch_size!",
+                         enabled as "enabled!",
+                         last_run_at as "last_run_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.board_id,
+            ecosystems,
+            data.interval_hours.unwrap_or(168),
+            data.batch_size.unwrap_or(10),
+            data.enabled.unwrap_or(true)
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &PgPool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM dependency_update_schedules WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn mark_run(pool: &PgPool, project_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE dependency_update_schedules SET last_run_at = NOW() WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Source ids among `source_ids` already covered by an unfinished update task of the
+    /// project, so packages aren't picked up twice while an update is still in flight
+    pub async fn open_sources(
+        pool: &PgPool,
+        project_id: Uuid,
+        source_ids: &[String],
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT DISTINCT d.source_id as "source_id!"
+               FROM dependency_update_tasks d
+               JOIN tasks t ON t.id = d.task_id
+               WHERE t.project_id = $1
+                 AND d.source_id = ANY($2)
+                 AND t.status NOT IN ('done', 'cancelled')"#,
+            project_id,
+            source_ids
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Record the packages an update task covers
+    pub async fn record_sources(
+        pool: &PgPool,
+        task_id: Uuid,
+        source_ids: &[String],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO dependency_update_tasks (task_id, source_id)
+             SELECT $1, UNNEST($2::text[])
+             ON CONFLICT DO NOTHING",
+            task_id,
+            source_ids
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Changes an agent proposed in a dry-run column, for one repo of the workspace
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct DryRunPatch {
+    pub id: Uuid,
+    pub execution_process_id: Uuid,
+    pub task_id: Uuid,
+    pub workspace_id: Uuid,
+    pub repo_id: Uuid,
+    /// `git diff --binary` against the repo's HEAD when the run started
+    pub patch: String,
+    /// When a person applied the patch to the workspace; none while it is pending
+    #[ts(type = "Date | null")]
+    pub applied_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl DryRunPatch {
+    pub async fn create(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+        task_id: Uuid,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        patch: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            DryRunPatch,
+            r#"INSERT INTO dry_run_patches
+                   (execution_process_id, task_id, workspace_id, repo_id, patch)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         execution_process_id as "execution_process_id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         repo_id as "repo_id!: Uuid",
+                         patch as "patch!",
+                         applied_at as "applied_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            execution_process_id,
+            task_id,
+            workspace_id,
+            repo_id,
+            patch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DryRunPatch,
+            r#"SELECT id as "id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      patch as "patch!",
+                      applied_at as "applied_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM dry_run_patches WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Patches proposed for a task, newest first
+    pub async fn find_by_task_id(pool: &PgPool, task_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DryRunPatch,
+            r#"SELECT id as "id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      patch as "patch!",
+                      applied_at as "applied_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM dry_run_patches
+               WHERE task_id = $1
+               ORDER BY created_at DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Mark a pending patch applied; None when it was already applied
+    pub async fn mark_applied(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DryRunPatch,
+            r#"UPDATE dry_run_patches
+               SET applied_at = NOW()
+               WHERE id = $1 AND applied_at IS NULL
+               RETURNING id as "id!: Uuid",
+                         execution_process_id as "execution_process_id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         repo_id as "repo_id!: Uuid",
+                         patch as "patch!",
+                         applied_at as "applied_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
@@ -0,0 +1,320 @@
+use chrono::{DateTime, Duration, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Which emails an address receives, and when its daily digest goes out
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct EmailNotificationPreference {
+    pub id: Uuid,
+    pub email: String,
+    pub name: Option<String>,
+    /// Email right away when a task takes an escalation transition
+    pub immediate_escalations: bool,
+    /// Email right away when a run fails
+    pub immediate_failures: bool,
+    pub digest_enabled: bool,
+    /// Hour of the day (UTC) the digest is sent
+    pub digest_hour: i32,
+    #[ts(type = "Date | null")]
+    pub last_digest_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateEmailNotificationPreference {
+    pub email: String,
+    pub name: Option<String>,
+    pub immediate_escalations: Option<bool>,
+    pub immediate_failures: Option<bool>,
+    pub digest_enabled: Option<bool>,
+    pub digest_hour: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateEmailNotificationPreference {
+    pub email: Option<String>,
+    pub name: Option<String>,
+    pub immediate_escalations: Option<bool>,
+    pub immediate_failures: Option<bool>,
+    pub digest_enabled: Option<bool>,
+    pub digest_hour: Option<i32>,
+}
+
+/// Kinds of email sent as soon as the event happens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImmediateEmailKind {
+    Escalation,
+    Failure,
+}
+
+/// One line of a digest
+#[derive(Debug, Clone, FromRow)]
+pub struct DigestEntry {
+    /// `entered_review`, `run_failed` or `pending_approval`
+    pub kind: String,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub project_name: String,
+    pub detail: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl EmailNotificationPreference {
+    /// Whether the digest should go out at `now`: at or after the chosen hour, and
+    /// not already sent today
+    pub fn digest_due(&self, now: DateTime<Utc>) -> bool {
+        if !self.digest_enabled || (now.hour() as i32) < self.digest_hour {
+            return false;
+        }
+        self.last_digest_at
+            .is_none_or(|sent| sent.date_naive() < now.date_naive())
+    }
+
+    /// Start of the period the next digest covers
+    pub fn digest_since(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.last_digest_at.unwrap_or(now - Duration::days(1))
+    }
+
+    pub async fn find_all(pool: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            EmailNotificationPreference,
+            r#"SELECT id as "id!: Uuid",
+                      email as "email!",
+                      name,
+                      immediate_escalations as "immediate_escalations!",
+                      immediate_failures as "immediate_failures!",
+                      digest_enabled as "digest_enabled!",
+                      digest_hour as "digest_hour!",
+                      last_digest_at as "last_digest_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM email_notification_preferences ORDER BY email"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            EmailNotificationPreference,
+            r#"SELECT id as "id!: Uuid",
+                      email as "email!",
+                      name,
+                      immediate_escalations as "immediate_escalations!",
+                      immediate_failures as "immediate_failures!",
+                      digest_enabled as "digest_enabled!",
+                      digest_hour as "digest_hour!",
+                      last_digest_at as "last_digest_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM email_notification_preferences WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_email(pool: &PgPool, email: &str) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            EmailNotificationPreference,
+            r#"SELECT id as "id!: Uuid",
+                      email as "email!",
+                      name,
+                      immediate_escalations as "immediate_escalations!",
+                      immediate_failures as "immediate_failures!",
+                      digest_enabled as "digest_enabled!",
+                      digest_hour as "digest_hour!",
+                      last_digest_at as "last_digest_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM email_notification_preferences
+               WHERE LOWER(email) = LOWER($1)"#,
+            email.trim()
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Addresses that want this kind of email right away
+    pub async fn find_immediate(
+        pool: &PgPool,
+        kind: ImmediateEmailKind,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let escalation = kind == ImmediateEmailKind::Escalation;
+        sqlx::query_as!(
+            EmailNotificationPreference,
+            r#"SELECT id as "id!: Uuid",
+                      email as "email!",
+                      name,
+                      immediate_escalations as "immediate_escalations!",
+                      immediate_failures as "immediate_failures!",
+                      digest_enabled as "digest_enabled!",
+                      digest_hour as "digest_hour!",
+                      last_digest_at as "last_digest_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM email_notification_preferences
+               WHERE CASE WHEN $1 THEN immediate_escalations ELSE immediate_failures END"#,
+            escalation
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        data: &CreateEmailNotificationPreference,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            EmailNotificationPreference,
+            r#"INSERT INTO email_notification_preferences
+                   (id, email, name, immediate_escalations, immediate_failures, digest_enabled,
+                    digest_hour)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         email as "email!",
+                         name,
+                         immediate_escalations as "immediate_escalations!",
+                         immediate_failures as "immediate_failures!",
+                         digest_enabled as "digest_enabled!",
+                         digest_hour as "digest_hour!",
+                         last_digest_at as "last_digest_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            data.email.trim(),
+            &data.name,
+            data.immediate_escalations.unwrap_or(true),
+            data.immediate_failures.unwrap_or(true),
+            data.digest_enabled.unwrap_or(true),
+            data.digest_hour.unwrap_or(8)
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        data: &UpdateEmailNotificationPreference,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            EmailNotificationPreference,
+            r#"UPDATE email_notification_preferences
+               SET email = COALESCE($2, email),
+                   name = COALESCE($3, name),
+                   immediate_escalations = COALESCE($4, immediate_escalations),
+                   immediate_failures = COALESCE($5, immediate_failures),
+                   digest_enabled = COALESCE($6, digest_enabled),
+                   digest_hour = COALESCE($7, digest_hour),
+                   updated_at = NOW()
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         email as "email!",
+                         name,
+                         immediate_escalations as "immediate_escalations!",
+                         immediate_failures as "immediate_failures!",
+                         digest_enabled as "digest_enabled!",
+                         digest_hour as "digest_hour!",
+                         last_digest_at as "last_digest_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.email.as_deref().map(str::trim),
+            &data.name,
+            data.immediate_escalations,
+            data.immediate_failures,
+            data.digest_enabled,
+            data.digest_hour
+        )
+        .fetch_optional(pool)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM email_notification_preferences WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn mark_digest_sent(
+        pool: &PgPool,
+        id: Uuid,
+        sent_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE email_notification_preferences SET last_digest_at = $2 WHERE id = $1",
+            id,
+            sent_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+impl DigestEntry {
+    /// Tasks that entered a review column and runs that failed after `since`, plus
+    /// plans and agent questions still waiting for someone, oldest first
+    pub async fn find_since(
+        pool: &PgPool,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DigestEntry,
+            r#"SELECT 'entered_review' as "kind!",
+                      t.id as "task_id!: Uuid",
+                      t.title as "task_title!",
+                      p.name as "project_name!",
+                      c.name AS detail,
+                      e.created_at as "occurred_at!: DateTime<Utc>"
+               FROM task_events e
+               JOIN tasks t ON t.id = e.task_id
+               JOIN projects p ON p.id = t.project_id
+               JOIN kanban_columns c ON c.id = e.to_column_id
+               WHERE e.event_type = 'column_enter'
+                 AND c.status = 'inreview'
+                 AND e.created_at > $1
+               UNION ALL
+               SELECT 'run_failed', t.id, t.title, p.name,
+                      CASE ep.run_reason::TEXT
+                          WHEN 'codingagent' THEN 'coding agent'
+                          WHEN 'setupscript' THEN 'setup script'
+                          WHEN 'cleanupscript' THEN 'cleanup script'
+                          ELSE 'manual run'
+                      END,
+                      COALESCE(ep.completed_at, ep.created_at)
+               FROM execution_processes ep
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               JOIN projects p ON p.id = t.project_id
+               WHERE ep.status = 'failed'
+                 AND ep.run_reason != 'devserver'
+                 AND COALESCE(ep.completed_at, ep.created_at) > $1
+               UNION ALL
+               SELECT 'pending_approval', t.id, t.title, p.name, 'plan review', pr.created_at
+               FROM plan_reviews pr
+               JOIN tasks t ON t.id = pr.task_id
+               JOIN projects p ON p.id = t.project_id
+               WHERE pr.status = 'pending' AND pr.plan IS NOT NULL
+               UNION ALL
+               SELECT 'pending_approval', t.id, t.title, p.name, 'waiting for a response',
+                      t.updated_at
+               FROM tasks t
+               JOIN projects p ON p.id = t.project_id
+               WHERE t.task_state = 'awaitingresponse'
+               ORDER BY occurred_at"#,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
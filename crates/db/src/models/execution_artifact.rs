@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A file an agent left in `.vibe/artifacts/`, stored in the images store
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionArtifact {
+    pub id: Uuid,
+    pub execution_process_id: Uuid,
+    pub task_id: Uuid,
+    /// Stored file, served from `/api/images/{image_id}/file`
+    pub image_id: Uuid,
+    /// Path relative to the artifacts directory, e.g. `coverage/summary.json`
+    pub name: String,
+    /// Repo whose artifacts directory held the file; none for the workspace root
+    pub repo_name: Option<String>,
+    pub mime_type: Option<String>,
+    pub size_bytes: i32,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateExecutionArtifact {
+    pub image_id: Uuid,
+    pub name: String,
+    pub repo_name: Option<String>,
+    pub mime_type: Option<String>,
+    pub size_bytes: i32,
+}
+
+impl ExecutionArtifact {
+    pub async fn create(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+        task_id: Uuid,
+        data: &CreateExecutionArtifact,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionArtifact,
+            r#"INSERT INTO execution_artifacts
+                   (execution_process_id, task_id, image_id, name, repo_name, mime_type, size_bytes)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         execution_process_id as "execution_process_id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         image_id as "image_id!: Uuid",
+                         name as "name!",
+                         repo_name,
+                         mime_type,
+                         size_bytes as "size_bytes!",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            execution_process_id,
+            task_id,
+            data.image_id,
+            &data.name,
+            &data.repo_name,
+            &data.mime_type,
+            data.size_bytes
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionArtifact,
+            r#"SELECT id as "id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      image_id as "image_id!: Uuid",
+                      name as "name!",
+                      repo_name,
+                      mime_type,
+                      size_bytes as "size_bytes!",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_artifacts
+               WHERE execution_process_id = $1
+               ORDER BY repo_name NULLS FIRST, name"#,
+            execution_process_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Delete artifacts collected before the cutoff of their task's retention; tasks kept
+    /// forever are skipped. The stored files go with the next orphaned image cleanup.
+    pub async fn delete_expired(
+        pool: &PgPool,
+        default_before: DateTime<Utc>,
+        short_before: DateTime<Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM execution_artifacts ea
+               WHERE ea.created_at < CASE (
+                   SELECT retention FROM task_log_retention r WHERE r.task_id = ea.task_id
+               )
+                   WHEN 'keep_forever' THEN NULL
+                   WHEN 'short' THEN $2
+                   ELSE $1
+               END"#,
+            default_before,
+            short_before
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Artifacts of every execution of a task, newest first
+    pub async fn find_by_task_id(pool: &PgPool, task_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionArtifact,
+            r#"SELECT id as "id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      image_id as "image_id!: Uuid",
+                      name as "name!",
+                      repo_name,
+                      mime_type,
+                      size_bytes as "size_bytes!",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_artifacts
+               WHERE task_id = $1
+               ORDER BY created_at DESC, repo_name NULLS FIRST, name"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliverableRequirementKind {
+    /// The column's question, answered with one of its answer options
+    Decision,
+    /// A file the deliverable names
+    RequiredFile,
+    /// The deliverable asks for tests; they must have been run and passed
+    Tests,
+}
+
+/// One requirement of the column's deliverable and whether the execution met it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct DeliverableRequirement {
+    pub kind: DeliverableRequirementKind,
+    pub requirement: String,
+    pub satisfied: bool,
+    /// What the requirement was checked against, e.g. the answer given or the test command run
+    pub evidence: Option<String>,
+}
+
+/// Deliverable requirements an execution satisfied and missed
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionDeliverableReport {
+    pub execution_process_id: Uuid,
+    /// Column the execution ran in
+    pub column_id: Option<Uuid>,
+    #[ts(type = "Array<DeliverableRequirement>")]
+    pub requirements: Json<Vec<DeliverableRequirement>>,
+    pub satisfied_count: i32,
+    pub missed_count: i32,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExecutionDeliverableReport {
+    /// Store the report for an execution, replacing any earlier one
+    pub async fn upsert(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+        column_id: Option<Uuid>,
+        requirements: &[DeliverableRequirement],
+    ) -> Result<Self, sqlx::Error> {
+        let satisfied = requirements.iter().filter(|r| r.satisfied).count() as i32;
+        let missed = requirements.len() as i32 - satisfied;
+        sqlx::query_as!(
+            ExecutionDeliverableReport,
+            r#"INSERT INTO execution_deliverable_reports
+                   (execution_process_id, column_id, requirements, satisfied_count, missed_count)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (execution_process_id) DO UPDATE
+               SET column_id = EXCLUDED.column_id,
+                   requirements = EXCLUDED.requirements,
+                   satisfied_count = EXCLUDED.satisfied_count,
+                   missed_count = EXCLUDED.missed_count,
+                   created_at = NOW()
+               RETURNING execution_process_id as "execution_process_id!: Uuid",
+                         column_id as "column_id: Uuid",
+                         requirements as "requirements!: Json<Vec<DeliverableRequirement>>",
+                         satisfied_count as "satisfied_count!",
+                         missed_count as "missed_count!",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            execution_process_id,
+            column_id,
+            Json(requirements) as _,
+            satisfied,
+            missed
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionDeliverableReport,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      column_id as "column_id: Uuid",
+                      requirements as "requirements!: Json<Vec<DeliverableRequirement>>",
+                      satisfied_count as "satisfied_count!",
+                      missed_count as "missed_count!",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_deliverable_reports
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
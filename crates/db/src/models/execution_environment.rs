@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A workspace repo as it was when the execution started
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct EnvironmentRepo {
+    pub repo_name: String,
+    pub target_branch: String,
+    /// Commit the target branch pointed at
+    pub base_commit: Option<String>,
+    /// Commit the workspace branch pointed at
+    pub head_commit: Option<String>,
+}
+
+/// What an execution process ran with
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionEnvironment {
+    pub execution_process_id: Uuid,
+    /// Executor profile, e.g. `CLAUDE_CODE:PLAN`; `None` for scripts
+    pub executor: Option<String>,
+    /// The profile's executor configuration, with env values redacted
+    pub executor_config: Option<serde_json::Value>,
+    /// CLI version the agent reported in its logs
+    pub executor_version: Option<String>,
+    /// Model the agent reported in its logs
+    pub model: Option<String>,
+    pub os: String,
+    pub arch: String,
+    /// Version of the server that ran the execution
+    pub app_version: String,
+    #[ts(type = "Array<EnvironmentRepo>")]
+    pub repos: Json<Vec<EnvironmentRepo>>,
+    /// Environment variables given to the process; secret values are redacted
+    #[ts(type = "Record<string, string>")]
+    pub env_vars: Json<BTreeMap<String, String>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateExecutionEnvironment {
+    pub executor: Option<String>,
+    pub executor_config: Option<serde_json::Value>,
+    pub os: String,
+    pub arch: String,
+    pub app_version: String,
+    pub repos: Vec<EnvironmentRepo>,
+    pub env_vars: BTreeMap<String, String>,
+}
+
+impl ExecutionEnvironment {
+    pub async fn create(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+        data: &CreateExecutionEnvironment,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionEnvironment,
+            r#"INSERT INTO execution_environments
+                   (execution_process_id, executor, executor_config, os, arch, app_version,
+                    repos, env_vars)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+               RETURNING execution_process_id as "execution_process_id!: Uuid",
+                         executor,
+                         executor_config,
+                         executor_version,
+                         model,
+                         os as "os!",
+                         arch as "arch!",
+                         app_version as "app_version!",
+                         repos as "repos!: Json<Vec<EnvironmentRepo>>",
+                         env_vars as "env_vars!: Json<BTreeMap<String, String>>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            execution_process_id,
+            data.executor,
+            data.executor_config,
+            data.os,
+            data.arch,
+            data.app_version,
+            Json(&data.repos) as _,
+            Json(&data.env_vars) as _
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionEnvironment,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      executor,
+                      executor_config,
+                      executor_version,
+                      model,
+                      os as "os!",
+                      arch as "arch!",
+                      app_version as "app_version!",
+                      repos as "repos!: Json<Vec<EnvironmentRepo>>",
+                      env_vars as "env_vars!: Json<BTreeMap<String, String>>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_environments
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Record the model and CLI version the agent reported; unknown values are kept
+    pub async fn set_reported(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+        model: Option<&str>,
+        executor_version: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE execution_environments
+             SET model = COALESCE($2, model),
+                 executor_version = COALESCE($3, executor_version)
+             WHERE execution_process_id = $1",
+            execution_process_id,
+            model,
+            executor_version
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
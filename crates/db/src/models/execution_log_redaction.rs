@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Secrets masked in an execution's output before its logs were stored
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionLogRedaction {
+    pub execution_process_id: Uuid,
+    /// Env vault values, AWS keys and GitHub tokens replaced with `[REDACTED]`
+    #[ts(type = "number")]
+    pub redaction_count: i64,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExecutionLogRedaction {
+    pub async fn record(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+        redaction_count: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO execution_log_redactions (execution_process_id, redaction_count)
+               VALUES ($1, $2)
+               ON CONFLICT (execution_process_id) DO UPDATE
+               SET redaction_count = EXCLUDED.redaction_count"#,
+            execution_process_id,
+            redaction_count
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Redactions of an execution; zero when nothing was masked
+    pub async fn for_execution_process(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        let redaction = sqlx::query_as!(
+            ExecutionLogRedaction,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      redaction_count as "redaction_count!",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_log_redactions
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(redaction.unwrap_or_else(|| Self {
+            execution_process_id,
+            redaction_count: 0,
+            created_at: Utc::now(),
+        }))
+    }
+}
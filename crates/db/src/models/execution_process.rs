@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use executors::{
-    actions::{ExecutorAction, ExecutorActionType},
+    actions::{AgentIdentity, ExecutorAction, ExecutorActionType},
     profile::ExecutorProfileId,
 };
 use serde::{Deserialize, Serialize};
@@ -45,6 +45,8 @@ pub enum ExecutionProcessStatus {
     Completed,
     Failed,
     Killed,
+    /// Still running when the server stopped; coding agent runs can be resumed
+    Interrupted,
 }
 
 impl std::fmt::Display for ExecutionProcessStatus {
@@ -54,6 +56,7 @@ impl std::fmt::Display for ExecutionProcessStatus {
             ExecutionProcessStatus::Completed => write!(f, "completed"),
             ExecutionProcessStatus::Failed => write!(f, "failed"),
             ExecutionProcessStatus::Killed => write!(f, "killed"),
+            ExecutionProcessStatus::Interrupted => write!(f, "interrupted"),
         }
     }
 }
@@ -66,6 +69,8 @@ pub enum ExecutionProcessRunReason {
     CleanupScript,
     CodingAgent,
     DevServer,
+    /// One-off agent run started by a user with their own prompt, outside the column workflow
+    ManualRun,
 }
 
 impl std::fmt::Display for ExecutionProcessRunReason {
@@ -75,6 +80,7 @@ impl std::fmt::Display for ExecutionProcessRunReason {
             ExecutionProcessRunReason::CleanupScript => write!(f, "cleanupscript"),
             ExecutionProcessRunReason::CodingAgent => write!(f, "codingagent"),
             ExecutionProcessRunReason::DevServer => write!(f, "devserver"),
+            ExecutionProcessRunReason::ManualRun => write!(f, "manualrun"),
         }
     }
 }
@@ -141,6 +147,20 @@ pub struct MissingBeforeContext {
     pub repo_path: Option<String>,
 }
 
+/// An interrupted coding agent run that can continue from its agent session
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct InterruptedSession {
+    pub execution_process_id: Uuid,
+    pub session_id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub project_id: Uuid,
+    pub agent_session_id: String,
+    #[ts(type = "Date | null")]
+    pub interrupted_at: Option<DateTime<Utc>>,
+}
+
 impl ExecutionProcess {
     /// Find execution process by ID
     pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
@@ -297,6 +317,42 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Interrupted agent runs that recorded an agent session and are still the latest
+    /// process in their session, i.e. nothing has been run since
+    pub async fn find_resumable_interrupted(
+        pool: &PgPool,
+    ) -> Result<Vec<InterruptedSession>, sqlx::Error> {
+        sqlx::query_as!(
+            InterruptedSession,
+            r#"SELECT ep.id as "execution_process_id!: Uuid",
+                      ep.session_id as "session_id!: Uuid",
+                      s.workspace_id as "workspace_id!: Uuid",
+                      t.id as "task_id!: Uuid",
+                      t.title as "task_title!",
+                      t.project_id as "project_id!: Uuid",
+                      cat.agent_session_id as "agent_session_id!",
+                      ep.completed_at as "interrupted_at: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN coding_agent_turns cat ON cat.execution_process_id = ep.id
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE ep.status = 'interrupted'
+                 AND ep.run_reason IN ('codingagent', 'manualrun')
+                 AND ep.dropped = FALSE
+                 AND cat.agent_session_id IS NOT NULL
+                 AND NOT EXISTS (
+                     SELECT 1 FROM execution_processes later
+                     WHERE later.session_id = ep.session_id
+                       AND later.dropped = FALSE
+                       AND later.created_at > ep.created_at
+                 )
+               ORDER BY ep.completed_at DESC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find running dev servers for a specific project
     pub async fn find_running_dev_servers_by_project(
         pool: &PgPool,
@@ -338,7 +394,8 @@ impl ExecutionProcess {
         Ok(count > 0)
     }
 
-    /// Check if a task has any running execution processes (coding agent, setup, or cleanup).
+    /// Check if a task has any running execution processes (coding agent, manual run, setup, or
+    /// cleanup).
     /// Matches the same logic as the has_in_progress_attempt subquery in Task::find_all_for_project.
     pub async fn has_running_processes_for_task(
         pool: &PgPool,
@@ -351,7 +408,7 @@ impl ExecutionProcess {
                JOIN workspaces w ON s.workspace_id = w.id
                WHERE w.task_id = $1
                  AND ep.status = 'running'
-                 AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent')"#,
+                 AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent', 'manualrun')"#,
             task_id
         )
         .fetch_one(pool)
@@ -406,7 +463,7 @@ impl ExecutionProcess {
                FROM execution_processes ep
                JOIN coding_agent_turns cat ON ep.id = cat.execution_process_id
                WHERE ep.session_id = $1
-                 AND ep.run_reason = 'codingagent'
+                 AND ep.run_reason IN ('codingagent', 'manualrun')
                  AND ep.dropped = FALSE
                  AND cat.agent_session_id IS NOT NULL
                ORDER BY ep.created_at DESC
@@ -451,6 +508,46 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Whether a coding agent run started on the process's task after it, in any of the
+    /// task's workspaces
+    pub async fn has_later_coding_agent_run(pool: &PgPool, id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS (
+                   SELECT 1
+                   FROM execution_processes this
+                   JOIN sessions this_s ON this.session_id = this_s.id
+                   JOIN workspaces this_w ON this_s.workspace_id = this_w.id
+                   JOIN workspaces w ON w.task_id = this_w.task_id
+                   JOIN sessions s ON s.workspace_id = w.id
+                   JOIN execution_processes ep ON ep.session_id = s.id
+                   WHERE this.id = $1
+                     AND ep.run_reason = 'codingagent'
+                     AND ep.created_at > this.created_at
+               ) as "exists!""#,
+            id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Agent the session's latest coding agent turn ran for, so follow-ups stay
+    /// attributed to it
+    pub async fn latest_agent_for_session(
+        pool: &PgPool,
+        session_id: Uuid,
+    ) -> Result<Option<AgentIdentity>, sqlx::Error> {
+        let latest = Self::find_latest_by_session_and_run_reason(
+            pool,
+            session_id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+        Ok(latest
+            .as_ref()
+            .and_then(|process| process.executor_action().ok())
+            .and_then(|action| action.agent.clone()))
+    }
+
     /// Find latest execution process by workspace and run reason (across all sessions)
     pub async fn find_latest_by_workspace_and_run_reason(
         pool: &PgPool,
@@ -503,6 +600,7 @@ impl ExecutionProcess {
             ExecutionProcessRunReason::CleanupScript => "cleanupscript",
             ExecutionProcessRunReason::CodingAgent => "codingagent",
             ExecutionProcessRunReason::DevServer => "devserver",
+            ExecutionProcessRunReason::ManualRun => "manualrun",
         };
         let status_str = "running";
 
@@ -536,6 +634,7 @@ impl ExecutionProcess {
         if let Ok(exp_process) = Self::find_by_id(pool, id).await
             && exp_process.is_some_and(|ep| {
                 ep.status == ExecutionProcessStatus::Killed
+                    || ep.status == ExecutionProcessStatus::Interrupted
                     || ep.status == ExecutionProcessStatus::Completed
             })
         {
@@ -628,6 +727,52 @@ impl ExecutionProcess {
         Ok(result.flatten())
     }
 
+    /// Most recent HEAD recorded for a repo across all of a workspace's sessions
+    pub async fn find_latest_head_commit(
+        pool: &PgPool,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+    ) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COALESCE(eprs.after_head_commit, eprs.before_head_commit) as "head_commit!"
+               FROM execution_process_repo_states eprs
+               JOIN execution_processes ep ON ep.id = eprs.execution_process_id
+               JOIN sessions s ON ep.session_id = s.id
+              WHERE s.workspace_id = $1
+                AND eprs.repo_id = $2
+                AND COALESCE(eprs.after_head_commit, eprs.before_head_commit) IS NOT NULL
+              ORDER BY ep.created_at DESC
+              LIMIT 1"#,
+            workspace_id,
+            repo_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// First process in a workspace (any session) started after the given time, ignoring
+    /// dev servers. Its before_head_commit is the workspace state at that moment.
+    pub async fn find_first_in_workspace_since(
+        pool: &PgPool,
+        workspace_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT ep.id as "id!: Uuid"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+              WHERE s.workspace_id = $1
+                AND ep.created_at >= $2
+                AND ep.run_reason != 'devserver'
+              ORDER BY ep.created_at ASC
+              LIMIT 1"#,
+            workspace_id,
+            since
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     /// Get the parent Session for this execution process
     pub async fn parent_session(&self, pool: &PgPool) -> Result<Option<Session>, sqlx::Error> {
         Session::find_by_id(pool, self.session_id).await
@@ -35,6 +35,31 @@ impl ExecutionProcessLogs {
         .await
     }
 
+    /// Delete the logs of finished executions that completed before the cutoff of their
+    /// task's retention; tasks kept forever are skipped
+    pub async fn delete_expired(
+        pool: &PgPool,
+        default_before: DateTime<Utc>,
+        short_before: DateTime<Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM execution_process_logs l
+               USING execution_processes ep
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               LEFT JOIN task_log_retention r ON r.task_id = w.task_id
+               WHERE l.execution_id = ep.id
+                 AND ep.completed_at IS NOT NULL
+                 AND COALESCE(r.retention, 'default') <> 'keep_forever'
+                 AND ep.completed_at < CASE WHEN r.retention = 'short' THEN $2 ELSE $1 END"#,
+            default_before,
+            short_before
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     /// Parse JSONL logs back into Vec<LogMsg>
     pub fn parse_logs(records: &[Self]) -> Result<Vec<LogMsg>, serde_json::Error> {
         let mut messages = Vec::new();
@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A coding agent run re-run with another executor, linked to the run it replays
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionReplay {
+    pub id: Uuid,
+    pub source_execution_process_id: Uuid,
+    pub replay_execution_process_id: Uuid,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExecutionReplay {
+    pub async fn create(
+        pool: &PgPool,
+        source_execution_process_id: Uuid,
+        replay_execution_process_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionReplay,
+            r#"INSERT INTO execution_replays
+                   (source_execution_process_id, replay_execution_process_id)
+               VALUES ($1, $2)
+               RETURNING id as "id!: Uuid",
+                         source_execution_process_id as "source_execution_process_id!: Uuid",
+                         replay_execution_process_id as "replay_execution_process_id!: Uuid",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            source_execution_process_id,
+            replay_execution_process_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Links the run takes part in, as source or as replay, oldest first
+    pub async fn find_linked(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionReplay,
+            r#"SELECT id as "id!: Uuid",
+                      source_execution_process_id as "source_execution_process_id!: Uuid",
+                      replay_execution_process_id as "replay_execution_process_id!: Uuid",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_replays
+               WHERE source_execution_process_id = $1 OR replay_execution_process_id = $1
+               ORDER BY created_at"#,
+            execution_process_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
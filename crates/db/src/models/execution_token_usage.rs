@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Token usage reported by a coding agent for one execution process
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionTokenUsage {
+    pub execution_process_id: Uuid,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Average token usage over past coding agent runs
+#[derive(Debug, Clone, Default, FromRow)]
+pub struct TokenUsageAverages {
+    pub samples: i64,
+    pub avg_input_tokens: Option<f64>,
+    pub avg_output_tokens: Option<f64>,
+}
+
+impl ExecutionTokenUsage {
+    /// Insert or replace the token usage for an execution process
+    pub async fn upsert(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+        input_tokens: i64,
+        output_tokens: i64,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionTokenUsage,
+            r#"INSERT INTO execution_token_usage (execution_process_id, input_tokens, output_tokens)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (execution_process_id) DO UPDATE
+               SET input_tokens = EXCLUDED.input_tokens,
+                   output_tokens = EXCLUDED.output_tokens
+               RETURNING execution_process_id as "execution_process_id!: Uuid",
+                         input_tokens as "input_tokens!",
+                         output_tokens as "output_tokens!",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            execution_process_id,
+            input_tokens,
+            output_tokens
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionTokenUsage,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      input_tokens as "input_tokens!",
+                      output_tokens as "output_tokens!",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_token_usage
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Average usage of runs launched by an agent (via `agent_sessions`)
+    pub async fn averages_for_agent(
+        pool: &PgPool,
+        agent_id: Uuid,
+    ) -> Result<TokenUsageAverages, sqlx::Error> {
+        sqlx::query_as!(
+            TokenUsageAverages,
+            r#"SELECT COUNT(*) AS "samples!",
+                      AVG(tu.input_tokens)::float8 AS avg_input_tokens,
+                      AVG(tu.output_tokens)::float8 AS avg_output_tokens
+               FROM execution_token_usage tu
+               JOIN execution_processes ep ON ep.id = tu.execution_process_id
+               JOIN agent_sessions s ON s.session_id = ep.session_id
+               WHERE s.agent_id = $1"#,
+            agent_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Average usage of every recorded run in a project
+    pub async fn averages_for_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<TokenUsageAverages, sqlx::Error> {
+        sqlx::query_as!(
+            TokenUsageAverages,
+            r#"SELECT COUNT(*) AS "samples!",
+                      AVG(tu.input_tokens)::float8 AS avg_input_tokens,
+                      AVG(tu.output_tokens)::float8 AS avg_output_tokens
+               FROM execution_token_usage tu
+               JOIN execution_processes ep ON ep.id = tu.execution_process_id
+               JOIN sessions se ON se.id = ep.session_id
+               JOIN workspaces w ON w.id = se.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE t.project_id = $1"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,181 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Files listed in the workflow history before the rest are summarised as a count
+const MAX_FILES_IN_HISTORY: usize = 20;
+
+/// Where a handoff note came from
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HandoffNoteSource {
+    /// Written by the agent through the `write_handoff_note` tool
+    #[default]
+    Agent,
+    /// Derived from the agent's summary, decision file and diff when it finished
+    Automatic,
+}
+
+impl HandoffNoteSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HandoffNoteSource::Agent => "agent",
+            HandoffNoteSource::Automatic => "automatic",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "agent" => Some(HandoffNoteSource::Agent),
+            "automatic" => Some(HandoffNoteSource::Automatic),
+            _ => None,
+        }
+    }
+}
+
+/// What an agent leaves for the next column when it finishes its work
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct HandoffNote {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// Column the agent was working in
+    pub column_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+    pub agent_id: Option<Uuid>,
+    /// See `HandoffNoteSource`
+    pub source: String,
+    pub summary: String,
+    pub files_touched: Vec<String>,
+    pub open_questions: Vec<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+pub struct CreateHandoffNote {
+    /// Defaults to the task's current column
+    #[serde(default)]
+    pub column_id: Option<Uuid>,
+    #[serde(default)]
+    pub workspace_id: Option<Uuid>,
+    #[serde(default)]
+    pub agent_id: Option<Uuid>,
+    pub summary: String,
+    #[serde(default)]
+    pub files_touched: Vec<String>,
+    #[serde(default)]
+    pub open_questions: Vec<String>,
+}
+
+impl HandoffNote {
+    pub async fn create(
+        pool: &PgPool,
+        task_id: Uuid,
+        source: HandoffNoteSource,
+        data: &CreateHandoffNote,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            HandoffNote,
+            r#"INSERT INTO handoff_notes
+                   (id, task_id, column_id, workspace_id, agent_id, source, summary,
+                    files_touched, open_questions)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         column_id as "column_id: Uuid",
+                         workspace_id as "workspace_id: Uuid",
+                         agent_id as "agent_id: Uuid",
+                         source as "source!",
+                         summary as "summary!",
+                         files_touched as "files_touched!",
+                         open_questions as "open_questions!",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            task_id,
+            data.column_id,
+            data.workspace_id,
+            data.agent_id,
+            source.as_str(),
+            data.summary.trim(),
+            &data.files_touched,
+            &data.open_questions
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Notes of a task, oldest first
+    pub async fn find_by_task(pool: &PgPool, task_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            HandoffNote,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      column_id as "column_id: Uuid",
+                      workspace_id as "workspace_id: Uuid",
+                      agent_id as "agent_id: Uuid",
+                      source as "source!",
+                      summary as "summary!",
+                      files_touched as "files_touched!",
+                      open_questions as "open_questions!",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM handoff_notes
+               WHERE task_id = $1
+               ORDER BY created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Whether a note was written for the task's column since `since`
+    pub async fn exists_since(
+        pool: &PgPool,
+        task_id: Uuid,
+        column_id: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS (
+                   SELECT 1 FROM handoff_notes
+                   WHERE task_id = $1
+                     AND column_id IS NOT DISTINCT FROM $2
+                     AND created_at >= $3
+               ) as "exists!""#,
+            task_id,
+            column_id,
+            since
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Markdown block for the workflow history shown to the next agent
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("\n**Handoff note:** {}\n", self.summary.trim());
+        if !self.files_touched.is_empty() {
+            let listed: Vec<String> = self
+                .files_touched
+                .iter()
+                .take(MAX_FILES_IN_HISTORY)
+                .map(|f| format!("`{f}`"))
+                .collect();
+            out.push_str(&format!("\nFiles touched: {}", listed.join(", ")));
+            if self.files_touched.len() > MAX_FILES_IN_HISTORY {
+                out.push_str(&format!(
+                    " and {} more",
+                    self.files_touched.len() - MAX_FILES_IN_HISTORY
+                ));
+            }
+            out.push('\n');
+        }
+        if !self.open_questions.is_empty() {
+            out.push_str("\nOpen questions:\n");
+            for question in &self.open_questions {
+                out.push_str(&format!("- {question}\n"));
+            }
+        }
+        out
+    }
+}
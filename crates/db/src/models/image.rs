@@ -156,6 +156,7 @@ impl Image {
         Ok(())
     }
 
+    /// Images referenced by neither a task nor an execution artifact
     pub async fn find_orphaned_images(pool: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Image,
@@ -169,7 +170,10 @@ impl Image {
                       i.updated_at as "updated_at!: DateTime<Utc>"
                FROM images i
                LEFT JOIN task_images ti ON i.id = ti.image_id
-               WHERE ti.task_id IS NULL"#
+               WHERE ti.task_id IS NULL
+                 AND NOT EXISTS (
+                     SELECT 1 FROM execution_artifacts ea WHERE ea.image_id = i.id
+                 )"#
         )
         .fetch_all(pool)
         .await
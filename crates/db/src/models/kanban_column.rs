@@ -1,10 +1,15 @@
 use chrono::{DateTime, Utc};
+use executors::{
+    actions::coding_agent_initial::PromptSection,
+    executors::BaseCodingAgent,
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Postgres, PgPool};
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::task::TaskStatus;
+use super::{security_finding::SecurityScanner, task::TaskStatus};
 
 /// A customizable Kanban column representing a task state
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
@@ -27,6 +32,35 @@ pub struct KanbanColumn {
     pub question: Option<String>,
     /// JSON array of valid answer options for the question
     pub answer_options: Option<String>,
+    /// Read-only analysis mode: code changes and commits made by the agent are discarded
+    pub read_only: bool,
+    /// Plan-only mode: the agent writes a plan for review and its code changes are
+    /// discarded; the task can't leave the column until the plan is approved
+    pub plan_only: bool,
+    /// Dry-run mode: the agent only proposes changes; nothing is committed and its
+    /// changes are saved as patches a person can apply
+    pub dry_run: bool,
+    /// Scanners run after the agent finishes (see `SecurityScanner`); empty disables the gate
+    pub security_scanners: Vec<String>,
+    /// Items a person must tick before a task can leave the column; empty disables it
+    pub checklist: Vec<String>,
+    /// Shell script run in the workspace before the column's agent starts
+    pub on_enter_script: Option<String>,
+    /// Shell script run in the workspace after the column's agent finishes; if it
+    /// fails the task stays in the column
+    pub on_exit_script: Option<String>,
+    /// Prepend an orientation of the workspace (repo layout and key files) to the
+    /// agent's prompt, so it spends fewer tool calls exploring
+    pub warm_up: bool,
+    /// Minutes a task escalated into this column may wait before it breaches the
+    /// column's response-time target; none disables SLO tracking
+    pub response_target_mins: Option<i32>,
+    /// Order of the sections of the agent's prompt (see `PromptSection`), overriding the
+    /// agent's; sections not listed are left out. Empty uses the agent's order.
+    pub prompt_sections: Vec<String>,
+    /// Executor profile variant (e.g. `OPUS`) the column's agent runs with, overriding
+    /// the agent's default variant
+    pub executor_variant: Option<String>,
     pub is_template: bool,
     pub template_group_id: Option<String>,
     #[ts(type = "Date")]
@@ -73,6 +107,27 @@ pub struct UpdateKanbanColumn {
     pub answer_options: Option<String>,
 }
 
+/// Change to a column's modes, gates, scripts or prompt overrides
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateKanbanColumnSettings {
+    pub read_only: Option<bool>,
+    pub plan_only: Option<bool>,
+    pub dry_run: Option<bool>,
+    pub security_scanners: Option<Vec<SecurityScanner>>,
+    pub checklist: Option<Vec<String>>,
+    /// Empty string clears the script
+    pub on_enter_script: Option<String>,
+    /// Empty string clears the script
+    pub on_exit_script: Option<String>,
+    pub warm_up: Option<bool>,
+    /// 0 clears the target
+    pub response_target_mins: Option<i32>,
+    /// Empty list clears the override
+    pub prompt_sections: Option<Vec<PromptSection>>,
+    /// Empty string restores the agent's default variant
+    pub executor_variant: Option<String>,
+}
+
 impl KanbanColumn {
     /// Find all columns for a board, ordered by position
     pub async fn find_by_board(
@@ -95,6 +150,17 @@ impl KanbanColumn {
                       deliverable,
                       question,
                       answer_options,
+                      read_only as "read_only!",
+                      plan_only as "plan_only!",
+                      dry_run as "dry_run!",
+                      security_scanners as "security_scanners!",
+                      checklist as "checklist!",
+                      on_enter_script,
+                      on_exit_script,
+                      warm_up as "warm_up!",
+                      response_target_mins,
+                      prompt_sections as "prompt_sections!",
+                      executor_variant,
                       is_template as "is_template!: bool",
                       template_group_id,
                       created_at as "created_at!: DateTime<Utc>",
@@ -126,6 +192,17 @@ impl KanbanColumn {
                       deliverable,
                       question,
                       answer_options,
+                      read_only as "read_only!",
+                      plan_only as "plan_only!",
+                      dry_run as "dry_run!",
+                      security_scanners as "security_scanners!",
+                      checklist as "checklist!",
+                      on_enter_script,
+                      on_exit_script,
+                      warm_up as "warm_up!",
+                      response_target_mins,
+                      prompt_sections as "prompt_sections!",
+                      executor_variant,
                       is_template as "is_template!: bool",
                       template_group_id,
                       created_at as "created_at!: DateTime<Utc>",
@@ -160,6 +237,17 @@ impl KanbanColumn {
                       deliverable,
                       question,
                       answer_options,
+                      read_only as "read_only!",
+                      plan_only as "plan_only!",
+                      dry_run as "dry_run!",
+                      security_scanners as "security_scanners!",
+                      checklist as "checklist!",
+                      on_enter_script,
+                      on_exit_script,
+                      warm_up as "warm_up!",
+                      response_target_mins,
+                      prompt_sections as "prompt_sections!",
+                      executor_variant,
                       is_template as "is_template!: bool",
                       template_group_id,
                       created_at as "created_at!: DateTime<Utc>",
@@ -194,6 +282,17 @@ impl KanbanColumn {
                       deliverable,
                       question,
                       answer_options,
+                      read_only as "read_only!",
+                      plan_only as "plan_only!",
+                      dry_run as "dry_run!",
+                      security_scanners as "security_scanners!",
+                      checklist as "checklist!",
+                      on_enter_script,
+                      on_exit_script,
+                      warm_up as "warm_up!",
+                      response_target_mins,
+                      prompt_sections as "prompt_sections!",
+                      executor_variant,
                       is_template as "is_template!: bool",
                       template_group_id,
                       created_at as "created_at!: DateTime<Utc>",
@@ -228,6 +327,17 @@ impl KanbanColumn {
                       deliverable,
                       question,
                       answer_options,
+                      read_only as "read_only!",
+                      plan_only as "plan_only!",
+                      dry_run as "dry_run!",
+                      security_scanners as "security_scanners!",
+                      checklist as "checklist!",
+                      on_enter_script,
+                      on_exit_script,
+                      warm_up as "warm_up!",
+                      response_target_mins,
+                      prompt_sections as "prompt_sections!",
+                      executor_variant,
                       is_template as "is_template!: bool",
                       template_group_id,
                       created_at as "created_at!: DateTime<Utc>",
@@ -277,6 +387,17 @@ impl KanbanColumn {
                          deliverable,
                          question,
                          answer_options,
+                         read_only as "read_only!",
+                         plan_only as "plan_only!",
+                         dry_run as "dry_run!",
+                         security_scanners as "security_scanners!",
+                         checklist as "checklist!",
+                         on_enter_script,
+                         on_exit_script,
+                         warm_up as "warm_up!",
+                         response_target_mins,
+                         prompt_sections as "prompt_sections!",
+                         executor_variant,
                          is_template as "is_template!: bool",
                          template_group_id,
                          created_at as "created_at!: DateTime<Utc>",
@@ -332,6 +453,17 @@ impl KanbanColumn {
                          deliverable,
                          question,
                          answer_options,
+                         read_only as "read_only!",
+                         plan_only as "plan_only!",
+                         dry_run as "dry_run!",
+                         security_scanners as "security_scanners!",
+                         checklist as "checklist!",
+                         on_enter_script,
+                         on_exit_script,
+                         warm_up as "warm_up!",
+                         response_target_mins,
+                         prompt_sections as "prompt_sections!",
+                         executor_variant,
                          is_template as "is_template!: bool",
                          template_group_id,
                          created_at as "created_at!: DateTime<Utc>",
@@ -408,6 +540,17 @@ impl KanbanColumn {
                          deliverable,
                          question,
                          answer_options,
+                         read_only as "read_only!",
+                         plan_only as "plan_only!",
+                         dry_run as "dry_run!",
+                         security_scanners as "security_scanners!",
+                         checklist as "checklist!",
+                         on_enter_script,
+                         on_exit_script,
+                         warm_up as "warm_up!",
+                         response_target_mins,
+                         prompt_sections as "prompt_sections!",
+                         executor_variant,
                          is_template as "is_template!: bool",
                          template_group_id,
                          created_at as "created_at!: DateTime<Utc>",
@@ -430,12 +573,195 @@ impl KanbanColumn {
         .await
     }
 
-    /// Reorder columns - update positions for all columns in a board
+    pub async fn is_read_only(pool: &PgPool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let read_only = sqlx::query_scalar!(
+            r#"SELECT read_only as "read_only!" FROM kanban_columns WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(read_only.unwrap_or(false))
+    }
+
+    pub async fn is_dry_run(pool: &PgPool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let dry_run = sqlx::query_scalar!(
+            r#"SELECT dry_run as "dry_run!" FROM kanban_columns WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(dry_run.unwrap_or(false))
+    }
+
+    /// Configured security scanners, skipping unknown names
+    pub fn scanners(&self) -> Vec<SecurityScanner> {
+        self.security_scanners
+            .iter()
+            .filter_map(|s| SecurityScanner::from_str(s))
+            .collect()
+    }
+
+    /// The column's prompt section order, if it overrides the agent's
+    pub fn prompt_section_order(&self) -> Option<Vec<PromptSection>> {
+        Some(PromptSection::parse_list(&self.prompt_sections)).filter(|s| !s.is_empty())
+    }
+
+    /// Profile the column's agent runs with: the column's variant of the agent's
+    /// executor, or the executor's default when the column has none or the executor
+    /// doesn't offer it
+    pub fn executor_profile(&self, executor: BaseCodingAgent) -> ExecutorProfileId {
+        let Some(variant) = &self.executor_variant else {
+            return ExecutorProfileId::new(executor);
+        };
+        let profile = ExecutorProfileId {
+            executor,
+            variant: Some(variant.clone()),
+        };
+        if ExecutorConfigs::get_cached().get_coding_agent(&profile).is_none() {
+            tracing::warn!(
+                "Column {} variant '{}' isn't configured for {}; using the default",
+                self.id,
+                variant,
+                executor
+            );
+            return ExecutorProfileId::new(executor);
+        }
+        profile
+    }
+
+    /// Change a column's modes, gates, scripts or prompt overrides; blank scripts and
+    /// variants are cleared
+    pub async fn update_settings(
+        pool: &PgPool,
+        id: Uuid,
+        data: &UpdateKanbanColumnSettings,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        let security_scanners = match &data.security_scanners {
+            Some(scanners) => scanners.iter().map(|s| s.as_str().to_string()).collect(),
+            None => current.security_scanners,
+        };
+        let checklist: Vec<String> = match &data.checklist {
+            Some(items) => items
+                .iter()
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect(),
+            None => current.checklist,
+        };
+        let script = |update: &Option<String>, current: Option<String>| match update {
+            Some(script) => Some(script.trim().to_string()).filter(|s| !s.is_empty()),
+            None => current,
+        };
+        let on_enter_script = script(&data.on_enter_script, current.on_enter_script);
+        let on_exit_script = script(&data.on_exit_script, current.on_exit_script);
+        let response_target_mins = match data.response_target_mins {
+            Some(mins) => Some(mins).filter(|mins| *mins > 0),
+            None => current.response_target_mins,
+        };
+        let prompt_sections: Vec<String> = match &data.prompt_sections {
+            Some(sections) => PromptSection::to_names(sections),
+            None => current.prompt_sections,
+        };
+        let executor_variant = match &data.executor_variant {
+            Some(variant) => Some(variant.trim().to_string()).filter(|v| !v.is_empty()),
+            None => current.executor_variant,
+        };
+
+        sqlx::query_as!(
+            KanbanColumn,
+            r#"UPDATE kanban_columns
+               SET read_only = $2, plan_only = $3, dry_run = $4, security_scanners = $5,
+                   checklist = $6, on_enter_script = $7, on_exit_script = $8, warm_up = $9,
+                   response_target_mins = $10, prompt_sections = $11, executor_variant = $12,
+                   updated_at = NOW()
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         board_id as "board_id!: Uuid",
+                         name,
+                         slug,
+                         position as "position!: i32",
+                         color,
+                         is_initial as "is_initial!: bool",
+                         is_terminal as "is_terminal!: bool",
+                         starts_workflow as "starts_workflow!: bool",
+                         status as "status!: TaskStatus",
+                         agent_id as "agent_id: Uuid",
+                         deliverable,
+                         question,
+                         answer_options,
+                         read_only as "read_only!",
+                         plan_only as "plan_only!",
+                         dry_run as "dry_run!",
+                         security_scanners as "security_scanners!",
+                         checklist as "checklist!",
+                         on_enter_script,
+                         on_exit_script,
+                         warm_up as "warm_up!",
+                         response_target_mins,
+                         prompt_sections as "prompt_sections!",
+                         executor_variant,
+                         is_template as "is_template!: bool",
+                         template_group_id,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.read_only.unwrap_or(current.read_only),
+            data.plan_only.unwrap_or(current.plan_only),
+            data.dry_run.unwrap_or(current.dry_run),
+            security_scanners,
+            checklist,
+            on_enter_script,
+            on_exit_script,
+            data.warm_up.unwrap_or(current.warm_up),
+            response_target_mins,
+            prompt_sections,
+            executor_variant
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Copy a column's modes, gates, scripts and prompt overrides to another column
+    /// (used when cloning/applying templates)
+    pub async fn copy_settings(
+        executor: impl Executor<'_, Database = Postgres>,
+        from_column_id: Uuid,
+        to_column_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE kanban_columns c
+               SET read_only = s.read_only,
+                   plan_only = s.plan_only,
+                   dry_run = s.dry_run,
+                   security_scanners = s.security_scanners,
+                   checklist = s.checklist,
+                   on_enter_script = s.on_enter_script,
+                   on_exit_script = s.on_exit_script,
+                   warm_up = s.warm_up,
+                   response_target_mins = s.response_target_mins,
+                   prompt_sections = s.prompt_sections,
+                   executor_variant = s.executor_variant,
+                   updated_at = NOW()
+               FROM kanban_columns s
+               WHERE s.id = $1 AND c.id = $2"#,
+            from_column_id,
+            to_column_id
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Reorder columns - renumber the positions of a board's columns in one transaction
     pub async fn reorder_board(
         pool: &PgPool,
         board_id: Uuid,
         column_ids: &[Uuid],
     ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
         for (position, column_id) in column_ids.iter().enumerate() {
             let pos = position as i32;
             sqlx::query!(
@@ -446,10 +772,10 @@ impl KanbanColumn {
                 pos,
                 board_id
             )
-            .execute(pool)
+            .execute(&mut *tx)
             .await?;
         }
-        Ok(())
+        tx.commit().await
     }
 
     /// Delete a column
@@ -610,6 +936,17 @@ impl KanbanColumn {
                       deliverable,
                       question,
                       answer_options,
+                      read_only as "read_only!",
+                      plan_only as "plan_only!",
+                      dry_run as "dry_run!",
+                      security_scanners as "security_scanners!",
+                      checklist as "checklist!",
+                      on_enter_script,
+                      on_exit_script,
+                      warm_up as "warm_up!",
+                      response_target_mins,
+                      prompt_sections as "prompt_sections!",
+                      executor_variant,
                       is_template as "is_template!: bool",
                       template_group_id,
                       created_at as "created_at!: DateTime<Utc>",
@@ -1,32 +1,86 @@
 pub mod agent;
+pub mod agent_mcp_settings;
+pub mod agent_prompt_settings;
+pub mod agent_session;
+pub mod agent_stats;
+pub mod api_token;
 pub mod automation_execution;
 pub mod automation_rule;
+pub mod background_job;
 pub mod board;
+pub mod board_layout_settings;
+pub mod board_notification_settings;
+pub mod board_share;
+pub mod board_snapshot;
+pub mod chat_approval;
 pub mod coding_agent_turn;
+pub mod column_label_rule;
 pub mod context_artifact;
+pub mod context_artifact_review;
+pub mod context_artifact_ttl;
+pub mod context_budget;
+pub mod dashboard;
+pub mod data_migration;
+pub mod dependency_update_schedule;
+pub mod dry_run_patch;
+pub mod email_notification_preference;
 pub mod evaluate_run;
+pub mod execution_artifact;
+pub mod execution_deliverable_report;
+pub mod execution_environment;
+pub mod execution_log_redaction;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod execution_process_repo_state;
+pub mod execution_replay;
+pub mod execution_token_usage;
 pub mod file_lock;
 pub mod group_event;
+pub mod handoff_note;
 pub mod image;
 pub mod kanban_column;
 pub mod merge;
+pub mod notification_channel;
+pub mod notification_channel_identity;
+pub mod plan_review;
 pub mod project;
+pub mod project_env_var;
+pub mod project_log_redaction_settings;
+pub mod project_module_memory_settings;
 pub mod project_repo;
+pub mod project_repo_script_settings;
+pub mod project_security_settings;
+pub mod project_share_sync_settings;
+pub mod project_timezone_settings;
 pub mod repo;
+pub mod repo_forge_settings;
+pub mod repo_merge_settings;
+pub mod review_comment;
+pub mod reviewer_feedback;
 pub mod scratch;
+pub mod security_finding;
 pub mod session;
+pub mod setup_script_run;
+pub mod shared_task_sync;
 pub mod skill;
 pub mod state_transition;
 pub mod tag;
 pub mod task;
+pub mod task_comment;
 pub mod task_dependency;
 pub mod task_event;
 pub mod task_group;
 pub mod task_group_dependency;
 pub mod task_label;
+pub mod task_log_retention;
+pub mod task_subscription;
 pub mod task_trigger;
+pub mod transition_artifact_requirement;
+pub mod transition_retry_policy;
+pub mod user;
 pub mod workspace;
+pub mod workspace_changed_paths;
+pub mod workspace_container;
+pub mod workspace_orientation;
 pub mod workspace_repo;
+pub mod workspace_template;
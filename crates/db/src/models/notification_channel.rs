@@ -0,0 +1,390 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Backend a notification channel delivers to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannelKind {
+    Slack,
+    Discord,
+    Http,
+    Matrix,
+    Telegram,
+}
+
+impl NotificationChannelKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationChannelKind::Slack => "slack",
+            NotificationChannelKind::Discord => "discord",
+            NotificationChannelKind::Http => "http",
+            NotificationChannelKind::Matrix => "matrix",
+            NotificationChannelKind::Telegram => "telegram",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "slack" => Some(NotificationChannelKind::Slack),
+            "discord" => Some(NotificationChannelKind::Discord),
+            "http" => Some(NotificationChannelKind::Http),
+            "matrix" => Some(NotificationChannelKind::Matrix),
+            "telegram" => Some(NotificationChannelKind::Telegram),
+            _ => None,
+        }
+    }
+}
+
+/// Events that can be routed to notification channels
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    ExecutionCompleted,
+    ExecutionFailed,
+    ExecutionKilled,
+    /// A task reached its failure limit and took an escalation transition
+    TaskEscalated,
+    /// An agent's plan is waiting for approval
+    ApprovalRequested,
+    /// A task's labels were changed by hand or by column label rules
+    LabelsChanged,
+}
+
+impl NotificationEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationEvent::ExecutionCompleted => "execution_completed",
+            NotificationEvent::ExecutionFailed => "execution_failed",
+            NotificationEvent::ExecutionKilled => "execution_killed",
+            NotificationEvent::TaskEscalated => "task_escalated",
+            NotificationEvent::ApprovalRequested => "approval_requested",
+            NotificationEvent::LabelsChanged => "labels_changed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "execution_completed" => Some(NotificationEvent::ExecutionCompleted),
+            "execution_failed" => Some(NotificationEvent::ExecutionFailed),
+            "execution_killed" => Some(NotificationEvent::ExecutionKilled),
+            "task_escalated" => Some(NotificationEvent::TaskEscalated),
+            "approval_requested" => Some(NotificationEvent::ApprovalRequested),
+            "labels_changed" => Some(NotificationEvent::LabelsChanged),
+            _ => None,
+        }
+    }
+
+    /// Urgent events are always delivered right away; the rest can be batched into a
+    /// board's digest
+    pub fn is_urgent(&self) -> bool {
+        matches!(
+            self,
+            NotificationEvent::ExecutionFailed
+                | NotificationEvent::TaskEscalated
+                | NotificationEvent::ApprovalRequested
+        )
+    }
+}
+
+/// Slack, Discord, Matrix, Telegram or HTTP webhook that receives notifications for a
+/// project or for every project linked to a remote organization
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct NotificationChannel {
+    pub id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub organization_id: Option<Uuid>,
+    pub name: String,
+    pub kind: String,
+    /// Webhook URL, or the API base URL for Matrix (homeserver) and Telegram
+    pub webhook_url: String,
+    /// Matrix room id, Telegram chat id, or Slack channel id when posting with a bot token
+    pub destination: Option<String>,
+    /// Matrix access token, or Telegram or Slack bot token; never sent to clients
+    #[serde(skip_serializing, default)]
+    #[ts(skip)]
+    pub access_token: Option<String>,
+    /// Overrides the default title; supports `{{placeholders}}`
+    pub title_template: Option<String>,
+    /// Overrides the default message; supports `{{placeholders}}`
+    pub message_template: Option<String>,
+    /// Events delivered to this channel; empty routes every event
+    pub events: Vec<String>,
+    pub enabled: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateNotificationChannel {
+    pub name: String,
+    pub kind: NotificationChannelKind,
+    /// Matrix homeserver URL; may be empty for Telegram and Slack bot-token channels
+    pub webhook_url: String,
+    /// Matrix room id, Telegram chat id, or Slack channel id when posting with a bot token
+    pub destination: Option<String>,
+    /// Matrix access token, or Telegram or Slack bot token
+    pub access_token: Option<String>,
+    pub title_template: Option<String>,
+    pub message_template: Option<String>,
+    /// Defaults to every event
+    pub events: Option<Vec<NotificationEvent>>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateNotificationChannel {
+    pub name: Option<String>,
+    pub kind: Option<NotificationChannelKind>,
+    pub webhook_url: Option<String>,
+    pub destination: Option<String>,
+    pub access_token: Option<String>,
+    /// Null clears the template, falling back to the default title
+    #[serde(default, deserialize_with = "crate::serde_helpers::deserialize_optional_nullable")]
+    #[ts(optional, type = "string | null")]
+    pub title_template: Option<Option<String>>,
+    /// Null clears the template, falling back to the default message
+    #[serde(default, deserialize_with = "crate::serde_helpers::deserialize_optional_nullable")]
+    #[ts(optional, type = "string | null")]
+    pub message_template: Option<Option<String>>,
+    pub events: Option<Vec<NotificationEvent>>,
+    pub enabled: Option<bool>,
+}
+
+fn event_names(events: &[NotificationEvent]) -> Vec<String> {
+    events.iter().map(|e| e.as_str().to_string()).collect()
+}
+
+impl NotificationChannel {
+    pub fn get_kind(&self) -> Option<NotificationChannelKind> {
+        NotificationChannelKind::from_str(&self.kind)
+    }
+
+    /// Whether the channel's routing rules accept this event
+    pub fn routes(&self, event: NotificationEvent) -> bool {
+        self.enabled && (self.events.is_empty() || self.events.iter().any(|e| e == event.as_str()))
+    }
+
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationChannel,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      organization_id as "organization_id: Uuid",
+                      name as "name!",
+                      kind as "kind!",
+                      webhook_url as "webhook_url!",
+                      destination,
+                      access_token,
+                      title_template,
+                      message_template,
+                      events as "events!",
+                      enabled as "enabled!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM notification_channels WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationChannel,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      organization_id as "organization_id: Uuid",
+                      name as "name!",
+                      kind as "kind!",
+                      webhook_url as "webhook_url!",
+                      destination,
+                      access_token,
+                      title_template,
+                      message_template,
+                      events as "events!",
+                      enabled as "enabled!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM notification_channels
+               WHERE project_id = $1
+               ORDER BY created_at"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_organization(
+        pool: &PgPool,
+        organization_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationChannel,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      organization_id as "organization_id: Uuid",
+                      name as "name!",
+                      kind as "kind!",
+                      webhook_url as "webhook_url!",
+                      destination,
+                      access_token,
+                      title_template,
+                      message_template,
+                      events as "events!",
+                      enabled as "enabled!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM notification_channels
+               WHERE organization_id = $1
+               ORDER BY created_at"#,
+            organization_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Enabled channels of the project and of its organization, if it has one
+    pub async fn find_enabled_for_project(
+        pool: &PgPool,
+        project_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationChannel,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      organization_id as "organization_id: Uuid",
+                      name as "name!",
+                      kind as "kind!",
+                      webhook_url as "webhook_url!",
+                      destination,
+                      access_token,
+                      title_template,
+                      message_template,
+                      events as "events!",
+                      enabled as "enabled!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM notification_channels
+               WHERE enabled AND (project_id = $1 OR organization_id = $2)
+               ORDER BY created_at"#,
+            project_id,
+            organization_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Create a channel scoped to either a project or an organization
+    pub async fn create(
+        pool: &PgPool,
+        project_id: Option<Uuid>,
+        organization_id: Option<Uuid>,
+        data: &CreateNotificationChannel,
+    ) -> Result<Self, sqlx::Error> {
+        let events = data.events.as_deref().map(event_names).unwrap_or_default();
+        sqlx::query_as!(
+            NotificationChannel,
+            r#"INSERT INTO notification_channels
+                   (id, project_id, organization_id, name, kind, webhook_url, destination,
+                    access_token, title_template, message_template, events, enabled)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id: Uuid",
+                         organization_id as "organization_id: Uuid",
+                         name as "name!",
+                         kind as "kind!",
+                         webhook_url as "webhook_url!",
+                         destination,
+                         access_token,
+                         title_template,
+                         message_template,
+                         events as "events!",
+                         enabled as "enabled!",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            project_id,
+            organization_id,
+            &data.name,
+            data.kind.as_str(),
+            &data.webhook_url,
+            &data.destination,
+            &data.access_token,
+            &data.title_template,
+            &data.message_template,
+            events,
+            data.enabled.unwrap_or(true)
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        data: &UpdateNotificationChannel,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        let kind = data
+            .kind
+            .map(|k| k.as_str().to_string())
+            .unwrap_or(existing.kind);
+        let events = data
+            .events
+            .as_deref()
+            .map(event_names)
+            .unwrap_or(existing.events);
+        sqlx::query_as!(
+            NotificationChannel,
+            r#"UPDATE notification_channels
+               SET name = $2, kind = $3, webhook_url = $4, destination = $5, access_token = $6,
+                   title_template = $7, message_template = $8, events = $9, enabled = $10,
+                   updated_at = NOW()
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id: Uuid",
+                         organization_id as "organization_id: Uuid",
+                         name as "name!",
+                         kind as "kind!",
+                         webhook_url as "webhook_url!",
+                         destination,
+                         access_token,
+                         title_template,
+                         message_template,
+                         events as "events!",
+                         enabled as "enabled!",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name.as_ref().unwrap_or(&existing.name),
+            kind,
+            data.webhook_url.as_ref().unwrap_or(&existing.webhook_url),
+            data.destination.clone().or(existing.destination),
+            data.access_token.clone().or(existing.access_token),
+            data.title_template.clone().unwrap_or(existing.title_template),
+            data.message_template.clone().unwrap_or(existing.message_template),
+            events,
+            data.enabled.unwrap_or(existing.enabled)
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM notification_channels WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
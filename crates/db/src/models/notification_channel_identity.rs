@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A chat user mapped to a local user, so their reactions in the channel can approve
+/// or reject on that user's behalf
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct NotificationChannelIdentity {
+    pub id: Uuid,
+    pub channel_id: Uuid,
+    /// Slack member id (`U0123ABCD`) or Matrix user id (`@alice:example.org`)
+    pub external_user_id: String,
+    pub user_id: Uuid,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateNotificationChannelIdentity {
+    pub external_user_id: String,
+    pub user_id: Uuid,
+}
+
+impl NotificationChannelIdentity {
+    pub async fn find_by_channel(
+        pool: &PgPool,
+        channel_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationChannelIdentity,
+            r#"SELECT id as "id!: Uuid",
+                      channel_id as "channel_id!: Uuid",
+                      external_user_id as "external_user_id!",
+                      user_id as "user_id!: Uuid",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM notification_channel_identities
+               WHERE channel_id = $1
+               ORDER BY created_at"#,
+            channel_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Map a chat user to a local user, replacing any earlier mapping of that chat user
+    pub async fn upsert(
+        pool: &PgPool,
+        channel_id: Uuid,
+        data: &CreateNotificationChannelIdentity,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationChannelIdentity,
+            r#"INSERT INTO notification_channel_identities
+                   (channel_id, external_user_id, user_id)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (channel_id, external_user_id) DO UPDATE
+               SET user_id = EXCLUDED.user_id
+               RETURNING id as "id!: Uuid",
+                         channel_id as "channel_id!: Uuid",
+                         external_user_id as "external_user_id!",
+                         user_id as "user_id!: Uuid",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            channel_id,
+            data.external_user_id.trim(),
+            data.user_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &PgPool, channel_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM notification_channel_identities WHERE id = $1 AND channel_id = $2",
+            id,
+            channel_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,205 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, TS, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PlanReviewStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// The plan written by a plan-only agent run, and its review
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct PlanReview {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// Session of the plan-only run
+    pub session_id: Uuid,
+    /// Plan-only column the run belongs to; `None` for manual runs
+    pub column_id: Option<Uuid>,
+    /// Contents of the plan file once the run has finished
+    pub plan: Option<String>,
+    pub status: PlanReviewStatus,
+    pub reviewer: Option<String>,
+    pub comment: Option<String>,
+    #[ts(type = "Date | null")]
+    pub reviewed_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct ReviewPlan {
+    /// Who reviewed the plan
+    pub reviewer: Option<String>,
+    pub comment: Option<String>,
+}
+
+impl PlanReview {
+    /// Record that a session is a plan-only run
+    pub async fn create(
+        pool: &PgPool,
+        task_id: Uuid,
+        session_id: Uuid,
+        column_id: Option<Uuid>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            PlanReview,
+            r#"INSERT INTO plan_reviews (id, task_id, session_id, column_id)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         session_id as "session_id!: Uuid",
+                         column_id as "column_id: Uuid",
+                         plan,
+                         status as "status!: PlanReviewStatus",
+                         reviewer,
+                         comment,
+                         reviewed_at as "reviewed_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            task_id,
+            session_id,
+            column_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PlanReview,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      session_id as "session_id!: Uuid",
+                      column_id as "column_id: Uuid",
+                      plan,
+                      status as "status!: PlanReviewStatus",
+                      reviewer,
+                      comment,
+                      reviewed_at as "reviewed_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM plan_reviews WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_session_id(
+        pool: &PgPool,
+        session_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PlanReview,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      session_id as "session_id!: Uuid",
+                      column_id as "column_id: Uuid",
+                      plan,
+                      status as "status!: PlanReviewStatus",
+                      reviewer,
+                      comment,
+                      reviewed_at as "reviewed_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM plan_reviews WHERE session_id = $1"#,
+            session_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// A task's plans, newest first
+    pub async fn find_by_task_id(pool: &PgPool, task_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PlanReview,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      session_id as "session_id!: Uuid",
+                      column_id as "column_id: Uuid",
+                      plan,
+                      status as "status!: PlanReviewStatus",
+                      reviewer,
+                      comment,
+                      reviewed_at as "reviewed_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM plan_reviews
+               WHERE task_id = $1
+               ORDER BY created_at DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The task's latest plan, or its latest plan written in `column_id`
+    pub async fn find_latest(
+        pool: &PgPool,
+        task_id: Uuid,
+        column_id: Option<Uuid>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PlanReview,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      session_id as "session_id!: Uuid",
+                      column_id as "column_id: Uuid",
+                      plan,
+                      status as "status!: PlanReviewStatus",
+                      reviewer,
+                      comment,
+                      reviewed_at as "reviewed_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM plan_reviews
+               WHERE task_id = $1 AND ($2::UUID IS NULL OR column_id = $2)
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            task_id,
+            column_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn set_plan(pool: &PgPool, session_id: Uuid, plan: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!("UPDATE plan_reviews SET plan = $2 WHERE session_id = $1", session_id, plan)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn review(
+        pool: &PgPool,
+        id: Uuid,
+        status: PlanReviewStatus,
+        data: &ReviewPlan,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            PlanReview,
+            r#"UPDATE plan_reviews
+               SET status = $2, reviewer = $3, comment = $4, reviewed_at = NOW()
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         session_id as "session_id!: Uuid",
+                         column_id as "column_id: Uuid",
+                         plan,
+                         status as "status!: PlanReviewStatus",
+                         reviewer,
+                         comment,
+                         reviewed_at as "reviewed_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            status as _,
+            &data.reviewer,
+            &data.comment
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Postgres, PgPool};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::project_repo::CreateProjectRepo;
@@ -17,7 +18,38 @@ pub enum ProjectError {
     CreateFailed(String),
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+/// Image used for Docker workspaces when the project doesn't set one
+pub const DEFAULT_DOCKER_IMAGE: &str = "node:22-bookworm";
+
+/// Where a project's agents and scripts run
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionBackend {
+    /// Git worktrees on the host
+    #[default]
+    Local,
+    /// Repos cloned into a per-workspace Docker container
+    Docker,
+}
+
+impl ExecutionBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionBackend::Local => "local",
+            ExecutionBackend::Docker => "docker",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "local" => Some(ExecutionBackend::Local),
+            "docker" => Some(ExecutionBackend::Docker),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct Project {
     pub id: Uuid,
     pub name: String,
@@ -34,20 +66,55 @@ pub struct Project {
     /// Persistent workspace for the PreReq Evaluator agent (PreReq Eval column)
     pub prereq_eval_workspace_id: Option<Uuid>,
     pub ready_locked: bool,
+    /// Tasks created without a column start in the board's initial column
+    pub auto_assign_initial_column: bool,
+    /// `local` or `docker` (see `ExecutionBackend`)
+    pub execution_backend: String,
+    /// Image for Docker workspaces; needs git and the agents' CLIs
+    pub docker_image: Option<String>,
+    /// Container ports published on the host's loopback interface at the same number
+    pub docker_ports: Vec<i32>,
+    /// Template for new workspace branches, e.g. `{agent}/{task_id}/{slug}`; None uses
+    /// `{prefix}/{short_id}-{slug}`
+    pub branch_template: Option<String>,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Deserialize, TS)]
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
 pub struct CreateProject {
     pub name: String,
     pub repositories: Vec<CreateProjectRepo>,
     pub board_id: Option<Uuid>,
+    /// Template board whose columns, transitions and agent assignments are cloned
+    /// into a new board for the project; takes precedence over `board_id`
+    #[serde(default)]
+    pub workflow_template_id: Option<Uuid>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectBoardSettings {
+    pub auto_assign_initial_column: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectExecutionSettings {
+    pub execution_backend: Option<ExecutionBackend>,
+    /// Empty string falls back to the default image
+    pub docker_image: Option<String>,
+    /// Replaces the published ports
+    pub docker_ports: Option<Vec<u16>>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectBranchSettings {
+    /// Empty string restores the default template
+    pub branch_template: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct UpdateProject {
     pub name: Option<String>,
     pub dev_script: Option<String>,
@@ -71,6 +138,22 @@ pub enum SearchMatchType {
 }
 
 impl Project {
+    /// Unknown values are treated as `local`
+    pub fn backend(&self) -> ExecutionBackend {
+        ExecutionBackend::from_str(&self.execution_backend).unwrap_or_default()
+    }
+
+    pub fn image(&self) -> &str {
+        self.docker_image.as_deref().unwrap_or(DEFAULT_DOCKER_IMAGE)
+    }
+
+    pub fn ports(&self) -> Vec<u16> {
+        self.docker_ports
+            .iter()
+            .filter_map(|port| u16::try_from(*port).ok())
+            .collect()
+    }
+
     pub async fn count(pool: &PgPool) -> Result<i64, sqlx::Error> {
         sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!: i64" FROM projects"#)
             .fetch_one(pool)
@@ -92,6 +175,11 @@ impl Project {
                       group_evaluator_workspace_id as "group_evaluator_workspace_id: Uuid",
                       prereq_eval_workspace_id as "prereq_eval_workspace_id: Uuid",
                       ready_locked as "ready_locked!: bool",
+                      auto_assign_initial_column as "auto_assign_initial_column!",
+                      execution_backend as "execution_backend!",
+                      docker_image,
+                      docker_ports as "docker_ports!",
+                      branch_template,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -115,6 +203,11 @@ impl Project {
                    p.group_evaluator_workspace_id as "group_evaluator_workspace_id: Uuid",
                    p.prereq_eval_workspace_id as "prereq_eval_workspace_id: Uuid",
                    p.ready_locked as "ready_locked!: bool",
+                   p.auto_assign_initial_column as "auto_assign_initial_column!",
+                   p.execution_backend as "execution_backend!",
+                   p.docker_image,
+                   p.docker_ports as "docker_ports!",
+                   p.branch_template,
                    p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>"
             FROM projects p
             WHERE p.id IN (
@@ -146,6 +239,11 @@ impl Project {
                       group_evaluator_workspace_id as "group_evaluator_workspace_id: Uuid",
                       prereq_eval_workspace_id as "prereq_eval_workspace_id: Uuid",
                       ready_locked as "ready_locked!: bool",
+                      auto_assign_initial_column as "auto_assign_initial_column!",
+                      execution_backend as "execution_backend!",
+                      docker_image,
+                      docker_ports as "docker_ports!",
+                      branch_template,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -173,6 +271,11 @@ impl Project {
                       group_evaluator_workspace_id as "group_evaluator_workspace_id: Uuid",
                       prereq_eval_workspace_id as "prereq_eval_workspace_id: Uuid",
                       ready_locked as "ready_locked!: bool",
+                      auto_assign_initial_column as "auto_assign_initial_column!",
+                      execution_backend as "execution_backend!",
+                      docker_image,
+                      docker_ports as "docker_ports!",
+                      branch_template,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM (
@@ -204,6 +307,11 @@ impl Project {
                       group_evaluator_workspace_id as "group_evaluator_workspace_id: Uuid",
                       prereq_eval_workspace_id as "prereq_eval_workspace_id: Uuid",
                       ready_locked as "ready_locked!: bool",
+                      auto_assign_initial_column as "auto_assign_initial_column!",
+                      execution_backend as "execution_backend!",
+                      docker_image,
+                      docker_ports as "docker_ports!",
+                      branch_template,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -240,6 +348,11 @@ impl Project {
                           group_evaluator_workspace_id as "group_evaluator_workspace_id: Uuid",
                           prereq_eval_workspace_id as "prereq_eval_workspace_id: Uuid",
                           ready_locked as "ready_locked!: bool",
+                          auto_assign_initial_column as "auto_assign_initial_column!",
+                          execution_backend as "execution_backend!",
+                          docker_image,
+                          docker_ports as "docker_ports!",
+                          branch_template,
                           created_at as "created_at!: DateTime<Utc>",
                           updated_at as "updated_at!: DateTime<Utc>""#,
             project_id,
@@ -281,6 +394,11 @@ impl Project {
                          group_evaluator_workspace_id as "group_evaluator_workspace_id: Uuid",
                          prereq_eval_workspace_id as "prereq_eval_workspace_id: Uuid",
                          ready_locked as "ready_locked!: bool",
+                         auto_assign_initial_column as "auto_assign_initial_column!",
+                         execution_backend as "execution_backend!",
+                         docker_image,
+                         docker_ports as "docker_ports!",
+                         branch_template,
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -358,7 +476,7 @@ impl Project {
 
     /// Update the board_id for a project
     pub async fn update_board_id(
-        pool: &PgPool,
+        executor: impl Executor<'_, Database = Postgres>,
         id: Uuid,
         board_id: Uuid,
     ) -> Result<(), sqlx::Error> {
@@ -367,7 +485,7 @@ impl Project {
             id,
             board_id
         )
-        .execute(pool)
+        .execute(executor)
         .await?;
         Ok(())
     }
@@ -443,4 +561,87 @@ impl Project {
         .await?;
         Ok(())
     }
+
+    /// Turn initial-column auto-assignment on or off for a project
+    pub async fn update_board_settings(
+        pool: &PgPool,
+        id: Uuid,
+        data: &UpdateProjectBoardSettings,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE projects
+               SET auto_assign_initial_column = COALESCE($2, auto_assign_initial_column),
+                   updated_at = NOW()
+               WHERE id = $1"#,
+            id,
+            data.auto_assign_initial_column,
+        )
+        .execute(pool)
+        .await?;
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Switch the project's backend, Docker image or published ports; a blank image
+    /// restores the default
+    pub async fn update_execution_settings(
+        pool: &PgPool,
+        id: Uuid,
+        data: &UpdateProjectExecutionSettings,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        let execution_backend = data.execution_backend.unwrap_or(current.backend());
+        let docker_image = match &data.docker_image {
+            Some(image) => Some(image.trim().to_string()).filter(|image| !image.is_empty()),
+            None => current.docker_image,
+        };
+        let docker_ports: Vec<i32> = match &data.docker_ports {
+            Some(ports) => ports.iter().map(|port| i32::from(*port)).collect(),
+            None => current.docker_ports,
+        };
+        sqlx::query!(
+            r#"UPDATE projects
+               SET execution_backend = $2,
+                   docker_image = $3,
+                   docker_ports = $4,
+                   updated_at = NOW()
+               WHERE id = $1"#,
+            id,
+            execution_backend.as_str(),
+            docker_image,
+            &docker_ports
+        )
+        .execute(pool)
+        .await?;
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Set or clear the project's branch template; blank templates are cleared
+    pub async fn update_branch_settings(
+        pool: &PgPool,
+        id: Uuid,
+        data: &UpdateProjectBranchSettings,
+    ) -> Result<Self, sqlx::Error> {
+        if let Some(template) = &data.branch_template {
+            let branch_template = Some(template.trim()).filter(|t| !t.is_empty());
+            sqlx::query!(
+                r#"UPDATE projects
+                   SET branch_template = $2,
+                       updated_at = NOW()
+                   WHERE id = $1"#,
+                id,
+                branch_template
+            )
+            .execute(pool)
+            .await?;
+        }
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
 }
@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An encrypted environment variable scoped to a project.
+/// The ciphertext and nonce are never serialized to API clients.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectEnvVar {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub key: String,
+    #[serde(skip_serializing, default)]
+    #[ts(skip)]
+    pub value_ciphertext: String,
+    #[serde(skip_serializing, default)]
+    #[ts(skip)]
+    pub value_nonce: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for creating a project env var (plaintext value, encrypted before storage)
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateProjectEnvVar {
+    pub key: String,
+    pub value: String,
+}
+
+/// Request body for replacing the value of an existing env var
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateProjectEnvVar {
+    pub value: String,
+}
+
+impl ProjectEnvVar {
+    /// Find all env vars for a project, ordered by key
+    pub async fn find_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"
+            SELECT id as "id!: Uuid",
+                   project_id as "project_id!: Uuid",
+                   key as "key!",
+                   value_ciphertext as "value_ciphertext!",
+                   value_nonce as "value_nonce!",
+                   created_at as "created_at!: DateTime<Utc>",
+                   updated_at as "updated_at!: DateTime<Utc>"
+            FROM project_env_vars
+            WHERE project_id = $1
+            ORDER BY key ASC
+            "#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Find a single env var by project and key
+    pub async fn find_by_key(
+        pool: &PgPool,
+        project_id: Uuid,
+        key: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"
+            SELECT id as "id!: Uuid",
+                   project_id as "project_id!: Uuid",
+                   key as "key!",
+                   value_ciphertext as "value_ciphertext!",
+                   value_nonce as "value_nonce!",
+                   created_at as "created_at!: DateTime<Utc>",
+                   updated_at as "updated_at!: DateTime<Utc>"
+            FROM project_env_vars
+            WHERE project_id = $1 AND key = $2
+            "#,
+            project_id,
+            key
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Insert a new env var. Fails on duplicate (project_id, key).
+    pub async fn create(
+        pool: &PgPool,
+        project_id: Uuid,
+        key: &str,
+        value_ciphertext: &str,
+        value_nonce: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"
+            INSERT INTO project_env_vars (id, project_id, key, value_ciphertext, value_nonce)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      key as "key!",
+                      value_ciphertext as "value_ciphertext!",
+                      value_nonce as "value_nonce!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            project_id,
+            key,
+            value_ciphertext,
+            value_nonce
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Replace the encrypted value of an existing env var
+    pub async fn update_value(
+        pool: &PgPool,
+        project_id: Uuid,
+        key: &str,
+        value_ciphertext: &str,
+        value_nonce: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"
+            UPDATE project_env_vars
+            SET value_ciphertext = $3, value_nonce = $4, updated_at = NOW()
+            WHERE project_id = $1 AND key = $2
+            RETURNING id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      key as "key!",
+                      value_ciphertext as "value_ciphertext!",
+                      value_nonce as "value_nonce!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            key,
+            value_ciphertext,
+            value_nonce
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Delete an env var. Returns true if a row was removed.
+    pub async fn delete(pool: &PgPool, project_id: Uuid, key: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_env_vars WHERE project_id = $1 AND key = $2",
+            project_id,
+            key
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// How a project's execution logs are redacted (defaults apply when no row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectLogRedactionSettings {
+    pub project_id: Uuid,
+    /// Matches of the AWS key and GitHub token patterns that are left as they are, e.g.
+    /// documented example keys. Env vault values are always redacted.
+    pub allowlist: Vec<String>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectLogRedactionSettings {
+    /// Replaces the whole allowlist
+    pub allowlist: Option<Vec<String>>,
+}
+
+impl ProjectLogRedactionSettings {
+    fn defaults(project_id: Uuid) -> Self {
+        Self {
+            project_id,
+            allowlist: Vec::new(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Redaction allowlist of a project; empty when none is stored
+    pub async fn for_project(pool: &PgPool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            ProjectLogRedactionSettings,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      allowlist as "allowlist!",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_log_redaction_settings
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(project_id)))
+    }
+
+    /// Replace the project's redaction allowlist
+    pub async fn update(
+        pool: &PgPool,
+        project_id: Uuid,
+        data: &UpdateProjectLogRedactionSettings,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::for_project(pool, project_id).await?;
+        let allowlist = match &data.allowlist {
+            Some(allowlist) => allowlist
+                .iter()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .collect(),
+            None => current.allowlist,
+        };
+        sqlx::query_as!(
+            ProjectLogRedactionSettings,
+            r#"INSERT INTO project_log_redaction_settings (project_id, allowlist)
+               VALUES ($1, $2)
+               ON CONFLICT (project_id) DO UPDATE
+               SET allowlist = EXCLUDED.allowlist,
+                   updated_at = NOW()
+               RETURNING project_id as "project_id!: Uuid",
+                         allowlist as "allowlist!",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            allowlist
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
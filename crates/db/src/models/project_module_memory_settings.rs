@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Whether merges refresh a project's module memories (defaults apply when no row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectModuleMemorySettings {
+    pub project_id: Uuid,
+    /// Start an agent run after each merge that rewrites the memories of the modules
+    /// the merged branch touched
+    pub refresh_after_merge: bool,
+    /// Most modules one refresh covers; the modules with the most changed files win
+    pub max_modules: i32,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectModuleMemorySettings {
+    pub refresh_after_merge: Option<bool>,
+    pub max_modules: Option<i32>,
+}
+
+impl ProjectModuleMemorySettings {
+    pub const DEFAULT_MAX_MODULES: i32 = 10;
+
+    fn defaults(project_id: Uuid) -> Self {
+        Self {
+            project_id,
+            refresh_after_merge: false,
+            max_modules: Self::DEFAULT_MAX_MODULES,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Module memory settings of a project; refreshes are off when none are stored
+    pub async fn for_project(pool: &PgPool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            ProjectModuleMemorySettings,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      refresh_after_merge as "refresh_after_merge!",
+                      max_modules as "max_modules!",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_module_memory_settings
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(project_id)))
+    }
+
+    /// Change whether merges refresh module memories and how many modules a refresh covers
+    pub async fn update(
+        pool: &PgPool,
+        project_id: Uuid,
+        data: &UpdateProjectModuleMemorySettings,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::for_project(pool, project_id).await?;
+        sqlx::query_as!(
+            ProjectModuleMemorySettings,
+            r#"INSERT INTO project_module_memory_settings
+                   (project_id, refresh_after_merge, max_modules)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (project_id) DO UPDATE
+               SET refresh_after_merge = EXCLUDED.refresh_after_merge,
+                   max_modules = EXCLUDED.max_modules,
+                   updated_at = NOW()
+               RETURNING project_id as "project_id!: Uuid",
+                         refresh_after_merge as "refresh_after_merge!",
+                         max_modules as "max_modules!",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.refresh_after_merge.unwrap_or(current.refresh_after_merge),
+            data.max_modules.unwrap_or(current.max_modules)
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
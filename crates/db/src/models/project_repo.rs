@@ -1,10 +1,12 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use chrono::{DateTime, Utc};
+use executors::actions::script::ScriptRequestLanguage;
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, PgPool};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::repo::Repo;
@@ -30,7 +32,8 @@ pub struct ProjectRepo {
     pub parallel_setup_script: bool,
 }
 
-/// ProjectRepo with the associated repo name (for script execution in worktrees)
+/// ProjectRepo with the associated repo name and script settings (for script
+/// execution in worktrees)
 #[derive(Debug, Clone, FromRow)]
 pub struct ProjectRepoWithName {
     pub id: Uuid,
@@ -41,9 +44,58 @@ pub struct ProjectRepoWithName {
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
     pub parallel_setup_script: bool,
+    pub script_language: Option<String>,
+    pub windows_setup_script: Option<String>,
+    pub windows_cleanup_script: Option<String>,
+    pub windows_script_language: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, TS)]
+impl ProjectRepoWithName {
+    /// Pick the script to run: on Windows the Windows variant when there is one
+    /// (PowerShell unless set otherwise), else the regular script in its language
+    /// (the platform shell unless set otherwise)
+    fn script_for(
+        &self,
+        script: &Option<String>,
+        windows_script: &Option<String>,
+        windows: bool,
+    ) -> Option<(String, ScriptRequestLanguage)> {
+        if windows && let Some(script) = windows_script {
+            let language = self
+                .windows_script_language
+                .as_deref()
+                .and_then(ScriptRequestLanguage::from_str)
+                .unwrap_or(ScriptRequestLanguage::PowerShell);
+            return Some((script.clone(), language));
+        }
+        let language = self.script_request_language();
+        script.clone().map(|script| (script, language))
+    }
+
+    /// Language of the repo's regular scripts (the platform shell unless set otherwise)
+    pub fn script_request_language(&self) -> ScriptRequestLanguage {
+        self.script_language
+            .as_deref()
+            .and_then(ScriptRequestLanguage::from_str)
+            .unwrap_or(ScriptRequestLanguage::Bash)
+    }
+
+    /// Setup script for the platform the server runs on, with its language
+    pub fn platform_setup_script(&self) -> Option<(String, ScriptRequestLanguage)> {
+        self.script_for(&self.setup_script, &self.windows_setup_script, cfg!(windows))
+    }
+
+    /// Cleanup script for the platform the server runs on, with its language
+    pub fn platform_cleanup_script(&self) -> Option<(String, ScriptRequestLanguage)> {
+        self.script_for(
+            &self.cleanup_script,
+            &self.windows_cleanup_script,
+            cfg!(windows),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
 pub struct CreateProjectRepo {
     pub display_name: String,
     pub git_repo_path: String,
@@ -114,9 +166,14 @@ impl ProjectRepo {
                       pr.setup_script,
                       pr.cleanup_script,
                       pr.copy_files,
-                      pr.parallel_setup_script as "parallel_setup_script!: bool"
+                      pr.parallel_setup_script as "parallel_setup_script!: bool",
+                      ss.script_language,
+                      ss.windows_setup_script,
+                      ss.windows_cleanup_script,
+                      ss.windows_script_language
                FROM project_repos pr
                JOIN repos r ON r.id = pr.repo_id
+               LEFT JOIN project_repo_script_settings ss ON ss.project_repo_id = pr.id
                WHERE pr.project_id = $1
                ORDER BY r.display_name ASC"#,
             project_id
@@ -125,6 +182,50 @@ impl ProjectRepo {
         .await
     }
 
+    /// Default target branches of a project's repos, for the repos that have one
+    pub async fn find_default_target_branches(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<HashMap<Uuid, String>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT repo_id as "repo_id!: Uuid",
+                      default_target_branch as "default_target_branch!"
+               FROM project_repos
+               WHERE project_id = $1 AND default_target_branch IS NOT NULL"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.repo_id, row.default_target_branch))
+            .collect())
+    }
+
+    /// Set or clear the branch attempts target in a repo when none is requested
+    pub async fn set_default_target_branch(
+        pool: &PgPool,
+        project_id: Uuid,
+        repo_id: Uuid,
+        branch: Option<&str>,
+    ) -> Result<(), ProjectRepoError> {
+        let result = sqlx::query!(
+            r#"UPDATE project_repos
+               SET default_target_branch = $1
+               WHERE project_id = $2 AND repo_id = $3"#,
+            branch,
+            project_id,
+            repo_id
+        )
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ProjectRepoError::NotFound);
+        }
+        Ok(())
+    }
+
     pub async fn find_repos_for_project(
         pool: &PgPool,
         project_id: Uuid,
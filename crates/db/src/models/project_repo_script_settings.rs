@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use executors::actions::script::ScriptRequestLanguage;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Script languages and Windows scripts of a project repo (defaults apply when no row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectRepoScriptSettings {
+    pub project_repo_id: Uuid,
+    /// Language of the setup and cleanup scripts (see `ScriptRequestLanguage`); none runs
+    /// them in the platform shell
+    pub script_language: Option<String>,
+    /// Setup script run instead of the regular one when the server runs on Windows
+    pub windows_setup_script: Option<String>,
+    /// Cleanup script run instead of the regular one when the server runs on Windows
+    pub windows_cleanup_script: Option<String>,
+    /// Language of the Windows scripts; none means PowerShell
+    pub windows_script_language: Option<String>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectRepoScriptSettings {
+    pub script_language: Option<ScriptRequestLanguage>,
+    pub windows_setup_script: Option<String>,
+    pub windows_cleanup_script: Option<String>,
+    pub windows_script_language: Option<ScriptRequestLanguage>,
+}
+
+fn non_empty(script: &Option<String>) -> Option<&str> {
+    script.as_deref().filter(|s| !s.trim().is_empty())
+}
+
+impl ProjectRepoScriptSettings {
+    fn defaults(project_repo_id: Uuid) -> Self {
+        Self {
+            project_repo_id,
+            script_language: None,
+            windows_setup_script: None,
+            windows_cleanup_script: None,
+            windows_script_language: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Script settings of a project repo; platform shell and no Windows scripts when none
+    /// are stored
+    pub async fn for_project_repo(
+        pool: &PgPool,
+        project_repo_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            ProjectRepoScriptSettings,
+            r#"SELECT project_repo_id as "project_repo_id!: Uuid",
+                      script_language,
+                      windows_setup_script,
+                      windows_cleanup_script,
+                      windows_script_language,
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_repo_script_settings
+               WHERE project_repo_id = $1"#,
+            project_repo_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(project_repo_id)))
+    }
+
+    /// Replace a project repo's script languages and Windows scripts; empty scripts are
+    /// cleared
+    pub async fn update(
+        pool: &PgPool,
+        project_repo_id: Uuid,
+        data: &UpdateProjectRepoScriptSettings,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectRepoScriptSettings,
+            r#"INSERT INTO project_repo_script_settings
+                   (project_repo_id, script_language, windows_setup_script,
+                    windows_cleanup_script, windows_script_language)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (project_repo_id) DO UPDATE
+               SET script_language = EXCLUDED.script_language,
+                   windows_setup_script = EXCLUDED.windows_setup_script,
+                   windows_cleanup_script = EXCLUDED.windows_cleanup_script,
+                   windows_script_language = EXCLUDED.windows_script_language,
+                   updated_at = NOW()
+               RETURNING project_repo_id as "project_repo_id!: Uuid",
+                         script_language,
+                         windows_setup_script,
+                         windows_cleanup_script,
+                         windows_script_language,
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_repo_id,
+            data.script_language.map(|l| l.as_str()),
+            non_empty(&data.windows_setup_script),
+            non_empty(&data.windows_cleanup_script),
+            data.windows_script_language.map(|l| l.as_str())
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
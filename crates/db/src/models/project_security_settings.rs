@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// How a project's security gate treats scanners that can't run (defaults apply when no
+/// row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectSecuritySettings {
+    pub project_id: Uuid,
+    /// Record a scanner that is missing or fails as informational instead of blocking
+    pub allow_scanner_errors: bool,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectSecuritySettings {
+    pub allow_scanner_errors: Option<bool>,
+}
+
+impl ProjectSecuritySettings {
+    fn defaults(project_id: Uuid) -> Self {
+        Self {
+            project_id,
+            allow_scanner_errors: false,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Security settings of a project; scanner errors block the gate when none are stored
+    pub async fn for_project(pool: &PgPool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            ProjectSecuritySettings,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      allow_scanner_errors as "allow_scanner_errors!",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_security_settings
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(project_id)))
+    }
+
+    /// Set whether scanner errors are allowed, keeping the stored value for omitted fields
+    pub async fn update(
+        pool: &PgPool,
+        project_id: Uuid,
+        data: &UpdateProjectSecuritySettings,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::for_project(pool, project_id).await?;
+        let allow_scanner_errors = data
+            .allow_scanner_errors
+            .unwrap_or(current.allow_scanner_errors);
+        sqlx::query_as!(
+            ProjectSecuritySettings,
+            r#"INSERT INTO project_security_settings (project_id, allow_scanner_errors)
+               VALUES ($1, $2)
+               ON CONFLICT (project_id) DO UPDATE SET
+                   allow_scanner_errors = EXCLUDED.allow_scanner_errors,
+                   updated_at = NOW()
+               RETURNING project_id as "project_id!: Uuid",
+                         allow_scanner_errors as "allow_scanner_errors!",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            allow_scanner_errors
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
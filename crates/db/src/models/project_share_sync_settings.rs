@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// How edits made to a shared task on both sides since the last sync are settled
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareConflictStrategy {
+    /// The side edited most recently wins each conflicting field; the conflict is
+    /// recorded on the task's timeline
+    #[default]
+    LastWriterWins,
+    /// The task stops syncing until someone picks a side
+    Manual,
+}
+
+impl ShareConflictStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShareConflictStrategy::LastWriterWins => "last_writer_wins",
+            ShareConflictStrategy::Manual => "manual",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "last_writer_wins" => Some(ShareConflictStrategy::LastWriterWins),
+            "manual" => Some(ShareConflictStrategy::Manual),
+            _ => None,
+        }
+    }
+}
+
+/// Shared task sync settings of a project (defaults apply when no row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectShareSyncSettings {
+    pub project_id: Uuid,
+    /// See `ShareConflictStrategy`
+    pub conflict_strategy: String,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectShareSyncSettings {
+    pub conflict_strategy: Option<ShareConflictStrategy>,
+}
+
+impl ProjectShareSyncSettings {
+    fn defaults(project_id: Uuid) -> Self {
+        Self {
+            project_id,
+            conflict_strategy: ShareConflictStrategy::default().as_str().to_string(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn strategy(&self) -> ShareConflictStrategy {
+        ShareConflictStrategy::from_str(&self.conflict_strategy).unwrap_or_default()
+    }
+
+    /// Shared task sync settings of a project; the default conflict strategy when none are
+    /// stored
+    pub async fn for_project(pool: &PgPool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            ProjectShareSyncSettings,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      conflict_strategy as "conflict_strategy!",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_share_sync_settings
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(project_id)))
+    }
+
+    /// Change how the project settles conflicting shared task edits
+    pub async fn update(
+        pool: &PgPool,
+        project_id: Uuid,
+        data: &UpdateProjectShareSyncSettings,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::for_project(pool, project_id).await?;
+        let strategy = data.conflict_strategy.unwrap_or_else(|| current.strategy());
+        sqlx::query_as!(
+            ProjectShareSyncSettings,
+            r#"INSERT INTO project_share_sync_settings (project_id, conflict_strategy)
+               VALUES ($1, $2)
+               ON CONFLICT (project_id) DO UPDATE
+               SET conflict_strategy = EXCLUDED.conflict_strategy,
+                   updated_at = NOW()
+               RETURNING project_id as "project_id!: Uuid",
+                         conflict_strategy as "conflict_strategy!",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            strategy.as_str()
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,112 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Timezone a project's digests and daily snapshots are evaluated in (defaults apply
+/// when no row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectTimezoneSettings {
+    pub project_id: Uuid,
+    /// IANA timezone name, e.g. `Europe/Berlin`
+    pub timezone: String,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectTimezoneSettings {
+    pub timezone: Option<String>,
+}
+
+impl ProjectTimezoneSettings {
+    pub const DEFAULT_TIMEZONE: &'static str = "UTC";
+
+    fn defaults(project_id: Uuid) -> Self {
+        Self {
+            project_id,
+            timezone: Self::DEFAULT_TIMEZONE.to_string(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Timezone of a project; UTC when none is stored
+    pub async fn for_project(pool: &PgPool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            ProjectTimezoneSettings,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      timezone as "timezone!",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_timezone_settings
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(project_id)))
+    }
+
+    /// Timezone of a board: that of its oldest project with a timezone set, or UTC
+    pub async fn for_board(pool: &PgPool, board_id: Uuid) -> Result<String, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COALESCE((SELECT z.timezone FROM project_timezone_settings z
+                                JOIN projects p ON p.id = z.project_id
+                                WHERE p.board_id = $1
+                                ORDER BY p.created_at LIMIT 1), 'UTC') as "timezone!""#,
+            board_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Whether Postgres knows the timezone name
+    pub async fn is_known(pool: &PgPool, timezone: &str) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM pg_timezone_names WHERE name = $1) as "known!""#,
+            timezone
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Wall-clock time of `instant` in `timezone`. Postgres' timezone database applies
+    /// the UTC offset in effect at that instant, so DST changes are accounted for.
+    pub async fn local_time(
+        pool: &PgPool,
+        timezone: &str,
+        instant: DateTime<Utc>,
+    ) -> Result<NaiveDateTime, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT $1::timestamptz AT TIME ZONE $2 as "local_time!""#,
+            instant,
+            timezone
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Change the project's timezone. Callers check the timezone with `is_known` first.
+    pub async fn update(
+        pool: &PgPool,
+        project_id: Uuid,
+        data: &UpdateProjectTimezoneSettings,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::for_project(pool, project_id).await?;
+        sqlx::query_as!(
+            ProjectTimezoneSettings,
+            r#"INSERT INTO project_timezone_settings (project_id, timezone)
+               VALUES ($1, $2)
+               ON CONFLICT (project_id) DO UPDATE
+               SET timezone = EXCLUDED.timezone,
+                   updated_at = NOW()
+               RETURNING project_id as "project_id!: Uuid",
+                         timezone as "timezone!",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.timezone.clone().unwrap_or(current.timezone)
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Where a repository's pull requests, status checks and issues live
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ForgeKind {
+    /// GitHub through the `gh` CLI
+    #[default]
+    Github,
+    /// A Gitea or Forgejo instance through its REST API
+    Gitea,
+    /// gitlab.com or a self-managed GitLab; merge requests count as pull requests
+    Gitlab,
+    /// Bitbucket Cloud
+    Bitbucket,
+}
+
+impl ForgeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ForgeKind::Github => "github",
+            ForgeKind::Gitea => "gitea",
+            ForgeKind::Gitlab => "gitlab",
+            ForgeKind::Bitbucket => "bitbucket",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "github" => Some(ForgeKind::Github),
+            "gitea" => Some(ForgeKind::Gitea),
+            "gitlab" => Some(ForgeKind::Gitlab),
+            "bitbucket" => Some(ForgeKind::Bitbucket),
+            _ => None,
+        }
+    }
+}
+
+/// Forge settings for a repository (GitHub when no row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct RepoForgeSettings {
+    pub repo_id: Uuid,
+    /// See `ForgeKind`
+    pub forge: String,
+    /// Root URL of the Gitea/Forgejo or self-managed GitLab instance, e.g.
+    /// `https://codeberg.org`; unused for GitHub and Bitbucket
+    pub base_url: Option<String>,
+    /// Whether an API token is stored; the token itself is never returned
+    pub has_token: bool,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub token_ciphertext: Option<String>,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub token_nonce: Option<String>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateRepoForgeSettings {
+    pub forge: Option<ForgeKind>,
+    /// Empty string clears the URL
+    pub base_url: Option<String>,
+    /// Stored encrypted; empty string removes the stored token, as does a change of
+    /// forge or instance URL without a new one
+    pub api_token: Option<String>,
+}
+
+impl RepoForgeSettings {
+    fn defaults(repo_id: Uuid) -> Self {
+        Self {
+            repo_id,
+            forge: ForgeKind::default().as_str().to_string(),
+            base_url: None,
+            has_token: false,
+            token_ciphertext: None,
+            token_nonce: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Settings for a repo, falling back to GitHub
+    pub async fn for_repo(pool: &PgPool, repo_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            RepoForgeSettings,
+            r#"SELECT repo_id as "repo_id!: Uuid",
+                      forge as "forge!",
+                      base_url,
+                      token_ciphertext IS NOT NULL as "has_token!",
+                      token_ciphertext,
+                      token_nonce,
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM repo_forge_settings
+               WHERE repo_id = $1"#,
+            repo_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(repo_id)))
+    }
+
+    /// Configured forge, falling back to GitHub for unknown values
+    pub fn kind(&self) -> ForgeKind {
+        ForgeKind::from_str(&self.forge).unwrap_or_default()
+    }
+
+    /// Change the repo's forge, instance URL or token. `sealed_token` is the
+    /// `(ciphertext, nonce)` of `data.api_token` when that is a new, non-empty token. A
+    /// stored token is only kept while the forge and instance URL stay the same, so it's
+    /// never sent to a host it wasn't entered for.
+    pub async fn update(
+        pool: &PgPool,
+        repo_id: Uuid,
+        data: &UpdateRepoForgeSettings,
+        sealed_token: Option<(String, String)>,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::for_repo(pool, repo_id).await?;
+        let forge = data.forge.unwrap_or(current.kind());
+        let base_url = match data.base_url.as_deref().map(str::trim) {
+            Some("") => None,
+            Some(url) => Some(url.trim_end_matches('/').to_string()),
+            None => current.base_url.clone(),
+        };
+        let same_host = forge == current.kind() && base_url == current.base_url;
+        let (token_ciphertext, token_nonce) = match (data.api_token.as_deref(), sealed_token) {
+            (Some(_), Some((ciphertext, nonce))) => (Some(ciphertext), Some(nonce)),
+            (None, _) if same_host => (current.token_ciphertext, current.token_nonce),
+            _ => (None, None),
+        };
+        sqlx::query_as!(
+            RepoForgeSettings,
+            r#"INSERT INTO repo_forge_settings
+                   (repo_id, forge, base_url, token_ciphertext, token_nonce)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (repo_id) DO UPDATE
+               SET forge = EXCLUDED.forge,
+                   base_url = EXCLUDED.base_url,
+                   token_ciphertext = EXCLUDED.token_ciphertext,
+                   token_nonce = EXCLUDED.token_nonce,
+                   updated_at = NOW()
+               RETURNING repo_id as "repo_id!: Uuid",
+                         forge as "forge!",
+                         base_url,
+                         token_ciphertext IS NOT NULL as "has_token!",
+                         token_ciphertext,
+                         token_nonce,
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            repo_id,
+            forge.as_str(),
+            base_url,
+            token_ciphertext,
+            token_nonce
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,121 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// How a task branch lands on its target branch
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// One commit with all of the branch's changes
+    #[default]
+    Squash,
+    /// A merge commit that keeps the branch's commits
+    MergeCommit,
+}
+
+impl MergeStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MergeStrategy::Squash => "squash",
+            MergeStrategy::MergeCommit => "merge_commit",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "squash" => Some(MergeStrategy::Squash),
+            "merge_commit" => Some(MergeStrategy::MergeCommit),
+            _ => None,
+        }
+    }
+}
+
+/// Merge rules for a repository (defaults apply when no row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct RepoMergeSettings {
+    pub repo_id: Uuid,
+    /// Refuse to merge unless the task branch is rebased onto the target branch
+    pub require_clean_rebase: bool,
+    /// See `MergeStrategy`
+    pub merge_strategy: String,
+    /// Delete the task branch and its worktree after a successful merge
+    pub delete_branch_after_merge: bool,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateRepoMergeSettings {
+    pub require_clean_rebase: Option<bool>,
+    pub merge_strategy: Option<MergeStrategy>,
+    pub delete_branch_after_merge: Option<bool>,
+}
+
+impl RepoMergeSettings {
+    fn defaults(repo_id: Uuid) -> Self {
+        Self {
+            repo_id,
+            require_clean_rebase: true,
+            merge_strategy: MergeStrategy::default().as_str().to_string(),
+            delete_branch_after_merge: false,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Merge rules of a repo; clean rebases required and squash merges when none are stored
+    pub async fn for_repo(pool: &PgPool, repo_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            RepoMergeSettings,
+            r#"SELECT repo_id as "repo_id!: Uuid",
+                      require_clean_rebase as "require_clean_rebase!",
+                      merge_strategy as "merge_strategy!",
+                      delete_branch_after_merge as "delete_branch_after_merge!",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM repo_merge_settings
+               WHERE repo_id = $1"#,
+            repo_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(repo_id)))
+    }
+
+    /// Configured strategy, falling back to squash for unknown values
+    pub fn strategy(&self) -> MergeStrategy {
+        MergeStrategy::from_str(&self.merge_strategy).unwrap_or_default()
+    }
+
+    /// Change the repo's rebase requirement, merge strategy or branch cleanup
+    pub async fn update(
+        pool: &PgPool,
+        repo_id: Uuid,
+        data: &UpdateRepoMergeSettings,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::for_repo(pool, repo_id).await?;
+        let merge_strategy = data.merge_strategy.unwrap_or(current.strategy());
+        sqlx::query_as!(
+            RepoMergeSettings,
+            r#"INSERT INTO repo_merge_settings
+                   (repo_id, require_clean_rebase, merge_strategy, delete_branch_after_merge)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (repo_id) DO UPDATE
+               SET require_clean_rebase = EXCLUDED.require_clean_rebase,
+                   merge_strategy = EXCLUDED.merge_strategy,
+                   delete_branch_after_merge = EXCLUDED.delete_branch_after_merge,
+                   updated_at = NOW()
+               RETURNING repo_id as "repo_id!: Uuid",
+                         require_clean_rebase as "require_clean_rebase!",
+                         merge_strategy as "merge_strategy!",
+                         delete_branch_after_merge as "delete_branch_after_merge!",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            repo_id,
+            data.require_clean_rebase.unwrap_or(current.require_clean_rebase),
+            merge_strategy.as_str(),
+            data.delete_branch_after_merge.unwrap_or(current.delete_branch_after_merge)
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
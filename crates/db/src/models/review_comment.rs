@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Side of the diff a review comment's line number refers to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewCommentSide {
+    Old,
+    New,
+}
+
+impl ReviewCommentSide {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewCommentSide::Old => "old",
+            ReviewCommentSide::New => "new",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "old" => Some(ReviewCommentSide::Old),
+            "new" => Some(ReviewCommentSide::New),
+            _ => None,
+        }
+    }
+}
+
+/// Inline comment on a file/line of a workspace's diff
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ReviewComment {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub repo_id: Uuid,
+    pub file_path: String,
+    pub line_number: i32,
+    pub side: String,
+    pub body: String,
+    /// When the comment was sent to the agent as rejection feedback; null while pending
+    #[ts(type = "Date | null")]
+    pub sent_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateReviewComment {
+    pub repo_id: Uuid,
+    pub file_path: String,
+    pub line_number: i32,
+    /// Defaults to the new side
+    pub side: Option<ReviewCommentSide>,
+    pub body: String,
+}
+
+impl ReviewComment {
+    pub fn get_side(&self) -> ReviewCommentSide {
+        ReviewCommentSide::from_str(&self.side).unwrap_or(ReviewCommentSide::New)
+    }
+
+    pub async fn find_by_workspace(
+        pool: &PgPool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewComment,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      file_path as "file_path!",
+                      line_number as "line_number!",
+                      side as "side!",
+                      body as "body!",
+                      sent_at as "sent_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM review_comments
+               WHERE workspace_id = $1
+               ORDER BY created_at"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Comments not yet sent to the agent, ordered by file and line
+    pub async fn find_pending(pool: &PgPool, workspace_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewComment,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      file_path as "file_path!",
+                      line_number as "line_number!",
+                      side as "side!",
+                      body as "body!",
+                      sent_at as "sent_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM review_comments
+               WHERE workspace_id = $1 AND sent_at IS NULL
+               ORDER BY repo_id, file_path, line_number, created_at"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        workspace_id: Uuid,
+        data: &CreateReviewComment,
+    ) -> Result<Self, sqlx::Error> {
+        let side = data.side.unwrap_or(ReviewCommentSide::New);
+        sqlx::query_as!(
+            ReviewComment,
+            r#"INSERT INTO review_comments
+                   (id, workspace_id, repo_id, file_path, line_number, side, body)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         repo_id as "repo_id!: Uuid",
+                         file_path as "file_path!",
+                         line_number as "line_number!",
+                         side as "side!",
+                         body as "body!",
+                         sent_at as "sent_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            workspace_id,
+            data.repo_id,
+            &data.file_path,
+            data.line_number,
+            side.as_str(),
+            &data.body
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &PgPool, workspace_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM review_comments WHERE id = $1 AND workspace_id = $2",
+            id,
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn mark_sent(pool: &PgPool, ids: &[Uuid]) -> Result<(), sqlx::Error> {
+        sqlx::query!("UPDATE review_comments SET sent_at = NOW() WHERE id = ANY($1)", ids)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Who gave reviewer feedback
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewerFeedbackSource {
+    /// A reviewing agent wrote `feedback` to its decision file
+    Agent,
+    /// A person rejected the work from the review panel
+    Human,
+}
+
+impl ReviewerFeedbackSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewerFeedbackSource::Agent => "agent",
+            ReviewerFeedbackSource::Human => "human",
+        }
+    }
+}
+
+/// Feedback from a reviewer for the implementer's next run on a task
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ReviewerFeedback {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// Counts up from 1 for each piece of feedback on the task
+    pub version: i32,
+    /// Column the work was reviewed in
+    pub column_id: Option<Uuid>,
+    /// Execution whose work was rejected
+    pub execution_process_id: Option<Uuid>,
+    /// See `ReviewerFeedbackSource`
+    pub source: String,
+    pub feedback: String,
+    /// Set once the implementer has run with this feedback and the task moved on
+    #[ts(type = "Date | null")]
+    pub addressed_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateReviewerFeedback {
+    pub column_id: Option<Uuid>,
+    pub execution_process_id: Option<Uuid>,
+    pub source: ReviewerFeedbackSource,
+    pub feedback: String,
+}
+
+impl ReviewerFeedback {
+    /// Record feedback as the task's next version
+    pub async fn create(
+        pool: &PgPool,
+        task_id: Uuid,
+        data: &CreateReviewerFeedback,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewerFeedback,
+            r#"INSERT INTO reviewer_feedback
+                   (id, task_id, version, column_id, execution_process_id, source, feedback)
+               SELECT $1, $2, COALESCE(MAX(version), 0) + 1, $3, $4, $5, $6
+               FROM reviewer_feedback
+               WHERE task_id = $2
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         version as "version!",
+                         column_id as "column_id: Uuid",
+                         execution_process_id as "execution_process_id: Uuid",
+                         source as "source!",
+                         feedback as "feedback!",
+                         addressed_at as "addressed_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            task_id,
+            data.column_id,
+            data.execution_process_id,
+            data.source.as_str(),
+            data.feedback.trim()
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Feedback on a task, newest version first
+    pub async fn find_by_task(pool: &PgPool, task_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewerFeedback,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      version as "version!",
+                      column_id as "column_id: Uuid",
+                      execution_process_id as "execution_process_id: Uuid",
+                      source as "source!",
+                      feedback as "feedback!",
+                      addressed_at as "addressed_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM reviewer_feedback
+               WHERE task_id = $1
+               ORDER BY version DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The latest feedback on a task that hasn't been addressed yet
+    pub async fn find_pending(pool: &PgPool, task_id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewerFeedback,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      version as "version!",
+                      column_id as "column_id: Uuid",
+                      execution_process_id as "execution_process_id: Uuid",
+                      source as "source!",
+                      feedback as "feedback!",
+                      addressed_at as "addressed_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM reviewer_feedback
+               WHERE task_id = $1 AND addressed_at IS NULL
+               ORDER BY version DESC
+               LIMIT 1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Mark a task's pending feedback given before `before` as addressed
+    pub async fn mark_addressed(
+        pool: &PgPool,
+        task_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE reviewer_feedback SET addressed_at = NOW()
+             WHERE task_id = $1 AND addressed_at IS NULL AND created_at < $2",
+            task_id,
+            before
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
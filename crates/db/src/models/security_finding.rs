@@ -0,0 +1,168 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Scanners the security gate can run in a workspace
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityScanner {
+    CargoAudit,
+    NpmAudit,
+    Gitleaks,
+}
+
+impl SecurityScanner {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecurityScanner::CargoAudit => "cargo_audit",
+            SecurityScanner::NpmAudit => "npm_audit",
+            SecurityScanner::Gitleaks => "gitleaks",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "cargo_audit" => Some(SecurityScanner::CargoAudit),
+            "npm_audit" => Some(SecurityScanner::NpmAudit),
+            "gitleaks" => Some(SecurityScanner::Gitleaks),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingSeverity {
+    Info,
+    Low,
+    Moderate,
+    High,
+    Critical,
+}
+
+impl FindingSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FindingSeverity::Info => "info",
+            FindingSeverity::Low => "low",
+            FindingSeverity::Moderate => "moderate",
+            FindingSeverity::High => "high",
+            FindingSeverity::Critical => "critical",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "info" => Some(FindingSeverity::Info),
+            "low" => Some(FindingSeverity::Low),
+            "moderate" | "medium" => Some(FindingSeverity::Moderate),
+            "high" => Some(FindingSeverity::High),
+            "critical" => Some(FindingSeverity::Critical),
+            _ => None,
+        }
+    }
+
+    /// High and critical findings block the success transition
+    pub fn is_blocking(&self) -> bool {
+        *self >= FindingSeverity::High
+    }
+}
+
+/// A finding reported by a security scanner for one execution
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct SecurityFinding {
+    pub id: Uuid,
+    pub execution_process_id: Uuid,
+    pub scanner: String,
+    pub severity: String,
+    pub title: String,
+    /// Affected package or file (e.g. "openssl 0.10.55", "src/config.rs:12")
+    pub location: Option<String>,
+    pub details: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateSecurityFinding {
+    pub scanner: SecurityScanner,
+    pub severity: FindingSeverity,
+    pub title: String,
+    pub location: Option<String>,
+    pub details: Option<String>,
+}
+
+impl SecurityFinding {
+    pub fn get_severity(&self) -> Option<FindingSeverity> {
+        FindingSeverity::from_str(&self.severity)
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+        data: &CreateSecurityFinding,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            SecurityFinding,
+            r#"INSERT INTO security_findings
+                   (id, execution_process_id, scanner, severity, title, location, details)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         execution_process_id as "execution_process_id!: Uuid",
+                         scanner as "scanner!",
+                         severity as "severity!",
+                         title as "title!",
+                         location,
+                         details,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            execution_process_id,
+            data.scanner.as_str(),
+            data.severity.as_str(),
+            &data.title,
+            &data.location,
+            &data.details
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SecurityFinding,
+            r#"SELECT id as "id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      scanner as "scanner!",
+                      severity as "severity!",
+                      title as "title!",
+                      location,
+                      details,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM security_findings
+               WHERE execution_process_id = $1
+               ORDER BY created_at ASC"#,
+            execution_process_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace previous findings (e.g. when the gate is re-run for an execution)
+    pub async fn delete_by_execution_process_id(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM security_findings WHERE execution_process_id = $1",
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
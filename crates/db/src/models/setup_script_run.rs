@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::project_repo::ProjectRepo;
+
+/// Recent first attempts considered when judging whether a setup script flaps
+pub const FLAKY_WINDOW: usize = 10;
+
+/// Runs included in the project's setup script history
+const HISTORY_LIMIT: i64 = 50;
+
+/// Outcome of one setup script run for a project repo
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct SetupScriptRun {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub repo_id: Uuid,
+    pub execution_process_id: Uuid,
+    pub succeeded: bool,
+    pub exit_code: Option<i32>,
+    /// This run was the automatic retry of a failed run
+    pub is_retry: bool,
+    /// Set on a failed run that was retried
+    pub retry_execution_process_id: Option<Uuid>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateSetupScriptRun {
+    pub project_id: Uuid,
+    pub repo_id: Uuid,
+    pub execution_process_id: Uuid,
+    pub succeeded: bool,
+    pub exit_code: Option<i32>,
+    pub is_retry: bool,
+}
+
+/// Setup script reliability for one repo of a project
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SetupScriptHealth {
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub runs: i64,
+    pub failures: i64,
+    pub retries: i64,
+    /// Retries that passed, i.e. failures that weren't charged to the task
+    pub recovered: i64,
+    /// The script both passed and failed within its recent runs
+    pub flaky: bool,
+    /// Newest first
+    pub recent: Vec<SetupScriptRun>,
+}
+
+impl SetupScriptRun {
+    /// Whether recent first attempts (newest first) include both passes and failures
+    pub fn has_flapped(runs: &[SetupScriptRun]) -> bool {
+        let window: Vec<bool> = runs
+            .iter()
+            .filter(|r| !r.is_retry)
+            .take(FLAKY_WINDOW)
+            .map(|r| r.succeeded)
+            .collect();
+        window.contains(&true) && window.contains(&false)
+    }
+
+    pub async fn create(pool: &PgPool, data: &CreateSetupScriptRun) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            SetupScriptRun,
+            r#"INSERT INTO setup_script_runs
+                   (id, project_id, repo_id, execution_process_id, succeeded, exit_code, is_retry)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         repo_id as "repo_id!: Uuid",
+                         execution_process_id as "execution_process_id!: Uuid",
+                         succeeded as "succeeded!",
+                         exit_code,
+                         is_retry as "is_retry!",
+                         retry_execution_process_id as "retry_execution_process_id: Uuid",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            data.project_id,
+            data.repo_id,
+            data.execution_process_id,
+            data.succeeded,
+            data.exit_code,
+            data.is_retry
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Whether the execution is the automatic retry of an earlier failed run
+    pub async fn is_retry_execution(
+        pool: &PgPool,
+        execution_process_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM setup_script_runs WHERE retry_execution_process_id = $1
+               ) as "exists!""#,
+            execution_process_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_retry_execution(
+        pool: &PgPool,
+        id: Uuid,
+        retry_execution_process_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE setup_script_runs SET retry_execution_process_id = $2 WHERE id = $1",
+            id,
+            retry_execution_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Recent runs of a repo's setup script in a project, newest first
+    pub async fn find_recent(
+        pool: &PgPool,
+        project_id: Uuid,
+        repo_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SetupScriptRun,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      succeeded as "succeeded!",
+                      exit_code,
+                      is_retry as "is_retry!",
+                      retry_execution_process_id as "retry_execution_process_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM setup_script_runs
+               WHERE project_id = $1 AND repo_id = $2
+               ORDER BY created_at DESC
+               LIMIT $3"#,
+            project_id,
+            repo_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Setup script health for every project repo that has run its script
+    pub async fn health_for_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<SetupScriptHealth>, sqlx::Error> {
+        let mut health = Vec::new();
+        for repo in ProjectRepo::find_repos_for_project(pool, project_id).await? {
+            let recent = Self::find_recent(pool, project_id, repo.id, HISTORY_LIMIT).await?;
+            if recent.is_empty() {
+                continue;
+            }
+            let count =
+                |f: fn(&SetupScriptRun) -> bool| recent.iter().filter(|r| f(r)).count() as i64;
+            health.push(SetupScriptHealth {
+                repo_id: repo.id,
+                repo_name: repo.display_name,
+                runs: recent.len() as i64,
+                failures: count(|r| !r.succeeded),
+                retries: count(|r| r.is_retry),
+                recovered: count(|r| r.is_retry && r.succeeded),
+                flaky: Self::has_flapped(&recent),
+                recent,
+            });
+        }
+        Ok(health)
+    }
+}
@@ -0,0 +1,248 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::{Task, TaskStatus};
+
+/// The fields a task shares with its remote copy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct SharedTaskFields {
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+}
+
+impl From<&Task> for SharedTaskFields {
+    fn from(task: &Task) -> Self {
+        Self {
+            title: task.title.clone(),
+            description: task.description.clone(),
+            status: task.status.clone(),
+        }
+    }
+}
+
+/// What a task and its remote copy last agreed on, the base of the three-way merge
+/// that pulls remote edits back
+#[derive(Debug, Clone, FromRow)]
+pub struct SharedTaskSyncState {
+    pub task_id: Uuid,
+    pub base: Json<SharedTaskFields>,
+    /// `updated_at` of the remote copy at that point
+    pub remote_updated_at: DateTime<Utc>,
+    pub synced_at: DateTime<Utc>,
+}
+
+impl SharedTaskSyncState {
+    pub async fn find(pool: &PgPool, task_id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SharedTaskSyncState,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      base as "base!: Json<SharedTaskFields>",
+                      remote_updated_at as "remote_updated_at!: DateTime<Utc>",
+                      synced_at as "synced_at!: DateTime<Utc>"
+               FROM shared_task_sync_states
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Record that both sides now hold `base`
+    pub async fn record(
+        pool: &PgPool,
+        task_id: Uuid,
+        base: &SharedTaskFields,
+        remote_updated_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO shared_task_sync_states (task_id, base, remote_updated_at)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (task_id) DO UPDATE
+               SET base = EXCLUDED.base,
+                   remote_updated_at = EXCLUDED.remote_updated_at,
+                   synced_at = NOW()"#,
+            task_id,
+            Json(base) as _,
+            remote_updated_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Which side's values settle a conflict
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    Local,
+    Remote,
+}
+
+impl ConflictResolution {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConflictResolution::Local => "local",
+            ConflictResolution::Remote => "remote",
+        }
+    }
+}
+
+/// Shared task fields edited both locally and remotely since the last sync
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct SharedTaskConflict {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub shared_task_id: Uuid,
+    /// Names of the conflicting fields: `title`, `description` and/or `status`
+    pub fields: Vec<String>,
+    #[ts(type = "SharedTaskFields")]
+    pub local: Json<SharedTaskFields>,
+    #[ts(type = "SharedTaskFields")]
+    pub remote: Json<SharedTaskFields>,
+    /// `local` or `remote` once resolved; last-writer-wins conflicts are recorded
+    /// resolved
+    pub resolution: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date | null")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl SharedTaskConflict {
+    /// Record a conflict, open unless `resolution` is given
+    pub async fn create(
+        pool: &PgPool,
+        task_id: Uuid,
+        shared_task_id: Uuid,
+        fields: &[&str],
+        local: &SharedTaskFields,
+        remote: &SharedTaskFields,
+        resolution: Option<ConflictResolution>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            SharedTaskConflict,
+            r#"INSERT INTO shared_task_conflicts
+                   (task_id, shared_task_id, fields, local, remote, resolution, resolved_at)
+               VALUES ($1, $2, $3, $4, $5, $6, CASE WHEN $6 IS NULL THEN NULL ELSE NOW() END)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         shared_task_id as "shared_task_id!: Uuid",
+                         fields as "fields!",
+                         local as "local!: Json<SharedTaskFields>",
+                         remote as "remote!: Json<SharedTaskFields>",
+                         resolution,
+                         created_at as "created_at!: DateTime<Utc>",
+                         resolved_at as "resolved_at: DateTime<Utc>""#,
+            task_id,
+            shared_task_id,
+            fields as _,
+            Json(local) as _,
+            Json(remote) as _,
+            resolution.map(|r| r.as_str())
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SharedTaskConflict,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      shared_task_id as "shared_task_id!: Uuid",
+                      fields as "fields!",
+                      local as "local!: Json<SharedTaskFields>",
+                      remote as "remote!: Json<SharedTaskFields>",
+                      resolution,
+                      created_at as "created_at!: DateTime<Utc>",
+                      resolved_at as "resolved_at: DateTime<Utc>"
+               FROM shared_task_conflicts
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The task's unresolved conflict, if any; sync of the task pauses while one exists
+    pub async fn find_open_for_task(
+        pool: &PgPool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SharedTaskConflict,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      shared_task_id as "shared_task_id!: Uuid",
+                      fields as "fields!",
+                      local as "local!: Json<SharedTaskFields>",
+                      remote as "remote!: Json<SharedTaskFields>",
+                      resolution,
+                      created_at as "created_at!: DateTime<Utc>",
+                      resolved_at as "resolved_at: DateTime<Utc>"
+               FROM shared_task_conflicts
+               WHERE task_id = $1 AND resolved_at IS NULL"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Unresolved conflicts of a project's tasks, oldest first
+    pub async fn find_open_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SharedTaskConflict,
+            r#"SELECT c.id as "id!: Uuid",
+                      c.task_id as "task_id!: Uuid",
+                      c.shared_task_id as "shared_task_id!: Uuid",
+                      c.fields as "fields!",
+                      c.local as "local!: Json<SharedTaskFields>",
+                      c.remote as "remote!: Json<SharedTaskFields>",
+                      c.resolution,
+                      c.created_at as "created_at!: DateTime<Utc>",
+                      c.resolved_at as "resolved_at: DateTime<Utc>"
+               FROM shared_task_conflicts c
+               JOIN tasks t ON t.id = c.task_id
+               WHERE t.project_id = $1 AND c.resolved_at IS NULL
+               ORDER BY c.created_at"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Mark an open conflict resolved; None when it was already resolved
+    pub async fn resolve(
+        pool: &PgPool,
+        id: Uuid,
+        resolution: ConflictResolution,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SharedTaskConflict,
+            r#"UPDATE shared_task_conflicts
+               SET resolution = $2, resolved_at = NOW()
+               WHERE id = $1 AND resolved_at IS NULL
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         shared_task_id as "shared_task_id!: Uuid",
+                         fields as "fields!",
+                         local as "local!: Json<SharedTaskFields>",
+                         remote as "remote!: Json<SharedTaskFields>",
+                         resolution,
+                         created_at as "created_at!: DateTime<Utc>",
+                         resolved_at as "resolved_at: DateTime<Utc>""#,
+            id,
+            resolution.as_str()
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
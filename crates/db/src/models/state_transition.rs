@@ -36,7 +36,8 @@ pub struct StateTransition {
     pub escalation_column_id: Option<Uuid>,
     pub name: Option<String>,
     pub requires_confirmation: bool,
-    /// Value to match for this transition (e.g., "approve" or "reject")
+    /// Value to match for this transition (e.g., "approve" or "reject"),
+    /// or an expression over decision keys (e.g., "coverage >= 80")
     pub condition_value: Option<String>,
     /// Number of times the else path can be taken before escalation
     pub max_failures: Option<i32>,
@@ -298,6 +299,37 @@ impl StateTransition {
         .await
     }
 
+    /// All non-template transitions leaving a column, at any scope
+    pub async fn find_from_column(
+        pool: &PgPool,
+        from_column_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            StateTransition,
+            r#"SELECT id as "id!: Uuid",
+                      board_id as "board_id: Uuid",
+                      project_id as "project_id: Uuid",
+                      task_id as "task_id: Uuid",
+                      from_column_id as "from_column_id!: Uuid",
+                      to_column_id as "to_column_id!: Uuid",
+                      else_column_id as "else_column_id: Uuid",
+                      escalation_column_id as "escalation_column_id: Uuid",
+                      name,
+                      requires_confirmation as "requires_confirmation!: bool",
+                      condition_value,
+                      max_failures,
+                      is_template as "is_template!: bool",
+                      template_group_id,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM state_transitions
+               WHERE from_column_id = $1 AND is_template = FALSE
+               ORDER BY created_at ASC"#,
+            from_column_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find transitions from a specific column for a task (with hierarchy resolution)
     pub async fn find_from_column_for_task(
         pool: &PgPool,
@@ -5,9 +5,10 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, PgPool};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct Tag {
     pub id: Uuid,
     pub tag_name: String,
@@ -16,7 +17,7 @@ pub struct Tag {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct CreateTag {
     pub tag_name: String,
     pub content: String,
@@ -4,6 +4,7 @@ use serde_json::Value as JsonValue;
 use sqlx::{Executor, FromRow, Postgres, PgPool, Type};
 use strum_macros::{Display, EnumString};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::{
@@ -13,7 +14,7 @@ use super::{
 };
 
 #[derive(
-    Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display, Default,
+    Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, ToSchema, EnumString, Display, Default,
 )]
 #[sqlx(type_name = "task_status", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
@@ -29,7 +30,7 @@ pub enum TaskStatus {
 
 /// Task state within the workflow - tracks where the task is in the process
 #[derive(
-    Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display, Default,
+    Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, ToSchema, EnumString, Display, Default,
 )]
 #[sqlx(type_name = "task_state", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
@@ -46,7 +47,7 @@ pub enum TaskState {
     Transitioning,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct Task {
     pub id: Uuid,
     pub project_id: Uuid, // Foreign key to Project
@@ -60,12 +61,21 @@ pub struct Task {
     pub task_state: TaskState,
     #[sqlx(json)]
     #[ts(type = "Record<string, unknown> | null")]
+    #[schema(value_type = Option<Object>)]
     pub workflow_decisions: Option<JsonValue>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set while the task is archived; archived tasks stay out of task lists and streams
+    pub archived_at: Option<DateTime<Utc>>,
+    /// The task this one is a sub-task of
+    pub parent_task_id: Option<Uuid>,
+    /// Set while the task is handed back to a human
+    pub detached_at: Option<DateTime<Utc>>,
+    /// The user the task is assigned to
+    pub assignee_id: Option<Uuid>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct TaskWithAttemptStatus {
     #[serde(flatten)]
     #[ts(flatten)]
@@ -96,7 +106,7 @@ pub struct TaskRelationships {
     pub children: Vec<Task>,       // Tasks created from this workspace
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct CreateTask {
     pub project_id: Uuid,
     pub title: String,
@@ -149,7 +159,7 @@ impl CreateTask {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct UpdateTask {
     pub title: Option<String>,
     pub description: Option<String>,
@@ -160,6 +170,20 @@ pub struct UpdateTask {
     pub task_group_id: Option<Uuid>,
 }
 
+/// How many of a task's sub-tasks (tasks with `parent_task_id` set to it) are finished.
+/// Cancelled sub-tasks count as finished.
+#[derive(Debug, Clone, Copy, Default, FromRow, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct SubtaskProgress {
+    pub total: i64,
+    pub done: i64,
+}
+
+impl SubtaskProgress {
+    pub fn all_done(&self) -> bool {
+        self.total > 0 && self.done == self.total
+    }
+}
+
 impl Task {
     pub fn to_prompt(&self) -> String {
         if let Some(description) = self.description.as_ref().filter(|d| !d.trim().is_empty()) {
@@ -169,6 +193,11 @@ impl Task {
         }
     }
 
+    /// Detached tasks are skipped by auto-transitions and agent auto-start
+    pub fn is_detached(&self) -> bool {
+        self.detached_at.is_some()
+    }
+
     pub async fn parent_project(&self, pool: &PgPool) -> Result<Option<Project>, sqlx::Error> {
         Project::find_by_id(pool, self.project_id).await
     }
@@ -191,7 +220,7 @@ WITH latest_attempts AS (
   FROM workspaces w
   LEFT JOIN sessions s ON s.workspace_id = w.id
   LEFT JOIN execution_processes ep ON ep.session_id = s.id
-    AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+    AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','manualrun')
   WHERE w.cancelled_at IS NULL
     AND w.task_id IN (SELECT id FROM tasks WHERE project_id = $1)
   ORDER BY w.task_id, w.created_at DESC, s.created_at DESC, ep.created_at DESC
@@ -205,7 +234,7 @@ running_attempts AS (
   JOIN sessions s ON s.workspace_id = w.id
   JOIN execution_processes ep ON ep.session_id = s.id
   WHERE ep.status = 'running'
-    AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+    AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','manualrun')
     AND w.task_id IN (SELECT id FROM tasks WHERE project_id = $1)
   GROUP BY w.task_id
 )
@@ -223,6 +252,10 @@ SELECT
   t.workflow_decisions            AS "workflow_decisions: JsonValue",
   t.created_at                    AS "created_at!: DateTime<Utc>",
   t.updated_at                    AS "updated_at!: DateTime<Utc>",
+  t.archived_at                   AS "archived_at: DateTime<Utc>",
+  t.parent_task_id                AS "parent_task_id: Uuid",
+  t.detached_at                   AS "detached_at: DateTime<Utc>",
+  t.assignee_id                   AS "assignee_id: Uuid",
 
   COALESCE(CASE WHEN ra.has_running THEN 1 ELSE 0 END, 0) AS "has_in_progress_attempt!: i64",
   COALESCE(CASE WHEN la.latest_status IN ('failed','killed') THEN 1 ELSE 0 END, 0) AS "last_attempt_failed!: i64",
@@ -232,7 +265,7 @@ SELECT
 FROM tasks t
 LEFT JOIN latest_attempts la ON la.task_id = t.id
 LEFT JOIN running_attempts ra ON ra.task_id = t.id
-WHERE t.project_id = $1
+WHERE t.project_id = $1 AND t.archived_at IS NULL
 ORDER BY t.created_at DESC"#,
             project_id
         )
@@ -256,6 +289,10 @@ ORDER BY t.created_at DESC"#,
                     workflow_decisions: rec.workflow_decisions,
                     created_at: rec.created_at,
                     updated_at: rec.updated_at,
+                    archived_at: rec.archived_at,
+                    parent_task_id: rec.parent_task_id,
+                    detached_at: rec.detached_at,
+                    assignee_id: rec.assignee_id,
                 },
                 has_in_progress_attempt: rec.has_in_progress_attempt != 0,
                 last_attempt_failed: rec.last_attempt_failed != 0,
@@ -288,7 +325,11 @@ ORDER BY t.created_at DESC"#,
                 task_state as "task_state!: TaskState",
                 workflow_decisions as "workflow_decisions: JsonValue",
                 created_at as "created_at!: DateTime<Utc>",
-                updated_at as "updated_at!: DateTime<Utc>"
+                updated_at as "updated_at!: DateTime<Utc>",
+                archived_at as "archived_at: DateTime<Utc>",
+                parent_task_id as "parent_task_id: Uuid",
+                detached_at as "detached_at: DateTime<Utc>",
+                assignee_id as "assignee_id: Uuid"
                FROM tasks
                WHERE project_id = $1
                  AND task_group_id IS NULL
@@ -303,7 +344,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived_at as "archived_at: DateTime<Utc>", parent_task_id as "parent_task_id: Uuid", detached_at as "detached_at: DateTime<Utc>", assignee_id as "assignee_id: Uuid"
                FROM tasks
                WHERE id = $1"#,
             id
@@ -316,7 +357,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_group(pool: &PgPool, group_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived_at as "archived_at: DateTime<Utc>", parent_task_id as "parent_task_id: Uuid", detached_at as "detached_at: DateTime<Utc>", assignee_id as "assignee_id: Uuid"
                FROM tasks
                WHERE task_group_id = $1
                ORDER BY created_at ASC"#,
@@ -336,7 +377,7 @@ ORDER BY t.created_at DESC"#,
                 JOIN execution_processes ep ON ep.session_id = s.id
                 WHERE w.task_id = $1
                   AND ep.status = 'running'
-                  AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent')
+                  AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent', 'manualrun')
                 LIMIT 1
             ) as "exists!: bool""#,
             task_id
@@ -351,7 +392,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_rowid(pool: &PgPool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived_at as "archived_at: DateTime<Utc>", parent_task_id as "parent_task_id: Uuid", detached_at as "detached_at: DateTime<Utc>", assignee_id as "assignee_id: Uuid"
                FROM (
                    SELECT *, ROW_NUMBER() OVER (ORDER BY created_at) as rn
                    FROM tasks
@@ -372,7 +413,7 @@ ORDER BY t.created_at DESC"#,
     {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived_at as "archived_at: DateTime<Utc>", parent_task_id as "parent_task_id: Uuid", detached_at as "detached_at: DateTime<Utc>", assignee_id as "assignee_id: Uuid"
                FROM tasks
                WHERE shared_task_id = $1
                LIMIT 1"#,
@@ -385,7 +426,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_all_shared(pool: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived_at as "archived_at: DateTime<Utc>", parent_task_id as "parent_task_id: Uuid", detached_at as "detached_at: DateTime<Utc>", assignee_id as "assignee_id: Uuid"
                FROM tasks
                WHERE shared_task_id IS NOT NULL"#
         )
@@ -404,7 +445,7 @@ ORDER BY t.created_at DESC"#,
             Task,
             r#"INSERT INTO tasks (id, project_id, title, description, status, column_id, parent_workspace_id, shared_task_id, task_group_id, workflow_decisions)
                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived_at as "archived_at: DateTime<Utc>", parent_task_id as "parent_task_id: Uuid", detached_at as "detached_at: DateTime<Utc>", assignee_id as "assignee_id: Uuid""#,
             task_id,
             data.project_id,
             data.title,
@@ -436,7 +477,7 @@ ORDER BY t.created_at DESC"#,
             r#"UPDATE tasks
                SET title = $3, description = $4, status = $5, column_id = $6, parent_workspace_id = $7
                WHERE id = $1 AND project_id = $2
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived_at as "archived_at: DateTime<Utc>", parent_task_id as "parent_task_id: Uuid", detached_at as "detached_at: DateTime<Utc>", assignee_id as "assignee_id: Uuid""#,
             id,
             project_id,
             title,
@@ -490,6 +531,35 @@ ORDER BY t.created_at DESC"#,
         Ok(())
     }
 
+    /// Tasks of a project with `status` outside any column, leaving out tasks in a task
+    /// group and the hidden tasks anchoring the project's column-agent workspaces
+    pub async fn find_columnless_ids(
+        pool: &PgPool,
+        project_id: Uuid,
+        status: &TaskStatus,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT t.id as "id!: Uuid"
+               FROM tasks t
+               JOIN projects p ON p.id = t.project_id
+               WHERE t.project_id = $1
+                 AND t.status = $2
+                 AND t.column_id IS NULL
+                 AND t.task_group_id IS NULL
+                 AND NOT EXISTS (
+                     SELECT 1 FROM workspaces w
+                     WHERE w.task_id = t.id
+                       AND w.id IN (p.agent_workspace_id, p.grouper_workspace_id,
+                                    p.group_evaluator_workspace_id, p.prereq_eval_workspace_id)
+                 )
+               ORDER BY t.created_at"#,
+            project_id,
+            status.to_string()
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Update the task_group_id field for a task
     pub async fn update_task_group(
         pool: &PgPool,
@@ -665,7 +735,7 @@ ORDER BY t.created_at DESC"#,
         // Find only child tasks that have this workspace as their parent
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived_at as "archived_at: DateTime<Utc>", parent_task_id as "parent_task_id: Uuid", detached_at as "detached_at: DateTime<Utc>", assignee_id as "assignee_id: Uuid"
                FROM tasks
                WHERE parent_workspace_id = $1
                ORDER BY created_at DESC"#,
@@ -742,4 +812,139 @@ ORDER BY t.created_at DESC"#,
         // Get the workspace
         Workspace::find_by_id(pool, workspace_id).await
     }
+
+    /// Sub-tasks linked to the task through `parent_task_id`, oldest first
+    pub async fn find_subtasks(
+        pool: &PgPool,
+        parent_task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived_at as "archived_at: DateTime<Utc>", parent_task_id as "parent_task_id: Uuid", detached_at as "detached_at: DateTime<Utc>", assignee_id as "assignee_id: Uuid"
+               FROM tasks
+               WHERE parent_task_id = $1
+               ORDER BY created_at ASC"#,
+            parent_task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn set_parent_task(
+        pool: &PgPool,
+        task_id: Uuid,
+        parent_task_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE tasks SET parent_task_id = $2, updated_at = NOW() WHERE id = $1",
+            task_id,
+            parent_task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn subtask_progress(
+        pool: &PgPool,
+        parent_task_id: Uuid,
+    ) -> Result<SubtaskProgress, sqlx::Error> {
+        sqlx::query_as!(
+            SubtaskProgress,
+            r#"SELECT COUNT(*) AS "total!: i64",
+                      COUNT(*) FILTER (WHERE status IN ('done', 'cancelled')) AS "done!: i64"
+               FROM tasks
+               WHERE parent_task_id = $1"#,
+            parent_task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_detached(
+        pool: &PgPool,
+        task_id: Uuid,
+        detached: bool,
+    ) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"UPDATE tasks
+               SET detached_at = CASE WHEN $2 THEN COALESCE(detached_at, NOW()) END,
+                   updated_at = NOW()
+               WHERE id = $1
+               RETURNING detached_at as "detached_at: DateTime<Utc>""#,
+            task_id,
+            detached
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_archived(
+        pool: &PgPool,
+        task_id: Uuid,
+        archived: bool,
+    ) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"UPDATE tasks
+               SET archived_at = CASE WHEN $2 THEN COALESCE(archived_at, NOW()) END,
+                   updated_at = NOW()
+               WHERE id = $1
+               RETURNING archived_at as "archived_at: DateTime<Utc>""#,
+            task_id,
+            archived
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// The project's archived tasks, most recently archived first
+    pub async fn find_archived_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived_at as "archived_at: DateTime<Utc>", parent_task_id as "parent_task_id: Uuid", detached_at as "detached_at: DateTime<Utc>", assignee_id as "assignee_id: Uuid"
+               FROM tasks
+               WHERE project_id = $1 AND archived_at IS NOT NULL
+               ORDER BY archived_at DESC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Assign the task to a user, or unassign it with `None`
+    pub async fn set_assignee(
+        pool: &PgPool,
+        task_id: Uuid,
+        assignee_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE tasks SET assignee_id = $2, updated_at = NOW() WHERE id = $1",
+            task_id,
+            assignee_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Unarchived tasks assigned to the user across all projects, most recently updated
+    /// first
+    pub async fn find_by_assignee(
+        pool: &PgPool,
+        assignee_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", task_state as "task_state!: TaskState", workflow_decisions as "workflow_decisions: JsonValue", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived_at as "archived_at: DateTime<Utc>", parent_task_id as "parent_task_id: Uuid", detached_at as "detached_at: DateTime<Utc>", assignee_id as "assignee_id: Uuid"
+               FROM tasks
+               WHERE assignee_id = $1 AND archived_at IS NULL
+               ORDER BY updated_at DESC"#,
+            assignee_id
+        )
+        .fetch_all(pool)
+        .await
+    }
 }
@@ -0,0 +1,181 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Comment in a task's thread
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskComment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub content: String,
+    /// Append the comment to the next agent prompt, alongside reviewer feedback
+    pub visible_to_agent: bool,
+    /// Set once an agent run that started after the comment has moved the task on
+    #[ts(type = "Date | null")]
+    pub delivered_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateTaskComment {
+    pub content: String,
+    /// Defaults to false
+    pub visible_to_agent: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateTaskComment {
+    pub content: Option<String>,
+    pub visible_to_agent: Option<bool>,
+}
+
+impl TaskComment {
+    /// Comments on a task, oldest first
+    pub async fn find_by_task(pool: &PgPool, task_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      content as "content!",
+                      visible_to_agent as "visible_to_agent!",
+                      delivered_at as "delivered_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_comments
+               WHERE task_id = $1
+               ORDER BY created_at"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// A comment on the given task
+    pub async fn find_by_id(
+        pool: &PgPool,
+        task_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      content as "content!",
+                      visible_to_agent as "visible_to_agent!",
+                      delivered_at as "delivered_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_comments WHERE id = $1 AND task_id = $2"#,
+            id,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Comments the next agent run should see, oldest first
+    pub async fn find_pending_for_agent(
+        pool: &PgPool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      content as "content!",
+                      visible_to_agent as "visible_to_agent!",
+                      delivered_at as "delivered_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_comments
+               WHERE task_id = $1 AND visible_to_agent AND delivered_at IS NULL
+               ORDER BY created_at"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        task_id: Uuid,
+        data: &CreateTaskComment,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"INSERT INTO task_comments (id, task_id, content, visible_to_agent)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         content as "content!",
+                         visible_to_agent as "visible_to_agent!",
+                         delivered_at as "delivered_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            task_id,
+            data.content.trim(),
+            data.visible_to_agent.unwrap_or(false)
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Apply a partial update
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        data: &UpdateTaskComment,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"UPDATE task_comments
+               SET content = COALESCE($2, content),
+                   visible_to_agent = COALESCE($3, visible_to_agent),
+                   updated_at = NOW()
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         content as "content!",
+                         visible_to_agent as "visible_to_agent!",
+                         delivered_at as "delivered_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.content.as_deref().map(str::trim),
+            data.visible_to_agent
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_comments WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Mark a task's agent-visible comments last changed before `before` as delivered
+    pub async fn mark_delivered(
+        pool: &PgPool,
+        task_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE task_comments SET delivered_at = NOW()
+             WHERE task_id = $1 AND visible_to_agent AND delivered_at IS NULL
+               AND updated_at < $2",
+            task_id,
+            before
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
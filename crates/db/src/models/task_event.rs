@@ -6,6 +6,11 @@ use strum_macros::{Display, EnumString};
 use ts_rs::TS;
 use uuid::Uuid;
 
+use super::{
+    handoff_note::HandoffNote,
+    reviewer_feedback::{ReviewerFeedback, ReviewerFeedbackSource},
+};
+
 /// Type of task event for workflow tracking
 #[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display)]
 #[sqlx(type_name = "event_type", rename_all = "snake_case")]
@@ -39,6 +44,28 @@ pub enum TaskEventType {
     ArtifactCreated,
     /// Task state changed (queued, in_progress, transitioning, awaiting_response)
     TaskStateChange,
+    /// Workspace was reset to an earlier workflow stage
+    Rollback,
+    /// A person ticked an item of the column's checklist
+    ChecklistItemChecked,
+    /// A person unticked an item of the column's checklist
+    ChecklistItemUnchecked,
+    /// Task was handed back to a human and detached from automation
+    HandedBack,
+    /// Automation was resumed for a handed-back task
+    Reattached,
+    /// A reviewer sent the work back with feedback for the implementer
+    ReviewerFeedback,
+    /// A column's label rules added or removed labels as the task moved
+    LabelsChanged,
+    /// Files the agent left in `.vibe/artifacts/` were collected
+    ArtifactsCollected,
+    /// A workspace whose worktree directory had gone missing was recreated
+    WorkspaceRecreated,
+    /// The task and its shared copy were both edited since they last synced
+    SharedTaskConflict,
+    /// A patch was applied to the workspace and committed
+    PatchApplied,
 }
 
 /// What triggered this event
@@ -108,6 +135,23 @@ pub struct TaskEvent {
     pub created_at: DateTime<Utc>,
 }
 
+/// A checklist item being ticked or unticked, from a checklist task event
+#[derive(Debug, Clone, FromRow)]
+pub struct ChecklistTick {
+    pub item: String,
+    pub checked: bool,
+    pub actor_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A task entering a column, from a `column_enter` event
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct ColumnEntry {
+    pub task_id: Uuid,
+    pub column_id: Uuid,
+    pub entered_at: DateTime<Utc>,
+}
+
 /// Enriched task event with related entity names for display
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct TaskEventWithNames {
@@ -454,6 +498,102 @@ impl TaskEvent {
         Ok(count)
     }
 
+    /// Checklist ticks for a column since the task last entered it, oldest first
+    pub async fn find_checklist_ticks(
+        pool: &PgPool,
+        task_id: Uuid,
+        column_id: Uuid,
+    ) -> Result<Vec<ChecklistTick>, sqlx::Error> {
+        sqlx::query_as!(
+            ChecklistTick,
+            r#"SELECT metadata::jsonb->>'item' as "item!",
+                      event_type = 'checklist_item_checked' as "checked!",
+                      actor_id,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_events
+               WHERE task_id = $1
+                 AND from_column_id = $2
+                 AND event_type IN ('checklist_item_checked', 'checklist_item_unchecked')
+                 AND metadata::jsonb->>'item' IS NOT NULL
+                 AND created_at >= COALESCE(
+                     (SELECT MAX(created_at) FROM task_events
+                      WHERE task_id = $1 AND event_type = 'column_enter' AND to_column_id = $2),
+                     '-infinity'::timestamptz
+                 )
+               ORDER BY created_at ASC"#,
+            task_id,
+            column_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Every column entry of the tasks that have been on a board, ordered by task and
+    /// then oldest first. Entries into other boards' columns are included so a task's
+    /// time in a board column ends when it moves elsewhere.
+    pub async fn find_column_entries_for_board(
+        pool: &PgPool,
+        board_id: Uuid,
+    ) -> Result<Vec<ColumnEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            ColumnEntry,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      to_column_id as "column_id!: Uuid",
+                      created_at as "entered_at!: DateTime<Utc>"
+               FROM task_events
+               WHERE event_type = 'column_enter'
+                 AND to_column_id IS NOT NULL
+                 AND task_id IN (
+                     SELECT e.task_id FROM task_events e
+                     JOIN kanban_columns c ON c.id = e.to_column_id
+                     WHERE c.board_id = $1 AND e.event_type = 'column_enter'
+                 )
+               ORDER BY task_id, created_at ASC"#,
+            board_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Column entries of a task, oldest first
+    pub async fn find_column_entries_for_task(
+        pool: &PgPool,
+        task_id: Uuid,
+    ) -> Result<Vec<ColumnEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            ColumnEntry,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      to_column_id as "column_id!: Uuid",
+                      created_at as "entered_at!: DateTime<Utc>"
+               FROM task_events
+               WHERE task_id = $1
+                 AND event_type = 'column_enter'
+                 AND to_column_id IS NOT NULL
+               ORDER BY created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Count and latest time of the column entries into a board's columns; changes
+    /// whenever a task moves on the board or one of its tasks is deleted
+    pub async fn column_entries_fingerprint(
+        pool: &PgPool,
+        board_id: Uuid,
+    ) -> Result<(i64, Option<DateTime<Utc>>), sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!", MAX(e.created_at) as "latest: DateTime<Utc>"
+               FROM task_events e
+               JOIN kanban_columns c ON c.id = e.to_column_id
+               WHERE c.board_id = $1 AND e.event_type = 'column_enter'"#,
+            board_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok((row.count, row.latest))
+    }
+
     /// Build a workflow history summary for a task, showing work done in prior columns.
     /// This is used to provide context to agents about what has been accomplished.
     /// Returns a markdown-formatted string with column sections and commit history.
@@ -483,7 +623,11 @@ impl TaskEvent {
         .fetch_all(pool)
         .await?;
 
-        if events.is_empty() {
+        // Handoff notes go under the column they were written in, after its commits
+        let notes = HandoffNote::find_by_task(pool, task_id).await?;
+        let mut notes = notes.iter().peekable();
+
+        if events.is_empty() && notes.peek().is_none() {
             return Ok(String::new());
         }
 
@@ -501,6 +645,9 @@ impl TaskEvent {
                         }
                         column_commits.clear();
                     }
+                    while let Some(note) = notes.next_if(|n| n.created_at < event.created_at) {
+                        history.push_str(&note.to_markdown());
+                    }
 
                     // Start new column section
                     if let Some(ref col_name) = event.column_name {
@@ -528,6 +675,9 @@ impl TaskEvent {
                 history.push_str(&format!("- `{}`: {}\n", short_hash, msg));
             }
         }
+        for note in notes {
+            history.push_str(&note.to_markdown());
+        }
 
         // If no meaningful content was generated, return empty
         if history == "## Prior Work\n\n" {
@@ -665,6 +815,127 @@ impl CreateTaskEvent {
         }
     }
 
+    /// Create a rollback event
+    /// Records the workspace being reset to the commits of an earlier stage
+    pub fn rollback(
+        task_id: Uuid,
+        workspace_id: Uuid,
+        from_column_id: Option<Uuid>,
+        to_column_id: Uuid,
+        target_event_id: Uuid,
+        commits: JsonValue,
+    ) -> Self {
+        let metadata = serde_json::json!({
+            "target_event_id": target_event_id,
+            "commits": commits,
+        });
+        Self {
+            task_id,
+            event_type: TaskEventType::Rollback,
+            from_column_id,
+            to_column_id: Some(to_column_id),
+            workspace_id: Some(workspace_id),
+            session_id: None,
+            executor: None,
+            automation_rule_id: None,
+            trigger_type: Some(EventTriggerType::Manual),
+            commit_hash: None,
+            commit_message: None,
+            metadata: Some(metadata),
+            actor_type: Some(ActorType::User),
+            actor_id: None,
+        }
+    }
+
+    /// Create a checklist event for an item of the column the task is in
+    pub fn checklist_item(
+        task_id: Uuid,
+        column_id: Uuid,
+        item: &str,
+        checked: bool,
+        actor_id: Option<String>,
+    ) -> Self {
+        Self {
+            task_id,
+            event_type: if checked {
+                TaskEventType::ChecklistItemChecked
+            } else {
+                TaskEventType::ChecklistItemUnchecked
+            },
+            from_column_id: Some(column_id),
+            to_column_id: None,
+            workspace_id: None,
+            session_id: None,
+            executor: None,
+            automation_rule_id: None,
+            trigger_type: Some(EventTriggerType::Manual),
+            commit_hash: None,
+            commit_message: None,
+            metadata: Some(serde_json::json!({ "item": item })),
+            actor_type: Some(ActorType::User),
+            actor_id,
+        }
+    }
+
+    /// Create a handback event (or a reattach event when `detached` is false)
+    pub fn handback(
+        task_id: Uuid,
+        column_id: Option<Uuid>,
+        detached: bool,
+        actor_id: Option<String>,
+        reason: Option<String>,
+    ) -> Self {
+        Self {
+            task_id,
+            event_type: if detached {
+                TaskEventType::HandedBack
+            } else {
+                TaskEventType::Reattached
+            },
+            from_column_id: column_id,
+            to_column_id: None,
+            workspace_id: None,
+            session_id: None,
+            executor: None,
+            automation_rule_id: None,
+            trigger_type: Some(EventTriggerType::Manual),
+            commit_hash: None,
+            commit_message: None,
+            metadata: reason.map(|reason| serde_json::json!({ "reason": reason })),
+            actor_type: Some(ActorType::User),
+            actor_id,
+        }
+    }
+
+    /// Create a manual action event for a person approving or rejecting a plan
+    pub fn plan_reviewed(
+        task_id: Uuid,
+        column_id: Option<Uuid>,
+        plan_review_id: Uuid,
+        approved: bool,
+        actor_id: Option<String>,
+    ) -> Self {
+        Self {
+            task_id,
+            event_type: TaskEventType::ManualAction,
+            from_column_id: column_id,
+            to_column_id: None,
+            workspace_id: None,
+            session_id: None,
+            executor: None,
+            automation_rule_id: None,
+            trigger_type: Some(EventTriggerType::Manual),
+            commit_hash: None,
+            commit_message: None,
+            metadata: Some(serde_json::json!({
+                "action": if approved { "plan_approved" } else { "plan_rejected" },
+                "plan_review_id": plan_review_id,
+            })),
+            actor_type: Some(ActorType::User),
+            actor_id,
+        }
+    }
+
     /// Create a task created event
     pub fn task_created(task_id: Uuid, actor_type: ActorType, actor_id: Option<String>) -> Self {
         Self {
@@ -818,4 +1089,190 @@ impl CreateTaskEvent {
             actor_id: None,
         }
     }
+
+    /// Create a reviewer feedback event, shown in the task's activity feed
+    pub fn reviewer_feedback(
+        feedback: &ReviewerFeedback,
+        source: ReviewerFeedbackSource,
+        workspace_id: Option<Uuid>,
+    ) -> Self {
+        let metadata = serde_json::json!({
+            "feedback_id": feedback.id.to_string(),
+            "version": feedback.version,
+            "feedback": feedback.feedback,
+            "execution_process_id": feedback.execution_process_id.map(|id| id.to_string()),
+        });
+        Self {
+            task_id: feedback.task_id,
+            event_type: TaskEventType::ReviewerFeedback,
+            from_column_id: feedback.column_id,
+            to_column_id: None,
+            workspace_id,
+            session_id: None,
+            executor: None,
+            automation_rule_id: None,
+            trigger_type: Some(match source {
+                ReviewerFeedbackSource::Agent => EventTriggerType::Automation,
+                ReviewerFeedbackSource::Human => EventTriggerType::Manual,
+            }),
+            commit_hash: None,
+            commit_message: None,
+            metadata: Some(metadata),
+            actor_type: Some(match source {
+                ReviewerFeedbackSource::Agent => ActorType::Agent,
+                ReviewerFeedbackSource::Human => ActorType::User,
+            }),
+            actor_id: None,
+        }
+    }
+
+    /// Create an event for labels changed by the label rules of the columns a task
+    /// left and entered
+    pub fn labels_changed(
+        task_id: Uuid,
+        from_column_id: Option<Uuid>,
+        to_column_id: Option<Uuid>,
+        added: &[Uuid],
+        removed: &[Uuid],
+    ) -> Self {
+        Self {
+            task_id,
+            event_type: TaskEventType::LabelsChanged,
+            from_column_id,
+            to_column_id,
+            workspace_id: None,
+            session_id: None,
+            executor: None,
+            automation_rule_id: None,
+            trigger_type: Some(EventTriggerType::Automation),
+            commit_hash: None,
+            commit_message: None,
+            metadata: Some(serde_json::json!({ "added": added, "removed": removed })),
+            actor_type: Some(ActorType::System),
+            actor_id: None,
+        }
+    }
+
+    /// Create an event for artifacts collected from an execution
+    pub fn artifacts_collected(
+        task_id: Uuid,
+        workspace_id: Uuid,
+        execution_process_id: Uuid,
+        names: &[String],
+    ) -> Self {
+        Self {
+            task_id,
+            event_type: TaskEventType::ArtifactsCollected,
+            from_column_id: None,
+            to_column_id: None,
+            workspace_id: Some(workspace_id),
+            session_id: None,
+            executor: None,
+            automation_rule_id: None,
+            trigger_type: Some(EventTriggerType::Automation),
+            commit_hash: None,
+            commit_message: None,
+            metadata: Some(serde_json::json!({
+                "execution_process_id": execution_process_id.to_string(),
+                "count": names.len(),
+                "names": names,
+            })),
+            actor_type: Some(ActorType::Agent),
+            actor_id: None,
+        }
+    }
+
+    /// Create an event for a workspace recreated after its worktrees went missing
+    pub fn workspace_recreated(
+        task_id: Uuid,
+        workspace_id: Uuid,
+        repos: &[String],
+        restored_branches: &[String],
+    ) -> Self {
+        Self {
+            task_id,
+            event_type: TaskEventType::WorkspaceRecreated,
+            from_column_id: None,
+            to_column_id: None,
+            workspace_id: Some(workspace_id),
+            session_id: None,
+            executor: None,
+            automation_rule_id: None,
+            trigger_type: Some(EventTriggerType::System),
+            commit_hash: None,
+            commit_message: None,
+            metadata: Some(serde_json::json!({
+                "repos": repos,
+                "restored_branches": restored_branches,
+            })),
+            actor_type: Some(ActorType::System),
+            actor_id: None,
+        }
+    }
+
+    /// Create an event for a conflict between a task and its shared copy; `resolution`
+    /// is None while the conflict waits for someone to pick a side
+    pub fn shared_task_conflict(
+        task_id: Uuid,
+        conflict_id: Uuid,
+        fields: &[&str],
+        resolution: Option<&str>,
+    ) -> Self {
+        Self {
+            task_id,
+            event_type: TaskEventType::SharedTaskConflict,
+            from_column_id: None,
+            to_column_id: None,
+            workspace_id: None,
+            session_id: None,
+            executor: None,
+            automation_rule_id: None,
+            trigger_type: Some(EventTriggerType::System),
+            commit_hash: None,
+            commit_message: None,
+            metadata: Some(serde_json::json!({
+                "conflict_id": conflict_id.to_string(),
+                "fields": fields,
+                "resolution": resolution,
+            })),
+            actor_type: Some(ActorType::System),
+            actor_id: None,
+        }
+    }
+
+    /// Create an event for a patch applied to a workspace repo and committed. `source`
+    /// says where the patch came from, e.g. `dry_run` or `external`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn patch_applied(
+        task_id: Uuid,
+        workspace_id: Uuid,
+        repo_name: &str,
+        commit_hash: &str,
+        commit_message: &str,
+        source: &str,
+        source_ref: Option<&str>,
+        actor_type: ActorType,
+        actor_id: Option<String>,
+    ) -> Self {
+        Self {
+            task_id,
+            event_type: TaskEventType::PatchApplied,
+            from_column_id: None,
+            to_column_id: None,
+            workspace_id: Some(workspace_id),
+            session_id: None,
+            executor: None,
+            automation_rule_id: None,
+            trigger_type: Some(EventTriggerType::Manual),
+            commit_hash: Some(commit_hash.to_string()),
+            commit_message: Some(commit_message.to_string()),
+            metadata: Some(serde_json::json!({
+                "repo": repo_name,
+                "source": source,
+                "source_ref": source_ref,
+            })),
+            actor_type: Some(actor_type),
+            actor_id,
+        }
+    }
 }
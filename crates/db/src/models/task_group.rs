@@ -433,7 +433,11 @@ impl TaskGroup {
                 task_state as "task_state!: TaskState",
                 workflow_decisions as "workflow_decisions: JsonValue",
                 created_at as "created_at!: DateTime<Utc>",
-                updated_at as "updated_at!: DateTime<Utc>"
+                updated_at as "updated_at!: DateTime<Utc>",
+                archived_at as "archived_at: DateTime<Utc>",
+                parent_task_id as "parent_task_id: Uuid",
+                detached_at as "detached_at: DateTime<Utc>",
+                assignee_id as "assignee_id: Uuid"
                FROM tasks
                WHERE task_group_id = $1
                ORDER BY created_at ASC"#,
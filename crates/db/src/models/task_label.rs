@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 /// A label that can be applied to tasks within a project
@@ -44,6 +45,17 @@ pub struct TaskLabelAssignment {
     pub created_at: DateTime<Utc>,
 }
 
+/// How a task must match several label filters
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelMatch {
+    /// The task has at least one of the labels
+    #[default]
+    Any,
+    /// The task has every label
+    All,
+}
+
 impl TaskLabel {
     /// Find all labels for a project
     pub async fn find_by_project(pool: &PgPool, project_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
@@ -208,6 +220,25 @@ impl TaskLabel {
         Ok(rows.into_iter().map(|r| r.task_id).collect())
     }
 
+    /// Tasks carrying any or all of `label_ids`
+    pub async fn find_task_ids_with_labels(
+        pool: &PgPool,
+        label_ids: &[Uuid],
+        label_match: LabelMatch,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT task_id as "task_id!: Uuid"
+               FROM task_label_assignments
+               WHERE label_id = ANY($1)
+               GROUP BY task_id
+               HAVING NOT $2 OR COUNT(DISTINCT label_id) = cardinality($1)"#,
+            label_ids,
+            label_match == LabelMatch::All
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Reorder labels within a project
     pub async fn reorder(pool: &PgPool, project_id: Uuid, label_ids: &[Uuid]) -> Result<(), sqlx::Error> {
         for (position, label_id) in label_ids.iter().enumerate() {
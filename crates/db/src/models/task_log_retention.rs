@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// How long a task's execution logs and artifacts are kept
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogRetention {
+    /// Pruned after the default retention period
+    #[default]
+    Default,
+    /// Never pruned, e.g. for compliance-relevant work
+    KeepForever,
+    /// Pruned after the short retention period, e.g. for routine chores
+    Short,
+}
+
+impl LogRetention {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogRetention::Default => "default",
+            LogRetention::KeepForever => "keep_forever",
+            LogRetention::Short => "short",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "default" => Some(LogRetention::Default),
+            "keep_forever" => Some(LogRetention::KeepForever),
+            "short" => Some(LogRetention::Short),
+            _ => None,
+        }
+    }
+}
+
+/// A task's log retention override (the default applies when no row exists)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskLogRetention {
+    pub task_id: Uuid,
+    /// See `LogRetention`
+    pub retention: String,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateTaskLogRetention {
+    pub retention: Option<LogRetention>,
+}
+
+impl TaskLogRetention {
+    fn defaults(task_id: Uuid) -> Self {
+        Self {
+            task_id,
+            retention: LogRetention::default().as_str().to_string(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn retention(&self) -> LogRetention {
+        LogRetention::from_str(&self.retention).unwrap_or_default()
+    }
+
+    /// Override for a task, falling back to the default
+    pub async fn for_task(pool: &PgPool, task_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            TaskLogRetention,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      retention as "retention!",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_log_retention
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| Self::defaults(task_id)))
+    }
+
+    /// Set the retention period of a task's logs
+    pub async fn update(
+        pool: &PgPool,
+        task_id: Uuid,
+        data: &UpdateTaskLogRetention,
+    ) -> Result<Self, sqlx::Error> {
+        let current = Self::for_task(pool, task_id).await?;
+        let retention = data.retention.unwrap_or_else(|| current.retention());
+        sqlx::query_as!(
+            TaskLogRetention,
+            r#"INSERT INTO task_log_retention (task_id, retention)
+               VALUES ($1, $2)
+               ON CONFLICT (task_id) DO UPDATE
+               SET retention = EXCLUDED.retention,
+                   updated_at = NOW()
+               RETURNING task_id as "task_id!: Uuid",
+                         retention as "retention!",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            task_id,
+            retention.as_str()
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
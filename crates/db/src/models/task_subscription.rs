@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A task, or a label, the user is watching
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskSubscription {
+    pub id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub label_id: Option<Uuid>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Watch either a task or a label
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateTaskSubscription {
+    pub task_id: Option<Uuid>,
+    pub label_id: Option<Uuid>,
+}
+
+/// An event on a watched task, with what's needed to notify about it
+#[derive(Debug, Clone, FromRow)]
+pub struct WatchedTaskEvent {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub event_type: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskSubscription {
+    pub async fn find_all(pool: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSubscription,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id: Uuid",
+                      label_id as "label_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_subscriptions ORDER BY created_at DESC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSubscription,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id: Uuid",
+                      label_id as "label_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_subscriptions WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Subscribe to a task or label. Subscribing twice returns the existing subscription.
+    pub async fn create(pool: &PgPool, data: &CreateTaskSubscription) -> Result<Self, sqlx::Error> {
+        let created = sqlx::query_as!(
+            TaskSubscription,
+            r#"INSERT INTO task_subscriptions (id, task_id, label_id)
+               VALUES ($1, $2, $3)
+               ON CONFLICT DO NOTHING
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id: Uuid",
+                         label_id as "label_id: Uuid",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            data.task_id,
+            data.label_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        if let Some(subscription) = created {
+            return Ok(subscription);
+        }
+
+        sqlx::query_as!(
+            TaskSubscription,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id: Uuid",
+                      label_id as "label_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_subscriptions
+               WHERE task_id IS NOT DISTINCT FROM $1 AND label_id IS NOT DISTINCT FROM $2"#,
+            data.task_id,
+            data.label_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_subscriptions WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Every task watched directly or through a label
+    pub async fn find_watched_task_ids(pool: &PgPool) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT task_id as "task_id!: Uuid" FROM task_subscriptions
+               WHERE task_id IS NOT NULL
+               UNION
+               SELECT tla.task_id FROM task_label_assignments tla
+               JOIN task_subscriptions ts ON ts.label_id = tla.label_id"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn watches_task(pool: &PgPool, task_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS (
+                   SELECT 1 FROM task_subscriptions WHERE task_id = $1
+                   UNION
+                   SELECT 1 FROM task_label_assignments tla
+                   JOIN task_subscriptions ts ON ts.label_id = tla.label_id
+                   WHERE tla.task_id = $1
+               ) as "exists!""#,
+            task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Events on watched tasks recorded after `since`, oldest first
+    pub async fn find_events_since(
+        pool: &PgPool,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<WatchedTaskEvent>, sqlx::Error> {
+        sqlx::query_as!(
+            WatchedTaskEvent,
+            r#"SELECT e.id as "id!: Uuid",
+                      e.task_id as "task_id!: Uuid",
+                      t.title as "task_title!",
+                      e.event_type::TEXT as "event_type!",
+                      e.created_at as "created_at!: DateTime<Utc>"
+               FROM task_events e
+               JOIN tasks t ON t.id = e.task_id
+               WHERE e.created_at > $1
+                 AND e.task_id IN (
+                     SELECT task_id FROM task_subscriptions WHERE task_id IS NOT NULL
+                     UNION
+                     SELECT tla.task_id FROM task_label_assignments tla
+                     JOIN task_subscriptions ts ON ts.label_id = tla.label_id
+                 )
+               ORDER BY e.created_at ASC"#,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
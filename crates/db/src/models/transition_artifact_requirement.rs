@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::context_artifact::ArtifactType;
+
+/// Context artifact a stage must produce before a transition's success path fires
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TransitionArtifactRequirement {
+    pub transition_id: Uuid,
+    /// Artifact type that must exist for the task (e.g. "iplan")
+    pub artifact_type: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertTransitionArtifactRequirement {
+    pub artifact_type: ArtifactType,
+}
+
+impl TransitionArtifactRequirement {
+    /// `None` for artifact types this version doesn't know about
+    pub fn get_artifact_type(&self) -> Option<ArtifactType> {
+        ArtifactType::from_str(&self.artifact_type)
+    }
+
+    pub async fn find_by_transition_id(
+        pool: &PgPool,
+        transition_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TransitionArtifactRequirement,
+            r#"SELECT transition_id as "transition_id!: Uuid",
+                      artifact_type as "artifact_type!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM transition_artifact_requirements
+               WHERE transition_id = $1"#,
+            transition_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Create or replace the artifact requirement for a transition
+    pub async fn upsert(
+        pool: &PgPool,
+        transition_id: Uuid,
+        data: &UpsertTransitionArtifactRequirement,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TransitionArtifactRequirement,
+            r#"INSERT INTO transition_artifact_requirements (transition_id, artifact_type)
+               VALUES ($1, $2)
+               ON CONFLICT (transition_id) DO UPDATE
+               SET artifact_type = EXCLUDED.artifact_type,
+                   updated_at = NOW()
+               RETURNING transition_id as "transition_id!: Uuid",
+                         artifact_type as "artifact_type!",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            transition_id,
+            data.artifact_type.as_str()
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &PgPool, transition_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM transition_artifact_requirements WHERE transition_id = $1",
+            transition_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Cheaper executor profile used when a transition keeps taking its else path
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TransitionRetryPolicy {
+    pub transition_id: Uuid,
+    /// Executor for retries (e.g. "CLAUDE_CODE"); null keeps the agent's executor
+    pub executor: Option<String>,
+    /// Profile variant for retries (e.g. "HAIKU"); null uses the default variant
+    pub variant: Option<String>,
+    /// Number of else transitions after which retries use this profile
+    pub downgrade_after: i32,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertTransitionRetryPolicy {
+    pub executor: Option<String>,
+    pub variant: Option<String>,
+    /// Defaults to 1 (downgrade from the first retry)
+    pub downgrade_after: Option<i32>,
+}
+
+impl TransitionRetryPolicy {
+    pub async fn find_by_transition_id(
+        pool: &PgPool,
+        transition_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TransitionRetryPolicy,
+            r#"SELECT transition_id as "transition_id!: Uuid",
+                      executor,
+                      variant,
+                      downgrade_after as "downgrade_after!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM transition_retry_policies
+               WHERE transition_id = $1"#,
+            transition_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Create or replace the retry policy for a transition
+    pub async fn upsert(
+        pool: &PgPool,
+        transition_id: Uuid,
+        data: &UpsertTransitionRetryPolicy,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TransitionRetryPolicy,
+            r#"INSERT INTO transition_retry_policies (transition_id, executor, variant, downgrade_after)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (transition_id) DO UPDATE
+               SET executor = EXCLUDED.executor,
+                   variant = EXCLUDED.variant,
+                   downgrade_after = EXCLUDED.downgrade_after,
+                   updated_at = NOW()
+               RETURNING transition_id as "transition_id!: Uuid",
+                         executor,
+                         variant,
+                         downgrade_after as "downgrade_after!",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            transition_id,
+            &data.executor,
+            &data.variant,
+            data.downgrade_after.unwrap_or(1)
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &PgPool, transition_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM transition_retry_policies WHERE transition_id = $1",
+            transition_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Whether a retry after `else_count` else transitions should be downgraded
+    pub fn applies_to(&self, else_count: i64) -> bool {
+        else_count >= i64::from(self.downgrade_after)
+    }
+}
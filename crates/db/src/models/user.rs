@@ -0,0 +1,219 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Someone who works on the board and can be assigned tasks
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct User {
+    pub id: Uuid,
+    /// Account id on the remote server, for users that have signed in
+    pub remote_user_id: Option<Uuid>,
+    pub username: Option<String>,
+    pub email: String,
+    pub display_name: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateUser {
+    pub email: String,
+    pub username: Option<String>,
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateUser {
+    pub email: Option<String>,
+    pub username: Option<String>,
+    pub display_name: Option<String>,
+}
+
+impl User {
+    /// Name to show for the user: display name, then username, then email
+    pub fn label(&self) -> &str {
+        self.display_name
+            .as_deref()
+            .or(self.username.as_deref())
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or(&self.email)
+    }
+
+    pub async fn find_all(pool: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid",
+                      remote_user_id as "remote_user_id: Uuid",
+                      username,
+                      email as "email!",
+                      display_name,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM users ORDER BY COALESCE(display_name, username, email)"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid",
+                      remote_user_id as "remote_user_id: Uuid",
+                      username,
+                      email as "email!",
+                      display_name,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM users WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_email(pool: &PgPool, email: &str) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid",
+                      remote_user_id as "remote_user_id: Uuid",
+                      username,
+                      email as "email!",
+                      display_name,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM users WHERE LOWER(email) = LOWER($1)"#,
+            email.trim()
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The local user of a signed-in account, created or refreshed from its profile. An
+    /// existing user with the same email (e.g. added by hand before signing in) is
+    /// linked to the account.
+    pub async fn upsert_remote(
+        pool: &PgPool,
+        remote_user_id: Uuid,
+        username: Option<&str>,
+        email: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = sqlx::query_scalar!(
+            r#"SELECT id as "id!: Uuid"
+               FROM users
+               WHERE remote_user_id = $1 OR LOWER(email) = LOWER($2)
+               ORDER BY (remote_user_id = $1) DESC NULLS LAST
+               LIMIT 1"#,
+            remote_user_id,
+            email.trim()
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        match existing {
+            Some(id) => {
+                sqlx::query_as!(
+                    User,
+                    r#"UPDATE users
+                     SET remote_user_id = $2,
+                         username = COALESCE($3, username),
+                         email = $4,
+                         updated_at = NOW()
+                     WHERE id = $1
+                     RETURNING id as "id!: Uuid",
+                               remote_user_id as "remote_user_id: Uuid",
+                               username,
+                               email as "email!",
+                               display_name,
+                               created_at as "created_at!: DateTime<Utc>",
+                               updated_at as "updated_at!: DateTime<Utc>""#,
+                    id,
+                    remote_user_id,
+                    username,
+                    email.trim()
+                )
+                .fetch_one(pool)
+                .await
+            }
+            None => {
+                sqlx::query_as!(
+                    User,
+                    r#"INSERT INTO users (id, remote_user_id, username, email)
+                     VALUES ($1, $2, $3, $4)
+                     RETURNING id as "id!: Uuid",
+                               remote_user_id as "remote_user_id: Uuid",
+                               username,
+                               email as "email!",
+                               display_name,
+                               created_at as "created_at!: DateTime<Utc>",
+                               updated_at as "updated_at!: DateTime<Utc>""#,
+                    Uuid::new_v4(),
+                    remote_user_id,
+                    username,
+                    email.trim()
+                )
+                .fetch_one(pool)
+                .await
+            }
+        }
+    }
+
+    pub async fn create(pool: &PgPool, data: &CreateUser) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"INSERT INTO users (id, email, username, display_name)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id as "id!: Uuid",
+                       remote_user_id as "remote_user_id: Uuid",
+                       username,
+                       email as "email!",
+                       display_name,
+                       created_at as "created_at!: DateTime<Utc>",
+                       updated_at as "updated_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            data.email.trim(),
+            &data.username,
+            &data.display_name
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(pool: &PgPool, id: Uuid, data: &UpdateUser) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"UPDATE users
+             SET email = COALESCE($2, email),
+                 username = COALESCE($3, username),
+                 display_name = COALESCE($4, display_name),
+                 updated_at = NOW()
+             WHERE id = $1
+             RETURNING id as "id!: Uuid",
+                       remote_user_id as "remote_user_id: Uuid",
+                       username,
+                       email as "email!",
+                       display_name,
+                       created_at as "created_at!: DateTime<Utc>",
+                       updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.email.as_deref().map(str::trim),
+            &data.username,
+            &data.display_name
+        )
+        .fetch_optional(pool)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Tasks assigned to the user become unassigned
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(r#"DELETE FROM users WHERE id = $1"#, id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
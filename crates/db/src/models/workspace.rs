@@ -553,6 +553,16 @@ impl Workspace {
         Ok(())
     }
 
+    /// Whether any workspace already uses the branch
+    pub async fn branch_in_use(pool: &PgPool, branch: &str) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM workspaces WHERE branch = $1) as "exists!""#,
+            branch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
     /// Delete a workspace by ID
     pub async fn delete(pool: &PgPool, id: Uuid) -> Result<u64, sqlx::Error> {
         let result = sqlx::query!("DELETE FROM workspaces WHERE id = $1", id)
@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Files a workspace has changed in one repo, relative to the repo's target branch
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceChangedPaths {
+    pub workspace_id: Uuid,
+    pub repo_id: Uuid,
+    pub paths: Vec<String>,
+    #[ts(type = "Date")]
+    pub checked_at: DateTime<Utc>,
+}
+
+/// A repo checked out in a workspace whose task is still open
+#[derive(Debug, Clone, FromRow)]
+pub struct ActiveWorkspaceRepo {
+    pub workspace_id: Uuid,
+    pub container_ref: String,
+    pub branch: String,
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub repo_path: String,
+    pub target_branch: String,
+}
+
+impl ActiveWorkspaceRepo {
+    pub fn repo_path(&self) -> PathBuf {
+        PathBuf::from(&self.repo_path)
+    }
+
+    pub fn worktree_path(&self) -> PathBuf {
+        PathBuf::from(&self.container_ref).join(&self.repo_name)
+    }
+}
+
+/// Recorded changes of an active workspace, with its task and repo
+#[derive(Debug, Clone, FromRow)]
+pub struct ActiveWorkspaceChanges {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub paths: Vec<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl WorkspaceChangedPaths {
+    /// Repos of all workspaces that have a worktree and whose task isn't done or cancelled
+    pub async fn find_active_workspace_repos(
+        pool: &PgPool,
+    ) -> Result<Vec<ActiveWorkspaceRepo>, sqlx::Error> {
+        sqlx::query_as!(
+            ActiveWorkspaceRepo,
+            r#"SELECT w.id as "workspace_id!: Uuid",
+                      w.container_ref as "container_ref!",
+                      w.branch as "branch!",
+                      r.id as "repo_id!: Uuid",
+                      r.name as "repo_name!",
+                      r.path as "repo_path!",
+                      wr.target_branch as "target_branch!"
+               FROM workspaces w
+               JOIN tasks t ON t.id = w.task_id
+               JOIN workspace_repos wr ON wr.workspace_id = w.id
+               JOIN repos r ON r.id = wr.repo_id
+               WHERE w.cancelled_at IS NULL
+                 AND w.container_ref IS NOT NULL
+                 AND t.status NOT IN ('done', 'cancelled')"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &PgPool,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        paths: &[String],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO workspace_changed_paths (workspace_id, repo_id, paths)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (workspace_id, repo_id) DO UPDATE
+               SET paths = EXCLUDED.paths,
+                   checked_at = NOW()"#,
+            workspace_id,
+            repo_id,
+            paths
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Recorded changes of the project's active workspaces
+    pub async fn find_active_for_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<ActiveWorkspaceChanges>, sqlx::Error> {
+        sqlx::query_as!(
+            ActiveWorkspaceChanges,
+            r#"SELECT w.id as "workspace_id!: Uuid",
+                      t.id as "task_id!: Uuid",
+                      t.title as "task_title!",
+                      r.id as "repo_id!: Uuid",
+                      r.name as "repo_name!",
+                      c.paths as "paths!",
+                      c.checked_at as "checked_at!: DateTime<Utc>"
+               FROM workspace_changed_paths c
+               JOIN workspaces w ON w.id = c.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               JOIN repos r ON r.id = c.repo_id
+               WHERE t.project_id = $1
+                 AND w.cancelled_at IS NULL
+                 AND t.status NOT IN ('done', 'cancelled')
+               ORDER BY t.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
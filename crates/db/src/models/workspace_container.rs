@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Docker container a workspace's agents and scripts run in
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WorkspaceContainer {
+    pub workspace_id: Uuid,
+    pub container_name: String,
+    pub image: String,
+    /// Container ports published on the host's loopback interface
+    pub ports: Vec<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WorkspaceContainer {
+    pub async fn find_by_workspace_id(
+        pool: &PgPool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceContainer,
+            r#"SELECT workspace_id as "workspace_id!: Uuid",
+                      container_name as "container_name!",
+                      image as "image!",
+                      ports as "ports!",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM workspace_containers
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        workspace_id: Uuid,
+        container_name: &str,
+        image: &str,
+        ports: &[i32],
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceContainer,
+            r#"INSERT INTO workspace_containers (workspace_id, container_name, image, ports)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (workspace_id) DO UPDATE
+               SET container_name = EXCLUDED.container_name,
+                   image = EXCLUDED.image,
+                   ports = EXCLUDED.ports
+               RETURNING workspace_id as "workspace_id!: Uuid",
+                         container_name as "container_name!",
+                         image as "image!",
+                         ports as "ports!",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            workspace_id,
+            container_name,
+            image,
+            ports
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &PgPool, workspace_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM workspace_containers WHERE workspace_id = $1",
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
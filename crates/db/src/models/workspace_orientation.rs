@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Cached warm-up orientation of a workspace, shared by the agents of all its columns
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceOrientation {
+    pub workspace_id: Uuid,
+    pub content: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl WorkspaceOrientation {
+    pub async fn find(pool: &PgPool, workspace_id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceOrientation,
+            r#"SELECT workspace_id as "workspace_id!: Uuid",
+                      content as "content!",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM workspace_orientations
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &PgPool,
+        workspace_id: Uuid,
+        content: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceOrientation,
+            r#"INSERT INTO workspace_orientations (workspace_id, content)
+               VALUES ($1, $2)
+               ON CONFLICT (workspace_id) DO UPDATE
+               SET content = EXCLUDED.content, created_at = NOW()
+               RETURNING workspace_id as "workspace_id!: Uuid",
+                         content as "content!",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            workspace_id,
+            content
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &PgPool, workspace_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM workspace_orientations WHERE workspace_id = $1",
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
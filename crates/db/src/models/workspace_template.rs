@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A file seeded into every new workspace of a project
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceTemplate {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Repo whose worktree the path is relative to; none for the workspace root
+    pub repo_id: Option<Uuid>,
+    /// Relative path of the file, e.g. `AGENTS.md` or `tests/harness.py`
+    pub path: String,
+    /// File content; `{{task_id}}`, `{{branch}}`, `{{column}}` etc. are filled in
+    pub content: String,
+    /// Replace a file the repo already has instead of keeping it
+    pub overwrite: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateWorkspaceTemplate {
+    #[serde(default)]
+    pub repo_id: Option<Uuid>,
+    pub path: String,
+    pub content: String,
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateWorkspaceTemplate {
+    pub path: Option<String>,
+    pub content: Option<String>,
+    pub overwrite: Option<bool>,
+}
+
+impl WorkspaceTemplate {
+    /// Templates of a project, workspace-root files first, then by path
+    pub async fn find_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      repo_id as "repo_id: Uuid",
+                      path as "path!",
+                      content as "content!",
+                      overwrite as "overwrite!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM workspace_templates
+               WHERE project_id = $1
+               ORDER BY repo_id NULLS FIRST, path ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(
+        pool: &PgPool,
+        project_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      repo_id as "repo_id: Uuid",
+                      path as "path!",
+                      content as "content!",
+                      overwrite as "overwrite!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM workspace_templates
+               WHERE project_id = $1 AND id = $2"#,
+            project_id,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Whether another template of the project already writes this file
+    pub async fn target_taken(
+        pool: &PgPool,
+        project_id: Uuid,
+        repo_id: Option<Uuid>,
+        path: &str,
+        except_id: Option<Uuid>,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM workspace_templates
+                   WHERE project_id = $1
+                     AND repo_id IS NOT DISTINCT FROM $2
+                     AND path = $3
+                     AND id IS DISTINCT FROM $4
+               ) as "exists!""#,
+            project_id,
+            repo_id,
+            path,
+            except_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        project_id: Uuid,
+        data: &CreateWorkspaceTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceTemplate,
+            r#"INSERT INTO workspace_templates (id, project_id, repo_id, path, content, overwrite)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         repo_id as "repo_id: Uuid",
+                         path as "path!",
+                         content as "content!",
+                         overwrite as "overwrite!",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            Uuid::new_v4(),
+            project_id,
+            data.repo_id,
+            data.path.trim(),
+            &data.content,
+            data.overwrite
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Update the given fields, keeping the others
+    pub async fn update(
+        pool: &PgPool,
+        project_id: Uuid,
+        id: Uuid,
+        data: &UpdateWorkspaceTemplate,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceTemplate,
+            r#"UPDATE workspace_templates
+               SET path = COALESCE($3, path),
+                   content = COALESCE($4, content),
+                   overwrite = COALESCE($5, overwrite),
+                   updated_at = NOW()
+               WHERE project_id = $1 AND id = $2
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         repo_id as "repo_id: Uuid",
+                         path as "path!",
+                         content as "content!",
+                         overwrite as "overwrite!",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            id,
+            data.path.as_deref().map(str::trim),
+            data.content.as_deref(),
+            data.overwrite
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Delete a template. Returns true if a row was removed.
+    pub async fn delete(pool: &PgPool, project_id: Uuid, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM workspace_templates WHERE project_id = $1 AND id = $2",
+            project_id,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -19,19 +19,27 @@ use services::services::{
     analytics::{AnalyticsContext, AnalyticsService},
     approvals::Approvals,
     auth::AuthContext,
+    board_snapshots::BoardSnapshotService,
     config::{Config, ConfigError},
     container::{ContainerError, ContainerService},
+    dependency_updates::DependencyUpdateService,
+    email_notifications::EmailDigestService,
     events::{EventError, EventService},
     file_search_cache::FileSearchCache,
     filesystem::{FilesystemError, FilesystemService},
     filesystem_watcher::FilesystemWatcherError,
     git::{GitService, GitServiceError},
     image::{ImageError, ImageService},
+    job_queue::JobQueueService,
+    log_retention::LogRetentionService,
+    notification_digests::NotificationDigestService,
     pr_monitor::PrMonitorService,
     project::ProjectService,
     queued_message::QueuedMessageService,
     repo::RepoService,
-    share::SharePublisher,
+    share::{SharePublisher, SharedTaskSyncService},
+    task_watch::TaskWatchService,
+    workspace_conflicts::WorkspaceConflictService,
     worktree_manager::WorktreeError,
 };
 use sqlx::Error as SqlxError;
@@ -136,6 +144,46 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         PrMonitorService::spawn(db, analytics, publisher).await
     }
 
+    async fn spawn_dependency_update_service(&self) -> tokio::task::JoinHandle<()> {
+        DependencyUpdateService::spawn(self.db().clone()).await
+    }
+
+    async fn spawn_workspace_conflict_service(&self) -> tokio::task::JoinHandle<()> {
+        WorkspaceConflictService::spawn(self.db().clone()).await
+    }
+
+    async fn spawn_job_queue_service(&self) -> tokio::task::JoinHandle<()> {
+        JobQueueService::spawn(self.db().clone(), self.share_publisher().ok()).await
+    }
+
+    /// Pulls edits made to shared tasks remotely; None when sharing isn't configured
+    async fn spawn_shared_task_sync_service(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let publisher = self.share_publisher().ok()?;
+        Some(SharedTaskSyncService::spawn(publisher).await)
+    }
+
+    async fn spawn_task_watch_service(&self) -> tokio::task::JoinHandle<()> {
+        let notifications = self.container().notification_service().clone();
+        TaskWatchService::spawn(self.db().clone(), notifications).await
+    }
+
+    async fn spawn_email_digest_service(&self) -> tokio::task::JoinHandle<()> {
+        let notifications = self.container().notification_service().clone();
+        EmailDigestService::spawn(self.db().clone(), notifications).await
+    }
+
+    async fn spawn_board_snapshot_service(&self) -> tokio::task::JoinHandle<()> {
+        BoardSnapshotService::spawn(self.db().clone()).await
+    }
+
+    async fn spawn_notification_digest_service(&self) -> tokio::task::JoinHandle<()> {
+        NotificationDigestService::spawn(self.db().clone()).await
+    }
+
+    async fn spawn_log_retention_service(&self) -> tokio::task::JoinHandle<()> {
+        LogRetentionService::spawn(self.db().clone()).await
+    }
+
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
         let analytics_enabled = self.config().read().await.analytics_enabled;
         // Track events unless user has explicitly opted out
@@ -173,6 +221,7 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                             git_repo_path: repo_path.clone(),
                         }],
                         board_id: None,
+                        workflow_template_id: None,
                     };
 
                     match self
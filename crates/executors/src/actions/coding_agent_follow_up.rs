@@ -3,6 +3,7 @@ use std::{path::Path, sync::Arc};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use workspace_utils::path::join_relative;
 
 use crate::{
     actions::Executable,
@@ -46,7 +47,7 @@ impl Executable for CodingAgentFollowUpRequest {
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let effective_dir = match &self.working_dir {
-            Some(rel_path) => current_dir.join(rel_path),
+            Some(rel_path) => join_relative(current_dir, rel_path),
             None => current_dir.to_path_buf(),
         };
 
@@ -58,6 +59,13 @@ impl Executable for CodingAgentFollowUpRequest {
             ))?;
 
         agent.use_approvals(approvals.clone());
+        if !agent.add_mcp_servers(&env.mcp_servers)? {
+            tracing::warn!(
+                "Executor {} does not support per-agent MCP servers; skipping {} server(s)",
+                executor_profile_id.executor,
+                env.mcp_servers.len()
+            );
+        }
 
         agent
             .spawn_follow_up(&effective_dir, &self.prompt, &self.session_id, env)
@@ -3,6 +3,7 @@ use std::{path::Path, sync::Arc};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use workspace_utils::path::join_relative;
 
 use crate::{
     actions::Executable,
@@ -12,6 +13,146 @@ use crate::{
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
 
+/// Sections of an agent's initial prompt. Agents and columns can reorder them or leave
+/// some out; the default order is `PromptSection::DEFAULT_ORDER`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptSection {
+    SystemPrompt,
+    ProjectContext,
+    WorkflowHistory,
+    Task,
+    Instructions,
+    /// The column's question and any pending reviewer feedback
+    DecisionInstructions,
+    Deliverable,
+}
+
+impl PromptSection {
+    pub const DEFAULT_ORDER: [PromptSection; 7] = [
+        PromptSection::SystemPrompt,
+        PromptSection::ProjectContext,
+        PromptSection::WorkflowHistory,
+        PromptSection::Task,
+        PromptSection::Instructions,
+        PromptSection::DecisionInstructions,
+        PromptSection::Deliverable,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PromptSection::SystemPrompt => "system_prompt",
+            PromptSection::ProjectContext => "project_context",
+            PromptSection::WorkflowHistory => "workflow_history",
+            PromptSection::Task => "task",
+            PromptSection::Instructions => "instructions",
+            PromptSection::DecisionInstructions => "decision_instructions",
+            PromptSection::Deliverable => "deliverable",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::DEFAULT_ORDER
+            .into_iter()
+            .find(|section| section.as_str() == s)
+    }
+
+    /// Sections from stored names, skipping unknown and repeated ones
+    pub fn parse_list(names: &[String]) -> Vec<Self> {
+        let mut sections = Vec::new();
+        for section in names.iter().filter_map(|name| Self::from_str(name)) {
+            if !sections.contains(&section) {
+                sections.push(section);
+            }
+        }
+        sections
+    }
+
+    /// Names to store for sections, dropping repeated ones
+    pub fn to_names(sections: &[Self]) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for name in sections.iter().map(|section| section.as_str()) {
+            if !names.iter().any(|existing| existing == name) {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+}
+
+/// Language of the instruction text the server adds around an agent's prompt (section
+/// headings, decision instructions, column notices). Prompts written by users are sent as is.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptLanguage {
+    #[default]
+    English,
+    German,
+    Japanese,
+}
+
+impl PromptLanguage {
+    pub const ALL: [PromptLanguage; 3] = [
+        PromptLanguage::English,
+        PromptLanguage::German,
+        PromptLanguage::Japanese,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PromptLanguage::English => "english",
+            PromptLanguage::German => "german",
+            PromptLanguage::Japanese => "japanese",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|language| language.as_str() == s)
+    }
+
+    /// Heading of a prompt section, for the sections that have one
+    fn section_heading(&self, section: PromptSection) -> Option<&'static str> {
+        let heading = match (self, section) {
+            (PromptLanguage::English, PromptSection::ProjectContext) => "# Project Context",
+            (PromptLanguage::English, PromptSection::Task) => "## Task",
+            (PromptLanguage::English, PromptSection::Instructions) => "## Instructions",
+            (PromptLanguage::English, PromptSection::Deliverable) => "## Expected Deliverable",
+            (PromptLanguage::German, PromptSection::ProjectContext) => "# Projektkontext",
+            (PromptLanguage::German, PromptSection::Task) => "## Aufgabe",
+            (PromptLanguage::German, PromptSection::Instructions) => "## Anweisungen",
+            (PromptLanguage::German, PromptSection::Deliverable) => "## Erwartetes Ergebnis",
+            (PromptLanguage::Japanese, PromptSection::ProjectContext) => "# プロジェクトの背景",
+            (PromptLanguage::Japanese, PromptSection::Task) => "## タスク",
+            (PromptLanguage::Japanese, PromptSection::Instructions) => "## 指示",
+            (PromptLanguage::Japanese, PromptSection::Deliverable) => "## 期待される成果物",
+            _ => return None,
+        };
+        Some(heading)
+    }
+
+    /// Appended to the deliverable: stop once it is done
+    fn deliverable_stop_notice(&self) -> &'static str {
+        match self {
+            PromptLanguage::English => {
+                "**Important**: Once you have produced the deliverable described above, commit \
+                 your work and stop. Do not proceed to implement the plan yourself - your job is \
+                 complete when the deliverable is ready."
+            }
+            PromptLanguage::German => {
+                "**Wichtig**: Sobald du das oben beschriebene Ergebnis erstellt hast, committe \
+                 deine Arbeit und hör auf. Setze den Plan nicht selbst um - deine Aufgabe ist \
+                 erledigt, sobald das Ergebnis vorliegt."
+            }
+            PromptLanguage::Japanese => {
+                "**重要**: 上記の成果物を作成したら、作業をコミットして終了してください。\
+                 計画を自分で実装しないでください。成果物が完成した時点で作業は完了です。"
+            }
+        }
+    }
+}
+
+const PROMPT_SECTION_SEPARATOR: &str = "\n\n---\n\n";
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct CodingAgentInitialRequest {
     pub prompt: String,
@@ -35,9 +176,20 @@ pub struct CodingAgentInitialRequest {
     /// Optional agent start command to append (initial instruction)
     #[serde(default)]
     pub agent_start_command: Option<String>,
+    /// Optional decision instructions - the column's question and reviewer feedback
+    #[serde(default)]
+    pub agent_decision_instructions: Option<String>,
     /// Optional deliverable description - tells the agent what to produce and when to stop
     #[serde(default)]
     pub agent_deliverable: Option<String>,
+    /// Order of the prompt's sections; sections not listed are left out.
+    /// If None, uses `PromptSection::DEFAULT_ORDER`.
+    #[serde(default)]
+    pub prompt_sections: Option<Vec<PromptSection>>,
+    /// Language of the section headings and the deliverable's stop notice.
+    /// If None, uses English.
+    #[serde(default)]
+    pub prompt_language: Option<PromptLanguage>,
 }
 
 impl CodingAgentInitialRequest {
@@ -45,57 +197,53 @@ impl CodingAgentInitialRequest {
         self.executor_profile_id.executor
     }
 
-    /// Build the full prompt with agent context prepended and start command appended
+    /// Build the full prompt from the agent context and task, in section order
     pub fn build_full_prompt(&self) -> String {
-        let mut full = String::new();
-
-        // Prepend agent system prompt if present
-        if let Some(system_prompt) = &self.agent_system_prompt {
-            if !system_prompt.trim().is_empty() {
-                full.push_str(system_prompt.trim());
-                full.push_str("\n\n---\n\n");
-            }
-        }
-
-        // Add project context if present (ADRs, patterns, module memories from context artifacts)
-        if let Some(project_context) = &self.agent_project_context {
-            if !project_context.trim().is_empty() {
-                full.push_str("# Project Context\n\n");
-                full.push_str(project_context.trim());
-                full.push_str("\n\n---\n\n");
-            }
-        }
-
-        // Add workflow history if present (shows prior work from previous columns)
-        if let Some(workflow_history) = &self.agent_workflow_history {
-            if !workflow_history.trim().is_empty() {
-                full.push_str(workflow_history.trim());
-                full.push_str("\n\n---\n\n");
-            }
-        }
-
-        // Add the task prompt
-        full.push_str("## Task\n\n");
-        full.push_str(&self.prompt);
+        let sections = self
+            .prompt_sections
+            .as_deref()
+            .unwrap_or(&PromptSection::DEFAULT_ORDER);
+        sections
+            .iter()
+            .filter_map(|section| self.render_section(*section))
+            .collect::<Vec<_>>()
+            .join(PROMPT_SECTION_SEPARATOR)
+    }
 
-        // Append agent start command if present
-        if let Some(start_command) = &self.agent_start_command {
-            if !start_command.trim().is_empty() {
-                full.push_str("\n\n---\n\n## Instructions\n\n");
-                full.push_str(start_command.trim());
+    /// A section's text, or None when the request has nothing for it
+    fn render_section(&self, section: PromptSection) -> Option<String> {
+        let language = self.prompt_language.unwrap_or_default();
+        let non_empty = |text: &Option<String>| {
+            text.as_deref()
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+        };
+        let body = match section {
+            PromptSection::SystemPrompt => non_empty(&self.agent_system_prompt).map(str::to_string),
+            // ADRs, patterns, module memories from context artifacts
+            PromptSection::ProjectContext => {
+                non_empty(&self.agent_project_context).map(str::to_string)
             }
-        }
-
-        // Add deliverable section - tells the agent what to produce and when to stop
-        if let Some(deliverable) = &self.agent_deliverable {
-            if !deliverable.trim().is_empty() {
-                full.push_str("\n\n---\n\n## Expected Deliverable\n\n");
-                full.push_str(deliverable.trim());
-                full.push_str("\n\n**Important**: Once you have produced the deliverable described above, commit your work and stop. Do not proceed to implement the plan yourself - your job is complete when the deliverable is ready.");
+            // Prior work from previous columns
+            PromptSection::WorkflowHistory => {
+                non_empty(&self.agent_workflow_history).map(str::to_string)
             }
-        }
-
-        full
+            PromptSection::Task => Some(self.prompt.clone()),
+            PromptSection::Instructions => non_empty(&self.agent_start_command).map(str::to_string),
+            // Decision instructions carry their own leading separator
+            PromptSection::DecisionInstructions => non_empty(&self.agent_decision_instructions)
+                .map(|instructions| instructions.trim_start_matches("---").trim_start())
+                .filter(|instructions| !instructions.is_empty())
+                .map(str::to_string),
+            // Tells the agent what to produce and when to stop
+            PromptSection::Deliverable => non_empty(&self.agent_deliverable).map(|deliverable| {
+                format!("{deliverable}\n\n{}", language.deliverable_stop_notice())
+            }),
+        }?;
+        Some(match language.section_heading(section) {
+            Some(heading) => format!("{heading}\n\n{body}"),
+            None => body,
+        })
     }
 }
 
@@ -109,7 +257,7 @@ impl Executable for CodingAgentInitialRequest {
     ) -> Result<SpawnedChild, ExecutorError> {
         // Use working_dir if specified, otherwise use current_dir
         let effective_dir = match &self.working_dir {
-            Some(rel_path) => current_dir.join(rel_path),
+            Some(rel_path) => join_relative(current_dir, rel_path),
             None => current_dir.to_path_buf(),
         };
 
@@ -121,9 +269,83 @@ impl Executable for CodingAgentInitialRequest {
             ))?;
 
         agent.use_approvals(approvals.clone());
+        if !agent.add_mcp_servers(&env.mcp_servers)? {
+            tracing::warn!(
+                "Executor {} does not support per-agent MCP servers; skipping {} server(s)",
+                executor_profile_id.executor,
+                env.mcp_servers.len()
+            );
+        }
 
         // Build full prompt with agent context (system prompt + task + start command)
         let full_prompt = self.build_full_prompt();
         agent.spawn(&effective_dir, &full_prompt, env).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> CodingAgentInitialRequest {
+        CodingAgentInitialRequest {
+            prompt: "Fix the login bug".to_string(),
+            executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+            working_dir: None,
+            agent_system_prompt: Some("You are a careful engineer.".to_string()),
+            agent_project_context: None,
+            agent_workflow_history: Some("  ".to_string()),
+            agent_start_command: Some("Write tests first.".to_string()),
+            agent_decision_instructions: Some("\n\n---\n\n## Question\n\nShip it?\n".to_string()),
+            agent_deliverable: None,
+            prompt_sections: None,
+            prompt_language: None,
+        }
+    }
+
+    #[test]
+    fn default_order_joins_the_non_empty_sections() {
+        assert_eq!(
+            request().build_full_prompt(),
+            "You are a careful engineer.\n\n---\n\n## Task\n\nFix the login bug\n\n---\n\n\
+             ## Instructions\n\nWrite tests first.\n\n---\n\n## Question\n\nShip it?"
+        );
+    }
+
+    #[test]
+    fn configured_sections_are_reordered_and_the_rest_left_out() {
+        let request = CodingAgentInitialRequest {
+            prompt_sections: Some(vec![PromptSection::DecisionInstructions, PromptSection::Task]),
+            ..request()
+        };
+        assert_eq!(
+            request.build_full_prompt(),
+            "## Question\n\nShip it?\n\n---\n\n## Task\n\nFix the login bug"
+        );
+        assert_eq!(
+            PromptSection::parse_list(&["task".into(), "bogus".into(), "task".into()]),
+            vec![PromptSection::Task]
+        );
+    }
+
+    #[test]
+    fn headings_and_stop_notice_follow_the_prompt_language() {
+        let request = CodingAgentInitialRequest {
+            agent_deliverable: Some("A migration plan".to_string()),
+            prompt_sections: Some(vec![PromptSection::Task, PromptSection::Deliverable]),
+            prompt_language: Some(PromptLanguage::German),
+            ..request()
+        };
+        let prompt = request.build_full_prompt();
+        assert!(prompt.starts_with("## Aufgabe\n\nFix the login bug"));
+        assert!(prompt.contains("## Erwartetes Ergebnis\n\nA migration plan\n\n**Wichtig**"));
+
+        let request = CodingAgentInitialRequest {
+            prompt_language: Some(PromptLanguage::Japanese),
+            ..request
+        };
+        assert!(request.build_full_prompt().starts_with("## タスク\n\n"));
+        assert_eq!(PromptLanguage::from_str("japanese"), Some(PromptLanguage::Japanese));
+        assert_eq!(PromptLanguage::from_str("klingon"), None);
+    }
+}
@@ -27,16 +27,43 @@ pub enum ExecutorActionType {
     ScriptRequest,
 }
 
+/// Board agent a process runs for. Log entries the process produces are attributed
+/// to it, so merged multi-agent transcripts can tell the agents apart.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct AgentIdentity {
+    pub agent_id: String,
+    /// Agent color (hex) for UI styling
+    pub agent_color: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ExecutorAction {
     pub typ: ExecutorActionType,
     pub next_action: Option<Box<ExecutorAction>>,
+    /// Agent this action and the actions chained after it run for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<AgentIdentity>,
 }
 
 impl ExecutorAction {
     pub fn new(typ: ExecutorActionType, next_action: Option<Box<ExecutorAction>>) -> Self {
-        Self { typ, next_action }
+        Self {
+            typ,
+            next_action,
+            agent: None,
+        }
     }
+
+    /// Attribute this action and every action chained after it to `agent`, so setup,
+    /// column and cleanup scripts of an agent's stage carry its identity too
+    pub fn with_agent(mut self, agent: Option<AgentIdentity>) -> Self {
+        self.next_action = self
+            .next_action
+            .map(|next| Box::new(next.with_agent(agent.clone())));
+        self.agent = agent;
+        self
+    }
+
     pub fn append_action(mut self, action: ExecutorAction) -> Self {
         if let Some(next) = self.next_action {
             self.next_action = Some(Box::new(next.append_action(action)));
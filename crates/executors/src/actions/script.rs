@@ -5,7 +5,10 @@ use command_group::AsyncCommandGroup;
 use serde::{Deserialize, Serialize};
 use tokio::process::Command;
 use ts_rs::TS;
-use workspace_utils::shell::get_shell_command;
+use workspace_utils::{
+    path::join_relative,
+    shell::{get_shell_command, resolve_executable_path},
+};
 
 use crate::{
     actions::Executable,
@@ -14,9 +17,103 @@ use crate::{
     executors::{ExecutorError, SpawnedChild},
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
 pub enum ScriptRequestLanguage {
+    /// The platform shell: the user's shell on Unix, cmd on Windows
     Bash,
+    PowerShell,
+    Python,
+    /// Windows `cmd.exe`
+    Cmd,
+}
+
+impl ScriptRequestLanguage {
+    pub const ALL: [ScriptRequestLanguage; 4] = [
+        ScriptRequestLanguage::Bash,
+        ScriptRequestLanguage::PowerShell,
+        ScriptRequestLanguage::Python,
+        ScriptRequestLanguage::Cmd,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScriptRequestLanguage::Bash => "bash",
+            ScriptRequestLanguage::PowerShell => "powershell",
+            ScriptRequestLanguage::Python => "python",
+            ScriptRequestLanguage::Cmd => "cmd",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "bash" => Some(ScriptRequestLanguage::Bash),
+            "powershell" => Some(ScriptRequestLanguage::PowerShell),
+            "python" => Some(ScriptRequestLanguage::Python),
+            "cmd" => Some(ScriptRequestLanguage::Cmd),
+            _ => None,
+        }
+    }
+
+    /// Interpreters to try in order, with the arguments that precede the script
+    fn candidates(&self) -> Vec<(&'static str, &'static [&'static str])> {
+        const DASH_C: &[&str] = &["-c"];
+        const POWERSHELL_ARGS: &[&str] = &["-NoProfile", "-NonInteractive", "-Command"];
+        const PY_LAUNCHER_ARGS: &[&str] = &["-3", "-c"];
+        const CMD_ARGS: &[&str] = &["/C"];
+        match self {
+            // Resolved through the platform shell instead
+            ScriptRequestLanguage::Bash => Vec::new(),
+            ScriptRequestLanguage::PowerShell => {
+                vec![("pwsh", POWERSHELL_ARGS), ("powershell", POWERSHELL_ARGS)]
+            }
+            ScriptRequestLanguage::Python if cfg!(windows) => {
+                vec![("python", DASH_C), ("py", PY_LAUNCHER_ARGS)]
+            }
+            ScriptRequestLanguage::Python => vec![("python3", DASH_C), ("python", DASH_C)],
+            ScriptRequestLanguage::Cmd if cfg!(windows) => vec![("cmd", CMD_ARGS)],
+            ScriptRequestLanguage::Cmd => Vec::new(),
+        }
+    }
+
+    /// The program and arguments that run a script in this language, or `None`
+    /// when no interpreter for it is installed
+    pub async fn resolve_interpreter(&self) -> Option<(String, Vec<&'static str>)> {
+        for (program, args) in self.candidates() {
+            if let Some(path) = resolve_executable_path(program).await {
+                return Some((path.to_string_lossy().into_owned(), args.to_vec()));
+            }
+        }
+        match self {
+            ScriptRequestLanguage::Bash => {
+                let (shell, arg) = get_shell_command();
+                Some((shell, vec![arg]))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Which interpreter, if any, runs scripts of a language on this machine
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct ScriptInterpreter {
+    pub language: ScriptRequestLanguage,
+    pub available: bool,
+    /// Resolved path of the interpreter
+    pub path: Option<String>,
+}
+
+/// Detect interpreters for every script language
+pub async fn detect_interpreters() -> Vec<ScriptInterpreter> {
+    let mut interpreters = Vec::new();
+    for language in ScriptRequestLanguage::ALL {
+        let path = language.resolve_interpreter().await.map(|(path, _)| path);
+        interpreters.push(ScriptInterpreter {
+            language,
+            available: path.is_some(),
+            path,
+        });
+    }
+    interpreters
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -25,6 +122,10 @@ pub enum ScriptContext {
     CleanupScript,
     DevServer,
     ToolInstallScript,
+    /// Runs in the workspace before a column's agent starts
+    ColumnEnterScript,
+    /// Runs in the workspace after a column's agent finishes
+    ColumnExitScript,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -48,18 +149,22 @@ impl Executable for ScriptRequest {
     ) -> Result<SpawnedChild, ExecutorError> {
         // Use working_dir if specified, otherwise use current_dir
         let effective_dir = match &self.working_dir {
-            Some(rel_path) => current_dir.join(rel_path),
+            Some(rel_path) => join_relative(current_dir, rel_path),
             None => current_dir.to_path_buf(),
         };
 
-        let (shell_cmd, shell_arg) = get_shell_command();
-        let mut command = Command::new(shell_cmd);
+        let (program, args) = self.language.resolve_interpreter().await.ok_or_else(|| {
+            ExecutorError::ExecutableNotFound {
+                program: format!("{} interpreter", self.language.as_str()),
+            }
+        })?;
+        let mut command = Command::new(program);
         command
             .kill_on_drop(true)
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
-            .arg(shell_arg)
+            .args(args)
             .arg(&self.script)
             .current_dir(&effective_dir);
 
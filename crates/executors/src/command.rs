@@ -29,6 +29,25 @@ impl CommandParts {
         Self { program, args }
     }
 
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Replace `placeholder` inside every argument after the command line was split,
+    /// so values containing whitespace or quotes stay a single argument
+    pub fn substitute(mut self, placeholder: &str, value: &str) -> Self {
+        for arg in &mut self.args {
+            if arg.contains(placeholder) {
+                *arg = arg.replace(placeholder, value);
+            }
+        }
+        self
+    }
+
     pub async fn into_resolved(self) -> Result<(PathBuf, Vec<String>), ExecutorError> {
         let CommandParts { program, args } = self;
         let executable = resolve_executable_path(&program)
@@ -1,19 +1,95 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    path::Path,
+    process::Stdio,
+};
 
 use tokio::process::Command;
+use workspace_utils::redact::SecretRedactor;
 
-use crate::command::CmdOverrides;
+use crate::{command::CmdOverrides, mcp_config::AgentMcpServer};
 
 /// Environment variables to inject into executor processes
 #[derive(Debug, Clone, Default)]
 pub struct ExecutionEnv {
     pub vars: HashMap<String, String>,
+    /// Keys whose values must never appear in process output
+    pub secret_keys: HashSet<String>,
+    /// Extra MCP servers of the launching agent, added by coding agent actions before spawn
+    pub mcp_servers: Vec<AgentMcpServer>,
+    /// Run the process inside this Docker container instead of on the host
+    pub container: Option<ContainerExec>,
+}
+
+/// A running Docker container that executor processes are started in with `docker exec`.
+/// The workspace is mounted at the same path as on the host, so working directories
+/// need no translation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerExec {
+    pub container: String,
+    /// Names the pid file of the process inside the container, so it can be stopped.
+    /// The process leads its own group, so the pid is also its process group id.
+    pub process_label: String,
+}
+
+impl ContainerExec {
+    /// Pid file inside the container of the process started with this label
+    pub fn pid_file(&self) -> String {
+        format!("/tmp/vk-{}.pid", self.process_label)
+    }
+
+    /// `docker exec` of the command inside the container. The program is looked up on
+    /// the container's PATH; variables set on the command are passed by name, so their
+    /// values don't show up in the host's process list.
+    fn wrap(&self, command: &std::process::Command) -> Command {
+        let mut docker = Command::new("docker");
+        docker
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut args: Vec<OsString> = vec!["exec".into(), "-i".into()];
+        if let Some(dir) = command.get_current_dir() {
+            args.push("-w".into());
+            args.push(dir.into());
+        }
+        for (key, value) in command.get_envs() {
+            match value {
+                Some(value) => {
+                    docker.env(key, value);
+                    args.push("-e".into());
+                    args.push(key.into());
+                }
+                None => {
+                    docker.env_remove(key);
+                }
+            }
+        }
+        args.push(self.container.as_str().into());
+
+        // Lead a new process group, and record its id before replacing the shell, so
+        // the process and its children can be stopped together later
+        args.extend(["setsid".into(), "-w".into(), "sh".into(), "-c".into()]);
+        args.push(format!("echo $$ > {}; exec \"$@\"", self.pid_file()).into());
+        args.push("vk".into());
+        let program = command.get_program();
+        args.push(Path::new(program).file_name().unwrap_or(program).into());
+        args.extend(command.get_args().map(OsString::from));
+
+        docker.args(args);
+        docker
+    }
 }
 
 impl ExecutionEnv {
     pub fn new() -> Self {
         Self {
             vars: HashMap::new(),
+            secret_keys: HashSet::new(),
+            mcp_servers: Vec::new(),
+            container: None,
         }
     }
 
@@ -22,6 +98,23 @@ impl ExecutionEnv {
         self.vars.insert(key.into(), value.into());
     }
 
+    /// Insert an environment variable whose value is redacted from logs
+    pub fn insert_secret(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let key = key.into();
+        self.secret_keys.insert(key.clone());
+        self.vars.insert(key, value.into());
+    }
+
+    /// Build a redactor for the current values of all secret keys
+    pub fn redactor(&self) -> SecretRedactor {
+        SecretRedactor::new(
+            self.secret_keys
+                .iter()
+                .filter_map(|key| self.vars.get(key))
+                .cloned(),
+        )
+    }
+
     /// Merge additional vars into this env. Incoming keys overwrite existing ones.
     pub fn merge(&mut self, other: &HashMap<String, String>) {
         self.vars
@@ -43,11 +136,15 @@ impl ExecutionEnv {
         }
     }
 
-    /// Apply all environment variables to a Command
+    /// Apply all environment variables to a Command. With a container set, the command
+    /// is replaced by a `docker exec` of it; its stdio is always piped.
     pub fn apply_to_command(&self, command: &mut Command) {
         for (key, value) in &self.vars {
             command.env(key, value);
         }
+        if let Some(container) = &self.container {
+            *command = container.wrap(command.as_std());
+        }
     }
 
     pub fn contains_key(&self, key: &str) -> bool {
@@ -75,4 +172,60 @@ mod tests {
         assert_eq!(merged.vars.get("FOO").unwrap(), "profile"); // overrides
         assert_eq!(merged.vars.get("BAR").unwrap(), "profile");
     }
+
+    #[test]
+    fn redactor_only_covers_secret_values() {
+        let mut env = ExecutionEnv::new();
+        env.insert("VK_PROJECT_NAME", "visible-name");
+        env.insert_secret("API_TOKEN", "tok_123456");
+
+        let redactor = env.redactor();
+        assert_eq!(
+            redactor.redact("visible-name tok_123456"),
+            "visible-name [REDACTED]"
+        );
+    }
+
+    #[test]
+    fn container_commands_run_through_docker_exec() {
+        let mut env = ExecutionEnv::new();
+        env.insert_secret("API_TOKEN", "tok_123456");
+        env.container = Some(ContainerExec {
+            container: "vk-abc123".to_string(),
+            process_label: "exec1".to_string(),
+        });
+
+        let mut command = Command::new("/usr/local/bin/claude");
+        command.current_dir("/work/space").arg("--print");
+        env.apply_to_command(&mut command);
+
+        let docker = command.as_std();
+        let args: Vec<_> = docker
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(docker.get_program(), "docker");
+        assert_eq!(
+            args,
+            [
+                "exec",
+                "-i",
+                "-w",
+                "/work/space",
+                "-e",
+                "API_TOKEN",
+                "vk-abc123",
+                "setsid",
+                "-w",
+                "sh",
+                "-c",
+                "echo $$ > /tmp/vk-exec1.pid; exec \"$@\"",
+                "vk",
+                "claude",
+                "--print",
+            ]
+        );
+        // The secret only reaches docker's own environment
+        assert!(!args.iter().any(|arg| arg.contains("tok_123456")));
+    }
 }
@@ -112,6 +112,7 @@ impl AcpAgentHarness {
             child,
             exit_signal: Some(exit_rx),
             interrupt_sender: None,
+            message_sender: None,
         })
     }
 
@@ -161,6 +162,7 @@ impl AcpAgentHarness {
             child,
             exit_signal: Some(exit_rx),
             interrupt_sender: None,
+            message_sender: None,
         })
     }
 
@@ -280,6 +280,9 @@ impl ClaudeCode {
         // (Claude Code in SDK mode stays alive after processing, so we can't rely on process exit)
         let (exit_signal_tx, exit_signal_rx) = tokio::sync::oneshot::channel();
 
+        // Follow-up messages sent by the user while Claude is still working
+        let (message_tx, mut message_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+
         // Spawn task to handle the SDK client with control protocol
         let prompt_clone = combined_prompt.clone();
         let approvals_clone = self.approvals_service.clone();
@@ -308,6 +311,20 @@ impl ClaudeCode {
                 let _ = log_writer
                     .log_raw(&format!("Error: Failed to send prompt - {e}"))
                     .await;
+                return;
+            }
+
+            // Forward follow-ups until the container drops the sender on exit
+            while let Some(content) = message_rx.recv().await {
+                if let Ok(json) = serde_json::to_string(&ClaudeJson::FollowUpMessage {
+                    content: content.clone(),
+                }) {
+                    let _ = log_writer.log_raw(&json).await;
+                }
+                if let Err(e) = protocol_peer.send_user_message(content).await {
+                    tracing::warn!("Failed to send follow-up message: {e}");
+                    break;
+                }
             }
         });
 
@@ -315,6 +332,7 @@ impl ClaudeCode {
             child,
             exit_signal: Some(exit_signal_rx),
             interrupt_sender: Some(interrupt_tx),
+            message_sender: Some(message_tx),
         })
     }
 }
@@ -456,6 +474,28 @@ impl ClaudeLogProcessor {
         });
     }
 
+    /// Total token usage reported by the `result` lines of a raw stdout stream.
+    /// Returns `(input_tokens, output_tokens)`; input includes cache reads and writes.
+    pub fn token_usage_from_stdout(stdout: &str) -> Option<(u64, u64)> {
+        let mut total: Option<(u64, u64)> = None;
+        for line in stdout.lines() {
+            let Ok(ClaudeJson::Result {
+                usage: Some(usage), ..
+            }) = serde_json::from_str::<ClaudeJson>(line.trim())
+            else {
+                continue;
+            };
+            let input = usage.input_tokens.unwrap_or(0)
+                + usage.cache_creation_input_tokens.unwrap_or(0)
+                + usage.cache_read_input_tokens.unwrap_or(0);
+            let output = usage.output_tokens.unwrap_or(0);
+            let (i, o) = total.get_or_insert((0, 0));
+            *i += input;
+            *o += output;
+        }
+        total
+    }
+
     /// Extract session ID from Claude JSON
     fn extract_session_id(claude_json: &ClaudeJson) -> Option<String> {
         match claude_json {
@@ -467,6 +507,7 @@ impl ClaudeLogProcessor {
             ClaudeJson::Result { session_id, .. } => session_id.clone(),
             ClaudeJson::StreamEvent { .. } => None, // session might not have been initialized yet
             ClaudeJson::ApprovalResponse { .. } => None,
+            ClaudeJson::FollowUpMessage { .. } => None,
             ClaudeJson::Unknown { .. } => None,
         }
     }
@@ -1196,6 +1237,16 @@ impl ClaudeLogProcessor {
                     patches.push(ConversationPatch::add_normalized_entry(idx, entry));
                 }
             }
+            ClaudeJson::FollowUpMessage { content } => {
+                let entry = NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::UserMessage,
+                    content: content.clone(),
+                    metadata: None, agent_id: None, agent_color: None,
+                };
+                let idx = entry_index_provider.next();
+                patches.push(ConversationPatch::add_normalized_entry(idx, entry));
+            }
             ClaudeJson::Unknown { data } => {
                 let entry = NormalizedEntry {
                     timestamp: None,
@@ -1526,6 +1577,8 @@ pub enum ClaudeJson {
         num_turns: Option<u32>,
         #[serde(default, alias = "sessionId")]
         session_id: Option<String>,
+        #[serde(default)]
+        usage: Option<ClaudeUsage>,
     },
     #[serde(rename = "approval_response")]
     ApprovalResponse {
@@ -1533,6 +1586,9 @@ pub enum ClaudeJson {
         tool_name: String,
         approval_status: ApprovalStatus,
     },
+    /// User message sent to the agent while it was running
+    #[serde(rename = "follow_up_message")]
+    FollowUpMessage { content: String },
     // Catch-all for unknown message types
     #[serde(untagged)]
     Unknown {
@@ -1874,6 +1930,26 @@ mod tests {
         normalize_helper(&mut processor, json, worktree)
     }
 
+    #[test]
+    fn test_token_usage_from_stdout() {
+        let stdout = concat!(
+            r#"{"type":"system","subtype":"init","session_id":"abc123"}"#,
+            "\n",
+            r#"{"type":"assistant","message":{"type":"message","role":"assistant","content":[]}}"#,
+            "\n",
+            r#"{"type":"result","subtype":"success","is_error":false,"usage":{"input_tokens":10,"cache_read_input_tokens":90,"output_tokens":25}}"#,
+            "\n",
+        );
+        assert_eq!(
+            ClaudeLogProcessor::token_usage_from_stdout(stdout),
+            Some((100, 25))
+        );
+        assert_eq!(
+            ClaudeLogProcessor::token_usage_from_stdout("not json\n"),
+            None
+        );
+    }
+
     #[test]
     fn test_claude_json_parsing() {
         let system_json =
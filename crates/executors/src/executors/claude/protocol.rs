@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
 
 use futures::FutureExt;
 use tokio::{
@@ -20,6 +23,8 @@ use crate::executors::{
 #[derive(Clone)]
 pub struct ProtocolPeer {
     stdin: Arc<Mutex<ChildStdin>>,
+    /// User messages sent whose turn hasn't produced a result yet
+    pending_turns: Arc<AtomicUsize>,
 }
 
 impl ProtocolPeer {
@@ -32,6 +37,7 @@ impl ProtocolPeer {
     ) -> Self {
         let peer = Self {
             stdin: Arc::new(Mutex::new(stdin)),
+            pending_turns: Arc::new(AtomicUsize::new(0)),
         };
 
         let reader_peer = peer.clone();
@@ -89,7 +95,16 @@ impl ProtocolPeer {
                                 Ok(CLIMessage::ControlResponse { .. }) => {}
                                 Ok(CLIMessage::Result(_)) => {
                                     client.on_non_control(line).await?;
-                                    break;
+                                    // Keep running while follow-up messages are still queued
+                                    let remaining = self
+                                        .pending_turns
+                                        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                                            Some(n.saturating_sub(1))
+                                        })
+                                        .map_or(0, |n| n.saturating_sub(1));
+                                    if remaining == 0 {
+                                        break;
+                                    }
                                 }
                                 _ => {
                                     client.on_non_control(line).await?;
@@ -202,7 +217,12 @@ impl ProtocolPeer {
 
     pub async fn send_user_message(&self, content: String) -> Result<(), ExecutorError> {
         let message = Message::new_user(content);
-        self.send_json(&message).await
+        self.pending_turns.fetch_add(1, Ordering::SeqCst);
+        if let Err(e) = self.send_json(&message).await {
+            self.pending_turns.fetch_sub(1, Ordering::SeqCst);
+            return Err(e);
+        }
+        Ok(())
     }
 
     pub async fn initialize(&self, hooks: Option<serde_json::Value>) -> Result<(), ExecutorError> {
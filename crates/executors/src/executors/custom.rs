@@ -0,0 +1,291 @@
+use std::{path::Path, process::Stdio, sync::Arc};
+
+use async_trait::async_trait;
+use command_group::AsyncCommandGroup;
+use futures::StreamExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::{io::AsyncWriteExt, process::Command};
+use ts_rs::TS;
+use workspace_utils::msg_store::MsgStore;
+
+use crate::{
+    command::{CmdOverrides, CommandBuilder, CommandParts, apply_overrides},
+    env::ExecutionEnv,
+    executors::{
+        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+        claude::{ClaudeLogProcessor, HistoryStrategy},
+    },
+    logs::{
+        NormalizedEntry, NormalizedEntryType, plain_text_processor::PlainTextLogProcessor,
+        stderr_processor::normalize_stderr_logs, utils::EntryIndexProvider,
+    },
+};
+
+const PROMPT_PLACEHOLDER: &str = "{prompt}";
+const SESSION_ID_PLACEHOLDER: &str = "{session_id}";
+
+/// How the stdout of a custom executor is turned into conversation entries: each line as
+/// an assistant message, or parsed as Claude Code compatible `stream-json` output
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomLogFormat {
+    #[default]
+    PlainText,
+    ClaudeStreamJson,
+}
+
+/// User-defined executor declared under `CUSTOM` in profiles.json, e.g. a local LLM CLI.
+///
+/// The prompt replaces `{prompt}` in the command; without the placeholder it is written
+/// to stdin. Follow-ups run `follow_up_command` with `{session_id}` substituted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct Custom {
+    #[serde(default)]
+    pub append_prompt: AppendPrompt,
+    #[schemars(
+        title = "Command",
+        description = "Command line to run, e.g. `ollama run qwen2.5-coder {prompt}`. Without {prompt} the prompt is sent on stdin"
+    )]
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Follow-up Command",
+        description = "Command line for follow-ups; supports {prompt} and {session_id}. Follow-ups are disabled when unset"
+    )]
+    pub follow_up_command: Option<String>,
+    #[serde(default)]
+    #[schemars(
+        title = "Log Format",
+        description = "How the executor's stdout is parsed into the conversation"
+    )]
+    pub log_format: CustomLogFormat,
+    #[serde(flatten)]
+    pub cmd: CmdOverrides,
+}
+
+impl Custom {
+    fn build_command(&self, template: &str, prompt: &str) -> Result<CommandParts, ExecutorError> {
+        let builder = apply_overrides(CommandBuilder::new(template), &self.cmd);
+        Ok(builder
+            .build_initial()?
+            .substitute(PROMPT_PLACEHOLDER, prompt))
+    }
+
+    fn prompt_on_stdin(&self, template: &str) -> bool {
+        let template = self
+            .cmd
+            .base_command_override
+            .as_deref()
+            .unwrap_or(template);
+        !template.contains(PROMPT_PLACEHOLDER)
+            && !self
+                .cmd
+                .additional_params
+                .iter()
+                .flatten()
+                .any(|p| p.contains(PROMPT_PLACEHOLDER))
+    }
+
+    async fn spawn_internal(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        command_parts: CommandParts,
+        prompt_on_stdin: bool,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let (program_path, args) = command_parts.into_resolved().await?;
+
+        let mut command = Command::new(program_path);
+        command
+            .kill_on_drop(true)
+            .stdin(if prompt_on_stdin {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir)
+            .args(&args);
+
+        env.clone()
+            .with_profile(&self.cmd)
+            .apply_to_command(&mut command);
+
+        let mut child = command.group_spawn()?;
+
+        if prompt_on_stdin && let Some(mut stdin) = child.inner().stdin.take() {
+            stdin.write_all(prompt.as_bytes()).await?;
+            stdin.shutdown().await?;
+        }
+
+        Ok(child.into())
+    }
+
+    fn create_stdout_normalizer(index_provider: EntryIndexProvider) -> PlainTextLogProcessor {
+        PlainTextLogProcessor::builder()
+            .normalized_entry_producer(Box::new(|content: String| NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::AssistantMessage,
+                content,
+                metadata: None,
+                agent_id: None,
+                agent_color: None,
+            }))
+            .transform_lines(Box::new(|lines| {
+                lines.iter_mut().for_each(|line| {
+                    *line = strip_ansi_escapes::strip_str(&line);
+                })
+            }))
+            .index_provider(index_provider)
+            .build()
+    }
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Custom {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let command_parts = self.build_command(&self.command, &combined_prompt)?;
+        let prompt_on_stdin = self.prompt_on_stdin(&self.command);
+        self.spawn_internal(
+            current_dir,
+            &combined_prompt,
+            command_parts,
+            prompt_on_stdin,
+            env,
+        )
+        .await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let template = self.follow_up_command.as_deref().ok_or_else(|| {
+            ExecutorError::FollowUpNotSupported(
+                "custom executor has no follow_up_command configured".to_string(),
+            )
+        })?;
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let command_parts = self
+            .build_command(template, &combined_prompt)?
+            .substitute(SESSION_ID_PLACEHOLDER, session_id);
+        let prompt_on_stdin = self.prompt_on_stdin(template);
+        self.spawn_internal(
+            current_dir,
+            &combined_prompt,
+            command_parts,
+            prompt_on_stdin,
+            env,
+        )
+        .await
+    }
+
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &Path) {
+        let entry_index_provider = EntryIndexProvider::start_from(&msg_store);
+        normalize_stderr_logs(msg_store.clone(), entry_index_provider.clone());
+
+        match self.log_format {
+            CustomLogFormat::ClaudeStreamJson => ClaudeLogProcessor::process_logs(
+                msg_store,
+                current_dir,
+                entry_index_provider,
+                HistoryStrategy::Default,
+            ),
+            CustomLogFormat::PlainText => {
+                tokio::spawn(async move {
+                    let mut stdout_lines = msg_store.stdout_lines_stream();
+                    let mut processor = Self::create_stdout_normalizer(entry_index_provider);
+                    while let Some(Ok(line)) = stdout_lines.next().await {
+                        for patch in processor.process(line + "\n") {
+                            msg_store.push_patch(patch);
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+
+    fn get_availability_info(&self) -> AvailabilityInfo {
+        let program = CommandBuilder::new(self.command.as_str())
+            .build_initial()
+            .ok()
+            .map(|parts| parts.program().to_string());
+        let found = program.is_some_and(|program| {
+            let path = Path::new(&program);
+            if path.components().count() > 1 {
+                return path.exists();
+            }
+            std::env::var_os("PATH").is_some_and(|paths| {
+                std::env::split_paths(&paths).any(|dir| dir.join(&program).is_file())
+            })
+        });
+
+        if found {
+            AvailabilityInfo::InstallationFound
+        } else {
+            AvailabilityInfo::NotFound
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom(command: &str) -> Custom {
+        serde_json::from_value(serde_json::json!({ "command": command })).unwrap()
+    }
+
+    #[test]
+    fn prompt_stays_a_single_argument() {
+        let executor = custom("llm run --model local {prompt}");
+        let parts = executor
+            .build_command(&executor.command, "fix the \"bug\" in main.rs")
+            .unwrap();
+        assert_eq!(parts.program(), "llm");
+        assert_eq!(
+            parts.args(),
+            ["run", "--model", "local", "fix the \"bug\" in main.rs"]
+        );
+        assert!(!executor.prompt_on_stdin(&executor.command));
+    }
+
+    #[test]
+    fn prompt_goes_to_stdin_without_placeholder() {
+        let executor = custom("llm chat");
+        assert!(executor.prompt_on_stdin(&executor.command));
+        assert_eq!(executor.log_format, CustomLogFormat::PlainText);
+    }
+
+    #[test]
+    fn follow_up_substitutes_session_id() {
+        let executor: Custom = serde_json::from_value(serde_json::json!({
+            "command": "llm {prompt}",
+            "follow_up_command": "llm --resume={session_id} {prompt}",
+            "log_format": "claude_stream_json"
+        }))
+        .unwrap();
+        let parts = executor
+            .build_command(executor.follow_up_command.as_deref().unwrap(), "continue")
+            .unwrap()
+            .substitute(SESSION_ID_PLACEHOLDER, "abc");
+        assert_eq!(parts.args(), ["--resume=abc", "continue"]);
+        assert_eq!(executor.log_format, CustomLogFormat::ClaudeStreamJson);
+    }
+}
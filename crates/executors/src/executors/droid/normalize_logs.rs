@@ -15,6 +15,7 @@ use crate::logs::{
     ActionType, CommandExitStatus, CommandRunResult, FileChange, NormalizedEntry,
     NormalizedEntryError, NormalizedEntryType, TodoItem, ToolResult, ToolStatus,
     plain_text_processor::PlainTextLogProcessor,
+    stderr_processor::classify_stderr,
     utils::{
         EntryIndexProvider,
         patch::{add_normalized_entry, replace_normalized_entry},
@@ -676,7 +677,7 @@ fn normalize_stderr_logs(msg_store: Arc<MsgStore>, entry_index_provider: EntryIn
             .normalized_entry_producer(Box::new(|content: String| NormalizedEntry {
                 timestamp: None,
                 entry_type: NormalizedEntryType::ErrorMessage {
-                    error_type: NormalizedEntryError::Other,
+                    error_type: classify_stderr(&content),
                 },
                 content,
                 metadata: None, agent_id: None, agent_color: None,
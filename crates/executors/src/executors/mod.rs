@@ -19,7 +19,7 @@ use crate::{
     env::ExecutionEnv,
     executors::{
         amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot, cursor::CursorAgent,
-        droid::Droid, gemini::Gemini, opencode::Opencode, qwen::QwenCode,
+        custom::Custom, droid::Droid, gemini::Gemini, opencode::Opencode, qwen::QwenCode,
     },
     mcp_config::McpConfig,
 };
@@ -30,6 +30,7 @@ pub mod claude;
 pub mod codex;
 pub mod copilot;
 pub mod cursor;
+pub mod custom;
 pub mod droid;
 pub mod gemini;
 pub mod opencode;
@@ -100,6 +101,7 @@ pub enum CodingAgent {
     QwenCode,
     Copilot,
     Droid,
+    Custom,
 }
 
 impl CodingAgent {
@@ -166,7 +168,7 @@ impl CodingAgent {
                 BaseAgentCapability::SetupHelper,
             ],
             Self::CursorAgent(_) => vec![BaseAgentCapability::SetupHelper],
-            Self::Copilot(_) => vec![],
+            Self::Copilot(_) | Self::Custom(_) => vec![],
         }
     }
 }
@@ -248,6 +250,10 @@ pub type ExecutorExitSignal = tokio::sync::oneshot::Receiver<ExecutorExitResult>
 /// When sent, the executor should attempt to interrupt gracefully before being killed.
 pub type InterruptSender = tokio::sync::oneshot::Sender<()>;
 
+/// Sender for user messages injected into a running executor.
+/// Only executors with an interactive stdin protocol provide one.
+pub type MessageSender = tokio::sync::mpsc::UnboundedSender<String>;
+
 #[derive(Debug)]
 pub struct SpawnedChild {
     pub child: AsyncGroupChild,
@@ -255,6 +261,8 @@ pub struct SpawnedChild {
     pub exit_signal: Option<ExecutorExitSignal>,
     /// Container → Executor: signals when container wants to interrupt
     pub interrupt_sender: Option<InterruptSender>,
+    /// Container → Executor: follow-up user messages sent while the agent is running
+    pub message_sender: Option<MessageSender>,
 }
 
 impl From<AsyncGroupChild> for SpawnedChild {
@@ -263,6 +271,7 @@ impl From<AsyncGroupChild> for SpawnedChild {
             child,
             exit_signal: None,
             interrupt_sender: None,
+            message_sender: None,
         }
     }
 }
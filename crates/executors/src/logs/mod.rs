@@ -67,6 +67,14 @@ pub struct NormalizedConversation {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum NormalizedEntryError {
     SetupRequired,
+    /// Missing, invalid or expired credentials
+    AuthFailed,
+    /// The provider rejected the request because of rate limits or quota
+    RateLimited,
+    /// Connection, DNS or timeout failure
+    Network,
+    /// Compiler or type checker errors
+    CompileError,
     Other,
 }
 
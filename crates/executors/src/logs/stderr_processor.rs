@@ -1,16 +1,21 @@
 //! Standard stderr log processor for executors
 //!
 //! Uses `PlainTextLogProcessor` with a 2-second `latency_threshold` to split stderr streams into entries.
-//! Each entry is normalized as `ErrorMessage` and emitted as JSON patches to the message store.
+//! Each entry is normalized as `ErrorMessage`, classified by [`classify_stderr`], and emitted as
+//! JSON patches to the message store.
 //!
 //! Example:
 //! ```rust,ignore
 //! normalize_stderr_logs(msg_store.clone(), EntryIndexProvider::new());
 //! ```
 //!
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
 
 use futures::StreamExt;
+use regex::Regex;
 use workspace_utils::msg_store::MsgStore;
 
 use super::{
@@ -19,6 +24,78 @@ use super::{
 };
 use crate::logs::utils::EntryIndexProvider;
 
+/// Patterns checked in order; the first match decides the error type. Compiler output
+/// goes first since its line numbers and messages can look like status codes. Status
+/// codes only count next to an HTTP/status label or their reason phrase.
+static STDERR_PATTERNS: LazyLock<Vec<(NormalizedEntryError, Regex)>> = LazyLock::new(|| {
+    let patterns: [(NormalizedEntryError, &[&str]); 4] = [
+        (
+            NormalizedEntryError::CompileError,
+            &[
+                r"error\[E\d{4}\]",
+                r"error TS\d+",
+                r"could not compile",
+                r"compilation failed",
+                r"SyntaxError",
+                r":\d+:\d+: (fatal )?error:",
+            ],
+        ),
+        (
+            NormalizedEntryError::RateLimited,
+            &[
+                r"\b(http|status)( code)?:? 429\b",
+                r"\b429 too many",
+                r"rate[ _-]?limit",
+                r"too many requests",
+                r"quota exceeded",
+                r"resource_exhausted",
+                r"\boverloaded",
+            ],
+        ),
+        (
+            NormalizedEntryError::AuthFailed,
+            &[
+                r"\b(http|status)( code)?:? 401\b",
+                r"unauthori[sz]ed",
+                r"invalid[ _-]?api[ _-]?key",
+                r"authentication[ _-](failed|error)",
+                r"not logged in",
+                r"please (log|sign) ?in",
+                r"(token|credentials?) (has )?expired",
+                r"permission denied \(publickey",
+            ],
+        ),
+        (
+            NormalizedEntryError::Network,
+            &[
+                r"econnrefused|econnreset|etimedout|enotfound|eai_again",
+                r"connection (refused|reset|timed out)",
+                r"could not resolve host",
+                r"network is unreachable",
+                r"socket hang up",
+                r"getaddrinfo",
+                r"failed to connect",
+            ],
+        ),
+    ];
+    patterns
+        .into_iter()
+        .map(|(error_type, alternatives)| {
+            let pattern = format!("(?i){}", alternatives.join("|"));
+            (error_type, Regex::new(&pattern).unwrap())
+        })
+        .collect()
+});
+
+/// Classify a stderr entry into a specific error type, falling back to `Other`
+pub fn classify_stderr(content: &str) -> NormalizedEntryError {
+    STDERR_PATTERNS
+        .iter()
+        .find(|(_, pattern)| pattern.is_match(content))
+        .map(|(error_type, _)| error_type.clone())
+        .unwrap_or(NormalizedEntryError::Other)
+}
+
 /// Standard stderr log normalizer that uses PlainTextLogProcessor to stream error logs.
 ///
 /// Splits stderr output into discrete entries based on a latency threshold (2s) to group
@@ -27,7 +104,7 @@ use crate::logs::utils::EntryIndexProvider;
 ///
 /// # Options
 /// - `latency_threshold`: 2 seconds to separate error messages based on time gaps.
-/// - `normalized_entry_producer`: maps each chunk into an `ErrorMessage` entry.
+/// - `normalized_entry_producer`: maps each chunk into a classified `ErrorMessage` entry.
 ///
 /// # Use case
 /// Intended for executor stderr streams, grouping multi-line errors into cohesive entries
@@ -42,13 +119,16 @@ pub fn normalize_stderr_logs(msg_store: Arc<MsgStore>, entry_index_provider: Ent
 
         // Create a processor with time-based emission for stderr
         let mut processor = PlainTextLogProcessor::builder()
-            .normalized_entry_producer(Box::new(|content: String| NormalizedEntry {
-                timestamp: None,
-                entry_type: NormalizedEntryType::ErrorMessage {
-                    error_type: NormalizedEntryError::Other,
-                },
-                content: strip_ansi_escapes::strip_str(&content),
-                metadata: None, agent_id: None, agent_color: None,
+            .normalized_entry_producer(Box::new(|content: String| {
+                let content = strip_ansi_escapes::strip_str(&content);
+                NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::ErrorMessage {
+                        error_type: classify_stderr(&content),
+                    },
+                    content,
+                    metadata: None, agent_id: None, agent_color: None,
+                }
             }))
             .time_gap(Duration::from_secs(2)) // Break messages if they are 2 seconds apart
             .index_provider(entry_index_provider)
@@ -61,3 +141,57 @@ pub fn normalize_stderr_logs(msg_store: Arc<MsgStore>, entry_index_provider: Ent
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_provider_errors() {
+        assert_eq!(
+            classify_stderr("API Error: 429 Too Many Requests"),
+            NormalizedEntryError::RateLimited
+        );
+        assert_eq!(
+            classify_stderr("request failed with status 429"),
+            NormalizedEntryError::RateLimited
+        );
+        assert_eq!(
+            classify_stderr("Error: Invalid API key. Please run /login"),
+            NormalizedEntryError::AuthFailed
+        );
+        assert_eq!(
+            classify_stderr("request to https://api.example.com failed: getaddrinfo ENOTFOUND"),
+            NormalizedEntryError::Network
+        );
+    }
+
+    #[test]
+    fn classifies_compiler_errors() {
+        assert_eq!(
+            classify_stderr("error[E0308]: mismatched types\n --> src/main.rs:4:5"),
+            NormalizedEntryError::CompileError
+        );
+        assert_eq!(
+            classify_stderr("src/app.ts(3,7): error TS2322: Type 'string' is not assignable"),
+            NormalizedEntryError::CompileError
+        );
+        // Line 429 is not a rate limit
+        assert_eq!(
+            classify_stderr("src/main.rs:429:5: error: expected `;`"),
+            NormalizedEntryError::CompileError
+        );
+    }
+
+    #[test]
+    fn falls_back_to_other() {
+        assert_eq!(
+            classify_stderr("warning: unused variable `x`"),
+            NormalizedEntryError::Other
+        );
+        assert_eq!(
+            classify_stderr("Processed 429 files in 401ms"),
+            NormalizedEntryError::Other
+        );
+    }
+}
@@ -2,11 +2,14 @@ use std::sync::Arc;
 
 use json_patch::Patch;
 use serde::{Deserialize, Serialize};
-use serde_json::{from_value, json, to_value};
+use serde_json::{Value, from_value, json, to_value};
 use ts_rs::TS;
 use workspace_utils::{diff::Diff, msg_store::MsgStore};
 
-use crate::logs::{NormalizedEntry, utils::EntryIndexProvider};
+use crate::{
+    actions::AgentIdentity,
+    logs::{NormalizedEntry, utils::EntryIndexProvider},
+};
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, TS)]
 #[serde(rename_all = "lowercase")]
@@ -142,6 +145,32 @@ pub fn extract_normalized_entry_from_patch(patch: &Patch) -> Option<(usize, Norm
     })
 }
 
+/// Attribute the normalized entries a patch adds or replaces to `agent`. Entries that
+/// already name an agent (e.g. agent switches) keep theirs.
+pub fn attribute_to_agent(patch: Patch, agent: &AgentIdentity) -> Patch {
+    let Ok(mut value) = to_value(&patch) else {
+        return patch;
+    };
+    let Some(ops) = value.as_array_mut() else {
+        return patch;
+    };
+    for op in ops {
+        let Some(entry) = op
+            .get_mut("value")
+            .filter(|value| value.get("type").and_then(Value::as_str) == Some("NORMALIZED_ENTRY"))
+            .and_then(|value| value.get_mut("content"))
+            .and_then(Value::as_object_mut)
+        else {
+            continue;
+        };
+        if entry.get("agent_id").is_none_or(Value::is_null) {
+            entry.insert("agent_id".to_string(), json!(agent.agent_id));
+            entry.insert("agent_color".to_string(), json!(agent.agent_color));
+        }
+    }
+    from_value(value).unwrap_or(patch)
+}
+
 pub fn upsert_normalized_entry(
     msg_store: &Arc<MsgStore>,
     index: usize,
@@ -175,3 +204,39 @@ pub fn replace_normalized_entry(
 ) {
     upsert_normalized_entry(msg_store, index, normalized_entry, false);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::NormalizedEntryType;
+
+    #[test]
+    fn attributes_unowned_entries_only() {
+        let agent = AgentIdentity {
+            agent_id: "reviewer".to_string(),
+            agent_color: Some("#22c55e".to_string()),
+        };
+        let entry = NormalizedEntry::new(
+            None,
+            NormalizedEntryType::AssistantMessage,
+            "Looks good".to_string(),
+            None,
+        );
+
+        let patch =
+            attribute_to_agent(ConversationPatch::add_normalized_entry(0, entry.clone()), &agent);
+        let (_, attributed) = extract_normalized_entry_from_patch(&patch).unwrap();
+        assert_eq!(attributed.agent_id.as_deref(), Some("reviewer"));
+        assert_eq!(attributed.agent_color.as_deref(), Some("#22c55e"));
+
+        let owned = entry.with_agent(Some("planner".to_string()), None);
+        let patch = attribute_to_agent(ConversationPatch::replace(0, owned), &agent);
+        let (_, kept) = extract_normalized_entry_from_patch(&patch).unwrap();
+        assert_eq!(kept.agent_id.as_deref(), Some("planner"));
+        assert_eq!(kept.agent_color, None);
+
+        // Raw output patches are left alone
+        let stdout = ConversationPatch::add_stdout(1, "done".to_string());
+        assert_eq!(attribute_to_agent(stdout.clone(), &agent), stdout);
+    }
+}
@@ -9,13 +9,46 @@ use serde_json::{Map, Value};
 use tokio::fs;
 use ts_rs::TS;
 
-use crate::executors::{CodingAgent, ExecutorError};
+use crate::{
+    command::CommandBuildError,
+    executors::{CodingAgent, ExecutorError},
+};
 
 static DEFAULT_MCP_JSON: &str = include_str!("../default_mcp.json");
 pub static PRECONFIGURED_MCP_SERVERS: LazyLock<Value> = LazyLock::new(|| {
     serde_json::from_str::<Value>(DEFAULT_MCP_JSON).expect("Failed to parse default MCP JSON")
 });
 
+/// Extra stdio MCP server configured for a single agent, on top of the user's global config
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct AgentMcpServer {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+/// `{"mcpServers": {...}}` document accepted by Claude Code's `--mcp-config` and
+/// Copilot's `--additional-mcp-config`
+fn agent_mcp_servers_json(servers: &[AgentMcpServer]) -> Value {
+    let servers: Map<String, Value> = servers
+        .iter()
+        .map(|server| {
+            (
+                server.name.clone(),
+                serde_json::json!({
+                    "command": server.command,
+                    "args": server.args,
+                    "env": server.env,
+                }),
+            )
+        })
+        .collect();
+    serde_json::json!({ "mcpServers": servers })
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct McpConfig {
     servers: HashMap<String, serde_json::Value>,
@@ -293,7 +326,10 @@ impl CodingAgent {
         use Adapter::*;
 
         let adapter = match self {
-            CodingAgent::ClaudeCode(_) | CodingAgent::Amp(_) | CodingAgent::Droid(_) => Passthrough,
+            CodingAgent::ClaudeCode(_)
+            | CodingAgent::Amp(_)
+            | CodingAgent::Droid(_)
+            | CodingAgent::Custom(_) => Passthrough,
             CodingAgent::QwenCode(_) | CodingAgent::Gemini(_) => Gemini,
             CodingAgent::CursorAgent(_) => Cursor,
             CodingAgent::Codex(_) => Codex,
@@ -304,4 +340,75 @@ impl CodingAgent {
         let canonical = PRECONFIGURED_MCP_SERVERS.clone();
         apply_adapter(adapter, canonical)
     }
+
+    /// Add per-run MCP servers to the executor's command line. Returns false when the
+    /// executor has no per-run MCP flag; the servers are then left out.
+    pub fn add_mcp_servers(&mut self, servers: &[AgentMcpServer]) -> Result<bool, ExecutorError> {
+        if servers.is_empty() {
+            return Ok(true);
+        }
+        let (flag, cmd) = match self {
+            CodingAgent::ClaudeCode(claude) => ("--mcp-config", &mut claude.cmd),
+            CodingAgent::Copilot(copilot) => ("--additional-mcp-config", &mut copilot.cmd),
+            _ => return Ok(false),
+        };
+        let config = agent_mcp_servers_json(servers).to_string();
+        let config = shlex::try_quote(&config).map_err(CommandBuildError::from)?;
+        cmd.additional_params
+            .get_or_insert_with(Vec::new)
+            .push(format!("{flag}={config}"));
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn docs_server() -> AgentMcpServer {
+        AgentMcpServer {
+            name: "docs".to_string(),
+            command: "npx".to_string(),
+            args: vec!["-y".to_string(), "docs-search-mcp".to_string()],
+            env: HashMap::from([("DOCS_TOKEN".to_string(), "secret value".to_string())]),
+        }
+    }
+
+    #[test]
+    fn claude_code_gets_mcp_config_flag() {
+        let mut agent: CodingAgent =
+            serde_json::from_value(serde_json::json!({ "CLAUDE_CODE": {} })).unwrap();
+        assert!(agent.add_mcp_servers(&[docs_server()]).unwrap());
+
+        let CodingAgent::ClaudeCode(claude) = agent else {
+            unreachable!()
+        };
+        let params = claude.cmd.additional_params.unwrap();
+        let args = shlex::split(&params.join(" ")).unwrap();
+        assert_eq!(args.len(), 1);
+        let config = args[0].strip_prefix("--mcp-config=").unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(config).unwrap(),
+            serde_json::json!({
+                "mcpServers": {
+                    "docs": {
+                        "command": "npx",
+                        "args": ["-y", "docs-search-mcp"],
+                        "env": { "DOCS_TOKEN": "secret value" },
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn executors_without_flag_are_left_unchanged() {
+        let mut agent: CodingAgent =
+            serde_json::from_value(serde_json::json!({ "GEMINI": {} })).unwrap();
+        assert!(!agent.add_mcp_servers(&[docs_server()]).unwrap());
+        assert_eq!(
+            agent,
+            serde_json::from_value(serde_json::json!({ "GEMINI": {} })).unwrap()
+        );
+    }
 }
@@ -1,20 +1,43 @@
 use command_group::AsyncGroupChild;
 #[cfg(unix)]
 use nix::{
-    sys::signal::{Signal, killpg},
+    sys::signal::{Signal, kill, killpg},
     unistd::{Pid, getpgid},
 };
-use services::services::container::ContainerError;
 #[cfg(unix)]
+use services::services::process_reaper::{self, ProcessReaper};
+use services::services::container::ContainerError;
 use tokio::time::Duration;
+use uuid::Uuid;
+
+/// Keeps `taskkill` from flashing a console window
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// The execution's process tree: the group, plus descendants that left it
+#[cfg(unix)]
+async fn process_tree(leader: u32, pgid: Pid) -> Vec<u32> {
+    let own_pid = std::process::id();
+    let table = process_reaper::process_table().await;
+    process_reaper::process_tree(&table, leader, pgid.as_raw() as u32)
+        .into_iter()
+        .filter(|pid| *pid != own_pid)
+        .collect()
+}
 
-pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), ContainerError> {
-    // hit the whole process group, not just the leader
+pub async fn kill_process_group(
+    child: &mut AsyncGroupChild,
+    execution_process_id: Uuid,
+) -> Result<(), ContainerError> {
+    // hit the whole process tree, not just the leader
     #[cfg(unix)]
     {
         if let Some(pid) = child.inner().id() {
             let pgid = getpgid(Some(Pid::from_raw(pid as i32)))
                 .map_err(|e| ContainerError::KillFailed(std::io::Error::other(e)))?;
+            // Snapshot before signalling: children of a dead parent are reparented and
+            // can no longer be traced back to the execution
+            let mut tree = process_tree(pid, pgid).await;
 
             for sig in [Signal::SIGINT, Signal::SIGTERM, Signal::SIGKILL] {
                 if let Err(e) = killpg(pgid, sig) {
@@ -25,6 +48,67 @@ pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), Conta
                         e
                     );
                 }
+                // Members that called setsid or daemonized don't get the group signal
+                for member in tree.iter().filter(|member| **member != pid) {
+                    let _ = kill(Pid::from_raw(*member as i32), sig);
+                }
+                tokio::time::sleep(Duration::from_secs(2)).await;
+                if child
+                    .inner()
+                    .try_wait()
+                    .map_err(ContainerError::Io)?
+                    .is_some()
+                {
+                    break;
+                }
+                for member in process_tree(pid, pgid).await {
+                    if !tree.contains(&member) {
+                        tree.push(member);
+                    }
+                }
+            }
+
+            // Whatever is left once the leader is gone is force killed; processes that
+            // survive even that are retried by the reaper
+            let alive: Vec<_> = process_reaper::process_table()
+                .await
+                .into_iter()
+                .filter(|p| !p.zombie && p.pid != pid && tree.contains(&p.pid))
+                .collect();
+            if !alive.is_empty() {
+                tracing::warn!(
+                    "{} process(es) of execution {} outlived its leader; force killing",
+                    alive.len(),
+                    execution_process_id
+                );
+                for member in &alive {
+                    let _ = kill(Pid::from_raw(member.pid as i32), Signal::SIGKILL);
+                }
+                ProcessReaper::shared().track(execution_process_id, &alive);
+            }
+        }
+    }
+
+    // No signals on Windows: ask the process tree to close, then force it. `taskkill /T`
+    // also reaches children that broke away from the group's job object.
+    #[cfg(windows)]
+    {
+        let _ = execution_process_id;
+        if let Some(pid) = child.inner().id() {
+            for force in [false, true] {
+                let pid = pid.to_string();
+                let mut args = vec!["/PID", pid.as_str(), "/T"];
+                if force {
+                    args.push("/F");
+                }
+                if let Err(e) = tokio::process::Command::new("taskkill")
+                    .args(&args)
+                    .creation_flags(CREATE_NO_WINDOW)
+                    .output()
+                    .await
+                {
+                    tracing::warn!("Failed to run taskkill for process tree {}: {}", pid, e);
+                }
                 tokio::time::sleep(Duration::from_secs(2)).await;
                 if child
                     .inner()
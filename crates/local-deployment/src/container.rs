@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     io,
     path::{Path, PathBuf},
     sync::Arc,
@@ -12,11 +12,22 @@ use command_group::AsyncGroupChild;
 use db::{
     DBService,
     models::{
+        agent_mcp_settings::AgentMcpSettings,
+        agent_session::AgentSession,
+        chat_approval::ChatApprovalSubject,
         coding_agent_turn::CodingAgentTurn,
+        dry_run_patch::DryRunPatch,
+        execution_environment::ExecutionEnvironment,
+        execution_log_redaction::ExecutionLogRedaction,
         execution_process::{
             ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
         },
         execution_process_repo_state::ExecutionProcessRepoState,
+        execution_token_usage::ExecutionTokenUsage,
+        kanban_column::KanbanColumn,
+        notification_channel::NotificationEvent,
+        plan_review::PlanReview,
+        project_log_redaction_settings::ProjectLogRedactionSettings,
         project_repo::ProjectRepo,
         repo::Repo,
         scratch::{DraftFollowUpData, Scratch, ScratchType},
@@ -32,39 +43,57 @@ use executors::{
         Executable, ExecutorAction, ExecutorActionType,
         coding_agent_follow_up::CodingAgentFollowUpRequest,
         coding_agent_initial::CodingAgentInitialRequest,
+        script::{ScriptContext, ScriptRequest},
     },
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
     env::ExecutionEnv,
-    executors::{BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender},
+    executors::{
+        BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender,
+        MessageSender, claude::ClaudeLogProcessor,
+    },
     logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
     profile::ExecutorProfileId,
 };
-use futures::{FutureExt, TryStreamExt, stream::select};
+use futures::{FutureExt, Stream, StreamExt, TryStreamExt, future, stream::select};
 use serde_json::json;
 use services::services::{
+    agent_concurrency::AgentConcurrencyLimiter,
     analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
+    automation,
+    chat_approvals,
     config::Config,
     container::{ContainerError, ContainerRef, ContainerService},
+    decision_file::{DECISIONS_DIR_ENV, decisions_dir},
+    dev_server_preview::DevServerPreviews,
     diff_stream::{self, DiffStreamHandle},
+    env_vault::EnvVault,
     events::{execution_process_patch, task_patch},
+    execution_environment,
     git::{Commit, GitCli, GitService},
     image::ImageService,
+    mcp_credentials::{MCP_TOKEN_ENV_VAR, McpCredentials},
     notification::NotificationService,
+    notification_channels::{self, ChannelNotification},
+    plan_review::read_plan_file,
+    process_reaper::ProcessReaper,
     queued_message::QueuedMessageService,
     share::SharePublisher,
     workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
+    workspace_templates,
 };
 use tokio::{sync::RwLock, task::JoinHandle};
 use tokio_util::io::ReaderStream;
 use utils::{
     log_msg::LogMsg,
     msg_store::MsgStore,
+    output_limit::{OutputLimit, OutputLimiter},
+    redact::{LineRedactor, SecretRedactor},
     text::{git_branch_id, short_uuid, truncate_to_char_boundary},
 };
 use uuid::Uuid;
 
-use crate::{command, copy};
+use crate::{command, copy, workspace_runtime::WorkspaceRuntime};
 
 /// Info about a successful commit
 pub struct CommitInfo {
@@ -78,6 +107,7 @@ pub struct LocalContainerService {
     db: DBService,
     child_store: Arc<RwLock<HashMap<Uuid, Arc<RwLock<AsyncGroupChild>>>>>,
     interrupt_senders: Arc<RwLock<HashMap<Uuid, InterruptSender>>>,
+    message_senders: Arc<RwLock<HashMap<Uuid, MessageSender>>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
     events_msg_store: Arc<MsgStore>,
     config: Arc<RwLock<Config>>,
@@ -88,6 +118,8 @@ pub struct LocalContainerService {
     queued_message_service: QueuedMessageService,
     publisher: Result<SharePublisher, RemoteClientNotConfigured>,
     notification_service: NotificationService,
+    agent_limiter: AgentConcurrencyLimiter,
+    dev_server_previews: DevServerPreviews,
 }
 
 impl LocalContainerService {
@@ -107,11 +139,13 @@ impl LocalContainerService {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
         let interrupt_senders = Arc::new(RwLock::new(HashMap::new()));
         let notification_service = NotificationService::new(config.clone());
+        let agent_limiter = AgentConcurrencyLimiter::new(config.clone());
 
         let container = LocalContainerService {
             db,
             child_store,
             interrupt_senders,
+            message_senders: Arc::new(RwLock::new(HashMap::new())),
             msg_stores,
             events_msg_store,
             config,
@@ -122,9 +156,13 @@ impl LocalContainerService {
             queued_message_service,
             publisher,
             notification_service,
+            agent_limiter,
+            dev_server_previews: DevServerPreviews::new(),
         };
 
         container.spawn_workspace_cleanup().await;
+        container.spawn_process_reaper();
+        container.spawn_chat_approvals();
 
         container
     }
@@ -166,6 +204,9 @@ impl LocalContainerService {
     }
 
     pub async fn add_child_to_store(&self, id: Uuid, exec: AsyncGroupChild) {
+        if let Some(pid) = exec.inner().id() {
+            ProcessReaper::shared().register_spawned(id, pid);
+        }
         let mut map = self.child_store.write().await;
         map.insert(id, Arc::new(RwLock::new(exec)));
     }
@@ -185,6 +226,16 @@ impl LocalContainerService {
         map.remove(id)
     }
 
+    async fn add_message_sender(&self, id: Uuid, sender: MessageSender) {
+        let mut map = self.message_senders.write().await;
+        map.insert(id, sender);
+    }
+
+    async fn remove_message_sender(&self, id: &Uuid) {
+        let mut map = self.message_senders.write().await;
+        map.remove(id);
+    }
+
     pub async fn cleanup_workspace(db: &DBService, workspace: &Workspace) {
         let Some(container_ref) = &workspace.container_ref else {
             return;
@@ -211,26 +262,19 @@ impl LocalContainerService {
             }
         }
 
-        if repositories.is_empty() {
-            tracing::warn!(
-                "No repositories found for workspace {}, cleaning up workspace directory only",
-                workspace.id
-            );
-            if workspace_dir.exists()
-                && let Err(e) = tokio::fs::remove_dir_all(&workspace_dir).await
-            {
-                tracing::warn!("Failed to remove workspace directory: {}", e);
-            }
-        } else {
-            WorkspaceManager::cleanup_workspace(&workspace_dir, &repositories)
-                .await
-                .unwrap_or_else(|e| {
-                    tracing::warn!(
-                        "Failed to clean up workspace for workspace {}: {}",
-                        workspace.id,
-                        e
-                    );
-                });
+        let runtime = WorkspaceRuntime::load(db, workspace.id)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load container of workspace {}: {}", workspace.id, e);
+                WorkspaceRuntime::Worktree
+            });
+        runtime.remove(db, workspace, workspace_dir, &repositories).await;
+
+        let decisions_dir = decisions_dir(workspace.id);
+        if decisions_dir.exists()
+            && let Err(e) = tokio::fs::remove_dir_all(&decisions_dir).await
+        {
+            tracing::warn!("Failed to remove decisions directory: {}", e);
         }
 
         // Clear container_ref so this workspace won't be picked up again
@@ -317,6 +361,48 @@ impl LocalContainerService {
         });
     }
 
+    /// Periodically kill processes that outlived their execution and reap zombies
+    pub fn spawn_process_reaper(&self) {
+        let child_store = self.child_store.clone();
+        tokio::spawn(async move {
+            let mut reap_interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                reap_interval.tick().await;
+                // Running executions' exit statuses belong to their exit monitors
+                let mut owned = HashSet::new();
+                for child in child_store.read().await.values() {
+                    if let Some(pid) = child.read().await.inner().id() {
+                        owned.insert(pid);
+                    }
+                }
+                ProcessReaper::shared().sweep(&owned).await;
+            }
+        });
+    }
+
+    /// Periodically read reactions to approval requests posted in Slack and Matrix, and
+    /// act on those answered by a mapped user
+    pub fn spawn_chat_approvals(&self) {
+        let container = self.clone();
+        tokio::spawn(async move {
+            let mut poll_interval = tokio::time::interval(Duration::from_secs(30));
+            loop {
+                poll_interval.tick().await;
+                let pool = &container.db.pool;
+                let decisions = chat_approvals::collect_decisions(pool, &container.approvals).await;
+                for decision in decisions {
+                    if let Err(e) = container.apply_chat_decision(&decision).await {
+                        tracing::error!(
+                            "Failed to act on approval from chat for task {}: {}",
+                            decision.task_id,
+                            e
+                        );
+                    }
+                }
+            }
+        });
+    }
+
     /// Record the current HEAD commit for each repository as the "after" state.
     /// Errors are silently ignored since this runs after the main execution completes
     /// and failure should not block process finalization.
@@ -341,7 +427,7 @@ impl LocalContainerService {
     /// Get the commit message based on the execution run reason.
     async fn get_commit_message(&self, ctx: &ExecutionContext) -> String {
         match ctx.execution_process.run_reason {
-            ExecutionProcessRunReason::CodingAgent => {
+            ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::ManualRun => {
                 // Try to retrieve the task summary from the coding agent turn
                 // otherwise fallback to default message
                 match CodingAgentTurn::find_by_execution_process_id(
@@ -384,6 +470,146 @@ impl LocalContainerService {
         }
     }
 
+    /// Column of the agent session the run belongs to, if any
+    async fn run_column_id(&self, ctx: &ExecutionContext) -> Option<Uuid> {
+        match AgentSession::find_by_session_id(&self.db.pool, ctx.session.id).await {
+            Ok(session) => session.and_then(|s| s.column_id),
+            Err(e) => {
+                tracing::warn!("Failed to load agent session {}: {}", ctx.session.id, e);
+                None
+            }
+        }
+    }
+
+    /// Whether the run belongs to an agent session in a read-only analysis column
+    async fn is_read_only_run(&self, ctx: &ExecutionContext) -> bool {
+        let Some(column_id) = self.run_column_id(ctx).await else {
+            return false;
+        };
+
+        KanbanColumn::is_read_only(&self.db.pool, column_id)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load settings for column {}: {}", column_id, e);
+                false
+            })
+    }
+
+    /// Whether the run belongs to an agent session in a dry-run column
+    async fn is_dry_run(&self, ctx: &ExecutionContext) -> bool {
+        let Some(column_id) = self.run_column_id(ctx).await else {
+            return false;
+        };
+
+        KanbanColumn::is_dry_run(&self.db.pool, column_id)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load settings for column {}: {}", column_id, e);
+                false
+            })
+    }
+
+    /// Whether the run belongs to a plan-only session
+    async fn is_plan_only_run(&self, ctx: &ExecutionContext) -> bool {
+        match PlanReview::find_by_session_id(&self.db.pool, ctx.session.id).await {
+            Ok(review) => review.is_some(),
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load plan review of session {}: {}",
+                    ctx.session.id,
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    /// HEAD of each repo when the run started
+    async fn before_heads(
+        &self,
+        ctx: &ExecutionContext,
+    ) -> Result<HashMap<Uuid, String>, ContainerError> {
+        Ok(ExecutionProcessRepoState::find_by_execution_process_id(
+            &self.db.pool,
+            ctx.execution_process.id,
+        )
+        .await?
+        .into_iter()
+        .filter_map(|state| Some((state.repo_id, state.before_head_commit?)))
+        .collect())
+    }
+
+    /// Save what a dry run changed in each repo as a patch a person can apply later.
+    /// `.vibe/` is left out: it drives the workflow, not the code.
+    async fn save_dry_run_patches(
+        &self,
+        ctx: &ExecutionContext,
+        workspace_root: &Path,
+    ) -> Result<(), ContainerError> {
+        let before_heads = self.before_heads(ctx).await?;
+        for repo in &ctx.repos {
+            let worktree_path = workspace_root.join(&repo.name);
+            let before_head = before_heads.get(&repo.id).map(String::as_str);
+            let patch = self
+                .git()
+                .proposed_patch(&worktree_path, before_head, ".vibe")?;
+            if patch.trim().is_empty() {
+                continue;
+            }
+            DryRunPatch::create(
+                &self.db.pool,
+                ctx.execution_process.id,
+                ctx.task.id,
+                ctx.workspace.id,
+                repo.id,
+                &patch,
+            )
+            .await?;
+            tracing::info!(
+                "Saved dry-run patch for task {} (repo '{}')",
+                ctx.task.id,
+                repo.name
+            );
+        }
+        Ok(())
+    }
+
+    /// Undo commits and file changes made during a read-only run, keeping `.vibe/`
+    /// so the agent's decision and summary files still drive the workflow.
+    async fn discard_read_only_changes(
+        &self,
+        ctx: &ExecutionContext,
+        workspace_root: &Path,
+    ) -> Result<(), ContainerError> {
+        let before_heads = self.before_heads(ctx).await?;
+
+        let git = GitCli::new();
+        for repo in &ctx.repos {
+            let worktree_path = workspace_root.join(&repo.name);
+            let before_head = before_heads.get(&repo.id);
+            let moved = match (before_head, self.git().get_head_info(&worktree_path)) {
+                (Some(before), Ok(head)) => &head.oid != before,
+                _ => false,
+            };
+            let dirty = git.has_changes(&worktree_path).map_err(|e| {
+                ContainerError::Other(anyhow!("Failed to check repo '{}': {}", repo.name, e))
+            })?;
+            if !moved && !dirty {
+                continue;
+            }
+
+            tracing::warn!(
+                "Discarding changes in read-only column for task {} (repo '{}')",
+                ctx.task.id,
+                repo.name
+            );
+            self.git()
+                .discard_changes(&worktree_path, before_head.map(String::as_str), ".vibe")?;
+        }
+
+        Ok(())
+    }
+
     /// Check which repos have uncommitted changes. Fails if any repo is inaccessible.
     fn check_repos_for_changes(
         &self,
@@ -486,7 +712,7 @@ impl LocalContainerService {
                     // Executor signaled completion: kill group and use the provided result
                     if let Some(child_lock) = child_store.read().await.get(&exec_id).cloned() {
                         let mut child = child_lock.write().await ;
-                        if let Err(err) = command::kill_process_group(&mut child).await {
+                        if let Err(err) = command::kill_process_group(&mut child, exec_id).await {
                             tracing::error!("Failed to kill process group after exit signal: {} {}", exec_id, err);
                         }
                     }
@@ -517,13 +743,26 @@ impl LocalContainerService {
                 Err(_) => (None, ExecutionProcessStatus::Failed),
             };
 
-            if !ExecutionProcess::was_stopped(&db.pool, exec_id).await
+            let stopped = ExecutionProcess::was_stopped(&db.pool, exec_id).await;
+            if !stopped
                 && let Err(e) =
                     ExecutionProcess::update_completion(&db.pool, exec_id, status, exit_code).await
             {
                 tracing::error!("Failed to update execution process completion: {}", e);
             }
 
+            // Runs stopped by a person say nothing about the executor's health
+            if !stopped {
+                container
+                    .record_executor_outcome(exec_id, status == ExecutionProcessStatus::Completed)
+                    .await;
+            }
+
+            // Free the agent slot before any follow-up work can queue for a new one
+            container.agent_limiter.release(exec_id).await;
+            container.dev_server_previews.remove_execution(exec_id).await;
+            container.remove_message_sender(&exec_id).await;
+
             // Broadcast updated execution process status to WS subscribers
             if let Ok(Some(updated_process)) = ExecutionProcess::find_by_id(&db.pool, exec_id).await {
                 container
@@ -569,6 +808,15 @@ impl LocalContainerService {
                     }
                 }
 
+                // Manual runs aren't part of the column workflow but still use tokens
+                if matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::ManualRun
+                ) && let Err(e) = container.record_agent_reports(&ctx.execution_process).await
+                {
+                    tracing::warn!("Failed to record agent reports: {}", e);
+                }
+
                 let success = matches!(
                     ctx.execution_process.status,
                     ExecutionProcessStatus::Completed
@@ -595,12 +843,25 @@ impl LocalContainerService {
                         }
                     };
 
-                    // For coding agents, only run cleanup if there were actual changes
+                    // For coding agents, only run cleanup if there were actual changes;
+                    // a column exit script always runs
+                    let next_is_exit_script = ctx.execution_process
+                        .executor_action()
+                        .ok()
+                        .and_then(|a| a.next_action())
+                        .is_some_and(|next| matches!(
+                            next.typ(),
+                            ExecutorActionType::ScriptRequest(ScriptRequest {
+                                context: ScriptContext::ColumnExitScript,
+                                ..
+                            })
+                        ));
                     let should_start_next = if matches!(
                         ctx.execution_process.run_reason,
                         ExecutionProcessRunReason::CodingAgent
+                            | ExecutionProcessRunReason::ManualRun
                     ) {
-                        changes_committed
+                        changes_committed || next_is_exit_script
                     } else {
                         true
                     };
@@ -625,15 +886,24 @@ impl LocalContainerService {
                     }
                 }
 
+                // Flaky setup scripts get one retry, which finalizes when it completes; column
+                // entry scripts share the setup run reason but are never retried
+                let setup_retried = matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::SetupScript
+                ) && container.record_setup_script_run(&ctx).await;
+
                 // Step 2: Finalize if no next action was started.
                 // When next_action_started=true, the next action (cleanup script)
                 // will call finalize_task when IT completes.
-                let needs_finalize = !next_action_started;
+                let needs_finalize = !next_action_started && !setup_retried;
                 if needs_finalize {
                     // Check for queued follow-up messages before finalizing
                     let should_execute_queued = !matches!(
                         ctx.execution_process.status,
-                        ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed
+                        ExecutionProcessStatus::Failed
+                            | ExecutionProcessStatus::Killed
+                            | ExecutionProcessStatus::Interrupted
                     );
 
                     if let Some(queued_msg) =
@@ -718,8 +988,10 @@ impl LocalContainerService {
                 }
             }
 
-            // Cleanup child handle
+            // Cleanup child handle and its MCP token; its exit status has been collected
             child_store.write().await.remove(&exec_id);
+            ProcessReaper::shared().forget_spawned(exec_id);
+            McpCredentials::shared().revoke(exec_id);
         })
     }
 
@@ -765,25 +1037,49 @@ impl LocalContainerService {
         format!("{}-{}", short_uuid(workspace_id), task_title_id)
     }
 
-    async fn track_child_msgs_in_store(&self, id: Uuid, child: &mut AsyncGroupChild) {
+    async fn track_child_msgs_in_store(
+        &self,
+        id: Uuid,
+        child: &mut AsyncGroupChild,
+        redactor: SecretRedactor,
+        limit: OutputLimit,
+    ) {
         let store = Arc::new(MsgStore::new());
 
         let out = child.inner().stdout.take().expect("no stdout");
         let err = child.inner().stderr.take().expect("no stderr");
 
-        // Map stdout bytes -> LogMsg::Stdout
-        let out = ReaderStream::new(out)
-            .map_ok(|chunk| LogMsg::Stdout(String::from_utf8_lossy(&chunk).into_owned()));
+        // Project env vault values and credential patterns are masked here, before
+        // anything reaches the store, so neither persisted raw logs nor normalized
+        // entries contain them.
+        let redactor = Arc::new(redactor);
+        let count_redactor = redactor.clone();
+
+        // Map stdout bytes -> LogMsg::Stdout, capped per stream
+        let out = redact_output(ReaderStream::new(out), redactor.clone());
+        let out = limit_output(out, limit, LogMsg::Stdout);
 
-        // Map stderr bytes -> LogMsg::Stderr
-        let err = ReaderStream::new(err)
-            .map_ok(|chunk| LogMsg::Stderr(String::from_utf8_lossy(&chunk).into_owned()));
+        // Map stderr bytes -> LogMsg::Stderr, capped per stream
+        let err = redact_output(ReaderStream::new(err), redactor);
+        let err = limit_output(err, limit, LogMsg::Stderr);
 
         // If you have a JSON Patch source, map it to LogMsg::JsonPatch too, then select all three.
 
         // Merge and forward into the store
         let merged = select(out, err); // Stream<Item = Result<LogMsg, io::Error>>
-        store.clone().spawn_forwarder(merged);
+        let forwarder = store.clone().spawn_forwarder(merged);
+
+        // Record how many secrets were masked once the output is drained
+        let pool = self.db.pool.clone();
+        tokio::spawn(async move {
+            let _ = forwarder.await;
+            let count = count_redactor.redaction_count();
+            if count > 0
+                && let Err(e) = ExecutionLogRedaction::record(&pool, id, count as i64).await
+            {
+                tracing::warn!("Failed to record log redactions of execution {}: {}", id, e);
+            }
+        });
 
         let mut map = self.msg_stores().write().await;
         map.insert(id, store);
@@ -840,6 +1136,53 @@ impl LocalContainerService {
         None
     }
 
+    /// Persist what the agent reported in the execution's stdout: token usage (Claude
+    /// Code's stream-json `result` lines), its model and its CLI version. Other
+    /// executors don't report usage, so their runs get no usage row rather than zero.
+    async fn record_agent_reports(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<(), anyhow::Error> {
+        let exec_id = &execution_process.id;
+        let stdout = {
+            let msg_stores = self.msg_stores.read().await;
+            let Some(msg_store) = msg_stores.get(exec_id) else {
+                return Ok(());
+            };
+            msg_store
+                .get_history()
+                .iter()
+                .filter_map(|msg| match msg {
+                    LogMsg::Stdout(chunk) => Some(chunk.as_str()),
+                    _ => None,
+                })
+                .collect::<String>()
+        };
+
+        let reports_usage = execution_process
+            .executor_action()
+            .ok()
+            .and_then(|action| action.base_executor())
+            == Some(BaseCodingAgent::ClaudeCode);
+        if reports_usage
+            && let Some((input, output)) = ClaudeLogProcessor::token_usage_from_stdout(&stdout)
+        {
+            ExecutionTokenUsage::upsert(&self.db.pool, *exec_id, input as i64, output as i64)
+                .await?;
+        }
+
+        let reported = execution_environment::reported_by_agent(&stdout);
+        ExecutionEnvironment::set_reported(
+            &self.db.pool,
+            *exec_id,
+            reported.model.as_deref(),
+            reported.executor_version.as_deref(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
     /// Update the coding agent turn summary with the final assistant message
     async fn update_executor_session_summary(&self, exec_id: &Uuid) -> Result<(), anyhow::Error> {
         // Check if there's a coding agent turn for this execution process
@@ -896,6 +1239,118 @@ impl LocalContainerService {
         Ok(())
     }
 
+    /// Write the project's workspace templates; failures don't block the workspace
+    async fn seed_workspace_templates(&self, workspace_dir: &Path, workspace: &Workspace) {
+        match workspace_templates::seed_workspace(&self.db.pool, workspace, workspace_dir).await {
+            Ok(0) => {}
+            Ok(written) => tracing::debug!(
+                "Seeded {} workspace template file(s) into {}",
+                written,
+                workspace_dir.display()
+            ),
+            Err(e) => tracing::warn!("Failed to seed workspace templates: {}", e),
+        }
+    }
+
+    /// Recreate the workspace branch in repos where it was deleted along with the worktree,
+    /// starting from the last HEAD recorded for the workspace or else its target branch.
+    /// Returns the names of the repos whose branch was restored.
+    async fn restore_workspace_branches(
+        &self,
+        workspace: &Workspace,
+        repositories: &[Repo],
+        missing_repos: &[String],
+    ) -> Vec<String> {
+        let target_branches =
+            WorkspaceRepo::find_repos_with_target_branch_for_workspace(&self.db.pool, workspace.id)
+                .await
+                .unwrap_or_default();
+        let mut restored = Vec::new();
+
+        for repo in repositories.iter().filter(|r| missing_repos.contains(&r.name)) {
+            match self.git().check_branch_exists(&repo.path, &workspace.branch) {
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::warn!("Failed to check branch in repo '{}': {}", repo.name, e);
+                    continue;
+                }
+            }
+
+            let recorded_commit =
+                ExecutionProcess::find_latest_head_commit(&self.db.pool, workspace.id, repo.id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to look up recorded commit: {}", e);
+                        None
+                    });
+            let start_point = recorded_commit.or_else(|| {
+                target_branches
+                    .iter()
+                    .find(|t| t.repo.id == repo.id)
+                    .map(|t| t.target_branch.clone())
+            });
+            let Some(start_point) = start_point else {
+                tracing::warn!(
+                    "No recorded commit or target branch to restore '{}' in repo '{}'",
+                    workspace.branch,
+                    repo.name
+                );
+                continue;
+            };
+
+            match self
+                .git()
+                .create_branch_at(&repo.path, &workspace.branch, &start_point)
+            {
+                Ok(()) => {
+                    tracing::info!(
+                        "Restored branch '{}' in repo '{}' at {}",
+                        workspace.branch,
+                        repo.name,
+                        start_point
+                    );
+                    restored.push(repo.name.clone());
+                }
+                Err(e) => tracing::warn!(
+                    "Failed to restore branch '{}' in repo '{}': {}",
+                    workspace.branch,
+                    repo.name,
+                    e
+                ),
+            }
+        }
+
+        restored
+    }
+
+    /// Record a `workspace_recreated` event on the workspace's task
+    async fn record_workspace_recreated(
+        &self,
+        workspace: &Workspace,
+        missing_repos: &[String],
+        restored_branches: &[String],
+    ) {
+        tracing::info!(
+            "Recreated missing worktree(s) {:?} for workspace {}",
+            missing_repos,
+            workspace.id
+        );
+        let event = CreateTaskEvent::workspace_recreated(
+            workspace.task_id,
+            workspace.id,
+            missing_repos,
+            restored_branches,
+        );
+        if let Err(e) = TaskEvent::create(&self.db.pool, &event).await {
+            tracing::error!(
+                "Failed to record workspace recreation for task {}: {}",
+                workspace.task_id,
+                e
+            );
+        }
+    }
+
     /// Create workspace-level CLAUDE.md and AGENTS.md files that import from each repo.
     /// Uses the @import syntax to reference each repo's config files.
     /// Skips creating files if they already exist or if no repos have the source file.
@@ -1005,11 +1460,17 @@ impl LocalContainerService {
                 agent_project_context: None,
                 agent_workflow_history: None,
                 agent_start_command: None,
+                agent_decision_instructions: None,
                 agent_deliverable: None,
+                prompt_sections: None,
+                prompt_language: None,
             })
         };
 
-        let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
+        let agent =
+            ExecutionProcess::latest_agent_for_session(&self.db.pool, ctx.session.id).await?;
+        let action =
+            ExecutorAction::new(action_type, cleanup_action.map(Box::new)).with_agent(agent);
 
         self.start_execution(
             &ctx.workspace,
@@ -1021,6 +1482,47 @@ impl LocalContainerService {
     }
 }
 
+/// Cap one output stream, forwarding a truncation marker once the limit is hit and
+/// the preserved tail when the stream ends
+/// Redact process output line by line, so secrets split across read chunks are masked
+fn redact_output<S>(
+    stream: S,
+    redactor: Arc<SecretRedactor>,
+) -> impl Stream<Item = std::io::Result<String>> + Send + 'static
+where
+    S: Stream<Item = std::io::Result<bytes::Bytes>> + Send + 'static,
+{
+    let lines = Arc::new(std::sync::Mutex::new(LineRedactor::new(redactor)));
+    let tail_lines = lines.clone();
+    stream
+        .try_filter_map(move |chunk| future::ready(Ok(lines.lock().unwrap().push(&chunk))))
+        .chain(
+            futures::stream::once(async move { tail_lines.lock().unwrap().finish() })
+                .filter_map(|tail| future::ready(tail.map(Ok))),
+        )
+}
+
+fn limit_output<S>(
+    stream: S,
+    limit: OutputLimit,
+    to_msg: fn(String) -> LogMsg,
+) -> impl Stream<Item = std::io::Result<LogMsg>> + Send + 'static
+where
+    S: Stream<Item = std::io::Result<String>> + Send + 'static,
+{
+    let limiter = Arc::new(std::sync::Mutex::new(OutputLimiter::new(limit)));
+    let tail_limiter = limiter.clone();
+    stream
+        .try_filter_map(move |chunk| {
+            future::ready(Ok(limiter.lock().unwrap().push(&chunk)))
+        })
+        .chain(
+            futures::stream::once(async move { tail_limiter.lock().unwrap().finish() })
+                .filter_map(|tail| future::ready(tail.map(Ok))),
+        )
+        .map_ok(to_msg)
+}
+
 fn failure_exit_status() -> std::process::ExitStatus {
     #[cfg(unix)]
     {
@@ -1052,10 +1554,30 @@ impl ContainerService for LocalContainerService {
         self.publisher.as_ref().ok()
     }
 
+    fn approvals(&self) -> &Approvals {
+        &self.approvals
+    }
+
     fn notification_service(&self) -> &NotificationService {
         &self.notification_service
     }
 
+    fn image_service(&self) -> &ImageService {
+        &self.image_service
+    }
+
+    fn config(&self) -> &Arc<RwLock<Config>> {
+        &self.config
+    }
+
+    fn agent_limiter(&self) -> &AgentConcurrencyLimiter {
+        &self.agent_limiter
+    }
+
+    fn dev_server_previews(&self) -> &DevServerPreviews {
+        &self.dev_server_previews
+    }
+
     fn events_msg_store(&self) -> &Arc<MsgStore> {
         &self.events_msg_store
     }
@@ -1102,31 +1624,34 @@ impl ContainerService for LocalContainerService {
             })
             .collect();
 
-        let created_workspace = WorkspaceManager::create_workspace(
+        let project = task
+            .parent_project(&self.db.pool)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        let workspace_dir = WorkspaceRuntime::create(
+            &self.db,
+            &project,
+            workspace,
             &workspace_dir,
             &workspace_inputs,
-            &workspace.branch,
         )
         .await?;
 
         // Copy project files and images to workspace
-        self.copy_files_and_images(&created_workspace.workspace_dir, workspace)
-            .await?;
+        self.copy_files_and_images(&workspace_dir, workspace).await?;
 
-        Self::create_workspace_config_files(&created_workspace.workspace_dir, &repositories)
-            .await?;
+        self.seed_workspace_templates(&workspace_dir, workspace).await;
+
+        Self::create_workspace_config_files(&workspace_dir, &repositories).await?;
 
         Workspace::update_container_ref(
             &self.db.pool,
             workspace.id,
-            &created_workspace.workspace_dir.to_string_lossy(),
+            &workspace_dir.to_string_lossy(),
         )
         .await?;
 
-        Ok(created_workspace
-            .workspace_dir
-            .to_string_lossy()
-            .to_string())
+        Ok(workspace_dir.to_string_lossy().to_string())
     }
 
     async fn delete(&self, workspace: &Workspace) -> Result<(), ContainerError> {
@@ -1168,7 +1693,43 @@ impl ContainerService for LocalContainerService {
             WorkspaceManager::get_workspace_base_dir().join(&workspace_dir_name)
         };
 
-        WorkspaceManager::ensure_workspace_exists(&workspace_dir, &repositories, &workspace.branch)
+        // Repos deleted out-of-band are rebuilt by the workspace's runtime; note which ones
+        // so a recreation can be reported. The legacy single-repo layout keeps its
+        // worktree at the workspace root and is not missing.
+        let missing_repos: Vec<String> = if workspace.container_ref.is_some()
+            && !workspace_dir.join(".git").exists()
+        {
+            repositories
+                .iter()
+                .filter(|repo| !workspace_dir.join(&repo.name).exists())
+                .map(|repo| repo.name.clone())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let restored_branches = if missing_repos.is_empty() {
+            Vec::new()
+        } else {
+            self.restore_workspace_branches(workspace, &repositories, &missing_repos)
+                .await
+        };
+
+        let target_branches: HashMap<_, _> =
+            WorkspaceRepo::find_by_workspace_id(&self.db.pool, workspace.id)
+                .await?
+                .into_iter()
+                .map(|wr| (wr.repo_id, wr.target_branch))
+                .collect();
+        let workspace_inputs: Vec<RepoWorkspaceInput> = repositories
+            .iter()
+            .map(|repo| {
+                let target_branch = target_branches.get(&repo.id).cloned().unwrap_or_default();
+                RepoWorkspaceInput::new(repo.clone(), target_branch)
+            })
+            .collect();
+        WorkspaceRuntime::load(&self.db, workspace.id)
+            .await?
+            .ensure(workspace, &workspace_dir, &workspace_inputs)
             .await?;
 
         if workspace.container_ref.is_none() {
@@ -1184,8 +1745,18 @@ impl ContainerService for LocalContainerService {
         self.copy_files_and_images(&workspace_dir, workspace)
             .await?;
 
+        // Templates seed new workspaces only, so agents' edits aren't reverted
+        if workspace.container_ref.is_none() {
+            self.seed_workspace_templates(&workspace_dir, workspace).await;
+        }
+
         Self::create_workspace_config_files(&workspace_dir, &repositories).await?;
 
+        if !missing_repos.is_empty() {
+            self.record_workspace_recreated(workspace, &missing_repos, &restored_branches)
+                .await;
+        }
+
         Ok(workspace_dir.to_string_lossy().to_string())
     }
 
@@ -1238,6 +1809,7 @@ impl ContainerService for LocalContainerService {
                 ) => ExecutorApprovalBridge::new(
                     self.approvals.clone(),
                     self.db.clone(),
+                    self.share_publisher().cloned(),
                     execution_process.id,
                 ),
                 _ => Arc::new(NoopExecutorApprovalService {}),
@@ -1263,9 +1835,103 @@ impl ContainerService for LocalContainerService {
         env.insert("VK_TASK_ID", task.id.to_string());
         env.insert("VK_WORKSPACE_ID", workspace.id.to_string());
         env.insert("VK_WORKSPACE_BRANCH", &workspace.branch);
+        env.insert(
+            DECISIONS_DIR_ENV,
+            decisions_dir(workspace.id).to_string_lossy(),
+        );
+
+        // Inject the project's env vault (secrets are redacted from process output)
+        match EnvVault::shared() {
+            Ok(vault) => match vault.load_project_env(&self.db.pool, project.id).await {
+                Ok(vars) => {
+                    for (key, value) in vars {
+                        env.insert_secret(key, value);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to load env vault for project {}: {}", project.id, e);
+                }
+            },
+            Err(e) => tracing::warn!("Env vault unavailable: {}", e),
+        }
+
+        // MCP settings of the agent that launched this session
+        match AgentMcpSettings::for_session(&self.db.pool, execution_process.session_id).await {
+            Ok(Some(settings)) => {
+                if let Some(url) = settings.mcp_base_url {
+                    env.insert("VIBE_BACKEND_URL", url);
+                }
+                env.mcp_servers = settings.mcp_servers.0;
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Failed to load agent MCP settings for session {}: {}",
+                execution_process.session_id,
+                e
+            ),
+        }
+
+        // Scoped token the in-workspace MCP server uses to call back into the API
+        let mcp_token =
+            McpCredentials::shared().issue(execution_process.id, workspace.id, project.id);
+        env.insert_secret(MCP_TOKEN_ENV_VAR, mcp_token);
+        // Well-known credential formats are masked too, except the project's allowlist
+        let allowlist =
+            match ProjectLogRedactionSettings::for_project(&self.db.pool, project.id).await {
+                Ok(settings) => settings.allowlist,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to load log redaction settings for project {}: {}",
+                        project.id,
+                        e
+                    );
+                    Vec::new()
+                }
+            };
+        let redactor = env.redactor().with_patterns(allowlist);
+
+        WorkspaceRuntime::load(&self.db, workspace.id)
+            .await?
+            .configure_env(&mut env, execution_process.id)
+            .await;
+
+        // Record what the execution runs with, so a run can be compared with later ones
+        match WorkspaceRepo::find_repos_with_target_branch_for_workspace(
+            &self.db.pool,
+            workspace.id,
+        )
+        .await
+        {
+            Ok(repos) => {
+                let environment = execution_environment::capture(
+                    self.git(),
+                    executor_action,
+                    &env,
+                    &repos,
+                    &current_dir,
+                );
+                if let Err(e) =
+                    ExecutionEnvironment::create(&self.db.pool, execution_process.id, &environment)
+                        .await
+                {
+                    tracing::warn!(
+                        "Failed to record environment of execution {}: {}",
+                        execution_process.id,
+                        e
+                    );
+                }
+            }
+            Err(e) => tracing::warn!("Failed to load repos of workspace {}: {}", workspace.id, e),
+        }
+        let output_limit = self
+            .config
+            .read()
+            .await
+            .output_limits
+            .limit_for(&execution_process.run_reason);
 
         // Create the child and stream, add to execution tracker with timeout
-        let mut spawned = tokio::time::timeout(
+        let spawned = tokio::time::timeout(
             Duration::from_secs(30),
             executor_action.spawn(&current_dir, approvals_service, &env),
         )
@@ -1274,10 +1940,31 @@ impl ContainerService for LocalContainerService {
             ContainerError::Other(anyhow!(
                 "Timeout: process took more than 30 seconds to start"
             ))
-        })??;
+        })
+        .and_then(|result| result.map_err(ContainerError::from));
+        let mut spawned = match spawned {
+            Ok(spawned) => spawned,
+            Err(e) => {
+                McpCredentials::shared().revoke(execution_process.id);
+                return Err(e);
+            }
+        };
 
-        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
-            .await;
+        self.track_child_msgs_in_store(
+            execution_process.id,
+            &mut spawned.child,
+            redactor,
+            output_limit,
+        )
+        .await;
+
+        // Pick up the dev server's port from its output for the preview proxy
+        if execution_process.run_reason == ExecutionProcessRunReason::DevServer
+            && let Some(store) = self.get_msg_store_by_id(&execution_process.id).await
+        {
+            self.dev_server_previews
+                .watch(workspace.id, execution_process.id, store);
+        }
 
         self.add_child_to_store(execution_process.id, spawned.child)
             .await;
@@ -1288,17 +1975,101 @@ impl ContainerService for LocalContainerService {
                 .await;
         }
 
+        // Store message sender so the user can talk to the agent mid-run
+        if let Some(message_sender) = spawned.message_sender {
+            self.add_message_sender(execution_process.id, message_sender)
+                .await;
+        }
+
         // Spawn unified exit monitor: watches OS exit and optional executor signal
         let _hn = self.spawn_exit_monitor(&execution_process.id, spawned.exit_signal);
 
         Ok(())
     }
 
+    async fn send_message_to_execution(
+        &self,
+        execution_process_id: &Uuid,
+        message: String,
+    ) -> bool {
+        let map = self.message_senders.read().await;
+        map.get(execution_process_id)
+            .is_some_and(|sender| sender.send(message).is_ok())
+    }
+
+    fn spawn_queued_execution(
+        &self,
+        workspace: &Workspace,
+        execution_process: &ExecutionProcess,
+        executor_action: &ExecutorAction,
+        task: &Task,
+        executor: BaseCodingAgent,
+    ) {
+        let container = self.clone();
+        let workspace = workspace.clone();
+        let execution_process = execution_process.clone();
+        let executor_action = executor_action.clone();
+        let task = task.clone();
+        tokio::spawn(async move {
+            // Cancelled waiters were already marked stopped by `stop_execution`
+            if !container
+                .agent_limiter
+                .acquire(execution_process.id, workspace.id, task.project_id, executor)
+                .await
+            {
+                return;
+            }
+            if let Err(e) = container
+                .launch_execution(&workspace, &execution_process, &executor_action, &task)
+                .await
+            {
+                tracing::error!(
+                    "Failed to start queued execution process {}: {}",
+                    execution_process.id,
+                    e
+                );
+            }
+        });
+    }
+
+    fn spawn_column_automations(
+        &self,
+        task_id: Uuid,
+        from_column_id: Option<Uuid>,
+        to_column_id: Uuid,
+        transition_name: Option<String>,
+    ) {
+        // Scripts and webhooks can be slow, so don't hold up the move
+        let container = self.clone();
+        tokio::spawn(async move {
+            automation::run_column_automations(
+                &container,
+                task_id,
+                from_column_id,
+                to_column_id,
+                transition_name,
+            )
+            .await;
+        });
+    }
+
     async fn stop_execution(
         &self,
         execution_process: &ExecutionProcess,
         status: ExecutionProcessStatus,
     ) -> Result<(), ContainerError> {
+        // An agent still queued for a concurrency slot has no child yet
+        if self.agent_limiter.cancel(execution_process.id).await {
+            ExecutionProcess::update_completion(&self.db.pool, execution_process.id, status, None)
+                .await?;
+            if let Ok(Some(updated)) =
+                ExecutionProcess::find_by_id(&self.db.pool, execution_process.id).await
+            {
+                self.events_msg_store.push_patch(execution_process_patch::replace(&updated));
+            }
+            return Ok(());
+        }
+
         let child = self
             .get_child_from_store(&execution_process.id)
             .await
@@ -1344,10 +2115,19 @@ impl ContainerService for LocalContainerService {
             }
         }
 
+        // Processes in a workspace container outlive the local `docker exec` client
+        if let Ok(ctx) = ExecutionProcess::load_context(&self.db.pool, execution_process.id).await
+            && let Ok(runtime) = WorkspaceRuntime::load(&self.db, ctx.workspace.id).await
+        {
+            runtime.stop_process(execution_process.id).await;
+        }
+
         // Kill the child process and remove from the store
         {
             let mut child_guard = child.write().await;
-            if let Err(e) = command::kill_process_group(&mut child_guard).await {
+            if let Err(e) =
+                command::kill_process_group(&mut child_guard, execution_process.id).await
+            {
                 tracing::error!(
                     "Failed to stop execution process {}: {}",
                     execution_process.id,
@@ -1357,6 +2137,8 @@ impl ContainerService for LocalContainerService {
             }
         }
         self.remove_child_from_store(&execution_process.id).await;
+        self.remove_message_sender(&execution_process.id).await;
+        McpCredentials::shared().revoke(execution_process.id);
 
         // Mark the process finished in the MsgStore
         if let Some(msg) = self.msg_stores.write().await.remove(&execution_process.id) {
@@ -1372,14 +2154,8 @@ impl ContainerService for LocalContainerService {
         {
             match Task::update_status(&self.db.pool, ctx.task.id, TaskStatus::InReview).await {
                 Ok(_) => {
-                    if let Some(publisher) = self.share_publisher()
-                        && let Err(err) = publisher.update_shared_task_by_id(ctx.task.id).await
-                    {
-                        tracing::warn!(
-                            ?err,
-                            "Failed to propagate shared task update for {}",
-                            ctx.task.id
-                        );
+                    if let Some(publisher) = self.share_publisher() {
+                        publisher.propagate_task_update(ctx.task.id).await;
                     }
                 }
                 Err(e) => {
@@ -1470,7 +2246,9 @@ impl ContainerService for LocalContainerService {
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
         if !matches!(
             ctx.execution_process.run_reason,
-            ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::CleanupScript,
+            ExecutionProcessRunReason::CodingAgent
+                | ExecutionProcessRunReason::ManualRun
+                | ExecutionProcessRunReason::CleanupScript,
         ) {
             return Ok(false);
         }
@@ -1484,6 +2262,52 @@ impl ContainerService for LocalContainerService {
             .ok_or_else(|| ContainerError::Other(anyhow!("Container reference not found")))?;
         let workspace_root = PathBuf::from(container_ref);
 
+        if self.is_read_only_run(ctx).await {
+            self.discard_read_only_changes(ctx, &workspace_root).await?;
+            return Ok(false);
+        }
+
+        // Dry runs never commit: the agent's changes are kept only as patches
+        if self.is_dry_run(ctx).await {
+            self.save_dry_run_patches(ctx, &workspace_root).await?;
+            self.discard_read_only_changes(ctx, &workspace_root).await?;
+            return Ok(false);
+        }
+
+        // Plan-only runs keep only the plan, which goes to its reviewers
+        if self.is_plan_only_run(ctx).await {
+            if let Some(plan) = read_plan_file(&ctx.workspace).await {
+                match PlanReview::set_plan(&self.db.pool, ctx.session.id, &plan).await {
+                    Ok(()) => {
+                        let mut notification = ChannelNotification::for_task(
+                            NotificationEvent::ApprovalRequested,
+                            &ctx.project,
+                            &ctx.task,
+                            "Plan waiting for review",
+                        );
+                        // Lets Slack and Matrix reviewers answer with a reaction
+                        if let Ok(Some(review)) =
+                            PlanReview::find_by_session_id(&self.db.pool, ctx.session.id).await
+                        {
+                            notification = notification
+                                .requesting_approval(ChatApprovalSubject::PlanReview(review.id));
+                        }
+                        notification_channels::spawn_dispatch(
+                            self.db.pool.clone(),
+                            self.share_publisher().cloned(),
+                            ctx.project.clone(),
+                            notification,
+                        );
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to save plan of session {}: {}", ctx.session.id, e)
+                    }
+                }
+            }
+            self.discard_read_only_changes(ctx, &workspace_root).await?;
+            return Ok(false);
+        }
+
         let repos_with_changes = self.check_repos_for_changes(&workspace_root, &ctx.repos)?;
         if repos_with_changes.is_empty() {
             tracing::debug!("No changes to commit in any repository");
@@ -1493,8 +2317,16 @@ impl ContainerService for LocalContainerService {
         let commits = self.commit_repos(repos_with_changes, &message);
         let any_committed = !commits.is_empty();
 
+        let runtime = WorkspaceRuntime::load(&self.db, ctx.workspace.id).await?;
+
         // Record commit events for each successful commit
         for commit in commits {
+            if let Err(e) = runtime.publish_commit(
+                &workspace_root.join(&commit.repo_name),
+                &ctx.workspace.branch,
+            ) {
+                tracing::error!("Failed to push commit of repo '{}': {}", commit.repo_name, e);
+            }
             let event = CreateTaskEvent::commit(
                 ctx.task.id,
                 ctx.workspace.id,
@@ -1536,17 +2368,17 @@ impl ContainerService for LocalContainerService {
         .map_err(|e| ContainerError::Other(anyhow!("Copy files task failed: {e}")))?
     }
 
-    async fn kill_all_running_processes(&self) -> Result<(), ContainerError> {
-        tracing::info!("Killing all running processes");
+    async fn interrupt_all_running_processes(&self) -> Result<(), ContainerError> {
+        tracing::info!("Interrupting all running processes");
         let running_processes = ExecutionProcess::find_running(&self.db.pool).await?;
 
         for process in running_processes {
             if let Err(error) = self
-                .stop_execution(&process, ExecutionProcessStatus::Killed)
+                .stop_execution(&process, ExecutionProcessStatus::Interrupted)
                 .await
             {
                 tracing::error!(
-                    "Failed to cleanly kill running execution process {:?}: {:?}",
+                    "Failed to cleanly interrupt running execution process {:?}: {:?}",
                     process,
                     error
                 );
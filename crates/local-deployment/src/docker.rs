@@ -0,0 +1,408 @@
+//! Docker execution backend.
+//!
+//! Projects with the `docker` execution backend get a container per workspace instead
+//! of git worktrees. The repos are cloned inside the container into the workspace
+//! directory, which is bind-mounted at the same path as on the host, so the diff
+//! stream, commits and decision files work on the clones as they do on worktrees.
+//! Setup scripts, agents and cleanup scripts are started with `docker exec` (see
+//! `ContainerExec`). The workspace branch is pushed back to the source repo when it's
+//! created and after every commit, so merges and pull requests find it there.
+//!
+//! The image needs git and the CLIs of the agents the project uses. Containers run on
+//! Docker's default bridge network; the project's configured ports are published on
+//! the host's loopback interface at the same number, so dev servers listening on all
+//! interfaces in the container can be reached and previewed. Agents reach the local
+//! MCP endpoint through `host.docker.internal`, which needs the backend to listen on
+//! an address the bridge can reach (e.g. `HOST=0.0.0.0`) unless the agent sets its own
+//! MCP URL.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use db::{
+    DBService,
+    models::{workspace::Workspace, workspace_container::WorkspaceContainer},
+};
+use executors::env::{ContainerExec, ExecutionEnv};
+use services::services::{
+    container::ContainerError, decision_file::decisions_dir, git::GitCli,
+    workspace_manager::RepoWorkspaceInput,
+};
+use tokio::process::Command;
+use utils::port_file::read_port_file;
+use uuid::Uuid;
+
+/// Name containers resolve the host by
+const HOST_GATEWAY: &str = "host.docker.internal";
+
+pub struct DockerContainerService;
+
+impl DockerContainerService {
+    pub fn container_name(workspace_id: &Uuid) -> String {
+        format!("vk-ws-{}", workspace_id.simple())
+    }
+
+    /// Target for starting an execution's process inside the workspace container
+    pub fn exec_target(
+        container: &WorkspaceContainer,
+        execution_process_id: Uuid,
+    ) -> ContainerExec {
+        ContainerExec {
+            container: container.container_name.clone(),
+            process_label: execution_process_id.simple().to_string(),
+        }
+    }
+
+    /// Point a process in the container at the backend on the host, unless the launching
+    /// agent configured its own MCP URL
+    pub async fn configure_backend_url(env: &mut ExecutionEnv) {
+        if env.contains_key("VIBE_BACKEND_URL") {
+            return;
+        }
+        match read_port_file("vibe-kanban").await {
+            Ok(port) => env.insert("VIBE_BACKEND_URL", format!("http://{HOST_GATEWAY}:{port}")),
+            Err(e) => tracing::warn!("Backend port unknown for container processes: {}", e),
+        }
+    }
+
+    /// Run the docker CLI, returning its trimmed stdout
+    async fn docker(args: &[String]) -> Result<String, ContainerError> {
+        let output = Command::new("docker").args(args).output().await?;
+        if !output.status.success() {
+            return Err(ContainerError::Other(anyhow!(
+                "docker {} failed: {}",
+                args.first().map(String::as_str).unwrap_or_default(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// `docker exec` of a command in `dir` inside the container
+    async fn exec(
+        container_name: &str,
+        dir: &Path,
+        command: &[&str],
+    ) -> Result<String, ContainerError> {
+        let mut args = vec![
+            "exec".to_string(),
+            "-w".to_string(),
+            dir.to_string_lossy().to_string(),
+            container_name.to_string(),
+        ];
+        args.extend(command.iter().map(|arg| arg.to_string()));
+        Self::docker(&args).await
+    }
+
+    /// Owner of the workspace directory, so files the container writes stay editable
+    /// on the host
+    #[cfg(unix)]
+    fn host_user(workspace_dir: &Path) -> Option<String> {
+        use std::os::unix::fs::MetadataExt;
+        let metadata = std::fs::metadata(workspace_dir).ok()?;
+        Some(format!("{}:{}", metadata.uid(), metadata.gid()))
+    }
+
+    #[cfg(not(unix))]
+    fn host_user(_workspace_dir: &Path) -> Option<String> {
+        None
+    }
+
+    /// Arguments of `docker run` for a workspace container. The workspace, the decisions
+    /// directory and the source repos (read-only) are mounted at their host paths; ports
+    /// are published on the host's loopback interface only.
+    fn run_args(
+        container_name: &str,
+        image: &str,
+        ports: &[i32],
+        workspace_dir: &Path,
+        decisions_dir: &Path,
+        repos: &[RepoWorkspaceInput],
+        user: Option<&str>,
+    ) -> Vec<String> {
+        let mount = |path: &Path, mode: &str| {
+            let path = path.to_string_lossy();
+            format!("{path}:{path}{mode}")
+        };
+        let mut args = vec![
+            "run".to_string(),
+            "-d".to_string(),
+            "--name".to_string(),
+            container_name.to_string(),
+            "--add-host".to_string(),
+            format!("{HOST_GATEWAY}:host-gateway"),
+            "-e".to_string(),
+            "HOME=/tmp".to_string(),
+            "-v".to_string(),
+            mount(workspace_dir, ""),
+            "-v".to_string(),
+            mount(decisions_dir, ""),
+        ];
+        for input in repos {
+            args.push("-v".to_string());
+            args.push(mount(&input.repo.path, ":ro"));
+        }
+        for port in ports {
+            args.push("-p".to_string());
+            args.push(format!("127.0.0.1:{port}:{port}"));
+        }
+        if let Some(user) = user {
+            args.push("--user".to_string());
+            args.push(user.to_string());
+        }
+        args.extend([image.to_string(), "sleep".to_string(), "infinity".to_string()]);
+        args
+    }
+
+    async fn start_container(
+        container_name: &str,
+        image: &str,
+        ports: &[i32],
+        workspace: &Workspace,
+        workspace_dir: &Path,
+        repos: &[RepoWorkspaceInput],
+    ) -> Result<(), ContainerError> {
+        let decisions_dir = decisions_dir(workspace.id);
+        tokio::fs::create_dir_all(workspace_dir).await?;
+        tokio::fs::create_dir_all(&decisions_dir).await?;
+
+        // A stopped container from an earlier run may still hold the name
+        let _ = Self::docker(&["rm".to_string(), "-f".to_string(), container_name.to_string()])
+            .await;
+        let user = Self::host_user(workspace_dir);
+        Self::docker(&Self::run_args(
+            container_name,
+            image,
+            ports,
+            workspace_dir,
+            &decisions_dir,
+            repos,
+            user.as_deref(),
+        ))
+        .await?;
+        Ok(())
+    }
+
+    /// Clone a repo into the workspace and check out the workspace branch: the existing
+    /// branch when the source repo has it (a recreated workspace), otherwise a new one
+    /// from the target branch
+    async fn clone_repo(
+        container_name: &str,
+        workspace_dir: &Path,
+        input: &RepoWorkspaceInput,
+        branch: &str,
+    ) -> Result<(), ContainerError> {
+        let clone_dir = workspace_dir.join(&input.repo.name);
+        if clone_dir.join(".git").exists() {
+            return Ok(());
+        }
+        let source = input.repo.path.to_string_lossy().to_string();
+        let clone = clone_dir.to_string_lossy().to_string();
+        Self::exec(
+            container_name,
+            workspace_dir,
+            &["git", "clone", "--no-checkout", &source, &clone],
+        )
+        .await?;
+        // Local branches matching the source repo's, so base commits resolve as they do
+        // in a worktree
+        Self::exec(
+            container_name,
+            &clone_dir,
+            &["git", "fetch", "--update-head-ok", "origin", "+refs/heads/*:refs/heads/*"],
+        )
+        .await?;
+        let branch_ref = format!("refs/heads/{branch}");
+        let has_branch = Self::exec(
+            container_name,
+            &clone_dir,
+            &["git", "rev-parse", "--verify", "--quiet", &branch_ref],
+        )
+        .await
+        .is_ok();
+        if has_branch {
+            Self::exec(container_name, &clone_dir, &["git", "checkout", branch]).await?;
+        } else {
+            Self::exec(
+                container_name,
+                &clone_dir,
+                &["git", "checkout", "-b", branch, &input.target_branch],
+            )
+            .await?;
+        }
+        Self::push_branch(&clone_dir, branch)
+    }
+
+    /// Start a container for the workspace and clone its repos into it
+    pub async fn create(
+        db: &DBService,
+        workspace: &Workspace,
+        workspace_dir: &Path,
+        repos: &[RepoWorkspaceInput],
+        image: &str,
+        ports: &[i32],
+    ) -> Result<(), ContainerError> {
+        let container_name = Self::container_name(&workspace.id);
+        Self::start_container(&container_name, image, ports, workspace, workspace_dir, repos)
+            .await?;
+        for input in repos {
+            Self::clone_repo(&container_name, workspace_dir, input, &workspace.branch).await?;
+        }
+        WorkspaceContainer::create(&db.pool, workspace.id, &container_name, image, ports)
+            .await?;
+        Ok(())
+    }
+
+    /// Restart the workspace's container if it stopped or was removed, and clone repos
+    /// whose directories went missing
+    pub async fn ensure_running(
+        container: &WorkspaceContainer,
+        workspace: &Workspace,
+        workspace_dir: &Path,
+        repos: &[RepoWorkspaceInput],
+    ) -> Result<(), ContainerError> {
+        let running = Self::docker(&[
+            "inspect".to_string(),
+            "-f".to_string(),
+            "{{.State.Running}}".to_string(),
+            container.container_name.clone(),
+        ])
+        .await;
+        match running.as_deref() {
+            Ok("true") => {}
+            Ok(_) => {
+                Self::docker(&["start".to_string(), container.container_name.clone()]).await?;
+            }
+            Err(_) => {
+                Self::start_container(
+                    &container.container_name,
+                    &container.image,
+                    &container.ports,
+                    workspace,
+                    workspace_dir,
+                    repos,
+                )
+                .await?;
+            }
+        }
+        for input in repos {
+            Self::clone_repo(
+                &container.container_name,
+                workspace_dir,
+                input,
+                &workspace.branch,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Push the workspace branch of a clone to its source repo
+    pub fn push_branch(clone_dir: &Path, branch: &str) -> Result<(), ContainerError> {
+        let refspec = format!("+refs/heads/{branch}:refs/heads/{branch}");
+        GitCli::new()
+            .git(clone_dir, ["push", "origin", refspec.as_str()])
+            .map_err(|e| ContainerError::Other(anyhow!("Failed to push {branch}: {e}")))?;
+        Ok(())
+    }
+
+    /// Stop an execution's process group inside the container. Killing the local
+    /// `docker exec` doesn't reach it, so the group is signalled through the pid file
+    /// its leader wrote on start, and force killed if it outlives a grace period.
+    pub async fn stop_process(container: &WorkspaceContainer, execution_process_id: Uuid) {
+        let target = Self::exec_target(container, execution_process_id);
+        let script = format!(
+            "pid=$(cat {0} 2>/dev/null) && kill -TERM -- \"-$pid\" && sleep 2 && \
+             kill -KILL -- \"-$pid\" 2>/dev/null; rm -f {0}",
+            target.pid_file()
+        );
+        if let Err(e) =
+            Self::exec(&container.container_name, Path::new("/"), &["sh", "-c", &script]).await
+        {
+            tracing::debug!(
+                "Failed to signal execution {} in container {}: {}",
+                execution_process_id,
+                container.container_name,
+                e
+            );
+        }
+    }
+
+    /// Remove the workspace's container and its directory
+    pub async fn remove(db: &DBService, container: &WorkspaceContainer, workspace_dir: PathBuf) {
+        if let Err(e) = Self::docker(&[
+            "rm".to_string(),
+            "-f".to_string(),
+            container.container_name.clone(),
+        ])
+        .await
+        {
+            tracing::warn!(
+                "Failed to remove container {}: {}",
+                container.container_name,
+                e
+            );
+        }
+        if workspace_dir.exists()
+            && let Err(e) = tokio::fs::remove_dir_all(&workspace_dir).await
+        {
+            tracing::warn!("Failed to remove workspace directory: {}", e);
+        }
+        if let Err(e) = WorkspaceContainer::delete(&db.pool, container.workspace_id).await {
+            tracing::warn!(
+                "Failed to delete container record of workspace {}: {}",
+                container.workspace_id,
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use db::models::repo::Repo;
+
+    use super::*;
+
+    #[test]
+    fn workspace_is_mounted_at_its_host_path_and_ports_published_on_loopback() {
+        let repo: Repo = serde_json::from_value(serde_json::json!({
+            "id": Uuid::nil(),
+            "path": "/home/dev/app",
+            "name": "app",
+            "display_name": "app",
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:00:00Z",
+        }))
+        .unwrap();
+        let repos = [RepoWorkspaceInput::new(repo, "main".to_string())];
+
+        let args = DockerContainerService::run_args(
+            "vk-ws-1",
+            "node:22-bookworm",
+            &[5173],
+            Path::new("/tmp/ws/1"),
+            Path::new("/tmp/decisions/1"),
+            &repos,
+            Some("1000:1000"),
+        );
+
+        let mounts: Vec<&str> = args
+            .windows(2)
+            .filter(|pair| pair[0] == "-v")
+            .map(|pair| pair[1].as_str())
+            .collect();
+        assert_eq!(
+            mounts,
+            [
+                "/tmp/ws/1:/tmp/ws/1",
+                "/tmp/decisions/1:/tmp/decisions/1",
+                "/home/dev/app:/home/dev/app:ro",
+            ]
+        );
+        assert!(args.windows(2).any(|pair| pair == ["--user", "1000:1000"]));
+        assert!(args.windows(2).any(|pair| pair == ["-p", "127.0.0.1:5173:5173"]));
+        assert!(!args.iter().any(|arg| arg == "--network"));
+        assert_eq!(&args[args.len() - 3..], ["node:22-bookworm", "sleep", "infinity"]);
+    }
+}
@@ -34,6 +34,8 @@ use crate::container::LocalContainerService;
 mod command;
 pub mod container;
 mod copy;
+mod docker;
+mod workspace_runtime;
 
 #[derive(Clone)]
 pub struct LocalDeployment {
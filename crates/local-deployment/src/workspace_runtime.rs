@@ -0,0 +1,162 @@
+//! Where a workspace's repos live and its processes run.
+//!
+//! A workspace keeps the execution backend it was created with: git worktrees on the
+//! host, or clones inside a Docker container (see `docker`). The container service
+//! loads a workspace's runtime once per operation and leaves the backend specifics to
+//! it.
+
+use std::path::{Path, PathBuf};
+
+use db::{
+    DBService,
+    models::{
+        project::{ExecutionBackend, Project},
+        repo::Repo,
+        workspace::Workspace,
+        workspace_container::WorkspaceContainer,
+    },
+};
+use executors::env::ExecutionEnv;
+use services::services::{
+    container::ContainerError,
+    workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
+};
+use uuid::Uuid;
+
+use crate::docker::DockerContainerService;
+
+pub enum WorkspaceRuntime {
+    /// Git worktrees on the host; processes run as local children
+    Worktree,
+    /// Clones in the workspace's Docker container; processes run with `docker exec`
+    Docker(WorkspaceContainer),
+}
+
+impl WorkspaceRuntime {
+    pub async fn load(db: &DBService, workspace_id: Uuid) -> Result<Self, sqlx::Error> {
+        let container = WorkspaceContainer::find_by_workspace_id(&db.pool, workspace_id).await?;
+        Ok(container.map_or(Self::Worktree, Self::Docker))
+    }
+
+    /// Create a new workspace's repos with the project's execution backend, returning
+    /// the workspace directory
+    pub async fn create(
+        db: &DBService,
+        project: &Project,
+        workspace: &Workspace,
+        workspace_dir: &Path,
+        repos: &[RepoWorkspaceInput],
+    ) -> Result<PathBuf, ContainerError> {
+        match project.backend() {
+            ExecutionBackend::Docker => {
+                DockerContainerService::create(
+                    db,
+                    workspace,
+                    workspace_dir,
+                    repos,
+                    project.image(),
+                    &project.docker_ports,
+                )
+                .await?;
+                Ok(workspace_dir.to_path_buf())
+            }
+            ExecutionBackend::Local => {
+                let worktrees =
+                    WorkspaceManager::create_workspace(workspace_dir, repos, &workspace.branch)
+                        .await?;
+                Ok(worktrees.workspace_dir)
+            }
+        }
+    }
+
+    /// Bring back repos whose directories went missing, and the container if it stopped
+    pub async fn ensure(
+        &self,
+        workspace: &Workspace,
+        workspace_dir: &Path,
+        repos: &[RepoWorkspaceInput],
+    ) -> Result<(), ContainerError> {
+        match self {
+            Self::Worktree => {
+                let repositories: Vec<Repo> =
+                    repos.iter().map(|input| input.repo.clone()).collect();
+                WorkspaceManager::ensure_workspace_exists(
+                    workspace_dir,
+                    &repositories,
+                    &workspace.branch,
+                )
+                .await?;
+            }
+            Self::Docker(container) => {
+                DockerContainerService::ensure_running(container, workspace, workspace_dir, repos)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Run an execution's process in the container, if the workspace has one
+    pub async fn configure_env(&self, env: &mut ExecutionEnv, execution_process_id: Uuid) {
+        if let Self::Docker(container) = self {
+            env.container = Some(DockerContainerService::exec_target(
+                container,
+                execution_process_id,
+            ));
+            DockerContainerService::configure_backend_url(env).await;
+        }
+    }
+
+    /// Stop what's left of an execution after its local child is killed. Processes in a
+    /// container outlive the local `docker exec` client.
+    pub async fn stop_process(&self, execution_process_id: Uuid) {
+        if let Self::Docker(container) = self {
+            DockerContainerService::stop_process(container, execution_process_id).await;
+        }
+    }
+
+    /// Share a new commit of a repo's workspace branch. Worktrees already share it with
+    /// their repo; clones push it to their source repo.
+    pub fn publish_commit(&self, repo_dir: &Path, branch: &str) -> Result<(), ContainerError> {
+        match self {
+            Self::Worktree => Ok(()),
+            Self::Docker(_) => DockerContainerService::push_branch(repo_dir, branch),
+        }
+    }
+
+    /// Remove the workspace's repos and directory
+    pub async fn remove(
+        &self,
+        db: &DBService,
+        workspace: &Workspace,
+        workspace_dir: PathBuf,
+        repositories: &[Repo],
+    ) {
+        match self {
+            Self::Docker(container) => {
+                DockerContainerService::remove(db, container, workspace_dir).await;
+            }
+            Self::Worktree if repositories.is_empty() => {
+                tracing::warn!(
+                    "No repositories found for workspace {}, cleaning up workspace directory only",
+                    workspace.id
+                );
+                if workspace_dir.exists()
+                    && let Err(e) = tokio::fs::remove_dir_all(&workspace_dir).await
+                {
+                    tracing::warn!("Failed to remove workspace directory: {}", e);
+                }
+            }
+            Self::Worktree => {
+                WorkspaceManager::cleanup_workspace(&workspace_dir, repositories)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!(
+                            "Failed to clean up workspace for workspace {}: {}",
+                            workspace.id,
+                            e
+                        );
+                    });
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+ALTER TABLE projects
+    ADD COLUMN IF NOT EXISTS owner_user_id UUID REFERENCES users(id) ON DELETE SET NULL;
+
+-- Projects with no grants stay visible to the whole organization
+CREATE TABLE IF NOT EXISTS project_access_grants (
+    project_id         UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    user_id            UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    granted_by_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_access_grants_user
+    ON project_access_grants (user_id);
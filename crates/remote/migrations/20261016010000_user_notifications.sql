@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS user_notifications (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    shared_task_id  UUID REFERENCES shared_tasks(id) ON DELETE CASCADE,
+    actor_user_id   UUID REFERENCES users(id) ON DELETE SET NULL,
+    kind            TEXT NOT NULL,
+    message         TEXT NOT NULL,
+    read_at         TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_notifications_user_created
+    ON user_notifications (user_id, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_user_notifications_unread
+    ON user_notifications (user_id)
+    WHERE read_at IS NULL;
@@ -2,10 +2,12 @@ pub mod auth;
 pub mod github_app;
 pub mod identity_errors;
 pub mod invitations;
+pub mod notifications;
 pub mod oauth;
 pub mod oauth_accounts;
 pub mod organization_members;
 pub mod organizations;
+pub mod project_access;
 pub mod projects;
 pub mod reviews;
 pub mod tasks;
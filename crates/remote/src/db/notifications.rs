@@ -0,0 +1,120 @@
+use sqlx::PgPool;
+pub use utils::api::notifications::{UserNotification, UserNotificationKind};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    pub user_id: Uuid,
+    pub organization_id: Uuid,
+    pub shared_task_id: Option<Uuid>,
+    pub actor_user_id: Option<Uuid>,
+    pub kind: UserNotificationKind,
+    pub message: String,
+}
+
+pub struct NotificationRepository;
+
+impl NotificationRepository {
+    pub async fn create(pool: &PgPool, notification: NewNotification) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_notifications (
+                user_id, organization_id, shared_task_id, actor_user_id, kind, message
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(notification.user_id)
+        .bind(notification.organization_id)
+        .bind(notification.shared_task_id)
+        .bind(notification.actor_user_id)
+        .bind(notification.kind.as_str())
+        .bind(notification.message)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_for_user(
+        pool: &PgPool,
+        user_id: Uuid,
+        unread_only: bool,
+        limit: i64,
+    ) -> Result<Vec<UserNotification>, sqlx::Error> {
+        sqlx::query_as::<_, UserNotification>(
+            r#"
+            SELECT
+                n.id,
+                n.organization_id,
+                n.shared_task_id,
+                n.actor_user_id,
+                u.username AS actor_username,
+                n.kind,
+                n.message,
+                n.read_at,
+                n.created_at
+            FROM user_notifications n
+            LEFT JOIN users u ON u.id = n.actor_user_id
+            WHERE n.user_id = $1 AND (NOT $2 OR n.read_at IS NULL)
+            ORDER BY n.created_at DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(unread_only)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn unread_count(pool: &PgPool, user_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM user_notifications WHERE user_id = $1 AND read_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Marks the given notifications, or all of them when `ids` is `None`, as read
+    pub async fn mark_read(
+        pool: &PgPool,
+        user_id: Uuid,
+        ids: Option<&[Uuid]>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_notifications
+            SET read_at = NOW()
+            WHERE user_id = $1
+              AND read_at IS NULL
+              AND ($2::uuid[] IS NULL OR id = ANY($2))
+            "#,
+        )
+        .bind(user_id)
+        .bind(ids)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Organization members whose (lowercased) username is in `usernames`
+    pub async fn members_by_username(
+        pool: &PgPool,
+        organization_id: Uuid,
+        usernames: &[String],
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT u.id
+            FROM users u
+            JOIN organization_member_metadata m ON m.user_id = u.id
+            WHERE m.organization_id = $1 AND lower(u.username) = ANY($2)
+            "#,
+        )
+        .bind(organization_id)
+        .bind(usernames)
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,182 @@
+use sqlx::{Executor, PgPool, Postgres};
+pub use utils::api::projects::ProjectAccessGrant;
+use uuid::Uuid;
+
+use super::Tx;
+
+/// Rows of `projects p` that `$2` may see: the owner, organization admins, granted
+/// users, or anyone in the organization while the project has no grants.
+pub(crate) const CAN_ACCESS: &str = r#"
+    p.owner_user_id IS NOT DISTINCT FROM $2
+    OR NOT EXISTS (
+        SELECT 1 FROM project_access_grants g WHERE g.project_id = p.id
+    )
+    OR EXISTS (
+        SELECT 1 FROM project_access_grants g WHERE g.project_id = p.id AND g.user_id = $2
+    )
+    OR EXISTS (
+        SELECT 1
+        FROM organization_member_metadata m
+        WHERE m.organization_id = p.organization_id AND m.user_id = $2 AND m.role = 'admin'
+    )
+"#;
+
+pub struct ProjectAccessRepository;
+
+impl ProjectAccessRepository {
+    pub async fn owner(pool: &PgPool, project_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+        let owner: Option<Option<Uuid>> =
+            sqlx::query_scalar("SELECT owner_user_id FROM projects WHERE id = $1")
+                .bind(project_id)
+                .fetch_optional(pool)
+                .await?;
+        Ok(owner.flatten())
+    }
+
+    pub async fn set_owner<'a, E>(
+        executor: E,
+        project_id: Uuid,
+        owner_user_id: Uuid,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'a, Database = Postgres>,
+    {
+        sqlx::query("UPDATE projects SET owner_user_id = $2 WHERE id = $1")
+            .bind(project_id)
+            .bind(owner_user_id)
+            .execute(executor)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn can_access(
+        pool: &PgPool,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let allowed: Option<bool> = sqlx::query_scalar(&format!(
+            "SELECT ({CAN_ACCESS}) FROM projects p WHERE p.id = $1"
+        ))
+        .bind(project_id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+        Ok(allowed.unwrap_or(false))
+    }
+
+    /// Projects of the organization the user is not allowed to see
+    pub async fn hidden_projects(
+        pool: &PgPool,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar(&format!(
+            "SELECT p.id FROM projects p WHERE p.organization_id = $1 AND NOT ({CAN_ACCESS})"
+        ))
+        .bind(organization_id)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Projects of any of the organizations the user is not allowed to see
+    pub async fn hidden_projects_in(
+        pool: &PgPool,
+        organization_ids: &[Uuid],
+        user_id: Uuid,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar(&format!(
+            "SELECT p.id FROM projects p WHERE p.organization_id = ANY($1) AND NOT ({CAN_ACCESS})"
+        ))
+        .bind(organization_ids)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn list_grants(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<ProjectAccessGrant>, sqlx::Error> {
+        sqlx::query_as::<_, ProjectAccessGrant>(
+            r#"
+            SELECT project_id, user_id, granted_by_user_id, created_at
+            FROM project_access_grants
+            WHERE project_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn grant(
+        pool: &PgPool,
+        project_id: Uuid,
+        user_id: Uuid,
+        granted_by_user_id: Uuid,
+    ) -> Result<ProjectAccessGrant, sqlx::Error> {
+        sqlx::query_as::<_, ProjectAccessGrant>(
+            r#"
+            INSERT INTO project_access_grants (project_id, user_id, granted_by_user_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (project_id, user_id) DO UPDATE
+            SET granted_by_user_id = project_access_grants.granted_by_user_id
+            RETURNING project_id, user_id, granted_by_user_id, created_at
+            "#,
+        )
+        .bind(project_id)
+        .bind(user_id)
+        .bind(granted_by_user_id)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Returns whether a grant was removed
+    pub async fn revoke(
+        pool: &PgPool,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result =
+            sqlx::query("DELETE FROM project_access_grants WHERE project_id = $1 AND user_id = $2")
+                .bind(project_id)
+                .bind(user_id)
+                .execute(pool)
+                .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Drop the grants and project ownership a user held in an organization they left
+    pub async fn remove_member(
+        tx: &mut Tx<'_>,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            DELETE FROM project_access_grants g
+            USING projects p
+            WHERE g.project_id = p.id AND p.organization_id = $1 AND g.user_id = $2
+            "#,
+        )
+        .bind(organization_id)
+        .bind(user_id)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE projects
+            SET owner_user_id = NULL
+            WHERE organization_id = $1 AND owner_user_id = $2
+            "#,
+        )
+        .bind(organization_id)
+        .bind(user_id)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
@@ -7,6 +7,7 @@ use uuid::Uuid;
 
 use super::{
     identity_errors::IdentityError,
+    project_access::CAN_ACCESS,
     projects::{ProjectError, ProjectRepository},
     users::{UserData, fetch_user},
 };
@@ -378,6 +379,37 @@ impl<'a> SharedTaskRepository<'a> {
 
         Ok(tasks.into_iter().map(|r| r.id).collect())
     }
+
+    /// Current copies of the given tasks the user can see, for clients pulling remote
+    /// changes back into their local tasks
+    pub async fn find_many(
+        &self,
+        task_ids: &[Uuid],
+        user_id: Uuid,
+    ) -> Result<Vec<SharedTask>, SharedTaskError> {
+        // Project access grants narrow the organization's tasks further
+        let tasks = sqlx::query_as::<_, SharedTask>(&format!(
+            r#"
+            SELECT
+                t.id, t.organization_id, t.project_id, t.creator_user_id,
+                t.assignee_user_id, t.deleted_by_user_id, t.title, t.description,
+                t.status, t.deleted_at, t.shared_at, t.created_at, t.updated_at
+            FROM shared_tasks t
+            INNER JOIN organization_member_metadata om ON t.organization_id = om.organization_id
+            INNER JOIN projects p ON p.id = t.project_id
+            WHERE t.id = ANY($1)
+              AND t.deleted_at IS NULL
+              AND om.user_id = $2
+              AND ({CAN_ACCESS})
+            "#
+        ))
+        .bind(task_ids)
+        .bind(user_id)
+        .fetch_all(self.pool)
+        .await?;
+
+        Ok(tasks)
+    }
 }
 
 pub(crate) fn ensure_text_size(
@@ -409,4 +441,11 @@ impl SharedTaskRepository<'_> {
         .fetch_optional(pool)
         .await
     }
+
+    pub async fn project_id(pool: &PgPool, task_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar("SELECT project_id FROM shared_tasks WHERE id = $1")
+            .bind(task_id)
+            .fetch_optional(pool)
+            .await
+    }
 }
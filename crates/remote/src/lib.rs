@@ -4,6 +4,7 @@ pub mod config;
 pub mod db;
 pub mod github_app;
 pub mod mail;
+pub mod mentions;
 pub mod r2;
 pub mod routes;
 mod state;
@@ -0,0 +1,64 @@
+//! `@username` mentions in shared task text.
+
+/// Characters allowed in a username after the `@`
+fn is_username_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Lowercased usernames mentioned in `text`, without duplicates, in order of first
+/// appearance. An `@` preceded by a word character (as in an email address) is ignored.
+pub fn parse_mentions(text: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        let starts_mention =
+            c == '@' && !previous.is_some_and(|p| p.is_alphanumeric() || p == '_' || p == '@');
+        previous = Some(c);
+        if !starts_mention {
+            continue;
+        }
+
+        let start = index + 1;
+        let mut end = start;
+        while let Some(&(i, next)) = chars.peek() {
+            if !is_username_char(next) {
+                break;
+            }
+            end = i + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+
+        let username = text[start..end].trim_end_matches(['-', '_']).to_lowercase();
+        if !username.is_empty() && !mentions.contains(&username) {
+            mentions.push(username);
+        }
+    }
+
+    mentions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_mentions_in_order_without_duplicates() {
+        assert_eq!(
+            parse_mentions("@Alice please pair with @bob-smith, cc @alice"),
+            ["alice", "bob-smith"]
+        );
+    }
+
+    #[test]
+    fn ignores_email_addresses_and_bare_at_signs() {
+        assert!(parse_mentions("mail dev@example.com or @ me").is_empty());
+    }
+
+    #[test]
+    fn stops_at_punctuation() {
+        assert_eq!(parse_mentions("(@carol_d) and @dave."), ["carol_d", "dave"]);
+    }
+}
@@ -14,7 +14,10 @@ use tracing::error;
 use uuid::Uuid;
 
 use crate::{
-    AppState, auth::RequestContext, db::organizations::OrganizationRepository, validated_where,
+    AppState,
+    auth::RequestContext,
+    db::{organizations::OrganizationRepository, project_access::ProjectAccessRepository},
+    validated_where,
     validated_where::ValidatedWhere,
 };
 
@@ -60,19 +63,35 @@ pub async fn proxy_shared_tasks(
         return Ok(empty_shape_response());
     }
 
-    // Build org_id filter using compile-time validated WHERE clause
+    // Build org_id filter using compile-time validated WHERE clause, leaving out
+    // projects whose access grants don't include the user
     let org_uuids: Vec<Uuid> = orgs.iter().map(|o| o.id).collect();
-    let query = validated_where!("shared_tasks", r#""organization_id" = ANY($1)"#, &org_uuids);
-    let query_params = &[format!(
+    let hidden_projects =
+        ProjectAccessRepository::hidden_projects_in(state.pool(), &org_uuids, ctx.user.id)
+            .await
+            .map_err(|e| {
+                ProxyError::Authorization(format!("failed to fetch project access: {e}"))
+            })?;
+    let query = validated_where!(
+        "shared_tasks",
+        r#""organization_id" = ANY($1) AND NOT ("project_id" = ANY($2))"#,
+        &org_uuids,
+        &hidden_projects
+    );
+    let query_params = &[uuid_array_param(&org_uuids), uuid_array_param(&hidden_projects)];
+    tracing::debug!("Proxying Electric Shape request for shared_tasks table{query:?}");
+    proxy_table(&state, &query, &params, query_params).await
+}
+
+/// Postgres array literal for a `$n` shape parameter
+fn uuid_array_param(ids: &[Uuid]) -> String {
+    format!(
         "{{{}}}",
-        org_uuids
-            .iter()
+        ids.iter()
             .map(|u| u.to_string())
             .collect::<Vec<_>>()
             .join(",")
-    )];
-    tracing::debug!("Proxying Electric Shape request for shared_tasks table{query:?}");
-    proxy_table(&state, &query, &params, query_params).await
+    )
 }
 
 /// Proxy a Shape request to Electric for a specific table.
@@ -18,6 +18,7 @@ mod electric_proxy;
 mod error;
 mod github_app;
 mod identity;
+mod notifications;
 mod oauth;
 pub(crate) mod organization_members;
 mod organizations;
@@ -57,6 +58,7 @@ pub fn router(state: AppState) -> Router {
 
     let v1_protected = Router::<AppState>::new()
         .merge(identity::router())
+        .merge(notifications::router())
         .merge(projects::router())
         .merge(tasks::router())
         .merge(organizations::router())
@@ -0,0 +1,172 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+use tracing::instrument;
+use utils::api::notifications::{ListNotificationsResponse, MarkNotificationsReadRequest};
+use uuid::Uuid;
+
+use super::error::ErrorResponse;
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{
+        notifications::{NewNotification, NotificationRepository, UserNotificationKind},
+        tasks::SharedTask,
+    },
+    mentions::parse_mentions,
+};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+#[derive(Debug, Deserialize)]
+struct NotificationsQuery {
+    #[serde(default)]
+    unread_only: bool,
+    limit: Option<i64>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/notifications", get(list_notifications))
+        .route("/notifications/read", post(mark_notifications_read))
+}
+
+#[instrument(
+    name = "notifications.list_notifications",
+    skip(state, ctx, query),
+    fields(user_id = %ctx.user.id)
+)]
+async fn list_notifications(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<NotificationsQuery>,
+) -> Result<Json<ListNotificationsResponse>, ErrorResponse> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let load = async {
+        let notifications = NotificationRepository::list_for_user(
+            state.pool(),
+            ctx.user.id,
+            query.unread_only,
+            limit,
+        )
+        .await?;
+        let unread_count = NotificationRepository::unread_count(state.pool(), ctx.user.id).await?;
+        Ok::<_, sqlx::Error>(ListNotificationsResponse {
+            notifications,
+            unread_count,
+        })
+    };
+
+    load.await.map(Json).map_err(|error| {
+        tracing::error!(?error, "failed to list notifications");
+        ErrorResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to list notifications",
+        )
+    })
+}
+
+#[instrument(
+    name = "notifications.mark_notifications_read",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id)
+)]
+async fn mark_notifications_read(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<MarkNotificationsReadRequest>,
+) -> Result<StatusCode, ErrorResponse> {
+    NotificationRepository::mark_read(state.pool(), ctx.user.id, payload.ids.as_deref())
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to mark notifications read");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Notify organization members newly @mentioned in the task's description. Mentions
+/// already present in `previous_description` are not notified again. Failures are
+/// logged rather than failing the task update.
+pub(crate) async fn notify_mentions(
+    pool: &PgPool,
+    task: &SharedTask,
+    actor_user_id: Uuid,
+    previous_description: Option<&str>,
+) {
+    let previous = parse_mentions(previous_description.unwrap_or_default());
+    let mentioned: Vec<String> = parse_mentions(task.description.as_deref().unwrap_or_default())
+        .into_iter()
+        .filter(|username| !previous.contains(username))
+        .collect();
+    if mentioned.is_empty() {
+        return;
+    }
+
+    let user_ids =
+        match NotificationRepository::members_by_username(pool, task.organization_id, &mentioned)
+            .await
+        {
+            Ok(user_ids) => user_ids,
+            Err(error) => {
+                tracing::error!(?error, task_id = %task.id, "failed to resolve mentions");
+                return;
+            }
+        };
+
+    for user_id in user_ids.into_iter().filter(|id| *id != actor_user_id) {
+        notify(
+            pool,
+            task,
+            actor_user_id,
+            user_id,
+            UserNotificationKind::Mention,
+            format!("Mentioned you in \"{}\"", task.title),
+        )
+        .await;
+    }
+}
+
+/// Notify the task's assignee, unless they assigned the task to themselves
+pub(crate) async fn notify_assignment(pool: &PgPool, task: &SharedTask, actor_user_id: Uuid) {
+    let Some(assignee) = task.assignee_user_id.filter(|id| *id != actor_user_id) else {
+        return;
+    };
+    notify(
+        pool,
+        task,
+        actor_user_id,
+        assignee,
+        UserNotificationKind::Assigned,
+        format!("Assigned you \"{}\"", task.title),
+    )
+    .await;
+}
+
+async fn notify(
+    pool: &PgPool,
+    task: &SharedTask,
+    actor_user_id: Uuid,
+    user_id: Uuid,
+    kind: UserNotificationKind,
+    message: String,
+) {
+    let notification = NewNotification {
+        user_id,
+        organization_id: task.organization_id,
+        shared_task_id: Some(task.id),
+        actor_user_id: Some(actor_user_id),
+        kind,
+        message,
+    };
+    if let Err(error) = NotificationRepository::create(pool, notification).await {
+        tracing::error!(?error, task_id = %task.id, %user_id, "failed to create notification");
+    }
+}
@@ -24,6 +24,7 @@ use crate::{
         invitations::{Invitation, InvitationRepository},
         organization_members::{self, MemberRole},
         organizations::OrganizationRepository,
+        project_access::ProjectAccessRepository,
         projects::ProjectRepository,
         tasks::SharedTaskRepository,
     },
@@ -380,6 +381,10 @@ pub async fn remove_member(
     .await
     .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
+    ProjectAccessRepository::remove_member(&mut tx, org_id, user_id)
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
     tx.commit()
         .await
         .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
@@ -552,6 +557,20 @@ pub(crate) async fn ensure_project_access(
             membership_error(err, "project not accessible")
         })?;
 
+    let allowed = ProjectAccessRepository::can_access(pool, project_id, user_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to check project access grants");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+    if !allowed {
+        warn!(%project_id, %user_id, "project access denied by grants");
+        return Err(ErrorResponse::new(
+            StatusCode::FORBIDDEN,
+            "project not accessible",
+        ));
+    }
+
     Ok(organization_id)
 }
 
@@ -597,5 +616,25 @@ pub(crate) async fn ensure_task_access(
             membership_error(err, "task not accessible")
         })?;
 
+    let load_access = async {
+        match SharedTaskRepository::project_id(pool, task_id).await? {
+            Some(project_id) => {
+                ProjectAccessRepository::can_access(pool, project_id, user_id).await
+            }
+            None => Ok(false),
+        }
+    };
+    let allowed = load_access.await.map_err(|error| {
+        tracing::error!(?error, %task_id, "failed to check project access grants");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+    if !allowed {
+        warn!(%task_id, %user_id, "shared task access denied by project grants");
+        return Err(ErrorResponse::new(
+            StatusCode::FORBIDDEN,
+            "task not accessible",
+        ));
+    }
+
     Ok(organization_id)
 }
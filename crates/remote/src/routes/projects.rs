@@ -2,19 +2,29 @@ use axum::{
     Json, Router,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
-    routing::get,
+    routing::{delete, get, post},
 };
 use serde::Deserialize;
 use serde_json::Value;
 use tracing::instrument;
-use utils::api::projects::{ListProjectsResponse, RemoteProject};
+use utils::api::projects::{
+    GrantProjectAccessRequest, ListProjectsResponse, ProjectAccessGrant, ProjectAccessResponse,
+    RemoteProject, TransferProjectOwnershipRequest,
+};
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_member_access};
+use super::{
+    error::ErrorResponse,
+    organization_members::{ensure_admin_access, ensure_member_access, ensure_project_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::projects::{CreateProjectData, Project, ProjectError, ProjectRepository},
+    db::{
+        organization_members,
+        project_access::ProjectAccessRepository,
+        projects::{CreateProjectData, Project, ProjectError, ProjectRepository},
+    },
 };
 
 #[derive(Debug, Deserialize)]
@@ -34,6 +44,18 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/projects", get(list_projects).post(create_project))
         .route("/projects/{project_id}", get(get_project))
+        .route(
+            "/projects/{project_id}/access",
+            get(get_project_access).post(grant_project_access),
+        )
+        .route(
+            "/projects/{project_id}/access/{user_id}",
+            delete(revoke_project_access),
+        )
+        .route(
+            "/projects/{project_id}/transfer-ownership",
+            post(transfer_project_ownership),
+        )
 }
 
 #[instrument(
@@ -49,8 +71,19 @@ async fn list_projects(
     let target_org = params.organization_id;
     ensure_member_access(state.pool(), target_org, ctx.user.id).await?;
 
+    let hidden = ProjectAccessRepository::hidden_projects(state.pool(), target_org, ctx.user.id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, org_id = %target_org, "failed to load project access grants");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list projects")
+        })?;
+
     let projects = match ProjectRepository::list_by_organization(state.pool(), target_org).await {
-        Ok(rows) => rows.into_iter().map(to_remote_project).collect(),
+        Ok(rows) => rows
+            .into_iter()
+            .filter(|project| !hidden.contains(&project.id))
+            .map(to_remote_project)
+            .collect(),
         Err(error) => {
             tracing::error!(?error, org_id = %target_org, "failed to list remote projects");
             return Err(ErrorResponse::new(
@@ -81,7 +114,7 @@ async fn get_project(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
 
-    ensure_member_access(state.pool(), record.organization_id, ctx.user.id).await?;
+    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
 
     Ok(Json(to_remote_project(record)))
 }
@@ -142,6 +175,16 @@ async fn create_project(
         }
     };
 
+    if let Err(error) = ProjectAccessRepository::set_owner(&mut *tx, project.id, ctx.user.id).await
+    {
+        tx.rollback().await.ok();
+        tracing::error!(?error, "failed to set remote project owner");
+        return Err(ErrorResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal server error",
+        ));
+    }
+
     if let Err(error) = tx.commit().await {
         tracing::error!(?error, "failed to commit remote project creation");
         return Err(ErrorResponse::new(
@@ -153,6 +196,163 @@ async fn create_project(
     Ok(Json(to_remote_project(project)))
 }
 
+#[instrument(
+    name = "projects.get_project_access",
+    skip(state, ctx),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn get_project_access(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ProjectAccessResponse>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    Ok(Json(load_project_access(&state, project_id).await?))
+}
+
+#[instrument(
+    name = "projects.grant_project_access",
+    skip(state, ctx, payload),
+    fields(project_id = %project_id, user_id = %ctx.user.id, grantee = %payload.user_id)
+)]
+async fn grant_project_access(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<GrantProjectAccessRequest>,
+) -> Result<Json<ProjectAccessGrant>, ErrorResponse> {
+    let project = ensure_project_manager(&state, project_id, ctx.user.id).await?;
+    ensure_organization_member(&state, project.organization_id, payload.user_id).await?;
+
+    let grant =
+        ProjectAccessRepository::grant(state.pool(), project_id, payload.user_id, ctx.user.id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to grant project access");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            })?;
+
+    Ok(Json(grant))
+}
+
+#[instrument(
+    name = "projects.revoke_project_access",
+    skip(state, ctx),
+    fields(project_id = %project_id, user_id = %ctx.user.id, grantee = %grantee)
+)]
+async fn revoke_project_access(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, grantee)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ErrorResponse> {
+    ensure_project_manager(&state, project_id, ctx.user.id).await?;
+
+    let removed = ProjectAccessRepository::revoke(state.pool(), project_id, grantee)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to revoke project access");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+    if !removed {
+        return Err(ErrorResponse::new(StatusCode::NOT_FOUND, "grant not found"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(
+    name = "projects.transfer_project_ownership",
+    skip(state, ctx, payload),
+    fields(project_id = %project_id, user_id = %ctx.user.id, new_owner = %payload.new_owner_user_id)
+)]
+async fn transfer_project_ownership(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<TransferProjectOwnershipRequest>,
+) -> Result<Json<ProjectAccessResponse>, ErrorResponse> {
+    let project = ensure_project_manager(&state, project_id, ctx.user.id).await?;
+    ensure_organization_member(&state, project.organization_id, payload.new_owner_user_id).await?;
+
+    ProjectAccessRepository::set_owner(state.pool(), project_id, payload.new_owner_user_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to transfer project ownership");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(load_project_access(&state, project_id).await?))
+}
+
+/// Loads the project, requiring the caller to be its owner or an organization admin
+async fn ensure_project_manager(
+    state: &AppState,
+    project_id: Uuid,
+    user_id: Uuid,
+) -> Result<Project, ErrorResponse> {
+    let project = ProjectRepository::fetch_by_id(state.pool(), project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load project");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load project")
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
+    ensure_member_access(state.pool(), project.organization_id, user_id).await?;
+
+    let owner = ProjectAccessRepository::owner(state.pool(), project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load project owner");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+    if owner != Some(user_id) {
+        ensure_admin_access(state.pool(), project.organization_id, user_id).await?;
+    }
+
+    Ok(project)
+}
+
+async fn ensure_organization_member(
+    state: &AppState,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ErrorResponse> {
+    let is_member = organization_members::is_member(state.pool(), organization_id, user_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %organization_id, "failed to check membership");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+    if !is_member {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "user is not a member of the organization",
+        ));
+    }
+    Ok(())
+}
+
+async fn load_project_access(
+    state: &AppState,
+    project_id: Uuid,
+) -> Result<ProjectAccessResponse, ErrorResponse> {
+    let load = async {
+        let owner_user_id = ProjectAccessRepository::owner(state.pool(), project_id).await?;
+        let grants = ProjectAccessRepository::list_grants(state.pool(), project_id).await?;
+        Ok::<_, sqlx::Error>(ProjectAccessResponse {
+            project_id,
+            owner_user_id,
+            organization_wide: grants.is_empty(),
+            grants,
+        })
+    };
+    load.await.map_err(|error| {
+        tracing::error!(?error, %project_id, "failed to load project access");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })
+}
+
 fn to_remote_project(project: Project) -> RemoteProject {
     RemoteProject {
         id: project.id,
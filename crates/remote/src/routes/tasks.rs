@@ -13,6 +13,7 @@ use uuid::Uuid;
 
 use super::{
     error::{identity_error_response, task_error_response},
+    notifications::{notify_assignment, notify_mentions},
     organization_members::{ensure_project_access, ensure_task_access},
 };
 use crate::{
@@ -33,6 +34,7 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/tasks", post(create_shared_task))
         .route("/tasks/check", post(check_tasks_existence))
+        .route("/tasks/fetch", post(fetch_tasks))
         .route("/tasks/{task_id}", patch(update_shared_task))
         .route("/tasks/{task_id}", delete(delete_shared_task))
         .route("/tasks/{task_id}/assign", post(assign_task))
@@ -133,7 +135,11 @@ pub async fn create_shared_task(
     };
 
     match repo.create(data).await {
-        Ok(task) => (StatusCode::CREATED, Json(SharedTaskResponse::from(task))).into_response(),
+        Ok(task) => {
+            notify_mentions(pool, &task.task, ctx.user.id, None).await;
+            notify_assignment(pool, &task.task, ctx.user.id).await;
+            (StatusCode::CREATED, Json(SharedTaskResponse::from(task))).into_response()
+        }
         Err(error) => task_error_response(error, "failed to create shared task"),
     }
 }
@@ -197,7 +203,16 @@ pub async fn update_shared_task(
     };
 
     match repo.update(task_id, data).await {
-        Ok(task) => (StatusCode::OK, Json(SharedTaskResponse::from(task))).into_response(),
+        Ok(task) => {
+            notify_mentions(
+                pool,
+                &task.task,
+                ctx.user.id,
+                existing.description.as_deref(),
+            )
+            .await;
+            (StatusCode::OK, Json(SharedTaskResponse::from(task))).into_response()
+        }
         Err(error) => task_error_response(error, "failed to update shared task"),
     }
 }
@@ -259,7 +274,10 @@ pub async fn assign_task(
     };
 
     match repo.assign_task(task_id, data).await {
-        Ok(task) => (StatusCode::OK, Json(SharedTaskResponse::from(task))).into_response(),
+        Ok(task) => {
+            notify_assignment(pool, &task.task, ctx.user.id).await;
+            (StatusCode::OK, Json(SharedTaskResponse::from(task))).into_response()
+        }
         Err(error) => task_error_response(error, "failed to transfer task assignment"),
     }
 }
@@ -336,6 +354,30 @@ pub struct CheckTasksRequest {
     pub task_ids: Vec<Uuid>,
 }
 
+#[instrument(
+    name = "tasks.fetch",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id)
+)]
+pub async fn fetch_tasks(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<FetchTasksRequest>,
+) -> Response {
+    let pool = state.pool();
+    let repo = SharedTaskRepository::new(pool);
+
+    match repo.find_many(&payload.task_ids, ctx.user.id).await {
+        Ok(tasks) => (StatusCode::OK, Json(tasks)).into_response(),
+        Err(error) => task_error_response(error, "failed to fetch shared tasks"),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchTasksRequest {
+    pub task_ids: Vec<Uuid>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateSharedTaskRequest {
     pub project_id: Uuid,
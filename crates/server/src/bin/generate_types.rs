@@ -16,6 +16,53 @@ fn generate_types_content() -> String {
         db::models::project::UpdateProject::decl(),
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
+        db::models::project_env_var::ProjectEnvVar::decl(),
+        db::models::project_env_var::CreateProjectEnvVar::decl(),
+        db::models::project_env_var::UpdateProjectEnvVar::decl(),
+        db::models::workspace_orientation::WorkspaceOrientation::decl(),
+        db::models::workspace_template::WorkspaceTemplate::decl(),
+        db::models::workspace_template::CreateWorkspaceTemplate::decl(),
+        db::models::workspace_template::UpdateWorkspaceTemplate::decl(),
+        db::models::dependency_update_schedule::DependencyEcosystem::decl(),
+        db::models::dependency_update_schedule::DependencyUpdateSchedule::decl(),
+        db::models::dependency_update_schedule::UpsertDependencyUpdateSchedule::decl(),
+        db::models::notification_channel::NotificationChannelKind::decl(),
+        db::models::notification_channel::NotificationEvent::decl(),
+        db::models::notification_channel::NotificationChannel::decl(),
+        db::models::notification_channel::CreateNotificationChannel::decl(),
+        db::models::notification_channel::UpdateNotificationChannel::decl(),
+        db::models::notification_channel_identity::NotificationChannelIdentity::decl(),
+        db::models::notification_channel_identity::CreateNotificationChannelIdentity::decl(),
+        db::models::email_notification_preference::EmailNotificationPreference::decl(),
+        db::models::email_notification_preference::CreateEmailNotificationPreference::decl(),
+        db::models::email_notification_preference::UpdateEmailNotificationPreference::decl(),
+        db::models::user::User::decl(),
+        db::models::user::CreateUser::decl(),
+        db::models::user::UpdateUser::decl(),
+        db::models::api_token::ApiTokenScope::decl(),
+        db::models::api_token::ApiToken::decl(),
+        db::models::api_token::CreateApiToken::decl(),
+        db::models::api_token::CreatedApiToken::decl(),
+        db::models::dashboard::DashboardToken::decl(),
+        db::models::dashboard::CreateDashboardToken::decl(),
+        db::models::dashboard::CreatedDashboardToken::decl(),
+        db::models::dashboard::DashboardColumn::decl(),
+        db::models::dashboard::DashboardRunningAgent::decl(),
+        db::models::dashboard::DashboardEscalationReason::decl(),
+        db::models::dashboard::DashboardEscalation::decl(),
+        db::models::dashboard::BoardSnapshot::decl(),
+        db::models::board_snapshot::BoardColumnSnapshot::decl(),
+        db::models::board_layout_settings::BoardLayoutSettings::decl(),
+        db::models::board_layout_settings::UpdateBoardLayoutSettings::decl(),
+        db::models::board_notification_settings::NotificationDigestCadence::decl(),
+        db::models::board_notification_settings::BoardNotificationSettings::decl(),
+        db::models::board_notification_settings::UpdateBoardNotificationSettings::decl(),
+        db::models::board_notification_settings::NotificationDigestItem::decl(),
+        db::models::board_share::BoardShareLink::decl(),
+        db::models::board_share::CreatedBoardShareLink::decl(),
+        db::models::board_share::SharedBoardTask::decl(),
+        db::models::board_share::SharedBoardColumn::decl(),
+        db::models::board_share::SharedBoardView::decl(),
         db::models::board::Board::decl(),
         db::models::board::CreateBoard::decl(),
         db::models::board::UpdateBoard::decl(),
@@ -23,15 +70,60 @@ fn generate_types_content() -> String {
         db::models::kanban_column::KanbanColumn::decl(),
         db::models::kanban_column::CreateKanbanColumn::decl(),
         db::models::kanban_column::UpdateKanbanColumn::decl(),
+        db::models::column_label_rule::LabelRuleTrigger::decl(),
+        db::models::column_label_rule::LabelRuleAction::decl(),
+        db::models::column_label_rule::ColumnLabelRule::decl(),
+        db::models::column_label_rule::CreateColumnLabelRule::decl(),
+        db::models::kanban_column::UpdateKanbanColumnSettings::decl(),
+        db::models::security_finding::SecurityScanner::decl(),
+        db::models::security_finding::FindingSeverity::decl(),
+        db::models::security_finding::SecurityFinding::decl(),
+        db::models::execution_artifact::ExecutionArtifact::decl(),
+        db::models::execution_deliverable_report::DeliverableRequirementKind::decl(),
+        db::models::execution_deliverable_report::DeliverableRequirement::decl(),
+        db::models::execution_deliverable_report::ExecutionDeliverableReport::decl(),
         db::models::state_transition::StateTransition::decl(),
         db::models::state_transition::StateTransitionWithColumns::decl(),
         db::models::state_transition::CreateStateTransition::decl(),
         db::models::state_transition::UpdateStateTransition::decl(),
         db::models::state_transition::TransitionScope::decl(),
+        db::models::transition_retry_policy::TransitionRetryPolicy::decl(),
+        db::models::transition_retry_policy::UpsertTransitionRetryPolicy::decl(),
+        db::models::transition_artifact_requirement::TransitionArtifactRequirement::decl(),
+        db::models::transition_artifact_requirement::UpsertTransitionArtifactRequirement::decl(),
         db::models::repo::Repo::decl(),
+        db::models::repo_forge_settings::ForgeKind::decl(),
+        db::models::repo_forge_settings::RepoForgeSettings::decl(),
+        db::models::repo_forge_settings::UpdateRepoForgeSettings::decl(),
+        db::models::repo_merge_settings::MergeStrategy::decl(),
+        db::models::repo_merge_settings::RepoMergeSettings::decl(),
+        db::models::repo_merge_settings::UpdateRepoMergeSettings::decl(),
+        db::models::review_comment::ReviewCommentSide::decl(),
+        db::models::review_comment::ReviewComment::decl(),
+        db::models::review_comment::CreateReviewComment::decl(),
+        db::models::project::UpdateProjectBoardSettings::decl(),
+        db::models::project::UpdateProjectBranchSettings::decl(),
+        db::models::project_share_sync_settings::ShareConflictStrategy::decl(),
+        db::models::project_share_sync_settings::ProjectShareSyncSettings::decl(),
+        db::models::project_share_sync_settings::UpdateProjectShareSyncSettings::decl(),
+        db::models::project_log_redaction_settings::ProjectLogRedactionSettings::decl(),
+        db::models::project_log_redaction_settings::UpdateProjectLogRedactionSettings::decl(),
+        db::models::project_module_memory_settings::ProjectModuleMemorySettings::decl(),
+        db::models::project_module_memory_settings::UpdateProjectModuleMemorySettings::decl(),
+        db::models::project_timezone_settings::ProjectTimezoneSettings::decl(),
+        db::models::project_timezone_settings::UpdateProjectTimezoneSettings::decl(),
+        db::models::project_security_settings::ProjectSecuritySettings::decl(),
+        db::models::project_security_settings::UpdateProjectSecuritySettings::decl(),
+        db::models::shared_task_sync::SharedTaskFields::decl(),
+        db::models::shared_task_sync::ConflictResolution::decl(),
+        db::models::shared_task_sync::SharedTaskConflict::decl(),
+        db::models::project::ExecutionBackend::decl(),
+        db::models::project::UpdateProjectExecutionSettings::decl(),
         db::models::project_repo::ProjectRepo::decl(),
         db::models::project_repo::CreateProjectRepo::decl(),
         db::models::project_repo::UpdateProjectRepo::decl(),
+        db::models::project_repo_script_settings::ProjectRepoScriptSettings::decl(),
+        db::models::project_repo_script_settings::UpdateProjectRepoScriptSettings::decl(),
         db::models::workspace_repo::WorkspaceRepo::decl(),
         db::models::workspace_repo::CreateWorkspaceRepo::decl(),
         db::models::workspace_repo::RepoWithTargetBranch::decl(),
@@ -43,6 +135,7 @@ fn generate_types_content() -> String {
         db::models::task::Task::decl(),
         db::models::task::TaskWithAttemptStatus::decl(),
         db::models::task::TaskRelationships::decl(),
+        db::models::task::SubtaskProgress::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
         db::models::task_trigger::TaskTrigger::decl(),
@@ -61,6 +154,12 @@ fn generate_types_content() -> String {
         db::models::task_label::CreateTaskLabel::decl(),
         db::models::task_label::UpdateTaskLabel::decl(),
         db::models::task_label::TaskLabelAssignment::decl(),
+        db::models::task_label::LabelMatch::decl(),
+        db::models::task_log_retention::LogRetention::decl(),
+        db::models::task_log_retention::TaskLogRetention::decl(),
+        db::models::task_log_retention::UpdateTaskLogRetention::decl(),
+        db::models::task_subscription::TaskSubscription::decl(),
+        db::models::task_subscription::CreateTaskSubscription::decl(),
         db::models::scratch::DraftFollowUpData::decl(),
         db::models::scratch::ScratchPayload::decl(),
         db::models::scratch::ScratchType::decl(),
@@ -74,7 +173,13 @@ fn generate_types_content() -> String {
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
+        db::models::execution_process::InterruptedSession::decl(),
         db::models::execution_process_repo_state::ExecutionProcessRepoState::decl(),
+        db::models::execution_environment::EnvironmentRepo::decl(),
+        db::models::execution_environment::ExecutionEnvironment::decl(),
+        db::models::execution_log_redaction::ExecutionLogRedaction::decl(),
+        db::models::execution_replay::ExecutionReplay::decl(),
+        db::models::dry_run_patch::DryRunPatch::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
@@ -85,6 +190,10 @@ fn generate_types_content() -> String {
         utils::approvals::ApprovalResponse::decl(),
         utils::diff::Diff::decl(),
         utils::diff::DiffChangeKind::decl(),
+        utils::diff::DiffLineKind::decl(),
+        utils::diff::DiffLine::decl(),
+        utils::diff::DiffHunk::decl(),
+        services::services::review::ReviewFileDiff::decl(),
         utils::response::ApiResponse::<()>::decl(),
         utils::api::oauth::LoginStatus::decl(),
         utils::api::oauth::ProfileResponse::decl(),
@@ -114,14 +223,25 @@ fn generate_types_content() -> String {
         utils::api::projects::RemoteProject::decl(),
         utils::api::projects::ListProjectsResponse::decl(),
         utils::api::projects::RemoteProjectMembersResponse::decl(),
+        utils::api::projects::ProjectAccessGrant::decl(),
+        utils::api::projects::ProjectAccessResponse::decl(),
+        utils::api::projects::GrantProjectAccessRequest::decl(),
+        utils::api::projects::TransferProjectOwnershipRequest::decl(),
+        utils::api::notifications::UserNotificationKind::decl(),
+        utils::api::notifications::UserNotification::decl(),
+        utils::api::notifications::ListNotificationsResponse::decl(),
+        utils::api::notifications::MarkNotificationsReadRequest::decl(),
         server::routes::projects::CreateRemoteProjectRequest::decl(),
         server::routes::projects::LinkToExistingRequest::decl(),
+        server::routes::projects::RepoDefaultTargetBranch::decl(),
+        server::routes::projects::UpdateRepoDefaultTargetBranch::decl(),
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
         server::routes::tags::TagSearchParams::decl(),
         server::routes::oauth::TokenResponse::decl(),
         server::routes::config::UserSystemInfo::decl(),
         server::routes::config::Environment::decl(),
+        server::middleware::rate_limit::RateLimitMetrics::decl(),
         server::routes::config::McpServerQuery::decl(),
         server::routes::config::UpdateMcpServersBody::decl(),
         server::routes::config::GetMcpServerResponse::decl(),
@@ -130,17 +250,37 @@ fn generate_types_content() -> String {
         server::routes::config::CheckAgentAvailabilityQuery::decl(),
         server::routes::oauth::CurrentUserResponse::decl(),
         server::routes::sessions::CreateFollowUpAttempt::decl(),
+        server::routes::sessions::SendSessionMessageRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchResponse::decl(),
         server::routes::task_attempts::MergeTaskAttemptRequest::decl(),
+        server::routes::task_attempts::RepoMergeResult::decl(),
+        server::routes::task_attempts::PatchSource::decl(),
+        server::routes::task_attempts::ApplyPatchRequest::decl(),
+        server::routes::task_attempts::AppliedPatch::decl(),
         server::routes::task_attempts::PushTaskAttemptRequest::decl(),
         server::routes::task_attempts::RenameBranchRequest::decl(),
         server::routes::task_attempts::RenameBranchResponse::decl(),
         server::routes::task_attempts::OpenEditorRequest::decl(),
         server::routes::task_attempts::OpenEditorResponse::decl(),
         server::routes::shared_tasks::AssignSharedTaskRequest::decl(),
+        server::routes::shared_tasks::ResolveSharedTaskConflictRequest::decl(),
         server::routes::tasks::ShareTaskResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
+        server::routes::tasks::CostEstimateQuery::decl(),
+        server::routes::tasks::SetDecisionRequest::decl(),
+        server::routes::tasks::TaskStreamSubscription::decl(),
+        server::routes::tasks::TasksWsClientMessage::decl(),
+        server::routes::subtasks::TaskSubtasks::decl(),
+        server::routes::subtasks::CreateSubtasksFromPlanRequest::decl(),
+        server::routes::task_checklists::TickChecklistItem::decl(),
+        server::routes::task_handback::TaskHandback::decl(),
+        server::routes::task_handback::HandBackTaskRequest::decl(),
+        server::routes::task_archive::TaskArchive::decl(),
+        server::routes::users::TaskAssignment::decl(),
+        server::routes::users::AssignTask::decl(),
+        server::routes::task_executions::ExecuteTaskRequest::decl(),
+        server::routes::execution_processes::ReplayExecutionProcessRequest::decl(),
         server::routes::task_attempts::pr::CreateGitHubPrRequest::decl(),
         server::routes::images::ImageResponse::decl(),
         server::routes::images::ImageMetadata::decl(),
@@ -157,7 +297,7 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::AbortConflictsRequest::decl(),
         server::routes::task_attempts::GitOperationError::decl(),
         server::routes::task_attempts::PushError::decl(),
-        server::routes::task_attempts::pr::CreatePrError::decl(),
+        services::services::forge::CreatePrError::decl(),
         server::routes::task_attempts::BranchStatus::decl(),
         server::routes::task_attempts::RunScriptError::decl(),
         server::routes::task_attempts::pr::AttachPrResponse::decl(),
@@ -165,8 +305,17 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::pr::PrCommentsResponse::decl(),
         server::routes::task_attempts::pr::GetPrCommentsError::decl(),
         server::routes::task_attempts::pr::GetPrCommentsQuery::decl(),
+        server::routes::task_attempts::pr::PrChecksResponse::decl(),
+        server::routes::task_attempts::pr::GetPrChecksError::decl(),
+        server::routes::task_attempts::pr::GetPrChecksQuery::decl(),
+        server::routes::task_attempts::review::RejectReviewRequest::decl(),
+        server::routes::task_attempts::review::RejectReviewResponse::decl(),
         services::services::github::UnifiedPrComment::decl(),
+        services::services::forge::PrCheckState::decl(),
+        services::services::forge::PrCheck::decl(),
         server::routes::task_attempts::RepoBranchStatus::decl(),
+        services::services::process_reaper::LingeringProcess::decl(),
+        services::services::process_reaper::ProcessReaperReport::decl(),
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
         services::services::config::Config::decl(),
@@ -179,6 +328,50 @@ fn generate_types_content() -> String {
         services::services::config::SoundFile::decl(),
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
+        services::services::config::AgentConcurrencyConfig::decl(),
+        services::services::config::AttemptBudgetConfig::decl(),
+        services::services::config::OutputLimitsConfig::decl(),
+        services::services::config::RateLimitConfig::decl(),
+        services::services::cost_estimate::CostEstimate::decl(),
+        services::services::cycle_times::DurationStats::decl(),
+        services::services::cycle_times::ColumnCycleTime::decl(),
+        services::services::cycle_times::AgentCycleTime::decl(),
+        services::services::cycle_times::BoardCycleTimes::decl(),
+        services::services::cycle_times::TaskColumnVisit::decl(),
+        services::services::cycle_times::TaskColumnTotal::decl(),
+        services::services::cycle_times::TaskCycleTime::decl(),
+        services::services::escalation_slo::EscalationPathSlo::decl(),
+        services::services::escalation_slo::BoardEscalationSlo::decl(),
+        services::services::board_snapshots::BurnUpPoint::decl(),
+        services::services::board_snapshots::BoardSnapshotHistory::decl(),
+        services::services::column_checklist::ChecklistItemStatus::decl(),
+        services::services::column_checklist::TaskChecklist::decl(),
+        services::services::workspace_conflicts::ConflictingTask::decl(),
+        services::services::workspace_conflicts::WorkspaceConflict::decl(),
+        services::services::workspace_conflicts::ProjectConflicts::decl(),
+        services::services::board_validation::BoardIssueKind::decl(),
+        services::services::board_validation::BoardIssue::decl(),
+        services::services::board_validation::BoardValidationReport::decl(),
+        services::services::workflow_lint::WorkflowWarningKind::decl(),
+        services::services::workflow_lint::WorkflowWarning::decl(),
+        services::services::workflow_lint::StateTransitionWithWarnings::decl(),
+        services::services::workflow_lint::KanbanColumnWithWarnings::decl(),
+        services::services::task_import::ImportSource::decl(),
+        services::services::task_import::ImportFormat::decl(),
+        services::services::task_import::ImportTasksRequest::decl(),
+        services::services::task_import::PlannedImportTask::decl(),
+        services::services::task_import::ImportTasksResponse::decl(),
+        services::services::agent_concurrency::RunningAgentSlot::decl(),
+        services::services::agent_concurrency::QueuedAgentSlot::decl(),
+        services::services::agent_concurrency::AgentConcurrencySnapshot::decl(),
+        services::services::agent_concurrency::ProjectFairness::decl(),
+        services::services::executor_health::CircuitState::decl(),
+        services::services::executor_health::ExecutorHealthStatus::decl(),
+        services::services::readiness::ComponentStatus::decl(),
+        services::services::readiness::ReadinessReport::decl(),
+        services::services::log_export::TranscriptEntry::decl(),
+        services::services::log_export::ExecutionTranscript::decl(),
+        services::services::dev_server_preview::DevServerPreview::decl(),
         services::services::git::GitBranch::decl(),
         services::services::share::SharedTaskDetails::decl(),
         services::services::remote_types::RemoteTaskStatus::decl(),
@@ -190,12 +383,15 @@ fn generate_types_content() -> String {
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
         services::services::git::ConflictOp::decl(),
+        executors::actions::AgentIdentity::decl(),
         executors::actions::ExecutorAction::decl(),
         executors::mcp_config::McpConfig::decl(),
+        executors::mcp_config::AgentMcpServer::decl(),
         executors::actions::ExecutorActionType::decl(),
         executors::actions::script::ScriptContext::decl(),
         executors::actions::script::ScriptRequest::decl(),
         executors::actions::script::ScriptRequestLanguage::decl(),
+        executors::actions::script::ScriptInterpreter::decl(),
         executors::executors::BaseCodingAgent::decl(),
         executors::executors::CodingAgent::decl(),
         executors::executors::AvailabilityInfo::decl(),
@@ -220,8 +416,12 @@ fn generate_types_content() -> String {
         executors::executors::droid::Droid::decl(),
         executors::executors::droid::Autonomy::decl(),
         executors::executors::droid::ReasoningEffortLevel::decl(),
+        executors::executors::custom::Custom::decl(),
+        executors::executors::custom::CustomLogFormat::decl(),
         executors::executors::AppendPrompt::decl(),
         executors::actions::coding_agent_initial::CodingAgentInitialRequest::decl(),
+        executors::actions::coding_agent_initial::PromptSection::decl(),
+        executors::actions::coding_agent_initial::PromptLanguage::decl(),
         executors::actions::coding_agent_follow_up::CodingAgentFollowUpRequest::decl(),
         executors::logs::CommandExitStatus::decl(),
         executors::logs::CommandRunResult::decl(),
@@ -241,6 +441,14 @@ fn generate_types_content() -> String {
         db::models::agent::CreateAgent::decl(),
         db::models::agent::UpdateAgent::decl(),
         db::models::agent::ContextFile::decl(),
+        db::models::agent_stats::StatsTimeRange::decl(),
+        db::models::agent_stats::PerformanceStats::decl(),
+        db::models::agent_stats::AgentStats::decl(),
+        db::models::agent_stats::BoardStats::decl(),
+        db::models::agent_mcp_settings::AgentMcpSettings::decl(),
+        db::models::agent_mcp_settings::UpdateAgentMcpSettings::decl(),
+        db::models::agent_prompt_settings::AgentPromptSettings::decl(),
+        db::models::agent_prompt_settings::UpdateAgentPromptSettings::decl(),
         // Skill types
         db::models::skill::Skill::decl(),
         db::models::skill::CreateSkill::decl(),
@@ -257,6 +465,15 @@ fn generate_types_content() -> String {
         db::models::automation_rule::CreatePrConfig::decl(),
         db::models::automation_rule::WebhookConfig::decl(),
         db::models::automation_rule::NotifyConfig::decl(),
+        db::models::automation_rule::RunScriptConfig::decl(),
+        db::models::automation_rule::AssignAgentConfig::decl(),
+        db::models::automation_rule::SetLabelsConfig::decl(),
+        db::models::automation_rule::MoveToColumnConfig::decl(),
+        db::models::automation_rule::CreateFollowUpTaskConfig::decl(),
+        db::models::automation_rule::CreateSubtasksFromPlanConfig::decl(),
+        db::models::automation_execution::AutomationExecution::decl(),
+        db::models::setup_script_run::SetupScriptRun::decl(),
+        db::models::setup_script_run::SetupScriptHealth::decl(),
         // Task event types
         db::models::task_event::TaskEventType::decl(),
         db::models::task_event::EventTriggerType::decl(),
@@ -271,6 +488,31 @@ fn generate_types_content() -> String {
         db::models::context_artifact::CreateContextArtifact::decl(),
         db::models::context_artifact::UpdateContextArtifact::decl(),
         db::models::context_artifact::ContextPreviewStats::decl(),
+        db::models::context_artifact_review::ArtifactReviewStatus::decl(),
+        db::models::context_artifact_review::ContextArtifactReview::decl(),
+        db::models::context_artifact_review::ReviewContextArtifact::decl(),
+        db::models::context_artifact_ttl::ContextArtifactTtl::decl(),
+        db::models::context_artifact_ttl::SetContextArtifactTtl::decl(),
+        server::routes::context_artifacts::ReviewedContextArtifact::decl(),
+        db::models::context_budget::ContextBudgetOverride::decl(),
+        db::models::context_budget::ContextBudget::decl(),
+        db::models::context_budget::ContextBudgetSettings::decl(),
+        // Handoff note types
+        db::models::handoff_note::HandoffNoteSource::decl(),
+        db::models::handoff_note::HandoffNote::decl(),
+        db::models::handoff_note::CreateHandoffNote::decl(),
+        db::models::reviewer_feedback::ReviewerFeedbackSource::decl(),
+        db::models::reviewer_feedback::ReviewerFeedback::decl(),
+        db::models::task_comment::TaskComment::decl(),
+        db::models::task_comment::CreateTaskComment::decl(),
+        db::models::task_comment::UpdateTaskComment::decl(),
+        db::models::plan_review::PlanReviewStatus::decl(),
+        db::models::plan_review::PlanReview::decl(),
+        db::models::plan_review::ReviewPlan::decl(),
+        db::models::background_job::BackgroundJobStatus::decl(),
+        db::models::background_job::BackgroundJob::decl(),
+        db::models::data_migration::DataMigrationStatus::decl(),
+        db::models::data_migration::DataMigration::decl(),
         // Evaluate run types
         db::models::evaluate_run::EvaluateRun::decl(),
         db::models::evaluate_run::EvaluateRunSummary::decl(),
@@ -365,6 +607,10 @@ fn generate_schemas() -> Result<HashMap<&'static str, String>, serde_json::Error
             "droid",
             generate_json_schema::<executors::executors::droid::Droid>()?,
         ),
+        (
+            "custom",
+            generate_json_schema::<executors::executors::custom::Custom>()?,
+        ),
     ]);
     println!(
         "✅ JSON schemas generated. {} schemas created.",
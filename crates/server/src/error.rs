@@ -15,7 +15,9 @@ use git2::Error as Git2Error;
 use services::services::{
     config::{ConfigError, EditorOpenError},
     container::ContainerError,
+    env_vault::EnvVaultError,
     git::GitServiceError,
+    forge::CodeHostError,
     github::GitHubServiceError,
     image::ImageError,
     project::ProjectServiceError,
@@ -47,6 +49,8 @@ pub enum ApiError {
     #[error(transparent)]
     GitHubService(#[from] GitHubServiceError),
     #[error(transparent)]
+    CodeHost(#[from] CodeHostError),
+    #[error(transparent)]
     Deployment(#[from] DeploymentError),
     #[error(transparent)]
     Container(#[from] ContainerError),
@@ -57,6 +61,8 @@ pub enum ApiError {
     #[error(transparent)]
     Worktree(#[from] WorktreeError),
     #[error(transparent)]
+    EnvVault(#[from] EnvVaultError),
+    #[error(transparent)]
     Config(#[from] ConfigError),
     #[error(transparent)]
     Image(#[from] ImageError),
@@ -121,11 +127,28 @@ impl IntoResponse for ApiError {
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "GitServiceError"),
             },
             ApiError::GitHubService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError"),
+            ApiError::CodeHost(err) => match err {
+                CodeHostError::GitHub(_) => {
+                    (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError")
+                }
+                CodeHostError::NotConfigured(..) | CodeHostError::Repository(_) => {
+                    (StatusCode::BAD_REQUEST, "CodeHostError")
+                }
+                CodeHostError::EnvVault(_) => (StatusCode::INTERNAL_SERVER_ERROR, "CodeHostError"),
+                _ => (StatusCode::BAD_GATEWAY, "CodeHostError"),
+            },
             ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
+            ApiError::Container(ContainerError::ShuttingDown) => {
+                (StatusCode::SERVICE_UNAVAILABLE, "ShuttingDown")
+            }
+            ApiError::Container(ContainerError::BudgetExceeded(_)) => {
+                (StatusCode::BAD_REQUEST, "BudgetExceeded")
+            }
             ApiError::Container(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
             ApiError::Executor(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
             ApiError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DatabaseError"),
             ApiError::Worktree(_) => (StatusCode::INTERNAL_SERVER_ERROR, "WorktreeError"),
+            ApiError::EnvVault(_) => (StatusCode::INTERNAL_SERVER_ERROR, "EnvVaultError"),
             ApiError::Config(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ConfigError"),
             ApiError::Image(img_err) => match img_err {
                 ImageError::InvalidFormat => (StatusCode::BAD_REQUEST, "InvalidImageFormat"),
@@ -251,6 +274,7 @@ impl IntoResponse for ApiError {
             },
             ApiError::Unauthorized => "Unauthorized. Please sign in again.".to_string(),
             ApiError::BadRequest(msg) => msg.clone(),
+            ApiError::Container(ContainerError::BudgetExceeded(msg)) => msg.clone(),
             ApiError::Conflict(msg) => msg.clone(),
             ApiError::Forbidden(msg) => msg.clone(),
             _ => format!("{}: {}", error_type, self),
@@ -265,6 +289,12 @@ impl From<ShareError> for ApiError {
         match err {
             ShareError::Database(db_err) => ApiError::Database(db_err),
             ShareError::AlreadyShared(_) => ApiError::Conflict("Task already shared".to_string()),
+            ShareError::ConflictNotFound(_) => {
+                ApiError::BadRequest("Shared task conflict not found".to_string())
+            }
+            ShareError::ConflictAlreadyResolved(_) => {
+                ApiError::Conflict("Shared task conflict is already resolved".to_string())
+            }
             ShareError::TaskNotFound(_) => {
                 ApiError::Conflict("Task not found for sharing".to_string())
             }
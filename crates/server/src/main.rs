@@ -1,7 +1,12 @@
 use anyhow::{self, Error as AnyhowError};
 use deployment::{Deployment, DeploymentError};
 use server::{DeploymentImpl, routes};
-use services::services::container::ContainerService;
+use services::services::{
+    container::ContainerService,
+    data_migration,
+    job_queue::{self, Job},
+    shutdown::{LOG_FLUSH_TIMEOUT, ShutdownCoordinator},
+};
 use sqlx::Error as SqlxError;
 use strip_ansi_escapes::strip;
 use thiserror::Error;
@@ -52,18 +57,29 @@ async fn main() -> Result<(), VibeKanbanError> {
         .cleanup_orphan_executions()
         .await
         .map_err(DeploymentError::from)?;
-    deployment
-        .container()
-        .backfill_before_head_commits()
-        .await
-        .map_err(DeploymentError::from)?;
-    deployment
-        .container()
-        .backfill_repo_names()
-        .await
-        .map_err(DeploymentError::from)?;
+    // Backfills run from the admin API; only report what's outstanding
+    if let Err(e) = data_migration::check_on_startup(&deployment.db().pool).await {
+        tracing::warn!("Failed to check data migrations: {}", e);
+    }
     // Spawn PR monitor service (lightweight, runs every 60s)
     deployment.spawn_pr_monitor_service().await;
+    // Spawn dependency update scheduler (checks due schedules every 15 minutes)
+    deployment.spawn_dependency_update_service().await;
+    // Spawn workspace conflict detection (records changed files every 2 minutes)
+    deployment.spawn_workspace_conflict_service().await;
+    // Spawn background job worker (retries queued work, survives restarts)
+    deployment.spawn_job_queue_service().await;
+    // Spawn shared task sync (pulls remote edits back every 60s)
+    deployment.spawn_shared_task_sync_service().await;
+    // Spawn notifier for events on watched tasks and labels
+    deployment.spawn_task_watch_service().await;
+    // Spawn daily email digests (no-op unless SMTP is configured)
+    deployment.spawn_email_digest_service().await;
+    // Spawn hourly board column snapshots for burn-up charts
+    deployment.spawn_board_snapshot_service().await;
+    deployment.spawn_notification_digest_service().await;
+    // Spawn daily pruning of expired execution logs and artifacts
+    deployment.spawn_log_retention_service().await;
     // NOTE: Task Grouper service is NOT run automatically to avoid connection pool contention
     // It should be triggered manually via the "Group Tasks" button or API endpoint
     deployment
@@ -82,14 +98,11 @@ async fn main() -> Result<(), VibeKanbanError> {
     });
 
     // Verify shared tasks in background
-    let deployment_for_verification = deployment.clone();
-    tokio::spawn(async move {
-        if let Some(publisher) = deployment_for_verification.container().share_publisher()
-            && let Err(e) = publisher.cleanup_shared_tasks().await
-        {
-            tracing::warn!("Failed to verify shared tasks: {}", e);
-        }
-    });
+    if deployment.share_publisher().is_ok()
+        && let Err(e) = job_queue::enqueue(&deployment.db().pool, &Job::VerifySharedTasks).await
+    {
+        tracing::warn!("Failed to queue shared task verification: {}", e);
+    }
 
     let app_router = routes::router(deployment.clone());
 
@@ -169,7 +182,35 @@ pub async fn shutdown_signal() {
         }
     }
 
-    #[cfg(not(unix))]
+    #[cfg(windows)]
+    {
+        use tokio::signal::windows::{ctrl_break, ctrl_close, ctrl_shutdown};
+
+        // Ctrl+Break, closing the console window and system shutdown stop the server
+        // like Ctrl+C; if the handlers can't be installed, only Ctrl+C does
+        let console = async {
+            match (ctrl_break(), ctrl_close(), ctrl_shutdown()) {
+                (Ok(mut ctrl_break), Ok(mut ctrl_close), Ok(mut ctrl_shutdown)) => {
+                    tokio::select! {
+                        _ = ctrl_break.recv() => {},
+                        _ = ctrl_close.recv() => {},
+                        _ = ctrl_shutdown.recv() => {},
+                    }
+                }
+                _ => {
+                    tracing::error!("Failed to install Windows console handlers");
+                    std::future::pending::<()>().await;
+                }
+            }
+        };
+
+        tokio::select! {
+            _ = ctrl_c => {},
+            _ = console => {},
+        }
+    }
+
+    #[cfg(not(any(unix, windows)))]
     {
         // Only ctrl_c is available, so just await it
         ctrl_c.await;
@@ -177,9 +218,19 @@ pub async fn shutdown_signal() {
 }
 
 pub async fn perform_cleanup_actions(deployment: &DeploymentImpl) {
+    let shutdown = ShutdownCoordinator::shared();
+    shutdown.begin();
     deployment
         .container()
-        .kill_all_running_processes()
+        .interrupt_all_running_processes()
         .await
-        .expect("Failed to cleanly kill running execution processes");
+        .expect("Failed to cleanly interrupt running execution processes");
+
+    let unflushed = shutdown.flush_log_writers(LOG_FLUSH_TIMEOUT).await;
+    if unflushed > 0 {
+        tracing::warn!(
+            "{} execution log writers did not finish before shutdown",
+            unflushed
+        );
+    }
 }
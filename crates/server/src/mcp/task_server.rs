@@ -19,6 +19,7 @@ use rmcp::{
 };
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json;
+use services::services::{api_tokens::API_TOKEN_ENV_VAR, mcp_credentials::MCP_TOKEN_ENV_VAR};
 use uuid::Uuid;
 
 use crate::routes::{
@@ -203,6 +204,14 @@ pub struct ListTasksRequest {
     pub status: Option<String>,
     #[schemars(description = "Maximum number of tasks to return (default: 50)")]
     pub limit: Option<i32>,
+    #[schemars(
+        description = "Optional label names or IDs; only tasks with these labels are listed"
+    )]
+    pub labels: Option<Vec<String>>,
+    #[schemars(
+        description = "How tasks must match `labels`: 'any' (default) for at least one, 'all' for every label"
+    )]
+    pub label_match: Option<String>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -300,6 +309,8 @@ pub struct ListTasksResponse {
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct ListTasksFilters {
     pub status: Option<String>,
+    pub labels: Option<Vec<String>>,
+    pub label_match: Option<String>,
     pub limit: i32,
 }
 
@@ -330,8 +341,8 @@ pub struct DeleteTaskRequest {
 pub struct McpWorkspaceRepoInput {
     #[schemars(description = "The repository ID")]
     pub repo_id: Uuid,
-    #[schemars(description = "The base branch for this repository")]
-    pub base_branch: String,
+    #[schemars(description = "The base branch for this repository. Defaults to the repository's default target branch in the project, else its current branch")]
+    pub base_branch: Option<String>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -443,7 +454,7 @@ pub struct TransitionSummary {
     pub to_column_id: String,
     #[schemars(description = "Optional name for the transition")]
     pub name: Option<String>,
-    #[schemars(description = "Answer value that triggers this transition (e.g., 'yes', 'approve')")]
+    #[schemars(description = "Answer value that triggers this transition (e.g., 'yes', 'approve'), or an expression over decision keys (e.g., 'coverage >= 80', 'tests_passed == true', 'severity in [high, critical]')")]
     pub condition_value: Option<String>,
     #[schemars(description = "Column ID to route to when condition doesn't match (else/fallback path)")]
     pub else_column_id: Option<String>,
@@ -509,7 +520,7 @@ pub struct CreateTransitionRequest {
     pub to_column_id: Uuid,
     #[schemars(description = "Optional name for the transition (e.g., 'Approve', 'Reject')")]
     pub name: Option<String>,
-    #[schemars(description = "Answer value that triggers this transition (e.g., 'yes', 'no'). Matched against the agent's answer in .vibe/decision.json.")]
+    #[schemars(description = "Answer value that triggers this transition (e.g., 'yes', 'no'), matched against the agent's answer in .vibe/decision.json. May also be an expression over decision keys: 'coverage >= 80', 'tests_passed == true', 'severity in [high, critical]'.")]
     pub condition_value: Option<String>,
     #[schemars(description = "Column ID to route to when condition doesn't match (else/retry path)")]
     pub else_column_id: Option<Uuid>,
@@ -611,6 +622,10 @@ pub struct CreateProjectMcpRequest {
         description = "List of repositories to add to the project. Each repository needs a display_name and git_repo_path."
     )]
     pub repositories: Vec<CreateProjectRepoInput>,
+    #[schemars(
+        description = "Optional workflow template board ID; the project gets a board cloned from it with the template's columns, transitions and agents"
+    )]
+    pub workflow_template_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -681,6 +696,59 @@ pub struct ListArtifactsResponse {
     pub project_id: String,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct UpsertModuleMemoryRequest {
+    #[schemars(description = "The ID of the project the module belongs to")]
+    pub project_id: Uuid,
+    #[schemars(description = "Module path, e.g. 'src/api'. The memory of this path is replaced if it exists.")]
+    pub path: String,
+    #[schemars(description = "Short title for the memory")]
+    pub title: String,
+    #[schemars(description = "What the module is for, its main types and entry points, and conventions worth knowing (markdown)")]
+    pub content: String,
+    #[schemars(description = "Task whose merge prompted the update")]
+    pub source_task_id: Option<Uuid>,
+    #[schemars(description = "Commit the memory describes")]
+    pub source_commit_hash: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct WriteHandoffNoteRequest {
+    #[schemars(description = "The task the note is for. Defaults to the task of the current workspace session.")]
+    pub task_id: Option<Uuid>,
+    #[schemars(description = "What was done in this column and what the next stage needs to know (markdown)")]
+    pub summary: String,
+    #[schemars(description = "Files created, changed or deleted in this column")]
+    pub files_touched: Option<Vec<String>>,
+    #[schemars(description = "Questions left open for the next stage or a human")]
+    pub open_questions: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct WriteHandoffNoteResponse {
+    pub note_id: String,
+    pub task_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SetDecisionMcpRequest {
+    #[schemars(description = "The task to set the decision for. Defaults to the task of the current workspace session.")]
+    pub task_id: Option<Uuid>,
+    #[schemars(description = "Decision key to set. Use 'answer' for the column's question (must be one of its answer options), or another key read by transition conditions (e.g. 'coverage')")]
+    pub key: String,
+    #[schemars(description = "The value to set (string, number, boolean or JSON)")]
+    pub value: serde_json::Value,
+    #[schemars(description = "Optional feedback for the next attempt, appended to any feedback already in the decision")]
+    pub feedback: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct SetDecisionMcpResponse {
+    pub task_id: String,
+    #[schemars(description = "The workspace's decision after the update")]
+    pub decision: serde_json::Value,
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskServer {
     client: reqwest::Client,
@@ -731,13 +799,31 @@ pub struct McpContext {
 impl TaskServer {
     pub fn new(base_url: &str) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: Self::build_client(),
             base_url: base_url.to_string(),
             tool_router: Self::tool_router(),
             context: None,
         }
     }
 
+    /// Authenticates with the execution's scoped token when running inside a workspace,
+    /// or with a personal access token from `VK_API_TOKEN` when running remotely
+    fn build_client() -> reqwest::Client {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Ok(token) =
+            std::env::var(MCP_TOKEN_ENV_VAR).or_else(|_| std::env::var(API_TOKEN_ENV_VAR))
+            && let Ok(mut value) =
+                reqwest::header::HeaderValue::from_str(&format!("Bearer {token}"))
+        {
+            value.set_sensitive(true);
+            headers.insert(reqwest::header::AUTHORIZATION, value);
+        }
+        reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new())
+    }
+
     pub async fn init(mut self) -> Self {
         let context = self.fetch_context_at_startup().await;
 
@@ -755,7 +841,7 @@ impl TaskServer {
     async fn fetch_context_at_startup(&self) -> Option<McpContext> {
         let current_dir = std::env::current_dir().ok()?;
         let canonical_path = current_dir.canonicalize().unwrap_or(current_dir);
-        let normalized_path = utils::path::normalize_macos_private_alias(&canonical_path);
+        let normalized_path = utils::path::normalize_path_aliases(&canonical_path);
 
         let url = self.url("/api/containers/attempt-context");
         let query = ContainerQuery {
@@ -1183,6 +1269,8 @@ impl TaskServer {
             project_id,
             status,
             limit,
+            labels,
+            label_match,
         }): Parameters<ListTasksRequest>,
     ) -> Result<CallToolResult, ErrorData> {
         let status_filter = if let Some(ref status_str) = status {
@@ -1199,9 +1287,22 @@ impl TaskServer {
             None
         };
 
-        let url = self.url(&format!("/api/tasks?project_id={}", project_id));
+        let url = self.url("/api/tasks");
+        let mut query = vec![("project_id", project_id.to_string())];
+        if let Some(labels) = labels.as_ref().filter(|labels| !labels.is_empty()) {
+            query.push(("labels", labels.join(",")));
+        }
+        if let Some(ref label_match) = label_match {
+            if label_match != "any" && label_match != "all" {
+                return Self::err(
+                    "Invalid label_match. Valid values: 'any', 'all'".to_string(),
+                    Some(label_match.to_string()),
+                );
+            }
+            query.push(("label_match", label_match.clone()));
+        }
         let all_tasks: Vec<TaskWithAttemptStatus> =
-            match self.send_json(self.client.get(&url)).await {
+            match self.send_json(self.client.get(&url).query(&query)).await {
                 Ok(t) => t,
                 Err(e) => return Ok(e),
             };
@@ -1227,6 +1328,8 @@ impl TaskServer {
             project_id: project_id.to_string(),
             applied_filters: ListTasksFilters {
                 status: status.clone(),
+                labels,
+                label_match,
                 limit: task_limit as i32,
             },
         };
@@ -1987,7 +2090,11 @@ impl TaskServer {
     #[tool(description = "Create a new project with one or more repositories")]
     async fn create_project(
         &self,
-        Parameters(CreateProjectMcpRequest { name, repositories }): Parameters<CreateProjectMcpRequest>,
+        Parameters(CreateProjectMcpRequest {
+            name,
+            repositories,
+            workflow_template_id,
+        }): Parameters<CreateProjectMcpRequest>,
     ) -> Result<CallToolResult, ErrorData> {
         let url = self.url("/api/projects");
         let repos_payload: Vec<serde_json::Value> = repositories
@@ -2002,7 +2109,8 @@ impl TaskServer {
 
         let payload = serde_json::json!({
             "name": name,
-            "repositories": repos_payload
+            "repositories": repos_payload,
+            "workflow_template_id": workflow_template_id
         });
 
         let project: serde_json::Value = match self
@@ -2130,12 +2238,137 @@ impl TaskServer {
             project_id: project_id.to_string(),
         })
     }
+
+    #[tool(description = "Create or replace the module memory of a path in a project. Module memories describe a module for agents working on files in it; there is one per path, so this overwrites the previous memory instead of adding another.")]
+    async fn upsert_module_memory(
+        &self,
+        Parameters(UpsertModuleMemoryRequest {
+            project_id,
+            path,
+            title,
+            content,
+            source_task_id,
+            source_commit_hash,
+        }): Parameters<UpsertModuleMemoryRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = self.url("/api/context-artifacts/module-memory");
+        let payload = serde_json::json!({
+            "project_id": project_id,
+            "path": path,
+            "title": title,
+            "content": content,
+            "source_task_id": source_task_id,
+            "source_commit_hash": source_commit_hash,
+        });
+
+        let artifact: serde_json::Value = match self
+            .send_json(self.client.post(&url).json(&payload))
+            .await
+        {
+            Ok(a) => a,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&CreateArtifactResponse {
+            artifact_id: artifact["id"].as_str().unwrap_or("").to_string(),
+            title: artifact["title"].as_str().unwrap_or("").to_string(),
+            artifact_type: artifact["artifact_type"].as_str().unwrap_or("").to_string(),
+            scope: artifact["scope"].as_str().unwrap_or("").to_string(),
+            token_estimate: artifact["token_estimate"].as_i64().unwrap_or(0) as i32,
+            chain_id: artifact["chain_id"].as_str().map(|s| s.to_string()),
+        })
+    }
+
+    #[tool(description = "Write a handoff note for the next stage of the workflow before finishing your work in a column: a summary of what you did, the files you touched and any open questions. The note is shown to the agents of later columns. `task_id` defaults to the current task.")]
+    async fn write_handoff_note(
+        &self,
+        Parameters(WriteHandoffNoteRequest {
+            task_id,
+            summary,
+            files_touched,
+            open_questions,
+        }): Parameters<WriteHandoffNoteRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let Some(task_id) = task_id.or(self.context.as_ref().map(|ctx| ctx.task_id)) else {
+            return Self::err(
+                "task_id is required outside of a workspace session".to_string(),
+                None::<String>,
+            );
+        };
+
+        let mut payload = serde_json::json!({
+            "summary": summary,
+            "files_touched": files_touched.unwrap_or_default(),
+            "open_questions": open_questions.unwrap_or_default(),
+        });
+        if let Some(ctx) = self.context.as_ref().filter(|ctx| ctx.task_id == task_id) {
+            payload["workspace_id"] = serde_json::Value::String(ctx.workspace_id.to_string());
+            if let Some(column) = &ctx.column {
+                payload["column_id"] = serde_json::Value::String(column.column_id.to_string());
+            }
+        }
+
+        let url = self.url(&format!("/api/tasks/{}/handoff-notes", task_id));
+        let note: serde_json::Value = match self
+            .send_json(self.client.post(&url).json(&payload))
+            .await
+        {
+            Ok(n) => n,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&WriteHandoffNoteResponse {
+            note_id: note["id"].as_str().unwrap_or("").to_string(),
+            task_id: task_id.to_string(),
+        })
+    }
+
+    #[tool(description = "Set a key in the current workspace's decision (.vibe/decision.json) without writing the file yourself. Keys already in the decision are kept. An 'answer' is checked against the column's answer options before anything is written. `task_id` defaults to the current task.")]
+    async fn set_decision(
+        &self,
+        Parameters(SetDecisionMcpRequest {
+            task_id,
+            key,
+            value,
+            feedback,
+        }): Parameters<SetDecisionMcpRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let Some(task_id) = task_id.or(self.context.as_ref().map(|ctx| ctx.task_id)) else {
+            return Self::err(
+                "task_id is required outside of a workspace session".to_string(),
+                None::<String>,
+            );
+        };
+
+        let mut payload = serde_json::json!({
+            "key": key,
+            "value": value,
+            "feedback": feedback,
+        });
+        if let Some(ctx) = self.context.as_ref().filter(|ctx| ctx.task_id == task_id) {
+            payload["workspace_id"] = serde_json::Value::String(ctx.workspace_id.to_string());
+        }
+
+        let url = self.url(&format!("/api/tasks/{}/decision", task_id));
+        let decision: serde_json::Value = match self
+            .send_json(self.client.post(&url).json(&payload))
+            .await
+        {
+            Ok(d) => d,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&SetDecisionMcpResponse {
+            task_id: task_id.to_string(),
+            decision,
+        })
+    }
 }
 
 #[tool_handler]
 impl ServerHandler for TaskServer {
     fn get_info(&self) -> ServerInfo {
-        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`.. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_task', 'update_task', 'delete_task', 'list_repos', 'create_task_group', 'add_task_to_group', 'add_group_dependency', 'list_boards', 'create_board', 'get_board', 'create_column', 'create_transition', 'list_agents', 'get_project', 'update_project', 'create_project', 'create_artifact', 'list_artifacts'. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string();
+        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`.. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_task', 'update_task', 'delete_task', 'list_repos', 'create_task_group', 'add_task_to_group', 'add_group_dependency', 'list_boards', 'create_board', 'get_board', 'create_column', 'create_transition', 'list_agents', 'get_project', 'update_project', 'create_project', 'create_artifact', 'list_artifacts', 'upsert_module_memory', 'write_handoff_note', 'set_decision'. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string();
 
         if let Some(ctx) = &self.context {
             let context_instruction = "Use 'get_context' to fetch project/task/workspace metadata for the active Vibe Kanban workspace session when available.";
@@ -2143,7 +2376,7 @@ impl ServerHandler for TaskServer {
             // Add workflow commit format instructions if we have column context
             let workflow_instruction = if ctx.column.is_some() {
                 format!(
-                    " WORKFLOW COMMIT FORMAT: When making commits for workflow tasks, use git trailers to track context. Format your commits as:\n\n<summary>\n\n<body with notes for next stage>\n\nTask-Id: {}\nColumn: <column-slug>\n\nThis allows workflow stages to be tracked and enables rollback by checking out prior commits.",
+                    " WORKFLOW COMMIT FORMAT: When making commits for workflow tasks, use git trailers to track context. Format your commits as:\n\n<summary>\n\n<body with notes for next stage>\n\nTask-Id: {}\nColumn: <column-slug>\n\nThis allows workflow stages to be tracked and enables rollback by checking out prior commits. Before finishing your work in this column, call 'write_handoff_note' with a summary, the files you touched and any open questions for the next stage.",
                     ctx.task_id
                 )
             } else {
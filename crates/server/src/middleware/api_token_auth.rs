@@ -0,0 +1,78 @@
+use axum::http::{Method, Uri};
+use chrono::Utc;
+use db::models::{api_token::ApiToken, task::Task, workspace::Workspace};
+use deployment::Deployment;
+use services::services::api_tokens;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Resolve a personal access token and check the request against its scope and
+/// project. Returns the token, to be added as a request extension.
+pub(crate) async fn authorize(
+    deployment: &DeploymentImpl,
+    method: &Method,
+    uri: &Uri,
+    token: &str,
+) -> Result<ApiToken, ApiError> {
+    let pool = &deployment.db().pool;
+    let api_token = ApiToken::find_by_hash(pool, &api_tokens::hash(token))
+        .await?
+        .filter(|api_token| api_token.is_active(Utc::now()))
+        .ok_or(ApiError::Unauthorized)?;
+
+    if api_tokens::is_auth_route(uri.path()) {
+        return Err(ApiError::Forbidden(
+            "Access tokens can't be used for sign-in or token management".to_string(),
+        ));
+    }
+    if !api_tokens::scope_allows(api_token.get_scope(), method.as_str()) {
+        return Err(ApiError::Forbidden("Token is read-only".to_string()));
+    }
+    if let Some(project_id) = api_token.project_id
+        && !targets_only_project(
+            deployment,
+            project_id,
+            api_tokens::request_targets(uri.path(), uri.query()),
+        )
+        .await?
+    {
+        tracing::warn!(
+            "Access token '{}' used outside its project: {}",
+            api_token.name,
+            uri.path()
+        );
+        return Err(ApiError::Forbidden(
+            "Token is not valid for this project".to_string(),
+        ));
+    }
+
+    ApiToken::touch(pool, api_token.id).await?;
+    Ok(api_token)
+}
+
+/// Whether the request names the project and nothing outside it
+pub(crate) async fn targets_only_project(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    targets: api_tokens::RequestTargets,
+) -> Result<bool, ApiError> {
+    if targets.is_empty() {
+        return Ok(false);
+    }
+    let pool = &deployment.db().pool;
+    let mut task_ids = targets.task_ids;
+    for workspace_id in targets.workspace_ids {
+        match Workspace::find_by_id(pool, workspace_id).await? {
+            Some(workspace) => task_ids.push(workspace.task_id),
+            None => return Ok(false),
+        }
+    }
+    for task_id in task_ids {
+        match Task::find_by_id(pool, task_id).await? {
+            Some(task) if task.project_id == project_id => {}
+            _ => return Ok(false),
+        }
+    }
+    Ok(targets.project_ids.iter().all(|id| *id == project_id))
+}
@@ -0,0 +1,97 @@
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, OriginalUri, Request, State},
+    http::Uri,
+    middleware::Next,
+    response::Response,
+};
+use deployment::Deployment;
+use services::services::{api_tokens, link_tokens, mcp_credentials::McpCredentials};
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{api_token_auth, rate_limit::bearer_token},
+};
+
+/// Checks scoped MCP tokens issued to executions. A valid token only reaches the routes
+/// the MCP server calls, within its own workspace and project; the credential is added
+/// as a request extension. Personal access tokens are checked against their scope and
+/// project the same way. Requests without either are refused from outside the loopback
+/// interface while any MCP token is out, or when `remote_api_requires_token` is set.
+/// Dashboard and shared board routes carry their own token in the path and are always
+/// let through.
+pub async fn mcp_auth_middleware(
+    State(deployment): State<DeploymentImpl>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let api_token = bearer_token(request.headers())
+        .filter(|token| api_tokens::is_api_token(token))
+        .map(str::to_string);
+    if let Some(token) = api_token {
+        let method = request.method().clone();
+        let uri = original_uri(&request);
+        let api_token = api_token_auth::authorize(&deployment, &method, &uri, &token).await?;
+        request.extensions_mut().insert(api_token);
+        return Ok(next.run(request).await);
+    }
+
+    let token = bearer_token(request.headers()).filter(|token| McpCredentials::is_mcp_token(token));
+
+    let Some(token) = token else {
+        let is_loopback = request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .is_none_or(|ConnectInfo(addr)| addr.ip().is_loopback());
+        if !is_public_token_request(&request)
+            && (!McpCredentials::shared().admits_tokenless(is_loopback)
+                || (!is_loopback && deployment.config().read().await.remote_api_requires_token))
+        {
+            return Err(ApiError::Unauthorized);
+        }
+        return Ok(next.run(request).await);
+    };
+
+    let credential = McpCredentials::shared()
+        .verify(token)
+        .ok_or(ApiError::Unauthorized)?;
+    let uri = original_uri(&request);
+    // Tasks and workspaces named by the request must belong to the token's project too
+    let targets = api_tokens::request_targets(uri.path(), uri.query());
+    let allowed = credential.allows_request(uri.path(), uri.query())
+        && (targets.is_empty()
+            || api_token_auth::targets_only_project(&deployment, credential.project_id, targets)
+                .await?);
+    if !allowed {
+        tracing::warn!(
+            "MCP token for execution {} used outside its workspace: {}",
+            credential.execution_process_id,
+            uri.path()
+        );
+        return Err(ApiError::Forbidden(
+            "Token is not valid for this project or workspace".to_string(),
+        ));
+    }
+
+    request.extensions_mut().insert(credential);
+    Ok(next.run(request).await)
+}
+
+/// The request URI before any `nest` stripped its prefix
+fn original_uri(request: &Request) -> Uri {
+    request
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|OriginalUri(uri)| uri.clone())
+        .unwrap_or_else(|| request.uri().clone())
+}
+
+fn is_public_token_request(request: &Request) -> bool {
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or(request.uri().path(), |OriginalUri(uri)| uri.path());
+    link_tokens::is_link_view_path(path)
+}
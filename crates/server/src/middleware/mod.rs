@@ -1,3 +1,6 @@
+pub mod api_token_auth;
+pub mod mcp_auth;
 pub mod model_loaders;
+pub mod rate_limit;
 
 pub use model_loaders::*;
@@ -0,0 +1,143 @@
+use std::{
+    net::SocketAddr,
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+
+use axum::{
+    extract::{ConnectInfo, OriginalUri, Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use deployment::Deployment;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use ts_rs::TS;
+use utils::{
+    rate_limit::{RateDecision, RateLimiter},
+    response::ApiResponse,
+};
+
+use crate::DeploymentImpl;
+
+static LIMITER: LazyLock<Mutex<RateLimiter>> = LazyLock::new(|| Mutex::new(RateLimiter::new()));
+
+static CHECKED: AtomicU64 = AtomicU64::new(0);
+static THROTTLED_BY_IP: AtomicU64 = AtomicU64::new(0);
+static THROTTLED_BY_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+/// Counters since server start for requests subject to rate limiting
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct RateLimitMetrics {
+    pub checked: u64,
+    pub throttled_by_ip: u64,
+    pub throttled_by_token: u64,
+}
+
+impl RateLimitMetrics {
+    pub fn snapshot() -> Self {
+        Self {
+            checked: CHECKED.load(Ordering::Relaxed),
+            throttled_by_ip: THROTTLED_BY_IP.load(Ordering::Relaxed),
+            throttled_by_token: THROTTLED_BY_TOKEN.load(Ordering::Relaxed),
+        }
+    }
+}
+
+pub(crate) fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+fn token_key(token: &str) -> String {
+    format!("token:{:x}", Sha256::digest(token.as_bytes()))
+}
+
+fn set_rate_limit_headers(headers: &mut HeaderMap, decision: &RateDecision) {
+    headers.insert("ratelimit-limit", HeaderValue::from(decision.limit));
+    headers.insert("ratelimit-remaining", HeaderValue::from(decision.remaining));
+    headers.insert(
+        "ratelimit-reset",
+        HeaderValue::from(decision.reset.as_secs().max(1)),
+    );
+}
+
+/// Per-IP and per-token request limits for the paths configured in `rate_limits`
+pub async fn rate_limit_middleware(
+    State(deployment): State<DeploymentImpl>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let config = deployment.config().read().await.rate_limits.clone();
+    if !config.applies_to(&path) {
+        return next.run(request).await;
+    }
+
+    let ip = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    if ip.is_some_and(|ip| !config.limits_ip(ip)) {
+        return next.run(request).await;
+    }
+    let ip = ip.map_or_else(|| "unknown".to_string(), |ip| ip.to_string());
+    // Buckets are keyed by a digest so raw tokens never sit in the limiter's memory
+    let token = bearer_token(request.headers()).map(token_key);
+
+    let (decision, throttled_by) = {
+        let mut limiter = LIMITER.lock().unwrap();
+        let now = Instant::now();
+        let by_ip = limiter.check(&format!("ip:{ip}"), config.per_ip(), now);
+        match token {
+            _ if !by_ip.allowed => (by_ip, Some(&THROTTLED_BY_IP)),
+            Some(token) => {
+                let by_token = limiter.check(&token, config.per_token(), now);
+                if !by_token.allowed {
+                    (by_token, Some(&THROTTLED_BY_TOKEN))
+                } else if by_token.remaining < by_ip.remaining {
+                    // Report whichever limit is closer to running out
+                    (by_token, None)
+                } else {
+                    (by_ip, None)
+                }
+            }
+            None => (by_ip, None),
+        }
+    };
+    CHECKED.fetch_add(1, Ordering::Relaxed);
+
+    if let Some(counter) = throttled_by {
+        counter.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!("Rate limited {} request to {}", ip, path);
+        let mut response = (
+            StatusCode::TOO_MANY_REQUESTS,
+            axum::Json(ApiResponse::<()>::error("Too many requests, slow down")),
+        )
+            .into_response();
+        let headers = response.headers_mut();
+        set_rate_limit_headers(headers, &decision);
+        headers.insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(decision.reset.as_secs().max(1)),
+        );
+        return response;
+    }
+
+    let mut response = next.run(request).await;
+    set_rate_limit_headers(response.headers_mut(), &decision);
+    response
+}
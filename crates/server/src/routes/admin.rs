@@ -0,0 +1,137 @@
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    background_job::{BackgroundJob, BackgroundJobStatus},
+    data_migration::DataMigration,
+    project::Project,
+    task::Task,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::{
+    data_migration::{self, DataMigrationError, DataMigrationKind},
+    events::task_patch,
+    initial_column,
+};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::tasks::task_to_status};
+
+const DEFAULT_JOB_LIMIT: i64 = 100;
+const MAX_JOB_LIMIT: i64 = 1000;
+
+#[derive(Debug, Deserialize)]
+pub struct JobsQuery {
+    pub status: Option<BackgroundJobStatus>,
+    pub limit: Option<i64>,
+}
+
+/// Background jobs, newest first; `status=dead` lists the dead letters
+pub async fn get_jobs(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<JobsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<BackgroundJob>>>, ApiError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_JOB_LIMIT)
+        .clamp(1, MAX_JOB_LIMIT);
+    let jobs = BackgroundJob::find_recent(&deployment.db().pool, query.status, limit).await?;
+    Ok(ResponseJson(ApiResponse::success(jobs)))
+}
+
+/// Move a project's column-less tasks into its board's initial column
+pub async fn assign_initial_column(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let moved = initial_column::backfill(pool, project_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::BadRequest("The project's board has no initial column".to_string())
+        })?;
+
+    for task in &moved {
+        deployment
+            .events()
+            .msg_store()
+            .push_patch(task_patch::replace(&task_to_status(task)));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "initial_column_backfilled",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+                "task_count": moved.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(moved)))
+}
+
+/// Run-once data migrations and how far each has got
+pub async fn get_data_migrations(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<DataMigration>>>, ApiError> {
+    let migrations = data_migration::list(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(migrations)))
+}
+
+/// Start (or retry after a failure) a data migration in the background; poll
+/// `GET /admin/data-migrations` for progress
+pub async fn run_data_migration(
+    State(deployment): State<DeploymentImpl>,
+    Path(name): Path<String>,
+) -> Result<ResponseJson<ApiResponse<DataMigration>>, ApiError> {
+    let kind = DataMigrationKind::from_name(&name)
+        .ok_or_else(|| ApiError::BadRequest(format!("Unknown data migration: {name}")))?;
+    let migration = data_migration::start(&deployment.db().pool, kind)
+        .await
+        .map_err(|e| match e {
+            DataMigrationError::AlreadyCompleted(_) | DataMigrationError::AlreadyRunning(_) => {
+                ApiError::Conflict(e.to_string())
+            }
+            DataMigrationError::Database(e) => ApiError::Database(e),
+        })?;
+
+    let background = deployment.clone();
+    tokio::spawn(async move {
+        data_migration::run(background.container(), kind).await;
+    });
+
+    deployment
+        .track_if_analytics_allowed(
+            "data_migration_started",
+            serde_json::json!({
+                "name": migration.name,
+                "attempt": migration.attempts,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(migration)))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/admin/jobs", get(get_jobs))
+        .route("/admin/data-migrations", get(get_data_migrations))
+        .route(
+            "/admin/data-migrations/{name}/run",
+            post(run_data_migration),
+        )
+        .route(
+            "/admin/projects/{project_id}/assign-initial-column",
+            post(assign_initial_column),
+        )
+}
@@ -1,11 +1,18 @@
+use std::collections::HashSet;
+
 use axum::{
     Extension, Json, Router,
-    extract::State,
+    extract::{Query, State},
     middleware::from_fn_with_state,
     response::Json as ResponseJson,
     routing::get,
 };
-use db::models::agent::{Agent, CreateAgent, UpdateAgent};
+use db::models::{
+    agent::{Agent, CreateAgent, UpdateAgent},
+    agent_mcp_settings::{AgentMcpSettings, UpdateAgentMcpSettings},
+    agent_prompt_settings::{AgentPromptSettings, UpdateAgentPromptSettings},
+    agent_stats::{AgentStats, StatsTimeRange},
+};
 use deployment::Deployment;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -102,9 +109,115 @@ pub async fn delete_agent(
     }
 }
 
+/// Performance stats for an agent (success rate, escalations, durations, tokens)
+pub async fn get_agent_stats(
+    Extension(agent): Extension<Agent>,
+    State(deployment): State<DeploymentImpl>,
+    Query(range): Query<StatsTimeRange>,
+) -> Result<ResponseJson<ApiResponse<AgentStats>>, ApiError> {
+    let stats =
+        AgentStats::for_agent(&deployment.db().read_pool, agent.id, &agent.name, &range).await?;
+    Ok(ResponseJson(ApiResponse::success(stats)))
+}
+
+pub async fn get_agent_mcp_settings(
+    Extension(agent): Extension<Agent>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AgentMcpSettings>>, ApiError> {
+    let settings = AgentMcpSettings::for_agent(&deployment.db().pool, agent.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Update the agent's MCP base URL and extra MCP servers
+pub async fn update_agent_mcp_settings(
+    Extension(agent): Extension<Agent>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateAgentMcpSettings>,
+) -> Result<ResponseJson<ApiResponse<AgentMcpSettings>>, ApiError> {
+    if let Some(url) = payload.mcp_base_url.as_deref().map(str::trim)
+        && !url.is_empty()
+        && !(url.starts_with("http://") || url.starts_with("https://"))
+    {
+        return Err(ApiError::BadRequest(
+            "mcp_base_url must be an http(s) URL".to_string(),
+        ));
+    }
+    if let Some(servers) = &payload.mcp_servers {
+        let mut names = HashSet::new();
+        for server in servers {
+            if server.name.trim().is_empty() || server.command.trim().is_empty() {
+                return Err(ApiError::BadRequest(
+                    "MCP servers need a name and a command".to_string(),
+                ));
+            }
+            if !names.insert(server.name.as_str()) {
+                return Err(ApiError::BadRequest(format!(
+                    "Duplicate MCP server name '{}'",
+                    server.name
+                )));
+            }
+        }
+    }
+
+    let settings = AgentMcpSettings::update(&deployment.db().pool, agent.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "agent_mcp_settings_updated",
+            serde_json::json!({
+                "agent_id": agent.id.to_string(),
+                "mcp_servers": settings.mcp_servers.len(),
+                "custom_base_url": settings.mcp_base_url.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+pub async fn get_agent_prompt_settings(
+    Extension(agent): Extension<Agent>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AgentPromptSettings>>, ApiError> {
+    let settings = AgentPromptSettings::for_agent(&deployment.db().pool, agent.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Update the order of the agent's prompt sections (columns can override it) and the
+/// language of the instruction text added to its prompts
+pub async fn update_agent_prompt_settings(
+    Extension(agent): Extension<Agent>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateAgentPromptSettings>,
+) -> Result<ResponseJson<ApiResponse<AgentPromptSettings>>, ApiError> {
+    let settings = AgentPromptSettings::update(&deployment.db().pool, agent.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "agent_prompt_settings_updated",
+            serde_json::json!({
+                "agent_id": agent.id.to_string(),
+                "prompt_sections": settings.prompt_sections,
+                "language": settings.language,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let agent_router = Router::new()
         .route("/", get(get_agent).put(update_agent).delete(delete_agent))
+        .route("/stats", get(get_agent_stats))
+        .route(
+            "/mcp-settings",
+            get(get_agent_mcp_settings).put(update_agent_mcp_settings),
+        )
+        .route(
+            "/prompt-settings",
+            get(get_agent_prompt_settings).put(update_agent_prompt_settings),
+        )
         .layer(from_fn_with_state(
             deployment.clone(),
             load_agent_middleware,
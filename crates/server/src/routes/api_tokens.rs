@@ -0,0 +1,89 @@
+//! Personal access tokens for CI jobs and remote MCP servers.
+//!
+//! Tokens are created here from the app and sent as `Authorization: Bearer vkpat_…`.
+//! The auth middleware checks their scope and project on every request; these routes
+//! themselves can't be reached with a token.
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use chrono::{Duration, Utc};
+use db::models::{
+    api_token::{ApiToken, CreateApiToken, CreatedApiToken},
+    project::Project,
+};
+use deployment::Deployment;
+use services::services::api_tokens;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::users};
+
+pub async fn get_api_tokens(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ApiToken>>>, ApiError> {
+    let tokens = ApiToken::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(tokens)))
+}
+
+/// Create a token. The token itself is only returned here, never again.
+pub async fn create_api_token(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateApiToken>,
+) -> Result<ResponseJson<ApiResponse<CreatedApiToken>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest("name is required".to_string()));
+    }
+    if payload.expires_in_days.is_some_and(|days| days <= 0) {
+        return Err(ApiError::BadRequest(
+            "expires_in_days must be positive".to_string(),
+        ));
+    }
+    if let Some(project_id) = payload.project_id {
+        Project::find_by_id(pool, project_id)
+            .await?
+            .ok_or_else(|| ApiError::BadRequest(format!("Project {project_id} not found")))?;
+    }
+
+    let user_id = users::current_user(&deployment).await?.map(|user| user.id);
+    let expires_at = payload
+        .expires_in_days
+        .map(|days| Utc::now() + Duration::days(i64::from(days)));
+    let (token, token_hash) = api_tokens::generate();
+    let api_token = ApiToken::create(pool, &payload, user_id, &token_hash, expires_at).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "api_token_created",
+            serde_json::json!({
+                "scope": api_token.scope,
+                "project_limited": api_token.project_id.is_some(),
+                "expires": api_token.expires_at.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(CreatedApiToken {
+        api_token,
+        token,
+    })))
+}
+
+/// Revoke a token; requests using it are refused from now on
+pub async fn revoke_api_token(
+    State(deployment): State<DeploymentImpl>,
+    Path(token_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<ApiToken>>, ApiError> {
+    let api_token = ApiToken::revoke(&deployment.db().pool, token_id).await?;
+    Ok(ResponseJson(ApiResponse::success(api_token)))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/auth/tokens", get(get_api_tokens).post(create_api_token))
+        .route("/auth/tokens/{token_id}", delete(revoke_api_token))
+}
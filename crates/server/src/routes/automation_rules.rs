@@ -6,6 +6,7 @@ use axum::{
     routing::get,
 };
 use db::models::{
+    automation_execution::AutomationExecution,
     automation_rule::{AutomationRule, AutomationRuleWithColumn, CreateAutomationRule, UpdateAutomationRule},
     project::Project,
 };
@@ -100,6 +101,15 @@ pub async fn delete_rule(
     }
 }
 
+/// Recent executions of a rule, for debugging automations
+pub async fn get_rule_executions(
+    Extension(rule): Extension<AutomationRule>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AutomationExecution>>>, ApiError> {
+    let executions = AutomationExecution::find_by_rule(&deployment.db().pool, rule.id, 100).await?;
+    Ok(ResponseJson(ApiResponse::success(executions)))
+}
+
 /// Toggle rule enabled state
 pub async fn toggle_rule(
     Extension(rule): Extension<AutomationRule>,
@@ -126,6 +136,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let rule_router = Router::new()
         .route("/", get(get_rule).put(update_rule).delete(delete_rule))
         .route("/toggle", axum::routing::post(toggle_rule))
+        .route("/executions", get(get_rule_executions))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_automation_rule_middleware,
@@ -1,14 +1,38 @@
+use std::str::FromStr;
+
 use axum::{
     Extension, Json, Router,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     middleware::from_fn_with_state,
     response::Json as ResponseJson,
     routing::get,
 };
+use chrono::{Duration, Utc};
+use db::models::agent::Agent;
+use db::models::agent_stats::{BoardStats, StatsTimeRange};
 use db::models::board::{Board, CreateBoard, UpdateBoard};
-use db::models::kanban_column::{CreateKanbanColumn, KanbanColumn, UpdateKanbanColumn};
+use db::models::board_layout_settings::{BoardLayoutSettings, UpdateBoardLayoutSettings};
+use db::models::board_notification_settings::{
+    BoardNotificationSettings, UpdateBoardNotificationSettings,
+};
+use db::models::board_snapshot::BoardColumnSnapshot;
+use db::models::kanban_column::{
+    CreateKanbanColumn, KanbanColumn, UpdateKanbanColumn, UpdateKanbanColumnSettings,
+};
+use db::models::project_timezone_settings::ProjectTimezoneSettings;
 use deployment::Deployment;
+use executors::{
+    executors::BaseCodingAgent,
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
 use serde::Deserialize;
+use services::services::{
+    board_snapshots::BoardSnapshotHistory,
+    board_validation::{self, BoardValidationReport},
+    cycle_times::{self, BoardCycleTimes},
+    escalation_slo::{self, BoardEscalationSlo},
+    workflow_lint::{self, KanbanColumnWithWarnings, WorkflowWarning},
+};
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
@@ -106,12 +130,12 @@ pub async fn list_board_columns(
     Ok(ResponseJson(ApiResponse::success(columns)))
 }
 
-/// Create a column for a board
+/// Create a column for a board, with warnings for transitions leaving it
 pub async fn create_board_column(
     Extension(board): Extension<Board>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateKanbanColumn>,
-) -> Result<ResponseJson<ApiResponse<KanbanColumn>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<KanbanColumnWithWarnings>>, ApiError> {
     let pool = &deployment.db().pool;
 
     // Enforce uniqueness: if this column wants is_initial or starts_workflow,
@@ -136,7 +160,11 @@ pub async fn create_board_column(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(column)))
+    let warnings = workflow_lint::lint_saved_column(&deployment.db().pool, &column).await?;
+    Ok(ResponseJson(ApiResponse::success(KanbanColumnWithWarnings {
+        column,
+        warnings,
+    })))
 }
 
 #[derive(Deserialize)]
@@ -145,13 +173,14 @@ pub struct ColumnPath {
     pub column_id: Uuid,
 }
 
-/// Update a board column
+/// Update a board column, with warnings for transitions that its question and
+/// answer options no longer satisfy
 pub async fn update_board_column(
     Extension(board): Extension<Board>,
     State(deployment): State<DeploymentImpl>,
     Path(path): Path<ColumnPath>,
     Json(payload): Json<UpdateKanbanColumn>,
-) -> Result<ResponseJson<ApiResponse<KanbanColumn>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<KanbanColumnWithWarnings>>, ApiError> {
     // Verify column belongs to this board
     let existing = KanbanColumn::find_by_id(&deployment.db().pool, path.column_id)
         .await?
@@ -174,7 +203,94 @@ pub async fn update_board_column(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(column)))
+    let warnings = workflow_lint::lint_saved_column(&deployment.db().pool, &column).await?;
+    Ok(ResponseJson(ApiResponse::success(KanbanColumnWithWarnings {
+        column,
+        warnings,
+    })))
+}
+
+/// Get a board column with its settings
+pub async fn get_board_column_settings(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+    Path(path): Path<ColumnPath>,
+) -> Result<ResponseJson<ApiResponse<KanbanColumn>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let existing = KanbanColumn::find_by_id(pool, path.column_id)
+        .await?
+        .ok_or(ApiError::BadRequest("Column not found".to_string()))?;
+
+    if existing.board_id != board.id {
+        return Err(ApiError::BadRequest("Column not found in this board".to_string()));
+    }
+
+    Ok(ResponseJson(ApiResponse::success(existing)))
+}
+
+/// Update a board column's settings (e.g. read-only analysis mode, security scanners,
+/// entry/exit scripts)
+pub async fn update_board_column_settings(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+    Path(path): Path<ColumnPath>,
+    Json(payload): Json<UpdateKanbanColumnSettings>,
+) -> Result<ResponseJson<ApiResponse<KanbanColumn>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let existing = KanbanColumn::find_by_id(pool, path.column_id)
+        .await?
+        .ok_or(ApiError::BadRequest("Column not found".to_string()))?;
+
+    if existing.board_id != board.id {
+        return Err(ApiError::BadRequest("Column not found in this board".to_string()));
+    }
+    if payload.response_target_mins.is_some_and(|mins| mins < 0) {
+        return Err(ApiError::BadRequest(
+            "response_target_mins can't be negative".to_string(),
+        ));
+    }
+    // A variant the column's agent can't run would silently fall back to the default
+    if let Some(variant) = payload.executor_variant.as_deref().map(str::trim)
+        && !variant.is_empty()
+        && let Some(agent_id) = existing.agent_id
+        && let Some(agent) = Agent::find_by_id(pool, agent_id).await?
+        && let Ok(executor) = BaseCodingAgent::from_str(&agent.executor)
+    {
+        let profile = ExecutorProfileId {
+            executor,
+            variant: Some(variant.to_string()),
+        };
+        if ExecutorConfigs::get_cached().get_coding_agent(&profile).is_none() {
+            return Err(ApiError::BadRequest(format!(
+                "Unknown executor profile '{}' for agent '{}'",
+                profile, agent.name
+            )));
+        }
+    }
+
+    let settings = KanbanColumn::update_settings(pool, existing.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "board_column_settings_updated",
+            serde_json::json!({
+                "board_id": board.id.to_string(),
+                "column_id": existing.id.to_string(),
+                "read_only": settings.read_only,
+                "plan_only": settings.plan_only,
+                "dry_run": settings.dry_run,
+                "security_scanners": settings.security_scanners.len(),
+                "on_enter_script": settings.on_enter_script.is_some(),
+                "on_exit_script": settings.on_exit_script.is_some(),
+                "warm_up": settings.warm_up,
+                "response_target": settings.response_target_mins.is_some(),
+                "prompt_sections": settings.prompt_sections,
+                "executor_variant": settings.executor_variant,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
 }
 
 /// Delete a board column
@@ -215,16 +331,27 @@ pub struct ReorderColumnsPayload {
     pub column_ids: Vec<Uuid>,
 }
 
-/// Reorder columns within a board
+/// Reorder columns within a board. The ids must list every column of the board; with
+/// strict column order the initial column stays first and terminal columns last.
 pub async fn reorder_board_columns(
     Extension(board): Extension<Board>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<ReorderColumnsPayload>,
 ) -> Result<ResponseJson<ApiResponse<Vec<KanbanColumn>>>, ApiError> {
-    KanbanColumn::reorder_board(&deployment.db().pool, board.id, &payload.column_ids).await?;
+    let pool = &deployment.db().pool;
+    let columns = KanbanColumn::find_by_board(pool, board.id).await?;
+    let layout = BoardLayoutSettings::for_board(pool, board.id).await?;
+    board_validation::validate_column_order(
+        &columns,
+        &payload.column_ids,
+        layout.strict_column_order,
+    )
+    .map_err(ApiError::BadRequest)?;
+
+    KanbanColumn::reorder_board(pool, board.id, &payload.column_ids).await?;
 
     // Fetch updated columns
-    let columns = KanbanColumn::find_by_board(&deployment.db().pool, board.id).await?;
+    let columns = KanbanColumn::find_by_board(pool, board.id).await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -323,6 +450,149 @@ pub async fn update_board_config(
     Ok(ResponseJson(ApiResponse::success(columns)))
 }
 
+/// Column ordering rules of the board
+pub async fn get_board_layout_settings(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<BoardLayoutSettings>>, ApiError> {
+    let settings = BoardLayoutSettings::for_board(&deployment.db().pool, board.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+pub async fn update_board_layout_settings(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateBoardLayoutSettings>,
+) -> Result<ResponseJson<ApiResponse<BoardLayoutSettings>>, ApiError> {
+    let settings =
+        BoardLayoutSettings::update(&deployment.db().pool, board.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "board_layout_settings_updated",
+            serde_json::json!({
+                "board_id": board.id.to_string(),
+                "strict_column_order": settings.strict_column_order,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Per-agent performance stats for a board, with board-wide totals
+pub async fn get_board_stats(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+    Query(range): Query<StatsTimeRange>,
+) -> Result<ResponseJson<ApiResponse<BoardStats>>, ApiError> {
+    let stats = BoardStats::for_board(&deployment.db().pool, board.id, &range).await?;
+    Ok(ResponseJson(ApiResponse::success(stats)))
+}
+
+/// Time tasks spend in each column, as percentiles per column and per assigned agent
+pub async fn get_board_cycle_times(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+    Query(range): Query<StatsTimeRange>,
+) -> Result<ResponseJson<ApiResponse<BoardCycleTimes>>, ApiError> {
+    let report = cycle_times::for_board(&deployment.db().read_pool, board.id, &range).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+/// Response times and target breaches of the board's escalation paths
+pub async fn get_board_escalation_slo(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+    Query(range): Query<StatsTimeRange>,
+) -> Result<ResponseJson<ApiResponse<BoardEscalationSlo>>, ApiError> {
+    let report = escalation_slo::for_board(&deployment.db().read_pool, board.id, &range).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BoardSnapshotsQuery {
+    /// How many days back to go; defaults to 30
+    pub days: Option<i64>,
+}
+
+/// Daily column counts of the board and the burn-up series derived from them
+pub async fn get_board_snapshots(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<BoardSnapshotsQuery>,
+) -> Result<ResponseJson<ApiResponse<BoardSnapshotHistory>>, ApiError> {
+    let days = query.days.unwrap_or(30).clamp(1, 365);
+    let pool = &deployment.db().read_pool;
+    // Snapshot days are dates in the board's timezone
+    let timezone = ProjectTimezoneSettings::for_board(pool, board.id).await?;
+    let today = ProjectTimezoneSettings::local_time(pool, &timezone, Utc::now())
+        .await?
+        .date();
+    let since = today - Duration::days(days - 1);
+    let snapshots = BoardColumnSnapshot::find_by_board(pool, board.id, since).await?;
+    Ok(ResponseJson(ApiResponse::success(BoardSnapshotHistory::new(
+        snapshots,
+    ))))
+}
+
+/// The board's notification digest settings (defaults when never configured)
+pub async fn get_board_notification_settings(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<BoardNotificationSettings>>, ApiError> {
+    let settings = BoardNotificationSettings::for_board(&deployment.db().pool, board.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Batch the board's non-urgent notifications into hourly or daily digests, or turn
+/// batching off. Failures, escalations and approval requests are always sent right away.
+pub async fn update_board_notification_settings(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateBoardNotificationSettings>,
+) -> Result<ResponseJson<ApiResponse<BoardNotificationSettings>>, ApiError> {
+    if payload.digest_hour.is_some_and(|hour| !(0..=23).contains(&hour)) {
+        return Err(ApiError::BadRequest(
+            "digest_hour must be between 0 and 23".to_string(),
+        ));
+    }
+    let settings =
+        BoardNotificationSettings::update(&deployment.db().pool, board.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "board_notification_settings_updated",
+            serde_json::json!({
+                "board_id": board.id.to_string(),
+                "digest_cadence": settings.digest_cadence,
+                "digest_hour": settings.digest_hour,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Conditional transitions on the board that can never match
+pub async fn get_board_workflow_warnings(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkflowWarning>>>, ApiError> {
+    let warnings = workflow_lint::lint_board(&deployment.db().pool, board.id).await?;
+    Ok(ResponseJson(ApiResponse::success(warnings)))
+}
+
+/// Dry-run the board's workflow: report unreachable columns, endless loops,
+/// terminal columns with transitions, missing else paths and unknown executors
+pub async fn validate_board(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<BoardValidationReport>>, ApiError> {
+    let report = board_validation::validate_board(&deployment.db().pool, board.id).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     // Routes for a specific board (requires board_id)
     let board_router = Router::new()
@@ -332,13 +602,35 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/columns",
             get(list_board_columns).post(create_board_column),
         )
-        .route("/columns/reorder", axum::routing::post(reorder_board_columns))
+        // POST is kept for older clients
+        .route(
+            "/columns/reorder",
+            axum::routing::patch(reorder_board_columns).post(reorder_board_columns),
+        )
+        .route(
+            "/layout-settings",
+            get(get_board_layout_settings).put(update_board_layout_settings),
+        )
         // Board-level column configuration
         .route("/config", axum::routing::put(update_board_config))
+        .route("/stats", get(get_board_stats))
+        .route("/cycle-times", get(get_board_cycle_times))
+        .route("/escalation-slo", get(get_board_escalation_slo))
+        .route("/snapshots", get(get_board_snapshots))
+        .route(
+            "/notification-settings",
+            get(get_board_notification_settings).put(update_board_notification_settings),
+        )
+        .route("/workflow-warnings", get(get_board_workflow_warnings))
+        .route("/validate", axum::routing::post(validate_board))
         .route(
             "/columns/{column_id}",
             axum::routing::put(update_board_column).delete(delete_board_column),
         )
+        .route(
+            "/columns/{column_id}/settings",
+            get(get_board_column_settings).put(update_board_column_settings),
+        )
         .route(
             "/save-as-template",
             axum::routing::post(super::workflow_templates::save_as_template),
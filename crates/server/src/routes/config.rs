@@ -10,6 +10,7 @@ use axum::{
 };
 use deployment::{Deployment, DeploymentError};
 use executors::{
+    actions::script::{self, ScriptInterpreter},
     executors::{
         AvailabilityInfo, BaseAgentCapability, BaseCodingAgent, StandardCodingAgentExecutor,
     },
@@ -41,6 +42,7 @@ pub fn router() -> Router<DeploymentImpl> {
             get(check_editor_availability),
         )
         .route("/agents/check-availability", get(check_agent_availability))
+        .route("/scripts/interpreters", get(get_script_interpreters))
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -485,3 +487,10 @@ async fn check_agent_availability(
 
     ResponseJson(ApiResponse::success(info))
 }
+
+/// Interpreters found for each setup/cleanup script language on this machine
+async fn get_script_interpreters(
+    State(_deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<Vec<ScriptInterpreter>>> {
+    ResponseJson(ApiResponse::success(script::detect_interpreters().await))
+}
@@ -7,6 +7,7 @@ use axum::{
 use db::models::workspace::{Workspace, WorkspaceContext};
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
+use services::services::process_reaper::{ProcessReaper, ProcessReaperReport};
 use utils::response::ApiResponse;
 
 use crate::{DeploymentImpl, error::ApiError};
@@ -21,8 +22,13 @@ pub async fn get_context(
     State(deployment): State<DeploymentImpl>,
     Query(payload): Query<ContainerQuery>,
 ) -> Result<ResponseJson<ApiResponse<WorkspaceContext>>, ApiError> {
-    let result =
-        Workspace::resolve_container_ref(&deployment.db().pool, &payload.container_ref).await;
+    // Canonical paths from Windows clients carry a verbatim prefix stored refs don't have
+    let container_ref = utils::path::normalize_path_aliases(&payload.container_ref);
+    let result = Workspace::resolve_container_ref(
+        &deployment.db().pool,
+        &container_ref.to_string_lossy(),
+    )
+    .await;
 
     match result {
         Ok(info) => {
@@ -39,6 +45,16 @@ pub async fn get_context(
     }
 }
 
+/// Processes that outlived their execution, and what the reaper has cleaned up
+pub async fn get_process_reaper_report()
+-> Result<ResponseJson<ApiResponse<ProcessReaperReport>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(
+        ProcessReaper::shared().report(),
+    )))
+}
+
 pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
-    Router::new().route("/containers/attempt-context", get(get_context))
+    Router::new()
+        .route("/containers/attempt-context", get(get_context))
+        .route("/containers/process-reaper", get(get_process_reaper_report))
 }
@@ -1,21 +1,31 @@
+use std::collections::HashMap;
+
 use axum::{
     Extension, Json, Router,
     extract::{Query, State},
     middleware::from_fn_with_state,
     response::Json as ResponseJson,
-    routing::get,
+    routing::{get, post},
 };
 use db::models::context_artifact::{
     ArtifactType, ContextArtifact, ContextPreviewStats, CreateContextArtifact, UpdateContextArtifact,
 };
+use db::models::context_artifact_review::{
+    ArtifactReviewStatus, ContextArtifactReview, ReviewContextArtifact,
+};
+use db::models::context_artifact_ttl::{ContextArtifactTtl, SetContextArtifactTtl};
 use db::models::project::Project;
+use db::models::task::Task;
 use deployment::Deployment;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use services::services::mcp_credentials::McpCredential;
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_context_artifact_middleware};
+use crate::{
+    DeploymentImpl, error::ApiError, middleware::load_context_artifact_middleware, routes::users,
+};
 
 #[derive(Deserialize, TS)]
 pub struct ContextArtifactQuery {
@@ -57,13 +67,16 @@ pub async fn get_context_artifact(
     Ok(ResponseJson(ApiResponse::success(artifact)))
 }
 
-/// Create a new context artifact
+/// Create a new context artifact. Knowledge written by agents starts as a draft
+/// and stays out of prompts until a person approves it.
 pub async fn create_context_artifact(
     State(deployment): State<DeploymentImpl>,
+    agent: Option<Extension<McpCredential>>,
     Json(payload): Json<CreateContextArtifact>,
 ) -> Result<ResponseJson<ApiResponse<ContextArtifact>>, ApiError> {
     let pool = &deployment.db().pool;
     let artifact_id = Uuid::new_v4();
+    let needs_review = agent.is_some() && payload.artifact_type.needs_review();
 
     // Lock the project when a brief or iplan is created — signals that new planning
     // work has entered the pipeline and the project needs re-stabilization before
@@ -75,6 +88,9 @@ pub async fn create_context_artifact(
     }
 
     let artifact = ContextArtifact::create(pool, payload, artifact_id).await?;
+    if needs_review {
+        ContextArtifactReview::mark_draft(pool, artifact.id).await?;
+    }
 
     deployment
         .track_if_analytics_allowed(
@@ -160,6 +176,7 @@ pub struct UpsertModuleMemoryRequest {
 /// Upsert module memory - creates or updates memory for a specific path
 pub async fn upsert_module_memory(
     State(deployment): State<DeploymentImpl>,
+    agent: Option<Extension<McpCredential>>,
     Json(payload): Json<UpsertModuleMemoryRequest>,
 ) -> Result<ResponseJson<ApiResponse<ContextArtifact>>, ApiError> {
     let artifact = ContextArtifact::upsert_module_memory(
@@ -172,6 +189,10 @@ pub async fn upsert_module_memory(
         payload.source_commit_hash.as_deref(),
     )
     .await?;
+    // A memory an agent rewrote goes back up for review
+    if agent.is_some() {
+        ContextArtifactReview::mark_draft(&deployment.db().pool, artifact.id).await?;
+    }
 
     deployment
         .track_if_analytics_allowed(
@@ -239,6 +260,9 @@ pub struct PreviewContextQuery {
     pub project_id: Uuid,
     #[serde(default)]
     pub task_id: Option<Uuid>,
+    /// Column whose budget override applies; defaults to the task's column
+    #[serde(default)]
+    pub column_id: Option<Uuid>,
 }
 
 /// Preview the assembled context that an agent would receive for a task.
@@ -247,10 +271,19 @@ pub async fn preview_context(
     State(deployment): State<DeploymentImpl>,
     Query(params): Query<PreviewContextQuery>,
 ) -> Result<ResponseJson<ApiResponse<ContextPreviewStats>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let column_id = match (params.column_id, params.task_id) {
+        (Some(column_id), _) => Some(column_id),
+        (None, Some(task_id)) => Task::find_by_id(pool, task_id)
+            .await?
+            .and_then(|task| task.column_id),
+        (None, None) => None,
+    };
     let stats = ContextArtifact::build_full_context_with_stats(
-        &deployment.db().pool,
+        pool,
         params.project_id,
         params.task_id,
+        column_id,
         &[],
     )
     .await?;
@@ -258,9 +291,127 @@ pub async fn preview_context(
     Ok(ResponseJson(ApiResponse::success(stats)))
 }
 
+#[derive(Deserialize, TS)]
+pub struct ArtifactReviewQuery {
+    pub project_id: Uuid,
+    /// Defaults to drafts waiting for review
+    #[serde(default)]
+    pub status: Option<ArtifactReviewStatus>,
+}
+
+/// A context artifact with its review state
+#[derive(Debug, Serialize, TS)]
+pub struct ReviewedContextArtifact {
+    pub artifact: ContextArtifact,
+    pub review: ContextArtifactReview,
+}
+
+/// Artifacts of a project in one review state (drafts by default), for people
+/// curating what agents wrote
+pub async fn get_artifact_reviews(
+    State(deployment): State<DeploymentImpl>,
+    Query(params): Query<ArtifactReviewQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ReviewedContextArtifact>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let status = params.status.unwrap_or(ArtifactReviewStatus::Draft);
+    let mut reviews: HashMap<Uuid, ContextArtifactReview> =
+        ContextArtifactReview::find_by_project(pool, params.project_id)
+            .await?
+            .into_iter()
+            .filter(|review| review.status == status)
+            .map(|review| (review.artifact_id, review))
+            .collect();
+    let artifacts = ContextArtifact::find_by_project(pool, params.project_id)
+        .await?
+        .into_iter()
+        .filter_map(|artifact| {
+            let review = reviews.remove(&artifact.id)?;
+            Some(ReviewedContextArtifact { artifact, review })
+        })
+        .collect();
+
+    Ok(ResponseJson(ApiResponse::success(artifacts)))
+}
+
+/// Approve or archive an artifact. Only people can review: agents' MCP tokens are
+/// refused, so an agent can't approve its own writing.
+pub async fn review_context_artifact(
+    Extension(artifact): Extension<ContextArtifact>,
+    State(deployment): State<DeploymentImpl>,
+    agent: Option<Extension<McpCredential>>,
+    Json(payload): Json<ReviewContextArtifact>,
+) -> Result<ResponseJson<ApiResponse<ContextArtifactReview>>, ApiError> {
+    if agent.is_some() {
+        return Err(ApiError::Forbidden(
+            "Agents can't review context artifacts".to_string(),
+        ));
+    }
+    let pool = &deployment.db().pool;
+    let current = ContextArtifactReview::for_artifact(pool, artifact.id).await?;
+    if !current.status.can_become(payload.status) {
+        return Err(ApiError::Conflict(format!(
+            "A {} artifact can't become {}",
+            current.status.as_str(),
+            payload.status.as_str()
+        )));
+    }
+
+    let mut payload = payload;
+    if payload.reviewer.is_none() {
+        payload.reviewer = users::current_user(&deployment)
+            .await?
+            .map(|user| user.label().to_string());
+    }
+    let review = ContextArtifactReview::review(pool, artifact.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "context_artifact_reviewed",
+            serde_json::json!({
+                "artifact_id": artifact.id.to_string(),
+                "artifact_type": artifact.artifact_type,
+                "status": review.status.as_str(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(review)))
+}
+
+#[derive(Deserialize, TS)]
+pub struct ArtifactTtlQuery {
+    pub project_id: Uuid,
+}
+
+/// How long approved artifacts of each type stay in a project's agent prompts
+pub async fn get_artifact_ttls(
+    State(deployment): State<DeploymentImpl>,
+    Query(params): Query<ArtifactTtlQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ContextArtifactTtl>>>, ApiError> {
+    let ttls = ContextArtifactTtl::find_by_project(&deployment.db().pool, params.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(ttls)))
+}
+
+pub async fn set_artifact_ttl(
+    State(deployment): State<DeploymentImpl>,
+    Query(params): Query<ArtifactTtlQuery>,
+    Json(payload): Json<SetContextArtifactTtl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ContextArtifactTtl>>>, ApiError> {
+    if payload.ttl_days.is_some_and(|days| days <= 0) {
+        return Err(ApiError::BadRequest("ttl_days must be positive".to_string()));
+    }
+    let pool = &deployment.db().pool;
+    Project::find_by_id(pool, params.project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let ttls = ContextArtifactTtl::set(pool, params.project_id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(ttls)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let artifact_router = Router::new()
         .route("/", get(get_context_artifact).put(update_context_artifact).delete(delete_context_artifact))
+        .route("/review", post(review_context_artifact))
         .layer(from_fn_with_state(deployment.clone(), load_context_artifact_middleware));
 
     let inner = Router::new()
@@ -269,6 +420,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/build-context", get(build_context))
         .route("/preview-context", get(preview_context))
         .route("/adrs", get(get_recent_adrs))
+        .route("/reviews", get(get_artifact_reviews))
+        .route("/ttls", get(get_artifact_ttls).put(set_artifact_ttl))
         .nest("/{artifact_id}", artifact_router);
 
     Router::new().nest("/context-artifacts", inner)
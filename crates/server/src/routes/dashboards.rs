@@ -0,0 +1,205 @@
+//! Read-only board dashboards for wall displays.
+//!
+//! Project members create dashboard tokens through the normal API. Anyone holding a
+//! token can open `/api/dashboards/{token}`, which renders the board snapshot and
+//! refreshes it from `/api/dashboards/{token}/stream` whenever the events stream
+//! reports a change. The token reaches nothing else.
+
+use axum::{
+    BoxError, Json, Router,
+    extract::{Path, State},
+    response::{Html, Json as ResponseJson, Sse, sse::Event},
+    routing::{delete, get},
+};
+use db::models::{
+    dashboard::{BoardSnapshot, CreateDashboardToken, CreatedDashboardToken, DashboardToken},
+    project::Project,
+};
+use deployment::Deployment;
+use futures_util::Stream;
+use services::services::link_tokens::{self, LinkTokenKind};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::link_views::{self, LinkPage},
+};
+
+pub async fn get_dashboard_tokens(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<DashboardToken>>>, ApiError> {
+    let tokens = DashboardToken::find_by_project(&deployment.db().pool, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(tokens)))
+}
+
+pub async fn create_dashboard_token(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateDashboardToken>,
+) -> Result<ResponseJson<ApiResponse<CreatedDashboardToken>>, ApiError> {
+    let pool = &deployment.db().pool;
+    Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err(ApiError::BadRequest("name is required".to_string()));
+    }
+
+    let (token, token_hash) = LinkTokenKind::Dashboard.generate();
+    let dashboard = DashboardToken::create(pool, project_id, name, &token_hash).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "dashboard_token_created",
+            serde_json::json!({ "project_id": project_id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(CreatedDashboardToken {
+        dashboard,
+        token,
+    })))
+}
+
+pub async fn delete_dashboard_token(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, token_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let deleted = DashboardToken::delete(&deployment.db().pool, project_id, token_id).await?;
+    if deleted == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Resolve a dashboard token to its project, recording that it was used
+async fn dashboard_project(deployment: &DeploymentImpl, token: &str) -> Result<Project, ApiError> {
+    let invalid = || ApiError::Forbidden("Invalid dashboard token".to_string());
+    if !LinkTokenKind::Dashboard.matches(token) {
+        return Err(invalid());
+    }
+    let pool = &deployment.db().pool;
+    let dashboard = DashboardToken::find_by_hash(pool, &link_tokens::hash(token))
+        .await?
+        .ok_or_else(invalid)?;
+    DashboardToken::touch(pool, dashboard.id).await?;
+    Project::find_by_id(pool, dashboard.project_id)
+        .await?
+        .ok_or_else(invalid)
+}
+
+pub async fn get_dashboard_snapshot(
+    State(deployment): State<DeploymentImpl>,
+    Path(token): Path<String>,
+) -> Result<ResponseJson<ApiResponse<BoardSnapshot>>, ApiError> {
+    let project = dashboard_project(&deployment, &token).await?;
+    let snapshot = BoardSnapshot::load(&deployment.db().read_pool, &project).await?;
+    Ok(ResponseJson(ApiResponse::success(snapshot)))
+}
+
+/// Emits a `snapshot` event after each change on the events stream, until the token
+/// is deleted
+pub async fn stream_dashboard(
+    State(deployment): State<DeploymentImpl>,
+    Path(token): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    dashboard_project(&deployment, &token).await?;
+
+    let refresh_deployment = deployment.clone();
+    let load = move || {
+        let deployment = refresh_deployment.clone();
+        let token = token.clone();
+        async move {
+            let project = dashboard_project(&deployment, &token).await?;
+            Ok::<_, ApiError>(BoardSnapshot::load(&deployment.db().read_pool, &project).await?)
+        }
+    };
+    Ok(link_views::refresh_stream(deployment, "snapshot", load).await)
+}
+
+pub async fn dashboard_page(
+    State(deployment): State<DeploymentImpl>,
+    Path(token): Path<String>,
+) -> Result<Html<String>, ApiError> {
+    dashboard_project(&deployment, &token).await?;
+    Ok(DASHBOARD_PAGE.render())
+}
+
+const DASHBOARD_PAGE: LinkPage = LinkPage {
+    event: "snapshot",
+    style: r#"      body { margin: 0; padding: 24px; font-family: system-ui, sans-serif; background: #111; color: #eee; }
+      h1 { margin: 0 0 4px; font-size: 28px; }
+      h2 { font-size: 18px; margin: 24px 0 8px; color: #aaa; }
+      #updated { color: #888; font-size: 13px; }
+      #columns { display: flex; gap: 12px; flex-wrap: wrap; }
+      .column { background: #1d1d1d; border-top: 4px solid #555; border-radius: 6px; padding: 12px 16px; min-width: 140px; }
+      .count { font-size: 40px; font-weight: 600; }
+      .meta { color: #aaa; font-size: 13px; }
+      ul { list-style: none; padding: 0; margin: 0; }
+      li { padding: 6px 0; border-bottom: 1px solid #262626; }
+      .escalated { color: #f87171; }
+      .awaiting_response { color: #fbbf24; }
+      .empty { color: #666; }
+"#,
+    body: r#"    <h1 id="project"></h1>
+    <div id="updated"></div>
+    <div id="columns"></div>
+    <h2>Running agents</h2>
+    <ul id="agents"></ul>
+    <h2>Needs attention</h2>
+    <ul id="escalations"></ul>
+"#,
+    script: r#"      function list(id, items, render, emptyText) {
+        const root = document.getElementById(id);
+        root.replaceChildren(...(items.length ? items.map(render) : [el('li', emptyText, 'empty')]));
+      }
+      function since(date) {
+        const minutes = Math.max(0, Math.round((Date.now() - new Date(date)) / 60000));
+        return minutes < 60 ? minutes + 'm' : Math.floor(minutes / 60) + 'h ' + (minutes % 60) + 'm';
+      }
+      function render(s) {
+        document.title = s.project_name;
+        document.getElementById('project').textContent = s.project_name;
+        document.getElementById('updated').textContent =
+          'Updated ' + new Date(s.generated_at).toLocaleTimeString();
+        document.getElementById('columns').replaceChildren(...s.columns.map((c) => {
+          const card = el('div', undefined, 'column');
+          if (c.color) card.style.borderTopColor = c.color;
+          card.append(el('div', c.name, 'meta'), el('div', String(c.task_count), 'count'));
+          const details = [];
+          if (c.in_progress) details.push(c.in_progress + ' running');
+          if (c.awaiting_response) details.push(c.awaiting_response + ' waiting');
+          card.append(el('div', details.join(' · '), 'meta'));
+          return card;
+        }));
+        list('agents', s.running_agents, (a) => el('li',
+          (a.agent_name || a.executor || 'Agent') + ' — ' + a.task_title +
+          (a.column_name ? ' (' + a.column_name + ')' : '') + ' · ' + since(a.started_at)
+        ), 'No agents running');
+        list('escalations', s.escalations, (e) => el('li',
+          e.title + (e.column_name ? ' (' + e.column_name + ')' : '') + ' · ' +
+          (e.reason === 'escalated' ? 'escalated' : 'awaiting response') + ' ' + since(e.since),
+          e.reason
+        ), 'Nothing needs attention');
+      }
+"#,
+};
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/projects/{project_id}/dashboard-tokens",
+            get(get_dashboard_tokens).post(create_dashboard_token),
+        )
+        .route(
+            "/projects/{project_id}/dashboard-tokens/{token_id}",
+            delete(delete_dashboard_token),
+        )
+        .route("/dashboards/{token}", get(dashboard_page))
+        .route("/dashboards/{token}/snapshot", get(get_dashboard_snapshot))
+        .route("/dashboards/{token}/stream", get(stream_dashboard))
+}
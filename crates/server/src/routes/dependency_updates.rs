@@ -0,0 +1,120 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    board::Board,
+    dependency_update_schedule::{DependencyUpdateSchedule, UpsertDependencyUpdateSchedule},
+    project::Project,
+    task::Task,
+};
+use deployment::Deployment;
+use services::services::dependency_updates;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+async fn ensure_project(deployment: &DeploymentImpl, project_id: Uuid) -> Result<(), ApiError> {
+    Project::find_by_id(&deployment.db().pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    Ok(())
+}
+
+pub async fn get_schedule(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Option<DependencyUpdateSchedule>>>, ApiError> {
+    ensure_project(&deployment, project_id).await?;
+    let schedule =
+        DependencyUpdateSchedule::find_by_project(&deployment.db().pool, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+pub async fn upsert_schedule(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<UpsertDependencyUpdateSchedule>,
+) -> Result<ResponseJson<ApiResponse<DependencyUpdateSchedule>>, ApiError> {
+    ensure_project(&deployment, project_id).await?;
+    let pool = &deployment.db().pool;
+
+    if payload.interval_hours.is_some_and(|h| h < 1) {
+        return Err(ApiError::BadRequest(
+            "interval_hours must be at least 1".to_string(),
+        ));
+    }
+    if payload.batch_size.is_some_and(|n| n < 1) {
+        return Err(ApiError::BadRequest(
+            "batch_size must be at least 1".to_string(),
+        ));
+    }
+    if payload.ecosystems.as_ref().is_some_and(|e| e.is_empty()) {
+        return Err(ApiError::BadRequest(
+            "At least one ecosystem is required".to_string(),
+        ));
+    }
+    if let Some(board_id) = payload.board_id
+        && Board::find_by_id(pool, board_id).await?.is_none()
+    {
+        return Err(ApiError::BadRequest("Board not found".to_string()));
+    }
+
+    let schedule = DependencyUpdateSchedule::upsert(pool, project_id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "dependency_update_schedule_saved",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+                "ecosystems": schedule.ecosystems,
+                "interval_hours": schedule.interval_hours,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+pub async fn delete_schedule(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ensure_project(&deployment, project_id).await?;
+    let deleted = DependencyUpdateSchedule::delete(&deployment.db().pool, project_id).await?;
+    if deleted == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Check for outdated dependencies now instead of waiting for the schedule
+pub async fn run_schedule_now(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    ensure_project(&deployment, project_id).await?;
+    let pool = &deployment.db().pool;
+    let schedule = DependencyUpdateSchedule::find_by_project(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let tasks = dependency_updates::run_schedule(pool, &schedule).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/projects/{project_id}/dependency-updates",
+            get(get_schedule)
+                .put(upsert_schedule)
+                .delete(delete_schedule),
+        )
+        .route(
+            "/projects/{project_id}/dependency-updates/run",
+            post(run_schedule_now),
+        )
+}
@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+
+use axum::{
+    Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    dry_run_patch::DryRunPatch,
+    repo::Repo,
+    task::Task,
+    task_event::{ActorType, CreateTaskEvent, TaskEvent},
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use services::services::container::ContainerService;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::users};
+
+/// Changes proposed by agents in dry-run columns, newest first
+pub async fn get_task_dry_run_patches(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<DryRunPatch>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let patches = DryRunPatch::find_by_task_id(pool, task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(patches)))
+}
+
+/// Apply a dry-run patch to its workspace and commit it. Fails without changing the
+/// workspace if the patch no longer applies, e.g. because the branch moved on.
+pub async fn apply_dry_run_patch(
+    State(deployment): State<DeploymentImpl>,
+    Path(patch_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<DryRunPatch>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let patch = DryRunPatch::find_by_id(pool, patch_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    if patch.applied_at.is_some() {
+        return Err(ApiError::Conflict("Patch has already been applied".to_string()));
+    }
+    if Task::has_active_attempt(pool, patch.task_id).await? {
+        return Err(ApiError::Conflict(
+            "An execution is running for this task; apply the patch once it finishes"
+                .to_string(),
+        ));
+    }
+
+    let workspace = Workspace::find_by_id(pool, patch.workspace_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let repo = Repo::find_by_id(pool, patch.repo_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let worktree_path = PathBuf::from(container_ref).join(&repo.name);
+    let user = users::current_user(&deployment).await?;
+    let applied_by = user
+        .as_ref()
+        .map(|user| user.label().to_string())
+        .unwrap_or_else(|| "user".to_string());
+    let message = format!(
+        "Apply dry-run patch from execution {}\n\nPatch-Source: dry_run ({})\nApplied-By: {}",
+        patch.execution_process_id, patch.id, applied_by
+    );
+    let commit_hash = deployment
+        .git()
+        .apply_patch(&worktree_path, &patch.patch, &message)
+        .map_err(|e| ApiError::Conflict(format!("Patch no longer applies: {e}")))?;
+
+    let applied = DryRunPatch::mark_applied(pool, patch.id)
+        .await?
+        .ok_or_else(|| ApiError::Conflict("Patch has already been applied".to_string()))?;
+
+    let patch_id = patch.id.to_string();
+    let event = CreateTaskEvent::patch_applied(
+        patch.task_id,
+        workspace.id,
+        &repo.name,
+        &commit_hash,
+        &message,
+        "dry_run",
+        Some(&patch_id),
+        ActorType::User,
+        user.map(|user| user.id.to_string()),
+    );
+    if let Err(e) = TaskEvent::create(pool, &event).await {
+        tracing::error!("Failed to record patch applied event for {}: {}", patch.id, e);
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "dry_run_patch_applied",
+            serde_json::json!({
+                "task_id": patch.task_id.to_string(),
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": repo.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(applied)))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/tasks/{task_id}/dry-run-patches",
+            get(get_task_dry_run_patches),
+        )
+        .route(
+            "/dry-run-patches/{patch_id}/apply",
+            post(apply_dry_run_patch),
+        )
+}
@@ -0,0 +1,148 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use db::models::email_notification_preference::{
+    CreateEmailNotificationPreference, EmailNotificationPreference,
+    UpdateEmailNotificationPreference,
+};
+use deployment::Deployment;
+use services::services::container::ContainerService;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+fn validate_email(email: &str) -> Result<(), ApiError> {
+    let email = email.trim();
+    match email.split_once('@') {
+        Some((local, domain)) if !local.is_empty() && domain.contains('.') => Ok(()),
+        _ => Err(ApiError::BadRequest(format!(
+            "'{email}' is not a valid email address"
+        ))),
+    }
+}
+
+fn validate_digest_hour(hour: Option<i32>) -> Result<(), ApiError> {
+    match hour {
+        Some(hour) if !(0..=23).contains(&hour) => Err(ApiError::BadRequest(
+            "digest_hour must be between 0 and 23".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Fail when another entry already uses the address
+async fn ensure_email_free(
+    deployment: &DeploymentImpl,
+    email: &str,
+    except_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let existing = EmailNotificationPreference::find_by_email(&deployment.db().pool, email).await?;
+    match existing {
+        Some(existing) if Some(existing.id) != except_id => Err(ApiError::Conflict(format!(
+            "{} already has notification preferences",
+            existing.email
+        ))),
+        _ => Ok(()),
+    }
+}
+
+pub async fn get_email_preferences(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<EmailNotificationPreference>>>, ApiError> {
+    let preferences = EmailNotificationPreference::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(preferences)))
+}
+
+pub async fn create_email_preference(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateEmailNotificationPreference>,
+) -> Result<ResponseJson<ApiResponse<EmailNotificationPreference>>, ApiError> {
+    validate_email(&payload.email)?;
+    validate_digest_hour(payload.digest_hour)?;
+    ensure_email_free(&deployment, &payload.email, None).await?;
+
+    let preference = EmailNotificationPreference::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "email_notifications_configured",
+            serde_json::json!({
+                "digest_enabled": preference.digest_enabled,
+                "immediate_escalations": preference.immediate_escalations,
+                "immediate_failures": preference.immediate_failures,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(preference)))
+}
+
+pub async fn update_email_preference(
+    State(deployment): State<DeploymentImpl>,
+    Path(preference_id): Path<Uuid>,
+    Json(payload): Json<UpdateEmailNotificationPreference>,
+) -> Result<ResponseJson<ApiResponse<EmailNotificationPreference>>, ApiError> {
+    if let Some(email) = &payload.email {
+        validate_email(email)?;
+        ensure_email_free(&deployment, email, Some(preference_id)).await?;
+    }
+    validate_digest_hour(payload.digest_hour)?;
+
+    let preference =
+        EmailNotificationPreference::update(&deployment.db().pool, preference_id, &payload)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(preference)))
+}
+
+pub async fn delete_email_preference(
+    State(deployment): State<DeploymentImpl>,
+    Path(preference_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let deleted = EmailNotificationPreference::delete(&deployment.db().pool, preference_id).await?;
+    if deleted == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Send a sample email to the address, ignoring its preferences
+pub async fn test_email_preference(
+    State(deployment): State<DeploymentImpl>,
+    Path(preference_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let preference = EmailNotificationPreference::find_by_id(&deployment.db().pool, preference_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    deployment
+        .container()
+        .notification_service()
+        .send_email(
+            &preference.email,
+            "Vibe Kanban test email",
+            "Email notifications are working.\n",
+        )
+        .await
+        .map_err(ApiError::BadRequest)?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/notification-preferences/email",
+            get(get_email_preferences).post(create_email_preference),
+        )
+        .route(
+            "/notification-preferences/email/{preference_id}",
+            put(update_email_preference).delete(delete_email_preference),
+        )
+        .route(
+            "/notification-preferences/email/{preference_id}/test",
+            post(test_email_preference),
+        )
+}
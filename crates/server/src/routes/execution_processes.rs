@@ -1,22 +1,44 @@
 use anyhow;
 use axum::{
-    Extension, Router,
+    Extension, Json, Router,
     extract::{
         Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
+    http::header,
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
     routing::{get, post},
 };
 use db::models::{
-    execution_process::{ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus},
+    execution_artifact::ExecutionArtifact,
+    execution_deliverable_report::ExecutionDeliverableReport,
+    execution_environment::ExecutionEnvironment,
+    execution_log_redaction::ExecutionLogRedaction,
+    execution_process::{
+        ExecutionProcess, ExecutionProcessError, ExecutionProcessRunReason, ExecutionProcessStatus,
+        InterruptedSession,
+    },
     execution_process_repo_state::ExecutionProcessRepoState,
+    execution_replay::ExecutionReplay,
+    security_finding::SecurityFinding,
+    task_log_retention::{LogRetention, TaskLogRetention},
 };
 use deployment::Deployment;
+use executors::{
+    actions::{ExecutorAction, ExecutorActionType},
+    executors::BaseCodingAgent,
+    profile::ExecutorProfileId,
+};
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::Deserialize;
-use services::services::container::ContainerService;
+use services::services::{
+    agent_concurrency::{AgentConcurrencySnapshot, ProjectFairness},
+    container::ContainerService,
+    deliverable_report,
+    log_export::{self, ExecutionTranscript, ExportFormat},
+};
+use ts_rs::TS;
 use utils::{log_msg::LogMsg, response::ApiResponse};
 use uuid::Uuid;
 
@@ -30,6 +52,18 @@ pub struct ExecutionProcessQuery {
     pub show_soft_deleted: Option<bool>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ReplayExecutionProcessRequest {
+    /// Executor to re-run the prompt with, e.g. Codex for a run made with Claude Code
+    pub executor_profile_id: ExecutorProfileId,
+}
+
 pub async fn get_execution_process_by_id(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(_deployment): State<DeploymentImpl>,
@@ -178,6 +212,83 @@ pub async fn stop_execution_process(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Coding agent runs interrupted by a server shutdown that can be resumed
+pub async fn get_interrupted_sessions(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<InterruptedSession>>>, ApiError> {
+    let sessions = ExecutionProcess::find_resumable_interrupted(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(sessions)))
+}
+
+pub async fn resume_execution_process(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let resumed = deployment
+        .container()
+        .resume_interrupted_execution(&execution_process)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "execution_process_resumed",
+            serde_json::json!({
+                "execution_process_id": execution_process.id.to_string(),
+                "resumed_execution_process_id": resumed.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(resumed)))
+}
+
+/// Re-run the exact prompt and agent context of a coding agent run with another
+/// executor, in a fresh workspace branch of the same task, and link the two runs so
+/// their output can be compared.
+pub async fn replay_execution_process(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReplayExecutionProcessRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    if !matches!(
+        execution_process.executor_action().map(ExecutorAction::typ),
+        Ok(ExecutorActionType::CodingAgentInitialRequest(_))
+    ) {
+        return Err(ApiError::BadRequest(
+            "Only the initial request of a coding agent run can be replayed".to_string(),
+        ));
+    }
+
+    let executor_profile_id = payload.executor_profile_id;
+    let replay = deployment
+        .container()
+        .replay_execution(&execution_process, executor_profile_id.clone())
+        .await?;
+    ExecutionReplay::create(&deployment.db().pool, execution_process.id, replay.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "execution_process_replayed",
+            serde_json::json!({
+                "execution_process_id": execution_process.id.to_string(),
+                "replay_execution_process_id": replay.id.to_string(),
+                "executor": executor_profile_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(replay)))
+}
+
+/// Replays of this run, or the run it replays
+pub async fn get_execution_process_replays(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutionReplay>>>, ApiError> {
+    let replays = ExecutionReplay::find_linked(&deployment.db().pool, execution_process.id).await?;
+    Ok(ResponseJson(ApiResponse::success(replays)))
+}
+
 pub async fn stream_execution_processes_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -243,11 +354,225 @@ pub async fn get_execution_process_repo_states(
     Ok(ResponseJson(ApiResponse::success(repo_states)))
 }
 
+/// What the execution ran with: executor, model, host, base commits and env vars
+pub async fn get_execution_process_environment(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionEnvironment>>, ApiError> {
+    let environment = ExecutionEnvironment::find_by_execution_process_id(
+        &deployment.db().pool,
+        execution_process.id,
+    )
+    .await?
+    .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(environment)))
+}
+
+/// How many secrets were masked in the execution's logs
+pub async fn get_execution_process_redactions(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionLogRedaction>>, ApiError> {
+    let redaction = ExecutionLogRedaction::for_execution_process(
+        &deployment.db().pool,
+        execution_process.id,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(redaction)))
+}
+
+/// Findings from the column's security scanning gate for this execution
+pub async fn get_execution_process_security_findings(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<SecurityFinding>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let findings =
+        SecurityFinding::find_by_execution_process_id(pool, execution_process.id).await?;
+    Ok(ResponseJson(ApiResponse::success(findings)))
+}
+
+/// Files the execution left in `.vibe/artifacts/`, collected when it finalized
+pub async fn get_execution_process_artifacts(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutionArtifact>>>, ApiError> {
+    let artifacts = ExecutionArtifact::find_by_execution_process_id(
+        &deployment.db().pool,
+        execution_process.id,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(artifacts)))
+}
+
+/// The execution's stored deliverable report
+pub async fn get_execution_process_deliverable_report(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionDeliverableReport>>, ApiError> {
+    let report = ExecutionDeliverableReport::find_by_execution_process_id(
+        &deployment.db().pool,
+        execution_process.id,
+    )
+    .await?
+    .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+/// Check the execution's transcript and workspace against its column's deliverable
+/// and store the result, replacing any earlier report
+pub async fn generate_execution_process_deliverable_report(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionDeliverableReport>>, ApiError> {
+    if execution_process.run_reason != ExecutionProcessRunReason::CodingAgent {
+        return Err(ApiError::BadRequest(
+            "Only coding agent runs have a column deliverable".to_string(),
+        ));
+    }
+    let history = deployment
+        .container()
+        .normalized_log_history(&execution_process.id)
+        .await
+        .unwrap_or_default();
+    let report =
+        deliverable_report::generate(&deployment.db().pool, &execution_process, &history)
+            .await?
+            .ok_or_else(|| {
+                ApiError::BadRequest("The column this execution ran in is unknown".to_string())
+            })?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "deliverable_report_generated",
+            serde_json::json!({
+                "execution_process_id": execution_process.id.to_string(),
+                "satisfied": report.satisfied_count,
+                "missed": report.missed_count,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+/// Transcript of the execution's normalized logs as a downloadable file; a running
+/// execution is exported as far as it has got
+pub async fn export_execution_process(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let history = deployment
+        .container()
+        .normalized_log_history(&execution_process.id)
+        .await
+        .unwrap_or_default();
+    let pool = &deployment.db().pool;
+    let task = ExecutionProcess::load_context(pool, execution_process.id)
+        .await
+        .ok()
+        .map(|ctx| (ctx.task.id, ctx.task.title));
+    let log_retention = match &task {
+        Some((task_id, _)) => TaskLogRetention::for_task(pool, *task_id).await?.retention(),
+        None => LogRetention::default(),
+    };
+    let transcript =
+        ExecutionTranscript::new(&execution_process, task, log_retention, &history);
+
+    let body = match query.format {
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(&transcript).map_err(std::io::Error::from)?
+        }
+        ExportFormat::Markdown => log_export::to_markdown(&transcript),
+        ExportFormat::Html => log_export::to_html(&transcript),
+    };
+    let disposition = format!(
+        "attachment; filename=\"execution-{}.{}\"",
+        execution_process.id,
+        query.format.extension()
+    );
+
+    deployment
+        .track_if_analytics_allowed(
+            "execution_process_exported",
+            serde_json::json!({
+                "execution_process_id": execution_process.id.to_string(),
+                "format": query.format.extension(),
+            }),
+        )
+        .await;
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                query.format.content_type().to_string(),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    ))
+}
+
+/// Running coding agents and those queued behind the concurrency limits
+pub async fn get_agent_queue(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AgentConcurrencySnapshot>>, ApiError> {
+    let snapshot = deployment.container().agent_limiter().snapshot().await;
+    Ok(ResponseJson(ApiResponse::success(snapshot)))
+}
+
+/// How the agent slots are being shared between projects
+pub async fn get_agent_queue_fairness(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectFairness>>>, ApiError> {
+    let projects = deployment.container().agent_limiter().fairness().await;
+    Ok(ResponseJson(ApiResponse::success(projects)))
+}
+
+/// Resume an executor paused by repeated failures without waiting for a probe
+pub async fn reset_executor_health(
+    State(deployment): State<DeploymentImpl>,
+    Path(executor): Path<BaseCodingAgent>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    deployment
+        .container()
+        .agent_limiter()
+        .reset_executor(executor)
+        .await;
+
+    deployment
+        .track_if_analytics_allowed(
+            "executor_health_reset",
+            serde_json::json!({ "executor": executor.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let workspace_id_router = Router::new()
         .route("/", get(get_execution_process_by_id))
         .route("/stop", post(stop_execution_process))
+        .route("/resume", post(resume_execution_process))
+        .route("/replay", post(replay_execution_process))
+        .route("/replays", get(get_execution_process_replays))
         .route("/repo-states", get(get_execution_process_repo_states))
+        .route("/environment", get(get_execution_process_environment))
+        .route("/redactions", get(get_execution_process_redactions))
+        .route(
+            "/security-findings",
+            get(get_execution_process_security_findings),
+        )
+        .route("/artifacts", get(get_execution_process_artifacts))
+        .route(
+            "/deliverable-report",
+            get(get_execution_process_deliverable_report)
+                .post(generate_execution_process_deliverable_report),
+        )
+        .route("/export", get(export_execution_process))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
         .layer(from_fn_with_state(
@@ -257,6 +582,13 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let workspaces_router = Router::new()
         .route("/stream/ws", get(stream_execution_processes_ws))
+        .route("/agent-queue", get(get_agent_queue))
+        .route("/agent-queue/fairness", get(get_agent_queue_fairness))
+        .route(
+            "/executor-health/{executor}/reset",
+            post(reset_executor_health),
+        )
+        .route("/interrupted", get(get_interrupted_sessions))
         .nest("/{id}", workspace_id_router);
 
     Router::new().nest("/execution-processes", workspaces_router)
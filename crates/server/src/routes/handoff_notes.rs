@@ -0,0 +1,74 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::{
+    handoff_note::{CreateHandoffNote, HandoffNote, HandoffNoteSource},
+    task::Task,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+async fn find_task(deployment: &DeploymentImpl, task_id: Uuid) -> Result<Task, ApiError> {
+    Task::find_by_id(&deployment.db().pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+/// Handoff notes of a task, oldest first
+pub async fn get_handoff_notes(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<HandoffNote>>>, ApiError> {
+    let task = find_task(&deployment, task_id).await?;
+    let notes = HandoffNote::find_by_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(notes)))
+}
+
+/// Write a handoff note for the next column (used by the `write_handoff_note` MCP tool)
+pub async fn create_handoff_note(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+    Json(mut payload): Json<CreateHandoffNote>,
+) -> Result<ResponseJson<ApiResponse<HandoffNote>>, ApiError> {
+    let task = find_task(&deployment, task_id).await?;
+    if payload.summary.trim().is_empty() {
+        return Err(ApiError::BadRequest("summary is required".to_string()));
+    }
+    payload.column_id = payload.column_id.or(task.column_id);
+    payload.files_touched.retain(|f| !f.trim().is_empty());
+    payload.open_questions.retain(|q| !q.trim().is_empty());
+
+    let note = HandoffNote::create(
+        &deployment.db().pool,
+        task.id,
+        HandoffNoteSource::Agent,
+        &payload,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "handoff_note_written",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "files_touched": note.files_touched.len(),
+                "open_questions": note.open_questions.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(note)))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/tasks/{task_id}/handoff-notes",
+        get(get_handoff_notes).post(create_handoff_note),
+    )
+}
@@ -1,6 +1,46 @@
-use axum::response::Json;
+use axum::{extract::State, http::StatusCode, response::Json};
+use deployment::Deployment;
+use services::services::readiness::{self, ReadinessReport};
 use utils::response::ApiResponse;
 
-pub async fn health_check() -> Json<ApiResponse<String>> {
+use crate::{DeploymentImpl, middleware::rate_limit::RateLimitMetrics};
+
+/// The process is up and serving requests
+#[utoipa::path(
+    get,
+    path = "/api/health/live",
+    tag = "health",
+    responses((status = 200, description = "Server is up", body = ApiResponse<String>))
+)]
+pub async fn liveness() -> Json<ApiResponse<String>> {
     Json(ApiResponse::success("OK".to_string()))
 }
+
+/// Check the database, migrations, executors and worktree directory; responds 503
+/// with the same report when any of them fails
+#[utoipa::path(
+    get,
+    path = "/api/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Every component is ready"),
+        (status = 503, description = "A component failed; the report says which"),
+    )
+)]
+pub async fn readiness(
+    State(deployment): State<DeploymentImpl>,
+) -> (StatusCode, Json<ApiResponse<ReadinessReport>>) {
+    let default_executor = deployment.config().read().await.executor_profile.executor;
+    let report = readiness::check(deployment.db(), default_executor).await;
+    let status = if report.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(ApiResponse::success(report)))
+}
+
+/// Requests checked and throttled by the API rate limiter since startup
+pub async fn rate_limit_metrics() -> Json<ApiResponse<RateLimitMetrics>> {
+    Json(ApiResponse::success(RateLimitMetrics::snapshot()))
+}
@@ -161,11 +161,19 @@ pub async fn serve_image(
         .as_deref()
         .unwrap_or("application/octet-stream");
 
-    let response = Response::builder()
+    let mut response = Response::builder()
         .status(StatusCode::OK)
         .header(header::CONTENT_TYPE, content_type)
         .header(header::CONTENT_LENGTH, metadata.len())
-        .header(header::CACHE_CONTROL, "public, max-age=31536000") // Cache for 1 year
+        .header(header::CACHE_CONTROL, "public, max-age=31536000"); // Cache for 1 year
+    // Execution artifacts can be any file (e.g. an HTML coverage report); only
+    // images are rendered inline
+    if !content_type.starts_with("image/") {
+        response = response
+            .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+            .header(header::CONTENT_DISPOSITION, "attachment");
+    }
+    let response = response
         .body(body)
         .map_err(|e| ApiError::Image(ImageError::ResponseBuildError(e.to_string())))?;
 
@@ -1,16 +1,24 @@
 use axum::{
     Extension, Json, Router,
-    extract::State,
+    extract::{Path, State},
     middleware::from_fn_with_state,
     response::Json as ResponseJson,
-    routing::{get, post},
+    routing::{delete, get, post},
 };
 use db::models::{
+    board_layout_settings::BoardLayoutSettings,
+    column_label_rule::{ColumnLabelRule, CreateColumnLabelRule},
+    context_budget::{ContextBudget, ContextBudgetOverride, ContextBudgetSettings},
     kanban_column::{CreateKanbanColumn, KanbanColumn, UpdateKanbanColumn},
     project::Project,
+    task_label::TaskLabel,
 };
 use deployment::Deployment;
 use serde::Deserialize;
+use services::services::{
+    board_validation,
+    workflow_lint::{self, KanbanColumnWithWarnings},
+};
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -42,7 +50,7 @@ pub async fn create_column(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateKanbanColumn>,
-) -> Result<ResponseJson<ApiResponse<KanbanColumn>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<KanbanColumnWithWarnings>>, ApiError> {
     let board_id = project.board_id.ok_or_else(|| {
         ApiError::BadRequest("Project has no board assigned".to_string())
     })?;
@@ -59,7 +67,11 @@ pub async fn create_column(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(column)))
+    let warnings = workflow_lint::lint_saved_column(&deployment.db().pool, &column).await?;
+    Ok(ResponseJson(ApiResponse::success(KanbanColumnWithWarnings {
+        column,
+        warnings,
+    })))
 }
 
 /// Get a single column
@@ -74,7 +86,7 @@ pub async fn update_column(
     Extension(column): Extension<KanbanColumn>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<UpdateKanbanColumn>,
-) -> Result<ResponseJson<ApiResponse<KanbanColumn>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<KanbanColumnWithWarnings>>, ApiError> {
     let updated = KanbanColumn::update(&deployment.db().pool, column.id, &payload).await?;
 
     deployment
@@ -87,7 +99,11 @@ pub async fn update_column(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(updated)))
+    let warnings = workflow_lint::lint_saved_column(&deployment.db().pool, &updated).await?;
+    Ok(ResponseJson(ApiResponse::success(KanbanColumnWithWarnings {
+        column: updated,
+        warnings,
+    })))
 }
 
 /// Delete a column
@@ -133,7 +149,7 @@ pub async fn delete_column(
     }
 }
 
-/// Reorder columns
+/// Reorder columns, with the same checks as reordering them on the board
 pub async fn reorder_columns(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -142,8 +158,18 @@ pub async fn reorder_columns(
     let board_id = project.board_id.ok_or_else(|| {
         ApiError::BadRequest("Project has no board assigned".to_string())
     })?;
-    KanbanColumn::reorder_board(&deployment.db().pool, board_id, &payload.column_ids).await?;
-    let columns = KanbanColumn::find_by_board(&deployment.db().pool, board_id).await?;
+    let pool = &deployment.db().pool;
+    let columns = KanbanColumn::find_by_board(pool, board_id).await?;
+    let layout = BoardLayoutSettings::for_board(pool, board_id).await?;
+    board_validation::validate_column_order(
+        &columns,
+        &payload.column_ids,
+        layout.strict_column_order,
+    )
+    .map_err(ApiError::BadRequest)?;
+
+    KanbanColumn::reorder_board(pool, board_id, &payload.column_ids).await?;
+    let columns = KanbanColumn::find_by_board(pool, board_id).await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -157,10 +183,108 @@ pub async fn reorder_columns(
     Ok(ResponseJson(ApiResponse::success(columns)))
 }
 
+/// Get a column's context budget override and the budget its tasks receive
+pub async fn get_column_context_budget(
+    Extension(project): Extension<Project>,
+    Extension(column): Extension<KanbanColumn>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ContextBudgetSettings>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let overrides = ContextBudgetOverride::for_column(pool, column.id).await?;
+    let effective = ContextBudget::resolve(pool, project.id, Some(column.id)).await?;
+    Ok(ResponseJson(ApiResponse::success(ContextBudgetSettings {
+        overrides,
+        effective,
+    })))
+}
+
+/// Replace a column's context budget override (unset fields use the project's)
+pub async fn update_column_context_budget(
+    Extension(project): Extension<Project>,
+    Extension(column): Extension<KanbanColumn>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ContextBudgetOverride>,
+) -> Result<ResponseJson<ApiResponse<ContextBudgetSettings>>, ApiError> {
+    payload.validate().map_err(ApiError::BadRequest)?;
+    let pool = &deployment.db().pool;
+    let overrides = ContextBudgetOverride::set_for_column(pool, column.id, &payload).await?;
+    let effective = ContextBudget::resolve(pool, project.id, Some(column.id)).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "column_context_budget_updated",
+            serde_json::json!({
+                "column_id": column.id.to_string(),
+                "token_budget": effective.token_budget,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(ContextBudgetSettings {
+        overrides,
+        effective,
+    })))
+}
+
+/// Label rules applied when tasks enter or leave the column
+pub async fn get_column_label_rules(
+    Extension(column): Extension<KanbanColumn>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ColumnLabelRule>>>, ApiError> {
+    let rules = ColumnLabelRule::find_by_column(&deployment.db().pool, column.id).await?;
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+/// Add a label rule; one for the same label and trigger is replaced
+pub async fn create_column_label_rule(
+    Extension(project): Extension<Project>,
+    Extension(column): Extension<KanbanColumn>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateColumnLabelRule>,
+) -> Result<ResponseJson<ApiResponse<ColumnLabelRule>>, ApiError> {
+    let pool = &deployment.db().pool;
+    TaskLabel::find_by_id(pool, payload.label_id)
+        .await?
+        .filter(|label| label.project_id == project.id)
+        .ok_or_else(|| ApiError::BadRequest("Label not found in this project".to_string()))?;
+    let rule = ColumnLabelRule::create(pool, column.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "column_label_rule_created",
+            serde_json::json!({
+                "column_id": column.id.to_string(),
+                "trigger": rule.trigger,
+                "action": rule.action,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn delete_column_label_rule(
+    State(deployment): State<DeploymentImpl>,
+    Path(rule_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if !ColumnLabelRule::delete(&deployment.db().pool, rule_id).await? {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     // Routes for a specific column (requires column_id)
     let column_router = Router::new()
         .route("/", get(get_column).put(update_column).delete(delete_column))
+        .route(
+            "/context-budget",
+            get(get_column_context_budget).put(update_column_context_budget),
+        )
+        .route(
+            "/label-rules",
+            get(get_column_label_rules).post(create_column_label_rule),
+        )
         .layer(from_fn_with_state(
             deployment.clone(),
             load_kanban_column_middleware,
@@ -176,5 +300,10 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             load_project_middleware,
         ));
 
-    Router::new().nest("/projects/{project_id}/columns", project_columns_router)
+    Router::new()
+        .nest("/projects/{project_id}/columns", project_columns_router)
+        .route(
+            "/column-label-rules/{rule_id}",
+            delete(delete_column_label_rule),
+        )
 }
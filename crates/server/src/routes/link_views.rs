@@ -0,0 +1,111 @@
+//! Shared plumbing for the read-only link views (dashboards and shared boards): the
+//! live refresh stream and the standalone page that renders it.
+
+use std::future::Future;
+
+use axum::{
+    BoxError,
+    response::{
+        Html, Sse,
+        sse::{Event, KeepAlive},
+    },
+};
+use deployment::Deployment;
+use futures_util::{Stream, StreamExt, future};
+use serde::Serialize;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Bursts of events arriving together only trigger one refresh
+const MAX_EVENTS_PER_REFRESH: usize = 256;
+
+/// Emits `event` with a freshly loaded view after each change on the events stream.
+/// `load` resolves the link's token every time, so once it is refused (the link was
+/// deleted) the stream ends.
+pub async fn refresh_stream<T, F, Fut>(
+    deployment: DeploymentImpl,
+    event: &'static str,
+    load: F,
+) -> Sse<impl Stream<Item = Result<Event, BoxError>>>
+where
+    T: Serialize,
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, ApiError>> + Send + 'static,
+{
+    let stream = deployment
+        .stream_events()
+        .await
+        .ready_chunks(MAX_EVENTS_PER_REFRESH)
+        .then(move |_| {
+            let refresh = load();
+            async move {
+                match refresh.await {
+                    Ok(view) => Some(Some(
+                        Event::default()
+                            .event(event)
+                            .json_data(&view)
+                            .map_err(BoxError::from),
+                    )),
+                    Err(ApiError::Forbidden(_)) => None,
+                    Err(e) => {
+                        tracing::error!("Failed to refresh {} view: {}", event, e);
+                        Some(None)
+                    }
+                }
+            }
+        })
+        .take_while(|refresh| future::ready(refresh.is_some()))
+        .filter_map(|refresh| future::ready(refresh.flatten()));
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// A standalone page for a link view. `script` defines `render(data)`, called with
+/// each `event` from the page's `/stream`; an `el(tag, text, className)` helper is
+/// available to it.
+pub struct LinkPage {
+    pub event: &'static str,
+    pub style: &'static str,
+    pub body: &'static str,
+    pub script: &'static str,
+}
+
+impl LinkPage {
+    pub fn render(&self) -> Html<String> {
+        Html(format!(
+            r#"{PAGE_HEAD}{style}    </style>
+  </head>
+  <body>
+{body}    <script>
+{EL_HELPER}{script}      const source = new EventSource(location.pathname.replace(/\/$/, '') + '/stream');
+      source.addEventListener('{event}', (event) => render(JSON.parse(event.data)));
+    </script>
+  </body>
+</html>
+"#,
+            style = self.style,
+            body = self.body,
+            script = self.script,
+            event = self.event,
+        ))
+    }
+}
+
+const PAGE_HEAD: &str = r#"<!doctype html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <meta name="referrer" content="no-referrer" />
+    <meta name="robots" content="noindex" />
+    <title>Board</title>
+    <style>
+"#;
+
+const EL_HELPER: &str = r#"      function el(tag, text, className) {
+        const node = document.createElement(tag);
+        if (text !== undefined) node.textContent = text;
+        if (className) node.className = className;
+        return node;
+      }
+"#;
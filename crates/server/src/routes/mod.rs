@@ -1,10 +1,19 @@
+use std::net::SocketAddr;
+
 use axum::{
     Router,
-    routing::{IntoMakeService, get},
+    extract::connect_info::IntoMakeServiceWithConnectInfo,
+    middleware::from_fn_with_state,
+    routing::get,
 };
 
-use crate::DeploymentImpl;
+use crate::{
+    DeploymentImpl,
+    middleware::{mcp_auth::mcp_auth_middleware, rate_limit::rate_limit_middleware},
+};
 
+pub mod admin;
+pub mod api_tokens;
 pub mod agents;
 pub mod approvals;
 pub mod automation_rules;
@@ -12,59 +21,110 @@ pub mod boards;
 pub mod config;
 pub mod containers;
 pub mod context_artifacts;
+pub mod dashboards;
 pub mod debug_events;
+pub mod dependency_updates;
+pub mod dry_run_patches;
+pub mod email_notifications;
 pub mod evaluate_runs;
 pub mod filesystem;
 // pub mod github;
 pub mod events;
 pub mod execution_processes;
 pub mod frontend;
+pub mod handoff_notes;
 pub mod health;
 pub mod images;
 pub mod kanban_columns;
+pub mod link_views;
+pub mod notification_channels;
 pub mod oauth;
+pub mod openapi;
 pub mod organizations;
+pub mod plan_reviews;
+pub mod preview;
+pub mod project_env_vars;
 pub mod projects;
 pub mod repo;
+pub mod reviewer_feedback;
 pub mod scratch;
 pub mod sessions;
+pub mod shared_boards;
 pub mod shared_tasks;
 pub mod skills;
 pub mod state_transitions;
+pub mod subscriptions;
+pub mod subtasks;
 pub mod tags;
+pub mod task_archive;
 pub mod task_attempts;
+pub mod task_checklists;
+pub mod task_comments;
 pub mod task_dependencies;
 pub mod task_events;
+pub mod task_executions;
 pub mod task_groups;
+pub mod task_handback;
+pub mod task_imports;
 pub mod group_events;
 pub mod task_labels;
 pub mod task_triggers;
 pub mod tasks;
+pub mod user_notifications;
+pub mod users;
 pub mod workflow_templates;
+pub mod workspace_conflicts;
+pub mod workspace_templates;
 
-pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
+pub fn router(deployment: DeploymentImpl) -> IntoMakeServiceWithConnectInfo<Router, SocketAddr> {
     // Create routers with different middleware layers
     let base_routes = Router::new()
-        .route("/health", get(health::health_check))
+        .route("/health/live", get(health::liveness))
+        .route("/health/ready", get(health::readiness))
+        .route("/rate-limits/metrics", get(health::rate_limit_metrics))
         .merge(config::router())
         .merge(containers::router(&deployment))
         .merge(projects::router(&deployment))
+        .merge(project_env_vars::router(&deployment))
+        .merge(workspace_templates::router(&deployment))
+        .merge(dependency_updates::router(&deployment))
+        .merge(workspace_conflicts::router(&deployment))
+        .merge(notification_channels::router(&deployment))
+        .merge(email_notifications::router(&deployment))
         .merge(tasks::router(&deployment))
         .merge(task_events::router(&deployment))
         .merge(task_labels::router(&deployment))
         .merge(task_triggers::router(&deployment))
         .merge(task_dependencies::router(&deployment))
         .merge(task_groups::router(&deployment))
+        .merge(task_imports::router(&deployment))
+        .merge(subtasks::router(&deployment))
         .merge(group_events::router(&deployment))
         .merge(shared_tasks::router())
         .merge(task_attempts::router(&deployment))
+        .merge(task_checklists::router(&deployment))
+        .merge(task_comments::router(&deployment))
+        .merge(task_handback::router(&deployment))
+        .merge(task_archive::router(&deployment))
+        .merge(task_executions::router(&deployment))
+        .merge(dry_run_patches::router(&deployment))
+        .merge(handoff_notes::router(&deployment))
+        .merge(reviewer_feedback::router(&deployment))
+        .merge(plan_reviews::router(&deployment))
         .merge(execution_processes::router(&deployment))
         .merge(tags::router(&deployment))
         .merge(oauth::router())
+        .merge(api_tokens::router(&deployment))
         .merge(organizations::router())
+        .merge(user_notifications::router())
+        .merge(users::router(&deployment))
+        .merge(subscriptions::router(&deployment))
+        .merge(preview::router())
         .merge(filesystem::router())
         .merge(repo::router())
         .merge(events::router(&deployment))
+        .merge(dashboards::router(&deployment))
+        .merge(shared_boards::router(&deployment))
         .merge(approvals::router())
         .merge(scratch::router(&deployment))
         .merge(sessions::router(&deployment))
@@ -78,12 +138,16 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(context_artifacts::router(&deployment))
         .merge(skills::router(&deployment))
         .merge(workflow_templates::router(&deployment))
+        .merge(admin::router(&deployment))
+        .merge(openapi::router())
         .nest("/images", images::routes())
+        .layer(from_fn_with_state(deployment.clone(), rate_limit_middleware))
+        .layer(from_fn_with_state(deployment.clone(), mcp_auth_middleware))
         .with_state(deployment);
 
     Router::new()
         .route("/", get(frontend::serve_frontend_root))
         .route("/{*path}", get(frontend::serve_frontend))
         .nest("/api", base_routes)
-        .into_make_service()
+        .into_make_service_with_connect_info::<SocketAddr>()
 }
@@ -0,0 +1,374 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get, post, put},
+};
+use db::models::{
+    execution_process::ExecutionProcessRunReason,
+    notification_channel::{
+        CreateNotificationChannel, NotificationChannel, NotificationChannelKind, NotificationEvent,
+        UpdateNotificationChannel,
+    },
+    notification_channel_identity::{CreateNotificationChannelIdentity, NotificationChannelIdentity},
+    project::Project,
+    user::User,
+};
+use deployment::Deployment;
+use services::services::notification_channels::{self, ChannelNotification};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Webhook URLs carry their secret in the path or query (Slack, Discord), so clients
+/// only ever see the scheme and host
+fn mask_webhook_url(webhook_url: &str) -> String {
+    match url::Url::parse(webhook_url) {
+        Ok(url) => match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}://{host}:{port}/…", url.scheme()),
+            (Some(host), None) => format!("{}://{host}/…", url.scheme()),
+            (None, _) => String::new(),
+        },
+        Err(_) => String::new(),
+    }
+}
+
+fn masked(mut channel: NotificationChannel) -> NotificationChannel {
+    channel.webhook_url = mask_webhook_url(&channel.webhook_url);
+    channel
+}
+
+/// Organization-wide channels are only visible to the organization's members
+async fn ensure_organization_member(
+    deployment: &DeploymentImpl,
+    org_id: Uuid,
+) -> Result<(), ApiError> {
+    let organizations = deployment
+        .remote_client()?
+        .list_organizations()
+        .await?
+        .organizations;
+    if organizations.iter().any(|org| org.id == org_id) {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden("Not a member of this organization".to_string()))
+    }
+}
+
+/// Load a channel, checking organization membership for organization-wide channels
+async fn find_channel(
+    deployment: &DeploymentImpl,
+    channel_id: Uuid,
+) -> Result<NotificationChannel, ApiError> {
+    let channel = NotificationChannel::find_by_id(&deployment.db().pool, channel_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    if let Some(org_id) = channel.organization_id {
+        ensure_organization_member(deployment, org_id).await?;
+    }
+    Ok(channel)
+}
+
+fn validate_webhook_url(url: &str) -> Result<(), ApiError> {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        Ok(())
+    } else {
+        Err(ApiError::BadRequest(
+            "webhook_url must be an http(s) URL".to_string(),
+        ))
+    }
+}
+
+/// Matrix and Telegram also need a destination and a token; Telegram may leave the
+/// URL empty to use the public Bot API, and so may Slack channels that post with a bot
+/// token to a channel id
+fn validate_target(
+    kind: NotificationChannelKind,
+    webhook_url: &str,
+    destination: Option<&str>,
+    access_token: Option<&str>,
+) -> Result<(), ApiError> {
+    let missing = |value: Option<&str>| value.is_none_or(|v| v.trim().is_empty());
+    match kind {
+        NotificationChannelKind::Matrix => {
+            if missing(destination) || missing(access_token) {
+                return Err(ApiError::BadRequest(
+                    "Matrix channels need a room id (destination) and an access_token".to_string(),
+                ));
+            }
+            validate_webhook_url(webhook_url)
+        }
+        NotificationChannelKind::Telegram => {
+            if missing(destination) || missing(access_token) {
+                return Err(ApiError::BadRequest(
+                    "Telegram channels need a chat id (destination) and a bot token (access_token)"
+                        .to_string(),
+                ));
+            }
+            if webhook_url.trim().is_empty() {
+                Ok(())
+            } else {
+                validate_webhook_url(webhook_url)
+            }
+        }
+        NotificationChannelKind::Slack if !missing(destination) && !missing(access_token) => {
+            if webhook_url.trim().is_empty() {
+                Ok(())
+            } else {
+                validate_webhook_url(webhook_url)
+            }
+        }
+        NotificationChannelKind::Slack
+        | NotificationChannelKind::Discord
+        | NotificationChannelKind::Http => validate_webhook_url(webhook_url),
+    }
+}
+
+fn validate_create(payload: &CreateNotificationChannel) -> Result<(), ApiError> {
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest("name is required".to_string()));
+    }
+    validate_target(
+        payload.kind,
+        &payload.webhook_url,
+        payload.destination.as_deref(),
+        payload.access_token.as_deref(),
+    )
+}
+
+pub async fn get_project_channels(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<NotificationChannel>>>, ApiError> {
+    let channels = NotificationChannel::find_by_project(&deployment.db().pool, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(channels.into_iter().map(masked).collect())))
+}
+
+pub async fn create_project_channel(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateNotificationChannel>,
+) -> Result<ResponseJson<ApiResponse<NotificationChannel>>, ApiError> {
+    let pool = &deployment.db().pool;
+    Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    validate_create(&payload)?;
+
+    let channel = NotificationChannel::create(pool, Some(project_id), None, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "notification_channel_created",
+            serde_json::json!({
+                "scope": "project",
+                "kind": channel.kind,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(masked(channel))))
+}
+
+pub async fn get_organization_channels(
+    State(deployment): State<DeploymentImpl>,
+    Path(org_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<NotificationChannel>>>, ApiError> {
+    ensure_organization_member(&deployment, org_id).await?;
+    let channels = NotificationChannel::find_by_organization(&deployment.db().pool, org_id).await?;
+    Ok(ResponseJson(ApiResponse::success(channels.into_iter().map(masked).collect())))
+}
+
+pub async fn create_organization_channel(
+    State(deployment): State<DeploymentImpl>,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<CreateNotificationChannel>,
+) -> Result<ResponseJson<ApiResponse<NotificationChannel>>, ApiError> {
+    ensure_organization_member(&deployment, org_id).await?;
+    validate_create(&payload)?;
+
+    let channel =
+        NotificationChannel::create(&deployment.db().pool, None, Some(org_id), &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "notification_channel_created",
+            serde_json::json!({
+                "scope": "organization",
+                "kind": channel.kind,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(masked(channel))))
+}
+
+pub async fn update_channel(
+    State(deployment): State<DeploymentImpl>,
+    Path(channel_id): Path<Uuid>,
+    Json(mut payload): Json<UpdateNotificationChannel>,
+) -> Result<ResponseJson<ApiResponse<NotificationChannel>>, ApiError> {
+    if payload.name.as_ref().is_some_and(|n| n.trim().is_empty()) {
+        return Err(ApiError::BadRequest("name is required".to_string()));
+    }
+    let existing = find_channel(&deployment, channel_id).await?;
+    // A client echoing back the masked URL it was given keeps the stored one
+    if payload
+        .webhook_url
+        .as_deref()
+        .is_some_and(|url| url == mask_webhook_url(&existing.webhook_url))
+    {
+        payload.webhook_url = None;
+    }
+    if let Some(kind) = payload.kind.or_else(|| existing.get_kind()) {
+        validate_target(
+            kind,
+            payload
+                .webhook_url
+                .as_ref()
+                .unwrap_or(&existing.webhook_url),
+            payload
+                .destination
+                .as_deref()
+                .or(existing.destination.as_deref()),
+            payload
+                .access_token
+                .as_deref()
+                .or(existing.access_token.as_deref()),
+        )?;
+    }
+    let channel = NotificationChannel::update(&deployment.db().pool, channel_id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(masked(channel))))
+}
+
+pub async fn delete_channel(
+    State(deployment): State<DeploymentImpl>,
+    Path(channel_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    find_channel(&deployment, channel_id).await?;
+    let deleted = NotificationChannel::delete(&deployment.db().pool, channel_id).await?;
+    if deleted == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Send a sample notification through the channel, ignoring its routing rules
+pub async fn test_channel(
+    State(deployment): State<DeploymentImpl>,
+    Path(channel_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let channel = find_channel(&deployment, channel_id).await?;
+
+    let project = match channel.project_id {
+        Some(project_id) => Project::find_by_id(pool, project_id).await?,
+        None => None,
+    };
+    let notification = ChannelNotification {
+        event: NotificationEvent::ExecutionCompleted,
+        project_id: project.as_ref().map(|p| p.id).unwrap_or_default(),
+        project_name: project
+            .map(|p| p.name)
+            .unwrap_or_else(|| "Example project".to_string()),
+        task_id: Uuid::nil(),
+        task_title: "Test notification".to_string(),
+        run_reason: Some(ExecutionProcessRunReason::CodingAgent),
+        exit_code: Some(0),
+        detail: None,
+        approval: None,
+    };
+
+    notification_channels::send(&channel, &notification)
+        .await
+        .map_err(ApiError::BadRequest)?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Chat users whose ✅/❌ reactions count as the mapped user's approval
+pub async fn get_channel_identities(
+    State(deployment): State<DeploymentImpl>,
+    Path(channel_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<NotificationChannelIdentity>>>, ApiError> {
+    find_channel(&deployment, channel_id).await?;
+    let identities =
+        NotificationChannelIdentity::find_by_channel(&deployment.db().pool, channel_id).await?;
+    Ok(ResponseJson(ApiResponse::success(identities)))
+}
+
+pub async fn map_channel_identity(
+    State(deployment): State<DeploymentImpl>,
+    Path(channel_id): Path<Uuid>,
+    Json(payload): Json<CreateNotificationChannelIdentity>,
+) -> Result<ResponseJson<ApiResponse<NotificationChannelIdentity>>, ApiError> {
+    if payload.external_user_id.trim().is_empty() {
+        return Err(ApiError::BadRequest("external_user_id is required".to_string()));
+    }
+    let pool = &deployment.db().pool;
+    let channel = find_channel(&deployment, channel_id).await?;
+    let supports_reactions = channel
+        .get_kind()
+        .is_some_and(|kind| notification_channels::supports_reactions(&channel, kind));
+    if !supports_reactions {
+        return Err(ApiError::BadRequest(
+            "Only Matrix channels and Slack channels with a bot token read reactions".to_string(),
+        ));
+    }
+    User::find_by_id(pool, payload.user_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("User not found".to_string()))?;
+
+    let identity = NotificationChannelIdentity::upsert(pool, channel_id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "notification_channel_identity_mapped",
+            serde_json::json!({ "kind": channel.kind }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(identity)))
+}
+
+pub async fn delete_channel_identity(
+    State(deployment): State<DeploymentImpl>,
+    Path((channel_id, identity_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    find_channel(&deployment, channel_id).await?;
+    let deleted =
+        NotificationChannelIdentity::delete(&deployment.db().pool, channel_id, identity_id)
+            .await?;
+    if deleted == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/projects/{project_id}/notification-channels",
+            get(get_project_channels).post(create_project_channel),
+        )
+        .route(
+            "/organizations/{org_id}/notification-channels",
+            get(get_organization_channels).post(create_organization_channel),
+        )
+        .route(
+            "/notification-channels/{channel_id}",
+            put(update_channel).delete(delete_channel),
+        )
+        .route(
+            "/notification-channels/{channel_id}/test",
+            post(test_channel),
+        )
+        .route(
+            "/notification-channels/{channel_id}/identities",
+            get(get_channel_identities).post(map_channel_identity),
+        )
+        .route(
+            "/notification-channels/{channel_id}/identities/{identity_id}",
+            delete(delete_channel_identity),
+        )
+}
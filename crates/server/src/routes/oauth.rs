@@ -9,9 +9,12 @@ use chrono::{DateTime, Utc};
 use deployment::Deployment;
 use rand::{Rng, distributions::Alphanumeric};
 use serde::{Deserialize, Serialize};
-use services::services::{config::save_config_to_file, oauth_credentials::Credentials};
+use services::services::{
+    config::save_config_to_file,
+    job_queue::{self, Job},
+    oauth_credentials::Credentials,
+};
 use sha2::{Digest, Sha256};
-use tokio;
 use ts_rs::TS;
 use utils::{
     api::oauth::{HandoffInitRequest, HandoffRedeemRequest, StatusResponse},
@@ -209,12 +212,10 @@ async fn handoff_complete(
     }
 
     // Trigger shared task cleanup in background
-    if let Ok(publisher) = deployment.share_publisher() {
-        tokio::spawn(async move {
-            if let Err(e) = publisher.cleanup_shared_tasks().await {
-                tracing::error!("Failed to cleanup shared tasks on login: {}", e);
-            }
-        });
+    if deployment.share_publisher().is_ok()
+        && let Err(e) = job_queue::enqueue(&deployment.db().pool, &Job::VerifySharedTasks).await
+    {
+        tracing::error!("Failed to queue shared task cleanup on login: {}", e);
     }
 
     Ok(close_window_response(format!(
@@ -0,0 +1,77 @@
+//! OpenAPI 3.1 description of the `/api` surface, served at `/api/openapi.json` with a
+//! Swagger UI at `/api/docs`. Operations are listed here as their handlers gain a
+//! `#[utoipa::path]` annotation.
+
+use axum::{
+    Router,
+    response::{Html, Json},
+    routing::get,
+};
+use utoipa::OpenApi;
+
+use crate::{
+    DeploymentImpl,
+    routes::{health, projects, tags, tasks},
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Vibe Kanban API",
+        description = "Local API used by the Vibe Kanban UI and the in-workspace MCP server. \
+            Every JSON response is wrapped in `ApiResponse`."
+    ),
+    paths(
+        health::liveness,
+        health::readiness,
+        projects::get_projects,
+        projects::create_project,
+        projects::get_project,
+        projects::update_project,
+        tasks::get_tasks,
+        tasks::create_task,
+        tasks::get_task,
+        tasks::update_task,
+        tasks::delete_task,
+        tags::get_tags,
+        tags::create_tag,
+    ),
+    tags(
+        (name = "health"),
+        (name = "projects"),
+        (name = "tasks"),
+        (name = "tags"),
+    )
+)]
+pub struct ApiDoc;
+
+const SWAGGER_UI: &str = r#"<!doctype html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8" />
+    <title>Vibe Kanban API</title>
+    <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+  </head>
+  <body>
+    <div id="swagger-ui"></div>
+    <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+    <script>
+      window.ui = SwaggerUIBundle({ url: '/api/openapi.json', dom_id: '#swagger-ui' });
+    </script>
+  </body>
+</html>
+"#;
+
+async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+async fn swagger_ui() -> Html<&'static str> {
+    Html(SWAGGER_UI)
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/openapi.json", get(openapi_json))
+        .route("/docs", get(swagger_ui))
+}
@@ -15,7 +15,10 @@ use utils::{
             ListOrganizationsResponse, Organization, RevokeInvitationRequest,
             UpdateMemberRoleRequest, UpdateMemberRoleResponse, UpdateOrganizationRequest,
         },
-        projects::RemoteProject,
+        projects::{
+            GrantProjectAccessRequest, ProjectAccessGrant, ProjectAccessResponse, RemoteProject,
+            TransferProjectOwnershipRequest,
+        },
     },
     response::ApiResponse,
 };
@@ -54,6 +57,18 @@ pub fn router() -> Router<DeploymentImpl> {
             "/organizations/{org_id}/members/{user_id}/role",
             patch(update_member_role),
         )
+        .route(
+            "/remote-projects/{project_id}/access",
+            get(get_project_access).post(grant_project_access),
+        )
+        .route(
+            "/remote-projects/{project_id}/access/{user_id}",
+            delete(revoke_project_access),
+        )
+        .route(
+            "/remote-projects/{project_id}/transfer-ownership",
+            post(transfer_project_ownership),
+        )
 }
 
 async fn list_organization_projects(
@@ -234,3 +249,69 @@ async fn update_member_role(
 
     Ok(ResponseJson(ApiResponse::success(response)))
 }
+
+async fn get_project_access(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<ProjectAccessResponse>>, ApiError> {
+    let client = deployment.remote_client()?;
+
+    let response = client.get_project_access(project_id).await?;
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+async fn grant_project_access(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(request): Json<GrantProjectAccessRequest>,
+) -> Result<ResponseJson<ApiResponse<ProjectAccessGrant>>, ApiError> {
+    let client = deployment.remote_client()?;
+
+    let response = client.grant_project_access(project_id, &request).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_access_granted",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+async fn revoke_project_access(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    let client = deployment.remote_client()?;
+
+    client.revoke_project_access(project_id, user_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn transfer_project_ownership(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(request): Json<TransferProjectOwnershipRequest>,
+) -> Result<ResponseJson<ApiResponse<ProjectAccessResponse>>, ApiError> {
+    let client = deployment.remote_client()?;
+
+    let response = client
+        .transfer_project_ownership(project_id, &request)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_ownership_transferred",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
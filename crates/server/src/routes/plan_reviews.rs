@@ -0,0 +1,90 @@
+use axum::{
+    Router,
+    extract::{Json, Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    plan_review::{PlanReview, PlanReviewStatus, ReviewPlan},
+    task_event::{CreateTaskEvent, TaskEvent},
+};
+use deployment::Deployment;
+use services::services::container::ContainerService;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::users};
+
+/// Plans written for a task, newest first
+pub async fn list_task_plan_reviews(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<PlanReview>>>, ApiError> {
+    let reviews = PlanReview::find_by_task_id(&deployment.db().pool, task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(reviews)))
+}
+
+pub async fn approve_plan(
+    State(deployment): State<DeploymentImpl>,
+    Path(review_id): Path<Uuid>,
+    Json(payload): Json<ReviewPlan>,
+) -> Result<ResponseJson<ApiResponse<PlanReview>>, ApiError> {
+    let review = review_plan(&deployment, review_id, PlanReviewStatus::Approved, &payload).await?;
+    deployment.container().resume_after_plan_approval(&review).await?;
+    Ok(ResponseJson(ApiResponse::success(review)))
+}
+
+pub async fn reject_plan(
+    State(deployment): State<DeploymentImpl>,
+    Path(review_id): Path<Uuid>,
+    Json(payload): Json<ReviewPlan>,
+) -> Result<ResponseJson<ApiResponse<PlanReview>>, ApiError> {
+    let review = review_plan(&deployment, review_id, PlanReviewStatus::Rejected, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(review)))
+}
+
+async fn review_plan(
+    deployment: &DeploymentImpl,
+    review_id: Uuid,
+    status: PlanReviewStatus,
+    payload: &ReviewPlan,
+) -> Result<PlanReview, ApiError> {
+    let pool = &deployment.db().pool;
+    let user = users::current_user(deployment).await?;
+    let mut payload = payload.clone();
+    if payload.reviewer.is_none() {
+        payload.reviewer = user.as_ref().map(|user| user.label().to_string());
+    }
+    let review = PlanReview::review(pool, review_id, status, &payload).await?;
+
+    let event = CreateTaskEvent::plan_reviewed(
+        review.task_id,
+        review.column_id,
+        review.id,
+        status == PlanReviewStatus::Approved,
+        user.map(|user| user.id.to_string()),
+    );
+    if let Err(e) = TaskEvent::create(pool, &event).await {
+        tracing::error!("Failed to record plan review event for task {}: {}", review.task_id, e);
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "plan_reviewed",
+            serde_json::json!({
+                "plan_review_id": review.id.to_string(),
+                "task_id": review.task_id.to_string(),
+                "approved": status == PlanReviewStatus::Approved,
+            }),
+        )
+        .await;
+
+    Ok(review)
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/tasks/{task_id}/plan-reviews", get(list_task_plan_reviews))
+        .route("/plan-reviews/{review_id}/approve", post(approve_plan))
+        .route("/plan-reviews/{review_id}/reject", post(reject_plan))
+}
@@ -0,0 +1,250 @@
+use std::sync::LazyLock;
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{
+        Path, Request, State,
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+    },
+    http::{HeaderMap, HeaderName, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::any,
+};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use futures_util::{SinkExt, StreamExt};
+use services::services::container::ContainerService;
+use tokio_tungstenite::tungstenite::{self, client::IntoClientRequest};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("failed to build preview proxy client")
+});
+
+/// Connection-level headers that must not be forwarded by a proxy
+fn is_hop_by_hop(name: &HeaderName) -> bool {
+    matches!(
+        name.as_str(),
+        "connection"
+            | "keep-alive"
+            | "proxy-authenticate"
+            | "proxy-authorization"
+            | "te"
+            | "trailer"
+            | "transfer-encoding"
+            | "upgrade"
+            | "host"
+            | "content-length"
+    )
+}
+
+/// Headers forwarded to the dev server: the API credential stays with the API
+fn forwards_to_dev_server(name: &HeaderName) -> bool {
+    !is_hop_by_hop(name) && *name != header::AUTHORIZATION
+}
+
+pub async fn proxy_preview_root(
+    State(deployment): State<DeploymentImpl>,
+    Path(workspace_id): Path<Uuid>,
+    ws: Option<WebSocketUpgrade>,
+    request: Request,
+) -> Result<Response, ApiError> {
+    proxy(&deployment, workspace_id, "", ws, request).await
+}
+
+pub async fn proxy_preview(
+    State(deployment): State<DeploymentImpl>,
+    Path((workspace_id, path)): Path<(Uuid, String)>,
+    ws: Option<WebSocketUpgrade>,
+    request: Request,
+) -> Result<Response, ApiError> {
+    proxy(&deployment, workspace_id, &path, ws, request).await
+}
+
+/// Forward a request to the workspace's running dev server on localhost, streaming
+/// bodies both ways. WebSocket upgrades (e.g. hot module reload) are bridged to the
+/// dev server too.
+///
+/// The preview is served under `/api`, behind the same auth middleware as every other
+/// route. Only ports detected from a still-running dev server process are reachable,
+/// so this can't be used to reach arbitrary local services.
+async fn proxy(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+    path: &str,
+    ws: Option<WebSocketUpgrade>,
+    request: Request,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let workspace = Workspace::find_by_id(pool, workspace_id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    let Some(preview) = deployment
+        .container()
+        .dev_server_previews()
+        .get(workspace.id)
+        .await
+    else {
+        return Ok((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "No dev server port detected for this workspace",
+        )
+            .into_response());
+    };
+
+    let running = ExecutionProcess::find_by_id(pool, preview.execution_process_id)
+        .await?
+        .is_some_and(|p| p.status == ExecutionProcessStatus::Running);
+    if !running {
+        return Ok((StatusCode::SERVICE_UNAVAILABLE, "Dev server is not running").into_response());
+    }
+
+    let mut target = format!("127.0.0.1:{}/{}", preview.port, path);
+    if let Some(query) = request.uri().query() {
+        target.push('?');
+        target.push_str(query);
+    }
+
+    if let Some(ws) = ws {
+        return Ok(proxy_websocket(ws, format!("ws://{target}"), request.headers()));
+    }
+
+    let url = format!("http://{target}");
+    let (parts, body) = request.into_parts();
+    let mut upstream = CLIENT
+        .request(parts.method, &url)
+        .body(reqwest::Body::wrap_stream(body.into_data_stream()));
+    for (name, value) in parts.headers.iter().filter(|(n, _)| forwards_to_dev_server(n)) {
+        upstream = upstream.header(name, value);
+    }
+
+    let response = match upstream.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::warn!("Preview proxy request to {} failed: {}", url, e);
+            return Ok((StatusCode::BAD_GATEWAY, "Dev server did not respond").into_response());
+        }
+    };
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    let mut proxied = Response::new(Body::from_stream(response.bytes_stream()));
+    *proxied.status_mut() = status;
+    for (name, value) in headers.iter().filter(|(n, _)| !is_hop_by_hop(n)) {
+        proxied.headers_mut().append(name, value.clone());
+    }
+    // Only the UI (same origin) may embed the preview
+    proxied.headers_mut().insert(
+        header::X_FRAME_OPTIONS,
+        header::HeaderValue::from_static("SAMEORIGIN"),
+    );
+
+    Ok(proxied)
+}
+
+/// Accept the browser's WebSocket and bridge it to the same path on the dev server,
+/// offering the subprotocols the browser asked for (Vite's HMR client needs its own)
+fn proxy_websocket(ws: WebSocketUpgrade, url: String, headers: &HeaderMap) -> Response {
+    let protocols: Vec<String> = headers
+        .get(header::SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.split(',').map(|p| p.trim().to_string()).collect())
+        .unwrap_or_default();
+
+    ws.protocols(protocols.clone())
+        .on_upgrade(move |client| async move {
+            let mut request = match url.as_str().into_client_request() {
+                Ok(request) => request,
+                Err(e) => {
+                    tracing::warn!("Invalid preview WebSocket URL {}: {}", url, e);
+                    return;
+                }
+            };
+            if !protocols.is_empty()
+                && let Ok(value) = protocols.join(", ").parse::<header::HeaderValue>()
+            {
+                request.headers_mut().insert(header::SEC_WEBSOCKET_PROTOCOL, value);
+            }
+            match tokio_tungstenite::connect_async(request).await {
+                Ok((upstream, _)) => bridge_websockets(client, upstream).await,
+                Err(e) => tracing::warn!("Preview WebSocket to {} failed: {}", url, e),
+            }
+        })
+}
+
+/// Relay messages both ways until either side closes
+async fn bridge_websockets<S>(client: WebSocket, upstream: tokio_tungstenite::WebSocketStream<S>)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let (mut client_tx, mut client_rx) = client.split();
+    let (mut upstream_tx, mut upstream_rx) = upstream.split();
+
+    let to_upstream = async {
+        while let Some(Ok(msg)) = client_rx.next().await {
+            if upstream_tx.send(to_upstream_message(msg)).await.is_err() {
+                break;
+            }
+        }
+    };
+    let to_client = async {
+        while let Some(Ok(msg)) = upstream_rx.next().await {
+            let Some(msg) = to_client_message(msg) else {
+                continue;
+            };
+            if client_tx.send(msg).await.is_err() {
+                break;
+            }
+        }
+    };
+    tokio::select! {
+        _ = to_upstream => {}
+        _ = to_client => {}
+    }
+}
+
+fn to_upstream_message(msg: Message) -> tungstenite::Message {
+    match msg {
+        Message::Text(text) => tungstenite::Message::Text(text.as_str().to_string().into()),
+        Message::Binary(data) => tungstenite::Message::Binary(data),
+        Message::Ping(data) => tungstenite::Message::Ping(data),
+        Message::Pong(data) => tungstenite::Message::Pong(data),
+        Message::Close(frame) => {
+            tungstenite::Message::Close(frame.map(|frame| tungstenite::protocol::CloseFrame {
+                code: frame.code.into(),
+                reason: frame.reason.as_str().to_string().into(),
+            }))
+        }
+    }
+}
+
+fn to_client_message(msg: tungstenite::Message) -> Option<Message> {
+    Some(match msg {
+        tungstenite::Message::Text(text) => Message::Text(text.as_str().to_string().into()),
+        tungstenite::Message::Binary(data) => Message::Binary(data),
+        tungstenite::Message::Ping(data) => Message::Ping(data),
+        tungstenite::Message::Pong(data) => Message::Pong(data),
+        tungstenite::Message::Close(frame) => Message::Close(frame.map(|frame| CloseFrame {
+            code: frame.code.into(),
+            reason: frame.reason.as_str().to_string().into(),
+        })),
+        // Raw frames only show up when writing, never when reading
+        tungstenite::Message::Frame(_) => return None,
+    })
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/preview/{workspace_id}/", any(proxy_preview_root))
+        .route("/preview/{workspace_id}/{*path}", any(proxy_preview))
+}
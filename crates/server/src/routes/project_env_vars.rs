@@ -0,0 +1,103 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    project_env_var::{CreateProjectEnvVar, ProjectEnvVar, UpdateProjectEnvVar},
+};
+use deployment::Deployment;
+use services::services::env_vault::{EnvVault, validate_env_key};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+async fn ensure_project(deployment: &DeploymentImpl, project_id: Uuid) -> Result<(), ApiError> {
+    Project::find_by_id(&deployment.db().pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    Ok(())
+}
+
+/// List env var keys for a project. Values are never returned.
+pub async fn list_env_vars(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectEnvVar>>>, ApiError> {
+    ensure_project(&deployment, project_id).await?;
+    let vars = ProjectEnvVar::find_by_project(&deployment.db().pool, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(vars)))
+}
+
+pub async fn create_env_var(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateProjectEnvVar>,
+) -> Result<ResponseJson<ApiResponse<ProjectEnvVar>>, ApiError> {
+    ensure_project(&deployment, project_id).await?;
+    validate_env_key(&payload.key).map_err(ApiError::BadRequest)?;
+
+    let pool = &deployment.db().pool;
+    if ProjectEnvVar::find_by_key(pool, project_id, &payload.key)
+        .await?
+        .is_some()
+    {
+        return Err(ApiError::Conflict(format!(
+            "Env var '{}' already exists for this project",
+            payload.key
+        )));
+    }
+
+    let (ciphertext, nonce) = EnvVault::shared()?.encrypt(&payload.value)?;
+    let var = ProjectEnvVar::create(pool, project_id, &payload.key, &ciphertext, &nonce).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_env_var_created",
+            serde_json::json!({ "project_id": project_id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(var)))
+}
+
+pub async fn update_env_var(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, key)): Path<(Uuid, String)>,
+    Json(payload): Json<UpdateProjectEnvVar>,
+) -> Result<ResponseJson<ApiResponse<ProjectEnvVar>>, ApiError> {
+    ensure_project(&deployment, project_id).await?;
+    let (ciphertext, nonce) = EnvVault::shared()?.encrypt(&payload.value)?;
+    let var =
+        ProjectEnvVar::update_value(&deployment.db().pool, project_id, &key, &ciphertext, &nonce)
+            .await?
+            .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(var)))
+}
+
+pub async fn delete_env_var(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, key)): Path<(Uuid, String)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ensure_project(&deployment, project_id).await?;
+    let deleted = ProjectEnvVar::delete(&deployment.db().pool, project_id, &key).await?;
+    if !deleted {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/projects/{project_id}/env",
+            get(list_env_vars).post(create_env_var),
+        )
+        .route(
+            "/projects/{project_id}/env/{key}",
+            put(update_env_var).delete(delete_env_var),
+        )
+}
@@ -13,14 +13,30 @@ use axum::{
     routing::{get, post},
 };
 use db::models::{
-    project::{CreateProject, Project, ProjectError, SearchResult, UpdateProject},
+    context_budget::{ContextBudget, ContextBudgetOverride, ContextBudgetSettings},
+    project::{
+        CreateProject, Project, ProjectError, SearchResult, UpdateProject,
+        UpdateProjectBoardSettings, UpdateProjectBranchSettings, UpdateProjectExecutionSettings,
+    },
+    project_log_redaction_settings::{
+        ProjectLogRedactionSettings, UpdateProjectLogRedactionSettings,
+    },
+    project_module_memory_settings::{
+        ProjectModuleMemorySettings, UpdateProjectModuleMemorySettings,
+    },
     project_repo::{CreateProjectRepo, ProjectRepo, UpdateProjectRepo},
+    project_repo_script_settings::{ProjectRepoScriptSettings, UpdateProjectRepoScriptSettings},
+    project_security_settings::{ProjectSecuritySettings, UpdateProjectSecuritySettings},
+    project_share_sync_settings::{ProjectShareSyncSettings, UpdateProjectShareSyncSettings},
+    project_timezone_settings::{ProjectTimezoneSettings, UpdateProjectTimezoneSettings},
     repo::Repo,
+    setup_script_run::{SetupScriptHealth, SetupScriptRun},
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use services::services::{
+    branch_template,
     container::ContainerService,
     events::project_patch,
     file_search_cache::SearchQuery, project::ProjectServiceError,
@@ -46,6 +62,12 @@ pub struct CreateRemoteProjectRequest {
     pub name: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects",
+    tag = "projects",
+    responses((status = 200, body = ApiResponse<Vec<Project>>))
+)]
 pub async fn get_projects(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<Vec<Project>>>, ApiError> {
@@ -95,6 +117,16 @@ async fn handle_projects_ws(socket: WebSocket, deployment: DeploymentImpl) -> an
     Ok(())
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, body = ApiResponse<Project>),
+        (status = 404, description = "Project not found")
+    )
+)]
 pub async fn get_project(
     Extension(project): Extension<Project>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
@@ -229,6 +261,13 @@ async fn apply_remote_project_link(
     Ok(updated_project)
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/projects",
+    tag = "projects",
+    request_body = CreateProject,
+    responses((status = 200, description = "Created project", body = ApiResponse<Project>))
+)]
 pub async fn create_project(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateProject>,
@@ -277,10 +316,27 @@ pub async fn create_project(
         Err(ProjectServiceError::NotGitRepository(_)) => Ok(ResponseJson(ApiResponse::error(
             "The specified directory is not a git repository",
         ))),
+        Err(ProjectServiceError::WorkflowTemplateNotFound) => Ok(ResponseJson(ApiResponse::error(
+            "The specified workflow template does not exist",
+        ))),
+        Err(e @ ProjectServiceError::InvalidWorkflowTemplate(_)) => {
+            Ok(ResponseJson(ApiResponse::error(&e.to_string())))
+        }
         Err(e) => Err(ProjectError::CreateFailed(e.to_string()).into()),
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    request_body = UpdateProject,
+    responses(
+        (status = 200, description = "Updated project", body = ApiResponse<Project>),
+        (status = 404, description = "Project not found")
+    )
+)]
 pub async fn update_project(
     Extension(existing_project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -617,6 +673,135 @@ pub async fn update_project_repository(
     }
 }
 
+async fn find_project_repo(
+    pool: &sqlx::PgPool,
+    project_id: Uuid,
+    repo_id: Uuid,
+) -> Result<ProjectRepo, ApiError> {
+    ProjectRepo::find_by_project_and_repo(pool, project_id, repo_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Repository not found in project".to_string()))
+}
+
+/// Script languages and Windows variants of a repo's setup and cleanup scripts
+pub async fn get_project_repository_script_settings(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, repo_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<ProjectRepoScriptSettings>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project_repo = find_project_repo(pool, project_id, repo_id).await?;
+    let settings = ProjectRepoScriptSettings::for_project_repo(pool, project_repo.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+pub async fn update_project_repository_script_settings(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, repo_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectRepoScriptSettings>,
+) -> Result<ResponseJson<ApiResponse<ProjectRepoScriptSettings>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project_repo = find_project_repo(pool, project_id, repo_id).await?;
+    let settings = ProjectRepoScriptSettings::update(pool, project_repo.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_repository_script_settings_updated",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+                "repository_id": repo_id.to_string(),
+                "script_language": payload.script_language.map(|l| l.as_str()),
+                "has_windows_scripts": settings.windows_setup_script.is_some()
+                    || settings.windows_cleanup_script.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RepoDefaultTargetBranch {
+    pub repo_id: Uuid,
+    /// Branch new attempts target when the request doesn't name one
+    pub default_target_branch: Option<String>,
+    /// Branch checked out in the repo, used when there is no default
+    pub current_branch: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateRepoDefaultTargetBranch {
+    pub default_target_branch: Option<String>,
+}
+
+async fn repo_default_target_branch(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    repo: &Repo,
+) -> Result<RepoDefaultTargetBranch, ApiError> {
+    let defaults =
+        ProjectRepo::find_default_target_branches(&deployment.db().pool, project_id).await?;
+    Ok(RepoDefaultTargetBranch {
+        repo_id: repo.id,
+        default_target_branch: defaults.get(&repo.id).cloned(),
+        current_branch: deployment.git().get_current_branch(&repo.path).ok(),
+    })
+}
+
+/// The branch attempts target in a repo when none is requested
+pub async fn get_project_repository_default_target_branch(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, repo_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<RepoDefaultTargetBranch>>, ApiError> {
+    let pool = &deployment.db().pool;
+    find_project_repo(pool, project_id, repo_id).await?;
+    let repo = Repo::find_by_id(pool, repo_id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+    let branch = repo_default_target_branch(&deployment, project_id, &repo).await?;
+    Ok(ResponseJson(ApiResponse::success(branch)))
+}
+
+pub async fn update_project_repository_default_target_branch(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, repo_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateRepoDefaultTargetBranch>,
+) -> Result<ResponseJson<ApiResponse<RepoDefaultTargetBranch>>, ApiError> {
+    let pool = &deployment.db().pool;
+    find_project_repo(pool, project_id, repo_id).await?;
+    let repo = Repo::find_by_id(pool, repo_id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    let branch = payload
+        .default_target_branch
+        .as_deref()
+        .map(str::trim)
+        .filter(|branch| !branch.is_empty());
+    if let Some(branch) = branch
+        && !deployment.git().check_branch_exists(&repo.path, branch)?
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Branch '{}' does not exist in {}",
+            branch, repo.name
+        )));
+    }
+    ProjectRepo::set_default_target_branch(pool, project_id, repo_id, branch).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_repository_default_target_branch_updated",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+                "repository_id": repo_id.to_string(),
+                "has_default": branch.is_some(),
+            }),
+        )
+        .await;
+
+    let branch = repo_default_target_branch(&deployment, project_id, &repo).await?;
+    Ok(ResponseJson(ApiResponse::success(branch)))
+}
+
 /// POST /projects/{id}/grouper/start — idempotent
 pub async fn start_grouper_agent(
     Extension(project): Extension<Project>,
@@ -875,6 +1060,337 @@ async fn unlock_project(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Setup script history per repo, flagging scripts that flap between passing and failing
+pub async fn get_setup_script_health(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<SetupScriptHealth>>>, ApiError> {
+    let health = SetupScriptRun::health_for_project(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(health)))
+}
+
+/// Get a project's board settings
+pub async fn get_project_board_settings(
+    Extension(project): Extension<Project>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Update a project's board settings (initial column auto-assignment)
+pub async fn update_project_board_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateProjectBoardSettings>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let project =
+        Project::update_board_settings(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_board_settings_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "auto_assign_initial_column": project.auto_assign_initial_column,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Get a project's execution backend
+pub async fn get_project_execution_settings(
+    Extension(project): Extension<Project>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Choose whether the project's agents run in local worktrees or Docker containers.
+/// Applies to workspaces created from now on; existing ones keep their backend.
+pub async fn update_project_execution_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateProjectExecutionSettings>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    if payload
+        .docker_image
+        .as_deref()
+        .is_some_and(|image| image.trim().contains(char::is_whitespace))
+    {
+        return Err(ApiError::BadRequest(
+            "docker_image must be a single image reference".to_string(),
+        ));
+    }
+    if payload
+        .docker_ports
+        .as_deref()
+        .is_some_and(|ports| ports.contains(&0))
+    {
+        return Err(ApiError::BadRequest(
+            "docker_ports must be between 1 and 65535".to_string(),
+        ));
+    }
+    let project =
+        Project::update_execution_settings(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_execution_settings_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "execution_backend": project.execution_backend,
+                "custom_image": project.docker_image.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Get a project's branch naming template
+pub async fn get_project_branch_settings(
+    Extension(project): Extension<Project>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Set the template for the branches of the project's new task workspaces
+pub async fn update_project_branch_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateProjectBranchSettings>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    if let Some(template) = payload.branch_template.as_deref().map(str::trim)
+        && !template.is_empty()
+    {
+        branch_template::validate(template).map_err(ApiError::BadRequest)?;
+    }
+    let project =
+        Project::update_branch_settings(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_branch_settings_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "custom_template": project.branch_template.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Get how a project settles conflicts between its tasks and their shared copies
+pub async fn get_project_share_sync_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectShareSyncSettings>>, ApiError> {
+    let settings =
+        ProjectShareSyncSettings::for_project(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Choose between last-writer-wins and manual resolution of shared task conflicts
+pub async fn update_project_share_sync_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateProjectShareSyncSettings>,
+) -> Result<ResponseJson<ApiResponse<ProjectShareSyncSettings>>, ApiError> {
+    let settings =
+        ProjectShareSyncSettings::update(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_share_sync_settings_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "conflict_strategy": settings.conflict_strategy,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Get the values a project's log redaction leaves alone
+pub async fn get_project_log_redaction_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectLogRedactionSettings>>, ApiError> {
+    let settings =
+        ProjectLogRedactionSettings::for_project(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Replace the allowlist of values the log redaction pass leaves alone
+pub async fn update_project_log_redaction_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateProjectLogRedactionSettings>,
+) -> Result<ResponseJson<ApiResponse<ProjectLogRedactionSettings>>, ApiError> {
+    let settings =
+        ProjectLogRedactionSettings::update(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_log_redaction_settings_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "allowlist_size": settings.allowlist.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Get whether merges refresh a project's module memories
+pub async fn get_project_module_memory_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectModuleMemorySettings>>, ApiError> {
+    let settings =
+        ProjectModuleMemorySettings::for_project(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Turn module memory refreshes after merges on or off, and cap the modules per refresh
+pub async fn update_project_module_memory_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateProjectModuleMemorySettings>,
+) -> Result<ResponseJson<ApiResponse<ProjectModuleMemorySettings>>, ApiError> {
+    if payload.max_modules.is_some_and(|max| max <= 0) {
+        return Err(ApiError::BadRequest("max_modules must be positive".to_string()));
+    }
+    let settings =
+        ProjectModuleMemorySettings::update(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_module_memory_settings_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "refresh_after_merge": settings.refresh_after_merge,
+                "max_modules": settings.max_modules,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Get a project's context budget override and the budget it resolves to
+pub async fn get_project_context_budget(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ContextBudgetSettings>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let overrides = ContextBudgetOverride::for_project(pool, project.id).await?;
+    let effective = ContextBudget::resolve(pool, project.id, None).await?;
+    Ok(ResponseJson(ApiResponse::success(ContextBudgetSettings {
+        overrides,
+        effective,
+    })))
+}
+
+/// Replace a project's context budget override (unset fields use the defaults)
+pub async fn update_project_context_budget(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ContextBudgetOverride>,
+) -> Result<ResponseJson<ApiResponse<ContextBudgetSettings>>, ApiError> {
+    payload.validate().map_err(ApiError::BadRequest)?;
+    let pool = &deployment.db().pool;
+    let overrides = ContextBudgetOverride::set_for_project(pool, project.id, &payload).await?;
+    let effective = ContextBudget::resolve(pool, project.id, None).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_context_budget_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "token_budget": effective.token_budget,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(ContextBudgetSettings {
+        overrides,
+        effective,
+    })))
+}
+
+/// Get the timezone a project's digests and daily snapshots use
+pub async fn get_project_timezone_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectTimezoneSettings>>, ApiError> {
+    let settings = ProjectTimezoneSettings::for_project(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Set a project's IANA timezone. Daily digests of its board go out at their hour in
+/// this timezone, and board snapshots are bucketed by its dates.
+pub async fn update_project_timezone_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateProjectTimezoneSettings>,
+) -> Result<ResponseJson<ApiResponse<ProjectTimezoneSettings>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if let Some(timezone) = &payload.timezone
+        && !ProjectTimezoneSettings::is_known(pool, timezone).await?
+    {
+        return Err(ApiError::BadRequest(format!("Unknown timezone '{timezone}'")));
+    }
+    let settings = ProjectTimezoneSettings::update(pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_timezone_settings_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "timezone": settings.timezone,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Get how the project's security gate treats scanners that can't run
+pub async fn get_project_security_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectSecuritySettings>>, ApiError> {
+    let settings = ProjectSecuritySettings::for_project(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Opt a project in or out of passing the security gate when a scanner is missing or
+/// fails to run
+pub async fn update_project_security_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateProjectSecuritySettings>,
+) -> Result<ResponseJson<ApiResponse<ProjectSecuritySettings>>, ApiError> {
+    let settings =
+        ProjectSecuritySettings::update(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_security_settings_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "allow_scanner_errors": settings.allow_scanner_errors,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let project_id_router = Router::new()
         .route(
@@ -889,6 +1405,43 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/group-evaluator/start", post(start_group_evaluator_agent))
         .route("/prereq-eval/start", post(start_prereq_eval_agent))
         .route("/unlock", post(unlock_project))
+        .route("/setup-script-health", get(get_setup_script_health))
+        .route(
+            "/board-settings",
+            get(get_project_board_settings).put(update_project_board_settings),
+        )
+        .route(
+            "/execution-settings",
+            get(get_project_execution_settings).put(update_project_execution_settings),
+        )
+        .route(
+            "/branch-settings",
+            get(get_project_branch_settings).put(update_project_branch_settings),
+        )
+        .route(
+            "/share-sync-settings",
+            get(get_project_share_sync_settings).put(update_project_share_sync_settings),
+        )
+        .route(
+            "/log-redaction-settings",
+            get(get_project_log_redaction_settings).put(update_project_log_redaction_settings),
+        )
+        .route(
+            "/module-memory-settings",
+            get(get_project_module_memory_settings).put(update_project_module_memory_settings),
+        )
+        .route(
+            "/context-budget",
+            get(get_project_context_budget).put(update_project_context_budget),
+        )
+        .route(
+            "/timezone-settings",
+            get(get_project_timezone_settings).put(update_project_timezone_settings),
+        )
+        .route(
+            "/security-settings",
+            get(get_project_security_settings).put(update_project_security_settings),
+        )
         .route(
             "/link",
             post(link_project_to_existing_remote).delete(unlink_project),
@@ -911,6 +1464,16 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
                 .put(update_project_repository)
                 .delete(delete_project_repository),
         )
+        .route(
+            "/{project_id}/repositories/{repo_id}/script-settings",
+            get(get_project_repository_script_settings)
+                .put(update_project_repository_script_settings),
+        )
+        .route(
+            "/{project_id}/repositories/{repo_id}/default-target-branch",
+            get(get_project_repository_default_target_branch)
+                .put(update_project_repository_default_target_branch),
+        )
         .route("/stream/ws", get(stream_projects_ws))
         .nest("/{id}", project_id_router);
 
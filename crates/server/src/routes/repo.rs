@@ -1,13 +1,17 @@
 use axum::{
-    Router,
+    Json, Router,
     extract::{Path, State},
     response::Json as ResponseJson,
     routing::{get, post},
 };
-use db::models::repo::Repo;
+use db::models::{
+    repo::Repo,
+    repo_forge_settings::{ForgeKind, RepoForgeSettings, UpdateRepoForgeSettings},
+    repo_merge_settings::{RepoMergeSettings, UpdateRepoMergeSettings},
+};
 use deployment::Deployment;
 use serde::Deserialize;
-use services::services::git::GitBranch;
+use services::services::{env_vault::EnvVault, forge::is_secure_instance_url, git::GitBranch};
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -74,9 +78,115 @@ pub async fn get_repo_branches(
     Ok(ResponseJson(ApiResponse::success(branches)))
 }
 
+/// Get a repo's merge settings (defaults when never configured)
+pub async fn get_repo_merge_settings(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<RepoMergeSettings>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repo = deployment.repo().get_by_id(pool, repo_id).await?;
+    let settings = RepoMergeSettings::for_repo(pool, repo.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Update a repo's merge settings (rebase requirement, merge strategy, branch deletion)
+pub async fn update_repo_merge_settings(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+    Json(payload): Json<UpdateRepoMergeSettings>,
+) -> Result<ResponseJson<ApiResponse<RepoMergeSettings>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repo = deployment.repo().get_by_id(pool, repo_id).await?;
+    let settings = RepoMergeSettings::update(pool, repo.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "repo_merge_settings_updated",
+            serde_json::json!({
+                "repo_id": repo.id.to_string(),
+                "require_clean_rebase": settings.require_clean_rebase,
+                "merge_strategy": settings.merge_strategy,
+                "delete_branch_after_merge": settings.delete_branch_after_merge,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Get a repo's forge settings (GitHub when never configured)
+pub async fn get_repo_forge_settings(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<RepoForgeSettings>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repo = deployment.repo().get_by_id(pool, repo_id).await?;
+    let settings = RepoForgeSettings::for_repo(pool, repo.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Choose the code host (GitHub, Gitea/Forgejo, GitLab or Bitbucket Cloud) for a repo's
+/// PRs, checks and issues. GitLab uses gitlab.com unless an instance URL is set, which
+/// must be https unless the instance runs on this machine. The API token is encrypted
+/// before it's stored, and dropped when the forge or instance URL changes.
+pub async fn update_repo_forge_settings(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+    Json(payload): Json<UpdateRepoForgeSettings>,
+) -> Result<ResponseJson<ApiResponse<RepoForgeSettings>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repo = deployment.repo().get_by_id(pool, repo_id).await?;
+
+    let current = RepoForgeSettings::for_repo(pool, repo.id).await?;
+    let forge = payload.forge.unwrap_or(current.kind());
+    let base_url = match payload.base_url.as_deref().map(str::trim) {
+        Some(url) => Some(url).filter(|url| !url.is_empty()),
+        None => current.base_url.as_deref(),
+    };
+    if let Some(url) = base_url
+        && !is_secure_instance_url(url)
+    {
+        return Err(ApiError::BadRequest(
+            "The instance URL must use https:// (http:// only for localhost)".to_string(),
+        ));
+    }
+    if forge == ForgeKind::Gitea && base_url.is_none() {
+        return Err(ApiError::BadRequest(
+            "Gitea/Forgejo repos need the instance URL".to_string(),
+        ));
+    }
+
+    let sealed_token = match payload.api_token.as_deref().map(str::trim) {
+        Some(token) if !token.is_empty() => Some(EnvVault::shared()?.encrypt(token)?),
+        _ => None,
+    };
+    let settings = RepoForgeSettings::update(pool, repo.id, &payload, sealed_token).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "repo_forge_settings_updated",
+            serde_json::json!({
+                "repo_id": repo.id.to_string(),
+                "forge": settings.forge,
+                "has_token": settings.has_token,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/repos", post(register_repo))
         .route("/repos/init", post(init_repo))
         .route("/repos/{repo_id}/branches", get(get_repo_branches))
+        .route(
+            "/repos/{repo_id}/merge-settings",
+            get(get_repo_merge_settings).put(update_repo_merge_settings),
+        )
+        .route(
+            "/repos/{repo_id}/forge-settings",
+            get(get_repo_forge_settings).put(update_repo_forge_settings),
+        )
 }
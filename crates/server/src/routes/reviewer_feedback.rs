@@ -0,0 +1,32 @@
+use axum::{
+    Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::{reviewer_feedback::ReviewerFeedback, task::Task};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Reviewer feedback on a task, newest version first
+pub async fn get_reviewer_feedback(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<ReviewerFeedback>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let feedback = ReviewerFeedback::find_by_task(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(feedback)))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/tasks/{task_id}/reviewer-feedback",
+        get(get_reviewer_feedback),
+    )
+}
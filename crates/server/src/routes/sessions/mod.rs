@@ -10,7 +10,7 @@ use axum::{
 use db::models::{
     agent::Agent,
     context_artifact::ContextArtifact,
-    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
     project_repo::ProjectRepo,
     scratch::{Scratch, ScratchType},
     session::{CreateSession, Session},
@@ -235,6 +235,7 @@ pub async fn follow_up(
                     pool,
                     project.id,
                     Some(task.id),
+                    task.column_id,
                     &[],
                 )
                 .await
@@ -254,12 +255,17 @@ pub async fn follow_up(
                 agent_project_context,
                 agent_workflow_history: None,
                 agent_start_command: None,
+                agent_decision_instructions: None,
                 agent_deliverable: None,
+                prompt_sections: None,
+                prompt_language: None,
             },
         )
     };
 
-    let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
+    let agent = ExecutionProcess::latest_agent_for_session(pool, session.id).await?;
+    let action =
+        ExecutorAction::new(action_type, cleanup_action.map(Box::new)).with_agent(agent);
 
     let execution_process = deployment
         .container()
@@ -285,10 +291,54 @@ pub async fn follow_up(
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct SendSessionMessageRequest {
+    pub message: String,
+}
+
+/// Inject a user message into the session's running coding agent.
+/// The agent's reply streams through the execution's existing log stream.
+pub async fn send_message(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SendSessionMessageRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let message = payload.message.trim();
+    if message.is_empty() {
+        return Err(ApiError::BadRequest("Message cannot be empty".to_string()));
+    }
+
+    let process = ExecutionProcess::find_latest_by_session_and_run_reason(
+        pool,
+        session.id,
+        &ExecutionProcessRunReason::CodingAgent,
+    )
+    .await?
+    .filter(|p| p.status == ExecutionProcessStatus::Running)
+    .ok_or_else(|| {
+        ApiError::Conflict("No coding agent is running in this session".to_string())
+    })?;
+
+    if !deployment
+        .container()
+        .send_message_to_execution(&process.id, message.to_string())
+        .await
+    {
+        return Err(ApiError::BadRequest(
+            "This executor does not accept messages while running".to_string(),
+        ));
+    }
+
+    Ok(ResponseJson(ApiResponse::success(process)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let session_id_router = Router::new()
         .route("/", get(get_session))
         .route("/follow-up", post(follow_up))
+        .route("/message", post(send_message))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_session_middleware,
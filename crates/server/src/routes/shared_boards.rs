@@ -0,0 +1,197 @@
+//! Read-only shared board links for stakeholders without accounts.
+//!
+//! Board members create share links through the normal API. Anyone holding a link
+//! can open `/api/shared-boards/{token}`, which renders the board's columns and task
+//! summaries and refreshes them from `/api/shared-boards/{token}/stream` whenever the
+//! events stream reports a change. Logs, diffs and workspaces are never exposed.
+
+use axum::{
+    BoxError, Extension, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::{Html, Json as ResponseJson, Sse, sse::Event},
+    routing::{delete, get},
+};
+use db::models::{
+    board::Board,
+    board_share::{BoardShareLink, CreatedBoardShareLink, SharedBoardView},
+};
+use deployment::Deployment;
+use futures_util::Stream;
+use serde::Deserialize;
+use services::services::link_tokens::{self, LinkTokenKind};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_board_middleware,
+    routes::link_views::{self, LinkPage},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ShareLinkPath {
+    pub link_id: Uuid,
+}
+
+pub async fn get_board_share_links(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<BoardShareLink>>>, ApiError> {
+    let links = BoardShareLink::find_by_board(&deployment.db().pool, board.id).await?;
+    Ok(ResponseJson(ApiResponse::success(links)))
+}
+
+/// Create a share link; the token is only returned here
+pub async fn create_board_share_link(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<CreatedBoardShareLink>>, ApiError> {
+    let (token, token_hash) = LinkTokenKind::SharedBoard.generate();
+    let link = BoardShareLink::create(&deployment.db().pool, board.id, &token_hash).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "board_share_link_created",
+            serde_json::json!({ "board_id": board.id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(CreatedBoardShareLink {
+        link,
+        url: LinkTokenKind::SharedBoard.path(&token),
+        token,
+    })))
+}
+
+pub async fn delete_board_share_link(
+    Extension(board): Extension<Board>,
+    State(deployment): State<DeploymentImpl>,
+    Path(path): Path<ShareLinkPath>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let deleted = BoardShareLink::delete(&deployment.db().pool, board.id, path.link_id).await?;
+    if deleted == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Resolve a share token to its board, recording that it was used
+async fn shared_board(deployment: &DeploymentImpl, token: &str) -> Result<Board, ApiError> {
+    let invalid = || ApiError::Forbidden("Invalid share link".to_string());
+    if !LinkTokenKind::SharedBoard.matches(token) {
+        return Err(invalid());
+    }
+    let pool = &deployment.db().pool;
+    let link = BoardShareLink::find_by_hash(pool, &link_tokens::hash(token))
+        .await?
+        .ok_or_else(invalid)?;
+    BoardShareLink::touch(pool, link.id).await?;
+    Board::find_by_id(pool, link.board_id)
+        .await?
+        .ok_or_else(invalid)
+}
+
+pub async fn get_shared_board_view(
+    State(deployment): State<DeploymentImpl>,
+    Path(token): Path<String>,
+) -> Result<ResponseJson<ApiResponse<SharedBoardView>>, ApiError> {
+    let board = shared_board(&deployment, &token).await?;
+    let view = SharedBoardView::load(&deployment.db().pool, &board).await?;
+    Ok(ResponseJson(ApiResponse::success(view)))
+}
+
+/// Emits a `board` event after each change on the events stream, until the link is
+/// deleted
+pub async fn stream_shared_board(
+    State(deployment): State<DeploymentImpl>,
+    Path(token): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    shared_board(&deployment, &token).await?;
+
+    let refresh_deployment = deployment.clone();
+    let load = move || {
+        let deployment = refresh_deployment.clone();
+        let token = token.clone();
+        async move {
+            let board = shared_board(&deployment, &token).await?;
+            Ok::<_, ApiError>(SharedBoardView::load(&deployment.db().pool, &board).await?)
+        }
+    };
+    Ok(link_views::refresh_stream(deployment, "board", load).await)
+}
+
+pub async fn shared_board_page(
+    State(deployment): State<DeploymentImpl>,
+    Path(token): Path<String>,
+) -> Result<Html<String>, ApiError> {
+    shared_board(&deployment, &token).await?;
+    Ok(SHARED_BOARD_PAGE.render())
+}
+
+const SHARED_BOARD_PAGE: LinkPage = LinkPage {
+    event: "board",
+    style: r#"      body { margin: 0; padding: 24px; font-family: system-ui, sans-serif; background: #f6f6f6; color: #222; }
+      h1 { margin: 0 0 4px; font-size: 24px; }
+      #updated { color: #777; font-size: 13px; margin-bottom: 16px; }
+      #columns { display: flex; gap: 12px; align-items: flex-start; overflow-x: auto; }
+      .column { background: #ececec; border-top: 4px solid #999; border-radius: 6px; padding: 10px; min-width: 220px; max-width: 280px; flex: 0 0 auto; }
+      .column h2 { font-size: 14px; margin: 0 0 8px; }
+      .column h2 span { color: #777; font-weight: normal; }
+      .task { background: #fff; border-radius: 4px; padding: 8px; margin-bottom: 6px; box-shadow: 0 1px 2px rgba(0, 0, 0, 0.08); }
+      .title { font-size: 14px; }
+      .meta { color: #777; font-size: 12px; margin-top: 4px; }
+      .inprogress .meta { color: #2563eb; }
+      .awaitingresponse .meta { color: #b45309; }
+      .empty { color: #999; font-size: 13px; }
+"#,
+    body: r#"    <h1 id="board"></h1>
+    <div id="updated"></div>
+    <div id="columns"></div>
+"#,
+    script: r#"      const STATES = { queued: 'Queued', inprogress: 'In progress', awaitingresponse: 'Waiting for input', transitioning: 'Moving' };
+      function render(b) {
+        document.title = b.board_name;
+        document.getElementById('board').textContent = b.board_name;
+        document.getElementById('updated').textContent =
+          'Updated ' + new Date(b.generated_at).toLocaleTimeString();
+        document.getElementById('columns').replaceChildren(...b.columns.map((c) => {
+          const column = el('div', undefined, 'column');
+          if (c.color) column.style.borderTopColor = c.color;
+          const heading = el('h2', c.name + ' ');
+          heading.append(el('span', String(c.tasks.length)));
+          column.append(heading);
+          if (!c.tasks.length) column.append(el('div', 'No tasks', 'empty'));
+          for (const t of c.tasks) {
+            const card = el('div', undefined, 'task ' + t.task_state);
+            card.append(el('div', t.title, 'title'));
+            const meta = [t.project_name];
+            if (!c.is_terminal) meta.push(STATES[t.task_state] || t.task_state);
+            card.append(el('div', meta.join(' · '), 'meta'));
+            column.append(card);
+          }
+          return column;
+        }));
+      }
+"#,
+};
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let share_links_router = Router::new()
+        .route(
+            "/",
+            get(get_board_share_links).post(create_board_share_link),
+        )
+        .route("/{link_id}", delete(delete_board_share_link))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_board_middleware,
+        ));
+
+    Router::new()
+        .nest("/boards/{board_id}/share", share_links_router)
+        .route("/shared-boards/{token}", get(shared_board_page))
+        .route("/shared-boards/{token}/view", get(get_shared_board_view))
+        .route("/shared-boards/{token}/stream", get(stream_shared_board))
+}
@@ -1,10 +1,13 @@
 use axum::{
     Json, Router,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     response::Json as ResponseJson,
-    routing::{delete, post},
+    routing::{delete, get, post},
+};
+use db::models::{
+    shared_task_sync::{ConflictResolution, SharedTaskConflict},
+    task::Task,
 };
-use db::models::task::Task;
 use deployment::Deployment;
 use services::services::remote_types::SharedTaskResponse;
 use serde::Deserialize;
@@ -21,6 +24,18 @@ pub struct AssignSharedTaskRequest {
     pub new_assignee_user_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SharedTaskConflictsQuery {
+    pub project_id: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export)]
+pub struct ResolveSharedTaskConflictRequest {
+    /// Which side's values to keep for the conflicting fields
+    pub resolution: ConflictResolution,
+}
+
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route(
@@ -32,6 +47,11 @@ pub fn router() -> Router<DeploymentImpl> {
             "/shared-tasks/link-to-local",
             post(link_shared_task_to_local),
         )
+        .route("/shared-tasks/conflicts", get(list_shared_task_conflicts))
+        .route(
+            "/shared-tasks/conflicts/{conflict_id}/resolve",
+            post(resolve_shared_task_conflict),
+        )
 }
 
 pub async fn assign_shared_task(
@@ -101,3 +121,40 @@ pub async fn link_shared_task_to_local(
 
     Ok(ResponseJson(ApiResponse::success(task)))
 }
+
+/// Conflicts between a project's tasks and their shared copies awaiting resolution
+pub async fn list_shared_task_conflicts(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<SharedTaskConflictsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<SharedTaskConflict>>>, ApiError> {
+    let conflicts =
+        SharedTaskConflict::find_open_by_project(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(conflicts)))
+}
+
+/// Keep the local or the remote values of a conflict's fields and resume syncing the task
+pub async fn resolve_shared_task_conflict(
+    Path(conflict_id): Path<Uuid>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ResolveSharedTaskConflictRequest>,
+) -> Result<ResponseJson<ApiResponse<SharedTaskConflict>>, ApiError> {
+    let Ok(publisher) = deployment.share_publisher() else {
+        return Err(ShareError::MissingConfig("share publisher unavailable").into());
+    };
+
+    let conflict = publisher
+        .resolve_conflict(conflict_id, payload.resolution)
+        .await?;
+
+    let props = serde_json::json!({
+        "task_id": conflict.task_id,
+        "shared_task_id": conflict.shared_task_id,
+        "resolution": payload.resolution.as_str(),
+        "fields": conflict.fields,
+    });
+    deployment
+        .track_if_analytics_allowed("resolve_shared_task_conflict", props)
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(conflict)))
+}
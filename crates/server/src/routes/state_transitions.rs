@@ -5,19 +5,61 @@ use axum::{
     response::Json as ResponseJson,
     routing::get,
 };
+use std::str::FromStr;
+
 use db::models::{
+    agent::Agent,
     board::Board,
+    kanban_column::KanbanColumn,
     project::Project,
     state_transition::{CreateStateTransition, StateTransition, StateTransitionWithColumns, UpdateStateTransition},
+    transition_artifact_requirement::{
+        TransitionArtifactRequirement, UpsertTransitionArtifactRequirement,
+    },
+    transition_retry_policy::{TransitionRetryPolicy, UpsertTransitionRetryPolicy},
 };
 use deployment::Deployment;
+use executors::{
+    executors::BaseCodingAgent,
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
+use services::services::{
+    transition_condition::TransitionCondition,
+    workflow_lint::{self, StateTransitionWithWarnings},
+};
 use utils::response::ApiResponse;
+use uuid::Uuid;
 
 use crate::{
     DeploymentImpl, error::ApiError,
     middleware::{load_board_middleware, load_project_middleware, load_state_transition_middleware},
 };
 
+/// Reject condition values that look like expressions but don't parse, and answers
+/// of the source column that would be read as an expression unless quoted
+async fn validate_condition(
+    deployment: &DeploymentImpl,
+    from_column_id: Uuid,
+    condition_value: Option<&str>,
+) -> Result<(), ApiError> {
+    let Some(condition) = condition_value else {
+        return Ok(());
+    };
+    let parsed = TransitionCondition::parse(condition).map_err(|e| {
+        ApiError::BadRequest(format!("Invalid condition '{}': {}", condition, e))
+    })?;
+    if matches!(parsed, TransitionCondition::Expr(_))
+        && let Some(column) = KanbanColumn::find_by_id(&deployment.db().pool, from_column_id).await?
+        && workflow_lint::answer_options(&column).iter().any(|a| a == condition.trim())
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Condition '{}' is one of the column's answers; quote it (\"{}\") to match it as an answer",
+            condition, condition
+        )));
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Board-level transitions (default workflow for all projects using this board)
 // ============================================================================
@@ -31,12 +73,14 @@ pub async fn get_board_transitions(
     Ok(ResponseJson(ApiResponse::success(transitions)))
 }
 
-/// Create a board-level state transition
+/// Create a board-level state transition. Conditions that can never match
+/// are returned as warnings.
 pub async fn create_board_transition(
     Extension(board): Extension<Board>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateStateTransition>,
-) -> Result<ResponseJson<ApiResponse<StateTransition>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<StateTransitionWithWarnings>>, ApiError> {
+    validate_condition(&deployment, payload.from_column_id, payload.condition_value.as_deref()).await?;
     let transition = StateTransition::create_for_board(&deployment.db().pool, board.id, &payload).await?;
 
     deployment
@@ -52,7 +96,11 @@ pub async fn create_board_transition(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(transition)))
+    let warnings = workflow_lint::lint_saved_transition(&deployment.db().pool, &transition).await?;
+    Ok(ResponseJson(ApiResponse::success(StateTransitionWithWarnings {
+        transition,
+        warnings,
+    })))
 }
 
 // ============================================================================
@@ -68,12 +116,14 @@ pub async fn get_project_transitions(
     Ok(ResponseJson(ApiResponse::success(transitions)))
 }
 
-/// Create a project-level state transition
+/// Create a project-level state transition. Conditions that can never match
+/// are returned as warnings.
 pub async fn create_project_transition(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateStateTransition>,
-) -> Result<ResponseJson<ApiResponse<StateTransition>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<StateTransitionWithWarnings>>, ApiError> {
+    validate_condition(&deployment, payload.from_column_id, payload.condition_value.as_deref()).await?;
     let transition = StateTransition::create_for_project(&deployment.db().pool, project.id, &payload).await?;
 
     deployment
@@ -89,7 +139,11 @@ pub async fn create_project_transition(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(transition)))
+    let warnings = workflow_lint::lint_saved_transition(&deployment.db().pool, &transition).await?;
+    Ok(ResponseJson(ApiResponse::success(StateTransitionWithWarnings {
+        transition,
+        warnings,
+    })))
 }
 
 // ============================================================================
@@ -103,12 +157,14 @@ pub async fn get_transition(
     Ok(ResponseJson(ApiResponse::success(transition)))
 }
 
-/// Update a transition
+/// Update a transition. Conditions that can never match are returned as warnings.
 pub async fn update_transition(
     Extension(transition): Extension<StateTransition>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<UpdateStateTransition>,
-) -> Result<ResponseJson<ApiResponse<StateTransition>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<StateTransitionWithWarnings>>, ApiError> {
+    let from_column_id = payload.from_column_id.unwrap_or(transition.from_column_id);
+    validate_condition(&deployment, from_column_id, payload.condition_value.as_deref()).await?;
     let updated = StateTransition::update(&deployment.db().pool, transition.id, &payload).await?;
 
     deployment
@@ -125,7 +181,11 @@ pub async fn update_transition(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(updated)))
+    let warnings = workflow_lint::lint_saved_transition(&deployment.db().pool, &updated).await?;
+    Ok(ResponseJson(ApiResponse::success(StateTransitionWithWarnings {
+        transition: updated,
+        warnings,
+    })))
 }
 
 /// Delete a transition
@@ -155,10 +215,155 @@ pub async fn delete_transition(
     }
 }
 
+// ============================================================================
+// Retry policy (cheaper executor profile for repeated else-path retries)
+// ============================================================================
+
+/// Get the retry policy for a transition (null if none is configured)
+pub async fn get_retry_policy(
+    Extension(transition): Extension<StateTransition>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<TransitionRetryPolicy>>>, ApiError> {
+    let policy =
+        TransitionRetryPolicy::find_by_transition_id(&deployment.db().pool, transition.id).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// Create or replace the retry policy for a transition
+pub async fn upsert_retry_policy(
+    Extension(transition): Extension<StateTransition>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertTransitionRetryPolicy>,
+) -> Result<ResponseJson<ApiResponse<TransitionRetryPolicy>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if transition.else_column_id.is_none() {
+        return Err(ApiError::BadRequest(
+            "Retry policies require the transition to have an else path".to_string(),
+        ));
+    }
+    if payload.downgrade_after.is_some_and(|n| n < 1) {
+        return Err(ApiError::BadRequest(
+            "downgrade_after must be at least 1".to_string(),
+        ));
+    }
+
+    // Resolve the executor retries will run with so the variant can be checked
+    let executor = match payload.executor.as_deref() {
+        Some(executor) => Some(BaseCodingAgent::from_str(executor).map_err(|_| {
+            ApiError::BadRequest(format!("Unknown executor '{}'", executor))
+        })?),
+        None => {
+            let else_column = match transition.else_column_id {
+                Some(column_id) => KanbanColumn::find_by_id(pool, column_id).await?,
+                None => None,
+            };
+            let agent = match else_column.and_then(|c| c.agent_id) {
+                Some(agent_id) => Agent::find_by_id(pool, agent_id).await?,
+                None => None,
+            };
+            agent.and_then(|a| BaseCodingAgent::from_str(&a.executor).ok())
+        }
+    };
+    if let Some(executor) = executor {
+        let profile = ExecutorProfileId {
+            executor,
+            variant: payload.variant.clone(),
+        };
+        if ExecutorConfigs::get_cached().get_coding_agent(&profile).is_none() {
+            return Err(ApiError::BadRequest(format!(
+                "Unknown executor profile '{}'",
+                profile
+            )));
+        }
+    }
+
+    let policy = TransitionRetryPolicy::upsert(pool, transition.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// Remove the retry policy from a transition
+pub async fn delete_retry_policy(
+    Extension(transition): Extension<StateTransition>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected =
+        TransitionRetryPolicy::delete(&deployment.db().pool, transition.id).await?;
+    if rows_affected == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+// ============================================================================
+// Artifact requirement (success path needs an artifact written during the stage)
+// ============================================================================
+
+/// Get the artifact requirement for a transition (null if none is configured)
+pub async fn get_artifact_requirement(
+    Extension(transition): Extension<StateTransition>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<TransitionArtifactRequirement>>>, ApiError> {
+    let requirement =
+        TransitionArtifactRequirement::find_by_transition_id(&deployment.db().pool, transition.id)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(requirement)))
+}
+
+/// Require an artifact type before the transition's success path fires. Without an
+/// else path a task missing the artifact stays in its column.
+pub async fn upsert_artifact_requirement(
+    Extension(transition): Extension<StateTransition>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertTransitionArtifactRequirement>,
+) -> Result<ResponseJson<ApiResponse<TransitionArtifactRequirement>>, ApiError> {
+    let requirement =
+        TransitionArtifactRequirement::upsert(&deployment.db().pool, transition.id, &payload)
+            .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "transition_artifact_requirement_set",
+            serde_json::json!({
+                "transition_id": transition.id.to_string(),
+                "artifact_type": requirement.artifact_type,
+                "has_else_path": transition.else_column_id.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(requirement)))
+}
+
+/// Remove the artifact requirement from a transition
+pub async fn delete_artifact_requirement(
+    Extension(transition): Extension<StateTransition>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected =
+        TransitionArtifactRequirement::delete(&deployment.db().pool, transition.id).await?;
+    if rows_affected == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     // Routes for a specific transition (requires transition_id)
     let transition_router = Router::new()
         .route("/", get(get_transition).put(update_transition).delete(delete_transition))
+        .route(
+            "/retry-policy",
+            get(get_retry_policy)
+                .put(upsert_retry_policy)
+                .delete(delete_retry_policy),
+        )
+        .route(
+            "/artifact-requirement",
+            get(get_artifact_requirement)
+                .put(upsert_artifact_requirement)
+                .delete(delete_artifact_requirement),
+        )
         .layer(from_fn_with_state(
             deployment.clone(),
             load_state_transition_middleware,
@@ -0,0 +1,129 @@
+use axum::{
+    Router,
+    extract::{
+        Json, Path, State,
+        ws::{WebSocket, WebSocketUpgrade},
+    },
+    response::{IntoResponse, Json as ResponseJson},
+    routing::{delete, get},
+};
+use db::models::{
+    task::Task,
+    task_label::TaskLabel,
+    task_subscription::{CreateTaskSubscription, TaskSubscription},
+};
+use deployment::Deployment;
+use futures_util::{SinkExt, StreamExt, TryStreamExt};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Tasks and labels the user is watching
+pub async fn list_subscriptions(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskSubscription>>>, ApiError> {
+    let subscriptions = TaskSubscription::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(subscriptions)))
+}
+
+/// Watch a task or a label; watching one already watched is a no-op
+pub async fn create_subscription(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskSubscription>,
+) -> Result<ResponseJson<ApiResponse<TaskSubscription>>, ApiError> {
+    let pool = &deployment.db().pool;
+    match (payload.task_id, payload.label_id) {
+        (Some(task_id), None) => {
+            Task::find_by_id(pool, task_id)
+                .await?
+                .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+        }
+        (None, Some(label_id)) => {
+            TaskLabel::find_by_id(pool, label_id)
+                .await?
+                .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+        }
+        _ => {
+            return Err(ApiError::BadRequest(
+                "Provide either task_id or label_id".to_string(),
+            ));
+        }
+    }
+
+    let subscription = TaskSubscription::create(pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_subscription_created",
+            serde_json::json!({
+                "subscription_id": subscription.id.to_string(),
+                "watches_label": subscription.label_id.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(subscription)))
+}
+
+pub async fn delete_subscription(
+    State(deployment): State<DeploymentImpl>,
+    Path(subscription_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let deleted = TaskSubscription::delete(&deployment.db().pool, subscription_id).await?;
+    if deleted == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn stream_watched_tasks_ws(
+    ws: WebSocketUpgrade,
+    State(deployment): State<DeploymentImpl>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_watched_tasks_ws(socket, deployment).await {
+            tracing::warn!("watched tasks WS closed: {}", e);
+        }
+    })
+}
+
+async fn handle_watched_tasks_ws(
+    socket: WebSocket,
+    deployment: DeploymentImpl,
+) -> anyhow::Result<()> {
+    let mut stream = deployment
+        .events()
+        .stream_watched_tasks_raw()
+        .await?
+        .map_ok(|msg| msg.to_ws_message_unchecked());
+
+    let (mut sender, mut receiver) = socket.split();
+
+    // Drain (and ignore) any client->server messages so pings/pongs work
+    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(msg) => {
+                if sender.send(msg).await.is_err() {
+                    break; // client disconnected
+                }
+            }
+            Err(e) => {
+                tracing::error!("stream error: {}", e);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/", get(list_subscriptions).post(create_subscription))
+        .route("/stream/ws", get(stream_watched_tasks_ws))
+        .route("/{subscription_id}", delete(delete_subscription));
+
+    Router::new().nest("/me/subscriptions", inner)
+}
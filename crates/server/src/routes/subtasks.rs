@@ -0,0 +1,126 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    context_artifact::{ArtifactType, ContextArtifact},
+    task::{SubtaskProgress, Task},
+    task_event::ActorType,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{events::task_patch, plan_subtasks};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::{tasks::task_to_status, users},
+};
+
+#[derive(Debug, Serialize, TS)]
+pub struct TaskSubtasks {
+    pub progress: SubtaskProgress,
+    pub subtasks: Vec<Task>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateSubtasksFromPlanRequest {
+    /// The iplan artifact to use; defaults to the latest plan written for the task
+    #[serde(default)]
+    pub artifact_id: Option<Uuid>,
+}
+
+async fn find_task(deployment: &DeploymentImpl, task_id: Uuid) -> Result<Task, ApiError> {
+    Task::find_by_id(&deployment.db().pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn get_subtasks(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<TaskSubtasks>>, ApiError> {
+    let task = find_task(&deployment, task_id).await?;
+    let pool = &deployment.db().pool;
+    let progress = Task::subtask_progress(pool, task.id).await?;
+    let subtasks = Task::find_subtasks(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(TaskSubtasks {
+        progress,
+        subtasks,
+    })))
+}
+
+/// Create a backlog sub-task for each work item of the task's implementation plan.
+/// Items that already have a sub-task with the same title are skipped.
+pub async fn create_subtasks_from_plan(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<CreateSubtasksFromPlanRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let task = find_task(&deployment, task_id).await?;
+    let pool = &deployment.db().pool;
+
+    let plan = match payload.artifact_id {
+        Some(artifact_id) => ContextArtifact::find_by_id(pool, artifact_id)
+            .await?
+            .filter(|a| a.project_id == task.project_id)
+            .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?,
+        None => ContextArtifact::find_latest_plan_for_task(pool, task.id)
+            .await?
+            .ok_or_else(|| ApiError::BadRequest("Task has no implementation plan".to_string()))?,
+    };
+    if ArtifactType::from_str(&plan.artifact_type) != Some(ArtifactType::IPlan) {
+        return Err(ApiError::BadRequest(
+            "Sub-tasks can only be created from an implementation plan".to_string(),
+        ));
+    }
+    if plan_subtasks::parse_plan_items(&plan.content).is_empty() {
+        return Err(ApiError::BadRequest(
+            "The plan has no work items".to_string(),
+        ));
+    }
+
+    let column = plan_subtasks::backlog_column(pool, &task).await?;
+    let subtasks = plan_subtasks::create_subtasks(
+        pool,
+        &task,
+        &plan,
+        column.as_ref(),
+        ActorType::User,
+        users::actor_id(&deployment).await,
+    )
+    .await?;
+    for subtask in &subtasks {
+        deployment
+            .events()
+            .msg_store()
+            .push_patch(task_patch::add(&task_to_status(subtask)));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "subtasks_created",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+                "subtask_count": subtasks.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(subtasks)))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/tasks/{task_id}/subtasks", get(get_subtasks))
+        .route(
+            "/tasks/{task_id}/subtasks/from-plan",
+            post(create_subtasks_from_plan),
+        )
+}
@@ -10,15 +10,24 @@ use deployment::Deployment;
 use serde::Deserialize;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::IntoParams;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_tag_middleware};
 
-#[derive(Deserialize, TS)]
+#[derive(Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct TagSearchParams {
     #[serde(default)]
     pub search: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/tags",
+    tag = "tags",
+    params(TagSearchParams),
+    responses((status = 200, body = ApiResponse<Vec<Tag>>))
+)]
 pub async fn get_tags(
     State(deployment): State<DeploymentImpl>,
     Query(params): Query<TagSearchParams>,
@@ -34,6 +43,13 @@ pub async fn get_tags(
     Ok(ResponseJson(ApiResponse::success(tags)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/tags",
+    tag = "tags",
+    request_body = CreateTag,
+    responses((status = 200, description = "Created tag", body = ApiResponse<Tag>))
+)]
 pub async fn create_tag(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTag>,
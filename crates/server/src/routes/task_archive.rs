@@ -0,0 +1,128 @@
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use chrono::{DateTime, Utc};
+use db::models::task::Task;
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::events::task_patch;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::tasks::task_to_status};
+
+#[derive(Debug, Serialize, TS)]
+pub struct TaskArchive {
+    pub task_id: Uuid,
+    pub archived: bool,
+    #[ts(type = "Date | null")]
+    pub archived_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ArchivedTasksQuery {
+    pub project_id: Uuid,
+}
+
+async fn find_task(deployment: &DeploymentImpl, task_id: Uuid) -> Result<Task, ApiError> {
+    Task::find_by_id(&deployment.db().pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn get_archived_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ArchivedTasksQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let tasks = Task::find_archived_by_project(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+/// Move a task out of the board. It keeps its workspaces and history, but no longer
+/// shows up in task lists or the task stream until it is unarchived.
+pub async fn archive_task(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<TaskArchive>>, ApiError> {
+    let task = find_task(&deployment, task_id).await?;
+    let pool = &deployment.db().pool;
+    if task.archived_at.is_some() {
+        return Err(ApiError::Conflict("Task is already archived".to_string()));
+    }
+    if Task::has_active_attempt(pool, task.id).await? {
+        return Err(ApiError::Conflict(
+            "Stop the running attempt before archiving the task".to_string(),
+        ));
+    }
+
+    let archived_at = Task::set_archived(pool, task.id, true).await?;
+    deployment
+        .events()
+        .msg_store()
+        .push_patch(task_patch::remove(task.id));
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_archived",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(TaskArchive {
+        task_id: task.id,
+        archived: true,
+        archived_at,
+    })))
+}
+
+/// Put an archived task back on the board, in the column it was archived from
+pub async fn unarchive_task(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<TaskArchive>>, ApiError> {
+    let task = find_task(&deployment, task_id).await?;
+    let pool = &deployment.db().pool;
+    if task.archived_at.is_none() {
+        return Err(ApiError::Conflict("Task is not archived".to_string()));
+    }
+
+    Task::set_archived(pool, task.id, false).await?;
+    if let Some(task) = Task::find_by_id(pool, task.id).await? {
+        deployment
+            .events()
+            .msg_store()
+            .push_patch(task_patch::add(&task_to_status(&task)));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_unarchived",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(TaskArchive {
+        task_id: task.id,
+        archived: false,
+        archived_at: None,
+    })))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/tasks/archived", get(get_archived_tasks))
+        .route(
+            "/tasks/{task_id}/archive",
+            post(archive_task).delete(unarchive_task),
+        )
+}
@@ -3,6 +3,7 @@ pub mod cursor_setup;
 pub mod gh_cli_setup;
 pub mod images;
 pub mod pr;
+pub mod review;
 pub mod util;
 
 use std::{
@@ -16,24 +17,29 @@ use axum::{
         Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
-    http::StatusCode,
+    http::{StatusCode, header},
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
-    routing::{get, post},
+    routing::{delete, get, post},
 };
 use db::models::{
     board::Board,
     execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    execution_process_repo_state::ExecutionProcessRepoState,
     kanban_column::KanbanColumn,
     merge::{Merge, MergeStatus, PrMerge, PullRequestInfo},
     project::Project,
     project_repo::ProjectRepo,
     repo::{Repo, RepoError},
+    repo_forge_settings::{ForgeKind, RepoForgeSettings},
+    repo_merge_settings::{MergeStrategy, RepoMergeSettings},
     session::{CreateSession, Session},
     task::{Task, TaskRelationships, TaskState, TaskStatus, TaskWithAttemptStatus},
     task_dependency::TaskDependency,
+    task_event::{ActorType, CreateTaskEvent, TaskEvent},
     task_trigger::{TaskTrigger, TriggerCondition},
     workspace::{CreateWorkspace, Workspace, WorkspaceError},
+    workspace_orientation::WorkspaceOrientation,
     workspace_repo::{CreateWorkspaceRepo, RepoWithTargetBranch, WorkspaceRepo},
 };
 use deployment::Deployment;
@@ -49,9 +55,14 @@ use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
     container::ContainerService,
+    cost_estimate,
+    dev_server_preview::DevServerPreview,
     events::task_patch,
-    git::{ConflictOp, GitCliError, GitServiceError},
+    git::{ConflictOp, GitCliError, GitServiceError, MergeOptions},
     github::GitHubService,
+    mcp_credentials::McpCredential,
+    module_memory_refresh::MergedRepoChanges,
+    worktree_manager::{WorktreeCleanup, WorktreeManager},
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
@@ -61,8 +72,9 @@ use uuid::Uuid;
 use crate::{
     DeploymentImpl, error::ApiError, middleware::load_workspace_middleware,
     routes::{
-        task_attempts::gh_cli_setup::GhCliSetupError,
+        task_attempts::{gh_cli_setup::GhCliSetupError, util::restore_worktrees_to_process},
         task_groups::check_and_start_next_group_tasks,
+        users,
     },
 };
 
@@ -122,7 +134,52 @@ pub struct CreateTaskAttemptBody {
 #[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
 pub struct WorkspaceRepoInput {
     pub repo_id: Uuid,
-    pub target_branch: String,
+    /// Defaults to the repo's default target branch in the project, else the branch
+    /// checked out in the repo
+    #[serde(default)]
+    pub target_branch: Option<String>,
+}
+
+/// Target branch of each repo for a new attempt: the requested one, else the repo's
+/// default target branch in the project, else the branch checked out in the repo
+pub(crate) async fn resolve_workspace_repos(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    repos: &[WorkspaceRepoInput],
+) -> Result<Vec<CreateWorkspaceRepo>, ApiError> {
+    let pool = &deployment.db().pool;
+    let defaults = ProjectRepo::find_default_target_branches(pool, project_id).await?;
+
+    let mut resolved = Vec::with_capacity(repos.len());
+    for input in repos {
+        let requested = input
+            .target_branch
+            .as_deref()
+            .map(str::trim)
+            .filter(|branch| !branch.is_empty());
+        let target_branch = match requested.or(defaults.get(&input.repo_id).map(String::as_str)) {
+            Some(branch) => branch.to_string(),
+            None => {
+                let repo = Repo::find_by_id(pool, input.repo_id)
+                    .await?
+                    .ok_or(SqlxError::RowNotFound)?;
+                deployment
+                    .git()
+                    .get_current_branch(&repo.path)
+                    .map_err(|_| {
+                        ApiError::BadRequest(format!(
+                            "Could not detect the current branch of {}; pass a target_branch",
+                            repo.name
+                        ))
+                    })?
+            }
+        };
+        resolved.push(CreateWorkspaceRepo {
+            repo_id: input.repo_id,
+            target_branch,
+        });
+    }
+    Ok(resolved)
 }
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -174,6 +231,15 @@ pub async fn create_task_attempt(
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
+    let workspace_repos = resolve_workspace_repos(&deployment, project.id, &payload.repos).await?;
+
+    // Refuse up front, before a workspace is set up; `start_execution` checks again for
+    // starts that don't come through here
+    let budget = deployment.config().read().await.attempt_budget.max_estimated_tokens;
+    if let Some(message) = cost_estimate::budget_exceeded(pool, &task, budget).await? {
+        return Err(ApiError::BadRequest(message));
+    }
+
     let agent_working_dir = project
         .default_agent_working_dir
         .as_ref()
@@ -194,9 +260,10 @@ pub async fn create_task_attempt(
         }
         None => {
             // Legacy path: Create per-task workspace (for ungrouped tasks)
+            let executor = executor_profile_id.executor.to_string();
             let git_branch_name = deployment
                 .container()
-                .git_branch_from_workspace(&attempt_id, &task.title)
+                .git_branch_from_workspace(&attempt_id, &task, Some(&executor))
                 .await;
 
             tracing::info!(
@@ -217,15 +284,6 @@ pub async fn create_task_attempt(
         }
     };
 
-    let workspace_repos: Vec<CreateWorkspaceRepo> = payload
-        .repos
-        .iter()
-        .map(|r| CreateWorkspaceRepo {
-            repo_id: r.repo_id,
-            target_branch: r.target_branch.clone(),
-        })
-        .collect();
-
     WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
     if let Err(err) = deployment
         .container()
@@ -349,7 +407,20 @@ async fn handle_task_attempt_diff_ws(
 
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct MergeTaskAttemptRequest {
+    /// Repo to merge; every repo of the attempt when omitted
+    #[serde(default)]
+    pub repo_id: Option<Uuid>,
+}
+
+/// Outcome of merging one repo of an attempt
+#[derive(Debug, Serialize, TS)]
+pub struct RepoMergeResult {
     pub repo_id: Uuid,
+    pub repo_name: String,
+    pub target_branch: String,
+    pub merge_commit: String,
+    pub strategy: MergeStrategy,
+    pub branch_deleted: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -357,29 +428,51 @@ pub struct PushTaskAttemptRequest {
     pub repo_id: Uuid,
 }
 
+/// Merge the attempt's branch into each repo's target branch, following the repo's
+/// merge settings (clean rebase requirement, squash or merge commit, branch deletion)
 #[axum::debug_handler]
 pub async fn merge_task_attempt(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<MergeTaskAttemptRequest>,
-) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<Vec<RepoMergeResult>>>, ApiError> {
     let pool = &deployment.db().pool;
 
-    let workspace_repo =
-        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, request.repo_id)
-            .await?
-            .ok_or(RepoError::NotFound)?;
-
-    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
-        .await?
-        .ok_or(RepoError::NotFound)?;
+    let mut repos = WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id)
+        .await?;
+    if let Some(repo_id) = request.repo_id {
+        repos.retain(|r| r.repo.id == repo_id);
+    }
+    if repos.is_empty() {
+        return Err(RepoError::NotFound.into());
+    }
 
     let container_ref = deployment
         .container()
         .ensure_container_exists(&workspace)
         .await?;
     let workspace_path = Path::new(&container_ref);
-    let worktree_path = workspace_path.join(repo.name);
+
+    // Check every repo's rebase requirement before merging any of them
+    let mut settings = Vec::with_capacity(repos.len());
+    for repo in &repos {
+        let repo_settings = RepoMergeSettings::for_repo(pool, repo.repo.id).await?;
+        if repo_settings.require_clean_rebase {
+            let (_, behind) = deployment.git().get_branch_status(
+                &repo.repo.path,
+                &workspace.branch,
+                &repo.target_branch,
+            )?;
+            if behind > 0 {
+                return Err(ApiError::Conflict(format!(
+                    "'{}' requires a clean rebase: '{}' is {} commit(s) ahead of '{}'. \
+                     Rebase the attempt before merging.",
+                    repo.repo.name, repo.target_branch, behind, workspace.branch
+                )));
+            }
+        }
+        settings.push(repo_settings);
+    }
 
     let task = workspace
         .parent_task(pool)
@@ -398,22 +491,76 @@ pub async fn merge_task_attempt(
         commit_message.push_str(description);
     }
 
-    let merge_commit_id = deployment.git().merge_changes(
-        &repo.path,
-        &worktree_path,
-        &workspace.branch,
-        &workspace_repo.target_branch,
-        &commit_message,
-    )?;
-
-    Merge::create_direct(
+    let latest_process = ExecutionProcess::find_latest_by_workspace_and_run_reason(
         pool,
         workspace.id,
-        workspace_repo.repo_id,
-        &workspace_repo.target_branch,
-        &merge_commit_id,
+        &ExecutionProcessRunReason::CodingAgent,
     )
     .await?;
+
+    let mut results = Vec::with_capacity(repos.len());
+    let mut merged = Vec::with_capacity(repos.len());
+    for (repo, repo_settings) in repos.iter().zip(&settings) {
+        let worktree_path = workspace_path.join(&repo.repo.name);
+        let strategy = repo_settings.strategy();
+        // Read what the branch changed before the merge moves the target branch
+        let changed_paths = deployment
+            .git()
+            .get_base_commit(&repo.repo.path, &workspace.branch, &repo.target_branch)
+            .and_then(|base| deployment.git().get_changed_paths(&worktree_path, &base))
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to list changed paths of {}: {}", repo.repo.name, e);
+                Vec::new()
+            });
+        let merge_commit_id = deployment.git().merge_changes_with_options(
+            &repo.repo.path,
+            &worktree_path,
+            &workspace.branch,
+            &repo.target_branch,
+            &commit_message,
+            MergeOptions {
+                merge_commit: strategy == MergeStrategy::MergeCommit,
+                require_up_to_date: repo_settings.require_clean_rebase,
+            },
+        )?;
+
+        Merge::create_direct(
+            pool,
+            workspace.id,
+            repo.repo.id,
+            &repo.target_branch,
+            &merge_commit_id,
+        )
+        .await?;
+        if let Some(process) = &latest_process {
+            ExecutionProcessRepoState::set_merge_commit(
+                pool,
+                process.id,
+                repo.repo.id,
+                &merge_commit_id,
+            )
+            .await?;
+        }
+
+        let branch_deleted = repo_settings.delete_branch_after_merge
+            && delete_merged_branch(&deployment, &repo.repo, &worktree_path, &workspace.branch)
+                .await;
+
+        merged.push(MergedRepoChanges {
+            repo_name: repo.repo.name.clone(),
+            merge_commit: merge_commit_id.clone(),
+            changed_paths,
+        });
+        results.push(RepoMergeResult {
+            repo_id: repo.repo.id,
+            repo_name: repo.repo.name.clone(),
+            target_branch: repo.target_branch.clone(),
+            merge_commit: merge_commit_id,
+            strategy,
+            branch_deleted,
+        });
+    }
+
     Task::update_status(pool, task.id, TaskStatus::Done).await?;
 
     // Satisfy all dependencies waiting on this task
@@ -426,6 +573,23 @@ pub async fn merge_task_attempt(
         tracing::error!("Failed to start next group tasks after merge for task {}: {}", task.id, e);
     }
 
+    // Refresh the module memories of what the merge touched, if the project opted in
+    let background = deployment.clone();
+    let merged_workspace = workspace.clone();
+    tokio::spawn(async move {
+        if let Err(e) = background
+            .container()
+            .refresh_module_memories(&merged_workspace, &merged)
+            .await
+        {
+            tracing::error!(
+                "Failed to start module memory refresh for workspace {}: {}",
+                merged_workspace.id,
+                e
+            );
+        }
+    });
+
     // Stop any running dev servers for this workspace
     let dev_servers =
         ExecutionProcess::find_running_dev_servers_by_workspace(pool, workspace.id).await?;
@@ -453,13 +617,7 @@ pub async fn merge_task_attempt(
 
     // Try broadcast update to other users in organization
     if let Ok(publisher) = deployment.share_publisher() {
-        if let Err(err) = publisher.update_shared_task_by_id(task.id).await {
-            tracing::warn!(
-                ?err,
-                "Failed to propagate shared task update for {}",
-                task.id
-            );
-        }
+        publisher.propagate_task_update(task.id).await;
     } else {
         tracing::debug!(
             "Share publisher unavailable; skipping remote update for {}",
@@ -473,6 +631,7 @@ pub async fn merge_task_attempt(
             serde_json::json!({
                 "task_id": task.id.to_string(),
                 "workspace_id": workspace.id.to_string(),
+                "repo_count": results.len(),
             }),
         )
         .await;
@@ -480,7 +639,310 @@ pub async fn merge_task_attempt(
     // Execute auto-start triggers for dependent tasks
     execute_task_triggers(&deployment, task.id, TriggerCondition::Merged).await;
 
-    Ok(ResponseJson(ApiResponse::success(())))
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
+/// Remove the repo's worktree and delete the merged task branch. Failures are logged
+/// rather than returned since the merge itself already succeeded.
+async fn delete_merged_branch(
+    deployment: &DeploymentImpl,
+    repo: &Repo,
+    worktree_path: &Path,
+    branch: &str,
+) -> bool {
+    let cleanup = WorktreeCleanup::new(worktree_path.to_path_buf(), Some(repo.path.clone()));
+    if let Err(e) = WorktreeManager::cleanup_worktree(&cleanup).await {
+        tracing::warn!(
+            "Failed to remove worktree {} before deleting branch {}: {}",
+            worktree_path.display(),
+            branch,
+            e
+        );
+        return false;
+    }
+    match deployment.git().delete_local_branch(&repo.path, branch) {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("Failed to delete merged branch {} in {}: {}", branch, repo.name, e);
+            false
+        }
+    }
+}
+
+/// Where a patch applied to a workspace came from
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchSource {
+    /// Proposed by an agent in a dry-run column
+    DryRun,
+    /// Produced by a tool outside Vibe Kanban
+    External,
+    /// Written by a person
+    Human,
+}
+
+impl PatchSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PatchSource::DryRun => "dry_run",
+            PatchSource::External => "external",
+            PatchSource::Human => "human",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ApplyPatchRequest {
+    pub repo_id: Uuid,
+    /// Unified diff with paths relative to the repo root
+    pub patch: String,
+    pub source: PatchSource,
+    /// Identifies the patch at its source, e.g. a dry-run patch ID or a tool name
+    #[serde(default)]
+    pub source_ref: Option<String>,
+    /// Subject of the commit; defaults to one naming the source
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct AppliedPatch {
+    pub repo_id: Uuid,
+    pub commit_hash: String,
+    pub commit_message: String,
+}
+
+/// Apply a unified diff to one of the workspace's repos and commit it. The commit
+/// message records where the patch came from and who applied it, and so does a
+/// `patch_applied` task event. Fails without changing the worktree if the patch
+/// doesn't apply.
+pub async fn apply_task_attempt_patch(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    agent: Option<Extension<McpCredential>>,
+    Json(request): Json<ApplyPatchRequest>,
+) -> Result<ResponseJson<ApiResponse<AppliedPatch>>, ApiError> {
+    if request.patch.trim().is_empty() {
+        return Err(ApiError::BadRequest("patch is empty".to_string()));
+    }
+    let pool = &deployment.db().pool;
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, request.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "A process is running in this workspace; apply the patch once it finishes"
+                .to_string(),
+        ));
+    }
+
+    let (actor_type, actor_id, applied_by) = match &agent {
+        Some(Extension(credential)) => {
+            let id = credential.execution_process_id.to_string();
+            let applied_by = format!("agent (execution {id})");
+            (ActorType::Agent, Some(id), applied_by)
+        }
+        None => {
+            let user = users::current_user(&deployment).await?;
+            let applied_by = user
+                .as_ref()
+                .map(|user| user.label().to_string())
+                .unwrap_or_else(|| "user".to_string());
+            (ActorType::User, user.map(|user| user.id.to_string()), applied_by)
+        }
+    };
+    let source = match &request.source_ref {
+        Some(source_ref) => format!("{} ({})", request.source.as_str(), source_ref),
+        None => request.source.as_str().to_string(),
+    };
+    let subject = request
+        .message
+        .as_deref()
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("Apply {} patch", request.source.as_str().replace('_', "-")));
+    let commit_message = format!("{subject}\n\nPatch-Source: {source}\nApplied-By: {applied_by}");
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let worktree_path = Path::new(&container_ref).join(&repo.name);
+    let commit_hash = deployment
+        .git()
+        .apply_patch(&worktree_path, &request.patch, &commit_message)
+        .map_err(|e| ApiError::Conflict(format!("Patch does not apply: {e}")))?;
+
+    let event = CreateTaskEvent::patch_applied(
+        workspace.task_id,
+        workspace.id,
+        &repo.name,
+        &commit_hash,
+        &commit_message,
+        request.source.as_str(),
+        request.source_ref.as_deref(),
+        actor_type,
+        actor_id,
+    );
+    if let Err(e) = TaskEvent::create(pool, &event).await {
+        tracing::error!(
+            "Failed to record patch applied event for workspace {}: {}",
+            workspace.id,
+            e
+        );
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_patch_applied",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": repo.id.to_string(),
+                "source": request.source.as_str(),
+                "by_agent": agent.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(AppliedPatch {
+        repo_id: repo.id,
+        commit_hash,
+        commit_message,
+    })))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkspaceExportFormat {
+    /// A git bundle with the attempt's branch of each repo
+    #[default]
+    Bundle,
+    /// The branch's commits since each repo's target branch, as patches for `git am`
+    Patch,
+}
+
+impl WorkspaceExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            WorkspaceExportFormat::Bundle => "application/octet-stream",
+            WorkspaceExportFormat::Patch => "text/x-patch; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            WorkspaceExportFormat::Bundle => "bundle",
+            WorkspaceExportFormat::Patch => "patch",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportTaskAttemptQuery {
+    #[serde(default)]
+    pub format: WorkspaceExportFormat,
+    /// Only export this repo
+    pub repo_id: Option<Uuid>,
+}
+
+/// Download the attempt's committed work to take it to a machine or repo host that
+/// isn't connected to vibe-kanban. In multi-repo workspaces the bundle's branches are
+/// named `<repo>/<branch>` and each patch's subject is tagged with its repo, so
+/// `repo_id` picks one repo's series for `git am`. Uncommitted changes are left out.
+pub async fn export_task_attempt(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExportTaskAttemptQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let mut repos = WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id)
+        .await?;
+    if let Some(repo_id) = query.repo_id {
+        repos.retain(|r| r.repo.id == repo_id);
+    }
+    if repos.is_empty() {
+        return Err(RepoError::NotFound.into());
+    }
+    let multi_repo = repos.len() > 1;
+
+    let body = match query.format {
+        WorkspaceExportFormat::Bundle => {
+            let branches: Vec<_> = repos
+                .iter()
+                .map(|repo| {
+                    let name = if multi_repo {
+                        format!("{}/{}", repo.repo.name, workspace.branch)
+                    } else {
+                        workspace.branch.clone()
+                    };
+                    (repo.repo.path.as_path(), workspace.branch.as_str(), name)
+                })
+                .collect();
+            deployment.git().bundle_branches(&branches)?
+        }
+        WorkspaceExportFormat::Patch => {
+            let mut patches = String::new();
+            for repo in &repos {
+                let base = deployment.git().get_base_commit(
+                    &repo.repo.path,
+                    &workspace.branch,
+                    &repo.target_branch,
+                )?;
+                let subject_prefix = if multi_repo {
+                    format!("PATCH {}", repo.repo.name)
+                } else {
+                    "PATCH".to_string()
+                };
+                patches.push_str(&deployment.git().format_patches(
+                    &repo.repo.path,
+                    &workspace.branch,
+                    &base,
+                    &subject_prefix,
+                )?);
+            }
+            if patches.is_empty() {
+                return Err(ApiError::BadRequest(
+                    "The attempt has no commits to export".to_string(),
+                ));
+            }
+            patches.into_bytes()
+        }
+    };
+    let disposition = format!(
+        "attachment; filename=\"{}.{}\"",
+        workspace.branch.replace('/', "-"),
+        query.format.extension()
+    );
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_exported",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "format": query.format.extension(),
+                "repo_count": repos.len(),
+            }),
+        )
+        .await;
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                query.format.content_type().to_string(),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    ))
 }
 
 pub async fn push_task_attempt_branch(
@@ -490,9 +952,6 @@ pub async fn push_task_attempt_branch(
 ) -> Result<ResponseJson<ApiResponse<(), PushError>>, ApiError> {
     let pool = &deployment.db().pool;
 
-    let github_service = GitHubService::new()?;
-    github_service.check_token().await?;
-
     let workspace_repo =
         WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, request.repo_id)
             .await?
@@ -502,6 +961,11 @@ pub async fn push_task_attempt_branch(
         .await?
         .ok_or(RepoError::NotFound)?;
 
+    // Gitea/Forgejo remotes authenticate through git itself
+    if RepoForgeSettings::for_repo(pool, repo.id).await?.kind() == ForgeKind::Github {
+        GitHubService::new()?.check_token().await?;
+    }
+
     let container_ref = deployment
         .container()
         .ensure_container_exists(&workspace)
@@ -528,9 +992,6 @@ pub async fn force_push_task_attempt_branch(
 ) -> Result<ResponseJson<ApiResponse<(), PushError>>, ApiError> {
     let pool = &deployment.db().pool;
 
-    let github_service = GitHubService::new()?;
-    github_service.check_token().await?;
-
     let workspace_repo =
         WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, request.repo_id)
             .await?
@@ -540,6 +1001,11 @@ pub async fn force_push_task_attempt_branch(
         .await?
         .ok_or(RepoError::NotFound)?;
 
+    // Gitea/Forgejo remotes authenticate through git itself
+    if RepoForgeSettings::for_repo(pool, repo.id).await?.kind() == ForgeKind::Github {
+        GitHubService::new()?.check_token().await?;
+    }
+
     let container_ref = deployment
         .container()
         .ensure_container_exists(&workspace)
@@ -1264,6 +1730,37 @@ pub async fn start_dev_server(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// The workspace's running dev server port and preview proxy path, if detected
+pub async fn get_dev_server_preview(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<DevServerPreview>>>, ApiError> {
+    let preview = deployment
+        .container()
+        .dev_server_previews()
+        .get(workspace.id)
+        .await;
+    Ok(ResponseJson(ApiResponse::success(preview)))
+}
+
+/// The warm-up orientation cached for the workspace, if a warm-up column has built one
+pub async fn get_workspace_orientation(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<WorkspaceOrientation>>>, ApiError> {
+    let orientation = WorkspaceOrientation::find(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(orientation)))
+}
+
+/// Drop the cached orientation; the next warm-up column builds a fresh one
+pub async fn clear_workspace_orientation(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    WorkspaceOrientation::delete(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub async fn get_task_attempt_children(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1369,6 +1866,138 @@ pub async fn cancel_task_attempt(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RollbackQuery {
+    /// Task event marking the workflow stage to return to
+    pub to_event: Uuid,
+    #[serde(default)]
+    pub force_when_dirty: bool,
+}
+
+/// Roll an attempt back to an earlier workflow stage: reset the worktrees to the commits
+/// the workspace had when that stage began and move the task back to its column
+pub async fn rollback_task_attempt(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<RollbackQuery>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let task = Task::find_by_id(pool, workspace.task_id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    // Events are newest first; the target must belong to this task
+    let events = TaskEvent::find_by_task_id(pool, task.id).await?;
+    let target_idx = events
+        .iter()
+        .position(|e| e.id == query.to_event)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let target_event = &events[target_idx];
+
+    // The stage's column is where the task was at the time of the event
+    let column_id = events[target_idx..]
+        .iter()
+        .find_map(|e| e.to_column_id)
+        .ok_or_else(|| {
+            ApiError::BadRequest("Task was not in a column at that event".to_string())
+        })?;
+    let column = KanbanColumn::find_by_id(pool, column_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    // The first process after the event started from the commits of that stage
+    let target_process_id =
+        ExecutionProcess::find_first_in_workspace_since(pool, workspace.id, target_event.created_at)
+            .await?
+            .ok_or_else(|| {
+                ApiError::BadRequest("Nothing has run in this attempt since that event".to_string())
+            })?;
+
+    if !query.force_when_dirty {
+        let is_clean = deployment
+            .container()
+            .is_container_clean(&workspace)
+            .await
+            .unwrap_or(true);
+        if !is_clean {
+            return Err(ApiError::Conflict(
+                "Workspace has uncommitted changes; pass force_when_dirty=true to discard them"
+                    .to_string(),
+            ));
+        }
+    }
+
+    // Stop any running processes before touching the worktrees
+    deployment.container().try_stop(&workspace, false).await;
+
+    restore_worktrees_to_process(
+        &deployment,
+        pool,
+        &workspace,
+        target_process_id,
+        true,
+        query.force_when_dirty,
+    )
+    .await?;
+
+    let commits: serde_json::Map<String, serde_json::Value> =
+        ExecutionProcessRepoState::find_by_execution_process_id(pool, target_process_id)
+            .await?
+            .into_iter()
+            .filter_map(|state| {
+                state
+                    .before_head_commit
+                    .map(|commit| (state.repo_id.to_string(), serde_json::Value::String(commit)))
+            })
+            .collect();
+
+    Task::update_column_id(pool, task.id, Some(column.id)).await?;
+    Task::update_status(pool, task.id, column.status.clone()).await?;
+    Task::update_task_state(pool, task.id, TaskState::Queued).await?;
+
+    let mut event = CreateTaskEvent::rollback(
+        task.id,
+        workspace.id,
+        task.column_id,
+        column.id,
+        target_event.id,
+        serde_json::Value::Object(commits),
+    );
+    event.actor_id = users::actor_id(&deployment).await;
+    if let Err(e) = TaskEvent::create(pool, &event).await {
+        tracing::error!("Failed to record rollback event for task {}: {}", task.id, e);
+    }
+
+    let updated_task = Task::find_by_id(pool, task.id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+    let task_status = TaskWithAttemptStatus {
+        task: updated_task.clone(),
+        has_in_progress_attempt: false,
+        last_attempt_failed: false,
+        executor: String::new(),
+        latest_attempt_id: Some(workspace.id),
+    };
+    deployment
+        .events()
+        .msg_store()
+        .push_patch(task_patch::replace(&task_status));
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_rolled_back",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "task_id": task.id.to_string(),
+                "column_id": column.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(updated_task)))
+}
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
@@ -1695,6 +2324,11 @@ pub async fn execute_task_triggers(
             }
         };
 
+        if task.is_detached() {
+            tracing::info!("Task {} was handed back to a human, skipping auto-start", task_id);
+            continue;
+        }
+
         // Find the project to get its board_id
         let project = match Project::find_by_id(pool, task.project_id).await {
             Ok(Some(project)) => project,
@@ -1758,6 +2392,12 @@ pub async fn execute_task_triggers(
             );
             continue;
         }
+        deployment.container().spawn_column_automations(
+            task.id,
+            task.column_id,
+            target_column.id,
+            None,
+        );
 
         tracing::info!(
             "Task {} auto-started (all triggers satisfied, moved to column '{}')",
@@ -1790,11 +2430,28 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/run-agent-setup", post(run_agent_setup))
         .route("/gh-cli-setup", post(gh_cli_setup_handler))
         .route("/start-dev-server", post(start_dev_server))
+        .route("/dev-server-preview", get(get_dev_server_preview))
+        .route(
+            "/orientation",
+            get(get_workspace_orientation).delete(clear_workspace_orientation),
+        )
         .route("/run-setup-script", post(run_setup_script))
         .route("/run-cleanup-script", post(run_cleanup_script))
         .route("/branch-status", get(get_task_attempt_branch_status))
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
+        .route("/review/diff", get(review::get_review_diff))
+        .route(
+            "/review/comments",
+            get(review::get_review_comments).post(review::create_review_comment),
+        )
+        .route(
+            "/review/comments/{comment_id}",
+            delete(review::delete_review_comment),
+        )
+        .route("/review/reject", post(review::reject_review))
         .route("/merge", post(merge_task_attempt))
+        .route("/apply-patch", post(apply_task_attempt_patch))
+        .route("/export", get(export_task_attempt))
         .route("/push", post(push_task_attempt_branch))
         .route("/push/force", post(force_push_task_attempt_branch))
         .route("/rebase", post(rebase_task_attempt))
@@ -1802,10 +2459,12 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/pr", post(pr::create_github_pr))
         .route("/pr/attach", post(pr::attach_existing_pr))
         .route("/pr/comments", get(pr::get_pr_comments))
+        .route("/pr/checks", get(pr::get_pr_checks))
         .route("/open-editor", post(open_task_attempt_in_editor))
         .route("/children", get(get_task_attempt_children))
         .route("/stop", post(stop_task_attempt_execution))
         .route("/cancel", post(cancel_task_attempt))
+        .route("/rollback", post(rollback_task_attempt))
         .route("/change-target-branch", post(change_target_branch))
         .route("/rename-branch", post(rename_branch))
         .route("/repos", get(get_task_attempt_repos))
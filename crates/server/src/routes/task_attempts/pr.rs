@@ -1,5 +1,3 @@
-use std::path::PathBuf;
-
 use axum::{
     Extension, Json,
     extract::{Query, State},
@@ -7,8 +5,9 @@ use axum::{
 };
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessRunReason},
-    merge::{Merge, MergeStatus},
+    merge::{Merge, MergeStatus, PullRequestInfo},
     repo::{Repo, RepoError},
+    repo_forge_settings::ForgeKind,
     session::{CreateSession, Session},
     task::{Task, TaskStatus},
     task_trigger::TriggerCondition,
@@ -20,12 +19,11 @@ use executors::actions::{
     ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
     coding_agent_initial::CodingAgentInitialRequest,
 };
-use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
-    container::ContainerService,
-    git::{GitCliError, GitServiceError},
-    github::{CreatePrRequest, GitHubService, GitHubServiceError, UnifiedPrComment},
+    container::{ContainerError, ContainerService},
+    forge::{CodeHost, CodeHostError, CreatePrError, PrCheck, PrCheckState, overall_state},
+    github::{CreatePrRequest, GitHubServiceError, UnifiedPrComment},
 };
 use ts_rs::TS;
 use utils::response::ApiResponse;
@@ -44,17 +42,6 @@ pub struct CreateGitHubPrRequest {
     pub auto_generate_description: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
-#[serde(tag = "type", rename_all = "snake_case")]
-#[ts(tag = "type", rename_all = "snake_case")]
-pub enum CreatePrError {
-    GithubCliNotInstalled,
-    GithubCliNotLoggedIn,
-    GitCliNotLoggedIn,
-    GitCliNotInstalled,
-    TargetBranchNotFound { branch: String },
-}
-
 #[derive(Debug, Serialize, TS)]
 pub struct AttachPrResponse {
     pub pr_attached: bool,
@@ -87,6 +74,27 @@ pub struct GetPrCommentsQuery {
     pub repo_id: Uuid,
 }
 
+#[derive(Debug, Serialize, TS)]
+pub struct PrChecksResponse {
+    /// Failure if any check failed, pending while any runs; null without checks
+    pub state: Option<PrCheckState>,
+    pub checks: Vec<PrCheck>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum GetPrChecksError {
+    NoPrAttached,
+    GithubCliNotInstalled,
+    GithubCliNotLoggedIn,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct GetPrChecksQuery {
+    pub repo_id: Uuid,
+}
+
 pub const DEFAULT_PR_DESCRIPTION_PROMPT: &str = r#"Update the GitHub PR that was just created with a better title and description.
 The PR number is #{pr_number} and the URL is {pr_url}.
 
@@ -170,11 +178,16 @@ async fn trigger_pr_description_follow_up(
             agent_project_context: None,
             agent_workflow_history: None,
             agent_start_command: None,
+            agent_decision_instructions: None,
             agent_deliverable: None,
+            prompt_sections: None,
+            prompt_language: None,
         })
     };
 
-    let action = ExecutorAction::new(action_type, None);
+    let agent =
+        ExecutionProcess::latest_agent_for_session(&deployment.db().pool, session.id).await?;
+    let action = ExecutorAction::new(action_type, None).with_agent(agent);
 
     deployment
         .container()
@@ -189,11 +202,36 @@ async fn trigger_pr_description_follow_up(
     Ok(())
 }
 
-pub async fn create_github_pr(
-    Extension(workspace): Extension<Workspace>,
-    State(deployment): State<DeploymentImpl>,
-    Json(request): Json<CreateGitHubPrRequest>,
-) -> Result<ResponseJson<ApiResponse<String, CreatePrError>>, ApiError> {
+/// Keep git and code host failures distinguishable in the API response
+fn from_container_error(e: ContainerError) -> ApiError {
+    match e {
+        ContainerError::GitServiceError(e) => ApiError::GitService(e),
+        ContainerError::CodeHost(e) => ApiError::CodeHost(e),
+        ContainerError::Sqlx(e) => ApiError::Database(e),
+        e => ApiError::Container(e),
+    }
+}
+
+/// Code host for a repo, from its forge settings and `origin` remote
+pub(crate) async fn code_host(
+    deployment: &DeploymentImpl,
+    repo: &Repo,
+) -> Result<Box<dyn CodeHost>, ApiError> {
+    deployment
+        .container()
+        .code_host(repo)
+        .await
+        .map_err(from_container_error)
+}
+
+/// Push the workspace branch of one repo and open a pull request for it on the repo's
+/// code host, recording it on the workspace. Problems the user has to fix (missing CLI,
+/// unknown target branch) come back as the inner error.
+pub(crate) async fn open_pr(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    request: &CreateGitHubPrRequest,
+) -> Result<Result<(PullRequestInfo, ForgeKind), CreatePrError>, ApiError> {
     let pool = &deployment.db().pool;
 
     let workspace_repo =
@@ -205,163 +243,66 @@ pub async fn create_github_pr(
         .await?
         .ok_or(RepoError::NotFound)?;
 
-    let repo_path = repo.path;
-    let target_branch = if let Some(branch) = request.target_branch {
-        branch
-    } else {
-        workspace_repo.target_branch.clone()
-    };
-
-    let container_ref = deployment
-        .container()
-        .ensure_container_exists(&workspace)
-        .await?;
-    let workspace_path = PathBuf::from(&container_ref);
-    let worktree_path = workspace_path.join(repo.name);
-
-    match deployment
-        .git()
-        .check_remote_branch_exists(&repo_path, &target_branch)
-    {
-        Ok(false) => {
-            return Ok(ResponseJson(ApiResponse::error_with_data(
-                CreatePrError::TargetBranchNotFound {
-                    branch: target_branch.clone(),
-                },
-            )));
-        }
-        Err(GitServiceError::GitCLI(GitCliError::AuthFailed(_))) => {
-            return Ok(ResponseJson(ApiResponse::error_with_data(
-                CreatePrError::GitCliNotLoggedIn,
-            )));
-        }
-        Err(GitServiceError::GitCLI(GitCliError::NotAvailable)) => {
-            return Ok(ResponseJson(ApiResponse::error_with_data(
-                CreatePrError::GitCliNotInstalled,
-            )));
-        }
-        Err(e) => return Err(ApiError::GitService(e)),
-        Ok(true) => {}
-    }
-
-    // Push the branch to GitHub first
-    if let Err(e) = deployment
-        .git()
-        .push_to_github(&worktree_path, &workspace.branch, false)
-    {
-        tracing::error!("Failed to push branch to GitHub: {}", e);
-        match e {
-            GitServiceError::GitCLI(GitCliError::AuthFailed(_)) => {
-                return Ok(ResponseJson(ApiResponse::error_with_data(
-                    CreatePrError::GitCliNotLoggedIn,
-                )));
-            }
-            GitServiceError::GitCLI(GitCliError::NotAvailable) => {
-                return Ok(ResponseJson(ApiResponse::error_with_data(
-                    CreatePrError::GitCliNotInstalled,
-                )));
-            }
-            _ => return Err(ApiError::GitService(e)),
-        }
-    }
-
-    let norm_target_branch_name = if matches!(
-        deployment
-            .git()
-            .find_branch_type(&repo_path, &target_branch)?,
-        BranchType::Remote
-    ) {
-        // Remote branches are formatted as {remote}/{branch} locally.
-        // For PR APIs, we must provide just the branch name.
-        let remote = deployment
-            .git()
-            .get_remote_name_from_branch_name(&worktree_path, &target_branch)?;
-        let remote_prefix = format!("{}/", remote);
-        target_branch
-            .strip_prefix(&remote_prefix)
-            .unwrap_or(&target_branch)
-            .to_string()
-    } else {
-        target_branch
-    };
-    // Create the PR using GitHub service
     let pr_request = CreatePrRequest {
         title: request.title.clone(),
         body: request.body.clone(),
         head_branch: workspace.branch.clone(),
-        base_branch: norm_target_branch_name.clone(),
+        base_branch: request
+            .target_branch
+            .clone()
+            .unwrap_or(workspace_repo.target_branch),
         draft: request.draft,
     };
-    // Use GitService to get the remote URL, then create GitHubRepoInfo
-    let repo_info = deployment.git().get_github_repo_info(&repo_path)?;
-
-    // Use GitHubService to create the PR
-    let github_service = GitHubService::new()?;
-    match github_service.create_pr(&repo_info, &pr_request).await {
-        Ok(pr_info) => {
-            // Update the workspace with PR information
-            if let Err(e) = Merge::create_pr(
-                pool,
-                workspace.id,
-                workspace_repo.repo_id,
-                &norm_target_branch_name,
-                pr_info.number,
-                &pr_info.url,
-            )
-            .await
-            {
-                tracing::error!("Failed to update workspace PR status: {}", e);
-            }
+    deployment
+        .container()
+        .open_pull_request(workspace, &repo, pr_request)
+        .await
+        .map_err(from_container_error)
+}
 
-            // Auto-open PR in browser
-            if let Err(e) = utils::browser::open_browser(&pr_info.url).await {
-                tracing::warn!("Failed to open PR in browser: {}", e);
-            }
-            deployment
-                .track_if_analytics_allowed(
-                    "github_pr_created",
-                    serde_json::json!({
-                        "workspace_id": workspace.id.to_string(),
-                    }),
-                )
-                .await;
-
-            // Trigger auto-description follow-up if enabled
-            if request.auto_generate_description
-                && let Err(e) = trigger_pr_description_follow_up(
-                    &deployment,
-                    &workspace,
-                    pr_info.number.into(),
-                    &pr_info.url,
-                )
-                .await
-            {
-                tracing::warn!(
-                    "Failed to trigger PR description follow-up for attempt {}: {}",
-                    workspace.id,
-                    e
-                );
-            }
+pub async fn create_github_pr(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<CreateGitHubPrRequest>,
+) -> Result<ResponseJson<ApiResponse<String, CreatePrError>>, ApiError> {
+    let (pr_info, kind) = match open_pr(&deployment, &workspace, &request).await? {
+        Ok(opened) => opened,
+        Err(e) => return Ok(ResponseJson(ApiResponse::error_with_data(e))),
+    };
 
-            Ok(ResponseJson(ApiResponse::success(pr_info.url)))
-        }
-        Err(e) => {
-            tracing::error!(
-                "Failed to create GitHub PR for attempt {}: {}",
-                workspace.id,
-                e
-            );
-            match &e {
-                GitHubServiceError::GhCliNotInstalled(_) => Ok(ResponseJson(
-                    ApiResponse::error_with_data(CreatePrError::GithubCliNotInstalled),
-                )),
-                GitHubServiceError::AuthFailed(_) => Ok(ResponseJson(
-                    ApiResponse::error_with_data(CreatePrError::GithubCliNotLoggedIn),
-                )),
-                _ => Err(ApiError::GitHubService(e)),
-            }
-        }
+    // Auto-open PR in browser
+    if let Err(e) = utils::browser::open_browser(&pr_info.url).await {
+        tracing::warn!("Failed to open PR in browser: {}", e);
+    }
+    deployment
+        .track_if_analytics_allowed(
+            &format!("{}_pr_created", kind.as_str()),
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+            }),
+        )
+        .await;
+
+    // Trigger auto-description follow-up if enabled. The default prompt edits the PR
+    // with `gh`, so it only runs for GitHub repos.
+    if request.auto_generate_description
+        && kind == ForgeKind::Github
+        && let Err(e) = trigger_pr_description_follow_up(
+            &deployment,
+            &workspace,
+            pr_info.number.into(),
+            &pr_info.url,
+        )
+        .await
+    {
+        tracing::warn!(
+            "Failed to trigger PR description follow-up for attempt {}: {}",
+            workspace.id,
+            e
+        );
     }
+
+    Ok(ResponseJson(ApiResponse::success(pr_info.url)))
 }
 
 pub async fn attach_existing_pr(
@@ -396,12 +337,10 @@ pub async fn attach_existing_pr(
         })));
     }
 
-    let github_service = GitHubService::new()?;
-    let repo_info = deployment.git().get_github_repo_info(&repo.path)?;
-
     // List all PRs for branch (open, closed, and merged)
-    let prs = github_service
-        .list_all_prs_for_branch(&repo_info, &workspace.branch)
+    let prs = code_host(&deployment, &repo)
+        .await?
+        .list_prs_for_branch(&workspace.branch)
         .await?;
 
     // Take the first PR (prefer open, but also accept merged/closed)
@@ -434,13 +373,7 @@ pub async fn attach_existing_pr(
 
             // Try broadcast update to other users in organization
             if let Ok(publisher) = deployment.share_publisher() {
-                if let Err(err) = publisher.update_shared_task_by_id(task.id).await {
-                    tracing::warn!(
-                        ?err,
-                        "Failed to propagate shared task update for {}",
-                        task.id
-                    );
-                }
+                publisher.propagate_task_update(task.id).await;
             } else {
                 tracing::debug!(
                     "Share publisher unavailable; skipping remote update for {}",
@@ -498,12 +431,10 @@ pub async fn get_pr_comments(
         }
     };
 
-    let github_service = GitHubService::new()?;
-    let repo_info = deployment.git().get_github_repo_info(&repo.path)?;
-
-    // Fetch comments from GitHub
-    match github_service
-        .get_pr_comments(&repo_info, pr_info.number.into())
+    // Fetch comments from the code host
+    match code_host(&deployment, &repo)
+        .await?
+        .get_pr_comments(pr_info.number.into())
         .await
     {
         Ok(comments) => Ok(ResponseJson(ApiResponse::success(PrCommentsResponse {
@@ -517,14 +448,76 @@ pub async fn get_pr_comments(
                 e
             );
             match &e {
-                GitHubServiceError::GhCliNotInstalled(_) => Ok(ResponseJson(
-                    ApiResponse::error_with_data(GetPrCommentsError::GithubCliNotInstalled),
-                )),
-                GitHubServiceError::AuthFailed(_) => Ok(ResponseJson(
+                CodeHostError::GitHub(GitHubServiceError::GhCliNotInstalled(_)) => Ok(
+                    ResponseJson(ApiResponse::error_with_data(
+                        GetPrCommentsError::GithubCliNotInstalled,
+                    )),
+                ),
+                CodeHostError::GitHub(GitHubServiceError::AuthFailed(_)) => Ok(ResponseJson(
                     ApiResponse::error_with_data(GetPrCommentsError::GithubCliNotLoggedIn),
                 )),
-                _ => Err(ApiError::GitHubService(e)),
+                _ => Err(ApiError::CodeHost(e)),
             }
         }
     }
 }
+
+/// Status checks on the PR attached for a repo, from the repo's code host
+pub async fn get_pr_checks(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<GetPrChecksQuery>,
+) -> Result<ResponseJson<ApiResponse<PrChecksResponse, GetPrChecksError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, query.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let merges = Merge::find_by_workspace_and_repo_id(pool, workspace.id, query.repo_id).await?;
+    let pr_info = match merges.into_iter().next() {
+        Some(Merge::Pr(pr_merge)) => pr_merge.pr_info,
+        _ => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                GetPrChecksError::NoPrAttached,
+            )));
+        }
+    };
+
+    let checks = match code_host(&deployment, &repo)
+        .await?
+        .get_pr_checks(pr_info.number.into())
+        .await
+    {
+        Ok(checks) => checks,
+        Err(e) => {
+            tracing::error!(
+                "Failed to fetch PR checks for attempt {}, PR #{}: {}",
+                workspace.id,
+                pr_info.number,
+                e
+            );
+            return match &e {
+                CodeHostError::GitHub(GitHubServiceError::GhCliNotInstalled(_)) => Ok(
+                    ResponseJson(ApiResponse::error_with_data(
+                        GetPrChecksError::GithubCliNotInstalled,
+                    )),
+                ),
+                CodeHostError::GitHub(GitHubServiceError::AuthFailed(_)) => Ok(ResponseJson(
+                    ApiResponse::error_with_data(GetPrChecksError::GithubCliNotLoggedIn),
+                )),
+                _ => Err(ApiError::CodeHost(e)),
+            };
+        }
+    };
+
+    Ok(ResponseJson(ApiResponse::success(PrChecksResponse {
+        state: overall_state(&checks),
+        checks,
+    })))
+}
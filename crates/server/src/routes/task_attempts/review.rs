@@ -0,0 +1,206 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    repo::RepoError,
+    review_comment::{CreateReviewComment, ReviewComment},
+    reviewer_feedback::{CreateReviewerFeedback, ReviewerFeedbackSource},
+    task::Task,
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    git::DiffTarget,
+    review::{ReviewFileDiff, format_review_feedback},
+    reviewer_feedback,
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct RejectReviewRequest {
+    /// Overall feedback placed before the inline comments
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RejectReviewResponse {
+    /// The feedback recorded for the implementer's next run
+    pub feedback: String,
+    /// Version of the feedback on the task
+    pub version: i32,
+    pub comments_sent: usize,
+}
+
+/// Structured diff of every workspace repo against its target branch
+pub async fn get_review_diff(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ReviewFileDiff>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let workspace_root = PathBuf::from(container_ref);
+
+    let target_branches: HashMap<_, _> = WorkspaceRepo::find_by_workspace_id(pool, workspace.id)
+        .await?
+        .into_iter()
+        .map(|wr| (wr.repo_id, wr.target_branch))
+        .collect();
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+
+    let mut files = Vec::new();
+    for repo in repos {
+        let Some(target_branch) = target_branches.get(&repo.id) else {
+            continue;
+        };
+        let base_commit =
+            deployment
+                .git()
+                .get_base_commit(&repo.path, &workspace.branch, target_branch)?;
+        let worktree_path = workspace_root.join(&repo.name);
+        let git = deployment.git().clone();
+        let diffs = tokio::task::spawn_blocking(move || {
+            git.get_diffs(
+                DiffTarget::Worktree {
+                    worktree_path: &worktree_path,
+                    base_commit: &base_commit,
+                },
+                None,
+            )
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+        files.extend(
+            diffs
+                .into_iter()
+                .map(|diff| ReviewFileDiff::from_diff(&repo, diff)),
+        );
+    }
+
+    Ok(ResponseJson(ApiResponse::success(files)))
+}
+
+pub async fn get_review_comments(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ReviewComment>>>, ApiError> {
+    let comments = ReviewComment::find_by_workspace(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+pub async fn create_review_comment(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateReviewComment>,
+) -> Result<ResponseJson<ApiResponse<ReviewComment>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if payload.body.trim().is_empty() {
+        return Err(ApiError::BadRequest("Comment body is required".to_string()));
+    }
+    if payload.line_number < 1 {
+        return Err(ApiError::BadRequest(
+            "line_number must be at least 1".to_string(),
+        ));
+    }
+    WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, payload.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let comment = ReviewComment::create(pool, workspace.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+pub async fn delete_review_comment(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let deleted = ReviewComment::delete(&deployment.db().pool, workspace.id, comment_id).await?;
+    if deleted == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Reject the work: record the summary and pending inline comments as reviewer
+/// feedback for the next agent run
+pub async fn reject_review(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RejectReviewRequest>,
+) -> Result<ResponseJson<ApiResponse<RejectReviewResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let comments = ReviewComment::find_pending(pool, workspace.id).await?;
+    let summary = payload.summary.as_deref().filter(|s| !s.trim().is_empty());
+    if summary.is_none() && comments.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Add a summary or at least one review comment".to_string(),
+        ));
+    }
+
+    let repo_names: HashMap<Uuid, String> =
+        WorkspaceRepo::find_repos_for_workspace(pool, workspace.id)
+            .await?
+            .into_iter()
+            .map(|repo| (repo.id, repo.name))
+            .collect();
+    let feedback = format_review_feedback(summary, &comments, |repo_id| {
+        repo_names.get(&repo_id).cloned()
+    });
+
+    let task = Task::find_by_id(pool, workspace.task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let rejected_run = ExecutionProcess::find_latest_by_workspace_and_run_reason(
+        pool,
+        workspace.id,
+        &ExecutionProcessRunReason::CodingAgent,
+    )
+    .await?;
+    let recorded = reviewer_feedback::record(
+        pool,
+        task.id,
+        Some(workspace.id),
+        &CreateReviewerFeedback {
+            column_id: task.column_id,
+            execution_process_id: rejected_run.map(|process| process.id),
+            source: ReviewerFeedbackSource::Human,
+            feedback: feedback.clone(),
+        },
+    )
+    .await?;
+
+    let ids: Vec<Uuid> = comments.iter().map(|c| c.id).collect();
+    ReviewComment::mark_sent(pool, &ids).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "review_rejected",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "comments": ids.len(),
+                "version": recorded.version,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(RejectReviewResponse {
+        feedback,
+        version: recorded.version,
+        comments_sent: ids.len(),
+    })))
+}
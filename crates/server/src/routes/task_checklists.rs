@@ -0,0 +1,110 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::{
+    kanban_column::KanbanColumn,
+    task::Task,
+    task_event::{CreateTaskEvent, TaskEvent},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::column_checklist::{self, TaskChecklist};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::users};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TickChecklistItem {
+    pub item: String,
+    /// `false` unticks the item
+    #[serde(default = "default_checked")]
+    pub checked: bool,
+    /// Who ticked the item (name or email), recorded on the task event
+    pub checked_by: Option<String>,
+}
+
+fn default_checked() -> bool {
+    true
+}
+
+async fn task_column(deployment: &DeploymentImpl, task_id: Uuid) -> Result<(Task, Uuid), ApiError> {
+    let task = Task::find_by_id(&deployment.db().pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let column_id = task
+        .column_id
+        .ok_or_else(|| ApiError::BadRequest("Task is not in a column".to_string()))?;
+    Ok((task, column_id))
+}
+
+/// Checklist of the task's current column, with who ticked each item and when
+pub async fn get_task_checklist(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<TaskChecklist>>, ApiError> {
+    let (task, column_id) = task_column(&deployment, task_id).await?;
+    let checklist = column_checklist::for_task(&deployment.db().pool, task.id, column_id).await?;
+    Ok(ResponseJson(ApiResponse::success(checklist)))
+}
+
+/// Tick (or untick) an item of the task's current column checklist
+pub async fn tick_checklist_item(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<TickChecklistItem>,
+) -> Result<ResponseJson<ApiResponse<TaskChecklist>>, ApiError> {
+    let (task, column_id) = task_column(&deployment, task_id).await?;
+    let pool = &deployment.db().pool;
+
+    let checklist = KanbanColumn::find_by_id(pool, column_id)
+        .await?
+        .map(|column| column.checklist)
+        .unwrap_or_default();
+    let item = payload.item.trim();
+    if !checklist.iter().any(|i| i == item) {
+        return Err(ApiError::BadRequest(format!(
+            "'{item}' is not on this column's checklist"
+        )));
+    }
+
+    // Ticks without a name are attributed to the signed-in user
+    let checked_by = match payload
+        .checked_by
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+    {
+        Some(name) => Some(name),
+        None => users::current_user(&deployment)
+            .await?
+            .map(|user| user.label().to_string()),
+    };
+    let event =
+        CreateTaskEvent::checklist_item(task.id, column_id, item, payload.checked, checked_by);
+    TaskEvent::create(pool, &event).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "checklist_item_ticked",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "column_id": column_id.to_string(),
+                "checked": payload.checked,
+            }),
+        )
+        .await;
+
+    let checklist = column_checklist::for_task(pool, task.id, column_id).await?;
+    Ok(ResponseJson(ApiResponse::success(checklist)))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/tasks/{task_id}/checklist",
+        get(get_task_checklist).post(tick_checklist_item),
+    )
+}
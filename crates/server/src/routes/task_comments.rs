@@ -0,0 +1,106 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    task::Task,
+    task_comment::{CreateTaskComment, TaskComment, UpdateTaskComment},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+async fn find_task(deployment: &DeploymentImpl, task_id: Uuid) -> Result<Task, ApiError> {
+    Task::find_by_id(&deployment.db().pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+async fn find_comment(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    comment_id: Uuid,
+) -> Result<TaskComment, ApiError> {
+    TaskComment::find_by_id(&deployment.db().pool, task_id, comment_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+/// Comment thread of a task, oldest first
+pub async fn get_task_comments(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskComment>>>, ApiError> {
+    let task = find_task(&deployment, task_id).await?;
+    let comments = TaskComment::find_by_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+/// Comment on a task. Comments visible to the agent are appended to the next agent
+/// prompt, alongside reviewer feedback.
+pub async fn create_task_comment(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<CreateTaskComment>,
+) -> Result<ResponseJson<ApiResponse<TaskComment>>, ApiError> {
+    let task = find_task(&deployment, task_id).await?;
+    if payload.content.trim().is_empty() {
+        return Err(ApiError::BadRequest("content is required".to_string()));
+    }
+    let comment = TaskComment::create(&deployment.db().pool, task.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_comment_created",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "visible_to_agent": comment.visible_to_agent,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+/// Edit a comment or change whether the agent sees it
+pub async fn update_task_comment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateTaskComment>,
+) -> Result<ResponseJson<ApiResponse<TaskComment>>, ApiError> {
+    let comment = find_comment(&deployment, task_id, comment_id).await?;
+    if payload
+        .content
+        .as_deref()
+        .is_some_and(|content| content.trim().is_empty())
+    {
+        return Err(ApiError::BadRequest("content must not be empty".to_string()));
+    }
+    let comment = TaskComment::update(&deployment.db().pool, comment.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+pub async fn delete_task_comment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let comment = find_comment(&deployment, task_id, comment_id).await?;
+    TaskComment::delete(&deployment.db().pool, comment.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/tasks/{task_id}/comments",
+            get(get_task_comments).post(create_task_comment),
+        )
+        .route(
+            "/tasks/{task_id}/comments/{comment_id}",
+            put(update_task_comment).delete(delete_task_comment),
+        )
+}
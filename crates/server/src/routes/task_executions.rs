@@ -0,0 +1,151 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::post,
+};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    plan_review::PlanReview,
+    session::{CreateSession, Session},
+    task::Task,
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType,
+        coding_agent_initial::{CodingAgentInitialRequest, PromptLanguage},
+    },
+    profile::ExecutorProfileId,
+};
+use serde::Deserialize;
+use services::services::{container::ContainerService, plan_review};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ExecuteTaskRequest {
+    pub prompt: String,
+    pub executor_profile_id: ExecutorProfileId,
+    /// Only write a plan for review; the run's other changes are discarded
+    #[serde(default)]
+    pub plan_only: bool,
+    /// Language of the plan-only notice; None uses English
+    #[serde(default)]
+    pub prompt_language: Option<PromptLanguage>,
+}
+
+/// Start a one-off agent run with the user's prompt in the task's workspace.
+/// The run gets its own session and is independent of the column's agent: it
+/// doesn't change the task's status or column when it finishes. Once the task has a
+/// plan, runs that may change the code wait for it to be approved.
+pub async fn execute_task(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<ExecuteTaskRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    let prompt = payload.prompt.trim();
+    if prompt.is_empty() {
+        return Err(ApiError::BadRequest("prompt is required".to_string()));
+    }
+    let workspace = Workspace::find_active_for_task(pool, task.id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Task has no active workspace".to_string()))?;
+    if Task::has_active_attempt(pool, task.id).await? {
+        return Err(ApiError::Conflict(
+            "An execution is already running for this task".to_string(),
+        ));
+    }
+    if !payload.plan_only {
+        let latest = PlanReview::find_latest(pool, task.id, None).await?;
+        if plan_review::blocks(latest.as_ref()) {
+            return Err(ApiError::Conflict(
+                "The task's plan must be approved before it can be implemented".to_string(),
+            ));
+        }
+    }
+
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+
+    let executor_profile_id = payload.executor_profile_id;
+    let session = Session::create(
+        pool,
+        &CreateSession {
+            executor: Some(executor_profile_id.executor.to_string()),
+        },
+        Uuid::new_v4(),
+        workspace.id,
+    )
+    .await?;
+    if payload.plan_only {
+        PlanReview::create(pool, task.id, session.id, None).await?;
+    }
+
+    let working_dir = workspace
+        .agent_working_dir
+        .as_ref()
+        .filter(|dir| !dir.is_empty())
+        .cloned();
+
+    // No cleanup script: its completion would be finalized as part of the column workflow
+    let action = ExecutorAction::new(
+        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt: prompt.to_string(),
+            executor_profile_id: executor_profile_id.clone(),
+            working_dir,
+            agent_system_prompt: None,
+            agent_project_context: None,
+            agent_workflow_history: None,
+            agent_start_command: None,
+            agent_decision_instructions: None,
+            agent_deliverable: payload.plan_only.then(|| {
+                let language = payload.prompt_language.unwrap_or_default();
+                plan_review::plan_only_deliverable(None, language)
+            }),
+            prompt_sections: None,
+            prompt_language: payload.prompt_language,
+        }),
+        None,
+    );
+
+    let execution_process = deployment
+        .container()
+        .start_execution(
+            &workspace,
+            &session,
+            &action,
+            &ExecutionProcessRunReason::ManualRun,
+        )
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "manual_run_started",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+                "workspace_id": workspace.id.to_string(),
+                "executor": executor_profile_id.to_string(),
+                "plan_only": payload.plan_only,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new().route("/tasks/{task_id}/execute", post(execute_task))
+}
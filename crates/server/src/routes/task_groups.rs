@@ -997,10 +997,13 @@ async fn launch_task_builder_agent(
         system_prompt: Some(agent.system_prompt.clone()),
         workflow_history: None,
         start_command: Some(prompt),
+        decision_instructions: None,
         deliverable: None,
         name: agent.name.clone(),
         color: agent.color.clone(),
         column_name: "Task Builder".to_string(),
+        agent_id: agent.id,
+        column_id: None,
         project_context: None,
         task_id_override: None,
     };
@@ -1514,10 +1517,13 @@ async fn launch_group_evaluator(
         system_prompt: Some(agent.system_prompt.clone()),
         workflow_history: None,
         start_command: Some(prompt),
+        decision_instructions: None,
         deliverable: None,
         name: agent.name.clone(),
         color: agent.color.clone(),
         column_name: "Analysis".to_string(),
+        agent_id: agent.id,
+        column_id: None,
         project_context: None,
         task_id_override: None,
     };
@@ -0,0 +1,182 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use db::models::{
+    task::{Task, TaskState},
+    task_event::{CreateTaskEvent, TaskEvent},
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{container::ContainerService, events::task_patch};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::{tasks::task_to_status, users},
+};
+
+#[derive(Debug, Serialize, TS)]
+pub struct TaskHandback {
+    pub task_id: Uuid,
+    /// Whether the task is detached from automation
+    pub detached: bool,
+    #[ts(type = "Date | null")]
+    pub detached_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct HandBackTaskRequest {
+    /// Who takes over the task, recorded on the task event
+    pub handed_to: Option<String>,
+    pub reason: Option<String>,
+    /// Stop the agent if it is still running; defaults to true
+    #[serde(default = "default_stop_agent")]
+    pub stop_agent: bool,
+}
+
+fn default_stop_agent() -> bool {
+    true
+}
+
+async fn find_task(deployment: &DeploymentImpl, task_id: Uuid) -> Result<Task, ApiError> {
+    Task::find_by_id(&deployment.db().pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+fn trimmed(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+async fn push_task_patch(deployment: &DeploymentImpl, task_id: Uuid) -> Result<(), ApiError> {
+    if let Some(task) = Task::find_by_id(&deployment.db().pool, task_id).await? {
+        deployment
+            .events()
+            .msg_store()
+            .push_patch(task_patch::add(&task_to_status(&task)));
+    }
+    Ok(())
+}
+
+pub async fn get_task_handback(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<TaskHandback>>, ApiError> {
+    let task = find_task(&deployment, task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(TaskHandback {
+        task_id: task.id,
+        detached: task.is_detached(),
+        detached_at: task.detached_at,
+    })))
+}
+
+/// Hand a task over to a human. The workspace, branch and history are kept, but the
+/// task is no longer moved between columns or picked up by agents automatically.
+pub async fn hand_back_task(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<HandBackTaskRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskHandback>>, ApiError> {
+    let task = find_task(&deployment, task_id).await?;
+    let pool = &deployment.db().pool;
+    if task.is_detached() {
+        return Err(ApiError::Conflict(
+            "Task has already been handed back".to_string(),
+        ));
+    }
+
+    // Detach first so a stopped agent's exit doesn't trigger an auto-transition
+    let detached_at = Task::set_detached(pool, task.id, true).await?;
+
+    let mut stopped_agent = false;
+    if payload.stop_agent && Task::has_active_attempt(pool, task.id).await? {
+        for workspace in Workspace::fetch_all(pool, Some(task.id)).await? {
+            deployment.container().try_stop(&workspace, false).await;
+        }
+        stopped_agent = true;
+    }
+    Task::update_task_state(pool, task.id, TaskState::Queued).await?;
+
+    let event = CreateTaskEvent::handback(
+        task.id,
+        task.column_id,
+        true,
+        trimmed(payload.handed_to),
+        trimmed(payload.reason),
+    );
+    TaskEvent::create(pool, &event).await?;
+    push_task_patch(&deployment, task.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_handed_back",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+                "stopped_agent": stopped_agent,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(TaskHandback {
+        task_id: task.id,
+        detached: true,
+        detached_at,
+    })))
+}
+
+/// Resume automation for a handed-back task. Nothing is started right away; the
+/// workflow picks the task up again the next time it moves or an agent finishes.
+pub async fn reattach_task(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<TaskHandback>>, ApiError> {
+    let task = find_task(&deployment, task_id).await?;
+    let pool = &deployment.db().pool;
+    if !task.is_detached() {
+        return Err(ApiError::Conflict(
+            "Task has not been handed back".to_string(),
+        ));
+    }
+
+    Task::set_detached(pool, task.id, false).await?;
+    let actor_id = users::actor_id(&deployment).await;
+    let event = CreateTaskEvent::handback(task.id, task.column_id, false, actor_id, None);
+    TaskEvent::create(pool, &event).await?;
+    push_task_patch(&deployment, task.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_reattached",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(TaskHandback {
+        task_id: task.id,
+        detached: false,
+        detached_at: None,
+    })))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/tasks/{task_id}/handback",
+        get(get_task_handback)
+            .post(hand_back_task)
+            .delete(reattach_task),
+    )
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::post,
+};
+use db::models::{
+    kanban_column::KanbanColumn,
+    project::Project,
+    project_repo::ProjectRepo,
+    repo_forge_settings::{ForgeKind, RepoForgeSettings},
+    task::{CreateTask, Task},
+    task_event::{ActorType, CreateTaskEvent, TaskEvent},
+    task_label::{CreateTaskLabel, TaskLabel},
+};
+use deployment::Deployment;
+use services::services::{
+    events::task_patch,
+    gitea::GiteaHost,
+    task_import::{self, ImportSource, ImportTasksRequest, ImportTasksResponse, ImportedIssue},
+};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::{tasks::task_to_status, users},
+};
+
+/// Open issues of a project repo whose forge is Gitea/Forgejo
+async fn fetch_gitea_issues(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    repo_id: Uuid,
+) -> Result<Vec<ImportedIssue>, ApiError> {
+    let pool = &deployment.db().pool;
+    if ProjectRepo::find_by_project_and_repo(pool, project_id, repo_id)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Repo {repo_id} is not part of this project"
+        )));
+    }
+    let forge = RepoForgeSettings::for_repo(pool, repo_id).await?;
+    if forge.kind() != ForgeKind::Gitea {
+        return Err(ApiError::BadRequest(
+            "The repo's forge is not Gitea/Forgejo; set it in the repo's forge settings"
+                .to_string(),
+        ));
+    }
+    let repo = deployment.repo().get_by_id(pool, repo_id).await?;
+    let gitea = GiteaHost::new(&forge, &deployment.git().get_remote_url(&repo.path)?)?;
+    let issues = gitea.list_open_issues().await?;
+    Ok(task_import::issues_from_api(&issues))
+}
+
+/// Import tasks from a GitHub Issues or Jira export, or from the open issues of a
+/// Gitea/Forgejo repo. With `dry_run` the planned tasks (and the labels that would be
+/// created) are returned without touching the project.
+pub async fn import_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<ImportTasksRequest>,
+) -> Result<ResponseJson<ApiResponse<ImportTasksResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    let issues = match (payload.source, payload.repo_id) {
+        (ImportSource::GiteaIssues, Some(repo_id)) if payload.content.trim().is_empty() => {
+            fetch_gitea_issues(&deployment, project_id, repo_id).await?
+        }
+        _ => task_import::parse(
+            payload.source,
+            payload.format.unwrap_or_default(),
+            &payload.content,
+        )
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?,
+    };
+    if issues.is_empty() {
+        return Err(ApiError::BadRequest("There are no issues to import".to_string()));
+    }
+
+    let columns = match project.board_id {
+        Some(board_id) => KanbanColumn::find_by_board(pool, board_id).await?,
+        None => Vec::new(),
+    };
+    let status_mapping = payload.status_mapping.unwrap_or_default();
+    if let Some(column_id) = status_mapping
+        .values()
+        .find(|id| !columns.iter().any(|c| c.id == **id))
+    {
+        return Err(ApiError::BadRequest(format!(
+            "status_mapping refers to column {column_id}, which is not on the project's board"
+        )));
+    }
+
+    let tasks = task_import::plan(payload.source, issues, &columns, &status_mapping);
+    let existing_labels = TaskLabel::find_by_project(pool, project_id).await?;
+    let existing_names: Vec<String> = existing_labels.iter().map(|l| l.name.clone()).collect();
+    let new_labels = task_import::new_labels(&tasks, &existing_names);
+
+    if payload.dry_run {
+        return Ok(ResponseJson(ApiResponse::success(ImportTasksResponse {
+            dry_run: true,
+            tasks,
+            new_labels,
+            created_task_ids: Vec::new(),
+        })));
+    }
+
+    let mut label_ids: HashMap<String, Uuid> = existing_labels
+        .iter()
+        .map(|l| (l.name.to_lowercase(), l.id))
+        .collect();
+    for name in &new_labels {
+        let label = TaskLabel::create(
+            pool,
+            &CreateTaskLabel {
+                project_id,
+                name: name.clone(),
+                color: None,
+                position: None,
+            },
+        )
+        .await?;
+        label_ids.insert(name.to_lowercase(), label.id);
+    }
+
+    let actor_id = users::actor_id(&deployment).await;
+    let mut created_task_ids = Vec::with_capacity(tasks.len());
+    for planned in &tasks {
+        let data = CreateTask {
+            project_id,
+            title: planned.title.clone(),
+            description: planned.description.clone(),
+            status: Some(planned.status.clone()),
+            column_id: planned.column_id,
+            parent_workspace_id: None,
+            image_ids: None,
+            shared_task_id: None,
+            task_group_id: None,
+        };
+        let task = Task::create(pool, &data, Uuid::new_v4()).await?;
+        for label in &planned.labels {
+            if let Some(label_id) = label_ids.get(&label.to_lowercase()) {
+                TaskLabel::assign_to_task(pool, task.id, *label_id).await?;
+            }
+        }
+
+        deployment
+            .events()
+            .msg_store()
+            .push_patch(task_patch::add(&task_to_status(&task)));
+        let event = CreateTaskEvent::task_created(task.id, ActorType::User, actor_id.clone());
+        if let Err(e) = TaskEvent::create(pool, &event).await {
+            tracing::error!(
+                "Failed to record task created event for task {}: {}",
+                task.id,
+                e
+            );
+        }
+        created_task_ids.push(task.id);
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "tasks_imported",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+                "source": payload.source,
+                "task_count": created_task_ids.len(),
+                "new_label_count": new_labels.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(ImportTasksResponse {
+        dry_run: false,
+        tasks,
+        new_labels,
+        created_task_ids,
+    })))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new().route("/projects/{project_id}/import", post(import_tasks))
+}
@@ -8,8 +8,12 @@ use serde::Deserialize;
 use sqlx::Error as SqlxError;
 use uuid::Uuid;
 
-use db::models::task_label::{CreateTaskLabel, TaskLabel, UpdateTaskLabel};
+use db::models::{
+    task::Task,
+    task_label::{CreateTaskLabel, TaskLabel, UpdateTaskLabel},
+};
 use deployment::Deployment;
+use services::services::{events::task_patch, notification_channels};
 use utils::response::ApiResponse;
 
 use crate::{error::ApiError, DeploymentImpl};
@@ -200,16 +204,66 @@ async fn assign_label_to_task(
         ));
     }
 
+    let already_assigned = TaskLabel::find_by_task(pool, task_id)
+        .await?
+        .iter()
+        .any(|l| l.id == label_id);
     TaskLabel::assign_to_task(pool, task_id, label_id).await?;
+    if !already_assigned {
+        push_task_labels_changed(&deployment, &task).await;
+        notification_channels::notify_labels_changed(
+            pool,
+            deployment.share_publisher().ok(),
+            task_id,
+            &[label_id],
+            &[],
+        )
+        .await;
+    }
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Re-send a task whose labels changed, so task streams filtered by label add or
+/// remove it
+async fn push_task_labels_changed(deployment: &DeploymentImpl, task: &Task) {
+    match Task::find_by_project_id_with_attempt_status(&deployment.db().pool, task.project_id)
+        .await
+    {
+        Ok(tasks) => {
+            if let Some(task_status) = tasks.into_iter().find(|t| t.id == task.id) {
+                deployment
+                    .events()
+                    .msg_store()
+                    .push_patch(task_patch::replace(&task_status));
+            }
+        }
+        Err(e) => tracing::warn!("Failed to load task {} for stream update: {}", task.id, e),
+    }
+}
+
 /// Remove a label from a task
 async fn remove_label_from_task(
     Path((task_id, label_id)): Path<(Uuid, Uuid)>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
     let pool = &deployment.db().pool;
+    let was_assigned = TaskLabel::find_by_task(pool, task_id)
+        .await?
+        .iter()
+        .any(|l| l.id == label_id);
     TaskLabel::remove_from_task(pool, task_id, label_id).await?;
+    if was_assigned {
+        if let Some(task) = Task::find_by_id(pool, task_id).await? {
+            push_task_labels_changed(&deployment, &task).await;
+        }
+        notification_channels::notify_labels_changed(
+            pool,
+            deployment.share_publisher().ok(),
+            task_id,
+            &[],
+            &[label_id],
+        )
+        .await;
+    }
     Ok(ResponseJson(ApiResponse::success(())))
 }
@@ -1,12 +1,13 @@
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use anyhow;
 use axum::{
     Extension, Json, Router,
     extract::{
         Query, State,
-        ws::{WebSocket, WebSocketUpgrade},
+        ws::{Message, WebSocket, WebSocketUpgrade},
     },
     http::StatusCode,
     middleware::from_fn_with_state,
@@ -15,17 +16,20 @@ use axum::{
 };
 use db::models::{
     agent::Agent,
-    automation_rule::{AutomationRule, TriggerType},
+    agent_prompt_settings::AgentPromptSettings,
     context_artifact::{ArtifactType, ContextArtifact},
+    execution_artifact::ExecutionArtifact,
     image::TaskImage,
     kanban_column::KanbanColumn,
     project::{Project, ProjectError},
     project_repo::ProjectRepo,
-    repo::Repo,
     tag::Tag,
     task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
     task_dependency::TaskDependency,
     task_event::{ActorType, CreateTaskEvent, EventTriggerType, TaskEvent},
+    task_label::{LabelMatch, TaskLabel},
+    task_log_retention::{TaskLogRetention, UpdateTaskLogRetention},
+    task_subscription::TaskSubscription,
     workspace::{CreateWorkspace, Workspace},
     workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
@@ -35,25 +39,35 @@ use executors::profile::ExecutorProfileId;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use services::services::{
-    container::{AgentContext, ContainerService, build_decision_instructions, read_decision_file},
-    events::task_patch,
+    automation,
+    column_checklist,
+    column_entry_debounce::{ColumnEntryDebouncer, debounce_delay},
+    container::{AgentContext, ContainerService, build_decision_instructions},
+    cost_estimate::CostEstimate,
+    cycle_times::{self, TaskCycleTime},
+    decision_file::{read_decision_file, validate_decision_value, write_decision_value},
+    events::{TaskStreamFilter, task_patch},
     git::GitService,
+    initial_column,
+    job_queue::{self, Job},
+    plan_review,
     share::ShareError,
-    workspace_manager::WorkspaceManager,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
 use utils::{api::oauth::LoginStatus, response::ApiResponse};
+use utoipa::IntoParams;
 use uuid::Uuid;
 
 use crate::{
     DeploymentImpl, error::ApiError, middleware::load_task_middleware,
-    routes::task_attempts::WorkspaceRepoInput,
+    routes::task_attempts::{WorkspaceRepoInput, resolve_workspace_repos},
     routes::debug_events::{emit_debug_event, DebugEvent},
+    routes::users,
 };
 
 /// Convert a Task to TaskWithAttemptStatus with default values (for broadcasting new tasks)
-fn task_to_status(task: &Task) -> TaskWithAttemptStatus {
+pub(crate) fn task_to_status(task: &Task) -> TaskWithAttemptStatus {
     TaskWithAttemptStatus {
         task: task.clone(),
         has_in_progress_attempt: false,
@@ -63,69 +77,257 @@ fn task_to_status(task: &Task) -> TaskWithAttemptStatus {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct TaskQuery {
     pub project_id: Uuid,
+    /// Comma-separated label ids or names (case-insensitive) to filter by
+    #[serde(default)]
+    pub labels: Option<String>,
+    /// Whether tasks need any (default) or all of `labels`
+    #[serde(default)]
+    pub label_match: Option<LabelMatch>,
+    /// Comma-separated column ids to filter by
+    #[serde(default)]
+    pub columns: Option<String>,
+    /// Only tasks assigned to this user
+    #[serde(default)]
+    pub assignee: Option<Uuid>,
+    /// Only tasks watched directly or through a label
+    #[serde(default)]
+    pub watched: Option<bool>,
 }
 
+/// Comma-separated list items, trimmed, without empty ones
+fn split_list(list: Option<&str>) -> impl Iterator<Item = &str> {
+    list.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+impl TaskQuery {
+    /// Filters of the query, resolved against the project
+    async fn stream_filter(&self, pool: &sqlx::PgPool) -> Result<TaskStreamFilter, ApiError> {
+        let columns = split_list(self.columns.as_deref())
+            .map(|id| {
+                Uuid::parse_str(id)
+                    .map_err(|_| ApiError::BadRequest(format!("Invalid column id: {id}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let subscription = TaskStreamSubscription {
+            columns,
+            labels: split_list(self.labels.as_deref()).map(str::to_string).collect(),
+            label_match: self.label_match,
+            assignee: self.assignee,
+            watched: self.watched.unwrap_or(false),
+        };
+        subscription.resolve(pool, self.project_id).await
+    }
+}
+
+/// Filters of a tasks WebSocket, sent as a `subscribe` message to change them without
+/// reconnecting. Each message replaces the previous filters.
+#[derive(Debug, Default, Deserialize, TS)]
+pub struct TaskStreamSubscription {
+    /// Column ids; empty allows every column
+    #[serde(default)]
+    pub columns: Vec<Uuid>,
+    /// Label ids or names (case-insensitive); empty allows every task
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Whether tasks need any (default) or all of `labels`
+    #[serde(default)]
+    pub label_match: Option<LabelMatch>,
+    /// Only tasks assigned to this user
+    #[serde(default)]
+    pub assignee: Option<Uuid>,
+    /// Only tasks watched directly or through a label
+    #[serde(default)]
+    pub watched: bool,
+}
+
+impl TaskStreamSubscription {
+    async fn resolve(
+        &self,
+        pool: &sqlx::PgPool,
+        project_id: Uuid,
+    ) -> Result<TaskStreamFilter, ApiError> {
+        Ok(TaskStreamFilter {
+            column_ids: self.columns.clone(),
+            label_ids: resolve_label_filter(pool, project_id, &self.labels).await?,
+            label_match: self.label_match.unwrap_or_default(),
+            assignee_id: self.assignee,
+            watched_only: self.watched,
+        })
+    }
+}
+
+/// Messages a tasks WebSocket client may send
+#[derive(Debug, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TasksWsClientMessage {
+    /// Replace the stream's filters; a fresh snapshot follows
+    Subscribe(TaskStreamSubscription),
+}
+
+/// Resolve the `labels` filter against the project's labels
+async fn resolve_label_filter(
+    pool: &sqlx::PgPool,
+    project_id: Uuid,
+    labels: &[String],
+) -> Result<Vec<Uuid>, ApiError> {
+    if labels.is_empty() {
+        return Ok(Vec::new());
+    }
+    let project_labels = TaskLabel::find_by_project(pool, project_id).await?;
+    let mut label_ids = Vec::new();
+    for term in labels
+        .iter()
+        .map(|term| term.trim())
+        .filter(|term| !term.is_empty())
+    {
+        let label = project_labels
+            .iter()
+            .find(|label| label.id.to_string() == term || label.name.eq_ignore_ascii_case(term))
+            .ok_or_else(|| ApiError::BadRequest(format!("Unknown label: {term}")))?;
+        if !label_ids.contains(&label.id) {
+            label_ids.push(label.id);
+        }
+    }
+    Ok(label_ids)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks",
+    tag = "tasks",
+    params(TaskQuery),
+    responses((status = 200, body = ApiResponse<Vec<TaskWithAttemptStatus>>))
+)]
 pub async fn get_tasks(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<TaskWithAttemptStatus>>>, ApiError> {
-    let tasks =
-        Task::find_by_project_id_with_attempt_status(&deployment.db().pool, query.project_id)
-            .await?;
+    let pool = &deployment.db().pool;
+    let mut tasks = Task::find_by_project_id_with_attempt_status(pool, query.project_id).await?;
+
+    let filter = query.stream_filter(pool).await?;
+    tasks.retain(|task| filter.allows_column(task.column_id));
+    if !filter.label_ids.is_empty() {
+        let task_ids =
+            TaskLabel::find_task_ids_with_labels(pool, &filter.label_ids, filter.label_match)
+                .await?;
+        tasks.retain(|task| task_ids.contains(&task.id));
+    }
+    if let Some(assignee_id) = filter.assignee_id {
+        let assigned = Task::find_by_assignee(pool, assignee_id).await?;
+        tasks.retain(|task| assigned.iter().any(|assigned| assigned.id == task.id));
+    }
+    if filter.watched_only {
+        let watched = TaskSubscription::find_watched_task_ids(pool).await?;
+        tasks.retain(|task| watched.contains(&task.id));
+    }
 
     Ok(ResponseJson(ApiResponse::success(tasks)))
 }
 
+/// Stream the project's tasks passing the query's filters: a snapshot, then a JSON Patch
+/// per task change holding only the fields that changed. A `subscribe` message (see
+/// `TasksWsClientMessage`) swaps the filters and starts over from a new snapshot.
 pub async fn stream_tasks_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskQuery>,
-) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_tasks_ws(socket, deployment, query.project_id).await {
+) -> Result<impl IntoResponse, ApiError> {
+    let filter = query.stream_filter(&deployment.db().pool).await?;
+    Ok(ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_tasks_ws(socket, deployment, query.project_id, filter).await {
             tracing::warn!("tasks WS closed: {}", e);
         }
-    })
+    }))
 }
 
 async fn handle_tasks_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
     project_id: Uuid,
+    filter: TaskStreamFilter,
 ) -> anyhow::Result<()> {
     // Get the raw stream and convert LogMsg to WebSocket messages
     let mut stream = deployment
         .events()
-        .stream_tasks_raw(project_id)
+        .stream_tasks_raw(project_id, filter)
         .await?
-        .map_ok(|msg| msg.to_ws_message_unchecked());
+        .map_ok(|msg| msg.to_ws_message_unchecked())
+        .boxed();
 
     // Split socket into sender and receiver
     let (mut sender, mut receiver) = socket.split();
 
-    // Drain (and ignore) any client->server messages so pings/pongs work
-    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
-
-    // Forward server messages
-    while let Some(item) = stream.next().await {
-        match item {
-            Ok(msg) => {
-                if sender.send(msg).await.is_err() {
-                    break; // client disconnected
+    loop {
+        tokio::select! {
+            // Forward server messages
+            item = stream.next() => {
+                match item {
+                    Some(Ok(msg)) => {
+                        if sender.send(msg).await.is_err() {
+                            break; // client disconnected
+                        }
+                    }
+                    Some(Err(e)) => {
+                        tracing::error!("stream error: {}", e);
+                        break;
+                    }
+                    None => break,
                 }
             }
-            Err(e) => {
-                tracing::error!("stream error: {}", e);
-                break;
+            // Re-subscribe on request; other client messages are ignored
+            msg = receiver.next() => {
+                let text = match msg {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+                let message = serde_json::from_str::<TasksWsClientMessage>(text.as_str());
+                let subscription = match message {
+                    Ok(TasksWsClientMessage::Subscribe(subscription)) => subscription,
+                    Err(e) => {
+                        tracing::debug!("Ignoring tasks WS message: {}", e);
+                        continue;
+                    }
+                };
+                let pool = &deployment.db().pool;
+                let filter = match subscription.resolve(pool, project_id).await {
+                    Ok(filter) => filter,
+                    Err(e) => {
+                        tracing::debug!("Ignoring tasks WS subscription: {}", e);
+                        continue;
+                    }
+                };
+                // The new stream opens with a snapshot replacing the client's tasks
+                stream = deployment
+                    .events()
+                    .stream_tasks_raw(project_id, filter)
+                    .await?
+                    .map_ok(|msg| msg.to_ws_message_unchecked())
+                    .boxed();
             }
         }
     }
     Ok(())
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path, description = "Task ID")),
+    responses(
+        (status = 200, body = ApiResponse<Task>),
+        (status = 404, description = "Task not found")
+    )
+)]
 pub async fn get_task(
     Extension(task): Extension<Task>,
     State(_deployment): State<DeploymentImpl>,
@@ -133,6 +335,56 @@ pub async fn get_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct CostEstimateQuery {
+    /// Column to estimate for (defaults to the task's current column)
+    pub column_id: Option<Uuid>,
+}
+
+/// Estimate token usage for starting an attempt on this task, checked
+/// against the configured attempt budget
+pub async fn get_task_cost_estimate(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<CostEstimateQuery>,
+) -> Result<ResponseJson<ApiResponse<CostEstimate>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let column = match query.column_id.or(task.column_id) {
+        Some(column_id) => Some(
+            KanbanColumn::find_by_id(pool, column_id)
+                .await?
+                .ok_or(SqlxError::RowNotFound)?,
+        ),
+        None => None,
+    };
+
+    let budget = deployment
+        .config()
+        .read()
+        .await
+        .attempt_budget
+        .max_estimated_tokens;
+    let estimate = CostEstimate::for_attempt(
+        pool,
+        task.project_id,
+        Some(task.id),
+        &task.to_prompt(),
+        column.as_ref(),
+        budget,
+    )
+    .await?;
+
+    Ok(ResponseJson(ApiResponse::success(estimate)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/tasks",
+    tag = "tasks",
+    request_body = CreateTask,
+    responses((status = 200, description = "Created task", body = ApiResponse<Task>))
+)]
 pub async fn create_task(
     State(deployment): State<DeploymentImpl>,
     Json(mut payload): Json<CreateTask>,
@@ -146,20 +398,22 @@ pub async fn create_task(
     );
 
     // If no column_id provided, default to the project's initial/backlog column
+    let mut initial_column_id = None;
     if payload.column_id.is_none() {
-        let project = Project::find_by_id(&deployment.db().pool, payload.project_id)
+        Project::find_by_id(&deployment.db().pool, payload.project_id)
             .await?
             .ok_or_else(|| ApiError::BadRequest(format!("Project {} not found", payload.project_id)))?;
 
-        if let Some(board_id) = project.board_id {
-            if let Some(initial_column) = KanbanColumn::find_initial(&deployment.db().pool, board_id).await? {
-                tracing::debug!(
-                    "Defaulting task column_id to initial column '{}' ({})",
-                    initial_column.name,
-                    initial_column.id
-                );
-                payload.column_id = Some(initial_column.id);
-            }
+        if let Some(initial_column) =
+            initial_column::column_for_new_task(&deployment.db().pool, payload.project_id).await?
+        {
+            tracing::debug!(
+                "Defaulting task column_id to initial column '{}' ({})",
+                initial_column.name,
+                initial_column.id
+            );
+            payload.column_id = Some(initial_column.id);
+            initial_column_id = Some(initial_column.id);
         }
     }
 
@@ -172,10 +426,26 @@ pub async fn create_task(
         .push_patch(task_patch::add(&task_to_status(&task)));
 
     // Record task created event
-    let event = CreateTaskEvent::task_created(task.id, ActorType::User, None);
+    let actor_id = users::actor_id(&deployment).await;
+    let event = CreateTaskEvent::task_created(task.id, ActorType::User, actor_id);
     if let Err(e) = TaskEvent::create(&deployment.db().pool, &event).await {
         tracing::error!("Failed to record task created event for task {}: {}", task.id, e);
     }
+    if let Some(column_id) = initial_column_id {
+        initial_column::record_entry(&deployment.db().pool, task.id, column_id).await;
+    }
+    if let Some(column_id) = task.column_id
+        && let Err(e) = automation::apply_column_label_rules(
+            &deployment.db().pool,
+            task.id,
+            task.project_id,
+            None,
+            Some(column_id),
+        )
+        .await
+    {
+        tracing::error!("Failed to apply column label rules to task {}: {}", task.id, e);
+    }
 
     if let Some(image_ids) = &payload.image_ids {
         TaskImage::associate_many_dedup(&deployment.db().pool, task.id, image_ids).await?;
@@ -227,6 +497,39 @@ pub async fn create_task_and_start(
         None
     };
 
+    let workspace_repos = resolve_workspace_repos(&deployment, project.id, &payload.repos).await?;
+
+    // Refuse to start if the attempt is estimated to exceed the budget
+    let budget = deployment
+        .config()
+        .read()
+        .await
+        .attempt_budget
+        .max_estimated_tokens;
+    if budget.is_some() {
+        let description = payload
+            .task
+            .description
+            .as_deref()
+            .filter(|d| !d.trim().is_empty());
+        let prompt = match description {
+            Some(description) => format!("{}\n\n{}", payload.task.title, description),
+            None => payload.task.title.clone(),
+        };
+        let estimate = CostEstimate::for_attempt(
+            pool,
+            project.id,
+            None,
+            &prompt,
+            workflow_column.as_ref(),
+            budget,
+        )
+        .await?;
+        if let Some(message) = estimate.budget_exceeded_message() {
+            return Err(ApiError::BadRequest(message));
+        }
+    }
+
     // Create the task with column_id set to the workflow start column (if found)
     let mut create_task_data = payload.task.clone();
     if let Some(ref wf_column) = workflow_column {
@@ -242,7 +545,8 @@ pub async fn create_task_and_start(
     let task = Task::create(pool, &create_task_data, task_id).await?;
 
     // Record task created event
-    let event = CreateTaskEvent::task_created(task.id, ActorType::User, None);
+    let actor_id = users::actor_id(&deployment).await;
+    let event = CreateTaskEvent::task_created(task.id, ActorType::User, actor_id);
     if let Err(e) = TaskEvent::create(pool, &event).await {
         tracing::error!("Failed to record task created event for task {}: {}", task.id, e);
     }
@@ -364,9 +668,10 @@ pub async fn create_task_and_start(
         }
         None => {
             // Legacy path: Create per-task workspace (for ungrouped tasks)
+            let executor = payload.executor_profile_id.executor.to_string();
             let git_branch_name = deployment
                 .container()
-                .git_branch_from_workspace(&attempt_id, &task.title)
+                .git_branch_from_workspace(&attempt_id, &task, Some(&executor))
                 .await;
 
             tracing::info!(
@@ -387,14 +692,6 @@ pub async fn create_task_and_start(
         }
     };
 
-    let workspace_repos: Vec<CreateWorkspaceRepo> = payload
-        .repos
-        .iter()
-        .map(|r| CreateWorkspaceRepo {
-            repo_id: r.repo_id,
-            target_branch: r.target_branch.clone(),
-        })
-        .collect();
     WorkspaceRepo::create_many(&deployment.db().pool, workspace.id, &workspace_repos).await?;
 
     let is_attempt_running = deployment
@@ -437,6 +734,17 @@ pub async fn create_task_and_start(
     Ok(ResponseJson(ApiResponse::success(task_with_status)))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path, description = "Task ID")),
+    request_body = UpdateTask,
+    responses(
+        (status = 200, description = "Updated task", body = ApiResponse<Task>),
+        (status = 404, description = "Task not found")
+    )
+)]
 pub async fn update_task(
     Extension(existing_task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -463,6 +771,29 @@ pub async fn update_task(
     // Check if moving to a new column
     let is_column_changing = payload.column_id.is_some() && payload.column_id != existing_task.column_id;
 
+    // Leaving a column with a checklist needs every item ticked first
+    if is_column_changing && let Some(current_column_id) = existing_task.column_id {
+        let pending =
+            column_checklist::pending_items(pool, existing_task.id, current_column_id).await?;
+        if !pending.is_empty() {
+            let column_name = KanbanColumn::find_by_id(pool, current_column_id)
+                .await?
+                .map(|c| c.name)
+                .unwrap_or_default();
+            return Err(ApiError::Conflict(column_checklist::blocked_message(
+                &column_name,
+                &pending,
+            )));
+        }
+        if plan_review::blocks_leaving_column(pool, existing_task.id, current_column_id).await? {
+            let column_name = KanbanColumn::find_by_id(pool, current_column_id)
+                .await?
+                .map(|c| c.name)
+                .unwrap_or_default();
+            return Err(ApiError::Conflict(plan_review::blocked_message(&column_name)));
+        }
+    }
+
     if is_column_changing {
         if let Some(target_column_id) = payload.column_id {
             // Get the target column to check if it starts a workflow
@@ -592,42 +923,31 @@ pub async fn update_task(
                 new_column_id,
                 EventTriggerType::DragDrop, // User-initiated column change
                 ActorType::User,
-                None,
+                users::actor_id(&deployment).await,
             );
             if let Err(e) = TaskEvent::create(pool, &event).await {
                 tracing::error!("Failed to record column transition event: {}", e);
             }
-        }
-
-        // Fire OnExit rules for the old column
-        if let Some(old_column_id) = existing_task.column_id {
-            let exit_rules = AutomationRule::find_triggered_rules(pool, old_column_id, TriggerType::OnExit).await?;
-            for rule in exit_rules {
-                tracing::info!(
-                    "Automation triggered: OnExit rule '{}' (action: {}) for task {} leaving column {}",
-                    rule.name.as_deref().unwrap_or("unnamed"),
-                    rule.action_type,
-                    task.id,
-                    old_column_id
-                );
-                // TODO: Execute automation action
+            if let Err(e) = automation::apply_column_label_rules(
+                pool,
+                task.id,
+                task.project_id,
+                existing_task.column_id,
+                Some(new_column_id),
+            )
+            .await
+            {
+                tracing::error!("Failed to apply column label rules to task {}: {}", task.id, e);
             }
+            deployment.container().spawn_column_automations(
+                task.id,
+                existing_task.column_id,
+                new_column_id,
+                None,
+            );
         }
 
-        // Fire OnEnter rules for the new column
         if let Some(new_column_id) = payload.column_id {
-            let enter_rules = AutomationRule::find_triggered_rules(pool, new_column_id, TriggerType::OnEnter).await?;
-            for rule in enter_rules {
-                tracing::info!(
-                    "Automation triggered: OnEnter rule '{}' (action: {}) for task {} entering column {}",
-                    rule.name.as_deref().unwrap_or("unnamed"),
-                    rule.action_type,
-                    task.id,
-                    new_column_id
-                );
-                // TODO: Execute automation action
-            }
-
             // Auto-start agent execution if column has an assigned agent
             if let Some(new_column) = KanbanColumn::find_by_id(pool, new_column_id).await? {
                 // Get old column name for debug event
@@ -662,49 +982,27 @@ pub async fn update_task(
                     new_column.starts_workflow,
                     new_column.agent_id
                 );
-                if let Some(agent_id) = new_column.agent_id {
-                    // Check if there's already an ACTIVE (running) execution - don't start another
-                    // Note: This allows starting a new execution after the previous one completes,
-                    // which is the intended behavior for agent-to-agent handoff via column transitions.
-                    // The workspace will be reused (not recreated) to maintain continuity.
-                    let has_running = Task::has_active_attempt(pool, task.id).await.unwrap_or(false);
-                    if has_running {
-                        tracing::debug!(
-                            "Skipping auto-start for task {} - execution already running",
-                            task.id
-                        );
-                        // Don't start another execution while one is running
-                    } else {
-                        // Fetch the agent to get its context
-                        match Agent::find_by_id(pool, agent_id).await {
-                            Ok(Some(agent)) => {
-                                if let Err(e) = spawn_agent_execution(
-                                    deployment.clone(),
-                                    task.clone(),
-                                    agent,
-                                    &new_column,
-                                ).await {
-                                    tracing::error!(
-                                        "Failed to auto-start agent execution for task {} in column {}: {}",
-                                        task.id,
-                                        new_column_id,
-                                        e
-                                    );
-                                }
-                            }
-                            Ok(None) => {
-                                tracing::warn!("Agent {} not found for column {}", agent_id, new_column.name);
-                            }
-                            Err(e) => {
-                                tracing::error!("Failed to fetch agent {}: {}", agent_id, e);
-                            }
-                        }
-                    }
-                } else {
+                // A later move supersedes this one's pending auto-start
+                let ticket = ColumnEntryDebouncer::shared().enter(task.id);
+                if new_column.agent_id.is_none() {
                     tracing::debug!(
                         "No agent assigned to column '{}' - skipping auto-start",
                         new_column.name
                     );
+                } else {
+                    let debounce_ms = deployment.config().read().await.column_entry_debounce_ms;
+                    match debounce_delay(debounce_ms) {
+                        Some(delay) => {
+                            tokio::spawn(debounced_auto_start(
+                                deployment.clone(),
+                                task.id,
+                                new_column.id,
+                                ticket,
+                                delay,
+                            ));
+                        }
+                        None => auto_start_column_agent(&deployment, &task, &new_column).await?,
+                    }
                 }
             }
         }
@@ -760,6 +1058,16 @@ async fn ensure_shared_task_auth(
     Ok(())
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path, description = "Task ID")),
+    responses(
+        (status = 202, description = "Task deletion started"),
+        (status = 409, description = "Task has running execution processes")
+    )
+)]
 pub async fn delete_task(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -847,40 +1155,15 @@ pub async fn delete_task(
         )
         .await;
 
-    let task_id = task.id;
-    let pool = pool.clone();
-    tokio::spawn(async move {
-        tracing::info!(
-            "Starting background cleanup for task {} ({} workspaces, {} repos)",
-            task_id,
-            workspace_dirs.len(),
-            repositories.len()
-        );
-
-        for workspace_dir in &workspace_dirs {
-            if let Err(e) = WorkspaceManager::cleanup_workspace(workspace_dir, &repositories).await
-            {
-                tracing::error!(
-                    "Background workspace cleanup failed for task {} at {}: {}",
-                    task_id,
-                    workspace_dir.display(),
-                    e
-                );
-            }
-        }
-
-        match Repo::delete_orphaned(&pool).await {
-            Ok(count) if count > 0 => {
-                tracing::info!("Deleted {} orphaned repo records", count);
-            }
-            Err(e) => {
-                tracing::error!("Failed to delete orphaned repos: {}", e);
-            }
-            _ => {}
-        }
-
-        tracing::info!("Background cleanup completed for task {}", task_id);
-    });
+    // Removing worktrees can be slow; the job queue finishes it even across restarts
+    let cleanup = Job::CleanupTaskWorkspaces {
+        task_id: task.id,
+        workspace_dirs,
+        repositories,
+    };
+    if let Err(e) = job_queue::enqueue(pool, &cleanup).await {
+        tracing::error!("Failed to queue workspace cleanup for task {}: {}", task.id, e);
+    }
 
     // Return 202 Accepted to indicate deletion was scheduled
     Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
@@ -918,6 +1201,125 @@ pub async fn share_task(
     })))
 }
 
+/// Start the column's agent on a task that just entered it, unless the task is
+/// detached or already has an execution running
+async fn auto_start_column_agent(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    column: &KanbanColumn,
+) -> Result<(), ApiError> {
+    let Some(agent_id) = column.agent_id else {
+        return Ok(());
+    };
+    let pool = &deployment.db().pool;
+
+    // Check if there's already an ACTIVE (running) execution - don't start another
+    // Note: This allows starting a new execution after the previous one completes,
+    // which is the intended behavior for agent-to-agent handoff via column transitions.
+    // The workspace will be reused (not recreated) to maintain continuity.
+    let has_running = Task::has_active_attempt(pool, task.id).await.unwrap_or(false);
+    if task.is_detached() {
+        tracing::debug!(
+            "Skipping auto-start for task {} - handed back to a human",
+            task.id
+        );
+    } else if has_running {
+        tracing::debug!(
+            "Skipping auto-start for task {} - execution already running",
+            task.id
+        );
+        // Don't start another execution while one is running
+    } else {
+        // Fetch the agent to get its context
+        match Agent::find_by_id(pool, agent_id).await {
+            Ok(Some(agent)) => {
+                if let Err(e) = spawn_agent_execution(
+                    deployment.clone(),
+                    task.clone(),
+                    agent,
+                    column,
+                ).await {
+                    tracing::error!(
+                        "Failed to auto-start agent execution for task {} in column {}: {}",
+                        task.id,
+                        column.id,
+                        e
+                    );
+                }
+            }
+            Ok(None) => {
+                tracing::warn!("Agent {} not found for column {}", agent_id, column.name);
+            }
+            Err(e) => {
+                tracing::error!("Failed to fetch agent {}: {}", agent_id, e);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Start the column's agent once the task has rested there for `delay`, so a task
+/// dragged through several columns only starts the agent where it ends up
+async fn debounced_auto_start(
+    deployment: DeploymentImpl,
+    task_id: Uuid,
+    column_id: Uuid,
+    ticket: u64,
+    delay: Duration,
+) {
+    tokio::time::sleep(delay).await;
+    if !ColumnEntryDebouncer::shared().settle(task_id, ticket) {
+        tracing::debug!(
+            "Skipping auto-start for task {} in column {} - moved again",
+            task_id,
+            column_id
+        );
+        return;
+    }
+
+    let pool = &deployment.db().pool;
+    let task = match Task::find_by_id(pool, task_id).await {
+        Ok(Some(task)) if task.column_id == Some(column_id) => task,
+        Ok(_) => return,
+        Err(e) => {
+            tracing::error!("Failed to load task {} for auto-start: {}", task_id, e);
+            return;
+        }
+    };
+    let column = match KanbanColumn::find_by_id(pool, column_id).await {
+        Ok(Some(column)) => column,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::error!("Failed to load column {} for auto-start: {}", column_id, e);
+            return;
+        }
+    };
+
+    if let Err(e) = auto_start_column_agent(&deployment, &task, &column).await {
+        tracing::error!("Failed to auto-start agent for task {}: {}", task_id, e);
+    }
+    push_task_update(&deployment, task_id).await;
+}
+
+/// Broadcast a task's current state to the tasks stream
+async fn push_task_update(deployment: &DeploymentImpl, task_id: Uuid) {
+    let pool = &deployment.db().pool;
+    if let Ok(Some(task)) = Task::find_by_id(pool, task_id).await {
+        let active_workspace = Workspace::find_active_for_task(pool, task.id).await.ok().flatten();
+        let task_status = TaskWithAttemptStatus {
+            task,
+            has_in_progress_attempt: active_workspace.is_some(),
+            last_attempt_failed: false,
+            executor: String::new(),
+            latest_attempt_id: active_workspace.map(|w| w.id),
+        };
+        deployment
+            .events()
+            .msg_store()
+            .push_patch(task_patch::replace(&task_status));
+    }
+}
+
 /// Spawn agent execution for a task when entering a column with an assigned agent
 pub async fn spawn_agent_execution(
     deployment: DeploymentImpl,
@@ -974,12 +1376,17 @@ pub async fn spawn_agent_execution(
                     return Err(anyhow::anyhow!("Project has no repositories configured"));
                 }
 
+                let default_branches =
+                    ProjectRepo::find_default_target_branches(pool, project.id).await?;
                 let git_service = GitService {};
                 let mut workspace_repos_to_create: Vec<CreateWorkspaceRepo> = Vec::new();
                 for repo in &repos {
-                    let target_branch = git_service
-                        .get_current_branch(&repo.path)
-                        .unwrap_or_else(|_| "main".to_string());
+                    let target_branch = match default_branches.get(&repo.id) {
+                        Some(branch) => branch.clone(),
+                        None => git_service
+                            .get_current_branch(&repo.path)
+                            .unwrap_or_else(|_| "main".to_string()),
+                    };
                     workspace_repos_to_create.push(CreateWorkspaceRepo {
                         repo_id: repo.id,
                         target_branch,
@@ -989,7 +1396,7 @@ pub async fn spawn_agent_execution(
                 let attempt_id = Uuid::new_v4();
                 let git_branch_name = deployment
                     .container()
-                    .git_branch_from_workspace(&attempt_id, &task.title)
+                    .git_branch_from_workspace(&attempt_id, &task, Some(&agent.name))
                     .await;
 
                 let agent_working_dir = project
@@ -1035,11 +1442,12 @@ pub async fn spawn_agent_execution(
         reusing_existing,
     });
 
-    // Parse the executor from agent.executor (e.g., "CLAUDE_CODE")
+    // Parse the executor from agent.executor (e.g., "CLAUDE_CODE"); the column may pick
+    // another variant of it (e.g. OPUS for planning columns)
     let base_agent = BaseCodingAgent::from_str(&agent.executor).map_err(|e| {
         anyhow::anyhow!("Failed to parse executor '{}': {}", agent.executor, e)
     })?;
-    let executor_profile_id = ExecutorProfileId::new(base_agent);
+    let executor_profile_id = column.executor_profile(base_agent);
 
     // Read existing decision file for any feedback from prior rejection
     let existing_decision = read_decision_file(&workspace).await;
@@ -1055,6 +1463,10 @@ pub async fn spawn_agent_execution(
 
     // Build decision instructions if this column has a question to answer
     // Uses hierarchical resolution: task -> project -> board
+    let language = AgentPromptSettings::for_agent(pool, agent.id)
+        .await
+        .map(|settings| settings.prompt_language())
+        .unwrap_or_default();
     let decision_instructions = build_decision_instructions(
         pool,
         column,
@@ -1062,9 +1474,11 @@ pub async fn spawn_agent_execution(
         task.project_id,
         Some(board_id),
         &existing_decision,
+        language,
     ).await;
 
-    // Combine agent's start_command (with tags expanded) with decision instructions
+    // Agent's start_command (with tags expanded) and decision instructions, as the
+    // prompt's instructions show them by default
     let start_command = match (&expanded_start_command, &decision_instructions) {
         (Some(cmd), Some(instructions)) => Some(format!("{}{}", cmd, instructions)),
         (Some(cmd), None) => Some(cmd.clone()),
@@ -1077,7 +1491,7 @@ pub async fn spawn_agent_execution(
         task_id: task.id.to_string(),
         workspace_id: workspace.id.to_string(),
         agent_name: agent.name.clone(),
-        executor: agent.executor.clone(),
+        executor: executor_profile_id.to_string(),
         system_prompt_length: agent.system_prompt.len(),
         system_prompt_preview: agent.system_prompt.chars().take(200).collect(),
         start_command_length: start_command.as_ref().map(|s| s.len()),
@@ -1132,11 +1546,14 @@ pub async fn spawn_agent_execution(
     let agent_context = AgentContext {
         system_prompt: Some(agent.system_prompt.clone()),
         workflow_history,
-        start_command,
+        start_command: expanded_start_command,
+        decision_instructions,
         deliverable: expanded_deliverable.clone(),
         name: agent.name.clone(),
         color: agent.color.clone(),
         column_name: column_name.clone(),
+        agent_id: agent.id,
+        column_id: Some(column.id),
         project_context,
         // Always use the dispatched task — handles group workspaces where workspace.task_id = tasks[0]
         task_id_override: Some(task.id),
@@ -1233,6 +1650,114 @@ async fn build_project_context_for_task(
     }
 }
 
+/// Artifacts collected from all of the task's executions, newest first
+pub async fn get_task_artifacts(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutionArtifact>>>, ApiError> {
+    let artifacts = ExecutionArtifact::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(artifacts)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SetDecisionRequest {
+    /// Decision key to set, e.g. `answer` or a key a transition expression reads
+    pub key: String,
+    #[ts(type = "JsonValue")]
+    pub value: serde_json::Value,
+    /// Appended to the decision's feedback for the next attempt
+    pub feedback: Option<String>,
+    /// Workspace whose decision file to write (defaults to the task's active one)
+    pub workspace_id: Option<Uuid>,
+}
+
+/// Merge a value into the workspace's decision file after checking it against the
+/// task's column (used by the `set_decision` MCP tool)
+pub async fn set_task_decision(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetDecisionRequest>,
+) -> Result<ResponseJson<ApiResponse<serde_json::Value>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace = match payload.workspace_id {
+        Some(workspace_id) => Workspace::find_by_id(pool, workspace_id)
+            .await?
+            .filter(|workspace| workspace.task_id == task.id),
+        None => Workspace::find_active_for_task(pool, task.id).await?,
+    }
+    .ok_or_else(|| ApiError::BadRequest("Task has no active workspace".to_string()))?;
+
+    let column = match task.column_id {
+        Some(column_id) => KanbanColumn::find_by_id(pool, column_id).await?,
+        None => None,
+    };
+    validate_decision_value(column.as_ref(), &payload.key, &payload.value)
+        .map_err(ApiError::BadRequest)?;
+
+    let decision = write_decision_value(
+        &workspace,
+        column.as_ref(),
+        &payload.key,
+        payload.value,
+        payload.feedback.as_deref(),
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_decision_set",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "key": payload.key.trim(),
+                "has_feedback": payload.feedback.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(decision)))
+}
+
+/// How long the task has spent in each column it visited
+pub async fn get_task_cycle_time(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskCycleTime>>, ApiError> {
+    let cycle_time = cycle_times::for_task(&deployment.db().read_pool, &task).await?;
+    Ok(ResponseJson(ApiResponse::success(cycle_time)))
+}
+
+/// The task's log retention override
+pub async fn get_task_log_retention(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskLogRetention>>, ApiError> {
+    let retention = TaskLogRetention::for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(retention)))
+}
+
+/// Keep the task's execution logs and artifacts forever, prune them early, or follow
+/// the default retention
+pub async fn update_task_log_retention(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateTaskLogRetention>,
+) -> Result<ResponseJson<ApiResponse<TaskLogRetention>>, ApiError> {
+    let retention = TaskLogRetention::update(&deployment.db().pool, task.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_log_retention_updated",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "retention": retention.retention,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(retention)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_actions_router = Router::new()
         .route("/", put(update_task))
@@ -1241,6 +1766,14 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
+        .route("/cost-estimate", get(get_task_cost_estimate))
+        .route("/artifacts", get(get_task_artifacts))
+        .route("/cycle-time", get(get_task_cycle_time))
+        .route(
+            "/log-retention",
+            get(get_task_log_retention).put(update_task_log_retention),
+        )
+        .route("/decision", post(set_task_decision))
         .merge(task_actions_router)
         .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
 
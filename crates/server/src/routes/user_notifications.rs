@@ -0,0 +1,56 @@
+use axum::{
+    Router,
+    extract::{Json, Query, State},
+    http::StatusCode,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use utils::{
+    api::notifications::{ListNotificationsResponse, MarkNotificationsReadRequest},
+    response::ApiResponse,
+};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+pub struct UserNotificationsQuery {
+    #[serde(default)]
+    pub unread_only: bool,
+    pub limit: Option<i64>,
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/user-notifications", get(list_user_notifications))
+        .route(
+            "/user-notifications/read",
+            post(mark_user_notifications_read),
+        )
+}
+
+/// Mention and assignment notifications for the signed-in user
+async fn list_user_notifications(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<UserNotificationsQuery>,
+) -> Result<ResponseJson<ApiResponse<ListNotificationsResponse>>, ApiError> {
+    let client = deployment.remote_client()?;
+
+    let response = client
+        .list_notifications(query.unread_only, query.limit)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+async fn mark_user_notifications_read(
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<MarkNotificationsReadRequest>,
+) -> Result<StatusCode, ApiError> {
+    let client = deployment.remote_client()?;
+
+    client.mark_notifications_read(&request).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
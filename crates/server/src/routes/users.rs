@@ -0,0 +1,230 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    task::Task,
+    user::{CreateUser, UpdateUser, User},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::{api::oauth::LoginStatus, response::ApiResponse};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Serialize, TS)]
+pub struct TaskAssignment {
+    pub task_id: Uuid,
+    pub assignee: Option<User>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AssignTask {
+    /// `null` unassigns the task
+    pub assignee_id: Option<Uuid>,
+}
+
+/// The local user of the signed-in account, or `None` when signed out
+pub(crate) async fn current_user(deployment: &DeploymentImpl) -> Result<Option<User>, ApiError> {
+    match deployment.get_login_status().await {
+        LoginStatus::LoggedOut => Ok(None),
+        LoginStatus::LoggedIn { profile } => {
+            let user = User::upsert_remote(
+                &deployment.db().pool,
+                profile.user_id,
+                profile.username.as_deref(),
+                &profile.email,
+            )
+            .await?;
+            Ok(Some(user))
+        }
+    }
+}
+
+/// `actor_id` for task events caused by the person using the app. Events are still
+/// recorded when the user can't be resolved, just without an actor id.
+pub(crate) async fn actor_id(deployment: &DeploymentImpl) -> Option<String> {
+    match current_user(deployment).await {
+        Ok(user) => user.map(|user| user.id.to_string()),
+        Err(e) => {
+            tracing::warn!("Failed to resolve the current user for a task event: {}", e);
+            None
+        }
+    }
+}
+
+fn validate_email(email: &str) -> Result<(), ApiError> {
+    let email = email.trim();
+    match email.split_once('@') {
+        Some((local, domain)) if !local.is_empty() && domain.contains('.') => Ok(()),
+        _ => Err(ApiError::BadRequest(format!(
+            "'{email}' is not a valid email address"
+        ))),
+    }
+}
+
+/// Fail when another user already has the address
+async fn ensure_email_free(
+    deployment: &DeploymentImpl,
+    email: &str,
+    except_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    match User::find_by_email(&deployment.db().pool, email).await? {
+        Some(existing) if Some(existing.id) != except_id => Err(ApiError::Conflict(format!(
+            "A user with email {} already exists",
+            existing.email
+        ))),
+        _ => Ok(()),
+    }
+}
+
+async fn find_user(deployment: &DeploymentImpl, user_id: Uuid) -> Result<User, ApiError> {
+    User::find_by_id(&deployment.db().pool, user_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn get_users(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<User>>>, ApiError> {
+    let users = User::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(users)))
+}
+
+/// Add a teammate so tasks can be assigned to them before they sign in
+pub async fn create_user(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateUser>,
+) -> Result<ResponseJson<ApiResponse<User>>, ApiError> {
+    validate_email(&payload.email)?;
+    ensure_email_free(&deployment, &payload.email, None).await?;
+    let user = User::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed("user_created", serde_json::json!({}))
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(user)))
+}
+
+pub async fn update_user(
+    State(deployment): State<DeploymentImpl>,
+    Path(user_id): Path<Uuid>,
+    Json(payload): Json<UpdateUser>,
+) -> Result<ResponseJson<ApiResponse<User>>, ApiError> {
+    if let Some(email) = &payload.email {
+        validate_email(email)?;
+        ensure_email_free(&deployment, email, Some(user_id)).await?;
+    }
+    let user = User::update(&deployment.db().pool, user_id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(user)))
+}
+
+/// Remove a user; their tasks become unassigned
+pub async fn delete_user(
+    State(deployment): State<DeploymentImpl>,
+    Path(user_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let deleted = User::delete(&deployment.db().pool, user_id).await?;
+    if deleted == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// The signed-in user, or `null` when signed out
+pub async fn get_me(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<User>>>, ApiError> {
+    let user = current_user(&deployment).await?;
+    Ok(ResponseJson(ApiResponse::success(user)))
+}
+
+/// Tasks assigned to the signed-in user, across all projects
+pub async fn get_my_tasks(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let user = current_user(&deployment).await?.ok_or(ApiError::Unauthorized)?;
+    let tasks = Task::find_by_assignee(&deployment.db().pool, user.id).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+pub async fn get_user_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Path(user_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let user = find_user(&deployment, user_id).await?;
+    let tasks = Task::find_by_assignee(&deployment.db().pool, user.id).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+pub async fn get_task_assignee(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<TaskAssignment>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let assignee = match task.assignee_id {
+        Some(user_id) => User::find_by_id(pool, user_id).await?,
+        None => None,
+    };
+    Ok(ResponseJson(ApiResponse::success(TaskAssignment {
+        task_id: task.id,
+        assignee,
+    })))
+}
+
+/// Assign a task to a user, or unassign it
+pub async fn assign_task(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<AssignTask>,
+) -> Result<ResponseJson<ApiResponse<TaskAssignment>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let assignee = match payload.assignee_id {
+        Some(user_id) => Some(
+            User::find_by_id(pool, user_id)
+                .await?
+                .ok_or_else(|| ApiError::BadRequest(format!("User {user_id} not found")))?,
+        ),
+        None => None,
+    };
+    Task::set_assignee(pool, task.id, assignee.as_ref().map(|user| user.id)).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_assigned",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "assigned": assignee.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(TaskAssignment {
+        task_id: task.id,
+        assignee,
+    })))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/me", get(get_me))
+        .route("/me/tasks", get(get_my_tasks))
+        .route("/users", get(get_users).post(create_user))
+        .route("/users/{user_id}", put(update_user).delete(delete_user))
+        .route("/users/{user_id}/tasks", get(get_user_tasks))
+        .route(
+            "/tasks/{task_id}/assignee",
+            get(get_task_assignee).put(assign_task),
+        )
+}
@@ -117,6 +117,7 @@ pub async fn apply_template(
             },
         )
         .await?;
+        KanbanColumn::copy_settings(pool, tmpl_col.id, column.id).await?;
         column_id_map.insert(tmpl_col.id, column.id);
     }
 
@@ -266,6 +267,7 @@ pub async fn save_as_template(
             new_agent_id,
         )
         .await?;
+        KanbanColumn::copy_settings(pool, col.id, new_col.id).await?;
         column_id_map.insert(col.id, new_col.id);
     }
 
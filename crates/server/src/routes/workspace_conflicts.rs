@@ -0,0 +1,33 @@
+use axum::{
+    Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::project::Project;
+use deployment::Deployment;
+use services::services::workspace_conflicts::{self, ProjectConflicts};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Files that more than one open task is changing, from the latest workspace snapshots
+pub async fn get_project_conflicts(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<ProjectConflicts>>, ApiError> {
+    let pool = &deployment.db().pool;
+    Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let conflicts = workspace_conflicts::project_conflicts(pool, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(conflicts)))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/projects/{project_id}/conflicts",
+        get(get_project_conflicts),
+    )
+}
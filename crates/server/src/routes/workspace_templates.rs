@@ -0,0 +1,138 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    project_repo::ProjectRepo,
+    workspace_template::{CreateWorkspaceTemplate, UpdateWorkspaceTemplate, WorkspaceTemplate},
+};
+use deployment::Deployment;
+use services::services::workspace_templates::validate_template_path;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+async fn ensure_project(deployment: &DeploymentImpl, project_id: Uuid) -> Result<(), ApiError> {
+    Project::find_by_id(&deployment.db().pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    Ok(())
+}
+
+async fn ensure_target_free(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    repo_id: Option<Uuid>,
+    path: &str,
+    except_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let pool = &deployment.db().pool;
+    if WorkspaceTemplate::target_taken(pool, project_id, repo_id, path.trim(), except_id).await? {
+        return Err(ApiError::Conflict(format!(
+            "A workspace template for '{}' already exists",
+            path.trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Files seeded into every new workspace of the project
+pub async fn list_workspace_templates(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkspaceTemplate>>>, ApiError> {
+    ensure_project(&deployment, project_id).await?;
+    let templates = WorkspaceTemplate::find_by_project(&deployment.db().pool, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(templates)))
+}
+
+pub async fn create_workspace_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateWorkspaceTemplate>,
+) -> Result<ResponseJson<ApiResponse<WorkspaceTemplate>>, ApiError> {
+    ensure_project(&deployment, project_id).await?;
+    validate_template_path(&payload.path).map_err(ApiError::BadRequest)?;
+
+    let pool = &deployment.db().pool;
+    if let Some(repo_id) = payload.repo_id
+        && ProjectRepo::find_by_project_and_repo(pool, project_id, repo_id)
+            .await?
+            .is_none()
+    {
+        return Err(ApiError::BadRequest(
+            "Repository not found in project".to_string(),
+        ));
+    }
+    ensure_target_free(&deployment, project_id, payload.repo_id, &payload.path, None).await?;
+
+    let template = WorkspaceTemplate::create(pool, project_id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "workspace_template_created",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+                "repo_scoped": template.repo_id.is_some(),
+                "overwrite": template.overwrite,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+pub async fn update_workspace_template(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, template_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateWorkspaceTemplate>,
+) -> Result<ResponseJson<ApiResponse<WorkspaceTemplate>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let existing = WorkspaceTemplate::find_by_id(pool, project_id, template_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    if let Some(path) = &payload.path {
+        validate_template_path(path).map_err(ApiError::BadRequest)?;
+        ensure_target_free(
+            &deployment,
+            project_id,
+            existing.repo_id,
+            path,
+            Some(existing.id),
+        )
+        .await?;
+    }
+
+    let template = WorkspaceTemplate::update(pool, project_id, template_id, &payload)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+pub async fn delete_workspace_template(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, template_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let deleted =
+        WorkspaceTemplate::delete(&deployment.db().pool, project_id, template_id).await?;
+    if !deleted {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/projects/{project_id}/workspace-templates",
+            get(list_workspace_templates).post(create_workspace_template),
+        )
+        .route(
+            "/projects/{project_id}/workspace-templates/{template_id}",
+            put(update_workspace_template).delete(delete_workspace_template),
+        )
+}
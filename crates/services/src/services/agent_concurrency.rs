@@ -0,0 +1,640 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use executors::executors::BaseCodingAgent;
+use serde::Serialize;
+use tokio::sync::{Mutex, Notify, RwLock};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::{
+    config::{AgentConcurrencyConfig, Config},
+    executor_health::{CircuitEvent, ExecutorCircuitBreaker, ExecutorHealthStatus},
+};
+
+/// How often a waiter paused by an open circuit checks whether it may probe
+const PAUSED_RECHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A coding agent currently holding a concurrency slot
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct RunningAgentSlot {
+    pub execution_process_id: Uuid,
+    pub project_id: Uuid,
+    pub executor: BaseCodingAgent,
+    #[ts(type = "Date")]
+    pub started_at: DateTime<Utc>,
+}
+
+/// A coding agent waiting for a concurrency slot
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct QueuedAgentSlot {
+    pub execution_process_id: Uuid,
+    pub workspace_id: Uuid,
+    pub project_id: Uuid,
+    pub executor: BaseCodingAgent,
+    /// Human-readable reason the agent is still waiting
+    pub reason: String,
+    #[ts(type = "Date")]
+    pub queued_at: DateTime<Utc>,
+}
+
+/// Point-in-time view of the limiter, for the UI
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct AgentConcurrencySnapshot {
+    pub limits: AgentConcurrencyConfig,
+    pub running: Vec<RunningAgentSlot>,
+    pub queued: Vec<QueuedAgentSlot>,
+    /// Executors that failed recently, and whether they're paused
+    pub executors: Vec<ExecutorHealthStatus>,
+}
+
+/// How one project's agents are faring under the limits
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ProjectFairness {
+    pub project_id: Uuid,
+    pub weight: u32,
+    pub running: usize,
+    pub queued: usize,
+    /// Part of the running slots the project's weight entitles it to, among projects
+    /// with agents running or waiting
+    pub fair_share: f64,
+    /// Part of the running slots it holds
+    pub actual_share: f64,
+    /// Agents admitted since the server started
+    #[ts(type = "number")]
+    pub admitted: u64,
+    /// Average time admitted agents spent queued
+    #[ts(type = "number")]
+    pub average_wait_ms: u64,
+    /// How long the project's longest-waiting queued agent has waited
+    #[ts(type = "number")]
+    pub longest_wait_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ProjectAdmissions {
+    admitted: u64,
+    total_wait_ms: u64,
+}
+
+#[derive(Default)]
+struct LimiterState {
+    running: Vec<RunningAgentSlot>,
+    /// In arrival order; see `blocked_reason` for who goes first
+    queued: Vec<QueuedAgentSlot>,
+    /// Waiters withdrawn from the queue whose `acquire` hasn't noticed yet
+    cancelled: HashSet<Uuid>,
+    health: ExecutorCircuitBreaker,
+    admissions: HashMap<Uuid, ProjectAdmissions>,
+}
+
+enum Claim {
+    Granted,
+    Queued { paused: bool },
+    Cancelled,
+}
+
+/// Global cap on concurrent coding agent processes, shared by every project.
+///
+/// Waiting agents of different projects share freed slots by project weight, so one
+/// busy project can't starve the others. Limits and weights are read from the config
+/// on every admission check, so changes apply without a restart. Slots are keyed by
+/// execution process id and must be released when the process exits. Executors whose
+/// circuit is open (see [`ExecutorCircuitBreaker`]) admit nothing but their probe.
+#[derive(Clone)]
+pub struct AgentConcurrencyLimiter {
+    config: Arc<RwLock<Config>>,
+    state: Arc<Mutex<LimiterState>>,
+    notify: Arc<Notify>,
+}
+
+impl AgentConcurrencyLimiter {
+    pub fn new(config: Arc<RwLock<Config>>) -> Self {
+        Self {
+            config,
+            state: Arc::new(Mutex::new(LimiterState::default())),
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Wait until a slot is available for `executor`, then claim it for
+    /// `execution_process_id`. Waiters are admitted by weighted fair share of their
+    /// projects, then in arrival order. Returns false if the waiter was cancelled
+    /// before it got a slot.
+    pub async fn acquire(
+        &self,
+        execution_process_id: Uuid,
+        workspace_id: Uuid,
+        project_id: Uuid,
+        executor: BaseCodingAgent,
+    ) -> bool {
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            match self
+                .claim(execution_process_id, workspace_id, project_id, executor)
+                .await
+            {
+                Claim::Granted => return true,
+                Claim::Cancelled => return false,
+                Claim::Queued { paused: true } => {
+                    let _ = tokio::time::timeout(PAUSED_RECHECK_INTERVAL, notified).await;
+                }
+                Claim::Queued { paused: false } => notified.await,
+            }
+        }
+    }
+
+    /// Claim a slot if one is free now. Otherwise the execution is queued and false is
+    /// returned; the caller must then `acquire` (or `cancel`) to keep its place.
+    pub async fn try_acquire(
+        &self,
+        execution_process_id: Uuid,
+        workspace_id: Uuid,
+        project_id: Uuid,
+        executor: BaseCodingAgent,
+    ) -> bool {
+        let claim = self
+            .claim(execution_process_id, workspace_id, project_id, executor)
+            .await;
+        matches!(claim, Claim::Granted)
+    }
+
+    /// Withdraw a queued execution, so its `acquire` returns false. Returns whether it
+    /// was waiting for a slot.
+    pub async fn cancel(&self, execution_process_id: Uuid) -> bool {
+        let mut state = self.state.lock().await;
+        let before = state.queued.len();
+        state
+            .queued
+            .retain(|q| q.execution_process_id != execution_process_id);
+        if state.queued.len() == before {
+            return false;
+        }
+        tracing::info!("Execution {} left the agent slot queue", execution_process_id);
+        state.cancelled.insert(execution_process_id);
+        self.notify.notify_waiters();
+        true
+    }
+
+    async fn claim(
+        &self,
+        execution_process_id: Uuid,
+        workspace_id: Uuid,
+        project_id: Uuid,
+        executor: BaseCodingAgent,
+    ) -> Claim {
+        let limits = self.config.read().await.agent_concurrency.clone();
+        let mut state = self.state.lock().await;
+        if state.cancelled.remove(&execution_process_id) {
+            return Claim::Cancelled;
+        }
+        let admitted = state
+            .health
+            .admit(executor, execution_process_id, Utc::now());
+        let paused = admitted.is_err();
+        let reason = match admitted {
+            Ok(()) => blocked_reason(&limits, &state, execution_process_id, project_id, executor),
+            Err(reason) => Some(reason),
+        };
+        let position = state
+            .queued
+            .iter()
+            .position(|q| q.execution_process_id == execution_process_id);
+
+        let Some(reason) = reason else {
+            let waited_ms = position
+                .map(|index| wait_ms(&state.queued[index], Utc::now()))
+                .unwrap_or(0);
+            state
+                .queued
+                .retain(|q| q.execution_process_id != execution_process_id);
+            let admissions = state.admissions.entry(project_id).or_default();
+            admissions.admitted += 1;
+            admissions.total_wait_ms += waited_ms;
+            state.running.push(RunningAgentSlot {
+                execution_process_id,
+                project_id,
+                executor,
+                started_at: Utc::now(),
+            });
+            if position.is_some() {
+                tracing::info!(
+                    "Agent concurrency slot granted to execution {} ({})",
+                    execution_process_id,
+                    executor
+                );
+                // Others may be able to move up now that we've left the queue
+                self.notify.notify_waiters();
+            }
+            return Claim::Granted;
+        };
+
+        match position {
+            Some(index) => state.queued[index].reason = reason,
+            None => {
+                tracing::info!(
+                    "Execution {} ({}) queued for an agent slot: {}",
+                    execution_process_id,
+                    executor,
+                    reason
+                );
+                state.queued.push(QueuedAgentSlot {
+                    execution_process_id,
+                    workspace_id,
+                    project_id,
+                    executor,
+                    reason,
+                    queued_at: Utc::now(),
+                });
+            }
+        }
+        Claim::Queued { paused }
+    }
+
+    /// Record whether a run holding a slot succeeded, for its executor's circuit.
+    /// Must be called before the slot is released.
+    pub async fn record_outcome(
+        &self,
+        execution_process_id: Uuid,
+        success: bool,
+    ) -> Option<(BaseCodingAgent, CircuitEvent)> {
+        let mut state = self.state.lock().await;
+        let executor = state
+            .running
+            .iter()
+            .find(|r| r.execution_process_id == execution_process_id)?
+            .executor;
+        let event = state
+            .health
+            .record(executor, execution_process_id, success, Utc::now());
+        // Waiters paused behind a probe can move on, or learn the new cooldown
+        self.notify.notify_waiters();
+        event.map(|event| (executor, event))
+    }
+
+    /// Close an executor's circuit by hand, releasing its waiters
+    pub async fn reset_executor(&self, executor: BaseCodingAgent) {
+        self.state.lock().await.health.reset(executor);
+        self.notify.notify_waiters();
+    }
+
+    /// Release the slot held by an execution process (no-op if it holds none). An
+    /// execution still waiting for a slot is cancelled.
+    pub async fn release(&self, execution_process_id: Uuid) {
+        if self.cancel(execution_process_id).await {
+            return;
+        }
+        let mut state = self.state.lock().await;
+        state.health.release(execution_process_id);
+        let before = state.running.len();
+        state
+            .running
+            .retain(|r| r.execution_process_id != execution_process_id);
+        if state.running.len() != before {
+            self.notify.notify_waiters();
+        }
+    }
+
+    pub async fn snapshot(&self) -> AgentConcurrencySnapshot {
+        let limits = self.config.read().await.agent_concurrency.clone();
+        let state = self.state.lock().await;
+        AgentConcurrencySnapshot {
+            limits,
+            running: state.running.clone(),
+            queued: state.queued.clone(),
+            executors: state.health.statuses(),
+        }
+    }
+
+    /// Per-project view of how the slots are being shared, longest wait first
+    pub async fn fairness(&self) -> Vec<ProjectFairness> {
+        let limits = self.config.read().await.agent_concurrency.clone();
+        let state = self.state.lock().await;
+        fairness(&limits, &state, Utc::now())
+    }
+}
+
+fn wait_ms(queued: &QueuedAgentSlot, now: DateTime<Utc>) -> u64 {
+    (now - queued.queued_at).num_milliseconds().max(0) as u64
+}
+
+fn project_weight(limits: &AgentConcurrencyConfig, project_id: Uuid) -> u32 {
+    limits
+        .project_weights
+        .get(&project_id)
+        .copied()
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Running agents of a project per unit of its weight
+fn project_load(limits: &AgentConcurrencyConfig, state: &LimiterState, project_id: Uuid) -> f64 {
+    let running = state
+        .running
+        .iter()
+        .filter(|r| r.project_id == project_id)
+        .count();
+    running as f64 / project_weight(limits, project_id) as f64
+}
+
+fn fairness(
+    limits: &AgentConcurrencyConfig,
+    state: &LimiterState,
+    now: DateTime<Utc>,
+) -> Vec<ProjectFairness> {
+    let mut project_ids: Vec<Uuid> = state
+        .running
+        .iter()
+        .map(|r| r.project_id)
+        .chain(state.queued.iter().map(|q| q.project_id))
+        .chain(state.admissions.keys().copied())
+        .collect();
+    project_ids.sort();
+    project_ids.dedup();
+
+    let active = |project_id: &Uuid| {
+        state.running.iter().any(|r| r.project_id == *project_id)
+            || state.queued.iter().any(|q| q.project_id == *project_id)
+    };
+    let active_weight: u32 = project_ids
+        .iter()
+        .filter(|id| active(id))
+        .map(|id| project_weight(limits, *id))
+        .sum();
+
+    let mut projects: Vec<ProjectFairness> = project_ids
+        .into_iter()
+        .map(|project_id| {
+            let weight = project_weight(limits, project_id);
+            let running = state
+                .running
+                .iter()
+                .filter(|r| r.project_id == project_id)
+                .count();
+            let queued: Vec<&QueuedAgentSlot> = state
+                .queued
+                .iter()
+                .filter(|q| q.project_id == project_id)
+                .collect();
+            let admissions = state
+                .admissions
+                .get(&project_id)
+                .copied()
+                .unwrap_or_default();
+            let fair_share = if active(&project_id) && active_weight > 0 {
+                weight as f64 / active_weight as f64
+            } else {
+                0.0
+            };
+            let actual_share = if state.running.is_empty() {
+                0.0
+            } else {
+                running as f64 / state.running.len() as f64
+            };
+            ProjectFairness {
+                project_id,
+                weight,
+                running,
+                queued: queued.len(),
+                fair_share,
+                actual_share,
+                admitted: admissions.admitted,
+                average_wait_ms: admissions
+                    .total_wait_ms
+                    .checked_div(admissions.admitted)
+                    .unwrap_or(0),
+                longest_wait_ms: queued.iter().map(|q| wait_ms(q, now)).max().unwrap_or(0),
+            }
+        })
+        .collect();
+    projects.sort_by(|a, b| b.longest_wait_ms.cmp(&a.longest_wait_ms));
+    projects
+}
+
+/// Running count and cap for `executor`, if it has a per-executor limit that is full
+fn executor_full(
+    limits: &AgentConcurrencyConfig,
+    state: &LimiterState,
+    executor: BaseCodingAgent,
+) -> Option<(usize, u32)> {
+    let max = *limits.per_executor.get(&executor)?;
+    let running = state
+        .running
+        .iter()
+        .filter(|r| r.executor == executor)
+        .count();
+    (running >= max as usize).then_some((running, max))
+}
+
+/// Why `execution_process_id` cannot start yet, or None if it can take a slot now
+fn blocked_reason(
+    limits: &AgentConcurrencyConfig,
+    state: &LimiterState,
+    execution_process_id: Uuid,
+    project_id: Uuid,
+    executor: BaseCodingAgent,
+) -> Option<String> {
+    if let Some(max) = limits.max_concurrent
+        && state.running.len() >= max as usize
+    {
+        return Some(format!(
+            "Global agent limit reached ({}/{} running)",
+            state.running.len(),
+            max
+        ));
+    }
+
+    if let Some((running, max)) = executor_full(limits, state, executor) {
+        return Some(format!(
+            "{} limit reached ({}/{} running)",
+            executor, running, max
+        ));
+    }
+
+    // Among waiters that could start now and would consume the same slot, the
+    // project running the fewest agents for its weight goes first, then arrival
+    // order. Waiters stuck on their own executor's limit don't hold up everyone else.
+    let position = state
+        .queued
+        .iter()
+        .position(|q| q.execution_process_id == execution_process_id);
+    let load = project_load(limits, state, project_id);
+    let ahead = state
+        .queued
+        .iter()
+        .enumerate()
+        .filter(|(_, q)| q.execution_process_id != execution_process_id)
+        .filter(|(_, q)| limits.max_concurrent.is_some() || q.executor == executor)
+        .filter(|(_, q)| executor_full(limits, state, q.executor).is_none())
+        .filter(|(_, q)| {
+            state
+                .health
+                .can_admit(q.executor, q.execution_process_id, Utc::now())
+        })
+        .filter(|(index, q)| {
+            let other = project_load(limits, state, q.project_id);
+            other < load || (other == load && position.is_none_or(|own| *index < own))
+        })
+        .count();
+    if ahead > 0 {
+        return Some(format!(
+            "Waiting behind {} agent(s) with a prior claim to a slot",
+            ahead
+        ));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn running(project_id: Uuid, executor: BaseCodingAgent) -> RunningAgentSlot {
+        RunningAgentSlot {
+            execution_process_id: Uuid::new_v4(),
+            project_id,
+            executor,
+            started_at: Utc::now(),
+        }
+    }
+
+    fn queued(id: Uuid, project_id: Uuid) -> QueuedAgentSlot {
+        QueuedAgentSlot {
+            execution_process_id: id,
+            workspace_id: Uuid::new_v4(),
+            project_id,
+            executor: BaseCodingAgent::ClaudeCode,
+            reason: String::new(),
+            queued_at: Utc::now(),
+        }
+    }
+
+    fn global_limit(max: u32) -> AgentConcurrencyConfig {
+        AgentConcurrencyConfig {
+            max_concurrent: Some(max),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unlimited_by_default() {
+        let project = Uuid::new_v4();
+        let state = LimiterState {
+            running: vec![running(project, BaseCodingAgent::ClaudeCode); 10],
+            queued: vec![],
+            ..Default::default()
+        };
+        let limits = AgentConcurrencyConfig::default();
+        assert!(
+            blocked_reason(
+                &limits,
+                &state,
+                Uuid::new_v4(),
+                project,
+                BaseCodingAgent::ClaudeCode
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn per_executor_limit_only_counts_that_executor() {
+        let project = Uuid::new_v4();
+        let state = LimiterState {
+            running: vec![
+                running(project, BaseCodingAgent::ClaudeCode),
+                running(project, BaseCodingAgent::Codex),
+            ],
+            queued: vec![],
+            ..Default::default()
+        };
+        let limits = AgentConcurrencyConfig {
+            per_executor: HashMap::from([(BaseCodingAgent::ClaudeCode, 1)]),
+            ..Default::default()
+        };
+        let blocked = |executor| blocked_reason(&limits, &state, Uuid::new_v4(), project, executor);
+        assert!(blocked(BaseCodingAgent::ClaudeCode).is_some());
+        assert!(blocked(BaseCodingAgent::Codex).is_none());
+    }
+
+    #[test]
+    fn waiters_are_admitted_in_order() {
+        let project = Uuid::new_v4();
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        let state = LimiterState {
+            running: vec![],
+            queued: vec![queued(first, project), queued(second, project)],
+            ..Default::default()
+        };
+        let limits = global_limit(1);
+        let claude = BaseCodingAgent::ClaudeCode;
+        assert!(blocked_reason(&limits, &state, first, project, claude).is_none());
+        assert!(blocked_reason(&limits, &state, second, project, claude).is_some());
+    }
+
+    #[test]
+    fn busy_project_yields_to_idle_one() {
+        let busy = Uuid::new_v4();
+        let idle = Uuid::new_v4();
+        let busy_waiter = Uuid::new_v4();
+        let idle_waiter = Uuid::new_v4();
+        let state = LimiterState {
+            running: vec![running(busy, BaseCodingAgent::ClaudeCode); 2],
+            queued: vec![queued(busy_waiter, busy), queued(idle_waiter, idle)],
+            ..Default::default()
+        };
+        let limits = global_limit(3);
+        let claude = BaseCodingAgent::ClaudeCode;
+        assert!(blocked_reason(&limits, &state, busy_waiter, busy, claude).is_some());
+        assert!(blocked_reason(&limits, &state, idle_waiter, idle, claude).is_none());
+
+        // Weighted four times heavier, two running agents are still under its share
+        let limits = AgentConcurrencyConfig {
+            project_weights: HashMap::from([(busy, 4)]),
+            ..global_limit(4)
+        };
+        let state = LimiterState {
+            running: vec![running(busy, claude), running(busy, claude), running(idle, claude)],
+            ..state
+        };
+        assert!(blocked_reason(&limits, &state, busy_waiter, busy, claude).is_none());
+        assert!(blocked_reason(&limits, &state, idle_waiter, idle, claude).is_some());
+
+        let projects = fairness(&limits, &state, Utc::now());
+        let busy_stats = projects.iter().find(|p| p.project_id == busy).unwrap();
+        assert_eq!((busy_stats.weight, busy_stats.running, busy_stats.queued), (4, 2, 1));
+        assert_eq!(busy_stats.fair_share, 0.8);
+    }
+
+    #[tokio::test]
+    async fn cancelled_waiter_gives_up_its_place() {
+        let mut config = Config::default();
+        config.agent_concurrency = global_limit(1);
+        let limiter = AgentConcurrencyLimiter::new(Arc::new(RwLock::new(config)));
+        let (project, workspace) = (Uuid::new_v4(), Uuid::new_v4());
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let claude = BaseCodingAgent::ClaudeCode;
+
+        assert!(limiter.try_acquire(first, workspace, project, claude).await);
+        assert!(!limiter.try_acquire(second, workspace, project, claude).await);
+        assert_eq!(limiter.snapshot().await.queued.len(), 1);
+
+        let waiter = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire(second, workspace, project, claude).await }
+        });
+        assert!(limiter.cancel(second).await);
+        assert!(!waiter.await.unwrap());
+        assert!(limiter.snapshot().await.queued.is_empty());
+        assert!(!limiter.cancel(second).await);
+    }
+}
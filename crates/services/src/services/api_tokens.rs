@@ -0,0 +1,153 @@
+//! Personal access tokens for headless API access.
+//!
+//! CI jobs and remote MCP servers send a token created under `/api/auth/tokens` as a
+//! bearer token instead of signing in. Read tokens may only make GET requests, and a
+//! token limited to a project only reaches routes that name that project (directly, or
+//! through one of its tasks or workspaces). Only the token's SHA-256 is stored.
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use db::models::api_token::ApiTokenScope;
+use ring::rand::{SecureRandom, SystemRandom};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Environment variable the MCP server reads a token from when it runs outside an
+/// execution, e.g. on another machine
+pub const API_TOKEN_ENV_VAR: &str = "VK_API_TOKEN";
+const TOKEN_PREFIX: &str = "vkpat_";
+const TOKEN_BYTES: usize = 32;
+
+/// Generate a new token, returning it together with its stored hash
+pub fn generate() -> (String, String) {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .expect("system random number generator failed");
+    let token = format!("{TOKEN_PREFIX}{}", URL_SAFE_NO_PAD.encode(bytes));
+    let hash = hash(&token);
+    (token, hash)
+}
+
+/// Whether a bearer token looks like a personal access token
+pub fn is_api_token(token: &str) -> bool {
+    token.starts_with(TOKEN_PREFIX) && token.len() > TOKEN_PREFIX.len()
+}
+
+pub fn hash(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// Whether the scope permits a request with this HTTP method
+pub fn scope_allows(scope: ApiTokenScope, method: &str) -> bool {
+    match scope {
+        ApiTokenScope::Write => true,
+        ApiTokenScope::Read => matches!(method, "GET" | "HEAD" | "OPTIONS"),
+    }
+}
+
+/// Sign-in and token management stay interactive; tokens can't mint other tokens or
+/// read the OAuth credentials
+pub fn is_auth_route(path: &str) -> bool {
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .take(2)
+        .any(|segment| segment == "auth")
+}
+
+/// Projects, tasks and workspaces a request refers to, from `/projects/{id}`,
+/// `/tasks/{id}` and `/task-attempts/{id}` path segments and the `project_id`,
+/// `task_id` and `workspace_id` query parameters
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RequestTargets {
+    pub project_ids: Vec<Uuid>,
+    pub task_ids: Vec<Uuid>,
+    pub workspace_ids: Vec<Uuid>,
+}
+
+impl RequestTargets {
+    pub fn is_empty(&self) -> bool {
+        self.project_ids.is_empty() && self.task_ids.is_empty() && self.workspace_ids.is_empty()
+    }
+
+    fn push(&mut self, kind: &str, id: Uuid) {
+        match kind {
+            "projects" | "project_id" => self.project_ids.push(id),
+            "tasks" | "task_id" => self.task_ids.push(id),
+            "task-attempts" | "workspace_id" => self.workspace_ids.push(id),
+            _ => {}
+        }
+    }
+}
+
+pub fn request_targets(path: &str, query: Option<&str>) -> RequestTargets {
+    let mut targets = RequestTargets::default();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    for pair in segments.windows(2) {
+        if let Ok(id) = Uuid::parse_str(pair[1]) {
+            targets.push(pair[0], id);
+        }
+    }
+    for (key, value) in query
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|param| param.split_once('='))
+    {
+        if let Ok(id) = Uuid::parse_str(value) {
+            targets.push(key, id);
+        }
+    }
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_tokens_are_unique_and_match_their_hash() {
+        let (token, token_hash) = generate();
+        let (other, other_hash) = generate();
+
+        assert!(is_api_token(&token));
+        assert_ne!(token, other);
+        assert_ne!(token_hash, other_hash);
+        assert_eq!(hash(&token), token_hash);
+        assert!(!is_api_token("vkpat_"));
+        assert!(!is_api_token("vkmcp_abc"));
+    }
+
+    #[test]
+    fn read_tokens_only_make_get_requests_and_never_reach_auth_routes() {
+        assert!(scope_allows(ApiTokenScope::Read, "GET"));
+        assert!(!scope_allows(ApiTokenScope::Read, "POST"));
+        assert!(scope_allows(ApiTokenScope::Write, "DELETE"));
+        assert!(is_auth_route("/api/auth/tokens"));
+        assert!(is_auth_route("/api/auth/token"));
+        assert!(!is_auth_route("/api/tasks/auth"));
+    }
+
+    #[test]
+    fn finds_the_projects_tasks_and_workspaces_a_request_refers_to() {
+        let project = Uuid::new_v4();
+        let task = Uuid::new_v4();
+        let workspace = Uuid::new_v4();
+
+        let targets = request_targets(
+            &format!("/api/task-attempts/{workspace}/diff"),
+            Some(&format!("project_id={project}&task_id={task}&limit=5")),
+        );
+        assert_eq!(
+            targets,
+            RequestTargets {
+                project_ids: vec![project],
+                task_ids: vec![task],
+                workspace_ids: vec![workspace],
+            }
+        );
+        assert!(request_targets("/api/projects", Some("limit=5")).is_empty());
+        assert_eq!(
+            request_targets(&format!("/api/tasks/{task}/labels"), None).task_ids,
+            vec![task]
+        );
+    }
+}
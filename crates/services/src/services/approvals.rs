@@ -133,6 +133,11 @@ impl Approvals {
         Ok((request, waiter))
     }
 
+    /// Whether the request still waits for an answer
+    pub fn is_pending(&self, id: &str) -> bool {
+        self.pending.contains_key(id)
+    }
+
     #[tracing::instrument(skip(self, id, req))]
     pub async fn respond(
         &self,
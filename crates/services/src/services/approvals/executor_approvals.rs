@@ -1,17 +1,28 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use db::{self, DBService};
+use db::{
+    self, DBService,
+    models::{
+        chat_approval::ChatApprovalSubject, execution_process::ExecutionProcess,
+        notification_channel::NotificationEvent,
+    },
+};
 use executors::approvals::{ExecutorApprovalError, ExecutorApprovalService};
 use serde_json::Value;
 use utils::approvals::{ApprovalRequest, ApprovalStatus, CreateApprovalRequest};
 use uuid::Uuid;
 
-use crate::services::approvals::Approvals;
+use crate::services::{
+    approvals::Approvals,
+    notification_channels::{self, ChannelNotification},
+    share::SharePublisher,
+};
 
 pub struct ExecutorApprovalBridge {
     approvals: Approvals,
     db: DBService,
+    publisher: Option<SharePublisher>,
     execution_process_id: Uuid,
 }
 
@@ -19,14 +30,48 @@ impl ExecutorApprovalBridge {
     pub fn new(
         approvals: Approvals,
         db: DBService,
+        publisher: Option<SharePublisher>,
         execution_process_id: Uuid,
     ) -> Arc<Self> {
         Arc::new(Self {
             approvals,
             db,
+            publisher,
             execution_process_id,
         })
     }
+
+    /// Post the request to the project's channels; Slack and Matrix reviewers can answer
+    /// it with a reaction
+    async fn notify_channels(&self, request: &ApprovalRequest) {
+        let Ok(approval_id) = Uuid::parse_str(&request.id) else {
+            return;
+        };
+        let ctx = match ExecutionProcess::load_context(&self.db.pool, self.execution_process_id)
+            .await
+        {
+            Ok(ctx) => ctx,
+            Err(e) => {
+                tracing::warn!("Failed to load context of approval request {}: {}", request.id, e);
+                return;
+            }
+        };
+        notification_channels::spawn_dispatch(
+            self.db.pool.clone(),
+            self.publisher.clone(),
+            ctx.project.clone(),
+            ChannelNotification::for_task(
+                NotificationEvent::ApprovalRequested,
+                &ctx.project,
+                &ctx.task,
+                format!("Agent asks to use {}", request.tool_name),
+            )
+            .requesting_approval(ChatApprovalSubject::ToolApproval {
+                approval_id,
+                execution_process_id: self.execution_process_id,
+            }),
+        );
+    }
 }
 
 #[async_trait]
@@ -48,11 +93,12 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
             self.execution_process_id,
         );
 
-        let (_, waiter) = self
+        let (request, waiter) = self
             .approvals
             .create_with_waiter(request)
             .await
             .map_err(ExecutorApprovalError::request_failed)?;
+        self.notify_channels(&request).await;
 
         let status = waiter.clone().await;
 
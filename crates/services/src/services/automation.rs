@@ -0,0 +1,992 @@
+use std::{path::PathBuf, process::Stdio, time::Duration};
+
+use db::models::{
+    agent::Agent,
+    automation_execution::{AutomationExecution, TriggerContext},
+    automation_rule::{
+        ActionType, AssignAgentConfig, AutomationRule, CreateFollowUpTaskConfig, CreatePrConfig,
+        CreateSubtasksFromPlanConfig, MoveToColumnConfig, NotifyConfig, RunScriptConfig,
+        SetLabelsConfig, TriggerType, WebhookConfig,
+    },
+    column_label_rule::{ColumnLabelRule, LabelRuleAction, LabelRuleTrigger},
+    context_artifact::ContextArtifact,
+    kanban_column::KanbanColumn,
+    merge::{Merge, MergeStatus},
+    project::Project,
+    repo::Repo,
+    repo_merge_settings::RepoMergeSettings,
+    task::{CreateTask, Task, TaskWithAttemptStatus},
+    task_event::{ActorType, CreateTaskEvent, EventTriggerType, TaskEvent},
+    task_label::TaskLabel,
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::services::{
+    column_checklist, container::ContainerService, events::task_patch, github::CreatePrRequest,
+    notification_channels, plan_review, plan_subtasks,
+};
+
+const DEFAULT_SCRIPT_TIMEOUT: Duration = Duration::from_secs(300);
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+/// Script output kept in the execution history
+const MAX_OUTPUT_BYTES: usize = 16 * 1024;
+
+/// Result of an action that ran without error
+#[derive(Debug)]
+pub enum ActionOutcome {
+    Completed(Value),
+    /// The action didn't apply (e.g. no workspace to run a script in)
+    Skipped(String),
+}
+
+/// Substitute `{{task.title}}`-style placeholders with values from the task and trigger,
+/// the same way channel notification templates are filled
+pub fn render_template(template: &str, task: &Task, trigger: &TriggerContext) -> String {
+    let vars = [
+        ("task.id", task.id.to_string()),
+        ("task.title", task.title.clone()),
+        (
+            "task.description",
+            task.description.clone().unwrap_or_default(),
+        ),
+        ("task.status", task.status.to_string()),
+        (
+            "from_column",
+            trigger.from_column_name.clone().unwrap_or_default(),
+        ),
+        ("to_column", trigger.to_column_name.clone()),
+        ("trigger", trigger.trigger_type.clone()),
+    ];
+    notification_channels::fill_placeholders(template, &vars)
+}
+
+fn truncate_output(output: &[u8]) -> String {
+    let text = String::from_utf8_lossy(output);
+    if text.len() <= MAX_OUTPUT_BYTES {
+        return text.to_string();
+    }
+    let mut end = text.len() - MAX_OUTPUT_BYTES;
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+    format!("[truncated]\n{}", &text[end..])
+}
+
+/// Execute a rule's action for a task. Agent and pull request actions need the
+/// container (executions, worktrees, code hosts) and are run by
+/// `run_column_automations`; they are skipped here.
+pub async fn execute_action(
+    pool: &PgPool,
+    rule: &AutomationRule,
+    task: &Task,
+    trigger: &TriggerContext,
+) -> Result<ActionOutcome, String> {
+    let action_type = rule
+        .get_action_type()
+        .ok_or_else(|| format!("Unknown action type '{}'", rule.action_type))?;
+    let invalid_config = |e: serde_json::Error| format!("Invalid action config: {e}");
+
+    match action_type {
+        ActionType::RunScript => {
+            run_script(
+                pool,
+                task,
+                &rule.get_action_config().map_err(invalid_config)?,
+            )
+            .await
+        }
+        ActionType::SetLabels => {
+            set_labels(
+                pool,
+                task,
+                &rule.get_action_config().map_err(invalid_config)?,
+            )
+            .await
+        }
+        ActionType::Webhook => {
+            let config: WebhookConfig = rule.get_action_config().map_err(invalid_config)?;
+            post_webhook(&config, task, trigger).await
+        }
+        ActionType::Notify => {
+            let config: NotifyConfig = rule.get_action_config().map_err(invalid_config)?;
+            notify(&config, task, trigger).await
+        }
+        ActionType::MoveToColumn => {
+            let config: MoveToColumnConfig = rule.get_action_config().map_err(invalid_config)?;
+            move_to_column(pool, rule.id, task, &config).await
+        }
+        ActionType::CreateFollowUpTask => {
+            let config: CreateFollowUpTaskConfig =
+                rule.get_action_config().map_err(invalid_config)?;
+            create_follow_up_task(pool, task, trigger, &config).await
+        }
+        ActionType::CreateSubtasksFromPlan => {
+            let config: CreateSubtasksFromPlanConfig =
+                rule.get_action_config().map_err(invalid_config)?;
+            create_subtasks_from_plan(pool, task, &config).await
+        }
+        ActionType::AssignAgent
+        | ActionType::RunAgent
+        | ActionType::CreateWorkspace
+        | ActionType::CreatePr
+        | ActionType::MergePr => Ok(ActionOutcome::Skipped(format!(
+            "{} is not supported by the automation executor",
+            action_type.as_str()
+        ))),
+    }
+}
+
+/// Automation rules a task moving between columns triggers: the OnExit rules of the
+/// column it left, then the OnEnter rules of the one it entered, each in priority order
+pub async fn triggered_rules(
+    pool: &PgPool,
+    task_id: Uuid,
+    from_column_id: Option<Uuid>,
+    to_column_id: Uuid,
+    transition_name: Option<String>,
+) -> Result<Vec<(AutomationRule, TriggerContext)>, sqlx::Error> {
+    let from_column_name = match from_column_id {
+        Some(column_id) => KanbanColumn::find_by_id(pool, column_id).await?.map(|c| c.name),
+        None => None,
+    };
+    let to_column_name = KanbanColumn::find_by_id(pool, to_column_id)
+        .await?
+        .map(|c| c.name)
+        .unwrap_or_default();
+    let trigger_context = |trigger_type: TriggerType| TriggerContext {
+        from_column_id,
+        from_column_name: from_column_name.clone(),
+        to_column_id,
+        to_column_name: to_column_name.clone(),
+        trigger_type: trigger_type.as_str().to_string(),
+        transition_name: transition_name.clone(),
+    };
+
+    let mut triggered = Vec::new();
+    let mut steps = vec![(to_column_id, TriggerType::OnEnter)];
+    if let Some(column_id) = from_column_id {
+        steps.insert(0, (column_id, TriggerType::OnExit));
+    }
+    for (column_id, trigger_type) in steps {
+        let rules =
+            AutomationRule::find_triggered_rules(pool, column_id, trigger_type.clone()).await?;
+        for rule in rules {
+            tracing::info!(
+                "Automation triggered: {} rule '{}' (action: {}) for task {} in column {}",
+                trigger_type.as_str(),
+                rule.name.as_deref().unwrap_or("unnamed"),
+                rule.action_type,
+                task_id,
+                column_id
+            );
+            triggered.push((rule, trigger_context(trigger_type)));
+        }
+    }
+    Ok(triggered)
+}
+
+/// Execute the automation rules a column change triggers, recording each run in the
+/// rule's execution history. Failures are recorded and don't stop later rules.
+pub async fn run_column_automations<C>(
+    container: &C,
+    task_id: Uuid,
+    from_column_id: Option<Uuid>,
+    to_column_id: Uuid,
+    transition_name: Option<String>,
+) where
+    C: ContainerService + Sync + ?Sized,
+{
+    let pool = &container.db().pool;
+    let rules =
+        match triggered_rules(pool, task_id, from_column_id, to_column_id, transition_name).await {
+            Ok(rules) => rules,
+            Err(e) => {
+                tracing::error!("Failed to load automation rules for task {}: {}", task_id, e);
+                return;
+            }
+        };
+    if rules.is_empty() {
+        return;
+    }
+
+    for (rule, trigger) in rules {
+        // Refetch so each rule sees changes made by earlier ones
+        let task = match Task::find_by_id(pool, task_id).await {
+            Ok(Some(task)) => task,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!("Failed to load task {} for automation: {}", task_id, e);
+                return;
+            }
+        };
+
+        let execution =
+            match AutomationExecution::create(pool, rule.id, task.id, Some(&trigger)).await {
+                Ok(execution) => execution,
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to record automation execution for rule {}: {}",
+                        rule.id,
+                        e
+                    );
+                    continue;
+                }
+            };
+        if let Err(e) = AutomationExecution::start(pool, execution.id).await {
+            tracing::error!(
+                "Failed to mark automation execution {} running: {}",
+                execution.id,
+                e
+            );
+        }
+
+        let outcome = match rule.get_action_type() {
+            Some(ActionType::AssignAgent) => assign_agent(container, &rule, &task).await,
+            Some(ActionType::CreatePr) => create_prs(container, &rule, &task, &trigger).await,
+            Some(ActionType::MergePr) => merge_prs(container, &task).await,
+            _ => execute_action(pool, &rule, &task, &trigger).await,
+        };
+
+        let recorded = match outcome {
+            Ok(ActionOutcome::Completed(result)) => {
+                let workspace_id = Workspace::find_active_for_task(pool, task.id)
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(|w| w.id);
+                AutomationExecution::complete(pool, execution.id, Some(result), workspace_id).await
+            }
+            Ok(ActionOutcome::Skipped(reason)) => {
+                tracing::info!("Automation rule {} skipped: {}", rule.id, reason);
+                AutomationExecution::skip(pool, execution.id, &reason).await
+            }
+            Err(e) => {
+                tracing::warn!("Automation rule {} failed for task {}: {}", rule.id, task.id, e);
+                AutomationExecution::fail(pool, execution.id, &e).await
+            }
+        };
+        if let Err(e) = recorded {
+            tracing::error!("Failed to record automation result {}: {}", execution.id, e);
+        }
+    }
+
+    // Actions may have moved the task or changed its labels
+    if let Ok(Some(task)) = Task::find_by_id(pool, task_id).await {
+        let active_workspace = Workspace::find_active_for_task(pool, task.id).await.ok().flatten();
+        let task_status = TaskWithAttemptStatus {
+            task,
+            has_in_progress_attempt: active_workspace.is_some(),
+            last_attempt_failed: false,
+            executor: String::new(),
+            latest_attempt_id: active_workspace.map(|w| w.id),
+        };
+        container
+            .events_msg_store()
+            .push_patch(task_patch::replace(&task_status));
+    }
+}
+
+/// Start the rule's agent on the task in its current column
+async fn assign_agent<C>(
+    container: &C,
+    rule: &AutomationRule,
+    task: &Task,
+) -> Result<ActionOutcome, String>
+where
+    C: ContainerService + Sync + ?Sized,
+{
+    let pool = &container.db().pool;
+    let config: AssignAgentConfig = rule
+        .get_action_config()
+        .map_err(|e| format!("Invalid action config: {e}"))?;
+    let agent = Agent::find_by_id(pool, config.agent_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Agent {} not found", config.agent_id))?;
+    let column_id = task
+        .column_id
+        .ok_or_else(|| "Task is not in a column".to_string())?;
+    let column = KanbanColumn::find_by_id(pool, column_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Column {} not found", column_id))?;
+
+    if Task::has_active_attempt(pool, task.id).await.unwrap_or(false) {
+        return Ok(ActionOutcome::Skipped(
+            "An execution is already running".to_string(),
+        ));
+    }
+
+    container
+        .initiate_column_handoff(task, &agent, &column, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(ActionOutcome::Completed(json!({
+        "agent_id": agent.id,
+        "agent_name": agent.name,
+    })))
+}
+
+/// Open a pull request on each repo of the task's workspace that doesn't have one yet,
+/// on whatever code host the repo uses
+async fn create_prs<C>(
+    container: &C,
+    rule: &AutomationRule,
+    task: &Task,
+    trigger: &TriggerContext,
+) -> Result<ActionOutcome, String>
+where
+    C: ContainerService + Sync + ?Sized,
+{
+    let pool = &container.db().pool;
+    let db_err = |e: sqlx::Error| e.to_string();
+    let config: CreatePrConfig = rule
+        .get_action_config()
+        .map_err(|e| format!("Invalid action config: {e}"))?;
+    let Some(workspace) = Workspace::find_active_for_task(pool, task.id)
+        .await
+        .map_err(db_err)?
+    else {
+        return Ok(ActionOutcome::Skipped("Task has no workspace".to_string()));
+    };
+    let body = render_template(&config.body_template, task, trigger);
+
+    let mut opened = Vec::new();
+    for workspace_repo in WorkspaceRepo::find_by_workspace_id(pool, workspace.id)
+        .await
+        .map_err(db_err)?
+    {
+        let merges =
+            Merge::find_by_workspace_and_repo_id(pool, workspace.id, workspace_repo.repo_id)
+                .await
+                .map_err(db_err)?;
+        if merges.iter().any(|merge| matches!(merge, Merge::Pr(_))) {
+            continue;
+        }
+        let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+            .await
+            .map_err(db_err)?
+            .ok_or_else(|| format!("Repo {} not found", workspace_repo.repo_id))?;
+        let request = CreatePrRequest {
+            title: render_template(&config.title_template, task, trigger),
+            body: Some(body.clone()).filter(|body| !body.trim().is_empty()),
+            head_branch: workspace.branch.clone(),
+            base_branch: workspace_repo.target_branch.clone(),
+            draft: config.draft,
+        };
+        let (pr_info, kind) = container
+            .open_pull_request(&workspace, &repo, request)
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.message())?;
+        opened.push(json!({
+            "repo_id": repo.id,
+            "forge": kind.as_str(),
+            "pr_number": pr_info.number,
+            "pr_url": pr_info.url,
+        }));
+    }
+    if opened.is_empty() {
+        return Ok(ActionOutcome::Skipped(
+            "Every repo of the workspace already has a pull request".to_string(),
+        ));
+    }
+    Ok(ActionOutcome::Completed(json!({ "pull_requests": opened })))
+}
+
+/// Merge the open pull requests of the task's workspace on their code hosts with each
+/// repo's merge strategy. The PR monitor picks up the merged state and finishes the task.
+async fn merge_prs<C>(container: &C, task: &Task) -> Result<ActionOutcome, String>
+where
+    C: ContainerService + Sync + ?Sized,
+{
+    let pool = &container.db().pool;
+    let db_err = |e: sqlx::Error| e.to_string();
+    let Some(workspace) = Workspace::find_active_for_task(pool, task.id)
+        .await
+        .map_err(db_err)?
+    else {
+        return Ok(ActionOutcome::Skipped("Task has no workspace".to_string()));
+    };
+
+    let mut merged = Vec::new();
+    for merge in Merge::find_by_workspace_id(pool, workspace.id)
+        .await
+        .map_err(db_err)?
+    {
+        let Merge::Pr(pr_merge) = merge else {
+            continue;
+        };
+        if !matches!(pr_merge.pr_info.status, MergeStatus::Open) {
+            continue;
+        }
+        let repo = Repo::find_by_id(pool, pr_merge.repo_id)
+            .await
+            .map_err(db_err)?
+            .ok_or_else(|| format!("Repo {} not found", pr_merge.repo_id))?;
+        let strategy = RepoMergeSettings::for_repo(pool, repo.id)
+            .await
+            .map_err(db_err)?
+            .strategy();
+        container
+            .code_host(&repo)
+            .await
+            .map_err(|e| e.to_string())?
+            .merge_pr(pr_merge.pr_info.number.into(), strategy)
+            .await
+            .map_err(|e| format!("Failed to merge PR #{}: {e}", pr_merge.pr_info.number))?;
+        merged.push(json!({
+            "repo_id": repo.id,
+            "pr_number": pr_merge.pr_info.number,
+            "pr_url": pr_merge.pr_info.url,
+            "strategy": strategy.as_str(),
+        }));
+    }
+    if merged.is_empty() {
+        return Ok(ActionOutcome::Skipped(
+            "No open pull request to merge".to_string(),
+        ));
+    }
+    Ok(ActionOutcome::Completed(json!({ "pull_requests": merged })))
+}
+
+/// Run a shell script in the task's active workspace
+async fn run_script(
+    pool: &PgPool,
+    task: &Task,
+    config: &RunScriptConfig,
+) -> Result<ActionOutcome, String> {
+    let workspace = Workspace::find_active_for_task(pool, task.id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some(dir) = workspace
+        .and_then(|w| w.container_ref)
+        .map(PathBuf::from)
+        .filter(|dir| dir.exists())
+    else {
+        return Ok(ActionOutcome::Skipped(
+            "Task has no active workspace to run the script in".to_string(),
+        ));
+    };
+
+    let timeout = config
+        .timeout_seconds
+        .filter(|s| *s > 0)
+        .map(|s| Duration::from_secs(s as u64))
+        .unwrap_or(DEFAULT_SCRIPT_TIMEOUT);
+
+    let mut command = if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.arg("/C").arg(&config.script);
+        c
+    } else {
+        let mut c = Command::new("bash");
+        c.arg("-c").arg(&config.script);
+        c
+    };
+    command
+        .current_dir(&dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let output = match tokio::time::timeout(timeout, command.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return Err(format!("Failed to start script: {e}")),
+        Err(_) => return Err(format!("Script timed out after {}s", timeout.as_secs())),
+    };
+
+    let result = json!({
+        "exit_code": output.status.code(),
+        "stdout": truncate_output(&output.stdout),
+        "stderr": truncate_output(&output.stderr),
+    });
+    if output.status.success() {
+        Ok(ActionOutcome::Completed(result))
+    } else {
+        Err(result.to_string())
+    }
+}
+
+async fn set_labels(
+    pool: &PgPool,
+    task: &Task,
+    config: &SetLabelsConfig,
+) -> Result<ActionOutcome, String> {
+    let db_err = |e: sqlx::Error| e.to_string();
+    let mut added = Vec::new();
+    for label_id in &config.label_ids {
+        match TaskLabel::find_by_id(pool, *label_id)
+            .await
+            .map_err(db_err)?
+        {
+            Some(label) if label.project_id == task.project_id => {
+                TaskLabel::assign_to_task(pool, task.id, label.id)
+                    .await
+                    .map_err(db_err)?;
+                added.push(label.id);
+            }
+            _ => tracing::warn!("Automation label {} not found in project", label_id),
+        }
+    }
+
+    let mut removed = Vec::new();
+    if config.replace.unwrap_or(false) {
+        for label in TaskLabel::find_by_task(pool, task.id)
+            .await
+            .map_err(db_err)?
+        {
+            if !config.label_ids.contains(&label.id) {
+                TaskLabel::remove_from_task(pool, task.id, label.id)
+                    .await
+                    .map_err(db_err)?;
+                removed.push(label.id);
+            }
+        }
+    }
+
+    Ok(ActionOutcome::Completed(
+        json!({ "added": added, "removed": removed }),
+    ))
+}
+
+/// Net change from applying label rule steps, in order, to a task's labels
+fn plan_label_changes(
+    current: &[Uuid],
+    steps: &[(LabelRuleAction, Uuid)],
+) -> (Vec<Uuid>, Vec<Uuid>) {
+    let mut labels = current.to_vec();
+    for (action, label_id) in steps {
+        match action {
+            LabelRuleAction::Add if !labels.contains(label_id) => labels.push(*label_id),
+            LabelRuleAction::Add => {}
+            LabelRuleAction::Remove => labels.retain(|id| id != label_id),
+        }
+    }
+    let added = labels
+        .iter()
+        .filter(|id| !current.contains(id))
+        .copied()
+        .collect();
+    let removed = current
+        .iter()
+        .filter(|id| !labels.contains(id))
+        .copied()
+        .collect();
+    (added, removed)
+}
+
+/// Apply the exit label rules of the column a task left, then the enter rules of
+/// the column it entered, recording the net change as a `labels_changed` event.
+/// Returns whether the task's labels changed.
+pub async fn apply_column_label_rules(
+    pool: &PgPool,
+    task_id: Uuid,
+    project_id: Uuid,
+    from_column_id: Option<Uuid>,
+    to_column_id: Option<Uuid>,
+) -> Result<bool, sqlx::Error> {
+    let mut rules = Vec::new();
+    if let Some(column_id) = from_column_id {
+        rules.extend(
+            ColumnLabelRule::find_for_trigger(pool, column_id, LabelRuleTrigger::Exit).await?,
+        );
+    }
+    if let Some(column_id) = to_column_id {
+        rules.extend(
+            ColumnLabelRule::find_for_trigger(pool, column_id, LabelRuleTrigger::Enter).await?,
+        );
+    }
+    if rules.is_empty() {
+        return Ok(false);
+    }
+
+    // Boards can be shared, so a rule's label may belong to another project
+    let mut steps = Vec::new();
+    for rule in &rules {
+        match TaskLabel::find_by_id(pool, rule.label_id).await? {
+            Some(label) if label.project_id == project_id => steps.push((rule.action, label.id)),
+            _ => tracing::debug!(
+                "Column label rule {} doesn't apply to project {}",
+                rule.id,
+                project_id
+            ),
+        }
+    }
+
+    let current: Vec<Uuid> = TaskLabel::find_by_task(pool, task_id)
+        .await?
+        .into_iter()
+        .map(|label| label.id)
+        .collect();
+    let (added, removed) = plan_label_changes(&current, &steps);
+    if added.is_empty() && removed.is_empty() {
+        return Ok(false);
+    }
+
+    for label_id in &added {
+        TaskLabel::assign_to_task(pool, task_id, *label_id).await?;
+    }
+    for label_id in &removed {
+        TaskLabel::remove_from_task(pool, task_id, *label_id).await?;
+    }
+    let event =
+        CreateTaskEvent::labels_changed(task_id, from_column_id, to_column_id, &added, &removed);
+    TaskEvent::create(pool, &event).await?;
+    notification_channels::notify_labels_changed(pool, None, task_id, &added, &removed).await;
+    Ok(true)
+}
+
+async fn send_request(request: reqwest::RequestBuilder) -> Result<ActionOutcome, String> {
+    let response = request
+        .timeout(HTTP_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let body: String = body.chars().take(2000).collect();
+    if status.is_success() {
+        Ok(ActionOutcome::Completed(
+            json!({ "status": status.as_u16(), "body": body }),
+        ))
+    } else {
+        Err(format!("Request returned {status}: {body}"))
+    }
+}
+
+async fn post_webhook(
+    config: &WebhookConfig,
+    task: &Task,
+    trigger: &TriggerContext,
+) -> Result<ActionOutcome, String> {
+    let method = config
+        .method
+        .as_deref()
+        .unwrap_or("POST")
+        .to_ascii_uppercase();
+    let method = reqwest::Method::from_bytes(method.as_bytes())
+        .map_err(|_| format!("Invalid HTTP method '{method}'"))?;
+
+    let mut request = reqwest::Client::new().request(method, &config.url);
+    if let Some(headers) = config.headers.as_ref().and_then(Value::as_object) {
+        for (name, value) in headers {
+            if let Some(value) = value.as_str() {
+                request = request.header(name, value);
+            }
+        }
+    }
+    request = match &config.body_template {
+        Some(template) => request
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(render_template(template, task, trigger)),
+        None => request.json(&json!({
+            "task": {
+                "id": task.id,
+                "title": task.title,
+                "status": task.status,
+                "project_id": task.project_id,
+            },
+            "trigger": trigger,
+        })),
+    };
+    send_request(request).await
+}
+
+async fn notify(
+    config: &NotifyConfig,
+    task: &Task,
+    trigger: &TriggerContext,
+) -> Result<ActionOutcome, String> {
+    let request = reqwest::Client::new()
+        .post(&config.webhook_url)
+        .json(&json!({
+            "channel": config.channel,
+            "text": render_template(&config.message_template, task, trigger),
+        }));
+    send_request(request).await
+}
+
+/// Move the task to another column. Moves made by automations don't fire the
+/// target column's rules, so rules can't loop between columns.
+async fn move_to_column(
+    pool: &PgPool,
+    rule_id: Uuid,
+    task: &Task,
+    config: &MoveToColumnConfig,
+) -> Result<ActionOutcome, String> {
+    let db_err = |e: sqlx::Error| e.to_string();
+    let column = KanbanColumn::find_by_id(pool, config.column_id)
+        .await
+        .map_err(db_err)?
+        .ok_or_else(|| format!("Column {} not found", config.column_id))?;
+    if task.column_id == Some(column.id) {
+        return Ok(ActionOutcome::Skipped(format!(
+            "Task is already in '{}'",
+            column.name
+        )));
+    }
+    if task.is_detached() {
+        return Ok(ActionOutcome::Skipped(
+            "Task was handed back to a human".to_string(),
+        ));
+    }
+    if let Some(current_column_id) = task.column_id {
+        let pending = column_checklist::pending_items(pool, task.id, current_column_id)
+            .await
+            .map_err(db_err)?;
+        if !pending.is_empty() {
+            return Ok(ActionOutcome::Skipped(format!(
+                "Checklist items still open: {}",
+                pending.join(", ")
+            )));
+        }
+        if plan_review::blocks_leaving_column(pool, task.id, current_column_id)
+            .await
+            .map_err(db_err)?
+        {
+            return Ok(ActionOutcome::Skipped(
+                "Plan is awaiting approval".to_string(),
+            ));
+        }
+    }
+
+    Task::update_column_id(pool, task.id, Some(column.id))
+        .await
+        .map_err(db_err)?;
+    Task::update_status(pool, task.id, column.status.clone())
+        .await
+        .map_err(db_err)?;
+
+    let mut event = CreateTaskEvent::column_transition(
+        task.id,
+        task.column_id,
+        column.id,
+        EventTriggerType::Automation,
+        ActorType::System,
+        None,
+    );
+    event.automation_rule_id = Some(rule_id);
+    if let Err(e) = TaskEvent::create(pool, &event).await {
+        tracing::error!("Failed to record automation column move: {}", e);
+    }
+    if let Err(e) = apply_column_label_rules(
+        pool,
+        task.id,
+        task.project_id,
+        task.column_id,
+        Some(column.id),
+    )
+    .await
+    {
+        tracing::error!(
+            "Failed to apply column label rules to task {}: {}",
+            task.id,
+            e
+        );
+    }
+
+    Ok(ActionOutcome::Completed(
+        json!({ "column_id": column.id, "column_name": column.name }),
+    ))
+}
+
+async fn create_follow_up_task(
+    pool: &PgPool,
+    task: &Task,
+    trigger: &TriggerContext,
+    config: &CreateFollowUpTaskConfig,
+) -> Result<ActionOutcome, String> {
+    let db_err = |e: sqlx::Error| e.to_string();
+    let column = match config.column_id {
+        Some(column_id) => Some(
+            KanbanColumn::find_by_id(pool, column_id)
+                .await
+                .map_err(db_err)?
+                .ok_or_else(|| format!("Column {column_id} not found"))?,
+        ),
+        None => match Project::find_by_id(pool, task.project_id)
+            .await
+            .map_err(db_err)?
+            .and_then(|p| p.board_id)
+        {
+            Some(board_id) => KanbanColumn::find_initial(pool, board_id)
+                .await
+                .map_err(db_err)?,
+            None => None,
+        },
+    };
+
+    let mut data = CreateTask::from_title_description(
+        task.project_id,
+        render_template(&config.title_template, task, trigger),
+        config
+            .description_template
+            .as_deref()
+            .map(|t| render_template(t, task, trigger)),
+    );
+    if let Some(column) = &column {
+        data.column_id = Some(column.id);
+        data.status = Some(column.status.clone());
+    }
+    let follow_up = Task::create(pool, &data, Uuid::new_v4())
+        .await
+        .map_err(db_err)?;
+
+    let event = CreateTaskEvent::task_created(follow_up.id, ActorType::System, None);
+    if let Err(e) = TaskEvent::create(pool, &event).await {
+        tracing::error!(
+            "Failed to record task created event for task {}: {}",
+            follow_up.id,
+            e
+        );
+    }
+
+    Ok(ActionOutcome::Completed(
+        json!({ "task_id": follow_up.id, "title": follow_up.title }),
+    ))
+}
+
+/// Explode the task's latest implementation plan into sub-tasks
+async fn create_subtasks_from_plan(
+    pool: &PgPool,
+    task: &Task,
+    config: &CreateSubtasksFromPlanConfig,
+) -> Result<ActionOutcome, String> {
+    let db_err = |e: sqlx::Error| e.to_string();
+    let Some(plan) = ContextArtifact::find_latest_plan_for_task(pool, task.id)
+        .await
+        .map_err(db_err)?
+    else {
+        return Ok(ActionOutcome::Skipped(
+            "Task has no implementation plan".to_string(),
+        ));
+    };
+    let column = match config.column_id {
+        Some(column_id) => Some(
+            KanbanColumn::find_by_id(pool, column_id)
+                .await
+                .map_err(db_err)?
+                .ok_or_else(|| format!("Column {column_id} not found"))?,
+        ),
+        None => plan_subtasks::backlog_column(pool, task)
+            .await
+            .map_err(db_err)?,
+    };
+
+    let subtasks =
+        plan_subtasks::create_subtasks(
+            pool,
+            task,
+            &plan,
+            column.as_ref(),
+            ActorType::System,
+            None,
+        )
+        .await
+            .map_err(db_err)?;
+    let task_ids: Vec<Uuid> = subtasks.iter().map(|t| t.id).collect();
+    Ok(ActionOutcome::Completed(
+        json!({ "plan_id": plan.id, "task_ids": task_ids }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use db::models::task::{TaskState, TaskStatus};
+
+    use super::*;
+
+    fn task() -> Task {
+        Task {
+            id: Uuid::nil(),
+            project_id: Uuid::nil(),
+            title: "Fix login".to_string(),
+            description: None,
+            status: TaskStatus::InReview,
+            column_id: None,
+            parent_workspace_id: None,
+            shared_task_id: None,
+            task_group_id: None,
+            task_state: TaskState::Queued,
+            workflow_decisions: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            archived_at: None,
+            parent_task_id: None,
+            detached_at: None,
+            assignee_id: None,
+        }
+    }
+
+    fn trigger() -> TriggerContext {
+        TriggerContext {
+            from_column_id: None,
+            from_column_name: Some("In Progress".to_string()),
+            to_column_id: Uuid::nil(),
+            to_column_name: "Review".to_string(),
+            trigger_type: "on_enter".to_string(),
+            transition_name: None,
+        }
+    }
+
+    #[test]
+    fn renders_task_and_column_placeholders() {
+        let rendered = render_template(
+            "Review {{task.title}} ({{ from_column }} -> {{to_column}})",
+            &task(),
+            &trigger(),
+        );
+        assert_eq!(rendered, "Review Fix login (In Progress -> Review)");
+    }
+
+    #[test]
+    fn leaves_unknown_placeholders() {
+        let rendered = render_template("{{task.owner}}: {{task.description}}", &task(), &trigger());
+        assert_eq!(rendered, "{{task.owner}}: ");
+    }
+
+    #[test]
+    fn label_rules_apply_in_order() {
+        let (kept, stale, review) = (Uuid::from_u128(1), Uuid::from_u128(2), Uuid::from_u128(3));
+        let (added, removed) = plan_label_changes(
+            &[kept, stale],
+            &[
+                (LabelRuleAction::Remove, stale),
+                (LabelRuleAction::Add, review),
+                (LabelRuleAction::Add, kept),
+            ],
+        );
+        assert_eq!(added, vec![review]);
+        assert_eq!(removed, vec![stale]);
+    }
+
+    #[test]
+    fn label_removed_on_exit_and_added_on_entry_is_unchanged() {
+        let label = Uuid::from_u128(1);
+        let (added, removed) = plan_label_changes(
+            &[label],
+            &[
+                (LabelRuleAction::Remove, label),
+                (LabelRuleAction::Add, label),
+            ],
+        );
+        assert!(added.is_empty());
+        assert!(removed.is_empty());
+    }
+
+    #[test]
+    fn truncates_long_output_from_the_start() {
+        let output = "x".repeat(MAX_OUTPUT_BYTES) + "tail";
+        let truncated = truncate_output(output.as_bytes());
+        assert!(truncated.starts_with("[truncated]\n"));
+        assert!(truncated.ends_with("tail"));
+    }
+}
@@ -0,0 +1,337 @@
+//! Bitbucket Cloud pull requests over the REST API (`api.bitbucket.org/2.0`).
+//!
+//! The stored token is either a repository/workspace access token (sent as a bearer
+//! token) or `username:app_password` (sent with basic auth). Checks are the build
+//! statuses reported on the pull request. Bitbucket Data Center isn't supported.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use db::models::{
+    merge::{MergeStatus, PullRequestInfo},
+    repo_forge_settings::{ForgeKind, RepoForgeSettings},
+    repo_merge_settings::MergeStrategy,
+};
+use reqwest::Method;
+use serde_json::{Value, json};
+use tracing::info;
+
+use crate::services::{
+    forge::{
+        CodeHost, CodeHostError, PrCheck, PrCheckState,
+        http::{self, Auth, RestClient},
+    },
+    github::{CreatePrRequest, UnifiedPrComment},
+};
+
+const HOST: &str = "Bitbucket";
+const WEB_URL: &str = "https://bitbucket.org";
+const API_ROOT: &str = "https://api.bitbucket.org/2.0";
+const PAGE_SIZE: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitbucketRepoInfo {
+    pub workspace: String,
+    pub repo_slug: String,
+}
+
+impl BitbucketRepoInfo {
+    /// Workspace and repo from a clone URL or a web URL (repo or pull request page)
+    pub fn from_url(url: &str) -> Result<Self, CodeHostError> {
+        let invalid =
+            || CodeHostError::Repository(format!("'{url}' is not a Bitbucket repository"));
+        let path = http::path_on_instance(url, WEB_URL).ok_or_else(invalid)?;
+        let mut segments = path.split('/').filter(|s| !s.is_empty());
+        let workspace = segments.next().ok_or_else(invalid)?;
+        let repo_slug = segments.next().ok_or_else(invalid)?;
+        let repo_slug = repo_slug.strip_suffix(".git").unwrap_or(repo_slug);
+        if repo_slug.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            workspace: workspace.to_string(),
+            repo_slug: repo_slug.to_string(),
+        })
+    }
+
+    fn api_path(&self, rest: &str) -> String {
+        format!("/repositories/{}/{}{rest}", self.workspace, self.repo_slug)
+    }
+}
+
+fn timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.as_str()?)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Items of a paginated response
+fn values(page: &Value) -> &[Value] {
+    page["values"].as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
+/// Pull request info from a pull request object. Bitbucket has no merge timestamp, so a
+/// merged PR's last update stands in for it.
+fn pr_info(value: &Value) -> Option<PullRequestInfo> {
+    let status = match value.get("state").and_then(Value::as_str) {
+        Some("OPEN") => MergeStatus::Open,
+        Some("MERGED") => MergeStatus::Merged,
+        Some("DECLINED") | Some("SUPERSEDED") => MergeStatus::Closed,
+        _ => MergeStatus::Unknown,
+    };
+    let merged_at = match status {
+        MergeStatus::Merged => timestamp(&value["updated_on"]),
+        _ => None,
+    };
+    Some(PullRequestInfo {
+        number: value.get("id")?.as_i64()? as i32,
+        url: value["links"]["html"]["href"].as_str()?.to_string(),
+        status,
+        merged_at,
+        merge_commit_sha: value["merge_commit"]["hash"].as_str().map(str::to_string),
+    })
+}
+
+/// Comments from a pull request's comment list; deleted comments are skipped and
+/// inline comments become review comments
+fn comments(page: &Value) -> Vec<UnifiedPrComment> {
+    values(page)
+        .iter()
+        .filter(|c| c["deleted"].as_bool() != Some(true))
+        .filter_map(|c| {
+            let id = c.get("id")?.as_i64()?;
+            let author = c["user"]["nickname"]
+                .as_str()
+                .or_else(|| c["user"]["display_name"].as_str())
+                .unwrap_or_default()
+                .to_string();
+            let body = c["content"]["raw"].as_str().unwrap_or_default().to_string();
+            let created_at = timestamp(&c["created_on"])?;
+            let url = c["links"]["html"]["href"].as_str().unwrap_or_default().to_string();
+            Some(match c.get("inline").filter(|inline| inline.is_object()) {
+                Some(inline) => UnifiedPrComment::Review {
+                    id,
+                    author,
+                    author_association: "NONE".to_string(),
+                    body,
+                    created_at,
+                    url,
+                    path: inline["path"].as_str().unwrap_or_default().to_string(),
+                    line: inline["to"].as_i64().or_else(|| inline["from"].as_i64()),
+                    diff_hunk: String::new(),
+                },
+                None => UnifiedPrComment::General {
+                    id: id.to_string(),
+                    author,
+                    author_association: "NONE".to_string(),
+                    body,
+                    created_at,
+                    url,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Checks from a pull request's build statuses
+fn build_checks(page: &Value) -> Vec<PrCheck> {
+    let text = |status: &Value, key: &str| {
+        status
+            .get(key)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    values(page)
+        .iter()
+        .map(|status| PrCheck {
+            name: text(status, "name")
+                .or_else(|| text(status, "key"))
+                .unwrap_or_else(|| "build".to_string()),
+            state: PrCheckState::from_forge(status["state"].as_str().unwrap_or_default()),
+            description: text(status, "description"),
+            url: text(status, "url"),
+        })
+        .collect()
+}
+
+/// One repository on Bitbucket Cloud
+#[derive(Clone)]
+pub struct BitbucketHost {
+    client: RestClient,
+    repo: BitbucketRepoInfo,
+}
+
+impl BitbucketHost {
+    /// Client for the repo named by `url`, decrypting the repo's token
+    pub fn new(settings: &RepoForgeSettings, url: &str) -> Result<Self, CodeHostError> {
+        let repo = BitbucketRepoInfo::from_url(url)?;
+        let auth = match http::api_token(settings)? {
+            Some(token) => match token.split_once(':') {
+                Some((user, password)) => Auth::Basic(user.to_string(), password.to_string()),
+                None => Auth::Bearer(token),
+            },
+            None => Auth::None,
+        };
+        Ok(Self {
+            client: RestClient::new(HOST, API_ROOT.to_string(), auth)?,
+            repo,
+        })
+    }
+
+    fn parse_pr(&self, value: &Value) -> Result<PullRequestInfo, CodeHostError> {
+        pr_info(value).ok_or_else(|| self.client.unexpected("pull request without id or link"))
+    }
+}
+
+#[async_trait]
+impl CodeHost for BitbucketHost {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::Bitbucket
+    }
+
+    async fn create_pr(&self, request: &CreatePrRequest) -> Result<PullRequestInfo, CodeHostError> {
+        let body = json!({
+            "title": request.title,
+            "description": request.body.as_deref().unwrap_or(""),
+            "source": { "branch": { "name": request.head_branch } },
+            "destination": { "branch": { "name": request.base_branch } },
+            "draft": request.draft.unwrap_or(false),
+        });
+        let value = self
+            .client
+            .submit(Method::POST, &self.repo.api_path("/pullrequests"), &body)
+            .await?;
+        let pr = self.parse_pr(&value)?;
+        info!(
+            "Created Bitbucket PR #{} for branch {} in {}/{}",
+            pr.number, request.head_branch, self.repo.workspace, self.repo.repo_slug
+        );
+        Ok(pr)
+    }
+
+    async fn get_pr(&self, number: i64) -> Result<PullRequestInfo, CodeHostError> {
+        let value = self
+            .client
+            .get(&self.repo.api_path(&format!("/pullrequests/{number}")), &[])
+            .await?;
+        self.parse_pr(&value)
+    }
+
+    async fn list_prs_for_branch(
+        &self,
+        branch: &str,
+    ) -> Result<Vec<PullRequestInfo>, CodeHostError> {
+        let escaped = branch.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut query = vec![
+            ("q", format!("source.branch.name = \"{escaped}\"")),
+            ("sort", "-updated_on".to_string()),
+            ("pagelen", PAGE_SIZE.to_string()),
+        ];
+        // Without explicit states only open pull requests are listed
+        for state in ["OPEN", "MERGED", "DECLINED", "SUPERSEDED"] {
+            query.push(("state", state.to_string()));
+        }
+        let page = self
+            .client
+            .get(&self.repo.api_path("/pullrequests"), &query)
+            .await?;
+        Ok(values(&page).iter().filter_map(pr_info).collect())
+    }
+
+    async fn get_pr_comments(&self, number: i64) -> Result<Vec<UnifiedPrComment>, CodeHostError> {
+        let query = [
+            ("sort", "created_on".to_string()),
+            ("pagelen", PAGE_SIZE.to_string()),
+        ];
+        let page = self
+            .client
+            .get(&self.repo.api_path(&format!("/pullrequests/{number}/comments")), &query)
+            .await?;
+        Ok(comments(&page))
+    }
+
+    async fn get_pr_checks(&self, number: i64) -> Result<Vec<PrCheck>, CodeHostError> {
+        let page = self
+            .client
+            .get(
+                &self.repo.api_path(&format!("/pullrequests/{number}/statuses")),
+                &[("pagelen", PAGE_SIZE.to_string())],
+            )
+            .await?;
+        Ok(build_checks(&page))
+    }
+
+    async fn merge_pr(&self, number: i64, strategy: MergeStrategy) -> Result<(), CodeHostError> {
+        let body = json!({ "merge_strategy": strategy.as_str() });
+        let path = self.repo.api_path(&format!("/pullrequests/{number}/merge"));
+        self.client.submit(Method::POST, &path, &body).await?;
+        info!(
+            "Merged Bitbucket PR #{number} in {}/{} ({})",
+            self.repo.workspace,
+            self.repo.repo_slug,
+            strategy.as_str()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_info_parses_clone_and_web_urls() {
+        let expected = BitbucketRepoInfo {
+            workspace: "acme".to_string(),
+            repo_slug: "api".to_string(),
+        };
+        for url in [
+            "https://ada@bitbucket.org/acme/api.git",
+            "git@bitbucket.org:acme/api.git",
+            "https://bitbucket.org/acme/api/pull-requests/9",
+        ] {
+            assert_eq!(BitbucketRepoInfo::from_url(url).unwrap(), expected);
+        }
+        assert!(BitbucketRepoInfo::from_url("https://bitbucket.org/acme").is_err());
+        assert!(BitbucketRepoInfo::from_url("https://gitlab.com/acme/api").is_err());
+    }
+
+    #[test]
+    fn pull_requests_comments_and_statuses_map_onto_shared_types() {
+        let merged = pr_info(&json!({
+            "id": 9, "state": "MERGED", "updated_on": "2026-10-16T09:30:00.123456+00:00",
+            "links": {"html": {"href": "https://bitbucket.org/acme/api/pull-requests/9"}},
+            "merge_commit": {"hash": "abc123"}
+        }))
+        .unwrap();
+        assert!(matches!(merged.status, MergeStatus::Merged));
+        assert!(merged.merged_at.is_some());
+        assert_eq!(merged.merge_commit_sha.as_deref(), Some("abc123"));
+        let declined = pr_info(&json!({
+            "id": 10, "state": "DECLINED", "links": {"html": {"href": "u"}}
+        }))
+        .unwrap();
+        assert!(matches!(declined.status, MergeStatus::Closed));
+
+        let comments = comments(&json!({"values": [
+            {"id": 1, "content": {"raw": "gone"}, "deleted": true,
+             "created_on": "2026-10-16T09:00:00+00:00"},
+            {"id": 2, "content": {"raw": "nit"}, "user": {"nickname": "ada"},
+             "created_on": "2026-10-16T09:01:00+00:00",
+             "inline": {"path": "src/main.rs", "from": null, "to": 12}}
+        ]}));
+        assert_eq!(comments.len(), 1);
+        assert!(matches!(
+            &comments[0],
+            UnifiedPrComment::Review { path, line: Some(12), .. } if path == "src/main.rs"
+        ));
+
+        let checks = build_checks(&json!({"values": [
+            {"key": "ci", "name": "Pipeline #4", "state": "INPROGRESS"},
+            {"key": "lint", "state": "FAILED", "url": "https://ci/lint"}
+        ]}));
+        assert_eq!(checks[0].state, PrCheckState::Pending);
+        assert_eq!(checks[1].name, "lint");
+        assert_eq!(checks[1].state, PrCheckState::Failure);
+    }
+}
@@ -0,0 +1,138 @@
+//! Daily board snapshots for burn-up charts.
+//!
+//! Every hour the column counts of all boards are written to `board_snapshots`, one row
+//! per board, column and day. The last capture of a day wins, so a day's counts are the
+//! ones at its end (or now, for today).
+
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::NaiveDate;
+use db::{DBService, models::board_snapshot::BoardColumnSnapshot};
+use serde::Serialize;
+use tokio::time::interval;
+use tracing::{debug, error, info};
+use ts_rs::TS;
+
+/// Scope and completed work of a board on one day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+pub struct BurnUpPoint {
+    #[ts(type = "string")]
+    pub date: NaiveDate,
+    /// Tasks on the board, in any column
+    pub total: i64,
+    /// Tasks in terminal columns
+    pub done: i64,
+}
+
+/// A board's snapshots with the burn-up series derived from them
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct BoardSnapshotHistory {
+    pub snapshots: Vec<BoardColumnSnapshot>,
+    pub burn_up: Vec<BurnUpPoint>,
+}
+
+impl BoardSnapshotHistory {
+    pub fn new(snapshots: Vec<BoardColumnSnapshot>) -> Self {
+        let burn_up = burn_up(&snapshots);
+        Self { snapshots, burn_up }
+    }
+}
+
+/// Collapse per-column snapshots into one burn-up point per day, oldest first
+pub fn burn_up(snapshots: &[BoardColumnSnapshot]) -> Vec<BurnUpPoint> {
+    let mut days: BTreeMap<NaiveDate, BurnUpPoint> = BTreeMap::new();
+    for snapshot in snapshots {
+        let point = days
+            .entry(snapshot.captured_on)
+            .or_insert_with(|| BurnUpPoint {
+                date: snapshot.captured_on,
+                total: 0,
+                done: 0,
+            });
+        point.total += snapshot.task_count;
+        if snapshot.is_terminal {
+            point.done += snapshot.task_count;
+        }
+    }
+    days.into_values().collect()
+}
+
+/// Captures column counts of every board on an interval
+pub struct BoardSnapshotService {
+    db: DBService,
+    poll_interval: Duration,
+}
+
+impl BoardSnapshotService {
+    pub async fn spawn(db: DBService) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            poll_interval: Duration::from_secs(3600),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting board snapshot service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            match BoardColumnSnapshot::capture_all(&self.db.pool).await {
+                Ok(rows) => debug!("Captured {} board column snapshot(s)", rows),
+                Err(e) => error!("Failed to capture board snapshots: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn snapshot(day: u32, count: i64, is_terminal: bool) -> BoardColumnSnapshot {
+        BoardColumnSnapshot {
+            id: Uuid::new_v4(),
+            board_id: Uuid::nil(),
+            captured_on: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            column_id: Uuid::new_v4(),
+            column_name: if is_terminal { "Done" } else { "Todo" }.to_string(),
+            status: if is_terminal { "done" } else { "todo" }.to_string(),
+            is_terminal,
+            task_count: count,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn burn_up_sums_columns_per_day() {
+        let snapshots = [
+            snapshot(1, 5, false),
+            snapshot(1, 1, true),
+            snapshot(2, 4, false),
+            snapshot(2, 3, true),
+        ];
+        let points = burn_up(&snapshots);
+        assert_eq!(points.len(), 2);
+        assert_eq!((points[0].total, points[0].done), (6, 1));
+        assert_eq!((points[1].total, points[1].done), (7, 3));
+    }
+
+    #[test]
+    fn burn_up_orders_days() {
+        let snapshots = [snapshot(3, 2, true), snapshot(1, 2, false)];
+        let dates: Vec<u32> = burn_up(&snapshots)
+            .iter()
+            .map(|p| chrono::Datelike::day(&p.date))
+            .collect();
+        assert_eq!(dates, vec![1, 3]);
+    }
+}
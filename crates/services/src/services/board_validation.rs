@@ -0,0 +1,492 @@
+//! Dry-run validation of a board's workflow.
+//!
+//! Walks the board's columns and board-level transitions looking for problems that
+//! only show up once real tasks are running: columns no task can reach, loops a
+//! task can circle forever, terminal columns that still route tasks onward,
+//! conditional transitions with nowhere to go when they don't match, and agents
+//! whose executor doesn't exist.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    str::FromStr,
+};
+
+use db::models::{agent::Agent, kanban_column::KanbanColumn, state_transition::StateTransition};
+use executors::executors::BaseCodingAgent;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BoardIssueKind {
+    /// No path of transitions leads from the initial column to this column
+    UnreachableColumn,
+    /// Tasks can loop through these columns with no escalation to break out
+    CycleWithoutEscalation,
+    /// A terminal column has transitions leaving it
+    TerminalWithTransitions,
+    /// A conditional transition has no else path and nothing else catches the task
+    MissingElsePath,
+    /// A column's agent uses an executor that doesn't exist
+    UnknownExecutor,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct BoardIssue {
+    pub kind: BoardIssueKind,
+    pub column_id: Option<Uuid>,
+    pub transition_id: Option<Uuid>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct BoardValidationReport {
+    pub board_id: Uuid,
+    /// True when no issues were found
+    pub valid: bool,
+    pub columns_checked: usize,
+    pub transitions_checked: usize,
+    pub issues: Vec<BoardIssue>,
+}
+
+/// Whether `executor` names a known coding agent executor
+fn is_known_executor(executor: &str) -> bool {
+    BaseCodingAgent::from_str(executor).is_ok()
+}
+
+/// Columns a transition can send a task to
+fn targets(transition: &StateTransition) -> impl Iterator<Item = Uuid> {
+    [
+        Some(transition.to_column_id),
+        transition.else_column_id,
+        transition.escalation_column_id,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Whether a transition routes a task away once it has failed often enough
+fn escalates(transition: &StateTransition) -> bool {
+    transition.max_failures.is_some() && transition.escalation_column_id.is_some()
+}
+
+/// Whether a transition is taken whatever the agent decides
+fn catches_all(transition: &StateTransition) -> bool {
+    transition.condition_value.is_none() && !transition.requires_confirmation
+}
+
+/// Columns reachable from `start` by following `edges`, excluding `start` itself
+/// unless a path leads back to it
+fn reachable_from(start: Uuid, edges: &HashMap<Uuid, Vec<Uuid>>) -> HashSet<Uuid> {
+    let mut seen = HashSet::new();
+    let mut queue: VecDeque<Uuid> = edges.get(&start).cloned().unwrap_or_default().into();
+    while let Some(column_id) = queue.pop_front() {
+        if seen.insert(column_id) {
+            queue.extend(edges.get(&column_id).into_iter().flatten());
+        }
+    }
+    seen
+}
+
+/// Check a board's columns (in position order) and board-level transitions.
+/// `agent_executors` maps each column agent to its executor.
+pub fn validate(
+    columns: &[KanbanColumn],
+    transitions: &[StateTransition],
+    agent_executors: &HashMap<Uuid, String>,
+) -> Vec<BoardIssue> {
+    let mut issues = Vec::new();
+    let name_of = |id: Uuid| {
+        columns
+            .iter()
+            .find(|c| c.id == id)
+            .map_or("unknown column", |c| c.name.as_str())
+    };
+
+    let mut outgoing: HashMap<Uuid, Vec<&StateTransition>> = HashMap::new();
+    for transition in transitions {
+        outgoing
+            .entry(transition.from_column_id)
+            .or_default()
+            .push(transition);
+    }
+
+    // Transition edges, plus the next-by-position fallback used by columns
+    // without transitions
+    let mut transition_edges: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for transition in transitions {
+        transition_edges
+            .entry(transition.from_column_id)
+            .or_default()
+            .extend(targets(transition));
+    }
+    let mut routing_edges = transition_edges.clone();
+    for (index, column) in columns.iter().enumerate() {
+        if !column.is_terminal
+            && !outgoing.contains_key(&column.id)
+            && let Some(next) = columns.get(index + 1)
+        {
+            routing_edges.entry(column.id).or_default().push(next.id);
+        }
+    }
+
+    // Unreachable columns
+    let start = columns
+        .iter()
+        .find(|c| c.is_initial)
+        .or_else(|| columns.first());
+    if let Some(start) = start {
+        let reachable = reachable_from(start.id, &routing_edges);
+        for column in columns {
+            if column.id != start.id && !reachable.contains(&column.id) {
+                issues.push(BoardIssue {
+                    kind: BoardIssueKind::UnreachableColumn,
+                    column_id: Some(column.id),
+                    transition_id: None,
+                    message: format!(
+                        "Column '{}' can't be reached from the initial column '{}'",
+                        column.name, start.name
+                    ),
+                });
+            }
+        }
+    }
+
+    // Cycles: columns that reach each other form a loop; it needs at least one
+    // transition inside it that escalates after repeated failures
+    let reach: HashMap<Uuid, HashSet<Uuid>> = columns
+        .iter()
+        .map(|c| (c.id, reachable_from(c.id, &transition_edges)))
+        .collect();
+    let mut reported: HashSet<Uuid> = HashSet::new();
+    for column in columns {
+        if reported.contains(&column.id) || !reach[&column.id].contains(&column.id) {
+            continue;
+        }
+        let cycle: Vec<&KanbanColumn> = columns
+            .iter()
+            .filter(|c| reach[&column.id].contains(&c.id) && reach[&c.id].contains(&column.id))
+            .collect();
+        reported.extend(cycle.iter().map(|c| c.id));
+        let in_cycle = |id: Uuid| cycle.iter().any(|c| c.id == id);
+        let has_escalation = transitions.iter().any(|t| {
+            escalates(t)
+                && in_cycle(t.from_column_id)
+                && t.escalation_column_id.is_some_and(|id| !in_cycle(id))
+        });
+        if !has_escalation {
+            let names: Vec<&str> = cycle.iter().map(|c| c.name.as_str()).collect();
+            issues.push(BoardIssue {
+                kind: BoardIssueKind::CycleWithoutEscalation,
+                column_id: Some(column.id),
+                transition_id: None,
+                message: format!(
+                    "Tasks can loop through {} forever; set max failures and an escalation column on one of its transitions",
+                    names.join(" → ")
+                ),
+            });
+        }
+    }
+
+    for column in columns {
+        let leaving = outgoing.get(&column.id).map(Vec::as_slice).unwrap_or(&[]);
+
+        if column.is_terminal {
+            for transition in leaving {
+                issues.push(BoardIssue {
+                    kind: BoardIssueKind::TerminalWithTransitions,
+                    column_id: Some(column.id),
+                    transition_id: Some(transition.id),
+                    message: format!(
+                        "Terminal column '{}' has a transition to '{}'",
+                        column.name,
+                        name_of(transition.to_column_id)
+                    ),
+                });
+            }
+        }
+
+        // A conditional transition that doesn't match falls through to the next
+        // one; tasks only get stuck when nothing in the column catches them
+        let caught = leaving
+            .iter()
+            .any(|t| catches_all(t) || t.else_column_id.is_some());
+        if !caught {
+            for transition in leaving.iter().filter(|t| t.condition_value.is_some()) {
+                issues.push(BoardIssue {
+                    kind: BoardIssueKind::MissingElsePath,
+                    column_id: Some(column.id),
+                    transition_id: Some(transition.id),
+                    message: format!(
+                        "Transition from '{}' on '{}' has no else path; tasks that don't match stay in '{}'",
+                        column.name,
+                        transition.condition_value.as_deref().unwrap_or_default(),
+                        column.name
+                    ),
+                });
+            }
+        }
+
+        if let Some(executor) = column.agent_id.and_then(|id| agent_executors.get(&id))
+            && !is_known_executor(executor)
+        {
+            issues.push(BoardIssue {
+                kind: BoardIssueKind::UnknownExecutor,
+                column_id: Some(column.id),
+                transition_id: None,
+                message: format!(
+                    "The agent of column '{}' uses unknown executor '{}'",
+                    column.name, executor
+                ),
+            });
+        }
+    }
+
+    issues
+}
+
+/// Check a requested column order: it must list every column of the board exactly once
+/// and, when `strict` is set, keep the initial column first and terminal columns last
+pub fn validate_column_order(
+    columns: &[KanbanColumn],
+    column_ids: &[Uuid],
+    strict: bool,
+) -> Result<(), String> {
+    let by_id: HashMap<Uuid, &KanbanColumn> = columns.iter().map(|c| (c.id, c)).collect();
+    let mut ordered = Vec::with_capacity(column_ids.len());
+    let mut seen = HashSet::new();
+    for id in column_ids {
+        let column = by_id
+            .get(id)
+            .ok_or_else(|| format!("Column {id} is not on this board"))?;
+        if !seen.insert(*id) {
+            return Err(format!("Column '{}' is listed more than once", column.name));
+        }
+        ordered.push(*column);
+    }
+    if ordered.len() != columns.len() {
+        return Err(format!(
+            "The new order must list all {} columns of the board",
+            columns.len()
+        ));
+    }
+    if !strict {
+        return Ok(());
+    }
+
+    if let Some(initial) = columns.iter().find(|c| c.is_initial)
+        && ordered.first().map(|c| c.id) != Some(initial.id)
+    {
+        return Err(format!("The initial column '{}' must stay first", initial.name));
+    }
+    if let Some(first_terminal) = ordered.iter().position(|c| c.is_terminal)
+        && let Some(column) = ordered[first_terminal..].iter().find(|c| !c.is_terminal)
+    {
+        return Err(format!(
+            "Column '{}' can't be placed after terminal column '{}'",
+            column.name, ordered[first_terminal].name
+        ));
+    }
+    Ok(())
+}
+
+/// Validate a saved board without running any tasks
+pub async fn validate_board(
+    pool: &PgPool,
+    board_id: Uuid,
+) -> Result<BoardValidationReport, sqlx::Error> {
+    let columns = KanbanColumn::find_by_board(pool, board_id).await?;
+    let transitions = StateTransition::find_by_board(pool, board_id).await?;
+
+    let mut agent_executors = HashMap::new();
+    for agent_id in columns.iter().filter_map(|c| c.agent_id) {
+        if agent_executors.contains_key(&agent_id) {
+            continue;
+        }
+        if let Some(agent) = Agent::find_by_id(pool, agent_id).await? {
+            agent_executors.insert(agent_id, agent.executor);
+        }
+    }
+
+    let issues = validate(&columns, &transitions, &agent_executors);
+    Ok(BoardValidationReport {
+        board_id,
+        valid: issues.is_empty(),
+        columns_checked: columns.len(),
+        transitions_checked: transitions.len(),
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use db::models::task::TaskStatus;
+
+    use super::*;
+
+    fn column(name: &str, position: i32) -> KanbanColumn {
+        KanbanColumn {
+            id: Uuid::new_v4(),
+            board_id: Uuid::nil(),
+            name: name.to_string(),
+            slug: name.to_lowercase(),
+            position,
+            color: None,
+            is_initial: position == 0,
+            is_terminal: false,
+            starts_workflow: false,
+            status: TaskStatus::Todo,
+            agent_id: None,
+            deliverable: None,
+            question: None,
+            answer_options: None,
+            read_only: false,
+            plan_only: false,
+            dry_run: false,
+            security_scanners: Vec::new(),
+            checklist: Vec::new(),
+            on_enter_script: None,
+            on_exit_script: None,
+            warm_up: false,
+            response_target_mins: None,
+            prompt_sections: Vec::new(),
+            executor_variant: None,
+            is_template: false,
+            template_group_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn transition(from: &KanbanColumn, to: &KanbanColumn) -> StateTransition {
+        StateTransition {
+            id: Uuid::new_v4(),
+            board_id: Some(Uuid::nil()),
+            project_id: None,
+            task_id: None,
+            from_column_id: from.id,
+            to_column_id: to.id,
+            else_column_id: None,
+            escalation_column_id: None,
+            name: None,
+            requires_confirmation: false,
+            condition_value: None,
+            max_failures: None,
+            is_template: false,
+            template_group_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn kinds(issues: &[BoardIssue]) -> Vec<BoardIssueKind> {
+        issues.iter().map(|issue| issue.kind).collect()
+    }
+
+    #[test]
+    fn unreachable_and_terminal_columns() {
+        let todo = column("Todo", 0);
+        let build = column("Build", 1);
+        let mut done = column("Done", 2);
+        done.is_terminal = true;
+        let archive = column("Archive", 3);
+        let columns = [todo.clone(), build.clone(), done.clone(), archive];
+
+        // Todo and Build fall through by position; Done is terminal, so Archive is
+        // only reachable through Done's (invalid) transition
+        let issues = validate(&columns, &[], &HashMap::new());
+        assert_eq!(kinds(&issues), vec![BoardIssueKind::UnreachableColumn]);
+
+        let issues = validate(&columns, &[transition(&done, &todo)], &HashMap::new());
+        assert!(kinds(&issues).contains(&BoardIssueKind::TerminalWithTransitions));
+    }
+
+    #[test]
+    fn cycles_need_an_escalation_out() {
+        let build = column("Build", 0);
+        let review = column("Review", 1);
+        let mut human = column("Human", 2);
+        human.is_terminal = true;
+        let columns = [build.clone(), review.clone(), human.clone()];
+
+        let mut to_human = transition(&review, &human);
+        to_human.condition_value = Some("approve".to_string());
+        to_human.else_column_id = Some(build.id);
+        let transitions = vec![transition(&build, &review), to_human.clone()];
+        assert_eq!(
+            kinds(&validate(&columns, &transitions, &HashMap::new())),
+            vec![BoardIssueKind::CycleWithoutEscalation]
+        );
+
+        to_human.max_failures = Some(3);
+        to_human.escalation_column_id = Some(human.id);
+        let transitions = vec![transition(&build, &review), to_human];
+        assert!(validate(&columns, &transitions, &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn missing_else_paths_and_unknown_executors() {
+        let mut review = column("Review", 0);
+        let mut done = column("Done", 1);
+        done.is_terminal = true;
+        let agent_id = Uuid::new_v4();
+        review.agent_id = Some(agent_id);
+        let columns = [review.clone(), done.clone()];
+
+        let mut approve = transition(&review, &done);
+        approve.condition_value = Some("approve".to_string());
+        let executors = HashMap::from([(agent_id, "CLAUDE_CODE".to_string())]);
+        assert_eq!(
+            kinds(&validate(&columns, &[approve.clone()], &executors)),
+            vec![BoardIssueKind::MissingElsePath]
+        );
+
+        // A catch-all transition after it keeps tasks moving
+        let transitions = [approve, transition(&review, &done)];
+        let executors = HashMap::from([(agent_id, "TYPEWRITER".to_string())]);
+        assert_eq!(
+            kinds(&validate(&columns, &transitions, &executors)),
+            vec![BoardIssueKind::UnknownExecutor]
+        );
+    }
+
+    #[test]
+    fn column_order_must_list_each_column_once() {
+        let todo = column("Todo", 0);
+        let done = column("Done", 1);
+        let columns = [todo.clone(), done.clone()];
+
+        assert!(validate_column_order(&columns, &[done.id, todo.id], false).is_ok());
+        assert!(validate_column_order(&columns, &[todo.id], false).is_err());
+        assert!(validate_column_order(&columns, &[todo.id, todo.id], false).is_err());
+        assert!(validate_column_order(&columns, &[todo.id, Uuid::new_v4()], false).is_err());
+    }
+
+    #[test]
+    fn strict_column_order_pins_initial_and_terminal_columns() {
+        let todo = column("Todo", 0);
+        let build = column("Build", 1);
+        let review = column("Review", 2);
+        let mut done = column("Done", 3);
+        done.is_terminal = true;
+        let columns = [todo.clone(), build.clone(), review.clone(), done.clone()];
+
+        let swapped = [todo.id, review.id, build.id, done.id];
+        assert!(validate_column_order(&columns, &swapped, true).is_ok());
+
+        let initial_moved = [build.id, todo.id, review.id, done.id];
+        assert!(validate_column_order(&columns, &initial_moved, false).is_ok());
+        assert_eq!(
+            validate_column_order(&columns, &initial_moved, true),
+            Err("The initial column 'Todo' must stay first".to_string())
+        );
+
+        let terminal_moved = [todo.id, done.id, build.id, review.id];
+        assert_eq!(
+            validate_column_order(&columns, &terminal_moved, true),
+            Err("Column 'Build' can't be placed after terminal column 'Done'".to_string())
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Branch naming templates for task workspaces.
+//!
+//! A project can set a template such as `{agent}/{task_id}/{slug}` or
+//! `{user}/{date}-{slug}` for the git branches of its new workspaces. Placeholders that
+//! come out empty (no assignee, no prefix) drop out together with their `/` segment, so
+//! the default template `{prefix}/{short_id}-{slug}` still gives `a1b2-fix-login` when
+//! no prefix is configured. Names already taken get a numeric suffix.
+
+use chrono::NaiveDate;
+use utils::text::{git_branch_id, short_uuid};
+use uuid::Uuid;
+
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "{prefix}/{short_id}-{slug}";
+
+/// `{prefix}`: global branch prefix, `{short_id}`: first 4 characters of the workspace
+/// id, `{workspace_id}`, `{task_id}`: first 8 characters of the task id, `{slug}`: task
+/// title, `{agent}`: agent or executor, `{user}`: task assignee, `{date}`: `YYYYMMDD`
+pub const PLACEHOLDERS: [&str; 8] = [
+    "prefix",
+    "short_id",
+    "workspace_id",
+    "task_id",
+    "slug",
+    "agent",
+    "user",
+    "date",
+];
+
+/// Placeholders that differ between a task's workspaces or between tasks; a template
+/// needs at least one so branches don't all collide
+const DISTINCT_PLACEHOLDERS: [&str; 4] = ["short_id", "workspace_id", "task_id", "slug"];
+
+/// Suffixes `-2`..`-MAX_SUFFIX` tried for a name that's taken
+const MAX_SUFFIX: usize = 20;
+
+/// Values a template is rendered with
+pub struct BranchValues<'a> {
+    pub prefix: &'a str,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: &'a str,
+    pub agent: Option<&'a str>,
+    pub user: Option<&'a str>,
+    pub date: NaiveDate,
+}
+
+impl BranchValues<'_> {
+    fn get(&self, placeholder: &str) -> String {
+        match placeholder {
+            "prefix" => self.prefix.trim_matches('/').to_string(),
+            "short_id" => short_uuid(&self.workspace_id),
+            "workspace_id" => self.workspace_id.simple().to_string(),
+            "task_id" => self.task_id.simple().to_string().chars().take(8).collect(),
+            "slug" => git_branch_id(self.task_title),
+            "agent" => self.agent.map(git_branch_id).unwrap_or_default(),
+            "user" => self.user.map(git_branch_id).unwrap_or_default(),
+            "date" => self.date.format("%Y%m%d").to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Split a template into literal text and placeholder names
+fn parse(template: &str) -> Result<Vec<(bool, &str)>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find(['{', '}']) {
+        if rest[open..].starts_with('}') {
+            return Err("Unmatched '}' in branch template".to_string());
+        }
+        let close = rest[open..]
+            .find('}')
+            .map(|i| open + i)
+            .ok_or_else(|| "Unclosed '{' in branch template".to_string())?;
+        let name = &rest[open + 1..close];
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "Unknown placeholder {{{name}}}; use {}",
+                PLACEHOLDERS.map(|p| format!("{{{p}}}")).join(", ")
+            ));
+        }
+        parts.push((false, &rest[..open]));
+        parts.push((true, name));
+        rest = &rest[close + 1..];
+    }
+    parts.push((false, rest));
+    Ok(parts)
+}
+
+/// Whether git accepts the name as a branch (`git check-ref-format --branch`)
+fn is_valid_branch_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(['/', '-', '.'])
+        && !name.ends_with(['/', '.'])
+        && !name.ends_with(".lock")
+        && !name.contains("..")
+        && !name.contains("//")
+        && !name.contains("@{")
+        && !name.contains("/.")
+        && !name
+            .chars()
+            .any(|c| c.is_control() || c.is_whitespace() || "~^:?*[\\".contains(c))
+}
+
+/// Check a template before it's saved
+pub fn validate(template: &str) -> Result<(), String> {
+    let parts = parse(template)?;
+    if !parts
+        .iter()
+        .any(|(placeholder, name)| *placeholder && DISTINCT_PLACEHOLDERS.contains(name))
+    {
+        return Err(format!(
+            "Branch template needs one of {}",
+            DISTINCT_PLACEHOLDERS.map(|p| format!("{{{p}}}")).join(", ")
+        ));
+    }
+    let literal: String = parts
+        .iter()
+        .filter(|(placeholder, _)| !placeholder)
+        .map(|(_, text)| *text)
+        .collect();
+    if literal
+        .chars()
+        .any(|c| c.is_control() || c.is_whitespace() || "~^:?*[\\".contains(c))
+        || literal.contains("..")
+        || literal.contains("@{")
+    {
+        return Err("Branch template contains characters git doesn't allow".to_string());
+    }
+    Ok(())
+}
+
+/// Branch name for a workspace. Empty `/` segments are dropped and segments are trimmed
+/// of `-` and `.`; an unusable template falls back to the default one.
+pub fn render(template: &str, values: &BranchValues) -> String {
+    let parts = match parse(template) {
+        Ok(parts) => parts,
+        Err(_) => parse(DEFAULT_BRANCH_TEMPLATE).unwrap(),
+    };
+    let raw: String = parts
+        .into_iter()
+        .map(|(placeholder, text)| {
+            if placeholder {
+                values.get(text)
+            } else {
+                text.to_string()
+            }
+        })
+        .collect();
+    let name = raw
+        .split('/')
+        .map(|segment| segment.trim_matches(['-', '.']))
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+    let name = name.trim_end_matches(".lock").to_string();
+    if is_valid_branch_name(&name) {
+        name
+    } else {
+        short_uuid(&values.workspace_id)
+    }
+}
+
+/// Names to try in order until one isn't taken: the name itself, then with `-2`, `-3`…
+pub fn candidates(name: &str) -> impl Iterator<Item = String> + '_ {
+    std::iter::once(name.to_string())
+        .chain((2..=MAX_SUFFIX).map(move |suffix| format!("{name}-{suffix}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values<'a>(prefix: &'a str, user: Option<&'a str>) -> BranchValues<'a> {
+        BranchValues {
+            prefix,
+            workspace_id: Uuid::parse_str("a1b2c3d4-0000-0000-0000-000000000000").unwrap(),
+            task_id: Uuid::parse_str("0f0e0d0c-0000-0000-0000-000000000000").unwrap(),
+            task_title: "Fix the login page!",
+            agent: Some("CLAUDE_CODE"),
+            user,
+            date: NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+        }
+    }
+
+    #[test]
+    fn default_template_matches_the_previous_branch_names() {
+        assert_eq!(
+            render(DEFAULT_BRANCH_TEMPLATE, &values("vk", None)),
+            "vk/a1b2-fix-the-login-pa"
+        );
+        assert_eq!(
+            render(DEFAULT_BRANCH_TEMPLATE, &values("", None)),
+            "a1b2-fix-the-login-pa"
+        );
+    }
+
+    #[test]
+    fn placeholders_render_and_empty_segments_drop_out() {
+        assert_eq!(
+            render("{agent}/{task_id}/{slug}", &values("", None)),
+            "claude-code/0f0e0d0c/fix-the-login-pa"
+        );
+        assert_eq!(
+            render("{user}/{date}-{slug}", &values("", Some("Ada Lovelace"))),
+            "ada-lovelace/20261016-fix-the-login-pa"
+        );
+        assert_eq!(
+            render("{user}/{date}-{slug}", &values("", None)),
+            "20261016-fix-the-login-pa"
+        );
+        let taken: Vec<String> = candidates("feat/x").take(3).collect();
+        assert_eq!(taken, ["feat/x", "feat/x-2", "feat/x-3"]);
+    }
+
+    #[test]
+    fn validation_rejects_unknown_placeholders_and_indistinct_templates() {
+        assert!(validate("{agent}/{task_id}/{slug}").is_ok());
+        assert!(validate("{agent}/{ticket}").unwrap_err().contains("Unknown placeholder"));
+        assert!(validate("{agent}/{slug").unwrap_err().contains("Unclosed"));
+        assert!(validate("{agent}/{date}").unwrap_err().contains("needs one of"));
+        assert!(validate("my branch/{slug}").is_err());
+    }
+}
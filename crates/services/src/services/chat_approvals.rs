@@ -0,0 +1,414 @@
+//! Approving from chat.
+//!
+//! Approval requests (plans, transitions waiting for confirmation and executor tool
+//! calls) posted to Matrix rooms, and to Slack channels that post with a bot token, are
+//! polled for ✅ and ❌ reactions. A reaction only counts when its author is mapped to a
+//! local user for that channel; the container then acts on that user's behalf.
+
+use std::time::Duration;
+
+use db::models::{
+    chat_approval::{ChatApprovalMessage, ChatApprovalStatus, ChatApprovalSubject},
+    execution_process::ExecutionProcess,
+    notification_channel::{NotificationChannel, NotificationChannelKind},
+    notification_channel_identity::NotificationChannelIdentity,
+    plan_review::{PlanReview, PlanReviewStatus},
+    state_transition::StateTransition,
+    task::Task,
+    user::User,
+};
+use reqwest::Url;
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::{
+    approvals::Approvals,
+    notification_channels::{SLACK_API_URL, supports_reactions},
+};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Approve,
+    Reject,
+}
+
+impl Verdict {
+    /// Slack reaction names and Matrix reaction keys that count as an answer
+    fn from_reaction(key: &str) -> Option<Self> {
+        match key.trim_end_matches('\u{fe0f}') {
+            "white_check_mark" | "heavy_check_mark" | "✅" | "✔" => Some(Verdict::Approve),
+            "x" | "negative_squared_cross_mark" | "❌" | "❎" => Some(Verdict::Reject),
+            _ => None,
+        }
+    }
+}
+
+/// A ✅ or ❌ left on an approval request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatReaction {
+    /// Slack member id or Matrix user id
+    pub external_user_id: String,
+    pub verdict: Verdict,
+}
+
+/// Reactions in a Slack `reactions.get` response
+pub fn parse_slack_reactions(response: &Value) -> Result<Vec<ChatReaction>, String> {
+    if response["ok"].as_bool() != Some(true) {
+        return Err(format!(
+            "Slack returned an error: {}",
+            response["error"].as_str().unwrap_or("unknown")
+        ));
+    }
+    let reactions = response["message"]["reactions"].as_array();
+    Ok(reactions
+        .into_iter()
+        .flatten()
+        .filter_map(|reaction| {
+            let verdict = Verdict::from_reaction(reaction["name"].as_str()?)?;
+            let users = reaction["users"].as_array()?;
+            Some(users.iter().filter_map(move |user| {
+                Some(ChatReaction {
+                    external_user_id: user.as_str()?.to_string(),
+                    verdict,
+                })
+            }))
+        })
+        .flatten()
+        .collect())
+}
+
+/// Reactions in a Matrix `/relations/{event_id}/m.annotation` response
+pub fn parse_matrix_reactions(response: &Value) -> Vec<ChatReaction> {
+    let chunk = response["chunk"].as_array();
+    chunk
+        .into_iter()
+        .flatten()
+        .filter_map(|event| {
+            let key = event["content"]["m.relates_to"]["key"].as_str()?;
+            Some(ChatReaction {
+                external_user_id: event["sender"].as_str()?.to_string(),
+                verdict: Verdict::from_reaction(key)?,
+            })
+        })
+        .collect()
+}
+
+/// The verdict of the reactions left by mapped users, and the user it's acted on for.
+/// Reactions from unmapped chat users are ignored, and a rejection from any mapped user
+/// outweighs approvals.
+pub fn decide(
+    reactions: &[ChatReaction],
+    identities: &[NotificationChannelIdentity],
+) -> Option<(Verdict, Uuid)> {
+    let mapped = |reaction: &ChatReaction| {
+        identities
+            .iter()
+            .find(|identity| identity.external_user_id == reaction.external_user_id)
+            .map(|identity| (reaction.verdict, identity.user_id))
+    };
+    let votes: Vec<_> = reactions.iter().filter_map(mapped).collect();
+    votes
+        .iter()
+        .find(|(verdict, _)| *verdict == Verdict::Reject)
+        .or_else(|| votes.first())
+        .copied()
+}
+
+fn reactions_url(
+    channel: &NotificationChannel,
+    kind: NotificationChannelKind,
+    message_ref: &str,
+) -> Result<Url, String> {
+    let parse = |url: &str| Url::parse(url).map_err(|e| format!("Invalid URL '{url}': {e}"));
+    let destination = channel.destination.as_deref().unwrap_or_default();
+    match kind {
+        NotificationChannelKind::Matrix => {
+            let mut url = parse(&channel.webhook_url)?;
+            url.path_segments_mut()
+                .map_err(|_| "URL cannot be a base".to_string())?
+                .pop_if_empty()
+                .extend([
+                    "_matrix",
+                    "client",
+                    "v1",
+                    "rooms",
+                    destination,
+                    "relations",
+                    message_ref,
+                    "m.annotation",
+                ]);
+            url.query_pairs_mut().append_pair("limit", "100");
+            Ok(url)
+        }
+        _ => {
+            let mut url = parse(&format!("{SLACK_API_URL}/reactions.get"))?;
+            url.query_pairs_mut()
+                .append_pair("channel", destination)
+                .append_pair("timestamp", message_ref)
+                .append_pair("full", "true");
+            Ok(url)
+        }
+    }
+}
+
+async fn fetch_reactions(
+    channel: &NotificationChannel,
+    kind: NotificationChannelKind,
+    message_ref: &str,
+) -> Result<Vec<ChatReaction>, String> {
+    let url = reactions_url(channel, kind, message_ref)?;
+    let response = reqwest::Client::new()
+        .get(url)
+        .timeout(HTTP_TIMEOUT)
+        .bearer_auth(channel.access_token.as_deref().unwrap_or_default())
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e.without_url()))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("Channel returned {status}"));
+    }
+    let body = response
+        .json::<Value>()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))?;
+    match kind {
+        NotificationChannelKind::Matrix => Ok(parse_matrix_reactions(&body)),
+        _ => parse_slack_reactions(&body),
+    }
+}
+
+/// An approval request answered from chat, for the container to act on
+#[derive(Debug, Clone)]
+pub struct ChatDecision {
+    pub task_id: Uuid,
+    pub subject: ChatApprovalSubject,
+    pub verdict: Verdict,
+    pub user: User,
+    pub channel_name: String,
+}
+
+/// Whether the subject still waits for an answer: a pending plan or tool call, or a
+/// transition whose run is still the task's latest while the task stays in the column
+/// the transition leaves and isn't handed back to a human
+async fn is_open(
+    pool: &PgPool,
+    approvals: &Approvals,
+    task_id: Uuid,
+    subject: ChatApprovalSubject,
+) -> Result<bool, sqlx::Error> {
+    match subject {
+        ChatApprovalSubject::PlanReview(id) => Ok(PlanReview::find_by_id(pool, id)
+            .await?
+            .is_some_and(|review| review.status == PlanReviewStatus::Pending)),
+        ChatApprovalSubject::Transition {
+            transition_id,
+            execution_process_id,
+        } => {
+            let Some(transition) = StateTransition::find_by_id(pool, transition_id).await? else {
+                return Ok(false);
+            };
+            let in_from_column = Task::find_by_id(pool, task_id).await?.is_some_and(|task| {
+                !task.is_detached() && task.column_id == Some(transition.from_column_id)
+            });
+            if !in_from_column {
+                return Ok(false);
+            }
+            // A later run asks again if it also ends up waiting on the transition
+            Ok(!ExecutionProcess::has_later_coding_agent_run(pool, execution_process_id).await?)
+        }
+        ChatApprovalSubject::ToolApproval { approval_id, .. } => {
+            Ok(approvals.is_pending(&approval_id.to_string()))
+        }
+    }
+}
+
+async fn expire(pool: &PgPool, message: &ChatApprovalMessage) {
+    if let Err(e) = ChatApprovalMessage::decide(pool, message.id, ChatApprovalStatus::Expired, None)
+        .await
+    {
+        tracing::error!("Failed to expire approval request {}: {}", message.id, e);
+    }
+}
+
+async fn check(
+    pool: &PgPool,
+    approvals: &Approvals,
+    message: &ChatApprovalMessage,
+) -> Result<Option<ChatDecision>, String> {
+    let Some(subject) = message.subject() else {
+        expire(pool, message).await;
+        return Ok(None);
+    };
+    if !is_open(pool, approvals, message.task_id, subject)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        expire(pool, message).await;
+        return Ok(None);
+    }
+    let Some(channel) = NotificationChannel::find_by_id(pool, message.channel_id)
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(None);
+    };
+    let Some(kind) = channel.get_kind().filter(|kind| supports_reactions(&channel, *kind)) else {
+        // The channel was changed to one whose reactions can't be read
+        expire(pool, message).await;
+        return Ok(None);
+    };
+
+    let reactions = fetch_reactions(&channel, kind, &message.message_ref).await?;
+    if reactions.is_empty() {
+        return Ok(None);
+    }
+    let identities = NotificationChannelIdentity::find_by_channel(pool, channel.id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some((verdict, user_id)) = decide(&reactions, &identities) else {
+        tracing::debug!(
+            "Approval request {} has reactions, but none from a mapped user",
+            message.id
+        );
+        return Ok(None);
+    };
+    let Some(user) = User::find_by_id(pool, user_id)
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(None);
+    };
+
+    let status = match verdict {
+        Verdict::Approve => ChatApprovalStatus::Approved,
+        Verdict::Reject => ChatApprovalStatus::Rejected,
+    };
+    if ChatApprovalMessage::decide(pool, message.id, status, Some(user.id))
+        .await
+        .map_err(|e| e.to_string())?
+        .is_none()
+    {
+        return Ok(None);
+    }
+    // The same request posted to other channels no longer needs an answer
+    ChatApprovalMessage::expire_subject(pool, message.task_id, subject)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(Some(ChatDecision {
+        task_id: message.task_id,
+        subject,
+        verdict,
+        user,
+        channel_name: channel.name,
+    }))
+}
+
+/// Read the reactions to every pending approval request and settle those answered by a
+/// mapped user. Requests settled some other way (in the UI, by a later run, by a tool
+/// call timing out) expire.
+pub async fn collect_decisions(pool: &PgPool, approvals: &Approvals) -> Vec<ChatDecision> {
+    let pending = match ChatApprovalMessage::find_pending(pool).await {
+        Ok(pending) => pending,
+        Err(e) => {
+            tracing::error!("Failed to load pending approval requests: {}", e);
+            return Vec::new();
+        }
+    };
+    let mut decisions = Vec::new();
+    for message in &pending {
+        match check(pool, approvals, message).await {
+            Ok(Some(decision)) => decisions.push(decision),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Failed to check reactions to approval request {}: {}",
+                message.id,
+                e
+            ),
+        }
+    }
+    decisions
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use super::*;
+
+    fn identity(external_user_id: &str, user_id: Uuid) -> NotificationChannelIdentity {
+        NotificationChannelIdentity {
+            id: Uuid::new_v4(),
+            channel_id: Uuid::nil(),
+            external_user_id: external_user_id.to_string(),
+            user_id,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn reads_slack_and_matrix_reactions() {
+        let slack = json!({
+            "ok": true,
+            "message": { "reactions": [
+                { "name": "eyes", "users": ["U1"] },
+                { "name": "white_check_mark", "users": ["U1", "U2"] },
+            ]},
+        });
+        assert_eq!(
+            parse_slack_reactions(&slack).unwrap(),
+            vec![
+                ChatReaction { external_user_id: "U1".to_string(), verdict: Verdict::Approve },
+                ChatReaction { external_user_id: "U2".to_string(), verdict: Verdict::Approve },
+            ]
+        );
+        assert!(parse_slack_reactions(&json!({ "ok": false, "error": "not_in_channel" })).is_err());
+
+        let matrix = json!({ "chunk": [
+            { "sender": "@bob:example.org",
+              "content": { "m.relates_to": { "rel_type": "m.annotation", "key": "❌" } } },
+            { "sender": "@amy:example.org",
+              "content": { "m.relates_to": { "rel_type": "m.annotation", "key": "👍" } } },
+        ]});
+        assert_eq!(
+            parse_matrix_reactions(&matrix),
+            vec![ChatReaction {
+                external_user_id: "@bob:example.org".to_string(),
+                verdict: Verdict::Reject,
+            }]
+        );
+    }
+
+    #[test]
+    fn only_mapped_users_decide_and_rejections_win() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let identities = [identity("U_ALICE", alice), identity("U_BOB", bob)];
+        let reaction = |user: &str, verdict| ChatReaction {
+            external_user_id: user.to_string(),
+            verdict,
+        };
+
+        // Strangers can't approve
+        assert_eq!(decide(&[reaction("U_EVE", Verdict::Approve)], &identities), None);
+        assert_eq!(
+            decide(
+                &[reaction("U_EVE", Verdict::Reject), reaction("U_ALICE", Verdict::Approve)],
+                &identities
+            ),
+            Some((Verdict::Approve, alice))
+        );
+        assert_eq!(
+            decide(
+                &[reaction("U_ALICE", Verdict::Approve), reaction("U_BOB", Verdict::Reject)],
+                &identities
+            ),
+            Some((Verdict::Reject, bob))
+        );
+    }
+}
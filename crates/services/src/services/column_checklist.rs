@@ -0,0 +1,158 @@
+//! Human checklists on columns.
+//!
+//! A column's settings can list items (e.g. "verify staging deploy") that a person
+//! must tick before a task leaves the column. Ticks are task events, so they carry
+//! who ticked the item and when; only ticks made since the task last entered the
+//! column count.
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    kanban_column::KanbanColumn,
+    task_event::{ChecklistTick, TaskEvent},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct ChecklistItemStatus {
+    pub item: String,
+    pub checked: bool,
+    /// Who ticked the item, as given when ticking it
+    pub checked_by: Option<String>,
+    #[ts(type = "Date | null")]
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+/// The checklist of the column a task is in
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskChecklist {
+    pub column_id: Uuid,
+    pub items: Vec<ChecklistItemStatus>,
+}
+
+impl TaskChecklist {
+    /// Items still to be ticked
+    pub fn pending(&self) -> Vec<&str> {
+        self.items
+            .iter()
+            .filter(|i| !i.checked)
+            .map(|i| i.item.as_str())
+            .collect()
+    }
+}
+
+/// Current state of each item; the latest tick or untick of an item wins
+pub fn item_statuses(items: &[String], ticks: &[ChecklistTick]) -> Vec<ChecklistItemStatus> {
+    items
+        .iter()
+        .map(|item| match ticks.iter().rev().find(|t| t.item == *item) {
+            Some(tick) if tick.checked => ChecklistItemStatus {
+                item: item.clone(),
+                checked: true,
+                checked_by: tick.actor_id.clone(),
+                checked_at: Some(tick.created_at),
+            },
+            _ => ChecklistItemStatus {
+                item: item.clone(),
+                checked: false,
+                checked_by: None,
+                checked_at: None,
+            },
+        })
+        .collect()
+}
+
+pub async fn for_task(
+    pool: &PgPool,
+    task_id: Uuid,
+    column_id: Uuid,
+) -> Result<TaskChecklist, sqlx::Error> {
+    let checklist = KanbanColumn::find_by_id(pool, column_id)
+        .await?
+        .map(|column| column.checklist)
+        .unwrap_or_default();
+    let items = if checklist.is_empty() {
+        Vec::new()
+    } else {
+        let ticks = TaskEvent::find_checklist_ticks(pool, task_id, column_id).await?;
+        item_statuses(&checklist, &ticks)
+    };
+    Ok(TaskChecklist { column_id, items })
+}
+
+/// Items that still block the task from leaving the column
+pub async fn pending_items(
+    pool: &PgPool,
+    task_id: Uuid,
+    column_id: Uuid,
+) -> Result<Vec<String>, sqlx::Error> {
+    let checklist = for_task(pool, task_id, column_id).await?;
+    Ok(checklist
+        .pending()
+        .into_iter()
+        .map(str::to_string)
+        .collect())
+}
+
+pub fn blocked_message(column_name: &str, pending: &[String]) -> String {
+    format!(
+        "The '{column_name}' checklist must be completed before the task can move on. \
+         Still to tick: {}",
+        pending.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn tick(item: &str, checked: bool, actor: &str, minutes: i64) -> ChecklistTick {
+        ChecklistTick {
+            item: item.to_string(),
+            checked,
+            actor_id: Some(actor.to_string()),
+            created_at: DateTime::<Utc>::UNIX_EPOCH + Duration::minutes(minutes),
+        }
+    }
+
+    #[test]
+    fn latest_tick_wins() {
+        let items = vec![
+            "Verify staging deploy".to_string(),
+            "Check error budget".to_string(),
+        ];
+        let ticks = vec![
+            tick("Verify staging deploy", true, "ana", 1),
+            tick("Check error budget", true, "ana", 2),
+            tick("Check error budget", false, "ben", 3),
+        ];
+        let statuses = item_statuses(&items, &ticks);
+
+        assert!(statuses[0].checked);
+        assert_eq!(statuses[0].checked_by.as_deref(), Some("ana"));
+        assert_eq!(
+            statuses[0].checked_at,
+            Some(DateTime::<Utc>::UNIX_EPOCH + Duration::minutes(1))
+        );
+        assert!(!statuses[1].checked);
+        assert_eq!(statuses[1].checked_by, None);
+    }
+
+    #[test]
+    fn pending_lists_unticked_items_in_order() {
+        let items = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let checklist = TaskChecklist {
+            column_id: Uuid::nil(),
+            items: item_statuses(
+                &items,
+                &[tick("b", true, "ana", 1), tick("gone", true, "ana", 2)],
+            ),
+        };
+
+        assert_eq!(checklist.pending(), vec!["a", "c"]);
+    }
+}
@@ -0,0 +1,105 @@
+//! Debounce for agent auto-start when a card is dragged across the board.
+//!
+//! Entering a column with an assigned agent starts that agent. Dragging a card through
+//! several columns in quick succession would start an agent in each one it passed, so
+//! an entry waits for a short delay first. A later entry for the same task supersedes
+//! it, and only the column the card comes to rest in starts its agent.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use uuid::Uuid;
+
+/// Used when `column_entry_debounce_ms` isn't set in the config
+pub const DEFAULT_DEBOUNCE_MS: u32 = 1500;
+
+static SHARED: LazyLock<ColumnEntryDebouncer> = LazyLock::new(ColumnEntryDebouncer::new);
+
+/// Delay before starting a column's agent; `None` starts it immediately
+pub fn debounce_delay(configured_ms: Option<u32>) -> Option<Duration> {
+    match configured_ms.unwrap_or(DEFAULT_DEBOUNCE_MS) {
+        0 => None,
+        ms => Some(Duration::from_millis(ms as u64)),
+    }
+}
+
+/// Latest pending column entry per task
+pub struct ColumnEntryDebouncer {
+    pending: Mutex<HashMap<Uuid, u64>>,
+    next_ticket: AtomicU64,
+}
+
+impl ColumnEntryDebouncer {
+    pub fn new() -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            next_ticket: AtomicU64::new(1),
+        }
+    }
+
+    pub fn shared() -> &'static ColumnEntryDebouncer {
+        &SHARED
+    }
+
+    /// Record that a task entered a column, superseding its pending entry if any
+    pub fn enter(&self, task_id: Uuid) -> u64 {
+        let ticket = self.next_ticket.fetch_add(1, Ordering::Relaxed);
+        self.pending.lock().unwrap().insert(task_id, ticket);
+        ticket
+    }
+
+    /// Whether `ticket` is still the task's latest entry. Settling it clears the
+    /// pending entry, so each ticket settles at most once.
+    pub fn settle(&self, task_id: Uuid, ticket: u64) -> bool {
+        let mut pending = self.pending.lock().unwrap();
+        if pending.get(&task_id) == Some(&ticket) {
+            pending.remove(&task_id);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Default for ColumnEntryDebouncer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_entry_supersedes_pending_one() {
+        let debouncer = ColumnEntryDebouncer::new();
+        let task = Uuid::new_v4();
+        let other_task = Uuid::new_v4();
+
+        let passed_through = debouncer.enter(task);
+        let other = debouncer.enter(other_task);
+        let resting = debouncer.enter(task);
+
+        assert!(!debouncer.settle(task, passed_through));
+        assert!(debouncer.settle(task, resting));
+        assert!(!debouncer.settle(task, resting));
+        assert!(debouncer.settle(other_task, other));
+    }
+
+    #[test]
+    fn zero_delay_disables_debounce() {
+        assert_eq!(debounce_delay(Some(0)), None);
+        assert_eq!(debounce_delay(Some(250)), Some(Duration::from_millis(250)));
+        assert_eq!(
+            debounce_delay(None),
+            Some(Duration::from_millis(DEFAULT_DEBOUNCE_MS as u64))
+        );
+    }
+}
@@ -26,6 +26,10 @@ pub type EditorType = versions::v8::EditorType;
 pub type GitHubConfig = versions::v8::GitHubConfig;
 pub type UiLanguage = versions::v8::UiLanguage;
 pub type ShowcaseState = versions::v8::ShowcaseState;
+pub type AgentConcurrencyConfig = versions::v8::AgentConcurrencyConfig;
+pub type AttemptBudgetConfig = versions::v8::AttemptBudgetConfig;
+pub type OutputLimitsConfig = versions::v8::OutputLimitsConfig;
+pub type RateLimitConfig = versions::v8::RateLimitConfig;
 
 /// Will always return config, trying old schemas or eventually returning default
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
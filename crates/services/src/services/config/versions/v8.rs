@@ -1,7 +1,12 @@
+use std::{collections::HashMap, net::IpAddr};
+
 use anyhow::Error;
+use db::models::execution_process::ExecutionProcessRunReason;
 use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utils::{output_limit::OutputLimit, rate_limit::RateLimit};
+use uuid::Uuid;
 pub use v7::{
     EditorConfig, EditorType, GitHubConfig, NotificationConfig, ShowcaseState, SoundFile,
     ThemeMode, UiLanguage,
@@ -17,6 +22,118 @@ fn default_pr_auto_description_enabled() -> bool {
     true
 }
 
+/// Caps on concurrently running coding agent processes, across all projects.
+/// Agents over the limit are queued until a slot frees up.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct AgentConcurrencyConfig {
+    /// Maximum coding agents running at once (None = unlimited)
+    #[serde(default)]
+    pub max_concurrent: Option<u32>,
+    /// Per-executor maximums, e.g. `{ "CLAUDE_CODE": 2 }`
+    #[serde(default)]
+    pub per_executor: HashMap<BaseCodingAgent, u32>,
+    /// Weights for sharing slots between projects with agents waiting, by project id.
+    /// Projects not listed weigh 1.
+    #[serde(default)]
+    pub project_weights: HashMap<Uuid, u32>,
+}
+
+/// Token budget checked against the cost estimate before an attempt starts
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct AttemptBudgetConfig {
+    /// Refuse to start attempts estimated above this many tokens (None = no limit)
+    #[serde(default)]
+    pub max_estimated_tokens: Option<u32>,
+}
+
+/// Per-stream output caps for execution processes, in KB. Output past a cap is
+/// replaced by a truncation marker, keeping the tail for diagnosis.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct OutputLimitsConfig {
+    /// Defaults to 20 MB
+    #[serde(default)]
+    pub coding_agent_kb: Option<u32>,
+    /// Defaults to 2 MB
+    #[serde(default)]
+    pub setup_script_kb: Option<u32>,
+    /// Defaults to 2 MB
+    #[serde(default)]
+    pub cleanup_script_kb: Option<u32>,
+    /// Defaults to 5 MB
+    #[serde(default)]
+    pub dev_server_kb: Option<u32>,
+    /// Output kept from the end of a truncated stream (defaults to 64 KB)
+    #[serde(default)]
+    pub tail_kb: Option<u32>,
+}
+
+impl OutputLimitsConfig {
+    pub fn limit_for(&self, run_reason: &ExecutionProcessRunReason) -> OutputLimit {
+        let max_kb = match run_reason {
+            ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::ManualRun => {
+                self.coding_agent_kb.unwrap_or(20 * 1024)
+            }
+            ExecutionProcessRunReason::SetupScript => self.setup_script_kb.unwrap_or(2 * 1024),
+            ExecutionProcessRunReason::CleanupScript => self.cleanup_script_kb.unwrap_or(2 * 1024),
+            ExecutionProcessRunReason::DevServer => self.dev_server_kb.unwrap_or(5 * 1024),
+        };
+        OutputLimit {
+            max_bytes: max_kb as usize * 1024,
+            tail_bytes: self.tail_kb.unwrap_or(64) as usize * 1024,
+        }
+    }
+}
+
+/// API rate limits, counted per client IP and per bearer token for requests whose
+/// path starts with one of `paths`. Throttled requests get a 429.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct RateLimitConfig {
+    /// Defaults to true
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Defaults to 600
+    #[serde(default)]
+    pub per_ip_per_minute: Option<u32>,
+    /// Defaults to 300
+    #[serde(default)]
+    pub per_token_per_minute: Option<u32>,
+    /// Path prefixes to limit (defaults to `/api/tasks` and `/api/context-artifacts`,
+    /// where MCP and agent traffic lands)
+    #[serde(default)]
+    pub paths: Option<Vec<String>>,
+    /// Also limit loopback clients (the local UI and agents on this machine).
+    /// Defaults to false
+    #[serde(default)]
+    pub limit_loopback: Option<bool>,
+}
+
+impl RateLimitConfig {
+    pub fn applies_to(&self, path: &str) -> bool {
+        if !self.enabled.unwrap_or(true) {
+            return false;
+        }
+        match &self.paths {
+            Some(paths) => paths.iter().any(|prefix| path.starts_with(prefix.as_str())),
+            None => ["/api/tasks", "/api/context-artifacts"]
+                .iter()
+                .any(|prefix| path.starts_with(prefix)),
+        }
+    }
+
+    /// Whether requests from this client address are limited at all
+    pub fn limits_ip(&self, ip: IpAddr) -> bool {
+        self.limit_loopback.unwrap_or(false) || !ip.to_canonical().is_loopback()
+    }
+
+    pub fn per_ip(&self) -> RateLimit {
+        RateLimit::per_minute(self.per_ip_per_minute.unwrap_or(600))
+    }
+
+    pub fn per_token(&self) -> RateLimit {
+        RateLimit::per_minute(self.per_token_per_minute.unwrap_or(300))
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TS)]
 pub struct Config {
     pub config_version: String,
@@ -41,6 +158,21 @@ pub struct Config {
     pub pr_auto_description_enabled: bool,
     #[serde(default)]
     pub pr_auto_description_prompt: Option<String>,
+    #[serde(default)]
+    pub agent_concurrency: AgentConcurrencyConfig,
+    #[serde(default)]
+    pub attempt_budget: AttemptBudgetConfig,
+    #[serde(default)]
+    pub output_limits: OutputLimitsConfig,
+    #[serde(default)]
+    pub rate_limits: RateLimitConfig,
+    #[serde(default)]
+    pub remote_api_requires_token: bool,
+    /// Delay before a column's agent starts after a task enters it, so dragging a task
+    /// across the board only starts the agent where it comes to rest. Defaults to
+    /// 1500; 0 starts agents immediately.
+    #[serde(default)]
+    pub column_entry_debounce_ms: Option<u32>,
 }
 
 impl Config {
@@ -66,6 +198,12 @@ impl Config {
             showcases: old_config.showcases,
             pr_auto_description_enabled: true,
             pr_auto_description_prompt: None,
+            agent_concurrency: AgentConcurrencyConfig::default(),
+            attempt_budget: AttemptBudgetConfig::default(),
+            output_limits: OutputLimitsConfig::default(),
+            rate_limits: RateLimitConfig::default(),
+            remote_api_requires_token: false,
+            column_entry_debounce_ms: None,
         }
     }
 
@@ -116,6 +254,12 @@ impl Default for Config {
             showcases: ShowcaseState::default(),
             pr_auto_description_enabled: true,
             pr_auto_description_prompt: None,
+            agent_concurrency: AgentConcurrencyConfig::default(),
+            attempt_budget: AttemptBudgetConfig::default(),
+            output_limits: OutputLimitsConfig::default(),
+            rate_limits: RateLimitConfig::default(),
+            remote_api_requires_token: false,
+            column_entry_debounce_ms: None,
         }
     }
 }
@@ -1,7 +1,8 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use anyhow::{Error as AnyhowError, anyhow};
@@ -10,8 +11,14 @@ use db::{
     DBService,
     models::{
         agent::Agent,
+        agent_prompt_settings::AgentPromptSettings,
+        agent_session::AgentSession,
+        chat_approval::ChatApprovalSubject,
         coding_agent_turn::{CodingAgentTurn, CreateCodingAgentTurn},
         context_artifact::{ArtifactScope, ArtifactType, ContextArtifact, CreateContextArtifact},
+        context_artifact_review::ContextArtifactReview,
+        email_notification_preference::ImmediateEmailKind,
+        execution_artifact::ExecutionArtifact,
         execution_process::{
             CreateExecutionProcess, ExecutionContext, ExecutionProcess, ExecutionProcessRunReason,
             ExecutionProcessStatus,
@@ -21,30 +28,46 @@ use db::{
             CreateExecutionProcessRepoState, ExecutionProcessRepoState,
         },
         kanban_column::KanbanColumn,
+        merge::{Merge, PullRequestInfo},
+        notification_channel::NotificationEvent,
+        plan_review::{PlanReview, PlanReviewStatus, ReviewPlan},
+        security_finding::SecurityFinding,
         project::{Project, UpdateProject},
+        project_module_memory_settings::ProjectModuleMemorySettings,
         project_repo::{ProjectRepo, ProjectRepoWithName},
+        project_security_settings::ProjectSecuritySettings,
         repo::Repo,
+        repo_forge_settings::{ForgeKind, RepoForgeSettings},
+        reviewer_feedback::ReviewerFeedback,
         session::{CreateSession, Session, SessionError},
+        setup_script_run::{CreateSetupScriptRun, FLAKY_WINDOW, SetupScriptRun},
         state_transition::StateTransition,
         tag::Tag,
         task::{CreateTask, Task, TaskState, TaskStatus},
+        task_comment::TaskComment,
         task_dependency::TaskDependency,
         task_event::{ActorType, CreateTaskEvent, EventTriggerType, TaskEvent},
+        transition_artifact_requirement::TransitionArtifactRequirement,
+        transition_retry_policy::TransitionRetryPolicy,
         task_group::TaskGroup,
         group_event::{CreateGroupEvent, GroupEvent},
         skill::Skill,
+        user::User,
         workspace::{CreateWorkspace, Workspace, WorkspaceError},
-        workspace_repo::WorkspaceRepo,
+        workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
     },
 };
 use executors::{
     actions::{
-        ExecutorAction, ExecutorActionType,
+        AgentIdentity, ExecutorAction, ExecutorActionType,
         coding_agent_initial::CodingAgentInitialRequest,
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
     executors::{BaseCodingAgent, ExecutorError, StandardCodingAgentExecutor},
-    logs::{NormalizedEntry, NormalizedEntryError, NormalizedEntryType, utils::ConversationPatch},
+    logs::{
+        NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
+        utils::{ConversationPatch, patch::attribute_to_agent},
+    },
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
 use futures::{StreamExt, future};
@@ -53,20 +76,52 @@ use std::str::FromStr;
 use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle};
 use utils::{
+    approvals::{ApprovalResponse, ApprovalStatus},
     log_msg::LogMsg,
     msg_store::MsgStore,
-    text::{git_branch_id, short_uuid},
+    text::short_uuid,
 };
 use uuid::Uuid;
 
 use crate::services::{
+    agent_concurrency::AgentConcurrencyLimiter,
+    approvals::{ApprovalError, Approvals},
+    automation,
+    branch_template::{self, BranchValues, DEFAULT_BRANCH_TEMPLATE},
+    chat_approvals::{ChatDecision, Verdict},
+    column_checklist,
+    config::Config,
+    cost_estimate,
+    data_migration::DataMigrationProgress,
+    decision_file::{delete_decision_files, prepare_decision_dirs, read_decision_file},
+    dev_server_preview::DevServerPreviews,
+    email_notifications,
     events::{execution_process_patch, group_event_patch, group_patch, project_patch},
-    git::{GitService, GitServiceError},
+    execution_artifacts,
+    execution_replay,
+    executor_health::CircuitEvent,
+    forge::{self, CodeHost, CodeHostError, CreatePrError},
+    git::{GitCliError, GitService, GitServiceError},
+    github::{CreatePrRequest, GitHubServiceError},
     group_analyzer::GroupAnalyzer,
+    handoff_notes,
+    image::ImageService,
+    module_memory_refresh::{self, MergedRepoChanges},
     notification::NotificationService,
+    notification_channels::{self, ChannelNotification},
+    plan_review,
     prereq_evaluator::{self, PREREQ_EVALUATOR_AGENT_ID},
+    prompt_language::{self, PromptLanguage},
+    reviewer_feedback,
+    security_scan,
     share::SharePublisher,
+    shutdown::{self, ShutdownCoordinator},
+    task_comments,
+    transition_condition::{
+        TransitionCondition, stage_started_at, with_artifacts, with_subtask_progress,
+    },
     workspace_manager::WorkspaceError as WorkspaceManagerError,
+    workspace_orientation,
     worktree_manager::WorktreeError,
 };
 pub type ContainerRef = String;
@@ -91,6 +146,12 @@ pub enum ContainerError {
     Io(#[from] std::io::Error),
     #[error("Failed to kill process: {0}")]
     KillFailed(std::io::Error),
+    #[error("Server is shutting down")]
+    ShuttingDown,
+    #[error("{0}")]
+    BudgetExceeded(String),
+    #[error(transparent)]
+    CodeHost(#[from] CodeHostError),
     #[error(transparent)]
     Other(#[from] AnyhowError), // Catches any unclassified errors
 }
@@ -100,10 +161,16 @@ pub struct AgentContext {
     pub system_prompt: Option<String>,
     pub workflow_history: Option<String>,
     pub start_command: Option<String>,
+    /// The column's question and pending reviewer feedback
+    pub decision_instructions: Option<String>,
     pub deliverable: Option<String>,
     pub name: String,
     pub color: Option<String>,
     pub column_name: String,
+    /// Agent being launched; recorded against the session for performance stats
+    pub agent_id: Uuid,
+    /// Kanban column the agent is working in (None for project-level agents)
+    pub column_id: Option<Uuid>,
     /// Project-level context from context artifacts (module memories, ADRs, patterns)
     pub project_context: Option<String>,
     /// Override the task ID used for state updates and prompt building.
@@ -112,82 +179,8 @@ pub struct AgentContext {
     pub task_id_override: Option<uuid::Uuid>,
 }
 
-/// Read the decision file (.vibe/decision.json) from a workspace.
-/// Checks both the workspace root and repo subdirectories, since
-/// the agent may run inside a repo subdirectory in multi-repo workspaces.
-pub async fn read_decision_file(workspace: &Workspace) -> Option<serde_json::Value> {
-    let worktree_path = workspace.container_ref.as_ref()?;
-    let base = PathBuf::from(worktree_path);
-
-    // 1. Check workspace root
-    let root_path = base.join(".vibe/decision.json");
-    if root_path.exists() {
-        return parse_decision_file(&root_path).await;
-    }
-
-    // 2. Check repo subdirectories (multi-repo workspaces)
-    if let Ok(mut entries) = tokio::fs::read_dir(&base).await {
-        while let Ok(Some(entry)) = entries.next_entry().await {
-            if entry.path().is_dir() {
-                let sub_path = entry.path().join(".vibe/decision.json");
-                if sub_path.exists() {
-                    return parse_decision_file(&sub_path).await;
-                }
-            }
-        }
-    }
-
-    None
-}
-
-async fn parse_decision_file(path: &PathBuf) -> Option<serde_json::Value> {
-    match tokio::fs::read_to_string(path).await {
-        Ok(content) => match serde_json::from_str(&content) {
-            Ok(value) => Some(value),
-            Err(e) => {
-                tracing::warn!("Failed to parse decision file at {:?}: {}", path, e);
-                None
-            }
-        },
-        Err(e) => {
-            tracing::warn!("Failed to read decision file at {:?}: {}", path, e);
-            None
-        }
-    }
-}
-
-/// Delete the decision file (.vibe/decision.json) from a workspace.
-/// Called after a transition so the next column starts with a clean slate.
-async fn delete_decision_file(workspace: &Workspace) {
-    let Some(worktree_path) = workspace.container_ref.as_ref() else {
-        return;
-    };
-    let base = PathBuf::from(worktree_path);
-
-    // 1. Check workspace root
-    let root_path = base.join(".vibe/decision.json");
-    if root_path.exists() {
-        if let Err(e) = tokio::fs::remove_file(&root_path).await {
-            tracing::warn!("Failed to delete decision file at {:?}: {}", root_path, e);
-        }
-        return;
-    }
-
-    // 2. Check repo subdirectories (multi-repo workspaces)
-    if let Ok(mut entries) = tokio::fs::read_dir(&base).await {
-        while let Ok(Some(entry)) = entries.next_entry().await {
-            if entry.path().is_dir() {
-                let sub_path = entry.path().join(".vibe/decision.json");
-                if sub_path.exists() {
-                    if let Err(e) = tokio::fs::remove_file(&sub_path).await {
-                        tracing::warn!("Failed to delete decision file at {:?}: {}", sub_path, e);
-                    }
-                    return;
-                }
-            }
-        }
-    }
-}
+/// How long `normalized_log_history` waits for more entries from stored logs
+const NORMALIZATION_IDLE_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Try to create a context artifact from a decision file
 /// If the decision contains artifact_type, title, content, and optionally scope,
@@ -261,6 +254,7 @@ pub async fn try_create_artifact_from_decision(
         content.len()
     );
 
+    let needs_review = artifact_type.needs_review();
     let create_artifact = CreateContextArtifact {
         project_id,
         artifact_type,
@@ -278,6 +272,12 @@ pub async fn try_create_artifact_from_decision(
 
     match ContextArtifact::create(pool, create_artifact, uuid::Uuid::new_v4()).await {
         Ok(artifact) => {
+            // Agent-written knowledge waits for a person before it reaches prompts
+            if needs_review
+                && let Err(e) = ContextArtifactReview::mark_draft(pool, artifact.id).await
+            {
+                tracing::error!("Failed to mark artifact {} for review: {}", artifact.id, e);
+            }
             tracing::info!(
                 target: "vibe_kanban::compound",
                 "  └─ ✅ Created artifact {} - knowledge compounded!",
@@ -418,32 +418,53 @@ pub enum TransitionResult {
     NoMatch,
 }
 
+/// Attribute the normalized entries of a log message to the agent its process ran for
+fn attribute_log_msg(msg: LogMsg, agent: Option<&AgentIdentity>) -> LogMsg {
+    match (msg, agent) {
+        (LogMsg::JsonPatch(patch), Some(agent)) => {
+            LogMsg::JsonPatch(attribute_to_agent(patch, agent))
+        }
+        (msg, _) => msg,
+    }
+}
+
 /// Evaluate a transition against the decision file and failure count.
 /// Returns which destination column to use based on the semantics:
 /// - to_column_id: answer matched (success)
 /// - else_column_id: answer didn't match, under failure limit (retry)
 /// - escalation_column_id: answer didn't match, at/over failure limit (emergency)
+///
+/// When `success_blocked` is set (security gate failed, or a required artifact is
+/// missing), a matching condition is treated as a failure so the task takes the
+/// else/escalation path instead.
 fn evaluate_transition(
     transition: &StateTransition,
     decision: &Option<serde_json::Value>,
     failure_count: i64,
+    success_blocked: bool,
 ) -> TransitionResult {
-    // Check if answer matches this transition's expected condition_value
+    // Check if the decision satisfies this transition's condition_value
+    // (a plain answer, or an expression like `coverage >= 80`)
     let condition_matches = match (&transition.condition_value, decision) {
-        (Some(expected_value), Some(dec)) => {
-            // Look up the "answer" key in decision.json
-            dec.get("answer")
-                .and_then(|v| v.as_str())
-                .map(|v| v == expected_value)
-                .unwrap_or(false)
-        }
+        (Some(condition), Some(dec)) => match TransitionCondition::parse(condition) {
+            Ok(parsed) => parsed.matches(dec),
+            Err(e) => {
+                tracing::warn!(
+                    "Invalid condition '{}' on transition {}: {}",
+                    condition,
+                    transition.id,
+                    e
+                );
+                false
+            }
+        },
         // No condition_value defined - unconditional transition (unless requires confirmation)
         (None, _) => !transition.requires_confirmation,
         // condition_value set but no decision file - no match
         (Some(_), None) => false,
     };
 
-    if condition_matches {
+    if condition_matches && !success_blocked {
         // Success path - go to to_column_id
         return TransitionResult::Success(transition.to_column_id);
     }
@@ -469,20 +490,146 @@ fn evaluate_transition(
     TransitionResult::NoMatch
 }
 
+/// Script request for a column's entry/exit script. Single-repo projects run it in the
+/// repo, in the repo's script language; multi-repo projects in the workspace root, in
+/// the language their repos share (Bash when they differ).
+fn column_script_request(
+    script: String,
+    context: ScriptContext,
+    repos: &[ProjectRepoWithName],
+) -> ScriptRequest {
+    let mut languages = repos.iter().map(|repo| repo.script_request_language());
+    let language = match languages.next() {
+        Some(first) if languages.all(|language| language == first) => first,
+        _ => ScriptRequestLanguage::Bash,
+    };
+    ScriptRequest {
+        script,
+        language,
+        context,
+        working_dir: match repos {
+            [repo] => Some(repo.repo_name.clone()),
+            _ => None,
+        },
+    }
+}
+
+/// Chain a column agent's request between the column's entry script and its exit
+/// script, followed by the repos' cleanup scripts. Returns the first action of the chain
+/// with its run reason.
+fn with_column_scripts(
+    agent_request: ExecutorActionType,
+    on_enter_script: Option<String>,
+    on_exit_script: Option<String>,
+    cleanup_action: Option<ExecutorAction>,
+    repos: &[ProjectRepoWithName],
+) -> (ExecutorAction, ExecutionProcessRunReason) {
+    let after_agent = match on_exit_script {
+        Some(script) => Some(ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(column_script_request(
+                script,
+                ScriptContext::ColumnExitScript,
+                repos,
+            )),
+            cleanup_action.map(Box::new),
+        )),
+        None => cleanup_action,
+    };
+    let agent_action = ExecutorAction::new(agent_request, after_agent.map(Box::new));
+
+    match on_enter_script {
+        Some(script) => (
+            ExecutorAction::new(
+                ExecutorActionType::ScriptRequest(column_script_request(
+                    script,
+                    ScriptContext::ColumnEnterScript,
+                    repos,
+                )),
+                Some(Box::new(agent_action)),
+            ),
+            ExecutionProcessRunReason::SetupScript,
+        ),
+        None => (agent_action, ExecutionProcessRunReason::CodingAgent),
+    }
+}
+
+/// Run reason of the action chained after `current`
+fn next_run_reason(
+    current: &ExecutorActionType,
+    next: &ExecutorActionType,
+) -> ExecutionProcessRunReason {
+    match (current, next) {
+        // Column exit scripts and the cleanup scripts after them clean up, whatever ran before
+        (
+            ExecutorActionType::ScriptRequest(_),
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                context: ScriptContext::CleanupScript | ScriptContext::ColumnExitScript,
+                ..
+            }),
+        ) => ExecutionProcessRunReason::CleanupScript,
+        (ExecutorActionType::ScriptRequest(_), ExecutorActionType::ScriptRequest(_)) => {
+            ExecutionProcessRunReason::SetupScript
+        }
+        (
+            ExecutorActionType::CodingAgentInitialRequest(_)
+            | ExecutorActionType::CodingAgentFollowUpRequest(_),
+            ExecutorActionType::ScriptRequest(_),
+        ) => ExecutionProcessRunReason::CleanupScript,
+        (
+            _,
+            ExecutorActionType::CodingAgentFollowUpRequest(_)
+            | ExecutorActionType::CodingAgentInitialRequest(_),
+        ) => ExecutionProcessRunReason::CodingAgent,
+    }
+}
+
 /// Build decision instructions for an agent based on the column's question and answer options.
 /// This tells the agent what to write to .vibe/decision.json to route the task.
-/// Also includes feedback from a prior rejection if present in the existing decision file.
+/// Also includes the task's pending reviewer feedback, or feedback left in the existing
+/// decision file.
 /// Uses hierarchical resolution: task-level > project-level > board-level transitions.
+/// The text is written in the agent's prompt language.
 pub async fn build_decision_instructions(
-    _pool: &sqlx::PgPool,
+    pool: &sqlx::PgPool,
     column: &KanbanColumn,
-    _task_id: Uuid,
-    _project_id: Uuid,
-    _board_id: Option<Uuid>,
+    task_id: Uuid,
+    project_id: Uuid,
+    board_id: Option<Uuid>,
     existing_decision: &Option<serde_json::Value>,
+    language: PromptLanguage,
 ) -> Option<String> {
-    // Only generate instructions if the column has a question
-    let question = column.question.as_ref()?;
+    // Feedback from a prior rejection (and agent-visible task comments) is passed on
+    // even if the column has no question
+    let feedback_section = match ReviewerFeedback::find_pending(pool, task_id).await {
+        Ok(Some(feedback)) => Some(reviewer_feedback::prompt_section(
+            &feedback.feedback,
+            Some(feedback.version),
+            language,
+        )),
+        Ok(None) => reviewer_feedback::feedback_from_decision(existing_decision.as_ref())
+            .map(|feedback| reviewer_feedback::prompt_section(&feedback, None, language)),
+        Err(e) => {
+            tracing::error!("Failed to load reviewer feedback for task {}: {}", task_id, e);
+            None
+        }
+    };
+    // Comments people marked visible to the agent go out with the feedback
+    let comments_section = match TaskComment::find_pending_for_agent(pool, task_id).await {
+        Ok(comments) => task_comments::prompt_section(&comments, language),
+        Err(e) => {
+            tracing::error!("Failed to load task comments for task {}: {}", task_id, e);
+            None
+        }
+    };
+    let feedback_section = match (feedback_section, comments_section) {
+        (Some(feedback), Some(comments)) => Some(format!("{feedback}{comments}")),
+        (feedback, comments) => feedback.or(comments),
+    };
+
+    // Only generate decision instructions if the column has a question
+    let Some(question) = column.question.as_ref() else {
+        return feedback_section.map(|section| format!("\n\n---\n{}", section));
+    };
 
     // Parse answer_options from the column (e.g. '["yes", "no"]')
     let options: Vec<String> = column.answer_options.as_ref()
@@ -490,27 +637,41 @@ pub async fn build_decision_instructions(
         .unwrap_or_default();
 
     if options.is_empty() {
-        return None;
+        return feedback_section.map(|section| format!("\n\n---\n{}", section));
     }
 
+    let text = prompt_language::decision_text(language);
     let mut instructions = String::new();
-    instructions.push_str("\n\n---\n\n## Question\n\n");
+    instructions.push_str(&format!("\n\n---\n\n## {}\n\n", text.heading));
     instructions.push_str(question);
-    instructions.push_str("\n\nAfter completing your work, answer this question by writing to `.vibe/decision.json`.\n");
-    instructions.push_str("Include the question text for readability.\n\n");
-    instructions.push_str(&format!("Valid answers: {}\n", options.iter().map(|o| format!("\"{}\"", o)).collect::<Vec<_>>().join(", ")));
+    instructions.push_str(&format!("\n\n{}\n\n", text.how_to_answer));
+    instructions.push_str(&format!("{}: {}\n", text.valid_answers, options.iter().map(|o| format!("\"{}\"", o)).collect::<Vec<_>>().join(", ")));
 
-    instructions.push_str(&format!("\nExample:\n```json\n{{\"question\": \"{}\", \"answer\": \"{}\"}}\n```\n", question, options[0]));
+    instructions.push_str(&format!("\n{}:\n```json\n{{\"question\": \"{}\", \"answer\": \"{}\"}}\n```\n", text.example, question, options[0]));
 
-    // Include feedback from prior rejection if present
-    if let Some(decision) = existing_decision {
-        if let Some(feedback) = decision.get("feedback").and_then(|f| f.as_str()) {
-            instructions.push_str("\n### Feedback from Previous Review\n\n");
-            instructions.push_str(feedback);
-            instructions.push_str("\n\nPlease address this feedback before proceeding.\n");
+    // Expression conditions (e.g. `coverage >= 80`) read keys besides "answer";
+    // tell the agent which ones routing depends on
+    let expressions: Vec<String> =
+        StateTransition::find_from_column_for_task(pool, column.id, task_id, project_id, board_id)
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|t| t.condition_value.as_deref())
+            .filter(|c| matches!(TransitionCondition::parse(c), Ok(TransitionCondition::Expr(_))))
+            .map(|c| c.trim().to_string())
+            .collect();
+    if !expressions.is_empty() {
+        instructions.push_str(&format!("\n{}\n", text.routing_conditions));
+        for expr in &expressions {
+            instructions.push_str(&format!("- `{}`\n", expr));
         }
     }
 
+    // Include feedback from prior rejection if present
+    if let Some(section) = &feedback_section {
+        instructions.push_str(section);
+    }
+
     Some(instructions)
 }
 
@@ -524,8 +685,22 @@ pub trait ContainerService {
 
     fn share_publisher(&self) -> Option<&SharePublisher>;
 
+    /// Pending executor tool approvals
+    fn approvals(&self) -> &Approvals;
+
     fn notification_service(&self) -> &NotificationService;
 
+    fn config(&self) -> &Arc<RwLock<Config>>;
+
+    /// Store for uploaded images and collected execution artifacts
+    fn image_service(&self) -> &ImageService;
+
+    /// Caps concurrent coding agent processes across all projects
+    fn agent_limiter(&self) -> &AgentConcurrencyLimiter;
+
+    /// Ports of running dev servers, for the preview proxy
+    fn dev_server_previews(&self) -> &DevServerPreviews;
+
     /// Global events msg_store used for broadcasting state changes to connected clients.
     fn events_msg_store(&self) -> &Arc<MsgStore>;
 
@@ -533,7 +708,8 @@ pub trait ContainerService {
 
     async fn create(&self, workspace: &Workspace) -> Result<ContainerRef, ContainerError>;
 
-    async fn kill_all_running_processes(&self) -> Result<(), ContainerError>;
+    /// Stop every running execution as `Interrupted`, for server shutdown
+    async fn interrupt_all_running_processes(&self) -> Result<(), ContainerError>;
 
     async fn delete(&self, workspace: &Workspace) -> Result<(), ContainerError>;
 
@@ -606,7 +782,7 @@ pub trait ContainerService {
 
         let prompt = prereq_evaluator::build_prereq_eval_prompt(&project);
 
-        let project_context = match ContextArtifact::build_full_context(pool, project_id, None, &[]).await {
+        let project_context = match ContextArtifact::build_full_context(pool, project_id, None, None, &[]).await {
             Ok(ctx) if !ctx.is_empty() => Some(ctx),
             Ok(_) => None,
             Err(e) => {
@@ -634,10 +810,13 @@ pub trait ContainerService {
             system_prompt: Some(agent.system_prompt.clone()),
             workflow_history: None,
             start_command: Some(prompt),
+            decision_instructions: None,
             deliverable: None,
             name: agent.name.clone(),
             color: agent.color.clone(),
             column_name: "Prerequisite Evaluation".to_string(),
+            agent_id: agent.id,
+            column_id: None,
             project_context,
             task_id_override: None,
         };
@@ -785,10 +964,12 @@ pub trait ContainerService {
             return false;
         }
 
-        // Always finalize failed or killed executions, regardless of next action
+        // Always finalize failed, killed or interrupted executions, regardless of next action
         if matches!(
             ctx.execution_process.status,
-            ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed
+            ExecutionProcessStatus::Failed
+                | ExecutionProcessStatus::Killed
+                | ExecutionProcessStatus::Interrupted
         ) {
             return true;
         }
@@ -810,6 +991,17 @@ pub trait ContainerService {
     ) {
         let pool = &self.db().pool;
 
+        // Manual runs happen outside the column workflow: the task keeps its column,
+        // status and state, and only the notification below is sent
+        let manual_run = matches!(
+            ctx.execution_process.run_reason,
+            ExecutionProcessRunReason::ManualRun
+        );
+
+        // Pick up files the agent left in .vibe/artifacts/ so they show on the
+        // timeline and can gate the transition below
+        execution_artifacts::collect(pool, self.image_service(), ctx).await;
+
         // Check if the agent wrote a decision file (indicating true completion)
         let decision = read_decision_file(&ctx.workspace).await;
         let has_decision = decision.is_some();
@@ -817,12 +1009,21 @@ pub trait ContainerService {
         // Try to auto-transition only if:
         // 1. Execution completed successfully
         // 2. Agent wrote a decision.json (indicating it's done, not just waiting for user input)
-        let transitioned = if matches!(ctx.execution_process.status, ExecutionProcessStatus::Completed) && has_decision {
+        let transitioned = if manual_run {
+            false
+        } else if matches!(ctx.execution_process.status, ExecutionProcessStatus::Completed) && has_decision {
             // Agent truly finished - set transitioning state and run auto-transition
             if let Err(e) = Task::update_task_state(pool, ctx.task.id, TaskState::Transitioning).await {
                 tracing::error!("Failed to set task to transitioning state: {}", e);
             }
 
+            // Leave a note for the next column before the decision file is cleared
+            if let Err(e) =
+                handoff_notes::record_automatic_note(pool, self.git(), ctx, decision.as_ref()).await
+            {
+                tracing::error!("Failed to record handoff note for task {}: {}", ctx.task.id, e);
+            }
+
             let transitioned = self.try_auto_transition(ctx).await;
 
             // Only reset to queued if no transition happened
@@ -855,17 +1056,11 @@ pub trait ContainerService {
 
         // Only update status to InReview if we didn't auto-transition
         // (auto-transition handles status update as part of column change)
-        if !transitioned {
+        if !transitioned && !manual_run {
             match Task::update_status(pool, ctx.task.id, TaskStatus::InReview).await {
                 Ok(_) => {
-                    if let Some(publisher) = share_publisher
-                        && let Err(err) = publisher.update_shared_task_by_id(ctx.task.id).await
-                    {
-                        tracing::warn!(
-                            ?err,
-                            "Failed to propagate shared task update for {}",
-                            ctx.task.id
-                        );
+                    if let Some(publisher) = share_publisher {
+                        publisher.propagate_task_update(ctx.task.id).await;
                     }
                 }
                 Err(e) => {
@@ -874,6 +1069,149 @@ pub trait ContainerService {
             }
         }
 
+        // Deliver to Slack/Discord/HTTP channels in the background so slow
+        // webhooks don't hold up the exit monitor
+        if let Some(notification) = ChannelNotification::for_execution(ctx) {
+            notification_channels::spawn_dispatch(
+                pool.clone(),
+                share_publisher.cloned(),
+                ctx.project.clone(),
+                notification,
+            );
+        }
+
+        if ctx.execution_process.status == ExecutionProcessStatus::Failed {
+            let (subject, body) = email_notifications::failure_email(ctx);
+            let notifications = self.notification_service().clone();
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                notifications
+                    .email_immediate(&pool, ImmediateEmailKind::Failure, &subject, &body)
+                    .await;
+            });
+        }
+    }
+
+    /// The artifact type a transition requires that the task didn't write during its
+    /// current stage in `column_id`, if any. Lookup errors don't block the transition.
+    async fn missing_required_artifact(
+        &self,
+        transition: &StateTransition,
+        task_id: Uuid,
+        column_id: Uuid,
+    ) -> Option<ArtifactType> {
+        let pool = &self.db().pool;
+        let requirement =
+            match TransitionArtifactRequirement::find_by_transition_id(pool, transition.id).await
+            {
+                Ok(requirement) => requirement?,
+                Err(e) => {
+                    tracing::error!("Failed to fetch artifact requirement: {}", e);
+                    return None;
+                }
+            };
+        let Some(artifact_type) = requirement.get_artifact_type() else {
+            tracing::warn!(
+                "Unknown artifact type '{}' required by transition {}",
+                requirement.artifact_type,
+                transition.id
+            );
+            return None;
+        };
+        let since = match TaskEvent::find_column_entries_for_task(pool, task_id).await {
+            Ok(entries) => stage_started_at(&entries, column_id),
+            Err(e) => {
+                tracing::error!("Failed to load column entries for task {}: {}", task_id, e);
+                None
+            }
+        };
+        match ContextArtifact::exists_for_task(pool, task_id, &artifact_type, since).await {
+            Ok(true) => None,
+            Ok(false) => Some(artifact_type),
+            Err(e) => {
+                tracing::error!("Failed to check artifacts for task {}: {}", task_id, e);
+                None
+            }
+        }
+    }
+
+    /// Run the column's configured security scanners in the workspace and store
+    /// the findings for this execution. Returns true if any finding is blocking. The
+    /// gate fails closed: if it can't tell whether the column scans, or a scanner can't
+    /// run, the task is blocked (the latter unless the project allows scanner errors).
+    async fn run_security_gate(&self, ctx: &ExecutionContext, column_id: Uuid) -> bool {
+        let pool = &self.db().pool;
+        let scanners = match KanbanColumn::find_by_id(pool, column_id).await {
+            Ok(column) => column.map(|column| column.scanners()).unwrap_or_default(),
+            Err(e) => {
+                tracing::error!("Failed to load column for security gate: {}", e);
+                return true;
+            }
+        };
+        if scanners.is_empty() {
+            return false;
+        }
+        let Some(container_ref) = ctx.workspace.container_ref.as_ref() else {
+            tracing::error!("Security gate has no workspace to scan for task {}", ctx.task.id);
+            return true;
+        };
+        let allow_scanner_errors =
+            match ProjectSecuritySettings::for_project(pool, ctx.project.id).await {
+                Ok(settings) => settings.allow_scanner_errors,
+                Err(e) => {
+                    tracing::error!("Failed to load security settings of project: {}", e);
+                    false
+                }
+            };
+
+        let workspace_root = PathBuf::from(container_ref);
+        let repo_dirs: Vec<PathBuf> = ctx
+            .repos
+            .iter()
+            .map(|repo| workspace_root.join(&repo.name))
+            .filter(|dir| dir.exists())
+            .collect();
+
+        tracing::info!(
+            target: "vibe_kanban::transition",
+            "  ├─ 🔒 Running security scanners [{}] for task {}",
+            scanners.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", "),
+            ctx.task.id
+        );
+
+        let findings = security_scan::run_scanners(
+            &workspace_root,
+            &repo_dirs,
+            &scanners,
+            allow_scanner_errors,
+        )
+        .await;
+        let exec_id = ctx.execution_process.id;
+        if let Err(e) = SecurityFinding::delete_by_execution_process_id(pool, exec_id).await {
+            tracing::error!("Failed to clear previous security findings: {}", e);
+        }
+        for finding in &findings {
+            if let Err(e) = SecurityFinding::create(pool, exec_id, finding).await {
+                tracing::error!("Failed to store security finding: {}", e);
+            }
+        }
+
+        let blocking = findings.iter().filter(|f| f.severity.is_blocking()).count();
+        if blocking > 0 {
+            tracing::warn!(
+                target: "vibe_kanban::transition",
+                "  ├─ ⛔ Security gate failed: {} blocking of {} findings",
+                blocking,
+                findings.len()
+            );
+        } else {
+            tracing::info!(
+                target: "vibe_kanban::transition",
+                "  ├─ ✅ Security gate passed ({} non-blocking findings)",
+                findings.len()
+            );
+        }
+        blocking > 0
     }
 
     /// Try to auto-transition the task to the next column based on state transitions.
@@ -900,6 +1238,16 @@ pub trait ContainerService {
             return false;
         };
 
+        // A task handed back to a human is only moved by hand
+        if task.is_detached() {
+            tracing::info!(
+                target: "vibe_kanban::transition",
+                "⏸️ Task {} was handed back to a human, skipping auto-transition",
+                task.id
+            );
+            return false;
+        }
+
         // Get current column to find its board and position
         let current_column = match KanbanColumn::find_by_id(pool, current_column_id).await {
             Ok(Some(col)) => col,
@@ -913,6 +1261,43 @@ pub trait ContainerService {
             }
         };
 
+        // A person has to work through the column's checklist before the task moves on
+        match column_checklist::pending_items(pool, task.id, current_column_id).await {
+            Ok(pending) if !pending.is_empty() => {
+                tracing::info!(
+                    target: "vibe_kanban::transition",
+                    "⏸️ Task {} stays in '{}' until its checklist is complete ({} open)",
+                    task.id,
+                    current_column.name,
+                    pending.len()
+                );
+                return false;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!("Failed to load column checklist for task {}: {}", task.id, e);
+                return false;
+            }
+        }
+
+        // A plan-only column holds the task until its plan is approved
+        match plan_review::blocks_leaving_column(pool, task.id, current_column_id).await {
+            Ok(true) => {
+                tracing::info!(
+                    target: "vibe_kanban::transition",
+                    "⏸️ Task {} stays in '{}' until its plan is approved",
+                    task.id,
+                    current_column.name
+                );
+                return false;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                tracing::error!("Failed to load plan review for task {}: {}", task.id, e);
+                return false;
+            }
+        }
+
         // Read decision file from workspace for conditional transitions
         tracing::info!(
             target: "vibe_kanban::transition",
@@ -1005,7 +1390,7 @@ pub trait ContainerService {
                         }
 
                         // Delete decision file to clean up
-                        delete_decision_file(&ctx.workspace).await;
+                        delete_decision_files(&ctx.workspace).await;
 
                         return true;
                     }
@@ -1131,6 +1516,23 @@ pub trait ContainerService {
                         if let Err(e) = TaskEvent::create(pool, &event).await {
                             tracing::error!("Failed to record self-complete transition event: {}", e);
                         }
+                        if let Err(e) = automation::apply_column_label_rules(
+                            pool,
+                            task.id,
+                            task.project_id,
+                            Some(current_column_id),
+                            Some(done_col.id),
+                        )
+                        .await
+                        {
+                            tracing::error!("Failed to apply column label rules to task {}: {}", task.id, e);
+                        }
+                        self.spawn_column_automations(
+                            task.id,
+                            Some(current_column_id),
+                            done_col.id,
+                            None,
+                        );
 
                         tracing::info!(
                             target: "vibe_kanban::transition",
@@ -1194,6 +1596,9 @@ pub trait ContainerService {
             );
         }
 
+        // Security gate: blocking findings keep the task off the success path
+        let security_blocked = self.run_security_gate(ctx, current_column_id).await;
+
         // Try state transitions first (with hierarchical resolution)
         tracing::info!(
             target: "vibe_kanban::transition",
@@ -1222,8 +1627,28 @@ pub trait ContainerService {
             transitions.len()
         );
 
+        // Conditions can also wait on the task's sub-tasks (`subtasks.all_done`)
+        let routing_decision = match Task::subtask_progress(pool, task.id).await {
+            Ok(progress) => with_subtask_progress(decision.clone(), &progress),
+            Err(e) => {
+                tracing::error!("Failed to load sub-task progress for task {}: {}", task.id, e);
+                decision.clone()
+            }
+        };
+        // ...and on the artifacts the execution left (`artifacts.count`)
+        let routing_decision =
+            match ExecutionArtifact::find_by_execution_process_id(pool, ctx.execution_process.id)
+                .await
+            {
+                Ok(artifacts) => with_artifacts(routing_decision, &artifacts),
+                Err(e) => {
+                    tracing::error!("Failed to load artifacts for task {}: {}", task.id, e);
+                    routing_decision
+                }
+            };
+
         // Find target column - either from explicit transition or by position fallback
-        let target_column = if !transitions.is_empty() {
+        let (target_column, transition_path, retry_policy) = if !transitions.is_empty() {
             // Count failures (times we previously took the else path from this column)
             // This is used for escalation logic
             let failure_count = TaskEvent::count_else_transitions(
@@ -1252,9 +1677,34 @@ pub trait ContainerService {
             // Evaluate each transition to find one that can route the task
             let mut target_column_id: Option<Uuid> = None;
             let mut transition_path = "unknown";
+            let mut else_transition_id: Option<Uuid> = None;
+            let mut awaiting_confirmation: Option<&StateTransition> = None;
+            // Why the success path of the transition taken was blocked, if it was
+            let mut missing_artifact_message: Option<String> = None;
 
             for transition in &transitions {
-                match evaluate_transition(transition, &decision, failure_count) {
+                // A stage that didn't write the required artifact (e.g. an iplan) can't
+                // take the success path
+                let missing_artifact = self
+                    .missing_required_artifact(transition, task.id, current_column_id)
+                    .await
+                    .map(|artifact_type| {
+                        format!(
+                            "Transition '{}' requires a {} artifact, but none was written in '{}'",
+                            transition.name.as_deref().unwrap_or("unnamed"),
+                            artifact_type.as_str(),
+                            current_column.name
+                        )
+                    });
+                if let Some(message) = &missing_artifact {
+                    tracing::info!(target: "vibe_kanban::transition", "  ├─ ⚠️ {}", message);
+                }
+                match evaluate_transition(
+                    transition,
+                    &routing_decision,
+                    failure_count,
+                    security_blocked || missing_artifact.is_some(),
+                ) {
                     TransitionResult::Success(col_id) => {
                         tracing::debug!(
                             "Transition '{}' matched (success) -> column {} for task {}",
@@ -1275,6 +1725,8 @@ pub trait ContainerService {
                         );
                         target_column_id = Some(col_id);
                         transition_path = "else";
+                        else_transition_id = Some(transition.id);
+                        missing_artifact_message = missing_artifact;
                         // Don't break - a later transition might have a matching condition
                         // Actually, we should use the first transition's else path
                         break;
@@ -1288,9 +1740,16 @@ pub trait ContainerService {
                         );
                         target_column_id = Some(col_id);
                         transition_path = "escalation";
+                        missing_artifact_message = missing_artifact;
                         break;
                     }
                     TransitionResult::NoMatch => {
+                        // An unconditional transition only moves the task once confirmed
+                        if transition.requires_confirmation
+                            && transition.condition_value.is_none()
+                        {
+                            awaiting_confirmation.get_or_insert(transition);
+                        }
                         // Try next transition
                         continue;
                     }
@@ -1302,9 +1761,28 @@ pub trait ContainerService {
                     "No matching transition for task {} in column {} (decision: {:?})",
                     task.id, current_column.name, decision
                 );
+                if let Some(transition) = awaiting_confirmation {
+                    self.request_transition_confirmation(ctx, &current_column, transition).await;
+                }
                 return false;
             };
 
+            // Only the transition taken records its missing artifact, not the ones
+            // evaluated before it
+            if let Some(message) = missing_artifact_message
+                && let Err(e) = TaskEvent::create(
+                    pool,
+                    &CreateTaskEvent::decision_validation_failed(
+                        task.id,
+                        ctx.workspace.id,
+                        &message,
+                    ),
+                )
+                .await
+            {
+                tracing::error!("Failed to record missing artifact event: {}", e);
+            }
+
             // Record additional metadata for else transitions (for failure counting)
             if transition_path == "else" {
                 // Record that this was an else path transition
@@ -1317,8 +1795,23 @@ pub trait ContainerService {
                 }
             }
 
+            // Repeated retries may switch to a cheaper executor profile; escalation
+            // and success paths always use the destination agent's own executor
+            let retry_policy = match else_transition_id {
+                Some(transition_id) => {
+                    match TransitionRetryPolicy::find_by_transition_id(pool, transition_id).await {
+                        Ok(policy) => policy.filter(|p| p.applies_to(failure_count + 1)),
+                        Err(e) => {
+                            tracing::error!("Failed to fetch retry policy: {}", e);
+                            None
+                        }
+                    }
+                }
+                None => None,
+            };
+
             match KanbanColumn::find_by_id(pool, col_id).await {
-                Ok(Some(col)) => col,
+                Ok(Some(col)) => (col, transition_path, retry_policy),
                 Ok(None) => {
                     tracing::error!("Target column {} not found", col_id);
                     return false;
@@ -1328,6 +1821,13 @@ pub trait ContainerService {
                     return false;
                 }
             }
+        } else if security_blocked {
+            tracing::info!(
+                target: "vibe_kanban::transition",
+                "  └─ Security gate blocked; no else path to take, task stays in '{}'",
+                current_column.name
+            );
+            return false;
         } else {
             // Fallback: use column position order (next column by position)
             let columns = match KanbanColumn::find_by_board(pool, current_column.board_id).await {
@@ -1351,7 +1851,7 @@ pub trait ContainerService {
                 return false;
             };
 
-            col.clone()
+            (col.clone(), "position", None)
         };
 
         // Update task's column and status
@@ -1419,8 +1919,8 @@ pub trait ContainerService {
             self.start_next_unblocked_group_tasks(task.id).await;
         }
 
-        // Record column transition event
-        let event = CreateTaskEvent::column_transition(
+        // Record column transition event (path is used for agent performance stats)
+        let mut event = CreateTaskEvent::column_transition(
             task.id,
             Some(current_column_id),
             target_column.id,
@@ -1428,9 +1928,50 @@ pub trait ContainerService {
             ActorType::System,
             None,
         );
+        event.metadata = Some(serde_json::json!({ "transition_path": transition_path }));
         if let Err(e) = TaskEvent::create(pool, &event).await {
             tracing::error!("Failed to record auto-transition event: {}", e);
         }
+        if transition_path == "escalation" {
+            let (subject, body) = email_notifications::escalation_email(
+                ctx,
+                &current_column.name,
+                &target_column.name,
+            );
+            let notifications = self.notification_service().clone();
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                notifications
+                    .email_immediate(&pool, ImmediateEmailKind::Escalation, &subject, &body)
+                    .await;
+            });
+            notification_channels::spawn_dispatch(
+                pool.clone(),
+                self.share_publisher().cloned(),
+                ctx.project.clone(),
+                ChannelNotification::for_task(
+                    NotificationEvent::TaskEscalated,
+                    &ctx.project,
+                    &ctx.task,
+                    format!(
+                        "Escalated from '{}' to '{}'",
+                        current_column.name, target_column.name
+                    ),
+                ),
+            );
+        }
+        if let Err(e) = automation::apply_column_label_rules(
+            pool,
+            task.id,
+            task.project_id,
+            Some(current_column_id),
+            Some(target_column.id),
+        )
+        .await
+        {
+            tracing::error!("Failed to apply column label rules to task {}: {}", task.id, e);
+        }
+        self.spawn_column_automations(task.id, Some(current_column_id), target_column.id, None);
 
         tracing::info!(
             target: "vibe_kanban::transition",
@@ -1440,8 +1981,20 @@ pub trait ContainerService {
             target_column.name
         );
 
+        // Record reviewer feedback for the next column's agent before the decision is cleared
+        if let Err(e) = reviewer_feedback::record_from_decision(
+            pool,
+            ctx,
+            current_column_id,
+            decision.as_ref(),
+        )
+        .await
+        {
+            tracing::error!("Failed to record reviewer feedback for task {}: {}", task.id, e);
+        }
+
         // Delete the decision file so the next column starts clean
-        delete_decision_file(&ctx.workspace).await;
+        delete_decision_files(&ctx.workspace).await;
 
         // If target column has an agent, start execution
         if let Some(agent_id) = target_column.agent_id {
@@ -1460,7 +2013,10 @@ pub trait ContainerService {
                         agent.name,
                         agent.role
                     );
-                    if let Err(e) = self.initiate_column_handoff(&task, &agent, &target_column).await {
+                    if let Err(e) = self
+                        .initiate_column_handoff(&task, &agent, &target_column, retry_policy.as_ref())
+                        .await
+                    {
                         tracing::error!(
                             target: "vibe_kanban::agent",
                             "  └─ ❌ Failed to start agent: {}",
@@ -1496,17 +2052,272 @@ pub trait ContainerService {
         true
     }
 
-    /// Hand off a task to the next column's agent (used by auto-transition)
+    /// Ask the project's channels to confirm a transition the task waits on; Slack and
+    /// Matrix channels can answer with a reaction
+    async fn request_transition_confirmation(
+        &self,
+        ctx: &ExecutionContext,
+        from_column: &KanbanColumn,
+        transition: &StateTransition,
+    ) {
+        let pool = &self.db().pool;
+        let to_column_name = KanbanColumn::find_by_id(pool, transition.to_column_id)
+            .await
+            .ok()
+            .flatten()
+            .map(|c| c.name)
+            .unwrap_or_default();
+        tracing::info!(
+            target: "vibe_kanban::transition",
+            "  └─ ⏸️ Task {} waits for confirmation to move from '{}' to '{}'",
+            ctx.task.id,
+            from_column.name,
+            to_column_name
+        );
+        notification_channels::spawn_dispatch(
+            pool.clone(),
+            self.share_publisher().cloned(),
+            ctx.project.clone(),
+            ChannelNotification::for_task(
+                NotificationEvent::ApprovalRequested,
+                &ctx.project,
+                &ctx.task,
+                format!("Confirm moving from '{}' to '{}'", from_column.name, to_column_name),
+            )
+            .requesting_approval(ChatApprovalSubject::Transition {
+                transition_id: transition.id,
+                execution_process_id: ctx.execution_process.id,
+            }),
+        );
+    }
+
+    /// A plan approved while its task waits in the plan-only column lets the task
+    /// move on, as if its run had just finished
+    async fn resume_after_plan_approval(&self, review: &PlanReview) -> Result<(), ContainerError> {
+        let pool = &self.db().pool;
+        let Some(column_id) = review.column_id else {
+            return Ok(());
+        };
+        let Some(task) = Task::find_by_id(pool, review.task_id).await? else {
+            return Ok(());
+        };
+        if task.column_id != Some(column_id) {
+            return Ok(());
+        }
+        let Some(process) = ExecutionProcess::find_latest_by_session_and_run_reason(
+            pool,
+            review.session_id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+
+        let ctx = ExecutionProcess::load_context(pool, process.id).await?;
+        self.try_auto_transition(&ctx).await;
+        Ok(())
+    }
+
+    /// Move a task along a transition confirmed from chat, and start the destination
+    /// column's agent
+    async fn confirm_transition(
+        &self,
+        task_id: Uuid,
+        transition_id: Uuid,
+        user: &User,
+    ) -> Result<(), ContainerError> {
+        let pool = &self.db().pool;
+        let transition = StateTransition::find_by_id(pool, transition_id)
+            .await?
+            .ok_or_else(|| anyhow!("Transition {} not found", transition_id))?;
+        let task = Task::find_by_id(pool, task_id)
+            .await?
+            .ok_or_else(|| anyhow!("Task {} not found", task_id))?;
+        // Handed back to a human since the request was posted; only moved by hand now
+        if task.is_detached() {
+            tracing::info!(
+                target: "vibe_kanban::transition",
+                "⏸️ Task {} was handed back to a human, ignoring confirmation by {}",
+                task.id,
+                user.label()
+            );
+            return Ok(());
+        }
+        // Moved by hand since the request was posted
+        if task.column_id != Some(transition.from_column_id) {
+            return Ok(());
+        }
+        let target_column = KanbanColumn::find_by_id(pool, transition.to_column_id)
+            .await?
+            .ok_or_else(|| anyhow!("Column {} not found", transition.to_column_id))?;
+
+        Task::update_column_id(pool, task.id, Some(target_column.id)).await?;
+        Task::update_status(pool, task.id, target_column.status.clone()).await?;
+        if target_column.is_terminal {
+            Task::update_task_state(pool, task.id, TaskState::Queued).await?;
+            if target_column.status == TaskStatus::Done {
+                TaskDependency::satisfy_by_prerequisite(pool, task.id).await?;
+                self.start_next_unblocked_group_tasks(task.id).await;
+            }
+        }
+
+        let mut event = CreateTaskEvent::column_transition(
+            task.id,
+            Some(transition.from_column_id),
+            target_column.id,
+            EventTriggerType::Manual,
+            ActorType::User,
+            Some(user.id.to_string()),
+        );
+        event.metadata = Some(serde_json::json!({ "transition_path": "success" }));
+        if let Err(e) = TaskEvent::create(pool, &event).await {
+            tracing::error!("Failed to record confirmed transition event: {}", e);
+        }
+        if let Err(e) = automation::apply_column_label_rules(
+            pool,
+            task.id,
+            task.project_id,
+            Some(transition.from_column_id),
+            Some(target_column.id),
+        )
+        .await
+        {
+            tracing::error!("Failed to apply column label rules to task {}: {}", task.id, e);
+        }
+        self.spawn_column_automations(
+            task.id,
+            Some(transition.from_column_id),
+            target_column.id,
+            transition.name.clone(),
+        );
+        tracing::info!(
+            target: "vibe_kanban::transition",
+            "✅ {} confirmed moving task {} to '{}'",
+            user.label(),
+            task.id,
+            target_column.name
+        );
+
+        if let Some(agent_id) = target_column.agent_id
+            && let Some(agent) = Agent::find_by_id(pool, agent_id).await?
+        {
+            self.initiate_column_handoff(&task, &agent, &target_column, None).await?;
+        }
+        Ok(())
+    }
+
+    /// Act on an approval request answered by reacting in a chat channel
+    async fn apply_chat_decision(&self, decision: &ChatDecision) -> Result<(), ContainerError> {
+        let pool = &self.db().pool;
+        let approved = decision.verdict == Verdict::Approve;
+        match decision.subject {
+            ChatApprovalSubject::PlanReview(review_id) => {
+                let status = if approved {
+                    PlanReviewStatus::Approved
+                } else {
+                    PlanReviewStatus::Rejected
+                };
+                let review = PlanReview::review(
+                    pool,
+                    review_id,
+                    status,
+                    &ReviewPlan {
+                        reviewer: Some(decision.user.label().to_string()),
+                        comment: Some(format!("Reacted in {}", decision.channel_name)),
+                    },
+                )
+                .await?;
+                let event = CreateTaskEvent::plan_reviewed(
+                    review.task_id,
+                    review.column_id,
+                    review.id,
+                    approved,
+                    Some(decision.user.id.to_string()),
+                );
+                if let Err(e) = TaskEvent::create(pool, &event).await {
+                    tracing::error!("Failed to record plan review event: {}", e);
+                }
+                if approved {
+                    self.resume_after_plan_approval(&review).await?;
+                }
+            }
+            ChatApprovalSubject::Transition { transition_id, .. } if approved => {
+                self.confirm_transition(decision.task_id, transition_id, &decision.user).await?;
+            }
+            // Declined transitions have no else path (it would have been taken), so
+            // the task stays where it is
+            ChatApprovalSubject::Transition { .. } => tracing::info!(
+                "{} declined moving task {} (reacted in {})",
+                decision.user.label(),
+                decision.task_id,
+                decision.channel_name
+            ),
+            ChatApprovalSubject::ToolApproval {
+                approval_id,
+                execution_process_id,
+            } => {
+                let status = if approved {
+                    ApprovalStatus::Approved
+                } else {
+                    ApprovalStatus::Denied {
+                        reason: Some(format!(
+                            "Rejected by {} in {}",
+                            decision.user.label(),
+                            decision.channel_name
+                        )),
+                    }
+                };
+                let response = ApprovalResponse {
+                    execution_process_id,
+                    status,
+                };
+                match self
+                    .approvals()
+                    .respond(pool, &approval_id.to_string(), response)
+                    .await
+                {
+                    Ok(_) => tracing::info!(
+                        "{} answered tool approval {} (reacted in {})",
+                        decision.user.label(),
+                        approval_id,
+                        decision.channel_name
+                    ),
+                    // Answered in the UI or timed out since the reactions were read
+                    Err(ApprovalError::AlreadyCompleted | ApprovalError::NotFound) => {}
+                    Err(e) => {
+                        return Err(ContainerError::Other(anyhow!(
+                            "Failed to answer tool approval {approval_id}: {e}"
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Hand off a task to the next column's agent (used by auto-transition).
+    /// `retry_policy` overrides the agent's executor for downgraded else-path retries.
     async fn initiate_column_handoff(
         &self,
         task: &Task,
         agent: &Agent,
         column: &KanbanColumn,
+        retry_policy: Option<&TransitionRetryPolicy>,
     ) -> Result<(), ContainerError> {
         let pool = &self.db().pool;
         let board_id = column.board_id;
         let column_name = &column.name;
 
+        if task.is_detached() {
+            tracing::info!(
+                target: "vibe_kanban::agent",
+                "  └─ Task {} was handed back to a human, not starting agent",
+                task.id
+            );
+            return Ok(());
+        }
+
         tracing::info!(
             target: "vibe_kanban::agent",
             "  ├─ Building agent context for task {} in column '{}'",
@@ -1538,13 +2349,36 @@ pub trait ContainerService {
 
             let base_agent = BaseCodingAgent::from_str(&agent.executor)
                 .map_err(|e| anyhow!("Failed to parse executor '{}': {}", agent.executor, e))?;
-            let executor_profile_id = ExecutorProfileId::new(base_agent);
+            let executor_profile_id = match retry_policy {
+                Some(policy) => {
+                    let executor = match policy.executor.as_deref() {
+                        Some(executor) => BaseCodingAgent::from_str(executor).map_err(|e| {
+                            anyhow!("Failed to parse retry executor '{}': {}", executor, e)
+                        })?,
+                        None => base_agent,
+                    };
+                    ExecutorProfileId {
+                        executor,
+                        variant: policy.variant.clone(),
+                    }
+                }
+                None => column.executor_profile(base_agent),
+            };
 
-            tracing::info!(
-                target: "vibe_kanban::agent",
-                "  │  ├─ Executor: {}",
-                agent.executor
-            );
+            if retry_policy.is_some() {
+                tracing::info!(
+                    target: "vibe_kanban::agent",
+                    "  │  ├─ Executor: {} (downgraded for retry, agent default {})",
+                    executor_profile_id,
+                    agent.executor
+                );
+            } else {
+                tracing::info!(
+                    target: "vibe_kanban::agent",
+                    "  │  ├─ Executor: {}",
+                    executor_profile_id
+                );
+            }
 
             // Read existing decision file for any feedback from prior rejection
             let existing_decision = read_decision_file(&workspace).await;
@@ -1557,6 +2391,10 @@ pub trait ContainerService {
 
             // Build decision instructions if this column has a question to answer
             // Uses hierarchical resolution: task -> project -> board
+            let language = AgentPromptSettings::for_agent(pool, agent.id)
+                .await
+                .map(|settings| settings.prompt_language())
+                .unwrap_or_default();
             let decision_instructions = build_decision_instructions(
                 pool,
                 column,
@@ -1564,6 +2402,7 @@ pub trait ContainerService {
                 task.project_id,
                 Some(board_id),
                 &existing_decision,
+                language,
             ).await;
 
             if decision_instructions.is_some() {
@@ -1573,13 +2412,7 @@ pub trait ContainerService {
                 );
             }
 
-            // Combine agent's start_command (with tags expanded) with decision instructions
-            let start_command = match (&expanded_start_command, &decision_instructions) {
-                (Some(cmd), Some(instructions)) => Some(format!("{}{}", cmd, instructions)),
-                (Some(cmd), None) => Some(cmd.clone()),
-                (None, Some(instructions)) => Some(instructions.clone()),
-                (None, None) => None,
-            };
+            let start_command = expanded_start_command.clone();
 
             if start_command.is_some() {
                 tracing::info!(
@@ -1613,6 +2446,7 @@ pub trait ContainerService {
                 pool,
                 task.project_id,
                 Some(task.id),
+                task.column_id,
                 &[], // Path-scoped context requires knowing which files the agent will touch
             ).await {
                 Ok(ctx) if !ctx.is_empty() => {
@@ -1686,10 +2520,13 @@ pub trait ContainerService {
                 system_prompt: Some(effective_system_prompt),
                 workflow_history,
                 start_command,
+                decision_instructions,
                 deliverable: expanded_deliverable.clone(),
                 name: agent.name.clone(),
                 color: agent.color.clone(),
                 column_name: column_name.to_string(),
+                agent_id: agent.id,
+                column_id: Some(column.id),
                 project_context,
                 task_id_override: None,
             };
@@ -1860,7 +2697,7 @@ pub trait ContainerService {
                 next_task_id, next_task.title, start_column.name
             );
 
-            if let Err(e) = self.initiate_column_handoff(&next_task, &agent, &start_column).await {
+            if let Err(e) = self.initiate_column_handoff(&next_task, &agent, &start_column, None).await {
                 tracing::error!(
                     "Failed to start next group task {} in group {}: {}",
                     next_task_id, group_id, e
@@ -1878,11 +2715,12 @@ pub trait ContainerService {
                 process.id,
                 process.session_id
             );
-            // Update the execution process status first
+            // The process died with the server rather than failing on its own, so it's
+            // recorded as interrupted and coding agent runs stay resumable
             if let Err(e) = ExecutionProcess::update_completion(
                 &self.db().pool,
                 process.id,
-                ExecutionProcessStatus::Failed,
+                ExecutionProcessStatus::Interrupted,
                 None, // No exit code for orphaned processes
             )
             .await
@@ -1919,12 +2757,15 @@ pub trait ContainerService {
                     }
                 }
             }
-            // Process marked as failed
-            tracing::info!("Marked orphaned execution process {} as failed", process.id);
+            tracing::info!(
+                "Marked orphaned execution process {} as interrupted",
+                process.id
+            );
             // Update task status to InReview for coding agent and setup script failures
             if matches!(
                 process.run_reason,
                 ExecutionProcessRunReason::CodingAgent
+                    | ExecutionProcessRunReason::ManualRun
                     | ExecutionProcessRunReason::SetupScript
                     | ExecutionProcessRunReason::CleanupScript
             ) && let Ok(Some(session)) =
@@ -1935,14 +2776,8 @@ pub trait ContainerService {
             {
                 match Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await {
                     Ok(_) => {
-                        if let Some(publisher) = self.share_publisher()
-                            && let Err(err) = publisher.update_shared_task_by_id(task.id).await
-                        {
-                            tracing::warn!(
-                                ?err,
-                                "Failed to propagate shared task update for {}",
-                                task.id
-                            );
+                        if let Some(publisher) = self.share_publisher() {
+                            publisher.propagate_task_update(task.id).await;
                         }
                     }
                     Err(e) => {
@@ -1954,6 +2789,14 @@ pub trait ContainerService {
                 }
             }
         }
+        match ExecutionProcess::find_resumable_interrupted(&self.db().pool).await {
+            Ok(sessions) if !sessions.is_empty() => tracing::info!(
+                "{} interrupted coding agent sessions can be resumed",
+                sessions.len()
+            ),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Failed to list resumable interrupted sessions: {}", e),
+        }
         Ok(())
     }
 
@@ -1962,9 +2805,13 @@ pub trait ContainerService {
     /// - If a process has after_head_commit and missing before_head_commit,
     ///   then set before_head_commit to the previous process's after_head_commit.
     /// - If there is no previous process, set before_head_commit to the base branch commit.
-    async fn backfill_before_head_commits(&self) -> Result<(), ContainerError> {
+    async fn backfill_before_head_commits(
+        &self,
+        progress: &mut DataMigrationProgress,
+    ) -> Result<(), ContainerError> {
         let pool = &self.db().pool;
         let rows = ExecutionProcess::list_missing_before_context(pool).await?;
+        progress.set_total(rows.len()).await?;
         for row in rows {
             // Skip if no after commit at all (shouldn't happen due to WHERE)
             // Prefer previous process after-commit if present
@@ -2004,6 +2851,7 @@ pub trait ContainerService {
                     e
                 );
             }
+            progress.advance().await?;
         }
 
         Ok(())
@@ -2011,9 +2859,13 @@ pub trait ContainerService {
 
     /// Backfill repo names that were migrated with a sentinel placeholder.
     /// Also backfills dev_script_working_dir and agent_working_dir for single-repo projects.
-    async fn backfill_repo_names(&self) -> Result<(), ContainerError> {
+    async fn backfill_repo_names(
+        &self,
+        progress: &mut DataMigrationProgress,
+    ) -> Result<(), ContainerError> {
         let pool = &self.db().pool;
         let repos = Repo::list_needing_name_fix(pool).await?;
+        progress.set_total(repos.len()).await?;
 
         if repos.is_empty() {
             return Ok(());
@@ -2079,38 +2931,34 @@ pub trait ContainerService {
                     }
                 }
             }
+            progress.advance().await?;
         }
 
         Ok(())
     }
 
     fn cleanup_actions_for_repos(&self, repos: &[ProjectRepoWithName]) -> Option<ExecutorAction> {
-        let repos_with_cleanup: Vec<_> = repos
-            .iter()
-            .filter(|r| r.cleanup_script.is_some())
-            .collect();
-
-        if repos_with_cleanup.is_empty() {
-            return None;
-        }
+        let mut scripts = repos.iter().filter_map(|r| {
+            r.platform_cleanup_script()
+                .map(|(script, language)| (r, script, language))
+        });
 
-        let mut iter = repos_with_cleanup.iter();
-        let first = iter.next()?;
+        let (first, script, language) = scripts.next()?;
         let mut root_action = ExecutorAction::new(
             ExecutorActionType::ScriptRequest(ScriptRequest {
-                script: first.cleanup_script.clone().unwrap(),
-                language: ScriptRequestLanguage::Bash,
+                script,
+                language,
                 context: ScriptContext::CleanupScript,
                 working_dir: Some(first.repo_name.clone()),
             }),
             None,
         );
 
-        for repo in iter {
+        for (repo, script, language) in scripts {
             root_action = root_action.append_action(ExecutorAction::new(
                 ExecutorActionType::ScriptRequest(ScriptRequest {
-                    script: repo.cleanup_script.clone().unwrap(),
-                    language: ScriptRequestLanguage::Bash,
+                    script,
+                    language,
                     context: ScriptContext::CleanupScript,
                     working_dir: Some(repo.repo_name.clone()),
                 }),
@@ -2122,29 +2970,27 @@ pub trait ContainerService {
     }
 
     fn setup_actions_for_repos(&self, repos: &[ProjectRepoWithName]) -> Option<ExecutorAction> {
-        let repos_with_setup: Vec<_> = repos.iter().filter(|r| r.setup_script.is_some()).collect();
+        let mut scripts = repos.iter().filter_map(|r| {
+            r.platform_setup_script()
+                .map(|(script, language)| (r, script, language))
+        });
 
-        if repos_with_setup.is_empty() {
-            return None;
-        }
-
-        let mut iter = repos_with_setup.iter();
-        let first = iter.next()?;
+        let (first, script, language) = scripts.next()?;
         let mut root_action = ExecutorAction::new(
             ExecutorActionType::ScriptRequest(ScriptRequest {
-                script: first.setup_script.clone().unwrap(),
-                language: ScriptRequestLanguage::Bash,
+                script,
+                language,
                 context: ScriptContext::SetupScript,
                 working_dir: Some(first.repo_name.clone()),
             }),
             None,
         );
 
-        for repo in iter {
+        for (repo, script, language) in scripts {
             root_action = root_action.append_action(ExecutorAction::new(
                 ExecutorActionType::ScriptRequest(ScriptRequest {
-                    script: repo.setup_script.clone().unwrap(),
-                    language: ScriptRequestLanguage::Bash,
+                    script,
+                    language,
                     context: ScriptContext::SetupScript,
                     working_dir: Some(repo.repo_name.clone()),
                 }),
@@ -2156,11 +3002,11 @@ pub trait ContainerService {
     }
 
     fn setup_action_for_repo(repo: &ProjectRepoWithName) -> Option<ExecutorAction> {
-        repo.setup_script.as_ref().map(|script| {
+        repo.platform_setup_script().map(|(script, language)| {
             ExecutorAction::new(
                 ExecutorActionType::ScriptRequest(ScriptRequest {
-                    script: script.clone(),
-                    language: ScriptRequestLanguage::Bash,
+                    script,
+                    language,
                     context: ScriptContext::SetupScript,
                     working_dir: Some(repo.repo_name.clone()),
                 }),
@@ -2175,11 +3021,11 @@ pub trait ContainerService {
     ) -> ExecutorAction {
         let mut chained = next_action;
         for repo in repos.iter().rev() {
-            if let Some(script) = &repo.setup_script {
+            if let Some((script, language)) = repo.platform_setup_script() {
                 chained = ExecutorAction::new(
                     ExecutorActionType::ScriptRequest(ScriptRequest {
-                        script: script.clone(),
-                        language: ScriptRequestLanguage::Bash,
+                        script,
+                        language,
                         context: ScriptContext::SetupScript,
                         working_dir: Some(repo.repo_name.clone()),
                     }),
@@ -2230,6 +3076,124 @@ pub trait ContainerService {
         workspace: &Workspace,
     ) -> Result<ContainerRef, ContainerError>;
 
+    /// Run the automation rules a column change triggers in the background (see
+    /// `automation::run_column_automations`). Every path that moves a task between
+    /// columns calls this, except automation moves themselves, so rules can't loop.
+    fn spawn_column_automations(
+        &self,
+        task_id: Uuid,
+        from_column_id: Option<Uuid>,
+        to_column_id: Uuid,
+        transition_name: Option<String>,
+    );
+
+    /// Code host for a repo, from its forge settings and `origin` remote
+    async fn code_host(&self, repo: &Repo) -> Result<Box<dyn CodeHost>, ContainerError> {
+        let settings = RepoForgeSettings::for_repo(&self.db().pool, repo.id).await?;
+        let remote_url = self.git().get_remote_url(&repo.path)?;
+        Ok(forge::for_repo(&settings, &remote_url)?)
+    }
+
+    /// Push the workspace branch of one repo and open a pull request for it into
+    /// `request.base_branch` on the repo's code host, recording it on the workspace.
+    /// Problems the user has to fix (missing CLI, unknown target branch) come back as the
+    /// inner error.
+    async fn open_pull_request(
+        &self,
+        workspace: &Workspace,
+        repo: &Repo,
+        mut request: CreatePrRequest,
+    ) -> Result<Result<(PullRequestInfo, ForgeKind), CreatePrError>, ContainerError> {
+        let container_ref = self.ensure_container_exists(workspace).await?;
+        let worktree_path = PathBuf::from(&container_ref).join(&repo.name);
+        let target_branch = request.base_branch.clone();
+
+        match self.git().check_remote_branch_exists(&repo.path, &target_branch) {
+            Ok(false) => {
+                return Ok(Err(CreatePrError::TargetBranchNotFound {
+                    branch: target_branch,
+                }));
+            }
+            Err(GitServiceError::GitCLI(GitCliError::AuthFailed(_))) => {
+                return Ok(Err(CreatePrError::GitCliNotLoggedIn));
+            }
+            Err(GitServiceError::GitCLI(GitCliError::NotAvailable)) => {
+                return Ok(Err(CreatePrError::GitCliNotInstalled));
+            }
+            Err(e) => return Err(e.into()),
+            Ok(true) => {}
+        }
+
+        // Push the branch to the remote first
+        if let Err(e) = self
+            .git()
+            .push_to_github(&worktree_path, &workspace.branch, false)
+        {
+            tracing::error!("Failed to push branch to remote: {}", e);
+            return match e {
+                GitServiceError::GitCLI(GitCliError::AuthFailed(_)) => {
+                    Ok(Err(CreatePrError::GitCliNotLoggedIn))
+                }
+                GitServiceError::GitCLI(GitCliError::NotAvailable) => {
+                    Ok(Err(CreatePrError::GitCliNotInstalled))
+                }
+                _ => Err(e.into()),
+            };
+        }
+
+        // Remote branches are formatted as {remote}/{branch} locally, but PR APIs
+        // take just the branch name
+        if matches!(
+            self.git().find_branch_type(&repo.path, &target_branch)?,
+            git2::BranchType::Remote
+        ) {
+            let remote = self
+                .git()
+                .get_remote_name_from_branch_name(&worktree_path, &target_branch)?;
+            if let Some(branch) = target_branch.strip_prefix(&format!("{}/", remote)) {
+                request.base_branch = branch.to_string();
+            }
+        }
+
+        let host = self.code_host(repo).await?;
+        let pr_info = match host.create_pr(&request).await {
+            Ok(pr_info) => pr_info,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to create {} PR for attempt {}: {}",
+                    host.kind().as_str(),
+                    workspace.id,
+                    e
+                );
+                return match &e {
+                    CodeHostError::GitHub(GitHubServiceError::GhCliNotInstalled(_)) => {
+                        Ok(Err(CreatePrError::GithubCliNotInstalled))
+                    }
+                    CodeHostError::GitHub(GitHubServiceError::AuthFailed(_)) => {
+                        Ok(Err(CreatePrError::GithubCliNotLoggedIn))
+                    }
+                    _ => Err(e.into()),
+                };
+            }
+        };
+
+        // Update the workspace with PR information
+        if let Err(e) = Merge::create_pr(
+            &self.db().pool,
+            workspace.id,
+            repo.id,
+            &request.base_branch,
+            pr_info.number,
+            &pr_info.url,
+        )
+        .await
+        {
+            tracing::error!("Failed to update workspace PR status: {}", e);
+        }
+
+        Ok(Ok((pr_info, host.kind())))
+    }
+
     async fn is_container_clean(&self, workspace: &Workspace) -> Result<bool, ContainerError>;
 
     async fn start_execution_inner(
@@ -2245,6 +3209,11 @@ pub trait ContainerService {
         status: ExecutionProcessStatus,
     ) -> Result<(), ContainerError>;
 
+    /// Send a user message to a running execution whose executor accepts input mid-run.
+    /// Returns false if the execution isn't running or doesn't support it.
+    async fn send_message_to_execution(&self, execution_process_id: &Uuid, message: String)
+    -> bool;
+
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError>;
 
     async fn copy_project_files(
@@ -2269,15 +3238,60 @@ pub trait ContainerService {
 
     async fn git_branch_prefix(&self) -> String;
 
-    async fn git_branch_from_workspace(&self, workspace_id: &Uuid, task_title: &str) -> String {
-        let task_title_id = git_branch_id(task_title);
+    /// Branch for a new workspace of `task`, from the project's branch template (see
+    /// `branch_template`). A name already used by a workspace or a project repo's branch
+    /// gets a numeric suffix.
+    async fn git_branch_from_workspace(
+        &self,
+        workspace_id: &Uuid,
+        task: &Task,
+        agent: Option<&str>,
+    ) -> String {
+        let pool = &self.db().pool;
+        let template = match Project::find_by_id(pool, task.project_id).await {
+            Ok(project) => project.and_then(|project| project.branch_template),
+            Err(e) => {
+                tracing::warn!("Failed to load branch settings of {}: {}", task.project_id, e);
+                None
+            }
+        };
+        let user = match task.assignee_id {
+            Some(user_id) => User::find_by_id(pool, user_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|user| user.username.clone().unwrap_or_else(|| user.label().to_string())),
+            None => None,
+        };
         let prefix = self.git_branch_prefix().await;
+        let branch = branch_template::render(
+            template.as_deref().unwrap_or(DEFAULT_BRANCH_TEMPLATE),
+            &BranchValues {
+                prefix: &prefix,
+                workspace_id: *workspace_id,
+                task_id: task.id,
+                task_title: &task.title,
+                agent,
+                user: user.as_deref(),
+                date: chrono::Utc::now().date_naive(),
+            },
+        );
 
-        if prefix.is_empty() {
-            format!("{}-{}", short_uuid(workspace_id), task_title_id)
-        } else {
-            format!("{}/{}-{}", prefix, short_uuid(workspace_id), task_title_id)
+        let repos = ProjectRepo::find_repos_for_project(pool, task.project_id)
+            .await
+            .unwrap_or_default();
+        for candidate in branch_template::candidates(&branch) {
+            let in_use = Workspace::branch_in_use(pool, &candidate).await.unwrap_or(false)
+                || repos.iter().any(|repo| {
+                    self.git()
+                        .check_branch_exists(&repo.path, &candidate)
+                        .unwrap_or(false)
+                });
+            if !in_use {
+                return candidate;
+            }
         }
+        format!("{branch}-{}", short_uuid(workspace_id))
     }
 
     async fn stream_raw_logs(
@@ -2300,7 +3314,7 @@ pub trait ContainerService {
         } else {
             // Fallback: load from DB and create direct stream
             let log_records =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
+                match ExecutionProcessLogs::find_by_execution_id(&self.db().read_pool, *id).await {
                     Ok(records) if !records.is_empty() => records,
                     Ok(_) => return None, // No logs exist
                     Err(e) => {
@@ -2335,12 +3349,14 @@ pub trait ContainerService {
         &self,
         id: &Uuid,
     ) -> Option<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>> {
+        let agent = self.process_agent(id).await;
         // First try in-memory store (existing behavior)
         if let Some(store) = self.get_msg_store_by_id(id).await {
             Some(
                 store
                     .history_plus_stream() // BoxStream<Result<LogMsg, io::Error>>
                     .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
+                    .map(move |msg| msg.map(|msg| attribute_log_msg(msg, agent.as_ref())))
                     .chain(futures::stream::once(async {
                         Ok::<_, std::io::Error>(LogMsg::Finished)
                     }))
@@ -2349,7 +3365,7 @@ pub trait ContainerService {
         } else {
             // Fallback: load from DB and normalize
             let log_records =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
+                match ExecutionProcessLogs::find_by_execution_id(&self.db().read_pool, *id).await {
                     Ok(records) if !records.is_empty() => records,
                     Ok(_) => return None, // No logs exist
                     Err(e) => {
@@ -2456,6 +3472,7 @@ pub trait ContainerService {
                 temp_store
                     .history_plus_stream()
                     .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
+                    .map(move |msg| msg.map(|msg| attribute_log_msg(msg, agent.as_ref())))
                     .chain(futures::stream::once(async {
                         Ok::<_, std::io::Error>(LogMsg::Finished)
                     }))
@@ -2464,6 +3481,46 @@ pub trait ContainerService {
         }
     }
 
+    /// Agent the process's log entries are attributed to, if it ran for one
+    async fn process_agent(&self, id: &Uuid) -> Option<AgentIdentity> {
+        let process = ExecutionProcess::find_by_id(&self.db().pool, *id)
+            .await
+            .ok()??;
+        process.executor_action().ok()?.agent.clone()
+    }
+
+    /// Normalized log patches of an execution so far, without waiting for it to finish
+    async fn normalized_log_history(&self, id: &Uuid) -> Option<Vec<LogMsg>> {
+        if let Some(store) = self.get_msg_store_by_id(id).await {
+            let agent = self.process_agent(id).await;
+            return Some(
+                store
+                    .get_history()
+                    .into_iter()
+                    .filter(|msg| matches!(msg, LogMsg::JsonPatch(..)))
+                    .map(|msg| attribute_log_msg(msg, agent.as_ref()))
+                    .collect(),
+            );
+        }
+
+        // Normalizing stored logs has no end marker; stop once it goes quiet
+        let mut stream = self.stream_normalized_logs(id).await?;
+        let mut history = Vec::new();
+        while let Ok(Some(msg)) =
+            tokio::time::timeout(NORMALIZATION_IDLE_TIMEOUT, stream.next()).await
+        {
+            match msg {
+                Ok(msg @ LogMsg::JsonPatch(..)) => history.push(msg),
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::error!("Failed to read normalized logs for {}: {}", id, e);
+                    break;
+                }
+            }
+        }
+        Some(history)
+    }
+
     fn spawn_stream_raw_logs_to_db(&self, execution_id: &Uuid) -> JoinHandle<()> {
         let execution_id = *execution_id;
         let msg_stores = self.msg_stores().clone();
@@ -2580,7 +3637,7 @@ pub trait ContainerService {
 
         let repos_with_setup: Vec<_> = project_repos
             .iter()
-            .filter(|pr| pr.setup_script.is_some())
+            .filter(|pr| pr.platform_setup_script().is_some())
             .collect();
 
         let all_parallel = repos_with_setup.iter().all(|pr| pr.parallel_setup_script);
@@ -2602,7 +3659,10 @@ pub trait ContainerService {
                 agent_project_context: None,
                 agent_workflow_history: None,
                 agent_start_command: None,
+                agent_decision_instructions: None,
                 agent_deliverable: None,
+                prompt_sections: None,
+                prompt_language: None,
             }),
             cleanup_action.map(Box::new),
         );
@@ -2685,6 +3745,9 @@ pub trait ContainerService {
             .await?
             .ok_or(SqlxError::RowNotFound)?;
 
+        // Keep decision files out of the agent's commits
+        prepare_decision_dirs(&workspace).await;
+
         // Create a session for this workspace
         let session = Session::create(
             &self.db().pool,
@@ -2696,42 +3759,174 @@ pub trait ContainerService {
         )
         .await?;
 
-        let prompt = task.to_prompt();
+        if let Err(e) = AgentSession::create(
+            &self.db().pool,
+            session.id,
+            agent_context.agent_id,
+            agent_context.column_id,
+        )
+        .await
+        {
+            tracing::warn!("Failed to record agent session {}: {}", session.id, e);
+        }
 
-        let repos_with_setup: Vec<_> = project_repos
-            .iter()
-            .filter(|pr| pr.setup_script.is_some())
-            .collect();
+        let mut prompt = task.to_prompt();
 
-        let all_parallel = repos_with_setup.iter().all(|pr| pr.parallel_setup_script);
+        let column = match agent_context.column_id {
+            Some(column_id) => KanbanColumn::find_by_id(&self.db().pool, column_id)
+                .await
+                .ok()
+                .flatten(),
+            None => None,
+        };
 
-        let cleanup_action = self.cleanup_actions_for_repos(&project_repos);
+        // Warm-up columns: hand the agent the workspace's layout and key files up front
+        // (built once per workspace) so it doesn't spend its first tool calls exploring
+        if column.as_ref().is_some_and(|c| c.warm_up)
+            && let Some(container_ref) = workspace.container_ref.as_ref()
+        {
+            let workspace_root = PathBuf::from(container_ref);
+            let repos = project_repos
+                .iter()
+                .map(|repo| (repo.repo_name.clone(), workspace_root.join(&repo.repo_name)))
+                .filter(|(_, dir)| dir.exists())
+                .collect();
+            match workspace_orientation::orientation_for(&self.db().pool, workspace.id, repos)
+                .await
+            {
+                Ok(orientation) if !orientation.is_empty() => {
+                    prompt = workspace_orientation::with_orientation(&orientation, &prompt);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Failed to load workspace orientation: {}", e),
+            }
+        }
 
-        let working_dir = workspace
-            .agent_working_dir
+        // Read-only analysis, plan-only and dry-run columns: tell the agent up front;
+        // anything it changes anyway is discarded (or, in dry runs, kept as a patch) when
+        // the execution finishes
+        let read_only = column.as_ref().is_some_and(|c| c.read_only);
+        let plan_only = column.as_ref().is_some_and(|c| c.plan_only);
+        let dry_run = column.as_ref().is_some_and(|c| c.dry_run);
+
+        // What these runs change is thrown away afterwards, which would take uncommitted
+        // work already in the worktree with it; refuse to start over such work
+        if (read_only || plan_only || dry_run)
+            && let Some(container_ref) = workspace.container_ref.as_ref()
+        {
+            let workspace_root = PathBuf::from(container_ref);
+            for repo in &project_repos {
+                let worktree_path = workspace_root.join(&repo.repo_name);
+                if worktree_path.exists()
+                    && self.git().has_discardable_changes(&worktree_path, ".vibe")?
+                {
+                    return Err(ContainerError::Other(anyhow!(
+                        "Repo '{}' has uncommitted changes that would be discarded after \
+                         this column's run; commit or discard them first",
+                        repo.repo_name
+                    )));
+                }
+            }
+        }
+        let prompt_settings =
+            AgentPromptSettings::for_agent(&self.db().pool, agent_context.agent_id)
+                .await
+                .ok();
+        let language = prompt_settings
             .as_ref()
-            .filter(|dir| !dir.is_empty())
-            .cloned();
+            .map(|settings| settings.prompt_language())
+            .unwrap_or_default();
+        let deliverable = if plan_only {
+            Some(plan_review::plan_only_deliverable(
+                agent_context.deliverable,
+                language,
+            ))
+        } else if read_only {
+            let notice = prompt_language::read_only_column_notice(language);
+            Some(match agent_context.deliverable {
+                Some(deliverable) => format!("{}\n\n{}", deliverable, notice),
+                None => notice.to_string(),
+            })
+        } else if dry_run {
+            let notice = prompt_language::dry_run_column_notice(language);
+            Some(match agent_context.deliverable {
+                Some(deliverable) => format!("{}\n\n{}", deliverable, notice),
+                None => notice.to_string(),
+            })
+        } else {
+            agent_context.deliverable
+        };
 
-        // Include agent context in the request
-        let coding_action = ExecutorAction::new(
-            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-                prompt,
+        // Prompt sections in the column's order, else the agent's (default when neither)
+        let prompt_sections = match column.as_ref().and_then(|c| c.prompt_section_order()) {
+            Some(sections) => Some(sections),
+            None => prompt_settings.and_then(|settings| settings.prompt_section_order()),
+        };
+
+        // Mark the session as plan-only before it runs, so its changes are discarded
+        if plan_only {
+            PlanReview::create(
+                &self.db().pool,
+                task.id,
+                session.id,
+                agent_context.column_id,
+            )
+            .await?;
+        }
+
+        let repos_with_setup: Vec<_> = project_repos
+            .iter()
+            .filter(|pr| pr.platform_setup_script().is_some())
+            .collect();
+
+        let all_parallel = repos_with_setup.iter().all(|pr| pr.parallel_setup_script);
+
+        let cleanup_action = self.cleanup_actions_for_repos(&project_repos);
+
+        let working_dir = workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+
+        // Column entry script runs right before the agent, after any setup scripts; the
+        // exit script after it, before the repos' cleanup scripts
+        let (on_enter_script, on_exit_script) = match column {
+            Some(column) => (column.on_enter_script, column.on_exit_script),
+            None => (None, None),
+        };
+        let (coding_action, coding_run_reason) = with_column_scripts(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt,
                 executor_profile_id: executor_profile_id.clone(),
                 working_dir,
                 agent_system_prompt: agent_context.system_prompt,
                 agent_project_context: agent_context.project_context,
                 agent_workflow_history: agent_context.workflow_history,
                 agent_start_command: agent_context.start_command,
-                agent_deliverable: agent_context.deliverable,
+                agent_decision_instructions: agent_context.decision_instructions,
+                agent_deliverable: deliverable,
+                prompt_sections,
+                prompt_language: Some(language),
             }),
-            cleanup_action.map(Box::new),
+            on_enter_script,
+            on_exit_script,
+            cleanup_action,
+            &project_repos,
         );
 
+        // Every process of the agent's stage, scripts included, is attributed to it
+        let agent_identity = Some(AgentIdentity {
+            agent_id: agent_context.agent_id.to_string(),
+            agent_color: agent_context.color.clone(),
+        });
+        let coding_action = coding_action.with_agent(agent_identity.clone());
+
         let execution_process = if all_parallel {
             // All parallel: start each setup independently, then start coding agent
             for repo in &repos_with_setup {
                 if let Some(action) = Self::setup_action_for_repo(repo)
+                    .map(|action| action.with_agent(agent_identity.clone()))
                     && let Err(e) = self
                         .start_execution(
                             &workspace,
@@ -2748,12 +3943,13 @@ pub trait ContainerService {
                 &workspace,
                 &session,
                 &coding_action,
-                &ExecutionProcessRunReason::CodingAgent,
+                &coding_run_reason,
             )
             .await?
         } else {
             // Any sequential: chain ALL setups → coding agent via next_action
-            let main_action = Self::build_sequential_setup_chain(&repos_with_setup, coding_action);
+            let main_action = Self::build_sequential_setup_chain(&repos_with_setup, coding_action)
+                .with_agent(agent_identity);
             self.start_execution(
                 &workspace,
                 &session,
@@ -2773,7 +3969,7 @@ pub trait ContainerService {
             },
             content: format!("Switching to {} for {}", agent_context.name, agent_context.column_name),
             metadata: None,
-            agent_id: None,
+            agent_id: Some(agent_context.agent_id.to_string()),
             agent_color: agent_context.color,
         };
         let patch = ConversationPatch::add_normalized_entry(0, agent_switch_entry);
@@ -2804,24 +4000,52 @@ pub trait ContainerService {
         executor_action: &ExecutorAction,
         run_reason: &ExecutionProcessRunReason,
     ) -> Result<ExecutionProcess, ContainerError> {
-        // Update task status to InProgress when starting an execution
+        let (execution_process, task) = self
+            .prepare_execution(workspace, session, executor_action, run_reason)
+            .await?;
+        self.run_prepared_execution(workspace, &execution_process, executor_action, &task)
+            .await?;
+        Ok(execution_process)
+    }
+
+    /// Record a new execution process (and move its task to in progress) without
+    /// spawning it, so callers can link the process elsewhere before it can finish
+    async fn prepare_execution(
+        &self,
+        workspace: &Workspace,
+        session: &Session,
+        executor_action: &ExecutorAction,
+        run_reason: &ExecutionProcessRunReason,
+    ) -> Result<(ExecutionProcess, Task), ContainerError> {
+        if ShutdownCoordinator::shared().is_shutting_down() {
+            return Err(ContainerError::ShuttingDown);
+        }
+
+        // Update task status to InProgress when starting an execution (not for dev servers
+        // or manual runs, which happen outside the column workflow)
         let task = workspace
             .parent_task(&self.db().pool)
             .await?
             .ok_or(SqlxError::RowNotFound)?;
+
+        // Every coding agent start comes through here, including automated handoffs and
+        // column-triggered follow-ups, so the attempt budget is enforced here
+        if executor_action.base_executor().is_some() {
+            let budget = self.config().read().await.attempt_budget.max_estimated_tokens;
+            if let Some(message) =
+                cost_estimate::budget_exceeded(&self.db().pool, &task, budget).await?
+            {
+                return Err(ContainerError::BudgetExceeded(message));
+            }
+        }
         if task.status != TaskStatus::InProgress
             && run_reason != &ExecutionProcessRunReason::DevServer
+            && run_reason != &ExecutionProcessRunReason::ManualRun
         {
             Task::update_status(&self.db().pool, task.id, TaskStatus::InProgress).await?;
 
-            if let Some(publisher) = self.share_publisher()
-                && let Err(err) = publisher.update_shared_task_by_id(task.id).await
-            {
-                tracing::warn!(
-                    ?err,
-                    "Failed to propagate shared task update for {}",
-                    task.id
-                );
+            if let Some(publisher) = self.share_publisher() {
+                publisher.propagate_task_update(task.id).await;
             }
         }
         // Create new execution process record
@@ -2901,10 +4125,67 @@ pub trait ContainerService {
             .await?;
         }
 
+        Ok((execution_process, task))
+    }
+
+    /// Spawn an execution process recorded by `prepare_execution`
+    async fn run_prepared_execution(
+        &self,
+        workspace: &Workspace,
+        execution_process: &ExecutionProcess,
+        executor_action: &ExecutorAction,
+        task: &Task,
+    ) -> Result<(), ContainerError> {
+        // Coding agents need a concurrency slot; the slot is released when the process
+        // exits (or immediately if it fails to start). One that has to queue for it is
+        // started in the background, so callers aren't held until a slot frees up.
+        if let Some(executor) = executor_action.base_executor()
+            && !self
+                .agent_limiter()
+                .try_acquire(execution_process.id, workspace.id, task.project_id, executor)
+                .await
+        {
+            self.spawn_queued_execution(
+                workspace,
+                execution_process,
+                executor_action,
+                task,
+                executor,
+            );
+            return Ok(());
+        }
+
+        self.launch_execution(workspace, execution_process, executor_action, task)
+            .await
+    }
+
+    /// Start a queued coding agent once it gets a concurrency slot. Stopping the
+    /// execution or deleting its workspace cancels the wait.
+    fn spawn_queued_execution(
+        &self,
+        workspace: &Workspace,
+        execution_process: &ExecutionProcess,
+        executor_action: &ExecutorAction,
+        task: &Task,
+        executor: BaseCodingAgent,
+    );
+
+    /// Spawn an execution process that holds its concurrency slot (if it needs one)
+    async fn launch_execution(
+        &self,
+        workspace: &Workspace,
+        execution_process: &ExecutionProcess,
+        executor_action: &ExecutorAction,
+        task: &Task,
+    ) -> Result<(), ContainerError> {
         if let Err(start_error) = self
-            .start_execution_inner(workspace, &execution_process, executor_action)
+            .start_execution_inner(workspace, execution_process, executor_action)
             .await
         {
+            self.record_executor_outcome(execution_process.id, false)
+                .await;
+            self.agent_limiter().release(execution_process.id).await;
+
             // Mark process as failed
             if let Err(update_error) = ExecutionProcess::update_completion(
                 &self.db().pool,
@@ -2985,8 +4266,406 @@ pub trait ContainerService {
             }
         }
 
-        self.spawn_stream_raw_logs_to_db(&execution_process.id);
-        Ok(execution_process)
+        ShutdownCoordinator::shared()
+            .track_log_writer(self.spawn_stream_raw_logs_to_db(&execution_process.id));
+        Ok(())
+    }
+
+    /// Feed how a coding agent run ended to its executor's circuit breaker, telling
+    /// admins when the executor gets paused or recovers
+    async fn record_executor_outcome(&self, execution_process_id: Uuid, success: bool) {
+        let Some((executor, event)) = self
+            .agent_limiter()
+            .record_outcome(execution_process_id, success)
+            .await
+        else {
+            return;
+        };
+        let (title, message) = event.notification(executor);
+        match event {
+            CircuitEvent::Tripped => tracing::error!("{}: {}", title, message),
+            CircuitEvent::Recovered => tracing::info!("{}: {}", title, message),
+        }
+        self.notification_service().notify(&title, &message).await;
+    }
+
+    /// Continue an interrupted coding agent run with a follow-up in its agent session
+    async fn resume_interrupted_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        if execution_process.status != ExecutionProcessStatus::Interrupted {
+            return Err(ContainerError::Other(anyhow!(
+                "Execution process {} was not interrupted",
+                execution_process.id
+            )));
+        }
+        let pool = &self.db().pool;
+        let agent_session_id =
+            CodingAgentTurn::find_by_execution_process_id(pool, execution_process.id)
+                .await?
+                .and_then(|turn| turn.agent_session_id)
+                .ok_or_else(|| {
+                    ContainerError::Other(anyhow!(
+                        "Execution process {} has no agent session to resume",
+                        execution_process.id
+                    ))
+                })?;
+        let action =
+            shutdown::resume_action(execution_process.executor_action()?, agent_session_id)
+                .ok_or_else(|| {
+                    ContainerError::Other(anyhow!(
+                        "Execution process {} is not a coding agent run",
+                        execution_process.id
+                    ))
+                })?;
+
+        let ctx = ExecutionProcess::load_context(pool, execution_process.id).await?;
+        self.ensure_container_exists(&ctx.workspace).await?;
+        self.start_execution(
+            &ctx.workspace,
+            &ctx.session,
+            &action,
+            &execution_process.run_reason,
+        )
+        .await
+    }
+
+    /// Re-run a coding agent run's prompt with another executor, in a fresh workspace
+    /// on the same repos and target branches. Setup scripts are started alongside the
+    /// agent, as in parallel mode, so the replay itself stays a manual run.
+    async fn replay_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+        executor_profile_id: ExecutorProfileId,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let action = execution_replay::replay_action(
+            execution_process.executor_action()?,
+            executor_profile_id.clone(),
+        )
+        .ok_or_else(|| {
+            ContainerError::Other(anyhow!(
+                "Execution process {} is not an initial coding agent run",
+                execution_process.id
+            ))
+        })?;
+
+        let pool = &self.db().pool;
+        let ctx = ExecutionProcess::load_context(pool, execution_process.id).await?;
+        let executor = executor_profile_id.executor.to_string();
+        let workspace_id = Uuid::new_v4();
+        let branch = self
+            .git_branch_from_workspace(&workspace_id, &ctx.task, Some(&executor))
+            .await;
+        let workspace = Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch,
+                agent_working_dir: ctx.workspace.agent_working_dir.clone(),
+            },
+            workspace_id,
+            ctx.task.id,
+        )
+        .await?;
+        let repos: Vec<_> = WorkspaceRepo::find_by_workspace_id(pool, ctx.workspace.id)
+            .await?
+            .into_iter()
+            .map(|repo| CreateWorkspaceRepo {
+                repo_id: repo.repo_id,
+                target_branch: repo.target_branch,
+            })
+            .collect();
+        WorkspaceRepo::create_many(pool, workspace.id, &repos).await?;
+
+        self.create(&workspace).await?;
+        let workspace = Workspace::find_by_id(pool, workspace.id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let session = Session::create(
+            pool,
+            &CreateSession {
+                executor: Some(executor),
+            },
+            Uuid::new_v4(),
+            workspace.id,
+        )
+        .await?;
+
+        let project_repos = ProjectRepo::find_by_project_id_with_names(pool, ctx.project.id).await?;
+        for repo in &project_repos {
+            if let Some(setup) = Self::setup_action_for_repo(repo)
+                && let Err(e) = self
+                    .start_execution(
+                        &workspace,
+                        &session,
+                        &setup,
+                        &ExecutionProcessRunReason::SetupScript,
+                    )
+                    .await
+            {
+                tracing::warn!(?e, "Failed to start setup script for replay");
+            }
+        }
+
+        self.start_execution(
+            &workspace,
+            &session,
+            &action,
+            &ExecutionProcessRunReason::ManualRun,
+        )
+        .await
+    }
+
+    /// After a workspace's branch was merged, start an agent run that rewrites the
+    /// module memories of the modules the merge touched, if the project opted in. The
+    /// run uses the executor of the workspace's last coding agent run and works on a
+    /// fresh branch of the updated target branches. It is a manual run, so it never
+    /// moves the merged task. Returns `None` when no refresh was needed.
+    async fn refresh_module_memories(
+        &self,
+        workspace: &Workspace,
+        merged: &[MergedRepoChanges],
+    ) -> Result<Option<ExecutionProcess>, ContainerError> {
+        let pool = &self.db().pool;
+        let task = workspace
+            .parent_task(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let settings = ProjectModuleMemorySettings::for_project(pool, task.project_id).await?;
+        if !settings.refresh_after_merge {
+            return Ok(None);
+        }
+        let modules =
+            module_memory_refresh::modules_to_refresh(merged, settings.max_modules as usize);
+        if modules.is_empty() {
+            return Ok(None);
+        }
+        let Some(last_run) = ExecutionProcess::find_latest_by_workspace_and_run_reason(
+            pool,
+            workspace.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?
+        else {
+            return Ok(None);
+        };
+        let executor_profile_id = match last_run.executor_action()?.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                request.executor_profile_id.clone()
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                request.executor_profile_id.clone()
+            }
+            _ => return Ok(None),
+        };
+
+        let mut current_memories = BTreeMap::new();
+        for module in &modules {
+            if let Some(memory) =
+                ContextArtifact::find_module_memory(pool, task.project_id, &module.path).await?
+            {
+                current_memories.insert(module.path.clone(), memory.content);
+            }
+        }
+        let prompt = module_memory_refresh::refresh_prompt(
+            task.project_id,
+            task.id,
+            &task.title,
+            merged,
+            &modules,
+            &current_memories,
+        );
+
+        let executor = executor_profile_id.executor.to_string();
+        let refresh_workspace_id = Uuid::new_v4();
+        let branch = self
+            .git_branch_from_workspace(&refresh_workspace_id, &task, Some("memory"))
+            .await;
+        let refresh_workspace = Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch,
+                agent_working_dir: workspace.agent_working_dir.clone(),
+            },
+            refresh_workspace_id,
+            task.id,
+        )
+        .await?;
+        let repos: Vec<_> = WorkspaceRepo::find_by_workspace_id(pool, workspace.id)
+            .await?
+            .into_iter()
+            .map(|repo| CreateWorkspaceRepo {
+                repo_id: repo.repo_id,
+                target_branch: repo.target_branch,
+            })
+            .collect();
+        WorkspaceRepo::create_many(pool, refresh_workspace.id, &repos).await?;
+
+        self.create(&refresh_workspace).await?;
+        let refresh_workspace = Workspace::find_by_id(pool, refresh_workspace.id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let session = Session::create(
+            pool,
+            &CreateSession {
+                executor: Some(executor),
+            },
+            Uuid::new_v4(),
+            refresh_workspace.id,
+        )
+        .await?;
+
+        let working_dir = refresh_workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+        let action = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt,
+                executor_profile_id,
+                working_dir,
+                agent_system_prompt: None,
+                agent_project_context: None,
+                agent_workflow_history: None,
+                agent_start_command: None,
+                agent_decision_instructions: None,
+                agent_deliverable: None,
+                prompt_sections: None,
+                prompt_language: None,
+            }),
+            None,
+        );
+        self.start_execution(
+            &refresh_workspace,
+            &session,
+            &action,
+            &ExecutionProcessRunReason::ManualRun,
+        )
+        .await
+        .map(Some)
+    }
+
+    /// Record a finished setup script run in its repo's history. A failure of a script
+    /// that passed recently is treated as a flake and retried once, rather than failing
+    /// the task. Returns true when a retry was started.
+    async fn record_setup_script_run(&self, ctx: &ExecutionContext) -> bool {
+        let pool = &self.db().pool;
+        let process = &ctx.execution_process;
+        // Stopped or still running processes say nothing about the script
+        if !matches!(
+            process.status,
+            ExecutionProcessStatus::Completed | ExecutionProcessStatus::Failed
+        ) {
+            return false;
+        }
+        let Ok(action) = process.executor_action() else {
+            return false;
+        };
+        // Column entry scripts share the setup run reason but aren't setup scripts
+        let ExecutorActionType::ScriptRequest(ScriptRequest {
+            context: ScriptContext::SetupScript,
+            working_dir,
+            ..
+        }) = action.typ()
+        else {
+            return false;
+        };
+        let Some(repo_name) = working_dir.as_deref() else {
+            return false;
+        };
+        let repo = match WorkspaceRepo::find_repos_for_workspace(pool, ctx.workspace.id).await {
+            Ok(repos) => repos.into_iter().find(|r| r.name == repo_name),
+            Err(e) => {
+                tracing::warn!("Failed to load repos for setup script history: {}", e);
+                return false;
+            }
+        };
+        let Some(repo) = repo else {
+            return false;
+        };
+
+        let succeeded = matches!(process.status, ExecutionProcessStatus::Completed)
+            && process.exit_code == Some(0);
+        let is_retry = SetupScriptRun::is_retry_execution(pool, process.id)
+            .await
+            .unwrap_or(false);
+        // History before this run decides whether a failure looks like a flake
+        let recent =
+            SetupScriptRun::find_recent(pool, ctx.task.project_id, repo.id, FLAKY_WINDOW as i64)
+                .await
+                .unwrap_or_default();
+
+        let run = match SetupScriptRun::create(
+            pool,
+            &CreateSetupScriptRun {
+                project_id: ctx.task.project_id,
+                repo_id: repo.id,
+                execution_process_id: process.id,
+                succeeded,
+                exit_code: process.exit_code,
+                is_retry,
+            },
+        )
+        .await
+        {
+            Ok(run) => run,
+            Err(e) => {
+                tracing::error!("Failed to record setup script run {}: {}", process.id, e);
+                return false;
+            }
+        };
+
+        let passed_recently = recent.iter().any(|r| !r.is_retry && r.succeeded);
+        if succeeded || is_retry || !passed_recently {
+            return false;
+        }
+
+        tracing::warn!(
+            "Setup script for repo {} failed but passed recently; retrying once",
+            repo.name
+        );
+        let (retry, task) = match self
+            .prepare_execution(
+                &ctx.workspace,
+                &ctx.session,
+                action,
+                &ExecutionProcessRunReason::SetupScript,
+            )
+            .await
+        {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                tracing::error!("Failed to retry setup script for repo {}: {}", repo.name, e);
+                return false;
+            }
+        };
+        // Link the retry before it runs, so however quickly it finishes it is already
+        // known as a retry and is never retried itself
+        if let Err(e) = SetupScriptRun::set_retry_execution(pool, run.id, retry.id).await {
+            tracing::error!("Failed to link setup script retry {}: {}", retry.id, e);
+            if let Err(e) = ExecutionProcess::update_completion(
+                pool,
+                retry.id,
+                ExecutionProcessStatus::Failed,
+                None,
+            )
+            .await
+            {
+                tracing::error!("Failed to mark unlinked retry {} as failed: {}", retry.id, e);
+            }
+            return false;
+        }
+        match self
+            .run_prepared_execution(&ctx.workspace, &retry, action, &task)
+            .await
+        {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::error!("Failed to retry setup script for repo {}: {}", repo.name, e);
+                false
+            }
+        }
     }
 
     async fn try_start_next_action(&self, ctx: &ExecutionContext) -> Result<(), ContainerError> {
@@ -2998,22 +4677,7 @@ pub trait ContainerService {
             return Ok(());
         };
 
-        // Determine the run reason of the next action
-        let next_run_reason = match (action.typ(), next_action.typ()) {
-            (ExecutorActionType::ScriptRequest(_), ExecutorActionType::ScriptRequest(_)) => {
-                ExecutionProcessRunReason::SetupScript
-            }
-            (
-                ExecutorActionType::CodingAgentInitialRequest(_)
-                | ExecutorActionType::CodingAgentFollowUpRequest(_),
-                ExecutorActionType::ScriptRequest(_),
-            ) => ExecutionProcessRunReason::CleanupScript,
-            (
-                _,
-                ExecutorActionType::CodingAgentFollowUpRequest(_)
-                | ExecutorActionType::CodingAgentInitialRequest(_),
-            ) => ExecutionProcessRunReason::CodingAgent,
-        };
+        let next_run_reason = next_run_reason(action.typ(), next_action.typ());
 
         self.start_execution(&ctx.workspace, &ctx.session, next_action, &next_run_reason)
             .await?;
@@ -3022,3 +4686,146 @@ pub trait ContainerService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(name: &str, script_language: Option<&str>) -> ProjectRepoWithName {
+        ProjectRepoWithName {
+            id: Uuid::new_v4(),
+            project_id: Uuid::nil(),
+            repo_id: Uuid::new_v4(),
+            repo_name: name.to_string(),
+            setup_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            parallel_setup_script: false,
+            script_language: script_language.map(str::to_string),
+            windows_setup_script: None,
+            windows_cleanup_script: None,
+            windows_script_language: None,
+        }
+    }
+
+    fn agent_request() -> ExecutorActionType {
+        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt: "Fix the bug".to_string(),
+            executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+            working_dir: None,
+            agent_system_prompt: None,
+            agent_project_context: None,
+            agent_workflow_history: None,
+            agent_start_command: None,
+            agent_decision_instructions: None,
+            agent_deliverable: None,
+            prompt_sections: None,
+            prompt_language: None,
+        })
+    }
+
+    fn script(context: ScriptContext) -> ExecutorActionType {
+        ExecutorActionType::ScriptRequest(ScriptRequest {
+            script: "true".to_string(),
+            language: ScriptRequestLanguage::Bash,
+            context,
+            working_dir: None,
+        })
+    }
+
+    fn context_of(action: &ExecutorAction) -> Option<ScriptContext> {
+        match action.typ() {
+            ExecutorActionType::ScriptRequest(request) => Some(request.context.clone()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn column_scripts_wrap_the_agent_before_cleanup() {
+        let repos = [repo("app", Some("python"))];
+        let cleanup = ExecutorAction::new(script(ScriptContext::CleanupScript), None);
+        let (first, run_reason) = with_column_scripts(
+            agent_request(),
+            Some("make warm".to_string()),
+            Some("make report".to_string()),
+            Some(cleanup),
+            &repos,
+        );
+
+        assert_eq!(run_reason, ExecutionProcessRunReason::SetupScript);
+        let ExecutorActionType::ScriptRequest(enter) = first.typ() else {
+            panic!("chain should start with the entry script");
+        };
+        assert_eq!(enter.context, ScriptContext::ColumnEnterScript);
+        assert_eq!(enter.language, ScriptRequestLanguage::Python);
+        assert_eq!(enter.working_dir.as_deref(), Some("app"));
+
+        let agent = first.next_action().unwrap();
+        assert_eq!(agent.typ(), &agent_request());
+        let exit = agent.next_action().unwrap();
+        assert_eq!(context_of(exit), Some(ScriptContext::ColumnExitScript));
+        let cleanup = exit.next_action().unwrap();
+        assert_eq!(context_of(cleanup), Some(ScriptContext::CleanupScript));
+        assert!(cleanup.next_action().is_none());
+
+        let (first, run_reason) = with_column_scripts(agent_request(), None, None, None, &repos);
+        assert_eq!(run_reason, ExecutionProcessRunReason::CodingAgent);
+        assert_eq!(first.typ(), &agent_request());
+        assert!(first.next_action().is_none());
+    }
+
+    #[test]
+    fn column_scripts_of_multi_repo_projects_run_in_the_shared_language() {
+        let shared = [
+            repo("api", Some("powershell")),
+            repo("web", Some("powershell")),
+        ];
+        let request =
+            column_script_request("x".to_string(), ScriptContext::ColumnExitScript, &shared);
+        assert_eq!(request.language, ScriptRequestLanguage::PowerShell);
+        assert_eq!(request.working_dir, None);
+
+        let mixed = [repo("api", Some("python")), repo("web", None)];
+        let request =
+            column_script_request("x".to_string(), ScriptContext::ColumnExitScript, &mixed);
+        assert_eq!(request.language, ScriptRequestLanguage::Bash);
+    }
+
+    #[test]
+    fn next_run_reason_follows_the_chain() {
+        let cases = [
+            (
+                ScriptContext::SetupScript,
+                ScriptContext::SetupScript,
+                ExecutionProcessRunReason::SetupScript,
+            ),
+            (
+                ScriptContext::SetupScript,
+                ScriptContext::ColumnEnterScript,
+                ExecutionProcessRunReason::SetupScript,
+            ),
+            (
+                ScriptContext::ColumnExitScript,
+                ScriptContext::CleanupScript,
+                ExecutionProcessRunReason::CleanupScript,
+            ),
+            (
+                ScriptContext::SetupScript,
+                ScriptContext::ColumnExitScript,
+                ExecutionProcessRunReason::CleanupScript,
+            ),
+        ];
+        for (current, next, expected) in cases {
+            assert_eq!(next_run_reason(&script(current), &script(next)), expected);
+        }
+
+        assert_eq!(
+            next_run_reason(&agent_request(), &script(ScriptContext::ColumnExitScript)),
+            ExecutionProcessRunReason::CleanupScript
+        );
+        assert_eq!(
+            next_run_reason(&script(ScriptContext::ColumnEnterScript), &agent_request()),
+            ExecutionProcessRunReason::CodingAgent
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use db::models::{
+    agent::Agent,
+    context_artifact::ContextArtifact,
+    execution_token_usage::{ExecutionTokenUsage, TokenUsageAverages},
+    kanban_column::KanbanColumn,
+    task::Task,
+};
+use serde::Serialize;
+use sqlx::PgPool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Rough token/cost estimate for starting an attempt in a column
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct CostEstimate {
+    pub column_id: Option<Uuid>,
+    pub agent_id: Option<Uuid>,
+    /// Task prompt plus the agent's system prompt, start command and deliverable
+    pub prompt_tokens: i64,
+    /// Project context (artifacts) injected into the prompt
+    pub context_tokens: i64,
+    /// Past runs the historical averages are based on (0 = no history)
+    pub historical_samples: i64,
+    pub historical_avg_input_tokens: Option<i64>,
+    pub historical_avg_output_tokens: Option<i64>,
+    pub estimated_input_tokens: i64,
+    pub estimated_output_tokens: i64,
+    pub estimated_total_tokens: i64,
+    /// Configured `attempt_budget.max_estimated_tokens`, if any
+    pub budget_tokens: Option<i64>,
+    pub within_budget: bool,
+}
+
+impl CostEstimate {
+    /// Estimate the cost of starting `task_prompt` in `column`.
+    ///
+    /// Historical averages come from the column's agent when it has recorded
+    /// runs, otherwise from the whole project.
+    pub async fn for_attempt(
+        pool: &PgPool,
+        project_id: Uuid,
+        task_id: Option<Uuid>,
+        task_prompt: &str,
+        column: Option<&KanbanColumn>,
+        budget_tokens: Option<u32>,
+    ) -> Result<Self, sqlx::Error> {
+        let agent = match column.and_then(|c| c.agent_id) {
+            Some(agent_id) => Agent::find_by_id(pool, agent_id).await?,
+            None => None,
+        };
+
+        let mut prompt_chars = task_prompt.len();
+        if let Some(agent) = &agent {
+            prompt_chars += agent.system_prompt.len();
+            prompt_chars += agent.start_command.as_deref().map_or(0, str::len);
+        }
+        if let Some(deliverable) = column.and_then(|c| c.deliverable.as_deref()) {
+            prompt_chars += deliverable.len();
+        }
+
+        let context = ContextArtifact::build_full_context_with_stats(
+            pool,
+            project_id,
+            task_id,
+            column.map(|c| c.id),
+            &[],
+        )
+        .await?;
+
+        let mut history = match &agent {
+            Some(agent) => ExecutionTokenUsage::averages_for_agent(pool, agent.id).await?,
+            None => TokenUsageAverages::default(),
+        };
+        if history.samples == 0 {
+            history = ExecutionTokenUsage::averages_for_project(pool, project_id).await?;
+        }
+
+        Ok(Self::from_parts(
+            column.map(|c| c.id),
+            agent.map(|a| a.id),
+            estimate_tokens(prompt_chars),
+            context.tokens_used as i64,
+            &history,
+            budget_tokens,
+        ))
+    }
+
+    fn from_parts(
+        column_id: Option<Uuid>,
+        agent_id: Option<Uuid>,
+        prompt_tokens: i64,
+        context_tokens: i64,
+        history: &TokenUsageAverages,
+        budget_tokens: Option<u32>,
+    ) -> Self {
+        let avg_input = history.avg_input_tokens.map(|v| v.round() as i64);
+        let avg_output = history.avg_output_tokens.map(|v| v.round() as i64);
+
+        // Past runs already include their initial prompt, so history replaces
+        // rather than adds to it - unless this prompt is unusually large.
+        let initial = prompt_tokens + context_tokens;
+        let estimated_input_tokens = avg_input.map_or(initial, |avg| avg.max(initial));
+        let estimated_output_tokens = avg_output.unwrap_or(0);
+        let estimated_total_tokens = estimated_input_tokens + estimated_output_tokens;
+
+        let budget_tokens = budget_tokens.map(i64::from);
+        Self {
+            column_id,
+            agent_id,
+            prompt_tokens,
+            context_tokens,
+            historical_samples: history.samples,
+            historical_avg_input_tokens: avg_input,
+            historical_avg_output_tokens: avg_output,
+            estimated_input_tokens,
+            estimated_output_tokens,
+            estimated_total_tokens,
+            budget_tokens,
+            within_budget: budget_tokens.is_none_or(|max| estimated_total_tokens <= max),
+        }
+    }
+
+    /// Error message for refusing to start, or None when within budget
+    pub fn budget_exceeded_message(&self) -> Option<String> {
+        let budget = self.budget_tokens.filter(|_| !self.within_budget)?;
+        Some(format!(
+            "Estimated usage of {} tokens exceeds the attempt budget of {} tokens",
+            self.estimated_total_tokens, budget
+        ))
+    }
+}
+
+/// Why `task` may not start under `budget_tokens`: the budget message when its
+/// estimate is over, None when within budget or no budget is set
+pub async fn budget_exceeded(
+    pool: &PgPool,
+    task: &Task,
+    budget_tokens: Option<u32>,
+) -> Result<Option<String>, sqlx::Error> {
+    if budget_tokens.is_none() {
+        return Ok(None);
+    }
+    let column = match task.column_id {
+        Some(column_id) => KanbanColumn::find_by_id(pool, column_id).await?,
+        None => None,
+    };
+    let estimate = CostEstimate::for_attempt(
+        pool,
+        task.project_id,
+        Some(task.id),
+        &task.to_prompt(),
+        column.as_ref(),
+        budget_tokens,
+    )
+    .await?;
+    Ok(estimate.budget_exceeded_message())
+}
+
+/// Same ~4 chars/token heuristic used for context budgeting
+fn estimate_tokens(chars: usize) -> i64 {
+    (chars / 4) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(samples: i64, input: f64, output: f64) -> TokenUsageAverages {
+        TokenUsageAverages {
+            samples,
+            avg_input_tokens: Some(input),
+            avg_output_tokens: Some(output),
+        }
+    }
+
+    #[test]
+    fn without_history_only_prompt_is_counted() {
+        let estimate =
+            CostEstimate::from_parts(None, None, 100, 50, &TokenUsageAverages::default(), None);
+        assert_eq!(estimate.estimated_input_tokens, 150);
+        assert_eq!(estimate.estimated_output_tokens, 0);
+        assert!(estimate.within_budget);
+    }
+
+    #[test]
+    fn history_replaces_smaller_initial_prompt() {
+        let estimate =
+            CostEstimate::from_parts(None, None, 100, 50, &history(3, 10_000.0, 2_000.0), None);
+        assert_eq!(estimate.estimated_input_tokens, 10_000);
+        assert_eq!(estimate.estimated_total_tokens, 12_000);
+    }
+
+    #[test]
+    fn over_budget_is_reported() {
+        let estimate = CostEstimate::from_parts(
+            None,
+            None,
+            100,
+            50,
+            &history(3, 10_000.0, 2_000.0),
+            Some(5_000),
+        );
+        assert!(!estimate.within_budget);
+        assert!(estimate.budget_exceeded_message().is_some());
+    }
+}
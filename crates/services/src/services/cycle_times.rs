@@ -0,0 +1,387 @@
+//! Cycle time analytics: how long tasks spend in each column.
+//!
+//! A task's visit to a column runs from its `column_enter` event to its next one, or
+//! is still open while the task sits in the column. Board reports give percentiles of
+//! finished visits per column and per assigned agent; a task breakdown lists its
+//! visits and the total per column. Boards can have thousands of events, so a board's
+//! visits are derived once and cached until its column entries change, which a cheap
+//! count/latest query detects, or the cache entry expires.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    agent::Agent,
+    agent_stats::StatsTimeRange,
+    kanban_column::KanbanColumn,
+    task::Task,
+    task_event::{ColumnEntry, TaskEvent},
+};
+use moka::future::Cache;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Derived visits per board, checked against the board's entries before reuse
+static VISIT_CACHE: LazyLock<Cache<Uuid, Arc<CachedVisits>>> = LazyLock::new(|| {
+    Cache::builder()
+        .max_capacity(200)
+        .time_to_live(Duration::from_secs(600))
+        .build()
+});
+
+pub(crate) struct CachedVisits {
+    fingerprint: (i64, Option<DateTime<Utc>>),
+    pub(crate) visits: Vec<ColumnVisit>,
+}
+
+/// A task's stay in one column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnVisit {
+    pub task_id: Uuid,
+    pub column_id: Uuid,
+    pub entered_at: DateTime<Utc>,
+    /// When the task entered its next column; none while it's still here
+    pub left_at: Option<DateTime<Utc>>,
+}
+
+impl ColumnVisit {
+    /// Time spent in the column, up to `now` for a visit still open
+    pub fn duration_secs(&self, now: DateTime<Utc>) -> f64 {
+        let end = self.left_at.unwrap_or(now);
+        (end - self.entered_at).num_milliseconds().max(0) as f64 / 1000.0
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct DurationStats {
+    /// Finished visits the statistics are computed from
+    pub samples: usize,
+    pub mean_secs: Option<f64>,
+    pub p50_secs: Option<f64>,
+    pub p75_secs: Option<f64>,
+    pub p90_secs: Option<f64>,
+    pub p95_secs: Option<f64>,
+    pub max_secs: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ColumnCycleTime {
+    pub column_id: Uuid,
+    pub column_name: String,
+    pub agent_id: Option<Uuid>,
+    /// Tasks in the column right now
+    pub in_column_now: usize,
+    pub stats: DurationStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct AgentCycleTime {
+    pub agent_id: Uuid,
+    pub agent_name: String,
+    /// Finished visits to every column the agent is assigned to
+    pub stats: DurationStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BoardCycleTimes {
+    pub board_id: Uuid,
+    /// Columns in board order
+    pub columns: Vec<ColumnCycleTime>,
+    pub agents: Vec<AgentCycleTime>,
+    #[ts(type = "Date")]
+    pub computed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskColumnVisit {
+    pub column_id: Uuid,
+    /// None when the column has been deleted
+    pub column_name: Option<String>,
+    #[ts(type = "Date")]
+    pub entered_at: DateTime<Utc>,
+    #[ts(type = "Date | null")]
+    pub left_at: Option<DateTime<Utc>>,
+    pub duration_secs: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskColumnTotal {
+    pub column_id: Uuid,
+    pub column_name: Option<String>,
+    pub visits: usize,
+    pub total_secs: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskCycleTime {
+    pub task_id: Uuid,
+    /// From the first column entry until now, or until the task reached a terminal column
+    pub total_secs: f64,
+    /// Per column, in order of first visit
+    pub columns: Vec<TaskColumnTotal>,
+    /// Every visit, oldest first
+    pub visits: Vec<TaskColumnVisit>,
+}
+
+/// Visits from column entries ordered by task and then time
+pub fn column_visits(entries: &[ColumnEntry]) -> Vec<ColumnVisit> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| ColumnVisit {
+            task_id: entry.task_id,
+            column_id: entry.column_id,
+            entered_at: entry.entered_at,
+            left_at: entries
+                .get(i + 1)
+                .filter(|next| next.task_id == entry.task_id)
+                .map(|next| next.entered_at),
+        })
+        .collect()
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+pub fn duration_stats(mut durations: Vec<f64>) -> DurationStats {
+    if durations.is_empty() {
+        return DurationStats::default();
+    }
+    durations.sort_by(f64::total_cmp);
+    let mean = durations.iter().sum::<f64>() / durations.len() as f64;
+    DurationStats {
+        samples: durations.len(),
+        mean_secs: Some(mean),
+        p50_secs: Some(percentile(&durations, 50.0)),
+        p75_secs: Some(percentile(&durations, 75.0)),
+        p90_secs: Some(percentile(&durations, 90.0)),
+        p95_secs: Some(percentile(&durations, 95.0)),
+        max_secs: durations.last().copied(),
+    }
+}
+
+/// Whether a finished visit ended inside the range
+fn in_range(visit: &ColumnVisit, range: &StatsTimeRange) -> bool {
+    let Some(left_at) = visit.left_at else {
+        return false;
+    };
+    range.from.is_none_or(|from| left_at >= from) && range.to.is_none_or(|to| left_at <= to)
+}
+
+/// Cycle times of a board's columns and agents from its visits
+pub fn board_cycle_times(
+    board_id: Uuid,
+    columns: &[KanbanColumn],
+    agent_names: &HashMap<Uuid, String>,
+    visits: &[ColumnVisit],
+    range: &StatsTimeRange,
+) -> BoardCycleTimes {
+    let mut finished: HashMap<Uuid, Vec<f64>> = HashMap::new();
+    let mut open: HashMap<Uuid, usize> = HashMap::new();
+    let now = Utc::now();
+    for visit in visits {
+        if visit.left_at.is_none() {
+            *open.entry(visit.column_id).or_default() += 1;
+        } else if in_range(visit, range) {
+            finished
+                .entry(visit.column_id)
+                .or_default()
+                .push(visit.duration_secs(now));
+        }
+    }
+
+    let mut per_agent: BTreeMap<Uuid, Vec<f64>> = BTreeMap::new();
+    let mut column_times = Vec::with_capacity(columns.len());
+    for column in columns {
+        let durations = finished.remove(&column.id).unwrap_or_default();
+        if let Some(agent_id) = column.agent_id {
+            per_agent
+                .entry(agent_id)
+                .or_default()
+                .extend(durations.iter().copied());
+        }
+        column_times.push(ColumnCycleTime {
+            column_id: column.id,
+            column_name: column.name.clone(),
+            agent_id: column.agent_id,
+            in_column_now: open.get(&column.id).copied().unwrap_or(0),
+            stats: duration_stats(durations),
+        });
+    }
+
+    let mut agents: Vec<AgentCycleTime> = per_agent
+        .into_iter()
+        .map(|(agent_id, durations)| AgentCycleTime {
+            agent_id,
+            agent_name: agent_names.get(&agent_id).cloned().unwrap_or_default(),
+            stats: duration_stats(durations),
+        })
+        .collect();
+    agents.sort_by(|a, b| a.agent_name.cmp(&b.agent_name));
+
+    BoardCycleTimes {
+        board_id,
+        columns: column_times,
+        agents,
+        computed_at: now,
+    }
+}
+
+/// A board's visits, from the cache while the board's column entries are unchanged
+pub(crate) async fn board_visits(
+    pool: &PgPool,
+    board_id: Uuid,
+) -> Result<Arc<CachedVisits>, sqlx::Error> {
+    let fingerprint = TaskEvent::column_entries_fingerprint(pool, board_id).await?;
+    if let Some(cached) = VISIT_CACHE.get(&board_id).await
+        && cached.fingerprint == fingerprint
+    {
+        return Ok(cached);
+    }
+
+    let entries = TaskEvent::find_column_entries_for_board(pool, board_id).await?;
+    let cached = Arc::new(CachedVisits {
+        fingerprint,
+        visits: column_visits(&entries),
+    });
+    VISIT_CACHE.insert(board_id, cached.clone()).await;
+    Ok(cached)
+}
+
+/// Time-in-column percentiles for a board's columns and agents
+pub async fn for_board(
+    pool: &PgPool,
+    board_id: Uuid,
+    range: &StatsTimeRange,
+) -> Result<BoardCycleTimes, sqlx::Error> {
+    let mut columns = KanbanColumn::find_by_board(pool, board_id).await?;
+    columns.sort_by_key(|c| c.position);
+    let agent_names = Agent::find_all(pool)
+        .await?
+        .into_iter()
+        .map(|a| (a.id, a.name))
+        .collect();
+    let cached = board_visits(pool, board_id).await?;
+    Ok(board_cycle_times(
+        board_id,
+        &columns,
+        &agent_names,
+        &cached.visits,
+        range,
+    ))
+}
+
+/// A task's visits to each column and the time spent in each
+pub async fn for_task(pool: &PgPool, task: &Task) -> Result<TaskCycleTime, sqlx::Error> {
+    let entries = TaskEvent::find_column_entries_for_task(pool, task.id).await?;
+    let visits = column_visits(&entries);
+
+    let mut columns: HashMap<Uuid, Option<KanbanColumn>> = HashMap::new();
+    for visit in &visits {
+        if !columns.contains_key(&visit.column_id) {
+            let column = KanbanColumn::find_by_id(pool, visit.column_id).await?;
+            columns.insert(visit.column_id, column);
+        }
+    }
+    let column = |id: &Uuid| columns.get(id).and_then(|c| c.as_ref());
+    let column_name = |id: &Uuid| column(id).map(|c| c.name.clone());
+    let is_terminal = |id: &Uuid| column(id).is_some_and(|c| c.is_terminal);
+
+    let now = Utc::now();
+    let mut totals: Vec<TaskColumnTotal> = Vec::new();
+    let mut task_visits = Vec::with_capacity(visits.len());
+    let mut total_secs = 0.0;
+    for visit in &visits {
+        // Time resting in a terminal column isn't part of the cycle
+        let duration_secs = if visit.left_at.is_none() && is_terminal(&visit.column_id) {
+            0.0
+        } else {
+            visit.duration_secs(now)
+        };
+        total_secs += duration_secs;
+        match totals.iter_mut().find(|t| t.column_id == visit.column_id) {
+            Some(total) => {
+                total.visits += 1;
+                total.total_secs += duration_secs;
+            }
+            None => totals.push(TaskColumnTotal {
+                column_id: visit.column_id,
+                column_name: column_name(&visit.column_id),
+                visits: 1,
+                total_secs: duration_secs,
+            }),
+        }
+        task_visits.push(TaskColumnVisit {
+            column_id: visit.column_id,
+            column_name: column_name(&visit.column_id),
+            entered_at: visit.entered_at,
+            left_at: visit.left_at,
+            duration_secs,
+        });
+    }
+
+    Ok(TaskCycleTime {
+        task_id: task.id,
+        total_secs,
+        columns: totals,
+        visits: task_visits,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn entry(task_id: Uuid, column_id: Uuid, minute: u32) -> ColumnEntry {
+        ColumnEntry {
+            task_id,
+            column_id,
+            entered_at: Utc.with_ymd_and_hms(2026, 1, 1, 12, minute, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn visits_end_at_the_tasks_next_entry() {
+        let (task_a, task_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let (todo, review) = (Uuid::new_v4(), Uuid::new_v4());
+        let entries = vec![
+            entry(task_a, todo, 0),
+            entry(task_a, review, 10),
+            entry(task_b, todo, 5),
+        ];
+        let visits = column_visits(&entries);
+
+        assert_eq!(visits.len(), 3);
+        assert_eq!(visits[0].left_at, Some(entries[1].entered_at));
+        assert_eq!(visits[0].duration_secs(Utc::now()), 600.0);
+        // Each task's last visit is still open
+        assert_eq!(visits[1].left_at, None);
+        assert_eq!(visits[2].left_at, None);
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let stats = duration_stats((1..=20).map(f64::from).collect());
+        assert_eq!(stats.samples, 20);
+        assert_eq!(stats.mean_secs, Some(10.5));
+        assert_eq!(stats.p50_secs, Some(10.0));
+        assert_eq!(stats.p75_secs, Some(15.0));
+        assert_eq!(stats.p90_secs, Some(18.0));
+        assert_eq!(stats.p95_secs, Some(19.0));
+        assert_eq!(stats.max_secs, Some(20.0));
+
+        assert_eq!(duration_stats(vec![42.0]).p95_secs, Some(42.0));
+        assert_eq!(duration_stats(Vec::new()), DurationStats::default());
+    }
+}
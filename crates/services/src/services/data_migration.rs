@@ -0,0 +1,205 @@
+//! Run-once data migrations.
+//!
+//! Backfills of existing rows used to run on every startup, which slowed boot on
+//! large installs even after there was nothing left to fix. Each backfill is now a
+//! named migration with bookkeeping in `data_migrations`: an admin starts it, it
+//! records progress while running, and once completed it never runs again. Failed
+//! or interrupted runs can be started again.
+
+use db::models::data_migration::{DataMigration, DataMigrationStatus};
+use sqlx::PgPool;
+use thiserror::Error;
+use tracing::{error, info, warn};
+
+use super::container::{ContainerError, ContainerService};
+
+/// Progress is written every this many items (and at the end)
+const PROGRESS_REPORT_INTERVAL: i32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataMigrationKind {
+    /// Set before_head_commit on legacy execution processes
+    BeforeHeadCommits,
+    /// Name repos migrated with a placeholder, and set single-repo working dirs
+    RepoNames,
+}
+
+impl DataMigrationKind {
+    pub const ALL: [Self; 2] = [Self::BeforeHeadCommits, Self::RepoNames];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BeforeHeadCommits => "backfill_before_head_commits",
+            Self::RepoNames => "backfill_repo_names",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DataMigrationError {
+    #[error("Data migration {0} has already completed")]
+    AlreadyCompleted(&'static str),
+    #[error("Data migration {0} is already running")]
+    AlreadyRunning(&'static str),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// Records how far a running migration has got
+pub struct DataMigrationProgress {
+    pool: PgPool,
+    name: &'static str,
+    processed: i32,
+    total: Option<i32>,
+}
+
+impl DataMigrationProgress {
+    fn new(pool: PgPool, kind: DataMigrationKind) -> Self {
+        Self {
+            pool,
+            name: kind.name(),
+            processed: 0,
+            total: None,
+        }
+    }
+
+    /// Record how many items the migration found to handle
+    pub async fn set_total(&mut self, total: usize) -> Result<(), sqlx::Error> {
+        self.total = Some(total as i32);
+        DataMigration::update_progress(&self.pool, self.name, self.processed, self.total).await
+    }
+
+    /// Count one handled item, writing progress periodically
+    pub async fn advance(&mut self) -> Result<(), sqlx::Error> {
+        self.processed += 1;
+        if should_report(self.processed, self.total) {
+            DataMigration::update_progress(&self.pool, self.name, self.processed, self.total)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+fn should_report(processed: i32, total: Option<i32>) -> bool {
+    processed % PROGRESS_REPORT_INTERVAL == 0 || Some(processed) == total
+}
+
+/// Every known migration with its bookkeeping; never-started ones are pending
+pub async fn list(pool: &PgPool) -> Result<Vec<DataMigration>, sqlx::Error> {
+    let recorded = DataMigration::find_all(pool).await?;
+    Ok(DataMigrationKind::ALL
+        .iter()
+        .map(|kind| {
+            recorded
+                .iter()
+                .find(|migration| migration.name == kind.name())
+                .cloned()
+                .unwrap_or_else(|| DataMigration::pending(kind.name()))
+        })
+        .collect())
+}
+
+/// Fail runs interrupted by a restart and warn about migrations still to run
+pub async fn check_on_startup(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let abandoned = DataMigration::fail_abandoned(pool).await?;
+    if abandoned > 0 {
+        warn!(
+            "{} data migration(s) were interrupted by a restart",
+            abandoned
+        );
+    }
+
+    let outstanding: Vec<_> = list(pool)
+        .await?
+        .into_iter()
+        .filter(|migration| migration.status != DataMigrationStatus::Completed)
+        .map(|migration| migration.name)
+        .collect();
+    if !outstanding.is_empty() {
+        warn!(
+            "Data migrations not yet completed: {}. Run them with POST /api/admin/data-migrations/{{name}}/run",
+            outstanding.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Claim a migration for a run. Fails if it is running or has completed.
+pub async fn start(
+    pool: &PgPool,
+    kind: DataMigrationKind,
+) -> Result<DataMigration, DataMigrationError> {
+    if let Some(migration) = DataMigration::claim(pool, kind.name()).await? {
+        return Ok(migration);
+    }
+    match DataMigration::find_by_name(pool, kind.name()).await? {
+        Some(migration) if migration.status == DataMigrationStatus::Completed => {
+            Err(DataMigrationError::AlreadyCompleted(kind.name()))
+        }
+        _ => Err(DataMigrationError::AlreadyRunning(kind.name())),
+    }
+}
+
+/// Run a migration claimed with [`start`], recording the outcome
+pub async fn run<C>(container: &C, kind: DataMigrationKind)
+where
+    C: ContainerService + Sync + ?Sized,
+{
+    let pool = &container.db().pool;
+    let mut progress = DataMigrationProgress::new(pool.clone(), kind);
+    info!("Running data migration {}", kind.name());
+
+    let result: Result<(), ContainerError> = match kind {
+        DataMigrationKind::BeforeHeadCommits => {
+            container.backfill_before_head_commits(&mut progress).await
+        }
+        DataMigrationKind::RepoNames => container.backfill_repo_names(&mut progress).await,
+    };
+
+    let recorded = match &result {
+        Ok(()) => {
+            info!(
+                "Data migration {} completed ({} items)",
+                kind.name(),
+                progress.processed
+            );
+            DataMigration::complete(pool, kind.name(), progress.processed, progress.total).await
+        }
+        Err(e) => {
+            error!("Data migration {} failed: {}", kind.name(), e);
+            DataMigration::fail(pool, kind.name(), &e.to_string()).await
+        }
+    };
+    if let Err(e) = recorded {
+        error!(
+            "Failed to record outcome of data migration {}: {}",
+            kind.name(),
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for kind in DataMigrationKind::ALL {
+            assert_eq!(DataMigrationKind::from_name(kind.name()), Some(kind));
+        }
+        assert_eq!(DataMigrationKind::from_name("backfill_everything"), None);
+    }
+
+    #[test]
+    fn progress_is_reported_periodically_and_at_the_end() {
+        assert!(!should_report(1, Some(120)));
+        assert!(should_report(50, Some(120)));
+        assert!(should_report(120, Some(120)));
+        assert!(!should_report(7, None));
+    }
+}
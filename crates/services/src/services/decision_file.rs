@@ -0,0 +1,345 @@
+//! The decision file agents write to route a task (`decision.json`).
+//!
+//! Agents write `.vibe/decision.json` at the workspace root or, in multi-repo
+//! workspaces, inside the repo they worked in. Agents that can't write to the
+//! worktree use the workspace's decisions directory outside it, exposed to them as
+//! `VK_DECISIONS_DIR`. Each repo's `.vibe/` gets a `.gitignore` so decisions aren't
+//! committed with the agent's changes.
+
+use std::path::{Path, PathBuf};
+
+use db::models::{kanban_column::KanbanColumn, workspace::Workspace};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use super::workflow_lint::answer_options;
+
+/// Environment variable pointing agents at the workspace's decisions directory
+pub const DECISIONS_DIR_ENV: &str = "VK_DECISIONS_DIR";
+
+const DECISION_FILE: &str = "decision.json";
+
+/// Keeps the decision file, collected artifacts (and itself) out of commits; other
+/// `.vibe/` files such as `summary.md` are left to the project
+const VIBE_GITIGNORE: &str = "decision.json\nartifacts/\n.gitignore\n";
+
+/// Per-workspace decisions directory outside the worktree
+pub fn decisions_dir(workspace_id: Uuid) -> PathBuf {
+    utils::cache_dir()
+        .join("decisions")
+        .join(workspace_id.to_string())
+}
+
+/// Repo directories of a workspace with their names, sorted by name
+pub(crate) async fn repo_dirs(workspace_root: &Path) -> Vec<(String, PathBuf)> {
+    let mut dirs = Vec::new();
+    if let Ok(mut entries) = tokio::fs::read_dir(workspace_root).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.is_dir() && path.join(".git").exists() {
+                dirs.push((entry.file_name().to_string_lossy().to_string(), path));
+            }
+        }
+    }
+    dirs.sort();
+    dirs
+}
+
+/// Create the workspace's decisions directory and a `.vibe/.gitignore` in each repo
+/// that doesn't have one yet
+pub async fn prepare_decision_dirs(workspace: &Workspace) {
+    if let Err(e) = tokio::fs::create_dir_all(decisions_dir(workspace.id)).await {
+        tracing::warn!("Failed to create decisions directory: {}", e);
+    }
+    let Some(worktree_path) = workspace.container_ref.as_ref() else {
+        return;
+    };
+    for (name, dir) in repo_dirs(Path::new(worktree_path)).await {
+        let gitignore = dir.join(".vibe/.gitignore");
+        if gitignore.exists() {
+            continue;
+        }
+        let result = match tokio::fs::create_dir_all(dir.join(".vibe")).await {
+            Ok(()) => tokio::fs::write(&gitignore, VIBE_GITIGNORE).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to write .vibe/.gitignore in {}: {}", name, e);
+        }
+    }
+}
+
+/// Combine the decision written outside the repos with those written per repo.
+///
+/// The base decision is the workspace-level one, else the first repo's. When more
+/// than one file was written, all repo decisions are added under `repos.<name>`, so
+/// conditions can route on a specific repo's answer (e.g. `repos.api.answer == approve`).
+pub fn merge_decisions(
+    primary: Option<Value>,
+    repo_decisions: Vec<(String, Value)>,
+) -> Option<Value> {
+    let file_count = usize::from(primary.is_some()) + repo_decisions.len();
+    let mut merged = primary.or_else(|| repo_decisions.first().map(|(_, d)| d.clone()))?;
+    if file_count > 1
+        && let Value::Object(map) = &mut merged
+    {
+        let repos = repo_decisions
+            .into_iter()
+            .collect::<serde_json::Map<_, _>>();
+        map.entry("repos").or_insert(Value::Object(repos));
+    }
+    Some(merged)
+}
+
+async fn parse_decision_file(path: &Path) -> Option<Value> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(content) => match serde_json::from_str(&content) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                tracing::warn!("Failed to parse decision file at {:?}: {}", path, e);
+                None
+            }
+        },
+        Err(e) => {
+            tracing::warn!("Failed to read decision file at {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Workspace-level decision file locations, in order of precedence
+fn primary_paths(workspace: &Workspace) -> Vec<PathBuf> {
+    let mut paths = vec![decisions_dir(workspace.id).join(DECISION_FILE)];
+    if let Some(worktree_path) = workspace.container_ref.as_ref() {
+        paths.push(Path::new(worktree_path).join(".vibe").join(DECISION_FILE));
+    }
+    paths
+}
+
+/// Decision files written inside the workspace's repos, sorted by repo name
+async fn repo_decision_paths(workspace: &Workspace) -> Vec<(String, PathBuf)> {
+    let Some(worktree_path) = workspace.container_ref.as_ref() else {
+        return Vec::new();
+    };
+    repo_dirs(Path::new(worktree_path))
+        .await
+        .into_iter()
+        .map(|(name, dir)| (name, dir.join(".vibe").join(DECISION_FILE)))
+        .filter(|(_, path)| path.exists())
+        .collect()
+}
+
+/// Read the workspace's decision: the decisions directory or workspace root file,
+/// combined with any per-repo decision files (see [`merge_decisions`])
+pub async fn read_decision_file(workspace: &Workspace) -> Option<Value> {
+    let mut primary = None;
+    for path in primary_paths(workspace) {
+        if path.exists() {
+            primary = parse_decision_file(&path).await;
+            break;
+        }
+    }
+
+    let mut repo_decisions = Vec::new();
+    for (name, path) in repo_decision_paths(workspace).await {
+        if let Some(decision) = parse_decision_file(&path).await {
+            repo_decisions.push((name, decision));
+        }
+    }
+
+    merge_decisions(primary, repo_decisions)
+}
+
+/// Check a value an agent sets through the API against the column's question.
+///
+/// `answer` is what transitions route on, so it must be one of the column's answer
+/// options when it has any. `repos` and `feedback` are managed by the server.
+pub fn validate_decision_value(
+    column: Option<&KanbanColumn>,
+    key: &str,
+    value: &Value,
+) -> Result<(), String> {
+    check_decision_value(key, value, &column.map(answer_options).unwrap_or_default())
+}
+
+fn check_decision_value(key: &str, value: &Value, options: &[String]) -> Result<(), String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("key is required".to_string());
+    }
+    if key == "repos" || key == "feedback" {
+        return Err(format!("'{key}' can't be set directly"));
+    }
+    if key == "answer" && !options.is_empty() {
+        let valid = value.as_str().is_some_and(|v| options.iter().any(|o| o == v));
+        if !valid {
+            return Err(format!(
+                "Invalid answer {value}. Valid answers: {}",
+                options.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Set `key` in a decision, keeping its other keys. Feedback is appended to any
+/// already given, and the column's question is recorded alongside an answer.
+pub fn set_decision_value(
+    existing: Option<Value>,
+    key: &str,
+    value: Value,
+    feedback: Option<&str>,
+    question: Option<&str>,
+) -> Value {
+    let mut map = match existing {
+        Some(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    if key == "answer"
+        && let Some(question) = question
+    {
+        map.entry("question")
+            .or_insert_with(|| Value::String(question.to_string()));
+    }
+    map.insert(key.to_string(), value);
+
+    if let Some(feedback) = feedback.map(str::trim).filter(|f| !f.is_empty()) {
+        let feedback = Value::String(feedback.to_string());
+        let merged = match map.remove("feedback") {
+            Some(Value::Array(mut points)) => {
+                points.push(feedback);
+                Value::Array(points)
+            }
+            Some(Value::String(previous)) if !previous.trim().is_empty() => {
+                Value::Array(vec![Value::String(previous), feedback])
+            }
+            _ => feedback,
+        };
+        map.insert("feedback".to_string(), merged);
+    }
+    Value::Object(map)
+}
+
+/// Merge a value into the workspace's decision file and return the new decision.
+///
+/// Writes to the workspace-level file the agent already wrote, else the worktree's
+/// `.vibe/decision.json`, else the decisions directory when there is no worktree.
+pub async fn write_decision_value(
+    workspace: &Workspace,
+    column: Option<&KanbanColumn>,
+    key: &str,
+    value: Value,
+    feedback: Option<&str>,
+) -> std::io::Result<Value> {
+    let paths = primary_paths(workspace);
+    let (path, existing) = match paths.iter().find(|path| path.exists()) {
+        Some(path) => (path.clone(), parse_decision_file(path).await),
+        None => (paths[paths.len() - 1].clone(), None),
+    };
+    let question = column.and_then(|c| c.question.as_deref());
+    let decision = set_decision_value(existing, key.trim(), value, feedback, question);
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let content = serde_json::to_string_pretty(&decision).map_err(std::io::Error::other)?;
+    tokio::fs::write(&path, content).await?;
+    Ok(decision)
+}
+
+/// Delete every decision file of a workspace.
+/// Called after a transition so the next column starts with a clean slate.
+pub async fn delete_decision_files(workspace: &Workspace) {
+    let repo_paths = repo_decision_paths(workspace).await;
+    let paths = primary_paths(workspace)
+        .into_iter()
+        .chain(repo_paths.into_iter().map(|(_, path)| path));
+    for path in paths {
+        if path.exists()
+            && let Err(e) = tokio::fs::remove_file(&path).await
+        {
+            tracing::warn!("Failed to delete decision file at {:?}: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn single_decision_is_used_as_is() {
+        let decision = json!({ "answer": "approve" });
+        assert_eq!(
+            merge_decisions(Some(decision.clone()), vec![]),
+            Some(decision.clone())
+        );
+        assert_eq!(
+            merge_decisions(None, vec![("api".to_string(), decision.clone())]),
+            Some(decision)
+        );
+        assert_eq!(merge_decisions(None, vec![]), None);
+    }
+
+    #[test]
+    fn repo_decisions_are_nested_by_repo() {
+        let merged = merge_decisions(
+            None,
+            vec![
+                ("api".to_string(), json!({ "answer": "approve" })),
+                ("web".to_string(), json!({ "answer": "reject" })),
+            ],
+        )
+        .unwrap();
+        assert_eq!(merged["answer"], "approve");
+        assert_eq!(merged["repos"]["api"]["answer"], "approve");
+        assert_eq!(merged["repos"]["web"]["answer"], "reject");
+
+        // The workspace-level decision takes precedence over the repos'
+        let merged = merge_decisions(
+            Some(json!({ "answer": "defer" })),
+            vec![("web".to_string(), json!({ "answer": "reject" }))],
+        )
+        .unwrap();
+        assert_eq!(merged["answer"], "defer");
+        assert_eq!(merged["repos"]["web"]["answer"], "reject");
+    }
+
+    #[test]
+    fn answers_must_be_column_options() {
+        let options = vec!["approve".to_string(), "reject".to_string()];
+        assert!(check_decision_value("answer", &json!("approve"), &options).is_ok());
+        assert!(check_decision_value("answer", &json!("maybe"), &options).is_err());
+        assert!(check_decision_value("answer", &json!(true), &options).is_err());
+        // Other keys and columns without options accept any value
+        assert!(check_decision_value("coverage", &json!(80), &options).is_ok());
+        assert!(check_decision_value("answer", &json!("maybe"), &[]).is_ok());
+        assert!(check_decision_value("repos", &json!({}), &options).is_err());
+        assert!(check_decision_value(" ", &json!(1), &options).is_err());
+    }
+
+    #[test]
+    fn set_value_merges_keys_and_appends_feedback() {
+        let decision = set_decision_value(
+            Some(json!({ "coverage": 85, "feedback": "Missing tests" })),
+            "answer",
+            json!("reject"),
+            Some("Error handling is incomplete"),
+            Some("Is the change ready?"),
+        );
+        assert_eq!(
+            decision,
+            json!({
+                "coverage": 85,
+                "question": "Is the change ready?",
+                "answer": "reject",
+                "feedback": ["Missing tests", "Error handling is incomplete"],
+            })
+        );
+
+        let decision = set_decision_value(None, "coverage", json!(90), None, Some("Ready?"));
+        assert_eq!(decision, json!({ "coverage": 90 }));
+    }
+}
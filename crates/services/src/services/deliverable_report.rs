@@ -0,0 +1,476 @@
+//! Deliverable reports: what an execution delivered against what its column asked for.
+//!
+//! A column's deliverable text, question and answer options set out what the agent
+//! should produce. The report turns them into requirements and checks each one
+//! against the execution's transcript and workspace: the question must be answered
+//! with one of the options, files the deliverable names (`docs/plan.md`) must have
+//! been written or exist, and when the deliverable mentions tests, the last test
+//! command the agent ran must have passed.
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_deliverable_report::{
+        DeliverableRequirement, DeliverableRequirementKind, ExecutionDeliverableReport,
+    },
+    execution_process::ExecutionProcess,
+    kanban_column::KanbanColumn,
+    task_event::{TaskEvent, TaskEventType},
+};
+use executors::logs::{ActionType, CommandExitStatus, FileChange, NormalizedEntryType, ToolStatus};
+use serde_json::Value;
+use sqlx::PgPool;
+use utils::log_msg::LogMsg;
+use uuid::Uuid;
+
+use super::{
+    decision_file::{read_decision_file, repo_dirs},
+    log_export::{TranscriptEntry, transcript_entries},
+    workflow_lint::answer_options,
+};
+
+const DECISION_FILE: &str = "decision.json";
+
+/// Commands that run a project's test suite
+const TEST_COMMANDS: &[&str] = &[
+    "cargo test",
+    "cargo nextest",
+    "npm test",
+    "npm run test",
+    "pnpm test",
+    "pnpm run test",
+    "yarn test",
+    "bun test",
+    "pytest",
+    "go test",
+    "jest",
+    "vitest",
+    "mvn test",
+    "gradle test",
+    "make test",
+    "rspec",
+];
+
+/// A test command from the transcript; `passed` is `None` when its outcome wasn't reported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestRun {
+    pub command: String,
+    pub passed: Option<bool>,
+}
+
+/// What the agent did during the execution, as far as the transcript shows
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptFacts {
+    /// Paths of files the agent wrote or edited
+    pub edited_paths: Vec<String>,
+    pub test_runs: Vec<TestRun>,
+    /// Last decision file the agent wrote in full
+    pub decision: Option<Value>,
+}
+
+pub fn is_test_command(command: &str) -> bool {
+    let command = command.to_lowercase();
+    TEST_COMMANDS.iter().any(|test| command.contains(test))
+}
+
+/// Whether `edited` is `required`, possibly given as an absolute or repo-prefixed path
+fn same_file(edited: &str, required: &str) -> bool {
+    let edited = edited.trim_start_matches("./");
+    edited == required || edited.ends_with(&format!("/{required}"))
+}
+
+fn command_passed(result: Option<&CommandExitStatus>, status: &ToolStatus) -> Option<bool> {
+    match result {
+        Some(CommandExitStatus::ExitCode { code }) => Some(*code == 0),
+        Some(CommandExitStatus::Success { success }) => Some(*success),
+        None => match status {
+            ToolStatus::Success => Some(true),
+            ToolStatus::Failed | ToolStatus::TimedOut | ToolStatus::Denied { .. } => Some(false),
+            ToolStatus::Created | ToolStatus::PendingApproval { .. } => None,
+        },
+    }
+}
+
+pub fn transcript_facts(entries: &[TranscriptEntry]) -> TranscriptFacts {
+    let mut facts = TranscriptFacts::default();
+    for entry in entries {
+        let TranscriptEntry::Entry(entry) = entry else {
+            continue;
+        };
+        let NormalizedEntryType::ToolUse {
+            action_type,
+            status,
+            ..
+        } = &entry.entry_type
+        else {
+            continue;
+        };
+        match action_type {
+            ActionType::FileEdit { path, changes } => {
+                if path.ends_with(DECISION_FILE) {
+                    let written = changes.iter().rev().find_map(|change| match change {
+                        FileChange::Write { content } => serde_json::from_str(content).ok(),
+                        _ => None,
+                    });
+                    if written.is_some() {
+                        facts.decision = written;
+                    }
+                } else if !facts.edited_paths.contains(path) {
+                    facts.edited_paths.push(path.clone());
+                }
+            }
+            ActionType::CommandRun { command, result } if is_test_command(command) => {
+                facts.test_runs.push(TestRun {
+                    command: command.clone(),
+                    passed: command_passed(
+                        result.as_ref().and_then(|r| r.exit_status.as_ref()),
+                        status,
+                    ),
+                });
+            }
+            _ => {}
+        }
+    }
+    facts
+}
+
+/// Whether a word from a deliverable looks like a file path, e.g. `docs/plan.md`
+fn is_path_like(word: &str, bare: bool) -> bool {
+    if word.is_empty() || word.contains(char::is_whitespace) || word.contains("://") {
+        return false;
+    }
+    if word.contains(['*', '?', '{', '$', '<']) {
+        return false;
+    }
+    let file_name = word.rsplit('/').next().unwrap_or(word);
+    let has_extension = file_name.rsplit_once('.').is_some_and(|(_, ext)| {
+        ext.len() <= 8
+            && ext.starts_with(|c: char| c.is_ascii_alphabetic())
+            && ext.chars().all(|c| c.is_ascii_alphanumeric())
+    });
+    // Bare words need a directory too, so prose such as "e.g." isn't taken for a file
+    has_extension && (!bare || word.contains('/'))
+}
+
+/// Files named in a deliverable, in backticks or as bare paths with a directory
+pub fn required_files(deliverable: &str) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    let mut push = |word: &str| {
+        let word = word.trim_start_matches("./").to_string();
+        if !word.ends_with(DECISION_FILE) && !files.contains(&word) {
+            files.push(word);
+        }
+    };
+
+    for (i, span) in deliverable.split('`').enumerate() {
+        if i % 2 == 1 {
+            if is_path_like(span.trim(), false) {
+                push(span.trim());
+            }
+            continue;
+        }
+        for word in span.split_whitespace() {
+            let word = word.trim_matches(|c: char| {
+                matches!(c, '.' | ',' | ';' | ':' | '(' | ')' | '"' | '\'' | '!' | '?')
+            });
+            if is_path_like(word, true) {
+                push(word);
+            }
+        }
+    }
+    files
+}
+
+/// Whether a deliverable asks for tests to be written or run
+pub fn asks_for_tests(deliverable: &str) -> bool {
+    deliverable
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .any(|word| {
+            matches!(
+                word.to_lowercase().as_str(),
+                "test" | "tests" | "testing" | "tested"
+            )
+        })
+}
+
+/// The column a task was in when an execution started; `events` are newest first
+pub fn run_column_id(
+    events: &[TaskEvent],
+    started_at: DateTime<Utc>,
+    current_column_id: Option<Uuid>,
+) -> Option<Uuid> {
+    events
+        .iter()
+        .filter(|e| e.event_type == TaskEventType::ColumnEnter && e.created_at <= started_at)
+        .find_map(|e| e.to_column_id)
+        .or(current_column_id)
+}
+
+fn decision_requirement(
+    question: &str,
+    options: &[String],
+    decision: Option<&Value>,
+) -> DeliverableRequirement {
+    let requirement = if options.is_empty() {
+        format!("Answer \"{question}\"")
+    } else {
+        format!("Answer \"{question}\" with one of: {}", options.join(", "))
+    };
+    let answer = decision.and_then(|d| d.get("answer")).and_then(Value::as_str);
+    let (satisfied, evidence) = match answer {
+        Some(answer) if options.is_empty() || options.iter().any(|o| o == answer) => {
+            (true, format!("Answered `{answer}`"))
+        }
+        Some(answer) => (false, format!("`{answer}` is not an answer option")),
+        None => (false, "No answer in the decision file".to_string()),
+    };
+    DeliverableRequirement {
+        kind: DeliverableRequirementKind::Decision,
+        requirement,
+        satisfied,
+        evidence: Some(evidence),
+    }
+}
+
+fn file_requirement(
+    file: &str,
+    facts: &TranscriptFacts,
+    exists: &dyn Fn(&str) -> bool,
+) -> DeliverableRequirement {
+    let (satisfied, evidence) = match facts.edited_paths.iter().find(|p| same_file(p, file)) {
+        Some(path) => (true, Some(format!("Written by the agent ({path})"))),
+        None if exists(file) => (true, Some("Exists in the workspace".to_string())),
+        None => (false, Some("Not written and not found in the workspace".to_string())),
+    };
+    DeliverableRequirement {
+        kind: DeliverableRequirementKind::RequiredFile,
+        requirement: format!("Produce `{file}`"),
+        satisfied,
+        evidence,
+    }
+}
+
+fn tests_requirement(facts: &TranscriptFacts) -> DeliverableRequirement {
+    let (satisfied, evidence) = match facts.test_runs.last() {
+        Some(TestRun {
+            command,
+            passed: Some(true),
+        }) => (true, format!("`{command}` passed")),
+        Some(TestRun {
+            command,
+            passed: Some(false),
+        }) => (false, format!("`{command}` failed")),
+        Some(TestRun { command, .. }) => (false, format!("`{command}` reported no result")),
+        None => (false, "No test command in the transcript".to_string()),
+    };
+    DeliverableRequirement {
+        kind: DeliverableRequirementKind::Tests,
+        requirement: "Run the tests and have them pass".to_string(),
+        satisfied,
+        evidence: Some(evidence),
+    }
+}
+
+/// Requirements of a column's deliverable, checked against an execution.
+///
+/// `exists` tells whether a required file is present in the workspace, for files
+/// the transcript doesn't show the agent writing.
+pub fn check_requirements(
+    column: &KanbanColumn,
+    decision: Option<&Value>,
+    facts: &TranscriptFacts,
+    exists: &dyn Fn(&str) -> bool,
+) -> Vec<DeliverableRequirement> {
+    let mut requirements = Vec::new();
+    if let Some(question) = column.question.as_deref().filter(|q| !q.trim().is_empty()) {
+        requirements.push(decision_requirement(
+            question,
+            &answer_options(column),
+            decision,
+        ));
+    }
+    if let Some(deliverable) = column.deliverable.as_deref() {
+        for file in required_files(deliverable) {
+            requirements.push(file_requirement(&file, facts, exists));
+        }
+        if asks_for_tests(deliverable) {
+            requirements.push(tests_requirement(facts));
+        }
+    }
+    requirements
+}
+
+/// Build and store the deliverable report of a coding agent execution.
+///
+/// Returns `None` when the column the execution ran in can't be determined.
+pub async fn generate(
+    pool: &PgPool,
+    execution_process: &ExecutionProcess,
+    history: &[LogMsg],
+) -> Result<Option<ExecutionDeliverableReport>, sqlx::Error> {
+    let ctx = ExecutionProcess::load_context(pool, execution_process.id).await?;
+    let events = TaskEvent::find_by_task_id(pool, ctx.task.id).await?;
+    let Some(column_id) = run_column_id(&events, execution_process.started_at, ctx.task.column_id)
+    else {
+        return Ok(None);
+    };
+    let Some(column) = KanbanColumn::find_by_id(pool, column_id).await? else {
+        return Ok(None);
+    };
+
+    let facts = transcript_facts(&transcript_entries(history));
+
+    // Decision files are deleted once the task moves on; after that the answer
+    // survives in the task's workflow decisions, keyed by column slug
+    let recorded = ctx
+        .task
+        .workflow_decisions
+        .as_ref()
+        .and_then(|d| d.get(&column.slug))
+        .cloned();
+    let decision = match (facts.decision.clone(), recorded) {
+        (Some(decision), _) => Some(decision),
+        (None, Some(recorded)) => Some(recorded),
+        (None, None) if ctx.task.column_id == Some(column.id) => {
+            read_decision_file(&ctx.workspace).await
+        }
+        (None, None) => None,
+    };
+
+    let mut roots = Vec::new();
+    if let Some(worktree_path) = ctx.workspace.container_ref.as_deref() {
+        let root = Path::new(worktree_path);
+        roots.push(root.to_path_buf());
+        roots.extend(repo_dirs(root).await.into_iter().map(|(_, dir)| dir));
+    }
+    let exists = |file: &str| roots.iter().any(|root| root.join(file).exists());
+
+    let requirements = check_requirements(&column, decision.as_ref(), &facts, &exists);
+    ExecutionDeliverableReport::upsert(pool, execution_process.id, Some(column.id), &requirements)
+        .await
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::logs::{CommandRunResult, NormalizedEntry};
+    use serde_json::json;
+
+    use super::*;
+
+    fn tool_use(action_type: ActionType, status: ToolStatus) -> TranscriptEntry {
+        TranscriptEntry::Entry(NormalizedEntry::new(
+            None,
+            NormalizedEntryType::ToolUse {
+                tool_name: "tool".to_string(),
+                action_type,
+                status,
+            },
+            String::new(),
+            None,
+        ))
+    }
+
+    fn column(deliverable: &str, question: Option<&str>, options: Option<&str>) -> KanbanColumn {
+        KanbanColumn {
+            id: Uuid::new_v4(),
+            board_id: Uuid::new_v4(),
+            name: "Review".to_string(),
+            slug: "review".to_string(),
+            position: 0,
+            color: None,
+            is_initial: false,
+            is_terminal: false,
+            starts_workflow: false,
+            status: db::models::task::TaskStatus::InProgress,
+            agent_id: None,
+            deliverable: Some(deliverable.to_string()),
+            question: question.map(str::to_string),
+            answer_options: options.map(str::to_string),
+            read_only: false,
+            plan_only: false,
+            dry_run: false,
+            security_scanners: Vec::new(),
+            checklist: Vec::new(),
+            on_enter_script: None,
+            on_exit_script: None,
+            warm_up: false,
+            response_target_mins: None,
+            prompt_sections: Vec::new(),
+            executor_variant: None,
+            is_template: false,
+            template_group_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn files_and_tests_are_read_from_deliverable_text() {
+        let deliverable = "Write the plan to `PLAN.md` and notes in docs/notes.md, e.g. risks. \
+                           Then run `cargo test` and write .vibe/decision.json.";
+        assert_eq!(required_files(deliverable), vec!["PLAN.md", "docs/notes.md"]);
+        assert!(asks_for_tests(deliverable));
+        assert!(!asks_for_tests("Write a summary of the latest changes"));
+    }
+
+    #[test]
+    fn requirements_are_checked_against_transcript() {
+        let entries = vec![
+            tool_use(
+                ActionType::FileEdit {
+                    path: "/tmp/worktree/api/PLAN.md".to_string(),
+                    changes: vec![FileChange::Write {
+                        content: "# Plan".to_string(),
+                    }],
+                },
+                ToolStatus::Success,
+            ),
+            tool_use(
+                ActionType::FileEdit {
+                    path: ".vibe/decision.json".to_string(),
+                    changes: vec![FileChange::Write {
+                        content: r#"{"answer": "maybe"}"#.to_string(),
+                    }],
+                },
+                ToolStatus::Success,
+            ),
+            tool_use(
+                ActionType::CommandRun {
+                    command: "cargo test --workspace".to_string(),
+                    result: Some(CommandRunResult {
+                        exit_status: Some(CommandExitStatus::ExitCode { code: 101 }),
+                        output: None,
+                    }),
+                },
+                ToolStatus::Success,
+            ),
+        ];
+        let facts = transcript_facts(&entries);
+        assert_eq!(facts.decision, Some(json!({ "answer": "maybe" })));
+
+        let column = column(
+            "Write `PLAN.md` and `docs/notes.md`, with tests passing",
+            Some("Is it ready?"),
+            Some(r#"["yes", "no"]"#),
+        );
+        let no_files = |_: &str| false;
+        let requirements =
+            check_requirements(&column, facts.decision.as_ref(), &facts, &no_files);
+        let satisfied: Vec<_> = requirements.iter().map(|r| (r.kind, r.satisfied)).collect();
+        assert_eq!(
+            satisfied,
+            vec![
+                (DeliverableRequirementKind::Decision, false),
+                (DeliverableRequirementKind::RequiredFile, true),
+                (DeliverableRequirementKind::RequiredFile, false),
+                (DeliverableRequirementKind::Tests, false),
+            ]
+        );
+        assert_eq!(
+            requirements[3].evidence.as_deref(),
+            Some("`cargo test --workspace` failed")
+        );
+    }
+}
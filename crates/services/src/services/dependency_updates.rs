@@ -0,0 +1,414 @@
+use std::{path::Path, process::Stdio, sync::LazyLock, time::Duration};
+
+use db::{
+    DBService,
+    models::{
+        dependency_update_schedule::{DependencyEcosystem, DependencyUpdateSchedule},
+        kanban_column::KanbanColumn,
+        project::Project,
+        project_repo::ProjectRepo,
+        task::{CreateTask, Task},
+        task_event::{ActorType, CreateTaskEvent, TaskEvent},
+    },
+};
+use regex::Regex;
+use serde_json::Value;
+use sqlx::PgPool;
+use tokio::{process::Command, time::interval};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+/// Dependency checks that don't finish within this time are skipped
+const CHECK_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// `cargo update --dry-run` lines, e.g. `    Updating serde v1.0.190 -> v1.0.193`
+static CARGO_UPDATE_LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*Updating (\S+) v(\S+) -> v(\S+)").unwrap());
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutdatedDependency {
+    pub name: String,
+    pub current: String,
+    pub latest: String,
+}
+
+/// Compatible (semver) updates available according to `cargo update --dry-run`
+pub fn parse_cargo_update_dry_run(output: &str) -> Vec<OutdatedDependency> {
+    output
+        .lines()
+        .filter_map(|line| CARGO_UPDATE_LINE.captures(line))
+        .map(|c| OutdatedDependency {
+            name: c[1].to_string(),
+            current: c[2].to_string(),
+            latest: c[3].to_string(),
+        })
+        .collect()
+}
+
+/// Packages reported by `npm outdated --json` whose installed version isn't the latest
+pub fn parse_npm_outdated(output: &str) -> Result<Vec<OutdatedDependency>, serde_json::Error> {
+    if output.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let report: Value = serde_json::from_str(output)?;
+    let mut outdated: Vec<OutdatedDependency> = report
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, info)| {
+            let latest = info.get("latest")?.as_str()?.to_string();
+            let current = info
+                .get("current")
+                .and_then(Value::as_str)
+                .unwrap_or("not installed")
+                .to_string();
+            (current != latest).then(|| OutdatedDependency {
+                name: name.clone(),
+                current,
+                latest,
+            })
+        })
+        .collect();
+    outdated.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(outdated)
+}
+
+async fn run_check(dir: &Path, program: &str, args: &[&str]) -> Result<(String, String), String> {
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    match tokio::time::timeout(CHECK_TIMEOUT, command.output()).await {
+        // Non-zero exits are normal (`npm outdated` exits 1 when anything is outdated)
+        Ok(Ok(output)) => Ok((
+            String::from_utf8_lossy(&output.stdout).to_string(),
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        )),
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(format!("{program} is not installed"))
+        }
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(format!("timed out after {}s", CHECK_TIMEOUT.as_secs())),
+    }
+}
+
+/// Detect outdated dependencies of one ecosystem in a repo.
+/// Repos without the ecosystem's manifest report nothing.
+pub async fn detect_outdated(
+    repo_path: &Path,
+    ecosystem: DependencyEcosystem,
+) -> Result<Vec<OutdatedDependency>, String> {
+    match ecosystem {
+        DependencyEcosystem::Cargo => {
+            if !repo_path.join("Cargo.lock").exists() {
+                return Ok(Vec::new());
+            }
+            // Cargo reports the planned updates on stderr
+            let (_, stderr) = run_check(repo_path, "cargo", &["update", "--dry-run"]).await?;
+            Ok(parse_cargo_update_dry_run(&stderr))
+        }
+        DependencyEcosystem::Npm => {
+            if !repo_path.join("package.json").exists() {
+                return Ok(Vec::new());
+            }
+            let (stdout, _) = run_check(repo_path, "npm", &["outdated", "--json"]).await?;
+            parse_npm_outdated(&stdout).map_err(|e| format!("invalid npm outdated output: {e}"))
+        }
+    }
+}
+
+/// Stable id of one package's update, independent of its versions and of how a run
+/// batches the updates
+pub fn source_id(ecosystem: DependencyEcosystem, repo_id: Uuid, package: &str) -> String {
+    format!("{}:{}:{}", ecosystem.as_str(), repo_id, package)
+}
+
+/// Outdated packages that no unfinished update task covers yet
+pub fn without_open_sources(
+    outdated: Vec<OutdatedDependency>,
+    ecosystem: DependencyEcosystem,
+    repo_id: Uuid,
+    open_sources: &[String],
+) -> Vec<OutdatedDependency> {
+    outdated
+        .into_iter()
+        .filter(|dep| !open_sources.contains(&source_id(ecosystem, repo_id, &dep.name)))
+        .collect()
+}
+
+pub fn batch_title(
+    ecosystem: DependencyEcosystem,
+    repo_name: &str,
+    batch: usize,
+    batches: usize,
+) -> String {
+    let title = format!(
+        "Update {} dependencies in {}",
+        ecosystem.as_str(),
+        repo_name
+    );
+    if batches > 1 {
+        format!("{} ({}/{})", title, batch, batches)
+    } else {
+        title
+    }
+}
+
+pub fn batch_description(
+    ecosystem: DependencyEcosystem,
+    repo_name: &str,
+    deps: &[OutdatedDependency],
+) -> String {
+    let mut description = format!(
+        "Scheduled dependency update for repository `{}`.\n\n\
+         | Package | Current | Latest |\n|---|---|---|\n",
+        repo_name
+    );
+    for dep in deps {
+        description.push_str(&format!(
+            "| {} | {} | {} |\n",
+            dep.name, dep.current, dep.latest
+        ));
+    }
+    let how = match ecosystem {
+        DependencyEcosystem::Cargo => {
+            "Update these crates with `cargo update -p <name>` (compatible versions only), \
+             then build and run the test suite."
+        }
+        DependencyEcosystem::Npm => {
+            "Update these packages with `npm install <name>@<latest>`, checking changelogs for \
+             breaking changes on major version bumps, then build and run the test suite."
+        }
+    };
+    description.push('\n');
+    description.push_str(how);
+    description
+}
+
+/// Run a schedule once: detect outdated dependencies in every project repo and
+/// create one task per batch in the workflow start column of the schedule's board.
+/// Returns the tasks that were created.
+pub async fn run_schedule(
+    pool: &PgPool,
+    schedule: &DependencyUpdateSchedule,
+) -> Result<Vec<Task>, sqlx::Error> {
+    let project = Project::find_by_id(pool, schedule.project_id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    // Route through the configured board, falling back to the project's own board
+    let column = match schedule.board_id.or(project.board_id) {
+        Some(board_id) => match KanbanColumn::find_workflow_start(pool, board_id).await? {
+            Some(column) => Some(column),
+            None => KanbanColumn::find_initial(pool, board_id).await?,
+        },
+        None => None,
+    };
+    if column.is_none() {
+        warn!(
+            "No workflow start column for dependency updates in project {}; tasks will have no column",
+            project.id
+        );
+    }
+
+    let batch_size = schedule.batch_size.max(1) as usize;
+    let repos = ProjectRepo::find_repos_for_project(pool, project.id).await?;
+    let mut created = Vec::new();
+
+    for repo in &repos {
+        for ecosystem in schedule.get_ecosystems() {
+            let outdated = match detect_outdated(&repo.path, ecosystem).await {
+                Ok(outdated) => outdated,
+                Err(e) => {
+                    warn!(
+                        "Skipping {} dependency check in {}: {}",
+                        ecosystem.as_str(),
+                        repo.name,
+                        e
+                    );
+                    continue;
+                }
+            };
+            let sources: Vec<String> = outdated
+                .iter()
+                .map(|dep| source_id(ecosystem, repo.id, &dep.name))
+                .collect();
+            let open = DependencyUpdateSchedule::open_sources(pool, project.id, &sources).await?;
+            let found = outdated.len();
+            let outdated = without_open_sources(outdated, ecosystem, repo.id, &open);
+            if outdated.len() < found {
+                debug!(
+                    "{} {} update(s) in {} already have an open task",
+                    found - outdated.len(),
+                    ecosystem.as_str(),
+                    repo.name
+                );
+            }
+            if outdated.is_empty() {
+                continue;
+            }
+
+            let batches: Vec<&[OutdatedDependency]> = outdated.chunks(batch_size).collect();
+            for (i, deps) in batches.iter().enumerate() {
+                let title = batch_title(ecosystem, &repo.display_name, i + 1, batches.len());
+                let mut data = CreateTask::from_title_description(
+                    project.id,
+                    title,
+                    Some(batch_description(ecosystem, &repo.display_name, deps)),
+                );
+                if let Some(column) = &column {
+                    data.column_id = Some(column.id);
+                    data.status = Some(column.status.clone());
+                }
+                let task = Task::create(pool, &data, Uuid::new_v4()).await?;
+                let sources: Vec<String> = deps
+                    .iter()
+                    .map(|dep| source_id(ecosystem, repo.id, &dep.name))
+                    .collect();
+                DependencyUpdateSchedule::record_sources(pool, task.id, &sources).await?;
+
+                let event = CreateTaskEvent::task_created(task.id, ActorType::System, None);
+                if let Err(e) = TaskEvent::create(pool, &event).await {
+                    error!(
+                        "Failed to record task created event for task {}: {}",
+                        task.id, e
+                    );
+                }
+                created.push(task);
+            }
+        }
+    }
+
+    DependencyUpdateSchedule::mark_run(pool, project.id).await?;
+    info!(
+        "Dependency update run for project {} created {} task(s)",
+        project.id,
+        created.len()
+    );
+    Ok(created)
+}
+
+/// Background service that runs due dependency update schedules
+pub struct DependencyUpdateService {
+    db: DBService,
+    poll_interval: Duration,
+}
+
+impl DependencyUpdateService {
+    pub async fn spawn(db: DBService) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            poll_interval: Duration::from_secs(15 * 60),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting dependency update scheduler with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+            let schedules = match DependencyUpdateSchedule::find_due(&self.db.pool).await {
+                Ok(schedules) => schedules,
+                Err(e) => {
+                    error!("Error loading dependency update schedules: {}", e);
+                    continue;
+                }
+            };
+            for schedule in schedules {
+                if let Err(e) = run_schedule(&self.db.pool, &schedule).await {
+                    error!(
+                        "Dependency update run failed for project {}: {}",
+                        schedule.project_id, e
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cargo_dry_run() {
+        let output = "    Updating crates.io index\n     Locking 2 packages to latest compatible versions\n    Updating serde v1.0.190 -> v1.0.193\n    Updating tokio v1.35.0 -> v1.35.1\nwarning: not updating lockfile due to dry run\n";
+        let deps = parse_cargo_update_dry_run(output);
+        assert_eq!(deps.len(), 2);
+        assert_eq!(
+            deps[0],
+            OutdatedDependency {
+                name: "serde".to_string(),
+                current: "1.0.190".to_string(),
+                latest: "1.0.193".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_npm_outdated() {
+        let output = r#"{
+            "react": {"current": "18.2.0", "wanted": "18.3.1", "latest": "19.0.0"},
+            "lodash": {"current": "4.17.21", "wanted": "4.17.21", "latest": "4.17.21"},
+            "vite": {"wanted": "5.4.0", "latest": "6.0.1"}
+        }"#;
+        let deps = parse_npm_outdated(output).unwrap();
+        let names: Vec<_> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["react", "vite"]);
+        assert_eq!(deps[1].current, "not installed");
+        assert!(parse_npm_outdated("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn skips_packages_with_an_open_task_whatever_their_batch() {
+        let repo_id = Uuid::new_v4();
+        let dep = |name: &str| OutdatedDependency {
+            name: name.to_string(),
+            current: "1.0.0".to_string(),
+            latest: "1.1.0".to_string(),
+        };
+        let open = vec![source_id(DependencyEcosystem::Cargo, repo_id, "serde")];
+        let pending = without_open_sources(
+            vec![dep("anyhow"), dep("serde"), dep("tokio")],
+            DependencyEcosystem::Cargo,
+            repo_id,
+            &open,
+        );
+        let names: Vec<_> = pending.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["anyhow", "tokio"]);
+
+        // Same package in another ecosystem or repo is a different update
+        let other_repo = without_open_sources(
+            vec![dep("serde")],
+            DependencyEcosystem::Cargo,
+            Uuid::new_v4(),
+            &open,
+        );
+        assert_eq!(other_repo.len(), 1);
+    }
+
+    #[test]
+    fn numbers_batches_only_when_split() {
+        assert_eq!(
+            batch_title(DependencyEcosystem::Cargo, "api", 1, 1),
+            "Update cargo dependencies in api"
+        );
+        assert_eq!(
+            batch_title(DependencyEcosystem::Npm, "web", 2, 3),
+            "Update npm dependencies in web (2/3)"
+        );
+    }
+}
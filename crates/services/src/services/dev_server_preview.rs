@@ -0,0 +1,180 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock},
+};
+
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use regex::Regex;
+use serde::Serialize;
+use tokio::{sync::RwLock, task::JoinHandle};
+use ts_rs::TS;
+use utils::{log_msg::LogMsg, msg_store::MsgStore};
+use uuid::Uuid;
+
+/// Give up scanning a dev server's output for a port after this many bytes
+const MAX_SCAN_BYTES: usize = 256 * 1024;
+
+static ANSI_ESCAPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]").unwrap());
+
+/// Local URLs printed by dev servers, e.g. `http://localhost:5173/`
+static LOCAL_URL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)https?://(?:localhost|127\.0\.0\.1|0\.0\.0\.0|\[::1?\]):(\d{2,5})").unwrap()
+});
+
+/// Plain-text announcements, e.g. `Listening on port 3000`
+static PORT_PHRASE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:listening|running|started|serving)\b[^\n]*?\bport\s*:?\s*(\d{2,5})")
+        .unwrap()
+});
+
+/// A running dev server's detected port, exposed through the preview proxy
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct DevServerPreview {
+    pub workspace_id: Uuid,
+    pub execution_process_id: Uuid,
+    pub port: u16,
+    /// Path of the reverse proxy for this workspace (`/api/preview/{workspace_id}/`)
+    pub proxy_path: String,
+    #[ts(type = "Date")]
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Registry of dev server ports keyed by workspace.
+///
+/// Ports are detected from the dev server's own output and dropped when its
+/// execution process exits. Only registered ports are ever proxied.
+#[derive(Clone, Default)]
+pub struct DevServerPreviews {
+    inner: Arc<RwLock<HashMap<Uuid, DevServerPreview>>>,
+}
+
+impl DevServerPreviews {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn get(&self, workspace_id: Uuid) -> Option<DevServerPreview> {
+        self.inner.read().await.get(&workspace_id).cloned()
+    }
+
+    pub async fn register(&self, workspace_id: Uuid, execution_process_id: Uuid, port: u16) {
+        tracing::info!(
+            "Dev server for workspace {} detected on port {}",
+            workspace_id,
+            port
+        );
+        self.inner.write().await.insert(
+            workspace_id,
+            DevServerPreview {
+                workspace_id,
+                execution_process_id,
+                port,
+                proxy_path: format!("/api/preview/{}/", workspace_id),
+                detected_at: Utc::now(),
+            },
+        );
+    }
+
+    /// Forget the port registered by an execution process (no-op if none)
+    pub async fn remove_execution(&self, execution_process_id: Uuid) {
+        self.inner
+            .write()
+            .await
+            .retain(|_, p| p.execution_process_id != execution_process_id);
+    }
+
+    /// Scan a dev server's stdout/stderr until a port is announced, then register it
+    pub fn watch(
+        &self,
+        workspace_id: Uuid,
+        execution_process_id: Uuid,
+        store: Arc<MsgStore>,
+    ) -> JoinHandle<()> {
+        let previews = self.clone();
+        tokio::spawn(async move {
+            let mut stream = store.history_plus_stream();
+            let mut pending = String::new();
+            let mut scanned = 0usize;
+
+            while let Some(Ok(msg)) = stream.next().await {
+                let chunk = match msg {
+                    LogMsg::Stdout(s) | LogMsg::Stderr(s) => s,
+                    LogMsg::Finished => break,
+                    _ => continue,
+                };
+                scanned += chunk.len();
+                pending.push_str(&chunk);
+
+                // Only inspect complete lines; keep the tail for the next chunk
+                let Some(end) = pending.rfind('\n') else {
+                    if scanned > MAX_SCAN_BYTES {
+                        break;
+                    }
+                    continue;
+                };
+                if let Some(port) = detect_port(&pending[..end]) {
+                    previews
+                        .register(workspace_id, execution_process_id, port)
+                        .await;
+                    return;
+                }
+                pending.drain(..=end);
+
+                if scanned > MAX_SCAN_BYTES {
+                    break;
+                }
+            }
+
+            if let Some(port) = detect_port(&pending) {
+                previews
+                    .register(workspace_id, execution_process_id, port)
+                    .await;
+            } else {
+                tracing::debug!(
+                    "No port detected in dev server output for workspace {}",
+                    workspace_id
+                );
+            }
+        })
+    }
+}
+
+/// Find the first local port announced in dev server output
+pub fn detect_port(output: &str) -> Option<u16> {
+    let output = ANSI_ESCAPE.replace_all(output, "");
+    output.lines().find_map(|line| {
+        LOCAL_URL
+            .captures(line)
+            .or_else(|| PORT_PHRASE.captures(line))
+            .and_then(|c| c[1].parse::<u16>().ok())
+            .filter(|port| *port != 0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_vite_url_with_colors() {
+        let output = "\n  VITE v5.0.0  ready in 300 ms\n\n  \u{1b}[32m➜\u{1b}[39m  Local:   http://localhost:\u{1b}[1m5173\u{1b}[22m/\n";
+        assert_eq!(detect_port(output), Some(5173));
+    }
+
+    #[test]
+    fn detects_port_phrase() {
+        assert_eq!(detect_port("Server listening on port 3000"), Some(3000));
+        assert_eq!(
+            detect_port("> next dev\n  - Local: http://127.0.0.1:4000"),
+            Some(4000)
+        );
+    }
+
+    #[test]
+    fn ignores_unrelated_numbers() {
+        assert_eq!(detect_port("Compiled 1234 modules in 5000ms"), None);
+        assert_eq!(detect_port("See https://example.com:8443/docs"), None);
+    }
+}
@@ -0,0 +1,341 @@
+//! Email notifications over SMTP.
+//!
+//! Addresses listed in `email_notification_preferences` can get escalations and failed
+//! runs emailed right away, and a daily digest of tasks that entered review, runs that
+//! failed and approvals still pending. SMTP is configured through the environment:
+//! `VK_SMTP_HOST`, `VK_SMTP_PORT`, `VK_SMTP_USERNAME`, `VK_SMTP_PASSWORD`, `VK_SMTP_FROM`
+//! and `VK_SMTP_TLS` (`starttls` by default, `tls` or `none`). Without a host, no email
+//! is sent.
+
+use std::{fmt, time::Duration};
+
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::{
+        email_notification_preference::{DigestEntry, EmailNotificationPreference},
+        execution_process::{ExecutionContext, ExecutionProcessRunReason},
+    },
+};
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, header::ContentType},
+    transport::smtp::authentication::Credentials,
+};
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+use super::notification::NotificationService;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SmtpTls {
+    StartTls,
+    Tls,
+    None,
+}
+
+/// Sends plain-text emails through the configured SMTP server
+#[derive(Clone)]
+pub struct EmailSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl fmt::Debug for EmailSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmailSender")
+            .field("from", &self.from.to_string())
+            .finish_non_exhaustive()
+    }
+}
+
+impl EmailSender {
+    /// Sender built from the `VK_SMTP_*` variables, if a host is set
+    pub fn from_env() -> Option<Self> {
+        let host = std::env::var("VK_SMTP_HOST").ok().filter(|h| !h.is_empty())?;
+        match Self::build(&host) {
+            Ok(sender) => Some(sender),
+            Err(e) => {
+                error!("Email notifications disabled, invalid SMTP settings: {}", e);
+                None
+            }
+        }
+    }
+
+    fn build(host: &str) -> Result<Self, String> {
+        let tls = match std::env::var("VK_SMTP_TLS").as_deref() {
+            Ok("tls") => SmtpTls::Tls,
+            Ok("none") => SmtpTls::None,
+            Ok("starttls") | Err(_) => SmtpTls::StartTls,
+            Ok(other) => return Err(format!("unknown VK_SMTP_TLS value '{other}'")),
+        };
+        let mut builder = match tls {
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+                .map_err(|e| e.to_string())?,
+            SmtpTls::Tls => {
+                AsyncSmtpTransport::<Tokio1Executor>::relay(host).map_err(|e| e.to_string())?
+            }
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+        };
+        if let Ok(port) = std::env::var("VK_SMTP_PORT") {
+            let port = port
+                .parse::<u16>()
+                .map_err(|_| format!("invalid VK_SMTP_PORT '{port}'"))?;
+            builder = builder.port(port);
+        }
+        if let Ok(username) = std::env::var("VK_SMTP_USERNAME") {
+            let password = std::env::var("VK_SMTP_PASSWORD").unwrap_or_default();
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        let from = std::env::var("VK_SMTP_FROM")
+            .unwrap_or_else(|_| format!("Vibe Kanban <vibe-kanban@{host}>"));
+        let from = from
+            .parse::<Mailbox>()
+            .map_err(|e| format!("invalid VK_SMTP_FROM '{from}': {e}"))?;
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+
+    pub async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), String> {
+        let to = to
+            .parse::<Mailbox>()
+            .map_err(|e| format!("invalid address '{to}': {e}"))?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(body.to_string())
+            .map_err(|e| e.to_string())?;
+        self.transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+fn run_reason_label(run_reason: &ExecutionProcessRunReason) -> &'static str {
+    match run_reason {
+        ExecutionProcessRunReason::SetupScript => "Setup script",
+        ExecutionProcessRunReason::CleanupScript => "Cleanup script",
+        ExecutionProcessRunReason::CodingAgent => "Coding agent",
+        ExecutionProcessRunReason::DevServer => "Dev server",
+        ExecutionProcessRunReason::ManualRun => "Manual run",
+    }
+}
+
+/// Subject and body of the email sent when a run fails
+pub fn failure_email(ctx: &ExecutionContext) -> (String, String) {
+    let subject = format!("[{}] {} failed", ctx.project.name, ctx.task.title);
+    let mut body = format!(
+        "{} for task \"{}\" in project {} failed",
+        run_reason_label(&ctx.execution_process.run_reason),
+        ctx.task.title,
+        ctx.project.name
+    );
+    if let Some(code) = ctx.execution_process.exit_code {
+        body.push_str(&format!(" with exit code {code}"));
+    }
+    body.push_str(".\n");
+    (subject, body)
+}
+
+/// Subject and body of the email sent when a task takes an escalation transition
+pub fn escalation_email(
+    ctx: &ExecutionContext,
+    from_column: &str,
+    to_column: &str,
+) -> (String, String) {
+    let subject = format!("[{}] {} escalated", ctx.project.name, ctx.task.title);
+    let body = format!(
+        "Task \"{}\" in project {} reached its failure limit in '{}' and was escalated \
+         to '{}'.\n",
+        ctx.task.title, ctx.project.name, from_column, to_column
+    );
+    (subject, body)
+}
+
+/// Subject and body of a digest, or `None` when there is nothing to report
+pub fn render_digest(entries: &[DigestEntry], now: DateTime<Utc>) -> Option<(String, String)> {
+    if entries.is_empty() {
+        return None;
+    }
+    let sections = [
+        ("entered_review", "Entered review"),
+        ("run_failed", "Failed runs"),
+        ("pending_approval", "Waiting for you"),
+    ];
+    let mut body = String::new();
+    for (kind, heading) in sections {
+        let lines: Vec<String> = entries
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| match &e.detail {
+                Some(detail) => format!("- [{}] {} ({})", e.project_name, e.task_title, detail),
+                None => format!("- [{}] {}", e.project_name, e.task_title),
+            })
+            .collect();
+        if lines.is_empty() {
+            continue;
+        }
+        body.push_str(&format!("{heading} ({})\n{}\n\n", lines.len(), lines.join("\n")));
+    }
+    let subject = format!(
+        "Vibe Kanban digest for {}: {} update(s)",
+        now.format("%Y-%m-%d"),
+        entries.len()
+    );
+    Some((subject, body.trim_end().to_string() + "\n"))
+}
+
+/// Sends each address its daily digest once its hour has come
+pub struct EmailDigestService {
+    db: DBService,
+    notifications: NotificationService,
+    poll_interval: Duration,
+}
+
+impl EmailDigestService {
+    pub async fn spawn(
+        db: DBService,
+        notifications: NotificationService,
+    ) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            notifications,
+            poll_interval: Duration::from_secs(300),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        if !self.notifications.email_enabled() {
+            info!("SMTP not configured; email digests are disabled");
+            return;
+        }
+        info!(
+            "Starting email digest service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            self.send_due_digests().await;
+        }
+    }
+
+    async fn send_due_digests(&self) {
+        let pool = &self.db.pool;
+        let preferences = match EmailNotificationPreference::find_all(pool).await {
+            Ok(preferences) => preferences,
+            Err(e) => {
+                error!("Failed to load email notification preferences: {}", e);
+                return;
+            }
+        };
+
+        let now = Utc::now();
+        for preference in preferences.iter().filter(|p| p.digest_due(now)) {
+            let entries = match DigestEntry::find_since(pool, preference.digest_since(now)).await
+            {
+                Ok(entries) => entries,
+                Err(e) => {
+                    error!("Failed to collect digest for {}: {}", preference.email, e);
+                    continue;
+                }
+            };
+            if let Some((subject, body)) = render_digest(&entries, now)
+                && let Err(e) = self
+                    .notifications
+                    .send_email(&preference.email, &subject, &body)
+                    .await
+            {
+                warn!("Failed to send digest to {}: {}", preference.email, e);
+                continue;
+            }
+            // Empty digests count as sent so the next one covers just one day
+            if let Err(e) =
+                EmailNotificationPreference::mark_digest_sent(pool, preference.id, now).await
+            {
+                error!("Failed to record digest for {}: {}", preference.email, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn entry(kind: &str, title: &str, detail: Option<&str>) -> DigestEntry {
+        DigestEntry {
+            kind: kind.to_string(),
+            task_id: Uuid::new_v4(),
+            task_title: title.to_string(),
+            project_name: "web".to_string(),
+            detail: detail.map(str::to_string),
+            occurred_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn digest_groups_entries_by_kind() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 8, 0, 0).unwrap();
+        let entries = [
+            entry("run_failed", "Fix login", Some("coding agent")),
+            entry("entered_review", "Add search", Some("Review")),
+            entry("pending_approval", "Plan export", Some("plan review")),
+        ];
+
+        let (subject, body) = render_digest(&entries, now).unwrap();
+        assert_eq!(subject, "Vibe Kanban digest for 2026-03-02: 3 update(s)");
+        assert_eq!(
+            body,
+            "Entered review (1)\n- [web] Add search (Review)\n\n\
+             Failed runs (1)\n- [web] Fix login (coding agent)\n\n\
+             Waiting for you (1)\n- [web] Plan export (plan review)\n"
+        );
+    }
+
+    #[test]
+    fn empty_digest_is_not_sent() {
+        assert!(render_digest(&[], Utc::now()).is_none());
+    }
+
+    #[test]
+    fn digest_is_due_once_a_day_after_its_hour() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 9, 30, 0).unwrap();
+        let mut preference = EmailNotificationPreference {
+            id: Uuid::new_v4(),
+            email: "dev@example.com".to_string(),
+            name: None,
+            immediate_escalations: true,
+            immediate_failures: true,
+            digest_enabled: true,
+            digest_hour: 9,
+            last_digest_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        assert!(preference.digest_due(now));
+
+        preference.last_digest_at = Some(now);
+        assert!(!preference.digest_due(now + chrono::Duration::hours(2)));
+        assert!(preference.digest_due(now + chrono::Duration::days(1)));
+
+        preference.digest_hour = 10;
+        preference.last_digest_at = None;
+        assert!(!preference.digest_due(now));
+    }
+}
@@ -0,0 +1,188 @@
+//! Encryption-at-rest for per-project environment variables.
+//!
+//! Values are sealed with AES-256-GCM. The 32-byte key is read from
+//! `VK_ENV_VAULT_KEY` (base64) when set, otherwise from `env_vault.key` in the
+//! asset directory, which is generated on first use.
+
+use std::{collections::HashMap, io::Write, path::Path};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use db::models::project_env_var::ProjectEnvVar;
+use once_cell::sync::OnceCell;
+use ring::{
+    aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey},
+    rand::{SecureRandom, SystemRandom},
+};
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+const KEY_ENV_VAR: &str = "VK_ENV_VAULT_KEY";
+const KEY_LEN: usize = 32;
+
+static SHARED_VAULT: OnceCell<EnvVault> = OnceCell::new();
+
+#[derive(Debug, Error)]
+pub enum EnvVaultError {
+    #[error("Io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Invalid vault key: {0}")]
+    InvalidKey(String),
+    #[error("Failed to encrypt value")]
+    Encrypt,
+    #[error("Failed to decrypt value for '{0}'")]
+    Decrypt(String),
+}
+
+pub struct EnvVault {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl EnvVault {
+    /// Process-wide vault, loading (or generating) the key on first call.
+    pub fn shared() -> Result<&'static EnvVault, EnvVaultError> {
+        SHARED_VAULT.get_or_try_init(|| {
+            let key = match std::env::var(KEY_ENV_VAR) {
+                Ok(encoded) => decode_key(encoded.trim())?,
+                Err(_) => load_or_create_key_file(&utils::assets::env_vault_key_path())?,
+            };
+            Self::from_key(&key)
+        })
+    }
+
+    pub fn from_key(key: &[u8]) -> Result<Self, EnvVaultError> {
+        let unbound = UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| EnvVaultError::InvalidKey(format!("expected {KEY_LEN} bytes")))?;
+        Ok(Self {
+            key: LessSafeKey::new(unbound),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Encrypt a plaintext value. Returns `(ciphertext_b64, nonce_b64)`.
+    pub fn encrypt(&self, plaintext: &str) -> Result<(String, String), EnvVaultError> {
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce_bytes)
+            .map_err(|_| EnvVaultError::Encrypt)?;
+        let nonce = Nonce::assume_unique_for_key(nonce_bytes);
+
+        let mut in_out = plaintext.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(nonce, Aad::empty(), &mut in_out)
+            .map_err(|_| EnvVaultError::Encrypt)?;
+
+        Ok((BASE64.encode(in_out), BASE64.encode(nonce_bytes)))
+    }
+
+    /// Decrypt a stored env var back to plaintext
+    pub fn decrypt(&self, var: &ProjectEnvVar) -> Result<String, EnvVaultError> {
+        self.open(&var.key, &var.value_ciphertext, &var.value_nonce)
+    }
+
+    /// Decrypt a value sealed by [`EnvVault::encrypt`]; `name` identifies it in errors
+    pub fn open(&self, name: &str, ciphertext: &str, nonce: &str) -> Result<String, EnvVaultError> {
+        let err = || EnvVaultError::Decrypt(name.to_string());
+
+        let nonce_bytes: [u8; NONCE_LEN] = BASE64
+            .decode(nonce)
+            .map_err(|_| err())?
+            .try_into()
+            .map_err(|_| err())?;
+        let mut in_out = BASE64.decode(ciphertext).map_err(|_| err())?;
+
+        let plaintext = self
+            .key
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce_bytes),
+                Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| err())?;
+
+        String::from_utf8(plaintext.to_vec()).map_err(|_| err())
+    }
+
+    /// Load and decrypt every env var for a project.
+    /// Values that fail to decrypt (e.g. key rotated) are skipped with a warning.
+    pub async fn load_project_env(
+        &self,
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<HashMap<String, String>, EnvVaultError> {
+        let vars = ProjectEnvVar::find_by_project(pool, project_id).await?;
+        let mut env = HashMap::with_capacity(vars.len());
+        for var in vars {
+            match self.decrypt(&var) {
+                Ok(value) => {
+                    env.insert(var.key, value);
+                }
+                Err(e) => {
+                    tracing::warn!("Skipping env var for project {}: {}", project_id, e);
+                }
+            }
+        }
+        Ok(env)
+    }
+}
+
+/// Env var names must be shell-safe and may not shadow the runtime `VK_*` variables.
+pub fn validate_env_key(key: &str) -> Result<(), String> {
+    let mut chars = key.chars();
+    let valid_start = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+    if !valid_start || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!(
+            "Invalid env var name '{key}': use letters, digits and underscores, not starting with a digit"
+        ));
+    }
+    if key.starts_with("VK_") {
+        return Err(format!("Env var name '{key}' uses the reserved VK_ prefix"));
+    }
+    Ok(())
+}
+
+fn decode_key(encoded: &str) -> Result<Vec<u8>, EnvVaultError> {
+    let key = BASE64
+        .decode(encoded)
+        .map_err(|e| EnvVaultError::InvalidKey(e.to_string()))?;
+    if key.len() != KEY_LEN {
+        return Err(EnvVaultError::InvalidKey(format!(
+            "expected {KEY_LEN} bytes, got {}",
+            key.len()
+        )));
+    }
+    Ok(key)
+}
+
+fn load_or_create_key_file(path: &Path) -> Result<Vec<u8>, EnvVaultError> {
+    if path.exists() {
+        return decode_key(std::fs::read_to_string(path)?.trim());
+    }
+
+    let mut key = vec![0u8; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| EnvVaultError::InvalidKey("failed to generate key".to_string()))?;
+    let mut file = {
+        let mut opts = std::fs::OpenOptions::new();
+        opts.create_new(true).write(true);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            opts.mode(0o600);
+        }
+
+        opts.open(path)?
+    };
+    file.write_all(BASE64.encode(&key).as_bytes())?;
+    file.sync_all()?;
+
+    tracing::info!("Generated new env vault key at {:?}", path);
+    Ok(key)
+}
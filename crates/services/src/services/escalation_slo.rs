@@ -0,0 +1,334 @@
+//! SLO reporting for escalation paths.
+//!
+//! An escalation path is a board transition's route from its column into its
+//! escalation column, usually a human review stage. Each visit to the escalation
+//! column that came straight from the path's column is an escalation; its response
+//! time runs until the task leaves the column. Escalation columns with a
+//! `response_target_mins` setting count escalations that took longer (or are still
+//! waiting past the target) as breaches.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    agent_stats::StatsTimeRange, kanban_column::KanbanColumn, state_transition::StateTransition,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::cycle_times::{self, ColumnVisit, DurationStats, duration_stats};
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct EscalationPathSlo {
+    pub from_column_id: Uuid,
+    pub from_column_name: String,
+    pub escalation_column_id: Uuid,
+    pub escalation_column_name: String,
+    /// The escalation column's response-time target; none when it has no target
+    pub response_target_mins: Option<i32>,
+    /// Escalations that entered the column in the range
+    pub escalations: usize,
+    /// Escalations still waiting in the column
+    pub open: usize,
+    /// Escalations that took longer than the target, including open ones already past it
+    pub breaches: usize,
+    /// Percentage of decided escalations (answered, or open past the target) that met
+    /// the target; none without a target or decided escalations
+    pub attainment_pct: Option<f64>,
+    /// Response times of answered escalations
+    pub response_times: DurationStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BoardEscalationSlo {
+    pub board_id: Uuid,
+    /// Paths in board order of their source column
+    pub paths: Vec<EscalationPathSlo>,
+    pub escalations: usize,
+    pub breaches: usize,
+    /// Attainment across every path with a target
+    pub attainment_pct: Option<f64>,
+    #[ts(type = "Date")]
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Running tally for one path
+#[derive(Default)]
+struct PathTally {
+    escalations: usize,
+    open: usize,
+    met: usize,
+    breaches: usize,
+    response_secs: Vec<f64>,
+}
+
+fn attainment(met: usize, breaches: usize) -> Option<f64> {
+    let decided = met + breaches;
+    (decided > 0).then(|| met as f64 * 100.0 / decided as f64)
+}
+
+fn entered_in_range(visit: &ColumnVisit, range: &StatsTimeRange) -> bool {
+    range.from.is_none_or(|from| visit.entered_at >= from)
+        && range.to.is_none_or(|to| visit.entered_at <= to)
+}
+
+/// SLO report for `paths` (source column, escalation column) from a board's visits,
+/// which are ordered by task and then time. `targets` holds the response-time target
+/// in minutes of escalation columns that have one.
+pub fn escalation_slo_report(
+    board_id: Uuid,
+    columns: &[KanbanColumn],
+    paths: &[(Uuid, Uuid)],
+    targets: &HashMap<Uuid, i32>,
+    visits: &[ColumnVisit],
+    range: &StatsTimeRange,
+    now: DateTime<Utc>,
+) -> BoardEscalationSlo {
+    let mut tallies: HashMap<(Uuid, Uuid), PathTally> = HashMap::new();
+    for (i, visit) in visits.iter().enumerate() {
+        let Some(previous) = i
+            .checked_sub(1)
+            .and_then(|j| visits.get(j))
+            .filter(|previous| previous.task_id == visit.task_id)
+        else {
+            continue;
+        };
+        let path = (previous.column_id, visit.column_id);
+        if !paths.contains(&path) || !entered_in_range(visit, range) {
+            continue;
+        }
+
+        let tally = tallies.entry(path).or_default();
+        tally.escalations += 1;
+        let secs = visit.duration_secs(now);
+        let breached = targets
+            .get(&visit.column_id)
+            .map(|mins| secs > f64::from(*mins) * 60.0);
+        if visit.left_at.is_none() {
+            tally.open += 1;
+        } else {
+            tally.response_secs.push(secs);
+        }
+        match breached {
+            Some(true) => tally.breaches += 1,
+            Some(false) if visit.left_at.is_some() => tally.met += 1,
+            _ => {}
+        }
+    }
+
+    let column = |id: &Uuid| columns.iter().find(|c| c.id == *id);
+    let mut ordered: Vec<&(Uuid, Uuid)> = paths
+        .iter()
+        .filter(|(from, to)| column(from).is_some() && column(to).is_some())
+        .collect();
+    ordered.sort_by_key(|(from, to)| {
+        (
+            column(from).map(|c| c.position),
+            column(to).map(|c| c.position),
+        )
+    });
+    ordered.dedup();
+
+    let (mut total_met, mut total_breaches, mut total_escalations) = (0, 0, 0);
+    let mut path_slos = Vec::with_capacity(ordered.len());
+    for (from, to) in ordered {
+        let tally = tallies.remove(&(*from, *to)).unwrap_or_default();
+        let response_target_mins = targets.get(to).copied();
+        total_escalations += tally.escalations;
+        if response_target_mins.is_some() {
+            total_met += tally.met;
+            total_breaches += tally.breaches;
+        }
+        path_slos.push(EscalationPathSlo {
+            from_column_id: *from,
+            from_column_name: column(from).map(|c| c.name.clone()).unwrap_or_default(),
+            escalation_column_id: *to,
+            escalation_column_name: column(to).map(|c| c.name.clone()).unwrap_or_default(),
+            response_target_mins,
+            escalations: tally.escalations,
+            open: tally.open,
+            breaches: tally.breaches,
+            attainment_pct: attainment(tally.met, tally.breaches),
+            response_times: duration_stats(tally.response_secs),
+        });
+    }
+
+    BoardEscalationSlo {
+        board_id,
+        paths: path_slos,
+        escalations: total_escalations,
+        breaches: total_breaches,
+        attainment_pct: attainment(total_met, total_breaches),
+        computed_at: now,
+    }
+}
+
+/// Escalation SLO report for a board's transitions with an escalation path
+pub async fn for_board(
+    pool: &PgPool,
+    board_id: Uuid,
+    range: &StatsTimeRange,
+) -> Result<BoardEscalationSlo, sqlx::Error> {
+    let columns = KanbanColumn::find_by_board(pool, board_id).await?;
+    let paths: Vec<(Uuid, Uuid)> = StateTransition::find_by_board(pool, board_id)
+        .await?
+        .into_iter()
+        .filter_map(|t| t.escalation_column_id.map(|to| (t.from_column_id, to)))
+        .collect();
+
+    let targets: HashMap<Uuid, i32> = columns
+        .iter()
+        .filter(|column| paths.iter().any(|(_, to)| *to == column.id))
+        .filter_map(|column| column.response_target_mins.map(|mins| (column.id, mins)))
+        .collect();
+
+    let cached = cycle_times::board_visits(pool, board_id).await?;
+    Ok(escalation_slo_report(
+        board_id,
+        &columns,
+        &paths,
+        &targets,
+        &cached.visits,
+        range,
+        Utc::now(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+    use db::models::task::TaskStatus;
+
+    use super::*;
+
+    fn column(name: &str, position: i32) -> KanbanColumn {
+        KanbanColumn {
+            id: Uuid::new_v4(),
+            board_id: Uuid::nil(),
+            name: name.to_string(),
+            slug: name.to_lowercase(),
+            position,
+            color: None,
+            is_initial: position == 0,
+            is_terminal: false,
+            starts_workflow: false,
+            status: TaskStatus::InReview,
+            agent_id: None,
+            deliverable: None,
+            question: None,
+            answer_options: None,
+            read_only: false,
+            plan_only: false,
+            dry_run: false,
+            security_scanners: Vec::new(),
+            checklist: Vec::new(),
+            on_enter_script: None,
+            on_exit_script: None,
+            warm_up: false,
+            response_target_mins: None,
+            prompt_sections: Vec::new(),
+            executor_variant: None,
+            is_template: false,
+            template_group_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn at(minute: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap() + Duration::minutes(minute)
+    }
+
+    fn visit(task_id: Uuid, column: &KanbanColumn, entered: i64, left: Option<i64>) -> ColumnVisit {
+        ColumnVisit {
+            task_id,
+            column_id: column.id,
+            entered_at: at(entered),
+            left_at: left.map(at),
+        }
+    }
+
+    #[test]
+    fn escalations_breach_when_answered_late_or_still_waiting_past_the_target() {
+        let (build, review, escalated) =
+            (column("Build", 1), column("Review", 2), column("Ops", 3));
+        let columns = vec![build.clone(), review.clone(), escalated.clone()];
+        let paths = [(review.id, escalated.id)];
+        let targets = HashMap::from([(escalated.id, 30)]);
+        let (fast, slow, waiting, other) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let visits = vec![
+            visit(fast, &review, 0, Some(10)),
+            visit(fast, &escalated, 10, Some(30)),
+            visit(slow, &review, 0, Some(5)),
+            visit(slow, &escalated, 5, Some(65)),
+            visit(waiting, &review, 0, Some(20)),
+            visit(waiting, &escalated, 20, None),
+            // Moved straight from Build, not along the escalation path
+            visit(other, &build, 0, Some(1)),
+            visit(other, &escalated, 1, Some(90)),
+        ];
+
+        let report = escalation_slo_report(
+            Uuid::nil(),
+            &columns,
+            &paths,
+            &targets,
+            &visits,
+            &StatsTimeRange::default(),
+            at(100),
+        );
+
+        let path = &report.paths[0];
+        assert_eq!(path.escalation_column_name, "Ops");
+        assert_eq!(path.escalations, 3);
+        assert_eq!(path.open, 1);
+        assert_eq!(path.breaches, 2);
+        assert_eq!(path.attainment_pct, Some(100.0 / 3.0));
+        assert_eq!(path.response_times.samples, 2);
+        assert_eq!(report.escalations, 3);
+        assert_eq!(report.breaches, 2);
+    }
+
+    #[test]
+    fn paths_without_a_target_report_times_but_no_attainment() {
+        let (review, escalated) = (column("Review", 1), column("Ops", 2));
+        let columns = vec![review.clone(), escalated.clone()];
+        let paths = [(review.id, escalated.id), (review.id, escalated.id)];
+        let task = Uuid::new_v4();
+        let visits = vec![
+            visit(task, &review, 0, Some(10)),
+            visit(task, &escalated, 10, Some(70)),
+        ];
+        let range = StatsTimeRange {
+            from: Some(at(0)),
+            to: None,
+        };
+
+        let report = escalation_slo_report(
+            Uuid::nil(),
+            &columns,
+            &paths,
+            &HashMap::new(),
+            &visits,
+            &range,
+            at(100),
+        );
+
+        // Transitions sharing a path are reported once
+        assert_eq!(report.paths.len(), 1);
+        let path = &report.paths[0];
+        assert_eq!(path.response_target_mins, None);
+        assert_eq!(path.escalations, 1);
+        assert_eq!(path.breaches, 0);
+        assert_eq!(path.attainment_pct, None);
+        assert_eq!(path.response_times.max_secs, Some(3600.0));
+        assert_eq!(report.attainment_pct, None);
+    }
+}
@@ -8,6 +8,8 @@ use utils::msg_store::MsgStore;
 pub mod patches;
 #[path = "events/streams.rs"]
 mod streams;
+#[path = "events/task_deltas.rs"]
+pub mod task_deltas;
 #[path = "events/types.rs"]
 pub mod types;
 
@@ -15,6 +17,7 @@ pub use patches::{
     execution_process_patch, group_event_patch, group_patch, project_patch, scratch_patch,
     task_patch, workspace_patch,
 };
+pub use task_deltas::TaskStreamFilter;
 pub use types::{EventError, EventPatch, EventPatchInner, HookTables, RecordTypes};
 
 #[derive(Clone)]
@@ -1,3 +1,8 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
 use db::models::{
     execution_process::ExecutionProcess,
     group_event::GroupEvent,
@@ -6,9 +11,12 @@ use db::models::{
     session::Session,
     task::{Task, TaskWithAttemptStatus},
     task_group::TaskGroup,
+    task_label::TaskLabel,
+    task_subscription::TaskSubscription,
 };
 use futures::StreamExt;
 use serde_json::json;
+use sqlx::PgPool;
 use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
 use utils::log_msg::LogMsg;
 use uuid::Uuid;
@@ -16,33 +24,82 @@ use uuid::Uuid;
 use super::{
     EventService,
     patches::execution_process_patch,
+    task_deltas::{TaskDeltas, TaskStreamFilter},
     types::{EventError, EventPatch, RecordTypes},
 };
 
 impl EventService {
-    /// Stream raw task messages for a specific project with initial snapshot
+    /// Project tasks passing a board stream's filter, without archived tasks
+    async fn filtered_project_tasks(
+        pool: &PgPool,
+        project_id: Uuid,
+        filter: &TaskStreamFilter,
+    ) -> Result<Vec<TaskWithAttemptStatus>, EventError> {
+        let mut tasks = Task::find_by_project_id_with_attempt_status(pool, project_id).await?;
+        tasks.retain(|task| filter.allows_column(task.column_id));
+        if !filter.label_ids.is_empty() {
+            let labelled: HashSet<Uuid> =
+                TaskLabel::find_task_ids_with_labels(pool, &filter.label_ids, filter.label_match)
+                    .await?
+                    .into_iter()
+                    .collect();
+            tasks.retain(|task| labelled.contains(&task.id));
+        }
+        if let Some(assignee_id) = filter.assignee_id {
+            tasks.retain(|task| task.assignee_id == Some(assignee_id));
+        }
+        if filter.watched_only {
+            let watched: HashSet<Uuid> = TaskSubscription::find_watched_task_ids(pool)
+                .await?
+                .into_iter()
+                .collect();
+            tasks.retain(|task| watched.contains(&task.id));
+        }
+        Ok(tasks)
+    }
+
+    /// Whether a changed task should be on a board stream with `filter`
+    async fn task_visible(
+        pool: &PgPool,
+        task: &TaskWithAttemptStatus,
+        filter: &TaskStreamFilter,
+    ) -> bool {
+        if !filter.allows_column(task.column_id) || task.archived_at.is_some() {
+            return false;
+        }
+        if filter.assignee_id.is_some() && task.assignee_id != filter.assignee_id {
+            return false;
+        }
+        if filter.watched_only
+            && !TaskSubscription::watches_task(pool, task.id)
+                .await
+                .unwrap_or(false)
+        {
+            return false;
+        }
+        if filter.label_ids.is_empty() {
+            return true;
+        }
+        match TaskLabel::find_by_task(pool, task.id).await {
+            Ok(labels) => {
+                let label_ids: Vec<Uuid> = labels.iter().map(|label| label.id).collect();
+                filter.allows_labels(&label_ids)
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Stream a project's tasks passing `filter`: a snapshot, then a JSON Patch per task
+    /// change with only the fields that changed (see `TaskDeltas`)
     pub async fn stream_tasks_raw(
         &self,
         project_id: Uuid,
+        filter: TaskStreamFilter,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
     {
-        // Get initial snapshot of tasks
-        let tasks = Task::find_by_project_id_with_attempt_status(&self.db.pool, project_id).await?;
-
-        // Convert task array to object keyed by task ID
-        let tasks_map: serde_json::Map<String, serde_json::Value> = tasks
-            .into_iter()
-            .map(|task| (task.id.to_string(), serde_json::to_value(task).unwrap()))
-            .collect();
-
-        let initial_patch = json!([
-            {
-                "op": "replace",
-                "path": "/tasks",
-                "value": tasks_map
-            }
-        ]);
-        let initial_msg = LogMsg::JsonPatch(serde_json::from_value(initial_patch).unwrap());
+        let tasks = Self::filtered_project_tasks(&self.db.pool, project_id, &filter).await?;
+        let deltas = Arc::new(Mutex::new(TaskDeltas::default()));
+        let initial_msg = LogMsg::JsonPatch(deltas.lock().unwrap().snapshot(tasks));
 
         // Clone necessary data for the async filter
         let db_pool = self.db.pool.clone();
@@ -51,45 +108,44 @@ impl EventService {
         let filtered_stream =
             BroadcastStream::new(self.msg_store.get_receiver()).filter_map(move |msg_result| {
                 let db_pool = db_pool.clone();
+                let filter = filter.clone();
+                let deltas = deltas.clone();
                 async move {
                     match msg_result {
                         Ok(LogMsg::JsonPatch(patch)) => {
                             // Filter events based on project_id
                             if let Some(patch_op) = patch.0.first() {
                                 // Check if this is a direct task patch (new format)
-                                if patch_op.path().starts_with("/tasks/") {
-                                    match patch_op {
-                                        json_patch::PatchOperation::Add(op) => {
-                                            // Parse task data directly from value
-                                            if let Ok(task) =
-                                                serde_json::from_value::<TaskWithAttemptStatus>(
-                                                    op.value.clone(),
-                                                )
-                                                && task.project_id == project_id
-                                            {
-                                                return Some(Ok(LogMsg::JsonPatch(patch)));
-                                            }
-                                        }
-                                        json_patch::PatchOperation::Replace(op) => {
-                                            // Parse task data directly from value
-                                            if let Ok(task) =
-                                                serde_json::from_value::<TaskWithAttemptStatus>(
-                                                    op.value.clone(),
-                                                )
-                                                && task.project_id == project_id
-                                            {
-                                                return Some(Ok(LogMsg::JsonPatch(patch)));
+                                if let Some(task_id) = patch_op
+                                    .path()
+                                    .strip_prefix("/tasks/")
+                                    .and_then(|id| Uuid::parse_str(id).ok())
+                                {
+                                    let value = match patch_op {
+                                        json_patch::PatchOperation::Add(op) => Some(&op.value),
+                                        json_patch::PatchOperation::Replace(op) => Some(&op.value),
+                                        json_patch::PatchOperation::Remove(_) => None,
+                                        _ => return None,
+                                    };
+                                    // Removals carry no task; only tasks the client has
+                                    // are removed
+                                    let visible = match value {
+                                        Some(value) => {
+                                            let task = serde_json::from_value::<
+                                                TaskWithAttemptStatus,
+                                            >(value.clone())
+                                            .ok()?;
+                                            if task.project_id != project_id {
+                                                return None;
                                             }
+                                            Self::task_visible(&db_pool, &task, &filter)
+                                                .await
+                                                .then(|| value.clone())
                                         }
-                                        json_patch::PatchOperation::Remove(_) => {
-                                            // For remove operations, we need to check project membership differently
-                                            // We could cache this information or let it pass through for now
-                                            // Since we don't have the task data, we'll allow all removals
-                                            // and let the client handle filtering
-                                            return Some(Ok(LogMsg::JsonPatch(patch)));
-                                        }
-                                        _ => {}
-                                    }
+                                        None => None,
+                                    };
+                                    let delta = deltas.lock().unwrap().update(task_id, visible)?;
+                                    return Some(Ok(LogMsg::JsonPatch(delta)));
                                 } else if let Ok(event_patch_value) = serde_json::to_value(patch_op)
                                     && let Ok(event_patch) =
                                         serde_json::from_value::<EventPatch>(event_patch_value)
@@ -137,7 +193,22 @@ impl EventService {
                             None
                         }
                         Ok(other) => Some(Ok(other)), // Pass through non-patch messages
-                        Err(_) => None,               // Filter out broadcast errors
+                        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                            // Missed changes can't be diffed; start over from a snapshot
+                            tracing::warn!(
+                                skipped = skipped,
+                                "tasks stream lagged; resyncing snapshot"
+                            );
+                            match Self::filtered_project_tasks(&db_pool, project_id, &filter).await
+                            {
+                                Ok(tasks) => Some(Ok(LogMsg::JsonPatch(
+                                    deltas.lock().unwrap().snapshot(tasks),
+                                ))),
+                                Err(err) => Some(Err(std::io::Error::other(format!(
+                                    "failed to resync tasks after lag: {err}"
+                                )))),
+                            }
+                        }
                     }
                 }
             });
@@ -149,6 +220,76 @@ impl EventService {
         Ok(combined_stream)
     }
 
+    /// Stream raw task messages for watched tasks (directly or through a label) with
+    /// initial snapshot
+    pub async fn stream_watched_tasks_raw(
+        &self,
+    ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
+    {
+        let watched: HashSet<Uuid> = TaskSubscription::find_watched_task_ids(&self.db.pool)
+            .await?
+            .into_iter()
+            .collect();
+        let mut project_ids = Vec::new();
+        for task_id in &watched {
+            if let Some(task) = Task::find_by_id(&self.db.pool, *task_id).await?
+                && !project_ids.contains(&task.project_id)
+            {
+                project_ids.push(task.project_id);
+            }
+        }
+        let mut tasks_map = serde_json::Map::new();
+        for project_id in project_ids {
+            let tasks =
+                Task::find_by_project_id_with_attempt_status(&self.db.pool, project_id).await?;
+            for task in tasks.into_iter().filter(|task| watched.contains(&task.id)) {
+                tasks_map.insert(task.id.to_string(), serde_json::to_value(task).unwrap());
+            }
+        }
+
+        let initial_patch = json!([
+            {
+                "op": "replace",
+                "path": "/tasks",
+                "value": tasks_map
+            }
+        ]);
+        let initial_msg = LogMsg::JsonPatch(serde_json::from_value(initial_patch).unwrap());
+
+        let db_pool = self.db.pool.clone();
+
+        // Subscriptions can change while the stream is open, so each update is checked
+        // against the current ones
+        let filtered_stream =
+            BroadcastStream::new(self.msg_store.get_receiver()).filter_map(move |msg_result| {
+                let db_pool = db_pool.clone();
+                async move {
+                    let Ok(LogMsg::JsonPatch(patch)) = msg_result else {
+                        return None;
+                    };
+                    let patch_op = patch.0.first()?;
+                    if !patch_op.path().starts_with("/tasks/") {
+                        return None;
+                    }
+                    let value = match patch_op {
+                        json_patch::PatchOperation::Add(op) => &op.value,
+                        json_patch::PatchOperation::Replace(op) => &op.value,
+                        _ => return None,
+                    };
+                    let task_id = serde_json::from_value::<TaskWithAttemptStatus>(value.clone())
+                        .ok()?
+                        .id;
+                    match TaskSubscription::watches_task(&db_pool, task_id).await {
+                        Ok(true) => Some(Ok(LogMsg::JsonPatch(patch))),
+                        _ => None,
+                    }
+                }
+            });
+
+        let initial_stream = futures::stream::once(async move { Ok(initial_msg) });
+        Ok(initial_stream.chain(filtered_stream).boxed())
+    }
+
     /// Stream raw project messages with initial snapshot
     pub async fn stream_projects_raw(
         &self,
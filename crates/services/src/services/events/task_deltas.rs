@@ -0,0 +1,151 @@
+//! Per-connection state of the task board stream.
+//!
+//! The stream sends a snapshot of the project's tasks and then one JSON Patch per task
+//! change, keyed by task id. Changes to a task the client already has are sent as the
+//! fields that changed (e.g. `/tasks/{id}/status`) instead of the whole task, and tasks
+//! entering or leaving the stream's filter are added or removed.
+
+use std::collections::HashMap;
+
+use db::models::{task::TaskWithAttemptStatus, task_label::LabelMatch};
+use json_patch::Patch;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+/// Which of a project's tasks a board stream sends
+#[derive(Debug, Clone, Default)]
+pub struct TaskStreamFilter {
+    /// Only tasks in these columns; empty allows every column
+    pub column_ids: Vec<Uuid>,
+    /// Only tasks with these labels (any or all, see `label_match`); empty allows every task
+    pub label_ids: Vec<Uuid>,
+    pub label_match: LabelMatch,
+    /// Only tasks assigned to this user
+    pub assignee_id: Option<Uuid>,
+    /// Only tasks watched directly or through a label
+    pub watched_only: bool,
+}
+
+impl TaskStreamFilter {
+    pub fn allows_column(&self, column_id: Option<Uuid>) -> bool {
+        self.column_ids.is_empty() || column_id.is_some_and(|id| self.column_ids.contains(&id))
+    }
+
+    /// Whether a task with `task_label_ids` passes the label filter
+    pub fn allows_labels(&self, task_label_ids: &[Uuid]) -> bool {
+        match self.label_match {
+            _ if self.label_ids.is_empty() => true,
+            LabelMatch::Any => self.label_ids.iter().any(|id| task_label_ids.contains(id)),
+            LabelMatch::All => self.label_ids.iter().all(|id| task_label_ids.contains(id)),
+        }
+    }
+}
+
+/// Tasks as last sent to one client
+#[derive(Debug, Default)]
+pub struct TaskDeltas {
+    sent: HashMap<Uuid, Value>,
+}
+
+impl TaskDeltas {
+    /// Patch replacing the client's tasks; later patches are relative to it
+    pub fn snapshot(&mut self, tasks: Vec<TaskWithAttemptStatus>) -> Patch {
+        self.sent = tasks
+            .into_iter()
+            .map(|task| (task.id, serde_json::to_value(&task).unwrap()))
+            .collect();
+        let tasks_map: serde_json::Map<String, Value> = self
+            .sent
+            .iter()
+            .map(|(id, task)| (id.to_string(), task.clone()))
+            .collect();
+        serde_json::from_value(json!([
+            {
+                "op": "replace",
+                "path": "/tasks",
+                "value": tasks_map
+            }
+        ]))
+        .unwrap()
+    }
+
+    /// Patch bringing the client's copy of a task up to date. `task` is its new state,
+    /// or None once it's deleted or no longer passes the filter. Returns None when the
+    /// client has nothing to change.
+    pub fn update(&mut self, task_id: Uuid, task: Option<Value>) -> Option<Patch> {
+        let path = format!("/tasks/{task_id}");
+        let Some(task) = task else {
+            self.sent.remove(&task_id)?;
+            return serde_json::from_value(json!([{ "op": "remove", "path": path }])).ok();
+        };
+        let ops = match self.sent.get(&task_id) {
+            None => json!([{ "op": "add", "path": path, "value": task }]),
+            Some(previous) => {
+                let diff = json_patch::diff(previous, &task);
+                if diff.0.is_empty() {
+                    return None;
+                }
+                let mut ops = serde_json::to_value(diff).ok()?;
+                for op in ops.as_array_mut()? {
+                    for key in ["path", "from"] {
+                        if let Some(Value::String(pointer)) = op.get_mut(key) {
+                            *pointer = format!("{path}{pointer}");
+                        }
+                    }
+                }
+                ops
+            }
+        };
+        self.sent.insert(task_id, task);
+        serde_json::from_value(ops).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_to_sent_tasks_are_patched_field_by_field() {
+        let mut deltas = TaskDeltas::default();
+        let id = Uuid::new_v4();
+        let task = json!({ "id": id, "title": "Fix login", "status": "todo" });
+
+        let added = deltas.update(id, Some(task.clone())).unwrap();
+        assert_eq!(serde_json::to_value(&added).unwrap()[0]["op"], "add");
+        assert!(deltas.update(id, Some(task)).is_none());
+
+        let moved = deltas
+            .update(id, Some(json!({ "id": id, "title": "Fix login", "status": "inreview" })))
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&moved).unwrap(),
+            json!([{ "op": "replace", "path": format!("/tasks/{id}/status"), "value": "inreview" }])
+        );
+
+        let removed = deltas.update(id, None).unwrap();
+        assert_eq!(serde_json::to_value(&removed).unwrap()[0]["op"], "remove");
+        // Tasks the client never got aren't removed again
+        assert!(deltas.update(id, None).is_none());
+    }
+
+    #[test]
+    fn filter_allows_tasks_in_its_columns_with_its_labels() {
+        let (column, other_column) = (Uuid::new_v4(), Uuid::new_v4());
+        let (bug, urgent) = (Uuid::new_v4(), Uuid::new_v4());
+        let filter = TaskStreamFilter {
+            column_ids: vec![column],
+            label_ids: vec![bug, urgent],
+            label_match: LabelMatch::All,
+            ..Default::default()
+        };
+
+        assert!(filter.allows_column(Some(column)));
+        assert!(!filter.allows_column(Some(other_column)));
+        assert!(!filter.allows_column(None));
+        assert!(filter.allows_labels(&[urgent, bug]));
+        assert!(!filter.allows_labels(&[bug]));
+        assert!(TaskStreamFilter::default().allows_column(None));
+        assert!(TaskStreamFilter::default().allows_labels(&[]));
+    }
+}
@@ -0,0 +1,221 @@
+//! Output artifacts left by agents.
+//!
+//! An agent can drop files into `.vibe/artifacts/` (in the workspace root or in any
+//! of its repos): screenshots, coverage reports, benchmark JSON. When the execution
+//! finalizes, the files are stored in the images store, attached to the execution
+//! process, recorded on the task timeline, and the directory is cleared so the
+//! next run starts empty. Transition conditions can then check them through
+//! [`with_artifacts`](super::transition_condition::with_artifacts).
+
+use std::path::{Path, PathBuf};
+
+use db::models::{
+    execution_artifact::{CreateExecutionArtifact, ExecutionArtifact},
+    execution_process::ExecutionContext,
+    task_event::{CreateTaskEvent, TaskEvent},
+};
+use sqlx::PgPool;
+
+use super::{
+    decision_file::repo_dirs,
+    image::{ImageError, ImageService},
+};
+
+/// Directory under `.vibe/` that agents write artifacts to
+pub const ARTIFACTS_DIR: &str = "artifacts";
+
+/// Files beyond this many per execution are left uncollected
+const MAX_ARTIFACTS_PER_EXECUTION: usize = 100;
+
+/// Mime type recorded for an artifact, guessed from its extension
+pub fn mime_type_for(name: &str) -> Option<&'static str> {
+    let extension = Path::new(name).extension()?.to_str()?.to_lowercase();
+    Some(match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "html" | "htm" => "text/html",
+        "txt" | "log" | "lcov" | "info" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        _ => return None,
+    })
+}
+
+/// Artifact name of a file: its path relative to the artifacts directory,
+/// with `/` separators
+fn artifact_name(artifacts_dir: &Path, file: &Path) -> Option<String> {
+    let relative = file.strip_prefix(artifacts_dir).ok()?;
+    let parts: Vec<_> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// Regular files under `dir`, sorted; symlinks are not followed
+async fn list_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(mut entries) = tokio::fs::read_dir(&current).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            match entry.file_type().await {
+                Ok(file_type) if file_type.is_dir() => pending.push(entry.path()),
+                Ok(file_type) if file_type.is_file() => files.push(entry.path()),
+                _ => {}
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Artifacts directories of a workspace with the repo they belong to (none for the
+/// workspace root)
+async fn artifact_dirs(workspace_root: &Path) -> Vec<(Option<String>, PathBuf)> {
+    let mut dirs = vec![(None, workspace_root.join(".vibe").join(ARTIFACTS_DIR))];
+    for (name, dir) in repo_dirs(workspace_root).await {
+        dirs.push((Some(name), dir.join(".vibe").join(ARTIFACTS_DIR)));
+    }
+    dirs.into_iter().filter(|(_, dir)| dir.is_dir()).collect()
+}
+
+/// Collect the artifacts an execution left in its workspace, attach them to the
+/// execution process and clear the artifacts directories
+pub async fn collect(
+    pool: &PgPool,
+    images: &ImageService,
+    ctx: &ExecutionContext,
+) -> Vec<ExecutionArtifact> {
+    let Some(worktree_path) = ctx.workspace.container_ref.as_ref() else {
+        return Vec::new();
+    };
+
+    let mut collected = Vec::new();
+    for (repo_name, dir) in artifact_dirs(Path::new(worktree_path)).await {
+        for file in list_files(&dir).await {
+            if collected.len() >= MAX_ARTIFACTS_PER_EXECUTION {
+                tracing::warn!(
+                    "Execution {} left more than {} artifacts; skipping the rest",
+                    ctx.execution_process.id,
+                    MAX_ARTIFACTS_PER_EXECUTION
+                );
+                break;
+            }
+            let Some(name) = artifact_name(&dir, &file) else {
+                continue;
+            };
+            match store_artifact(pool, images, ctx, &file, name, repo_name.clone()).await {
+                Ok(artifact) => collected.push(artifact),
+                Err(e) => tracing::warn!("Failed to collect artifact {:?}: {}", file, e),
+            }
+        }
+        if let Err(e) = tokio::fs::remove_dir_all(&dir).await {
+            tracing::warn!("Failed to clear artifacts directory {:?}: {}", dir, e);
+        }
+    }
+
+    if !collected.is_empty() {
+        tracing::info!(
+            "Collected {} artifact(s) from execution {}",
+            collected.len(),
+            ctx.execution_process.id
+        );
+        let names: Vec<String> = collected.iter().map(display_name).collect();
+        let event = CreateTaskEvent::artifacts_collected(
+            ctx.task.id,
+            ctx.workspace.id,
+            ctx.execution_process.id,
+            &names,
+        );
+        if let Err(e) = TaskEvent::create(pool, &event).await {
+            tracing::error!(
+                "Failed to record artifacts event for task {}: {}",
+                ctx.task.id,
+                e
+            );
+        }
+    }
+    collected
+}
+
+async fn store_artifact(
+    pool: &PgPool,
+    images: &ImageService,
+    ctx: &ExecutionContext,
+    file: &Path,
+    name: String,
+    repo_name: Option<String>,
+) -> Result<ExecutionArtifact, ImageError> {
+    let data = tokio::fs::read(file).await?;
+    let mime_type = mime_type_for(&name).map(str::to_string);
+    let file_name = file
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| name.clone());
+    let image = images
+        .store_file(&data, &file_name, mime_type.clone())
+        .await?;
+    let artifact = ExecutionArtifact::create(
+        pool,
+        ctx.execution_process.id,
+        ctx.task.id,
+        &CreateExecutionArtifact {
+            image_id: image.id,
+            name,
+            repo_name,
+            mime_type,
+            size_bytes: image.size_bytes,
+        },
+    )
+    .await?;
+    Ok(artifact)
+}
+
+/// Name shown on the timeline, prefixed with the repo for per-repo artifacts
+fn display_name(artifact: &ExecutionArtifact) -> String {
+    match &artifact.repo_name {
+        Some(repo) => format!("{repo}/{}", artifact.name),
+        None => artifact.name.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mime_types_are_guessed_from_extensions() {
+        assert_eq!(mime_type_for("screenshot.PNG"), Some("image/png"));
+        assert_eq!(
+            mime_type_for("bench/results.json"),
+            Some("application/json")
+        );
+        assert_eq!(mime_type_for("coverage/lcov.info"), Some("text/plain"));
+        assert_eq!(mime_type_for("core.dump"), None);
+        assert_eq!(mime_type_for("Makefile"), None);
+    }
+
+    #[test]
+    fn artifact_names_are_relative_to_the_directory() {
+        let dir = Path::new("/work/.vibe/artifacts");
+        assert_eq!(
+            artifact_name(dir, &dir.join("coverage").join("summary.json")),
+            Some("coverage/summary.json".to_string())
+        );
+        assert_eq!(
+            artifact_name(dir, &dir.join("shot.png")),
+            Some("shot.png".to_string())
+        );
+        assert_eq!(artifact_name(dir, Path::new("/elsewhere/file.txt")), None);
+    }
+}
@@ -0,0 +1,218 @@
+//! Environment capture for execution processes.
+//!
+//! When an execution starts, the executor profile and its configuration, the host,
+//! each repo's base branch commit and the process's environment variables are
+//! recorded, so a run that "behaved differently yesterday" can be compared with
+//! today's. Secret values never reach the table. The model and CLI version are only
+//! known once the agent reports them in its logs, and are filled in when it exits.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::Path,
+};
+
+use db::models::{
+    execution_environment::{CreateExecutionEnvironment, EnvironmentRepo},
+    workspace_repo::RepoWithTargetBranch,
+};
+use executors::{
+    actions::{ExecutorAction, ExecutorActionType},
+    env::ExecutionEnv,
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
+use serde_json::Value;
+use utils::{redact::REDACTED, version::APP_VERSION};
+
+use super::git::GitService;
+
+/// Variable name fragments that mark a value as secret even outside the env vault
+const SECRET_NAME_FRAGMENTS: &[&str] = &["KEY", "TOKEN", "SECRET", "PASSWORD", "CREDENTIAL"];
+
+/// Log fields agents report their CLI version in
+const VERSION_FIELDS: &[&str] = &["claude_code_version", "cli_version"];
+
+pub fn is_secret_name(name: &str) -> bool {
+    let name = name.to_uppercase();
+    SECRET_NAME_FRAGMENTS
+        .iter()
+        .any(|fragment| name.contains(fragment))
+}
+
+/// Environment variables with secret values replaced by [`REDACTED`]
+pub fn redact_env(
+    vars: &HashMap<String, String>,
+    secret_keys: &HashSet<String>,
+) -> BTreeMap<String, String> {
+    vars.iter()
+        .map(|(key, value)| {
+            let value = if secret_keys.contains(key) || is_secret_name(key) {
+                REDACTED.to_string()
+            } else {
+                value.clone()
+            };
+            (key.clone(), value)
+        })
+        .collect()
+}
+
+/// An executor configuration with the values of its `env` overrides redacted
+pub fn redact_config(mut config: Value) -> Value {
+    match &mut config {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if key == "env"
+                    && let Value::Object(env) = value
+                {
+                    for env_value in env.values_mut() {
+                        *env_value = Value::String(REDACTED.to_string());
+                    }
+                } else {
+                    *value = redact_config(value.take());
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                *item = redact_config(item.take());
+            }
+        }
+        _ => {}
+    }
+    config
+}
+
+/// Model and CLI version an agent reported in its output
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportedByAgent {
+    pub model: Option<String>,
+    pub executor_version: Option<String>,
+}
+
+/// The first model and CLI version found in the agent's JSON output lines
+pub fn reported_by_agent(stdout: &str) -> ReportedByAgent {
+    let mut reported = ReportedByAgent::default();
+    for line in stdout.lines() {
+        if reported.model.is_some() && reported.executor_version.is_some() {
+            break;
+        }
+        let Ok(Value::Object(event)) = serde_json::from_str::<Value>(line.trim()) else {
+            continue;
+        };
+        let string_field =
+            |value: Option<&Value>| value.and_then(Value::as_str).map(str::to_string);
+        if reported.model.is_none() {
+            reported.model = string_field(event.get("model"))
+                .or_else(|| string_field(event.get("message").and_then(|m| m.get("model"))));
+        }
+        if reported.executor_version.is_none() {
+            reported.executor_version = VERSION_FIELDS
+                .iter()
+                .find_map(|field| string_field(event.get(*field)));
+        }
+    }
+    reported
+}
+
+fn executor_profile_id(action: &ExecutorAction) -> Option<ExecutorProfileId> {
+    match action.typ() {
+        ExecutorActionType::CodingAgentInitialRequest(request) => {
+            Some(request.executor_profile_id.clone())
+        }
+        ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+            Some(request.get_executor_profile_id())
+        }
+        ExecutorActionType::ScriptRequest(_) => None,
+    }
+}
+
+/// Everything known about an execution's environment before it starts
+pub fn capture(
+    git: &GitService,
+    action: &ExecutorAction,
+    env: &ExecutionEnv,
+    repos: &[RepoWithTargetBranch],
+    workspace_root: &Path,
+) -> CreateExecutionEnvironment {
+    let profile_id = executor_profile_id(action);
+    let executor_config = profile_id
+        .as_ref()
+        .and_then(|id| ExecutorConfigs::get_cached().get_coding_agent(id))
+        .and_then(|agent| serde_json::to_value(agent).ok())
+        .map(redact_config);
+
+    let repos = repos
+        .iter()
+        .map(|repo| EnvironmentRepo {
+            repo_name: repo.repo.name.clone(),
+            target_branch: repo.target_branch.clone(),
+            base_commit: git
+                .get_branch_oid(&repo.repo.path, &repo.target_branch)
+                .ok(),
+            head_commit: git
+                .get_head_info(&workspace_root.join(&repo.repo.name))
+                .ok()
+                .map(|head| head.oid),
+        })
+        .collect();
+
+    CreateExecutionEnvironment {
+        executor: profile_id.map(|id| id.to_string()),
+        executor_config,
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        app_version: APP_VERSION.to_string(),
+        repos,
+        env_vars: redact_env(&env.vars, &env.secret_keys),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn secrets_are_redacted() {
+        let vars = HashMap::from([
+            ("VK_TASK_ID".to_string(), "42".to_string()),
+            ("DATABASE_URL".to_string(), "postgres://u:p@db".to_string()),
+            ("GITHUB_TOKEN".to_string(), "ghp_123".to_string()),
+        ]);
+        let secret_keys = HashSet::from(["DATABASE_URL".to_string()]);
+        let redacted = redact_env(&vars, &secret_keys);
+
+        assert_eq!(redacted["VK_TASK_ID"], "42");
+        assert_eq!(redacted["DATABASE_URL"], REDACTED);
+        assert_eq!(redacted["GITHUB_TOKEN"], REDACTED);
+
+        let config = redact_config(json!({
+            "CLAUDE_CODE": { "model": "opus", "env": { "ANTHROPIC_BASE_URL": "http://proxy" } }
+        }));
+        assert_eq!(config["CLAUDE_CODE"]["model"], "opus");
+        assert_eq!(config["CLAUDE_CODE"]["env"]["ANTHROPIC_BASE_URL"], REDACTED);
+    }
+
+    #[test]
+    fn model_and_version_come_from_first_reports() {
+        let stdout = [
+            "Starting agent...",
+            r#"{"type":"system","subtype":"init","claude_code_version":"2.0.75"}"#,
+            r#"{"type":"assistant","message":{"model":"claude-sonnet-4-20250514"}}"#,
+            r#"{"type":"assistant","message":{"model":"claude-haiku"}}"#,
+        ]
+        .join("\n");
+
+        assert_eq!(
+            reported_by_agent(&stdout),
+            ReportedByAgent {
+                model: Some("claude-sonnet-4-20250514".to_string()),
+                executor_version: Some("2.0.75".to_string()),
+            }
+        );
+        assert_eq!(
+            reported_by_agent("plain output"),
+            ReportedByAgent::default()
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Replays of a coding agent run with another executor.
+//!
+//! A replay re-sends the exact prompt and agent context of a finished run to a
+//! different executor profile, in a fresh workspace branch of the same task, so the
+//! two outputs can be compared side by side. Replays are manual runs: they never move
+//! the task or trigger column transitions.
+
+use executors::{
+    actions::{ExecutorAction, ExecutorActionType},
+    profile::ExecutorProfileId,
+};
+
+/// The source run's initial request with its executor profile swapped. The source's
+/// remaining actions (cleanup scripts) are left off, since they would finalize the
+/// task as part of the column workflow. `None` if the action isn't an initial coding
+/// agent request: follow-ups depend on the source agent's session and can't be sent
+/// to another executor.
+pub fn replay_action(
+    source: &ExecutorAction,
+    executor_profile_id: ExecutorProfileId,
+) -> Option<ExecutorAction> {
+    let ExecutorActionType::CodingAgentInitialRequest(request) = source.typ() else {
+        return None;
+    };
+    let mut request = request.clone();
+    request.executor_profile_id = executor_profile_id;
+    Some(ExecutorAction::new(
+        ExecutorActionType::CodingAgentInitialRequest(request),
+        None,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::{
+        actions::{
+            coding_agent_follow_up::CodingAgentFollowUpRequest,
+            coding_agent_initial::CodingAgentInitialRequest,
+            script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+        },
+        executors::BaseCodingAgent,
+    };
+
+    use super::*;
+
+    fn profile(executor: BaseCodingAgent) -> ExecutorProfileId {
+        ExecutorProfileId {
+            executor,
+            variant: None,
+        }
+    }
+
+    #[test]
+    fn replay_keeps_prompt_and_context_but_swaps_executor() {
+        let source = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: "Fix the login form".to_string(),
+                executor_profile_id: profile(BaseCodingAgent::ClaudeCode),
+                working_dir: Some("app".to_string()),
+                agent_system_prompt: Some("You are a reviewer".to_string()),
+                agent_project_context: Some("ADR-001".to_string()),
+                agent_workflow_history: None,
+                agent_start_command: Some("Start with the tests".to_string()),
+                agent_decision_instructions: None,
+                agent_deliverable: None,
+                prompt_sections: None,
+                prompt_language: None,
+            }),
+            Some(Box::new(ExecutorAction::new(
+                ExecutorActionType::ScriptRequest(ScriptRequest {
+                    script: "true".to_string(),
+                    language: ScriptRequestLanguage::Bash,
+                    context: ScriptContext::CleanupScript,
+                    working_dir: None,
+                }),
+                None,
+            ))),
+        );
+
+        let replay = replay_action(&source, profile(BaseCodingAgent::Codex)).unwrap();
+        let (
+            ExecutorActionType::CodingAgentInitialRequest(original),
+            ExecutorActionType::CodingAgentInitialRequest(replayed),
+        ) = (source.typ(), replay.typ())
+        else {
+            panic!("expected initial requests");
+        };
+        assert_eq!(replayed.executor_profile_id, profile(BaseCodingAgent::Codex));
+        assert_eq!(replayed.build_full_prompt(), original.build_full_prompt());
+        assert_eq!(replayed.working_dir.as_deref(), Some("app"));
+        assert!(replay.next_action().is_none());
+    }
+
+    #[test]
+    fn follow_ups_cannot_be_replayed() {
+        let follow_up = ExecutorAction::new(
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt: "Also update the docs".to_string(),
+                session_id: "agent-session".to_string(),
+                executor_profile_id: profile(BaseCodingAgent::ClaudeCode),
+                working_dir: None,
+            }),
+            None,
+        );
+        assert!(replay_action(&follow_up, profile(BaseCodingAgent::Codex)).is_none());
+    }
+}
@@ -0,0 +1,309 @@
+//! Circuit breaker per coding agent executor.
+//!
+//! When an executor fails several runs in a row (expired auth, provider outage) its
+//! circuit opens: new executions with it wait in the agent queue instead of being
+//! burnt against a dead provider. Once the cooldown has passed, the next waiting
+//! execution is let through as a probe. Its success closes the circuit and releases
+//! the rest; its failure reopens it with a longer cooldown.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use executors::executors::BaseCodingAgent;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Consecutive failures that open an executor's circuit
+pub const FAILURE_THRESHOLD: u32 = 3;
+
+const BASE_COOLDOWN_MINUTES: i64 = 5;
+const MAX_COOLDOWN_MINUTES: i64 = 60;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Executions run normally
+    Closed,
+    /// Executions wait until the cooldown has passed
+    Open,
+    /// One probe execution is running; the rest wait for its outcome
+    HalfOpen,
+}
+
+/// A change worth telling admins about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitEvent {
+    Tripped,
+    Recovered,
+}
+
+impl CircuitEvent {
+    /// `(title, message)` of the notification for this event
+    pub fn notification(&self, executor: BaseCodingAgent) -> (String, String) {
+        match self {
+            CircuitEvent::Tripped => (
+                format!("{executor} paused"),
+                format!(
+                    "{executor} failed {FAILURE_THRESHOLD} runs in a row. New runs wait in the \
+                     agent queue until a probe run succeeds."
+                ),
+            ),
+            CircuitEvent::Recovered => (
+                format!("{executor} recovered"),
+                format!("A probe run with {executor} succeeded; queued runs are starting."),
+            ),
+        }
+    }
+}
+
+/// Health of one executor, for the UI
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ExecutorHealthStatus {
+    pub executor: BaseCodingAgent,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    #[ts(type = "Date | null")]
+    pub opened_at: Option<DateTime<Utc>>,
+    /// When the next probe may start, while the circuit is open
+    #[ts(type = "Date | null")]
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+struct ExecutorHealth {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<DateTime<Utc>>,
+    cooldown: Duration,
+    probe: Option<Uuid>,
+}
+
+impl Default for ExecutorHealth {
+    fn default() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: None,
+            cooldown: Duration::minutes(BASE_COOLDOWN_MINUTES),
+            probe: None,
+        }
+    }
+}
+
+impl ExecutorHealth {
+    fn retry_at(&self) -> Option<DateTime<Utc>> {
+        self.opened_at.map(|opened_at| opened_at + self.cooldown)
+    }
+
+    fn open(&mut self, now: DateTime<Utc>) {
+        self.state = CircuitState::Open;
+        self.opened_at = Some(now);
+        self.probe = None;
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ExecutorCircuitBreaker {
+    executors: HashMap<BaseCodingAgent, ExecutorHealth>,
+}
+
+impl ExecutorCircuitBreaker {
+    /// Whether `execution_process_id` may start now, without claiming the probe
+    pub fn can_admit(
+        &self,
+        executor: BaseCodingAgent,
+        execution_process_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let Some(health) = self.executors.get(&executor) else {
+            return true;
+        };
+        match health.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => health.retry_at().is_none_or(|retry_at| now >= retry_at),
+            CircuitState::HalfOpen => health.probe == Some(execution_process_id),
+        }
+    }
+
+    /// Let `execution_process_id` start, making it the probe if the cooldown has
+    /// passed, or say why it has to wait
+    pub fn admit(
+        &mut self,
+        executor: BaseCodingAgent,
+        execution_process_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let Some(health) = self.executors.get_mut(&executor) else {
+            return Ok(());
+        };
+        match health.state {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open => match health.retry_at() {
+                Some(retry_at) if now < retry_at => Err(format!(
+                    "{executor} paused after {} consecutive failures; probing again at {}",
+                    health.consecutive_failures,
+                    retry_at.format("%H:%M UTC")
+                )),
+                _ => {
+                    health.state = CircuitState::HalfOpen;
+                    health.probe = Some(execution_process_id);
+                    Ok(())
+                }
+            },
+            CircuitState::HalfOpen if health.probe == Some(execution_process_id) => Ok(()),
+            CircuitState::HalfOpen => Err(format!(
+                "{executor} paused; waiting for a probe run to succeed"
+            )),
+        }
+    }
+
+    /// Record how a run with `executor` ended
+    pub fn record(
+        &mut self,
+        executor: BaseCodingAgent,
+        execution_process_id: Uuid,
+        success: bool,
+        now: DateTime<Utc>,
+    ) -> Option<CircuitEvent> {
+        let health = self.executors.entry(executor).or_default();
+        if success {
+            let recovered = health.state != CircuitState::Closed;
+            *health = ExecutorHealth::default();
+            return recovered.then_some(CircuitEvent::Recovered);
+        }
+
+        health.consecutive_failures += 1;
+        match health.state {
+            CircuitState::Closed if health.consecutive_failures >= FAILURE_THRESHOLD => {
+                health.open(now);
+                Some(CircuitEvent::Tripped)
+            }
+            CircuitState::HalfOpen if health.probe == Some(execution_process_id) => {
+                health.cooldown =
+                    (health.cooldown * 2).min(Duration::minutes(MAX_COOLDOWN_MINUTES));
+                health.open(now);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Forget a probe that ended without an outcome, so another run can probe
+    pub fn release(&mut self, execution_process_id: Uuid) {
+        for health in self.executors.values_mut() {
+            if health.probe == Some(execution_process_id) {
+                health.state = CircuitState::Open;
+                health.probe = None;
+            }
+        }
+    }
+
+    /// Close an executor's circuit by hand
+    pub fn reset(&mut self, executor: BaseCodingAgent) {
+        self.executors.remove(&executor);
+    }
+
+    /// Executors that have failed recently or are paused
+    pub fn statuses(&self) -> Vec<ExecutorHealthStatus> {
+        let mut statuses: Vec<_> = self
+            .executors
+            .iter()
+            .filter(|(_, health)| health.consecutive_failures > 0)
+            .map(|(executor, health)| ExecutorHealthStatus {
+                executor: *executor,
+                state: health.state,
+                consecutive_failures: health.consecutive_failures,
+                opened_at: health.opened_at,
+                retry_at: (health.state == CircuitState::Open)
+                    .then(|| health.retry_at())
+                    .flatten(),
+            })
+            .collect();
+        statuses.sort_by_key(|status| status.executor.to_string());
+        statuses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLAUDE: BaseCodingAgent = BaseCodingAgent::ClaudeCode;
+
+    fn tripped(now: DateTime<Utc>) -> ExecutorCircuitBreaker {
+        let mut breaker = ExecutorCircuitBreaker::default();
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            assert_eq!(breaker.record(CLAUDE, Uuid::new_v4(), false, now), None);
+        }
+        assert_eq!(
+            breaker.record(CLAUDE, Uuid::new_v4(), false, now),
+            Some(CircuitEvent::Tripped)
+        );
+        breaker
+    }
+
+    #[test]
+    fn repeated_failures_pause_only_that_executor() {
+        let now = Utc::now();
+        let mut breaker = tripped(now);
+
+        assert!(breaker.admit(CLAUDE, Uuid::new_v4(), now).is_err());
+        assert!(
+            breaker
+                .admit(BaseCodingAgent::Codex, Uuid::new_v4(), now)
+                .is_ok()
+        );
+
+        // A success in between resets the count
+        let mut breaker = ExecutorCircuitBreaker::default();
+        breaker.record(CLAUDE, Uuid::new_v4(), false, now);
+        breaker.record(CLAUDE, Uuid::new_v4(), false, now);
+        breaker.record(CLAUDE, Uuid::new_v4(), true, now);
+        assert_eq!(breaker.record(CLAUDE, Uuid::new_v4(), false, now), None);
+    }
+
+    #[test]
+    fn one_probe_after_cooldown_decides_recovery() {
+        let now = Utc::now();
+        let mut breaker = tripped(now);
+        let later = now + Duration::minutes(BASE_COOLDOWN_MINUTES);
+        let probe = Uuid::new_v4();
+
+        assert!(breaker.admit(CLAUDE, probe, later).is_ok());
+        assert!(breaker.admit(CLAUDE, Uuid::new_v4(), later).is_err());
+
+        // A failed probe reopens with a longer cooldown
+        assert_eq!(breaker.record(CLAUDE, probe, false, later), None);
+        assert!(
+            breaker
+                .admit(
+                    CLAUDE,
+                    Uuid::new_v4(),
+                    later + Duration::minutes(BASE_COOLDOWN_MINUTES)
+                )
+                .is_err()
+        );
+
+        let retry = later + Duration::minutes(2 * BASE_COOLDOWN_MINUTES);
+        let probe = Uuid::new_v4();
+        assert!(breaker.admit(CLAUDE, probe, retry).is_ok());
+        assert_eq!(
+            breaker.record(CLAUDE, probe, true, retry),
+            Some(CircuitEvent::Recovered)
+        );
+        assert!(breaker.admit(CLAUDE, Uuid::new_v4(), retry).is_ok());
+    }
+
+    #[test]
+    fn released_probe_lets_another_run_probe() {
+        let now = Utc::now();
+        let mut breaker = tripped(now);
+        let later = now + Duration::minutes(BASE_COOLDOWN_MINUTES);
+        let probe = Uuid::new_v4();
+        assert!(breaker.admit(CLAUDE, probe, later).is_ok());
+
+        breaker.release(probe);
+        assert!(breaker.admit(CLAUDE, Uuid::new_v4(), later).is_ok());
+    }
+}
@@ -0,0 +1,245 @@
+//! The code host behind a repository's pull requests.
+//!
+//! Routes, the PR monitor and the create/merge PR column actions talk to a
+//! [`CodeHost`] picked from the repo's forge settings, so workflows behave the same on
+//! GitHub (through `gh`), Gitea/Forgejo, GitLab and Bitbucket Cloud. GitLab merge
+//! requests are treated as pull requests throughout.
+
+use async_trait::async_trait;
+use db::models::{
+    merge::PullRequestInfo,
+    repo_forge_settings::{ForgeKind, RepoForgeSettings},
+    repo_merge_settings::MergeStrategy,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ts_rs::TS;
+use url::{Host, Url};
+
+use crate::services::{
+    bitbucket::BitbucketHost,
+    env_vault::EnvVaultError,
+    gitea::GiteaHost,
+    github::{CreatePrRequest, GitHubHost, GitHubServiceError, UnifiedPrComment},
+    gitlab::GitLabHost,
+};
+
+pub(crate) mod http;
+
+/// State of one status check (CI job, commit status) on a pull request
+#[derive(Debug, Clone, Copy, Serialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PrCheckState {
+    Pending,
+    Success,
+    Failure,
+    Skipped,
+}
+
+impl PrCheckState {
+    /// Map a forge's check status or conclusion (`success`, `FAILURE`, `in_progress`,
+    /// `skipped`…) onto a state; anything unfinished or unknown is pending
+    pub fn from_forge(state: &str) -> Self {
+        match state.to_ascii_lowercase().as_str() {
+            "success" | "successful" | "neutral" | "pass" => PrCheckState::Success,
+            "failure" | "failed" | "error" | "warning" | "fail" | "cancelled" | "canceled"
+            | "stopped" | "timed_out" | "action_required" | "startup_failure" => {
+                PrCheckState::Failure
+            }
+            "skipped" | "skipping" | "stale" => PrCheckState::Skipped,
+            _ => PrCheckState::Pending,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct PrCheck {
+    pub name: String,
+    pub state: PrCheckState,
+    pub description: Option<String>,
+    /// Link to the check's logs or details page
+    pub url: Option<String>,
+}
+
+/// State of a PR's checks as a whole: failure if any check failed, pending while any
+/// runs, success otherwise. None when the PR has no checks.
+pub fn overall_state(checks: &[PrCheck]) -> Option<PrCheckState> {
+    let states = || checks.iter().map(|check| check.state);
+    if checks.is_empty() {
+        None
+    } else if states().any(|state| state == PrCheckState::Failure) {
+        Some(PrCheckState::Failure)
+    } else if states().any(|state| state == PrCheckState::Pending) {
+        Some(PrCheckState::Pending)
+    } else {
+        Some(PrCheckState::Success)
+    }
+}
+
+/// Problems opening a pull request that the user has to fix
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum CreatePrError {
+    GithubCliNotInstalled,
+    GithubCliNotLoggedIn,
+    GitCliNotLoggedIn,
+    GitCliNotInstalled,
+    TargetBranchNotFound { branch: String },
+}
+
+impl CreatePrError {
+    /// Human-readable form for automation history
+    pub fn message(&self) -> String {
+        match self {
+            CreatePrError::GithubCliNotInstalled => "The GitHub CLI is not installed".to_string(),
+            CreatePrError::GithubCliNotLoggedIn => "The GitHub CLI is not logged in".to_string(),
+            CreatePrError::GitCliNotLoggedIn => "git is not authenticated for push".to_string(),
+            CreatePrError::GitCliNotInstalled => "git is not installed".to_string(),
+            CreatePrError::TargetBranchNotFound { branch } => {
+                format!("Target branch '{branch}' does not exist on the remote")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CodeHostError {
+    #[error(transparent)]
+    GitHub(#[from] GitHubServiceError),
+    #[error("{0} is not configured for this repository: {1}")]
+    NotConfigured(&'static str, String),
+    #[error("Repository error: {0}")]
+    Repository(String),
+    #[error("{0} authentication failed: {1}")]
+    AuthFailed(&'static str, String),
+    #[error("{0} request failed: {1}")]
+    Request(&'static str, String),
+    #[error("{host} API returned {status}: {message}")]
+    Api {
+        host: &'static str,
+        status: u16,
+        message: String,
+    },
+    #[error("Unexpected response from {0}: {1}")]
+    UnexpectedResponse(&'static str, String),
+    #[error(transparent)]
+    EnvVault(#[from] EnvVaultError),
+}
+
+impl CodeHostError {
+    pub fn should_retry(&self) -> bool {
+        match self {
+            CodeHostError::GitHub(err) => err.should_retry(),
+            CodeHostError::Request(..) => true,
+            CodeHostError::Api { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+/// Pull request operations on one repository, whatever hosts it
+#[async_trait]
+pub trait CodeHost: Send + Sync {
+    /// Which provider this is, for logs and analytics
+    fn kind(&self) -> ForgeKind;
+
+    /// Open a pull request from `head_branch` into `base_branch`
+    async fn create_pr(&self, request: &CreatePrRequest) -> Result<PullRequestInfo, CodeHostError>;
+
+    /// Current state of a pull request
+    async fn get_pr(&self, number: i64) -> Result<PullRequestInfo, CodeHostError>;
+
+    /// Pull requests (open, closed and merged) whose head is `branch`, newest first
+    async fn list_prs_for_branch(
+        &self,
+        branch: &str,
+    ) -> Result<Vec<PullRequestInfo>, CodeHostError>;
+
+    /// Comments on a pull request, oldest first
+    async fn get_pr_comments(&self, number: i64) -> Result<Vec<UnifiedPrComment>, CodeHostError>;
+
+    /// Status checks of the pull request's head commit
+    async fn get_pr_checks(&self, number: i64) -> Result<Vec<PrCheck>, CodeHostError>;
+
+    /// Merge a pull request on the host with the repo's merge strategy
+    async fn merge_pr(&self, number: i64, strategy: MergeStrategy) -> Result<(), CodeHostError>;
+}
+
+/// Code host for a repo: its forge settings pick the provider and `url` (the repo's
+/// remote, or the web URL of one of its pull requests) names the repository there
+pub fn for_repo(
+    settings: &RepoForgeSettings,
+    url: &str,
+) -> Result<Box<dyn CodeHost>, CodeHostError> {
+    let host: Box<dyn CodeHost> = match settings.kind() {
+        ForgeKind::Github => Box::new(GitHubHost::new(url)?),
+        ForgeKind::Gitea => Box::new(GiteaHost::new(settings, url)?),
+        ForgeKind::Gitlab => Box::new(GitLabHost::new(settings, url)?),
+        ForgeKind::Bitbucket => Box::new(BitbucketHost::new(settings, url)?),
+    };
+    Ok(host)
+}
+
+/// Whether an instance URL may be given an API token: https, or plain http to a
+/// loopback host for an instance running on this machine
+pub fn is_secure_instance_url(url: &str) -> bool {
+    let Ok(url) = Url::parse(url) else {
+        return false;
+    };
+    match (url.scheme(), url.host()) {
+        ("https", Some(_)) => true,
+        ("http", Some(Host::Domain(domain))) => domain.eq_ignore_ascii_case("localhost"),
+        ("http", Some(Host::Ipv4(ip))) => ip.is_loopback(),
+        ("http", Some(Host::Ipv6(ip))) => ip.is_loopback(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(state: &str) -> PrCheck {
+        PrCheck {
+            name: state.to_string(),
+            state: PrCheckState::from_forge(state),
+            description: None,
+            url: None,
+        }
+    }
+
+    #[test]
+    fn failures_outrank_pending_checks_and_skipped_checks_count_as_passing() {
+        assert_eq!(overall_state(&[]), None);
+        assert_eq!(
+            overall_state(&[check("success"), check("SKIPPED")]),
+            Some(PrCheckState::Success)
+        );
+        assert_eq!(
+            overall_state(&[check("SUCCESSFUL"), check("IN_PROGRESS")]),
+            Some(PrCheckState::Pending)
+        );
+        assert_eq!(
+            overall_state(&[check("pending"), check("error")]),
+            Some(PrCheckState::Failure)
+        );
+        assert_eq!(
+            overall_state(&[check("running"), check("canceled")]),
+            Some(PrCheckState::Failure)
+        );
+    }
+
+    #[test]
+    fn instance_urls_need_https_unless_they_are_local() {
+        assert!(is_secure_instance_url("https://codeberg.org"));
+        assert!(is_secure_instance_url("https://git.example.com/gitea/"));
+        assert!(is_secure_instance_url("http://localhost:3000"));
+        assert!(is_secure_instance_url("http://127.0.0.1:3000"));
+        assert!(is_secure_instance_url("http://[::1]:3000"));
+        assert!(!is_secure_instance_url("http://gitea.example.com"));
+        assert!(!is_secure_instance_url("http://10.0.0.5:3000"));
+        assert!(!is_secure_instance_url("ftp://localhost"));
+        assert!(!is_secure_instance_url("codeberg.org"));
+    }
+}
@@ -0,0 +1,208 @@
+//! JSON-over-HTTPS plumbing shared by the REST code hosts (Gitea/Forgejo, GitLab,
+//! Bitbucket).
+
+use std::time::Duration;
+
+use backon::{ExponentialBuilder, Retryable};
+use db::models::repo_forge_settings::RepoForgeSettings;
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+
+use crate::services::{env_vault::EnvVault, forge::CodeHostError};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How requests authenticate
+#[derive(Clone)]
+pub(crate) enum Auth {
+    None,
+    /// A raw header such as `PRIVATE-TOKEN: <token>`
+    Header(&'static str, String),
+    Bearer(String),
+    Basic(String, String),
+}
+
+/// The repo's stored API token, decrypted
+pub(crate) fn api_token(settings: &RepoForgeSettings) -> Result<Option<String>, CodeHostError> {
+    match (&settings.token_ciphertext, &settings.token_nonce) {
+        (Some(ciphertext), Some(nonce)) => Ok(Some(EnvVault::shared()?.open(
+            "forge API token",
+            ciphertext,
+            nonce,
+        )?)),
+        _ => Ok(None),
+    }
+}
+
+/// The configured instance URL without a trailing `/`, or `default`
+pub(crate) fn base_url(settings: &RepoForgeSettings, default: Option<&str>) -> Option<String> {
+    settings
+        .base_url
+        .as_deref()
+        .map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+        .or(default)
+        .map(str::to_string)
+}
+
+/// Lowercased host (without port) and path of an HTTP(S), `ssh://` or scp-style
+/// (`git@host:owner/repo.git`) URL
+pub(crate) fn host_and_path(url: &str) -> (String, &str) {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let rest = match rest.find(['/', '@']) {
+        Some(at) if rest[at..].starts_with('@') => &rest[at + 1..],
+        _ => rest,
+    };
+    let end = rest.find(['/', ':']).unwrap_or(rest.len());
+    let (host, mut path) = rest.split_at(end);
+    if let Some(after_colon) = path.strip_prefix(':') {
+        // `host:2222/owner/repo` has a port, `host:owner/repo` is scp-style
+        let digits = after_colon.chars().take_while(char::is_ascii_digit).count();
+        path = match after_colon[digits..].strip_prefix('/') {
+            Some(after_port) if digits > 0 => after_port,
+            _ => after_colon,
+        };
+    }
+    (host.to_ascii_lowercase(), path.trim_matches('/'))
+}
+
+/// Path of `url` on the instance at `base_url` (which may be served from a sub-path),
+/// or None when it points at another host
+pub(crate) fn path_on_instance<'a>(url: &'a str, base_url: &str) -> Option<&'a str> {
+    let (host, path) = host_and_path(url);
+    let (base_host, base_path) = host_and_path(base_url);
+    if host != base_host {
+        return None;
+    }
+    Some(match path.strip_prefix(base_path) {
+        Some(rest) if !base_path.is_empty() && rest.starts_with('/') => &rest[1..],
+        _ => path,
+    })
+}
+
+#[derive(Clone)]
+pub(crate) struct RestClient {
+    client: reqwest::Client,
+    /// Provider name for errors, e.g. `GitLab`
+    host: &'static str,
+    api_root: String,
+    auth: Auth,
+}
+
+impl RestClient {
+    pub fn new(host: &'static str, api_root: String, auth: Auth) -> Result<Self, CodeHostError> {
+        let client = reqwest::Client::builder()
+            .timeout(HTTP_TIMEOUT)
+            .build()
+            .map_err(|e| CodeHostError::Request(host, e.to_string()))?;
+        Ok(Self {
+            client,
+            host,
+            api_root,
+            auth,
+        })
+    }
+
+    async fn send(
+        &self,
+        method: &Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<&Value>,
+    ) -> Result<Value, CodeHostError> {
+        let mut request = self
+            .client
+            .request(method.clone(), format!("{}{path}", self.api_root))
+            .query(query);
+        request = match &self.auth {
+            Auth::None => request,
+            Auth::Header(name, value) => request.header(*name, value),
+            Auth::Bearer(token) => request.bearer_auth(token),
+            Auth::Basic(user, password) => request.basic_auth(user, Some(password)),
+        };
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let request_failed = |e: reqwest::Error| {
+            CodeHostError::Request(self.host, e.without_url().to_string())
+        };
+        let response = request.send().await.map_err(request_failed)?;
+        let status = response.status();
+        let text = response.text().await.map_err(request_failed)?;
+        if status.is_success() {
+            // Merge endpoints may answer with an empty body
+            if text.trim().is_empty() {
+                return Ok(Value::Null);
+            }
+            return serde_json::from_str(&text)
+                .map_err(|e| CodeHostError::UnexpectedResponse(self.host, e.to_string()));
+        }
+        let message = serde_json::from_str::<Value>(&text)
+            .ok()
+            .and_then(|v| {
+                v.get("message")
+                    .or_else(|| v.pointer("/error/message"))
+                    .map(|m| m.as_str().map_or_else(|| m.to_string(), str::to_string))
+            })
+            .unwrap_or_else(|| text.chars().take(500).collect());
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                Err(CodeHostError::AuthFailed(self.host, message))
+            }
+            _ => Err(CodeHostError::Api {
+                host: self.host,
+                status: status.as_u16(),
+                message,
+            }),
+        }
+    }
+
+    /// Call the API, retrying network errors and server errors of idempotent methods. A
+    /// repeated POST could act twice when the first attempt reached the host.
+    async fn call(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<&Value>,
+    ) -> Result<Value, CodeHostError> {
+        (|| async { self.send(&method, path, query, body).await })
+            .retry(
+                &ExponentialBuilder::default()
+                    .with_min_delay(Duration::from_secs(1))
+                    .with_max_delay(Duration::from_secs(30))
+                    .with_max_times(3)
+                    .with_jitter(),
+            )
+            .when(|e: &CodeHostError| method.is_idempotent() && e.should_retry())
+            .notify(|err: &CodeHostError, dur: Duration| {
+                tracing::warn!(
+                    "{} API call failed, retrying after {:.2}s: {}",
+                    self.host,
+                    dur.as_secs_f64(),
+                    err
+                );
+            })
+            .await
+    }
+
+    pub async fn get(&self, path: &str, query: &[(&str, String)]) -> Result<Value, CodeHostError> {
+        self.call(Method::GET, path, query, None).await
+    }
+
+    /// Create or change something (a pull request, a merge). Sent once: after a timeout
+    /// the host may have acted anyway, and a retry would open a second pull request or
+    /// fail on the merge that went through.
+    pub async fn submit(
+        &self,
+        method: Method,
+        path: &str,
+        body: &Value,
+    ) -> Result<Value, CodeHostError> {
+        self.send(&method, path, &[], Some(body)).await
+    }
+
+    pub fn unexpected(&self, what: &str) -> CodeHostError {
+        CodeHostError::UnexpectedResponse(self.host, what.to_string())
+    }
+}
@@ -66,6 +66,24 @@ pub struct GitBranch {
     pub last_commit_date: DateTime<Utc>,
 }
 
+/// How `merge_changes_with_options` lands a task branch on its base branch
+#[derive(Debug, Clone, Copy)]
+pub struct MergeOptions {
+    /// Create a merge commit with both branches as parents instead of a squash commit
+    pub merge_commit: bool,
+    /// Refuse to merge while the base branch has commits the task branch lacks
+    pub require_up_to_date: bool,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        Self {
+            merge_commit: false,
+            require_up_to_date: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct HeadInfo {
     pub branch: String,
@@ -789,6 +807,25 @@ impl GitService {
         task_branch_name: &str,
         base_branch_name: &str,
         commit_message: &str,
+    ) -> Result<String, GitServiceError> {
+        self.merge_changes_with_options(
+            base_worktree_path,
+            task_worktree_path,
+            task_branch_name,
+            base_branch_name,
+            commit_message,
+            MergeOptions::default(),
+        )
+    }
+
+    pub fn merge_changes_with_options(
+        &self,
+        base_worktree_path: &Path,
+        task_worktree_path: &Path,
+        task_branch_name: &str,
+        base_branch_name: &str,
+        commit_message: &str,
+        options: MergeOptions,
     ) -> Result<String, GitServiceError> {
         // Open the repositories
         let task_repo = self.open_repo(task_worktree_path)?;
@@ -799,7 +836,7 @@ impl GitService {
         let (_, task_behind) =
             self.get_branch_status(base_worktree_path, task_branch_name, base_branch_name)?;
 
-        if task_behind > 0 {
+        if options.require_up_to_date && task_behind > 0 {
             return Err(GitServiceError::BranchesDiverged(format!(
                 "Cannot merge: base branch '{base_branch_name}' is {task_behind} commits ahead of task branch '{task_branch_name}'. The base branch has moved forward since the task was created.",
             )));
@@ -826,16 +863,24 @@ impl GitService {
 
                 // Use CLI merge in base context
                 self.ensure_cli_commit_identity(&base_checkout_path)?;
-                let sha = git_cli
-                    .merge_squash_commit(
+                let merged = if options.merge_commit {
+                    git_cli.merge_no_ff_commit(
                         &base_checkout_path,
                         base_branch_name,
                         task_branch_name,
                         commit_message,
                     )
-                    .map_err(|e| {
-                        GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
-                    })?;
+                } else {
+                    git_cli.merge_squash_commit(
+                        &base_checkout_path,
+                        base_branch_name,
+                        task_branch_name,
+                        commit_message,
+                    )
+                };
+                let sha = merged.map_err(|e| {
+                    GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
+                })?;
 
                 // Update task branch ref for continuity
                 let task_refname = format!("refs/heads/{task_branch_name}");
@@ -856,28 +901,29 @@ impl GitService {
                 let base_commit = base_branch.get().peel_to_commit()?;
                 let task_commit = task_branch.get().peel_to_commit()?;
 
-                // Create the squash commit in-memory (no checkout) and update the base branch ref
+                // Create the merge commit in-memory (no checkout) and update the base branch ref
                 let signature = self.signature_with_fallback(&task_repo)?;
-                let squash_commit_id = self.perform_squash_merge(
+                let merge_commit_id = self.perform_merge(
                     &task_repo,
                     &base_commit,
                     &task_commit,
                     &signature,
                     commit_message,
                     base_branch_name,
+                    options.merge_commit,
                 )?;
 
-                // Update the task branch to the new squash commit so follow-up
+                // Update the task branch to the new commit so follow-up
                 // work can continue from the merged state without conflicts.
                 let task_refname = format!("refs/heads/{task_branch_name}");
                 base_repo.reference(
                     &task_refname,
-                    squash_commit_id,
+                    merge_commit_id,
                     true,
-                    "Reset task branch after squash merge",
+                    "Reset task branch after merge",
                 )?;
 
-                Ok(squash_commit_id.to_string())
+                Ok(merge_commit_id.to_string())
             }
         }
     }
@@ -1097,6 +1143,29 @@ impl GitService {
         Ok((st.uncommitted_tracked, st.untracked))
     }
 
+    /// Paths changed in the worktree (committed or not) since `base_commit`, sorted.
+    /// Renames report both the old and the new path.
+    pub fn get_changed_paths(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let entries = GitCli::new()
+            .diff_status(
+                worktree_path,
+                base_commit,
+                StatusDiffOptions { path_filter: None },
+            )
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git diff failed: {e}")))?;
+        let mut paths: Vec<String> = entries
+            .into_iter()
+            .flat_map(|e| std::iter::once(e.path).chain(e.old_path))
+            .collect();
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
+
     /// Evaluate whether any action is needed to reset to `target_commit_oid` and
     /// optionally perform the actions.
     pub fn reconcile_worktree_to_commit(
@@ -1161,6 +1230,130 @@ impl GitService {
         Ok(())
     }
 
+    /// Whether a worktree has tracked or untracked changes outside `keep_path`, i.e.
+    /// anything `discard_changes` would throw away besides commits
+    pub fn has_discardable_changes(
+        &self,
+        worktree_path: &Path,
+        keep_path: &str,
+    ) -> Result<bool, GitServiceError> {
+        let exclude = format!(":(exclude){keep_path}");
+        let out = GitCli::new()
+            .git(
+                worktree_path,
+                ["--no-optional-locks", "status", "--porcelain", "--", ".", exclude.as_str()],
+            )
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git status failed: {e}")))?;
+        Ok(!out.trim().is_empty())
+    }
+
+    /// Throw away everything done in a worktree since `base_commit`: commits are
+    /// undone and tracked/untracked changes removed. Paths under `keep_path` are
+    /// left as they are (ignored files are never touched).
+    pub fn discard_changes(
+        &self,
+        worktree_path: &Path,
+        base_commit: Option<&str>,
+        keep_path: &str,
+    ) -> Result<(), GitServiceError> {
+        let cli = GitCli::new();
+        let run = |args: &[&str]| {
+            cli.git(worktree_path, args).map_err(|e| {
+                GitServiceError::InvalidRepository(format!("git {} failed: {e}", args[0]))
+            })
+        };
+        let exclude = format!(":(exclude){keep_path}");
+
+        // Move HEAD back but keep the files, so `keep_path` survives even if tracked
+        run(&["reset", "-q", base_commit.unwrap_or("HEAD")])?;
+        run(&["checkout", "--", ".", &exclude])?;
+        run(&["clean", "-fdq", "--", ".", &exclude])?;
+        Ok(())
+    }
+
+    /// Everything done in a worktree since `base_commit` (commits as well as tracked and
+    /// untracked changes) as a binary patch, leaving out `keep_path`. Stages the changes.
+    pub fn proposed_patch(
+        &self,
+        worktree_path: &Path,
+        base_commit: Option<&str>,
+        keep_path: &str,
+    ) -> Result<String, GitServiceError> {
+        let cli = GitCli::new();
+        let run = |args: &[&str]| {
+            cli.git(worktree_path, args).map_err(|e| {
+                GitServiceError::InvalidRepository(format!("git {} failed: {e}", args[0]))
+            })
+        };
+        let exclude = format!(":(exclude){keep_path}");
+
+        run(&["add", "-A", "--", ".", &exclude])?;
+        let base = base_commit.unwrap_or("HEAD");
+        run(&["diff", "--cached", "--binary", base, "--", ".", &exclude])
+    }
+
+    /// Apply a patch to a worktree and commit it; returns the new commit
+    pub fn apply_patch(
+        &self,
+        worktree_path: &Path,
+        patch: &str,
+        message: &str,
+    ) -> Result<String, GitServiceError> {
+        let cli = GitCli::new();
+        cli.apply_patch(worktree_path, patch)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git apply failed: {e}")))?;
+        cli.commit(worktree_path, message)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git commit failed: {e}")))?;
+        Ok(self.get_head_info(worktree_path)?.oid)
+    }
+
+    /// Commits on `branch` since `base_commit` as an mbox patch series for `git am`, each
+    /// subject tagged `[<subject_prefix> n/m]`
+    pub fn format_patches(
+        &self,
+        repo_path: &Path,
+        branch: &str,
+        base_commit: &Commit,
+        subject_prefix: &str,
+    ) -> Result<String, GitServiceError> {
+        let range = format!("{base_commit}..{branch}");
+        let subject_prefix = format!("--subject-prefix={subject_prefix}");
+        GitCli::new()
+            .git(
+                repo_path,
+                ["format-patch", "--stdout", "--binary", &subject_prefix, &range],
+            )
+            .map_err(|e| {
+                GitServiceError::InvalidRepository(format!("git format-patch failed: {e}"))
+            })
+    }
+
+    /// One git bundle holding branches of several repos, for `git clone` or `git fetch`.
+    /// Each entry is a repo path, one of its branches and the branch's name in the bundle.
+    pub fn bundle_branches(
+        &self,
+        branches: &[(&Path, &str, String)],
+    ) -> Result<Vec<u8>, GitServiceError> {
+        let cli = GitCli::new();
+        let dir = tempfile::TempDir::new()?;
+        let run = |args: &[&str]| {
+            cli.git(dir.path(), args).map_err(|e| {
+                GitServiceError::InvalidRepository(format!("git {} failed: {e}", args[0]))
+            })
+        };
+
+        // Collect the branches in a scratch repo, then bundle all of its refs
+        run(&["init", "--bare", "--quiet"])?;
+        for (repo_path, branch, name) in branches {
+            let repo_path = repo_path.to_string_lossy();
+            let refspec = format!("refs/heads/{branch}:refs/heads/{name}");
+            run(&["fetch", "--quiet", "--no-tags", &repo_path, &refspec])?;
+        }
+        let bundle_path = dir.path().join("export.bundle");
+        run(&["bundle", "create", "--quiet", &bundle_path.to_string_lossy(), "--all"])?;
+        Ok(std::fs::read(bundle_path)?)
+    }
+
     /// Add a worktree for a branch, optionally creating the branch
     pub fn add_worktree(
         &self,
@@ -1273,7 +1466,10 @@ impl GitService {
     }
 
     /// Perform a squash merge of task branch into base branch, but fail on conflicts
-    fn perform_squash_merge(
+    /// Merge `task_commit` into the base branch as a squash commit (base as sole parent)
+    /// or, with `merge_commit`, as a merge commit with both as parents
+    #[allow(clippy::too_many_arguments)]
+    fn perform_merge(
         &self,
         repo: &Repository,
         base_commit: &git2::Commit,
@@ -1281,6 +1477,7 @@ impl GitService {
         signature: &git2::Signature,
         commit_message: &str,
         base_branch_name: &str,
+        merge_commit: bool,
     ) -> Result<git2::Oid, GitServiceError> {
         // In-memory merge to detect conflicts without touching the working tree
         let mut merge_opts = git2::MergeOptions::new();
@@ -1300,21 +1497,32 @@ impl GitService {
         let tree_id = index.write_tree_to(repo)?;
         let tree = repo.find_tree(tree_id)?;
 
-        // Create a squash commit: use merged tree with base_commit as sole parent
-        let squash_commit_id = repo.commit(
+        // Squash commit: merged tree with base_commit as sole parent.
+        // Merge commit: both the base and the task commit as parents.
+        let parents: Vec<&git2::Commit> = if merge_commit {
+            vec![base_commit, task_commit]
+        } else {
+            vec![base_commit]
+        };
+        let commit_id = repo.commit(
             None,           // Don't update any reference yet
             signature,      // Author
             signature,      // Committer
             commit_message, // Custom message
             &tree,          // Merged tree content
-            &[base_commit], // Single parent: base branch commit
+            &parents,
         )?;
 
         // Update the base branch reference to point to the new commit
         let refname = format!("refs/heads/{base_branch_name}");
-        repo.reference(&refname, squash_commit_id, true, "Squash merge")?;
+        let reflog = if merge_commit {
+            "Merge"
+        } else {
+            "Squash merge"
+        };
+        repo.reference(&refname, commit_id, true, reflog)?;
 
-        Ok(squash_commit_id)
+        Ok(commit_id)
     }
 
     /// Rebase a worktree branch onto a new base
@@ -1491,6 +1699,33 @@ impl GitService {
         Ok(())
     }
 
+    /// Create a local branch pointing at `start_point` (a commit SHA or another branch)
+    pub fn create_branch_at(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        start_point: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let commit = repo.revparse_single(start_point)?.peel_to_commit()?;
+        repo.branch(branch_name, &commit, false)?;
+        Ok(())
+    }
+
+    /// Delete a local branch. The branch must not be checked out in any worktree.
+    pub fn delete_local_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let mut branch = repo
+            .find_branch(branch_name, BranchType::Local)
+            .map_err(|_| GitServiceError::BranchNotFound(branch_name.to_string()))?;
+        branch.delete()?;
+        Ok(())
+    }
+
     /// Return true if a rebase is currently in progress in this worktree.
     pub fn is_rebase_in_progress(&self, worktree_path: &Path) -> Result<bool, GitServiceError> {
         let git = GitCli::new();
@@ -1595,21 +1830,27 @@ impl GitService {
         }
     }
 
-    /// Extract GitHub owner and repo name from git repo path
-    pub fn get_github_repo_info(
-        &self,
-        repo_path: &Path,
-    ) -> Result<GitHubRepoInfo, GitServiceError> {
+    /// URL of the repo's default remote
+    pub fn get_remote_url(&self, repo_path: &Path) -> Result<String, GitServiceError> {
         let repo = self.open_repo(repo_path)?;
         let remote_name = self.default_remote_name(&repo);
         let remote = repo.find_remote(&remote_name).map_err(|_| {
             GitServiceError::InvalidRepository(format!("No '{remote_name}' remote found"))
         })?;
 
-        let url = remote
+        remote
             .url()
-            .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))?;
-        GitHubRepoInfo::from_remote_url(url).map_err(|e| {
+            .map(str::to_string)
+            .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))
+    }
+
+    /// Extract GitHub owner and repo name from git repo path
+    pub fn get_github_repo_info(
+        &self,
+        repo_path: &Path,
+    ) -> Result<GitHubRepoInfo, GitServiceError> {
+        let url = self.get_remote_url(repo_path)?;
+        GitHubRepoInfo::from_remote_url(&url).map_err(|e| {
             GitServiceError::InvalidRepository(format!("Failed to parse remote URL: {e}"))
         })
     }
@@ -342,6 +342,13 @@ impl GitCli {
         self.git(worktree_path, ["commit", "-m", message])?;
         Ok(())
     }
+
+    /// Apply a patch to the worktree and stage it. Fails without touching anything if
+    /// any hunk doesn't apply.
+    pub fn apply_patch(&self, worktree_path: &Path, patch: &str) -> Result<(), GitCliError> {
+        self.git_with_stdin(worktree_path, ["apply", "--index", "-"], None, patch.as_bytes())?;
+        Ok(())
+    }
     /// Fetch a branch to the given remote using native git authentication.
     pub fn fetch_with_refspec(
         &self,
@@ -594,6 +601,27 @@ impl GitCli {
         Ok(sha)
     }
 
+    /// Merge `from_branch` into `base_branch` with a merge commit, even when a
+    /// fast-forward is possible. A conflicted merge is aborted before returning.
+    pub fn merge_no_ff_commit(
+        &self,
+        repo_path: &Path,
+        base_branch: &str,
+        from_branch: &str,
+        message: &str,
+    ) -> Result<String, GitCliError> {
+        self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
+        if let Err(e) = self.git(repo_path, ["merge", "--no-ff", "-m", message, from_branch]) {
+            let _ = self.abort_merge(repo_path);
+            return Err(e);
+        }
+        let sha = self
+            .git(repo_path, ["rev-parse", "HEAD"])?
+            .trim()
+            .to_string();
+        Ok(sha)
+    }
+
     /// Update a ref to a specific sha in the repo.
     pub fn update_ref(
         &self,
@@ -0,0 +1,363 @@
+//! Gitea and Forgejo integration over the REST API (`/api/v1`).
+//!
+//! Forgejo keeps Gitea's API, so one client serves both. A repo opts in through its
+//! forge settings, which hold the instance URL and an access token sealed by the env
+//! vault. Pull requests, their comments and commit statuses, and issues are read and
+//! created the same way the GitHub integration does through `gh`.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use db::models::{
+    merge::{MergeStatus, PullRequestInfo},
+    repo_forge_settings::{ForgeKind, RepoForgeSettings},
+    repo_merge_settings::MergeStrategy,
+};
+use reqwest::Method;
+use serde_json::{Value, json};
+use tracing::info;
+
+use crate::services::{
+    forge::{
+        CodeHost, CodeHostError, PrCheck, PrCheckState,
+        http::{self, Auth, RestClient},
+    },
+    github::{CreatePrRequest, UnifiedPrComment},
+};
+
+const HOST: &str = "Gitea/Forgejo";
+const PAGE_SIZE: usize = 50;
+/// Issue import stops after this many pages
+const MAX_ISSUE_PAGES: usize = 20;
+/// Gitea treats PRs whose title starts with this as work in progress (its drafts)
+const WIP_PREFIX: &str = "WIP: ";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GiteaRepoInfo {
+    pub owner: String,
+    pub repo_name: String,
+}
+
+impl GiteaRepoInfo {
+    /// Owner and repo from a clone URL or a web URL (repo, pull request or issue page)
+    /// on the instance at `base_url`, which may be served from a sub-path
+    pub fn from_url(url: &str, base_url: &str) -> Result<Self, CodeHostError> {
+        let invalid =
+            || CodeHostError::Repository(format!("'{url}' is not a repository on {base_url}"));
+        let path = http::path_on_instance(url, base_url).ok_or_else(invalid)?;
+        let mut segments = path.split('/').filter(|s| !s.is_empty());
+        let owner = segments.next().ok_or_else(invalid)?;
+        let repo_name = segments.next().ok_or_else(invalid)?;
+        let repo_name = repo_name.strip_suffix(".git").unwrap_or(repo_name);
+        if repo_name.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            owner: owner.to_string(),
+            repo_name: repo_name.to_string(),
+        })
+    }
+
+    fn api_path(&self, rest: &str) -> String {
+        format!("/repos/{}/{}{rest}", self.owner, self.repo_name)
+    }
+}
+
+/// Pull request info from a Gitea pull request object
+fn pr_info(value: &Value) -> Option<PullRequestInfo> {
+    let number = value.get("number")?.as_i64()?;
+    let url = value.get("html_url")?.as_str()?.to_string();
+    let status = if value.get("merged").and_then(Value::as_bool) == Some(true) {
+        MergeStatus::Merged
+    } else {
+        match value.get("state").and_then(Value::as_str) {
+            Some("open") => MergeStatus::Open,
+            Some("closed") => MergeStatus::Closed,
+            _ => MergeStatus::Unknown,
+        }
+    };
+    let merged_at = value
+        .get("merged_at")
+        .and_then(Value::as_str)
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+    let merge_commit_sha = value
+        .get("merge_commit_sha")
+        .and_then(Value::as_str)
+        .filter(|sha| !sha.is_empty())
+        .map(str::to_string);
+    Some(PullRequestInfo {
+        number: number as i32,
+        url,
+        status,
+        merged_at,
+        merge_commit_sha,
+    })
+}
+
+/// Checks from a combined commit status (`/commits/{ref}/status`)
+fn commit_checks(value: &Value) -> Vec<PrCheck> {
+    let text = |status: &Value, key: &str| {
+        status
+            .get(key)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    value
+        .get("statuses")
+        .and_then(Value::as_array)
+        .map(|statuses| {
+            statuses
+                .iter()
+                .map(|status| PrCheck {
+                    name: text(status, "context").unwrap_or_else(|| "status".to_string()),
+                    state: PrCheckState::from_forge(
+                        status
+                            .get("status")
+                            .or_else(|| status.get("state"))
+                            .and_then(Value::as_str)
+                            .unwrap_or_default(),
+                    ),
+                    description: text(status, "description"),
+                    url: text(status, "target_url"),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// One repository on a Gitea or Forgejo instance
+#[derive(Clone)]
+pub struct GiteaHost {
+    client: RestClient,
+    repo: GiteaRepoInfo,
+}
+
+impl GiteaHost {
+    /// Client for the repo named by `url` on its configured instance, decrypting its token
+    pub fn new(settings: &RepoForgeSettings, url: &str) -> Result<Self, CodeHostError> {
+        let base_url = http::base_url(settings, None).ok_or_else(|| {
+            CodeHostError::NotConfigured(HOST, "set the instance URL".to_string())
+        })?;
+        let repo = GiteaRepoInfo::from_url(url, &base_url)?;
+        let auth = match http::api_token(settings)? {
+            Some(token) => Auth::Header("Authorization", format!("token {token}")),
+            None => Auth::None,
+        };
+        Ok(Self {
+            client: RestClient::new(HOST, format!("{base_url}/api/v1"), auth)?,
+            repo,
+        })
+    }
+
+    fn parse_pr(&self, value: &Value) -> Result<PullRequestInfo, CodeHostError> {
+        pr_info(value).ok_or_else(|| self.client.unexpected("pull request without number or URL"))
+    }
+
+    /// Open issues (not pull requests) in the API's issue format, which matches GitHub's
+    pub async fn list_open_issues(&self) -> Result<Vec<Value>, CodeHostError> {
+        let mut issues = Vec::new();
+        for page in 1..=MAX_ISSUE_PAGES {
+            let query = [
+                ("state", "open".to_string()),
+                ("type", "issues".to_string()),
+                ("limit", PAGE_SIZE.to_string()),
+                ("page", page.to_string()),
+            ];
+            let value = self.client.get(&self.repo.api_path("/issues"), &query).await?;
+            let batch = value.as_array().cloned().unwrap_or_default();
+            let done = batch.len() < PAGE_SIZE;
+            issues.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+}
+
+#[async_trait]
+impl CodeHost for GiteaHost {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::Gitea
+    }
+
+    /// Drafts get Gitea's `WIP: ` title prefix
+    async fn create_pr(&self, request: &CreatePrRequest) -> Result<PullRequestInfo, CodeHostError> {
+        let title = if request.draft.unwrap_or(false) {
+            format!("{WIP_PREFIX}{}", request.title)
+        } else {
+            request.title.clone()
+        };
+        let body = json!({
+            "title": title,
+            "body": request.body.as_deref().unwrap_or(""),
+            "head": request.head_branch,
+            "base": request.base_branch,
+        });
+        let value = self
+            .client
+            .submit(Method::POST, &self.repo.api_path("/pulls"), &body)
+            .await?;
+        let pr = self.parse_pr(&value)?;
+        info!(
+            "Created Gitea PR #{} for branch {} in {}/{}",
+            pr.number, request.head_branch, self.repo.owner, self.repo.repo_name
+        );
+        Ok(pr)
+    }
+
+    async fn get_pr(&self, number: i64) -> Result<PullRequestInfo, CodeHostError> {
+        let value = self
+            .client
+            .get(&self.repo.api_path(&format!("/pulls/{number}")), &[])
+            .await?;
+        self.parse_pr(&value)
+    }
+
+    async fn list_prs_for_branch(
+        &self,
+        branch: &str,
+    ) -> Result<Vec<PullRequestInfo>, CodeHostError> {
+        let query = [
+            ("state", "all".to_string()),
+            ("sort", "recentupdate".to_string()),
+            ("limit", PAGE_SIZE.to_string()),
+        ];
+        let value = self.client.get(&self.repo.api_path("/pulls"), &query).await?;
+        Ok(value
+            .as_array()
+            .map(|prs| {
+                prs.iter()
+                    .filter(|pr| pr["head"]["ref"].as_str() == Some(branch))
+                    .filter_map(pr_info)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Conversation comments only; inline review comments aren't included
+    async fn get_pr_comments(&self, number: i64) -> Result<Vec<UnifiedPrComment>, CodeHostError> {
+        let path = self.repo.api_path(&format!("/issues/{number}/comments"));
+        let value = self.client.get(&path, &[]).await?;
+        let mut comments: Vec<UnifiedPrComment> = value
+            .as_array()
+            .map(|comments| {
+                comments
+                    .iter()
+                    .filter_map(|c| {
+                        Some(UnifiedPrComment::General {
+                            id: c.get("id")?.as_i64()?.to_string(),
+                            author: c["user"]["login"].as_str().unwrap_or_default().to_string(),
+                            author_association: "NONE".to_string(),
+                            body: c["body"].as_str().unwrap_or_default().to_string(),
+                            created_at: DateTime::parse_from_rfc3339(c["created_at"].as_str()?)
+                                .ok()?
+                                .with_timezone(&Utc),
+                            url: c["html_url"].as_str().unwrap_or_default().to_string(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        comments.sort_by_key(|c| match c {
+            UnifiedPrComment::General { created_at, .. }
+            | UnifiedPrComment::Review { created_at, .. } => *created_at,
+        });
+        Ok(comments)
+    }
+
+    /// Commit statuses (CI, Gitea/Forgejo Actions) of the pull request's head commit
+    async fn get_pr_checks(&self, number: i64) -> Result<Vec<PrCheck>, CodeHostError> {
+        let pr = self
+            .client
+            .get(&self.repo.api_path(&format!("/pulls/{number}")), &[])
+            .await?;
+        let sha = pr["head"]["sha"]
+            .as_str()
+            .ok_or_else(|| self.client.unexpected("pull request without head commit"))?;
+        let status = self
+            .client
+            .get(&self.repo.api_path(&format!("/commits/{sha}/status")), &[])
+            .await?;
+        Ok(commit_checks(&status))
+    }
+
+    async fn merge_pr(&self, number: i64, strategy: MergeStrategy) -> Result<(), CodeHostError> {
+        let style = match strategy {
+            MergeStrategy::Squash => "squash",
+            MergeStrategy::MergeCommit => "merge",
+        };
+        let path = self.repo.api_path(&format!("/pulls/{number}/merge"));
+        self.client
+            .submit(Method::POST, &path, &json!({ "Do": style }))
+            .await?;
+        info!(
+            "Merged Gitea PR #{number} in {}/{} ({style})",
+            self.repo.owner, self.repo.repo_name
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_info_parses_clone_and_web_urls_on_the_instance() {
+        let expected = GiteaRepoInfo {
+            owner: "forgejo".to_string(),
+            repo_name: "runner".to_string(),
+        };
+        for url in [
+            "https://codeberg.org/forgejo/runner.git",
+            "git@codeberg.org:forgejo/runner.git",
+            "ssh://git@codeberg.org:2222/forgejo/runner",
+            "https://codeberg.org/forgejo/runner/pulls/42",
+        ] {
+            assert_eq!(GiteaRepoInfo::from_url(url, "https://codeberg.org").unwrap(), expected);
+        }
+        assert_eq!(
+            GiteaRepoInfo::from_url(
+                "https://git.example.com/gitea/forgejo/runner/issues/3",
+                "https://git.example.com/gitea/"
+            )
+            .unwrap(),
+            expected
+        );
+        assert!(GiteaRepoInfo::from_url("git@github.com:o/r.git", "https://codeberg.org").is_err());
+        assert!(GiteaRepoInfo::from_url("https://codeberg.org/forgejo", "https://codeberg.org")
+            .is_err());
+    }
+
+    #[test]
+    fn pull_requests_and_commit_statuses_map_onto_shared_types() {
+        let merged = pr_info(&json!({
+            "number": 7, "html_url": "https://codeberg.org/o/r/pulls/7", "state": "closed",
+            "merged": true, "merged_at": "2026-10-16T09:30:00Z", "merge_commit_sha": "abc123"
+        }))
+        .unwrap();
+        assert_eq!(merged.number, 7);
+        assert!(matches!(merged.status, MergeStatus::Merged));
+        assert_eq!(merged.merge_commit_sha.as_deref(), Some("abc123"));
+        let closed = pr_info(&json!({
+            "number": 8, "html_url": "u", "state": "closed", "merged": false
+        }))
+        .unwrap();
+        assert!(matches!(closed.status, MergeStatus::Closed));
+
+        let checks = commit_checks(&json!({
+            "state": "pending",
+            "statuses": [
+                {"context": "ci/build", "status": "success", "target_url": "https://ci/1"},
+                {"context": "ci/test", "status": "pending", "description": ""}
+            ]
+        }));
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].state, PrCheckState::Success);
+        assert_eq!(checks[0].url.as_deref(), Some("https://ci/1"));
+        assert_eq!(checks[1].state, PrCheckState::Pending);
+        assert_eq!(checks[1].description, None);
+    }
+}
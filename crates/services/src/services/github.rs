@@ -1,8 +1,11 @@
 use std::time::Duration;
 
+use async_trait::async_trait;
 use backon::{ExponentialBuilder, Retryable};
 use chrono::{DateTime, Utc};
-use db::models::merge::PullRequestInfo;
+use db::models::{
+    merge::PullRequestInfo, repo_forge_settings::ForgeKind, repo_merge_settings::MergeStrategy,
+};
 use regex::Regex;
 use serde::Serialize;
 use thiserror::Error;
@@ -10,6 +13,8 @@ use tokio::task;
 use tracing::info;
 use ts_rs::TS;
 
+use crate::services::forge::{CodeHost, CodeHostError, PrCheck};
+
 mod cli;
 
 use cli::{GhCli, GhCliError, PrComment, PrReviewComment};
@@ -450,4 +455,119 @@ impl GitHubService {
         })
         .await
     }
+
+    /// Fetch the status checks of a pull request
+    pub async fn get_pr_checks(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        pr_number: i64,
+    ) -> Result<Vec<PrCheck>, GitHubServiceError> {
+        (|| async {
+            let owner = repo_info.owner.clone();
+            let repo = repo_info.repo_name.clone();
+            let cli = self.gh_cli.clone();
+            let checks = task::spawn_blocking(move || cli.get_pr_checks(&owner, &repo, pr_number))
+                .await
+                .map_err(|err| {
+                    GitHubServiceError::PullRequest(format!(
+                        "Failed to execute GitHub CLI for fetching PR #{pr_number} checks: {err}"
+                    ))
+                })?;
+            checks.map_err(GitHubServiceError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHubServiceError| e.should_retry())
+        .notify(|err: &GitHubServiceError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    /// Merge a pull request. Not retried: a merge that went through but timed out would
+    /// fail on the second attempt.
+    pub async fn merge_pr(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        pr_number: i64,
+        strategy: MergeStrategy,
+    ) -> Result<(), GitHubServiceError> {
+        let owner = repo_info.owner.clone();
+        let repo = repo_info.repo_name.clone();
+        let cli = self.gh_cli.clone();
+        let squash = strategy == MergeStrategy::Squash;
+        task::spawn_blocking(move || cli.merge_pr(&owner, &repo, pr_number, squash))
+            .await
+            .map_err(|err| {
+                GitHubServiceError::PullRequest(format!(
+                    "Failed to execute GitHub CLI for merging PR #{pr_number}: {err}"
+                ))
+            })??;
+        info!(
+            "Merged GitHub PR #{pr_number} in {}/{} ({})",
+            repo_info.owner,
+            repo_info.repo_name,
+            strategy.as_str()
+        );
+        Ok(())
+    }
+}
+
+/// A GitHub repository, reached through the `gh` CLI
+#[derive(Debug, Clone)]
+pub struct GitHubHost {
+    service: GitHubService,
+    repo: GitHubRepoInfo,
+}
+
+impl GitHubHost {
+    pub fn new(url: &str) -> Result<Self, GitHubServiceError> {
+        Ok(Self {
+            service: GitHubService::new()?,
+            repo: GitHubRepoInfo::from_remote_url(url)?,
+        })
+    }
+}
+
+#[async_trait]
+impl CodeHost for GitHubHost {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::Github
+    }
+
+    async fn create_pr(&self, request: &CreatePrRequest) -> Result<PullRequestInfo, CodeHostError> {
+        Ok(self.service.create_pr(&self.repo, request).await?)
+    }
+
+    async fn get_pr(&self, number: i64) -> Result<PullRequestInfo, CodeHostError> {
+        Ok(self.service.update_pr_status(&self.repo, number).await?)
+    }
+
+    async fn list_prs_for_branch(
+        &self,
+        branch: &str,
+    ) -> Result<Vec<PullRequestInfo>, CodeHostError> {
+        Ok(self.service.list_all_prs_for_branch(&self.repo, branch).await?)
+    }
+
+    async fn get_pr_comments(&self, number: i64) -> Result<Vec<UnifiedPrComment>, CodeHostError> {
+        Ok(self.service.get_pr_comments(&self.repo, number).await?)
+    }
+
+    async fn get_pr_checks(&self, number: i64) -> Result<Vec<PrCheck>, CodeHostError> {
+        Ok(self.service.get_pr_checks(&self.repo, number).await?)
+    }
+
+    async fn merge_pr(&self, number: i64, strategy: MergeStrategy) -> Result<(), CodeHostError> {
+        Ok(self.service.merge_pr(&self.repo, number, strategy).await?)
+    }
 }
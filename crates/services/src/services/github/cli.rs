@@ -17,7 +17,10 @@ use thiserror::Error;
 use ts_rs::TS;
 use utils::shell::resolve_executable_path_blocking;
 
-use crate::services::github::{CreatePrRequest, GitHubRepoInfo};
+use crate::services::{
+    forge::{PrCheck, PrCheckState},
+    github::{CreatePrRequest, GitHubRepoInfo},
+};
 
 /// Author information for a PR comment
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -245,6 +248,44 @@ impl GhCli {
         ])?;
         Self::parse_pr_review_comments(&raw)
     }
+
+    /// Fetch the status checks (check runs and commit statuses) of a pull request.
+    pub fn get_pr_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: i64,
+    ) -> Result<Vec<PrCheck>, GhCliError> {
+        let raw = self.run([
+            "pr",
+            "view",
+            &pr_number.to_string(),
+            "--repo",
+            &format!("{owner}/{repo}"),
+            "--json",
+            "statusCheckRollup",
+        ])?;
+        Self::parse_pr_checks(&raw)
+    }
+
+    /// Merge a pull request, squashing it into one commit or with a merge commit.
+    pub fn merge_pr(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: i64,
+        squash: bool,
+    ) -> Result<(), GhCliError> {
+        self.run([
+            "pr",
+            "merge",
+            &pr_number.to_string(),
+            "--repo",
+            &format!("{owner}/{repo}"),
+            if squash { "--squash" } else { "--merge" },
+        ])?;
+        Ok(())
+    }
 }
 
 impl GhCli {
@@ -357,6 +398,47 @@ impl GhCli {
         })
     }
 
+    /// Check runs carry `name`/`status`/`conclusion`/`detailsUrl`, commit statuses
+    /// `context`/`state`/`targetUrl`
+    fn parse_pr_checks(raw: &str) -> Result<Vec<PrCheck>, GhCliError> {
+        let value: Value = serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse gh pr view --json statusCheckRollup response: {err}; raw: {raw}"
+            ))
+        })?;
+        let text = |item: &Value, key: &str| {
+            item.get(key)
+                .and_then(Value::as_str)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let checks = value
+            .get("statusCheckRollup")
+            .and_then(Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|item| {
+                        let state = match text(item, "status").as_deref() {
+                            Some("COMPLETED") => text(item, "conclusion").unwrap_or_default(),
+                            Some(status) => status.to_string(),
+                            None => text(item, "state").unwrap_or_default(),
+                        };
+                        PrCheck {
+                            name: text(item, "name")
+                                .or_else(|| text(item, "context"))
+                                .unwrap_or_else(|| "check".to_string()),
+                            state: PrCheckState::from_forge(&state),
+                            description: text(item, "description"),
+                            url: text(item, "detailsUrl").or_else(|| text(item, "targetUrl")),
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(checks)
+    }
+
     fn extract_pr_info(value: &Value) -> Option<PullRequestInfo> {
         let number = value.get("number")?.as_i64()?;
         let url = value.get("url")?.as_str()?.to_string();
@@ -0,0 +1,338 @@
+//! GitLab merge requests over the REST API (`/api/v4`), on gitlab.com or a self-managed
+//! instance.
+//!
+//! Merge requests stand in for pull requests: the MR's `iid` is stored as the PR number
+//! and drafts use GitLab's `Draft: ` title prefix. Checks are the commit statuses of the
+//! MR's head commit, which include pipeline jobs.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use db::models::{
+    merge::{MergeStatus, PullRequestInfo},
+    repo_forge_settings::{ForgeKind, RepoForgeSettings},
+    repo_merge_settings::MergeStrategy,
+};
+use reqwest::Method;
+use serde_json::{Value, json};
+use tracing::info;
+
+use crate::services::{
+    forge::{
+        CodeHost, CodeHostError, PrCheck, PrCheckState,
+        http::{self, Auth, RestClient},
+    },
+    github::{CreatePrRequest, UnifiedPrComment},
+};
+
+const HOST: &str = "GitLab";
+const DEFAULT_BASE_URL: &str = "https://gitlab.com";
+const PAGE_SIZE: usize = 100;
+const DRAFT_PREFIX: &str = "Draft: ";
+
+/// Full path of a project (`group/subgroup/project`) from a clone URL or a web URL
+/// (project or merge request page) on the instance at `base_url`
+fn project_path(url: &str, base_url: &str) -> Result<String, CodeHostError> {
+    let invalid =
+        || CodeHostError::Repository(format!("'{url}' is not a GitLab project on {base_url}"));
+    let path = http::path_on_instance(url, base_url).ok_or_else(invalid)?;
+    // Web pages below a project live under `/-/` (`.../project/-/merge_requests/3`)
+    let path = path.split("/-/").next().unwrap_or_default().trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if path.split('/').filter(|s| !s.is_empty()).count() < 2 {
+        return Err(invalid());
+    }
+    Ok(path.to_string())
+}
+
+fn timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.as_str()?)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Pull request info from a merge request object
+fn pr_info(value: &Value) -> Option<PullRequestInfo> {
+    let status = match value.get("state").and_then(Value::as_str) {
+        Some("opened") => MergeStatus::Open,
+        Some("merged") => MergeStatus::Merged,
+        Some("closed") | Some("locked") => MergeStatus::Closed,
+        _ => MergeStatus::Unknown,
+    };
+    let merge_commit_sha = ["merge_commit_sha", "squash_commit_sha"]
+        .iter()
+        .find_map(|key| value.get(*key).and_then(Value::as_str))
+        .filter(|sha| !sha.is_empty())
+        .map(str::to_string);
+    Some(PullRequestInfo {
+        number: value.get("iid")?.as_i64()? as i32,
+        url: value.get("web_url")?.as_str()?.to_string(),
+        status,
+        merged_at: timestamp(&value["merged_at"]),
+        merge_commit_sha,
+    })
+}
+
+/// Comments from merge request notes; system notes ("added 2 commits") are skipped and
+/// diff notes become review comments
+fn note_comments(notes: &Value, mr_url: &str) -> Vec<UnifiedPrComment> {
+    let Some(notes) = notes.as_array() else {
+        return Vec::new();
+    };
+    notes
+        .iter()
+        .filter(|note| note["system"].as_bool() != Some(true))
+        .filter_map(|note| {
+            let id = note.get("id")?.as_i64()?;
+            let author = note["author"]["username"].as_str().unwrap_or_default().to_string();
+            let body = note["body"].as_str().unwrap_or_default().to_string();
+            let created_at = timestamp(&note["created_at"])?;
+            let url = format!("{mr_url}#note_{id}");
+            Some(match note["type"].as_str() {
+                Some("DiffNote") => UnifiedPrComment::Review {
+                    id,
+                    author,
+                    author_association: "NONE".to_string(),
+                    body,
+                    created_at,
+                    url,
+                    path: note["position"]["new_path"]
+                        .as_str()
+                        .or_else(|| note["position"]["old_path"].as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    line: note["position"]["new_line"]
+                        .as_i64()
+                        .or_else(|| note["position"]["old_line"].as_i64()),
+                    diff_hunk: String::new(),
+                },
+                _ => UnifiedPrComment::General {
+                    id: id.to_string(),
+                    author,
+                    author_association: "NONE".to_string(),
+                    body,
+                    created_at,
+                    url,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Checks from a commit's statuses (`/repository/commits/{sha}/statuses`)
+fn commit_checks(statuses: &Value) -> Vec<PrCheck> {
+    let text = |status: &Value, key: &str| {
+        status
+            .get(key)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    statuses
+        .as_array()
+        .map(|statuses| {
+            statuses
+                .iter()
+                .map(|status| PrCheck {
+                    name: text(status, "name").unwrap_or_else(|| "status".to_string()),
+                    state: PrCheckState::from_forge(
+                        status["status"].as_str().unwrap_or_default(),
+                    ),
+                    description: text(status, "description"),
+                    url: text(status, "target_url"),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// One project on a GitLab instance
+#[derive(Clone)]
+pub struct GitLabHost {
+    client: RestClient,
+    /// `group/subgroup/project`
+    project: String,
+}
+
+impl GitLabHost {
+    /// Client for the project named by `url`, on gitlab.com unless the forge settings
+    /// name another instance
+    pub fn new(settings: &RepoForgeSettings, url: &str) -> Result<Self, CodeHostError> {
+        let base_url = http::base_url(settings, Some(DEFAULT_BASE_URL)).unwrap_or_default();
+        let project = project_path(url, &base_url)?;
+        let auth = match http::api_token(settings)? {
+            Some(token) => Auth::Header("PRIVATE-TOKEN", token),
+            None => Auth::None,
+        };
+        Ok(Self {
+            client: RestClient::new(HOST, format!("{base_url}/api/v4"), auth)?,
+            project,
+        })
+    }
+
+    fn api_path(&self, rest: &str) -> String {
+        format!("/projects/{}{rest}", self.project.replace('/', "%2F"))
+    }
+
+    fn mr_path(&self, iid: i64, rest: &str) -> String {
+        self.api_path(&format!("/merge_requests/{iid}{rest}"))
+    }
+
+    fn parse_pr(&self, value: &Value) -> Result<PullRequestInfo, CodeHostError> {
+        pr_info(value).ok_or_else(|| self.client.unexpected("merge request without iid or URL"))
+    }
+}
+
+#[async_trait]
+impl CodeHost for GitLabHost {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::Gitlab
+    }
+
+    async fn create_pr(&self, request: &CreatePrRequest) -> Result<PullRequestInfo, CodeHostError> {
+        let title = if request.draft.unwrap_or(false) {
+            format!("{DRAFT_PREFIX}{}", request.title)
+        } else {
+            request.title.clone()
+        };
+        let body = json!({
+            "title": title,
+            "description": request.body.as_deref().unwrap_or(""),
+            "source_branch": request.head_branch,
+            "target_branch": request.base_branch,
+        });
+        let value = self
+            .client
+            .submit(Method::POST, &self.api_path("/merge_requests"), &body)
+            .await?;
+        let pr = self.parse_pr(&value)?;
+        info!(
+            "Created GitLab MR !{} for branch {} in {}",
+            pr.number, request.head_branch, self.project
+        );
+        Ok(pr)
+    }
+
+    async fn get_pr(&self, number: i64) -> Result<PullRequestInfo, CodeHostError> {
+        let value = self.client.get(&self.mr_path(number, ""), &[]).await?;
+        self.parse_pr(&value)
+    }
+
+    async fn list_prs_for_branch(
+        &self,
+        branch: &str,
+    ) -> Result<Vec<PullRequestInfo>, CodeHostError> {
+        let query = [
+            ("source_branch", branch.to_string()),
+            ("state", "all".to_string()),
+            ("order_by", "updated_at".to_string()),
+            ("sort", "desc".to_string()),
+            ("per_page", PAGE_SIZE.to_string()),
+        ];
+        let value = self
+            .client
+            .get(&self.api_path("/merge_requests"), &query)
+            .await?;
+        Ok(value
+            .as_array()
+            .map(|mrs| mrs.iter().filter_map(pr_info).collect())
+            .unwrap_or_default())
+    }
+
+    async fn get_pr_comments(&self, number: i64) -> Result<Vec<UnifiedPrComment>, CodeHostError> {
+        let mr = self.get_pr(number).await?;
+        let query = [
+            ("sort", "asc".to_string()),
+            ("order_by", "created_at".to_string()),
+            ("per_page", PAGE_SIZE.to_string()),
+        ];
+        let notes = self.client.get(&self.mr_path(number, "/notes"), &query).await?;
+        Ok(note_comments(&notes, &mr.url))
+    }
+
+    /// Statuses of the merge request's head commit, including pipeline jobs
+    async fn get_pr_checks(&self, number: i64) -> Result<Vec<PrCheck>, CodeHostError> {
+        let mr = self.client.get(&self.mr_path(number, ""), &[]).await?;
+        let sha = mr["sha"]
+            .as_str()
+            .ok_or_else(|| self.client.unexpected("merge request without head commit"))?;
+        let statuses = self
+            .client
+            .get(
+                &self.api_path(&format!("/repository/commits/{sha}/statuses")),
+                &[("per_page", PAGE_SIZE.to_string())],
+            )
+            .await?;
+        Ok(commit_checks(&statuses))
+    }
+
+    async fn merge_pr(&self, number: i64, strategy: MergeStrategy) -> Result<(), CodeHostError> {
+        let body = json!({ "squash": strategy == MergeStrategy::Squash });
+        self.client
+            .submit(Method::PUT, &self.mr_path(number, "/merge"), &body)
+            .await?;
+        info!(
+            "Merged GitLab MR !{number} in {} ({})",
+            self.project,
+            strategy.as_str()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_path_keeps_nested_groups_and_drops_web_suffixes() {
+        for url in [
+            "https://gitlab.com/acme/platform/api.git",
+            "git@gitlab.com:acme/platform/api.git",
+            "https://gitlab.com/acme/platform/api/-/merge_requests/12",
+        ] {
+            assert_eq!(project_path(url, DEFAULT_BASE_URL).unwrap(), "acme/platform/api");
+        }
+        assert_eq!(
+            project_path("ssh://git@git.corp.dev:2222/team/app", "https://git.corp.dev").unwrap(),
+            "team/app"
+        );
+        assert!(project_path("https://gitlab.com/acme", DEFAULT_BASE_URL).is_err());
+        assert!(project_path("git@github.com:acme/api.git", DEFAULT_BASE_URL).is_err());
+    }
+
+    #[test]
+    fn merge_requests_and_notes_map_onto_shared_types() {
+        let merged = pr_info(&json!({
+            "iid": 12, "web_url": "https://gitlab.com/a/b/-/merge_requests/12",
+            "state": "merged", "merged_at": "2026-10-16T09:30:00Z",
+            "merge_commit_sha": null, "squash_commit_sha": "abc123"
+        }))
+        .unwrap();
+        assert_eq!(merged.number, 12);
+        assert!(matches!(merged.status, MergeStatus::Merged));
+        assert_eq!(merged.merge_commit_sha.as_deref(), Some("abc123"));
+
+        let comments = note_comments(
+            &json!([
+                {"id": 1, "system": true, "body": "added 1 commit",
+                 "author": {"username": "bot"}, "created_at": "2026-10-16T09:00:00Z"},
+                {"id": 2, "type": null, "body": "LGTM",
+                 "author": {"username": "ada"}, "created_at": "2026-10-16T09:01:00Z"},
+                {"id": 3, "type": "DiffNote", "body": "nit",
+                 "author": {"username": "ada"}, "created_at": "2026-10-16T09:02:00Z",
+                 "position": {"new_path": "src/lib.rs", "new_line": 40}}
+            ]),
+            "https://gitlab.com/a/b/-/merge_requests/12",
+        );
+        assert_eq!(comments.len(), 2);
+        assert!(matches!(&comments[0], UnifiedPrComment::General { body, .. } if body == "LGTM"));
+        match &comments[1] {
+            UnifiedPrComment::Review { path, line, url, .. } => {
+                assert_eq!(path, "src/lib.rs");
+                assert_eq!(*line, Some(40));
+                assert!(url.ends_with("#note_3"));
+            }
+            other => panic!("expected a review comment, got {other:?}"),
+        }
+    }
+}
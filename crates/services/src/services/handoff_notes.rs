@@ -0,0 +1,201 @@
+//! Handoff notes left by agents when they finish a column.
+//!
+//! Agents can write a note explicitly with the `write_handoff_note` MCP tool. When an
+//! agent finishes a column without having done so, a note is derived from its
+//! `.vibe/summary.md`, its decision file and the files it changed during the run.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::ExecutionContext,
+    execution_process_repo_state::ExecutionProcessRepoState,
+    handoff_note::{CreateHandoffNote, HandoffNote, HandoffNoteSource},
+    kanban_column::KanbanColumn,
+};
+use serde_json::Value;
+use sqlx::PgPool;
+
+use super::git::{Commit, GitService};
+
+/// Summary for an automatic note: the agent's summary file, else the decision's
+/// `summary`, else the answer it gave
+pub fn summary_from(summary_md: Option<&str>, decision: Option<&Value>) -> String {
+    let decision_str = |key: &str| {
+        decision
+            .and_then(|d| d.get(key))
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+    if let Some(summary) = summary_md.map(str::trim).filter(|s| !s.is_empty()) {
+        summary.to_string()
+    } else if let Some(summary) = decision_str("summary") {
+        summary.to_string()
+    } else if let Some(answer) = decision_str("answer") {
+        format!("Finished with answer \"{answer}\"")
+    } else {
+        "Finished without a written summary".to_string()
+    }
+}
+
+/// `open_questions` from the decision file, as a list or a single string
+pub fn open_questions_from(decision: Option<&Value>) -> Vec<String> {
+    match decision.and_then(|d| d.get("open_questions")) {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::trim)
+            .filter(|q| !q.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Some(Value::String(question)) if !question.trim().is_empty() => {
+            vec![question.trim().to_string()]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// `.vibe/summary.md` from the workspace root or one of its repo directories, if it
+/// was written since `since` (summaries of earlier columns are left in place)
+async fn read_summary_file(workspace_root: &Path, since: DateTime<Utc>) -> Option<String> {
+    let mut candidates = vec![workspace_root.join(".vibe/summary.md")];
+    if let Ok(mut entries) = tokio::fs::read_dir(workspace_root).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            candidates.push(entry.path().join(".vibe/summary.md"));
+        }
+    }
+    for path in candidates {
+        let Ok(modified) = tokio::fs::metadata(&path).await.and_then(|m| m.modified()) else {
+            continue;
+        };
+        if DateTime::<Utc>::from(modified) < since {
+            continue;
+        }
+        if let Ok(content) = tokio::fs::read_to_string(&path).await {
+            return Some(content);
+        }
+    }
+    None
+}
+
+/// Files changed during the run, prefixed with the repo name in multi-repo workspaces
+async fn files_touched(
+    pool: &PgPool,
+    git: &GitService,
+    ctx: &ExecutionContext,
+    workspace_root: &Path,
+) -> Vec<String> {
+    let states =
+        ExecutionProcessRepoState::find_by_execution_process_id(pool, ctx.execution_process.id)
+            .await
+            .unwrap_or_default();
+
+    let mut files = Vec::new();
+    for repo in &ctx.repos {
+        let Some(before) = states
+            .iter()
+            .find(|s| s.repo_id == repo.id)
+            .and_then(|s| s.before_head_commit.as_deref())
+            .and_then(|sha| git2::Oid::from_str(sha).ok())
+        else {
+            continue;
+        };
+        let worktree_path = workspace_root.join(&repo.name);
+        match git.get_changed_paths(&worktree_path, &Commit::new(before)) {
+            Ok(paths) if ctx.repos.len() > 1 => {
+                files.extend(paths.into_iter().map(|p| format!("{}/{}", repo.name, p)));
+            }
+            Ok(paths) => files.extend(paths),
+            Err(e) => {
+                tracing::warn!("Failed to list files changed in {}: {}", repo.name, e);
+            }
+        }
+    }
+    files
+}
+
+/// Record a note for the column the agent just finished, unless it wrote one itself
+pub async fn record_automatic_note(
+    pool: &PgPool,
+    git: &GitService,
+    ctx: &ExecutionContext,
+    decision: Option<&Value>,
+) -> Result<Option<HandoffNote>, sqlx::Error> {
+    let column_id = ctx.task.column_id;
+    if HandoffNote::exists_since(
+        pool,
+        ctx.task.id,
+        column_id,
+        ctx.execution_process.started_at,
+    )
+    .await?
+    {
+        return Ok(None);
+    }
+    let Some(container_ref) = ctx.workspace.container_ref.as_ref() else {
+        return Ok(None);
+    };
+    let workspace_root = PathBuf::from(container_ref);
+
+    let agent_id = match column_id {
+        Some(id) => KanbanColumn::find_by_id(pool, id)
+            .await?
+            .and_then(|c| c.agent_id),
+        None => None,
+    };
+    let summary_md = read_summary_file(&workspace_root, ctx.execution_process.started_at).await;
+    let note = CreateHandoffNote {
+        column_id,
+        workspace_id: Some(ctx.workspace.id),
+        agent_id,
+        summary: summary_from(summary_md.as_deref(), decision),
+        files_touched: files_touched(pool, git, ctx, &workspace_root).await,
+        open_questions: open_questions_from(decision),
+    };
+    HandoffNote::create(pool, ctx.task.id, HandoffNoteSource::Automatic, &note)
+        .await
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn summary_prefers_summary_file_then_decision() {
+        let decision = json!({ "answer": "approve", "summary": "Added retries" });
+
+        assert_eq!(
+            summary_from(Some("  Reworked the parser\n"), Some(&decision)),
+            "Reworked the parser"
+        );
+        assert_eq!(summary_from(Some("   "), Some(&decision)), "Added retries");
+        assert_eq!(
+            summary_from(None, Some(&json!({ "answer": "approve" }))),
+            "Finished with answer \"approve\""
+        );
+        assert_eq!(
+            summary_from(None, None),
+            "Finished without a written summary"
+        );
+    }
+
+    #[test]
+    fn open_questions_accepts_list_or_string() {
+        let list = json!({ "open_questions": ["Keep the v1 endpoint?", " ", 3] });
+        let single = json!({ "open_questions": "Who owns the migration?" });
+
+        assert_eq!(
+            open_questions_from(Some(&list)),
+            vec!["Keep the v1 endpoint?"]
+        );
+        assert_eq!(
+            open_questions_from(Some(&single)),
+            vec!["Who owns the migration?"]
+        );
+        assert!(open_questions_from(Some(&json!({}))).is_empty());
+    }
+}
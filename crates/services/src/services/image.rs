@@ -52,14 +52,6 @@ impl ImageService {
         data: &[u8],
         original_filename: &str,
     ) -> Result<Image, ImageError> {
-        let file_size = data.len() as u64;
-
-        if file_size > self.max_size_bytes {
-            return Err(ImageError::TooLarge(file_size, self.max_size_bytes));
-        }
-
-        let hash = format!("{:x}", Sha256::digest(data));
-
         // Extract extension from original filename
         let extension = Path::new(original_filename)
             .extension()
@@ -80,6 +72,42 @@ impl ImageService {
             return Err(ImageError::InvalidFormat);
         }
 
+        self.persist(data, original_filename, extension, mime_type)
+            .await
+    }
+
+    /// Store any kind of file in the same deduplicated store, e.g. an artifact
+    /// collected from an execution
+    pub async fn store_file(
+        &self,
+        data: &[u8],
+        original_filename: &str,
+        mime_type: Option<String>,
+    ) -> Result<Image, ImageError> {
+        let extension = Path::new(original_filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("bin");
+
+        self.persist(data, original_filename, extension, mime_type)
+            .await
+    }
+
+    async fn persist(
+        &self,
+        data: &[u8],
+        original_filename: &str,
+        extension: &str,
+        mime_type: Option<String>,
+    ) -> Result<Image, ImageError> {
+        let file_size = data.len() as u64;
+
+        if file_size > self.max_size_bytes {
+            return Err(ImageError::TooLarge(file_size, self.max_size_bytes));
+        }
+
+        let hash = format!("{:x}", Sha256::digest(data));
+
         let existing_image = Image::find_by_hash(&self.pool, &hash).await?;
 
         if let Some(existing) = existing_image {
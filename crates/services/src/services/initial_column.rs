@@ -0,0 +1,82 @@
+//! Placing column-less tasks in the board's initial column.
+//!
+//! Tasks created over REST or MCP without a `column_id` would otherwise sit outside
+//! the board's workflow. Unless the project turns it off in its board settings,
+//! they start in the board's `is_initial` column instead, with a column-enter event
+//! like any other move. Tasks created before this was in place are moved by
+//! [`backfill`]; only those whose status matches the column's, so finished tasks
+//! don't land back in the backlog.
+
+use db::models::{
+    kanban_column::KanbanColumn,
+    project::Project,
+    task::Task,
+    task_event::{ActorType, CreateTaskEvent, EventTriggerType, TaskEvent},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// The initial column of the project's board, if it has one
+pub async fn initial_column(
+    pool: &PgPool,
+    project_id: Uuid,
+) -> Result<Option<KanbanColumn>, sqlx::Error> {
+    let Some(board_id) = Project::find_by_id(pool, project_id)
+        .await?
+        .and_then(|project| project.board_id)
+    else {
+        return Ok(None);
+    };
+    KanbanColumn::find_initial(pool, board_id).await
+}
+
+/// The column a new task without one should start in, unless the project has
+/// auto-assignment turned off
+pub async fn column_for_new_task(
+    pool: &PgPool,
+    project_id: Uuid,
+) -> Result<Option<KanbanColumn>, sqlx::Error> {
+    let Some(project) = Project::find_by_id(pool, project_id).await? else {
+        return Ok(None);
+    };
+    if !project.auto_assign_initial_column {
+        return Ok(None);
+    }
+    let Some(board_id) = project.board_id else {
+        return Ok(None);
+    };
+    KanbanColumn::find_initial(pool, board_id).await
+}
+
+/// Record that a task entered its initial column without anyone moving it
+pub async fn record_entry(pool: &PgPool, task_id: Uuid, column_id: Uuid) {
+    let event = CreateTaskEvent::column_transition(
+        task_id,
+        None,
+        column_id,
+        EventTriggerType::System,
+        ActorType::System,
+        None,
+    );
+    if let Err(e) = TaskEvent::create(pool, &event).await {
+        tracing::error!("Failed to record initial column entry for task {task_id}: {e}");
+    }
+}
+
+/// Move the project's column-less tasks with the initial column's status into it;
+/// `None` when the board has no initial column
+pub async fn backfill(pool: &PgPool, project_id: Uuid) -> Result<Option<Vec<Task>>, sqlx::Error> {
+    let Some(column) = initial_column(pool, project_id).await? else {
+        return Ok(None);
+    };
+
+    let mut moved = Vec::new();
+    for task_id in Task::find_columnless_ids(pool, project_id, &column.status).await? {
+        Task::update_column_id(pool, task_id, Some(column.id)).await?;
+        record_entry(pool, task_id, column.id).await;
+        if let Some(task) = Task::find_by_id(pool, task_id).await? {
+            moved.push(task);
+        }
+    }
+    Ok(Some(moved))
+}
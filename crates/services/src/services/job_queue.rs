@@ -0,0 +1,254 @@
+//! Persistent queue for background work.
+//!
+//! Work that used to run in a detached `tokio::spawn`, such as removing a deleted
+//! task's worktrees or pushing a task update to its shared copy, is lost if the server
+//! stops halfway. Such work is stored in `background_jobs` instead and run by a single
+//! worker loop. Failed jobs are retried with exponential backoff and left as dead
+//! letters once they run out of attempts; jobs interrupted by a restart run again.
+
+use std::{path::PathBuf, time::Duration};
+
+use chrono::Utc;
+use db::{
+    DBService,
+    models::{
+        background_job::{BackgroundJob, BackgroundJobStatus},
+        repo::Repo,
+    },
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use super::{
+    share::{ShareError, SharePublisher},
+    workspace_manager::WorkspaceManager,
+};
+
+/// Attempts before a job is marked dead
+pub const MAX_ATTEMPTS: i32 = 5;
+const BASE_RETRY_DELAY_SECS: i64 = 30;
+const MAX_RETRY_DELAY_SECS: i64 = 60 * 60;
+/// Completed jobs are kept this long for the admin view
+const COMPLETED_RETENTION_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Job {
+    /// Remove the worktrees of a deleted task, then repos no project uses any more
+    CleanupTaskWorkspaces {
+        task_id: Uuid,
+        workspace_dirs: Vec<PathBuf>,
+        repositories: Vec<Repo>,
+    },
+    /// Push a task's current state to its shared copy
+    SyncSharedTask { task_id: Uuid },
+    /// Unlink local tasks whose shared copy no longer exists
+    VerifySharedTasks,
+}
+
+impl Job {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Job::CleanupTaskWorkspaces { .. } => "cleanup_task_workspaces",
+            Job::SyncSharedTask { .. } => "sync_shared_task",
+            Job::VerifySharedTasks => "verify_shared_tasks",
+        }
+    }
+}
+
+/// Store a job for the worker to pick up
+pub async fn enqueue(pool: &PgPool, job: &Job) -> Result<BackgroundJob, sqlx::Error> {
+    let payload = serde_json::to_value(job).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+    BackgroundJob::create(pool, job.kind(), &payload, MAX_ATTEMPTS).await
+}
+
+/// Delay before retrying a job that has failed `attempts` times
+pub fn retry_delay(attempts: i32) -> chrono::Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    let secs = BASE_RETRY_DELAY_SECS.saturating_mul(1 << exponent);
+    chrono::Duration::seconds(secs.min(MAX_RETRY_DELAY_SECS))
+}
+
+pub struct JobQueueService {
+    db: DBService,
+    publisher: Option<SharePublisher>,
+    poll_interval: Duration,
+}
+
+impl JobQueueService {
+    pub async fn spawn(
+        db: DBService,
+        publisher: Option<SharePublisher>,
+    ) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            publisher,
+            poll_interval: Duration::from_secs(5),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting background job worker with interval {:?}",
+            self.poll_interval
+        );
+
+        match BackgroundJob::release_abandoned(&self.db.pool).await {
+            Ok(0) => {}
+            Ok(count) => info!(
+                "Requeued {} background jobs interrupted by a restart",
+                count
+            ),
+            Err(e) => error!("Failed to requeue interrupted background jobs: {}", e),
+        }
+        let retention_cutoff = Utc::now() - chrono::Duration::days(COMPLETED_RETENTION_DAYS);
+        if let Err(e) =
+            BackgroundJob::delete_completed_before(&self.db.pool, retention_cutoff).await
+        {
+            error!("Failed to prune completed background jobs: {}", e);
+        }
+
+        let mut interval = interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+            self.run_due_jobs().await;
+        }
+    }
+
+    async fn run_due_jobs(&self) {
+        loop {
+            match BackgroundJob::claim_next(&self.db.pool).await {
+                Ok(Some(job)) => self.process(job).await,
+                Ok(None) => break,
+                Err(e) => {
+                    error!("Failed to claim background job: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn process(&self, job: BackgroundJob) {
+        let result = match serde_json::from_value::<Job>(job.payload.clone()) {
+            Ok(payload) => self.run(payload).await,
+            Err(e) => Err(format!("Invalid job payload: {e}")),
+        };
+
+        let pool = &self.db.pool;
+        match result {
+            Ok(()) => {
+                debug!("Background job {} ({}) completed", job.id, job.kind);
+                if let Err(e) = BackgroundJob::complete(pool, job.id).await {
+                    error!("Failed to mark background job {} completed: {}", job.id, e);
+                }
+            }
+            Err(message) => {
+                let retry_at = Utc::now() + retry_delay(job.attempts);
+                match BackgroundJob::fail(pool, job.id, &message, retry_at).await {
+                    Ok(BackgroundJobStatus::Dead) => error!(
+                        "Background job {} ({}) failed {} times, giving up: {}",
+                        job.id, job.kind, job.attempts, message
+                    ),
+                    Ok(_) => warn!(
+                        "Background job {} ({}) failed, retrying at {}: {}",
+                        job.id, job.kind, retry_at, message
+                    ),
+                    Err(e) => error!("Failed to record failure of job {}: {}", job.id, e),
+                }
+            }
+        }
+    }
+
+    async fn run(&self, job: Job) -> Result<(), String> {
+        match job {
+            Job::CleanupTaskWorkspaces {
+                task_id,
+                workspace_dirs,
+                repositories,
+            } => {
+                info!(
+                    "Cleaning up task {} ({} workspaces, {} repos)",
+                    task_id,
+                    workspace_dirs.len(),
+                    repositories.len()
+                );
+                let mut failures = Vec::new();
+                for workspace_dir in &workspace_dirs {
+                    if let Err(e) =
+                        WorkspaceManager::cleanup_workspace(workspace_dir, &repositories).await
+                    {
+                        failures.push(format!("{}: {}", workspace_dir.display(), e));
+                    }
+                }
+                match Repo::delete_orphaned(&self.db.pool).await {
+                    Ok(count) if count > 0 => info!("Deleted {} orphaned repo records", count),
+                    Ok(_) => {}
+                    Err(e) => failures.push(format!("orphaned repos: {e}")),
+                }
+                if failures.is_empty() {
+                    Ok(())
+                } else {
+                    Err(failures.join("; "))
+                }
+            }
+            Job::SyncSharedTask { task_id } => {
+                let Some(publisher) = &self.publisher else {
+                    debug!(
+                        "Sharing is not configured; dropping update of task {}",
+                        task_id
+                    );
+                    return Ok(());
+                };
+                match publisher.update_shared_task_by_id(task_id).await {
+                    Ok(()) | Err(ShareError::TaskNotFound(_)) => Ok(()),
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+            Job::VerifySharedTasks => match &self.publisher {
+                Some(publisher) => publisher
+                    .cleanup_shared_tasks()
+                    .await
+                    .map_err(|e| e.to_string()),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_back_off_exponentially_up_to_an_hour() {
+        assert_eq!(retry_delay(1), chrono::Duration::seconds(30));
+        assert_eq!(retry_delay(2), chrono::Duration::seconds(60));
+        assert_eq!(retry_delay(4), chrono::Duration::seconds(240));
+        assert_eq!(retry_delay(20), chrono::Duration::hours(1));
+        assert_eq!(retry_delay(0), chrono::Duration::seconds(30));
+    }
+
+    #[test]
+    fn payload_carries_its_kind() {
+        let task_id = Uuid::new_v4();
+        let job = Job::SyncSharedTask { task_id };
+        let payload = serde_json::to_value(&job).unwrap();
+        assert_eq!(payload["kind"], job.kind());
+
+        let Job::SyncSharedTask { task_id: parsed } = serde_json::from_value(payload).unwrap()
+        else {
+            panic!("wrong job kind");
+        };
+        assert_eq!(parsed, task_id);
+
+        let verify = serde_json::to_value(Job::VerifySharedTasks).unwrap();
+        assert_eq!(verify["kind"], Job::VerifySharedTasks.kind());
+    }
+}
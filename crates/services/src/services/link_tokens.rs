@@ -0,0 +1,109 @@
+//! Tokens for read-only links: board dashboards for wall displays and shared board
+//! links for stakeholders without an account.
+//!
+//! A link token is embedded in the link's URL, so it only unlocks that link's view and
+//! never the rest of the API. Only the token's SHA-256 is stored.
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use ring::rand::{SecureRandom, SystemRandom};
+use sha2::{Digest, Sha256};
+
+const TOKEN_BYTES: usize = 24;
+
+/// The views a link token can open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkTokenKind {
+    /// A project's board snapshot
+    Dashboard,
+    /// A board's columns and task summaries
+    SharedBoard,
+}
+
+impl LinkTokenKind {
+    const ALL: [LinkTokenKind; 2] = [LinkTokenKind::Dashboard, LinkTokenKind::SharedBoard];
+
+    fn prefix(self) -> &'static str {
+        match self {
+            LinkTokenKind::Dashboard => "vkdash_",
+            LinkTokenKind::SharedBoard => "vkboard_",
+        }
+    }
+
+    fn path_prefix(self) -> &'static str {
+        match self {
+            LinkTokenKind::Dashboard => "/api/dashboards/",
+            LinkTokenKind::SharedBoard => "/api/shared-boards/",
+        }
+    }
+
+    /// Generate a new token, returning it together with its stored hash
+    pub fn generate(self) -> (String, String) {
+        let mut bytes = [0u8; TOKEN_BYTES];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .expect("system random number generator failed");
+        let token = format!("{}{}", self.prefix(), URL_SAFE_NO_PAD.encode(bytes));
+        let hash = hash(&token);
+        (token, hash)
+    }
+
+    /// Whether a string looks like a token of this kind
+    pub fn matches(self, token: &str) -> bool {
+        token.starts_with(self.prefix()) && token.len() > self.prefix().len()
+    }
+
+    /// Path of the view a token opens
+    pub fn path(self, token: &str) -> String {
+        format!("{}{token}", self.path_prefix())
+    }
+}
+
+pub fn hash(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// Whether a request path belongs to a link view, which carries its own token in the
+/// path instead of authenticating against the API
+pub fn is_link_view_path(path: &str) -> bool {
+    LinkTokenKind::ALL
+        .iter()
+        .any(|kind| path.starts_with(kind.path_prefix()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_tokens_are_unique_and_match_their_hash() {
+        let (token, token_hash) = LinkTokenKind::Dashboard.generate();
+        let (other, other_hash) = LinkTokenKind::Dashboard.generate();
+
+        assert!(LinkTokenKind::Dashboard.matches(&token));
+        assert_ne!(token, other);
+        assert_ne!(token_hash, other_hash);
+        assert_eq!(hash(&token), token_hash);
+        assert!(!token_hash.contains(&token));
+    }
+
+    #[test]
+    fn tokens_only_match_their_own_kind() {
+        let (dashboard_token, _) = LinkTokenKind::Dashboard.generate();
+        let (board_token, _) = LinkTokenKind::SharedBoard.generate();
+
+        assert!(!LinkTokenKind::SharedBoard.matches(&dashboard_token));
+        assert!(!LinkTokenKind::Dashboard.matches(&board_token));
+        assert!(!LinkTokenKind::SharedBoard.matches("vkboard_"));
+        assert!(!LinkTokenKind::Dashboard.matches("vkmcp_abc"));
+        assert!(!LinkTokenKind::Dashboard.matches(""));
+    }
+
+    #[test]
+    fn link_views_are_recognised_by_path() {
+        let path = LinkTokenKind::SharedBoard.path("vkboard_abc");
+        assert_eq!(path, "/api/shared-boards/vkboard_abc");
+        assert!(is_link_view_path(&path));
+        assert!(is_link_view_path("/api/dashboards/vkdash_abc/stream"));
+        assert!(!is_link_view_path("/api/projects"));
+    }
+}
@@ -0,0 +1,507 @@
+//! Self-contained transcripts of an execution's normalized logs.
+//!
+//! The normalized log of an execution is a sequence of JSON patches onto a list of
+//! conversation entries, which the UI applies while streaming. An export applies them
+//! once and renders the result as JSON, Markdown or a standalone HTML page, so a run
+//! can be audited or shared outside the app.
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    task_log_retention::LogRetention,
+};
+use executors::logs::{
+    ActionType, CommandExitStatus, FileChange, NormalizedEntry, NormalizedEntryType, ToolStatus,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use ts_rs::TS;
+use utils::{diff::Diff, log_msg::LogMsg};
+use uuid::Uuid;
+
+use super::log_retention::retained_until;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Markdown,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Markdown => "text/markdown; charset=utf-8",
+            ExportFormat::Html => "text/html; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+/// One entry of the conversation, in the order the UI shows them
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(tag = "type", content = "content", rename_all = "snake_case")]
+pub enum TranscriptEntry {
+    Entry(NormalizedEntry),
+    Stdout(String),
+    Stderr(String),
+    Diff(Diff),
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ExecutionTranscript {
+    pub execution_process_id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub task_title: Option<String>,
+    pub run_reason: ExecutionProcessRunReason,
+    pub status: ExecutionProcessStatus,
+    #[ts(type = "Date")]
+    pub started_at: DateTime<Utc>,
+    #[ts(type = "Date | null")]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub exported_at: DateTime<Utc>,
+    /// Retention of the task's logs when exported
+    pub log_retention: LogRetention,
+    /// When the stored logs become due for pruning; null when they are kept forever or
+    /// the execution is still running
+    #[ts(type = "Date | null")]
+    pub retained_until: Option<DateTime<Utc>>,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+impl ExecutionTranscript {
+    pub fn new(
+        process: &ExecutionProcess,
+        task: Option<(Uuid, String)>,
+        log_retention: LogRetention,
+        history: &[LogMsg],
+    ) -> Self {
+        let (task_id, task_title) = task.unzip();
+        let retained_until = process
+            .completed_at
+            .and_then(|completed_at| retained_until(log_retention, completed_at));
+        Self {
+            execution_process_id: process.id,
+            task_id,
+            task_title,
+            run_reason: process.run_reason.clone(),
+            status: process.status.clone(),
+            started_at: process.started_at,
+            completed_at: process.completed_at,
+            exported_at: Utc::now(),
+            log_retention,
+            retained_until,
+            entries: transcript_entries(history),
+        }
+    }
+
+    fn title(&self) -> String {
+        match &self.task_title {
+            Some(title) => format!("Transcript: {title}"),
+            None => format!("Transcript: execution {}", self.execution_process_id),
+        }
+    }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        let mut details = vec![
+            ("Execution", self.execution_process_id.to_string()),
+            ("Run reason", self.run_reason.to_string()),
+            ("Status", self.status.to_string()),
+            ("Started", self.started_at.to_rfc3339()),
+        ];
+        if let Some(completed_at) = self.completed_at {
+            details.push(("Completed", completed_at.to_rfc3339()));
+        }
+        details.push(("Exported", self.exported_at.to_rfc3339()));
+        match (self.log_retention, self.retained_until) {
+            (LogRetention::KeepForever, _) => {
+                details.push(("Retention", "kept forever".to_string()));
+            }
+            (_, Some(retained_until)) => {
+                details.push(("Retained until", retained_until.to_rfc3339()));
+            }
+            (_, None) => {}
+        }
+        details
+    }
+}
+
+/// Apply the normalized log patches in `history` and return the resulting entries
+pub fn transcript_entries(history: &[LogMsg]) -> Vec<TranscriptEntry> {
+    let mut doc = json!({ "entries": [] });
+    for msg in history {
+        if let LogMsg::JsonPatch(patch) = msg
+            && let Err(e) = json_patch::patch(&mut doc, patch)
+        {
+            tracing::debug!("Skipping log patch that doesn't apply: {}", e);
+        }
+    }
+
+    let Value::Array(entries) = doc["entries"].take() else {
+        return Vec::new();
+    };
+    entries
+        .into_iter()
+        .filter_map(|mut entry| {
+            let content = entry.get_mut("content")?.take();
+            match entry.get("type")?.as_str()? {
+                "NORMALIZED_ENTRY" => serde_json::from_value(content)
+                    .ok()
+                    .map(TranscriptEntry::Entry),
+                "STDOUT" => content
+                    .as_str()
+                    .map(|s| TranscriptEntry::Stdout(s.to_string())),
+                "STDERR" => content
+                    .as_str()
+                    .map(|s| TranscriptEntry::Stderr(s.to_string())),
+                "DIFF" => serde_json::from_value(content)
+                    .ok()
+                    .map(TranscriptEntry::Diff),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// An entry prepared for rendering: a heading, prose and preformatted blocks
+struct Block {
+    kind: &'static str,
+    title: String,
+    text: Option<String>,
+    code: Vec<String>,
+}
+
+fn tool_status(status: &ToolStatus) -> &'static str {
+    match status {
+        ToolStatus::Created => "started",
+        ToolStatus::Success => "succeeded",
+        ToolStatus::Failed => "failed",
+        ToolStatus::Denied { .. } => "denied",
+        ToolStatus::PendingApproval { .. } => "awaiting approval",
+        ToolStatus::TimedOut => "timed out",
+    }
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn action_code(action: &ActionType) -> Vec<String> {
+    match action {
+        ActionType::CommandRun { command, result } => {
+            let mut block = format!("$ {command}");
+            if let Some(result) = result {
+                if let Some(output) = result.output.as_deref().filter(|o| !o.trim().is_empty()) {
+                    block.push('\n');
+                    block.push_str(output.trim_end());
+                }
+                match result.exit_status {
+                    Some(CommandExitStatus::ExitCode { code }) if code != 0 => {
+                        block.push_str(&format!("\n[exit code {code}]"));
+                    }
+                    Some(CommandExitStatus::Success { success: false }) => {
+                        block.push_str("\n[failed]");
+                    }
+                    _ => {}
+                }
+            }
+            vec![block]
+        }
+        ActionType::FileEdit { path, changes } => changes
+            .iter()
+            .map(|change| match change {
+                FileChange::Edit { unified_diff, .. } => unified_diff.trim_end().to_string(),
+                FileChange::Write { content } => format!("{path}\n{}", content.trim_end()),
+                FileChange::Delete => format!("deleted {path}"),
+                FileChange::Rename { new_path } => format!("renamed {path} -> {new_path}"),
+            })
+            .collect(),
+        ActionType::Tool {
+            arguments, result, ..
+        } => {
+            let mut blocks = Vec::new();
+            if let Some(arguments) = arguments {
+                blocks.push(serde_json::to_string_pretty(arguments).unwrap_or_default());
+            }
+            if let Some(result) = result {
+                blocks.push(match &result.value {
+                    Value::String(s) => s.clone(),
+                    value => serde_json::to_string_pretty(value).unwrap_or_default(),
+                });
+            }
+            blocks
+        }
+        ActionType::PlanPresentation { plan } => vec![plan.clone()],
+        _ => Vec::new(),
+    }
+}
+
+fn block(entry: &TranscriptEntry) -> Option<Block> {
+    let entry = match entry {
+        TranscriptEntry::Entry(entry) => entry,
+        TranscriptEntry::Stdout(output) => {
+            return Some(Block {
+                kind: "output",
+                title: "Output".to_string(),
+                text: None,
+                code: vec![output.clone()],
+            });
+        }
+        TranscriptEntry::Stderr(output) => {
+            return Some(Block {
+                kind: "error",
+                title: "Error output".to_string(),
+                text: None,
+                code: vec![output.clone()],
+            });
+        }
+        TranscriptEntry::Diff(diff) => {
+            let path = diff.new_path.as_ref().or(diff.old_path.as_ref())?;
+            return Some(Block {
+                kind: "diff",
+                title: format!("Changed {path}"),
+                text: match (diff.additions, diff.deletions) {
+                    (Some(added), Some(removed)) => Some(format!("+{added} -{removed}")),
+                    _ => None,
+                },
+                code: Vec::new(),
+            });
+        }
+    };
+
+    let text = non_empty(&entry.content);
+    let (kind, title, code) = match &entry.entry_type {
+        NormalizedEntryType::UserMessage => ("user", "User".to_string(), Vec::new()),
+        NormalizedEntryType::UserFeedback { denied_tool } => (
+            "user",
+            format!("User feedback (denied {denied_tool})"),
+            Vec::new(),
+        ),
+        NormalizedEntryType::AssistantMessage => ("assistant", "Assistant".to_string(), Vec::new()),
+        NormalizedEntryType::Thinking => ("thinking", "Thinking".to_string(), Vec::new()),
+        NormalizedEntryType::SystemMessage => ("system", "System".to_string(), Vec::new()),
+        NormalizedEntryType::ErrorMessage { .. } => ("error", "Error".to_string(), Vec::new()),
+        NormalizedEntryType::AgentSwitch {
+            agent_name,
+            column_name,
+            ..
+        } => (
+            "system",
+            format!("Agent {agent_name} ({column_name})"),
+            Vec::new(),
+        ),
+        NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            status,
+        } => (
+            "tool",
+            format!("Tool: {tool_name} ({})", tool_status(status)),
+            action_code(action_type),
+        ),
+        NormalizedEntryType::Loading | NormalizedEntryType::NextAction { .. } => return None,
+    };
+    if text.is_none() && code.is_empty() {
+        return None;
+    }
+    Some(Block {
+        kind,
+        title,
+        text,
+        code,
+    })
+}
+
+/// A code fence longer than any run of backticks in `code`
+fn fence(code: &str) -> String {
+    let longest = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+pub fn to_markdown(transcript: &ExecutionTranscript) -> String {
+    let mut out = format!("# {}\n\n", transcript.title());
+    for (label, value) in transcript.details() {
+        out.push_str(&format!("- **{label}:** {value}\n"));
+    }
+
+    for block in transcript.entries.iter().filter_map(block) {
+        out.push_str(&format!("\n## {}\n", block.title));
+        if let Some(text) = &block.text {
+            out.push_str(&format!("\n{text}\n"));
+        }
+        for code in &block.code {
+            let fence = fence(code);
+            out.push_str(&format!("\n{fence}\n{}\n{fence}\n", code.trim_end()));
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+pub fn to_html(transcript: &ExecutionTranscript) -> String {
+    let title = escape_html(&transcript.title());
+    let mut body = format!("<h1>{title}</h1>\n<dl>\n");
+    for (label, value) in transcript.details() {
+        body.push_str(&format!(
+            "<dt>{label}</dt><dd>{}</dd>\n",
+            escape_html(&value)
+        ));
+    }
+    body.push_str("</dl>\n");
+
+    for block in transcript.entries.iter().filter_map(block) {
+        body.push_str(&format!(
+            "<section class=\"{}\">\n<h2>{}</h2>\n",
+            block.kind,
+            escape_html(&block.title)
+        ));
+        if let Some(text) = &block.text {
+            body.push_str(&format!(
+                "<div class=\"text\">{}</div>\n",
+                escape_html(text)
+            ));
+        }
+        for code in &block.code {
+            body.push_str(&format!("<pre>{}</pre>\n", escape_html(code.trim_end())));
+        }
+        body.push_str("</section>\n");
+    }
+
+    format!(
+        r#"<!doctype html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <title>{title}</title>
+    <style>
+      body {{ margin: 0 auto; max-width: 960px; padding: 24px; font-family: system-ui, sans-serif; color: #222; }}
+      dl {{ display: grid; grid-template-columns: max-content 1fr; gap: 2px 12px; color: #555; font-size: 14px; }}
+      dt {{ font-weight: 600; }}
+      dd {{ margin: 0; }}
+      section {{ border-left: 3px solid #ccc; padding: 4px 12px; margin: 16px 0; }}
+      section.user {{ border-color: #2563eb; }}
+      section.assistant {{ border-color: #16a34a; }}
+      section.tool {{ border-color: #9333ea; }}
+      section.error {{ border-color: #dc2626; }}
+      section.thinking, section.system {{ color: #666; }}
+      h2 {{ font-size: 14px; margin: 4px 0; }}
+      .text {{ white-space: pre-wrap; }}
+      pre {{ background: #f4f4f5; padding: 8px; overflow-x: auto; font-size: 13px; }}
+    </style>
+  </head>
+  <body>
+{body}  </body>
+</html>
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::logs::{CommandRunResult, utils::patch::ConversationPatch};
+
+    use super::*;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry::new(None, entry_type, content.to_string(), None)
+    }
+
+    fn command(command: &str, output: &str) -> NormalizedEntry {
+        entry(
+            NormalizedEntryType::ToolUse {
+                tool_name: "bash".to_string(),
+                action_type: ActionType::CommandRun {
+                    command: command.to_string(),
+                    result: Some(CommandRunResult {
+                        exit_status: Some(CommandExitStatus::ExitCode { code: 1 }),
+                        output: Some(output.to_string()),
+                    }),
+                },
+                status: ToolStatus::Failed,
+            },
+            "",
+        )
+    }
+
+    fn transcript(entries: Vec<TranscriptEntry>) -> ExecutionTranscript {
+        ExecutionTranscript {
+            execution_process_id: Uuid::nil(),
+            task_id: None,
+            task_title: Some("Fix <login>".to_string()),
+            run_reason: ExecutionProcessRunReason::CodingAgent,
+            status: ExecutionProcessStatus::Completed,
+            started_at: Utc::now(),
+            completed_at: None,
+            exported_at: Utc::now(),
+            log_retention: LogRetention::Default,
+            retained_until: None,
+            entries,
+        }
+    }
+
+    #[test]
+    fn patches_are_applied_in_order() {
+        let history = vec![
+            LogMsg::JsonPatch(ConversationPatch::add_normalized_entry(
+                0,
+                entry(NormalizedEntryType::AssistantMessage, "Working"),
+            )),
+            LogMsg::JsonPatch(ConversationPatch::add_stdout(1, "npm test".to_string())),
+            LogMsg::JsonPatch(ConversationPatch::replace(
+                0,
+                entry(NormalizedEntryType::AssistantMessage, "Done"),
+            )),
+            LogMsg::Stdout("raw output is not part of the transcript".to_string()),
+        ];
+
+        let entries = transcript_entries(&history);
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(&entries[0], TranscriptEntry::Entry(e) if e.content == "Done"));
+        assert!(matches!(&entries[1], TranscriptEntry::Stdout(s) if s == "npm test"));
+    }
+
+    #[test]
+    fn markdown_fences_outlast_backticks_in_output() {
+        let markdown = to_markdown(&transcript(vec![TranscriptEntry::Entry(command(
+            "cat README.md",
+            "```rust\nfn main() {}\n```",
+        ))]));
+
+        assert!(markdown.starts_with("# Transcript: Fix <login>\n"));
+        assert!(markdown.contains("## Tool: bash (failed)"));
+        assert!(markdown.contains("\n````\n$ cat README.md\n```rust"));
+        assert!(markdown.contains("[exit code 1]\n````\n"));
+    }
+
+    #[test]
+    fn html_escapes_log_content() {
+        let html = to_html(&transcript(vec![TranscriptEntry::Entry(entry(
+            NormalizedEntryType::AssistantMessage,
+            "<script>alert(1)</script>",
+        ))]));
+
+        assert!(html.contains("<title>Transcript: Fix &lt;login&gt;</title>"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!html.contains("<script>"));
+    }
+}
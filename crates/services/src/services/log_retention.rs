@@ -0,0 +1,111 @@
+//! Pruning of old execution logs and artifacts.
+//!
+//! Once a day the stored logs of finished executions and their collected artifacts are
+//! deleted when they are older than their task's retention period. Tasks marked
+//! `keep_forever` keep their full transcripts, while routine chores marked `short` are
+//! pruned early.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::{
+        execution_artifact::ExecutionArtifact, execution_process_logs::ExecutionProcessLogs,
+        task_log_retention::LogRetention,
+    },
+};
+use tokio::time::interval;
+use tracing::{error, info};
+
+/// Days logs and artifacts of tasks without an override are kept
+pub const DEFAULT_RETENTION_DAYS: i64 = 90;
+/// Days logs and artifacts of tasks marked `short` are kept
+pub const SHORT_RETENTION_DAYS: i64 = 14;
+
+/// Days a retention keeps logs and artifacts; None when they are never pruned
+pub fn retention_days(retention: LogRetention) -> Option<i64> {
+    match retention {
+        LogRetention::Default => Some(DEFAULT_RETENTION_DAYS),
+        LogRetention::KeepForever => None,
+        LogRetention::Short => Some(SHORT_RETENTION_DAYS),
+    }
+}
+
+/// When logs of an execution that completed at `completed_at` become due for pruning
+pub fn retained_until(
+    retention: LogRetention,
+    completed_at: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    retention_days(retention).map(|days| completed_at + chrono::Duration::days(days))
+}
+
+/// Deletes expired execution logs and artifacts on an interval
+pub struct LogRetentionService {
+    db: DBService,
+    poll_interval: Duration,
+}
+
+impl LogRetentionService {
+    pub async fn spawn(db: DBService) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            poll_interval: Duration::from_secs(24 * 60 * 60),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting log retention service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            self.prune(Utc::now()).await;
+        }
+    }
+
+    async fn prune(&self, now: DateTime<Utc>) {
+        let default_before = now - chrono::Duration::days(DEFAULT_RETENTION_DAYS);
+        let short_before = now - chrono::Duration::days(SHORT_RETENTION_DAYS);
+        let pool = &self.db.pool;
+
+        match ExecutionProcessLogs::delete_expired(pool, default_before, short_before).await {
+            Ok(0) => {}
+            Ok(rows) => info!("Pruned {} expired execution log chunk(s)", rows),
+            Err(e) => error!("Failed to prune expired execution logs: {}", e),
+        }
+        match ExecutionArtifact::delete_expired(pool, default_before, short_before).await {
+            Ok(0) => {}
+            Ok(rows) => info!("Pruned {} expired execution artifact(s)", rows),
+            Err(e) => error!("Failed to prune expired execution artifacts: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn keep_forever_is_never_due() {
+        let completed_at = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(retained_until(LogRetention::KeepForever, completed_at), None);
+    }
+
+    #[test]
+    fn short_retention_is_due_before_default() {
+        let completed_at = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let short = retained_until(LogRetention::Short, completed_at).unwrap();
+        let default = retained_until(LogRetention::Default, completed_at).unwrap();
+        assert_eq!(short, Utc.with_ymd_and_hms(2026, 1, 15, 0, 0, 0).unwrap());
+        assert!(short < default);
+    }
+}
@@ -0,0 +1,288 @@
+//! Short-lived API tokens for the MCP server running inside an execution.
+//!
+//! Each execution is issued a random token in `VK_MCP_TOKEN`. The in-workspace MCP
+//! client sends it as a bearer token, so the API knows which workspace is calling and
+//! can reject requests outside that workspace's project. Only the routes the MCP server
+//! calls are reachable with a token. Tokens live in memory only: they are revoked when
+//! the execution exits and never survive a server restart.
+
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, RwLock},
+};
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Duration, Utc};
+use ring::rand::{SecureRandom, SystemRandom};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Environment variable carrying the token into the execution
+pub const MCP_TOKEN_ENV_VAR: &str = "VK_MCP_TOKEN";
+const TOKEN_PREFIX: &str = "vkmcp_";
+const TOKEN_BYTES: usize = 32;
+/// Backstop for executions whose exit is never observed
+const TOKEN_TTL_HOURS: i64 = 12;
+
+static SHARED: LazyLock<McpCredentials> = LazyLock::new(McpCredentials::new);
+
+/// Routes the in-workspace MCP server calls, `*` standing for one path segment
+const MCP_ROUTES: &[&str] = &[
+    "/api/agents",
+    "/api/boards",
+    "/api/boards/*",
+    "/api/boards/*/columns",
+    "/api/boards/*/transitions",
+    "/api/containers/attempt-context",
+    "/api/context-artifacts",
+    "/api/context-artifacts/*",
+    "/api/projects",
+    "/api/projects/*",
+    "/api/projects/*/analyze-backlog",
+    "/api/projects/*/labels",
+    "/api/projects/*/repositories",
+    "/api/projects/*/task-group-dependencies",
+    "/api/projects/*/task-groups",
+    "/api/projects/*/unlock",
+    "/api/tags",
+    "/api/task-attempts",
+    "/api/task-groups/*",
+    "/api/task-groups/*/set-execution-dag",
+    "/api/task-groups/*/transition",
+    "/api/tasks",
+    "/api/tasks/*",
+    "/api/tasks/*/decision",
+    "/api/tasks/*/handoff-notes",
+    "/api/tasks/*/labels",
+    "/api/tasks/*/labels/*",
+    "/api/tasks/*/task-group/*",
+];
+
+fn is_mcp_route(path: &str) -> bool {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    MCP_ROUTES.iter().any(|route| {
+        let pattern: Vec<&str> = route.split('/').filter(|s| !s.is_empty()).collect();
+        pattern.len() == segments.len()
+            && pattern
+                .iter()
+                .zip(&segments)
+                .all(|(expected, actual)| *expected == "*" || expected == actual)
+    })
+}
+
+/// What an issued token grants access to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McpCredential {
+    pub execution_process_id: Uuid,
+    pub workspace_id: Uuid,
+    pub project_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl McpCredential {
+    /// Whether the request is one the MCP server makes and targets only this
+    /// credential's project and workspace. Checks `/projects/{id}` and
+    /// `/task-attempts/{id}` path segments and the `project_id` / `workspace_id` query
+    /// parameters; any other route is refused.
+    pub fn allows_request(&self, path: &str, query: Option<&str>) -> bool {
+        if !is_mcp_route(path) {
+            return false;
+        }
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let path_ok = segments
+            .windows(2)
+            .all(|pair| match (pair[0], Uuid::parse_str(pair[1])) {
+                ("projects", Ok(id)) => id == self.project_id,
+                ("task-attempts", Ok(id)) => id == self.workspace_id,
+                _ => true,
+            });
+        let query_ok = query
+            .unwrap_or_default()
+            .split('&')
+            .filter_map(|param| param.split_once('='))
+            .all(|(key, value)| match key {
+                "project_id" => Uuid::parse_str(value) == Ok(self.project_id),
+                "workspace_id" => Uuid::parse_str(value) == Ok(self.workspace_id),
+                _ => true,
+            });
+        path_ok && query_ok
+    }
+}
+
+/// In-memory registry of issued tokens, keyed by the token's SHA-256
+pub struct McpCredentials {
+    tokens: RwLock<HashMap<String, McpCredential>>,
+    rng: SystemRandom,
+}
+
+fn token_key(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+impl McpCredentials {
+    pub fn new() -> Self {
+        Self {
+            tokens: RwLock::new(HashMap::new()),
+            rng: SystemRandom::new(),
+        }
+    }
+
+    pub fn shared() -> &'static McpCredentials {
+        &SHARED
+    }
+
+    /// Whether a bearer token looks like one of ours (as opposed to e.g. an OAuth token)
+    pub fn is_mcp_token(token: &str) -> bool {
+        token.starts_with(TOKEN_PREFIX)
+    }
+
+    /// Issue a token for an execution, scoped to its workspace and project
+    pub fn issue(
+        &self,
+        execution_process_id: Uuid,
+        workspace_id: Uuid,
+        project_id: Uuid,
+    ) -> String {
+        let mut bytes = [0u8; TOKEN_BYTES];
+        self.rng
+            .fill(&mut bytes)
+            .expect("system random number generator failed");
+        let token = format!("{TOKEN_PREFIX}{}", URL_SAFE_NO_PAD.encode(bytes));
+
+        let credential = McpCredential {
+            execution_process_id,
+            workspace_id,
+            project_id,
+            expires_at: Utc::now() + Duration::hours(TOKEN_TTL_HOURS),
+        };
+        self.tokens
+            .write()
+            .unwrap()
+            .insert(token_key(&token), credential);
+        token
+    }
+
+    pub fn verify(&self, token: &str) -> Option<McpCredential> {
+        self.verify_at(token, Utc::now())
+    }
+
+    fn verify_at(&self, token: &str, now: DateTime<Utc>) -> Option<McpCredential> {
+        if !Self::is_mcp_token(token) {
+            return None;
+        }
+        let key = token_key(token);
+        let credential = self.tokens.read().unwrap().get(&key).cloned()?;
+        if credential.expires_at <= now {
+            self.tokens.write().unwrap().remove(&key);
+            return None;
+        }
+        Some(credential)
+    }
+
+    /// Whether a request without a token may go through. Once any token is out,
+    /// executions call home with it, so only the local UI on the loopback interface
+    /// goes without.
+    pub fn admits_tokenless(&self, from_loopback: bool) -> bool {
+        let now = Utc::now();
+        from_loopback
+            || !self
+                .tokens
+                .read()
+                .unwrap()
+                .values()
+                .any(|credential| credential.expires_at > now)
+    }
+
+    /// Revoke every token issued for the execution
+    pub fn revoke(&self, execution_process_id: Uuid) {
+        self.tokens
+            .write()
+            .unwrap()
+            .retain(|_, credential| credential.execution_process_id != execution_process_id);
+    }
+}
+
+impl Default for McpCredentials {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issued_tokens_verify_until_revoked() {
+        let credentials = McpCredentials::new();
+        let exec_id = Uuid::new_v4();
+        let token = credentials.issue(exec_id, Uuid::new_v4(), Uuid::new_v4());
+
+        assert!(McpCredentials::is_mcp_token(&token));
+        assert_eq!(
+            credentials.verify(&token).map(|c| c.execution_process_id),
+            Some(exec_id)
+        );
+        assert!(credentials.verify("vkmcp_forged").is_none());
+
+        credentials.revoke(exec_id);
+        assert!(credentials.verify(&token).is_none());
+    }
+
+    #[test]
+    fn expired_tokens_are_rejected() {
+        let credentials = McpCredentials::new();
+        let token = credentials.issue(Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let later = Utc::now() + Duration::hours(TOKEN_TTL_HOURS + 1);
+        assert!(credentials.verify_at(&token, later).is_none());
+        assert!(credentials.verify(&token).is_none());
+    }
+
+    #[test]
+    fn requests_outside_the_scope_are_refused() {
+        let credential = McpCredential {
+            execution_process_id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            expires_at: Utc::now(),
+        };
+        let other = Uuid::new_v4();
+
+        let own_project = format!("/api/projects/{}/task-groups", credential.project_id);
+        assert!(credential.allows_request(&own_project, None));
+        assert!(credential.allows_request("/api/tasks", Some("limit=5")));
+        assert!(!credential.allows_request(&format!("/api/projects/{other}"), None));
+        assert!(!credential.allows_request(&format!("/api/task-attempts/{other}/diff"), None));
+        assert!(!credential.allows_request("/api/tasks", Some(&format!("project_id={other}"))));
+    }
+
+    #[test]
+    fn routes_the_mcp_server_does_not_call_are_refused() {
+        let credential = McpCredential {
+            execution_process_id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            expires_at: Utc::now(),
+        };
+
+        let own_project = format!("/api/projects/{}", credential.project_id);
+        assert!(credential.allows_request(&own_project, None));
+        assert!(!credential.allows_request(&format!("{own_project}/secrets"), None));
+        assert!(!credential.allows_request("/api/config", None));
+        assert!(!credential.allows_request("/api/api-tokens", None));
+    }
+
+    #[test]
+    fn requests_without_a_token_are_refused_while_tokens_are_out() {
+        let credentials = McpCredentials::new();
+        assert!(credentials.admits_tokenless(false));
+
+        let exec_id = Uuid::new_v4();
+        credentials.issue(exec_id, Uuid::new_v4(), Uuid::new_v4());
+        assert!(!credentials.admits_tokenless(false));
+        assert!(credentials.admits_tokenless(true));
+
+        credentials.revoke(exec_id);
+        assert!(credentials.admits_tokenless(false));
+    }
+}
@@ -1,30 +1,84 @@
+pub mod agent_concurrency;
 pub mod analytics;
+pub mod api_tokens;
 pub mod approvals;
 pub mod auth;
+pub mod automation;
+pub mod bitbucket;
+pub mod board_snapshots;
+pub mod board_validation;
+pub mod branch_template;
+pub mod chat_approvals;
+pub mod column_checklist;
+pub mod column_entry_debounce;
 pub mod config;
 pub mod container;
+pub mod cycle_times;
+pub mod cost_estimate;
+pub mod data_migration;
+pub mod decision_file;
+pub mod deliverable_report;
+pub mod dependency_updates;
+pub mod dev_server_preview;
 pub mod diff_stream;
+pub mod email_notifications;
+pub mod escalation_slo;
+pub mod env_vault;
 pub mod events;
+pub mod execution_artifacts;
+pub mod execution_environment;
+pub mod execution_replay;
+pub mod executor_health;
 pub mod file_ranker;
 pub mod file_search_cache;
 pub mod filesystem;
 pub mod filesystem_watcher;
+pub mod forge;
 pub mod git;
+pub mod gitea;
 pub mod github;
+pub mod gitlab;
 pub mod group_analyzer;
 pub mod group_evaluator;
+pub mod handoff_notes;
 pub mod image;
+pub mod initial_column;
+pub mod job_queue;
+pub mod link_tokens;
+pub mod log_export;
+pub mod log_retention;
+pub mod mcp_credentials;
+pub mod module_memory_refresh;
 pub mod notification;
+pub mod notification_channels;
+pub mod notification_digests;
 pub mod oauth_credentials;
+pub mod plan_review;
+pub mod plan_subtasks;
 pub mod pr_monitor;
 pub mod prereq_evaluator;
+pub mod process_reaper;
 pub mod project;
 pub mod project_agent;
+pub mod prompt_language;
 pub mod queued_message;
+pub mod readiness;
 pub mod remote_client;
 pub mod remote_types;
 pub mod repo;
+pub mod review;
+pub mod reviewer_feedback;
+pub mod security_scan;
 pub mod share;
+pub mod shutdown;
+pub mod task_comments;
 pub mod task_grouper;
+pub mod task_import;
+pub mod task_watch;
+pub mod transition_condition;
+pub mod workflow_lint;
+pub mod workspace_conflicts;
 pub mod workspace_manager;
+pub mod workspace_orientation;
+pub mod workspace_templates;
 pub mod worktree_manager;
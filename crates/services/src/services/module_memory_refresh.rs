@@ -0,0 +1,182 @@
+//! Module memory refreshes after merges.
+//!
+//! When a project opts in, merging a task's branch starts a short agent run on the
+//! updated target branch. The agent reads the modules the merge touched and rewrites
+//! their module memories through the `upsert_module_memory` MCP tool, so path-scoped
+//! context keeps up with the code without anyone calling `create_artifact` by hand.
+
+use std::collections::BTreeMap;
+
+use uuid::Uuid;
+
+/// What a merge changed in one repo
+#[derive(Debug, Clone)]
+pub struct MergedRepoChanges {
+    pub repo_name: String,
+    pub merge_commit: String,
+    /// Repo-relative paths the merged branch changed
+    pub changed_paths: Vec<String>,
+}
+
+/// A module whose memory the refresh rewrites
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleToRefresh {
+    /// Directory of the changed files, prefixed with the repo name in multi-repo merges
+    pub path: String,
+    pub changed_files: Vec<String>,
+}
+
+/// Group changed files by their directory, keeping the `max_modules` modules with the
+/// most changed files. Files at a repo's root are modules of their own.
+pub fn modules_to_refresh(
+    merged: &[MergedRepoChanges],
+    max_modules: usize,
+) -> Vec<ModuleToRefresh> {
+    let mut modules: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for repo in merged {
+        for file in &repo.changed_paths {
+            let dir = match file.rsplit_once('/') {
+                Some((dir, _)) => dir,
+                None => file.as_str(),
+            };
+            let path = if merged.len() > 1 {
+                format!("{}/{}", repo.repo_name, dir)
+            } else {
+                dir.to_string()
+            };
+            modules.entry(path).or_default().push(file.clone());
+        }
+    }
+
+    let mut modules: Vec<ModuleToRefresh> = modules
+        .into_iter()
+        .map(|(path, changed_files)| ModuleToRefresh {
+            path,
+            changed_files,
+        })
+        .collect();
+    // Stable sort keeps the path order among modules with as many changed files
+    modules.sort_by(|a, b| b.changed_files.len().cmp(&a.changed_files.len()));
+    modules.truncate(max_modules);
+    modules
+}
+
+/// Prompt of the refresh run. `current_memories` holds the existing memory of each
+/// module that has one, keyed by module path.
+pub fn refresh_prompt(
+    project_id: Uuid,
+    task_id: Uuid,
+    task_title: &str,
+    merged: &[MergedRepoChanges],
+    modules: &[ModuleToRefresh],
+    current_memories: &BTreeMap<String, String>,
+) -> String {
+    let mut prompt = format!(
+        "The branch of task \"{task_title}\" was just merged. Update the module memories \
+         of the modules it touched so future agents get an accurate picture of them.\n\n\
+         For each module below, read its code as it is now and call the \
+         `upsert_module_memory` tool with project_id `{project_id}`, the module path, a \
+         short title and a concise markdown summary: what the module is for, its main \
+         types and entry points, and conventions or pitfalls worth knowing. Pass \
+         source_task_id `{task_id}`. Keep what is still true in an existing memory and \
+         drop what the merge made stale. Do not modify, commit or push any files.\n\n\
+         Merge commits:\n"
+    );
+    for repo in merged {
+        prompt.push_str(&format!("- {}: {}\n", repo.repo_name, repo.merge_commit));
+    }
+
+    for module in modules {
+        prompt.push_str(&format!("\n## Module `{}`\n\nChanged files:\n", module.path));
+        for file in &module.changed_files {
+            prompt.push_str(&format!("- {file}\n"));
+        }
+        match current_memories.get(&module.path) {
+            Some(memory) => {
+                prompt.push_str(&format!("\nCurrent memory:\n\n{}\n", memory.trim()));
+            }
+            None => prompt.push_str("\nNo memory yet.\n"),
+        }
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merged(repo_name: &str, changed_paths: &[&str]) -> MergedRepoChanges {
+        MergedRepoChanges {
+            repo_name: repo_name.to_string(),
+            merge_commit: "abc123".to_string(),
+            changed_paths: changed_paths.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn groups_files_by_directory_and_keeps_busiest_modules() {
+        let merged = [merged(
+            "app",
+            &[
+                "README.md",
+                "src/api/users.rs",
+                "src/api/tasks.rs",
+                "src/db/pool.rs",
+                "src/api/mod.rs",
+            ],
+        )];
+
+        let modules = modules_to_refresh(&merged, 2);
+        assert_eq!(
+            modules,
+            vec![
+                ModuleToRefresh {
+                    path: "src/api".to_string(),
+                    changed_files: vec![
+                        "src/api/users.rs".to_string(),
+                        "src/api/tasks.rs".to_string(),
+                        "src/api/mod.rs".to_string(),
+                    ],
+                },
+                ModuleToRefresh {
+                    path: "README.md".to_string(),
+                    changed_files: vec!["README.md".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn multi_repo_modules_are_prefixed_with_the_repo() {
+        let merged = [merged("web", &["src/app.tsx"]), merged("api", &["src/main.rs"])];
+        let paths: Vec<String> = modules_to_refresh(&merged, 10)
+            .into_iter()
+            .map(|m| m.path)
+            .collect();
+        assert_eq!(paths, vec!["api/src", "web/src"]);
+    }
+
+    #[test]
+    fn prompt_names_the_tool_and_includes_current_memories() {
+        let merged = [merged("app", &["src/api/users.rs", "src/db/pool.rs"])];
+        let modules = modules_to_refresh(&merged, 10);
+        let memories = BTreeMap::from([(
+            "src/api".to_string(),
+            "Axum handlers for the REST API".to_string(),
+        )]);
+
+        let prompt = refresh_prompt(
+            Uuid::nil(),
+            Uuid::nil(),
+            "Add user search",
+            &merged,
+            &modules,
+            &memories,
+        );
+        assert!(prompt.contains("`upsert_module_memory`"));
+        assert!(prompt.contains("## Module `src/api`"));
+        assert!(prompt.contains("Current memory:\n\nAxum handlers for the REST API"));
+        assert!(prompt.contains("Changed files:\n- src/db/pool.rs\n\nNo memory yet."));
+        assert!(prompt.contains("- app: abc123"));
+    }
+}
@@ -1,14 +1,23 @@
 use std::sync::{Arc, OnceLock};
 
+use db::models::email_notification_preference::{
+    EmailNotificationPreference, ImmediateEmailKind,
+};
+use sqlx::PgPool;
 use tokio::sync::RwLock;
 use utils;
 
-use crate::services::config::{Config, NotificationConfig, SoundFile};
+use crate::services::{
+    config::{Config, NotificationConfig, SoundFile},
+    email_notifications::EmailSender,
+};
 
 /// Service for handling cross-platform notifications including sound alerts and push notifications
 #[derive(Debug, Clone)]
 pub struct NotificationService {
     config: Arc<RwLock<Config>>,
+    /// SMTP sender, when configured through the environment
+    email: Option<EmailSender>,
 }
 
 /// Cache for WSL root path from PowerShell
@@ -16,7 +25,10 @@ static WSL_ROOT_PATH_CACHE: OnceLock<Option<String>> = OnceLock::new();
 
 impl NotificationService {
     pub fn new(config: Arc<RwLock<Config>>) -> Self {
-        Self { config }
+        Self {
+            config,
+            email: EmailSender::from_env(),
+        }
     }
 
     /// Send both sound and push notifications if enabled
@@ -25,6 +37,43 @@ impl NotificationService {
         Self::send_notification(&config, title, message).await;
     }
 
+    pub fn email_enabled(&self) -> bool {
+        self.email.is_some()
+    }
+
+    /// Send one email; fails when SMTP isn't configured
+    pub async fn send_email(&self, to: &str, subject: &str, body: &str) -> Result<(), String> {
+        match &self.email {
+            Some(sender) => sender.send(to, subject, body).await,
+            None => Err("SMTP is not configured".to_string()),
+        }
+    }
+
+    /// Email everyone who wants this kind of event right away
+    pub async fn email_immediate(
+        &self,
+        pool: &PgPool,
+        kind: ImmediateEmailKind,
+        subject: &str,
+        body: &str,
+    ) {
+        if self.email.is_none() {
+            return;
+        }
+        let recipients = match EmailNotificationPreference::find_immediate(pool, kind).await {
+            Ok(recipients) => recipients,
+            Err(e) => {
+                tracing::error!("Failed to load email recipients: {}", e);
+                return;
+            }
+        };
+        for recipient in recipients {
+            if let Err(e) = self.send_email(&recipient.email, subject, body).await {
+                tracing::warn!("Failed to email {}: {}", recipient.email, e);
+            }
+        }
+    }
+
     /// Internal method to send notifications with a given config
     async fn send_notification(config: &NotificationConfig, title: &str, message: &str) {
         if config.sound_enabled {
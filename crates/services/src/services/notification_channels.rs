@@ -0,0 +1,749 @@
+use std::time::Duration;
+
+use db::models::{
+    board_notification_settings::{
+        BoardNotificationSettings, CreateNotificationDigestItem, NotificationDigestItem,
+    },
+    chat_approval::{ChatApprovalMessage, ChatApprovalSubject},
+    execution_process::{ExecutionContext, ExecutionProcessRunReason, ExecutionProcessStatus},
+    notification_channel::{NotificationChannel, NotificationChannelKind, NotificationEvent},
+    project::Project,
+    task::Task,
+    task_label::TaskLabel,
+};
+use reqwest::{Method, Url};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::share::SharePublisher;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(15);
+/// Used for Telegram channels that leave `webhook_url` empty
+pub const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+/// Used for Slack channels that post with a bot token instead of a webhook
+pub const SLACK_API_URL: &str = "https://slack.com/api";
+
+/// An event delivered to notification channels, with the values templates can use
+#[derive(Debug, Clone)]
+pub struct ChannelNotification {
+    pub event: NotificationEvent,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub task_id: Uuid,
+    pub task_title: String,
+    /// Set for execution events
+    pub run_reason: Option<ExecutionProcessRunReason>,
+    pub exit_code: Option<i32>,
+    /// What happened, for events that aren't about an execution
+    pub detail: Option<String>,
+    /// Set for approval requests that can be answered by reacting in Slack or Matrix
+    pub approval: Option<ChatApprovalSubject>,
+}
+
+/// Replace `{{name}}` (or `{{ name }}`) placeholders with their values, leaving unknown
+/// ones as they are. Shared by channel templates and automation rule templates.
+pub fn fill_placeholders(template: &str, vars: &[(&str, String)]) -> String {
+    vars.iter()
+        .fold(template.to_string(), |acc, (name, value)| {
+            acc.replace(&format!("{{{{{name}}}}}"), value)
+                .replace(&format!("{{{{ {name} }}}}"), value)
+        })
+}
+
+impl ChannelNotification {
+    /// Notification for an execution that has stopped, if its status is final
+    pub fn for_execution(ctx: &ExecutionContext) -> Option<Self> {
+        let event = match ctx.execution_process.status {
+            ExecutionProcessStatus::Completed => NotificationEvent::ExecutionCompleted,
+            ExecutionProcessStatus::Failed => NotificationEvent::ExecutionFailed,
+            ExecutionProcessStatus::Killed => NotificationEvent::ExecutionKilled,
+            // Interrupted runs are resumed after a restart rather than reported
+            ExecutionProcessStatus::Running | ExecutionProcessStatus::Interrupted => return None,
+        };
+        Some(Self {
+            event,
+            project_id: ctx.project.id,
+            project_name: ctx.project.name.clone(),
+            task_id: ctx.task.id,
+            task_title: ctx.task.title.clone(),
+            run_reason: Some(ctx.execution_process.run_reason.clone()),
+            exit_code: ctx.execution_process.exit_code,
+            detail: None,
+            approval: None,
+        })
+    }
+
+    /// Notification for something that happened to a task outside of an execution
+    pub fn for_task(
+        event: NotificationEvent,
+        project: &Project,
+        task: &Task,
+        detail: impl Into<String>,
+    ) -> Self {
+        Self {
+            event,
+            project_id: project.id,
+            project_name: project.name.clone(),
+            task_id: task.id,
+            task_title: task.title.clone(),
+            run_reason: None,
+            exit_code: None,
+            detail: Some(detail.into()),
+            approval: None,
+        }
+    }
+
+    /// Let chat channels answer this notification with a ✅ or ❌ reaction
+    pub fn requesting_approval(mut self, subject: ChatApprovalSubject) -> Self {
+        self.approval = Some(subject);
+        self
+    }
+
+    fn default_title(&self) -> String {
+        let outcome = match self.event {
+            NotificationEvent::ExecutionCompleted => "completed",
+            NotificationEvent::ExecutionFailed => "failed",
+            NotificationEvent::ExecutionKilled => "was stopped",
+            NotificationEvent::TaskEscalated => "was escalated",
+            NotificationEvent::ApprovalRequested => "is waiting for approval",
+            NotificationEvent::LabelsChanged => "labels changed",
+        };
+        format!("{} {}", self.task_title, outcome)
+    }
+
+    fn default_message(&self) -> String {
+        let Some(run_reason) = &self.run_reason else {
+            return match &self.detail {
+                Some(detail) => format!("Project {}: {}", self.project_name, detail),
+                None => format!("Project {}", self.project_name),
+            };
+        };
+        let process = match run_reason {
+            ExecutionProcessRunReason::SetupScript => "Setup script",
+            ExecutionProcessRunReason::CleanupScript => "Cleanup script",
+            ExecutionProcessRunReason::CodingAgent => "Coding agent",
+            ExecutionProcessRunReason::DevServer => "Dev server",
+            ExecutionProcessRunReason::ManualRun => "Manual run",
+        };
+        let mut message = format!("{} in project {}", process, self.project_name);
+        if let Some(code) = self.exit_code {
+            message.push_str(&format!(" exited with code {code}"));
+        }
+        message
+    }
+
+    /// Substitute `{{placeholders}}` such as `{{task.title}}` or `{{exit_code}}`.
+    /// Unknown placeholders are left as they are.
+    pub fn render(&self, template: &str) -> String {
+        let vars = [
+            ("event", self.event.as_str().to_string()),
+            ("project.id", self.project_id.to_string()),
+            ("project.name", self.project_name.clone()),
+            ("task.id", self.task_id.to_string()),
+            ("task.title", self.task_title.clone()),
+            (
+                "run_reason",
+                self.run_reason
+                    .as_ref()
+                    .map(|r| r.to_string())
+                    .unwrap_or_default(),
+            ),
+            (
+                "exit_code",
+                self.exit_code.map(|c| c.to_string()).unwrap_or_default(),
+            ),
+            ("detail", self.detail.clone().unwrap_or_default()),
+        ];
+        fill_placeholders(template, &vars)
+    }
+
+    /// The title and message for a channel, using its templates where set
+    pub fn title_and_message(&self, channel: &NotificationChannel) -> (String, String) {
+        let title = match &channel.title_template {
+            Some(template) => self.render(template),
+            None => self.default_title(),
+        };
+        let message = match &channel.message_template {
+            Some(template) => self.render(template),
+            None => self.default_message(),
+        };
+        (title, message)
+    }
+}
+
+/// Request body in the format the channel's backend expects. `destination` is the
+/// Telegram chat id or the Slack channel id of bot-token channels; Matrix rooms are
+/// part of the endpoint instead.
+pub fn payload(
+    kind: NotificationChannelKind,
+    destination: Option<&str>,
+    notification: &ChannelNotification,
+    title: &str,
+    message: &str,
+) -> Value {
+    match kind {
+        NotificationChannelKind::Http => json!({
+            "event": notification.event,
+            "title": title,
+            "message": message,
+            "project_id": notification.project_id,
+            "task_id": notification.task_id,
+            "run_reason": notification.run_reason.as_ref().map(|r| r.to_string()),
+            "exit_code": notification.exit_code,
+            "detail": notification.detail,
+        }),
+        _ => text_payload(kind, destination, title, message),
+    }
+}
+
+/// Request body of a digest. HTTP channels also get the individual notifications.
+pub fn digest_payload(
+    kind: NotificationChannelKind,
+    destination: Option<&str>,
+    title: &str,
+    message: &str,
+    items: &[NotificationDigestItem],
+) -> Value {
+    match kind {
+        NotificationChannelKind::Http => json!({
+            "event": "digest",
+            "title": title,
+            "message": message,
+            "items": items,
+        }),
+        _ => text_payload(kind, destination, title, message),
+    }
+}
+
+fn text_payload(
+    kind: NotificationChannelKind,
+    destination: Option<&str>,
+    title: &str,
+    message: &str,
+) -> Value {
+    match kind {
+        NotificationChannelKind::Slack => {
+            let text = format!("*{title}*\n{message}");
+            match destination {
+                Some(channel) => json!({ "channel": channel, "text": text }),
+                None => json!({ "text": text }),
+            }
+        }
+        NotificationChannelKind::Discord => json!({ "content": format!("**{title}**\n{message}") }),
+        NotificationChannelKind::Matrix => json!({
+            "msgtype": "m.text",
+            "body": format!("{title}\n{message}"),
+        }),
+        NotificationChannelKind::Telegram => json!({
+            "chat_id": destination,
+            "text": format!("{title}\n{message}"),
+        }),
+        NotificationChannelKind::Http => json!({ "title": title, "message": message }),
+    }
+}
+
+/// Whether the channel posts through Slack's Web API with a bot token, which unlike a
+/// webhook tells us the posted message's ts
+fn is_slack_bot(channel: &NotificationChannel, kind: NotificationChannelKind) -> bool {
+    let set = |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+    kind == NotificationChannelKind::Slack
+        && set(&channel.access_token)
+        && set(&channel.destination)
+}
+
+/// Whether reactions to the channel's messages can be read back, so approval requests
+/// can be answered from the chat
+pub fn supports_reactions(channel: &NotificationChannel, kind: NotificationChannelKind) -> bool {
+    kind == NotificationChannelKind::Matrix || is_slack_bot(channel, kind)
+}
+
+/// Method and URL a notification is delivered to. Matrix sends an `m.room.message`
+/// event to the room with `txn_id` as the transaction id; Telegram calls the bot's
+/// `sendMessage`, and Slack channels with a bot token call `chat.postMessage`.
+pub fn endpoint(
+    channel: &NotificationChannel,
+    kind: NotificationChannelKind,
+    txn_id: Uuid,
+) -> Result<(Method, Url), String> {
+    let required = |value: &Option<String>, what: &str| {
+        value
+            .as_deref()
+            .filter(|v| !v.trim().is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| format!("{} channels need a {what}", kind.as_str()))
+    };
+    let parse = |url: &str| Url::parse(url).map_err(|e| format!("Invalid URL '{url}': {e}"));
+    let with_segments = |mut url: Url, segments: &[&str]| {
+        url.path_segments_mut()
+            .map_err(|_| "URL cannot be a base".to_string())?
+            .pop_if_empty()
+            .extend(segments);
+        Ok::<_, String>(url)
+    };
+
+    match kind {
+        NotificationChannelKind::Matrix => {
+            let room_id = required(&channel.destination, "room id")?;
+            let txn_id = txn_id.to_string();
+            let url = with_segments(
+                parse(&channel.webhook_url)?,
+                &[
+                    "_matrix",
+                    "client",
+                    "v3",
+                    "rooms",
+                    &room_id,
+                    "send",
+                    "m.room.message",
+                    &txn_id,
+                ],
+            )?;
+            Ok((Method::PUT, url))
+        }
+        NotificationChannelKind::Telegram => {
+            let bot_token = required(&channel.access_token, "bot token")?;
+            required(&channel.destination, "chat id")?;
+            let base = match channel.webhook_url.trim() {
+                "" => TELEGRAM_API_URL,
+                url => url,
+            };
+            let url = with_segments(parse(base)?, &[&format!("bot{bot_token}"), "sendMessage"])?;
+            Ok((Method::POST, url))
+        }
+        NotificationChannelKind::Slack if is_slack_bot(channel, kind) => {
+            let url = with_segments(parse(SLACK_API_URL)?, &["chat.postMessage"])?;
+            Ok((Method::POST, url))
+        }
+        NotificationChannelKind::Slack
+        | NotificationChannelKind::Discord
+        | NotificationChannelKind::Http => Ok((Method::POST, parse(&channel.webhook_url)?)),
+    }
+}
+
+/// Id of the posted message in a successful response: the Slack message ts or the
+/// Matrix event id. Slack's Web API reports failures in the body with a 200 status.
+pub fn message_ref(
+    kind: NotificationChannelKind,
+    response: &Value,
+) -> Result<Option<String>, String> {
+    let field = |name: &str| response.get(name).and_then(Value::as_str).map(str::to_string);
+    match kind {
+        NotificationChannelKind::Slack if response.get("ok").is_some() => {
+            if response["ok"].as_bool() == Some(true) {
+                Ok(field("ts"))
+            } else {
+                Err(format!(
+                    "Slack returned an error: {}",
+                    field("error").unwrap_or_else(|| "unknown".to_string())
+                ))
+            }
+        }
+        NotificationChannelKind::Matrix => Ok(field("event_id")),
+        _ => Ok(None),
+    }
+}
+
+fn channel_kind(channel: &NotificationChannel) -> Result<NotificationChannelKind, String> {
+    channel
+        .get_kind()
+        .ok_or_else(|| format!("Unknown channel kind '{}'", channel.kind))
+}
+
+/// Deliver a notification to one channel, regardless of its routing rules. Returns the
+/// posted message's id where the backend reports one.
+pub async fn send(
+    channel: &NotificationChannel,
+    notification: &ChannelNotification,
+) -> Result<Option<String>, String> {
+    let kind = channel_kind(channel)?;
+    let (title, mut message) = notification.title_and_message(channel);
+    if notification.approval.is_some() && supports_reactions(channel, kind) {
+        message.push_str("\nReact with ✅ to approve or ❌ to reject.");
+    }
+    let body = payload(
+        kind,
+        channel.destination.as_deref(),
+        notification,
+        &title,
+        &message,
+    );
+    deliver(channel, kind, &body).await
+}
+
+/// Deliver a board digest to one channel
+pub async fn send_digest(
+    channel: &NotificationChannel,
+    title: &str,
+    message: &str,
+    items: &[NotificationDigestItem],
+) -> Result<(), String> {
+    let kind = channel_kind(channel)?;
+    let body = digest_payload(kind, channel.destination.as_deref(), title, message, items);
+    deliver(channel, kind, &body).await.map(|_| ())
+}
+
+async fn deliver(
+    channel: &NotificationChannel,
+    kind: NotificationChannelKind,
+    body: &Value,
+) -> Result<Option<String>, String> {
+    let (method, url) = endpoint(channel, kind, Uuid::new_v4())?;
+    let mut request = reqwest::Client::new()
+        .request(method, url)
+        .timeout(HTTP_TIMEOUT)
+        .json(body);
+    if kind == NotificationChannelKind::Matrix || is_slack_bot(channel, kind) {
+        let access_token = channel
+            .access_token
+            .as_deref()
+            .ok_or_else(|| format!("{} channels need an access token", kind.as_str()))?;
+        request = request.bearer_auth(access_token);
+    }
+    // Telegram URLs embed the bot token, so errors are reported without the URL
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e.without_url()))?;
+    let status = response.status();
+    if status.is_success() {
+        // Webhooks answer with plain text; only JSON responses carry a message id
+        let body = response.json::<Value>().await.unwrap_or(Value::Null);
+        message_ref(kind, &body)
+    } else {
+        let body: String = response
+            .text()
+            .await
+            .unwrap_or_default()
+            .chars()
+            .take(500)
+            .collect();
+        Err(format!("Channel returned {status}: {body}"))
+    }
+}
+
+/// Deliver a notification to every enabled channel of the project (and of its
+/// organization) whose routing rules accept the event. Non-urgent events for a board
+/// with a digest cadence are queued for its next digest instead.
+pub async fn dispatch(
+    pool: &PgPool,
+    organization_id: Option<Uuid>,
+    notification: &ChannelNotification,
+) {
+    let channels = match NotificationChannel::find_enabled_for_project(
+        pool,
+        notification.project_id,
+        organization_id,
+    )
+    .await
+    {
+        Ok(channels) => channels,
+        Err(e) => {
+            tracing::error!("Failed to load notification channels: {}", e);
+            return;
+        }
+    };
+    let batching = if notification.event.is_urgent() {
+        None
+    } else {
+        BoardNotificationSettings::batching_for_project(pool, notification.project_id)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to load board notification settings: {}", e);
+                None
+            })
+    };
+    for channel in channels
+        .iter()
+        .filter(|channel| channel.routes(notification.event))
+    {
+        if let Some(settings) = &batching {
+            let (title, message) = notification.title_and_message(channel);
+            let item = CreateNotificationDigestItem {
+                board_id: settings.board_id,
+                channel_id: channel.id,
+                event: notification.event.as_str().to_string(),
+                project_id: notification.project_id,
+                task_id: notification.task_id,
+                title,
+                message,
+            };
+            if let Err(e) = NotificationDigestItem::create(pool, &item).await {
+                tracing::error!("Failed to queue notification for digest: {}", e);
+            }
+            continue;
+        }
+        match send(channel, notification).await {
+            Ok(message_ref) => {
+                if let (Some(subject), Some(message_ref)) = (notification.approval, message_ref)
+                    && let Err(e) = ChatApprovalMessage::create(
+                        pool,
+                        channel.id,
+                        notification.task_id,
+                        subject,
+                        &message_ref,
+                    )
+                    .await
+                {
+                    tracing::error!("Failed to record approval request message: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!(
+                "Failed to deliver {} notification to channel '{}': {}",
+                notification.event.as_str(),
+                channel.name,
+                e
+            ),
+        }
+    }
+}
+
+/// Dispatch in the background so slow webhooks don't hold up the caller. The project's
+/// organization is looked up through the share publisher, when there is one.
+pub fn spawn_dispatch(
+    pool: PgPool,
+    publisher: Option<SharePublisher>,
+    project: Project,
+    notification: ChannelNotification,
+) {
+    tokio::spawn(async move {
+        let organization_id = match publisher {
+            Some(publisher) => publisher
+                .organization_for_project(&project)
+                .await
+                .unwrap_or_else(|err| {
+                    tracing::warn!(?err, "Failed to resolve organization for notifications");
+                    None
+                }),
+            None => None,
+        };
+        dispatch(&pool, organization_id, &notification).await;
+    });
+}
+
+/// "Added bug, urgent; removed triage"
+pub fn labels_detail(added: &[String], removed: &[String]) -> String {
+    match (added.is_empty(), removed.is_empty()) {
+        (false, false) => format!("Added {}; removed {}", added.join(", "), removed.join(", ")),
+        (false, true) => format!("Added {}", added.join(", ")),
+        (true, false) => format!("Removed {}", removed.join(", ")),
+        (true, true) => "Labels unchanged".to_string(),
+    }
+}
+
+async fn label_names(pool: &PgPool, ids: &[Uuid]) -> Vec<String> {
+    let mut names = Vec::new();
+    for id in ids {
+        if let Ok(Some(label)) = TaskLabel::find_by_id(pool, *id).await {
+            names.push(label.name);
+        }
+    }
+    names
+}
+
+/// Let channels know a task's labels were added or removed
+pub async fn notify_labels_changed(
+    pool: &PgPool,
+    publisher: Option<SharePublisher>,
+    task_id: Uuid,
+    added: &[Uuid],
+    removed: &[Uuid],
+) {
+    let added = label_names(pool, added).await;
+    let removed = label_names(pool, removed).await;
+    let task = match Task::find_by_id(pool, task_id).await {
+        Ok(Some(task)) => task,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::error!("Failed to load task for label notification: {}", e);
+            return;
+        }
+    };
+    let project = match Project::find_by_id(pool, task.project_id).await {
+        Ok(Some(project)) => project,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::error!("Failed to load project for label notification: {}", e);
+            return;
+        }
+    };
+    let notification = ChannelNotification::for_task(
+        NotificationEvent::LabelsChanged,
+        &project,
+        &task,
+        labels_detail(&added, &removed),
+    );
+    spawn_dispatch(pool.clone(), publisher, project, notification);
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn notification() -> ChannelNotification {
+        ChannelNotification {
+            event: NotificationEvent::ExecutionFailed,
+            project_id: Uuid::nil(),
+            project_name: "api".to_string(),
+            task_id: Uuid::nil(),
+            task_title: "Fix login".to_string(),
+            run_reason: Some(ExecutionProcessRunReason::CodingAgent),
+            exit_code: Some(1),
+            detail: None,
+            approval: None,
+        }
+    }
+
+    fn channel(events: &[&str]) -> NotificationChannel {
+        NotificationChannel {
+            id: Uuid::nil(),
+            project_id: Some(Uuid::nil()),
+            organization_id: None,
+            name: "alerts".to_string(),
+            kind: "slack".to_string(),
+            webhook_url: "https://hooks.slack.com/services/x".to_string(),
+            destination: None,
+            access_token: None,
+            title_template: Some("[{{ event }}] {{task.title}}".to_string()),
+            message_template: None,
+            events: events.iter().map(|e| e.to_string()).collect(),
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn renders_templates_and_defaults() {
+        let (title, message) = notification().title_and_message(&channel(&[]));
+        assert_eq!(title, "[execution_failed] Fix login");
+        assert_eq!(message, "Coding agent in project api exited with code 1");
+    }
+
+    #[test]
+    fn task_events_describe_what_happened() {
+        let mut n = notification();
+        n.event = NotificationEvent::TaskEscalated;
+        n.run_reason = None;
+        n.exit_code = None;
+        n.detail = Some("Moved from 'Review' to 'Blocked'".to_string());
+        let mut c = channel(&[]);
+        c.title_template = None;
+
+        let (title, message) = n.title_and_message(&c);
+        assert_eq!(title, "Fix login was escalated");
+        assert_eq!(message, "Project api: Moved from 'Review' to 'Blocked'");
+        assert!(n.event.is_urgent());
+        assert!(!NotificationEvent::LabelsChanged.is_urgent());
+    }
+
+    #[test]
+    fn describes_label_changes() {
+        let names = |n: &[&str]| n.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            labels_detail(&names(&["bug", "urgent"]), &names(&["triage"])),
+            "Added bug, urgent; removed triage"
+        );
+        assert_eq!(labels_detail(&[], &names(&["triage"])), "Removed triage");
+    }
+
+    #[test]
+    fn routes_only_listed_events() {
+        assert!(channel(&[]).routes(NotificationEvent::ExecutionCompleted));
+        let failures_only = channel(&["execution_failed"]);
+        assert!(failures_only.routes(NotificationEvent::ExecutionFailed));
+        assert!(!failures_only.routes(NotificationEvent::ExecutionCompleted));
+    }
+
+    #[test]
+    fn formats_payload_per_backend() {
+        let n = notification();
+        assert_eq!(
+            payload(NotificationChannelKind::Slack, None, &n, "T", "M"),
+            json!({ "text": "*T*\nM" })
+        );
+        assert_eq!(
+            payload(NotificationChannelKind::Discord, None, &n, "T", "M"),
+            json!({ "content": "**T**\nM" })
+        );
+        assert_eq!(
+            payload(NotificationChannelKind::Http, None, &n, "T", "M")["event"],
+            "execution_failed"
+        );
+        assert_eq!(
+            payload(
+                NotificationChannelKind::Telegram,
+                Some("-100"),
+                &n,
+                "T",
+                "M"
+            ),
+            json!({ "chat_id": "-100", "text": "T\nM" })
+        );
+    }
+
+    #[test]
+    fn builds_matrix_and_telegram_endpoints() {
+        let mut matrix = channel(&[]);
+        matrix.kind = "matrix".to_string();
+        matrix.webhook_url = "https://matrix.example.org/".to_string();
+        matrix.destination = Some("!room:example.org".to_string());
+        let txn_id = Uuid::nil();
+        let (method, url) = endpoint(&matrix, NotificationChannelKind::Matrix, txn_id).unwrap();
+        assert_eq!(method, Method::PUT);
+        assert_eq!(
+            url.as_str(),
+            format!(
+                "https://matrix.example.org/_matrix/client/v3/rooms/!room:example.org/send/\
+                 m.room.message/{txn_id}"
+            )
+        );
+
+        let mut telegram = channel(&[]);
+        telegram.webhook_url = String::new();
+        telegram.destination = Some("-100".to_string());
+        telegram.access_token = Some("123:abc".to_string());
+        let (method, url) = endpoint(&telegram, NotificationChannelKind::Telegram, txn_id).unwrap();
+        assert_eq!(method, Method::POST);
+        assert_eq!(
+            url.as_str(),
+            "https://api.telegram.org/bot123:abc/sendMessage"
+        );
+
+        telegram.access_token = None;
+        assert!(endpoint(&telegram, NotificationChannelKind::Telegram, txn_id).is_err());
+    }
+
+    #[test]
+    fn slack_bot_channels_post_through_the_web_api() {
+        let mut slack = channel(&[]);
+        assert!(!supports_reactions(&slack, NotificationChannelKind::Slack));
+        slack.webhook_url = String::new();
+        slack.destination = Some("C0123".to_string());
+        slack.access_token = Some("xoxb-1".to_string());
+        assert!(supports_reactions(&slack, NotificationChannelKind::Slack));
+
+        let (_, url) = endpoint(&slack, NotificationChannelKind::Slack, Uuid::nil()).unwrap();
+        assert_eq!(url.as_str(), "https://slack.com/api/chat.postMessage");
+        assert_eq!(
+            message_ref(
+                NotificationChannelKind::Slack,
+                &json!({ "ok": true, "ts": "1700000000.000100" })
+            ),
+            Ok(Some("1700000000.000100".to_string()))
+        );
+        assert!(
+            message_ref(
+                NotificationChannelKind::Slack,
+                &json!({ "ok": false, "error": "channel_not_found" })
+            )
+            .is_err()
+        );
+        assert_eq!(
+            message_ref(NotificationChannelKind::Matrix, &json!({ "event_id": "$abc" })),
+            Ok(Some("$abc".to_string()))
+        );
+    }
+}
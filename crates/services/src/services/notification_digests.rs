@@ -0,0 +1,289 @@
+//! Board-level notification digests.
+//!
+//! Boards can batch their non-urgent channel notifications (completed runs, label
+//! changes, ...) into an hourly or daily digest. `notification_channels::dispatch` queues
+//! those in `notification_digest_items`; this service sends each channel one message
+//! per period listing them. Failures, escalations and approval requests are never
+//! batched. Daily digests go out at their hour in the timezone of the board's projects.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::{
+        board::Board,
+        board_notification_settings::{
+            BoardNotificationSettings, DigestClock, NotificationDigestCadence,
+            NotificationDigestItem,
+        },
+        notification_channel::NotificationChannel,
+        project_timezone_settings::ProjectTimezoneSettings,
+    },
+};
+use tokio::time::interval;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use super::notification_channels;
+
+/// Lines listed in one digest message before the rest is summarized
+const MAX_DIGEST_LINES: usize = 50;
+
+/// Title and message of a channel's digest
+pub fn render_digest(
+    board_name: &str,
+    cadence: NotificationDigestCadence,
+    items: &[NotificationDigestItem],
+) -> (String, String) {
+    let period = match cadence {
+        NotificationDigestCadence::Daily => "Daily",
+        NotificationDigestCadence::Hourly | NotificationDigestCadence::Off => "Hourly",
+    };
+    let title = format!(
+        "{period} digest for {board_name}: {} update(s)",
+        items.len()
+    );
+    let mut lines: Vec<String> = items
+        .iter()
+        .take(MAX_DIGEST_LINES)
+        .map(|item| format!("- {}: {}", item.title, item.message))
+        .collect();
+    if items.len() > MAX_DIGEST_LINES {
+        lines.push(format!("… and {} more", items.len() - MAX_DIGEST_LINES));
+    }
+    (title, lines.join("\n"))
+}
+
+/// Sends each board's queued notifications once its digest is due
+pub struct NotificationDigestService {
+    db: DBService,
+    poll_interval: Duration,
+}
+
+impl NotificationDigestService {
+    pub async fn spawn(db: DBService) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            poll_interval: Duration::from_secs(300),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting notification digest service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            self.send_due_digests().await;
+        }
+    }
+
+    async fn send_due_digests(&self) {
+        let pool = &self.db.pool;
+        let boards = match BoardNotificationSettings::find_batching(pool).await {
+            Ok(boards) => boards,
+            Err(e) => {
+                error!("Failed to load board notification settings: {}", e);
+                return;
+            }
+        };
+
+        let now = Utc::now();
+        for settings in &boards {
+            let clock = match self.board_clock(settings, now).await {
+                Ok(clock) => clock,
+                Err(e) => {
+                    error!("Failed to resolve timezone of board {}: {}", settings.board_id, e);
+                    continue;
+                }
+            };
+            if !settings.digest_due(now, &clock) {
+                continue;
+            }
+            if let Err(e) = self.send_board_digest(settings).await {
+                error!("Failed to send digest for board {}: {}", settings.board_id, e);
+                continue;
+            }
+            if let Err(e) =
+                BoardNotificationSettings::mark_digest_sent(pool, settings.board_id, now).await
+            {
+                error!("Failed to record digest for board {}: {}", settings.board_id, e);
+            }
+        }
+    }
+
+    /// `now` and the last digest in the board's timezone. Hourly digests only compare
+    /// instants, so they skip the lookup.
+    async fn board_clock(
+        &self,
+        settings: &BoardNotificationSettings,
+        now: DateTime<Utc>,
+    ) -> Result<DigestClock, sqlx::Error> {
+        if settings.cadence() != NotificationDigestCadence::Daily {
+            return Ok(DigestClock::utc(now, settings.last_digest_at));
+        }
+        let pool = &self.db.pool;
+        let timezone = ProjectTimezoneSettings::for_board(pool, settings.board_id).await?;
+        let last_digest_at = match settings.last_digest_at {
+            Some(sent) => Some(ProjectTimezoneSettings::local_time(pool, &timezone, sent).await?),
+            None => None,
+        };
+        Ok(DigestClock {
+            now: ProjectTimezoneSettings::local_time(pool, &timezone, now).await?,
+            last_digest_at,
+        })
+    }
+
+    /// Send every channel its queued items. Items a channel failed to take stay queued
+    /// for the next digest.
+    async fn send_board_digest(
+        &self,
+        settings: &BoardNotificationSettings,
+    ) -> Result<(), sqlx::Error> {
+        let pool = &self.db.pool;
+        let items = NotificationDigestItem::find_by_board(pool, settings.board_id).await?;
+        if items.is_empty() {
+            return Ok(());
+        }
+        let board_name = Board::find_by_id(pool, settings.board_id)
+            .await?
+            .map(|board| board.name)
+            .unwrap_or_else(|| "board".to_string());
+
+        let mut by_channel: BTreeMap<Uuid, Vec<NotificationDigestItem>> = BTreeMap::new();
+        for item in items {
+            by_channel.entry(item.channel_id).or_default().push(item);
+        }
+
+        for (channel_id, items) in by_channel {
+            let ids: Vec<Uuid> = items.iter().map(|item| item.id).collect();
+            let channel = match NotificationChannel::find_by_id(pool, channel_id).await? {
+                Some(channel) if channel.enabled => channel,
+                // Nothing to deliver to any more
+                _ => {
+                    NotificationDigestItem::delete_many(pool, &ids).await?;
+                    continue;
+                }
+            };
+            let (title, message) = render_digest(&board_name, settings.cadence(), &items);
+            match notification_channels::send_digest(&channel, &title, &message, &items).await {
+                Ok(()) => {
+                    NotificationDigestItem::delete_many(pool, &ids).await?;
+                }
+                Err(e) => warn!(
+                    "Failed to deliver digest to channel '{}': {}",
+                    channel.name, e
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone};
+
+    use super::*;
+
+    fn item(title: &str, message: &str) -> NotificationDigestItem {
+        NotificationDigestItem {
+            id: Uuid::new_v4(),
+            board_id: Uuid::nil(),
+            channel_id: Uuid::nil(),
+            event: "execution_completed".to_string(),
+            project_id: Uuid::nil(),
+            task_id: Uuid::new_v4(),
+            title: title.to_string(),
+            message: message.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn settings(cadence: &str, last_digest_at: Option<DateTime<Utc>>) -> BoardNotificationSettings {
+        BoardNotificationSettings {
+            board_id: Uuid::nil(),
+            digest_cadence: cadence.to_string(),
+            digest_hour: 9,
+            last_digest_at,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn digest_lists_each_notification() {
+        let items = [
+            item("Fix login completed", "Coding agent in project api exited with code 0"),
+            item("Add search labels changed", "Project api: Added ready"),
+        ];
+        let (title, message) = render_digest("Main", NotificationDigestCadence::Daily, &items);
+        assert_eq!(title, "Daily digest for Main: 2 update(s)");
+        assert_eq!(
+            message,
+            "- Fix login completed: Coding agent in project api exited with code 0\n\
+             - Add search labels changed: Project api: Added ready"
+        );
+    }
+
+    #[test]
+    fn hourly_digests_wait_an_hour() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 10, 30, 0).unwrap();
+        let due = |settings: &BoardNotificationSettings, now| {
+            settings.digest_due(now, &DigestClock::utc(now, settings.last_digest_at))
+        };
+        assert!(due(&settings("hourly", None), now));
+        let recent = settings("hourly", Some(now - chrono::Duration::minutes(20)));
+        assert!(!due(&recent, now));
+        assert!(due(&recent, now + chrono::Duration::minutes(40)));
+        assert!(!due(&settings("off", None), now));
+    }
+
+    #[test]
+    fn daily_digests_go_out_once_after_their_hour() {
+        let due = |settings: &BoardNotificationSettings, now| {
+            settings.digest_due(now, &DigestClock::utc(now, settings.last_digest_at))
+        };
+        let morning = Utc.with_ymd_and_hms(2026, 3, 2, 8, 0, 0).unwrap();
+        assert!(!due(&settings("daily", None), morning));
+        let later = morning + chrono::Duration::hours(2);
+        assert!(due(&settings("daily", None), later));
+        assert!(!due(&settings("daily", Some(later)), later + chrono::Duration::hours(3)));
+    }
+
+    #[test]
+    fn daily_digests_follow_the_local_clock() {
+        let local = |day, hour, minute| {
+            NaiveDate::from_ymd_opt(2026, 3, day)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+                .unwrap()
+        };
+        // 23:30 UTC is already 08:30 the next morning in Tokyo
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 23, 30, 0).unwrap();
+        let daily = settings("daily", Some(now - chrono::Duration::hours(20)));
+        let before_hour = DigestClock {
+            now: local(2, 8, 30),
+            last_digest_at: Some(local(1, 12, 30)),
+        };
+        assert!(!daily.digest_due(now, &before_hour));
+        let after_hour = DigestClock {
+            now: local(2, 9, 30),
+            ..before_hour
+        };
+        assert!(daily.digest_due(now, &after_hour));
+
+        // A repeated hour after clocks go back is still the same local day
+        let repeated = DigestClock {
+            now: local(2, 9, 45),
+            last_digest_at: Some(local(2, 9, 15)),
+        };
+        assert!(!daily.digest_due(now, &repeated));
+    }
+}
@@ -0,0 +1,117 @@
+//! Plan-only agent runs.
+//!
+//! A column flagged `plan_only`, or a manual run started with `plan_only`, has the
+//! agent write a plan (`.vibe/plan.md`) and a decision file without touching the
+//! code: anything else it changes or commits is discarded when the run finishes,
+//! as in read-only columns. Each such run gets a [`PlanReview`]; the task can't
+//! leave the column, or be given a normal run, until a reviewer approves the plan.
+
+use std::path::Path;
+
+use db::models::{
+    plan_review::{PlanReview, PlanReviewStatus},
+    workspace::Workspace,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::{
+    decision_file::{decisions_dir, repo_dirs},
+    prompt_language::{self, PromptLanguage},
+};
+
+const PLAN_FILE: &str = "plan.md";
+
+/// The deliverable for a plan-only run
+pub fn plan_only_deliverable(deliverable: Option<String>, language: PromptLanguage) -> String {
+    let notice = prompt_language::plan_only_notice(language);
+    match deliverable {
+        Some(deliverable) => format!("{deliverable}\n\n{notice}"),
+        None => notice.to_string(),
+    }
+}
+
+/// Read the plan written during a plan-only run: the decisions directory's plan,
+/// else the workspace root's, else the first repo's
+pub async fn read_plan_file(workspace: &Workspace) -> Option<String> {
+    let mut paths = vec![decisions_dir(workspace.id).join(PLAN_FILE)];
+    if let Some(worktree_path) = workspace.container_ref.as_ref() {
+        let root = Path::new(worktree_path);
+        paths.push(root.join(".vibe").join(PLAN_FILE));
+        for (_, dir) in repo_dirs(root).await {
+            paths.push(dir.join(".vibe").join(PLAN_FILE));
+        }
+    }
+    for path in paths {
+        if let Ok(plan) = tokio::fs::read_to_string(&path).await
+            && !plan.trim().is_empty()
+        {
+            return Some(plan);
+        }
+    }
+    None
+}
+
+/// Whether a task's latest plan keeps it from moving on
+pub fn blocks(latest: Option<&PlanReview>) -> bool {
+    latest.is_some_and(|review| review.status != PlanReviewStatus::Approved)
+}
+
+/// Whether the plan written in `column_id` still awaits approval
+pub async fn blocks_leaving_column(
+    pool: &PgPool,
+    task_id: Uuid,
+    column_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let latest = PlanReview::find_latest(pool, task_id, Some(column_id)).await?;
+    Ok(blocks(latest.as_ref()))
+}
+
+pub fn blocked_message(column_name: &str) -> String {
+    format!("The plan written in '{column_name}' must be approved before the task can move on")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn review(status: PlanReviewStatus) -> PlanReview {
+        PlanReview {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            session_id: Uuid::new_v4(),
+            column_id: None,
+            plan: Some("1. Add the endpoint".to_string()),
+            status,
+            reviewer: None,
+            comment: None,
+            reviewed_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn only_approved_plans_unblock() {
+        assert!(!blocks(None));
+        assert!(blocks(Some(&review(PlanReviewStatus::Pending))));
+        assert!(blocks(Some(&review(PlanReviewStatus::Rejected))));
+        assert!(!blocks(Some(&review(PlanReviewStatus::Approved))));
+    }
+
+    #[test]
+    fn notice_is_appended_to_deliverable() {
+        let notice = prompt_language::plan_only_notice(PromptLanguage::English);
+        assert_eq!(plan_only_deliverable(None, PromptLanguage::English), notice);
+        let deliverable = plan_only_deliverable(
+            Some("Outline the migration".to_string()),
+            PromptLanguage::English,
+        );
+        assert!(deliverable.starts_with("Outline the migration\n\n"));
+        assert!(deliverable.ends_with(notice));
+        assert!(
+            plan_only_deliverable(None, PromptLanguage::German).starts_with("NUR-PLANUNGS-LAUF")
+        );
+    }
+}
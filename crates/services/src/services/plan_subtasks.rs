@@ -0,0 +1,321 @@
+//! Sub-tasks derived from implementation plans (iplan artifacts).
+//!
+//! A plan's work items become tasks linked to the task that wrote the plan through
+//! `parent_task_id`, placed in the board's initial column. Items are read from the
+//! list under a "Tasks"/"Steps" heading (or `Step N:` headings); plans without one
+//! use every top-level list item outside definition-of-done style sections.
+
+use db::models::{
+    context_artifact::ContextArtifact,
+    kanban_column::KanbanColumn,
+    project::Project,
+    task::{CreateTask, Task},
+    task_event::{ActorType, CreateTaskEvent, TaskEvent},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const MAX_TITLE_CHARS: usize = 120;
+
+/// One work item of a plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanItem {
+    pub title: String,
+    pub description: Option<String>,
+}
+
+fn heading(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let text = trimmed.trim_start_matches('#');
+    (text.len() < trimmed.len() && text.starts_with(' ')).then(|| text.trim())
+}
+
+fn is_task_heading(heading: &str) -> bool {
+    let heading = heading.to_lowercase();
+    ["task", "step", "work item", "implementation"]
+        .iter()
+        .any(|word| heading.contains(word))
+}
+
+fn is_excluded_heading(heading: &str) -> bool {
+    let heading = heading.to_lowercase();
+    [
+        "done",
+        "acceptance",
+        "out of scope",
+        "question",
+        "risk",
+        "context",
+        "why",
+    ]
+    .iter()
+    .any(|word| heading.contains(word))
+}
+
+/// `Step 2: Add the API` -> `Add the API`
+fn step_heading_title(heading: &str) -> Option<&str> {
+    let lower = heading.to_lowercase();
+    let prefix = ["step", "task", "phase"]
+        .into_iter()
+        .find(|p| lower.starts_with(p))?;
+    let rest = heading[prefix.len()..].trim_start();
+    let digits = rest.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let title = rest[digits..].trim_start_matches([':', '.', ')', '-', ' ', '\u{2014}']);
+    Some(if title.is_empty() { heading } else { title })
+}
+
+/// Text of a top-level list item, without its marker or checkbox
+fn list_item(line: &str) -> Option<&str> {
+    if line.starts_with("  ") || line.starts_with('\t') {
+        return None;
+    }
+    let line = line.trim_start();
+    let rest = if let Some(rest) = ["- ", "* ", "+ "].iter().find_map(|m| line.strip_prefix(m)) {
+        rest
+    } else {
+        let digits = line.chars().take_while(char::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        line[digits..]
+            .strip_prefix(". ")
+            .or_else(|| line[digits..].strip_prefix(") "))?
+    };
+    let rest = rest.trim_start();
+    let rest = ["[ ] ", "[x] ", "[X] "]
+        .iter()
+        .find_map(|c| rest.strip_prefix(c))
+        .unwrap_or(rest);
+    Some(rest.trim())
+}
+
+/// Split `**Title**: details` into a title and the rest; long titles are truncated
+fn item_from_text(text: &str) -> PlanItem {
+    let (title, rest) = match text.strip_prefix("**").and_then(|t| t.split_once("**")) {
+        Some((bold, rest)) => (
+            bold.trim().trim_end_matches(':').to_string(),
+            rest.trim_start_matches([':', '-', ' ', '\u{2014}']).trim(),
+        ),
+        None => (text.trim_end_matches(':').to_string(), ""),
+    };
+    let mut description = (!rest.is_empty()).then(|| rest.to_string());
+    let title = if title.chars().count() > MAX_TITLE_CHARS {
+        description = Some(match description {
+            Some(d) => format!("{text}\n{d}"),
+            None => text.to_string(),
+        });
+        let cut: String = title.chars().take(MAX_TITLE_CHARS).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        title
+    };
+    PlanItem { title, description }
+}
+
+fn push_detail(item: &mut PlanItem, line: &str) {
+    match &mut item.description {
+        Some(description) => {
+            description.push('\n');
+            description.push_str(line);
+        }
+        None => item.description = Some(line.to_string()),
+    }
+}
+
+/// Work items of a plan, in document order
+pub fn parse_plan_items(markdown: &str) -> Vec<PlanItem> {
+    let mut task_items: Vec<PlanItem> = Vec::new();
+    let mut other_items: Vec<PlanItem> = Vec::new();
+    let mut section = String::new();
+    let mut in_code = false;
+    // Where continuation lines go: (in task bucket, index)
+    let mut current: Option<(bool, usize)> = None;
+    let mut in_step = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+
+        if let Some(text) = heading(line) {
+            section = text.to_string();
+            current = None;
+            in_step = false;
+            if let Some(title) = step_heading_title(text) {
+                task_items.push(PlanItem {
+                    title: title.to_string(),
+                    description: None,
+                });
+                current = Some((true, task_items.len() - 1));
+                in_step = true;
+            }
+            continue;
+        }
+
+        let bucket_is_task = is_task_heading(&section);
+        if !in_step && let Some(text) = list_item(line) {
+            if text.is_empty() || (!bucket_is_task && is_excluded_heading(&section)) {
+                current = None;
+                continue;
+            }
+            let items = if bucket_is_task {
+                &mut task_items
+            } else {
+                &mut other_items
+            };
+            items.push(item_from_text(text));
+            current = Some((bucket_is_task, items.len() - 1));
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        match current {
+            // Indented lines belong to the item above; step headings take every line
+            Some((is_task, index)) if in_step || line.starts_with([' ', '\t']) => {
+                let items = if is_task {
+                    &mut task_items
+                } else {
+                    &mut other_items
+                };
+                push_detail(&mut items[index], trimmed);
+            }
+            _ => current = None,
+        }
+    }
+
+    if task_items.is_empty() {
+        other_items
+    } else {
+        task_items
+    }
+}
+
+/// The board's initial column for the task's project
+pub async fn backlog_column(
+    pool: &PgPool,
+    task: &Task,
+) -> Result<Option<KanbanColumn>, sqlx::Error> {
+    match Project::find_by_id(pool, task.project_id)
+        .await?
+        .and_then(|p| p.board_id)
+    {
+        Some(board_id) => KanbanColumn::find_initial(pool, board_id).await,
+        None => Ok(None),
+    }
+}
+
+/// Create a sub-task of `parent` for each plan item. Items whose title matches an
+/// existing sub-task are skipped, so running this again after the plan grows only
+/// adds the new items.
+pub async fn create_subtasks(
+    pool: &PgPool,
+    parent: &Task,
+    plan: &ContextArtifact,
+    column: Option<&KanbanColumn>,
+    actor_type: ActorType,
+    actor_id: Option<String>,
+) -> Result<Vec<Task>, sqlx::Error> {
+    let existing: Vec<String> = Task::find_subtasks(pool, parent.id)
+        .await?
+        .into_iter()
+        .map(|t| t.title.trim().to_lowercase())
+        .collect();
+
+    let mut created = Vec::new();
+    for item in parse_plan_items(&plan.content) {
+        if existing.contains(&item.title.trim().to_lowercase()) {
+            continue;
+        }
+        let origin = format!(
+            "Sub-task of \"{}\" from plan \"{}\"",
+            parent.title, plan.title
+        );
+        let description = match item.description {
+            Some(details) => format!("{details}\n\n{origin}"),
+            None => origin,
+        };
+        let mut data =
+            CreateTask::from_title_description(parent.project_id, item.title, Some(description));
+        if let Some(column) = column {
+            data.column_id = Some(column.id);
+            data.status = Some(column.status.clone());
+        }
+        let mut task = Task::create(pool, &data, Uuid::new_v4()).await?;
+        Task::set_parent_task(pool, task.id, Some(parent.id)).await?;
+        task.parent_task_id = Some(parent.id);
+
+        let event = CreateTaskEvent::task_created(task.id, actor_type.clone(), actor_id.clone());
+        if let Err(e) = TaskEvent::create(pool, &event).await {
+            tracing::error!(
+                "Failed to record task created event for task {}: {}",
+                task.id,
+                e
+            );
+        }
+        created.push(task);
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titles(items: &[PlanItem]) -> Vec<&str> {
+        items.iter().map(|i| i.title.as_str()).collect()
+    }
+
+    #[test]
+    fn reads_the_task_section_and_its_details() {
+        let plan = "# Login rework\n\nWhy: sessions expire.\n\n- not a task\n\n\
+                    ## Tasks\n\n\
+                    1. **Add migration**: `sessions.expires_at` column\n   \
+                       - backfill existing rows\n\
+                    2. [ ] Update the auth middleware\n\n\
+                    ## Definition of done\n\n- [ ] Tests pass\n";
+        let items = parse_plan_items(plan);
+
+        assert_eq!(
+            titles(&items),
+            vec!["Add migration", "Update the auth middleware"]
+        );
+        assert_eq!(
+            items[0].description.as_deref(),
+            Some("`sessions.expires_at` column\n- backfill existing rows")
+        );
+        assert_eq!(items[1].description, None);
+    }
+
+    #[test]
+    fn step_headings_become_items() {
+        let plan = "## Step 1: Create schema\nAdd the table.\n- with an index\n\n\
+                    ## Step 2 - Add API\n\n```rust\n- not an item\n```\n";
+        let items = parse_plan_items(plan);
+
+        assert_eq!(titles(&items), vec!["Create schema", "Add API"]);
+        assert_eq!(
+            items[0].description.as_deref(),
+            Some("Add the table.\n- with an index")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_top_level_items_outside_excluded_sections() {
+        let plan = "Overview\n\n- Build the importer\n* Wire the route\n  \
+                    continued detail\n\n### Acceptance criteria\n- Imports 1k issues\n";
+        let items = parse_plan_items(plan);
+
+        assert_eq!(titles(&items), vec!["Build the importer", "Wire the route"]);
+        assert_eq!(items[1].description.as_deref(), Some("continued detail"));
+    }
+}
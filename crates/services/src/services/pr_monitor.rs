@@ -7,6 +7,7 @@ use db::{
         kanban_column::KanbanColumn,
         merge::{Merge, MergeStatus, PrMerge},
         project::Project,
+        repo_forge_settings::RepoForgeSettings,
         task::{Task, TaskStatus},
         task_dependency::TaskDependency,
         task_trigger::TaskTrigger,
@@ -21,21 +22,21 @@ use tracing::{debug, error, info};
 
 use crate::services::{
     analytics::AnalyticsContext,
-    github::{GitHubRepoInfo, GitHubService, GitHubServiceError},
+    forge::{self, CodeHostError},
     share::SharePublisher,
 };
 
 #[derive(Debug, Error)]
 enum PrMonitorError {
     #[error(transparent)]
-    GitHubServiceError(#[from] GitHubServiceError),
+    CodeHost(#[from] CodeHostError),
     #[error(transparent)]
     WorkspaceError(#[from] WorkspaceError),
     #[error(transparent)]
     Sqlx(#[from] SqlxError),
 }
 
-/// Service to monitor GitHub PRs and update task status when they are merged
+/// Service to monitor PRs on the repos' code hosts and update task status when they are merged
 pub struct PrMonitorService {
     db: DBService,
     poll_interval: Duration,
@@ -100,12 +101,9 @@ impl PrMonitorService {
 
     /// Check the status of a specific PR
     async fn check_pr_status(&self, pr_merge: &PrMerge) -> Result<(), PrMonitorError> {
-        // GitHubService now uses gh CLI, no token needed
-        let github_service = GitHubService::new()?;
-        let repo_info = GitHubRepoInfo::from_remote_url(&pr_merge.pr_info.url)?;
-
-        let pr_status = github_service
-            .update_pr_status(&repo_info, pr_merge.pr_info.number.into())
+        let settings = RepoForgeSettings::for_repo(&self.db.pool, pr_merge.repo_id).await?;
+        let pr_status = forge::for_repo(&settings, &pr_merge.pr_info.url)?
+            .get_pr(pr_merge.pr_info.number.into())
             .await?;
 
         debug!(
@@ -115,7 +113,7 @@ impl PrMonitorService {
 
         // Update the PR status in the database
         if !matches!(&pr_status.status, MergeStatus::Open) {
-            // Update merge status with the latest information from GitHub
+            // Update merge status with the latest information from the code host
             Merge::update_status(
                 &self.db.pool,
                 pr_merge.id,
@@ -155,14 +153,8 @@ impl PrMonitorService {
                     );
                 }
 
-                if let Some(publisher) = &self.publisher
-                    && let Err(err) = publisher.update_shared_task_by_id(workspace.task_id).await
-                {
-                    tracing::warn!(
-                        ?err,
-                        "Failed to propagate shared task update for {}",
-                        workspace.task_id
-                    );
+                if let Some(publisher) = &self.publisher {
+                    publisher.propagate_task_update(workspace.task_id).await;
                 }
 
                 // Execute auto-start triggers for dependent tasks
@@ -0,0 +1,429 @@
+//! Process tree bookkeeping for executions.
+//!
+//! Executions run in their own process group (a job object on Windows), but children
+//! such as file watchers started by dev servers may leave the group with `setsid` or by
+//! daemonizing. Stopping an execution therefore walks the whole process tree, and
+//! anything that survives is handed to the reaper. The reaper retries those processes
+//! periodically, reaps zombies of the executions it was told about, and keeps a report
+//! of what it did.
+//!
+//! A process is identified by its pid and start time, so a pid the OS has handed to an
+//! unrelated process since is never signalled.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{LazyLock, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use ts_rs::TS;
+use uuid::Uuid;
+
+static SHARED: LazyLock<ProcessReaper> = LazyLock::new(ProcessReaper::new);
+
+/// Sweeps that try to kill a lingering process before the reaper gives up on it
+const MAX_KILL_ATTEMPTS: u32 = 5;
+
+/// One row of the OS process table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub ppid: u32,
+    pub pgid: u32,
+    pub zombie: bool,
+    /// Start time as printed by `ps`; with the pid it identifies the process
+    pub started: String,
+}
+
+/// A process that outlived the stop of its execution
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct LingeringProcess {
+    pub pid: u32,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub started: String,
+    pub execution_process_id: Uuid,
+    #[ts(type = "Date")]
+    pub since: DateTime<Utc>,
+    /// Sweeps that have tried to kill it
+    pub attempts: u32,
+}
+
+/// What the reaper has done since the server started
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct ProcessReaperReport {
+    #[ts(type = "Date | null")]
+    pub last_sweep_at: Option<DateTime<Utc>>,
+    pub lingering: Vec<LingeringProcess>,
+    pub killed_last_sweep: u32,
+    pub reaped_last_sweep: u32,
+    pub killed_total: u64,
+    pub reaped_total: u64,
+}
+
+/// Parse `ps -A -o pid=,ppid=,pgid=,stat=,lstart=` output, skipping lines that don't
+/// parse
+pub fn parse_process_table(output: &str) -> Vec<ProcessInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            let ppid = fields.next()?.parse().ok()?;
+            let pgid = fields.next()?.parse().ok()?;
+            let zombie = fields.next()?.starts_with('Z');
+            let started = fields.collect::<Vec<_>>().join(" ");
+            if started.is_empty() {
+                return None;
+            }
+            Some(ProcessInfo {
+                pid,
+                ppid,
+                pgid,
+                zombie,
+                started,
+            })
+        })
+        .collect()
+}
+
+/// `root`, the members of process group `pgid` and all their descendants, including
+/// ones that moved to another group or session. Zombies are left out.
+pub fn process_tree(table: &[ProcessInfo], root: u32, pgid: u32) -> Vec<u32> {
+    let mut tree: HashSet<u32> = table
+        .iter()
+        .filter(|p| p.pid == root || p.pgid == pgid)
+        .map(|p| p.pid)
+        .collect();
+    tree.insert(root);
+    loop {
+        let children: Vec<u32> = table
+            .iter()
+            .filter(|p| tree.contains(&p.ppid) && !tree.contains(&p.pid))
+            .map(|p| p.pid)
+            .collect();
+        if children.is_empty() {
+            break;
+        }
+        tree.extend(children);
+    }
+
+    let zombies: HashSet<u32> = table.iter().filter(|p| p.zombie).map(|p| p.pid).collect();
+    let mut tree: Vec<u32> = tree.into_iter().filter(|pid| !zombies.contains(pid)).collect();
+    tree.sort_unstable();
+    tree
+}
+
+/// Current process table; empty if it can't be read
+#[cfg(unix)]
+pub async fn process_table() -> Vec<ProcessInfo> {
+    match tokio::process::Command::new("ps")
+        .args(["-A", "-o", "pid=,ppid=,pgid=,stat=,lstart="])
+        .output()
+        .await
+    {
+        Ok(output) => parse_process_table(&String::from_utf8_lossy(&output.stdout)),
+        Err(e) => {
+            tracing::warn!("Failed to read the process table: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Process trees are handled by `taskkill /T` and job objects on Windows
+#[cfg(not(unix))]
+pub async fn process_table() -> Vec<ProcessInfo> {
+    Vec::new()
+}
+
+/// Start time of `pid` as printed by `ps`, if it is running
+#[cfg(unix)]
+async fn start_time(pid: u32) -> Option<String> {
+    let output = tokio::process::Command::new("ps")
+        .args(["-o", "lstart=", "-p", &pid.to_string()])
+        .output()
+        .await
+        .ok()?;
+    let started = String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(started).filter(|s| !s.is_empty())
+}
+
+#[cfg(not(unix))]
+async fn start_time(_pid: u32) -> Option<String> {
+    None
+}
+
+pub struct ProcessReaper {
+    lingering: Mutex<HashMap<u32, LingeringProcess>>,
+    /// Pids of execution leaders the server spawned, by execution; the only processes
+    /// the reaper waits for
+    spawned: Mutex<HashMap<u32, Uuid>>,
+    /// Zombie children seen in the last sweep; reaped if still there in the next one
+    zombie_children: Mutex<HashSet<u32>>,
+    report: Mutex<ProcessReaperReport>,
+}
+
+impl Default for ProcessReaper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessReaper {
+    pub fn new() -> Self {
+        Self {
+            lingering: Mutex::new(HashMap::new()),
+            spawned: Mutex::new(HashMap::new()),
+            zombie_children: Mutex::new(HashSet::new()),
+            report: Mutex::new(ProcessReaperReport::default()),
+        }
+    }
+
+    pub fn shared() -> &'static ProcessReaper {
+        &SHARED
+    }
+
+    /// Record the leader of an execution the server spawned, so it is reaped if its
+    /// handle is dropped without waiting for it
+    pub fn register_spawned(&self, execution_process_id: Uuid, pid: u32) {
+        self.spawned
+            .lock()
+            .unwrap()
+            .insert(pid, execution_process_id);
+    }
+
+    /// Forget an execution's leader once its exit status has been collected
+    pub fn forget_spawned(&self, execution_process_id: Uuid) {
+        self.spawned
+            .lock()
+            .unwrap()
+            .retain(|_, id| *id != execution_process_id);
+    }
+
+    /// Hand over processes of an execution that survived being killed
+    pub fn track(&self, execution_process_id: Uuid, processes: &[ProcessInfo]) {
+        let mut lingering = self.lingering.lock().unwrap();
+        for process in processes {
+            // An entry for the same pid but another start time is for a process that
+            // has since exited
+            if lingering
+                .get(&process.pid)
+                .is_some_and(|tracked| tracked.started == process.started)
+            {
+                continue;
+            }
+            lingering.insert(
+                process.pid,
+                LingeringProcess {
+                    pid: process.pid,
+                    started: process.started.clone(),
+                    execution_process_id,
+                    since: Utc::now(),
+                    attempts: 0,
+                },
+            );
+        }
+    }
+
+    pub fn report(&self) -> ProcessReaperReport {
+        let mut report = self.report.lock().unwrap().clone();
+        report.lingering = self.lingering.lock().unwrap().values().cloned().collect();
+        report.lingering.sort_by_key(|p| p.since);
+        report
+    }
+
+    /// Lingering processes to kill in this sweep. Entries whose process exited, or
+    /// whose pid now belongs to another process, are dropped, as are those that have
+    /// used up their attempts.
+    fn due_for_kill(&self, table: &[ProcessInfo]) -> Vec<(u32, String)> {
+        let alive: HashMap<u32, &ProcessInfo> = table.iter().map(|p| (p.pid, p)).collect();
+        let mut lingering = self.lingering.lock().unwrap();
+        lingering.retain(|pid, process| {
+            let running = alive
+                .get(pid)
+                .is_some_and(|p| !p.zombie && p.started == process.started);
+            if running && process.attempts >= MAX_KILL_ATTEMPTS {
+                tracing::error!(
+                    "Giving up on process {} of execution {} after {} kill attempts",
+                    pid,
+                    process.execution_process_id,
+                    process.attempts
+                );
+                return false;
+            }
+            running
+        });
+        lingering
+            .values_mut()
+            .map(|process| {
+                process.attempts += 1;
+                (process.pid, process.started.clone())
+            })
+            .collect()
+    }
+
+    /// Kill lingering processes that are still alive and reap zombies of spawned
+    /// executions that nothing waited for. `owned` are the pids of live executions,
+    /// whose exit status belongs to their exit monitor.
+    pub async fn sweep(&self, owned: &HashSet<u32>) -> ProcessReaperReport {
+        let table = process_table().await;
+        let own_pid = std::process::id();
+        let mut killed = 0;
+        let mut reaped = 0;
+
+        for (pid, started) in self.due_for_kill(&table) {
+            // The table may be stale by now; only signal the process that was tracked
+            if start_time(pid).await.as_deref() == Some(started.as_str()) && kill_pid(pid) {
+                killed += 1;
+            }
+        }
+
+        let zombies: HashSet<u32> = {
+            let mut spawned = self.spawned.lock().unwrap();
+            spawned.retain(|pid, _| table.iter().any(|p| p.pid == *pid));
+            table
+                .iter()
+                .filter(|p| p.zombie && p.ppid == own_pid && !owned.contains(&p.pid))
+                .filter(|p| spawned.contains_key(&p.pid))
+                .map(|p| p.pid)
+                .collect()
+        };
+        {
+            let mut previous = self.zombie_children.lock().unwrap();
+            for pid in zombies.intersection(&previous) {
+                if reap_pid(*pid) {
+                    self.spawned.lock().unwrap().remove(pid);
+                    reaped += 1;
+                }
+            }
+            *previous = zombies;
+        }
+
+        if killed > 0 || reaped > 0 {
+            tracing::warn!(
+                "Process reaper killed {} lingering process(es) and reaped {} zombie(s)",
+                killed,
+                reaped
+            );
+        }
+
+        {
+            let mut report = self.report.lock().unwrap();
+            report.last_sweep_at = Some(Utc::now());
+            report.killed_last_sweep = killed;
+            report.reaped_last_sweep = reaped;
+            report.killed_total += u64::from(killed);
+            report.reaped_total += u64::from(reaped);
+        }
+        self.report()
+    }
+}
+
+#[cfg(unix)]
+fn kill_pid(pid: u32) -> bool {
+    use nix::{
+        sys::signal::{Signal, kill},
+        unistd::Pid,
+    };
+    kill(Pid::from_raw(pid as i32), Signal::SIGKILL).is_ok()
+}
+
+#[cfg(not(unix))]
+fn kill_pid(_pid: u32) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn reap_pid(pid: u32) -> bool {
+    use nix::{
+        sys::wait::{WaitPidFlag, WaitStatus, waitpid},
+        unistd::Pid,
+    };
+    matches!(
+        waitpid(Pid::from_raw(pid as i32), Some(WaitPidFlag::WNOHANG)),
+        Ok(status) if status != WaitStatus::StillAlive
+    )
+}
+
+#[cfg(not(unix))]
+fn reap_pid(_pid: u32) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PS_OUTPUT: &str = "    1     0     1 Ss   Mon Oct 12 08:00:00 2026
+  100     1   100 S    Fri Oct 16 09:00:00 2026
+  101   100   100 S+   Fri Oct 16 09:00:01 2026
+  102   101   102 Ss   Fri Oct 16 09:00:02 2026
+  103   102   102 S    Fri Oct 16 09:00:02 2026
+  104   100   100 Z    Fri Oct 16 09:00:03 2026
+  200     1   200 S    Thu Oct 15 17:30:00 2026
+  201   200   100 S    Fri Oct 16 09:00:04 2026
+garbage line
+";
+
+    #[test]
+    fn parses_the_process_table() {
+        let table = parse_process_table(PS_OUTPUT);
+        assert_eq!(table.len(), 8);
+        assert_eq!(
+            table[4],
+            ProcessInfo {
+                pid: 103,
+                ppid: 102,
+                pgid: 102,
+                zombie: false,
+                started: "Fri Oct 16 09:00:02 2026".to_string(),
+            }
+        );
+        assert!(table[5].zombie);
+    }
+
+    #[test]
+    fn tree_follows_children_that_left_the_group() {
+        let table = parse_process_table(PS_OUTPUT);
+        // 102 called setsid and 103 is its child; 201 joined the group from elsewhere;
+        // the zombie 104 and unrelated 200 are left alone
+        assert_eq!(process_tree(&table, 100, 100), vec![100, 101, 102, 103, 201]);
+    }
+
+    #[test]
+    fn tracked_processes_show_in_the_report() {
+        let table = parse_process_table(PS_OUTPUT);
+        let reaper = ProcessReaper::new();
+        let exec_id = Uuid::new_v4();
+        reaper.track(exec_id, &table[1..3]);
+        reaper.track(exec_id, &table[1..2]);
+
+        let report = reaper.report();
+        assert_eq!(report.lingering.len(), 2);
+        assert!(report.lingering.iter().all(|p| p.execution_process_id == exec_id));
+    }
+
+    #[test]
+    fn reused_pids_and_exhausted_entries_are_not_killed() {
+        let mut table = parse_process_table(PS_OUTPUT);
+        let reaper = ProcessReaper::new();
+        reaper.track(Uuid::new_v4(), &table[1..3]);
+
+        // 101 exited and its pid went to an unrelated process
+        table[2].started = "Fri Oct 16 10:15:00 2026".to_string();
+        assert_eq!(
+            reaper.due_for_kill(&table),
+            vec![(100, "Fri Oct 16 09:00:00 2026".to_string())]
+        );
+
+        for _ in 1..MAX_KILL_ATTEMPTS {
+            assert_eq!(reaper.due_for_kill(&table).len(), 1);
+        }
+        assert!(reaper.due_for_kill(&table).is_empty());
+        assert!(reaper.report().lingering.is_empty());
+    }
+}
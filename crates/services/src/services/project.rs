@@ -14,7 +14,7 @@ use db::models::{
 };
 use std::collections::HashMap;
 use ignore::WalkBuilder;
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use thiserror::Error;
 use utils::api::projects::RemoteProject;
 use uuid::Uuid;
@@ -52,6 +52,10 @@ pub enum ProjectServiceError {
     GitError(String),
     #[error("Remote client error: {0}")]
     RemoteClient(String),
+    #[error("Workflow template not found")]
+    WorkflowTemplateNotFound,
+    #[error("Invalid workflow template: {0}")]
+    InvalidWorkflowTemplate(String),
 }
 
 pub type Result<T> = std::result::Result<T, ProjectServiceError>;
@@ -69,6 +73,115 @@ impl From<RepoError> for ProjectServiceError {
     }
 }
 
+/// A template board's columns and transitions, ready to be cloned for a new project
+struct WorkflowTemplate {
+    columns: Vec<KanbanColumn>,
+    transitions: Vec<StateTransition>,
+}
+
+impl WorkflowTemplate {
+    async fn load(pool: &PgPool, template_board_id: Uuid) -> Result<Self> {
+        let board = Board::find_by_id(pool, template_board_id)
+            .await?
+            .ok_or(ProjectServiceError::WorkflowTemplateNotFound)?;
+        if !board.is_template {
+            return Err(ProjectServiceError::InvalidWorkflowTemplate(
+                "not a template board".to_string(),
+            ));
+        }
+        let template_group_id = board.template_group_id.as_deref().ok_or_else(|| {
+            ProjectServiceError::InvalidWorkflowTemplate("template has no group ID".to_string())
+        })?;
+
+        let template = Self {
+            columns: KanbanColumn::find_by_template_group(pool, template_group_id).await?,
+            transitions: StateTransition::find_by_template_group(pool, template_group_id).await?,
+        };
+        template.validate()?;
+        Ok(template)
+    }
+
+    /// Every transition has to connect columns of the template
+    fn validate(&self) -> Result<()> {
+        let column_ids: HashSet<Uuid> = self.columns.iter().map(|column| column.id).collect();
+        for transition in &self.transitions {
+            let referenced = [transition.from_column_id, transition.to_column_id]
+                .into_iter()
+                .chain(transition.else_column_id)
+                .chain(transition.escalation_column_id);
+            for column_id in referenced {
+                if !column_ids.contains(&column_id) {
+                    return Err(ProjectServiceError::InvalidWorkflowTemplate(format!(
+                        "transition '{}' references a column outside the template",
+                        transition.name.as_deref().unwrap_or("unnamed")
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Create a board for `project_name` with the template's columns, column settings
+    /// and transitions. Columns keep the template's agents, which are shared.
+    async fn clone_board(&self, conn: &mut PgConnection, project_name: &str) -> Result<Uuid> {
+        let board = Board::create(
+            &mut *conn,
+            &CreateBoard {
+                name: format!("{} Board", project_name),
+                description: Some("Created from a workflow template".to_string()),
+            },
+        )
+        .await?;
+
+        let mut column_id_map: HashMap<Uuid, Uuid> = HashMap::new();
+        for tmpl_col in &self.columns {
+            let column = KanbanColumn::create_for_board(
+                &mut *conn,
+                board.id,
+                &CreateKanbanColumn {
+                    name: tmpl_col.name.clone(),
+                    slug: tmpl_col.slug.clone(),
+                    position: tmpl_col.position,
+                    color: tmpl_col.color.clone(),
+                    is_initial: Some(tmpl_col.is_initial),
+                    is_terminal: Some(tmpl_col.is_terminal),
+                    starts_workflow: Some(tmpl_col.starts_workflow),
+                    status: Some(tmpl_col.status.clone()),
+                    agent_id: tmpl_col.agent_id,
+                    deliverable: tmpl_col.deliverable.clone(),
+                    question: tmpl_col.question.clone(),
+                    answer_options: tmpl_col.answer_options.clone(),
+                },
+            )
+            .await?;
+            KanbanColumn::copy_settings(&mut *conn, tmpl_col.id, column.id).await?;
+            column_id_map.insert(tmpl_col.id, column.id);
+        }
+
+        // `validate` made sure every referenced column was cloned
+        let remap = |id: Uuid| column_id_map[&id];
+        for tmpl_trans in &self.transitions {
+            StateTransition::create_for_board(
+                &mut *conn,
+                board.id,
+                &CreateStateTransition {
+                    from_column_id: remap(tmpl_trans.from_column_id),
+                    to_column_id: remap(tmpl_trans.to_column_id),
+                    else_column_id: tmpl_trans.else_column_id.map(remap),
+                    escalation_column_id: tmpl_trans.escalation_column_id.map(remap),
+                    name: tmpl_trans.name.clone(),
+                    requires_confirmation: Some(tmpl_trans.requires_confirmation),
+                    condition_value: tmpl_trans.condition_value.clone(),
+                    max_failures: tmpl_trans.max_failures,
+                },
+            )
+            .await?;
+        }
+
+        Ok(board.id)
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct ProjectService;
 
@@ -77,9 +190,6 @@ impl ProjectService {
         Self
     }
 
-    /// Setup Board template group ID (well-known UUID from migration)
-    const SETUP_BOARD_TEMPLATE_GROUP_ID: &'static str = "33333333-3333-3333-3333-333333333333";
-
     pub async fn create_project(
         &self,
         pool: &PgPool,
@@ -111,23 +221,43 @@ impl ProjectService {
             });
         }
 
+        let template = match payload.workflow_template_id {
+            Some(template_board_id) => Some(WorkflowTemplate::load(pool, template_board_id).await?),
+            None => None,
+        };
+
         let id = Uuid::new_v4();
 
-        let project = Project::create(pool, &payload, id)
+        // The project, its repos and its board from the template land together or not at all
+        let mut tx = pool.begin().await?;
+
+        let project = Project::create(&mut *tx, &payload, id)
             .await
             .map_err(|e| ProjectServiceError::Project(ProjectError::CreateFailed(e.to_string())))?;
 
         let mut created_repo: Option<Repo> = None;
         for repo in &normalized_repos {
             let repo_entity =
-                Repo::find_or_create(pool, Path::new(&repo.git_repo_path), &repo.display_name)
+                Repo::find_or_create(&mut *tx, Path::new(&repo.git_repo_path), &repo.display_name)
                     .await?;
-            ProjectRepo::create(pool, project.id, repo_entity.id).await?;
+            ProjectRepo::create(&mut *tx, project.id, repo_entity.id).await?;
             if created_repo.is_none() {
                 created_repo = Some(repo_entity);
             }
         }
 
+        // A template gets its own board; otherwise the given board is shared, not cloned
+        let board_id = match &template {
+            Some(template) => {
+                let board_id = template.clone_board(&mut tx, &payload.name).await?;
+                Project::update_board_id(&mut *tx, project.id, board_id).await?;
+                Some(board_id)
+            }
+            None => payload.board_id,
+        };
+
+        tx.commit().await?;
+
         // Determine the default_agent_working_dir for single-repo projects
         let default_agent_working_dir = if normalized_repos.len() == 1 {
             created_repo.as_ref().map(|r| r.name.clone())
@@ -135,9 +265,6 @@ impl ProjectService {
             None
         };
 
-        // Use the provided board_id directly — boards are shared, not cloned per project
-        let board_id = payload.board_id;
-
         // Update project with board_id and default_agent_working_dir
         if board_id.is_some() || default_agent_working_dir.is_some() {
             Project::update(
@@ -162,95 +289,6 @@ impl ProjectService {
         Ok(project)
     }
 
-    /// Apply the Setup Board template to create a new board for a project
-    async fn apply_setup_board_template(
-        &self,
-        pool: &PgPool,
-        project_name: &str,
-    ) -> Result<Uuid> {
-        let template_group_id = Self::SETUP_BOARD_TEMPLATE_GROUP_ID;
-
-        // Get template entities
-        let template_columns = KanbanColumn::find_by_template_group(pool, template_group_id).await?;
-        let template_transitions =
-            StateTransition::find_by_template_group(pool, template_group_id).await?;
-
-        // Create the project's board
-        let board = Board::create(
-            pool,
-            &CreateBoard {
-                name: format!("{} Board", project_name),
-                description: Some("Auto-generated from Setup Board template".to_string()),
-            },
-        )
-        .await?;
-
-        // Reuse template agents directly (no cloning)
-        // Columns reference the shared template agents by their original IDs
-
-        // Create columns (build old_id -> new_id mapping)
-        let mut column_id_map: HashMap<Uuid, Uuid> = HashMap::new();
-        for tmpl_col in &template_columns {
-            // Use the template agent_id directly — agents are shared, not cloned
-            let new_agent_id = tmpl_col.agent_id;
-
-            let column = KanbanColumn::create_for_board(
-                pool,
-                board.id,
-                &CreateKanbanColumn {
-                    name: tmpl_col.name.clone(),
-                    slug: tmpl_col.slug.clone(),
-                    position: tmpl_col.position,
-                    color: tmpl_col.color.clone(),
-                    is_initial: Some(tmpl_col.is_initial),
-                    is_terminal: Some(tmpl_col.is_terminal),
-                    starts_workflow: Some(tmpl_col.starts_workflow),
-                    status: Some(tmpl_col.status.clone()),
-                    agent_id: new_agent_id,
-                    deliverable: tmpl_col.deliverable.clone(),
-                    question: tmpl_col.question.clone(),
-                    answer_options: tmpl_col.answer_options.clone(),
-                },
-            )
-            .await?;
-            column_id_map.insert(tmpl_col.id, column.id);
-        }
-
-        // Create transitions with remapped column IDs
-        for tmpl_trans in &template_transitions {
-            let Some(new_from) = column_id_map.get(&tmpl_trans.from_column_id).copied() else {
-                continue;
-            };
-            let Some(new_to) = column_id_map.get(&tmpl_trans.to_column_id).copied() else {
-                continue;
-            };
-            let new_else = tmpl_trans
-                .else_column_id
-                .and_then(|id| column_id_map.get(&id).copied());
-            let new_escalation = tmpl_trans
-                .escalation_column_id
-                .and_then(|id| column_id_map.get(&id).copied());
-
-            StateTransition::create_for_board(
-                pool,
-                board.id,
-                &CreateStateTransition {
-                    from_column_id: new_from,
-                    to_column_id: new_to,
-                    else_column_id: new_else,
-                    escalation_column_id: new_escalation,
-                    name: tmpl_trans.name.clone(),
-                    requires_confirmation: Some(tmpl_trans.requires_confirmation),
-                    condition_value: tmpl_trans.condition_value.clone(),
-                    max_failures: tmpl_trans.max_failures,
-                },
-            )
-            .await?;
-        }
-
-        Ok(board.id)
-    }
-
     pub async fn update_project(
         &self,
         pool: &PgPool,
@@ -0,0 +1,201 @@
+//! Translations of the instruction text the server writes into agent prompts.
+//!
+//! Users write task descriptions, system prompts and deliverables in their own language,
+//! but the boilerplate around them (decision instructions, reviewer feedback and task
+//! comment headings, read-only, dry-run and plan-only notices) comes from the server.
+//! An agent's prompt settings pick the language of that text; file names, tool names
+//! and decision file keys stay as they are, since the server reads them back.
+
+pub use executors::actions::coding_agent_initial::PromptLanguage;
+
+/// Text of the decision instructions for a column's question
+pub struct DecisionText {
+    pub heading: &'static str,
+    /// Where and how to write the answer
+    pub how_to_answer: &'static str,
+    pub valid_answers: &'static str,
+    pub example: &'static str,
+    /// Introduces the expression conditions routing evaluates
+    pub routing_conditions: &'static str,
+}
+
+pub fn decision_text(language: PromptLanguage) -> &'static DecisionText {
+    match language {
+        PromptLanguage::English => &DecisionText {
+            heading: "Question",
+            how_to_answer: "After completing your work, answer this question by writing to \
+                `.vibe/decision.json`.\nIf you can't write to the workspace, write it to \
+                `$VK_DECISIONS_DIR/decision.json` instead. Include the question text for \
+                readability. If the `set_decision` tool is available, you can call it with \
+                `answer` instead of writing the file.",
+            valid_answers: "Valid answers",
+            example: "Example",
+            routing_conditions: "Routing also evaluates these conditions against \
+                `.vibe/decision.json`, so include the keys they reference:",
+        },
+        PromptLanguage::German => &DecisionText {
+            heading: "Frage",
+            how_to_answer: "Beantworte diese Frage nach Abschluss deiner Arbeit, indem du in \
+                `.vibe/decision.json` schreibst.\nFalls du nicht in den Workspace schreiben \
+                kannst, schreibe stattdessen nach `$VK_DECISIONS_DIR/decision.json`. Gib zur \
+                besseren Lesbarkeit den Fragetext mit an. Falls das Tool `set_decision` \
+                verfügbar ist, kannst du es mit `answer` aufrufen, anstatt die Datei zu \
+                schreiben.",
+            valid_answers: "Gültige Antworten",
+            example: "Beispiel",
+            routing_conditions: "Das Routing wertet außerdem diese Bedingungen gegen \
+                `.vibe/decision.json` aus, gib also die Schlüssel an, auf die sie sich beziehen:",
+        },
+        PromptLanguage::Japanese => &DecisionText {
+            heading: "質問",
+            how_to_answer: "作業が完了したら、`.vibe/decision.json` に書き込んでこの質問に\
+                回答してください。\nワークスペースに書き込めない場合は、代わりに \
+                `$VK_DECISIONS_DIR/decision.json` に書き込んでください。読みやすさのため\
+                質問文も含めてください。`set_decision` ツールが利用できる場合は、ファイルを\
+                書く代わりに `answer` を指定して呼び出すこともできます。",
+            valid_answers: "有効な回答",
+            example: "例",
+            routing_conditions: "ルーティングでは `.vibe/decision.json` に対して次の条件も\
+                評価されるため、条件が参照するキーも含めてください:",
+        },
+    }
+}
+
+/// Heading of a reviewer feedback section; rounds after the first are numbered
+pub fn feedback_heading(language: PromptLanguage, round: Option<i32>) -> String {
+    let heading = match language {
+        PromptLanguage::English => "Feedback from Previous Review",
+        PromptLanguage::German => "Feedback aus dem vorherigen Review",
+        PromptLanguage::Japanese => "前回のレビューからのフィードバック",
+    };
+    match round {
+        Some(round) if round > 1 => match language {
+            PromptLanguage::English => format!("{heading} (round {round})"),
+            PromptLanguage::German => format!("{heading} (Runde {round})"),
+            PromptLanguage::Japanese => format!("{heading}(第{round}ラウンド)"),
+        },
+        _ => heading.to_string(),
+    }
+}
+
+/// Closes a reviewer feedback section
+pub fn feedback_request(language: PromptLanguage) -> &'static str {
+    match language {
+        PromptLanguage::English => "Please address this feedback before proceeding.",
+        PromptLanguage::German => "Bitte berücksichtige dieses Feedback, bevor du fortfährst.",
+        PromptLanguage::Japanese => "先に進む前に、このフィードバックに対応してください。",
+    }
+}
+
+/// Heading of the section listing people's comments on the task
+pub fn comments_heading(language: PromptLanguage) -> &'static str {
+    match language {
+        PromptLanguage::English => "Comments on this Task",
+        PromptLanguage::German => "Kommentare zu dieser Aufgabe",
+        PromptLanguage::Japanese => "このタスクへのコメント",
+    }
+}
+
+/// Appended to the deliverable of agents working in read-only analysis columns
+pub fn read_only_column_notice(language: PromptLanguage) -> &'static str {
+    match language {
+        PromptLanguage::English => {
+            "READ-ONLY COLUMN: Do not modify, create, or delete project files and do not \
+             commit. Only analyze the code and report your findings in .vibe/ (e.g. \
+             .vibe/summary.md and .vibe/decision.json). Any other changes will be discarded."
+        }
+        PromptLanguage::German => {
+            "NUR-LESE-SPALTE: Ändere, erstelle oder lösche keine Projektdateien und committe \
+             nicht. Analysiere nur den Code und berichte deine Ergebnisse in .vibe/ (z. B. \
+             .vibe/summary.md und .vibe/decision.json). Alle anderen Änderungen werden \
+             verworfen."
+        }
+        PromptLanguage::Japanese => {
+            "読み取り専用カラム: プロジェクトのファイルを変更・作成・削除せず、コミットも\
+             しないでください。コードを分析し、結果を .vibe/ (例: .vibe/summary.md と \
+             .vibe/decision.json) に報告するだけにしてください。それ以外の変更は破棄されます。"
+        }
+    }
+}
+
+/// Appended to the deliverable of agents working in dry-run columns
+pub fn dry_run_column_notice(language: PromptLanguage) -> &'static str {
+    match language {
+        PromptLanguage::English => {
+            "DRY-RUN COLUMN: Only propose changes. Edit files as needed, but do not commit, \
+             push or create branches. When you finish, your changes are saved as a patch for a \
+             person to review and apply, and the workspace is reset."
+        }
+        PromptLanguage::German => {
+            "DRY-RUN-SPALTE: Schlage Änderungen nur vor. Bearbeite Dateien nach Bedarf, aber \
+             committe und pushe nicht und erstelle keine Branches. Wenn du fertig bist, werden \
+             deine Änderungen als Patch gespeichert, den eine Person prüft und anwendet, und \
+             der Workspace wird zurückgesetzt."
+        }
+        PromptLanguage::Japanese => {
+            "ドライランカラム: 変更の提案のみを行ってください。必要に応じてファイルを編集して\
+             構いませんが、コミット・プッシュ・ブランチの作成は行わないでください。終了すると、\
+             変更は担当者がレビューして適用するためのパッチとして保存され、ワークスペースは\
+             リセットされます。"
+        }
+    }
+}
+
+/// Appended to the deliverable of agents doing a plan-only run
+pub fn plan_only_notice(language: PromptLanguage) -> &'static str {
+    match language {
+        PromptLanguage::English => {
+            "PLAN-ONLY RUN: Do not modify, create, or delete project files and do not commit. \
+             Write your implementation plan to .vibe/plan.md (or plan.md in $VK_DECISIONS_DIR) \
+             and your decision to .vibe/decision.json. A reviewer approves the plan before an \
+             implementation run gets write access; any other changes will be discarded."
+        }
+        PromptLanguage::German => {
+            "NUR-PLANUNGS-LAUF: Ändere, erstelle oder lösche keine Projektdateien und committe \
+             nicht. Schreibe deinen Umsetzungsplan nach .vibe/plan.md (oder plan.md in \
+             $VK_DECISIONS_DIR) und deine Entscheidung nach .vibe/decision.json. Ein Reviewer \
+             genehmigt den Plan, bevor ein Umsetzungslauf Schreibzugriff erhält; alle anderen \
+             Änderungen werden verworfen."
+        }
+        PromptLanguage::Japanese => {
+            "計画のみの実行: プロジェクトのファイルを変更・作成・削除せず、コミットもしないで\
+             ください。実装計画を .vibe/plan.md ($VK_DECISIONS_DIR 内の plan.md でも可) に、\
+             判断を .vibe/decision.json に書き込んでください。実装の実行に書き込み権限が\
+             与えられる前にレビュアーが計画を承認します。それ以外の変更は破棄されます。"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_and_tool_names_are_kept_in_every_language() {
+        for language in PromptLanguage::ALL {
+            let text = decision_text(language);
+            assert!(text.how_to_answer.contains("`.vibe/decision.json`"));
+            assert!(text.how_to_answer.contains("`$VK_DECISIONS_DIR/decision.json`"));
+            assert!(text.how_to_answer.contains("`set_decision`"));
+            assert!(text.routing_conditions.contains("`.vibe/decision.json`"));
+            assert!(read_only_column_notice(language).contains(".vibe/summary.md"));
+            assert!(plan_only_notice(language).contains(".vibe/plan.md"));
+        }
+    }
+
+    #[test]
+    fn feedback_rounds_are_numbered_after_the_first() {
+        assert_eq!(
+            feedback_heading(PromptLanguage::English, Some(1)),
+            "Feedback from Previous Review"
+        );
+        assert_eq!(
+            feedback_heading(PromptLanguage::German, Some(2)),
+            "Feedback aus dem vorherigen Review (Runde 2)"
+        );
+        assert_eq!(
+            feedback_heading(PromptLanguage::Japanese, Some(3)),
+            "前回のレビューからのフィードバック(第3ラウンド)"
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Readiness probes for orchestration environments.
+//!
+//! `/api/health/live` only says the process is serving requests; `/api/health/ready`
+//! runs the checks below and reports each component, so a load balancer or Kubernetes
+//! can hold traffic back until the server can actually run agents.
+
+use std::{collections::BTreeSet, path::Path, str::FromStr};
+
+use db::{DBService, models::agent::Agent};
+use executors::{
+    executors::{BaseCodingAgent, StandardCodingAgentExecutor},
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
+use serde::Serialize;
+use ts_rs::TS;
+
+use super::worktree_manager::WorktreeManager;
+
+/// Outcome of one readiness check
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ComponentStatus {
+    pub name: String,
+    pub ok: bool,
+    /// What failed, or what was checked when the component is fine
+    pub detail: Option<String>,
+}
+
+impl ComponentStatus {
+    fn ok(name: &str, detail: Option<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            ok: true,
+            detail,
+        }
+    }
+
+    fn failed(name: &str, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            ok: false,
+            detail: Some(detail),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ReadinessReport {
+    /// Whether every component is ok
+    pub ready: bool,
+    pub components: Vec<ComponentStatus>,
+}
+
+impl ReadinessReport {
+    pub fn new(components: Vec<ComponentStatus>) -> Self {
+        Self {
+            ready: components.iter().all(|component| component.ok),
+            components,
+        }
+    }
+}
+
+/// Run every readiness check. `default_executor` is the executor of the user's config;
+/// the executors agents are configured with are checked as well.
+pub async fn check(db: &DBService, default_executor: BaseCodingAgent) -> ReadinessReport {
+    let database = database(db).await;
+    let migrations = if database.ok {
+        migrations(db).await
+    } else {
+        ComponentStatus::failed("migrations", "database unreachable".to_string())
+    };
+
+    let mut used_executors = BTreeSet::from([default_executor.to_string()]);
+    if database.ok {
+        match Agent::find_all(&db.pool).await {
+            Ok(agents) => used_executors.extend(agents.into_iter().map(|agent| agent.executor)),
+            Err(e) => tracing::warn!("Failed to load agents for readiness check: {}", e),
+        }
+    }
+    let executors = executors(&used_executors.into_iter().collect::<Vec<_>>());
+    let worktrees = worktree_dir(&WorktreeManager::get_worktree_base_dir()).await;
+
+    ReadinessReport::new(vec![database, migrations, executors, worktrees])
+}
+
+async fn database(db: &DBService) -> ComponentStatus {
+    match sqlx::query_scalar::<_, i32>("SELECT 1")
+        .fetch_one(&db.pool)
+        .await
+    {
+        Ok(_) => ComponentStatus::ok("database", None),
+        Err(e) => ComponentStatus::failed("database", e.to_string()),
+    }
+}
+
+async fn migrations(db: &DBService) -> ComponentStatus {
+    match db.pending_migrations().await {
+        Ok(pending) if pending.is_empty() => ComponentStatus::ok("migrations", None),
+        Ok(pending) => ComponentStatus::failed(
+            "migrations",
+            format!(
+                "{} pending: {}",
+                pending.len(),
+                pending
+                    .iter()
+                    .map(|version| version.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ),
+        Err(e) => ComponentStatus::failed("migrations", e.to_string()),
+    }
+}
+
+/// Whether the executors named (as stored on agents, e.g. `CLAUDE_CODE`) are installed
+fn executors(names: &[String]) -> ComponentStatus {
+    let missing: Vec<&str> = names
+        .iter()
+        .filter(|name| {
+            let Ok(executor) = BaseCodingAgent::from_str(name) else {
+                return true;
+            };
+            !ExecutorConfigs::get_cached()
+                .get_coding_agent(&ExecutorProfileId::new(executor))
+                .is_some_and(|agent| agent.get_availability_info().is_available())
+        })
+        .map(String::as_str)
+        .collect();
+
+    if missing.is_empty() {
+        ComponentStatus::ok("executors", Some(names.join(", ")))
+    } else {
+        ComponentStatus::failed("executors", format!("not found: {}", missing.join(", ")))
+    }
+}
+
+/// Whether worktrees can be created under `dir`
+async fn worktree_dir(dir: &Path) -> ComponentStatus {
+    let probe = dir.join(format!(".readiness-{}", uuid::Uuid::new_v4()));
+    let result = async {
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(&probe, b"").await?;
+        tokio::fs::remove_file(&probe).await
+    }
+    .await;
+
+    match result {
+        Ok(()) => ComponentStatus::ok("worktree_dir", Some(dir.display().to_string())),
+        Err(e) => ComponentStatus::failed("worktree_dir", format!("{}: {}", dir.display(), e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn worktree_dir_must_be_writable() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("worktrees");
+        let status = worktree_dir(&dir).await;
+        assert!(status.ok, "{:?}", status.detail);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+
+        // A file where the directory should be
+        let blocked = root.path().join("blocked");
+        std::fs::write(&blocked, "").unwrap();
+        assert!(!worktree_dir(&blocked.join("worktrees")).await.ok);
+    }
+
+    #[test]
+    fn report_is_ready_only_when_every_component_is_ok() {
+        let ok = ComponentStatus::ok("database", None);
+        assert!(ReadinessReport::new(vec![ok.clone()]).ready);
+
+        let failed = ComponentStatus::failed("migrations", "1 pending: 42".to_string());
+        let report = ReadinessReport::new(vec![ok, failed]);
+        assert!(!report.ready);
+        assert_eq!(report.components.len(), 2);
+    }
+
+    #[test]
+    fn unknown_executor_names_are_reported_missing() {
+        let status = executors(&["NOT_AN_EXECUTOR".to_string()]);
+        assert!(!status.ok);
+        assert_eq!(status.detail.as_deref(), Some("not found: NOT_AN_EXECUTOR"));
+    }
+}
@@ -5,8 +5,8 @@ use std::time::Duration;
 use backon::{ExponentialBuilder, Retryable};
 use chrono::Duration as ChronoDuration;
 use super::remote_types::{
-    AssignSharedTaskRequest, CheckTasksRequest, CreateSharedTaskRequest, SharedTaskResponse,
-    UpdateSharedTaskRequest,
+    AssignSharedTaskRequest, CheckTasksRequest, CreateSharedTaskRequest, FetchTasksRequest,
+    SharedTask, SharedTaskResponse, UpdateSharedTaskRequest,
 };
 use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
@@ -16,6 +16,7 @@ use tracing::warn;
 use url::Url;
 use utils::{
     api::{
+        notifications::{ListNotificationsResponse, MarkNotificationsReadRequest},
         oauth::{
             HandoffInitRequest, HandoffInitResponse, HandoffRedeemRequest, HandoffRedeemResponse,
             ProfileResponse, TokenRefreshRequest, TokenRefreshResponse,
@@ -27,7 +28,10 @@ use utils::{
             ListOrganizationsResponse, Organization, RevokeInvitationRequest,
             UpdateMemberRoleRequest, UpdateMemberRoleResponse, UpdateOrganizationRequest,
         },
-        projects::{ListProjectsResponse, RemoteProject},
+        projects::{
+            GrantProjectAccessRequest, ListProjectsResponse, ProjectAccessGrant,
+            ProjectAccessResponse, RemoteProject, TransferProjectOwnershipRequest,
+        },
     },
     jwt::extract_expiration,
 };
@@ -545,6 +549,76 @@ impl RemoteClient {
         .await
     }
 
+    /// Gets the owner and access grants of a project.
+    pub async fn get_project_access(
+        &self,
+        project_id: Uuid,
+    ) -> Result<ProjectAccessResponse, RemoteClientError> {
+        self.get_authed(&format!("/v1/projects/{project_id}/access"))
+            .await
+    }
+
+    /// Grants a member access to a project.
+    pub async fn grant_project_access(
+        &self,
+        project_id: Uuid,
+        request: &GrantProjectAccessRequest,
+    ) -> Result<ProjectAccessGrant, RemoteClientError> {
+        self.post_authed(&format!("/v1/projects/{project_id}/access"), Some(request))
+            .await
+    }
+
+    /// Revokes a member's access grant on a project.
+    pub async fn revoke_project_access(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), RemoteClientError> {
+        self.delete_authed(&format!("/v1/projects/{project_id}/access/{user_id}"))
+            .await
+    }
+
+    /// Transfers project ownership to another member.
+    pub async fn transfer_project_ownership(
+        &self,
+        project_id: Uuid,
+        request: &TransferProjectOwnershipRequest,
+    ) -> Result<ProjectAccessResponse, RemoteClientError> {
+        self.post_authed(
+            &format!("/v1/projects/{project_id}/transfer-ownership"),
+            Some(request),
+        )
+        .await
+    }
+
+    /// Lists the authenticated user's notifications, newest first.
+    pub async fn list_notifications(
+        &self,
+        unread_only: bool,
+        limit: Option<i64>,
+    ) -> Result<ListNotificationsResponse, RemoteClientError> {
+        let mut path = format!("/v1/notifications?unread_only={unread_only}");
+        if let Some(limit) = limit {
+            path.push_str(&format!("&limit={limit}"));
+        }
+        self.get_authed(&path).await
+    }
+
+    /// Marks notifications as read; all unread ones when `request.ids` is `None`.
+    pub async fn mark_notifications_read(
+        &self,
+        request: &MarkNotificationsReadRequest,
+    ) -> Result<(), RemoteClientError> {
+        self.send(
+            reqwest::Method::POST,
+            "/v1/notifications/read",
+            true,
+            Some(request),
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Creates a shared task.
     pub async fn create_shared_task(
         &self,
@@ -596,6 +670,16 @@ impl RemoteClient {
         let request = CheckTasksRequest { task_ids };
         self.post_authed("/v1/tasks/check", Some(&request)).await
     }
+
+    /// Fetches the current remote copies of shared tasks; deleted or inaccessible
+    /// tasks are left out.
+    pub async fn fetch_shared_tasks(
+        &self,
+        task_ids: Vec<Uuid>,
+    ) -> Result<Vec<SharedTask>, RemoteClientError> {
+        let request = FetchTasksRequest { task_ids };
+        self.post_authed("/v1/tasks/fetch", Some(&request)).await
+    }
 }
 
 #[derive(Debug, Serialize)]
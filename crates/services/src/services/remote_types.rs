@@ -50,6 +50,12 @@ pub struct CheckTasksRequest {
     pub task_ids: Vec<Uuid>,
 }
 
+/// Request for the current remote copies of shared tasks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchTasksRequest {
+    pub task_ids: Vec<Uuid>,
+}
+
 /// Response containing shared task data
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct SharedTaskResponse {
@@ -0,0 +1,155 @@
+use db::models::{
+    repo::Repo,
+    review_comment::{ReviewComment, ReviewCommentSide},
+};
+use serde::Serialize;
+use ts_rs::TS;
+use utils::diff::{Diff, DiffChangeKind, DiffHunk, create_diff_hunks};
+use uuid::Uuid;
+
+/// A changed file of a workspace repo, split into hunks for line-level review
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ReviewFileDiff {
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    /// Path relative to the repo root (the new path for renames)
+    pub path: String,
+    pub old_path: Option<String>,
+    pub change: DiffChangeKind,
+    /// True when contents were too large to diff; `hunks` is empty
+    pub content_omitted: bool,
+    pub hunks: Vec<DiffHunk>,
+}
+
+impl ReviewFileDiff {
+    pub fn from_diff(repo: &Repo, diff: Diff) -> Self {
+        let hunks = if diff.content_omitted {
+            Vec::new()
+        } else {
+            create_diff_hunks(
+                diff.old_content.as_deref().unwrap_or_default(),
+                diff.new_content.as_deref().unwrap_or_default(),
+            )
+        };
+        Self {
+            repo_id: repo.id,
+            repo_name: repo.name.clone(),
+            path: diff
+                .new_path
+                .clone()
+                .or_else(|| diff.old_path.clone())
+                .unwrap_or_default(),
+            old_path: diff.old_path,
+            change: diff.change,
+            content_omitted: diff.content_omitted,
+            hunks,
+        }
+    }
+}
+
+/// Rejection feedback for the agent: the reviewer's summary followed by the inline
+/// comments grouped by file. `repo_name` resolves a comment's repo to its folder name.
+pub fn format_review_feedback(
+    summary: Option<&str>,
+    comments: &[ReviewComment],
+    repo_name: impl Fn(Uuid) -> Option<String>,
+) -> String {
+    let mut feedback = summary.map(str::trim).unwrap_or_default().to_string();
+    if comments.is_empty() {
+        return feedback;
+    }
+
+    if !feedback.is_empty() {
+        feedback.push_str("\n\n");
+    }
+    feedback.push_str("Review comments on specific lines:\n");
+    let mut current_file: Option<(Uuid, &str)> = None;
+    for comment in comments {
+        if current_file != Some((comment.repo_id, comment.file_path.as_str())) {
+            let path = match repo_name(comment.repo_id) {
+                Some(repo) => format!("{}/{}", repo, comment.file_path),
+                None => comment.file_path.clone(),
+            };
+            feedback.push_str(&format!("\n`{path}`\n"));
+            current_file = Some((comment.repo_id, comment.file_path.as_str()));
+        }
+        let line = match comment.get_side() {
+            ReviewCommentSide::New => format!("line {}", comment.line_number),
+            ReviewCommentSide::Old => format!("removed line {}", comment.line_number),
+        };
+        feedback.push_str(&format!("- {}: {}\n", line, comment.body.trim()));
+    }
+    feedback
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn comment(file_path: &str, line_number: i32, side: &str, body: &str) -> ReviewComment {
+        ReviewComment {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::nil(),
+            repo_id: Uuid::nil(),
+            file_path: file_path.to_string(),
+            line_number,
+            side: side.to_string(),
+            body: body.to_string(),
+            sent_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn groups_comments_by_file() {
+        let comments = vec![
+            comment("src/lib.rs", 4, "new", "Handle the error"),
+            comment("src/lib.rs", 9, "old", "Keep this check"),
+            comment("src/main.rs", 1, "new", "Unused import"),
+        ];
+        let feedback = format_review_feedback(Some("Close, but not quite. "), &comments, |_| {
+            Some("api".to_string())
+        });
+        assert_eq!(
+            feedback,
+            "Close, but not quite.\n\nReview comments on specific lines:\n\
+             \n`api/src/lib.rs`\n- line 4: Handle the error\n- removed line 9: Keep this check\n\
+             \n`api/src/main.rs`\n- line 1: Unused import\n"
+        );
+    }
+
+    #[test]
+    fn summary_only_without_comments() {
+        assert_eq!(
+            format_review_feedback(Some("Needs tests"), &[], |_| None),
+            "Needs tests"
+        );
+    }
+
+    #[test]
+    fn builds_hunks_for_modified_files() {
+        let repo = Repo {
+            id: Uuid::nil(),
+            path: "/tmp/api".into(),
+            name: "api".to_string(),
+            display_name: "api".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let diff = Diff {
+            change: DiffChangeKind::Modified,
+            old_path: Some("a.txt".to_string()),
+            new_path: Some("a.txt".to_string()),
+            old_content: Some("one\ntwo\n".to_string()),
+            new_content: Some("one\n2\n".to_string()),
+            content_omitted: false,
+            additions: None,
+            deletions: None,
+        };
+        let file = ReviewFileDiff::from_diff(&repo, diff);
+        assert_eq!(file.path, "a.txt");
+        assert_eq!(file.hunks.len(), 1);
+    }
+}
@@ -0,0 +1,141 @@
+//! Reviewer feedback: what a reviewer asks the implementer to change.
+//!
+//! A reviewing agent gives feedback by writing a `feedback` key to its decision file;
+//! a person gives it by rejecting the work from the review panel. Each piece is stored
+//! as the task's next version, linked to the execution whose work was rejected, and
+//! shown to the implementer until a run that started after it has moved the task on.
+
+use db::models::{
+    execution_process::{ExecutionContext, ExecutionProcess, ExecutionProcessRunReason},
+    reviewer_feedback::{CreateReviewerFeedback, ReviewerFeedback, ReviewerFeedbackSource},
+    task_comment::TaskComment,
+    task_event::{CreateTaskEvent, TaskEvent},
+};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::prompt_language::{self, PromptLanguage};
+
+/// `feedback` from a decision file, as a string or a list of points
+pub fn feedback_from_decision(decision: Option<&Value>) -> Option<String> {
+    let feedback = match decision?.get("feedback")? {
+        Value::String(feedback) => feedback.trim().to_string(),
+        Value::Array(points) => points
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::trim)
+            .filter(|point| !point.is_empty())
+            .map(|point| format!("- {point}"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return None,
+    };
+    (!feedback.is_empty()).then_some(feedback)
+}
+
+/// Prompt section asking the implementer to address the feedback
+pub fn prompt_section(feedback: &str, version: Option<i32>, language: PromptLanguage) -> String {
+    format!(
+        "\n### {}\n\n{}\n\n{}\n",
+        prompt_language::feedback_heading(language, version),
+        feedback.trim(),
+        prompt_language::feedback_request(language)
+    )
+}
+
+/// Store feedback and add it to the task's activity feed
+pub async fn record(
+    pool: &PgPool,
+    task_id: Uuid,
+    workspace_id: Option<Uuid>,
+    data: &CreateReviewerFeedback,
+) -> Result<ReviewerFeedback, sqlx::Error> {
+    let feedback = ReviewerFeedback::create(pool, task_id, data).await?;
+    let event = CreateTaskEvent::reviewer_feedback(&feedback, data.source, workspace_id);
+    if let Err(e) = TaskEvent::create(pool, &event).await {
+        tracing::error!("Failed to record reviewer feedback event: {}", e);
+    }
+    Ok(feedback)
+}
+
+/// Called when a task moves on after an agent run: feedback given before the run
+/// started has been addressed, agent-visible task comments from before it have been
+/// delivered, and feedback in the run's decision is recorded for the next column's agent
+pub async fn record_from_decision(
+    pool: &PgPool,
+    ctx: &ExecutionContext,
+    column_id: Uuid,
+    decision: Option<&Value>,
+) -> Result<Option<ReviewerFeedback>, sqlx::Error> {
+    // The finishing process may be a cleanup script; the reviewed work is the agent's
+    let agent_run = ExecutionProcess::find_latest_by_session_and_run_reason(
+        pool,
+        ctx.session.id,
+        &ExecutionProcessRunReason::CodingAgent,
+    )
+    .await?
+    .unwrap_or_else(|| ctx.execution_process.clone());
+
+    ReviewerFeedback::mark_addressed(pool, ctx.task.id, agent_run.started_at).await?;
+    TaskComment::mark_delivered(pool, ctx.task.id, agent_run.started_at).await?;
+
+    let Some(feedback) = feedback_from_decision(decision) else {
+        return Ok(None);
+    };
+    let data = CreateReviewerFeedback {
+        column_id: Some(column_id),
+        execution_process_id: Some(agent_run.id),
+        source: ReviewerFeedbackSource::Agent,
+        feedback,
+    };
+    record(pool, ctx.task.id, Some(ctx.workspace.id), &data)
+        .await
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn feedback_is_read_as_text_or_points() {
+        let text = json!({ "answer": "reject", "feedback": "  Add tests for retries\n" });
+        assert_eq!(
+            feedback_from_decision(Some(&text)).as_deref(),
+            Some("Add tests for retries")
+        );
+
+        let points = json!({ "feedback": ["Add tests", " ", "Rename the flag"] });
+        assert_eq!(
+            feedback_from_decision(Some(&points)).as_deref(),
+            Some("- Add tests\n- Rename the flag")
+        );
+
+        assert_eq!(
+            feedback_from_decision(Some(&json!({ "feedback": "  " }))),
+            None
+        );
+        assert_eq!(
+            feedback_from_decision(Some(&json!({ "answer": "approve" }))),
+            None
+        );
+        assert_eq!(feedback_from_decision(None), None);
+    }
+
+    #[test]
+    fn later_rounds_are_numbered() {
+        let english = PromptLanguage::English;
+        assert!(
+            prompt_section("Fix it", Some(1), english)
+                .contains("### Feedback from Previous Review\n")
+        );
+        assert!(prompt_section("Fix it", Some(3), english).contains("(round 3)"));
+        assert!(prompt_section("Fix it", None, english).contains("Fix it\n\nPlease address"));
+        assert!(
+            prompt_section("Fix it", None, PromptLanguage::German).contains("Fix it\n\nBitte")
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use db::models::security_finding::{CreateSecurityFinding, FindingSeverity, SecurityScanner};
+use serde_json::Value;
+use tokio::process::Command;
+
+/// Scanners that don't finish within this time count as failed
+const SCAN_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Run the configured scanners in each repo directory of a workspace.
+///
+/// Scanners that don't apply to a repo (e.g. no Cargo.lock) are skipped. One that isn't
+/// installed, times out or fails is reported as a blocking finding, so the gate fails
+/// closed, unless the project allows scanner errors; then the finding is informational.
+pub async fn run_scanners(
+    workspace_root: &Path,
+    repo_dirs: &[PathBuf],
+    scanners: &[SecurityScanner],
+    allow_scanner_errors: bool,
+) -> Vec<CreateSecurityFinding> {
+    let mut findings = Vec::new();
+    for dir in repo_dirs {
+        let prefix = dir
+            .strip_prefix(workspace_root)
+            .ok()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map(|p| p.to_string_lossy().to_string());
+
+        for scanner in scanners {
+            match run_scanner(*scanner, dir).await {
+                Ok(mut found) => {
+                    if let Some(prefix) = &prefix {
+                        for finding in &mut found {
+                            finding.location = finding
+                                .location
+                                .as_ref()
+                                .map(|loc| format!("{}: {}", prefix, loc));
+                        }
+                    }
+                    findings.extend(found);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Security scanner {} failed in {}: {}",
+                        scanner.as_str(),
+                        dir.display(),
+                        e
+                    );
+                    findings.push(scanner_failure(
+                        *scanner,
+                        prefix.clone(),
+                        e,
+                        allow_scanner_errors,
+                    ));
+                }
+            }
+        }
+    }
+    findings
+}
+
+/// Finding recorded for a scanner that couldn't produce a report
+fn scanner_failure(
+    scanner: SecurityScanner,
+    location: Option<String>,
+    error: String,
+    allowed: bool,
+) -> CreateSecurityFinding {
+    CreateSecurityFinding {
+        scanner,
+        severity: if allowed {
+            FindingSeverity::Info
+        } else {
+            FindingSeverity::High
+        },
+        title: format!("{} could not run", scanner.as_str()),
+        location,
+        details: Some(error),
+    }
+}
+
+async fn run_scanner(
+    scanner: SecurityScanner,
+    dir: &Path,
+) -> Result<Vec<CreateSecurityFinding>, String> {
+    match scanner {
+        SecurityScanner::CargoAudit => {
+            if !dir.join("Cargo.lock").exists() {
+                return Ok(Vec::new());
+            }
+            let output = run_command(dir, "cargo", &["audit", "--json"]).await?;
+            parse_cargo_audit(&output).map_err(|e| format!("invalid cargo audit output: {e}"))
+        }
+        SecurityScanner::NpmAudit => {
+            if !dir.join("package-lock.json").exists() {
+                return Ok(Vec::new());
+            }
+            let output = run_command(dir, "npm", &["audit", "--json"]).await?;
+            parse_npm_audit(&output).map_err(|e| format!("invalid npm audit output: {e}"))
+        }
+        SecurityScanner::Gitleaks => {
+            let report = tempfile::NamedTempFile::new().map_err(|e| e.to_string())?;
+            let report_path = report.path().to_string_lossy().to_string();
+            run_command(
+                dir,
+                "gitleaks",
+                &[
+                    "detect",
+                    "--no-git",
+                    "--redact",
+                    "--source",
+                    ".",
+                    "--report-format",
+                    "json",
+                    "--report-path",
+                    &report_path,
+                    "--exit-code",
+                    "0",
+                ],
+            )
+            .await?;
+            let output = tokio::fs::read_to_string(report.path())
+                .await
+                .map_err(|e| e.to_string())?;
+            parse_gitleaks(&output).map_err(|e| format!("invalid gitleaks report: {e}"))
+        }
+    }
+}
+
+/// Run a scanner and return its stdout. Non-zero exits are expected when
+/// scanners find something, so only spawn failures and timeouts are errors.
+async fn run_command(dir: &Path, program: &str, args: &[&str]) -> Result<String, String> {
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let output = match tokio::time::timeout(SCAN_TIMEOUT, command.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(format!("{program} is not installed"));
+        }
+        Ok(Err(e)) => return Err(e.to_string()),
+        Err(_) => return Err(format!("timed out after {}s", SCAN_TIMEOUT.as_secs())),
+    };
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// Parse `cargo audit --json`. RustSec vulnerabilities are treated as high
+/// severity; informational warnings (unmaintained, yanked) as low.
+pub fn parse_cargo_audit(output: &str) -> Result<Vec<CreateSecurityFinding>, serde_json::Error> {
+    let report: Value = serde_json::from_str(output)?;
+    let mut findings = Vec::new();
+
+    let vulnerabilities = report
+        .pointer("/vulnerabilities/list")
+        .and_then(Value::as_array);
+    for vuln in vulnerabilities.into_iter().flatten() {
+        let advisory = vuln.get("advisory").unwrap_or(&Value::Null);
+        let package = vuln.get("package").unwrap_or(&Value::Null);
+        findings.push(CreateSecurityFinding {
+            scanner: SecurityScanner::CargoAudit,
+            severity: FindingSeverity::High,
+            title: str_field(advisory, "title").unwrap_or_else(|| "Vulnerable crate".to_string()),
+            location: str_field(package, "name").map(|name| match str_field(package, "version") {
+                Some(version) => format!("{name} {version}"),
+                None => name,
+            }),
+            details: str_field(advisory, "id"),
+        });
+    }
+
+    let warnings = report.get("warnings").and_then(Value::as_object);
+    for (kind, list) in warnings.into_iter().flatten() {
+        for warning in list.as_array().into_iter().flatten() {
+            let package = warning.get("package").unwrap_or(&Value::Null);
+            findings.push(CreateSecurityFinding {
+                scanner: SecurityScanner::CargoAudit,
+                severity: FindingSeverity::Low,
+                title: format!("Crate is {kind}"),
+                location: str_field(package, "name"),
+                details: warning.get("advisory").and_then(|a| str_field(a, "id")),
+            });
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Parse `npm audit --json` (npm 7+), one finding per vulnerable package
+pub fn parse_npm_audit(output: &str) -> Result<Vec<CreateSecurityFinding>, serde_json::Error> {
+    let report: Value = serde_json::from_str(output)?;
+    let vulnerabilities = report.get("vulnerabilities").and_then(Value::as_object);
+
+    Ok(vulnerabilities
+        .into_iter()
+        .flatten()
+        .map(|(name, vuln)| {
+            // `via` holds advisory objects, or names of vulnerable dependencies
+            let advisory = vuln
+                .get("via")
+                .and_then(Value::as_array)
+                .and_then(|via| via.iter().find(|v| v.is_object()));
+            CreateSecurityFinding {
+                scanner: SecurityScanner::NpmAudit,
+                severity: str_field(vuln, "severity")
+                    .and_then(|s| FindingSeverity::from_str(&s))
+                    .unwrap_or(FindingSeverity::Moderate),
+                title: advisory
+                    .and_then(|a| str_field(a, "title"))
+                    .unwrap_or_else(|| "Depends on a vulnerable package".to_string()),
+                location: Some(match str_field(vuln, "range") {
+                    Some(range) => format!("{name} {range}"),
+                    None => name.clone(),
+                }),
+                details: advisory.and_then(|a| str_field(a, "url")),
+            }
+        })
+        .collect())
+}
+
+/// Parse a gitleaks JSON report. Leaked secrets are always high severity.
+pub fn parse_gitleaks(output: &str) -> Result<Vec<CreateSecurityFinding>, serde_json::Error> {
+    if output.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let report: Vec<Value> = serde_json::from_str(output)?;
+
+    Ok(report
+        .iter()
+        .map(|leak| CreateSecurityFinding {
+            scanner: SecurityScanner::Gitleaks,
+            severity: FindingSeverity::High,
+            title: str_field(leak, "Description")
+                .unwrap_or_else(|| "Potential secret committed".to_string()),
+            location: str_field(leak, "File").map(|file| {
+                match leak.get("StartLine").and_then(Value::as_i64) {
+                    Some(line) => format!("{file}:{line}"),
+                    None => file,
+                }
+            }),
+            details: str_field(leak, "RuleID"),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cargo_audit_vulnerabilities_and_warnings() {
+        let output = r#"{
+            "vulnerabilities": {"found": true, "count": 1, "list": [{
+                "advisory": {"id": "RUSTSEC-2023-0044", "title": "openssl `X509VerifyParamRef::set_host` buffer over-read"},
+                "package": {"name": "openssl", "version": "0.10.48"}
+            }]},
+            "warnings": {"unmaintained": [{"kind": "unmaintained", "package": {"name": "ansi_term"}, "advisory": {"id": "RUSTSEC-2021-0139"}}]}
+        }"#;
+        let findings = parse_cargo_audit(output).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].severity, FindingSeverity::High);
+        assert_eq!(findings[0].location.as_deref(), Some("openssl 0.10.48"));
+        assert_eq!(findings[1].severity, FindingSeverity::Low);
+        assert_eq!(findings[1].title, "Crate is unmaintained");
+    }
+
+    #[test]
+    fn parses_npm_audit_severities() {
+        let output = r#"{
+            "auditReportVersion": 2,
+            "vulnerabilities": {
+                "lodash": {"name": "lodash", "severity": "critical", "range": "<4.17.21",
+                    "via": [{"title": "Prototype Pollution in lodash", "url": "https://github.com/advisories/GHSA-p6mc-m468-83gw"}]},
+                "some-wrapper": {"name": "some-wrapper", "severity": "moderate", "via": ["lodash"]}
+            }
+        }"#;
+        let findings = parse_npm_audit(output).unwrap();
+        let lodash = findings
+            .iter()
+            .find(|f| f.location.as_deref() == Some("lodash <4.17.21"))
+            .unwrap();
+        assert!(lodash.severity.is_blocking());
+        assert_eq!(lodash.title, "Prototype Pollution in lodash");
+        let wrapper = findings
+            .iter()
+            .find(|f| f.location.as_deref() == Some("some-wrapper"))
+            .unwrap();
+        assert!(!wrapper.severity.is_blocking());
+    }
+
+    #[test]
+    fn scanner_failures_block_unless_the_project_allows_them() {
+        let error = "gitleaks is not installed".to_string();
+        let blocking = scanner_failure(SecurityScanner::Gitleaks, None, error.clone(), false);
+        assert!(blocking.severity.is_blocking());
+        assert_eq!(blocking.details.as_deref(), Some("gitleaks is not installed"));
+
+        let allowed = scanner_failure(SecurityScanner::Gitleaks, None, error, true);
+        assert!(!allowed.severity.is_blocking());
+    }
+
+    #[test]
+    fn parses_gitleaks_report() {
+        let output = r#"[{"Description": "AWS Access Key", "File": "src/config.rs", "StartLine": 12, "RuleID": "aws-access-token", "Secret": "REDACTED"}]"#;
+        let findings = parse_gitleaks(output).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].location.as_deref(), Some("src/config.rs:12"));
+        assert!(findings[0].severity.is_blocking());
+        assert!(parse_gitleaks("").unwrap().is_empty());
+    }
+}
@@ -1,9 +1,11 @@
 mod config;
 mod publisher;
 mod status;
+mod sync;
 
 pub use config::ShareConfig;
 pub use publisher::{SharePublisher, SharedTaskDetails};
+pub use sync::SharedTaskSyncService;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -34,6 +36,10 @@ pub enum ShareError {
     InvalidResponse,
     #[error("task {0} is already shared")]
     AlreadyShared(Uuid),
+    #[error("shared task conflict {0} not found")]
+    ConflictNotFound(Uuid),
+    #[error("shared task conflict {0} is already resolved")]
+    ConflictAlreadyResolved(Uuid),
     #[error("GitHub token is required to fetch repository ID")]
     MissingGitHubToken,
     #[error(transparent)]
@@ -1,17 +1,28 @@
+use std::collections::HashMap;
+
 use db::{
     DBService,
     models::{
         project::Project,
+        project_share_sync_settings::{ProjectShareSyncSettings, ShareConflictStrategy},
+        shared_task_sync::{
+            ConflictResolution, SharedTaskConflict, SharedTaskFields, SharedTaskSyncState,
+        },
         task::{CreateTask, Task, TaskStatus},
+        task_event::{CreateTaskEvent, TaskEvent},
     },
 };
 use super::super::remote_types::{
-    AssignSharedTaskRequest, CreateSharedTaskRequest, SharedTaskResponse, UpdateSharedTaskRequest,
+    AssignSharedTaskRequest, CreateSharedTaskRequest, SharedTask, SharedTaskResponse,
+    UpdateSharedTaskRequest,
 };
 use uuid::Uuid;
 
-use super::{ShareError, status};
-use crate::services::remote_client::RemoteClient;
+use super::{ShareError, status, sync};
+use crate::services::{
+    job_queue::{self, Job},
+    remote_client::RemoteClient,
+};
 
 #[derive(Clone)]
 pub struct SharePublisher {
@@ -59,6 +70,13 @@ impl SharePublisher {
         let remote_task = self.client.create_shared_task(&payload).await?;
 
         Task::set_shared_task_id(&self.db.pool, task.id, Some(remote_task.task.id)).await?;
+        SharedTaskSyncState::record(
+            &self.db.pool,
+            task.id,
+            &status::remote_fields(&remote_task.task),
+            remote_task.task.updated_at,
+        )
+        .await?;
         Ok(remote_task.task.id)
     }
 
@@ -68,16 +86,38 @@ impl SharePublisher {
             return Ok(());
         };
 
+        // Pushing now would overwrite the remote side of a conflict awaiting resolution
+        if SharedTaskConflict::find_open_for_task(&self.db.pool, task.id)
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        self.push_fields(task.id, shared_task_id, &SharedTaskFields::from(task))
+            .await
+    }
+
+    /// Push fields to the remote copy and record them as what both sides agree on
+    async fn push_fields(
+        &self,
+        task_id: Uuid,
+        shared_task_id: Uuid,
+        fields: &SharedTaskFields,
+    ) -> Result<(), ShareError> {
         let payload = UpdateSharedTaskRequest {
-            title: Some(task.title.clone()),
-            description: task.description.clone(),
-            status: Some(status::to_remote(&task.status)),
+            title: Some(fields.title.clone()),
+            description: fields.description.clone(),
+            status: Some(status::to_remote(&fields.status)),
         };
 
-        self.client
+        let response = self
+            .client
             .update_shared_task(shared_task_id, &payload)
             .await?;
 
+        SharedTaskSyncState::record(&self.db.pool, task_id, fields, response.task.updated_at)
+            .await?;
         Ok(())
     }
 
@@ -89,6 +129,21 @@ impl SharePublisher {
         self.update_shared_task(&task).await
     }
 
+    /// Push a task update to its shared copy, queueing a retry if that fails
+    pub async fn propagate_task_update(&self, task_id: Uuid) {
+        let Err(err) = self.update_shared_task_by_id(task_id).await else {
+            return;
+        };
+        tracing::warn!(
+            ?err,
+            "Failed to propagate shared task update for {}; queued a retry",
+            task_id
+        );
+        if let Err(e) = job_queue::enqueue(&self.db.pool, &Job::SyncSharedTask { task_id }).await {
+            tracing::error!("Failed to queue shared task update for {}: {}", task_id, e);
+        }
+    }
+
     pub async fn assign_shared_task(
         &self,
         shared_task_id: Uuid,
@@ -157,6 +212,205 @@ impl SharePublisher {
             .contains(&shared_task_id))
     }
 
+    /// Remote organization of a project that is linked to a remote project
+    pub async fn organization_for_project(
+        &self,
+        project: &Project,
+    ) -> Result<Option<Uuid>, ShareError> {
+        let Some(remote_project_id) = project.remote_project_id else {
+            return Ok(None);
+        };
+        let remote_project = self.client.get_project(remote_project_id).await?;
+        Ok(Some(remote_project.organization_id))
+    }
+
+    /// Pull edits made to the remote copies of shared tasks into the local tasks, push
+    /// local edits the other way and settle fields edited on both sides
+    pub async fn sync_shared_tasks(&self) -> Result<(), ShareError> {
+        let tasks = Task::find_all_shared(&self.db.pool).await?;
+
+        // Fetch in chunks of 100 to avoid hitting payload limits
+        for chunk in tasks.chunks(100) {
+            let shared_ids: Vec<Uuid> = chunk.iter().filter_map(|t| t.shared_task_id).collect();
+            let remote_tasks: HashMap<Uuid, SharedTask> = self
+                .client
+                .fetch_shared_tasks(shared_ids)
+                .await?
+                .into_iter()
+                .map(|remote_task| (remote_task.id, remote_task))
+                .collect();
+
+            for task in chunk {
+                // Tasks deleted remotely are unlinked by `cleanup_shared_tasks`
+                let Some(remote_task) = task.shared_task_id.and_then(|id| remote_tasks.get(&id))
+                else {
+                    continue;
+                };
+                if let Err(e) = self.reconcile(task, remote_task).await {
+                    tracing::warn!("Failed to sync shared task for {}: {}", task.id, e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn reconcile(&self, task: &Task, remote_task: &SharedTask) -> Result<(), ShareError> {
+        let pool = &self.db.pool;
+        if SharedTaskConflict::find_open_for_task(pool, task.id)
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        let local = SharedTaskFields::from(task);
+        let remote = status::remote_fields(remote_task);
+        let state = SharedTaskSyncState::find(pool, task.id).await?;
+        if local == remote {
+            if state.as_ref().is_none_or(|state| state.base.0 != local) {
+                SharedTaskSyncState::record(pool, task.id, &local, remote_task.updated_at).await?;
+            }
+            return Ok(());
+        }
+
+        let base = match state {
+            Some(state) => state.base.0,
+            // Never synced (shared before two-way sync): the side edited last is newer
+            None if task.updated_at >= remote_task.updated_at => remote.clone(),
+            None => local.clone(),
+        };
+        let mut merge = sync::three_way_merge(&base, &local, &remote);
+
+        if !merge.conflicts.is_empty() {
+            let strategy = ProjectShareSyncSettings::for_project(pool, task.project_id)
+                .await?
+                .strategy();
+            let resolution = match strategy {
+                ShareConflictStrategy::Manual => None,
+                ShareConflictStrategy::LastWriterWins
+                    if remote_task.updated_at > task.updated_at =>
+                {
+                    Some(ConflictResolution::Remote)
+                }
+                ShareConflictStrategy::LastWriterWins => Some(ConflictResolution::Local),
+            };
+            let conflict = SharedTaskConflict::create(
+                pool,
+                task.id,
+                remote_task.id,
+                &merge.conflicts,
+                &local,
+                &remote,
+                resolution,
+            )
+            .await?;
+            let event = CreateTaskEvent::shared_task_conflict(
+                task.id,
+                conflict.id,
+                &merge.conflicts,
+                resolution.map(|r| r.as_str()),
+            );
+            TaskEvent::create(pool, &event).await?;
+
+            match resolution {
+                // Leave both sides alone until someone picks one
+                None => return Ok(()),
+                Some(ConflictResolution::Remote) => {
+                    sync::take_fields(&mut merge.merged, &remote, &merge.conflicts)
+                }
+                Some(ConflictResolution::Local) => {}
+            }
+        }
+
+        self.converge(task, remote_task, &merge.merged).await
+    }
+
+    /// Bring the task and its remote copy to `merged`
+    async fn converge(
+        &self,
+        task: &Task,
+        remote_task: &SharedTask,
+        merged: &SharedTaskFields,
+    ) -> Result<(), ShareError> {
+        if *merged != SharedTaskFields::from(task) {
+            Task::update(
+                &self.db.pool,
+                task.id,
+                task.project_id,
+                merged.title.clone(),
+                merged.description.clone(),
+                merged.status.clone(),
+                task.column_id,
+                task.parent_workspace_id,
+            )
+            .await?;
+        }
+
+        if *merged != status::remote_fields(remote_task) {
+            self.push_fields(task.id, remote_task.id, merged).await
+        } else {
+            SharedTaskSyncState::record(&self.db.pool, task.id, merged, remote_task.updated_at)
+                .await?;
+            Ok(())
+        }
+    }
+
+    /// Settle an open conflict by keeping one side's values for the conflicting fields;
+    /// the other fields are merged as usual
+    pub async fn resolve_conflict(
+        &self,
+        conflict_id: Uuid,
+        resolution: ConflictResolution,
+    ) -> Result<SharedTaskConflict, ShareError> {
+        let pool = &self.db.pool;
+        let conflict = SharedTaskConflict::find_by_id(pool, conflict_id)
+            .await?
+            .ok_or(ShareError::ConflictNotFound(conflict_id))?;
+        if conflict.resolved_at.is_some() {
+            return Err(ShareError::ConflictAlreadyResolved(conflict_id));
+        }
+        let task = Task::find_by_id(pool, conflict.task_id)
+            .await?
+            .ok_or(ShareError::TaskNotFound(conflict.task_id))?;
+
+        // Both sides may have moved on since the conflict was found
+        let remote_task = self
+            .client
+            .fetch_shared_tasks(vec![conflict.shared_task_id])
+            .await?
+            .pop();
+        if let Some(remote_task) = &remote_task {
+            let local = SharedTaskFields::from(&task);
+            let remote = status::remote_fields(remote_task);
+            let base = SharedTaskSyncState::find(pool, task.id)
+                .await?
+                .map_or_else(|| local.clone(), |state| state.base.0);
+            let mut merged = sync::three_way_merge(&base, &local, &remote).merged;
+            let fields: Vec<&str> = conflict.fields.iter().map(String::as_str).collect();
+            let winner = match resolution {
+                ConflictResolution::Local => &local,
+                ConflictResolution::Remote => &remote,
+            };
+            sync::take_fields(&mut merged, winner, &fields);
+            self.converge(&task, remote_task, &merged).await?;
+        }
+
+        let resolved = SharedTaskConflict::resolve(pool, conflict_id, resolution)
+            .await?
+            .ok_or(ShareError::ConflictAlreadyResolved(conflict_id))?;
+        let fields: Vec<&str> = resolved.fields.iter().map(String::as_str).collect();
+        let event = CreateTaskEvent::shared_task_conflict(
+            task.id,
+            conflict_id,
+            &fields,
+            Some(resolution.as_str()),
+        );
+        TaskEvent::create(pool, &event).await?;
+
+        Ok(resolved)
+    }
+
     pub async fn cleanup_shared_tasks(&self) -> Result<(), ShareError> {
         let tasks = Task::find_all_shared(&self.db.pool).await?;
         if tasks.is_empty() {
@@ -1,5 +1,5 @@
-use db::models::task::TaskStatus;
-use super::super::remote_types::RemoteTaskStatus;
+use db::models::{shared_task_sync::SharedTaskFields, task::TaskStatus};
+use super::super::remote_types::{RemoteTaskStatus, SharedTask};
 
 pub(super) fn to_remote(status: &TaskStatus) -> RemoteTaskStatus {
     match status {
@@ -10,3 +10,22 @@ pub(super) fn to_remote(status: &TaskStatus) -> RemoteTaskStatus {
         TaskStatus::Cancelled => RemoteTaskStatus::Cancelled,
     }
 }
+
+pub(super) fn from_remote(status: &RemoteTaskStatus) -> TaskStatus {
+    match status {
+        RemoteTaskStatus::Todo => TaskStatus::Todo,
+        RemoteTaskStatus::InProgress => TaskStatus::InProgress,
+        RemoteTaskStatus::InReview => TaskStatus::InReview,
+        RemoteTaskStatus::Done => TaskStatus::Done,
+        RemoteTaskStatus::Cancelled => TaskStatus::Cancelled,
+    }
+}
+
+/// The synced fields of a task's remote copy
+pub(super) fn remote_fields(task: &SharedTask) -> SharedTaskFields {
+    SharedTaskFields {
+        title: task.title.clone(),
+        description: task.description.clone(),
+        status: from_remote(&task.status),
+    }
+}
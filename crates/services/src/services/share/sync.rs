@@ -0,0 +1,163 @@
+//! Two-way sync of shared tasks.
+//!
+//! The publisher pushes local edits as they happen; this service polls the remote
+//! copies and pulls their edits back. Each pass three-way merges a task's title,
+//! description and status against what both sides last agreed on: a field edited on one
+//! side takes that side's value, and a field edited on both is a conflict settled by the
+//! project's `ShareConflictStrategy`.
+
+use std::time::Duration;
+
+use db::models::shared_task_sync::SharedTaskFields;
+use tokio::time::interval;
+use tracing::{debug, info, warn};
+
+use super::{ShareError, SharePublisher};
+use crate::RemoteClientError;
+
+/// Result of merging a task with its remote copy
+#[derive(Debug, PartialEq)]
+pub(super) struct Merge {
+    pub merged: SharedTaskFields,
+    /// Fields edited on both sides to different values; `merged` keeps the local value
+    pub conflicts: Vec<&'static str>,
+}
+
+fn merge_field<T: Clone + PartialEq>(
+    name: &'static str,
+    base: &T,
+    local: &T,
+    remote: &T,
+    conflicts: &mut Vec<&'static str>,
+) -> T {
+    if local == remote || remote == base {
+        local.clone()
+    } else if local == base {
+        remote.clone()
+    } else {
+        conflicts.push(name);
+        local.clone()
+    }
+}
+
+pub(super) fn three_way_merge(
+    base: &SharedTaskFields,
+    local: &SharedTaskFields,
+    remote: &SharedTaskFields,
+) -> Merge {
+    let mut conflicts = Vec::new();
+    let merged = SharedTaskFields {
+        title: merge_field("title", &base.title, &local.title, &remote.title, &mut conflicts),
+        description: merge_field(
+            "description",
+            &base.description,
+            &local.description,
+            &remote.description,
+            &mut conflicts,
+        ),
+        status: merge_field(
+            "status",
+            &base.status,
+            &local.status,
+            &remote.status,
+            &mut conflicts,
+        ),
+    };
+    Merge { merged, conflicts }
+}
+
+/// Copy the named fields from `from` into `into`
+pub(super) fn take_fields(into: &mut SharedTaskFields, from: &SharedTaskFields, fields: &[&str]) {
+    for field in fields {
+        match *field {
+            "title" => into.title = from.title.clone(),
+            "description" => into.description = from.description.clone(),
+            "status" => into.status = from.status.clone(),
+            _ => {}
+        }
+    }
+}
+
+pub struct SharedTaskSyncService {
+    publisher: SharePublisher,
+    poll_interval: Duration,
+}
+
+impl SharedTaskSyncService {
+    pub async fn spawn(publisher: SharePublisher) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            publisher,
+            poll_interval: Duration::from_secs(60),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting shared task sync service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+            match self.publisher.sync_shared_tasks().await {
+                Ok(()) => {}
+                // Not signed in to the share service; nothing to pull
+                Err(ShareError::RemoteClientError(RemoteClientError::Auth)) => {
+                    debug!("Skipping shared task sync: not signed in");
+                }
+                Err(e) => warn!("Error syncing shared tasks: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use db::models::task::TaskStatus;
+
+    use super::*;
+
+    fn fields(title: &str, description: Option<&str>, status: TaskStatus) -> SharedTaskFields {
+        SharedTaskFields {
+            title: title.to_string(),
+            description: description.map(str::to_string),
+            status,
+        }
+    }
+
+    #[test]
+    fn edits_on_one_side_are_taken_without_conflict() {
+        let base = fields("Fix login", Some("Steps"), TaskStatus::Todo);
+        let local = fields("Fix login flow", Some("Steps"), TaskStatus::Todo);
+        let remote = fields("Fix login", Some("Steps"), TaskStatus::InProgress);
+
+        let merge = three_way_merge(&base, &local, &remote);
+
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(
+            merge.merged,
+            fields("Fix login flow", Some("Steps"), TaskStatus::InProgress)
+        );
+    }
+
+    #[test]
+    fn differing_edits_on_both_sides_conflict_and_keep_local_until_settled() {
+        let base = fields("Fix login", None, TaskStatus::Todo);
+        let local = fields("Fix login", Some("Local notes"), TaskStatus::Done);
+        let remote = fields("Fix login", Some("Remote notes"), TaskStatus::Done);
+
+        let mut merge = three_way_merge(&base, &local, &remote);
+
+        // Both moved to done: same edit, no conflict
+        assert_eq!(merge.conflicts, vec!["description"]);
+        assert_eq!(merge.merged, local);
+
+        take_fields(&mut merge.merged, &remote, &merge.conflicts);
+        assert_eq!(merge.merged, remote);
+    }
+}
@@ -0,0 +1,187 @@
+//! Graceful shutdown of in-flight executions.
+//!
+//! On SIGTERM or Ctrl+C the server stops starting new executions, stops the running
+//! ones as `Interrupted` and waits for their log writers to drain what the MsgStores
+//! still hold into `execution_process_logs`. Interrupted coding agent runs keep their
+//! agent session, so after a restart they can be resumed with a follow-up instead of
+//! being written off as failed.
+
+use std::{
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use executors::actions::{
+    ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
+};
+use tokio::task::JoinHandle;
+
+/// How long shutdown waits for log writers before giving up on them
+pub const LOG_FLUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Prompt sent to an agent when its interrupted run is resumed
+pub const RESUME_PROMPT: &str = "The server running this session was restarted while you \
+                                 were working. Continue the task from where you left off.";
+
+static SHARED: LazyLock<ShutdownCoordinator> = LazyLock::new(ShutdownCoordinator::new);
+
+pub struct ShutdownCoordinator {
+    shutting_down: AtomicBool,
+    log_writers: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl ShutdownCoordinator {
+    pub fn new() -> Self {
+        Self {
+            shutting_down: AtomicBool::new(false),
+            log_writers: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn shared() -> &'static ShutdownCoordinator {
+        &SHARED
+    }
+
+    /// Stop accepting new executions
+    pub fn begin(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Keep the task persisting an execution's logs so shutdown can wait for it
+    pub fn track_log_writer(&self, handle: JoinHandle<()>) {
+        let mut writers = self.log_writers.lock().unwrap();
+        writers.retain(|writer| !writer.is_finished());
+        writers.push(handle);
+    }
+
+    /// Wait for tracked log writers to finish, up to `timeout` in total. Returns how
+    /// many were still running when the timeout elapsed.
+    pub async fn flush_log_writers(&self, timeout: Duration) -> usize {
+        let writers = std::mem::take(&mut *self.log_writers.lock().unwrap());
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut unfinished = 0;
+        for writer in writers {
+            if tokio::time::timeout_at(deadline, writer).await.is_err() {
+                unfinished += 1;
+            }
+        }
+        unfinished
+    }
+}
+
+impl Default for ShutdownCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Follow-up that continues an interrupted coding agent run in its agent session,
+/// keeping the run's remaining actions. `None` if the action isn't a coding agent run.
+pub fn resume_action(
+    interrupted: &ExecutorAction,
+    agent_session_id: String,
+) -> Option<ExecutorAction> {
+    let (executor_profile_id, working_dir) = match interrupted.typ() {
+        ExecutorActionType::CodingAgentInitialRequest(request) => (
+            request.executor_profile_id.clone(),
+            request.working_dir.clone(),
+        ),
+        ExecutorActionType::CodingAgentFollowUpRequest(request) => (
+            request.get_executor_profile_id(),
+            request.working_dir.clone(),
+        ),
+        ExecutorActionType::ScriptRequest(_) => return None,
+    };
+    let resumed = ExecutorAction::new(
+        ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+            prompt: RESUME_PROMPT.to_string(),
+            session_id: agent_session_id,
+            executor_profile_id,
+            working_dir,
+        }),
+        interrupted.next_action.clone(),
+    );
+    Some(resumed.with_agent(interrupted.agent.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::{
+        actions::{
+            AgentIdentity,
+            script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+        },
+        executors::BaseCodingAgent,
+        profile::ExecutorProfileId,
+    };
+
+    use super::*;
+
+    fn script(context: ScriptContext) -> ExecutorAction {
+        ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: "true".to_string(),
+                language: ScriptRequestLanguage::Bash,
+                context,
+                working_dir: None,
+            }),
+            None,
+        )
+    }
+
+    #[test]
+    fn resume_continues_agent_session_and_keeps_next_action() {
+        let profile = ExecutorProfileId {
+            executor: BaseCodingAgent::ClaudeCode,
+            variant: Some("PLAN".to_string()),
+        };
+        let interrupted = ExecutorAction::new(
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt: "Add tests".to_string(),
+                session_id: "old".to_string(),
+                executor_profile_id: profile.clone(),
+                working_dir: Some("app".to_string()),
+            }),
+            Some(Box::new(script(ScriptContext::CleanupScript))),
+        )
+        .with_agent(Some(AgentIdentity {
+            agent_id: "developer".to_string(),
+            agent_color: Some("#3b82f6".to_string()),
+        }));
+
+        let resumed = resume_action(&interrupted, "agent-session".to_string()).unwrap();
+        let ExecutorActionType::CodingAgentFollowUpRequest(request) = resumed.typ() else {
+            panic!("expected a follow-up");
+        };
+        assert_eq!(request.session_id, "agent-session");
+        assert_eq!(request.prompt, RESUME_PROMPT);
+        assert_eq!(request.executor_profile_id, profile);
+        assert_eq!(request.working_dir.as_deref(), Some("app"));
+        assert!(matches!(
+            resumed.next_action().map(ExecutorAction::typ),
+            Some(ExecutorActionType::ScriptRequest(_))
+        ));
+        assert_eq!(resumed.agent, interrupted.agent);
+        assert_eq!(
+            resumed.next_action().and_then(|next| next.agent.as_ref()),
+            interrupted.agent.as_ref()
+        );
+
+        assert!(resume_action(&script(ScriptContext::DevServer), "s".to_string()).is_none());
+    }
+
+    #[test]
+    fn begin_stops_new_executions() {
+        let coordinator = ShutdownCoordinator::new();
+        assert!(!coordinator.is_shutting_down());
+        coordinator.begin();
+        assert!(coordinator.is_shutting_down());
+    }
+}
@@ -0,0 +1,75 @@
+//! Task comment threads.
+//!
+//! People discuss a task in its comment thread between workflow stages. Comments
+//! marked visible to the agent are appended to the next agent prompt next to reviewer
+//! feedback, and count as delivered once a run that started after them has moved the
+//! task on, so asynchronous guidance reaches exactly the next stage.
+
+use db::models::task_comment::TaskComment;
+
+use super::prompt_language::{self, PromptLanguage};
+
+/// Prompt section listing the comments the next agent run should see, oldest first
+pub fn prompt_section(comments: &[TaskComment], language: PromptLanguage) -> Option<String> {
+    let comments: Vec<&str> = comments
+        .iter()
+        .filter(|comment| comment.visible_to_agent)
+        .map(|comment| comment.content.trim())
+        .filter(|content| !content.is_empty())
+        .collect();
+    if comments.is_empty() {
+        return None;
+    }
+
+    let mut section = format!("\n### {}\n\n", prompt_language::comments_heading(language));
+    for content in comments {
+        // Keep multi-line comments inside their list item
+        section.push_str(&format!("- {}\n", content.replace('\n', "\n  ")));
+    }
+    Some(section)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn comment(content: &str, visible_to_agent: bool) -> TaskComment {
+        TaskComment {
+            id: Uuid::new_v4(),
+            task_id: Uuid::nil(),
+            content: content.to_string(),
+            visible_to_agent,
+            delivered_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn lists_only_agent_visible_comments() {
+        let comments = [
+            comment("Use the v2 endpoint", true),
+            comment("Note to self: ask design", false),
+            comment("Keep the old flag\nuntil 3.0 ships", true),
+        ];
+        assert_eq!(
+            prompt_section(&comments, PromptLanguage::English).unwrap(),
+            "\n### Comments on this Task\n\n- Use the v2 endpoint\n\
+             - Keep the old flag\n  until 3.0 ships\n"
+        );
+    }
+
+    #[test]
+    fn no_section_without_visible_comments() {
+        let comments = [comment("Internal only", false), comment("   ", true)];
+        assert_eq!(prompt_section(&comments, PromptLanguage::German), None);
+        assert!(
+            prompt_section(&[comment("Bitte", true)], PromptLanguage::German)
+                .unwrap()
+                .contains("### Kommentare zu dieser Aufgabe")
+        );
+    }
+}
@@ -0,0 +1,552 @@
+//! Importing tasks from GitHub Issues, Gitea/Forgejo issues and Jira exports.
+//!
+//! Exports (or issues fetched from a Gitea/Forgejo repo) are parsed into
+//! [`ImportedIssue`]s, then planned against the project's board: each issue's status is
+//! matched to a column (explicit mapping first, then column name/slug, then closed
+//! issues to the terminal column and everything else to the initial column). The plan
+//! is returned as-is for dry runs.
+
+use std::collections::HashMap;
+
+use db::models::{kanban_column::KanbanColumn, task::TaskStatus};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportSource {
+    GithubIssues,
+    /// Fetched from the repo's Gitea/Forgejo instance, or a JSON export of its issues API
+    GiteaIssues,
+    Jira,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ImportTasksRequest {
+    pub source: ImportSource,
+    /// Format of `content`; defaults to JSON
+    pub format: Option<ImportFormat>,
+    /// Contents of the exported file; may be empty for `gitea_issues` with `repo_id`
+    #[serde(default)]
+    pub content: String,
+    /// For `gitea_issues` without an export: fetch the open issues of this repo from its
+    /// configured Gitea/Forgejo instance
+    pub repo_id: Option<Uuid>,
+    /// Return the planned tasks without creating anything
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Source status (case-insensitive) to column id, checked before name matching
+    #[ts(type = "Record<string, string> | null")]
+    pub status_mapping: Option<HashMap<String, Uuid>>,
+}
+
+/// An issue read from an export, before it is mapped onto the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedIssue {
+    /// `#12` for GitHub and Gitea, the issue key for Jira
+    pub external_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    pub status: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct PlannedImportTask {
+    pub external_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    pub source_status: String,
+    pub column_id: Option<Uuid>,
+    pub column_name: Option<String>,
+    pub status: TaskStatus,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ImportTasksResponse {
+    pub dry_run: bool,
+    pub tasks: Vec<PlannedImportTask>,
+    /// Labels that do not exist in the project yet (created unless `dry_run`)
+    pub new_labels: Vec<String>,
+    /// Empty for dry runs
+    pub created_task_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("invalid JSON export: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid CSV export: {0}")]
+    Csv(String),
+    #[error("{0}")]
+    Unsupported(String),
+}
+
+/// Parse an export into issues
+pub fn parse(
+    source: ImportSource,
+    format: ImportFormat,
+    content: &str,
+) -> Result<Vec<ImportedIssue>, ImportError> {
+    match (source, format) {
+        (ImportSource::GithubIssues | ImportSource::GiteaIssues, ImportFormat::Json) => {
+            let value: Value = serde_json::from_str(content)?;
+            Ok(issues_from_api(items(&value, "issues")?))
+        }
+        (ImportSource::GithubIssues | ImportSource::GiteaIssues, ImportFormat::Csv) => {
+            Err(ImportError::Unsupported(
+                "GitHub and Gitea issues can only be imported from a JSON export".to_string(),
+            ))
+        }
+        (ImportSource::Jira, ImportFormat::Json) => parse_jira_json(content),
+        (ImportSource::Jira, ImportFormat::Csv) => parse_jira_csv(content),
+    }
+}
+
+/// Items of a top-level array, or of the array under `key`
+fn items<'a>(value: &'a Value, key: &str) -> Result<&'a Vec<Value>, ImportError> {
+    value
+        .as_array()
+        .or_else(|| value.get(key).and_then(Value::as_array))
+        .ok_or_else(|| {
+            ImportError::Unsupported(format!("expected an array of issues or an `{key}` array"))
+        })
+}
+
+fn non_empty(text: Option<&str>) -> Option<String> {
+    text.map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+}
+
+/// Issues from the GitHub or Gitea REST API (`/repos/{owner}/{repo}/issues`) or
+/// `gh issue list --json`. Pull requests returned by the issues API are skipped (Gitea
+/// sends `"pull_request": null` on plain issues).
+pub fn issues_from_api(items: &[Value]) -> Vec<ImportedIssue> {
+    let mut issues = Vec::new();
+    for item in items {
+        if item.get("pull_request").is_some_and(|pr| !pr.is_null()) {
+            continue;
+        }
+        let Some(title) = non_empty(item.get("title").and_then(Value::as_str)) else {
+            continue;
+        };
+        let external_id = match item.get("number").and_then(Value::as_i64) {
+            Some(number) => format!("#{number}"),
+            None => title.clone(),
+        };
+        let labels = item
+            .get("labels")
+            .and_then(Value::as_array)
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(|label| label.as_str().or_else(|| label["name"].as_str()))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        issues.push(ImportedIssue {
+            external_id,
+            title,
+            description: non_empty(item.get("body").and_then(Value::as_str)),
+            labels,
+            status: item
+                .get("state")
+                .and_then(Value::as_str)
+                .unwrap_or("open")
+                .to_lowercase(),
+            url: item
+                .get("html_url")
+                .or_else(|| item.get("url"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        });
+    }
+    issues
+}
+
+/// Plain text of a Jira description, which is either a string or an Atlassian
+/// Document Format tree (REST API v3)
+fn jira_text(value: &Value) -> Option<String> {
+    fn collect(value: &Value, out: &mut String) {
+        if let Some(text) = value.get("text").and_then(Value::as_str) {
+            out.push_str(text);
+        }
+        if let Some(children) = value.get("content").and_then(Value::as_array) {
+            for child in children {
+                collect(child, out);
+            }
+            if value.get("type").and_then(Value::as_str) == Some("paragraph") {
+                out.push('\n');
+            }
+        }
+    }
+    match value {
+        Value::String(text) => non_empty(Some(text)),
+        Value::Object(_) => {
+            let mut out = String::new();
+            collect(value, &mut out);
+            non_empty(Some(&out))
+        }
+        _ => None,
+    }
+}
+
+/// Issues from the Jira search API (`{"issues": [{"key", "fields": {...}}]}`)
+fn parse_jira_json(content: &str) -> Result<Vec<ImportedIssue>, ImportError> {
+    let value: Value = serde_json::from_str(content)?;
+    let mut issues = Vec::new();
+    for item in items(&value, "issues")? {
+        let fields = item.get("fields").unwrap_or(item);
+        let Some(title) = non_empty(fields.get("summary").and_then(Value::as_str)) else {
+            continue;
+        };
+        let labels = fields
+            .get("labels")
+            .and_then(Value::as_array)
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        issues.push(ImportedIssue {
+            external_id: item
+                .get("key")
+                .and_then(Value::as_str)
+                .map_or_else(|| title.clone(), str::to_string),
+            title,
+            description: fields.get("description").and_then(jira_text),
+            labels,
+            status: fields
+                .get("status")
+                .and_then(|status| status.get("name").or(Some(status)))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            url: None,
+        });
+    }
+    Ok(issues)
+}
+
+/// Issues from Jira's "Export CSV (all fields)". Jira repeats the `Labels` header
+/// once per label, so every matching column is read.
+fn parse_jira_csv(content: &str) -> Result<Vec<ImportedIssue>, ImportError> {
+    let mut records = parse_csv(content)?.into_iter();
+    let header = records
+        .next()
+        .ok_or_else(|| ImportError::Csv("the export is empty".to_string()))?;
+    let columns = |name: &str| -> Vec<usize> {
+        header
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| h.trim().eq_ignore_ascii_case(name))
+            .map(|(i, _)| i)
+            .collect()
+    };
+    let summary = *columns("Summary")
+        .first()
+        .ok_or_else(|| ImportError::Csv("missing a Summary column".to_string()))?;
+    let key = columns("Issue key").first().copied();
+    let status = columns("Status").first().copied();
+    let description = columns("Description").first().copied();
+    let labels = columns("Labels");
+
+    let field = |record: &[String], index: Option<usize>| -> Option<String> {
+        non_empty(index.and_then(|i| record.get(i)).map(String::as_str))
+    };
+    Ok(records
+        .filter_map(|record| {
+            let title = field(&record, Some(summary))?;
+            Some(ImportedIssue {
+                external_id: field(&record, key).unwrap_or_else(|| title.clone()),
+                description: field(&record, description),
+                labels: labels
+                    .iter()
+                    .filter_map(|&i| field(&record, Some(i)))
+                    .collect(),
+                status: field(&record, status).unwrap_or_default(),
+                url: None,
+                title,
+            })
+        })
+        .collect())
+}
+
+/// Minimal RFC 4180 parser: quoted fields may contain commas, newlines and `""`
+fn parse_csv(content: &str) -> Result<Vec<Vec<String>>, ImportError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (in_quotes, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => in_quotes = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => in_quotes = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                if record.iter().any(|f| !f.is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(ImportError::Csv("unterminated quoted field".to_string()));
+    }
+    record.push(field);
+    if record.iter().any(|f| !f.is_empty()) {
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Lowercase alphanumerics only, so "In Progress" matches the `in-progress` slug
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn is_closed(status: &str) -> bool {
+    matches!(
+        normalize(status).as_str(),
+        "closed" | "done" | "resolved" | "complete" | "completed"
+    )
+}
+
+fn resolve_column<'a>(
+    status: &str,
+    columns: &'a [KanbanColumn],
+    status_mapping: &HashMap<String, Uuid>,
+) -> Option<&'a KanbanColumn> {
+    let key = normalize(status);
+    status_mapping
+        .iter()
+        .find(|(source, _)| normalize(source) == key)
+        .and_then(|(_, column_id)| columns.iter().find(|c| c.id == *column_id))
+        .or_else(|| {
+            columns.iter().find(|c| {
+                !key.is_empty() && (normalize(&c.name) == key || normalize(&c.slug) == key)
+            })
+        })
+        .or_else(|| {
+            is_closed(status)
+                .then(|| columns.iter().find(|c| c.is_terminal))
+                .flatten()
+        })
+        .or_else(|| columns.iter().find(|c| c.is_initial))
+        .or_else(|| columns.first())
+}
+
+/// Map issues onto the board's columns
+pub fn plan(
+    source: ImportSource,
+    issues: Vec<ImportedIssue>,
+    columns: &[KanbanColumn],
+    status_mapping: &HashMap<String, Uuid>,
+) -> Vec<PlannedImportTask> {
+    issues
+        .into_iter()
+        .map(|issue| {
+            let column = resolve_column(&issue.status, columns, status_mapping);
+            let origin = match (&issue.url, source) {
+                (Some(url), _) => url.clone(),
+                (None, ImportSource::GithubIssues) => format!("GitHub issue {}", issue.external_id),
+                (None, ImportSource::GiteaIssues) => format!("Gitea issue {}", issue.external_id),
+                (None, ImportSource::Jira) => format!("Jira {}", issue.external_id),
+            };
+            let description = match issue.description {
+                Some(body) => format!("{body}\n\nImported from {origin}"),
+                None => format!("Imported from {origin}"),
+            };
+            let status = match column {
+                Some(column) => column.status.clone(),
+                None if is_closed(&issue.status) => TaskStatus::Done,
+                None => TaskStatus::Todo,
+            };
+            PlannedImportTask {
+                external_id: issue.external_id,
+                title: issue.title,
+                description: Some(description),
+                labels: issue.labels,
+                source_status: issue.status,
+                column_id: column.map(|c| c.id),
+                column_name: column.map(|c| c.name.clone()),
+                status,
+            }
+        })
+        .collect()
+}
+
+/// Labels used by the plan that are not in `existing` (case-insensitive), in first-seen order
+pub fn new_labels(tasks: &[PlannedImportTask], existing: &[String]) -> Vec<String> {
+    let mut seen: Vec<String> = existing.iter().map(|l| l.to_lowercase()).collect();
+    let mut labels = Vec::new();
+    for label in tasks.iter().flat_map(|t| &t.labels) {
+        let key = label.to_lowercase();
+        if !seen.contains(&key) {
+            seen.push(key);
+            labels.push(label.clone());
+        }
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn column(name: &str, is_initial: bool, is_terminal: bool, status: TaskStatus) -> KanbanColumn {
+        KanbanColumn {
+            id: Uuid::new_v4(),
+            board_id: Uuid::nil(),
+            name: name.to_string(),
+            slug: name.to_lowercase().replace(' ', "-"),
+            position: 0,
+            color: None,
+            is_initial,
+            is_terminal,
+            starts_workflow: false,
+            status,
+            agent_id: None,
+            deliverable: None,
+            question: None,
+            answer_options: None,
+            read_only: false,
+            plan_only: false,
+            dry_run: false,
+            security_scanners: Vec::new(),
+            checklist: Vec::new(),
+            on_enter_script: None,
+            on_exit_script: None,
+            warm_up: false,
+            response_target_mins: None,
+            prompt_sections: Vec::new(),
+            executor_variant: None,
+            is_template: false,
+            template_group_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn github_json_skips_pull_requests_and_reads_labels() {
+        let content = r#"[
+            {"number": 7, "title": "Crash on save", "body": "Steps...", "state": "OPEN",
+             "labels": [{"name": "bug"}], "html_url": "https://github.com/o/r/issues/7"},
+            {"number": 8, "title": "A PR", "pull_request": {}},
+            {"number": 9, "title": "Docs", "body": "", "state": "closed", "labels": ["docs"]}
+        ]"#;
+        let issues = parse(ImportSource::GithubIssues, ImportFormat::Json, content).unwrap();
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].external_id, "#7");
+        assert_eq!(issues[0].status, "open");
+        assert_eq!(issues[0].labels, vec!["bug"]);
+        assert_eq!(issues[1].description, None);
+        assert_eq!(issues[1].labels, vec!["docs"]);
+    }
+
+    #[test]
+    fn jira_csv_handles_quotes_and_repeated_label_columns() {
+        let content = "Summary,Issue key,Status,Description,Labels,Labels\r\n\
+            \"Login, again\",PROJ-1,In Progress,\"Line one\nsaid \"\"hi\"\"\",auth,urgent\r\n\
+            Cleanup,PROJ-2,Done,,,\r\n";
+        let issues = parse(ImportSource::Jira, ImportFormat::Csv, content).unwrap();
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].title, "Login, again");
+        assert_eq!(
+            issues[0].description.as_deref(),
+            Some("Line one\nsaid \"hi\"")
+        );
+        assert_eq!(issues[0].labels, vec!["auth", "urgent"]);
+        assert_eq!(issues[1].external_id, "PROJ-2");
+        assert!(parse(ImportSource::Jira, ImportFormat::Csv, "Summary\n\"open").is_err());
+    }
+
+    #[test]
+    fn statuses_map_to_columns() {
+        let columns = vec![
+            column("Backlog", true, false, TaskStatus::Todo),
+            column("In Progress", false, false, TaskStatus::InProgress),
+            column("Review", false, false, TaskStatus::InReview),
+            column("Done", false, true, TaskStatus::Done),
+        ];
+        let issue = |status: &str| ImportedIssue {
+            external_id: "PROJ-1".to_string(),
+            title: "Task".to_string(),
+            description: None,
+            labels: vec!["Bug".to_string()],
+            status: status.to_string(),
+            url: None,
+        };
+        let mapping = HashMap::from([("QA".to_string(), columns[2].id)]);
+        let planned = plan(
+            ImportSource::Jira,
+            vec![
+                issue("in-progress"),
+                issue("qa"),
+                issue("Resolved"),
+                issue("Triage"),
+            ],
+            &columns,
+            &mapping,
+        );
+
+        let names: Vec<_> = planned.iter().map(|t| t.column_name.as_deref()).collect();
+        assert_eq!(
+            names,
+            vec![
+                Some("In Progress"),
+                Some("Review"),
+                Some("Done"),
+                Some("Backlog")
+            ]
+        );
+        assert_eq!(planned[2].status, TaskStatus::Done);
+        assert_eq!(
+            planned[0].description.as_deref(),
+            Some("Imported from Jira PROJ-1")
+        );
+        assert_eq!(
+            new_labels(&planned, &["bug".to_string()]),
+            Vec::<String>::new()
+        );
+        assert_eq!(new_labels(&planned, &[]), vec!["Bug"]);
+    }
+}
@@ -0,0 +1,174 @@
+//! Notifications for watched tasks.
+//!
+//! A user can watch single tasks, or labels to watch every task carrying them. Events
+//! recorded on watched tasks raise a desktop notification whether or not the project's
+//! notification channels route them. Events are picked up by polling `task_events`,
+//! and those recorded during one poll are batched into one notification per task.
+
+use std::{str::FromStr, time::Duration};
+
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::{
+        task_event::TaskEventType,
+        task_subscription::{TaskSubscription, WatchedTaskEvent},
+    },
+};
+use tokio::time::interval;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::notification::NotificationService;
+
+/// What happened, as shown in a notification
+pub fn describe_event(event_type: &str) -> String {
+    let Ok(event_type) = TaskEventType::from_str(event_type) else {
+        return event_type.replace('_', " ");
+    };
+    match event_type {
+        TaskEventType::ColumnEnter => "moved to a new column",
+        TaskEventType::ColumnExit => "left its column",
+        TaskEventType::AgentStart => "agent started",
+        TaskEventType::AgentComplete => "agent finished",
+        TaskEventType::AgentFailed => "agent failed",
+        TaskEventType::Commit => "new commit",
+        TaskEventType::ManualAction => "manual action",
+        TaskEventType::TaskCreated => "created",
+        TaskEventType::StatusChange => "status changed",
+        TaskEventType::ElseTransition => "transition condition not met",
+        TaskEventType::DecisionValidationFailed => "agent decision missing",
+        TaskEventType::ArtifactCreated => "context artifact added",
+        TaskEventType::TaskStateChange => "state changed",
+        TaskEventType::Rollback => "rolled back",
+        TaskEventType::ChecklistItemChecked => "checklist item ticked",
+        TaskEventType::ChecklistItemUnchecked => "checklist item unticked",
+        TaskEventType::HandedBack => "handed back",
+        TaskEventType::Reattached => "automation resumed",
+        TaskEventType::ReviewerFeedback => "reviewer feedback",
+        TaskEventType::LabelsChanged => "labels changed",
+        TaskEventType::ArtifactsCollected => "artifacts collected",
+        TaskEventType::WorkspaceRecreated => "workspace recreated",
+        TaskEventType::SharedTaskConflict => "shared copy conflict",
+        TaskEventType::PatchApplied => "patch applied",
+    }
+    .to_string()
+}
+
+/// One `(title, message)` per task, in the order tasks first appear in `events`
+pub fn notifications_for(events: &[WatchedTaskEvent]) -> Vec<(String, String)> {
+    let mut by_task: Vec<(Uuid, String, Vec<String>)> = Vec::new();
+    for event in events {
+        let description = describe_event(&event.event_type);
+        match by_task.iter_mut().find(|(id, _, _)| *id == event.task_id) {
+            Some((_, _, descriptions)) => {
+                if !descriptions.contains(&description) {
+                    descriptions.push(description);
+                }
+            }
+            None => by_task.push((event.task_id, event.task_title.clone(), vec![description])),
+        }
+    }
+    by_task
+        .into_iter()
+        .map(|(_, title, descriptions)| (title, descriptions.join(", ")))
+        .collect()
+}
+
+pub struct TaskWatchService {
+    db: DBService,
+    notifications: NotificationService,
+    poll_interval: Duration,
+}
+
+impl TaskWatchService {
+    pub async fn spawn(
+        db: DBService,
+        notifications: NotificationService,
+    ) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            notifications,
+            poll_interval: Duration::from_secs(5),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting watched task notifier with interval {:?}",
+            self.poll_interval
+        );
+
+        // Only events from now on; what happened while the server was down is in the
+        // task history
+        let mut since = Utc::now();
+        let mut interval = interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+            since = self.notify_since(since).await;
+        }
+    }
+
+    /// Notify about events after `since`, returning the time to poll from next
+    async fn notify_since(&self, since: DateTime<Utc>) -> DateTime<Utc> {
+        let events = match TaskSubscription::find_events_since(&self.db.pool, since).await {
+            Ok(events) => events,
+            Err(e) => {
+                error!("Failed to load events on watched tasks: {}", e);
+                return since;
+            }
+        };
+        for (title, message) in notifications_for(&events) {
+            self.notifications.notify(&title, &message).await;
+        }
+        events.last().map_or(since, |event| event.created_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(task_id: Uuid, title: &str, event_type: &str) -> WatchedTaskEvent {
+        WatchedTaskEvent {
+            id: Uuid::new_v4(),
+            task_id,
+            task_title: title.to_string(),
+            event_type: event_type.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn events_are_batched_per_task() {
+        let login = Uuid::new_v4();
+        let search = Uuid::new_v4();
+        let events = [
+            event(login, "Fix login", "column_enter"),
+            event(search, "Add search", "agent_failed"),
+            event(login, "Fix login", "agent_start"),
+            event(login, "Fix login", "agent_start"),
+        ];
+
+        assert_eq!(
+            notifications_for(&events),
+            vec![
+                (
+                    "Fix login".to_string(),
+                    "moved to a new column, agent started".to_string()
+                ),
+                ("Add search".to_string(), "agent failed".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_event_types_are_still_described() {
+        assert_eq!(describe_event("reviewer_feedback"), "reviewer feedback");
+        assert_eq!(describe_event("pr_merged"), "pr merged");
+    }
+}
@@ -0,0 +1,549 @@
+//! Conditions for workflow transitions.
+//!
+//! A transition's `condition_value` is either a plain answer (matched against the
+//! `answer` key in `.vibe/decision.json`, the original behaviour) or a comparison
+//! against any decision key:
+//!
+//! ```text
+//! coverage >= 80
+//! tests_passed == true
+//! severity in [high, critical]
+//! review.verdict != "reject"
+//! ```
+//!
+//! Keys may be dotted paths into nested objects. Right-hand values are numbers,
+//! `true`/`false`, `null`, quoted strings, or bare words (treated as strings).
+//! An answer that itself contains an operator is written quoted (`"a < b"`);
+//! answers saved before expressions existed were quoted by a migration.
+//!
+//! Tasks with sub-tasks also expose `subtasks.total`, `subtasks.done` and
+//! `subtasks.all_done`, so `subtasks.all_done == true` waits for every sub-task.
+//! Executions that left files in `.vibe/artifacts/` expose `artifacts.count` and
+//! per-extension counts, so `artifacts.by_extension.png >= 1` requires a screenshot.
+//!
+//! A transition can also require a context artifact type (e.g. an iplan) to have been
+//! written during the stage; without it the success path is treated as failed.
+
+use std::{collections::BTreeMap, fmt, path::Path};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_artifact::ExecutionArtifact, task::SubtaskProgress, task_event::ColumnEntry,
+};
+use uuid::Uuid;
+use serde_json::{Value, json};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ConditionParseError {
+    #[error("Missing key before '{0}'")]
+    MissingKey(String),
+    #[error("Invalid key '{0}': use letters, digits, '_' and '.'")]
+    InvalidKey(String),
+    #[error("Missing value after '{0}'")]
+    MissingValue(String),
+    #[error("'{0}' expects a list like [a, b]")]
+    ExpectedList(String),
+    #[error("Unterminated string in '{0}'")]
+    UnterminatedString(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConditionOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    In,
+    NotIn,
+}
+
+impl fmt::Display for ConditionOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ConditionOp::Eq => "==",
+            ConditionOp::Ne => "!=",
+            ConditionOp::Gt => ">",
+            ConditionOp::Ge => ">=",
+            ConditionOp::Lt => "<",
+            ConditionOp::Le => "<=",
+            ConditionOp::In => "in",
+            ConditionOp::NotIn => "not in",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionLiteral {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionExpr {
+    /// Dotted path into the decision JSON (e.g. `coverage`, `review.verdict`)
+    pub key: String,
+    pub op: ConditionOp,
+    /// One literal, or several for `in` / `not in`
+    pub values: Vec<ConditionLiteral>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransitionCondition {
+    /// Plain value matched against the decision's `answer` key
+    Answer(String),
+    Expr(ConditionExpr),
+}
+
+/// Symbolic operators, longest first so `>=` wins over `>`
+const SYMBOL_OPS: [(&str, ConditionOp); 6] = [
+    ("==", ConditionOp::Eq),
+    ("!=", ConditionOp::Ne),
+    (">=", ConditionOp::Ge),
+    ("<=", ConditionOp::Le),
+    (">", ConditionOp::Gt),
+    ("<", ConditionOp::Lt),
+];
+
+impl TransitionCondition {
+    /// Parse a `condition_value`. Strings without an operator are plain answers,
+    /// so every existing condition keeps its meaning.
+    pub fn parse(input: &str) -> Result<Self, ConditionParseError> {
+        let input = input.trim();
+        match find_operator(input) {
+            Some((op, start, end)) => {
+                let key = input[..start].trim();
+                let rhs = input[end..].trim();
+                let op_text = op.to_string();
+                if key.is_empty() {
+                    return Err(ConditionParseError::MissingKey(op_text));
+                }
+                if !is_valid_key(key) {
+                    return Err(ConditionParseError::InvalidKey(key.to_string()));
+                }
+                if rhs.is_empty() {
+                    return Err(ConditionParseError::MissingValue(op_text));
+                }
+                let values = match op {
+                    ConditionOp::In | ConditionOp::NotIn => parse_list(rhs, &op_text)?,
+                    _ => vec![parse_literal(rhs)?],
+                };
+                Ok(Self::Expr(ConditionExpr {
+                    key: key.to_string(),
+                    op,
+                    values,
+                }))
+            }
+            None => Ok(Self::Answer(unquote(input).to_string())),
+        }
+    }
+
+    /// Whether the decision file satisfies this condition.
+    /// Missing keys never match.
+    pub fn matches(&self, decision: &Value) -> bool {
+        match self {
+            Self::Answer(expected) => decision
+                .get("answer")
+                .and_then(|v| v.as_str())
+                .is_some_and(|v| v == expected),
+            Self::Expr(expr) => expr.matches(decision),
+        }
+    }
+
+    /// Decision key this condition reads
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Answer(_) => "answer",
+            Self::Expr(expr) => &expr.key,
+        }
+    }
+}
+
+/// Add the task's sub-task progress to the decision under `subtasks`. Tasks without
+/// sub-tasks keep their decision as it is.
+pub fn with_subtask_progress(decision: Option<Value>, progress: &SubtaskProgress) -> Option<Value> {
+    if progress.total == 0 {
+        return decision;
+    }
+    let mut decision = decision.unwrap_or_else(|| json!({}));
+    if let Some(object) = decision.as_object_mut() {
+        object.insert(
+            "subtasks".to_string(),
+            json!({
+                "total": progress.total,
+                "done": progress.done,
+                "all_done": progress.all_done(),
+            }),
+        );
+    }
+    Some(decision)
+}
+
+/// Add the execution's collected artifacts to the decision under `artifacts`:
+/// the total `count` and a count per lowercase file extension in `by_extension`.
+/// Executions without artifacts keep their decision as it is.
+pub fn with_artifacts(decision: Option<Value>, artifacts: &[ExecutionArtifact]) -> Option<Value> {
+    if artifacts.is_empty() {
+        return decision;
+    }
+    let mut by_extension: BTreeMap<String, usize> = BTreeMap::new();
+    for artifact in artifacts {
+        if let Some(extension) = Path::new(&artifact.name)
+            .extension()
+            .and_then(|e| e.to_str())
+        {
+            *by_extension.entry(extension.to_lowercase()).or_default() += 1;
+        }
+    }
+    let mut decision = decision.unwrap_or_else(|| json!({}));
+    if let Some(object) = decision.as_object_mut() {
+        object.insert(
+            "artifacts".to_string(),
+            json!({
+                "count": artifacts.len(),
+                "by_extension": by_extension,
+            }),
+        );
+    }
+    Some(decision)
+}
+
+/// When the task's current stage began: its latest entry into the column. Required
+/// artifacts must be written after this, so a plan from an earlier pass doesn't count.
+pub fn stage_started_at(entries: &[ColumnEntry], column_id: Uuid) -> Option<DateTime<Utc>> {
+    entries
+        .iter()
+        .filter(|entry| entry.column_id == column_id)
+        .map(|entry| entry.entered_at)
+        .max()
+}
+
+impl ConditionExpr {
+    fn matches(&self, decision: &Value) -> bool {
+        let Some(actual) = lookup(decision, &self.key) else {
+            return false;
+        };
+        match self.op {
+            ConditionOp::Eq => literal_eq(actual, &self.values[0]),
+            ConditionOp::Ne => !literal_eq(actual, &self.values[0]),
+            ConditionOp::In => self.values.iter().any(|v| literal_eq(actual, v)),
+            ConditionOp::NotIn => !self.values.iter().any(|v| literal_eq(actual, v)),
+            ConditionOp::Gt | ConditionOp::Ge | ConditionOp::Lt | ConditionOp::Le => {
+                let (Some(a), ConditionLiteral::Number(b)) = (as_number(actual), &self.values[0])
+                else {
+                    return false;
+                };
+                match self.op {
+                    ConditionOp::Gt => a > *b,
+                    ConditionOp::Ge => a >= *b,
+                    ConditionOp::Lt => a < *b,
+                    _ => a <= *b,
+                }
+            }
+        }
+    }
+}
+
+/// Locate the operator outside of quotes/brackets: returns (op, start, end) byte offsets
+fn find_operator(input: &str) -> Option<(ConditionOp, usize, usize)> {
+    let mut quote: Option<char> = None;
+    for (i, c) in input.char_indices() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        let rest = &input[i..];
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '[' => break,
+            // Word operators need whitespace before them and a list after
+            c if c.is_whitespace() => {
+                for (word, op) in [(" not in", ConditionOp::NotIn), (" in", ConditionOp::In)] {
+                    if rest.starts_with(word) && rest[word.len()..].trim_start().starts_with('[') {
+                        return Some((op, i, i + word.len()));
+                    }
+                }
+            }
+            _ => {
+                for (sym, op) in SYMBOL_OPS {
+                    if rest.starts_with(sym) {
+                        return Some((op, i, i + sym.len()));
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// A whole condition in matching quotes is a literal answer
+fn unquote(input: &str) -> &str {
+    ['"', '\'']
+        .into_iter()
+        .find_map(|q| input.strip_prefix(q)?.strip_suffix(q))
+        .unwrap_or(input)
+}
+
+fn is_valid_key(key: &str) -> bool {
+    key.split('.').all(|part| {
+        let mut chars = part.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+fn parse_list(rhs: &str, op_text: &str) -> Result<Vec<ConditionLiteral>, ConditionParseError> {
+    let inner = rhs
+        .strip_prefix('[')
+        .and_then(|r| r.strip_suffix(']'))
+        .ok_or_else(|| ConditionParseError::ExpectedList(op_text.to_string()))?;
+    split_list(inner)?
+        .into_iter()
+        .filter(|item| !item.is_empty())
+        .map(parse_literal)
+        .collect()
+}
+
+/// Split list items on commas that aren't inside quotes
+fn split_list(inner: &str) -> Result<Vec<&str>, ConditionParseError> {
+    let mut items = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, ',') => {
+                items.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if quote.is_some() {
+        return Err(ConditionParseError::UnterminatedString(inner.to_string()));
+    }
+    items.push(inner[start..].trim());
+    Ok(items)
+}
+
+fn parse_literal(raw: &str) -> Result<ConditionLiteral, ConditionParseError> {
+    let raw = raw.trim();
+    for q in ['"', '\''] {
+        if let Some(rest) = raw.strip_prefix(q) {
+            return rest
+                .strip_suffix(q)
+                .map(|s| ConditionLiteral::String(s.to_string()))
+                .ok_or_else(|| ConditionParseError::UnterminatedString(raw.to_string()));
+        }
+    }
+    Ok(match raw {
+        "true" => ConditionLiteral::Bool(true),
+        "false" => ConditionLiteral::Bool(false),
+        "null" => ConditionLiteral::Null,
+        _ => match raw.parse::<f64>() {
+            Ok(n) => ConditionLiteral::Number(n),
+            Err(_) => ConditionLiteral::String(raw.to_string()),
+        },
+    })
+}
+
+fn lookup<'a>(decision: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.')
+        .try_fold(decision, |value, part| value.get(part))
+}
+
+/// Agents often write numbers and booleans as strings; accept both forms
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn literal_eq(actual: &Value, expected: &ConditionLiteral) -> bool {
+    match expected {
+        ConditionLiteral::Null => actual.is_null(),
+        ConditionLiteral::Bool(b) => match actual {
+            Value::Bool(a) => a == b,
+            Value::String(s) => s.eq_ignore_ascii_case(if *b { "true" } else { "false" }),
+            _ => false,
+        },
+        ConditionLiteral::Number(n) => as_number(actual).is_some_and(|a| a == *n),
+        ConditionLiteral::String(s) => actual.as_str().is_some_and(|a| a == s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(condition: &str, decision: Value) -> bool {
+        TransitionCondition::parse(condition)
+            .unwrap()
+            .matches(&decision)
+    }
+
+    #[test]
+    fn plain_values_match_answer() {
+        assert_eq!(
+            TransitionCondition::parse("approve").unwrap(),
+            TransitionCondition::Answer("approve".to_string())
+        );
+        assert!(eval("approve", json!({"answer": "approve"})));
+        assert!(!eval("approve", json!({"answer": "reject"})));
+        // Words that merely contain "in" are still plain answers
+        assert!(eval("sign in later", json!({"answer": "sign in later"})));
+    }
+
+    #[test]
+    fn quoted_answers_stay_literal() {
+        assert_eq!(
+            TransitionCondition::parse("\"cost > budget\"").unwrap(),
+            TransitionCondition::Answer("cost > budget".to_string())
+        );
+        assert!(eval("'a == b'", json!({"answer": "a == b"})));
+        assert!(!eval("\"cost > budget\"", json!({"cost": 2, "budget": 1})));
+    }
+
+    #[test]
+    fn numeric_comparisons() {
+        assert!(eval("coverage >= 80", json!({"coverage": 80})));
+        assert!(eval("coverage >= 80", json!({"coverage": "92.5"})));
+        assert!(!eval("coverage >= 80", json!({"coverage": 79.9})));
+        assert!(eval("errors < 1", json!({"errors": 0})));
+        assert!(!eval("coverage > 80", json!({"coverage": "n/a"})));
+        assert!(!eval("coverage > 80", json!({})));
+    }
+
+    #[test]
+    fn boolean_and_string_equality() {
+        assert!(eval("tests_passed == true", json!({"tests_passed": true})));
+        assert!(eval(
+            "tests_passed == true",
+            json!({"tests_passed": "true"})
+        ));
+        assert!(!eval(
+            "tests_passed == true",
+            json!({"tests_passed": false})
+        ));
+        assert!(eval(
+            "review.verdict != \"reject\"",
+            json!({"review": {"verdict": "ok"}})
+        ));
+    }
+
+    #[test]
+    fn list_membership() {
+        assert!(eval(
+            "severity in [high, critical]",
+            json!({"severity": "critical"})
+        ));
+        assert!(!eval(
+            "severity in [high, critical]",
+            json!({"severity": "low"})
+        ));
+        assert!(eval(
+            "severity not in [high, 'very, bad']",
+            json!({"severity": "low"})
+        ));
+        assert!(!eval(
+            "severity not in [high, 'very, bad']",
+            json!({"severity": "very, bad"})
+        ));
+    }
+
+    #[test]
+    fn subtask_progress_is_exposed_to_conditions() {
+        let all_done = SubtaskProgress { total: 2, done: 2 };
+        let decision = with_subtask_progress(None, &all_done).unwrap();
+        assert!(eval("subtasks.all_done == true", decision));
+
+        let pending = SubtaskProgress { total: 2, done: 1 };
+        let decision = with_subtask_progress(Some(json!({"answer": "approve"})), &pending).unwrap();
+        assert!(eval("approve", decision.clone()));
+        assert!(!eval("subtasks.all_done == true", decision));
+
+        assert_eq!(
+            with_subtask_progress(None, &SubtaskProgress::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn artifacts_are_exposed_to_conditions() {
+        let artifact = |name: &str| ExecutionArtifact {
+            id: Uuid::new_v4(),
+            execution_process_id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            image_id: Uuid::new_v4(),
+            name: name.to_string(),
+            repo_name: None,
+            mime_type: None,
+            size_bytes: 1,
+            created_at: Utc::now(),
+        };
+        let artifacts = [
+            artifact("screenshot.PNG"),
+            artifact("coverage/summary.json"),
+            artifact("after.png"),
+        ];
+        let decision = with_artifacts(Some(json!({"answer": "done"})), &artifacts).unwrap();
+        assert!(eval("done", decision.clone()));
+        assert!(eval("artifacts.count == 3", decision.clone()));
+        assert!(eval("artifacts.by_extension.png >= 2", decision.clone()));
+        assert!(!eval("artifacts.by_extension.html >= 1", decision));
+
+        assert_eq!(with_artifacts(None, &[]), None);
+    }
+
+    #[test]
+    fn stage_starts_at_the_latest_entry_into_the_column() {
+        let task_id = Uuid::new_v4();
+        let planning = Uuid::new_v4();
+        let review = Uuid::new_v4();
+        let now = Utc::now();
+        let entry = |column_id, minutes_ago| ColumnEntry {
+            task_id,
+            column_id,
+            entered_at: now - chrono::Duration::minutes(minutes_ago),
+        };
+        let entries = [entry(planning, 30), entry(review, 20), entry(planning, 10)];
+
+        assert_eq!(
+            stage_started_at(&entries, planning),
+            Some(now - chrono::Duration::minutes(10))
+        );
+        assert_eq!(stage_started_at(&entries, Uuid::new_v4()), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            TransitionCondition::parse(">= 80"),
+            Err(ConditionParseError::MissingKey(">=".to_string()))
+        );
+        assert_eq!(
+            TransitionCondition::parse("coverage >="),
+            Err(ConditionParseError::MissingValue(">=".to_string()))
+        );
+        assert!(matches!(
+            TransitionCondition::parse("test coverage >= 80"),
+            Err(ConditionParseError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            TransitionCondition::parse("verdict == \"ok"),
+            Err(ConditionParseError::UnterminatedString(_))
+        ));
+    }
+}
@@ -0,0 +1,227 @@
+//! Checks that conditional transitions can actually be taken.
+//!
+//! A column's question defines the decision key its agent writes (`answer`) and its
+//! `answer_options` the values it may write. A transition whose condition reads
+//! `answer` from a column without a question, or expects an answer that isn't one of
+//! the options, never matches and leaves tasks stuck in place. Conditions on other
+//! keys (e.g. `coverage >= 80`) are listed in the agent's instructions and aren't
+//! checked here.
+
+use db::models::{kanban_column::KanbanColumn, state_transition::StateTransition};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::transition_condition::{ConditionLiteral, ConditionOp, TransitionCondition};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowWarningKind {
+    /// The condition reads `answer` but the source column doesn't ask a question
+    MissingQuestion,
+    /// The condition expects an answer that isn't one of the column's options
+    UnknownAnswer,
+}
+
+/// A transition that will never be taken as configured
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct WorkflowWarning {
+    pub transition_id: Uuid,
+    pub from_column_id: Uuid,
+    pub kind: WorkflowWarningKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct StateTransitionWithWarnings {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub transition: StateTransition,
+    pub warnings: Vec<WorkflowWarning>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct KanbanColumnWithWarnings {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub column: KanbanColumn,
+    /// Warnings for the transitions leaving this column
+    pub warnings: Vec<WorkflowWarning>,
+}
+
+/// Answers a condition on `answer` expects; empty for other keys and for
+/// comparisons that aren't about specific values
+fn expected_answers(condition: &TransitionCondition) -> Vec<String> {
+    match condition {
+        TransitionCondition::Answer(answer) => vec![answer.clone()],
+        TransitionCondition::Expr(expr) if expr.key == "answer" => match expr.op {
+            ConditionOp::Eq | ConditionOp::Ne | ConditionOp::In | ConditionOp::NotIn => expr
+                .values
+                .iter()
+                .filter_map(|v| match v {
+                    ConditionLiteral::String(s) => Some(s.clone()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        },
+        TransitionCondition::Expr(_) => Vec::new(),
+    }
+}
+
+/// Check a condition against the source column's question and answer options
+pub fn lint_condition(
+    condition_value: &str,
+    question: Option<&str>,
+    answer_options: &[String],
+) -> Vec<(WorkflowWarningKind, String)> {
+    let condition_value = condition_value.trim();
+    if condition_value.is_empty() {
+        return Vec::new();
+    }
+    // Unparseable conditions are rejected when the transition is saved
+    let Ok(condition) = TransitionCondition::parse(condition_value) else {
+        return Vec::new();
+    };
+    if condition.key() != "answer" {
+        return Vec::new();
+    }
+
+    if question.is_none_or(|q| q.trim().is_empty()) {
+        return vec![(
+            WorkflowWarningKind::MissingQuestion,
+            format!(
+                "Condition '{condition_value}' reads the agent's answer, but the column doesn't ask a question"
+            ),
+        )];
+    }
+    if answer_options.is_empty() {
+        return Vec::new();
+    }
+    expected_answers(&condition)
+        .into_iter()
+        .filter(|answer| !answer_options.contains(answer))
+        .map(|answer| {
+            (
+                WorkflowWarningKind::UnknownAnswer,
+                format!(
+                    "Condition '{condition_value}' expects \"{answer}\", which isn't one of the column's answers ({})",
+                    answer_options.join(", ")
+                ),
+            )
+        })
+        .collect()
+}
+
+/// `answer_options` of a column, a JSON array of strings
+pub fn answer_options(column: &KanbanColumn) -> Vec<String> {
+    column
+        .answer_options
+        .as_deref()
+        .and_then(|opts| serde_json::from_str(opts).ok())
+        .unwrap_or_default()
+}
+
+/// Warnings for one transition leaving `column`
+pub fn lint_transition(
+    column: &KanbanColumn,
+    transition: &StateTransition,
+) -> Vec<WorkflowWarning> {
+    let Some(condition_value) = transition.condition_value.as_deref() else {
+        return Vec::new();
+    };
+    lint_condition(
+        condition_value,
+        column.question.as_deref(),
+        &answer_options(column),
+    )
+    .into_iter()
+    .map(|(kind, message)| WorkflowWarning {
+        transition_id: transition.id,
+        from_column_id: column.id,
+        kind,
+        message: format!("{} (from column '{}')", message, column.name),
+    })
+    .collect()
+}
+
+/// Warnings for a saved transition, checked against its source column
+pub async fn lint_saved_transition(
+    pool: &PgPool,
+    transition: &StateTransition,
+) -> Result<Vec<WorkflowWarning>, sqlx::Error> {
+    Ok(
+        match KanbanColumn::find_by_id(pool, transition.from_column_id).await? {
+            Some(column) => lint_transition(&column, transition),
+            None => Vec::new(),
+        },
+    )
+}
+
+/// Warnings for every transition leaving a saved column
+pub async fn lint_saved_column(
+    pool: &PgPool,
+    column: &KanbanColumn,
+) -> Result<Vec<WorkflowWarning>, sqlx::Error> {
+    Ok(StateTransition::find_from_column(pool, column.id)
+        .await?
+        .iter()
+        .flat_map(|transition| lint_transition(column, transition))
+        .collect())
+}
+
+/// Warnings for every transition leaving one of the board's columns
+pub async fn lint_board(
+    pool: &PgPool,
+    board_id: Uuid,
+) -> Result<Vec<WorkflowWarning>, sqlx::Error> {
+    let mut warnings = Vec::new();
+    for column in KanbanColumn::find_by_board(pool, board_id).await? {
+        warnings.extend(lint_saved_column(pool, &column).await?);
+    }
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> Vec<String> {
+        vec!["approve".to_string(), "reject".to_string()]
+    }
+
+    #[test]
+    fn answer_conditions_need_a_question() {
+        let warnings = lint_condition("approve", None, &[]);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].0, WorkflowWarningKind::MissingQuestion);
+
+        assert!(lint_condition("approve", Some("Ship it?"), &options()).is_empty());
+        // Conditions on other keys don't depend on the question
+        assert!(lint_condition("coverage >= 80", None, &[]).is_empty());
+        assert!(lint_condition("subtasks.all_done == true", None, &[]).is_empty());
+    }
+
+    #[test]
+    fn expected_answers_must_be_options() {
+        let kinds = |condition: &str| -> Vec<WorkflowWarningKind> {
+            lint_condition(condition, Some("Ship it?"), &options())
+                .into_iter()
+                .map(|(kind, _)| kind)
+                .collect()
+        };
+
+        assert_eq!(kinds("approved"), vec![WorkflowWarningKind::UnknownAnswer]);
+        assert!(kinds("answer == approve").is_empty());
+        assert_eq!(
+            kinds("answer in [approve, defer, later]"),
+            vec![
+                WorkflowWarningKind::UnknownAnswer,
+                WorkflowWarningKind::UnknownAnswer
+            ]
+        );
+        // Without declared options any answer is accepted
+        assert!(lint_condition("approved", Some("Ship it?"), &[]).is_empty());
+    }
+}
@@ -0,0 +1,258 @@
+//! Conflict detection between concurrent workspaces.
+//!
+//! A background service records, for every active workspace, which files it has
+//! changed in each repo. Workspaces of different tasks that change the same files
+//! in the same repo are reported as conflicting, so two agents can be kept from
+//! rewriting the same module before their branches ever meet.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::workspace_changed_paths::{
+        ActiveWorkspaceChanges, ActiveWorkspaceRepo, WorkspaceChangedPaths,
+    },
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tokio::time::interval;
+use tracing::{debug, error, info};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::git::{GitService, GitServiceError};
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct ConflictingTask {
+    pub task_id: Uuid,
+    pub title: String,
+    pub workspace_id: Uuid,
+}
+
+/// Tasks whose workspaces change the same files of a repo
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct WorkspaceConflict {
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub tasks: Vec<ConflictingTask>,
+    /// Files changed by every one of the tasks
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProjectConflicts {
+    pub conflicts: Vec<WorkspaceConflict>,
+    /// Tasks involved in at least one conflict, for flagging task cards
+    pub conflicting_task_ids: Vec<Uuid>,
+    /// Oldest workspace snapshot the result is based on
+    #[ts(type = "Date | null")]
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+/// Group files changed by more than one task into conflicts, one per repo and set of tasks
+pub fn find_conflicts(changes: &[ActiveWorkspaceChanges]) -> Vec<WorkspaceConflict> {
+    let mut tasks_by_path: BTreeMap<(Uuid, &str), BTreeSet<Uuid>> = BTreeMap::new();
+    for change in changes {
+        for path in &change.paths {
+            tasks_by_path
+                .entry((change.repo_id, path.as_str()))
+                .or_default()
+                .insert(change.task_id);
+        }
+    }
+
+    let mut paths_by_tasks: BTreeMap<(Uuid, Vec<Uuid>), Vec<String>> = BTreeMap::new();
+    for ((repo_id, path), task_ids) in tasks_by_path {
+        if task_ids.len() > 1 {
+            paths_by_tasks
+                .entry((repo_id, task_ids.into_iter().collect()))
+                .or_default()
+                .push(path.to_string());
+        }
+    }
+
+    let mut conflicts: Vec<WorkspaceConflict> = paths_by_tasks
+        .into_iter()
+        .map(|((repo_id, task_ids), paths)| {
+            let changes_of = |task_id: Uuid| {
+                changes
+                    .iter()
+                    .find(|c| c.task_id == task_id && c.repo_id == repo_id)
+            };
+            WorkspaceConflict {
+                repo_id,
+                repo_name: changes_of(task_ids[0])
+                    .map(|c| c.repo_name.clone())
+                    .unwrap_or_default(),
+                tasks: task_ids
+                    .iter()
+                    .filter_map(|&id| changes_of(id))
+                    .map(|c| ConflictingTask {
+                        task_id: c.task_id,
+                        title: c.task_title.clone(),
+                        workspace_id: c.workspace_id,
+                    })
+                    .collect(),
+                paths,
+            }
+        })
+        .collect();
+    conflicts.sort_by(|a, b| (&a.repo_name, &a.paths).cmp(&(&b.repo_name, &b.paths)));
+    conflicts
+}
+
+pub async fn project_conflicts(
+    pool: &PgPool,
+    project_id: Uuid,
+) -> Result<ProjectConflicts, sqlx::Error> {
+    let changes = WorkspaceChangedPaths::find_active_for_project(pool, project_id).await?;
+    let conflicts = find_conflicts(&changes);
+    let conflicting_task_ids: BTreeSet<Uuid> = conflicts
+        .iter()
+        .flat_map(|c| c.tasks.iter().map(|t| t.task_id))
+        .collect();
+    Ok(ProjectConflicts {
+        conflicts,
+        conflicting_task_ids: conflicting_task_ids.into_iter().collect(),
+        checked_at: changes.iter().map(|c| c.checked_at).min(),
+    })
+}
+
+fn changed_paths(
+    git: &GitService,
+    repo: &ActiveWorkspaceRepo,
+) -> Result<Vec<String>, GitServiceError> {
+    let base_commit = git.get_base_commit(&repo.repo_path(), &repo.branch, &repo.target_branch)?;
+    git.get_changed_paths(&repo.worktree_path(), &base_commit)
+}
+
+/// Periodically records the changed files of every active workspace
+pub struct WorkspaceConflictService {
+    db: DBService,
+    git: GitService,
+    poll_interval: Duration,
+}
+
+impl WorkspaceConflictService {
+    pub async fn spawn(db: DBService) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            git: GitService::new(),
+            poll_interval: Duration::from_secs(2 * 60),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting workspace conflict detection with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.refresh().await {
+                error!("Error refreshing workspace changes: {}", e);
+            }
+        }
+    }
+
+    async fn refresh(&self) -> Result<(), sqlx::Error> {
+        let repos = WorkspaceChangedPaths::find_active_workspace_repos(&self.db.pool).await?;
+        for repo in repos {
+            // Worktrees are created lazily and cleaned up when idle
+            if !repo.worktree_path().exists() {
+                continue;
+            }
+            let git = self.git.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let paths = changed_paths(&git, &repo);
+                (repo, paths)
+            })
+            .await;
+            let (repo, paths) = match result {
+                Ok((repo, Ok(paths))) => (repo, paths),
+                Ok((repo, Err(e))) => {
+                    debug!(
+                        "Skipping changes of workspace {} in repo {}: {}",
+                        repo.workspace_id, repo.repo_name, e
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    error!("Workspace change check panicked: {}", e);
+                    continue;
+                }
+            };
+            WorkspaceChangedPaths::upsert(&self.db.pool, repo.workspace_id, repo.repo_id, &paths)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(task: u128, repo: u128, paths: &[&str]) -> ActiveWorkspaceChanges {
+        ActiveWorkspaceChanges {
+            workspace_id: Uuid::from_u128(task + 100),
+            task_id: Uuid::from_u128(task),
+            task_title: format!("Task {task}"),
+            repo_id: Uuid::from_u128(repo),
+            repo_name: format!("repo-{repo}"),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            checked_at: DateTime::<Utc>::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn reports_files_changed_by_several_tasks() {
+        let conflicts = find_conflicts(&[
+            changes(1, 9, &["src/auth.rs", "src/lib.rs", "README.md"]),
+            changes(2, 9, &["src/lib.rs", "src/auth.rs"]),
+            changes(3, 9, &["docs/guide.md"]),
+        ]);
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].repo_name, "repo-9");
+        assert_eq!(conflicts[0].paths, vec!["src/auth.rs", "src/lib.rs"]);
+        let task_ids: Vec<Uuid> = conflicts[0].tasks.iter().map(|t| t.task_id).collect();
+        assert_eq!(task_ids, vec![Uuid::from_u128(1), Uuid::from_u128(2)]);
+        assert_eq!(conflicts[0].tasks[1].workspace_id, Uuid::from_u128(102));
+    }
+
+    #[test]
+    fn same_path_in_different_repos_is_not_a_conflict() {
+        let conflicts = find_conflicts(&[
+            changes(1, 8, &["src/main.rs"]),
+            changes(2, 9, &["src/main.rs"]),
+        ]);
+
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn splits_conflicts_by_set_of_tasks() {
+        let conflicts = find_conflicts(&[
+            changes(1, 9, &["a.rs", "b.rs"]),
+            changes(2, 9, &["a.rs", "b.rs"]),
+            changes(3, 9, &["b.rs"]),
+        ]);
+
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].paths, vec!["a.rs"]);
+        assert_eq!(conflicts[0].tasks.len(), 2);
+        assert_eq!(conflicts[1].paths, vec!["b.rs"]);
+        assert_eq!(conflicts[1].tasks.len(), 3);
+    }
+}
@@ -0,0 +1,190 @@
+//! Warm-up orientation for column agents.
+//!
+//! Agents tend to open every column by listing the repository and reading the same few
+//! files. Columns with warm-up enabled do that up front instead: the layout of each repo
+//! and the start of its key files are collected once per workspace, cached in
+//! `workspace_orientations`, and prepended to the agent's prompt.
+
+use std::path::{Path, PathBuf};
+
+use db::models::workspace_orientation::WorkspaceOrientation;
+use ignore::WalkBuilder;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// How deep the repo layout goes (1 = top-level entries only)
+const LAYOUT_DEPTH: usize = 2;
+const MAX_LAYOUT_ENTRIES: usize = 150;
+/// Files read into the orientation when a repo has them, in this order
+const KEY_FILES: &[&str] = &[
+    "AGENTS.md",
+    "CLAUDE.md",
+    "README.md",
+    "README",
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "go.mod",
+    "Makefile",
+];
+const MAX_KEY_FILE_LINES: usize = 40;
+/// Upper bound for the whole orientation, so it never crowds out the task itself
+const MAX_ORIENTATION_CHARS: usize = 12_000;
+
+/// Paths of a repo down to `LAYOUT_DEPTH`, directories ending in `/`, skipping hidden and
+/// gitignored entries
+pub fn repo_layout(repo_dir: &Path) -> Vec<String> {
+    let mut entries: Vec<String> = WalkBuilder::new(repo_dir)
+        .max_depth(Some(LAYOUT_DEPTH))
+        .hidden(true)
+        .git_ignore(true)
+        .require_git(false)
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            name != "node_modules" && name != "target"
+        })
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.depth() > 0)
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(repo_dir).ok()?;
+            let mut path = relative.to_string_lossy().replace('\\', "/");
+            if entry.file_type().is_some_and(|t| t.is_dir()) {
+                path.push('/');
+            }
+            Some(path)
+        })
+        .collect();
+    entries.sort();
+    entries
+}
+
+/// First lines of a key file, or `None` if the repo doesn't have it
+fn key_file_excerpt(repo_dir: &Path, name: &str) -> Option<String> {
+    let content = std::fs::read_to_string(repo_dir.join(name)).ok()?;
+    let lines: Vec<&str> = content.lines().collect();
+    let mut excerpt = lines
+        .iter()
+        .take(MAX_KEY_FILE_LINES)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    if lines.len() > MAX_KEY_FILE_LINES {
+        excerpt.push_str(&format!(
+            "\n… ({} more lines)",
+            lines.len() - MAX_KEY_FILE_LINES
+        ));
+    }
+    Some(excerpt)
+}
+
+/// Orientation for the given repos (name and checkout directory)
+pub fn build_orientation(repos: &[(String, PathBuf)]) -> String {
+    let mut out = String::from(
+        "## Workspace orientation\n\nCollected before you started. Use it instead of \
+         re-exploring the repository; read files directly when you need more detail.\n",
+    );
+    for (name, dir) in repos {
+        out.push_str(&format!("\n### Repository `{name}`\n\n"));
+        let layout = repo_layout(dir);
+        out.push_str("```text\n");
+        for path in layout.iter().take(MAX_LAYOUT_ENTRIES) {
+            out.push_str(path);
+            out.push('\n');
+        }
+        if layout.len() > MAX_LAYOUT_ENTRIES {
+            out.push_str(&format!("… ({} more)\n", layout.len() - MAX_LAYOUT_ENTRIES));
+        }
+        out.push_str("```\n");
+
+        for file in KEY_FILES {
+            if let Some(excerpt) = key_file_excerpt(dir, file) {
+                out.push_str(&format!("\n#### {file}\n\n```\n{excerpt}\n```\n"));
+            }
+        }
+    }
+
+    if out.chars().count() > MAX_ORIENTATION_CHARS {
+        out = out.chars().take(MAX_ORIENTATION_CHARS).collect();
+        out.push_str("\n… (orientation truncated)\n");
+    }
+    out
+}
+
+/// The agent's prompt with the orientation in front of it
+pub fn with_orientation(orientation: &str, prompt: &str) -> String {
+    format!("{}\n---\n\n{}", orientation.trim_end(), prompt)
+}
+
+/// The workspace's orientation, built and cached on first use
+pub async fn orientation_for(
+    pool: &PgPool,
+    workspace_id: Uuid,
+    repos: Vec<(String, PathBuf)>,
+) -> Result<String, sqlx::Error> {
+    if let Some(cached) = WorkspaceOrientation::find(pool, workspace_id).await? {
+        return Ok(cached.content);
+    }
+    let content = tokio::task::spawn_blocking(move || build_orientation(&repos))
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Building workspace orientation panicked: {}", e);
+            String::new()
+        });
+    if content.is_empty() {
+        return Ok(content);
+    }
+    WorkspaceOrientation::upsert(pool, workspace_id, &content).await?;
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/nested/deep")).unwrap();
+        std::fs::create_dir_all(root.join("build")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(root.join("src/nested/deep/file.rs"), "").unwrap();
+        std::fs::write(root.join("build/out.bin"), "").unwrap();
+        std::fs::write(root.join(".env"), "SECRET=1\n").unwrap();
+        std::fs::write(root.join(".gitignore"), "build/\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn layout_is_shallow_and_skips_ignored_entries() {
+        let dir = repo();
+        assert_eq!(
+            repo_layout(dir.path()),
+            vec!["src/", "src/main.rs", "src/nested/"]
+        );
+    }
+
+    #[test]
+    fn orientation_includes_layout_and_key_files() {
+        let dir = repo();
+        let readme: Vec<String> = (1..=45).map(|i| format!("line {i}")).collect();
+        std::fs::write(dir.path().join("README.md"), readme.join("\n")).unwrap();
+
+        let orientation =
+            build_orientation(&[("web".to_string(), dir.path().to_path_buf())]);
+        assert!(orientation.contains("### Repository `web`"));
+        assert!(orientation.contains("src/main.rs\n"));
+        assert!(orientation.contains("#### README.md"));
+        assert!(orientation.contains("line 40\n… (5 more lines)"));
+        assert!(!orientation.contains("line 41"));
+        assert!(!orientation.contains("Cargo.toml"));
+    }
+
+    #[test]
+    fn orientation_goes_before_the_prompt() {
+        assert_eq!(
+            with_orientation("## Workspace orientation\n\nfoo\n", "Fix the bug"),
+            "## Workspace orientation\n\nfoo\n---\n\nFix the bug"
+        );
+    }
+}
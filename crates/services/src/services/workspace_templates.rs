@@ -0,0 +1,168 @@
+//! Workspace templates: files a project seeds into every new workspace.
+//!
+//! Unlike `copy_files`, which copies files from the project's repo, templates are
+//! stored with the project and may contain `{{variable}}` placeholders filled in
+//! for the workspace (task id, branch, column). A file the repo already has is
+//! kept unless the template is marked `overwrite`.
+
+use std::path::{Component, Path};
+
+use db::models::{
+    kanban_column::KanbanColumn, project::Project, task::Task, workspace::Workspace,
+    workspace_repo::WorkspaceRepo, workspace_template::WorkspaceTemplate,
+};
+use sqlx::PgPool;
+use utils::path::join_relative;
+
+/// Values of the placeholders available in template content for a workspace;
+/// `column` and `column_slug` are empty for a task outside any column
+pub fn template_variables(
+    workspace: &Workspace,
+    task: &Task,
+    project: &Project,
+    column: Option<&KanbanColumn>,
+) -> Vec<(&'static str, String)> {
+    vec![
+        ("task_id", task.id.to_string()),
+        ("task_title", task.title.clone()),
+        ("branch", workspace.branch.clone()),
+        ("workspace_id", workspace.id.to_string()),
+        ("project_id", project.id.to_string()),
+        ("project_name", project.name.clone()),
+        ("column", column.map(|c| c.name.clone()).unwrap_or_default()),
+        ("column_slug", column.map(|c| c.slug.clone()).unwrap_or_default()),
+    ]
+}
+
+/// Fill in `{{name}}` placeholders (spaces inside the braces are allowed); unknown
+/// ones are left as they are
+pub fn render(content: &str, variables: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = after[..end].trim();
+        match variables.iter().find(|(key, _)| *key == name) {
+            Some((_, value)) => out.push_str(value),
+            None => out.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Template paths must stay inside the workspace and out of `.git`
+pub fn validate_template_path(path: &str) -> Result<(), String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("path is required".to_string());
+    }
+    let components: Vec<_> = Path::new(path).components().collect();
+    if !components
+        .iter()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(format!(
+            "Invalid path '{path}': use a path relative to the workspace without '..'"
+        ));
+    }
+    if components.iter().any(|c| c.as_os_str() == ".git") {
+        return Err(format!("Invalid path '{path}': templates can't write into .git"));
+    }
+    Ok(())
+}
+
+/// Write the project's templates into a newly created workspace; returns how many
+/// files were written. Failures to write a file are logged and skipped.
+pub async fn seed_workspace(
+    pool: &PgPool,
+    workspace: &Workspace,
+    workspace_dir: &Path,
+) -> Result<usize, sqlx::Error> {
+    let Some(task) = workspace.parent_task(pool).await? else {
+        return Ok(0);
+    };
+    let templates = WorkspaceTemplate::find_by_project(pool, task.project_id).await?;
+    if templates.is_empty() {
+        return Ok(0);
+    }
+    let Some(project) = Project::find_by_id(pool, task.project_id).await? else {
+        return Ok(0);
+    };
+    let column = match task.column_id {
+        Some(column_id) => KanbanColumn::find_by_id(pool, column_id).await?,
+        None => None,
+    };
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let variables = template_variables(workspace, &task, &project, column.as_ref());
+
+    let mut written = 0;
+    for template in &templates {
+        let base = match template.repo_id {
+            None => workspace_dir.to_path_buf(),
+            Some(repo_id) => match repos.iter().find(|repo| repo.id == repo_id) {
+                Some(repo) => workspace_dir.join(&repo.name),
+                // The repo isn't part of this workspace
+                None => continue,
+            },
+        };
+        let target = join_relative(&base, &template.path);
+        if target.exists() && !template.overwrite {
+            tracing::debug!("Keeping existing {:?} over workspace template", target);
+            continue;
+        }
+
+        let content = render(&template.content, &variables);
+        let result = match target.parent() {
+            Some(parent) => tokio::fs::create_dir_all(parent).await,
+            None => Ok(()),
+        };
+        let result = match result {
+            Ok(()) => tokio::fs::write(&target, content).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => written += 1,
+            Err(e) => tracing::warn!("Failed to write workspace template {:?}: {}", target, e),
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_fills_known_placeholders() {
+        let variables = vec![
+            ("task_id", "42".to_string()),
+            ("branch", "vk/abc-fix-login".to_string()),
+        ];
+        assert_eq!(
+            render("Task {{task_id}} on {{ branch }}", &variables),
+            "Task 42 on vk/abc-fix-login"
+        );
+        // Unknown and unterminated placeholders are kept
+        assert_eq!(render("{{unknown}} {{task_id", &variables), "{{unknown}} {{task_id");
+        assert_eq!(render("no placeholders", &variables), "no placeholders");
+    }
+
+    #[test]
+    fn paths_must_stay_inside_the_workspace() {
+        assert!(validate_template_path("AGENTS.md").is_ok());
+        assert!(validate_template_path("tests/harness/stub.py").is_ok());
+        assert!(validate_template_path("./.editorconfig").is_ok());
+
+        assert!(validate_template_path("").is_err());
+        assert!(validate_template_path("../outside.txt").is_err());
+        assert!(validate_template_path("/etc/passwd").is_err());
+        assert!(validate_template_path(".git/hooks/pre-commit").is_err());
+    }
+}
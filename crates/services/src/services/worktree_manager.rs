@@ -8,7 +8,7 @@ use std::{
 use git2::{Error as GitError, Repository};
 use thiserror::Error;
 use tracing::{debug, info, trace};
-use utils::{path::normalize_macos_private_alias, shell::resolve_executable_path};
+use utils::{path::paths_equal, shell::resolve_executable_path};
 
 use super::git::{GitService, GitServiceError};
 
@@ -203,10 +203,9 @@ impl WorktreeManager {
             let gitdir_path = entry.path().join("gitdir");
             if gitdir_path.exists()
                 && let Ok(gitdir_content) = fs::read_to_string(&gitdir_path)
-                && normalize_macos_private_alias(Path::new(gitdir_content.trim()))
+                && Path::new(gitdir_content.trim())
                     .parent()
-                    .map(|p| p == worktree_path)
-                    .unwrap_or(false)
+                    .is_some_and(|p| paths_equal(p, worktree_path))
             {
                 return Ok(Some(entry.file_name().to_string_lossy().to_string()));
             }
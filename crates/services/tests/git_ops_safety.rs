@@ -5,7 +5,7 @@ use std::{
 };
 
 use git2::{PushOptions, Repository, build::CheckoutBuilder};
-use services::services::git::{GitCli, GitCliError, GitService};
+use services::services::git::{GitCli, GitCliError, GitService, MergeOptions};
 use tempfile::TempDir;
 // Avoid direct git CLI usage in tests; exercise GitService instead.
 
@@ -1406,3 +1406,76 @@ fn merge_base_ahead_of_task_should_error() {
         "Merge should error when base branch is ahead of task branch"
     );
 }
+
+#[test]
+fn merge_commit_option_keeps_task_commit_as_parent() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let s = GitService::new();
+
+    let task_oid = s.get_branch_oid(&repo_path, "feature").unwrap();
+    let sha = s
+        .merge_changes_with_options(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "merge commit",
+            MergeOptions {
+                merge_commit: true,
+                ..MergeOptions::default()
+            },
+        )
+        .unwrap();
+
+    let repo = Repository::open(&repo_path).unwrap();
+    let merged = repo
+        .find_commit(git2::Oid::from_str(&sha).unwrap())
+        .unwrap();
+    assert_eq!(merged.parent_count(), 2);
+    assert_eq!(merged.parent_id(1).unwrap().to_string(), task_oid);
+    assert_eq!(s.get_branch_oid(&repo_path, "main").unwrap(), sha);
+}
+
+#[test]
+fn merge_behind_base_only_when_up_to_date_not_required() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let s = GitService::new();
+
+    // main moves ahead with an unrelated change
+    let repo = Repository::open(&repo_path).unwrap();
+    checkout_branch(&repo, "main");
+    write_file(&repo_path, "other.txt", "main moved\n");
+    commit_all(&repo, "main moves ahead");
+    checkout_branch(&repo, "new-base");
+
+    let strict = s.merge_changes(&repo_path, &worktree_path, "feature", "main", "squash");
+    assert!(
+        strict.is_err(),
+        "default options require an up-to-date branch"
+    );
+
+    let sha = s
+        .merge_changes_with_options(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            MergeOptions {
+                require_up_to_date: false,
+                ..MergeOptions::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(s.get_branch_oid(&repo_path, "main").unwrap(), sha);
+
+    let tree = repo
+        .find_commit(git2::Oid::from_str(&sha).unwrap())
+        .unwrap()
+        .tree()
+        .unwrap();
+    assert!(tree.get_name("other.txt").is_some());
+    assert!(tree.get_name("feat.txt").is_some());
+}
@@ -398,6 +398,25 @@ fn worktree_diff_respects_path_filter() {
     );
 }
 
+#[test]
+fn changed_paths_include_committed_and_uncommitted_changes() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    write_file(&repo_path, "src/lib.rs", "base\n");
+    let s = GitService::new();
+    let _ = s.commit(&repo_path, "baseline").unwrap();
+
+    create_branch(&repo_path, "feature");
+    checkout_branch(&repo_path, "feature");
+    write_file(&repo_path, "src/lib.rs", "changed\n");
+    let _ = s.commit(&repo_path, "change lib").unwrap();
+    write_file(&repo_path, "src/new.rs", "new\n");
+
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let paths = s.get_changed_paths(&repo_path, &base_commit).unwrap();
+    assert_eq!(paths, vec!["src/lib.rs", "src/new.rs"]);
+}
+
 #[test]
 fn get_branch_oid_nonexistent_errors() {
     let td = TempDir::new().unwrap();
@@ -544,3 +563,113 @@ fn squash_merge_libgit2_sets_author_without_user() {
         assert_eq!(email.as_deref(), Some("noreply@vibekanban.com"));
     }
 }
+
+#[test]
+fn dry_run_patch_captures_commits_and_untracked_files_and_reapplies() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "src/lib.rs", "fn a() {}\n");
+    s.commit(&repo_path, "base").unwrap();
+    let base = s.get_head_info(&repo_path).unwrap().oid;
+
+    // The agent commits despite being told not to, and leaves more changes behind
+    write_file(&repo_path, "src/lib.rs", "fn a() {}\nfn b() {}\n");
+    s.commit(&repo_path, "agent commit").unwrap();
+    write_file(&repo_path, "src/new.rs", "fn c() {}\n");
+    write_file(&repo_path, ".vibe/summary.md", "done\n");
+
+    let patch = s.proposed_patch(&repo_path, Some(&base), ".vibe").unwrap();
+    assert!(patch.contains("fn b() {}"));
+    assert!(patch.contains("src/new.rs"));
+    assert!(!patch.contains("summary.md"));
+
+    s.discard_changes(&repo_path, Some(&base), ".vibe").unwrap();
+    assert_eq!(s.get_head_info(&repo_path).unwrap().oid, base);
+    assert!(!repo_path.join("src/new.rs").exists());
+
+    s.apply_patch(&repo_path, &patch, "Apply dry-run patch").unwrap();
+    assert_ne!(s.get_head_info(&repo_path).unwrap().oid, base);
+    assert_eq!(
+        fs::read_to_string(repo_path.join("src/lib.rs")).unwrap(),
+        "fn a() {}\nfn b() {}\n"
+    );
+    assert!(repo_path.join("src/new.rs").exists());
+}
+
+#[test]
+fn discardable_changes_include_untracked_files_but_not_the_kept_path() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "src/lib.rs", "fn a() {}\n");
+    s.commit(&repo_path, "base").unwrap();
+    assert!(!s.has_discardable_changes(&repo_path, ".vibe").unwrap());
+
+    write_file(&repo_path, ".vibe/decision.json", "{}\n");
+    assert!(!s.has_discardable_changes(&repo_path, ".vibe").unwrap());
+
+    write_file(&repo_path, "notes.md", "work in progress\n");
+    assert!(s.has_discardable_changes(&repo_path, ".vibe").unwrap());
+}
+
+#[test]
+fn apply_patch_commits_a_plain_unified_diff_and_rejects_stale_ones() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "src/lib.rs", "fn a() {}\n");
+    s.commit(&repo_path, "base").unwrap();
+    let base = s.get_head_info(&repo_path).unwrap().oid;
+
+    // A diff as an external tool or a person would write it, without index lines
+    let patch = "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1,2 @@\n fn a() {}\n+fn b() {}\n";
+    let commit = s
+        .apply_patch(&repo_path, patch, "Apply external patch")
+        .unwrap();
+    assert_eq!(s.get_head_info(&repo_path).unwrap().oid, commit);
+    assert_ne!(commit, base);
+    assert_eq!(
+        fs::read_to_string(repo_path.join("src/lib.rs")).unwrap(),
+        "fn a() {}\nfn b() {}\n"
+    );
+
+    // Applying it again fails without touching the worktree or making a commit
+    assert!(s.apply_patch(&repo_path, patch, "Apply again").is_err());
+    assert_eq!(s.get_head_info(&repo_path).unwrap().oid, commit);
+    assert!(s.is_worktree_clean(&repo_path).unwrap());
+}
+
+#[test]
+fn exports_a_branch_as_patches_and_as_a_bundle() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "README.md", "base\n");
+    s.commit(&repo_path, "base").unwrap();
+    create_branch(&repo_path, "vk/feature");
+    checkout_branch(&repo_path, "vk/feature");
+    write_file(&repo_path, "src/a.rs", "fn a() {}\n");
+    s.commit(&repo_path, "Add a").unwrap();
+    write_file(&repo_path, "src/b.rs", "fn b() {}\n");
+    s.commit(&repo_path, "Add b").unwrap();
+
+    let base = s.get_base_commit(&repo_path, "vk/feature", "main").unwrap();
+    let patches = s
+        .format_patches(&repo_path, "vk/feature", &base, "PATCH app")
+        .unwrap();
+    assert!(patches.contains("Subject: [PATCH app 1/2] Add a"));
+    assert!(patches.contains("Subject: [PATCH app 2/2] Add b"));
+    assert!(!patches.contains("Subject: [PATCH app 1/2] base"));
+
+    let bundle = s
+        .bundle_branches(&[(repo_path.as_path(), "vk/feature", "app/vk/feature".to_string())])
+        .unwrap();
+    let bundle_path = td.path().join("export.bundle");
+    fs::write(&bundle_path, bundle).unwrap();
+    let heads = GitCli::new()
+        .git(td.path(), ["bundle", "list-heads", &*bundle_path.to_string_lossy()])
+        .unwrap();
+    let head = s.get_head_info(&repo_path).unwrap().oid;
+    assert_eq!(heads.trim(), format!("{head} refs/heads/app/vk/feature"));
+}
@@ -1,3 +1,4 @@
+pub mod notifications;
 pub mod oauth;
 pub mod organizations;
 pub mod projects;
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum UserNotificationKind {
+    Mention,
+    Assigned,
+}
+
+impl UserNotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserNotificationKind::Mention => "mention",
+            UserNotificationKind::Assigned => "assigned",
+        }
+    }
+}
+
+impl TryFrom<String> for UserNotificationKind {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "mention" => Ok(UserNotificationKind::Mention),
+            "assigned" => Ok(UserNotificationKind::Assigned),
+            other => Err(format!("unknown notification kind: {other}")),
+        }
+    }
+}
+
+/// Inbox entry for a user, e.g. a mention in a shared task's description
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, TS)]
+#[ts(export)]
+pub struct UserNotification {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub shared_task_id: Option<Uuid>,
+    pub actor_user_id: Option<Uuid>,
+    pub actor_username: Option<String>,
+    #[sqlx(try_from = "String")]
+    pub kind: UserNotificationKind,
+    pub message: String,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ListNotificationsResponse {
+    pub notifications: Vec<UserNotification>,
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MarkNotificationsReadRequest {
+    /// Notifications to mark as read; all unread notifications when omitted
+    #[serde(default)]
+    pub ids: Option<Vec<Uuid>>,
+}
@@ -27,3 +27,37 @@ pub struct RemoteProjectMembersResponse {
     pub organization_id: Uuid,
     pub members: Vec<OrganizationMemberWithProfile>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, TS)]
+#[ts(export)]
+pub struct ProjectAccessGrant {
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    pub granted_by_user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Who can see a remote project. Without grants every organization member has access;
+/// once a user is granted access, only the owner, organization admins and granted
+/// users do.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ProjectAccessResponse {
+    pub project_id: Uuid,
+    pub owner_user_id: Option<Uuid>,
+    pub grants: Vec<ProjectAccessGrant>,
+    /// True while the project has no grants, so every organization member can see it
+    pub organization_wide: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GrantProjectAccessRequest {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TransferProjectOwnershipRequest {
+    pub new_owner_user_id: Uuid,
+}
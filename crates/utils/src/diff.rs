@@ -42,6 +42,36 @@ pub enum DiffChangeKind {
     PermissionChange,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+/// One line of a hunk with its line numbers on each side
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    /// Line number in the old file; None for added lines
+    pub old_line: Option<u32>,
+    /// Line number in the new file; None for removed lines
+    pub new_line: Option<u32>,
+    pub content: String,
+}
+
+// Structured hunks for line-level review
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHunk {
+    pub header: String,
+    pub old_start: u32,
+    pub new_start: u32,
+    pub lines: Vec<DiffLine>,
+}
+
 // ==============================
 // Unified diff utility functions
 // ==============================
@@ -244,3 +274,97 @@ pub fn normalize_unified_diff(file_path: &str, unified_diff: &str) -> String {
     let hunks = extract_unified_diff_hunks(unified_diff);
     concatenate_diff_hunks(file_path, &hunks)
 }
+
+/// Parses a single unified diff hunk (starting with its `@@` header) into numbered lines.
+pub fn parse_diff_hunk(hunk: &str) -> Option<DiffHunk> {
+    let mut lines = hunk.lines();
+    let header = lines.next()?.trim_end().to_string();
+    let ranges = header.strip_prefix("@@ ")?.split(" @@").next()?;
+    let mut parts = ranges.split_whitespace();
+    let range_start = |part: Option<&str>, sign: char| -> Option<u32> {
+        part?.strip_prefix(sign)?.split(',').next()?.parse().ok()
+    };
+    let old_start = range_start(parts.next(), '-')?;
+    let new_start = range_start(parts.next(), '+')?;
+
+    let (mut old_line, mut new_line) = (old_start, new_start);
+    let mut parsed = Vec::new();
+    for line in lines {
+        let (kind, content) = match line.split_at_checked(1) {
+            Some(("+", content)) => (DiffLineKind::Added, content),
+            Some(("-", content)) => (DiffLineKind::Removed, content),
+            Some((" ", content)) => (DiffLineKind::Context, content),
+            // "\ No newline at end of file" and other non-content lines
+            _ => continue,
+        };
+        let (old, new) = match kind {
+            DiffLineKind::Added => (None, Some(new_line)),
+            DiffLineKind::Removed => (Some(old_line), None),
+            DiffLineKind::Context => (Some(old_line), Some(new_line)),
+        };
+        old_line += u32::from(old.is_some());
+        new_line += u32::from(new.is_some());
+        parsed.push(DiffLine {
+            kind,
+            old_line: old,
+            new_line: new,
+            content: content.to_string(),
+        });
+    }
+
+    Some(DiffHunk {
+        header,
+        old_start,
+        new_start,
+        lines: parsed,
+    })
+}
+
+/// Structured hunks between two text snapshots, with 3 lines of context.
+pub fn create_diff_hunks(old: &str, new: &str) -> Vec<DiffHunk> {
+    create_unified_diff_hunks(old, new)
+        .iter()
+        .filter_map(|hunk| parse_diff_hunk(hunk))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_hunk_lines_on_both_sides() {
+        let hunk = parse_diff_hunk("@@ -10,3 +10,3 @@ fn main\n a\n-b\n+c\n d\n").unwrap();
+        assert_eq!((hunk.old_start, hunk.new_start), (10, 10));
+        let numbers: Vec<_> = hunk
+            .lines
+            .iter()
+            .map(|l| (l.old_line, l.new_line))
+            .collect();
+        assert_eq!(
+            numbers,
+            vec![
+                (Some(10), Some(10)),
+                (Some(11), None),
+                (None, Some(11)),
+                (Some(12), Some(12)),
+            ]
+        );
+    }
+
+    #[test]
+    fn creates_hunks_from_snapshots() {
+        let hunks = create_diff_hunks("one\ntwo\nthree\n", "one\n2\nthree\n");
+        assert_eq!(hunks.len(), 1);
+        let added: Vec<_> = hunks[0]
+            .lines
+            .iter()
+            .filter(|l| l.kind == DiffLineKind::Added)
+            .collect();
+        assert_eq!(added.len(), 1);
+        assert_eq!(
+            (added[0].new_line, added[0].content.as_str()),
+            (Some(2), "2")
+        );
+    }
+}
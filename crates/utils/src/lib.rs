@@ -11,8 +11,11 @@ pub mod git;
 pub mod jwt;
 pub mod log_msg;
 pub mod msg_store;
+pub mod output_limit;
 pub mod path;
 pub mod port_file;
+pub mod rate_limit;
+pub mod redact;
 pub mod response;
 pub mod sentry;
 pub mod shell;
@@ -0,0 +1,201 @@
+/// A line longer than this past the cap is cut rather than completed
+const MAX_LINE_OVERRUN: usize = 64 * 1024;
+
+/// Per-stream cap on process output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputLimit {
+    /// Total bytes forwarded for the stream, including the preserved tail
+    pub max_bytes: usize,
+    /// Bytes at the end of the output that are kept once the cap is hit
+    pub tail_bytes: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum State {
+    Passing,
+    /// Over the cap, but the current line is forwarded to its end so line-based
+    /// parsers (e.g. JSON-per-line agent output) never see half a line
+    FinishingLine {
+        overrun: usize,
+    },
+    Truncating,
+}
+
+/// Caps a stream of output chunks. Output up to `max_bytes - tail_bytes` passes
+/// through; after that whole lines are dropped, a truncation marker line is emitted,
+/// and the last `tail_bytes` are flushed by [`OutputLimiter::finish`].
+#[derive(Debug)]
+pub struct OutputLimiter {
+    head_bytes: usize,
+    tail_bytes: usize,
+    forwarded: usize,
+    state: State,
+    dropped: usize,
+    tail: String,
+}
+
+fn floor_char_boundary(s: &str, mut index: usize) -> usize {
+    if index >= s.len() {
+        return s.len();
+    }
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+impl OutputLimiter {
+    pub fn new(limit: OutputLimit) -> Self {
+        Self {
+            head_bytes: limit.max_bytes.saturating_sub(limit.tail_bytes),
+            tail_bytes: limit.tail_bytes.min(limit.max_bytes),
+            forwarded: 0,
+            state: State::Passing,
+            dropped: 0,
+            tail: String::new(),
+        }
+    }
+
+    pub fn is_truncating(&self) -> bool {
+        self.state == State::Truncating
+    }
+
+    /// The part of `chunk` to forward now, if any
+    pub fn push(&mut self, chunk: &str) -> Option<String> {
+        let was_truncating = self.state == State::Truncating;
+        let mut out = String::new();
+        let mut rest = chunk;
+
+        if self.state == State::Passing {
+            let room = self.head_bytes.saturating_sub(self.forwarded);
+            if rest.len() <= room {
+                self.forwarded += rest.len();
+                return (!rest.is_empty()).then(|| rest.to_string());
+            }
+            let (head, remainder) = rest.split_at(floor_char_boundary(rest, room));
+            out.push_str(head);
+            rest = remainder;
+            self.state = if out.is_empty() || out.ends_with('\n') {
+                State::Truncating
+            } else {
+                State::FinishingLine { overrun: 0 }
+            };
+        }
+
+        if let State::FinishingLine { overrun } = self.state {
+            match rest.find('\n') {
+                Some(i) if overrun + i < MAX_LINE_OVERRUN => {
+                    out.push_str(&rest[..=i]);
+                    rest = &rest[i + 1..];
+                    self.state = State::Truncating;
+                }
+                _ if overrun + rest.len() < MAX_LINE_OVERRUN => {
+                    out.push_str(rest);
+                    self.state = State::FinishingLine {
+                        overrun: overrun + rest.len(),
+                    };
+                    rest = "";
+                }
+                _ => {
+                    let cut = floor_char_boundary(rest, MAX_LINE_OVERRUN - overrun);
+                    out.push_str(&rest[..cut]);
+                    out.push('\n');
+                    rest = &rest[cut..];
+                    self.state = State::Truncating;
+                }
+            }
+        }
+        if !was_truncating && self.state == State::Truncating {
+            out.push_str(&format!(
+                "[output truncated after {} KB; the last {} KB will be shown when the process \
+                 exits]\n",
+                self.head_bytes / 1024,
+                self.tail_bytes / 1024
+            ));
+        }
+
+        if self.state == State::Truncating && !rest.is_empty() {
+            self.tail.push_str(rest);
+            if self.tail.len() > self.tail_bytes {
+                let excess = self.tail.len() - self.tail_bytes;
+                // Keep the tail starting at a line boundary where possible
+                let cut = match self.tail.as_bytes()[excess - 1..]
+                    .iter()
+                    .position(|b| *b == b'\n')
+                {
+                    Some(i) => excess + i,
+                    None => {
+                        let mut cut = excess;
+                        while !self.tail.is_char_boundary(cut) {
+                            cut += 1;
+                        }
+                        cut
+                    }
+                };
+                self.dropped += cut;
+                self.tail.drain(..cut);
+            }
+        }
+
+        self.forwarded += out.len();
+        (!out.is_empty()).then_some(out)
+    }
+
+    /// Call when the stream ends: the omitted-bytes marker followed by the preserved tail
+    pub fn finish(&mut self) -> Option<String> {
+        if self.state != State::Truncating || (self.dropped == 0 && self.tail.is_empty()) {
+            return None;
+        }
+        let tail = std::mem::take(&mut self.tail);
+        let dropped = std::mem::take(&mut self.dropped);
+        Some(format!("[{dropped} bytes of output omitted]\n{tail}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(max_bytes: usize, tail_bytes: usize) -> OutputLimiter {
+        OutputLimiter::new(OutputLimit {
+            max_bytes,
+            tail_bytes,
+        })
+    }
+
+    #[test]
+    fn passes_output_under_the_limit() {
+        let mut limiter = limiter(100, 20);
+        assert_eq!(limiter.push("hello\n").as_deref(), Some("hello\n"));
+        assert_eq!(limiter.finish(), None);
+    }
+
+    #[test]
+    fn finishes_the_current_line_before_truncating() {
+        let mut limiter = limiter(10, 0);
+        let out = limiter
+            .push("12345678{\"json\":true}\nnext line\n")
+            .unwrap();
+        assert!(out.starts_with("12345678{\"json\":true}\n[output truncated"));
+        assert!(limiter.is_truncating());
+        assert_eq!(limiter.push("more\n"), None);
+        assert_eq!(
+            limiter.finish().as_deref(),
+            Some("[15 bytes of output omitted]\n")
+        );
+    }
+
+    #[test]
+    fn keeps_the_tail_from_a_line_boundary() {
+        let mut limiter = limiter(16, 10);
+        limiter.push("aaaa\n");
+        limiter.push("bbbb\n");
+        limiter.push("cccc\n");
+        limiter.push("dddd\n");
+        limiter.push("eeee\n");
+        assert_eq!(
+            limiter.finish().as_deref(),
+            Some("[5 bytes of output omitted]\ndddd\neeee\n")
+        );
+    }
+}
@@ -8,8 +8,8 @@ pub const VIBE_IMAGES_DIR: &str = ".vibe-images";
 pub fn make_path_relative(path: &str, worktree_path: &str) -> String {
     tracing::trace!("Making path relative: {} -> {}", path, worktree_path);
 
-    let path_obj = normalize_macos_private_alias(Path::new(&path));
-    let worktree_path_obj = normalize_macos_private_alias(Path::new(worktree_path));
+    let path_obj = normalize_path_aliases(Path::new(&path));
+    let worktree_path_obj = normalize_path_aliases(Path::new(worktree_path));
 
     // If path is already relative, return as is
     if path_obj.is_relative() {
@@ -17,7 +17,7 @@ pub fn make_path_relative(path: &str, worktree_path: &str) -> String {
     }
 
     if let Ok(relative_path) = path_obj.strip_prefix(&worktree_path_obj) {
-        let result = relative_path.to_string_lossy().to_string();
+        let result = display_relative(relative_path);
         tracing::trace!("Successfully made relative: '{}' -> '{}'", path, result);
         if result.is_empty() {
             return ".".to_string();
@@ -45,7 +45,7 @@ pub fn make_path_relative(path: &str, worktree_path: &str) -> String {
 
             match canon_path.strip_prefix(&canon_worktree) {
                 Ok(relative_path) => {
-                    let result = relative_path.to_string_lossy().to_string();
+                    let result = display_relative(relative_path);
                     tracing::debug!(
                         "Successfully made relative with canonical paths: '{}' -> '{}'",
                         path,
@@ -101,6 +101,82 @@ pub fn normalize_macos_private_alias<P: AsRef<Path>>(p: P) -> PathBuf {
     p.to_path_buf()
 }
 
+/// Strip the verbatim prefix `canonicalize` adds on Windows (`\\?\C:\...`,
+/// `\\?\UNC\server\share\...`) without resolving paths. The Windows counterpart of
+/// [`normalize_macos_private_alias`], so canonical paths match stored ones such as a
+/// workspace's `container_ref`.
+pub fn normalize_windows_verbatim_prefix<P: AsRef<Path>>(p: P) -> PathBuf {
+    let p = p.as_ref();
+    if cfg!(windows)
+        && let Some(s) = p.to_str()
+        && let Some(stripped) = strip_verbatim_prefix(s)
+    {
+        return PathBuf::from(stripped);
+    }
+    p.to_path_buf()
+}
+
+fn strip_verbatim_prefix(s: &str) -> Option<String> {
+    if let Some(rest) = s.strip_prefix(r"\\?\UNC\") {
+        return Some(format!(r"\\{rest}"));
+    }
+    // Only drive paths have a plain equivalent; other verbatim paths are kept
+    let rest = s.strip_prefix(r"\\?\")?;
+    let bytes = rest.as_bytes();
+    (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+        .then(|| rest.to_string())
+}
+
+/// Remove the platform aliases of a path (macOS `/private`, Windows verbatim prefix)
+pub fn normalize_path_aliases<P: AsRef<Path>>(p: P) -> PathBuf {
+    normalize_windows_verbatim_prefix(normalize_macos_private_alias(p))
+}
+
+/// Whether two paths name the same location once platform aliases are removed.
+/// Windows paths are compared case-insensitively and regardless of separator.
+pub fn paths_equal<A: AsRef<Path>, B: AsRef<Path>>(a: A, b: B) -> bool {
+    let a = normalize_path_aliases(a);
+    let b = normalize_path_aliases(b);
+    if cfg!(windows) {
+        let key = |p: &Path| {
+            p.to_string_lossy()
+                .replace('/', "\\")
+                .trim_end_matches('\\')
+                .to_lowercase()
+        };
+        key(&a) == key(&b)
+    } else {
+        a == b
+    }
+}
+
+/// Join a relative path such as an agent working dir onto `base`. On Windows both
+/// `/` and `\` separate components, so `api/src` doesn't end up as `C:\ws\api/src`.
+pub fn join_relative(base: &Path, relative: &str) -> PathBuf {
+    if Path::new(relative).is_absolute() {
+        return PathBuf::from(relative);
+    }
+    let separators: &[char] = if cfg!(windows) { &['/', '\\'] } else { &['/'] };
+    let mut path = base.to_path_buf();
+    for part in relative
+        .split(separators)
+        .filter(|part| !part.is_empty() && *part != ".")
+    {
+        path.push(part);
+    }
+    path
+}
+
+/// A relative path as shown to users and agents: `/`-separated on every platform
+fn display_relative(relative: &Path) -> String {
+    let result = relative.to_string_lossy().to_string();
+    if cfg!(windows) {
+        result.replace('\\', "/")
+    } else {
+        result
+    }
+}
+
 pub fn get_vibe_kanban_temp_dir() -> std::path::PathBuf {
     let dir_name = if cfg!(debug_assertions) {
         "vibe-kanban-dev"
@@ -114,8 +190,16 @@ pub fn get_vibe_kanban_temp_dir() -> std::path::PathBuf {
     } else if cfg!(target_os = "linux") {
         // Linux: use /var/tmp instead of /tmp to avoid RAM usage
         std::path::PathBuf::from("/var/tmp").join(dir_name)
+    } else if cfg!(windows) {
+        // Windows: the temp dir can contain 8.3 short names (e.g. `RUNNER~1`); use its
+        // long form so worktree paths match what agents see as their current directory
+        let temp_dir = std::env::temp_dir();
+        std::fs::canonicalize(&temp_dir)
+            .map(normalize_windows_verbatim_prefix)
+            .unwrap_or(temp_dir)
+            .join(dir_name)
     } else {
-        // Windows and other platforms: use temp dir with vibe-kanban subdirectory
+        // Other platforms: use temp dir with vibe-kanban subdirectory
         std::env::temp_dir().join(dir_name)
     }
 }
@@ -150,6 +234,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_strip_verbatim_prefix() {
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\C:\Users\dev\worktrees\vk-test").as_deref(),
+            Some(r"C:\Users\dev\worktrees\vk-test")
+        );
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\UNC\server\share\repo").as_deref(),
+            Some(r"\\server\share\repo")
+        );
+        // Device paths and plain paths are left alone
+        assert_eq!(strip_verbatim_prefix(r"\\?\Volume{abc}\repo"), None);
+        assert_eq!(strip_verbatim_prefix(r"C:\Users\dev"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_join_relative() {
+        let base = Path::new("/tmp/ws");
+        assert_eq!(join_relative(base, "api/src"), PathBuf::from("/tmp/ws/api/src"));
+        assert_eq!(join_relative(base, "./api/"), PathBuf::from("/tmp/ws/api"));
+        assert_eq!(join_relative(base, ""), PathBuf::from("/tmp/ws"));
+        assert_eq!(join_relative(base, "/elsewhere"), PathBuf::from("/elsewhere"));
+    }
+
     #[cfg(target_os = "macos")]
     #[test]
     fn test_make_path_relative_macos_private_alias() {
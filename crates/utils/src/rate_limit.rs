@@ -0,0 +1,143 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Windows kept before expired ones are swept
+const PRUNE_THRESHOLD: usize = 4096;
+
+/// Requests allowed per window for a single client key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub window: Duration,
+}
+
+impl RateLimit {
+    pub fn per_minute(requests: u32) -> Self {
+        Self {
+            requests,
+            window: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Outcome of a rate limit check, carrying the values for `RateLimit-*` headers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Time until the current window resets
+    pub reset: Duration,
+}
+
+#[derive(Debug)]
+struct Window {
+    started: Instant,
+    window: Duration,
+    count: u32,
+}
+
+/// Fixed-window request counters keyed by client (IP address or token)
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    windows: HashMap<String, Window>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a request for `key` and decide whether it is allowed. Rejected requests
+    /// are not counted, so a throttled client recovers as soon as the window resets.
+    pub fn check(&mut self, key: &str, limit: RateLimit, now: Instant) -> RateDecision {
+        if self.windows.len() >= PRUNE_THRESHOLD {
+            self.prune(now);
+        }
+
+        let window = self
+            .windows
+            .entry(key.to_string())
+            .or_insert_with(|| Window {
+                started: now,
+                window: limit.window,
+                count: 0,
+            });
+        if now.duration_since(window.started) >= limit.window {
+            window.started = now;
+            window.count = 0;
+        }
+        window.window = limit.window;
+
+        let allowed = window.count < limit.requests;
+        if allowed {
+            window.count += 1;
+        }
+        RateDecision {
+            allowed,
+            limit: limit.requests,
+            remaining: limit.requests.saturating_sub(window.count),
+            reset: limit
+                .window
+                .saturating_sub(now.duration_since(window.started)),
+        }
+    }
+
+    /// Drop windows that have already ended
+    pub fn prune(&mut self, now: Instant) {
+        self.windows
+            .retain(|_, window| now.duration_since(window.started) < window.window);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_requests_over_the_limit() {
+        let mut limiter = RateLimiter::new();
+        let now = Instant::now();
+        let limit = RateLimit::per_minute(2);
+
+        assert_eq!(limiter.check("ip:1", limit, now).remaining, 1);
+        assert_eq!(limiter.check("ip:1", limit, now).remaining, 0);
+        let rejected = limiter.check("ip:1", limit, now + Duration::from_secs(15));
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.reset, Duration::from_secs(45));
+
+        // Other clients have their own window
+        assert!(limiter.check("ip:2", limit, now).allowed);
+    }
+
+    #[test]
+    fn window_resets_after_it_ends() {
+        let mut limiter = RateLimiter::new();
+        let now = Instant::now();
+        let limit = RateLimit::per_minute(1);
+
+        assert!(limiter.check("token:a", limit, now).allowed);
+        assert!(!limiter.check("token:a", limit, now).allowed);
+        let later = limiter.check("token:a", limit, now + Duration::from_secs(60));
+        assert!(later.allowed);
+        assert_eq!(later.reset, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn prune_drops_expired_windows() {
+        let mut limiter = RateLimiter::new();
+        let now = Instant::now();
+        limiter.check("old", RateLimit::per_minute(5), now);
+        limiter.check(
+            "new",
+            RateLimit::per_minute(5),
+            now + Duration::from_secs(50),
+        );
+
+        limiter.prune(now + Duration::from_secs(70));
+        assert_eq!(limiter.windows.len(), 1);
+        assert!(limiter.windows.contains_key("new"));
+    }
+}
@@ -613,8 +613,17 @@ const getToolStatusAppearance = (status: ToolStatus): ToolStatusAppearance => {
  *******************/
 
 export const DisplayConversationEntryMaxWidth = (props: Props) => {
+  // Entries produced while a board agent was active are marked with its color
+  const agentColor =
+    'entry_type' in props.entry &&
+    props.entry.entry_type.type !== 'agent_switch'
+      ? props.entry.agent_color
+      : null;
   return (
-    <div className="mx-auto w-full max-w-[50rem]">
+    <div
+      className={`mx-auto w-full max-w-[50rem] ${agentColor ? 'border-l-2' : ''}`}
+      style={agentColor ? { borderLeftColor: agentColor } : undefined}
+    >
       <DisplayConversationEntry {...props} />
     </div>
   );
@@ -45,6 +45,7 @@ const ProjectFormDialogImpl = NiceModal.create<ProjectFormDialogProps>(() => {
         name: projectName,
         repositories: [{ display_name: projectName, git_repo_path: repo.path }],
         board_id: null,
+        workflow_template_id: null,
       };
 
       createProjectMutate(createData);
@@ -13,6 +13,13 @@ import {
   AlertCircle,
   FileText,
   Zap,
+  RotateCcw,
+  MessageSquare,
+  Tag,
+  Paperclip,
+  FolderSync,
+  GitMerge,
+  FileDiff,
 } from 'lucide-react';
 import { Badge } from '@/components/ui/badge';
 import { useTaskEvents } from '@/hooks';
@@ -94,6 +101,41 @@ const EVENT_CONFIG: Record<
     label: 'State changed',
     color: 'bg-teal-500/10 text-teal-500 border-teal-500/20',
   },
+  rollback: {
+    icon: RotateCcw,
+    label: 'Rolled back',
+    color: 'bg-rose-500/10 text-rose-500 border-rose-500/20',
+  },
+  reviewer_feedback: {
+    icon: MessageSquare,
+    label: 'Reviewer feedback',
+    color: 'bg-orange-500/10 text-orange-500 border-orange-500/20',
+  },
+  labels_changed: {
+    icon: Tag,
+    label: 'Labels updated',
+    color: 'bg-pink-500/10 text-pink-500 border-pink-500/20',
+  },
+  artifacts_collected: {
+    icon: Paperclip,
+    label: 'Artifacts collected',
+    color: 'bg-sky-500/10 text-sky-500 border-sky-500/20',
+  },
+  workspace_recreated: {
+    icon: FolderSync,
+    label: 'Workspace recreated',
+    color: 'bg-amber-500/10 text-amber-500 border-amber-500/20',
+  },
+  shared_task_conflict: {
+    icon: GitMerge,
+    label: 'Shared copy conflict',
+    color: 'bg-rose-500/10 text-rose-500 border-rose-500/20',
+  },
+  patch_applied: {
+    icon: FileDiff,
+    label: 'Patch applied',
+    color: 'bg-teal-500/10 text-teal-500 border-teal-500/20',
+  },
 };
 
 const ACTOR_ICONS: Record<ActorType, typeof User> = {
@@ -178,6 +220,63 @@ function EventItem({ event }: { event: TaskEventWithNames }) {
         }
         return config.label;
       }
+      case 'rollback':
+        return event.to_column_name
+          ? `Rolled back to ${event.to_column_name}`
+          : config.label;
+      case 'reviewer_feedback': {
+        const meta = event.metadata as {
+          version?: number;
+          feedback?: string;
+        } | null;
+        if (meta?.feedback) {
+          return `${config.label} (v${meta.version ?? 1}): ${meta.feedback}`;
+        }
+        return config.label;
+      }
+      case 'labels_changed': {
+        const meta = event.metadata as {
+          added?: string[];
+          removed?: string[];
+        } | null;
+        const added = meta?.added?.length ?? 0;
+        const removed = meta?.removed?.length ?? 0;
+        return `${config.label} (+${added}, −${removed})`;
+      }
+      case 'artifacts_collected': {
+        const meta = event.metadata as { names?: string[] } | null;
+        const names = meta?.names ?? [];
+        if (names.length === 0) return config.label;
+        const shown = names.slice(0, 3).join(', ');
+        const more = names.length > 3 ? ` +${names.length - 3} more` : '';
+        return `${config.label}: ${shown}${more}`;
+      }
+      case 'workspace_recreated': {
+        const meta = event.metadata as { repos?: string[] } | null;
+        const repos = meta?.repos ?? [];
+        if (repos.length === 0) return config.label;
+        return `${config.label}: ${repos.join(', ')}`;
+      }
+      case 'shared_task_conflict': {
+        const meta = event.metadata as {
+          fields?: string[];
+          resolution?: string | null;
+        } | null;
+        const fields = meta?.fields?.join(', ') ?? '';
+        const outcome = meta?.resolution
+          ? `kept ${meta.resolution} version`
+          : 'awaiting resolution';
+        return `${config.label} (${fields}): ${outcome}`;
+      }
+      case 'patch_applied': {
+        const meta = event.metadata as {
+          repo?: string;
+          source?: string;
+        } | null;
+        const source = meta?.source?.replace('_', '-') ?? 'external';
+        const repo = meta?.repo ? ` to ${meta.repo}` : '';
+        return `${config.label}${repo} (${source})`;
+      }
       default:
         return config.label;
     }
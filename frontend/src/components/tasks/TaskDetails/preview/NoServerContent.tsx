@@ -123,7 +123,7 @@ export function NoServerContent({
 
     const repos = projectRepos.map((repo) => ({
       repo_id: repo.id,
-      target_branch: 'main',
+      target_branch: null,
     }));
 
     createAndStart.mutate({
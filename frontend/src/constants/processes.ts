@@ -6,12 +6,16 @@ export const PROCESS_RUN_REASONS = {
   CLEANUP_SCRIPT: 'cleanupscript' as ExecutionProcessRunReason,
   CODING_AGENT: 'codingagent' as ExecutionProcessRunReason,
   DEV_SERVER: 'devserver' as ExecutionProcessRunReason,
+  MANUAL_RUN: 'manualrun' as ExecutionProcessRunReason,
 } as const;
 
 export const isCodingAgent = (
   runReason: ExecutionProcessRunReason
 ): boolean => {
-  return runReason === PROCESS_RUN_REASONS.CODING_AGENT;
+  return (
+    runReason === PROCESS_RUN_REASONS.CODING_AGENT ||
+    runReason === PROCESS_RUN_REASONS.MANUAL_RUN
+  );
 };
 
 export const shouldShowInLogs = (
@@ -48,6 +48,7 @@ export const ExecutionProcessesProvider: React.FC<{
       visible.some(
         (process) =>
           (process.run_reason === 'codingagent' ||
+            process.run_reason === 'manualrun' ||
             process.run_reason === 'setupscript' ||
             process.run_reason === 'cleanupscript') &&
           process.status === 'running'
@@ -121,7 +121,8 @@ export const useConversationHistory = ({
       (ep) =>
         ep.run_reason === 'setupscript' ||
         ep.run_reason === 'cleanupscript' ||
-        ep.run_reason === 'codingagent'
+        ep.run_reason === 'codingagent' ||
+        ep.run_reason === 'manualrun'
     );
   }, [executionProcessesRaw]);
 
@@ -330,6 +331,12 @@ export const useConversationHistory = ({
               case 'ToolInstallScript':
                 toolName = 'Tool Install Script';
                 break;
+              case 'ColumnEnterScript':
+                toolName = 'Column Entry Script';
+                break;
+              case 'ColumnExitScript':
+                toolName = 'Column Exit Script';
+                break;
               default:
                 return [];
             }
@@ -384,6 +391,9 @@ export const useConversationHistory = ({
               },
               content: toolName,
               timestamp: null,
+              // Scripts of an agent's stage are attributed to the agent
+              agent_id: p.executionProcess.executor_action.agent?.agent_id,
+              agent_color: p.executionProcess.executor_action.agent?.agent_color,
             };
             const toolPatch: PatchType = {
               type: 'NORMALIZED_ENTRY',
@@ -37,7 +37,8 @@ export interface UseProjectTasksResult {
 /**
  * Stream tasks for a project via WebSocket (JSON Patch) and expose as array + map.
  * Server sends initial snapshot: replace /tasks with an object keyed by id.
- * Live updates arrive at /tasks/<id> via add/replace/remove operations.
+ * Live updates arrive at /tasks/<id> via add/replace/remove operations, or at
+ * /tasks/<id>/<field> when only some fields of a task changed.
  */
 export const useProjectTasks = (projectId: string): UseProjectTasksResult => {
   const { project } = useProject();
@@ -7,6 +7,9 @@ import {
   CreateBoard,
   UpdateBoard,
   KanbanColumn,
+  KanbanColumnWithWarnings,
+  ColumnLabelRule,
+  CreateColumnLabelRule,
   CreateKanbanColumn,
   UpdateKanbanColumn,
   Config,
@@ -20,7 +23,17 @@ import {
   DirectoryListResponse,
   DirectoryEntry,
   ExecutionProcess,
+  ExecuteTaskRequest,
+  ReplayExecutionProcessRequest,
   ExecutionProcessRepoState,
+  ExecutionEnvironment,
+  ExecutionLogRedaction,
+  ExecutionReplay,
+  ExecutionArtifact,
+  ExecutionDeliverableReport,
+  InterruptedSession,
+  AgentConcurrencySnapshot,
+  ProjectFairness,
   GitBranch,
   Project,
   ProjectRepo,
@@ -29,6 +42,9 @@ import {
   CreateProject,
   CreateProjectRepo,
   UpdateProjectRepo,
+  ProjectRepoScriptSettings,
+  UpdateProjectRepoScriptSettings,
+  ScriptInterpreter,
   SearchResult,
   ShareTaskResponse,
   Task,
@@ -72,6 +88,14 @@ import {
   CreateRemoteProjectRequest,
   LinkToExistingRequest,
   UpdateMemberRoleResponse,
+  GrantProjectAccessRequest,
+  ProjectAccessGrant,
+  ProjectAccessResponse,
+  TransferProjectOwnershipRequest,
+  ListNotificationsResponse,
+  MarkNotificationsReadRequest,
+  TaskSubscription,
+  CreateTaskSubscription,
   Invitation,
   RemoteProject,
   ListInvitationsResponse,
@@ -87,9 +111,13 @@ import {
   CurrentUserResponse,
   SharedTaskResponse,
   SharedTaskDetails,
+  SharedTaskConflict,
+  ConflictResolution,
   QueueStatus,
   PrCommentsResponse,
   MergeTaskAttemptRequest,
+  ApplyPatchRequest,
+  AppliedPatch,
   PushTaskAttemptRequest,
   RepoBranchStatus,
   AbortConflictsRequest,
@@ -98,11 +126,13 @@ import {
   Agent,
   CreateAgent,
   UpdateAgent,
+  AgentPromptSettings,
+  UpdateAgentPromptSettings,
   TaskEventWithNames,
   CreateTaskEvent,
   TaskEvent,
-  StateTransition,
   StateTransitionWithColumns,
+  StateTransitionWithWarnings,
   CreateStateTransition,
   UpdateStateTransition,
   TemplateInfo,
@@ -117,15 +147,75 @@ import {
   UpdateTaskGroup,
   TaskGroupDependency,
   TaskLabel,
+  LabelMatch,
+  UpdateProjectBoardSettings,
+  UpdateProjectExecutionSettings,
+  UpdateProjectBranchSettings,
+  ProjectShareSyncSettings,
+  UpdateProjectShareSyncSettings,
+  ProjectLogRedactionSettings,
+  UpdateProjectLogRedactionSettings,
+  ProjectModuleMemorySettings,
+  UpdateProjectModuleMemorySettings,
+  ProjectTimezoneSettings,
+  UpdateProjectTimezoneSettings,
+  ProjectSecuritySettings,
+  UpdateProjectSecuritySettings,
+  RepoForgeSettings,
+  UpdateRepoForgeSettings,
+  PrChecksResponse,
   CreateTaskLabel,
   UpdateTaskLabel,
   ContextArtifact,
   ContextPreviewStats,
+  ArtifactReviewStatus,
+  ContextArtifactReview,
+  ReviewContextArtifact,
+  ContextArtifactTtl,
+  SetContextArtifactTtl,
+  ReviewedContextArtifact,
+  ContextBudgetOverride,
+  ContextBudgetSettings,
   EvaluateRun,
   CreateEvaluateRun,
   Skill,
   CreateSkill,
   UpdateSkill,
+  ProjectConflicts,
+  WorkflowWarning,
+  BoardValidationReport,
+  BoardShareLink,
+  CreatedBoardShareLink,
+  ReviewerFeedback,
+  TaskComment,
+  CreateTaskComment,
+  UpdateTaskComment,
+  PlanReview,
+  DryRunPatch,
+  ReviewPlan,
+  BoardCycleTimes,
+  BoardEscalationSlo,
+  BoardSnapshotHistory,
+  BoardNotificationSettings,
+  UpdateBoardNotificationSettings,
+  BoardLayoutSettings,
+  UpdateBoardLayoutSettings,
+  TaskArchive,
+  TaskAssignment,
+  ApiToken,
+  CreateApiToken,
+  CreatedApiToken,
+  User,
+  CreateUser,
+  UpdateUser,
+  TaskCycleTime,
+  TaskLogRetention,
+  UpdateTaskLogRetention,
+  RepoDefaultTargetBranch,
+  UpdateRepoDefaultTargetBranch,
+  WorkspaceTemplate,
+  CreateWorkspaceTemplate,
+  UpdateWorkspaceTemplate,
 } from 'shared/types';
 import type { WorkspaceWithSession } from '@/types/attempt';
 import { createWorkspaceWithSession } from '@/types/attempt';
@@ -298,6 +388,12 @@ export const projectsApi = {
     return handleApiResponse<RemoteProjectMembersResponse>(response);
   },
 
+  // Files that more than one open task is changing, for flagging task cards
+  getConflicts: async (projectId: string): Promise<ProjectConflicts> => {
+    const response = await makeRequest(`/api/projects/${projectId}/conflicts`);
+    return handleApiResponse<ProjectConflicts>(response);
+  },
+
   delete: async (id: string): Promise<void> => {
     const response = await makeRequest(`/api/projects/${id}`, {
       method: 'DELETE',
@@ -421,6 +517,107 @@ export const projectsApi = {
     return handleApiResponse<ProjectRepo>(response);
   },
 
+  getRepositoryScriptSettings: async (
+    projectId: string,
+    repoId: string
+  ): Promise<ProjectRepoScriptSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/repositories/${repoId}/script-settings`
+    );
+    return handleApiResponse<ProjectRepoScriptSettings>(response);
+  },
+
+  updateRepositoryScriptSettings: async (
+    projectId: string,
+    repoId: string,
+    data: UpdateProjectRepoScriptSettings
+  ): Promise<ProjectRepoScriptSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/repositories/${repoId}/script-settings`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ProjectRepoScriptSettings>(response);
+  },
+
+  getRepositoryDefaultTargetBranch: async (
+    projectId: string,
+    repoId: string
+  ): Promise<RepoDefaultTargetBranch> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/repositories/${repoId}/default-target-branch`
+    );
+    return handleApiResponse<RepoDefaultTargetBranch>(response);
+  },
+
+  updateRepositoryDefaultTargetBranch: async (
+    projectId: string,
+    repoId: string,
+    data: UpdateRepoDefaultTargetBranch
+  ): Promise<RepoDefaultTargetBranch> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/repositories/${repoId}/default-target-branch`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<RepoDefaultTargetBranch>(response);
+  },
+
+  getWorkspaceTemplates: async (
+    projectId: string
+  ): Promise<WorkspaceTemplate[]> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/workspace-templates`
+    );
+    return handleApiResponse<WorkspaceTemplate[]>(response);
+  },
+
+  createWorkspaceTemplate: async (
+    projectId: string,
+    data: CreateWorkspaceTemplate
+  ): Promise<WorkspaceTemplate> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/workspace-templates`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<WorkspaceTemplate>(response);
+  },
+
+  updateWorkspaceTemplate: async (
+    projectId: string,
+    templateId: string,
+    data: UpdateWorkspaceTemplate
+  ): Promise<WorkspaceTemplate> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/workspace-templates/${templateId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<WorkspaceTemplate>(response);
+  },
+
+  deleteWorkspaceTemplate: async (
+    projectId: string,
+    templateId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/workspace-templates/${templateId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
   startAgent: async (
     projectId: string
   ): Promise<{ workspace_id: string; created: boolean }> => {
@@ -445,12 +642,235 @@ export const projectsApi = {
     const response = await makeRequest(`/api/projects/${projectId}/prereq-eval/start`, { method: 'POST' });
     return handleApiResponse<{ workspace_id: string; created: boolean }>(response);
   },
+
+  getBoardSettings: async (
+    projectId: string
+  ): Promise<Project> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/board-settings`
+    );
+    return handleApiResponse<Project>(response);
+  },
+
+  updateBoardSettings: async (
+    projectId: string,
+    data: UpdateProjectBoardSettings
+  ): Promise<Project> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/board-settings`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<Project>(response);
+  },
+
+  getExecutionSettings: async (
+    projectId: string
+  ): Promise<Project> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/execution-settings`
+    );
+    return handleApiResponse<Project>(response);
+  },
+
+  updateExecutionSettings: async (
+    projectId: string,
+    data: UpdateProjectExecutionSettings
+  ): Promise<Project> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/execution-settings`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<Project>(response);
+  },
+
+  getBranchSettings: async (
+    projectId: string
+  ): Promise<Project> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/branch-settings`
+    );
+    return handleApiResponse<Project>(response);
+  },
+
+  updateBranchSettings: async (
+    projectId: string,
+    data: UpdateProjectBranchSettings
+  ): Promise<Project> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/branch-settings`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<Project>(response);
+  },
+
+  getShareSyncSettings: async (
+    projectId: string
+  ): Promise<ProjectShareSyncSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/share-sync-settings`
+    );
+    return handleApiResponse<ProjectShareSyncSettings>(response);
+  },
+
+  updateShareSyncSettings: async (
+    projectId: string,
+    data: UpdateProjectShareSyncSettings
+  ): Promise<ProjectShareSyncSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/share-sync-settings`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ProjectShareSyncSettings>(response);
+  },
+
+  getLogRedactionSettings: async (
+    projectId: string
+  ): Promise<ProjectLogRedactionSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/log-redaction-settings`
+    );
+    return handleApiResponse<ProjectLogRedactionSettings>(response);
+  },
+
+  updateLogRedactionSettings: async (
+    projectId: string,
+    data: UpdateProjectLogRedactionSettings
+  ): Promise<ProjectLogRedactionSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/log-redaction-settings`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ProjectLogRedactionSettings>(response);
+  },
+
+  getModuleMemorySettings: async (
+    projectId: string
+  ): Promise<ProjectModuleMemorySettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/module-memory-settings`
+    );
+    return handleApiResponse<ProjectModuleMemorySettings>(response);
+  },
+
+  updateModuleMemorySettings: async (
+    projectId: string,
+    data: UpdateProjectModuleMemorySettings
+  ): Promise<ProjectModuleMemorySettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/module-memory-settings`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ProjectModuleMemorySettings>(response);
+  },
+
+  getTimezoneSettings: async (
+    projectId: string
+  ): Promise<ProjectTimezoneSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/timezone-settings`
+    );
+    return handleApiResponse<ProjectTimezoneSettings>(response);
+  },
+
+  updateTimezoneSettings: async (
+    projectId: string,
+    data: UpdateProjectTimezoneSettings
+  ): Promise<ProjectTimezoneSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/timezone-settings`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ProjectTimezoneSettings>(response);
+  },
+
+  getSecuritySettings: async (
+    projectId: string
+  ): Promise<ProjectSecuritySettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/security-settings`
+    );
+    return handleApiResponse<ProjectSecuritySettings>(response);
+  },
+
+  updateSecuritySettings: async (
+    projectId: string,
+    data: UpdateProjectSecuritySettings
+  ): Promise<ProjectSecuritySettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/security-settings`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ProjectSecuritySettings>(response);
+  },
+
+  getContextBudget: async (
+    projectId: string
+  ): Promise<ContextBudgetSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/context-budget`
+    );
+    return handleApiResponse<ContextBudgetSettings>(response);
+  },
+
+  updateContextBudget: async (
+    projectId: string,
+    data: ContextBudgetOverride
+  ): Promise<ContextBudgetSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/context-budget`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ContextBudgetSettings>(response);
+  },
+
+  /** Move column-less tasks into the board's initial column */
+  assignInitialColumn: async (projectId: string): Promise<Task[]> => {
+    const response = await makeRequest(
+      `/api/admin/projects/${projectId}/assign-initial-column`,
+      { method: 'POST' }
+    );
+    return handleApiResponse<Task[]>(response);
+  },
 };
 
 // Task Management APIs
 export const tasksApi = {
-  listByProject: async (projectId: string): Promise<TaskWithAttemptStatus[]> => {
-    const response = await makeRequest(`/api/tasks?project_id=${projectId}`);
+  listByProject: async (
+    projectId: string,
+    labels?: string[],
+    labelMatch?: LabelMatch
+  ): Promise<TaskWithAttemptStatus[]> => {
+    const params = new URLSearchParams({ project_id: projectId });
+    if (labels?.length) params.set('labels', labels.join(','));
+    if (labelMatch) params.set('label_match', labelMatch);
+    const response = await makeRequest(`/api/tasks?${params.toString()}`);
     return handleApiResponse<TaskWithAttemptStatus[]>(response);
   },
 
@@ -459,6 +879,32 @@ export const tasksApi = {
     return handleApiResponse<Task>(response);
   },
 
+  getArtifacts: async (taskId: string): Promise<ExecutionArtifact[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/artifacts`);
+    return handleApiResponse<ExecutionArtifact[]>(response);
+  },
+
+  getCycleTime: async (taskId: string): Promise<TaskCycleTime> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/cycle-time`);
+    return handleApiResponse<TaskCycleTime>(response);
+  },
+
+  getLogRetention: async (taskId: string): Promise<TaskLogRetention> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/log-retention`);
+    return handleApiResponse<TaskLogRetention>(response);
+  },
+
+  updateLogRetention: async (
+    taskId: string,
+    data: UpdateTaskLogRetention
+  ): Promise<TaskLogRetention> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/log-retention`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskLogRetention>(response);
+  },
+
   create: async (data: CreateTask): Promise<Task> => {
     const response = await makeRequest(`/api/tasks`, {
       method: 'POST',
@@ -485,6 +931,75 @@ export const tasksApi = {
     return handleApiResponse<Task>(response);
   },
 
+  execute: async (
+    taskId: string,
+    data: ExecuteTaskRequest
+  ): Promise<ExecutionProcess> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/execute`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
+  getReviewerFeedback: async (taskId: string): Promise<ReviewerFeedback[]> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/reviewer-feedback`
+    );
+    return handleApiResponse<ReviewerFeedback[]>(response);
+  },
+
+  getComments: async (taskId: string): Promise<TaskComment[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/comments`);
+    return handleApiResponse<TaskComment[]>(response);
+  },
+
+  createComment: async (
+    taskId: string,
+    data: CreateTaskComment
+  ): Promise<TaskComment> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/comments`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskComment>(response);
+  },
+
+  updateComment: async (
+    taskId: string,
+    commentId: string,
+    data: UpdateTaskComment
+  ): Promise<TaskComment> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/comments/${commentId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskComment>(response);
+  },
+
+  deleteComment: async (taskId: string, commentId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/comments/${commentId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  getPlanReviews: async (taskId: string): Promise<PlanReview[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/plan-reviews`);
+    return handleApiResponse<PlanReview[]>(response);
+  },
+
+  getDryRunPatches: async (taskId: string): Promise<DryRunPatch[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/dry-run-patches`);
+    return handleApiResponse<DryRunPatch[]>(response);
+  },
+
   delete: async (taskId: string): Promise<void> => {
     const response = await makeRequest(`/api/tasks/${taskId}`, {
       method: 'DELETE',
@@ -492,6 +1007,44 @@ export const tasksApi = {
     return handleApiResponse<void>(response);
   },
 
+  listArchived: async (projectId: string): Promise<Task[]> => {
+    const params = new URLSearchParams({ project_id: projectId });
+    const response = await makeRequest(
+      `/api/tasks/archived?${params.toString()}`
+    );
+    return handleApiResponse<Task[]>(response);
+  },
+
+  archive: async (taskId: string): Promise<TaskArchive> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/archive`, {
+      method: 'POST',
+    });
+    return handleApiResponse<TaskArchive>(response);
+  },
+
+  unarchive: async (taskId: string): Promise<TaskArchive> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/archive`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<TaskArchive>(response);
+  },
+
+  getAssignee: async (taskId: string): Promise<TaskAssignment> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/assignee`);
+    return handleApiResponse<TaskAssignment>(response);
+  },
+
+  assign: async (
+    taskId: string,
+    assigneeId: string | null
+  ): Promise<TaskAssignment> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/assignee`, {
+      method: 'PUT',
+      body: JSON.stringify({ assignee_id: assigneeId }),
+    });
+    return handleApiResponse<TaskAssignment>(response);
+  },
+
   share: async (taskId: string): Promise<ShareTaskResponse> => {
     const response = await makeRequest(`/api/tasks/${taskId}/share`, {
       method: 'POST',
@@ -532,6 +1085,27 @@ export const tasksApi = {
     });
     return handleApiResponse<Task | null>(response);
   },
+
+  listConflicts: async (projectId: string): Promise<SharedTaskConflict[]> => {
+    const response = await makeRequest(
+      `/api/shared-tasks/conflicts?project_id=${encodeURIComponent(projectId)}`
+    );
+    return handleApiResponse<SharedTaskConflict[]>(response);
+  },
+
+  resolveConflict: async (
+    conflictId: string,
+    resolution: ConflictResolution
+  ): Promise<SharedTaskConflict> => {
+    const response = await makeRequest(
+      `/api/shared-tasks/conflicts/${conflictId}/resolve`,
+      {
+        method: 'POST',
+        body: JSON.stringify({ resolution }),
+      }
+    );
+    return handleApiResponse<SharedTaskConflict>(response);
+  },
 };
 
 // Task Triggers API
@@ -736,6 +1310,28 @@ export const attemptsApi = {
     return handleApiResponse<void>(response);
   },
 
+  applyPatch: async (
+    attemptId: string,
+    data: ApplyPatchRequest
+  ): Promise<AppliedPatch> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/apply-patch`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<AppliedPatch>(response);
+  },
+
+  getExportUrl: (
+    attemptId: string,
+    format: 'bundle' | 'patch' = 'bundle',
+    repoId?: string
+  ): string =>
+    `/api/task-attempts/${attemptId}/export?format=${format}` +
+    (repoId ? `&repo_id=${repoId}` : ''),
+
   push: async (
     attemptId: string,
     data: PushTaskAttemptRequest
@@ -888,6 +1484,16 @@ export const attemptsApi = {
     );
     return handleApiResponse<PrCommentsResponse>(response);
   },
+
+  getPrChecks: async (
+    attemptId: string,
+    repoId: string
+  ): Promise<PrChecksResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/checks?repo_id=${encodeURIComponent(repoId)}`
+    );
+    return handleApiResponse<PrChecksResponse>(response);
+  },
 };
 
 // Execution Process APIs
@@ -906,6 +1512,46 @@ export const executionProcessesApi = {
     return handleApiResponse<ExecutionProcessRepoState[]>(response);
   },
 
+  getEnvironment: async (processId: string): Promise<ExecutionEnvironment> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/environment`
+    );
+    return handleApiResponse<ExecutionEnvironment>(response);
+  },
+
+  getRedactions: async (processId: string): Promise<ExecutionLogRedaction> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/redactions`
+    );
+    return handleApiResponse<ExecutionLogRedaction>(response);
+  },
+
+  getArtifacts: async (processId: string): Promise<ExecutionArtifact[]> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/artifacts`
+    );
+    return handleApiResponse<ExecutionArtifact[]>(response);
+  },
+
+  getDeliverableReport: async (
+    processId: string
+  ): Promise<ExecutionDeliverableReport> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/deliverable-report`
+    );
+    return handleApiResponse<ExecutionDeliverableReport>(response);
+  },
+
+  generateDeliverableReport: async (
+    processId: string
+  ): Promise<ExecutionDeliverableReport> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/deliverable-report`,
+      { method: 'POST' }
+    );
+    return handleApiResponse<ExecutionDeliverableReport>(response);
+  },
+
   stopExecutionProcess: async (processId: string): Promise<void> => {
     const response = await makeRequest(
       `/api/execution-processes/${processId}/stop`,
@@ -915,6 +1561,69 @@ export const executionProcessesApi = {
     );
     return handleApiResponse<void>(response);
   },
+
+  getAgentQueue: async (): Promise<AgentConcurrencySnapshot> => {
+    const response = await makeRequest('/api/execution-processes/agent-queue');
+    return handleApiResponse<AgentConcurrencySnapshot>(response);
+  },
+
+  getAgentQueueFairness: async (): Promise<ProjectFairness[]> => {
+    const response = await makeRequest(
+      '/api/execution-processes/agent-queue/fairness'
+    );
+    return handleApiResponse<ProjectFairness[]>(response);
+  },
+
+  resetExecutorHealth: async (executor: BaseCodingAgent): Promise<void> => {
+    const response = await makeRequest(
+      `/api/execution-processes/executor-health/${executor}/reset`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  getInterrupted: async (): Promise<InterruptedSession[]> => {
+    const response = await makeRequest('/api/execution-processes/interrupted');
+    return handleApiResponse<InterruptedSession[]>(response);
+  },
+
+  resume: async (processId: string): Promise<ExecutionProcess> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/resume`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
+  replay: async (
+    processId: string,
+    data: ReplayExecutionProcessRequest
+  ): Promise<ExecutionProcess> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/replay`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
+  getReplays: async (processId: string): Promise<ExecutionReplay[]> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/replays`
+    );
+    return handleApiResponse<ExecutionReplay[]>(response);
+  },
+
+  getExportUrl: (
+    processId: string,
+    format: 'json' | 'markdown' | 'html' = 'json'
+  ): string => `/api/execution-processes/${processId}/export?format=${format}`,
 };
 
 // File System APIs
@@ -962,7 +1671,23 @@ export const repoApi = {
       method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponse<Repo>(response);
+    return handleApiResponse<Repo>(response);
+  },
+
+  getForgeSettings: async (repoId: string): Promise<RepoForgeSettings> => {
+    const response = await makeRequest(`/api/repos/${repoId}/forge-settings`);
+    return handleApiResponse<RepoForgeSettings>(response);
+  },
+
+  updateForgeSettings: async (
+    repoId: string,
+    data: UpdateRepoForgeSettings
+  ): Promise<RepoForgeSettings> => {
+    const response = await makeRequest(`/api/repos/${repoId}/forge-settings`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<RepoForgeSettings>(response);
   },
 };
 
@@ -995,6 +1720,10 @@ export const configApi = {
     );
     return handleApiResponse<AvailabilityInfo>(response);
   },
+  getScriptInterpreters: async (): Promise<ScriptInterpreter[]> => {
+    const response = await makeRequest('/api/scripts/interpreters');
+    return handleApiResponse<ScriptInterpreter[]>(response);
+  },
 };
 
 // Task Tags APIs (all tags are global)
@@ -1178,6 +1907,29 @@ export const imagesApi = {
   },
 };
 
+// Personal access tokens for headless API access
+export const apiTokensApi = {
+  list: async (): Promise<ApiToken[]> => {
+    const response = await makeRequest('/api/auth/tokens');
+    return handleApiResponse<ApiToken[]>(response);
+  },
+
+  create: async (data: CreateApiToken): Promise<CreatedApiToken> => {
+    const response = await makeRequest('/api/auth/tokens', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<CreatedApiToken>(response);
+  },
+
+  revoke: async (tokenId: string): Promise<ApiToken> => {
+    const response = await makeRequest(`/api/auth/tokens/${tokenId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<ApiToken>(response);
+  },
+};
+
 // Approval API
 export const approvalsApi = {
   respond: async (
@@ -1347,6 +2099,183 @@ export const organizationsApi = {
     });
     return handleApiResponse<void>(response);
   },
+
+  getProjectAccess: async (
+    remoteProjectId: string
+  ): Promise<ProjectAccessResponse> => {
+    const response = await makeRequest(
+      `/api/remote-projects/${remoteProjectId}/access`
+    );
+    return handleApiResponse<ProjectAccessResponse>(response);
+  },
+
+  grantProjectAccess: async (
+    remoteProjectId: string,
+    userId: string
+  ): Promise<ProjectAccessGrant> => {
+    const body: GrantProjectAccessRequest = { user_id: userId };
+    const response = await makeRequest(
+      `/api/remote-projects/${remoteProjectId}/access`,
+      {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify(body),
+      }
+    );
+    return handleApiResponse<ProjectAccessGrant>(response);
+  },
+
+  revokeProjectAccess: async (
+    remoteProjectId: string,
+    userId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/remote-projects/${remoteProjectId}/access/${userId}`,
+      { method: 'DELETE' }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  transferProjectOwnership: async (
+    remoteProjectId: string,
+    newOwnerUserId: string
+  ): Promise<ProjectAccessResponse> => {
+    const body: TransferProjectOwnershipRequest = {
+      new_owner_user_id: newOwnerUserId,
+    };
+    const response = await makeRequest(
+      `/api/remote-projects/${remoteProjectId}/transfer-ownership`,
+      {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify(body),
+      }
+    );
+    return handleApiResponse<ProjectAccessResponse>(response);
+  },
+};
+
+// Inbox of mention and assignment notifications for the signed-in user
+export const userNotificationsApi = {
+  list: async (unreadOnly = false): Promise<ListNotificationsResponse> => {
+    const response = await makeRequest(
+      `/api/user-notifications?unread_only=${unreadOnly}`
+    );
+    return handleApiResponse<ListNotificationsResponse>(response);
+  },
+
+  markRead: async (ids?: string[]): Promise<void> => {
+    const body: MarkNotificationsReadRequest = { ids: ids ?? null };
+    const response = await makeRequest('/api/user-notifications/read', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(body),
+    });
+    return handleApiResponse<void>(response);
+  },
+};
+
+// People tasks can be assigned to
+export const usersApi = {
+  list: async (): Promise<User[]> => {
+    const response = await makeRequest('/api/users');
+    return handleApiResponse<User[]>(response);
+  },
+
+  create: async (data: CreateUser): Promise<User> => {
+    const response = await makeRequest('/api/users', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<User>(response);
+  },
+
+  update: async (userId: string, data: UpdateUser): Promise<User> => {
+    const response = await makeRequest(`/api/users/${userId}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<User>(response);
+  },
+
+  delete: async (userId: string): Promise<void> => {
+    const response = await makeRequest(`/api/users/${userId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  me: async (): Promise<User | null> => {
+    const response = await makeRequest('/api/me');
+    return handleApiResponse<User | null>(response);
+  },
+
+  myTasks: async (): Promise<Task[]> => {
+    const response = await makeRequest('/api/me/tasks');
+    return handleApiResponse<Task[]>(response);
+  },
+
+  tasks: async (userId: string): Promise<Task[]> => {
+    const response = await makeRequest(`/api/users/${userId}/tasks`);
+    return handleApiResponse<Task[]>(response);
+  },
+};
+
+// Tasks and labels the user watches
+export const subscriptionsApi = {
+  list: async (): Promise<TaskSubscription[]> => {
+    const response = await makeRequest('/api/me/subscriptions');
+    return handleApiResponse<TaskSubscription[]>(response);
+  },
+
+  create: async (data: CreateTaskSubscription): Promise<TaskSubscription> => {
+    const response = await makeRequest('/api/me/subscriptions', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskSubscription>(response);
+  },
+
+  delete: async (subscriptionId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/me/subscriptions/${subscriptionId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  getStreamUrl: (): string => '/api/me/subscriptions/stream/ws',
+};
+
+// Reviews of plans written by plan-only agent runs
+export const planReviewsApi = {
+  approve: async (reviewId: string, data: ReviewPlan): Promise<PlanReview> => {
+    const response = await makeRequest(`/api/plan-reviews/${reviewId}/approve`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<PlanReview>(response);
+  },
+
+  reject: async (reviewId: string, data: ReviewPlan): Promise<PlanReview> => {
+    const response = await makeRequest(`/api/plan-reviews/${reviewId}/reject`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<PlanReview>(response);
+  },
+};
+
+// Changes proposed by agents in dry-run columns
+export const dryRunPatchesApi = {
+  apply: async (patchId: string): Promise<DryRunPatch> => {
+    const response = await makeRequest(`/api/dry-run-patches/${patchId}/apply`, {
+      method: 'POST',
+    });
+    return handleApiResponse<DryRunPatch>(response);
+  },
 };
 
 // Scratch API
@@ -1460,6 +2389,25 @@ export const agentsApi = {
     });
     return handleApiResponse<void>(response);
   },
+
+  getPromptSettings: async (agentId: string): Promise<AgentPromptSettings> => {
+    const response = await makeRequest(`/api/agents/${agentId}/prompt-settings`);
+    return handleApiResponse<AgentPromptSettings>(response);
+  },
+
+  updatePromptSettings: async (
+    agentId: string,
+    data: UpdateAgentPromptSettings
+  ): Promise<AgentPromptSettings> => {
+    const response = await makeRequest(
+      `/api/agents/${agentId}/prompt-settings`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<AgentPromptSettings>(response);
+  },
 };
 
 // Boards API (kanban board templates)
@@ -1506,19 +2454,19 @@ export const boardsApi = {
   createColumn: async (
     boardId: string,
     data: CreateKanbanColumn
-  ): Promise<KanbanColumn> => {
+  ): Promise<KanbanColumnWithWarnings> => {
     const response = await makeRequest(`/api/boards/${boardId}/columns`, {
       method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponse<KanbanColumn>(response);
+    return handleApiResponse<KanbanColumnWithWarnings>(response);
   },
 
   updateColumn: async (
     boardId: string,
     columnId: string,
     data: UpdateKanbanColumn
-  ): Promise<KanbanColumn> => {
+  ): Promise<KanbanColumnWithWarnings> => {
     const response = await makeRequest(
       `/api/boards/${boardId}/columns/${columnId}`,
       {
@@ -1526,7 +2474,7 @@ export const boardsApi = {
         body: JSON.stringify(data),
       }
     );
-    return handleApiResponse<KanbanColumn>(response);
+    return handleApiResponse<KanbanColumnWithWarnings>(response);
   },
 
   deleteColumn: async (boardId: string, columnId: string): Promise<void> => {
@@ -1546,7 +2494,7 @@ export const boardsApi = {
     const response = await makeRequest(
       `/api/boards/${boardId}/columns/reorder`,
       {
-        method: 'POST',
+        method: 'PATCH',
         body: JSON.stringify({ column_ids: columnIds }),
       }
     );
@@ -1567,6 +2515,116 @@ export const boardsApi = {
     });
     return handleApiResponse<KanbanColumn[]>(response);
   },
+
+  getWorkflowWarnings: async (boardId: string): Promise<WorkflowWarning[]> => {
+    const response = await makeRequest(
+      `/api/boards/${boardId}/workflow-warnings`
+    );
+    return handleApiResponse<WorkflowWarning[]>(response);
+  },
+
+  validate: async (boardId: string): Promise<BoardValidationReport> => {
+    const response = await makeRequest(`/api/boards/${boardId}/validate`, {
+      method: 'POST',
+    });
+    return handleApiResponse<BoardValidationReport>(response);
+  },
+
+  getCycleTimes: async (
+    boardId: string,
+    range?: { from?: string; to?: string }
+  ): Promise<BoardCycleTimes> => {
+    const params = new URLSearchParams(range);
+    const response = await makeRequest(
+      `/api/boards/${boardId}/cycle-times?${params.toString()}`
+    );
+    return handleApiResponse<BoardCycleTimes>(response);
+  },
+
+  getEscalationSlo: async (
+    boardId: string,
+    range?: { from?: string; to?: string }
+  ): Promise<BoardEscalationSlo> => {
+    const params = new URLSearchParams(range);
+    const response = await makeRequest(
+      `/api/boards/${boardId}/escalation-slo?${params.toString()}`
+    );
+    return handleApiResponse<BoardEscalationSlo>(response);
+  },
+
+  getSnapshots: async (
+    boardId: string,
+    days?: number
+  ): Promise<BoardSnapshotHistory> => {
+    const params = new URLSearchParams();
+    if (days) params.set('days', String(days));
+    const response = await makeRequest(
+      `/api/boards/${boardId}/snapshots?${params.toString()}`
+    );
+    return handleApiResponse<BoardSnapshotHistory>(response);
+  },
+
+  getNotificationSettings: async (
+    boardId: string
+  ): Promise<BoardNotificationSettings> => {
+    const response = await makeRequest(
+      `/api/boards/${boardId}/notification-settings`
+    );
+    return handleApiResponse<BoardNotificationSettings>(response);
+  },
+
+  updateNotificationSettings: async (
+    boardId: string,
+    data: UpdateBoardNotificationSettings
+  ): Promise<BoardNotificationSettings> => {
+    const response = await makeRequest(
+      `/api/boards/${boardId}/notification-settings`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<BoardNotificationSettings>(response);
+  },
+
+  getLayoutSettings: async (boardId: string): Promise<BoardLayoutSettings> => {
+    const response = await makeRequest(`/api/boards/${boardId}/layout-settings`);
+    return handleApiResponse<BoardLayoutSettings>(response);
+  },
+
+  updateLayoutSettings: async (
+    boardId: string,
+    data: UpdateBoardLayoutSettings
+  ): Promise<BoardLayoutSettings> => {
+    const response = await makeRequest(
+      `/api/boards/${boardId}/layout-settings`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<BoardLayoutSettings>(response);
+  },
+
+  getShareLinks: async (boardId: string): Promise<BoardShareLink[]> => {
+    const response = await makeRequest(`/api/boards/${boardId}/share`);
+    return handleApiResponse<BoardShareLink[]>(response);
+  },
+
+  createShareLink: async (boardId: string): Promise<CreatedBoardShareLink> => {
+    const response = await makeRequest(`/api/boards/${boardId}/share`, {
+      method: 'POST',
+    });
+    return handleApiResponse<CreatedBoardShareLink>(response);
+  },
+
+  deleteShareLink: async (boardId: string, linkId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/boards/${boardId}/share/${linkId}`,
+      { method: 'DELETE' }
+    );
+    return handleApiResponse<void>(response);
+  },
 };
 
 // State Transitions API (workflow routing rules)
@@ -1581,19 +2639,19 @@ export const stateTransitionsApi = {
   createForBoard: async (
     boardId: string,
     data: CreateStateTransition
-  ): Promise<StateTransition> => {
+  ): Promise<StateTransitionWithWarnings> => {
     const response = await makeRequest(`/api/boards/${boardId}/transitions`, {
       method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponse<StateTransition>(response);
+    return handleApiResponse<StateTransitionWithWarnings>(response);
   },
 
   updateForBoard: async (
     boardId: string,
     transitionId: string,
     data: UpdateStateTransition
-  ): Promise<StateTransition> => {
+  ): Promise<StateTransitionWithWarnings> => {
     const response = await makeRequest(
       `/api/boards/${boardId}/transitions/${transitionId}`,
       {
@@ -1601,7 +2659,7 @@ export const stateTransitionsApi = {
         body: JSON.stringify(data),
       }
     );
-    return handleApiResponse<StateTransition>(response);
+    return handleApiResponse<StateTransitionWithWarnings>(response);
   },
 
   deleteFromBoard: async (boardId: string, transitionId: string): Promise<void> => {
@@ -1623,19 +2681,19 @@ export const stateTransitionsApi = {
   createForProject: async (
     projectId: string,
     data: CreateStateTransition
-  ): Promise<StateTransition> => {
+  ): Promise<StateTransitionWithWarnings> => {
     const response = await makeRequest(`/api/projects/${projectId}/transitions`, {
       method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponse<StateTransition>(response);
+    return handleApiResponse<StateTransitionWithWarnings>(response);
   },
 
   updateForProject: async (
     projectId: string,
     transitionId: string,
     data: UpdateStateTransition
-  ): Promise<StateTransition> => {
+  ): Promise<StateTransitionWithWarnings> => {
     const response = await makeRequest(
       `/api/projects/${projectId}/transitions/${transitionId}`,
       {
@@ -1643,7 +2701,7 @@ export const stateTransitionsApi = {
         body: JSON.stringify(data),
       }
     );
-    return handleApiResponse<StateTransition>(response);
+    return handleApiResponse<StateTransitionWithWarnings>(response);
   },
 
   deleteFromProject: async (projectId: string, transitionId: string): Promise<void> => {
@@ -1665,12 +2723,12 @@ export const stateTransitionsApi = {
   create: async (
     projectId: string,
     data: CreateStateTransition
-  ): Promise<StateTransition> => {
+  ): Promise<StateTransitionWithWarnings> => {
     const response = await makeRequest(`/api/projects/${projectId}/transitions`, {
       method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponse<StateTransition>(response);
+    return handleApiResponse<StateTransitionWithWarnings>(response);
   },
 
   delete: async (projectId: string, transitionId: string): Promise<void> => {
@@ -1704,19 +2762,19 @@ export const columnsApi = {
   create: async (
     projectId: string,
     data: CreateKanbanColumn
-  ): Promise<KanbanColumn> => {
+  ): Promise<KanbanColumnWithWarnings> => {
     const response = await makeRequest(`/api/projects/${projectId}/columns`, {
       method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponse<KanbanColumn>(response);
+    return handleApiResponse<KanbanColumnWithWarnings>(response);
   },
 
   update: async (
     projectId: string,
     columnId: string,
     data: UpdateKanbanColumn
-  ): Promise<KanbanColumn> => {
+  ): Promise<KanbanColumnWithWarnings> => {
     const response = await makeRequest(
       `/api/projects/${projectId}/columns/${columnId}`,
       {
@@ -1724,7 +2782,32 @@ export const columnsApi = {
         body: JSON.stringify(data),
       }
     );
-    return handleApiResponse<KanbanColumn>(response);
+    return handleApiResponse<KanbanColumnWithWarnings>(response);
+  },
+
+  getContextBudget: async (
+    projectId: string,
+    columnId: string
+  ): Promise<ContextBudgetSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/columns/${columnId}/context-budget`
+    );
+    return handleApiResponse<ContextBudgetSettings>(response);
+  },
+
+  updateContextBudget: async (
+    projectId: string,
+    columnId: string,
+    data: ContextBudgetOverride
+  ): Promise<ContextBudgetSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/columns/${columnId}/context-budget`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ContextBudgetSettings>(response);
   },
 
   delete: async (projectId: string, columnId: string): Promise<void> => {
@@ -1737,6 +2820,38 @@ export const columnsApi = {
     return handleApiResponse<void>(response);
   },
 
+  listLabelRules: async (
+    projectId: string,
+    columnId: string
+  ): Promise<ColumnLabelRule[]> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/columns/${columnId}/label-rules`
+    );
+    return handleApiResponse<ColumnLabelRule[]>(response);
+  },
+
+  createLabelRule: async (
+    projectId: string,
+    columnId: string,
+    data: CreateColumnLabelRule
+  ): Promise<ColumnLabelRule> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/columns/${columnId}/label-rules`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ColumnLabelRule>(response);
+  },
+
+  deleteLabelRule: async (ruleId: string): Promise<void> => {
+    const response = await makeRequest(`/api/column-label-rules/${ruleId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
   reorder: async (projectId: string, columnIds: string[]): Promise<void> => {
     const response = await makeRequest(
       `/api/projects/${projectId}/columns/reorder`,
@@ -1812,15 +2927,64 @@ export const contextArtifactsApi = {
 
   previewContext: async (
     projectId: string,
-    taskId?: string
+    taskId?: string,
+    columnId?: string
   ): Promise<ContextPreviewStats> => {
     const params = new URLSearchParams({ project_id: projectId });
     if (taskId) params.set('task_id', taskId);
+    if (columnId) params.set('column_id', columnId);
     const response = await makeRequest(
       `/api/context-artifacts/preview-context?${params.toString()}`
     );
     return handleApiResponse<ContextPreviewStats>(response);
   },
+
+  getReviews: async (
+    projectId: string,
+    status?: ArtifactReviewStatus
+  ): Promise<ReviewedContextArtifact[]> => {
+    const params = new URLSearchParams({ project_id: projectId });
+    if (status) params.set('status', status);
+    const response = await makeRequest(
+      `/api/context-artifacts/reviews?${params.toString()}`
+    );
+    return handleApiResponse<ReviewedContextArtifact[]>(response);
+  },
+
+  review: async (
+    artifactId: string,
+    data: ReviewContextArtifact
+  ): Promise<ContextArtifactReview> => {
+    const response = await makeRequest(
+      `/api/context-artifacts/${artifactId}/review`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ContextArtifactReview>(response);
+  },
+
+  getTtls: async (projectId: string): Promise<ContextArtifactTtl[]> => {
+    const response = await makeRequest(
+      `/api/context-artifacts/ttls?project_id=${projectId}`
+    );
+    return handleApiResponse<ContextArtifactTtl[]>(response);
+  },
+
+  setTtl: async (
+    projectId: string,
+    data: SetContextArtifactTtl
+  ): Promise<ContextArtifactTtl[]> => {
+    const response = await makeRequest(
+      `/api/context-artifacts/ttls?project_id=${projectId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ContextArtifactTtl[]>(response);
+  },
 };
 
 // Evaluate Runs API
@@ -1100,7 +1100,8 @@ export function ProjectTasks() {
             // Create repos input for the task attempt
             const repos = projectRepos.map((repo) => ({
               repo_id: repo.id,
-              target_branch: 'main', // TODO: Get default branch from repo
+              // The server picks the repo's default target branch
+              target_branch: null,
             }));
 
             // Create and start a task attempt with the column's agent
@@ -339,6 +339,7 @@ export function AgentDefinitionsSettings() {
                     <SelectItem value="QWEN_CODE">Qwen Code</SelectItem>
                     <SelectItem value="COPILOT">Copilot</SelectItem>
                     <SelectItem value="DROID">Droid</SelectItem>
+                    <SelectItem value="CUSTOM">Custom</SelectItem>
                   </SelectContent>
                 </Select>
               </div>
@@ -823,7 +823,7 @@ export function EvaluateSettings() {
       let commitHash: string | null = null;
       let commitMessage: string | null = null;
       try {
-        const healthRes = await fetch('/api/health');
+        const healthRes = await fetch('/api/health/live');
         const health = await healthRes.json();
         commitHash = health.data?.commit_hash || null;
         commitMessage = health.data?.commit_message || null;
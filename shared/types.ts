@@ -16,9 +16,35 @@ group_evaluator_workspace_id: string | null,
 /**
  * Persistent workspace for the PreReq Evaluator agent (PreReq Eval column)
  */
-prereq_eval_workspace_id: string | null, ready_locked: boolean, created_at: Date, updated_at: Date, };
+prereq_eval_workspace_id: string | null, ready_locked: boolean, 
+/**
+ * Tasks created without a column start in the board's initial column
+ */
+auto_assign_initial_column: boolean, 
+/**
+ * `local` or `docker` (see `ExecutionBackend`)
+ */
+execution_backend: string, 
+/**
+ * Image for Docker workspaces; needs git and the agents' CLIs
+ */
+docker_image: string | null, 
+/**
+ * Container ports published on the host's loopback interface at the same number
+ */
+docker_ports: Array<number>, 
+/**
+ * Template for new workspace branches, e.g. `{agent}/{task_id}/{slug}`; None uses
+ * `{prefix}/{short_id}-{slug}`
+ */
+branch_template: string | null, created_at: Date, updated_at: Date, };
 
-export type CreateProject = { name: string, repositories: Array<CreateProjectRepo>, board_id: string | null, };
+export type CreateProject = { name: string, repositories: Array<CreateProjectRepo>, board_id: string | null, 
+/**
+ * Template board whose columns, transitions and agent assignments are cloned
+ * into a new board for the project; takes precedence over `board_id`
+ */
+workflow_template_id: string | null, };
 
 export type UpdateProject = { name: string | null, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, board_id: string | null, };
 
@@ -26,6 +52,236 @@ export type SearchResult = { path: string, is_file: boolean, match_type: SearchM
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
+export type ProjectEnvVar = { id: string, project_id: string, key: string, created_at: Date, updated_at: Date, };
+
+export type CreateProjectEnvVar = { key: string, value: string, };
+
+export type UpdateProjectEnvVar = { value: string, };
+
+export type WorkspaceOrientation = { workspace_id: string, content: string, created_at: Date, };
+
+export type WorkspaceTemplate = { id: string, project_id: string, 
+/**
+ * Repo whose worktree the path is relative to; none for the workspace root
+ */
+repo_id: string | null, 
+/**
+ * Relative path of the file, e.g. `AGENTS.md` or `tests/harness.py`
+ */
+path: string, 
+/**
+ * File content; `{{task_id}}`, `{{branch}}`, `{{column}}` etc. are filled in
+ */
+content: string, 
+/**
+ * Replace a file the repo already has instead of keeping it
+ */
+overwrite: boolean, created_at: Date, updated_at: Date, };
+
+export type CreateWorkspaceTemplate = { repo_id: string | null, path: string, content: string, overwrite: boolean, };
+
+export type UpdateWorkspaceTemplate = { path: string | null, content: string | null, overwrite: boolean | null, };
+
+export type DependencyEcosystem = "cargo" | "npm";
+
+export type DependencyUpdateSchedule = { project_id: string, 
+/**
+ * Workflow board the update tasks are routed through; null uses the project's board
+ */
+board_id: string | null, ecosystems: Array<string>, interval_hours: number, 
+/**
+ * Maximum number of dependency updates per task
+ */
+batch_size: number, enabled: boolean, last_run_at: Date | null, created_at: Date, updated_at: Date, };
+
+export type UpsertDependencyUpdateSchedule = { board_id: string | null, 
+/**
+ * Defaults to cargo and npm
+ */
+ecosystems: Array<DependencyEcosystem> | null, 
+/**
+ * Defaults to weekly (168 hours)
+ */
+interval_hours: number | null, 
+/**
+ * Defaults to 10
+ */
+batch_size: number | null, enabled: boolean | null, };
+
+export type NotificationChannelKind = "slack" | "discord" | "http" | "matrix" | "telegram";
+
+export type NotificationEvent = "execution_completed" | "execution_failed" | "execution_killed" | "task_escalated" | "approval_requested" | "labels_changed";
+
+export type NotificationChannel = { id: string, project_id: string | null, organization_id: string | null, name: string, kind: string, 
+/**
+ * Webhook URL, or the API base URL for Matrix (homeserver) and Telegram
+ */
+webhook_url: string, 
+/**
+ * Matrix room id, Telegram chat id, or Slack channel id when posting with a bot token
+ */
+destination: string | null, 
+/**
+ * Overrides the default title; supports `{{placeholders}}`
+ */
+title_template: string | null, 
+/**
+ * Overrides the default message; supports `{{placeholders}}`
+ */
+message_template: string | null, 
+/**
+ * Events delivered to this channel; empty routes every event
+ */
+events: Array<string>, enabled: boolean, created_at: Date, updated_at: Date, };
+
+export type CreateNotificationChannel = { name: string, kind: NotificationChannelKind, 
+/**
+ * Matrix homeserver URL; may be empty for Telegram and Slack bot-token channels
+ */
+webhook_url: string, 
+/**
+ * Matrix room id, Telegram chat id, or Slack channel id when posting with a bot token
+ */
+destination: string | null, 
+/**
+ * Matrix access token, or Telegram or Slack bot token
+ */
+access_token: string | null, title_template: string | null, message_template: string | null, 
+/**
+ * Defaults to every event
+ */
+events: Array<NotificationEvent> | null, enabled: boolean | null, };
+
+export type UpdateNotificationChannel = { name: string | null, kind: NotificationChannelKind | null, webhook_url: string | null, destination: string | null, access_token: string | null, 
+/**
+ * Null clears the template, falling back to the default title
+ */
+title_template?: string | null, 
+/**
+ * Null clears the template, falling back to the default message
+ */
+message_template?: string | null, events: Array<NotificationEvent> | null, enabled: boolean | null, };
+
+export type NotificationChannelIdentity = { id: string, channel_id: string, 
+/**
+ * Slack member id (`U0123ABCD`) or Matrix user id (`@alice:example.org`)
+ */
+external_user_id: string, user_id: string, created_at: Date, };
+
+export type CreateNotificationChannelIdentity = { external_user_id: string, user_id: string, };
+
+export type EmailNotificationPreference = { id: string, email: string, name: string | null, 
+/**
+ * Email right away when a task takes an escalation transition
+ */
+immediate_escalations: boolean, 
+/**
+ * Email right away when a run fails
+ */
+immediate_failures: boolean, digest_enabled: boolean, 
+/**
+ * Hour of the day (UTC) the digest is sent
+ */
+digest_hour: number, last_digest_at: Date | null, created_at: Date, updated_at: Date, };
+
+export type CreateEmailNotificationPreference = { email: string, name: string | null, immediate_escalations: boolean | null, immediate_failures: boolean | null, digest_enabled: boolean | null, digest_hour: number | null, };
+
+export type UpdateEmailNotificationPreference = { email: string | null, name: string | null, immediate_escalations: boolean | null, immediate_failures: boolean | null, digest_enabled: boolean | null, digest_hour: number | null, };
+
+export type User = { id: string, 
+/**
+ * Account id on the remote server, for users that have signed in
+ */
+remote_user_id: string | null, username: string | null, email: string, display_name: string | null, created_at: Date, updated_at: Date, };
+
+export type CreateUser = { email: string, username: string | null, display_name: string | null, };
+
+export type UpdateUser = { email: string | null, username: string | null, display_name: string | null, };
+
+export type ApiTokenScope = "read" | "write";
+
+export type ApiToken = { id: string, name: string, 
+/**
+ * `read` or `write` (see `ApiTokenScope`)
+ */
+scope: string, 
+/**
+ * Limits the token to this project's routes when set
+ */
+project_id: string | null, 
+/**
+ * Who created the token
+ */
+user_id: string | null, expires_at: Date | null, last_used_at: Date | null, revoked_at: Date | null, created_at: Date, };
+
+export type CreateApiToken = { name: string, scope: ApiTokenScope, project_id: string | null, 
+/**
+ * Token never expires when unset
+ */
+expires_in_days: number | null, };
+
+export type CreatedApiToken = { api_token: ApiToken, token: string, };
+
+export type DashboardToken = { id: string, project_id: string, name: string, last_used_at: Date | null, created_at: Date, };
+
+export type CreateDashboardToken = { name: string, };
+
+export type CreatedDashboardToken = { dashboard: DashboardToken, token: string, };
+
+export type DashboardColumn = { id: string, name: string, color: string | null, position: number, is_terminal: boolean, task_count: bigint, in_progress: bigint, awaiting_response: bigint, };
+
+export type DashboardRunningAgent = { execution_process_id: string, task_id: string, task_title: string, column_name: string | null, agent_name: string | null, executor: string | null, started_at: Date, };
+
+export type DashboardEscalationReason = "escalated" | "awaiting_response";
+
+export type DashboardEscalation = { task_id: string, title: string, column_name: string | null, reason: DashboardEscalationReason, since: Date, };
+
+export type BoardSnapshot = { project_id: string, project_name: string, generated_at: Date, columns: Array<DashboardColumn>, running_agents: Array<DashboardRunningAgent>, escalations: Array<DashboardEscalation>, };
+
+export type BoardColumnSnapshot = { id: string, board_id: string, captured_on: string, column_id: string, column_name: string, status: string, is_terminal: boolean, task_count: bigint, created_at: Date, };
+
+export type BoardLayoutSettings = { board_id: string, 
+/**
+ * Reordering must keep the initial column first and terminal columns last
+ */
+strict_column_order: boolean, updated_at: Date, };
+
+export type UpdateBoardLayoutSettings = { strict_column_order: boolean | null, };
+
+export type NotificationDigestCadence = "off" | "hourly" | "daily";
+
+export type BoardNotificationSettings = { board_id: string, 
+/**
+ * `off`, `hourly` or `daily` (see `NotificationDigestCadence`)
+ */
+digest_cadence: string, 
+/**
+ * Hour of the day daily digests are sent, in the timezone of the board's projects
+ */
+digest_hour: number, last_digest_at: Date | null, updated_at: Date, };
+
+export type UpdateBoardNotificationSettings = { digest_cadence: NotificationDigestCadence | null, digest_hour: number | null, };
+
+export type NotificationDigestItem = { id: string, board_id: string, channel_id: string, event: string, project_id: string, task_id: string, 
+/**
+ * Title and message as rendered for the channel when the event happened
+ */
+title: string, message: string, created_at: Date, };
+
+export type BoardShareLink = { id: string, board_id: string, last_used_at: Date | null, created_at: Date, };
+
+export type CreatedBoardShareLink = { link: BoardShareLink, token: string, 
+/**
+ * Path of the shared view, relative to the server's origin
+ */
+url: string, };
+
+export type SharedBoardTask = { id: string, title: string, project_name: string, task_state: TaskState, updated_at: Date, };
+
+export type SharedBoardColumn = { id: string, name: string, color: string | null, is_terminal: boolean, tasks: Array<SharedBoardTask>, };
+
+export type SharedBoardView = { board_id: string, board_name: string, generated_at: Date, columns: Array<SharedBoardColumn>, };
+
 export type Board = { id: string, name: string, description: string | null, is_template: boolean, template_group_id: string | null, template_name: string | null, template_description: string | null, template_icon: string | null, created_at: Date, updated_at: Date, };
 
 export type CreateBoard = { name: string, description: string | null, };
@@ -50,7 +306,58 @@ question: string | null,
 /**
  * JSON array of valid answer options for the question
  */
-answer_options: string | null, is_template: boolean, template_group_id: string | null, created_at: Date, updated_at: Date, };
+answer_options: string | null, 
+/**
+ * Read-only analysis mode: code changes and commits made by the agent are discarded
+ */
+read_only: boolean, 
+/**
+ * Plan-only mode: the agent writes a plan for review and its code changes are
+ * discarded; the task can't leave the column until the plan is approved
+ */
+plan_only: boolean, 
+/**
+ * Dry-run mode: the agent only proposes changes; nothing is committed and its
+ * changes are saved as patches a person can apply
+ */
+dry_run: boolean, 
+/**
+ * Scanners run after the agent finishes (see `SecurityScanner`); empty disables the gate
+ */
+security_scanners: Array<string>, 
+/**
+ * Items a person must tick before a task can leave the column; empty disables it
+ */
+checklist: Array<string>, 
+/**
+ * Shell script run in the workspace before the column's agent starts
+ */
+on_enter_script: string | null, 
+/**
+ * Shell script run in the workspace after the column's agent finishes; if it
+ * fails the task stays in the column
+ */
+on_exit_script: string | null, 
+/**
+ * Prepend an orientation of the workspace (repo layout and key files) to the
+ * agent's prompt, so it spends fewer tool calls exploring
+ */
+warm_up: boolean, 
+/**
+ * Minutes a task escalated into this column may wait before it breaches the
+ * column's response-time target; none disables SLO tracking
+ */
+response_target_mins: number | null, 
+/**
+ * Order of the sections of the agent's prompt (see `PromptSection`), overriding the
+ * agent's; sections not listed are left out. Empty uses the agent's order.
+ */
+prompt_sections: Array<string>, 
+/**
+ * Executor profile variant (e.g. `OPUS`) the column's agent runs with, overriding
+ * the agent's default variant
+ */
+executor_variant: string | null, is_template: boolean, template_group_id: string | null, created_at: Date, updated_at: Date, };
 
 export type CreateKanbanColumn = { name: string, slug: string, position: number, color: string | null, is_initial: boolean | null, is_terminal: boolean | null, starts_workflow: boolean | null, status: TaskStatus | null, agent_id: string | null, deliverable: string | null, question: string | null, answer_options: string | null, };
 
@@ -63,6 +370,74 @@ export type UpdateKanbanColumn = { name: string | null, slug: string | null, pos
  */
 agent_id?: string | null, deliverable: string | null, question: string | null, answer_options: string | null, };
 
+export type LabelRuleTrigger = "enter" | "exit";
+
+export type LabelRuleAction = "add" | "remove";
+
+export type ColumnLabelRule = { id: string, column_id: string, label_id: string, trigger: LabelRuleTrigger, action: LabelRuleAction, created_at: Date, };
+
+export type CreateColumnLabelRule = { label_id: string, trigger: LabelRuleTrigger, action: LabelRuleAction, };
+
+export type UpdateKanbanColumnSettings = { read_only: boolean | null, plan_only: boolean | null, dry_run: boolean | null, security_scanners: Array<SecurityScanner> | null, checklist: Array<string> | null, 
+/**
+ * Empty string clears the script
+ */
+on_enter_script: string | null, 
+/**
+ * Empty string clears the script
+ */
+on_exit_script: string | null, warm_up: boolean | null, 
+/**
+ * 0 clears the target
+ */
+response_target_mins: number | null, 
+/**
+ * Empty list clears the override
+ */
+prompt_sections: Array<PromptSection> | null, 
+/**
+ * Empty string restores the agent's default variant
+ */
+executor_variant: string | null, };
+
+export type SecurityScanner = "cargo_audit" | "npm_audit" | "gitleaks";
+
+export type FindingSeverity = "info" | "low" | "moderate" | "high" | "critical";
+
+export type SecurityFinding = { id: string, execution_process_id: string, scanner: string, severity: string, title: string, 
+/**
+ * Affected package or file (e.g. "openssl 0.10.55", "src/config.rs:12")
+ */
+location: string | null, details: string | null, created_at: Date, };
+
+export type ExecutionArtifact = { id: string, execution_process_id: string, task_id: string, 
+/**
+ * Stored file, served from `/api/images/{image_id}/file`
+ */
+image_id: string, 
+/**
+ * Path relative to the artifacts directory, e.g. `coverage/summary.json`
+ */
+name: string, 
+/**
+ * Repo whose artifacts directory held the file; none for the workspace root
+ */
+repo_name: string | null, mime_type: string | null, size_bytes: number, created_at: Date, };
+
+export type DeliverableRequirementKind = "decision" | "required_file" | "tests";
+
+export type DeliverableRequirement = { kind: DeliverableRequirementKind, requirement: string, satisfied: boolean, 
+/**
+ * What the requirement was checked against, e.g. the answer given or the test command run
+ */
+evidence: string | null, };
+
+export type ExecutionDeliverableReport = { execution_process_id: string, 
+/**
+ * Column the execution ran in
+ */
+column_id: string | null, requirements: Array<DeliverableRequirement>, satisfied_count: number, missed_count: number, created_at: Date, };
+
 export type StateTransition = { id: string, 
 /**
  * Board ID for board-level transitions (NULL for project/task level)
@@ -89,7 +464,8 @@ else_column_id: string | null,
  */
 escalation_column_id: string | null, name: string | null, requires_confirmation: boolean, 
 /**
- * Value to match for this transition (e.g., "approve" or "reject")
+ * Value to match for this transition (e.g., "approve" or "reject"),
+ * or an expression over decision keys (e.g., "coverage >= 80")
  */
 condition_value: string | null, 
 /**
@@ -145,71 +521,301 @@ else_column_id?: string | null, escalation_column_id?: string | null, name: stri
 
 export type TransitionScope = "board" | "project" | "task";
 
-export type Repo = { id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
+export type TransitionRetryPolicy = { transition_id: string, 
+/**
+ * Executor for retries (e.g. "CLAUDE_CODE"); null keeps the agent's executor
+ */
+executor: string | null, 
+/**
+ * Profile variant for retries (e.g. "HAIKU"); null uses the default variant
+ */
+variant: string | null, 
+/**
+ * Number of else transitions after which retries use this profile
+ */
+downgrade_after: number, created_at: Date, updated_at: Date, };
 
-export type ProjectRepo = { id: string, project_id: string, repo_id: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, };
+export type UpsertTransitionRetryPolicy = { executor: string | null, variant: string | null, 
+/**
+ * Defaults to 1 (downgrade from the first retry)
+ */
+downgrade_after: number | null, };
 
-export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
+export type TransitionArtifactRequirement = { transition_id: string, 
+/**
+ * Artifact type that must exist for the task (e.g. "iplan")
+ */
+artifact_type: string, created_at: Date, updated_at: Date, };
 
-export type UpdateProjectRepo = { setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean | null, };
+export type UpsertTransitionArtifactRequirement = { artifact_type: ArtifactType, };
 
-export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
+export type Repo = { id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
 
-export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
+export type MergeStrategy = "squash" | "merge_commit";
 
-export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
+export type ForgeKind = "github" | "gitea" | "gitlab" | "bitbucket";
 
-export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
+export type RepoForgeSettings = { repo_id: string, 
+/**
+ * See `ForgeKind`
+ */
+forge: string, 
+/**
+ * Root URL of the Gitea/Forgejo or self-managed GitLab instance, e.g.
+ * `https://codeberg.org`; unused for GitHub and Bitbucket
+ */
+base_url: string | null, 
+/**
+ * Whether an API token is stored; the token itself is never returned
+ */
+has_token: boolean, updated_at: Date, };
 
-export type CreateTag = { tag_name: string, content: string, };
+export type UpdateRepoForgeSettings = { forge: ForgeKind | null, 
+/**
+ * Empty string clears the URL
+ */
+base_url: string | null, 
+/**
+ * Stored encrypted; empty string removes the stored token, as does a change of
+ * forge or instance URL without a new one
+ */
+api_token: string | null, };
 
-export type UpdateTag = { tag_name: string | null, content: string | null, };
+export type RepoMergeSettings = { repo_id: string, 
+/**
+ * Refuse to merge unless the task branch is rebased onto the target branch
+ */
+require_clean_rebase: boolean, 
+/**
+ * See `MergeStrategy`
+ */
+merge_strategy: string, 
+/**
+ * Delete the task branch and its worktree after a successful merge
+ */
+delete_branch_after_merge: boolean, updated_at: Date, };
 
-export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
+export type UpdateRepoMergeSettings = { require_clean_rebase: boolean | null, merge_strategy: MergeStrategy | null, delete_branch_after_merge: boolean | null, };
 
-export type TaskState = "queued" | "inprogress" | "awaitingresponse" | "transitioning";
+export type ReviewCommentSide = "old" | "new";
 
-export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, column_id: string | null, parent_workspace_id: string | null, shared_task_id: string | null, task_group_id: string | null, task_state: TaskState, workflow_decisions: Record<string, unknown> | null, created_at: string, updated_at: string, };
+export type ReviewComment = { id: string, workspace_id: string, repo_id: string, file_path: string, line_number: number, side: string, body: string, 
+/**
+ * When the comment was sent to the agent as rejection feedback; null while pending
+ */
+sent_at: Date | null, created_at: Date, };
 
-export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_attempt_failed: boolean, executor: string, latest_attempt_id: string | null, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, column_id: string | null, parent_workspace_id: string | null, shared_task_id: string | null, task_group_id: string | null, task_state: TaskState, workflow_decisions: Record<string, unknown> | null, created_at: string, updated_at: string, };
+export type CreateReviewComment = { repo_id: string, file_path: string, line_number: number, 
+/**
+ * Defaults to the new side
+ */
+side: ReviewCommentSide | null, body: string, };
 
-export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
+export type UpdateProjectBoardSettings = { auto_assign_initial_column: boolean | null, };
 
-export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, column_id: string | null, parent_workspace_id: string | null, image_ids: Array<string> | null, shared_task_id: string | null, task_group_id: string | null, };
+export type UpdateProjectBranchSettings = { 
+/**
+ * Empty string restores the default template
+ */
+branch_template: string | null, };
 
-export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, column_id: string | null, parent_workspace_id: string | null, image_ids: Array<string> | null, task_group_id: string | null, };
+export type ShareConflictStrategy = "last_writer_wins" | "manual";
 
-export type TaskTrigger = { id: string, 
+export type ProjectShareSyncSettings = { project_id: string, 
 /**
- * The task that will auto-start
+ * See `ShareConflictStrategy`
  */
-task_id: string, 
+conflict_strategy: string, updated_at: Date, };
+
+export type UpdateProjectShareSyncSettings = { conflict_strategy: ShareConflictStrategy | null, };
+
+export type ProjectLogRedactionSettings = { project_id: string, 
 /**
- * The task to watch for completion
+ * Matches of the AWS key and GitHub token patterns that are left as they are, e.g.
+ * documented example keys. Env vault values are always redacted.
  */
-trigger_task_id: string, 
+allowlist: Array<string>, updated_at: Date, };
+
+export type UpdateProjectLogRedactionSettings = { 
 /**
- * Condition for when to trigger (stored as string)
+ * Replaces the whole allowlist
  */
-trigger_on: string, 
+allowlist: Array<string> | null, };
+
+export type ProjectModuleMemorySettings = { project_id: string, 
 /**
- * If true, trigger persists after firing; if false, removed after firing
+ * Start an agent run after each merge that rewrites the memories of the modules
+ * the merged branch touched
  */
-is_persistent: boolean, created_at: Date, 
+refresh_after_merge: boolean, 
 /**
- * When this trigger was satisfied (null if not yet fired)
+ * Most modules one refresh covers; the modules with the most changed files win
  */
-fired_at: Date | null, };
-
-export type CreateTaskTrigger = { task_id: string, trigger_task_id: string, trigger_on: string, is_persistent: boolean, };
+max_modules: number, updated_at: Date, };
 
-export type TriggerCondition = "completed" | { "completed_with_status": string } | "merged";
+export type UpdateProjectModuleMemorySettings = { refresh_after_merge: boolean | null, max_modules: number | null, };
 
-export type TaskDependency = { id: string, 
+export type ProjectTimezoneSettings = { project_id: string, 
 /**
- * The blocked task
+ * IANA timezone name, e.g. `Europe/Berlin`
  */
-task_id: string, 
+timezone: string, updated_at: Date, };
+
+export type UpdateProjectTimezoneSettings = { timezone: string | null, };
+
+export type ProjectSecuritySettings = { project_id: string, 
+/**
+ * Record a scanner that is missing or fails as informational instead of blocking
+ */
+allow_scanner_errors: boolean, updated_at: Date, };
+
+export type UpdateProjectSecuritySettings = { allow_scanner_errors: boolean | null, };
+
+export type SharedTaskFields = { title: string, description: string | null, status: TaskStatus, };
+
+export type ConflictResolution = "local" | "remote";
+
+export type SharedTaskConflict = { id: string, task_id: string, shared_task_id: string, 
+/**
+ * Names of the conflicting fields: `title`, `description` and/or `status`
+ */
+fields: Array<string>, local: SharedTaskFields, remote: SharedTaskFields, 
+/**
+ * `local` or `remote` once resolved; last-writer-wins conflicts are recorded
+ * resolved
+ */
+resolution: string | null, created_at: Date, resolved_at: Date | null, };
+
+export type ExecutionBackend = "local" | "docker";
+
+export type UpdateProjectExecutionSettings = { execution_backend: ExecutionBackend | null, 
+/**
+ * Empty string falls back to the default image
+ */
+docker_image: string | null, 
+/**
+ * Replaces the published ports
+ */
+docker_ports: Array<number> | null, };
+
+export type ProjectRepo = { id: string, project_id: string, repo_id: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, };
+
+export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
+
+export type UpdateProjectRepo = { setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean | null, };
+
+export type ProjectRepoScriptSettings = { project_repo_id: string, 
+/**
+ * Language of the setup and cleanup scripts (see `ScriptRequestLanguage`); none runs
+ * them in the platform shell
+ */
+script_language: string | null, 
+/**
+ * Setup script run instead of the regular one when the server runs on Windows
+ */
+windows_setup_script: string | null, 
+/**
+ * Cleanup script run instead of the regular one when the server runs on Windows
+ */
+windows_cleanup_script: string | null, 
+/**
+ * Language of the Windows scripts; none means PowerShell
+ */
+windows_script_language: string | null, updated_at: Date, };
+
+export type UpdateProjectRepoScriptSettings = { script_language: ScriptRequestLanguage | null, windows_setup_script: string | null, windows_cleanup_script: string | null, windows_script_language: ScriptRequestLanguage | null, };
+
+export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
+
+export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
+
+export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
+
+export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
+
+export type CreateTag = { tag_name: string, content: string, };
+
+export type UpdateTag = { tag_name: string | null, content: string | null, };
+
+export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
+
+export type TaskState = "queued" | "inprogress" | "awaitingresponse" | "transitioning";
+
+export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, column_id: string | null, parent_workspace_id: string | null, shared_task_id: string | null, task_group_id: string | null, task_state: TaskState, workflow_decisions: Record<string, unknown> | null, created_at: string, updated_at: string, 
+/**
+ * Set while the task is archived; archived tasks stay out of task lists and streams
+ */
+archived_at: string | null, 
+/**
+ * The task this one is a sub-task of
+ */
+parent_task_id: string | null, 
+/**
+ * Set while the task is handed back to a human
+ */
+detached_at: string | null, 
+/**
+ * The user the task is assigned to
+ */
+assignee_id: string | null, };
+
+export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_attempt_failed: boolean, executor: string, latest_attempt_id: string | null, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, column_id: string | null, parent_workspace_id: string | null, shared_task_id: string | null, task_group_id: string | null, task_state: TaskState, workflow_decisions: Record<string, unknown> | null, created_at: string, updated_at: string, 
+/**
+ * Set while the task is archived; archived tasks stay out of task lists and streams
+ */
+archived_at: string | null, 
+/**
+ * The task this one is a sub-task of
+ */
+parent_task_id: string | null, 
+/**
+ * Set while the task is handed back to a human
+ */
+detached_at: string | null, 
+/**
+ * The user the task is assigned to
+ */
+assignee_id: string | null, };
+
+export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
+
+export type SubtaskProgress = { total: bigint, done: bigint, };
+
+export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, column_id: string | null, parent_workspace_id: string | null, image_ids: Array<string> | null, shared_task_id: string | null, task_group_id: string | null, };
+
+export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, column_id: string | null, parent_workspace_id: string | null, image_ids: Array<string> | null, task_group_id: string | null, };
+
+export type TaskTrigger = { id: string, 
+/**
+ * The task that will auto-start
+ */
+task_id: string, 
+/**
+ * The task to watch for completion
+ */
+trigger_task_id: string, 
+/**
+ * Condition for when to trigger (stored as string)
+ */
+trigger_on: string, 
+/**
+ * If true, trigger persists after firing; if false, removed after firing
+ */
+is_persistent: boolean, created_at: Date, 
+/**
+ * When this trigger was satisfied (null if not yet fired)
+ */
+fired_at: Date | null, };
+
+export type CreateTaskTrigger = { task_id: string, trigger_task_id: string, trigger_on: string, is_persistent: boolean, };
+
+export type TriggerCondition = "completed" | { "completed_with_status": string } | "merged";
+
+export type TaskDependency = { id: string, 
+/**
+ * The blocked task
+ */
+task_id: string, 
 /**
  * The prerequisite task that must complete first
  */
@@ -273,6 +879,22 @@ export type UpdateTaskLabel = { name: string | null, color: string | null, posit
 
 export type TaskLabelAssignment = { task_id: string, label_id: string, created_at: Date, };
 
+export type LabelMatch = "any" | "all";
+
+export type LogRetention = "default" | "keep_forever" | "short";
+
+export type TaskLogRetention = { task_id: string, 
+/**
+ * See `LogRetention`
+ */
+retention: string, updated_at: Date, };
+
+export type UpdateTaskLogRetention = { retention: LogRetention | null, };
+
+export type TaskSubscription = { id: string, task_id: string | null, label_id: string | null, created_at: Date, };
+
+export type CreateTaskSubscription = { task_id: string | null, label_id: string | null, };
+
 export type DraftFollowUpData = { message: string, variant: string | null, };
 
 export type ScratchPayload = { "type": "DRAFT_TASK", "data": string } | { "type": "DRAFT_FOLLOW_UP", "data": DraftFollowUpData };
@@ -317,12 +939,68 @@ export type ExecutionProcess = { id: string, session_id: string, run_reason: Exe
  */
 dropped: boolean, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
 
-export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
+export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed", interrupted = "interrupted" }
 
-export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
+export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver" | "manualrun";
+
+export type InterruptedSession = { execution_process_id: string, session_id: string, workspace_id: string, task_id: string, task_title: string, project_id: string, agent_session_id: string, interrupted_at: Date | null, };
 
 export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
 
+export type EnvironmentRepo = { repo_name: string, target_branch: string, 
+/**
+ * Commit the target branch pointed at
+ */
+base_commit: string | null, 
+/**
+ * Commit the workspace branch pointed at
+ */
+head_commit: string | null, };
+
+export type ExecutionEnvironment = { execution_process_id: string, 
+/**
+ * Executor profile, e.g. `CLAUDE_CODE:PLAN`; `None` for scripts
+ */
+executor: string | null, 
+/**
+ * The profile's executor configuration, with env values redacted
+ */
+executor_config: JsonValue | null, 
+/**
+ * CLI version the agent reported in its logs
+ */
+executor_version: string | null, 
+/**
+ * Model the agent reported in its logs
+ */
+model: string | null, os: string, arch: string, 
+/**
+ * Version of the server that ran the execution
+ */
+app_version: string, repos: Array<EnvironmentRepo>, 
+/**
+ * Environment variables given to the process; secret values are redacted
+ */
+env_vars: Record<string, string>, created_at: Date, };
+
+export type ExecutionLogRedaction = { execution_process_id: string, 
+/**
+ * Env vault values, AWS keys and GitHub tokens replaced with `[REDACTED]`
+ */
+redaction_count: number, created_at: Date, };
+
+export type DryRunPatch = { id: string, execution_process_id: string, task_id: string, workspace_id: string, repo_id: string, 
+/**
+ * `git diff --binary` against the repo's HEAD when the run started
+ */
+patch: string, 
+/**
+ * When a person applied the patch to the workspace; none while it is pending
+ */
+applied_at: Date | null, created_at: Date, };
+
+export type ExecutionReplay = { id: string, source_execution_process_id: string, replay_execution_process_id: string, created_at: Date, };
+
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
 
 export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
@@ -351,6 +1029,30 @@ additions: number | null, deletions: number | null, };
 
 export type DiffChangeKind = "added" | "deleted" | "modified" | "renamed" | "copied" | "permissionChange";
 
+export type DiffLineKind = "context" | "added" | "removed";
+
+export type DiffLine = { kind: DiffLineKind, 
+/**
+ * Line number in the old file; None for added lines
+ */
+oldLine: number | null, 
+/**
+ * Line number in the new file; None for removed lines
+ */
+newLine: number | null, content: string, };
+
+export type DiffHunk = { header: string, oldStart: number, newStart: number, lines: Array<DiffLine>, };
+
+export type ReviewFileDiff = { repo_id: string, repo_name: string, 
+/**
+ * Path relative to the repo root (the new path for renames)
+ */
+path: string, old_path: string | null, change: DiffChangeKind, 
+/**
+ * True when contents were too large to diff; `hunks` is empty
+ */
+content_omitted: boolean, hunks: Array<DiffHunk>, };
+
 export type ApiResponse<T, E = T> = { success: boolean, data: T | null, error_data: E | null, message: string | null, };
 
 export type LoginStatus = { "status": "loggedout" } | { "status": "loggedin", profile: ProfileResponse, };
@@ -409,10 +1111,46 @@ export type ListProjectsResponse = { projects: Array<RemoteProject>, };
 
 export type RemoteProjectMembersResponse = { organization_id: string, members: Array<OrganizationMemberWithProfile>, };
 
+export type ProjectAccessGrant = { project_id: string, user_id: string, granted_by_user_id: string | null, created_at: string, };
+
+export type ProjectAccessResponse = { project_id: string, owner_user_id: string | null, grants: Array<ProjectAccessGrant>, 
+/**
+ * True while the project has no grants, so every organization member can see it
+ */
+organization_wide: boolean, };
+
+export type GrantProjectAccessRequest = { user_id: string, };
+
+export type TransferProjectOwnershipRequest = { new_owner_user_id: string, };
+
+export type UserNotificationKind = "mention" | "assigned";
+
+export type UserNotification = { id: string, organization_id: string, shared_task_id: string | null, actor_user_id: string | null, actor_username: string | null, kind: UserNotificationKind, message: string, read_at: string | null, created_at: string, };
+
+export type ListNotificationsResponse = { notifications: Array<UserNotification>, unread_count: bigint, };
+
+export type MarkNotificationsReadRequest = { 
+/**
+ * Notifications to mark as read; all unread notifications when omitted
+ */
+ids: Array<string> | null, };
+
 export type CreateRemoteProjectRequest = { organization_id: string, name: string, };
 
 export type LinkToExistingRequest = { remote_project_id: string, };
 
+export type RepoDefaultTargetBranch = { repo_id: string, 
+/**
+ * Branch new attempts target when the request doesn't name one
+ */
+default_target_branch: string | null, 
+/**
+ * Branch checked out in the repo, used when there is no default
+ */
+current_branch: string | null, };
+
+export type UpdateRepoDefaultTargetBranch = { default_target_branch: string | null, };
+
 export type RegisterRepoRequest = { path: string, display_name: string | null, };
 
 export type InitRepoRequest = { parent_path: string, folder_name: string, };
@@ -429,6 +1167,8 @@ capabilities: { [key in string]?: Array<BaseAgentCapability> }, executors: { [ke
 
 export type Environment = { os_type: string, os_version: string, os_architecture: string, bitness: string, };
 
+export type RateLimitMetrics = { checked: bigint, throttled_by_ip: bigint, throttled_by_token: bigint, };
+
 export type McpServerQuery = { executor: BaseCodingAgent, };
 
 export type UpdateMcpServersBody = { servers: { [key in string]?: JsonValue }, };
@@ -445,11 +1185,37 @@ export type CurrentUserResponse = { user_id: string, };
 
 export type CreateFollowUpAttempt = { prompt: string, variant: string | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
 
+export type SendSessionMessageRequest = { message: string, };
+
 export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: string, };
 
 export type ChangeTargetBranchResponse = { repo_id: string, new_target_branch: string, status: [number, number], };
 
-export type MergeTaskAttemptRequest = { repo_id: string, };
+export type MergeTaskAttemptRequest = { 
+/**
+ * Repo to merge; every repo of the attempt when omitted
+ */
+repo_id: string | null, };
+
+export type RepoMergeResult = { repo_id: string, repo_name: string, target_branch: string, merge_commit: string, strategy: MergeStrategy, branch_deleted: boolean, };
+
+export type PatchSource = "dry_run" | "external" | "human";
+
+export type ApplyPatchRequest = { repo_id: string, 
+/**
+ * Unified diff with paths relative to the repo root
+ */
+patch: string, source: PatchSource, 
+/**
+ * Identifies the patch at its source, e.g. a dry-run patch ID or a tool name
+ */
+source_ref: string | null, 
+/**
+ * Subject of the commit; defaults to one naming the source
+ */
+message: string | null, };
+
+export type AppliedPatch = { repo_id: string, commit_hash: string, commit_message: string, };
 
 export type PushTaskAttemptRequest = { repo_id: string, };
 
@@ -463,10 +1229,120 @@ export type OpenEditorResponse = { url: string | null, };
 
 export type AssignSharedTaskRequest = { new_assignee_user_id: string | null, };
 
+export type ResolveSharedTaskConflictRequest = { 
+/**
+ * Which side's values to keep for the conflicting fields
+ */
+resolution: ConflictResolution, };
+
 export type ShareTaskResponse = { shared_task_id: string, };
 
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
 
+export type CostEstimateQuery = { 
+/**
+ * Column to estimate for (defaults to the task's current column)
+ */
+column_id: string | null, };
+
+export type SetDecisionRequest = { 
+/**
+ * Decision key to set, e.g. `answer` or a key a transition expression reads
+ */
+key: string, value: JsonValue, 
+/**
+ * Appended to the decision's feedback for the next attempt
+ */
+feedback: string | null, 
+/**
+ * Workspace whose decision file to write (defaults to the task's active one)
+ */
+workspace_id: string | null, };
+
+export type TaskStreamSubscription = { 
+/**
+ * Column ids; empty allows every column
+ */
+columns: Array<string>, 
+/**
+ * Label ids or names (case-insensitive); empty allows every task
+ */
+labels: Array<string>, 
+/**
+ * Whether tasks need any (default) or all of `labels`
+ */
+label_match: LabelMatch | null, 
+/**
+ * Only tasks assigned to this user
+ */
+assignee: string | null, 
+/**
+ * Only tasks watched directly or through a label
+ */
+watched: boolean, };
+
+export type TasksWsClientMessage = { "type": "subscribe" } & TaskStreamSubscription;
+
+export type TaskSubtasks = { progress: SubtaskProgress, subtasks: Array<Task>, };
+
+export type CreateSubtasksFromPlanRequest = { 
+/**
+ * The iplan artifact to use; defaults to the latest plan written for the task
+ */
+artifact_id: string | null, };
+
+export type TickChecklistItem = { item: string, 
+/**
+ * `false` unticks the item
+ */
+checked: boolean, 
+/**
+ * Who ticked the item (name or email), recorded on the task event
+ */
+checked_by: string | null, };
+
+export type TaskHandback = { task_id: string, 
+/**
+ * Whether the task is detached from automation
+ */
+detached: boolean, detached_at: Date | null, };
+
+export type HandBackTaskRequest = { 
+/**
+ * Who takes over the task, recorded on the task event
+ */
+handed_to: string | null, reason: string | null, 
+/**
+ * Stop the agent if it is still running; defaults to true
+ */
+stop_agent: boolean, };
+
+export type TaskArchive = { task_id: string, archived: boolean, archived_at: Date | null, };
+
+export type TaskAssignment = { task_id: string, assignee: User | null, };
+
+export type AssignTask = { 
+/**
+ * `null` unassigns the task
+ */
+assignee_id: string | null, };
+
+export type ExecuteTaskRequest = { prompt: string, executor_profile_id: ExecutorProfileId, 
+/**
+ * Only write a plan for review; the run's other changes are discarded
+ */
+plan_only: boolean, 
+/**
+ * Language of the plan-only notice; None uses English
+ */
+prompt_language: PromptLanguage | null, };
+
+export type ReplayExecutionProcessRequest = { 
+/**
+ * Executor to re-run the prompt with, e.g. Codex for a run made with Claude Code
+ */
+executor_profile_id: ExecutorProfileId, };
+
 export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
@@ -483,7 +1359,12 @@ export type SaveAsTemplateResponse = { template_board_id: string, template_group
 
 export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
 
-export type WorkspaceRepoInput = { repo_id: string, target_branch: string, };
+export type WorkspaceRepoInput = { repo_id: string, 
+/**
+ * Defaults to the repo's default target branch in the project, else the branch
+ * checked out in the repo
+ */
+target_branch: string | null, };
 
 export type RunAgentSetupRequest = { executor_profile_id: ExecutorProfileId, };
 
@@ -497,75 +1378,566 @@ export type AbortConflictsRequest = { repo_id: string, };
 
 export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" };
 
-export type PushError = { "type": "force_push_required" };
+export type PushError = { "type": "force_push_required" };
+
+export type CreatePrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, };
+
+export type BranchStatus = { commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
+/**
+ * True if a `git rebase` is currently in progress in this worktree
+ */
+is_rebase_in_progress: boolean, 
+/**
+ * Current conflict operation if any
+ */
+conflict_op: ConflictOp | null, 
+/**
+ * List of files currently in conflicted (unmerged) state
+ */
+conflicted_files: Array<string>, };
+
+export type RunScriptError = { "type": "no_script_configured" } | { "type": "process_already_running" };
+
+export type AttachPrResponse = { pr_attached: boolean, pr_url: string | null, pr_number: bigint | null, pr_status: MergeStatus | null, };
+
+export type AttachExistingPrRequest = { repo_id: string, };
+
+export type PrCommentsResponse = { comments: Array<UnifiedPrComment>, };
+
+export type GetPrCommentsError = { "type": "no_pr_attached" } | { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" };
+
+export type GetPrCommentsQuery = { repo_id: string, };
+
+export type PrChecksResponse = { 
+/**
+ * Failure if any check failed, pending while any runs; null without checks
+ */
+state: PrCheckState | null, checks: Array<PrCheck>, };
+
+export type GetPrChecksError = { "type": "no_pr_attached" } | { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" };
+
+export type GetPrChecksQuery = { repo_id: string, };
+
+export type RejectReviewRequest = { 
+/**
+ * Overall feedback placed before the inline comments
+ */
+summary: string | null, };
+
+export type RejectReviewResponse = { 
+/**
+ * The feedback recorded for the implementer's next run
+ */
+feedback: string, 
+/**
+ * Version of the feedback on the task
+ */
+version: number, comments_sent: number, };
+
+export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string, body: string, created_at: string, url: string, } | { "comment_type": "review", id: bigint, author: string, author_association: string, body: string, created_at: string, url: string, path: string, line: bigint | null, diff_hunk: string, };
+
+export type PrCheckState = "pending" | "success" | "failure" | "skipped";
+
+export type PrCheck = { name: string, state: PrCheckState, description: string | null, 
+/**
+ * Link to the check's logs or details page
+ */
+url: string | null, };
+
+export type RepoBranchStatus = { repo_id: string, repo_name: string, commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
+/**
+ * True if a `git rebase` is currently in progress in this worktree
+ */
+is_rebase_in_progress: boolean, 
+/**
+ * Current conflict operation if any
+ */
+conflict_op: ConflictOp | null, 
+/**
+ * List of files currently in conflicted (unmerged) state
+ */
+conflicted_files: Array<string>, };
+
+export type LingeringProcess = { pid: number, execution_process_id: string, since: Date, 
+/**
+ * Sweeps that have tried to kill it
+ */
+attempts: number, };
+
+export type ProcessReaperReport = { last_sweep_at: Date | null, lingering: Array<LingeringProcess>, killed_last_sweep: number, reaped_last_sweep: number, killed_total: bigint, reaped_total: bigint, };
+
+export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
+
+export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
+
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, agent_concurrency: AgentConcurrencyConfig, attempt_budget: AttemptBudgetConfig, output_limits: OutputLimitsConfig, rate_limits: RateLimitConfig, remote_api_requires_token: boolean, 
+/**
+ * Delay before a column's agent starts after a task enters it, so dragging a task
+ * across the board only starts the agent where it comes to rest. Defaults to
+ * 1500; 0 starts agents immediately.
+ */
+column_entry_debounce_ms: number | null, };
+
+export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
+
+export enum ThemeMode { LIGHT = "LIGHT", DARK = "DARK", SYSTEM = "SYSTEM" }
+
+export type EditorConfig = { editor_type: EditorType, custom_command: string | null, remote_ssh_host: string | null, remote_ssh_user: string | null, };
+
+export enum EditorType { VS_CODE = "VS_CODE", CURSOR = "CURSOR", WINDSURF = "WINDSURF", INTELLI_J = "INTELLI_J", ZED = "ZED", XCODE = "XCODE", CUSTOM = "CUSTOM" }
+
+export type EditorOpenError = { "type": "executable_not_found", executable: string, editor_type: EditorType, } | { "type": "invalid_command", details: string, editor_type: EditorType, } | { "type": "launch_failed", executable: string, details: string, editor_type: EditorType, };
+
+export type GitHubConfig = { pat: string | null, oauth_token: string | null, username: string | null, primary_email: string | null, default_pr_base: string | null, };
+
+export enum SoundFile { ABSTRACT_SOUND1 = "ABSTRACT_SOUND1", ABSTRACT_SOUND2 = "ABSTRACT_SOUND2", ABSTRACT_SOUND3 = "ABSTRACT_SOUND3", ABSTRACT_SOUND4 = "ABSTRACT_SOUND4", COW_MOOING = "COW_MOOING", PHONE_VIBRATION = "PHONE_VIBRATION", ROOSTER = "ROOSTER" }
+
+export type UiLanguage = "BROWSER" | "EN" | "JA" | "ES" | "KO" | "ZH_HANS";
+
+export type ShowcaseState = { seen_features: Array<string>, };
+
+export type AgentConcurrencyConfig = { 
+/**
+ * Maximum coding agents running at once (None = unlimited)
+ */
+max_concurrent: number | null, 
+/**
+ * Per-executor maximums, e.g. `{ "CLAUDE_CODE": 2 }`
+ */
+per_executor: { [key in BaseCodingAgent]?: number }, 
+/**
+ * Weights for sharing slots between projects with agents waiting, by project id.
+ * Projects not listed weigh 1.
+ */
+project_weights: { [key in string]?: number }, };
+
+export type AttemptBudgetConfig = { 
+/**
+ * Refuse to start attempts estimated above this many tokens (None = no limit)
+ */
+max_estimated_tokens: number | null, };
+
+export type OutputLimitsConfig = { 
+/**
+ * Defaults to 20 MB
+ */
+coding_agent_kb: number | null, 
+/**
+ * Defaults to 2 MB
+ */
+setup_script_kb: number | null, 
+/**
+ * Defaults to 2 MB
+ */
+cleanup_script_kb: number | null, 
+/**
+ * Defaults to 5 MB
+ */
+dev_server_kb: number | null, 
+/**
+ * Output kept from the end of a truncated stream (defaults to 64 KB)
+ */
+tail_kb: number | null, };
+
+export type RateLimitConfig = { 
+/**
+ * Defaults to true
+ */
+enabled: boolean | null, 
+/**
+ * Defaults to 600
+ */
+per_ip_per_minute: number | null, 
+/**
+ * Defaults to 300
+ */
+per_token_per_minute: number | null, 
+/**
+ * Path prefixes to limit (defaults to `/api/tasks` and `/api/context-artifacts`,
+ * where MCP and agent traffic lands)
+ */
+paths: Array<string> | null, 
+/**
+ * Also limit loopback clients (the local UI and agents on this machine).
+ * Defaults to false
+ */
+limit_loopback: boolean | null, };
+
+export type CostEstimate = { column_id: string | null, agent_id: string | null, 
+/**
+ * Task prompt plus the agent's system prompt, start command and deliverable
+ */
+prompt_tokens: bigint, 
+/**
+ * Project context (artifacts) injected into the prompt
+ */
+context_tokens: bigint, 
+/**
+ * Past runs the historical averages are based on (0 = no history)
+ */
+historical_samples: bigint, historical_avg_input_tokens: bigint | null, historical_avg_output_tokens: bigint | null, estimated_input_tokens: bigint, estimated_output_tokens: bigint, estimated_total_tokens: bigint, 
+/**
+ * Configured `attempt_budget.max_estimated_tokens`, if any
+ */
+budget_tokens: bigint | null, within_budget: boolean, };
+
+export type DurationStats = { 
+/**
+ * Finished visits the statistics are computed from
+ */
+samples: number, mean_secs: number | null, p50_secs: number | null, p75_secs: number | null, p90_secs: number | null, p95_secs: number | null, max_secs: number | null, };
+
+export type ColumnCycleTime = { column_id: string, column_name: string, agent_id: string | null, 
+/**
+ * Tasks in the column right now
+ */
+in_column_now: number, stats: DurationStats, };
+
+export type AgentCycleTime = { agent_id: string, agent_name: string, 
+/**
+ * Finished visits to every column the agent is assigned to
+ */
+stats: DurationStats, };
+
+export type BoardCycleTimes = { board_id: string, 
+/**
+ * Columns in board order
+ */
+columns: Array<ColumnCycleTime>, agents: Array<AgentCycleTime>, computed_at: Date, };
+
+export type EscalationPathSlo = { from_column_id: string, from_column_name: string, escalation_column_id: string, escalation_column_name: string, 
+/**
+ * The escalation column's response-time target; none when it has no target
+ */
+response_target_mins: number | null, 
+/**
+ * Escalations that entered the column in the range
+ */
+escalations: number, 
+/**
+ * Escalations still waiting in the column
+ */
+open: number, 
+/**
+ * Escalations that took longer than the target, including open ones already past it
+ */
+breaches: number, 
+/**
+ * Percentage of decided escalations (answered, or open past the target) that met
+ * the target; none without a target or decided escalations
+ */
+attainment_pct: number | null, 
+/**
+ * Response times of answered escalations
+ */
+response_times: DurationStats, };
+
+export type BoardEscalationSlo = { board_id: string, 
+/**
+ * Paths in board order of their source column
+ */
+paths: Array<EscalationPathSlo>, escalations: number, breaches: number, 
+/**
+ * Attainment across every path with a target
+ */
+attainment_pct: number | null, computed_at: Date, };
+
+export type TaskColumnVisit = { column_id: string, 
+/**
+ * None when the column has been deleted
+ */
+column_name: string | null, entered_at: Date, left_at: Date | null, duration_secs: number, };
+
+export type TaskColumnTotal = { column_id: string, column_name: string | null, visits: number, total_secs: number, };
+
+export type TaskCycleTime = { task_id: string, 
+/**
+ * From the first column entry until now, or until the task reached a terminal column
+ */
+total_secs: number, 
+/**
+ * Per column, in order of first visit
+ */
+columns: Array<TaskColumnTotal>, 
+/**
+ * Every visit, oldest first
+ */
+visits: Array<TaskColumnVisit>, };
+
+export type BurnUpPoint = { date: string, 
+/**
+ * Tasks on the board, in any column
+ */
+total: bigint, 
+/**
+ * Tasks in terminal columns
+ */
+done: bigint, };
+
+export type BoardSnapshotHistory = { snapshots: Array<BoardColumnSnapshot>, burn_up: Array<BurnUpPoint>, };
+
+export type ChecklistItemStatus = { item: string, checked: boolean, 
+/**
+ * Who ticked the item, as given when ticking it
+ */
+checked_by: string | null, checked_at: Date | null, };
+
+export type TaskChecklist = { column_id: string, items: Array<ChecklistItemStatus>, };
+
+export type ConflictingTask = { task_id: string, title: string, workspace_id: string, };
+
+export type WorkspaceConflict = { repo_id: string, repo_name: string, tasks: Array<ConflictingTask>, 
+/**
+ * Files changed by every one of the tasks
+ */
+paths: Array<string>, };
+
+export type ProjectConflicts = { conflicts: Array<WorkspaceConflict>, 
+/**
+ * Tasks involved in at least one conflict, for flagging task cards
+ */
+conflicting_task_ids: Array<string>, 
+/**
+ * Oldest workspace snapshot the result is based on
+ */
+checked_at: Date | null, };
+
+export type BoardIssueKind = "unreachable_column" | "cycle_without_escalation" | "terminal_with_transitions" | "missing_else_path" | "unknown_executor";
+
+export type BoardIssue = { kind: BoardIssueKind, column_id: string | null, transition_id: string | null, message: string, };
+
+export type BoardValidationReport = { board_id: string, 
+/**
+ * True when no issues were found
+ */
+valid: boolean, columns_checked: number, transitions_checked: number, issues: Array<BoardIssue>, };
+
+export type WorkflowWarningKind = "missing_question" | "unknown_answer";
+
+export type WorkflowWarning = { transition_id: string, from_column_id: string, kind: WorkflowWarningKind, message: string, };
+
+export type StateTransitionWithWarnings = { warnings: Array<WorkflowWarning>, id: string, 
+/**
+ * Board ID for board-level transitions (NULL for project/task level)
+ */
+board_id: string | null, 
+/**
+ * Project ID for project-level transitions (NULL for board/task level)
+ */
+project_id: string | null, 
+/**
+ * Task ID for task-level transitions (NULL for board/project level)
+ */
+task_id: string | null, from_column_id: string, 
+/**
+ * Where to go when condition matches (success path)
+ */
+to_column_id: string, 
+/**
+ * Where to go when condition doesn't match (else path)
+ */
+else_column_id: string | null, 
+/**
+ * Where to go when max_failures is reached (escalation path)
+ */
+escalation_column_id: string | null, name: string | null, requires_confirmation: boolean, 
+/**
+ * Value to match for this transition (e.g., "approve" or "reject"),
+ * or an expression over decision keys (e.g., "coverage >= 80")
+ */
+condition_value: string | null, 
+/**
+ * Number of times the else path can be taken before escalation
+ */
+max_failures: number | null, is_template: boolean, template_group_id: string | null, created_at: Date, };
+
+export type KanbanColumnWithWarnings = { 
+/**
+ * Warnings for the transitions leaving this column
+ */
+warnings: Array<WorkflowWarning>, id: string, board_id: string, name: string, slug: string, position: number, color: string | null, is_initial: boolean, is_terminal: boolean, 
+/**
+ * When true, entering this column creates a new attempt/workspace
+ */
+starts_workflow: boolean, status: TaskStatus, agent_id: string | null, 
+/**
+ * What the agent should produce before moving to the next column
+ */
+deliverable: string | null, 
+/**
+ * Question the agent must answer before moving to the next column
+ */
+question: string | null, 
+/**
+ * JSON array of valid answer options for the question
+ */
+answer_options: string | null, 
+/**
+ * Read-only analysis mode: code changes and commits made by the agent are discarded
+ */
+read_only: boolean, 
+/**
+ * Plan-only mode: the agent writes a plan for review and its code changes are
+ * discarded; the task can't leave the column until the plan is approved
+ */
+plan_only: boolean, 
+/**
+ * Dry-run mode: the agent only proposes changes; nothing is committed and its
+ * changes are saved as patches a person can apply
+ */
+dry_run: boolean, 
+/**
+ * Scanners run after the agent finishes (see `SecurityScanner`); empty disables the gate
+ */
+security_scanners: Array<string>, 
+/**
+ * Items a person must tick before a task can leave the column; empty disables it
+ */
+checklist: Array<string>, 
+/**
+ * Shell script run in the workspace before the column's agent starts
+ */
+on_enter_script: string | null, 
+/**
+ * Shell script run in the workspace after the column's agent finishes; if it
+ * fails the task stays in the column
+ */
+on_exit_script: string | null, 
+/**
+ * Prepend an orientation of the workspace (repo layout and key files) to the
+ * agent's prompt, so it spends fewer tool calls exploring
+ */
+warm_up: boolean, 
+/**
+ * Minutes a task escalated into this column may wait before it breaches the
+ * column's response-time target; none disables SLO tracking
+ */
+response_target_mins: number | null, 
+/**
+ * Order of the sections of the agent's prompt (see `PromptSection`), overriding the
+ * agent's; sections not listed are left out. Empty uses the agent's order.
+ */
+prompt_sections: Array<string>, 
+/**
+ * Executor profile variant (e.g. `OPUS`) the column's agent runs with, overriding
+ * the agent's default variant
+ */
+executor_variant: string | null, is_template: boolean, template_group_id: string | null, created_at: Date, updated_at: Date, };
+
+export type ImportSource = "github_issues" | "gitea_issues" | "jira";
 
-export type CreatePrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, };
+export type ImportFormat = "json" | "csv";
 
-export type BranchStatus = { commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
+export type ImportTasksRequest = { source: ImportSource, 
 /**
- * True if a `git rebase` is currently in progress in this worktree
+ * Format of `content`; defaults to JSON
  */
-is_rebase_in_progress: boolean, 
+format: ImportFormat | null, 
 /**
- * Current conflict operation if any
+ * Contents of the exported file; may be empty for `gitea_issues` with `repo_id`
  */
-conflict_op: ConflictOp | null, 
+content: string, 
 /**
- * List of files currently in conflicted (unmerged) state
+ * For `gitea_issues` without an export: fetch the open issues of this repo from its
+ * configured Gitea/Forgejo instance
  */
-conflicted_files: Array<string>, };
-
-export type RunScriptError = { "type": "no_script_configured" } | { "type": "process_already_running" };
-
-export type AttachPrResponse = { pr_attached: boolean, pr_url: string | null, pr_number: bigint | null, pr_status: MergeStatus | null, };
+repo_id: string | null, 
+/**
+ * Return the planned tasks without creating anything
+ */
+dry_run: boolean, 
+/**
+ * Source status (case-insensitive) to column id, checked before name matching
+ */
+status_mapping: Record<string, string> | null, };
 
-export type AttachExistingPrRequest = { repo_id: string, };
+export type PlannedImportTask = { external_id: string, title: string, description: string | null, labels: Array<string>, source_status: string, column_id: string | null, column_name: string | null, status: TaskStatus, };
 
-export type PrCommentsResponse = { comments: Array<UnifiedPrComment>, };
+export type ImportTasksResponse = { dry_run: boolean, tasks: Array<PlannedImportTask>, 
+/**
+ * Labels that do not exist in the project yet (created unless `dry_run`)
+ */
+new_labels: Array<string>, 
+/**
+ * Empty for dry runs
+ */
+created_task_ids: Array<string>, };
 
-export type GetPrCommentsError = { "type": "no_pr_attached" } | { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" };
+export type RunningAgentSlot = { execution_process_id: string, project_id: string, executor: BaseCodingAgent, started_at: Date, };
 
-export type GetPrCommentsQuery = { repo_id: string, };
+export type QueuedAgentSlot = { execution_process_id: string, workspace_id: string, project_id: string, executor: BaseCodingAgent, 
+/**
+ * Human-readable reason the agent is still waiting
+ */
+reason: string, queued_at: Date, };
 
-export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string, body: string, created_at: string, url: string, } | { "comment_type": "review", id: bigint, author: string, author_association: string, body: string, created_at: string, url: string, path: string, line: bigint | null, diff_hunk: string, };
+export type AgentConcurrencySnapshot = { limits: AgentConcurrencyConfig, running: Array<RunningAgentSlot>, queued: Array<QueuedAgentSlot>, 
+/**
+ * Executors that failed recently, and whether they're paused
+ */
+executors: Array<ExecutorHealthStatus>, };
 
-export type RepoBranchStatus = { repo_id: string, repo_name: string, commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
+export type ProjectFairness = { project_id: string, weight: number, running: number, queued: number, 
 /**
- * True if a `git rebase` is currently in progress in this worktree
+ * Part of the running slots the project's weight entitles it to, among projects
+ * with agents running or waiting
  */
-is_rebase_in_progress: boolean, 
+fair_share: number, 
 /**
- * Current conflict operation if any
+ * Part of the running slots it holds
  */
-conflict_op: ConflictOp | null, 
+actual_share: number, 
 /**
- * List of files currently in conflicted (unmerged) state
+ * Agents admitted since the server started
  */
-conflicted_files: Array<string>, };
-
-export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
-
-export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
-
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, };
-
-export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
-
-export enum ThemeMode { LIGHT = "LIGHT", DARK = "DARK", SYSTEM = "SYSTEM" }
+admitted: number, 
+/**
+ * Average time admitted agents spent queued
+ */
+average_wait_ms: number, 
+/**
+ * How long the project's longest-waiting queued agent has waited
+ */
+longest_wait_ms: number, };
 
-export type EditorConfig = { editor_type: EditorType, custom_command: string | null, remote_ssh_host: string | null, remote_ssh_user: string | null, };
+export type CircuitState = "closed" | "open" | "half_open";
 
-export enum EditorType { VS_CODE = "VS_CODE", CURSOR = "CURSOR", WINDSURF = "WINDSURF", INTELLI_J = "INTELLI_J", ZED = "ZED", XCODE = "XCODE", CUSTOM = "CUSTOM" }
+export type ExecutorHealthStatus = { executor: BaseCodingAgent, state: CircuitState, consecutive_failures: number, opened_at: Date | null, 
+/**
+ * When the next probe may start, while the circuit is open
+ */
+retry_at: Date | null, };
 
-export type EditorOpenError = { "type": "executable_not_found", executable: string, editor_type: EditorType, } | { "type": "invalid_command", details: string, editor_type: EditorType, } | { "type": "launch_failed", executable: string, details: string, editor_type: EditorType, };
+export type ComponentStatus = { name: string, ok: boolean, 
+/**
+ * What failed, or what was checked when the component is fine
+ */
+detail: string | null, };
 
-export type GitHubConfig = { pat: string | null, oauth_token: string | null, username: string | null, primary_email: string | null, default_pr_base: string | null, };
+export type ReadinessReport = { 
+/**
+ * Whether every component is ok
+ */
+ready: boolean, components: Array<ComponentStatus>, };
 
-export enum SoundFile { ABSTRACT_SOUND1 = "ABSTRACT_SOUND1", ABSTRACT_SOUND2 = "ABSTRACT_SOUND2", ABSTRACT_SOUND3 = "ABSTRACT_SOUND3", ABSTRACT_SOUND4 = "ABSTRACT_SOUND4", COW_MOOING = "COW_MOOING", PHONE_VIBRATION = "PHONE_VIBRATION", ROOSTER = "ROOSTER" }
+export type TranscriptEntry = { "type": "entry", "content": NormalizedEntry } | { "type": "stdout", "content": string } | { "type": "stderr", "content": string } | { "type": "diff", "content": Diff };
 
-export type UiLanguage = "BROWSER" | "EN" | "JA" | "ES" | "KO" | "ZH_HANS";
+export type ExecutionTranscript = { execution_process_id: string, task_id: string | null, task_title: string | null, run_reason: ExecutionProcessRunReason, status: ExecutionProcessStatus, started_at: Date, completed_at: Date | null, exported_at: Date, 
+/**
+ * Retention of the task's logs when exported
+ */
+log_retention: LogRetention, 
+/**
+ * When the stored logs become due for pruning; null when they are kept forever or
+ * the execution is still running
+ */
+retained_until: Date | null, entries: Array<TranscriptEntry>, };
 
-export type ShowcaseState = { seen_features: Array<string>, };
+export type DevServerPreview = { workspace_id: string, execution_process_id: string, port: number, 
+/**
+ * Path of the reverse proxy for this workspace (`/api/preview/{workspace_id}/`)
+ */
+proxy_path: string, detected_at: Date, };
 
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
@@ -601,13 +1973,25 @@ export type QueueStatus = { "status": "empty" } | { "status": "queued", message:
 
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
-export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
+export type AgentIdentity = { agent_id: string, 
+/**
+ * Agent color (hex) for UI styling
+ */
+agent_color: string | null, };
+
+export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, 
+/**
+ * Agent this action and the actions chained after it run for
+ */
+agent?: AgentIdentity | null, };
 
 export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path: Array<string>, template: JsonValue, preconfigured: JsonValue, is_toml_config: boolean, };
 
+export type AgentMcpServer = { name: string, command: string, args: Array<string>, env: { [key in string]?: string }, };
+
 export type ExecutorActionType = { "type": "CodingAgentInitialRequest" } & CodingAgentInitialRequest | { "type": "CodingAgentFollowUpRequest" } & CodingAgentFollowUpRequest | { "type": "ScriptRequest" } & ScriptRequest;
 
-export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer" | "ToolInstallScript";
+export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer" | "ToolInstallScript" | "ColumnEnterScript" | "ColumnExitScript";
 
 export type ScriptRequest = { script: string, language: ScriptRequestLanguage, context: ScriptContext, 
 /**
@@ -616,11 +2000,17 @@ export type ScriptRequest = { script: string, language: ScriptRequestLanguage, c
  */
 working_dir: string | null, };
 
-export type ScriptRequestLanguage = "Bash";
+export type ScriptRequestLanguage = "Bash" | "PowerShell" | "Python" | "Cmd";
+
+export type ScriptInterpreter = { language: ScriptRequestLanguage, available: boolean, 
+/**
+ * Resolved path of the interpreter
+ */
+path: string | null, };
 
-export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", DROID = "DROID" }
+export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", DROID = "DROID", CUSTOM = "CUSTOM" }
 
-export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid };
+export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } | { "CUSTOM": Custom };
 
 export type AvailabilityInfo = { "type": "LOGIN_DETECTED", last_auth_timestamp: bigint, } | { "type": "INSTALLATION_FOUND" } | { "type": "NOT_FOUND" };
 
@@ -644,7 +2034,7 @@ executor: BaseCodingAgent,
  */
 variant: string | null, };
 
-export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } };
+export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } | { "CUSTOM": Custom } };
 
 export type ExecutorConfigs = { executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
 
@@ -686,6 +2076,10 @@ export type Autonomy = "normal" | "low" | "medium" | "high" | "skip-permissions-
 
 export type DroidReasoningEffort = "none" | "dynamic" | "off" | "low" | "medium" | "high";
 
+export type Custom = { append_prompt: AppendPrompt, command: string, follow_up_command?: string | null, log_format: CustomLogFormat, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+
+export type CustomLogFormat = "plain_text" | "claude_stream_json";
+
 export type AppendPrompt = string | null;
 
 export type CodingAgentInitialRequest = { prompt: string, 
@@ -714,10 +2108,32 @@ agent_workflow_history: string | null,
  * Optional agent start command to append (initial instruction)
  */
 agent_start_command: string | null, 
+/**
+ * Optional decision instructions - the column's question and reviewer feedback
+ */
+agent_decision_instructions: string | null, 
 /**
  * Optional deliverable description - tells the agent what to produce and when to stop
  */
-agent_deliverable: string | null, };
+agent_deliverable: string | null, 
+/**
+ * Order of the prompt's sections; sections not listed are left out.
+ * If None, uses `PromptSection::DEFAULT_ORDER`.
+ */
+prompt_sections: Array<PromptSection> | null, 
+/**
+ * Language of the section headings and the deliverable's stop notice.
+ * If None, uses English.
+ */
+prompt_language: PromptLanguage | null, };
+
+export type PromptSection = "system_prompt" | "project_context" | "workflow_history" | "task" | "instructions" | "decision_instructions" | "deliverable";
+
+/**
+ * Language of the instruction text the server adds around an agent's prompt (section
+ * headings, decision instructions, column notices). Prompts written by users are sent as is.
+ */
+export type PromptLanguage = "english" | "german" | "japanese";
 
 export type CodingAgentFollowUpRequest = { prompt: string, session_id: string, 
 /**
@@ -760,7 +2176,7 @@ export type ActionType = { "action": "file_read", path: string, } | { "action":
 
 export type TodoItem = { content: string, status: string, priority: string | null, };
 
-export type NormalizedEntryError = { "type": "setup_required" } | { "type": "other" };
+export type NormalizedEntryError = { "type": "setup_required" } | { "type": "auth_failed" } | { "type": "rate_limited" } | { "type": "network" } | { "type": "compile_error" } | { "type": "other" };
 
 export type ToolResult = { type: ToolResultValueType, 
 /**
@@ -784,6 +2200,73 @@ export type UpdateAgent = { name: string | null, role: string | null, system_pro
 
 export type ContextFile = { pattern: string, instruction: string | null, };
 
+export type StatsTimeRange = { from: Date | null, to: Date | null, };
+
+export type PerformanceStats = { 
+/**
+ * Automated transitions out of columns the agent works in
+ */
+transitions: bigint, success_transitions: bigint, else_transitions: bigint, escalations: bigint, 
+/**
+ * success / (success + else + escalation); null when no conditional transitions ran
+ */
+success_rate: number | null, 
+/**
+ * escalation / (success + else + escalation)
+ */
+escalation_rate: number | null, 
+/**
+ * Coding agent execution processes
+ */
+executions: bigint, failed_executions: bigint, avg_execution_secs: number | null, 
+/**
+ * Executions whose agent reported token usage (only Claude Code does); the token
+ * totals cover these only and are null when there are none
+ */
+token_usage_executions: bigint, input_tokens: bigint | null, output_tokens: bigint | null, total_tokens: bigint | null, };
+
+export type AgentStats = { agent_id: string, agent_name: string, stats: PerformanceStats, };
+
+export type BoardStats = { board_id: string, 
+/**
+ * Totals across every agent on the board
+ */
+totals: PerformanceStats, agents: Array<AgentStats>, };
+
+export type AgentMcpSettings = { agent_id: string, 
+/**
+ * Backend URL for the vibe_kanban MCP server; None uses the local server
+ */
+mcp_base_url: string | null, 
+/**
+ * Extra MCP servers wired into the coding agent's configuration at spawn
+ */
+mcp_servers: Array<AgentMcpServer>, updated_at: Date, };
+
+export type UpdateAgentMcpSettings = { 
+/**
+ * An empty string clears the override
+ */
+mcp_base_url: string | null, mcp_servers: Array<AgentMcpServer> | null, };
+
+export type AgentPromptSettings = { agent_id: string, 
+/**
+ * Order of the sections of the agent's prompt (see `PromptSection`); sections not
+ * listed are left out. Empty uses the default order.
+ */
+prompt_sections: Array<string>, 
+/**
+ * Language of the instruction text the server adds to the agent's prompts
+ * (see `PromptLanguage`)
+ */
+language: string, updated_at: Date, };
+
+export type UpdateAgentPromptSettings = { 
+/**
+ * Empty list restores the default order
+ */
+prompt_sections: Array<PromptSection> | null, language: PromptLanguage | null, };
+
 export type Skill = { id: string, name: string, description: string | null, content: string, created_at: Date, updated_at: Date, };
 
 export type CreateSkill = { name: string, description: string | null, content: string, };
@@ -800,7 +2283,7 @@ export type UpdateAutomationRule = { trigger_type: TriggerType | null, action_ty
 
 export type TriggerType = "on_enter" | "on_exit";
 
-export type AutomationActionType = "run_agent" | "create_workspace" | "create_pr" | "merge_pr" | "webhook" | "notify";
+export type AutomationActionType = "run_agent" | "create_workspace" | "create_pr" | "merge_pr" | "webhook" | "notify" | "run_script" | "assign_agent" | "set_labels" | "move_to_column" | "create_follow_up_task" | "create_subtasks_from_plan";
 
 export type RunAgentConfig = { agent_id: string | null, prompt_template: string, executor: string | null, timeout_minutes: number | null, };
 
@@ -820,7 +2303,61 @@ export type WebhookConfig = { url: string, method: string | null, headers: JsonV
 
 export type NotifyConfig = { channel: string, webhook_url: string, message_template: string, };
 
-export type TaskEventType = "column_enter" | "column_exit" | "agent_start" | "agent_complete" | "agent_failed" | "commit" | "manual_action" | "task_created" | "status_change" | "else_transition" | "decision_validation_failed" | "artifact_created" | "task_state_change";
+export type RunScriptConfig = { script: string, 
+/**
+ * Defaults to 300 seconds
+ */
+timeout_seconds: number | null, };
+
+export type AssignAgentConfig = { agent_id: string, };
+
+export type SetLabelsConfig = { label_ids: Array<string>, 
+/**
+ * Remove labels not in the list (default: only add)
+ */
+replace: boolean | null, };
+
+export type MoveToColumnConfig = { column_id: string, };
+
+export type CreateFollowUpTaskConfig = { title_template: string, description_template: string | null, 
+/**
+ * Column for the new task (defaults to the board's initial column)
+ */
+column_id: string | null, };
+
+export type CreateSubtasksFromPlanConfig = { 
+/**
+ * Column for the sub-tasks (defaults to the board's initial column)
+ */
+column_id: string | null, };
+
+export type AutomationExecution = { id: string, rule_id: string, task_id: string, workspace_id: string | null, status: string, trigger_context: string | null, result: string | null, started_at: Date | null, completed_at: Date | null, created_at: Date, };
+
+export type SetupScriptRun = { id: string, project_id: string, repo_id: string, execution_process_id: string, succeeded: boolean, exit_code: number | null, 
+/**
+ * This run was the automatic retry of a failed run
+ */
+is_retry: boolean, 
+/**
+ * Set on a failed run that was retried
+ */
+retry_execution_process_id: string | null, created_at: Date, };
+
+export type SetupScriptHealth = { repo_id: string, repo_name: string, runs: bigint, failures: bigint, retries: bigint, 
+/**
+ * Retries that passed, i.e. failures that weren't charged to the task
+ */
+recovered: bigint, 
+/**
+ * The script both passed and failed within its recent runs
+ */
+flaky: boolean, 
+/**
+ * Newest first
+ */
+recent: Array<SetupScriptRun>, };
+
+export type TaskEventType = "column_enter" | "column_exit" | "agent_start" | "agent_complete" | "agent_failed" | "commit" | "manual_action" | "task_created" | "status_change" | "else_transition" | "decision_validation_failed" | "artifact_created" | "task_state_change" | "rollback" | "checklist_item_checked" | "checklist_item_unchecked" | "handed_back" | "reattached" | "reviewer_feedback" | "labels_changed" | "artifacts_collected" | "workspace_recreated" | "shared_task_conflict" | "patch_applied";
 
 export type EventTriggerType = "manual" | "automation" | "drag_drop" | "system";
 
@@ -884,6 +2421,155 @@ export type UpdateContextArtifact = { title: string | null, content: string | nu
 
 export type ContextPreviewStats = { context: string, tokens_used: number, token_budget: number, artifacts_included: number, artifacts_total: number, };
 
+export type ArtifactReviewStatus = "draft" | "approved" | "archived";
+
+export type ContextArtifactReview = { artifact_id: string, status: ArtifactReviewStatus, reviewer: string | null, reviewed_at: Date | null, 
+/**
+ * When the artifact drops out of agent prompts, from its type's TTL; none if it
+ * never expires
+ */
+expires_at: Date | null, };
+
+export type ReviewContextArtifact = { status: ArtifactReviewStatus, 
+/**
+ * Who reviewed the artifact
+ */
+reviewer: string | null, };
+
+export type ContextArtifactTtl = { project_id: string, artifact_type: string, ttl_days: number, updated_at: Date, };
+
+export type SetContextArtifactTtl = { artifact_type: ArtifactType, 
+/**
+ * None removes the TTL, so artifacts of the type never expire
+ */
+ttl_days: number | null, };
+
+export type ReviewedContextArtifact = { artifact: ContextArtifact, review: ContextArtifactReview, };
+
+export type ContextBudgetOverride = { 
+/**
+ * Total tokens available for injected context
+ */
+token_budget: number | null, 
+/**
+ * Relative share of the budget for project-wide artifacts
+ */
+global_weight: number | null, 
+/**
+ * Relative share of the budget for artifacts tied to the task
+ */
+task_weight: number | null, 
+/**
+ * Relative share of the budget for module memories of touched paths
+ */
+path_weight: number | null, };
+
+export type ContextBudget = { token_budget: number, global_weight: number, task_weight: number, path_weight: number, };
+
+export type ContextBudgetSettings = { overrides: ContextBudgetOverride, effective: ContextBudget, };
+
+export type HandoffNoteSource = "agent" | "automatic";
+
+export type HandoffNote = { id: string, task_id: string, 
+/**
+ * Column the agent was working in
+ */
+column_id: string | null, workspace_id: string | null, agent_id: string | null, 
+/**
+ * See `HandoffNoteSource`
+ */
+source: string, summary: string, files_touched: Array<string>, open_questions: Array<string>, created_at: Date, };
+
+export type CreateHandoffNote = { 
+/**
+ * Defaults to the task's current column
+ */
+column_id: string | null, workspace_id: string | null, agent_id: string | null, summary: string, files_touched: Array<string>, open_questions: Array<string>, };
+
+export type ReviewerFeedbackSource = "agent" | "human";
+
+export type ReviewerFeedback = { id: string, task_id: string, 
+/**
+ * Counts up from 1 for each piece of feedback on the task
+ */
+version: number, 
+/**
+ * Column the work was reviewed in
+ */
+column_id: string | null, 
+/**
+ * Execution whose work was rejected
+ */
+execution_process_id: string | null, 
+/**
+ * See `ReviewerFeedbackSource`
+ */
+source: string, feedback: string, 
+/**
+ * Set once the implementer has run with this feedback and the task moved on
+ */
+addressed_at: Date | null, created_at: Date, };
+
+export type TaskComment = { id: string, task_id: string, content: string, 
+/**
+ * Append the comment to the next agent prompt, alongside reviewer feedback
+ */
+visible_to_agent: boolean, 
+/**
+ * Set once an agent run that started after the comment has moved the task on
+ */
+delivered_at: Date | null, created_at: Date, updated_at: Date, };
+
+export type CreateTaskComment = { content: string, 
+/**
+ * Defaults to false
+ */
+visible_to_agent: boolean | null, };
+
+export type UpdateTaskComment = { content: string | null, visible_to_agent: boolean | null, };
+
+export type PlanReviewStatus = "pending" | "approved" | "rejected";
+
+export type PlanReview = { id: string, task_id: string, 
+/**
+ * Session of the plan-only run
+ */
+session_id: string, 
+/**
+ * Plan-only column the run belongs to; `None` for manual runs
+ */
+column_id: string | null, 
+/**
+ * Contents of the plan file once the run has finished
+ */
+plan: string | null, status: PlanReviewStatus, reviewer: string | null, comment: string | null, reviewed_at: Date | null, created_at: Date, };
+
+export type ReviewPlan = { 
+/**
+ * Who reviewed the plan
+ */
+reviewer: string | null, comment: string | null, };
+
+export type BackgroundJobStatus = "pending" | "running" | "completed" | "dead";
+
+export type BackgroundJob = { id: string, kind: string, payload: unknown, status: BackgroundJobStatus, attempts: number, max_attempts: number, last_error: string | null, 
+/**
+ * Not claimed before this time; pushed back after each failure
+ */
+run_after: Date, claimed_at: Date | null, completed_at: Date | null, created_at: Date, updated_at: Date, };
+
+export type DataMigrationStatus = "pending" | "running" | "completed" | "failed";
+
+export type DataMigration = { name: string, status: DataMigrationStatus, 
+/**
+ * Items handled so far in the current (or last) run
+ */
+processed: number, 
+/**
+ * Items the current (or last) run found to handle
+ */
+total: number | null, attempts: number, last_error: string | null, started_at: Date | null, completed_at: Date | null, updated_at: Date, };
+
 export type EvaluateRun = { id: string, commit_hash: string | null, commit_message: string | null, project_name: string, started_at: string, completed_at: string, 
 /**
  * JSON blob: { tasks, artifacts, events, context_previews }